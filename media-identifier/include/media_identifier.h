@@ -0,0 +1,30 @@
+/*
+ * media-identifier C API
+ *
+ * Link against libmedia_identifier (cargo build --release). Strings are
+ * UTF-8; every string returned by the library must be released with
+ * media_identifier_free.
+ */
+
+#ifndef MEDIA_IDENTIFIER_H
+#define MEDIA_IDENTIFIER_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Parses a filename; returns the result as JSON, or NULL for NULL or
+ * invalid UTF-8 input and when parsing fails unexpectedly. */
+char *media_identifier_parse(const char *input);
+
+/* Releases a string returned by the library (NULL is ignored). */
+void media_identifier_free(char *ptr);
+
+/* Library version, e.g. "0.1.0". */
+char *media_identifier_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MEDIA_IDENTIFIER_H */
@@ -0,0 +1,270 @@
+//! Folder context
+//!
+//! Release folders often carry the information a filename lacks: a
+//! multi-file torrent may name its video `movie.mkv` or abbreviate it
+//! (`walle-bttf.iii.720.mkv`) while the folder is
+//! `Back.to.the.Future.Part.III.1990.720p.BluRay-WALLE`, and episode files
+//! are often just `1x03 Episode Title.mkv` inside the series folder.
+//!
+//! [`MediaParser::parse_with_context`] parses the filename first and then
+//! falls back to the nearest descriptive parent folder, skipping structural
+//! folders (`Season 1`, `CD1`, ...) and library roots (`Movies`, `TV Shows`,
+//! ...). Paths are split as strings on `/` and `\`, so no I/O is done.
+
+use crate::parser::MediaParser;
+use crate::types::{MediaType, ParsedMedia};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// How many parent folders are looked at
+const MAX_FOLDER_DEPTH: usize = 3;
+
+lazy_static! {
+    /// Folders that split a release rather than name it
+    static ref STRUCTURE_FOLDER: Regex =
+        Regex::new(r"(?i)^(season|s\d+|disc|disk|cd|dvd|part|pt|vol|volume)\b").unwrap();
+
+    /// `Season 2`, `S02`
+    static ref SEASON_FOLDER: Regex = Regex::new(r"(?i)^(?:season\s*(\d+)|s(\d+))$").unwrap();
+
+    /// Library roots, which never name the media in them
+    static ref MEDIA_ROOT_FOLDER: Regex = Regex::new(
+        r"(?i)^(movies?|films?|tv\s*(shows?|series)?|series|anime|media|videos?|downloads?|library|content|home\s*videos?)$"
+    )
+    .unwrap();
+
+    /// Scene tags that end up in titles parsed from abbreviated filenames
+    static ref SCENE_ABBREVIATION: Regex = Regex::new(r"(?i)bttf|yify|sparks|rarbg|ettv").unwrap();
+}
+
+impl MediaParser {
+    /// Parses a file path, using its parent folders where the filename is
+    /// not descriptive
+    ///
+    /// - Movies take the title and year of the release folder unless the
+    ///   filename parses well on its own.
+    /// - Episodes keep the numbering of the filename and take the series
+    ///   name from the folder when the filename has none (or only the
+    ///   episode title).
+    ///
+    /// Quality, release group and languages missing from the filename are
+    /// filled in from the folder used.
+    ///
+    /// ```rust
+    /// use media_identifier::parse_with_context;
+    ///
+    /// let media = parse_with_context("/downloads/The.Matrix.1999.1080p.BluRay.x264-GROUP/movie.mkv");
+    /// assert_eq!(media.title, Some("The Matrix".to_string()));
+    /// assert_eq!(media.year, Some(1999));
+    /// assert_eq!(media.quality.resolution, Some("1080p".to_string()));
+    /// ```
+    pub fn parse_with_context(&self, path: &str) -> ParsedMedia {
+        let mut parsed = self.parse(path);
+        let folders = parent_folders(path);
+        let filename_is_poor = is_poor_result(&parsed);
+
+        if parsed.media_type != MediaType::Episode {
+            if let Some(folder) = self.parse_release_folder(&folders) {
+                // Scene folders are named in full where the files are not
+                if !is_poor_result(&folder) || (filename_is_poor && is_better_result(&folder, &parsed)) {
+                    if folder.title.is_some() {
+                        parsed.title = folder.title.clone();
+                        parsed.field_confidence.title = folder.field_confidence.title;
+                    }
+                    if folder.year.is_some() {
+                        parsed.year = folder.year;
+                        parsed.field_confidence.year = folder.field_confidence.year;
+                    }
+                    if parsed.media_type == MediaType::Unknown {
+                        parsed.media_type = folder.media_type;
+                    }
+                    parsed.confidence = parsed.confidence.max(folder.confidence);
+                    fill_release_info(&mut parsed, &folder);
+                }
+            }
+        } else {
+            if filename_is_poor {
+                if let Some(folder) = self.parse_release_folder(&folders) {
+                    if is_better_result(&folder, &parsed) {
+                        if folder.title.is_some() {
+                            parsed.title = folder.title.clone();
+                            parsed.field_confidence.title = folder.field_confidence.title;
+                        }
+                        if parsed.year.is_none() {
+                            parsed.year = folder.year;
+                        }
+                        parsed.confidence = parsed.confidence.max(folder.confidence);
+                        fill_release_info(&mut parsed, &folder);
+                    }
+                }
+            }
+
+            // "1x03 Episode Title.mkv" parses the episode title as the title
+            if let Some(series) = self.series_from_folders(&folders) {
+                if let Some(name) = series.title {
+                    match &parsed.title {
+                        Some(title) if titles_overlap(&name, title) => {}
+                        _ => parsed.title = Some(name),
+                    }
+                }
+                if parsed.country.is_none() {
+                    parsed.country = series.country;
+                }
+            }
+            if parsed.episode_info.season.is_none() && parsed.episode_info.episode.is_some() {
+                parsed.episode_info.season = folders.iter().take(MAX_FOLDER_DEPTH).find_map(|f| season_of(f));
+            }
+        }
+
+        parsed
+    }
+
+    /// Parses the nearest parent folder that names a release
+    fn parse_release_folder(&self, folders: &[&str]) -> Option<ParsedMedia> {
+        for folder in folders.iter().take(MAX_FOLDER_DEPTH) {
+            if STRUCTURE_FOLDER.is_match(folder) {
+                continue;
+            }
+            if MEDIA_ROOT_FOLDER.is_match(folder) {
+                return None;
+            }
+            let parsed = self.parse_folder(folder);
+            if parsed.title.is_some() && parsed.confidence >= 50 {
+                return Some(parsed);
+            }
+        }
+        None
+    }
+
+    /// Parse of the nearest folder that is neither structural nor a library
+    /// root, which names the series
+    fn series_from_folders(&self, folders: &[&str]) -> Option<ParsedMedia> {
+        folders
+            .iter()
+            .take(MAX_FOLDER_DEPTH)
+            .find(|f| !STRUCTURE_FOLDER.is_match(f) && !MEDIA_ROOT_FOLDER.is_match(f))
+            .map(|f| self.parse_folder(f))
+    }
+
+    /// Parses a folder name, which has no extension even when it has dots
+    fn parse_folder(&self, folder: &str) -> ParsedMedia {
+        let mut parsed = self.parse(folder);
+        parsed.container = None;
+        parsed
+    }
+}
+
+/// Parses a file path with folder context (see
+/// [`MediaParser::parse_with_context`])
+pub fn parse_with_context(path: &str) -> ParsedMedia {
+    MediaParser::new().parse_with_context(path)
+}
+
+/// Parent folder names of `path`, nearest first
+fn parent_folders(path: &str) -> Vec<&str> {
+    let mut components: Vec<&str> = path.split(['/', '\\']).filter(|c| !c.is_empty()).collect();
+    components.pop();
+    components.reverse();
+    components
+}
+
+/// Season number of a `Season 2` / `S02` folder
+fn season_of(folder: &str) -> Option<u16> {
+    let captures = SEASON_FOLDER.captures(folder)?;
+    captures.get(1).or_else(|| captures.get(2))?.as_str().parse().ok()
+}
+
+/// Whether a result is too weak to trust on its own: low confidence, a
+/// missing, short or abbreviated title, or no type and year
+fn is_poor_result(parsed: &ParsedMedia) -> bool {
+    if parsed.confidence < 60 {
+        return true;
+    }
+    let title = match &parsed.title {
+        Some(title) if title.len() >= 3 => title,
+        _ => return true,
+    };
+    if parsed.media_type == MediaType::Unknown && parsed.year.is_none() {
+        return true;
+    }
+
+    // A resolution in the title means the parser missed where it ends
+    let words: Vec<&str> = title.split_whitespace().collect();
+    if words.iter().any(|w| matches!(w.parse::<u16>(), Ok(480 | 576 | 720 | 1080 | 2160))) {
+        return true;
+    }
+
+    // Short single-case first words are abbreviations or group names
+    // ("bttf", "walle")
+    if let Some(first) = words.first() {
+        if first.len() <= 5
+            && first.chars().all(|c| c.is_ascii_alphabetic())
+            && (first.chars().all(|c| c.is_ascii_uppercase()) || first.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            return true;
+        }
+    }
+
+    SCENE_ABBREVIATION.is_match(title)
+}
+
+/// Whether the folder's result is more complete than the filename's
+fn is_better_result(folder: &ParsedMedia, filename: &ParsedMedia) -> bool {
+    let folder_title = folder.title.as_deref().unwrap_or("");
+    let filename_title = filename.title.as_deref().unwrap_or("");
+
+    folder_title.len() > filename_title.len() + 3
+        || (folder.year.is_some() && filename.year.is_none())
+        || folder.confidence > filename.confidence + 10
+}
+
+/// Whether one title contains the other, ignoring case and punctuation
+fn titles_overlap(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| -> String { s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect() };
+    let (a, b) = (normalize(a), normalize(b));
+    !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+/// Fills release details the filename lacks from its release folder
+fn fill_release_info(parsed: &mut ParsedMedia, folder: &ParsedMedia) {
+    let quality = &mut parsed.quality;
+    quality.resolution = quality.resolution.take().or_else(|| folder.quality.resolution.clone());
+    quality.source = quality.source.take().or_else(|| folder.quality.source.clone());
+    quality.codec = quality.codec.take().or_else(|| folder.quality.codec.clone());
+    quality.audio = quality.audio.take().or_else(|| folder.quality.audio.clone());
+    if parsed.release_group.is_none() {
+        parsed.release_group = folder.release_group.clone();
+    }
+    if parsed.country.is_none() {
+        parsed.country = folder.country.clone();
+    }
+    if parsed.languages.is_empty() {
+        parsed.languages = folder.languages.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_folder_helpers() {
+        assert_eq!(parent_folders("/media/Movies/Wonka (2023)/wonka.mkv"), vec!["Wonka (2023)", "Movies", "media"]);
+        assert_eq!(parent_folders(r"D:\Shows\Dark Matter\Season 2\05.mkv"), vec!["Season 2", "Dark Matter", "Shows", "D:"]);
+        assert_eq!(season_of("Season 02"), Some(2));
+        assert_eq!(season_of("S3"), Some(3));
+        assert_eq!(season_of("Seasonal"), None);
+
+        assert!(MEDIA_ROOT_FOLDER.is_match("TV Shows"));
+        assert!(MEDIA_ROOT_FOLDER.is_match("downloads"));
+        assert!(!MEDIA_ROOT_FOLDER.is_match("Breaking Bad"));
+        assert!(!MEDIA_ROOT_FOLDER.is_match("2001 A Space Odyssey"));
+
+        assert!(!is_poor_result(&parse("Breaking.Bad.S01E01.720p.mkv")));
+        assert!(!is_poor_result(&parse("Wonka.2023.720p.BluRay.mkv")));
+        assert!(is_poor_result(&parse("unknown.file.mkv")));
+        assert!(is_poor_result(&parse("bttf.720.mkv")));
+        assert!(is_poor_result(&parse("walle-bttf.iii.mkv")));
+    }
+}
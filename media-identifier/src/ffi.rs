@@ -0,0 +1,96 @@
+//! C API
+//!
+//! Exposes the parser to other languages through the cdylib
+//! (`libmedia_identifier.so` / `.dylib` / `.dll`), e.g. from Node with
+//! `koffi` or `ffi-napi`. Results are returned as JSON strings, the same
+//! shape `parse_json` produces; strings returned by the library must be
+//! released with `media_identifier_free`. A panic never crosses the C
+//! boundary: the call returns NULL instead.
+//!
+//! ```c
+//! char *json = media_identifier_parse("Dark.Matter.S01E05.720p.mkv");
+//! if (json) {
+//!     puts(json);
+//!     media_identifier_free(json);
+//! }
+//! ```
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Parses a NUL-terminated UTF-8 filename and returns the result as JSON
+///
+/// Returns NULL when `input` is NULL or not valid UTF-8, or when parsing
+/// panics.
+///
+/// # Safety
+/// `input` must be NULL or point to a NUL-terminated string that stays
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn media_identifier_parse(input: *const c_char) -> *mut c_char {
+    if input.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return std::ptr::null_mut();
+    };
+    guarded(|| into_c_string(crate::parse_json(input)))
+}
+
+/// Releases a string returned by this library
+///
+/// # Safety
+/// `ptr` must be NULL or a string returned by `media_identifier_parse` or
+/// `media_identifier_version`, released only once.
+#[no_mangle]
+pub unsafe extern "C" fn media_identifier_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(CString::from_raw(ptr))));
+    }
+}
+
+/// Library version (e.g. "0.1.0"), to be released with `media_identifier_free`
+#[no_mangle]
+pub extern "C" fn media_identifier_version() -> *mut c_char {
+    guarded(|| into_c_string(env!("CARGO_PKG_VERSION").to_string()))
+}
+
+/// Runs an entry point's body, turning a panic into NULL since unwinding
+/// into C is undefined behavior
+fn guarded(body: impl FnOnce() -> *mut c_char) -> *mut c_char {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or(std::ptr::null_mut())
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    // JSON escapes control characters, so only a stray NUL could fail here
+    CString::new(text).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let input = CString::new("Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv").unwrap();
+        unsafe {
+            let json = media_identifier_parse(input.as_ptr());
+            assert!(!json.is_null());
+            let value: serde_json::Value = serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+            assert_eq!(value["title"], "Dark Matter");
+            assert_eq!(value["episode"], 5);
+            media_identifier_free(json);
+
+            assert!(media_identifier_parse(std::ptr::null()).is_null());
+            media_identifier_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_panics_become_null() {
+        assert!(guarded(|| panic!("parser bug")).is_null());
+        let version = media_identifier_version();
+        assert!(!version.is_null());
+        unsafe { media_identifier_free(version) };
+    }
+}
@@ -0,0 +1,315 @@
+//! Filename formatting
+//!
+//! The inverse of the parser: renders a canonical filename from the fields
+//! of a [`ParsedMedia`] using a template, e.g.
+//!
+//! ```rust
+//! use media_identifier::{parse, FilenameFormatter};
+//!
+//! let media = parse("Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv");
+//! let formatter = FilenameFormatter::new("{title} - {SxxEyy}< [{resolution}]>.{ext}").unwrap();
+//! assert_eq!(formatter.format(&media), "Dark Matter - S01E05 [720p].mkv");
+//! ```
+//!
+//! Template syntax:
+//!
+//! - `{field}` inserts a field; `{field:02}` zero-pads a number to 2 digits
+//! - `<...>` is an optional section, left out when any field in it is empty
+//! - `{{`, `}}`, `<<` and `>>` are literal braces and angle brackets
+//!
+//! Fields: `title`, `year`, `country` (`US`, `UK`, ...), `season`, `episode`, `episode_end`,
+//! `episode_title`, `absolute_episode`, `air_date`, `SxxEyy` (`S01E05`,
+//! `S01E05E06` or `S01E05-E08`), `resolution`, `source`, `codec`, `audio`,
+//! `languages`, `flags`, `group` and `ext`.
+//!
+//! Characters that are not allowed in filenames are removed from the
+//! values, so the result is always a single path component.
+
+use crate::types::{MediaType, ParsedMedia};
+use thiserror::Error;
+
+/// Template for movies: `The Matrix (1999) [1080p].mkv`
+pub const MOVIE_TEMPLATE: &str = "{title}< ({year})>< [{resolution}]>.{ext}";
+
+/// Template for episodes: `Dark Matter - S01E05 - Episode Five [720p].mkv`
+pub const EPISODE_TEMPLATE: &str = "{title}< {country}> - {SxxEyy}< - {episode_title}>< [{resolution}]>.{ext}";
+
+/// Scene-style template: `Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv`
+///
+/// Use with [`FilenameFormatter::with_word_separator`]`('.')`.
+pub const SCENE_TEMPLATE: &str = "{title}<.{country}><.{year}><.{SxxEyy}><.{resolution}><.{source}><.{codec}><-{group}>.{ext}";
+
+const FIELDS: &[&str] = &[
+    "title",
+    "year",
+    "country",
+    "season",
+    "episode",
+    "episode_end",
+    "episode_title",
+    "absolute_episode",
+    "air_date",
+    "SxxEyy",
+    "resolution",
+    "source",
+    "codec",
+    "audio",
+    "languages",
+    "flags",
+    "group",
+    "ext",
+];
+
+/// Invalid filename template
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TemplateError {
+    #[error("unknown field '{0}'")]
+    UnknownField(String),
+    #[error("invalid format '{0}' (expected a width like 02)")]
+    InvalidFormat(String),
+    #[error("unclosed '{0}' at position {1}")]
+    Unclosed(char, usize),
+    #[error("unexpected '{0}' at position {1}")]
+    Unexpected(char, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field { name: String, width: usize },
+    Optional(Vec<Segment>),
+}
+
+/// Renders filenames from a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameFormatter {
+    segments: Vec<Segment>,
+    word_separator: Option<char>,
+}
+
+impl FilenameFormatter {
+    /// Parses a template
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let chars: Vec<char> = template.chars().collect();
+        let mut pos = 0;
+        let segments = parse_segments(&chars, &mut pos, false)?;
+        Ok(Self { segments, word_separator: None })
+    }
+
+    /// Default formatter for a media type ([`MOVIE_TEMPLATE`] or
+    /// [`EPISODE_TEMPLATE`])
+    pub fn for_media_type(media_type: MediaType) -> Self {
+        let template = match media_type {
+            MediaType::Episode => EPISODE_TEMPLATE,
+            _ => MOVIE_TEMPLATE,
+        };
+        Self::new(template).expect("built-in templates are valid")
+    }
+
+    /// Joins the words of values with `separator` instead of spaces
+    /// (e.g. `.` for scene names)
+    pub fn with_word_separator(mut self, separator: char) -> Self {
+        self.word_separator = Some(separator);
+        self
+    }
+
+    /// Renders the filename of `media`
+    pub fn format(&self, media: &ParsedMedia) -> String {
+        let mut out = String::new();
+        self.render(&self.segments, media, &mut out);
+        out.trim().to_string()
+    }
+
+    /// Renders segments into `out`; false if a field was empty
+    fn render(&self, segments: &[Segment], media: &ParsedMedia, out: &mut String) -> bool {
+        let mut complete = true;
+        for segment in segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field { name, width } => match self.value(name, *width, media) {
+                    Some(value) => out.push_str(&value),
+                    None => complete = false,
+                },
+                Segment::Optional(inner) => {
+                    let mut section = String::new();
+                    if self.render(inner, media, &mut section) {
+                        out.push_str(&section);
+                    }
+                }
+            }
+        }
+        complete
+    }
+
+    fn value(&self, name: &str, width: usize, media: &ParsedMedia) -> Option<String> {
+        let number = |n: Option<u16>| n.map(|n| format!("{:0width$}", n, width = width));
+        let episode = &media.episode_info;
+        let value = match name {
+            "title" => media.title.clone(),
+            "year" => number(media.year),
+            // Release names use UK rather than the ISO code
+            "country" => media.country.as_deref().map(|c| if c == "GB" { "UK" } else { c }.to_string()),
+            "season" => number(episode.season),
+            "episode" => number(episode.episode),
+            "episode_end" => number(episode.episode_end),
+            "episode_title" => episode.episode_title.clone(),
+            "absolute_episode" => number(episode.absolute_episode),
+            "air_date" => episode.air_date.clone(),
+            "SxxEyy" => season_episode(media),
+            "resolution" => media.quality.resolution.clone(),
+            "source" => media.quality.source.clone(),
+            "codec" => media.quality.codec.clone(),
+            "audio" => media.quality.audio.clone(),
+            "languages" => Some(media.languages.join(" ")).filter(|l| !l.is_empty()),
+            "flags" => Some(media.release_flags.join(" ")).filter(|f| !f.is_empty()),
+            "group" => media.release_group.clone(),
+            "ext" => media.container.clone(),
+            _ => None,
+        }?;
+
+        let value = sanitize(&value);
+        let value = match self.word_separator {
+            Some(separator) => value.split_whitespace().collect::<Vec<_>>().join(&separator.to_string()),
+            None => value,
+        };
+        Some(value).filter(|v| !v.is_empty())
+    }
+}
+
+impl std::str::FromStr for FilenameFormatter {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::new(template)
+    }
+}
+
+/// `S01E05`, `S01E05E06` for two episodes, `S01E05-E08` for longer ranges
+fn season_episode(media: &ParsedMedia) -> Option<String> {
+    let info = &media.episode_info;
+    let episode = info.episode?;
+    let mut text = format!("S{:02}E{:02}", info.season.unwrap_or(1), episode);
+    match info.episode_end {
+        Some(end) if end == episode + 1 => text.push_str(&format!("E{:02}", end)),
+        Some(end) if end > episode => text.push_str(&format!("-E{:02}", end)),
+        _ => {}
+    }
+    Some(text)
+}
+
+/// Removes characters that are invalid in filenames on common systems
+fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| match c {
+            ':' | '/' | '\\' | '*' | '?' | '"' | '<' | '>' | '|' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_segments(chars: &[char], pos: &mut usize, optional: bool) -> Result<Vec<Segment>, TemplateError> {
+    let start = pos.saturating_sub(1);
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        let next = chars.get(*pos + 1).copied();
+        match c {
+            '{' | '}' | '<' | '>' if next == Some(c) => {
+                literal.push(c);
+                *pos += 2;
+            }
+            '{' => {
+                let close = chars[*pos..]
+                    .iter()
+                    .position(|&c| c == '}')
+                    .ok_or(TemplateError::Unclosed('{', *pos))?;
+                let spec: String = chars[*pos + 1..*pos + close].iter().collect();
+                let (name, width) = match spec.split_once(':') {
+                    Some((name, format)) => {
+                        let width = format
+                            .strip_prefix('0')
+                            .and_then(|w| w.parse::<usize>().ok())
+                            .filter(|w| *w <= 10)
+                            .ok_or_else(|| TemplateError::InvalidFormat(format.to_string()))?;
+                        (name, width)
+                    }
+                    None => (spec.as_str(), 0),
+                };
+                if !FIELDS.contains(&name) {
+                    return Err(TemplateError::UnknownField(name.to_string()));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field { name: name.to_string(), width });
+                *pos += close + 1;
+            }
+            '<' => {
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                *pos += 1;
+                segments.push(Segment::Optional(parse_segments(chars, pos, true)?));
+            }
+            '>' if optional => {
+                *pos += 1;
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(literal));
+                }
+                return Ok(segments);
+            }
+            '}' | '>' => return Err(TemplateError::Unexpected(c, *pos)),
+            c => {
+                literal.push(c);
+                *pos += 1;
+            }
+        }
+    }
+
+    if optional {
+        return Err(TemplateError::Unclosed('<', start));
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_templates() {
+        let media = parse("Dark.Matter.S01E05E06.720p.HDTV.x264-KILLERS.mkv");
+        let formatter = FilenameFormatter::new("{title} {season}x{episode:02}< {{{group}}}><[{audio}]>.{ext}").unwrap();
+        assert_eq!(formatter.format(&media), "Dark Matter 1x05 {KILLERS}.mkv");
+        assert_eq!(
+            FilenameFormatter::for_media_type(MediaType::Episode).format(&media),
+            "Dark Matter - S01E05E06 [720p].mkv"
+        );
+
+        let mut movie = parse("The.Matrix.1999.1080p.BluRay.x264.mkv");
+        assert_eq!(
+            FilenameFormatter::for_media_type(MediaType::Movie).format(&movie),
+            "The Matrix (1999) [1080p].mkv"
+        );
+        movie.title = Some("Mission: Impossible / Fallout".to_string());
+        movie.year = None;
+        assert_eq!(
+            FilenameFormatter::for_media_type(MediaType::Movie).format(&movie),
+            "Mission Impossible Fallout [1080p].mkv"
+        );
+
+        assert_eq!(FilenameFormatter::new("{name}"), Err(TemplateError::UnknownField("name".into())));
+        assert_eq!(FilenameFormatter::new("{year:x}"), Err(TemplateError::InvalidFormat("x".into())));
+        assert_eq!(FilenameFormatter::new("{title}<.{year}"), Err(TemplateError::Unclosed('<', 7)));
+        assert_eq!(FilenameFormatter::new("{title}>"), Err(TemplateError::Unexpected('>', 7)));
+    }
+}
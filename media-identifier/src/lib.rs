@@ -35,14 +35,54 @@
 //! - **Codec**: x264, x265, HEVC, XviD
 //! - **Audio**: DTS, AC3, AAC, DD5.1, Atmos
 //! - **Language**: Hun, Eng, Ger, Fre, etc.
+//! - **Release Flags**: PROPER, REPACK, INTERNAL, REMUX
 //! - **Release Group**: -SPARKS, -YIFY, etc.
+//!
+//! ## Folder Context
+//!
+//! [`parse_with_context`] takes a full path and falls back to the parent
+//! folders where the filename is not descriptive, e.g. `movie.mkv` in a
+//! `The.Matrix.1999.1080p.BluRay-GROUP` torrent folder.
+//!
+//! ## Formatting
+//!
+//! [`FilenameFormatter`] goes the other way, rendering a canonical filename
+//! from parsed fields with a template, so renamed files parse back to the
+//! same fields.
+//!
+//! ## Bindings
+//!
+//! The library also builds as a cdylib with a C API (see [`ffi`] and
+//! `include/media_identifier.h`), and with the `wasm` feature as a
+//! WebAssembly module for browsers and Node (see `wasm.rs`). Both return
+//! results as the JSON of [`parse_json`].
+//!
+//! The parser itself does no I/O and keeps its state in the input, so it
+//! runs unchanged on `wasm32-unknown-unknown`; it is not `no_std`, as the
+//! `regex` patterns need the standard library.
 
+pub mod context;
+pub mod ffi;
+pub mod formatter;
 pub mod markers;
 pub mod parser;
 pub mod patterns;
+pub mod report;
 pub mod tokenizer;
 pub mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // Re-export main types and functions for convenience
-pub use parser::{parse, parse_debug, AnalysisResult, MediaParser, ParserConfig};
-pub use types::{EpisodeInfo, Hole, Match, MatchCategory, MediaType, ParsedMedia, QualityInfo};
+pub use context::parse_with_context;
+pub use formatter::{FilenameFormatter, TemplateError};
+pub use parser::{parse, parse_debug, AnalysisResult, MediaParser, ParserConfig, ParserProfile, TitleCase};
+pub use types::{EpisodeInfo, FieldConfidence, Hole, Match, MatchCategory, MediaType, ParsedMedia, QualityInfo};
+
+/// Parses a filename and serializes the result as JSON
+///
+/// This is the output of the C and WebAssembly bindings.
+pub fn parse_json(input: &str) -> String {
+    // ParsedMedia has only string keys and plain values, so this cannot fail
+    serde_json::to_string(&parse(input)).expect("ParsedMedia serializes to JSON")
+}
@@ -1,50 +1,236 @@
-use media_identifier::{parse, parse_debug, MediaType};
+use media_identifier::report::{self, TableFormat};
+use media_identifier::{FilenameFormatter, MediaParser, MediaType, ParsedMedia, ParserProfile};
 use std::env;
 use std::io::{self, BufRead};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    // Check for flags
-    let debug_mode = args.iter().any(|a| a == "--debug" || a == "-d");
-    let json_mode = args.iter().any(|a| a == "--json" || a == "-j");
-    let stdin_mode = args.iter().any(|a| a == "--stdin" || a == "-");
-    
-    // Filter out flags to get filenames
-    let filenames: Vec<&String> = args.iter()
-        .skip(1)
-        .filter(|a| !a.starts_with('-'))
-        .collect();
+/// Exit code bit set when a result's confidence is below `--fail-threshold`
+const EXIT_LOW_CONFIDENCE: u8 = 1;
+/// Exit code bit set when `--compare` finds a mismatch
+const EXIT_MISMATCH: u8 = 2;
+/// Exit code for invalid arguments or an unreadable expectation file
+const EXIT_USAGE: u8 = 64;
 
-    if stdin_mode || filenames.is_empty() {
-        // Read from stdin
-        let stdin = io::stdin();
-        for line in stdin.lock().lines() {
-            if let Ok(filename) = line {
-                let filename = filename.trim();
-                if !filename.is_empty() {
-                    process_file(filename, debug_mode, json_mode);
+const USAGE: &str = "\
+Usage: media-identifier [OPTIONS] [FILENAME...]
+
+Parses media filenames given as arguments, or one per line on stdin.
+
+Options:
+  -d, --debug               Show the pattern matches
+  -j, --json                JSON output
+  -f, --format <csv|tsv>    One row per filename, with a header line
+      --csv, --tsv          Same as --format csv / --format tsv
+  -c, --context             Treat arguments as paths and use the parent
+                            folders where the filename is not descriptive
+  -p, --profile <NAME>      Parser profile: default, strict, lenient, anime
+                            or sports
+  -r, --rename <TEMPLATE>   Print the canonical filename from TEMPLATE
+                            (e.g. \"{title} - {SxxEyy}.{ext}\"), or \"auto\"
+                            for the movie/episode default
+      --fail-threshold <N>  Exit with status 1 when a confidence is below N
+      --compare <FILE>      Check the filenames of FILE (CSV/TSV with a
+                            filename column) against its other columns;
+                            exit with status 2 on a mismatch
+  -h, --help                Show this help
+
+Exit status bits: 1 = low confidence, 2 = mismatch; 64 = usage error.";
+
+/// Output style for parse results
+#[derive(Clone, Copy, PartialEq)]
+enum Output {
+    Human,
+    Json,
+    Table(TableFormat),
+    Rename,
+}
+
+struct Options {
+    debug: bool,
+    /// Parse with folder context (--context)
+    context: bool,
+    output: Output,
+    profile: ParserProfile,
+    /// Template of --rename (None = default per media type)
+    rename: Option<FilenameFormatter>,
+    fail_threshold: Option<u8>,
+    compare: Option<String>,
+    stdin: bool,
+    filenames: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        debug: false,
+        context: false,
+        output: Output::Human,
+        profile: ParserProfile::Default,
+        rename: None,
+        fail_threshold: None,
+        compare: None,
+        stdin: false,
+        filenames: Vec::new(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().cloned().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "--debug" | "-d" => options.debug = true,
+            "--json" | "-j" => options.output = Output::Json,
+            "--context" | "-c" => options.context = true,
+            "--stdin" | "-" => options.stdin = true,
+            "--csv" => options.output = Output::Table(TableFormat::Csv),
+            "--tsv" => options.output = Output::Table(TableFormat::Tsv),
+            "--format" | "-f" => {
+                let name = value(arg)?;
+                let format = TableFormat::from_name(&name).ok_or(format!("Unknown format: {}", name))?;
+                options.output = Output::Table(format);
+            }
+            "--profile" | "-p" => {
+                let name = value(arg)?;
+                options.profile = ParserProfile::from_name(&name).ok_or(format!("Unknown profile: {}", name))?;
+            }
+            "--rename" | "-r" => {
+                let template = value(arg)?;
+                options.output = Output::Rename;
+                if template != "auto" {
+                    options.rename = Some(FilenameFormatter::new(&template).map_err(|e| format!("Invalid template: {}", e))?);
                 }
             }
+            "--fail-threshold" => {
+                let threshold = value(arg)?;
+                let threshold = threshold
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|t| *t <= 100)
+                    .ok_or(format!("Invalid threshold: {} (0-100)", threshold))?;
+                options.fail_threshold = Some(threshold);
+            }
+            "--compare" => options.compare = Some(value(arg)?),
+            flag if flag.starts_with('-') => return Err(format!("Unknown option: {}", flag)),
+            filename => options.filenames.push(filename.to_string()),
+        }
+    }
+    Ok(options)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_USAGE);
         }
+    };
+
+    if let Some(path) = &options.compare {
+        return run_compare(path, &options);
+    }
+
+    let filenames: Vec<String> = if options.stdin || options.filenames.is_empty() {
+        // Read from stdin
+        io::stdin()
+            .lock()
+            .lines()
+            .map_while(Result::ok)
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
     } else {
-        // Process command line arguments
-        for filename in filenames {
-            process_file(filename, debug_mode, json_mode);
+        options.filenames.clone()
+    };
+
+    if let Output::Table(format) = options.output {
+        println!("{}", format.header());
+    }
+    let mut status = 0;
+    for filename in &filenames {
+        let result = parse_file(filename, &options);
+        print_result(filename, &result, &options);
+        if options.fail_threshold.is_some_and(|t| result.confidence < t) {
+            status |= EXIT_LOW_CONFIDENCE;
+        }
+    }
+    ExitCode::from(status)
+}
+
+/// Checks the filenames of an expectation file against their expected values
+fn run_compare(path: &str, options: &Options) -> ExitCode {
+    let expectations = match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| report::read_expectations(&text))
+    {
+        Ok(expectations) => expectations,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    if let Output::Table(format) = options.output {
+        println!("{}", format.join(&["filename", "column", "expected", "actual"]));
+    }
+    let mut status = 0;
+    let mut failed = 0;
+    for expectation in &expectations {
+        let result = parse_file(&expectation.filename, options);
+        let mismatches = report::compare(&result, expectation);
+        if !mismatches.is_empty() {
+            status |= EXIT_MISMATCH;
+            failed += 1;
+        }
+        if options.fail_threshold.is_some_and(|t| result.confidence < t) {
+            status |= EXIT_LOW_CONFIDENCE;
+        }
+
+        for m in &mismatches {
+            match options.output {
+                Output::Table(format) => {
+                    println!("{}", format.join(&[&expectation.filename, &m.column, &m.expected, &m.actual]));
+                }
+                _ => println!("✗ {}: {} expected '{}', got '{}'", expectation.filename, m.column, m.expected, m.actual),
+            }
         }
     }
+
+    eprintln!("{}/{} filenames match", expectations.len() - failed, expectations.len());
+    ExitCode::from(status)
 }
 
-fn process_file(filename: &str, debug_mode: bool, json_mode: bool) {
-    let result = if debug_mode {
-        parse_debug(filename)
+fn parse_file(filename: &str, options: &Options) -> ParsedMedia {
+    let parser = if options.debug {
+        let mut config = MediaParser::with_profile(options.profile).config().clone();
+        config.include_matches = true;
+        MediaParser::with_config(config)
     } else {
-        parse(filename)
+        MediaParser::with_profile(options.profile)
     };
+    if options.context {
+        parser.parse_with_context(filename)
+    } else {
+        parser.parse(filename)
+    }
+}
 
-    if json_mode {
+fn print_result(filename: &str, result: &ParsedMedia, options: &Options) {
+    let debug_mode = options.debug;
+    if let Output::Table(format) = options.output {
+        println!("{}", format.row(filename, result));
+    } else if options.output == Output::Rename {
+        let renamed = match &options.rename {
+            Some(formatter) => formatter.format(result),
+            None => FilenameFormatter::for_media_type(result.media_type).format(result),
+        };
+        println!("{}", renamed);
+    } else if options.output == Output::Json {
         // JSON output
-        match serde_json::to_string_pretty(&result) {
+        match serde_json::to_string_pretty(result) {
             Ok(json) => println!("{}", json),
             Err(e) => eprintln!("JSON error: {}", e),
         }
@@ -81,6 +267,13 @@ fn process_file(filename: &str, debug_mode: bool, json_mode: bool) {
                     }
                 }
                 println!();
+            } else if let Some(e) = result.episode_info.absolute_episode {
+                println!("Episode:      {} (absolute)", e);
+            } else if let Some(e) = result.episode_info.episode {
+                println!("Episode:      {}", e);
+            }
+            if let Some(ref date) = result.episode_info.air_date {
+                println!("Air Date:     {}", date);
             }
             if let Some(ref ep_title) = result.episode_info.episode_title {
                 println!("Ep. Title:    {}", ep_title);
@@ -110,11 +303,17 @@ fn process_file(filename: &str, debug_mode: bool, json_mode: bool) {
             println!("Group:        {}", group);
         }
         
+        if !result.release_flags.is_empty() {
+            println!("Flags:        {}", result.release_flags.join(", "));
+        }
+        
         if let Some(ref container) = result.container {
             println!("Container:    {}", container);
         }
         
-        println!("Confidence:   {}%", result.confidence);
+        let fields = result.field_confidence;
+        println!("Confidence:   {}% (title {}, season/episode {}, year {})",
+            result.confidence, fields.title, fields.season_episode, fields.year);
         
         // Debug info
         if debug_mode && !result.matches.is_empty() {
@@ -1,4 +1,4 @@
-use crate::types::{Match, MatchCategory, Hole};
+use crate::types::{FieldConfidence, Match, MatchCategory, Hole};
 
 /// Conflict resolver for overlapping matches
 pub struct ConflictResolver;
@@ -48,6 +48,66 @@ impl ConflictResolver {
         resolved.sort_by_key(|m| m.start);
         resolved
     }
+
+    /// Gives number-like tokens back to titles that are numbers or years
+    ///
+    /// Release years come right before the release details, so of several
+    /// year-like tokens only the last one is the year: "2012.2009.1080p"
+    /// is "2012" from 2009, "Airport.1975.1974" is "Airport 1975". Bare
+    /// episode numbers followed by a year are part of the title too
+    /// ("Blade.Runner.2049.2017", "Room.237.2012"), and a year that is the
+    /// whole name ("2012.1080p") is the title rather than a year.
+    pub fn resolve_numeric_titles(input: &str, mut matches: Vec<Match>) -> Vec<Match> {
+        let is_year = |m: &Match| m.category == MatchCategory::Year;
+        let is_bare_number = |m: &Match| {
+            m.category == MatchCategory::Episode && input[m.start..m.end].bytes().all(|b| b.is_ascii_digit())
+        };
+
+        // "2049" in "Blade.Runner.2049.2017" is not S20E49
+        let last_year_start = matches.iter().filter(|m| is_year(m)).map(|m| m.start).max();
+        if let Some(year_start) = last_year_start {
+            matches.retain(|m| !(is_bare_number(m) && m.start < year_start));
+        }
+
+        // Years after an episode marker are episode titles ("Doctor Who - S05E01 - 1969")
+        let episode_start = matches
+            .iter()
+            .filter(|m| matches!(m.category, MatchCategory::Episode | MatchCategory::Season | MatchCategory::Date))
+            .map(|m| m.start)
+            .min()
+            .unwrap_or(input.len());
+        let title_years: Vec<usize> = matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| is_year(m) && m.start < episode_start)
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut title_numbers: Vec<usize> = match title_years.split_last() {
+            // Which one is the release year is still a guess
+            Some((&last, earlier)) if !earlier.is_empty() => {
+                matches[last].confidence = matches[last].confidence.saturating_sub(30);
+                earlier.to_vec()
+            }
+            _ => Vec::new(),
+        };
+        // A lone leading year with nothing but release details after it
+        if let [only] = title_years[..] {
+            let year = &matches[only];
+            let next_start = matches.iter().filter(|m| m.start >= year.end).map(|m| m.start).min().unwrap_or(input.len());
+            let rest = &input[year.end..next_start];
+            if year.start == 0 && !rest.chars().any(char::is_alphanumeric) {
+                title_numbers.push(only);
+            }
+        }
+
+        let mut index = 0;
+        matches.retain(|_| {
+            index += 1;
+            !title_numbers.contains(&(index - 1))
+        });
+        matches
+    }
 }
 
 /// Hole finder - identifies unmatched regions in the input
@@ -171,9 +231,9 @@ impl TitleExtractor {
 
     /// Extract episode title (text between season/episode marker and quality markers)
     pub fn extract_episode_title(input: &str, matches: &[Match]) -> Option<String> {
-        // Find season/episode match
+        // Find season/episode match (or the air date of daily shows)
         let episode_match = matches.iter()
-            .find(|m| m.category == MatchCategory::Episode || m.category == MatchCategory::Season);
+            .find(|m| matches!(m.category, MatchCategory::Episode | MatchCategory::Season | MatchCategory::Date));
 
         // Find first quality/source/codec marker after episode
         let episode_end = episode_match.map(|m| m.end).unwrap_or(0);
@@ -184,7 +244,7 @@ impl TitleExtractor {
                     m.category,
                     MatchCategory::Quality | MatchCategory::Source | MatchCategory::Codec 
                     | MatchCategory::Audio | MatchCategory::Language | MatchCategory::Noise
-                    | MatchCategory::ReleaseGroup
+                    | MatchCategory::ReleaseFlag | MatchCategory::ReleaseGroup
                 )
             })
             .min_by_key(|m| m.start);
@@ -203,15 +263,18 @@ impl TitleExtractor {
     }
 
     /// Determine if a match type serves as a title boundary
-    fn is_title_boundary(m: &Match) -> bool {
+    pub(crate) fn is_title_boundary(m: &Match) -> bool {
         matches!(
             m.category,
             MatchCategory::Year 
             | MatchCategory::Season 
             | MatchCategory::Episode 
+            | MatchCategory::Date
+            | MatchCategory::Country
             | MatchCategory::Quality
             | MatchCategory::Source
             | MatchCategory::Codec
+            | MatchCategory::ReleaseFlag
             | MatchCategory::Noise
         )
     }
@@ -303,6 +366,43 @@ impl PostProcessor {
         languages
     }
 
+    /// Determine the confidence of the title, season/episode and year
+    ///
+    /// Numbering and year take the confidence of their match (explicit
+    /// S01E05 beats a guess from "117"); a year is less certain when the
+    /// name holds several. The title is less certain when nothing marked
+    /// its end, or when it is a short word or a bare number.
+    pub fn field_confidence(matches: &[Match], title: Option<&str>) -> FieldConfidence {
+        let season_episode = matches
+            .iter()
+            .filter(|m| matches!(m.category, MatchCategory::Episode | MatchCategory::Date))
+            .map(|m| if m.category == MatchCategory::Date { 90 } else { m.confidence })
+            .max()
+            .or_else(|| matches.iter().filter(|m| m.category == MatchCategory::Season).map(|m| m.confidence).max())
+            .unwrap_or(0);
+
+        let years: Vec<&Match> = matches.iter().filter(|m| m.category == MatchCategory::Year).collect();
+        let year = match years.as_slice() {
+            [] => 0,
+            [only] => only.confidence,
+            [first, ..] => first.confidence.saturating_sub(30),
+        };
+
+        let title = title.map_or(0, |title| {
+            let mut confidence: u8 = if matches.iter().any(TitleExtractor::is_title_boundary) { 90 } else { 60 };
+            let words: Vec<&str> = title.split_whitespace().collect();
+            if words.len() == 1 && title.chars().count() <= 3 {
+                confidence = confidence.saturating_sub(30);
+            }
+            if title.chars().all(|c| c.is_ascii_digit() || c == ' ') {
+                confidence = confidence.saturating_sub(20);
+            }
+            confidence
+        });
+
+        FieldConfidence { title, season_episode, year }
+    }
+
     /// Determine confidence score for the overall parse
     pub fn calculate_confidence(matches: &[Match], has_title: bool) -> u8 {
         let mut confidence = 50u8;
@@ -370,6 +470,29 @@ mod tests {
         assert_eq!(resolved[0].category, MatchCategory::Year);
     }
 
+    #[test]
+    fn test_numeric_titles() {
+        let year = |start, value: &str| Match::new(start, start + 4, value.to_string(), MatchCategory::Year);
+        let input = "2012.2009.1080p";
+        let matches = vec![year(0, "2012"), year(5, "2009"), make_match(10, 15, MatchCategory::Quality)];
+        let resolved = ConflictResolver::resolve_numeric_titles(input, matches);
+        assert_eq!(resolved.iter().filter(|m| m.category == MatchCategory::Year).count(), 1);
+        assert_eq!(resolved[0].value, "2009");
+
+        let input = "Room.237.2012";
+        let matches = vec![make_match(5, 8, MatchCategory::Episode), year(9, "2012")];
+        let resolved = ConflictResolver::resolve_numeric_titles(input, matches);
+        assert_eq!(resolved.len(), 1);
+
+        let input = "2012.720p";
+        let matches = vec![year(0, "2012"), make_match(5, 9, MatchCategory::Quality)];
+        assert_eq!(ConflictResolver::resolve_numeric_titles(input, matches).len(), 1);
+
+        // A year before the title words stays a year
+        let input = "2019.Movie.Title";
+        assert_eq!(ConflictResolver::resolve_numeric_titles(input, vec![year(0, "2019")]).len(), 1);
+    }
+
     #[test]
     fn test_find_holes() {
         let input = "Hello.World.2023.720p";
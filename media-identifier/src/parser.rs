@@ -1,5 +1,5 @@
 use crate::markers::{ConflictResolver, HoleFinder, PostProcessor, TitleExtractor};
-use crate::patterns::PatternRegistry;
+use crate::patterns::{PatternRegistry, DEFAULT_YEAR_RANGE};
 use crate::tokenizer::Tokenizer;
 use crate::types::{
     EpisodeInfo, Hole, Match, MatchCategory, MatchInfo, MediaType, ParsedMedia, QualityInfo,
@@ -16,6 +16,17 @@ pub struct AnalysisResult {
     pub holes: Vec<Hole>,
 }
 
+/// How extracted titles are cased
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TitleCase {
+    /// Keep the casing of the filename
+    #[default]
+    Preserve,
+    /// Capitalize lowercase words ("the.dark.knight" -> "The Dark Knight"),
+    /// keeping short joining words lowercase and leaving other words as-is
+    Title,
+}
+
 /// Configuration for the parser
 #[derive(Debug, Clone)]
 pub struct ParserConfig {
@@ -25,6 +36,19 @@ pub struct ParserConfig {
     pub extract_episode_titles: bool,
     /// Use smart tokenization (merge hyphenated words)
     pub smart_tokenize: bool,
+    /// Guess episodes from bare numbers (117 -> S01E17, 2401 -> S24E01)
+    pub bare_episode_numbers: bool,
+    /// Anime releases: bracketed groups and absolute episode numbers
+    /// ("[Group] Title - 012")
+    pub anime: bool,
+    /// Air dates (2024.03.15) as episode markers of daily shows
+    pub date_based: bool,
+    /// Sports events: round, week and matchday numbers as episodes
+    pub sports: bool,
+    /// Years accepted as release years (inclusive)
+    pub year_range: (u16, u16),
+    /// Casing of titles and episode titles
+    pub title_case: TitleCase,
 }
 
 impl Default for ParserConfig {
@@ -33,6 +57,85 @@ impl Default for ParserConfig {
             include_matches: false,
             extract_episode_titles: true,
             smart_tokenize: true,
+            bare_episode_numbers: true,
+            anime: false,
+            date_based: false,
+            sports: false,
+            year_range: DEFAULT_YEAR_RANGE,
+            title_case: TitleCase::Preserve,
+        }
+    }
+}
+
+impl ParserConfig {
+    /// Configuration of a named profile
+    pub fn profile(profile: ParserProfile) -> Self {
+        let default = Self::default();
+        match profile {
+            ParserProfile::Default => default,
+            ParserProfile::Strict => Self {
+                bare_episode_numbers: false,
+                ..default
+            },
+            ParserProfile::Lenient => Self {
+                date_based: true,
+                year_range: (1900, 2099),
+                title_case: TitleCase::Title,
+                ..default
+            },
+            ParserProfile::Anime => Self {
+                anime: true,
+                bare_episode_numbers: false,
+                ..default
+            },
+            ParserProfile::Sports => Self {
+                sports: true,
+                date_based: true,
+                bare_episode_numbers: false,
+                ..default
+            },
+        }
+    }
+}
+
+/// Named parser configurations for different kinds of libraries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParserProfile {
+    /// Scene and P2P naming (the default configuration)
+    #[default]
+    Default,
+    /// Only explicit markers: no episode guesses from bare numbers
+    Strict,
+    /// Guess more: air dates, years from 1900 to 2099, title-cased titles
+    Lenient,
+    /// Fansub naming with absolute episode numbers
+    Anime,
+    /// Dated events with rounds or weeks
+    Sports,
+}
+
+impl ParserProfile {
+    /// All profiles
+    pub const ALL: [ParserProfile; 5] = [
+        ParserProfile::Default,
+        ParserProfile::Strict,
+        ParserProfile::Lenient,
+        ParserProfile::Anime,
+        ParserProfile::Sports,
+    ];
+
+    /// Profile by name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParserProfile::Default => "default",
+            ParserProfile::Strict => "strict",
+            ParserProfile::Lenient => "lenient",
+            ParserProfile::Anime => "anime",
+            ParserProfile::Sports => "sports",
         }
     }
 }
@@ -62,6 +165,16 @@ impl MediaParser {
         Self { config }
     }
 
+    /// Create a new parser with a profile's configuration
+    pub fn with_profile(profile: ParserProfile) -> Self {
+        Self::with_config(ParserConfig::profile(profile))
+    }
+
+    /// The parser's configuration
+    pub fn config(&self) -> &ParserConfig {
+        &self.config
+    }
+
     /// Parse a media filename
     pub fn parse(&self, input: &str) -> ParsedMedia {
         // Step 0: Handle file path - extract just the filename
@@ -71,17 +184,20 @@ impl MediaParser {
         let (name_without_ext, container) = self.strip_extension(&filename);
 
         // Step 2: Find all pattern matches
-        let all_matches = PatternRegistry::find_all_matches(&name_without_ext);
+        let all_matches = PatternRegistry::find_all_matches_with(&name_without_ext, &self.config);
 
         // Step 3: Resolve conflicts
         let resolved_matches = ConflictResolver::resolve(all_matches);
+        let resolved_matches = ConflictResolver::resolve_numeric_titles(&name_without_ext, resolved_matches);
 
         // Step 4: Extract title
-        let title = TitleExtractor::extract_title(&name_without_ext, &resolved_matches);
+        let title = TitleExtractor::extract_title(&name_without_ext, &resolved_matches)
+            .map(|t| self.apply_title_case(t));
 
         // Step 5: Extract episode title if enabled and this is a TV show
         let episode_title = if self.config.extract_episode_titles {
             TitleExtractor::extract_episode_title(&name_without_ext, &resolved_matches)
+                .map(|t| self.apply_title_case(t))
         } else {
             None
         };
@@ -93,11 +209,14 @@ impl MediaParser {
         let episode_info = self.extract_episode_info(&resolved_matches, episode_title);
         let quality_info = self.extract_quality_info(&resolved_matches);
         let year = self.extract_year(&resolved_matches);
+        let country = self.extract_country(&resolved_matches);
         let languages = PostProcessor::normalize_languages(&resolved_matches);
         let release_group = self.extract_release_group(&resolved_matches);
+        let release_flags = self.extract_release_flags(&resolved_matches);
 
         // Step 8: Calculate confidence
         let confidence = PostProcessor::calculate_confidence(&resolved_matches, title.is_some());
+        let field_confidence = PostProcessor::field_confidence(&resolved_matches, title.as_deref());
 
         // Step 9: Build result
         ParsedMedia {
@@ -105,12 +224,15 @@ impl MediaParser {
             media_type,
             title,
             year,
+            country,
             episode_info,
             quality: quality_info,
             languages,
             release_group,
+            release_flags,
             container,
             confidence,
+            field_confidence,
             matches: if self.config.include_matches {
                 resolved_matches.iter().map(MatchInfo::from).collect()
             } else {
@@ -134,8 +256,9 @@ impl MediaParser {
     pub fn analyze(&self, input: &str) -> AnalysisResult {
         let filename = self.extract_filename(input);
         let (name_without_ext, _) = self.strip_extension(&filename);
-        let all_matches = PatternRegistry::find_all_matches(&name_without_ext);
+        let all_matches = PatternRegistry::find_all_matches_with(&name_without_ext, &self.config);
         let resolved_matches = ConflictResolver::resolve(all_matches);
+        let resolved_matches = ConflictResolver::resolve_numeric_titles(&name_without_ext, resolved_matches);
         let holes = HoleFinder::find_holes(&name_without_ext, &resolved_matches);
 
         AnalysisResult {
@@ -166,9 +289,9 @@ impl MediaParser {
 
     /// Detect whether this is a movie or TV episode
     fn detect_media_type(&self, matches: &[Match]) -> MediaType {
-        // If we have season/episode markers, it's a TV show
+        // If we have season/episode markers (or an air date), it's a TV show
         let has_episode = matches.iter().any(|m| {
-            m.category == MatchCategory::Episode || m.category == MatchCategory::Season
+            matches!(m.category, MatchCategory::Episode | MatchCategory::Season | MatchCategory::Date)
         });
 
         if has_episode {
@@ -201,12 +324,45 @@ impl MediaParser {
             }
         }
 
+        // Anime numbering counts episodes across seasons
+        let absolute_episode = if self.config.anime && season.is_none() {
+            episode
+        } else {
+            None
+        };
+        let air_date = matches
+            .iter()
+            .find(|m| m.category == MatchCategory::Date)
+            .map(|m| m.value.clone());
+
         EpisodeInfo {
             season,
             episode,
             episode_end,
             episode_title,
-            absolute_episode: None, // Could be added for anime support
+            absolute_episode,
+            air_date,
+        }
+    }
+
+    /// Apply the configured title casing
+    fn apply_title_case(&self, title: String) -> String {
+        match self.config.title_case {
+            TitleCase::Preserve => title,
+            TitleCase::Title => title
+                .split(' ')
+                .enumerate()
+                .map(|(i, word)| {
+                    let lowercase = word.chars().all(|c| !c.is_uppercase());
+                    let joining = matches!(word, "a" | "an" | "and" | "at" | "by" | "for" | "in" | "of" | "on" | "or" | "the" | "to" | "vs");
+                    if !lowercase || (i > 0 && joining) {
+                        return word.to_string();
+                    }
+                    let mut chars = word.chars();
+                    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
         }
     }
 
@@ -278,6 +434,14 @@ impl MediaParser {
         year_match.and_then(|m| m.value.parse().ok())
     }
 
+    /// Extract the country code of a remake
+    fn extract_country(&self, matches: &[Match]) -> Option<String> {
+        matches
+            .iter()
+            .find(|m| m.category == MatchCategory::Country)
+            .map(|m| m.value.clone())
+    }
+
     /// Extract release group from matches
     fn extract_release_group(&self, matches: &[Match]) -> Option<String> {
         matches
@@ -285,6 +449,17 @@ impl MediaParser {
             .find(|m| m.category == MatchCategory::ReleaseGroup)
             .map(|m| m.value.clone())
     }
+
+    /// Extract release flags from matches (deduplicated, in filename order)
+    fn extract_release_flags(&self, matches: &[Match]) -> Vec<String> {
+        let mut flags: Vec<String> = Vec::new();
+        for m in matches.iter().filter(|m| m.category == MatchCategory::ReleaseFlag) {
+            if !flags.contains(&m.value) {
+                flags.push(m.value.clone());
+            }
+        }
+        flags
+    }
 }
 
 impl Default for MediaParser {
@@ -409,6 +584,64 @@ mod tests {
         assert_eq!(result.release_group, Some("FULCRUM".to_string()));
     }
 
+    #[test]
+    fn test_release_flags() {
+        let result = parse("Dark.Matter.S02E08.PROPER.REPACK.720p.HDTV.x264-KILLERS.mkv");
+        assert_eq!(result.title, Some("Dark Matter".to_string()));
+        assert_eq!(result.release_flags, vec!["PROPER".to_string(), "REPACK".to_string()]);
+
+        let original = parse("Dark.Matter.S02E08.720p.HDTV.x264-KILLERS.mkv");
+        assert!(result.supersedes(&original));
+        assert!(!original.supersedes(&result));
+    }
+
+    #[test]
+    fn test_field_confidence() {
+        let episode = parse("Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv").field_confidence;
+        assert_eq!((episode.title, episode.season_episode, episode.year), (90, 100, 0));
+
+        let guessed = parse("Show.117.HDTV.mkv").field_confidence;
+        assert_eq!(guessed.season_episode, 75);
+
+        // Two years: which one is the release year is uncertain
+        let odyssey = parse("2001.A.Space.Odyssey.1968.mkv").field_confidence;
+        assert!(odyssey.year < 100);
+
+        let unmarked = parse("some random name.mkv").field_confidence;
+        assert_eq!(unmarked.title, 60);
+    }
+
+    #[test]
+    fn test_profiles() {
+        let anime = MediaParser::with_profile(ParserProfile::Anime)
+            .parse("[SubsPlease] Sousou no Frieren - 12 (1080p) [ABCD1234].mkv");
+        assert_eq!(anime.media_type, MediaType::Episode);
+        assert_eq!(anime.title, Some("Sousou no Frieren".to_string()));
+        assert_eq!(anime.episode_info.absolute_episode, Some(12));
+        assert_eq!(anime.release_group, Some("SubsPlease".to_string()));
+
+        let daily = MediaParser::with_profile(ParserProfile::Lenient)
+            .parse("the.daily.show.2024.03.15.guest.name.720p.WEB.mkv");
+        assert_eq!(daily.media_type, MediaType::Episode);
+        assert_eq!(daily.title, Some("The Daily Show".to_string()));
+        assert_eq!(daily.episode_info.air_date, Some("2024-03-15".to_string()));
+        assert_eq!(daily.episode_info.episode_title, Some("Guest Name".to_string()));
+
+        let sports = MediaParser::with_profile(ParserProfile::Sports)
+            .parse("NFL.2023.Week.12.Bills.vs.Eagles.720p.mkv");
+        assert_eq!(sports.title, Some("NFL".to_string()));
+        assert_eq!(sports.episode_info.episode, Some(12));
+        assert_eq!(sports.episode_info.episode_title, Some("Bills vs Eagles".to_string()));
+
+        // Bare numbers are guessed by default but not in strict mode
+        assert_eq!(parse("Show.117.HDTV.mkv").episode_info.episode, Some(17));
+        let strict = MediaParser::with_profile(ParserProfile::Strict).parse("Show.117.HDTV.mkv");
+        assert_eq!(strict.episode_info.episode, None);
+
+        assert_eq!(ParserProfile::from_name(" Anime "), Some(ParserProfile::Anime));
+        assert_eq!(ParserProfile::from_name("unknown"), None);
+    }
+
     #[test]
     fn test_analyze_holes() {
         // Test the analyze() function that exposes holes
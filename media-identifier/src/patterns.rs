@@ -1,7 +1,11 @@
+use crate::parser::ParserConfig;
 use crate::types::{Match, MatchCategory};
 use lazy_static::lazy_static;
 use regex::Regex;
 
+/// Years accepted as release years unless configured otherwise
+pub const DEFAULT_YEAR_RANGE: (u16, u16) = (1940, 2039);
+
 /// A pattern matcher that can find matches in tokens or raw strings
 pub trait Pattern: Send + Sync {
     fn name(&self) -> &'static str;
@@ -51,9 +55,27 @@ lazy_static! {
         r"[.\-_\s]([1-9]\d)(\d{2})[.\-_\s]"
     ).unwrap();
 
-    // Year pattern (4 digits, reasonable range)
+    // Year pattern (4 digits, narrowed to the configured range in code)
     static ref YEAR_PATTERN: Regex = Regex::new(
-        r"\b(19[4-9]\d|20[0-3]\d)\b"
+        r"\b(1[89]\d{2}|20\d{2})\b"
+    ).unwrap();
+
+    // Air date of daily shows: 2024.03.15, 2024-03-15
+    static ref DATE_PATTERN: Regex = Regex::new(
+        r"\b((?:19|20)\d{2})[.\-_ ](0[1-9]|1[0-2])[.\-_ ](0[1-9]|[12]\d|3[01])\b"
+    ).unwrap();
+
+    // Anime release group in brackets at the start: "[SubsPlease] Title - 01"
+    static ref ANIME_GROUP_PATTERN: Regex = Regex::new(r"^\[([^\]]+)\]").unwrap();
+
+    // Anime absolute episode after a spaced dash: "Title - 012", "Title - 12v2"
+    static ref ANIME_EPISODE_PATTERN: Regex = Regex::new(
+        r"\s-\s(\d{1,4})(?:v\d)?\b"
+    ).unwrap();
+
+    // Sports event numbering: Round 5, R05, Week.12, Matchday 3
+    static ref SPORTS_ROUND_PATTERN: Regex = Regex::new(
+        r"(?i)\b(?:Round|Rd|R|Week|Wk|Matchday|MD|Game)[.\s_-]?(\d{1,3})\b"
     ).unwrap();
 
     // Resolution/Quality patterns
@@ -124,6 +146,14 @@ lazy_static! {
         ("Dual Audio", Regex::new(r"(?i)\bDual[.\s]?Audio\b").unwrap()),
     ];
 
+    // Release flags (fix-up releases and release kind)
+    static ref RELEASE_FLAG_PATTERNS: Vec<(&'static str, Regex)> = vec![
+        ("PROPER", Regex::new(r"(?i)\bPROPER\b").unwrap()),
+        ("REPACK", Regex::new(r"(?i)\b(REPACK\d?|RERIP)\b").unwrap()),
+        ("INTERNAL", Regex::new(r"(?i)\bINTERNAL\b").unwrap()),
+        ("REMUX", Regex::new(r"(?i)\bREMUX\b").unwrap()),
+    ];
+
     // Release group pattern (typically at the end after a hyphen)
     static ref RELEASE_GROUP_PATTERN: Regex = Regex::new(
         r"-([A-Za-z0-9]+)(?:\.[a-z]{2,4})?$"
@@ -138,9 +168,6 @@ lazy_static! {
     // Noise tokens (things to strip/ignore)
     static ref NOISE_PATTERNS: Vec<Regex> = vec![
         Regex::new(r"(?i)\bREMASTERED\b").unwrap(),
-        Regex::new(r"(?i)\bPROPER\b").unwrap(),
-        Regex::new(r"(?i)\bREPACK\b").unwrap(),
-        Regex::new(r"(?i)\bINTERNAL\b").unwrap(),
         Regex::new(r"(?i)\bLIMITED\b").unwrap(),
         Regex::new(r"(?i)\bREAD\.?NFO\b").unwrap(),
         Regex::new(r"(?i)\bHYBRID\b").unwrap(),
@@ -151,6 +178,13 @@ lazy_static! {
         Regex::new(r"(?i)\bSample\b").unwrap(),
     ];
 
+    // Country of a remake or local version ("The.Office.US.S01E01",
+    // "Shameless (UK) 2011"): an upper-case code right before the year,
+    // numbering or quality, or at the end
+    static ref COUNTRY_PATTERN: Regex = Regex::new(
+        r"(?:^|[\s._(\[-])(US|UK|GB|AU|NZ|CA)(?:[)\]]?$|[)\]]?[\s._-]+(?:[Ss]\d|\d{1,2}x\d|(?:19|20)\d{2}\b|\d{3,4}p\b|\())"
+    ).unwrap();
+
     // Season range pattern (S01-S03)
     static ref SEASON_RANGE_PATTERN: Regex = Regex::new(
         r"(?i)\b[Ss](\d{1,2})-[Ss]?(\d{1,2})\b"
@@ -166,6 +200,12 @@ pub struct SeasonEpisodePattern;
 
 impl SeasonEpisodePattern {
     pub fn find_matches(input: &str) -> Vec<Match> {
+        Self::find_matches_with(input, true)
+    }
+
+    /// Find matches, guessing episodes from bare numbers (117, 2401) only
+    /// when `bare_numbers` is set
+    pub fn find_matches_with(input: &str, bare_numbers: bool) -> Vec<Match> {
         let mut matches = Vec::new();
 
         // Try each pattern
@@ -315,7 +355,7 @@ impl SeasonEpisodePattern {
 
         // Check for 3-digit episode format (117 -> S01E17)
         // Only if we haven't found any other episode patterns
-        if !matches.iter().any(|m| m.category == MatchCategory::Episode) {
+        if bare_numbers && !matches.iter().any(|m| m.category == MatchCategory::Episode) {
             for cap in EPISODE_3DIGIT_PATTERN.captures_iter(input) {
                 let season_str = cap.get(1).unwrap().as_str();
                 let episode_str = cap.get(2).unwrap().as_str();
@@ -371,7 +411,7 @@ impl SeasonEpisodePattern {
 
         // Check for 4-digit episode format (2401 -> S24E01) for high season shows
         // Only if we haven't found any episode patterns yet
-        if !matches.iter().any(|m| m.category == MatchCategory::Episode) {
+        if bare_numbers && !matches.iter().any(|m| m.category == MatchCategory::Episode) {
             for cap in EPISODE_4DIGIT_PATTERN.captures_iter(input) {
                 let season_str = cap.get(1).unwrap().as_str();
                 let episode_str = cap.get(2).unwrap().as_str();
@@ -415,11 +455,19 @@ pub struct YearPattern;
 
 impl YearPattern {
     pub fn find_matches(input: &str) -> Vec<Match> {
+        Self::find_matches_in_range(input, DEFAULT_YEAR_RANGE)
+    }
+
+    /// Find years between `range.0` and `range.1` (inclusive)
+    pub fn find_matches_in_range(input: &str, range: (u16, u16)) -> Vec<Match> {
         let mut matches = Vec::new();
         
         for cap in YEAR_PATTERN.captures_iter(input) {
             let m = cap.get(1).unwrap();
             let year: u16 = m.as_str().parse().unwrap();
+            if year < range.0 || year > range.1 {
+                continue;
+            }
             
             // Confidence based on position and context
             let confidence = if m.start() > 0 {
@@ -442,6 +490,95 @@ impl YearPattern {
     }
 }
 
+/// Air date matcher for daily shows and sports events
+pub struct DatePattern;
+
+impl DatePattern {
+    pub fn find_matches(input: &str) -> Vec<Match> {
+        DATE_PATTERN
+            .captures_iter(input)
+            .map(|cap| {
+                let full = cap.get(0).unwrap();
+                let date = format!("{}-{}-{}", &cap[1], &cap[2], &cap[3]);
+                Match::new(full.start(), full.end(), date, MatchCategory::Date)
+            })
+            .collect()
+    }
+}
+
+/// Anime release matcher: bracketed group and absolute episode numbers
+pub struct AnimePattern;
+
+impl AnimePattern {
+    pub fn find_matches(input: &str, year_range: (u16, u16)) -> Vec<Match> {
+        let mut matches = Vec::new();
+
+        if let Some(cap) = ANIME_GROUP_PATTERN.captures(input) {
+            let full = cap.get(0).unwrap();
+            let group = cap[1].trim().to_string();
+            matches.push(Match::new(full.start(), full.end(), group, MatchCategory::ReleaseGroup)
+                .with_confidence(90));
+        }
+
+        for cap in ANIME_EPISODE_PATTERN.captures_iter(input) {
+            let number = cap.get(1).unwrap();
+            let episode: u16 = number.as_str().parse().unwrap();
+            // "Title - 2019" is more likely a year than episode 2019
+            if episode == 0 || (number.len() == 4 && episode >= year_range.0 && episode <= year_range.1) {
+                continue;
+            }
+            matches.push(Match::new(number.start(), cap.get(0).unwrap().end(), format!("E{:02}", episode), MatchCategory::Episode)
+                .with_raw(format!("0|{}|0", episode))
+                .with_confidence(85));
+            break;
+        }
+
+        matches
+    }
+}
+
+/// Sports event matcher: round, week and matchday numbers
+pub struct SportsPattern;
+
+impl SportsPattern {
+    pub fn find_matches(input: &str) -> Vec<Match> {
+        SPORTS_ROUND_PATTERN
+            .captures_iter(input)
+            .filter_map(|cap| {
+                let full = cap.get(0).unwrap();
+                let round: u16 = cap[1].parse().ok().filter(|&n| n > 0)?;
+                Some(Match::new(full.start(), full.end(), format!("E{:02}", round), MatchCategory::Episode)
+                    .with_raw(format!("0|{}|0", round))
+                    .with_confidence(85))
+            })
+            .take(1)
+            .collect()
+    }
+}
+
+/// Country code matcher for remakes and local versions of shows
+pub struct CountryPattern;
+
+impl CountryPattern {
+    /// Finds the country after the title, as an ISO 3166 code (UK -> GB)
+    pub fn find_matches(input: &str) -> Vec<Match> {
+        COUNTRY_PATTERN
+            .captures_iter(input)
+            .filter_map(|cap| {
+                let code = cap.get(1).unwrap();
+                // A title comes first
+                if code.start() < 2 {
+                    return None;
+                }
+                let country = if code.as_str() == "UK" { "GB" } else { code.as_str() };
+                Some(Match::new(code.start(), code.end(), country.to_string(), MatchCategory::Country)
+                    .with_confidence(80))
+            })
+            .take(1)
+            .collect()
+    }
+}
+
 /// Generic pattern matcher for simple regex->normalized value mappings
 pub struct SimplePatternMatcher;
 
@@ -598,13 +735,32 @@ pub struct PatternRegistry;
 impl PatternRegistry {
     /// Find all matches in the input string using all registered patterns
     pub fn find_all_matches(input: &str) -> Vec<Match> {
+        Self::find_all_matches_with(input, &ParserConfig::default())
+    }
+
+    /// Find all matches using the pattern sets enabled in `config`
+    pub fn find_all_matches_with(input: &str, config: &ParserConfig) -> Vec<Match> {
         let mut all_matches = Vec::new();
         
         // Season/Episode (highest priority for TV detection)
-        all_matches.extend(SeasonEpisodePattern::find_matches(input));
+        all_matches.extend(SeasonEpisodePattern::find_matches_with(input, config.bare_episode_numbers));
+
+        // Optional pattern sets
+        if config.anime {
+            all_matches.extend(AnimePattern::find_matches(input, config.year_range));
+        }
+        if config.date_based {
+            all_matches.extend(DatePattern::find_matches(input));
+        }
+        if config.sports {
+            all_matches.extend(SportsPattern::find_matches(input));
+        }
         
         // Year
-        all_matches.extend(YearPattern::find_matches(input));
+        all_matches.extend(YearPattern::find_matches_in_range(input, config.year_range));
+
+        // Country of remakes (US/UK versions)
+        all_matches.extend(CountryPattern::find_matches(input));
         
         // Quality markers
         all_matches.extend(SimplePatternMatcher::find_matches(
@@ -625,6 +781,11 @@ impl PatternRegistry {
             input, &LANGUAGE_PATTERNS, MatchCategory::Language
         ));
         
+        // Release flags
+        all_matches.extend(SimplePatternMatcher::find_matches(
+            input, &RELEASE_FLAG_PATTERNS, MatchCategory::ReleaseFlag
+        ));
+        
         // Noise tokens
         all_matches.extend(SimplePatternMatcher::find_noise_matches(input));
         
@@ -699,6 +860,33 @@ mod tests {
         assert_eq!(matches[0].value, "SPARKS");
     }
 
+    #[test]
+    fn test_release_flags() {
+        let matches = SimplePatternMatcher::find_matches(
+            "Movie.2020.REPACK2.1080p.BluRay.REMUX", &RELEASE_FLAG_PATTERNS, MatchCategory::ReleaseFlag
+        );
+        let values: Vec<&str> = matches.iter().map(|m| m.value.as_str()).collect();
+        assert_eq!(values, vec!["REPACK", "REMUX"]);
+    }
+
+    #[test]
+    fn test_optional_pattern_sets() {
+        let dates = DatePattern::find_matches("The.Daily.Show.2024.03.15.720p");
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].value, "2024-03-15");
+
+        let anime = AnimePattern::find_matches("[SubsPlease] Sousou no Frieren - 12v2 (1080p)", DEFAULT_YEAR_RANGE);
+        assert_eq!(anime[0].value, "SubsPlease");
+        assert_eq!(anime[1].raw, "0|12|0");
+        assert!(AnimePattern::find_matches("Title - 2019 (1080p)", DEFAULT_YEAR_RANGE).is_empty());
+
+        let sports = SportsPattern::find_matches("NFL.2023.Week.12.Bills.vs.Eagles");
+        assert_eq!(sports[0].raw, "0|12|0");
+
+        assert!(YearPattern::find_matches("Blade.Runner.2049.2017").iter().all(|m| m.value == "2017"));
+        assert_eq!(YearPattern::find_matches_in_range("Metropolis.1927", (1900, 2099)).len(), 1);
+    }
+
     #[test]
     fn test_season_range() {
         let matches = SeasonEpisodePattern::find_matches("Show.S01-S03.Complete");
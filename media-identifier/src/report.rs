@@ -0,0 +1,270 @@
+//! Tabular output and corpus comparison
+//!
+//! Formats parse results as CSV or TSV rows for scripts, and checks them
+//! against a file of expected values, so a corpus of filenames can be
+//! regression-tested from the command line.
+
+use crate::types::{MediaType, ParsedMedia};
+
+/// Columns written for each parsed filename, in order
+pub const COLUMNS: &[&str] = &[
+    "filename",
+    "media_type",
+    "title",
+    "year",
+    "season",
+    "episode",
+    "episode_end",
+    "episode_title",
+    "resolution",
+    "source",
+    "codec",
+    "audio",
+    "languages",
+    "release_group",
+    "release_flags",
+    "container",
+    "confidence",
+    "country",
+];
+
+/// Tabular output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+}
+
+impl TableFormat {
+    /// Parses a format name ("csv" or "tsv")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(TableFormat::Csv),
+            "tsv" => Some(TableFormat::Tsv),
+            _ => None,
+        }
+    }
+
+    pub fn separator(&self) -> char {
+        match self {
+            TableFormat::Csv => ',',
+            TableFormat::Tsv => '\t',
+        }
+    }
+
+    /// Joins fields into one line, quoting (CSV) or flattening (TSV) values
+    /// that contain the separator, quotes or line breaks
+    pub fn join<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        let escaped: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                let field = field.as_ref();
+                match self {
+                    TableFormat::Csv if field.contains([',', '"', '\n', '\r']) => {
+                        format!("\"{}\"", field.replace('"', "\"\""))
+                    }
+                    TableFormat::Tsv => field.replace(['\t', '\n', '\r'], " "),
+                    _ => field.to_string(),
+                }
+            })
+            .collect();
+        escaped.join(&self.separator().to_string())
+    }
+
+    /// Splits one line into fields, undoing [`TableFormat::join`]
+    pub fn split(&self, line: &str) -> Vec<String> {
+        if *self == TableFormat::Tsv {
+            return line.split('\t').map(str::to_string).collect();
+        }
+
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        fields.push(field);
+        fields
+    }
+
+    /// The header line
+    pub fn header(&self) -> String {
+        self.join(COLUMNS)
+    }
+
+    /// One line for a parsed filename, with the fields of [`COLUMNS`]
+    pub fn row(&self, filename: &str, parsed: &ParsedMedia) -> String {
+        let fields: Vec<String> = COLUMNS
+            .iter()
+            .map(|column| match *column {
+                "filename" => filename.to_string(),
+                column => field(parsed, column).unwrap_or_default(),
+            })
+            .collect();
+        self.join(&fields)
+    }
+}
+
+/// Value of a column for a parse result (None when empty or unknown)
+///
+/// Lists are joined with `|`, so they stay one field in either format.
+pub fn field(parsed: &ParsedMedia, column: &str) -> Option<String> {
+    let list = |values: &[String]| (!values.is_empty()).then(|| values.join("|"));
+    match column {
+        "media_type" => Some(
+            match parsed.media_type {
+                MediaType::Movie => "movie",
+                MediaType::Episode => "episode",
+                MediaType::Unknown => "unknown",
+            }
+            .to_string(),
+        ),
+        "title" => parsed.title.clone(),
+        "year" => parsed.year.map(|y| y.to_string()),
+        "season" => parsed.episode_info.season.map(|s| s.to_string()),
+        "episode" => parsed.episode_info.episode.map(|e| e.to_string()),
+        "episode_end" => parsed.episode_info.episode_end.map(|e| e.to_string()),
+        "episode_title" => parsed.episode_info.episode_title.clone(),
+        "resolution" => parsed.quality.resolution.clone(),
+        "source" => parsed.quality.source.clone(),
+        "codec" => parsed.quality.codec.clone(),
+        "audio" => parsed.quality.audio.clone(),
+        "languages" => list(&parsed.languages),
+        "release_group" => parsed.release_group.clone(),
+        "release_flags" => list(&parsed.release_flags),
+        "container" => parsed.container.clone(),
+        "confidence" => Some(parsed.confidence.to_string()),
+        "country" => parsed.country.clone(),
+        _ => None,
+    }
+}
+
+/// Expected values for one filename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    pub filename: String,
+    /// Column and expected value; an empty value expects an empty field
+    pub values: Vec<(String, String)>,
+}
+
+/// A field that differs from its expected value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub column: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Reads expected values from CSV or TSV text
+///
+/// The first line names the columns; it must include `filename`, and any
+/// other column of [`COLUMNS`] is checked. The separator is a tab when the
+/// header has one, a comma otherwise. Blank lines and lines starting with
+/// `#` are skipped.
+pub fn read_expectations(text: &str) -> Result<Vec<Expectation>, String> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+    let (_, header) = lines.next().ok_or("Expectation file is empty")?;
+    let format = if header.contains('\t') { TableFormat::Tsv } else { TableFormat::Csv };
+
+    let columns: Vec<String> = format.split(header).iter().map(|c| c.trim().to_string()).collect();
+    let filename_column = columns
+        .iter()
+        .position(|c| c == "filename")
+        .ok_or("Expectation file has no filename column")?;
+    if let Some(unknown) = columns.iter().find(|c| !COLUMNS.contains(&c.as_str())) {
+        return Err(format!("Unknown column: {}", unknown));
+    }
+
+    lines
+        .map(|(number, line)| {
+            let fields = format.split(line);
+            if fields.len() != columns.len() {
+                return Err(format!(
+                    "Line {}: {} fields, expected {}",
+                    number + 1,
+                    fields.len(),
+                    columns.len()
+                ));
+            }
+            Ok(Expectation {
+                filename: fields[filename_column].clone(),
+                values: columns
+                    .iter()
+                    .zip(fields)
+                    .filter(|(column, _)| column.as_str() != "filename")
+                    .map(|(column, value)| (column.clone(), value))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Fields of a parse result that differ from the expectation
+///
+/// Text is compared case-insensitively, as titles are often written in
+/// different case than the parser produces.
+pub fn compare(parsed: &ParsedMedia, expectation: &Expectation) -> Vec<Mismatch> {
+    expectation
+        .values
+        .iter()
+        .filter_map(|(column, expected)| {
+            let actual = field(parsed, column).unwrap_or_default();
+            (!actual.eq_ignore_ascii_case(expected.trim())).then(|| Mismatch {
+                column: column.clone(),
+                expected: expected.clone(),
+                actual,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_rows_round_trip() {
+        let parsed = parse("Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv");
+        for format in [TableFormat::Csv, TableFormat::Tsv] {
+            let fields = format.split(&format.row("Dark, Matter.mkv", &parsed));
+            assert_eq!(fields.len(), COLUMNS.len());
+            assert_eq!(fields[0], "Dark, Matter.mkv");
+            assert_eq!(fields[2], "Dark Matter");
+            assert_eq!(fields[4], "1");
+        }
+        assert_eq!(TableFormat::Csv.join(&["a\"b", "c"]), "\"a\"\"b\",c");
+    }
+
+    #[test]
+    fn test_compare_expectations() {
+        let expectations = read_expectations(
+            "# corpus\nfilename,title,season,release_group,year\n\
+             Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv,dark matter,1,KILLERS,\n\
+             Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv,Dark Matter,2,KILLERS,2015\n",
+        )
+        .unwrap();
+        assert_eq!(expectations.len(), 2);
+
+        let parsed = parse(&expectations[0].filename);
+        assert!(compare(&parsed, &expectations[0]).is_empty());
+
+        let columns: Vec<String> = compare(&parsed, &expectations[1]).into_iter().map(|m| m.column).collect();
+        assert_eq!(columns, vec!["season", "year"]);
+
+        assert!(read_expectations("title\nFoo\n").is_err());
+        assert!(read_expectations("filename,rating\nfoo.mkv,5\n").is_err());
+    }
+}
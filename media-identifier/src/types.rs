@@ -23,6 +23,8 @@ pub enum MatchCategory {
     Year,
     Season,
     Episode,
+    Date,       // Air date of daily shows and sports events
+    Country,    // US/UK version of a show
     EpisodeTitle,
     Quality,
     Source,
@@ -30,8 +32,9 @@ pub enum MatchCategory {
     Audio,
     Language,
     ReleaseGroup,
+    ReleaseFlag, // PROPER, REPACK, INTERNAL, REMUX
     Other,
-    Noise,      // Tokens to ignore (REMASTERED, PROPER, etc.)
+    Noise,      // Tokens to ignore (REMASTERED, LIMITED, etc.)
     Container,  // File extension
 }
 
@@ -41,7 +44,9 @@ impl MatchCategory {
         match self {
             MatchCategory::Season => 100,
             MatchCategory::Episode => 100,
+            MatchCategory::Date => 100, // Wins over the year inside it
             MatchCategory::Year => 90,
+            MatchCategory::Country => 85,
             MatchCategory::Quality => 80,
             MatchCategory::Source => 75,
             MatchCategory::Codec => 70,
@@ -49,6 +54,7 @@ impl MatchCategory {
             MatchCategory::Language => 60,
             MatchCategory::ReleaseGroup => 95, // High because it's usually at the end
             MatchCategory::Container => 100,
+            MatchCategory::ReleaseFlag => 50,
             MatchCategory::Noise => 50,
             MatchCategory::EpisodeTitle => 40,
             MatchCategory::Title => 30,
@@ -126,6 +132,7 @@ pub struct EpisodeInfo {
     pub episode_end: Option<u16>,    // For multi-episode (E01E02 or E01-E02)
     pub episode_title: Option<String>,
     pub absolute_episode: Option<u16>, // For anime-style numbering
+    pub air_date: Option<String>,      // YYYY-MM-DD, for date-based shows
 }
 
 /// Quality information
@@ -137,6 +144,15 @@ pub struct QualityInfo {
     pub audio: Option<String>,       // DTS, AC3, DD+5.1, etc.
 }
 
+/// Confidence of the individual fields of a parse (0-100, 0 = not found)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldConfidence {
+    pub title: u8,
+    /// Season/episode numbering (or the air date of daily shows)
+    pub season_episode: u8,
+    pub year: u8,
+}
+
 /// The final parsed result
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParsedMedia {
@@ -151,6 +167,11 @@ pub struct ParsedMedia {
     
     /// Release year
     pub year: Option<u16>,
+
+    /// Country of a remake or local version, as an ISO 3166 code (e.g.
+    /// "US" for "The Office US", "GB" for "Shameless UK")
+    #[serde(default)]
+    pub country: Option<String>,
     
     /// Episode information (for TV shows)
     #[serde(flatten)]
@@ -166,17 +187,42 @@ pub struct ParsedMedia {
     /// Release group (e.g., "FULCRUM", "MaMMuT")
     pub release_group: Option<String>,
     
+    /// Release flags (e.g., ["PROPER", "REMUX"])
+    pub release_flags: Vec<String>,
+    
     /// File container/extension
     pub container: Option<String>,
     
     /// Confidence score for the overall parse (0-100)
     pub confidence: u8,
+
+    /// Confidence of the title, season/episode and year separately
+    #[serde(default)]
+    pub field_confidence: FieldConfidence,
     
     /// All matches found (for debugging)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub matches: Vec<MatchInfo>,
 }
 
+impl ParsedMedia {
+    /// Number of fix-up releases this file represents (PROPER/REPACK)
+    ///
+    /// A PROPER or REPACK replaces an earlier, flawed release of the same
+    /// content, so a higher count means a newer version.
+    pub fn proper_count(&self) -> usize {
+        self.release_flags
+            .iter()
+            .filter(|f| f.as_str() == "PROPER" || f.as_str() == "REPACK")
+            .count()
+    }
+
+    /// Check if this release should replace `other` (same content, newer fix-up release)
+    pub fn supersedes(&self, other: &ParsedMedia) -> bool {
+        self.proper_count() > other.proper_count()
+    }
+}
+
 /// Simplified match info for serialization
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MatchInfo {
@@ -0,0 +1,25 @@
+//! WebAssembly bindings
+//!
+//! Built with the `wasm` feature, e.g.
+//! `wasm-pack build --target web -- --features wasm` for browsers or
+//! `--target nodejs` for Node. `parse` returns the result as a JSON string:
+//!
+//! ```js
+//! import init, { parse } from "media-identifier";
+//! await init();
+//! const media = JSON.parse(parse("Dark.Matter.S01E05.720p.mkv"));
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+/// Parses a filename and returns the result as JSON
+#[wasm_bindgen(js_name = parse)]
+pub fn parse(input: &str) -> String {
+    crate::parse_json(input)
+}
+
+/// Library version
+#[wasm_bindgen(js_name = version)]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
@@ -1,6 +1,6 @@
 //! Integration tests using real-world media filenames
 
-use media_identifier::{parse, MediaType};
+use media_identifier::{parse, parse_with_context, MediaType};
 
 // ============================================================================
 // MOVIES
@@ -99,6 +99,16 @@ fn test_dark_matter_s02() {
     assert_eq!(r.episode_info.episode, Some(8));
     assert_eq!(r.quality.resolution, Some("720p".to_string()));
     assert_eq!(r.quality.source, Some("HDTV".to_string()));
+    assert_eq!(r.release_flags, vec!["PROPER".to_string()]);
+}
+
+#[test]
+fn test_remux_internal_flags_not_in_title() {
+    let r = parse("The.Matrix.1999.INTERNAL.2160p.UHD.BluRay.REMUX.HEVC.TrueHD.Atmos-FGT.mkv");
+    assert_eq!(r.title, Some("The Matrix".to_string()));
+    assert_eq!(r.year, Some(1999));
+    assert_eq!(r.release_flags, vec!["INTERNAL".to_string(), "REMUX".to_string()]);
+    assert_eq!(r.proper_count(), 0);
 }
 
 #[test]
@@ -612,3 +622,136 @@ fn test_batch_all_samples() {
         println!("✓ {} -> {:?}: {:?}", sample, r.media_type, r.title);
     }
 }
+
+// ============================================================================
+// FORMATTING
+// ============================================================================
+
+#[test]
+fn test_format_round_trip() {
+    use media_identifier::formatter::{EPISODE_TEMPLATE, MOVIE_TEMPLATE, SCENE_TEMPLATE};
+    use media_identifier::FilenameFormatter;
+
+    let samples = [
+        "Ballerina.2025.Hybrid.BDRip.x264.HUN-FULCRUM.mkv",
+        "Dark.Matter.S01E05.720p.HDTV.x264-KILLERS.mkv",
+        "Stargate.Atlantis.S01E01-E02.Rising.BDRip.x264.Hun.Eng-MaMMuT.mkv",
+        "The.Matrix.1999.1080p.BluRay.x264.mkv",
+    ];
+    let scene = FilenameFormatter::new(SCENE_TEMPLATE).unwrap().with_word_separator('.');
+
+    for sample in samples {
+        let original = parse(sample);
+        let template = match original.media_type {
+            MediaType::Episode => EPISODE_TEMPLATE,
+            _ => MOVIE_TEMPLATE,
+        };
+        for formatter in [&FilenameFormatter::new(template).unwrap(), &scene] {
+            let renamed = formatter.format(&original);
+            let r = parse(&renamed);
+            assert_eq!(r.title, original.title, "{} -> {}", sample, renamed);
+            assert_eq!(r.episode_info.season, original.episode_info.season, "{} -> {}", sample, renamed);
+            assert_eq!(r.episode_info.episode, original.episode_info.episode, "{} -> {}", sample, renamed);
+            assert_eq!(r.episode_info.episode_end, original.episode_info.episode_end, "{} -> {}", sample, renamed);
+            assert_eq!(r.quality.resolution, original.quality.resolution, "{} -> {}", sample, renamed);
+            assert_eq!(r.container, original.container, "{} -> {}", sample, renamed);
+            if original.media_type == MediaType::Movie {
+                assert_eq!(r.year, original.year, "{} -> {}", sample, renamed);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// FOLDER CONTEXT
+// ============================================================================
+
+#[test]
+fn test_parse_with_context() {
+    // Generic name in a torrent folder
+    let r = parse_with_context("/downloads/Ballerina.2025.Hybrid.BDRip.x264.HUN-FULCRUM/movie.mkv");
+    assert_eq!(r.media_type, MediaType::Movie);
+    assert_eq!(r.title, Some("Ballerina".to_string()));
+    assert_eq!(r.year, Some(2025));
+    assert_eq!(r.release_group, Some("FULCRUM".to_string()));
+    assert_eq!(r.container, Some("mkv".to_string()));
+
+    // Abbreviated scene filename
+    let r = parse_with_context("/media/Movies/Back to the Future III (1990)/walle-bttf.iii.720.mkv");
+    assert!(r.title.unwrap().contains("Back to the Future"));
+    assert_eq!(r.year, Some(1990));
+
+    // A descriptive filename wins over the folder
+    let r = parse_with_context("/media/Movies/Some Collection/Wonka.2023.720p.BluRay.mkv");
+    assert_eq!(r.title, Some("Wonka".to_string()));
+    assert_eq!(r.year, Some(2023));
+
+    // Library roots are never titles
+    let r = parse_with_context("/media/Movies/movie.mkv");
+    assert_ne!(r.title.as_deref().map(str::to_lowercase), Some("movies".to_string()));
+
+    // Episode titles give way to the series folder; numbering stays
+    let r = parse_with_context("/tv/Star.Trek.Enterprise.S01.2001.BluRay.HUN.ENG-FOX/1x03 Megszoksz vagy megszoksz.mkv");
+    assert_eq!(r.title, Some("Star Trek Enterprise".to_string()));
+    assert_eq!(r.episode_info.season, Some(1));
+    assert_eq!(r.episode_info.episode, Some(3));
+}
+
+#[test]
+fn test_country_tokens() {
+    let r = parse("The.Office.US.S02E01.720p.WEB-DL.mkv");
+    assert_eq!(r.title, Some("The Office".to_string()));
+    assert_eq!(r.country, Some("US".to_string()));
+    assert_eq!(r.episode_info.season, Some(2));
+
+    let r = parse("Shameless (UK) 2004 S01E01.avi");
+    assert_eq!(r.title, Some("Shameless".to_string()));
+    assert_eq!(r.country, Some("GB".to_string()));
+    assert_eq!(r.year, Some(2004));
+
+    // The series folder names the version
+    let r = parse_with_context("/tv/Shameless US/Season 3/Shameless.S03E04.mkv");
+    assert_eq!(r.title, Some("Shameless".to_string()));
+    assert_eq!(r.country, Some("US".to_string()));
+
+    // Lower-case words and leading codes are titles
+    assert_eq!(parse("Us.2019.1080p.BluRay.mkv").title, Some("Us".to_string()));
+    assert_eq!(parse("US.Marshals.1998.720p.mkv").country, None);
+
+    // Renamed files keep the version
+    use media_identifier::FilenameFormatter;
+    let r = parse("The.Office.US.S02E01.720p.WEB-DL.mkv");
+    let renamed = FilenameFormatter::for_media_type(MediaType::Episode).format(&r);
+    assert_eq!(renamed, "The Office US - S02E01 [720p].mkv");
+    assert_eq!(parse(&renamed).country, Some("US".to_string()));
+}
+
+#[test]
+fn test_numeric_titles() {
+    // (filename, title, year)
+    let cases = [
+        ("1917 (2019).mkv", "1917", Some(2019)),
+        ("2012.2009.1080p.BluRay.x264.mkv", "2012", Some(2009)),
+        ("1984.1984.720p.mkv", "1984", Some(1984)),
+        ("2046 (2004).mkv", "2046", Some(2004)),
+        ("2001.A.Space.Odyssey.1968.1080p.mkv", "2001 A Space Odyssey", Some(1968)),
+        ("Airport.1975.1974.DVDRip.mkv", "Airport 1975", Some(1974)),
+        ("Blade.Runner.2049.2017.2160p.mkv", "Blade Runner 2049", Some(2017)),
+        ("Room.237.2012.720p.mkv", "Room 237", Some(2012)),
+        ("The.Number.23.2007.mkv", "The Number 23", Some(2007)),
+        ("2012.1080p.BluRay.x264-GRP.mkv", "2012", None),
+    ];
+    for (filename, title, year) in cases {
+        let r = parse(filename);
+        assert_eq!(r.title.as_deref(), Some(title), "{}", filename);
+        assert_eq!(r.year, year, "{}", filename);
+        assert_ne!(r.media_type, MediaType::Episode, "{}", filename);
+    }
+
+    // Numbered episodes and years before an episode marker are unchanged
+    let r = parse("The.Simpsons.2401.720p.mkv");
+    assert_eq!(r.episode_info.season, Some(24));
+    let r = parse("Doctor.Who.2005.S01E01.mkv");
+    assert_eq!(r.title, Some("Doctor Who".to_string()));
+    assert_eq!(r.year, Some(2005));
+}
@@ -0,0 +1,412 @@
+-- Initial schema
+--
+-- Tables and indexes of the schema before versioned migrations. Every
+-- statement is idempotent, so databases created by earlier releases take
+-- this migration as their baseline.
+
+-- 1. Create Media Table
+CREATE TABLE IF NOT EXISTS media (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    file_path TEXT NOT NULL UNIQUE,
+    media_type TEXT DEFAULT 'movie',
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    backdrop_url TEXT,
+    trailer_url TEXT,
+    duration_seconds INTEGER,
+    release_date TEXT,
+    resolution TEXT,
+    genres TEXT,
+    series_id INTEGER REFERENCES series(id),
+    season INTEGER,
+    episode INTEGER,
+    episode_end INTEGER,
+    tmdb_id INTEGER,
+    original_title TEXT,
+    rating REAL,
+    confidence_score REAL DEFAULT 0.0,
+    verification_status TEXT DEFAULT 'unverified',
+    identification_strategy TEXT,
+    error_notes TEXT,
+    alternative_matches TEXT,
+    content_rating TEXT,
+    content_warnings TEXT,
+    current_position INTEGER DEFAULT 0,
+    is_watched INTEGER DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 2. Create Watch Progress Table
+CREATE TABLE IF NOT EXISTS watch_progress (
+    media_id INTEGER PRIMARY KEY,
+    current_position_seconds INTEGER NOT NULL DEFAULT 0,
+    is_watched BOOLEAN NOT NULL DEFAULT 0,
+    last_updated DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+-- 3. Create Series Table
+CREATE TABLE IF NOT EXISTS series (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tmdb_id INTEGER,
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT
+);
+
+-- 4. Create Collections Table
+CREATE TABLE IF NOT EXISTS collections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    poster_url TEXT,
+    backdrop_url TEXT,
+    tmdb_collection_id INTEGER,
+    sort_mode TEXT DEFAULT 'timeline',
+    collection_type TEXT DEFAULT 'auto',
+    total_items INTEGER DEFAULT 0,
+    available_items INTEGER DEFAULT 0
+);
+
+-- 5. Create Collection Items Table
+CREATE TABLE IF NOT EXISTS collection_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    collection_id INTEGER NOT NULL,
+    media_id INTEGER,
+    tmdb_id INTEGER NOT NULL,
+    media_type TEXT DEFAULT 'movie',
+    title TEXT NOT NULL,
+    overview TEXT,
+    poster_url TEXT,
+    release_date TEXT,
+    timeline_order INTEGER NOT NULL,
+    release_order INTEGER NOT NULL,
+    timeline_year INTEGER,
+    timeline_notes TEXT,
+    season_number INTEGER,
+    episode_number INTEGER,
+    is_available INTEGER DEFAULT 0,
+    FOREIGN KEY(collection_id) REFERENCES collections(id) ON DELETE CASCADE,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE SET NULL
+);
+
+-- 6. Create Verification History Table
+CREATE TABLE IF NOT EXISTS verification_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    content_id TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    original_match_id INTEGER NOT NULL,
+    corrected_match_id INTEGER,
+    confidence_before REAL NOT NULL,
+    confidence_after REAL NOT NULL,
+    verified_by TEXT NOT NULL,
+    verification_date DATETIME DEFAULT CURRENT_TIMESTAMP,
+    notes TEXT
+);
+
+-- 7. Create TMDB Cache Table
+CREATE TABLE IF NOT EXISTS tmdb_cache (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    external_id TEXT NOT NULL,
+    external_type TEXT NOT NULL,
+    resolved_tmdb_id INTEGER NOT NULL,
+    resolved_type TEXT NOT NULL,
+    cached_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    ttl DATETIME NOT NULL,
+    UNIQUE(external_id, external_type)
+);
+
+-- 8. Create General Cache Table (for CacheRepository)
+CREATE TABLE IF NOT EXISTS cache (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    expires_at INTEGER NOT NULL
+);
+
+-- Create index for cache expiration cleanup
+CREATE INDEX IF NOT EXISTS idx_cache_expires ON cache(expires_at);
+
+-- 8.5. Create Events Table (for event sourcing)
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_type TEXT NOT NULL,
+    aggregate_id TEXT,
+    aggregate_type TEXT,
+    payload TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    correlation_id TEXT,
+    causation_id TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Create indexes for event queries
+CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type);
+CREATE INDEX IF NOT EXISTS idx_events_aggregate ON events(aggregate_type, aggregate_id);
+CREATE INDEX IF NOT EXISTS idx_events_created ON events(created_at);
+
+-- 9. Create Media Credits Table (cast/crew cache from TMDB)
+CREATE TABLE IF NOT EXISTS media_credits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    media_id INTEGER NOT NULL,
+    person_id INTEGER NOT NULL,
+    person_name TEXT NOT NULL,
+    role TEXT NOT NULL,
+    character_name TEXT,
+    department TEXT,
+    profile_url TEXT,
+    credit_order INTEGER DEFAULT 0,
+    credit_type TEXT NOT NULL,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+-- Create index for credits lookup by media_id
+CREATE INDEX IF NOT EXISTS idx_media_credits_media_id ON media_credits(media_id);
+
+-- 10. Create Generated Subtitles Table (for tracking auto-generated subtitles)
+CREATE TABLE IF NOT EXISTS generated_subtitles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    media_id INTEGER NOT NULL,
+    audio_track_index INTEGER NOT NULL,
+    audio_fingerprint TEXT NOT NULL,
+    source_language TEXT,
+    target_language TEXT,
+    srt_filename TEXT NOT NULL,
+    duration_seconds REAL,
+    was_translated INTEGER DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE,
+    UNIQUE(media_id, audio_track_index, target_language)
+);
+
+-- Create index for generated subtitles lookup
+CREATE INDEX IF NOT EXISTS idx_generated_subtitles_media_id ON generated_subtitles(media_id);
+
+-- Create index for fingerprint lookup (for finding existing subtitles by audio track)
+CREATE INDEX IF NOT EXISTS idx_generated_subtitles_fingerprint ON generated_subtitles(audio_fingerprint);
+
+-- 11. Create Seasons Table
+CREATE TABLE IF NOT EXISTS seasons (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    series_id INTEGER NOT NULL,
+    season_number INTEGER NOT NULL,
+    tmdb_id INTEGER,
+    name TEXT,
+    overview TEXT,
+    poster_url TEXT,
+    air_date TEXT,
+    episode_count INTEGER,
+    rating REAL,
+    FOREIGN KEY(series_id) REFERENCES series(id) ON DELETE CASCADE,
+    UNIQUE(series_id, season_number)
+);
+
+-- 12. Create Media Localizations Table (per-language title/overview variants)
+CREATE TABLE IF NOT EXISTS media_localizations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    media_id INTEGER NOT NULL,
+    language TEXT NOT NULL,
+    title TEXT NOT NULL,
+    overview TEXT,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE,
+    UNIQUE(media_id, language)
+);
+
+-- 13. Create People Table (cached TMDB person details)
+CREATE TABLE IF NOT EXISTS people (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    biography TEXT,
+    birthday TEXT,
+    deathday TEXT,
+    place_of_birth TEXT,
+    profile_url TEXT,
+    known_for_department TEXT,
+    imdb_id TEXT,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 14. Create Extra Artwork Table (fanart.tv logos, clearart, disc art)
+CREATE TABLE IF NOT EXISTS extra_artwork (
+    owner_type TEXT NOT NULL,
+    owner_id INTEGER NOT NULL,
+    logo_url TEXT,
+    clearart_url TEXT,
+    disc_url TEXT,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(owner_type, owner_id)
+);
+
+-- 15. Create Device Quality Preferences Table (remembered quality overrides)
+CREATE TABLE IF NOT EXISTS device_quality_preferences (
+    device_id TEXT PRIMARY KEY,
+    quality TEXT,
+    max_bitrate_kbps INTEGER,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 16. Create Subtitle Offsets Table (player subtitle delay per user/media/track)
+CREATE TABLE IF NOT EXISTS subtitle_offsets (
+    user_id TEXT NOT NULL,
+    media_id INTEGER NOT NULL,
+    track_index INTEGER NOT NULL,
+    offset_ms INTEGER NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(user_id, media_id, track_index)
+);
+
+-- 17. Create User Audio Preferences Table (last picked audio language per user)
+CREATE TABLE IF NOT EXISTS user_audio_preferences (
+    user_id TEXT PRIMARY KEY,
+    language TEXT NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 18. Create Job Runs Table (throughput history for job completion estimates)
+CREATE TABLE IF NOT EXISTS job_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    media_seconds REAL NOT NULL,
+    elapsed_seconds REAL NOT NULL,
+    completed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_job_runs_kind ON job_runs(kind, id);
+
+-- 19. Create Media Loudness Table (first loudnorm pass per audio track)
+CREATE TABLE IF NOT EXISTS media_loudness (
+    media_id INTEGER NOT NULL,
+    audio_track INTEGER NOT NULL,
+    input_i REAL NOT NULL,
+    input_tp REAL NOT NULL,
+    input_lra REAL NOT NULL,
+    input_thresh REAL NOT NULL,
+    target_offset REAL NOT NULL,
+    measured_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(media_id, audio_track),
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+-- 20. Create User Subtitle Preferences Table (languages in order of preference)
+CREATE TABLE IF NOT EXISTS user_subtitle_preferences (
+    user_id TEXT PRIMARY KEY,
+    languages TEXT NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 21. Create Media Bookmarks Table (scene bookmarks with notes per user)
+CREATE TABLE IF NOT EXISTS media_bookmarks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    media_id INTEGER NOT NULL,
+    position_seconds REAL NOT NULL,
+    note TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_media_bookmarks_user_media ON media_bookmarks(user_id, media_id);
+
+-- 22. Create Media Crop Table (black bars found by cropdetect)
+CREATE TABLE IF NOT EXISTS media_crop (
+    media_id INTEGER PRIMARY KEY,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    x INTEGER NOT NULL,
+    y INTEGER NOT NULL,
+    source_width INTEGER NOT NULL,
+    source_height INTEGER NOT NULL,
+    detected_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 23. Create Device Keys Table (per-device API keys, stored hashed)
+CREATE TABLE IF NOT EXISTS device_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    last_used_at TEXT
+);
+
+-- 24. Create User Accessibility Preferences Table (audio description / SDH auto-selection)
+CREATE TABLE IF NOT EXISTS user_accessibility_preferences (
+    user_id TEXT PRIMARY KEY,
+    audio_description BOOLEAN NOT NULL DEFAULT 0,
+    hearing_impaired_subtitles BOOLEAN NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 25. Create User Parental Controls Table (rating limits, tag blocklists, override PIN)
+CREATE TABLE IF NOT EXISTS user_parental_controls (
+    user_id TEXT PRIMARY KEY,
+    max_age INTEGER,
+    block_unrated BOOLEAN NOT NULL DEFAULT 0,
+    blocked_tags TEXT NOT NULL DEFAULT '[]',
+    pin_hash TEXT,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 26. Create User Profiles Table (per-profile languages, avatar and kid mode)
+CREATE TABLE IF NOT EXISTS user_profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    avatar TEXT,
+    audio_language TEXT,
+    subtitle_language TEXT,
+    ui_language TEXT,
+    kid_mode BOOLEAN NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_user_profiles_user ON user_profiles(user_id);
+
+-- 27. Create Audit Log Table (security-relevant actions, pruned by retention)
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action TEXT NOT NULL,
+    actor TEXT,
+    target TEXT,
+    details TEXT,
+    ip TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
+
+-- 28. Create Playlists Tables (ordered user queues of movies and episodes)
+CREATE TABLE IF NOT EXISTS playlists (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS playlist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    playlist_id INTEGER NOT NULL,
+    media_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    added_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_playlists_user ON playlists(user_id);
+CREATE INDEX IF NOT EXISTS idx_playlist_items_playlist ON playlist_items(playlist_id, position);
+
+-- 29. Create Watch History Table (one row per finished stream session)
+CREATE TABLE IF NOT EXISTS watch_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    media_id INTEGER NOT NULL,
+    client TEXT,
+    started_at TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    bytes_sent INTEGER NOT NULL DEFAULT 0,
+    completed BOOLEAN NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_watch_history_user ON watch_history(user_id, started_at);
+CREATE INDEX IF NOT EXISTS idx_watch_history_media ON watch_history(media_id, started_at);
+
+-- Create index for credits lookup by person (filmography within the library)
+CREATE INDEX IF NOT EXISTS idx_media_credits_person_id ON media_credits(person_id);
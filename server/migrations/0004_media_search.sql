@@ -0,0 +1,60 @@
+-- Full-text search
+--
+-- FTS5 index over media titles (movie and episode titles), original
+-- titles, overviews and cast names, keyed by media id. Triggers keep it in
+-- sync with the media and media_credits tables.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS media_search USING fts5(
+    title,
+    original_title,
+    overview,
+    cast_names,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Indexed terms, for typo tolerant lookups
+CREATE VIRTUAL TABLE IF NOT EXISTS media_search_vocab USING fts5vocab(media_search, row);
+
+CREATE TRIGGER IF NOT EXISTS media_search_insert AFTER INSERT ON media BEGIN
+    INSERT INTO media_search (rowid, title, original_title, overview, cast_names)
+    VALUES (new.id, new.title, new.original_title, new.overview, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_search_update AFTER UPDATE OF title, original_title, overview ON media BEGIN
+    UPDATE media_search
+    SET title = new.title, original_title = new.original_title, overview = new.overview
+    WHERE rowid = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_search_delete AFTER DELETE ON media BEGIN
+    DELETE FROM media_search WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_search_cast_insert AFTER INSERT ON media_credits
+WHEN new.credit_type = 'cast' BEGIN
+    UPDATE media_search
+    SET cast_names = (
+        SELECT group_concat(person_name, ' ') FROM media_credits
+        WHERE media_id = new.media_id AND credit_type = 'cast'
+    )
+    WHERE rowid = new.media_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS media_search_cast_delete AFTER DELETE ON media_credits
+WHEN old.credit_type = 'cast' BEGIN
+    UPDATE media_search
+    SET cast_names = coalesce((
+        SELECT group_concat(person_name, ' ') FROM media_credits
+        WHERE media_id = old.media_id AND credit_type = 'cast'
+    ), '')
+    WHERE rowid = old.media_id;
+END;
+
+-- Index the existing library
+INSERT INTO media_search (rowid, title, original_title, overview, cast_names)
+SELECT id, title, original_title, overview, coalesce((
+    SELECT group_concat(person_name, ' ') FROM media_credits
+    WHERE media_credits.media_id = media.id AND credit_type = 'cast'
+), '')
+FROM media
+WHERE id NOT IN (SELECT rowid FROM media_search);
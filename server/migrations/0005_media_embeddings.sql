@@ -0,0 +1,14 @@
+-- Semantic search
+--
+-- Embedding vectors of media overviews (little-endian f32), with the model
+-- that made them and a hash of the embedded text so unchanged overviews are
+-- not embedded again.
+
+CREATE TABLE IF NOT EXISTS media_embeddings (
+    media_id INTEGER PRIMARY KEY,
+    model TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    embedded_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
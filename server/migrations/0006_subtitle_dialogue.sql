@@ -0,0 +1,44 @@
+-- Dialogue search
+--
+-- Cues of the subtitle files of each media item, with an FTS5 index over
+-- their text. Every indexed file is a source, recorded with a signature
+-- (size and modification time) so unchanged files are not read again.
+-- Removing a source or its media removes its cues.
+
+CREATE TABLE IF NOT EXISTS subtitle_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    media_id INTEGER NOT NULL,
+    path TEXT NOT NULL UNIQUE,
+    language TEXT,
+    signature TEXT NOT NULL,
+    indexed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_subtitle_sources_media ON subtitle_sources(media_id);
+
+CREATE TABLE IF NOT EXISTS subtitle_cues (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_id INTEGER NOT NULL,
+    start_seconds REAL NOT NULL,
+    end_seconds REAL NOT NULL,
+    text TEXT NOT NULL,
+    FOREIGN KEY(source_id) REFERENCES subtitle_sources(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_subtitle_cues_source ON subtitle_cues(source_id);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS subtitle_dialogue USING fts5(
+    text,
+    content = 'subtitle_cues',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS subtitle_cues_insert AFTER INSERT ON subtitle_cues BEGIN
+    INSERT INTO subtitle_dialogue (rowid, text) VALUES (new.id, new.text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS subtitle_cues_delete AFTER DELETE ON subtitle_cues BEGIN
+    INSERT INTO subtitle_dialogue (subtitle_dialogue, rowid, text) VALUES ('delete', old.id, old.text);
+END;
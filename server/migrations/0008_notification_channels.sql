@@ -0,0 +1,16 @@
+-- Notification channels
+--
+-- Discord, Telegram, Gotify and email destinations of notifications for
+-- people. `config` is a JSON object whose fields depend on `kind`;
+-- `event_types` limits the events notified (comma-separated, empty = all).
+
+CREATE TABLE IF NOT EXISTS notification_channels (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    config TEXT NOT NULL DEFAULT '{}',
+    event_types TEXT NOT NULL DEFAULT '',
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Runtime settings
+--
+-- Settings changed through the admin API, kept across restarts. They take
+-- precedence over the configuration file and environment; `value` is JSON.
+
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Recorded TMDB responses
+--
+-- Every answered TMDB request, keyed by endpoint and language, so that an
+-- offline server (TMDB_OFFLINE) can identify media without reaching TMDB.
+-- `export-metadata` / `import-metadata` move them between databases.
+
+CREATE TABLE IF NOT EXISTS tmdb_responses (
+    request TEXT PRIMARY KEY,
+    status INTEGER NOT NULL,
+    body BLOB NOT NULL,
+    fetched_at TEXT NOT NULL
+);
@@ -0,0 +1,19 @@
+-- Background jobs
+--
+-- Subtitle generation, translation and batch jobs, so they outlive the
+-- process. `status` is the job's last known status as JSON; `payload` is
+-- the request a job is resumed from after a restart (NULL = not resumable).
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id TEXT PRIMARY KEY,
+    job_type TEXT NOT NULL,
+    state TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    payload TEXT,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_state ON jobs(state, priority DESC, created_at);
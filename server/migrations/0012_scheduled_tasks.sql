@@ -0,0 +1,15 @@
+-- Scheduled tasks
+--
+-- Schedule and enabled changes made through the API (NULL = configured
+-- value) and the last run of each task, by task name.
+
+CREATE TABLE IF NOT EXISTS scheduled_tasks (
+    name TEXT PRIMARY KEY,
+    schedule TEXT,
+    enabled INTEGER,
+    last_started_at TEXT,
+    last_duration_ms INTEGER,
+    last_success INTEGER,
+    last_message TEXT,
+    updated_at TEXT NOT NULL
+);
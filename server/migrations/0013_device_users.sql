@@ -0,0 +1,6 @@
+-- Users of device keys
+--
+-- Each device streams, and is checked against parental controls, as the
+-- user its key was issued for. Keys issued before belong to "default".
+
+ALTER TABLE device_keys ADD COLUMN user_id TEXT NOT NULL DEFAULT 'default';
@@ -0,0 +1,6 @@
+-- Wrong parental control PINs
+--
+-- Kept with the controls so a restart does not reset a PIN lockout.
+
+ALTER TABLE user_parental_controls ADD COLUMN pin_failures INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE user_parental_controls ADD COLUMN pin_failed_at TEXT;
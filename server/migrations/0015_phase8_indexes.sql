@@ -1,7 +1,11 @@
 -- Phase 8: Scalability Enhancements - Database Indexes
 --
 -- This migration adds indexes to optimize query performance
--- for the HomeFlixD application.
+-- for the HomeFlixD application. Restored from the old phase8_indexes.sql
+-- and matched to the current columns (series.title, tmdb_collection_id,
+-- verification_history.content_id and verification_date); collections have
+-- no created_at. IF NOT EXISTS keeps it safe where the indexes were created
+-- by hand.
 --
 -- Performance improvements:
 -- - Faster lookups by file_path (unique constraint)
@@ -74,7 +78,7 @@ CREATE INDEX IF NOT EXISTS idx_series_tmdb_id
 
 -- Index on name for series search
 CREATE INDEX IF NOT EXISTS idx_series_name 
-    ON series(name COLLATE NOCASE);
+    ON series(title COLLATE NOCASE);
 
 -- Index on created_at for sorting
 CREATE INDEX IF NOT EXISTS idx_series_created_at 
@@ -84,30 +88,26 @@ CREATE INDEX IF NOT EXISTS idx_series_created_at
 
 -- Index on tmdb_id for collection lookups
 CREATE INDEX IF NOT EXISTS idx_collections_tmdb_id 
-    ON collections(tmdb_id);
+    ON collections(tmdb_collection_id);
 
 -- Index on name for collection search
 CREATE INDEX IF NOT EXISTS idx_collections_name 
     ON collections(name COLLATE NOCASE);
 
--- Index on created_at for sorting
-CREATE INDEX IF NOT EXISTS idx_collections_created_at 
-    ON collections(created_at DESC);
-
 -- Verification history table indexes
 
 -- Index on media_id for history lookups
 CREATE INDEX IF NOT EXISTS idx_verification_history_media_id 
-    ON verification_history(media_id);
+    ON verification_history(content_id);
 
 -- Index on timestamp for sorting
 CREATE INDEX IF NOT EXISTS idx_verification_history_timestamp 
-    ON verification_history(timestamp DESC);
+    ON verification_history(verification_date DESC);
 
 -- Composite index on media_id and timestamp
 -- Used for finding latest verification for a media item
 CREATE INDEX IF NOT EXISTS idx_verification_history_media_timestamp 
-    ON verification_history(media_id, timestamp DESC);
+    ON verification_history(content_id, verification_date DESC);
 
 -- Analyze tables after index creation
 -- This updates query planner statistics
@@ -0,0 +1,14 @@
+-- Stream token sessions
+--
+-- Sessions issued to device keys, so revoking a device also revokes the
+-- tokens it was given, and revoked sessions, which must stay revoked across
+-- restarts. Rows are dropped once their tokens have expired.
+
+CREATE TABLE IF NOT EXISTS stream_tokens (
+    session TEXT PRIMARY KEY,
+    device_id INTEGER,
+    expires_at INTEGER NOT NULL,
+    revoked INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_stream_tokens_device ON stream_tokens(device_id);
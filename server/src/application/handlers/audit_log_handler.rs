@@ -0,0 +1,61 @@
+//! Audit Log Handler
+//!
+//! Writes audit events to the audit log and prunes it by retention.
+
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::{debug, error, info};
+use crate::domain::events::AuditEvent;
+use crate::domain::repositories::AuditLogRepository;
+use crate::interfaces::messaging::EventHandler;
+use crate::shared::error::{MessagingError, RepositoryError};
+
+/// Audit Log Handler
+///
+/// Records every [`AuditEvent`]. A failed write is logged instead of
+/// failing the audited request.
+pub struct AuditLogHandler {
+    repository: Arc<dyn AuditLogRepository>,
+    /// Days entries are kept (0 = forever)
+    retention_days: u32,
+}
+
+impl AuditLogHandler {
+    /// Creates a new audit log handler keeping entries forever
+    pub fn new(repository: Arc<dyn AuditLogRepository>) -> Self {
+        Self {
+            repository,
+            retention_days: 0,
+        }
+    }
+
+    /// Keeps entries for `days` days (0 = forever)
+    pub fn with_retention_days(mut self, days: u32) -> Self {
+        self.retention_days = days;
+        self
+    }
+
+    /// Removes entries older than the retention, returning how many
+    pub async fn prune(&self) -> Result<u64, RepositoryError> {
+        if self.retention_days == 0 {
+            return Ok(0);
+        }
+        let cutoff = Utc::now() - Duration::days(i64::from(self.retention_days));
+        let removed = self.repository.delete_before(cutoff).await?;
+        if removed > 0 {
+            info!("Pruned {} audit log entries older than {} days", removed, self.retention_days);
+        }
+        Ok(removed)
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<AuditEvent> for AuditLogHandler {
+    async fn handle(&self, event: AuditEvent) -> Result<(), MessagingError> {
+        debug!("Audit {} of {:?} by {:?}", event.action.as_str(), event.target, event.actor);
+        if let Err(e) = self.repository.record(&event).await {
+            error!("Failed to record audit event {}: {}", event.action.as_str(), e);
+        }
+        Ok(())
+    }
+}
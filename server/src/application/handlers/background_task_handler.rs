@@ -32,10 +32,10 @@ impl BackgroundTaskHandler {
 impl EventHandler<BackgroundScanScheduledEvent> for BackgroundTaskHandler {
     async fn handle(&self, event: BackgroundScanScheduledEvent) -> Result<(), MessagingError> {
         info!(
-            "Background scan scheduled: path={}, scheduled_at={}, interval={}s",
+            "Background scan scheduled: path={}, scheduled_at={}, schedule={}",
             event.scan_path,
             event.scheduled_at,
-            event.scan_interval_secs
+            event.schedule
         );
 
         // Future: Resource management, notifications
@@ -3,36 +3,85 @@
 //! Handles events that require cache invalidation.
 
 use std::sync::Arc;
-use tracing::{info, error};
+use tracing::{debug, error};
 use crate::domain::repositories::CacheRepository;
+use crate::infrastructure::cache::{TranscodeCache, TranscriptionCache};
 use crate::interfaces::messaging::EventHandler;
-use crate::domain::events::MediaVerifiedEvent;
+use crate::domain::events::{LibraryChangeKind, LibraryChangedEvent, LibraryEntity};
 use crate::shared::error::MessagingError;
 
 /// Cache Invalidation Handler
+///
+/// Drops cached entries of library records whenever they change, and the
+/// transcoded segments and transcriptions of removed media.
 pub struct CacheInvalidationHandler {
     cache_repository: Arc<dyn CacheRepository>,
+    transcode_cache: Option<Arc<TranscodeCache>>,
+    transcription_cache: Option<Arc<TranscriptionCache>>,
 }
 
 impl CacheInvalidationHandler {
     /// Creates a new cache invalidation handler
     pub fn new(cache_repository: Arc<dyn CacheRepository>) -> Self {
-        Self { cache_repository }
+        Self {
+            cache_repository,
+            transcode_cache: None,
+            transcription_cache: None,
+        }
+    }
+
+    /// Also deletes cached transcodes of removed media
+    pub fn with_transcode_cache(mut self, transcode_cache: Arc<TranscodeCache>) -> Self {
+        self.transcode_cache = Some(transcode_cache);
+        self
+    }
+
+    /// Also deletes Whisper transcriptions of removed media
+    pub fn with_transcription_cache(mut self, transcription_cache: Arc<TranscriptionCache>) -> Self {
+        self.transcription_cache = Some(transcription_cache);
+        self
     }
 }
 
 #[async_trait::async_trait]
-impl EventHandler<MediaVerifiedEvent> for CacheInvalidationHandler {
-    async fn handle(&self, event: MediaVerifiedEvent) -> Result<(), MessagingError> {
-        info!("Invalidating cache for verified media: {}", event.media_id);
-        
-        let key = format!("media:{}", event.media_id);
-        if let Err(e) = self.cache_repository.delete(&key).await {
-            error!("Failed to invalidate cache: {}", e);
-            // We don't return error here to avoid failing the event processing
-            // as cache invalidation failure is non-critical
+impl EventHandler<LibraryChangedEvent> for CacheInvalidationHandler {
+    async fn handle(&self, event: LibraryChangedEvent) -> Result<(), MessagingError> {
+        // Cached entries hold metadata only, watch state is always read live
+        if !event.affects_metadata() {
+            return Ok(());
         }
-        
+
+        let prefix = match event.entity {
+            LibraryEntity::Media => "media",
+            LibraryEntity::Series => "series",
+            LibraryEntity::Collection => "collection",
+        };
+        debug!("Invalidating cache for {} {:?}", prefix, event.ids);
+
+        for id in &event.ids {
+            let key = format!("{}:{}", prefix, id);
+            if let Err(e) = self.cache_repository.delete(&key).await {
+                error!("Failed to invalidate cache: {}", e);
+                // We don't return error here to avoid failing the event processing
+                // as cache invalidation failure is non-critical
+            }
+        }
+
+        if event.entity == LibraryEntity::Media && event.change == LibraryChangeKind::Removed {
+            for id in &event.ids {
+                if let Some(transcode_cache) = &self.transcode_cache {
+                    if let Err(e) = transcode_cache.remove_media(*id) {
+                        error!("Failed to remove cached transcodes of media {}: {}", id, e);
+                    }
+                }
+                if let Some(transcription_cache) = &self.transcription_cache {
+                    if let Err(e) = transcription_cache.remove_media(*id) {
+                        error!("Failed to remove transcriptions of media {}: {}", id, e);
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 }
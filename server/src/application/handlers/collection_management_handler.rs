@@ -9,6 +9,8 @@ use crate::domain::events::{
     CollectionCreatedEvent,
     CollectionUpdatedEvent,
     CollectionItemAddedEvent,
+    CollectionItemRemovedEvent,
+    CollectionDeletedEvent,
 };
 use crate::interfaces::messaging::EventHandler;
 use crate::shared::error::MessagingError;
@@ -75,3 +77,32 @@ impl EventHandler<CollectionItemAddedEvent> for CollectionManagementHandler {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl EventHandler<CollectionItemRemovedEvent> for CollectionManagementHandler {
+    async fn handle(&self, event: CollectionItemRemovedEvent) -> Result<(), MessagingError> {
+        info!(
+            "Collection item removed: collection_id={}, item_id={}, media_id={:?}",
+            event.collection_id,
+            event.item_id,
+            event.media_id
+        );
+
+        // Future: Invalidate cache, update statistics
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<CollectionDeletedEvent> for CollectionManagementHandler {
+    async fn handle(&self, event: CollectionDeletedEvent) -> Result<(), MessagingError> {
+        info!(
+            "Collection deleted: id={}, name={}",
+            event.collection_id,
+            event.name
+        );
+
+        // Future: Invalidate cache, send notifications
+        Ok(())
+    }
+}
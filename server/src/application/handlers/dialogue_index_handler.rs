@@ -0,0 +1,31 @@
+//! Dialogue Index Handler
+//!
+//! Adds newly generated subtitles to the dialogue search index.
+
+use std::sync::Arc;
+use tracing::warn;
+use crate::application::services::DialogueSearch;
+use crate::domain::events::SubtitleGenerationCompletedEvent;
+use crate::interfaces::messaging::EventHandler;
+use crate::shared::error::MessagingError;
+
+/// Indexes the subtitles of a media item once generation completes
+pub struct DialogueIndexHandler {
+    dialogue_search: Arc<DialogueSearch>,
+}
+
+impl DialogueIndexHandler {
+    pub fn new(dialogue_search: Arc<DialogueSearch>) -> Self {
+        Self { dialogue_search }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<SubtitleGenerationCompletedEvent> for DialogueIndexHandler {
+    async fn handle(&self, event: SubtitleGenerationCompletedEvent) -> Result<(), MessagingError> {
+        if let Err(e) = self.dialogue_search.index_media(event.media_id).await {
+            warn!("Failed to index the dialogue of media {}: {}", event.media_id, e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Live Event Handler
+//!
+//! Forwards domain events to connected clients through the live event channel.
+
+use std::sync::Arc;
+use serde_json::json;
+use tracing::warn;
+
+use crate::application::services::{LiveEvent, LiveEventBroadcaster};
+use crate::domain::events::{BackgroundScanStartedEvent, MediaIdentifiedEvent, ScanCompletedEvent, WatchStateBatchUpdatedEvent, LibraryChangedEvent};
+use crate::domain::repositories::MediaRepository;
+use crate::interfaces::messaging::{DomainEvent, EventHandler};
+use crate::shared::error::MessagingError;
+
+/// Live Event Handler
+///
+/// Pushes newly identified media and scan lifecycle events to live clients,
+/// so libraries fill in while the first scan is still running. These are
+/// recorded for replay; library changes, of which scans write thousands,
+/// are only sent live.
+pub struct LiveEventHandler {
+    /// Media repository for resolving display fields
+    media_repository: Arc<dyn MediaRepository>,
+    /// Live event channel
+    broadcaster: Arc<LiveEventBroadcaster>,
+}
+
+impl LiveEventHandler {
+    /// Creates a new live event handler
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        broadcaster: Arc<LiveEventBroadcaster>,
+    ) -> Self {
+        Self {
+            media_repository,
+            broadcaster,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<MediaIdentifiedEvent> for LiveEventHandler {
+    async fn handle(&self, event: MediaIdentifiedEvent) -> Result<(), MessagingError> {
+        // Looked up even when nobody is listening: the event is recorded for
+        // clients that reconnect
+        let media = match self.media_repository.find_by_id(event.media_id).await {
+            Ok(media) => media,
+            Err(e) => {
+                warn!("Failed to load media {} for live event: {}", event.media_id, e);
+                None
+            }
+        };
+
+        let data = json!({
+            "media_id": event.media_id,
+            "media_type": event.media_type,
+            "tmdb_id": event.tmdb_id,
+            "title": media.as_ref().map(|m| m.title.clone()),
+            "poster_url": media.as_ref().and_then(|m| m.poster_url.clone()),
+            "series_id": media.as_ref().and_then(|m| m.series_id),
+            "season": media.as_ref().and_then(|m| m.season),
+            "episode": media.as_ref().and_then(|m| m.episode),
+            "confidence_score": event.confidence_score,
+        });
+        self.broadcaster.publish_recorded(LiveEvent::new(event.event_type(), data)).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<ScanCompletedEvent> for LiveEventHandler {
+    async fn handle(&self, event: ScanCompletedEvent) -> Result<(), MessagingError> {
+        let event_type = event.event_type();
+        self.broadcaster.publish_recorded(LiveEvent::new(event_type, event)).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<BackgroundScanStartedEvent> for LiveEventHandler {
+    async fn handle(&self, event: BackgroundScanStartedEvent) -> Result<(), MessagingError> {
+        let event_type = event.event_type();
+        self.broadcaster.publish_recorded(LiveEvent::new(event_type, event)).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<WatchStateBatchUpdatedEvent> for LiveEventHandler {
+    async fn handle(&self, event: WatchStateBatchUpdatedEvent) -> Result<(), MessagingError> {
+        let event_type = event.event_type();
+        self.broadcaster.publish_recorded(LiveEvent::new(event_type, event)).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<LibraryChangedEvent> for LiveEventHandler {
+    async fn handle(&self, event: LibraryChangedEvent) -> Result<(), MessagingError> {
+        // Scans write thousands of records; skip serializing them for nobody
+        if self.broadcaster.subscriber_count() == 0 {
+            return Ok(());
+        }
+        let event_type = event.event_type();
+        self.broadcaster.publish(LiveEvent::new(event_type, event));
+        Ok(())
+    }
+}
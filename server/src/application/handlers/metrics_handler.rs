@@ -4,7 +4,7 @@
 
 use tracing::info;
 use crate::interfaces::messaging::EventHandler;
-use crate::domain::events::{MediaIdentifiedEvent, ScanCompletedEvent};
+use crate::domain::events::{MediaIdentifiedEvent, MediaVerifiedEvent, ScanCompletedEvent};
 use crate::shared::error::MessagingError;
 
 /// Metrics Handler
@@ -34,3 +34,14 @@ impl EventHandler<ScanCompletedEvent> for MetricsHandler {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl EventHandler<MediaVerifiedEvent> for MetricsHandler {
+    async fn handle(&self, event: MediaVerifiedEvent) -> Result<(), MessagingError> {
+        info!(
+            "Updating metrics for media verified: confidence {} -> {}",
+            event.confidence_before, event.confidence_after
+        );
+        Ok(())
+    }
+}
@@ -17,6 +17,12 @@ pub mod streaming_handler;
 pub mod collection_management_handler;
 pub mod thumbnail_generation_handler;
 pub mod background_task_handler;
+pub mod live_event_handler;
+pub mod audit_log_handler;
+pub mod watch_history_handler;
+pub mod search_suggestions_handler;
+pub mod dialogue_index_handler;
+pub mod webhook_handler;
 
 pub use media_identified_handler::MediaIdentifiedHandler;
 pub use scan_completed_handler::ScanCompletedHandler;
@@ -31,3 +37,9 @@ pub use streaming_handler::StreamingHandler;
 pub use collection_management_handler::CollectionManagementHandler;
 pub use thumbnail_generation_handler::ThumbnailGenerationHandler;
 pub use background_task_handler::BackgroundTaskHandler;
+pub use live_event_handler::LiveEventHandler;
+pub use audit_log_handler::AuditLogHandler;
+pub use watch_history_handler::WatchHistoryHandler;
+pub use search_suggestions_handler::SearchSuggestionsHandler;
+pub use dialogue_index_handler::DialogueIndexHandler;
+pub use webhook_handler::WebhookHandler;
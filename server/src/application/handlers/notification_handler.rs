@@ -1,33 +1,190 @@
 //! Notification Handler
 //!
-//! Handles events that require sending notifications.
+//! Turns events into messages for people and hands them to the notification
+//! channels routed their type.
 
-use tracing::info;
-use crate::interfaces::messaging::EventHandler;
-use crate::domain::events::ScanCompletedEvent;
+use std::sync::Arc;
+use tracing::warn;
+use crate::application::services::NotificationService;
+use crate::domain::events::{
+    MediaIdentifiedEvent, ScanCompletedEvent, ScanFailedEvent, StreamStartedEvent, SubtitleGenerationCompletedEvent,
+    SubtitleGenerationFailedEvent,
+};
+use crate::domain::entities::Media;
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::domain::value_objects::MediaType;
+use crate::infrastructure::external::notifications::Notification;
+use crate::interfaces::messaging::{DomainEvent, EventHandler};
 use crate::shared::error::MessagingError;
 
+/// Longest overview quoted in a notification, in characters
+const MAX_OVERVIEW: usize = 300;
+
 /// Notification Handler
-pub struct NotificationHandler;
+pub struct NotificationHandler {
+    notifications: Arc<NotificationService>,
+    /// Media repository for naming media
+    media_repository: Arc<dyn MediaRepository>,
+    /// Series repository for naming episodes
+    series_repository: Arc<dyn SeriesRepository>,
+}
 
 impl NotificationHandler {
     /// Creates a new notification handler
-    pub fn new() -> Self {
-        Self
+    pub fn new(
+        notifications: Arc<NotificationService>,
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+    ) -> Self {
+        Self {
+            notifications,
+            media_repository,
+            series_repository,
+        }
+    }
+
+    /// Names a media item: "Dune (2021)" or "Severance S01E02"
+    async fn media_label(&self, media_id: i64) -> String {
+        let media = self.find_media(media_id).await;
+        self.label(media_id, media).await
+    }
+
+    async fn find_media(&self, media_id: i64) -> Option<Media> {
+        self.media_repository.find_by_id(media_id).await.unwrap_or_else(|e| {
+            warn!("Failed to load media {} for notification: {}", media_id, e);
+            None
+        })
+    }
+
+    async fn label(&self, media_id: i64, media: Option<Media>) -> String {
+        let Some(media) = media else {
+            return format!("Media {}", media_id);
+        };
+        match (media.media_type, media.series_id, media.season, media.episode) {
+            (MediaType::Episode, Some(series_id), Some(season), Some(episode)) => {
+                let series = self.series_repository.find_by_id(series_id).await.ok().flatten();
+                let series_title = series.map(|s| s.title).unwrap_or(media.title);
+                format!("{} S{:02}E{:02}", series_title, season, episode)
+            }
+            _ => match media.release_date.as_deref().and_then(|d| d.get(..4)) {
+                Some(year) => format!("{} ({})", media.title, year),
+                None => media.title,
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<MediaIdentifiedEvent> for NotificationHandler {
+    async fn handle(&self, event: MediaIdentifiedEvent) -> Result<(), MessagingError> {
+        if !self.notifications.is_routed(event.event_type()).await {
+            return Ok(());
+        }
+
+        let media = self.find_media(event.media_id).await;
+        let overview = media.as_ref().and_then(|m| m.overview.clone()).unwrap_or_default();
+        let label = self.label(event.media_id, media).await;
+        let kind = if event.media_type == "episode" { "New episode" } else { "New movie" };
+        let overview = match overview.char_indices().nth(MAX_OVERVIEW) {
+            Some((end, _)) => format!("{}…", &overview[..end]),
+            None => overview,
+        };
+
+        let notification = Notification::new(format!("{}: {}", kind, label), overview);
+        self.notifications.notify(event.event_type(), notification).await;
+        Ok(())
     }
 }
 
 #[async_trait::async_trait]
 impl EventHandler<ScanCompletedEvent> for NotificationHandler {
     async fn handle(&self, event: ScanCompletedEvent) -> Result<(), MessagingError> {
-        info!(
-            "Sending notification for scan completion: processed={}, identified={}, failed={}",
-            event.processed_count, event.identified_count, event.failed_count
+        let notification = Notification::new(
+            "Library scan finished",
+            format!(
+                "Processed {} files in {}m {}s: {} identified, {} failed.\n{}",
+                event.processed_count,
+                event.duration_secs / 60,
+                event.duration_secs % 60,
+                event.identified_count,
+                event.failed_count,
+                event.scan_path
+            ),
+        );
+        self.notifications.notify(event.event_type(), notification).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<ScanFailedEvent> for NotificationHandler {
+    async fn handle(&self, event: ScanFailedEvent) -> Result<(), MessagingError> {
+        let notification = Notification::new(
+            "Library scan failed",
+            format!(
+                "{}\nProcessed {} files, {} identified, before failing.\n{}",
+                event.error_message, event.processed_count, event.identified_count, event.scan_path
+            ),
+        );
+        self.notifications.notify(event.event_type(), notification).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<SubtitleGenerationCompletedEvent> for NotificationHandler {
+    async fn handle(&self, event: SubtitleGenerationCompletedEvent) -> Result<(), MessagingError> {
+        if !self.notifications.is_routed(event.event_type()).await {
+            return Ok(());
+        }
+
+        let how = if event.was_translated { "generated and translated" } else { "generated" };
+        let notification = Notification::new(
+            format!("Subtitles ready: {}", self.media_label(event.media_id).await),
+            format!("{} subtitles were {}.", event.language, how),
+        );
+        self.notifications.notify(event.event_type(), notification).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<SubtitleGenerationFailedEvent> for NotificationHandler {
+    async fn handle(&self, event: SubtitleGenerationFailedEvent) -> Result<(), MessagingError> {
+        let event_type = event.event_type();
+        if !self.notifications.is_routed(event_type).await {
+            return Ok(());
+        }
+
+        let notification = Notification::new(
+            format!("Subtitle generation failed: {}", self.media_label(event.media_id).await),
+            event.error_message,
+        );
+        self.notifications.notify(event_type, notification).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<StreamStartedEvent> for NotificationHandler {
+    async fn handle(&self, event: StreamStartedEvent) -> Result<(), MessagingError> {
+        let event_type = event.event_type();
+        if !self.notifications.is_routed(event_type).await {
+            return Ok(());
+        }
+
+        let mode = if event.needs_transcoding { "transcoding" } else { "direct play" };
+        let client = match (event.client_ip, event.user_agent) {
+            (Some(ip), Some(agent)) => format!("{} ({})", ip, agent),
+            (Some(ip), None) => ip,
+            (None, Some(agent)) => agent,
+            (None, None) => "an unknown client".to_string(),
+        };
+        let notification = Notification::new(
+            format!("Now playing: {}", self.media_label(event.media_id).await),
+            format!("Streaming to {}, {}.", client, mode),
         );
-        
-        // In a real implementation, this would send an email, push notification, etc.
-        // For now, we just log it.
-        
+        self.notifications.notify(event_type, notification).await;
         Ok(())
     }
 }
@@ -9,6 +9,7 @@ use crate::domain::events::{
     ProgressUpdatedEvent,
     MediaWatchedEvent,
     MediaUnwatchedEvent,
+    WatchStateBatchUpdatedEvent,
 };
 use crate::interfaces::messaging::EventHandler;
 use crate::shared::error::MessagingError;
@@ -62,3 +63,19 @@ impl EventHandler<MediaUnwatchedEvent> for ProgressTrackingHandler {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl EventHandler<WatchStateBatchUpdatedEvent> for ProgressTrackingHandler {
+    async fn handle(&self, event: WatchStateBatchUpdatedEvent) -> Result<(), MessagingError> {
+        info!(
+            "Watch state batch: {} {} ({} items) watched={}",
+            event.scope,
+            event.scope_id,
+            event.media_ids.len(),
+            event.is_watched
+        );
+
+        // Future: Update statistics, recommendation engine
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+//! Search Suggestions Handler
+//!
+//! Marks the suggestion index out of date when library records change.
+
+use std::sync::Arc;
+use crate::application::services::SearchSuggestions;
+use crate::domain::events::LibraryChangedEvent;
+use crate::interfaces::messaging::EventHandler;
+use crate::shared::error::MessagingError;
+
+/// Invalidates the suggestion index on library changes
+pub struct SearchSuggestionsHandler {
+    suggestions: Arc<SearchSuggestions>,
+}
+
+impl SearchSuggestionsHandler {
+    pub fn new(suggestions: Arc<SearchSuggestions>) -> Self {
+        Self { suggestions }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<LibraryChangedEvent> for SearchSuggestionsHandler {
+    async fn handle(&self, event: LibraryChangedEvent) -> Result<(), MessagingError> {
+        if event.affects_metadata() {
+            self.suggestions.invalidate();
+        }
+        Ok(())
+    }
+}
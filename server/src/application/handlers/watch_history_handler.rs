@@ -0,0 +1,83 @@
+//! Watch History Handler
+//!
+//! Records finished stream sessions in the playback history and marks them
+//! completed when their media is marked watched.
+
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::{debug, error};
+use crate::domain::events::{MediaWatchedEvent, ProgressUpdatedEvent, StreamEndedEvent};
+use crate::domain::repositories::{NewPlayback, WatchHistoryRepository};
+use crate::interfaces::messaging::EventHandler;
+use crate::shared::error::MessagingError;
+
+/// Shorter sessions (probes, seeks before giving up) are not recorded
+const MIN_PLAYBACK_SECONDS: f64 = 30.0;
+/// How far back a watched mark finds the playback it completes
+const COMPLETION_WINDOW_HOURS: i64 = 24;
+
+/// Watch History Handler
+///
+/// A failed write is logged instead of failing the stream.
+pub struct WatchHistoryHandler {
+    repository: Arc<dyn WatchHistoryRepository>,
+}
+
+impl WatchHistoryHandler {
+    /// Creates a new watch history handler
+    pub fn new(repository: Arc<dyn WatchHistoryRepository>) -> Self {
+        Self { repository }
+    }
+
+    async fn complete(&self, media_id: i64) {
+        let since = Utc::now() - Duration::hours(COMPLETION_WINDOW_HOURS);
+        if let Err(e) = self.repository.mark_completed(media_id, since).await {
+            error!("Failed to mark playback of media {} completed: {}", media_id, e);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<StreamEndedEvent> for WatchHistoryHandler {
+    async fn handle(&self, event: StreamEndedEvent) -> Result<(), MessagingError> {
+        let (Some(user), Some(started_at)) = (event.user, event.started_at) else {
+            return Ok(());
+        };
+        let seconds = event.duration_seconds.unwrap_or(0.0);
+        if seconds < MIN_PLAYBACK_SECONDS {
+            debug!("Not recording {:.0}s playback of media {}", seconds, event.media_id);
+            return Ok(());
+        }
+
+        let playback = NewPlayback {
+            user_id: user,
+            media_id: event.media_id,
+            client: event.client,
+            started_at,
+            duration_seconds: seconds.round() as i64,
+            bytes_sent: event.bytes_streamed.unwrap_or(0) as i64,
+        };
+        if let Err(e) = self.repository.record(&playback).await {
+            error!("Failed to record playback of media {}: {}", event.media_id, e);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<ProgressUpdatedEvent> for WatchHistoryHandler {
+    async fn handle(&self, event: ProgressUpdatedEvent) -> Result<(), MessagingError> {
+        if event.is_watched {
+            self.complete(event.media_id).await;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler<MediaWatchedEvent> for WatchHistoryHandler {
+    async fn handle(&self, event: MediaWatchedEvent) -> Result<(), MessagingError> {
+        self.complete(event.media_id).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Webhook Handler
+//!
+//! Forwards domain events to the webhooks subscribed to their type.
+
+use std::sync::Arc;
+use crate::application::services::WebhookService;
+use crate::domain::events::{
+    CollectionDetectedEvent, MediaIdentifiedEvent, MediaWatchedEvent, ScanCompletedEvent, ScanFailedEvent,
+    StreamEndedEvent, StreamStartedEvent, SubtitleGenerationCompletedEvent, SubtitleGenerationFailedEvent,
+};
+use crate::interfaces::messaging::{DomainEvent, EventHandler};
+use crate::shared::error::MessagingError;
+
+/// Delivers the domain events it is subscribed to to webhooks
+///
+/// The payload is the event as serialized for the event store. Deliveries
+/// run in the background, so slow endpoints don't hold up the bus.
+pub struct WebhookHandler {
+    webhooks: Arc<WebhookService>,
+}
+
+impl WebhookHandler {
+    pub fn new(webhooks: Arc<WebhookService>) -> Self {
+        Self { webhooks }
+    }
+
+    async fn forward<T: DomainEvent>(&self, event: T) -> Result<(), MessagingError> {
+        let data = serde_json::to_value(&event).map_err(|e| MessagingError::Serialization(e.to_string()))?;
+        self.webhooks.dispatch(event.event_type(), data).await;
+        Ok(())
+    }
+}
+
+/// Implements `EventHandler` for event types webhooks can subscribe to
+/// (a generic impl would overlap the `AsyncEventHandler` one)
+macro_rules! forward_events {
+    ($($event:ty),* $(,)?) => {
+        $(
+            #[async_trait::async_trait]
+            impl EventHandler<$event> for WebhookHandler {
+                async fn handle(&self, event: $event) -> Result<(), MessagingError> {
+                    self.forward(event).await
+                }
+            }
+        )*
+    };
+}
+
+forward_events!(
+    MediaIdentifiedEvent,
+    ScanCompletedEvent,
+    ScanFailedEvent,
+    CollectionDetectedEvent,
+    SubtitleGenerationCompletedEvent,
+    SubtitleGenerationFailedEvent,
+    StreamStartedEvent,
+    StreamEndedEvent,
+    MediaWatchedEvent,
+);
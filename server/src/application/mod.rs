@@ -20,3 +20,4 @@ pub use use_cases::manage_series::ManageSeriesUseCase;
 pub use services::scanner_orchestrator::ScannerOrchestrator;
 pub use services::metadata_enricher::MetadataEnricher;
 pub use services::collection_manager::CollectionManager;
+pub use services::playback_qos::PlaybackQos;
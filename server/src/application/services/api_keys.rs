@@ -0,0 +1,183 @@
+//! API Keys
+//!
+//! Authenticates API requests when `API_SECRET` is set. Besides the shared
+//! secret, every device can get its own long-lived key, so a lost device is
+//! cut off by revoking its key instead of rotating the secret on all others.
+//!
+//! Keys are `hfx_` followed by 64 hex characters; only their SHA-256 is
+//! stored.
+
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::repositories::{DeviceKey, DeviceKeyRepository};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Prefix of device keys
+const KEY_PREFIX: &str = "hfx_";
+/// Characters of a key kept to tell keys apart
+const VISIBLE_PREFIX_LEN: usize = 12;
+/// Longest accepted device name
+const MAX_NAME_CHARS: usize = 100;
+
+/// Who an authenticated request comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+    /// Holder of the shared secret; may manage device keys
+    Admin,
+    /// A device with its own key
+    Device(DeviceKey),
+}
+
+impl Caller {
+    /// User a device is bound to; None for the shared secret, which may act
+    /// for any user
+    pub fn device_user(&self) -> Option<&str> {
+        match self {
+            Caller::Admin => None,
+            Caller::Device(device) => Some(&device.user_id),
+        }
+    }
+
+    /// ID of the calling device; None for the shared secret
+    pub fn device_id(&self) -> Option<i64> {
+        match self {
+            Caller::Admin => None,
+            Caller::Device(device) => Some(device.id),
+        }
+    }
+}
+
+/// Shared secret and device key authentication
+pub struct ApiKeyService {
+    repository: Arc<dyn DeviceKeyRepository>,
+    /// SHA-256 of the shared secret (None = authentication disabled)
+    secret_hash: Option<String>,
+}
+
+impl ApiKeyService {
+    pub fn new(repository: Arc<dyn DeviceKeyRepository>, secret: Option<&str>) -> Self {
+        Self {
+            repository,
+            secret_hash: secret.map(hash_key),
+        }
+    }
+
+    /// Whether requests must carry the shared secret or a device key
+    pub fn is_enabled(&self) -> bool {
+        self.secret_hash.is_some()
+    }
+
+    /// Identifies the caller presenting `key`, or None for an unknown key
+    pub async fn authenticate(&self, key: &str) -> Option<Caller> {
+        let hash = hash_key(key);
+        if self.secret_hash.as_deref() == Some(hash.as_str()) {
+            return Some(Caller::Admin);
+        }
+        if !key.starts_with(KEY_PREFIX) {
+            return None;
+        }
+        match self.repository.find_by_hash(&hash).await {
+            Ok(Some(device)) => {
+                if let Err(e) = self.repository.touch(device.id).await {
+                    warn!("Failed to record use of device key {}: {}", device.id, e);
+                }
+                Some(Caller::Device(device))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to look up device key: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Issues a key for a device of `user_id`, returning the device and the
+    /// key
+    ///
+    /// The key is not stored and cannot be shown again.
+    pub async fn create(&self, name: &str, user_id: &str) -> Result<(DeviceKey, String), ApplicationError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+            return Err(ApplicationError::Domain(DomainError::InvalidInput(format!(
+                "Device name must be 1 to {} characters", MAX_NAME_CHARS
+            ))));
+        }
+        let user_id = user_id.trim();
+        if user_id.is_empty() || user_id.chars().count() > MAX_NAME_CHARS {
+            return Err(ApplicationError::Domain(DomainError::InvalidInput(format!(
+                "User must be 1 to {} characters", MAX_NAME_CHARS
+            ))));
+        }
+        let key = format!("{}{}{}", KEY_PREFIX, uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        let device = self.repository.create(name, user_id, &key[..VISIBLE_PREFIX_LEN], &hash_key(&key)).await?;
+        info!("Issued API key {} to device '{}' of user {}", device.prefix, device.name, device.user_id);
+        Ok((device, key))
+    }
+
+    /// Lists the devices with a key
+    pub async fn list(&self) -> Result<Vec<DeviceKey>, ApplicationError> {
+        Ok(self.repository.find_all().await?)
+    }
+
+    /// Revokes the key of a device
+    pub async fn revoke(&self, id: i64) -> Result<(), ApplicationError> {
+        if !self.repository.delete(id).await? {
+            return Err(ApplicationError::Domain(DomainError::NotFound(format!("Device {} not found", id))));
+        }
+        info!("Revoked the API key of device {}", id);
+        Ok(())
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteDeviceKeyRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_device_keys() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let service = ApiKeyService::new(Arc::new(SqliteDeviceKeyRepository::new(pool.clone())), Some("shared secret"));
+
+        assert!(service.is_enabled());
+        assert_eq!(service.authenticate("shared secret").await, Some(Caller::Admin));
+        assert_eq!(service.authenticate("guess").await, None);
+
+        let (tablet, key) = service.create(" Tablet ", "kids").await.unwrap();
+        assert_eq!(tablet.name, "Tablet");
+        assert_eq!(tablet.user_id, "kids");
+        assert_eq!(key.len(), 68);
+        assert!(key.starts_with(&tablet.prefix));
+        let Some(Caller::Device(device)) = service.authenticate(&key).await else {
+            panic!("device key not accepted");
+        };
+        assert_eq!(device.id, tablet.id);
+        assert_eq!(Caller::Device(device).device_user(), Some("kids"));
+        assert!(service.list().await.unwrap()[0].last_used_at.is_some());
+
+        // A revoked key stops working, the shared secret keeps working
+        service.revoke(tablet.id).await.unwrap();
+        assert_eq!(service.authenticate(&key).await, None);
+        assert_eq!(service.authenticate("shared secret").await, Some(Caller::Admin));
+        assert!(service.revoke(tablet.id).await.is_err());
+        assert!(service.create("  ", "kids").await.is_err());
+        assert!(service.create("Phone", " ").await.is_err());
+
+        let open = ApiKeyService::new(Arc::new(SqliteDeviceKeyRepository::new(pool)), None);
+        assert!(!open.is_enabled());
+        assert_eq!(open.authenticate("").await, None);
+    }
+}
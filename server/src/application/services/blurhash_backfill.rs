@@ -0,0 +1,165 @@
+//! Blurhash Backfill
+//!
+//! Computes blurhash placeholders for posters and backdrops that do not have
+//! one yet, so clients can render an instant blurred preview while the real
+//! artwork loads.
+
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::infrastructure::cache::ImageCache;
+use crate::interfaces::external_services::ArtworkMirror;
+use crate::shared::error::ApplicationError;
+
+/// Items fetched from the repositories per batch
+const BATCH_SIZE: usize = 100;
+
+/// Outcome of hashing one artwork URL
+enum HashOutcome {
+    /// Hash computed
+    Hashed(String),
+    /// The image is missing upstream or cannot be decoded; stored as an empty
+    /// hash so it is not retried on every run
+    Unavailable,
+    /// Transient failure (network, cache); retried on the next run
+    Failed,
+}
+
+/// Blurhash Backfill
+///
+/// # Architecture Notes
+/// - Images are loaded through the artwork mirror, so mirrored artwork is
+///   hashed without network access
+/// - Hashes are cleared by the repositories when artwork changes, which puts
+///   the item back into the backfill queue
+pub struct BlurhashBackfill {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    artwork_mirror: Arc<dyn ArtworkMirror>,
+}
+
+impl BlurhashBackfill {
+    /// Creates a new blurhash backfill
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        artwork_mirror: Arc<dyn ArtworkMirror>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            artwork_mirror,
+        }
+    }
+
+    /// Computes missing blurhashes for media and series artwork
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of hashes computed
+    ///
+    /// # Errors
+    /// Returns error if the repositories fail. Failures for individual images
+    /// are logged and skipped.
+    pub async fn backfill_library(&self) -> Result<usize, ApplicationError> {
+        let mut hashed = 0;
+
+        loop {
+            let batch = self.media_repository.find_missing_blurhashes(BATCH_SIZE).await?;
+            let mut progressed = false;
+            for media in &batch {
+                let Some(id) = media.id else { continue };
+                let poster = self.missing_hash(&media.poster_url, &media.poster_blurhash).await;
+                let backdrop = self.missing_hash(&media.backdrop_url, &media.backdrop_blurhash).await;
+                if poster.is_none() && backdrop.is_none() {
+                    continue;
+                }
+                hashed += count_hashed(&poster, &backdrop);
+                self.media_repository
+                    .update_blurhashes(id, poster.as_deref(), backdrop.as_deref())
+                    .await?;
+                progressed = true;
+            }
+            // Stop when only transient failures are left
+            if batch.len() < BATCH_SIZE || !progressed {
+                break;
+            }
+        }
+
+        loop {
+            let batch = self.series_repository.find_missing_blurhashes(BATCH_SIZE).await?;
+            let mut progressed = false;
+            for series in &batch {
+                let Some(id) = series.id else { continue };
+                let poster = self.missing_hash(&series.poster_url, &series.poster_blurhash).await;
+                let backdrop = self.missing_hash(&series.backdrop_url, &series.backdrop_blurhash).await;
+                if poster.is_none() && backdrop.is_none() {
+                    continue;
+                }
+                hashed += count_hashed(&poster, &backdrop);
+                self.series_repository
+                    .update_blurhashes(id, poster.as_deref(), backdrop.as_deref())
+                    .await?;
+                progressed = true;
+            }
+            if batch.len() < BATCH_SIZE || !progressed {
+                break;
+            }
+        }
+
+        if hashed > 0 {
+            info!("Blurhash backfill complete: {} placeholders computed", hashed);
+        }
+        Ok(hashed)
+    }
+
+    /// Hashes an artwork URL if it has no hash yet
+    ///
+    /// # Returns
+    /// * The hash to store (empty for unavailable images), or None to leave
+    ///   the stored value unchanged
+    async fn missing_hash(&self, url: &Option<String>, hash: &Option<String>) -> Option<String> {
+        let url = url.as_deref().filter(|_| hash.is_none())?;
+        match self.hash_url(url).await {
+            HashOutcome::Hashed(hash) => Some(hash),
+            HashOutcome::Unavailable => Some(String::new()),
+            HashOutcome::Failed => None,
+        }
+    }
+
+    /// Loads and hashes one image
+    async fn hash_url(&self, url: &str) -> HashOutcome {
+        let bytes = match self.artwork_mirror.fetch(url).await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                debug!("Artwork not found upstream, no blurhash: {}", url);
+                return HashOutcome::Unavailable;
+            }
+            Err(e) => {
+                warn!("Failed to load artwork for blurhash {}: {}", url, e);
+                return HashOutcome::Failed;
+            }
+        };
+
+        // Decoding is CPU-bound
+        match tokio::task::spawn_blocking(move || ImageCache::blurhash(&bytes)).await {
+            Ok(Ok(hash)) => HashOutcome::Hashed(hash),
+            Ok(Err(e)) => {
+                warn!("Failed to compute blurhash for {}: {}", url, e);
+                HashOutcome::Unavailable
+            }
+            Err(e) => {
+                warn!("Blurhash task failed for {}: {}", url, e);
+                HashOutcome::Failed
+            }
+        }
+    }
+}
+
+/// Counts the non-empty hashes of an update
+fn count_hashed(poster: &Option<String>, backdrop: &Option<String>) -> usize {
+    [poster, backdrop]
+        .into_iter()
+        .filter(|h| h.as_deref().is_some_and(|h| !h.is_empty()))
+        .count()
+}
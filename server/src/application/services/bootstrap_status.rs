@@ -0,0 +1,172 @@
+//! Bootstrap Status
+//!
+//! Tracks the server's first-run state so clients can show progress while the
+//! initial library scan is still running instead of an empty library.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Mutex;
+
+use crate::application::use_cases::scan_library::ScanProgress;
+
+/// Startup phase of the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BootstrapPhase {
+    /// Server is up, initial scan not started yet
+    Starting,
+    /// Initial scan is walking and identifying files
+    Scanning,
+    /// Initial scan done, collections are being built
+    Finalizing,
+    /// Initial scan finished (or scanning is disabled)
+    Ready,
+    /// Initial scan failed
+    Failed,
+}
+
+/// Snapshot of first-run progress
+#[derive(Debug, Clone, Serialize)]
+pub struct BootstrapStatus {
+    pub phase: BootstrapPhase,
+    /// Whether the library was empty when the server started
+    pub first_run: bool,
+    pub total_files: usize,
+    pub processed: usize,
+    pub identified: usize,
+    pub failed: usize,
+    pub percentage: f64,
+    pub estimated_seconds_remaining: Option<f64>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+/// Thread-safe holder for the bootstrap status
+///
+/// Only the first scan after startup is tracked; later periodic scans leave
+/// the status at `Ready`.
+pub struct BootstrapTracker {
+    status: Mutex<BootstrapStatus>,
+}
+
+impl BootstrapTracker {
+    /// Creates a tracker in the `Starting` phase
+    pub fn new(first_run: bool) -> Self {
+        Self {
+            status: Mutex::new(BootstrapStatus {
+                phase: BootstrapPhase::Starting,
+                first_run,
+                total_files: 0,
+                processed: 0,
+                identified: 0,
+                failed: 0,
+                percentage: 0.0,
+                estimated_seconds_remaining: None,
+                started_at: None,
+                completed_at: None,
+                error: None,
+            }),
+        }
+    }
+
+    /// Whether the initial scan has finished (successfully or not)
+    pub fn is_complete(&self) -> bool {
+        matches!(
+            self.status.lock().unwrap().phase,
+            BootstrapPhase::Ready | BootstrapPhase::Failed
+        )
+    }
+
+    /// Marks the initial scan as started
+    pub fn scan_started(&self) {
+        let mut status = self.status.lock().unwrap();
+        if status.phase == BootstrapPhase::Starting {
+            status.phase = BootstrapPhase::Scanning;
+            status.started_at = Some(Utc::now());
+        }
+    }
+
+    /// Records scan progress; ignored once the initial scan is over
+    pub fn update_progress(&self, progress: &ScanProgress) {
+        let mut status = self.status.lock().unwrap();
+        if status.phase != BootstrapPhase::Scanning {
+            return;
+        }
+        status.total_files = progress.total;
+        status.processed = progress.processed;
+        status.identified = progress.identified;
+        status.failed = progress.failed;
+        status.percentage = progress.percentage;
+        status.estimated_seconds_remaining = progress.estimated_seconds_remaining;
+    }
+
+    /// Marks file processing as done while post-scan work runs
+    pub fn finalizing(&self) {
+        let mut status = self.status.lock().unwrap();
+        if status.phase == BootstrapPhase::Scanning {
+            status.phase = BootstrapPhase::Finalizing;
+            status.percentage = 100.0;
+            status.estimated_seconds_remaining = None;
+        }
+    }
+
+    /// Marks the server as fully bootstrapped
+    pub fn ready(&self) {
+        let mut status = self.status.lock().unwrap();
+        if !matches!(status.phase, BootstrapPhase::Ready | BootstrapPhase::Failed) {
+            status.phase = BootstrapPhase::Ready;
+            status.completed_at = Some(Utc::now());
+        }
+    }
+
+    /// Marks the initial scan as failed
+    pub fn failed(&self, error: String) {
+        let mut status = self.status.lock().unwrap();
+        if !matches!(status.phase, BootstrapPhase::Ready | BootstrapPhase::Failed) {
+            status.phase = BootstrapPhase::Failed;
+            status.completed_at = Some(Utc::now());
+            status.error = Some(error);
+        }
+    }
+
+    /// Returns a copy of the current status
+    pub fn snapshot(&self) -> BootstrapStatus {
+        self.status.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_initial_scan_only() {
+        let tracker = BootstrapTracker::new(true);
+        let mut progress = ScanProgress::new(10);
+        progress.processed = 4;
+
+        // Progress before the scan starts is ignored
+        tracker.update_progress(&progress);
+        assert_eq!(tracker.snapshot().processed, 0);
+
+        tracker.scan_started();
+        tracker.update_progress(&progress);
+        let status = tracker.snapshot();
+        assert_eq!(status.phase, BootstrapPhase::Scanning);
+        assert_eq!(status.total_files, 10);
+        assert_eq!(status.processed, 4);
+
+        tracker.finalizing();
+        tracker.ready();
+        assert!(tracker.is_complete());
+
+        // Later scans don't reset the status
+        tracker.scan_started();
+        progress.processed = 1;
+        tracker.update_progress(&progress);
+        let status = tracker.snapshot();
+        assert_eq!(status.phase, BootstrapPhase::Ready);
+        assert_eq!(status.processed, 4);
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::sync::Arc;
 use std::collections::HashMap;
+use serde::Serialize;
 use tracing::{info, debug, warn};
 
 use crate::domain::entities::{Collection, CollectionItem};
@@ -14,10 +15,12 @@ use crate::domain::events::{
     CollectionCreatedEvent,
     CollectionUpdatedEvent,
     CollectionItemAddedEvent,
+    CollectionItemRemovedEvent,
+    CollectionDeletedEvent,
 };
 use crate::interfaces::external_services::TmdbService;
 use crate::interfaces::messaging::EventBus;
-use crate::shared::error::ApplicationError;
+use crate::shared::error::{ApplicationError, DomainError};
 
 /// Collection Manager
 ///
@@ -426,21 +429,30 @@ impl<E: EventBus + ?Sized> CollectionManager<E> {
     ///
     /// # Arguments
     /// * `name` - Collection name
-    /// * `media_ids` - List of media IDs to include
+    /// * `description` - Optional description
+    /// * `media_ids` - Movies and episodes to include, in order
     ///
     /// # Returns
-    /// * `Result<i64, ApplicationError>` - Collection ID
+    /// * `Result<Collection, ApplicationError>` - The new collection
+    ///
+    /// # Errors
+    /// Returns error if the name is empty or a media item does not exist
     pub async fn create_custom_collection(
         &self,
         name: String,
+        description: Option<String>,
         media_ids: Vec<i64>,
-    ) -> Result<i64, ApplicationError> {
+    ) -> Result<Collection, ApplicationError> {
         info!("Creating custom collection: {}", name);
 
-        let mut collection = Collection::new(name.clone())?
-            .with_collection_type("custom".to_string());
-        collection.available_items = media_ids.len() as i32;
+        let mut media = Vec::with_capacity(media_ids.len());
+        for media_id in &media_ids {
+            media.push(self.find_media(*media_id).await?);
+        }
 
+        let collection = Collection::new(name.clone())?
+            .with_description(description)
+            .with_collection_type("custom".to_string());
         let collection_id = self.collection_repository.save(&collection).await?;
 
         // Publish collection created event
@@ -454,12 +466,231 @@ impl<E: EventBus + ?Sized> CollectionManager<E> {
             warn!("Failed to publish collection created event: {}", e);
         }
 
+        for (order, media) in media.iter().enumerate() {
+            self.save_custom_item(collection_id, media, order as i32).await?;
+        }
+        let collection = self.refresh_custom_counts(collection_id).await?;
+
         info!(
             "Custom collection created: {} (ID: {}) with {} media",
             name, collection_id, media_ids.len()
         );
 
-        Ok(collection_id)
+        Ok(collection)
+    }
+
+    /// Renames a custom collection or changes its description or sort mode
+    ///
+    /// # Errors
+    /// Returns error if the collection does not exist or is not custom, or
+    /// the name is empty or the sort mode unknown
+    pub async fn update_custom_collection(
+        &self,
+        collection_id: i64,
+        name: String,
+        description: Option<String>,
+        sort_mode: Option<String>,
+    ) -> Result<Collection, ApplicationError> {
+        let mut collection = self.find_custom(collection_id).await?;
+        if name.trim().is_empty() {
+            return Err(DomainError::InvalidInput("Collection name cannot be empty".into()).into());
+        }
+        if let Some(sort_mode) = sort_mode {
+            if sort_mode != "timeline" && sort_mode != "release" {
+                return Err(DomainError::InvalidInput(format!("Unknown sort mode '{}'", sort_mode)).into());
+            }
+            collection.sort_mode = sort_mode;
+        }
+        collection.name = name;
+        collection.description = description;
+
+        self.collection_repository.update(&collection).await?;
+        self.publish_updated(&collection).await;
+        Ok(collection)
+    }
+
+    /// Sets or clears the poster of a custom collection
+    pub async fn set_custom_poster(&self, collection_id: i64, poster_url: Option<String>) -> Result<Collection, ApplicationError> {
+        let mut collection = self.find_custom(collection_id).await?;
+        collection.poster_url = poster_url;
+
+        self.collection_repository.update(&collection).await?;
+        self.publish_updated(&collection).await;
+        Ok(collection)
+    }
+
+    /// Adds a movie or episode to a custom collection
+    ///
+    /// # Arguments
+    /// * `position` - Place to insert at, from 0 (None or past the end = appended)
+    ///
+    /// # Returns
+    /// * `Result<CollectionItem, ApplicationError>` - The new item
+    pub async fn add_custom_item(
+        &self,
+        collection_id: i64,
+        media_id: i64,
+        position: Option<usize>,
+    ) -> Result<CollectionItem, ApplicationError> {
+        self.find_custom(collection_id).await?;
+        let media = self.find_media(media_id).await?;
+        let mut items = self.collection_repository.find_items(collection_id).await?;
+        if items.iter().any(|item| item.media_id == Some(media_id)) {
+            return Err(DomainError::InvalidInput(format!("Media {} is already in the collection", media_id)).into());
+        }
+
+        let position = position.unwrap_or(items.len()).min(items.len());
+        let item = self.save_custom_item(collection_id, &media, position as i32).await?;
+        items.insert(position, item.clone());
+        self.renumber(&mut items).await?;
+        self.refresh_custom_counts(collection_id).await?;
+
+        Ok(item)
+    }
+
+    /// Removes an item from a custom collection
+    pub async fn remove_custom_item(&self, collection_id: i64, item_id: i64) -> Result<(), ApplicationError> {
+        self.find_custom(collection_id).await?;
+        let mut items = self.collection_repository.find_items(collection_id).await?;
+        let index = items
+            .iter()
+            .position(|item| item.id == item_id)
+            .ok_or_else(|| DomainError::NotFound(format!("Item {} not found", item_id)))?;
+        let item = items.remove(index);
+
+        self.collection_repository.delete_item(collection_id, item_id).await?;
+        self.renumber(&mut items).await?;
+
+        let event = CollectionItemRemovedEvent::new(collection_id, item_id, item.media_id);
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection item removed event: {}", e);
+        }
+        self.refresh_custom_counts(collection_id).await?;
+        Ok(())
+    }
+
+    /// Puts the items of a custom collection in the order of `item_ids`,
+    /// which must list every item once
+    pub async fn reorder_custom_items(&self, collection_id: i64, item_ids: &[i64]) -> Result<Vec<CollectionItem>, ApplicationError> {
+        let collection = self.find_custom(collection_id).await?;
+        let items = self.collection_repository.find_items(collection_id).await?;
+        let mut by_id: HashMap<i64, CollectionItem> = items.into_iter().map(|item| (item.id, item)).collect();
+        if item_ids.len() != by_id.len() {
+            return Err(DomainError::InvalidInput("item_ids must list every item of the collection once".into()).into());
+        }
+        let mut ordered = Vec::with_capacity(item_ids.len());
+        for item_id in item_ids {
+            let item = by_id
+                .remove(item_id)
+                .ok_or_else(|| DomainError::InvalidInput("item_ids must list every item of the collection once".into()))?;
+            ordered.push(item);
+        }
+
+        self.renumber(&mut ordered).await?;
+        self.publish_updated(&collection).await;
+        Ok(ordered)
+    }
+
+    async fn find_custom(&self, collection_id: i64) -> Result<Collection, ApplicationError> {
+        let collection = self.collection_repository
+            .find_by_id(collection_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Collection {} not found", collection_id)))?;
+        if !collection.is_custom() {
+            return Err(DomainError::InvalidInput(format!(
+                "Collection {} is managed automatically ({}); only custom collections can be changed",
+                collection_id, collection.collection_type
+            )).into());
+        }
+        Ok(collection)
+    }
+
+    async fn find_media(&self, media_id: i64) -> Result<crate::domain::entities::Media, ApplicationError> {
+        let media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Media {} not found", media_id)))?;
+        if !media.is_movie() && !media.is_episode() {
+            return Err(DomainError::InvalidInput(format!("Media {} is not a movie or episode", media_id)).into());
+        }
+        Ok(media)
+    }
+
+    async fn save_custom_item(
+        &self,
+        collection_id: i64,
+        media: &crate::domain::entities::Media,
+        order: i32,
+    ) -> Result<CollectionItem, ApplicationError> {
+        let media_id = media.id.unwrap_or(0);
+        let mut item = CollectionItem {
+            id: 0,
+            collection_id,
+            tmdb_id: media.tmdb_id.unwrap_or(0),
+            media_type: media.media_type.as_str().to_string(),
+            title: media.title.clone(),
+            overview: media.overview.clone(),
+            poster_url: media.poster_url.clone(),
+            release_date: media.release_date.clone(),
+            timeline_order: order,
+            release_order: order,
+            timeline_year: None,
+            timeline_notes: None,
+            is_available: true,
+            media_id: Some(media_id),
+        };
+        item.id = self.collection_repository.save_item(&item).await?;
+
+        let event = CollectionItemAddedEvent::new(
+            collection_id,
+            Some(media_id),
+            item.tmdb_id,
+            item.media_type.clone(),
+            item.title.clone(),
+        );
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection item added event: {}", e);
+        }
+        Ok(item)
+    }
+
+    /// Stores the order of custom items as listed
+    async fn renumber(&self, items: &mut [CollectionItem]) -> Result<(), ApplicationError> {
+        for (order, item) in items.iter_mut().enumerate() {
+            let order = order as i32;
+            if item.timeline_order != order || item.release_order != order {
+                item.timeline_order = order;
+                item.release_order = order;
+                self.collection_repository.update_item(item).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Recounts the items of a custom collection and publishes the update
+    async fn refresh_custom_counts(&self, collection_id: i64) -> Result<Collection, ApplicationError> {
+        let items = self.collection_repository.find_items(collection_id).await?;
+        let available = items.iter().filter(|item| item.is_available).count() as i32;
+        self.collection_repository.update_counts(collection_id, items.len() as i32, available).await?;
+
+        let collection = self.collection_repository
+            .find_by_id(collection_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Collection {} not found", collection_id)))?;
+        self.publish_updated(&collection).await;
+        Ok(collection)
+    }
+
+    async fn publish_updated(&self, collection: &Collection) {
+        let event = CollectionUpdatedEvent::new(
+            collection.id.unwrap_or(0),
+            collection.name.clone(),
+            collection.total_items,
+            collection.available_items,
+        );
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection updated event: {}", e);
+        }
     }
 
     /// Lists all collections
@@ -494,17 +725,89 @@ impl<E: EventBus + ?Sized> CollectionManager<E> {
         })
     }
 
-    /// Deletes a collection
+    /// Deletes a custom collection with its items
     ///
     /// # Arguments
     /// * `collection_id` - Collection ID to delete
     pub async fn delete_collection(&self, collection_id: i64) -> Result<(), ApplicationError> {
         info!("Deleting collection ID: {}", collection_id);
+        let collection = self.find_custom(collection_id).await?;
+        self.collection_repository.delete_items(collection_id).await?;
         self.collection_repository.delete(collection_id).await?;
+
+        let event = CollectionDeletedEvent::new(collection_id, collection.name);
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection deleted event: {}", e);
+        }
         info!("Collection {} deleted", collection_id);
         Ok(())
     }
 
+    /// Which items of presets are in the library
+    ///
+    /// Items are matched by TMDB ID like when presets are applied.
+    ///
+    /// # Returns
+    /// * `Result<Vec<Vec<bool>>, ApplicationError>` - Availability of each item, per preset
+    pub async fn preset_availability(&self, presets: &[PresetCollection]) -> Result<Vec<Vec<bool>>, ApplicationError> {
+        let movies: std::collections::HashSet<i64> = self.media_repository
+            .find_by_type(crate::domain::value_objects::MediaType::Movie)
+            .await?
+            .iter()
+            .filter_map(|m| m.tmdb_id)
+            .collect();
+        let series: std::collections::HashSet<i64> = self.series_repository
+            .find_all()
+            .await?
+            .iter()
+            .filter_map(|s| s.tmdb_id)
+            .collect();
+
+        Ok(presets.iter()
+            .map(|preset| {
+                preset.items.iter()
+                    .map(|item| if item.media_type == "movie" { &movies } else { &series }.contains(&item.tmdb_id))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Removes the collection made from a preset
+    ///
+    /// The collection is found like when presets are applied: by TMDB
+    /// collection ID first (it is named after TMDB then), then by name.
+    ///
+    /// # Arguments
+    /// * `preset` - The preset the collection was made from
+    ///
+    /// # Returns
+    /// * `Result<bool, ApplicationError>` - Whether a collection of the preset existed
+    pub async fn remove_preset_collection(&self, preset: &PresetCollection) -> Result<bool, ApplicationError> {
+        let by_tmdb = match preset.tmdb_collection_id {
+            Some(tmdb_id) => self.collection_repository.find_by_tmdb_id(tmdb_id).await?,
+            None => None,
+        };
+        let collection = match by_tmdb {
+            Some(collection) => collection,
+            None => match self.collection_repository.find_by_name(&preset.name).await? {
+                Some(collection) => collection,
+                None => return Ok(false),
+            },
+        };
+        let Some(collection_id) = collection.id.filter(|_| collection.is_preset()) else {
+            return Ok(false);
+        };
+        self.collection_repository.delete_items(collection_id).await?;
+        self.collection_repository.delete(collection_id).await?;
+
+        let event = CollectionDeletedEvent::new(collection_id, collection.name);
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection deleted event: {}", e);
+        }
+        info!("Removed collection of preset '{}' (ID: {})", preset.name, collection_id);
+        Ok(true)
+    }
+
     /// Creates and updates preset franchise collections
     ///
     /// This method:
@@ -590,12 +893,13 @@ impl<E: EventBus + ?Sized> CollectionManager<E> {
             let protected_collection_id = existing_collection.as_ref().and_then(|c| c.id);
 
             // Clean up conflicting collections
-            // If any movie in this preset is already in an existing collection (that isn't this preset),
+            // If any movie in this preset is already in a TMDB-detected collection (that isn't this preset),
             // we remove the conflicting collection to ensure the preset takes precedence.
+            // Custom, genre and decade collections may share items with presets.
             let mut conflicting_collections = std::collections::HashSet::new();
             for item in &preset.items {
                 if let Ok(collections) = self.collection_repository.find_collections_by_item_tmdb_id(item.tmdb_id).await {
-                    for col in collections {
+                    for col in collections.into_iter().filter(|c| c.is_auto()) {
                         // Conflict if this collection is NOT the one we are currently processing/updating
                         if let Some(col_id) = col.id {
                             if Some(col_id) != protected_collection_id {
@@ -909,6 +1213,392 @@ impl<E: EventBus + ?Sized> CollectionManager<E> {
 
         Ok(stats)
     }
+
+    /// Recomputes collection counts and item availability from the library
+    ///
+    /// Counts drift when media is deleted outside a scan (the item's media_id
+    /// is cleared but it stays flagged available) or re-identified. Items are
+    /// matched by TMDB ID like during detection; collections without items
+    /// (custom ones) are left alone. With `dry_run` only the report is built.
+    ///
+    /// # Returns
+    /// * `Result<ReconcileReport, ApplicationError>` - Collections that were out of sync
+    pub async fn reconcile_counts(&self, dry_run: bool) -> Result<ReconcileReport, ApplicationError> {
+        let movies = self.media_repository
+            .find_by_type(crate::domain::value_objects::MediaType::Movie)
+            .await?;
+        let movie_by_tmdb: HashMap<i64, Option<i64>> = movies
+            .iter()
+            .filter_map(|m| m.tmdb_id.map(|tid| (tid, m.id)))
+            .collect();
+        let series_by_tmdb: HashMap<i64, Option<i64>> = self.series_repository
+            .find_all()
+            .await?
+            .iter()
+            .filter_map(|s| s.tmdb_id.map(|tid| (tid, s.id)))
+            .collect();
+
+        let mut report = ReconcileReport { dry_run, ..Default::default() };
+        for collection in self.collection_repository.find_all().await? {
+            let Some(collection_id) = collection.id else {
+                continue;
+            };
+            report.collections_checked += 1;
+
+            let items = self.collection_repository.find_items(collection_id).await?;
+            if items.is_empty() {
+                continue;
+            }
+            let changed_items = reconcile_items(&items, &movie_by_tmdb, &series_by_tmdb);
+            let total_items = items.len() as i32;
+            let available_items = items.iter()
+                .map(|item| changed_items.iter().find(|c| c.id == item.id).unwrap_or(item))
+                .filter(|item| item.is_available)
+                .count() as i32;
+
+            let counts_changed = collection.total_items != total_items || collection.available_items != available_items;
+            if !counts_changed && changed_items.is_empty() {
+                continue;
+            }
+            report.items_updated += changed_items.len();
+            report.collections.push(CollectionCountFix {
+                collection_id,
+                name: collection.name.clone(),
+                previous_total: collection.total_items,
+                previous_available: collection.available_items,
+                total_items,
+                available_items,
+                items_updated: changed_items.len(),
+            });
+            if dry_run {
+                continue;
+            }
+
+            for item in &changed_items {
+                self.collection_repository.update_item(item).await?;
+            }
+            if counts_changed {
+                self.collection_repository.update_counts(collection_id, total_items, available_items).await?;
+                let event = CollectionUpdatedEvent::new(collection_id, collection.name, total_items, available_items);
+                if let Err(e) = self.event_bus.publish(event).await {
+                    warn!("Failed to publish collection updated event: {}", e);
+                }
+            }
+        }
+
+        if !report.collections.is_empty() {
+            info!(
+                "Collection reconcile{}: {}/{} collections out of sync, {} items updated",
+                if dry_run { " (dry run)" } else { "" },
+                report.collections.len(), report.collections_checked, report.items_updated
+            );
+        }
+        Ok(report)
+    }
+
+    /// Maintains genre and decade collections of the movie library
+    ///
+    /// Groups movies with a TMDB ID by genre ("Comedy Movies") and release
+    /// decade ("80s Movies"). Groups with at least `MIN_AUTO_COLLECTION_ITEMS`
+    /// movies become collections of type "genre" or "decade", using the
+    /// artwork of their best rated movie; collections of a disabled kind or
+    /// whose group shrank below the minimum are removed.
+    ///
+    /// # Arguments
+    /// * `genres` - Maintain genre collections
+    /// * `decades` - Maintain decade collections
+    pub async fn sync_auto_collections(&self, genres: bool, decades: bool) -> Result<AutoCollectionStats, ApplicationError> {
+        let movies = self.media_repository
+            .find_by_type(crate::domain::value_objects::MediaType::Movie)
+            .await?;
+        let groups = auto_collection_groups(&movies, genres, decades);
+
+        let mut stats = AutoCollectionStats::default();
+        for kind in [AUTO_GENRE, AUTO_DECADE] {
+            let mut existing: HashMap<String, Collection> = self.collection_repository
+                .find_by_type(kind)
+                .await?
+                .into_iter()
+                .map(|c| (c.name.clone(), c))
+                .collect();
+
+            for group in groups.iter().filter(|g| g.kind == kind) {
+                match existing.remove(&group.name) {
+                    Some(collection) => {
+                        if self.update_auto_collection(collection, group).await? {
+                            stats.collections_updated += 1;
+                        }
+                    }
+                    None => {
+                        self.create_auto_collection(group).await?;
+                        stats.collections_created += 1;
+                    }
+                }
+            }
+
+            for collection in existing.into_values() {
+                let Some(collection_id) = collection.id else {
+                    continue;
+                };
+                self.collection_repository.delete_items(collection_id).await?;
+                self.collection_repository.delete(collection_id).await?;
+                let event = CollectionDeletedEvent::new(collection_id, collection.name);
+                if let Err(e) = self.event_bus.publish(event).await {
+                    warn!("Failed to publish collection deleted event: {}", e);
+                }
+                stats.collections_removed += 1;
+            }
+        }
+
+        if stats.collections_created + stats.collections_updated + stats.collections_removed > 0 {
+            info!(
+                "Auto collections: {} created, {} updated, {} removed",
+                stats.collections_created, stats.collections_updated, stats.collections_removed
+            );
+        }
+        Ok(stats)
+    }
+
+    async fn create_auto_collection(&self, group: &AutoCollectionGroup<'_>) -> Result<(), ApplicationError> {
+        let (poster_url, backdrop_url) = group.artwork();
+        let collection = Collection::new(group.name.clone())?
+            .with_description(Some(group.description()))
+            .with_poster_url(poster_url)
+            .with_backdrop_url(backdrop_url)
+            .with_collection_type(group.kind.to_string())
+            .with_sort_mode("timeline".to_string());
+        let collection_id = self.collection_repository.save(&collection).await?;
+        self.collection_repository.save_items(&group.items(collection_id)).await?;
+        let count = group.movies.len() as i32;
+        self.collection_repository.update_counts(collection_id, count, count).await?;
+
+        let event = CollectionCreatedEvent::new(collection_id, group.name.clone(), None, group.kind.to_string());
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish collection created event: {}", e);
+        }
+        debug!("Created {} collection '{}' with {} movies", group.kind, group.name, count);
+        Ok(())
+    }
+
+    /// Replaces the items and artwork of an auto collection if the library changed
+    ///
+    /// # Returns
+    /// * `Result<bool, ApplicationError>` - Whether anything changed
+    async fn update_auto_collection(&self, mut collection: Collection, group: &AutoCollectionGroup<'_>) -> Result<bool, ApplicationError> {
+        let Some(collection_id) = collection.id else {
+            return Ok(false);
+        };
+        let items = group.items(collection_id);
+        let current = self.collection_repository.find_items(collection_id).await?;
+        let items_changed = current.len() != items.len()
+            || current.iter().zip(&items).any(|(a, b)| {
+                a.tmdb_id != b.tmdb_id || a.media_id != b.media_id
+                    || a.timeline_order != b.timeline_order || a.release_order != b.release_order
+            });
+
+        let (poster_url, backdrop_url) = group.artwork();
+        let description = Some(group.description());
+        let details_changed = collection.poster_url != poster_url
+            || collection.backdrop_url != backdrop_url
+            || collection.description != description;
+        if !items_changed && !details_changed {
+            return Ok(false);
+        }
+
+        if items_changed {
+            self.collection_repository.delete_items(collection_id).await?;
+            self.collection_repository.save_items(&items).await?;
+        }
+        let count = items.len() as i32;
+        collection.poster_url = poster_url;
+        collection.backdrop_url = backdrop_url;
+        collection.description = description;
+        collection.update_counts(count, count);
+        self.collection_repository.update(&collection).await?;
+        self.publish_updated(&collection).await;
+        Ok(true)
+    }
+}
+
+/// Collection type of genre collections
+pub const AUTO_GENRE: &str = "genre";
+/// Collection type of decade collections
+pub const AUTO_DECADE: &str = "decade";
+/// Smaller genre and decade groups get no collection
+const MIN_AUTO_COLLECTION_ITEMS: usize = 5;
+
+/// Movies of one genre or decade collection
+#[derive(Debug)]
+struct AutoCollectionGroup<'a> {
+    /// AUTO_GENRE or AUTO_DECADE
+    kind: &'static str,
+    name: String,
+    /// Genre name or decade label ("80s")
+    label: String,
+    /// Best rated first for genres, oldest first for decades
+    movies: Vec<&'a crate::domain::entities::Media>,
+}
+
+impl AutoCollectionGroup<'_> {
+    fn description(&self) -> String {
+        if self.kind == AUTO_GENRE {
+            format!("{} movies in the library", self.label)
+        } else {
+            format!("Movies from the {}", self.label)
+        }
+    }
+
+    /// Poster and backdrop of the best rated movie that has them
+    fn artwork(&self) -> (Option<String>, Option<String>) {
+        let mut by_rating = self.movies.clone();
+        by_rating.sort_by(|a, b| b.rating.unwrap_or(0.0).total_cmp(&a.rating.unwrap_or(0.0)));
+        let poster = by_rating.iter().find_map(|m| m.poster_url.clone());
+        let backdrop = by_rating.iter().find_map(|m| m.backdrop_url.clone());
+        (poster, backdrop)
+    }
+
+    /// Collection items in the group's order, with their release order
+    fn items(&self, collection_id: i64) -> Vec<CollectionItem> {
+        let mut by_release: Vec<usize> = (0..self.movies.len()).collect();
+        by_release.sort_by(|&a, &b| self.movies[a].release_date.cmp(&self.movies[b].release_date));
+        let mut release_order = vec![0; self.movies.len()];
+        for (order, &index) in by_release.iter().enumerate() {
+            release_order[index] = order as i32;
+        }
+
+        self.movies.iter().enumerate()
+            .map(|(index, movie)| CollectionItem {
+                id: 0,
+                collection_id,
+                tmdb_id: movie.tmdb_id.unwrap_or(0),
+                media_type: "movie".to_string(),
+                title: movie.title.clone(),
+                overview: movie.overview.clone(),
+                poster_url: movie.poster_url.clone(),
+                release_date: movie.release_date.clone(),
+                timeline_order: index as i32,
+                release_order: release_order[index],
+                timeline_year: None,
+                timeline_notes: None,
+                is_available: true,
+                media_id: movie.id,
+            })
+            .collect()
+    }
+}
+
+/// Groups movies into genre and decade collections
+///
+/// Movies without a TMDB ID are left out, as reconciling matches items by
+/// TMDB ID. Groups are sorted by name.
+fn auto_collection_groups(
+    movies: &[crate::domain::entities::Media],
+    genres: bool,
+    decades: bool,
+) -> Vec<AutoCollectionGroup<'_>> {
+    let mut by_genre: HashMap<String, Vec<&crate::domain::entities::Media>> = HashMap::new();
+    let mut by_decade: HashMap<i32, Vec<&crate::domain::entities::Media>> = HashMap::new();
+    for movie in movies.iter().filter(|m| m.tmdb_id.is_some()) {
+        if genres {
+            for genre in movie.genres.as_deref().unwrap_or("").split(',').map(str::trim).filter(|g| !g.is_empty()) {
+                by_genre.entry(genre.to_string()).or_default().push(movie);
+            }
+        }
+        if decades {
+            let year = movie.release_date.as_deref().and_then(|d| d.get(..4)).and_then(|y| y.parse::<i32>().ok());
+            if let Some(year) = year {
+                by_decade.entry(year - year.rem_euclid(10)).or_default().push(movie);
+            }
+        }
+    }
+
+    let mut groups: Vec<AutoCollectionGroup> = Vec::new();
+    for (genre, mut movies) in by_genre {
+        if movies.len() < MIN_AUTO_COLLECTION_ITEMS {
+            continue;
+        }
+        movies.sort_by(|a, b| {
+            b.rating.unwrap_or(0.0).total_cmp(&a.rating.unwrap_or(0.0)).then_with(|| a.title.cmp(&b.title))
+        });
+        groups.push(AutoCollectionGroup { kind: AUTO_GENRE, name: format!("{} Movies", genre), label: genre, movies });
+    }
+    for (decade, mut movies) in by_decade {
+        if movies.len() < MIN_AUTO_COLLECTION_ITEMS {
+            continue;
+        }
+        movies.sort_by(|a, b| a.release_date.cmp(&b.release_date).then_with(|| a.title.cmp(&b.title)));
+        let label = decade_label(decade);
+        groups.push(AutoCollectionGroup { kind: AUTO_DECADE, name: format!("{} Movies", label), label, movies });
+    }
+    groups.sort_by(|a, b| a.kind.cmp(b.kind).then_with(|| a.name.cmp(&b.name)));
+    groups
+}
+
+/// "80s" for 1980, "2010s" for 2010; full years where two digits are ambiguous
+fn decade_label(decade: i32) -> String {
+    if (1930..2000).contains(&decade) {
+        format!("{}s", decade % 100)
+    } else {
+        format!("{}s", decade)
+    }
+}
+
+/// Items whose availability or media link differ from the library
+///
+/// Lookups map TMDB IDs to library IDs: movie items link media, series
+/// items link the series (as when presets are created).
+fn reconcile_items(
+    items: &[CollectionItem],
+    movie_by_tmdb: &HashMap<i64, Option<i64>>,
+    series_by_tmdb: &HashMap<i64, Option<i64>>,
+) -> Vec<CollectionItem> {
+    items.iter()
+        .filter_map(|item| {
+            let library = if item.media_type == "movie" { movie_by_tmdb } else { series_by_tmdb };
+            let (is_available, media_id) = match library.get(&item.tmdb_id) {
+                Some(id) => (true, *id),
+                None => (false, None),
+            };
+            (item.is_available != is_available || item.media_id != media_id).then(|| CollectionItem {
+                is_available,
+                media_id,
+                ..item.clone()
+            })
+        })
+        .collect()
+}
+
+/// Result of a collection count reconcile
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconcileReport {
+    /// Whether changes were only reported
+    pub dry_run: bool,
+    /// Collections examined
+    pub collections_checked: usize,
+    /// Collection items whose availability was corrected
+    pub items_updated: usize,
+    /// Collections that were out of sync
+    pub collections: Vec<CollectionCountFix>,
+}
+
+/// Corrected counts of one collection
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionCountFix {
+    pub collection_id: i64,
+    pub name: String,
+    pub previous_total: i32,
+    pub previous_available: i32,
+    pub total_items: i32,
+    pub available_items: i32,
+    pub items_updated: usize,
+}
+
+/// Statistics from genre and decade collection maintenance
+#[derive(Debug, Clone, Default)]
+pub struct AutoCollectionStats {
+    pub collections_created: usize,
+    pub collections_updated: usize,
+    pub collections_removed: usize,
 }
 
 /// Statistics from preset collection creation
@@ -939,3 +1629,74 @@ pub struct CollectionDetail {
     /// Number of media in collection
     pub media_count: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: i64, tmdb_id: i64, media_type: &str, is_available: bool, media_id: Option<i64>) -> CollectionItem {
+        CollectionItem {
+            id,
+            collection_id: 1,
+            tmdb_id,
+            media_type: media_type.to_string(),
+            title: format!("Item {}", id),
+            overview: None,
+            poster_url: None,
+            release_date: None,
+            timeline_order: id as i32,
+            release_order: id as i32,
+            timeline_year: None,
+            timeline_notes: None,
+            is_available,
+            media_id,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_items() {
+        let movies = HashMap::from([(100, Some(10)), (101, Some(12))]);
+        let series = HashMap::from([(200, Some(3))]);
+        let items = vec![
+            // In sync
+            item(1, 100, "movie", true, Some(10)),
+            // Media deleted: link cleared by the database, flag left set
+            item(2, 102, "movie", true, None),
+            // Added to the library since
+            item(3, 101, "movie", false, None),
+            item(4, 200, "tv", false, None),
+        ];
+
+        let changed = reconcile_items(&items, &movies, &series);
+
+        let summary: Vec<_> = changed.iter().map(|i| (i.id, i.is_available, i.media_id)).collect();
+        assert_eq!(summary, vec![(2, false, None), (3, true, Some(12)), (4, true, Some(3))]);
+    }
+
+    #[test]
+    fn test_auto_collection_groups() {
+        let movie = |id: i64, date: &str, genres: &str, rating: f32| crate::domain::entities::Media {
+            id: Some(id),
+            tmdb_id: Some(100 + id),
+            release_date: Some(date.to_string()),
+            genres: Some(genres.to_string()),
+            rating: Some(rating),
+            ..crate::domain::entities::Media::new(format!("/movies/{}.mkv", id), crate::domain::value_objects::MediaType::Movie, format!("Movie {}", id)).unwrap()
+        };
+        let mut movies: Vec<_> = (1..=5).map(|id| movie(id, &format!("198{}-01-01", id), "Action, Comedy", id as f32)).collect();
+        movies.push(movie(6, "2012-05-04", "Action", 9.5));
+        movies.push(crate::domain::entities::Media { tmdb_id: None, ..movie(7, "1985-01-01", "Action", 1.0) });
+
+        let groups = auto_collection_groups(&movies, true, true);
+
+        let names: Vec<_> = groups.iter().map(|g| (g.kind, g.name.as_str(), g.movies.len())).collect();
+        assert_eq!(names, vec![("decade", "80s Movies", 5), ("genre", "Action Movies", 6), ("genre", "Comedy Movies", 5)]);
+        // Genres list the best rated first, decades the oldest
+        assert_eq!(groups[1].movies[0].id, Some(6));
+        assert_eq!(groups[0].movies[0].id, Some(1));
+        let items = groups[1].items(9);
+        assert_eq!((items[0].timeline_order, items[0].release_order), (0, 5));
+        assert_eq!(decade_label(2010), "2010s");
+        assert!(auto_collection_groups(&movies, false, false).is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Content Rating Backfill
+//!
+//! Fetches TMDB certifications ("PG-13", "TV-MA") for movies and series that
+//! do not have one yet, for parental controls. Episodes inherit the rating of
+//! their series.
+
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::interfaces::external_services::TmdbContentRatingFetcher;
+use crate::shared::error::ApplicationError;
+
+/// Items fetched from the repositories per batch
+const BATCH_SIZE: usize = 100;
+
+/// Content Rating Backfill
+///
+/// # Architecture Notes
+/// - Titles without a certification are stored with an empty rating so they
+///   are not fetched again on every run
+/// - Ratings are cleared by the repositories when the TMDB match changes,
+///   which puts the item back into the backfill queue
+pub struct ContentRatingBackfill {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    fetcher: Arc<dyn TmdbContentRatingFetcher>,
+}
+
+impl ContentRatingBackfill {
+    /// Creates a new content rating backfill
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        fetcher: Arc<dyn TmdbContentRatingFetcher>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            fetcher,
+        }
+    }
+
+    /// Fetches missing content ratings of series, movies and episodes
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of ratings stored
+    ///
+    /// # Errors
+    /// Returns error if the repositories fail. TMDB failures for individual
+    /// titles are logged and retried on the next run.
+    pub async fn backfill_library(&self) -> Result<usize, ApplicationError> {
+        let mut stored = 0;
+
+        // Series first, so their episodes can inherit the rating below
+        loop {
+            let batch = self.series_repository.find_missing_content_ratings(BATCH_SIZE).await?;
+            let mut progressed = false;
+            for series in &batch {
+                let (Some(id), Some(tmdb_id)) = (series.id, series.tmdb_id) else { continue };
+                match self.fetcher.fetch_tv_content_rating(tmdb_id).await {
+                    Ok(info) => {
+                        let rating = info.rating.unwrap_or_default();
+                        self.series_repository.update_content_rating(id, &rating).await?;
+                        stored += 1;
+                        progressed = true;
+                    }
+                    Err(e) => warn!("Failed to fetch content rating of series {}: {}", id, e),
+                }
+            }
+            // Stop when only failures are left
+            if batch.len() < BATCH_SIZE || !progressed {
+                break;
+            }
+        }
+
+        loop {
+            let batch = self.media_repository.find_missing_content_ratings(BATCH_SIZE).await?;
+            let mut progressed = false;
+            for media in &batch {
+                let Some(id) = media.id else { continue };
+                let (rating, warnings) = match media.series_id {
+                    Some(series_id) => {
+                        let Some(series) = self.series_repository.find_by_id(series_id).await? else { continue };
+                        (series.content_rating.unwrap_or_default(), None)
+                    }
+                    None => {
+                        let Some(tmdb_id) = media.tmdb_id else { continue };
+                        match self.fetcher.fetch_movie_content_rating(tmdb_id).await {
+                            Ok(info) => {
+                                let warnings = (!info.descriptors.is_empty()).then(|| info.descriptors.join(", "));
+                                (info.rating.unwrap_or_default(), warnings)
+                            }
+                            Err(e) => {
+                                warn!("Failed to fetch content rating of media {}: {}", id, e);
+                                continue;
+                            }
+                        }
+                    }
+                };
+                self.media_repository
+                    .update_content_rating(id, &rating, warnings.as_deref())
+                    .await?;
+                stored += 1;
+                progressed = true;
+            }
+            if batch.len() < BATCH_SIZE || !progressed {
+                break;
+            }
+        }
+
+        if stored > 0 {
+            info!("Content rating backfill complete: {} ratings stored", stored);
+        }
+        Ok(stored)
+    }
+}
@@ -0,0 +1,133 @@
+//! Crop Detection
+//!
+//! Finds the black bars of letterboxed video so clients, or the web
+//! transcoder on request, can remove them. Detection samples a few seconds
+//! at several points of the video and is stored per media item. It runs on
+//! request, or in the background the first time playback info is asked for
+//! when automatic detection is enabled.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::domain::repositories::{CropDetection, CropRepository};
+use crate::interfaces::external_services::{CropDetector, VideoAnalyzer};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Crop detection and lookup
+pub struct CropDetectionService {
+    detector: Arc<dyn CropDetector>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    repository: Arc<dyn CropRepository>,
+    /// Detect in the background when a stored result is missing
+    auto_detect: bool,
+    /// Media items being detected
+    detecting: Arc<Mutex<HashSet<i64>>>,
+    /// Detections decode video, so they run one at a time
+    permits: Arc<Semaphore>,
+}
+
+impl CropDetectionService {
+    pub fn new(
+        detector: Arc<dyn CropDetector>,
+        video_analyzer: Arc<dyn VideoAnalyzer>,
+        repository: Arc<dyn CropRepository>,
+    ) -> Self {
+        Self {
+            detector,
+            video_analyzer,
+            repository,
+            auto_detect: false,
+            detecting: Arc::new(Mutex::new(HashSet::new())),
+            permits: Arc::new(Semaphore::new(1)),
+        }
+    }
+
+    /// Detects crops in the background for media without a stored one
+    pub fn with_auto_detect(mut self, enabled: bool) -> Self {
+        self.auto_detect = enabled;
+        self
+    }
+
+    /// Returns the stored crop of a media item
+    ///
+    /// Starts detecting it in the background if automatic detection is
+    /// enabled and none is stored yet.
+    pub async fn crop(&self, media_id: i64, file_path: &str) -> Option<CropDetection> {
+        match self.repository.find(media_id).await {
+            Ok(Some(crop)) => return Some(crop),
+            Ok(None) if self.auto_detect => self.detect_in_background(media_id, file_path),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load crop of media {}: {}", media_id, e),
+        }
+        None
+    }
+
+    /// Detects and stores the crop of a media item now
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` when the file has no analyzable video
+    pub async fn detect(&self, media_id: i64, file_path: &str) -> Result<CropDetection, ApplicationError> {
+        let _permit = self.permits.acquire().await
+            .map_err(|e| ApplicationError::Internal(e.to_string()))?;
+        let crop = run_detection(&*self.detector, &*self.video_analyzer, file_path).await?;
+        self.repository.save(media_id, &crop).await?;
+        log_detection(media_id, &crop);
+        Ok(crop)
+    }
+
+    fn detect_in_background(&self, media_id: i64, file_path: &str) {
+        if !self.detecting.lock().unwrap_or_else(|e| e.into_inner()).insert(media_id) {
+            return;
+        }
+
+        let detector = self.detector.clone();
+        let video_analyzer = self.video_analyzer.clone();
+        let repository = self.repository.clone();
+        let detecting = self.detecting.clone();
+        let permits = self.permits.clone();
+        let file_path = file_path.to_string();
+        tokio::spawn(async move {
+            if let Ok(_permit) = permits.acquire().await {
+                match run_detection(&*detector, &*video_analyzer, &file_path).await {
+                    Ok(crop) => {
+                        log_detection(media_id, &crop);
+                        if let Err(e) = repository.save(media_id, &crop).await {
+                            warn!("Failed to save crop of media {}: {}", media_id, e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to detect crop of media {}: {}", media_id, e),
+                }
+            }
+            detecting.lock().unwrap_or_else(|e| e.into_inner()).remove(&media_id);
+        });
+    }
+}
+
+async fn run_detection(
+    detector: &dyn CropDetector,
+    video_analyzer: &dyn VideoAnalyzer,
+    file_path: &str,
+) -> Result<CropDetection, ApplicationError> {
+    let analysis = video_analyzer.analyze(file_path).await?;
+    if analysis.width == 0 || analysis.height == 0 {
+        return Err(ApplicationError::Domain(DomainError::InvalidInput(format!("{} has no video", file_path))));
+    }
+    detector
+        .detect(file_path, analysis.duration_seconds, analysis.width, analysis.height)
+        .await?
+        .ok_or_else(|| ApplicationError::Domain(DomainError::InvalidInput(format!(
+            "No picture found in the samples of {}", file_path
+        ))))
+}
+
+fn log_detection(media_id: i64, crop: &CropDetection) {
+    info!(
+        "Detected crop of media {}: {} ({:.2}:1{})",
+        media_id,
+        crop.filter(),
+        crop.aspect_ratio(),
+        if crop.has_bars() { "" } else { ", no bars" }
+    );
+}
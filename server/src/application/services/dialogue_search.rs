@@ -0,0 +1,232 @@
+//! Dialogue Search
+//!
+//! Finds the movies and episodes in which something is said, with the
+//! times of the matching cues so playback can start at the scene. The cues
+//! of every subtitle file of a media item (downloaded, extracted or
+//! generated) are indexed; files are read again only when their size or
+//! modification time changes.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::{DialogueCue, DialogueRepository, MediaRepository, SubtitleSource};
+use crate::domain::value_objects::MediaType;
+use crate::infrastructure::subtitle::{read_subtitle_file, SubtitleOptions, SubtitleStore, TagPolicy};
+use crate::shared::error::ApplicationError;
+
+/// Matching cues looked at per search
+const CUE_CANDIDATES: usize = 500;
+/// Matching cues returned per title
+const CUES_PER_TITLE: usize = 5;
+
+/// A cue matching a dialogue search
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatchedCue {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Language of the subtitle the cue is from
+    pub language: Option<String>,
+    pub text: String,
+}
+
+/// A title with the cues matching a dialogue search, in playback order
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueHit {
+    pub media_id: i64,
+    pub cues: Vec<MatchedCue>,
+}
+
+/// Dialogue search over subtitle cues
+pub struct DialogueSearch {
+    media_repository: Arc<dyn MediaRepository>,
+    dialogue_repository: Arc<dyn DialogueRepository>,
+    subtitle_store: Arc<SubtitleStore>,
+    indexing: Mutex<()>,
+}
+
+impl DialogueSearch {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        dialogue_repository: Arc<dyn DialogueRepository>,
+        subtitle_store: Arc<SubtitleStore>,
+    ) -> Self {
+        Self {
+            media_repository,
+            dialogue_repository,
+            subtitle_store,
+            indexing: Mutex::new(()),
+        }
+    }
+
+    /// Indexes the new and changed subtitle files of all movies and
+    /// episodes, and drops the files that are gone
+    ///
+    /// Returns the number of files indexed.
+    pub async fn index_library(&self) -> Result<usize, ApplicationError> {
+        let _guard = self.indexing.lock().await;
+        let mut media = self.media_repository.find_by_type(MediaType::Movie).await?;
+        media.extend(self.media_repository.find_by_type(MediaType::Episode).await?);
+
+        let indexed = self.index(&media).await?;
+        if indexed > 0 {
+            info!("Indexed the dialogue of {} subtitle files", indexed);
+        }
+        Ok(indexed)
+    }
+
+    /// Indexes the subtitle files of one media item
+    pub async fn index_media(&self, media_id: i64) -> Result<usize, ApplicationError> {
+        let _guard = self.indexing.lock().await;
+        match self.media_repository.find_by_id(media_id).await? {
+            Some(media) => self.index(&[media]).await,
+            None => Ok(0),
+        }
+    }
+
+    async fn index(&self, media: &[Media]) -> Result<usize, ApplicationError> {
+        let media_ids: HashSet<i64> = media.iter().filter_map(|m| m.id).collect();
+        let mut known: HashMap<String, SubtitleSource> = self
+            .dialogue_repository
+            .find_sources()
+            .await?
+            .into_iter()
+            .filter(|source| media_ids.contains(&source.media_id))
+            .map(|source| (source.path.clone(), source))
+            .collect();
+
+        let mut indexed = 0;
+        for item in media {
+            let Some(media_id) = item.id else { continue };
+            for subtitle in self.subtitle_store.detector(media_id).discover(Path::new(&item.file_path)) {
+                let previous = known.remove(&subtitle.file_path);
+                let Some(signature) = signature(Path::new(&subtitle.file_path)) else { continue };
+                if previous.is_some_and(|source| source.signature == signature) {
+                    continue;
+                }
+
+                let options = SubtitleOptions::new(TagPolicy::Strip).with_language(subtitle.language.clone());
+                let cues: Vec<DialogueCue> = match read_subtitle_file(&subtitle.file_path, &options) {
+                    Ok(parsed) => parsed
+                        .cues
+                        .into_iter()
+                        .map(|cue| DialogueCue { start: cue.start, end: cue.end, text: cue.text })
+                        .collect(),
+                    Err(e) => {
+                        debug!("Skipping subtitle {} for dialogue search: {}", subtitle.file_path, e);
+                        Vec::new()
+                    }
+                };
+                let source = SubtitleSource {
+                    media_id,
+                    path: subtitle.file_path,
+                    language: subtitle.language,
+                    signature,
+                };
+                self.dialogue_repository.replace_source(&source, &cues).await?;
+                indexed += 1;
+            }
+        }
+
+        // Files of these media that were not found again
+        for path in known.into_keys() {
+            self.dialogue_repository.delete_source(&path).await?;
+        }
+        Ok(indexed)
+    }
+
+    /// Titles in which the query is said, best match first
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<DialogueHit>, ApplicationError> {
+        let matches = self.dialogue_repository.search(query, CUE_CANDIDATES).await?;
+
+        let mut hits: Vec<DialogueHit> = Vec::new();
+        for found in matches {
+            let cue = MatchedCue {
+                start: found.start,
+                end: found.end,
+                language: found.language,
+                text: found.text,
+            };
+            match hits.iter_mut().find(|hit| hit.media_id == found.media_id) {
+                Some(hit) => {
+                    if hit.cues.len() < CUES_PER_TITLE {
+                        hit.cues.push(cue);
+                    }
+                }
+                None => hits.push(DialogueHit { media_id: found.media_id, cues: vec![cue] }),
+            }
+        }
+        for hit in &mut hits {
+            hit.cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+        }
+        hits.truncate(limit);
+        Ok(hits)
+    }
+}
+
+/// Size and modification time of a file, None if it can't be read
+fn signature(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(format!("{}:{}", metadata.len(), modified.as_millis()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{SqliteDialogueRepository, SqliteMediaRepository};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_index_and_search_subtitles() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("Show.S01E01.mkv");
+        std::fs::write(&video, b"").unwrap();
+        let subtitle = dir.path().join("Show.S01E01.en.srt");
+        std::fs::write(
+            &subtitle,
+            "1\n00:00:01,000 --> 00:00:03,000\n<i>Winter is coming.</i>\n\n\
+             2\n00:10:00,000 --> 00:10:02,000\nWinter is coming, they say.\n\n",
+        )
+        .unwrap();
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repository = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let media = Media::new(video.to_string_lossy().into_owned(), MediaType::Episode, "Pilot".to_string()).unwrap();
+        let media_id = media_repository.save(&media).await.unwrap();
+
+        let search = DialogueSearch::new(
+            media_repository,
+            Arc::new(SqliteDialogueRepository::new(pool)),
+            Arc::new(SubtitleStore::new(&dir.path().join("data").to_string_lossy())),
+        );
+        assert_eq!(search.index_library().await.unwrap(), 1);
+        // Unchanged files are not read again
+        assert_eq!(search.index_media(media_id).await.unwrap(), 0);
+
+        let hits = search.search("winter is com", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].media_id, media_id);
+        let starts: Vec<f64> = hits[0].cues.iter().map(|c| c.start).collect();
+        assert_eq!(starts, vec![1.0, 600.0]);
+        assert_eq!(hits[0].cues[0].text, "Winter is coming.");
+        assert_eq!(hits[0].cues[0].language.as_deref(), Some("en"));
+
+        // Removed files leave the index
+        std::fs::remove_file(&subtitle).unwrap();
+        search.index_library().await.unwrap();
+        assert!(search.search("winter", 10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! fanart.tv Enricher
+//!
+//! Fetches logos, clearart and disc art for library movies and series from
+//! fanart.tv, stores the picks, and mirrors the images into local storage.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::domain::repositories::{
+    ArtworkOwner, ArtworkRepository, ExtraArtwork, MediaRepository, SeriesRepository,
+};
+use crate::domain::value_objects::MediaType;
+use crate::interfaces::external_services::{
+    ArtworkKind, ArtworkMirror, FanartArtwork, FanartService, TmdbExternalIdsFetcher,
+};
+use crate::shared::error::ApplicationError;
+
+/// Items are re-checked after this many days (new artwork gets uploaded over time)
+const RECHECK_AFTER_DAYS: i64 = 30;
+
+/// fanart.tv Enricher
+///
+/// # Architecture Notes
+/// - Movies are looked up by TMDB ID, series by their TVDB ID (resolved via TMDB)
+/// - Items without artwork are stored as empty records so they are not
+///   re-requested on every scan
+pub struct FanartEnricher {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    artwork_repository: Arc<dyn ArtworkRepository>,
+    fanart_service: Arc<dyn FanartService>,
+    external_ids: Arc<dyn TmdbExternalIdsFetcher>,
+    artwork_mirror: Option<Arc<dyn ArtworkMirror>>,
+    language: Option<String>,
+}
+
+impl FanartEnricher {
+    /// Creates a new fanart.tv enricher
+    ///
+    /// # Arguments
+    /// * `language` - Preferred artwork language; region suffixes are ignored ("hu-HU" -> "hu")
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        artwork_repository: Arc<dyn ArtworkRepository>,
+        fanart_service: Arc<dyn FanartService>,
+        external_ids: Arc<dyn TmdbExternalIdsFetcher>,
+        language: Option<String>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            artwork_repository,
+            fanart_service,
+            external_ids,
+            artwork_mirror: None,
+            language: language.and_then(|l| l.split('-').next().map(str::to_lowercase)),
+        }
+    }
+
+    /// Mirrors fetched artwork into local storage
+    pub fn with_artwork_mirror(mut self, artwork_mirror: Arc<dyn ArtworkMirror>) -> Self {
+        self.artwork_mirror = Some(artwork_mirror);
+        self
+    }
+
+    /// Fetches artwork for movies and series not checked recently
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of items that received artwork
+    ///
+    /// # Errors
+    /// Returns error if the library cannot be listed. Failures for individual
+    /// items are logged and skipped.
+    pub async fn enrich_library(&self) -> Result<usize, ApplicationError> {
+        let mut enriched = 0;
+
+        let fresh: HashSet<i64> = self.artwork_repository
+            .find_fresh_ids(ArtworkOwner::Media, RECHECK_AFTER_DAYS).await?
+            .into_iter()
+            .collect();
+        for media in self.media_repository.find_by_type(MediaType::Movie).await? {
+            let (Some(id), Some(tmdb_id)) = (media.id, media.tmdb_id) else {
+                continue;
+            };
+            if fresh.contains(&id) {
+                continue;
+            }
+            match self.fanart_service.fetch_movie_artwork(tmdb_id, self.language.as_deref()).await {
+                Ok(artwork) => {
+                    if self.store(ArtworkOwner::Media, id, artwork).await? {
+                        enriched += 1;
+                    }
+                }
+                Err(e) => warn!("Failed to fetch fanart.tv artwork for movie {}: {}", id, e),
+            }
+        }
+
+        let fresh: HashSet<i64> = self.artwork_repository
+            .find_fresh_ids(ArtworkOwner::Series, RECHECK_AFTER_DAYS).await?
+            .into_iter()
+            .collect();
+        for series in self.series_repository.find_all().await? {
+            let (Some(id), Some(tmdb_id)) = (series.id, series.tmdb_id) else {
+                continue;
+            };
+            if fresh.contains(&id) {
+                continue;
+            }
+            let tvdb_id = match self.external_ids.fetch_tvdb_id(tmdb_id).await {
+                Ok(Some(tvdb_id)) => tvdb_id,
+                Ok(None) => {
+                    debug!("Series {} has no TVDB ID, skipping fanart.tv", id);
+                    self.store(ArtworkOwner::Series, id, FanartArtwork::default()).await?;
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Failed to resolve TVDB ID for series {}: {}", id, e);
+                    continue;
+                }
+            };
+            match self.fanart_service.fetch_tv_artwork(tvdb_id, self.language.as_deref()).await {
+                Ok(artwork) => {
+                    if self.store(ArtworkOwner::Series, id, artwork).await? {
+                        enriched += 1;
+                    }
+                }
+                Err(e) => warn!("Failed to fetch fanart.tv artwork for series {}: {}", id, e),
+            }
+        }
+
+        info!("fanart.tv enrichment complete: {} items received artwork", enriched);
+        Ok(enriched)
+    }
+
+    /// Stores and mirrors artwork, returning whether anything was found
+    async fn store(&self, owner: ArtworkOwner, id: i64, artwork: FanartArtwork) -> Result<bool, ApplicationError> {
+        let found = !artwork.is_empty();
+        let extra = ExtraArtwork {
+            logo_url: artwork.clearlogo,
+            clearart_url: artwork.clearart,
+            disc_url: artwork.disc,
+        };
+
+        if let Some(mirror) = &self.artwork_mirror {
+            let images = [
+                (&extra.logo_url, ArtworkKind::Logo),
+                (&extra.clearart_url, ArtworkKind::ClearArt),
+                (&extra.disc_url, ArtworkKind::Disc),
+            ];
+            for (url, kind) in images {
+                if let Some(url) = url {
+                    mirror.mirror(url, kind).await;
+                }
+            }
+        }
+
+        self.artwork_repository.save(owner, id, &extra).await?;
+        Ok(found)
+    }
+}
@@ -0,0 +1,620 @@
+//! HLS Sessions
+//!
+//! Tracks HLS playback sessions: builds the master and media playlists,
+//! starts (and restarts, after seeks) transcoders as players request
+//! segments, and removes the segment directories of sessions that went idle.
+//! Subtitles are offered as WebVTT renditions segmented like the video.
+//! With a [`TranscodeCache`], finished segments outlive their session and
+//! are served again when the same file is played with the same profile.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::process::Child;
+use tracing::{debug, info, warn};
+
+use crate::infrastructure::cache::TranscodeCache;
+use crate::infrastructure::subtitle::{Cue, VttSegmenter};
+use crate::interfaces::external_services::{HlsTranscodeRequest, HlsTranscoder, HlsVariant};
+use crate::shared::error::TranscodeError;
+
+/// A request this many segments past the transcoder's progress restarts it
+/// at the requested position instead of waiting
+const MAX_SEGMENT_LOOKAHEAD: u32 = 5;
+
+/// How often a pending segment is checked for
+const SEGMENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Running transcoder of one variant
+struct TranscodeJob {
+    process: Child,
+    start_segment: u32,
+    /// Highest segment known to exist (speeds up progress checks)
+    produced: Option<u32>,
+}
+
+/// Playback session state
+struct HlsSession {
+    media_id: i64,
+    file_path: String,
+    duration_seconds: f64,
+    source_size: (u32, u32),
+    audio_track: u32,
+    variants: Vec<HlsVariant>,
+    jobs: HashMap<String, TranscodeJob>,
+    subtitles: Vec<HlsSubtitle>,
+    /// Parsed cues of the subtitles, by subtitle index
+    subtitle_cues: HashMap<usize, Arc<Vec<Cue>>>,
+    last_access: Instant,
+}
+
+/// Subtitle rendition of a session
+#[derive(Debug, Clone)]
+pub struct HlsSubtitle {
+    /// Subtitle index, as in `/v2/subtitles/:media_id/:index`
+    pub index: usize,
+    /// Name shown by players
+    pub name: String,
+    /// Language code
+    pub language: Option<String>,
+    pub is_default: bool,
+    /// Only covers foreign-language dialogue and signs
+    pub forced: bool,
+    /// For the deaf and hard of hearing (SDH)
+    pub hearing_impaired: bool,
+}
+
+/// Summary of an active session
+#[derive(Debug, Clone, Serialize)]
+pub struct HlsSessionInfo {
+    pub session_id: String,
+    pub media_id: i64,
+    pub variants: Vec<String>,
+    pub active_transcodes: usize,
+    pub idle_seconds: u64,
+}
+
+/// HLS session manager
+///
+/// # Architecture Notes
+/// - Playlists are generated from the media duration, so players see the
+///   whole timeline immediately and can seek before segments exist
+/// - Each session gets its own directory under the root; it is deleted
+///   when the session stops or idles out
+/// - Finished segments are copied into the transcode cache (when set), keyed
+///   by media ID and a profile built from the variant, audio track and
+///   segment length
+pub struct HlsSessionManager {
+    transcoder: Arc<dyn HlsTranscoder>,
+    root_dir: PathBuf,
+    segment_seconds: u32,
+    idle_timeout: Duration,
+    segment_wait: Duration,
+    sessions: Mutex<HashMap<String, HlsSession>>,
+    cache: Option<Arc<TranscodeCache>>,
+}
+
+impl HlsSessionManager {
+    /// Creates a new session manager
+    ///
+    /// # Defaults
+    /// - Segment length: 6s
+    /// - Idle timeout: 5 minutes
+    /// - Segment wait: 30s
+    pub fn new(transcoder: Arc<dyn HlsTranscoder>, root_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            transcoder,
+            root_dir: root_dir.into(),
+            segment_seconds: 6,
+            idle_timeout: Duration::from_secs(300),
+            segment_wait: Duration::from_secs(30),
+            sessions: Mutex::new(HashMap::new()),
+            cache: None,
+        }
+    }
+
+    /// Sets how long a session may go without requests before it is removed
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sets how long a segment request waits for the transcoder
+    pub fn with_segment_wait(mut self, wait: Duration) -> Self {
+        self.segment_wait = wait;
+        self
+    }
+
+    /// Keeps finished segments in a transcode cache
+    pub fn with_cache(mut self, cache: Arc<TranscodeCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Removes segment directories left over from a previous run
+    pub fn clear_stale(&self) -> Result<(), TranscodeError> {
+        if self.root_dir.exists() {
+            std::fs::remove_dir_all(&self.root_dir)?;
+        }
+        std::fs::create_dir_all(&self.root_dir)?;
+        Ok(())
+    }
+
+    /// Starts a new session
+    ///
+    /// # Arguments
+    /// * `source_size` - Source frame size (width, height)
+    ///
+    /// # Returns
+    /// * Session ID used in playlist and segment URLs
+    pub fn create_session(
+        &self,
+        media_id: i64,
+        file_path: String,
+        duration_seconds: f64,
+        source_size: (u32, u32),
+        audio_track: u32,
+    ) -> Result<String, TranscodeError> {
+        if duration_seconds <= 0.0 {
+            return Err(TranscodeError::InvalidRequest("Unknown media duration".to_string()));
+        }
+
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.validate_source(media_id, &file_path) {
+                warn!("Failed to check cached segments of media {}: {}", media_id, e);
+            }
+        }
+
+        let session_id = uuid::Uuid::new_v4().simple().to_string();
+        std::fs::create_dir_all(self.root_dir.join(&session_id))?;
+
+        let session = HlsSession {
+            media_id,
+            file_path,
+            duration_seconds,
+            source_size,
+            audio_track,
+            variants: HlsVariant::ladder(source_size.1),
+            jobs: HashMap::new(),
+            subtitles: Vec::new(),
+            subtitle_cues: HashMap::new(),
+            last_access: Instant::now(),
+        };
+        info!("HLS session {} started for media {} ({} variants)", session_id, media_id, session.variants.len());
+        self.lock().insert(session_id.clone(), session);
+
+        Ok(session_id)
+    }
+
+    /// Offers subtitles with the session's variants
+    pub fn set_subtitles(&self, session_id: &str, subtitles: Vec<HlsSubtitle>) -> Result<(), TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        session.subtitles = subtitles;
+        session.subtitle_cues.clear();
+        Ok(())
+    }
+
+    /// Builds the master playlist of a session
+    ///
+    /// Variant URIs are relative: `{session_id}/{variant}/index.m3u8`;
+    /// subtitle renditions are at `{session_id}/subs/{index}/index.m3u8`.
+    pub fn master_playlist(&self, session_id: &str) -> Result<String, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        let (source_width, source_height) = session.source_size;
+
+        let mut playlist = String::from("#EXTM3U\n#EXT-X-VERSION:3\n");
+        for subtitle in &session.subtitles {
+            let language = subtitle.language.as_ref()
+                .map(|l| format!(",LANGUAGE=\"{}\"", quoted(l)))
+                .unwrap_or_default();
+            let forced = if subtitle.forced { ",FORCED=YES" } else { "" };
+            let characteristics = if subtitle.hearing_impaired {
+                ",CHARACTERISTICS=\"public.accessibility.transcribes-spoken-dialog,public.accessibility.describes-music-and-sound\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                playlist,
+                "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"{}\"{},DEFAULT={},AUTOSELECT=YES{}{},URI=\"{}/subs/{}/index.m3u8\"",
+                quoted(&subtitle.name),
+                language,
+                if subtitle.is_default { "YES" } else { "NO" },
+                forced,
+                characteristics,
+                session_id,
+                subtitle.index
+            );
+        }
+        let subtitle_group = if session.subtitles.is_empty() { "" } else { ",SUBTITLES=\"subs\"" };
+        for variant in &session.variants {
+            let height = variant.height.unwrap_or(source_height);
+            let width = if source_height > 0 {
+                (source_width as u64 * height as u64 / source_height as u64) as u32 & !1
+            } else {
+                source_width
+            };
+            let _ = writeln!(
+                playlist,
+                "#EXT-X-STREAM-INF:BANDWIDTH={},RESOLUTION={}x{},CODECS=\"avc1.640028,mp4a.40.2\"{}\n{}/{}/index.m3u8",
+                variant.bandwidth(), width, height, subtitle_group, session_id, variant.name
+            );
+        }
+        Ok(playlist)
+    }
+
+    /// Builds the media playlist of a variant
+    ///
+    /// Segment URIs are relative to the playlist (`seg_00000.ts`).
+    pub fn media_playlist(&self, session_id: &str, variant: &str) -> Result<String, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        Self::variant(session, variant)?;
+
+        let count = self.segment_count(session.duration_seconds);
+        let mut playlist = format!(
+            "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{}\n#EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n",
+            self.segment_seconds
+        );
+        for index in 0..count {
+            let start = index as f64 * self.segment_seconds as f64;
+            let length = (session.duration_seconds - start).min(self.segment_seconds as f64);
+            let _ = writeln!(playlist, "#EXTINF:{:.3},\n{}", length, HlsTranscodeRequest::segment_file_name(index));
+        }
+        playlist.push_str("#EXT-X-ENDLIST\n");
+        Ok(playlist)
+    }
+
+    /// Builds the media playlist of a subtitle rendition
+    ///
+    /// Segments line up with the video segments (`sub_00000.vtt`).
+    pub fn subtitle_playlist(&self, session_id: &str, subtitle: usize) -> Result<String, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        Self::subtitle(session, subtitle)?;
+        Ok(VttSegmenter::new(self.segment_seconds).playlist(session.duration_seconds))
+    }
+
+    /// Parsed cues of a subtitle, if they were loaded before
+    pub fn subtitle_cues(&self, session_id: &str, subtitle: usize) -> Result<Option<Arc<Vec<Cue>>>, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        Self::subtitle(session, subtitle)?;
+        Ok(session.subtitle_cues.get(&subtitle).cloned())
+    }
+
+    /// Keeps the parsed cues of a subtitle for its further segments
+    pub fn store_subtitle_cues(&self, session_id: &str, subtitle: usize, cues: Vec<Cue>) -> Result<Arc<Vec<Cue>>, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        Self::subtitle(session, subtitle)?;
+        let cues = Arc::new(cues);
+        session.subtitle_cues.insert(subtitle, cues.clone());
+        Ok(cues)
+    }
+
+    /// Renders a WebVTT segment of a subtitle rendition
+    pub fn subtitle_segment(&self, session_id: &str, cues: &[Cue], index: u32) -> Result<String, TranscodeError> {
+        let mut sessions = self.lock();
+        let session = Self::session_mut(&mut sessions, session_id)?;
+        let segmenter = VttSegmenter::new(self.segment_seconds);
+        if index >= segmenter.segment_count(session.duration_seconds) {
+            return Err(TranscodeError::InvalidRequest(format!("Segment {} out of range", index)));
+        }
+        Ok(segmenter.segment(cues, index))
+    }
+
+    /// Gets the path of a segment, transcoding it first if needed
+    ///
+    /// Waits while the running transcoder catches up; requests far ahead of
+    /// (or behind) its position restart it at the requested segment.
+    ///
+    /// # Returns
+    /// * `(media_id, path)` of the finished segment file
+    pub async fn segment(&self, session_id: &str, variant: &str, index: u32) -> Result<(i64, PathBuf), TranscodeError> {
+        let segment_file = HlsTranscodeRequest::segment_file_name(index);
+        let (media_id, path, profile) = {
+            let mut sessions = self.lock();
+            let session = Self::session_mut(&mut sessions, session_id)?;
+            let variant = Self::variant(session, variant)?.clone();
+            if index >= self.segment_count(session.duration_seconds) {
+                return Err(TranscodeError::InvalidRequest(format!("Segment {} out of range", index)));
+            }
+
+            let profile = self.cache_profile(session, &variant);
+            if let Some(cached) = self.cache.as_ref().and_then(|c| c.get(session.media_id, &profile, &segment_file)) {
+                return Ok((session.media_id, cached));
+            }
+
+            let dir = self.root_dir.join(session_id).join(&variant.name);
+            let path = dir.join(&segment_file);
+            if !path.exists() && self.needs_restart(session, &variant.name, &dir, index) {
+                if let Some(mut job) = session.jobs.remove(&variant.name) {
+                    let _ = job.process.start_kill();
+                }
+                debug!("HLS session {}: transcoding {} from segment {}", session_id, variant.name, index);
+                let request = HlsTranscodeRequest {
+                    input_path: session.file_path.clone(),
+                    output_dir: dir,
+                    start_segment: index,
+                    segment_seconds: self.segment_seconds,
+                    audio_track: session.audio_track,
+                    variant: variant.clone(),
+                };
+                let process = self.transcoder.start_hls(&request)?;
+                session.jobs.insert(variant.name.clone(), TranscodeJob { process, start_segment: index, produced: None });
+            }
+            (session.media_id, path, profile)
+        };
+
+        let deadline = Instant::now() + self.segment_wait;
+        loop {
+            if path.exists() {
+                self.cache_segment(media_id, &profile, &segment_file, &path);
+                return Ok((media_id, path));
+            }
+            if self.transcoder_exited(session_id, variant) {
+                // The last segment may land right before the process exits
+                if path.exists() {
+                    self.cache_segment(media_id, &profile, &segment_file, &path);
+                    return Ok((media_id, path));
+                }
+                return Err(TranscodeError::ExecutionFailed(format!("Transcoder stopped before segment {}", index)));
+            }
+            if Instant::now() >= deadline {
+                return Err(TranscodeError::Timeout(format!("Segment {} not ready", index)));
+            }
+            tokio::time::sleep(SEGMENT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Stops a session and deletes its segments
+    ///
+    /// # Returns
+    /// * Whether the session existed
+    pub fn stop_session(&self, session_id: &str) -> bool {
+        let Some(session) = self.lock().remove(session_id) else {
+            return false;
+        };
+        self.dispose(session_id, session);
+        true
+    }
+
+    /// Stops sessions without requests within the idle timeout
+    ///
+    /// # Returns
+    /// * Number of sessions removed
+    pub fn cleanup_idle(&self) -> usize {
+        let idle: Vec<(String, HlsSession)> = {
+            let mut sessions = self.lock();
+            let ids: Vec<String> = sessions
+                .iter()
+                .filter(|(_, s)| s.last_access.elapsed() >= self.idle_timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| sessions.remove(&id).map(|s| (id, s)))
+                .collect()
+        };
+
+        let removed = idle.len();
+        for (session_id, session) in idle {
+            debug!("HLS session {} idle, removing", session_id);
+            self.dispose(&session_id, session);
+        }
+        removed
+    }
+
+    /// Lists active sessions
+    pub fn sessions(&self) -> Vec<HlsSessionInfo> {
+        self.lock()
+            .iter()
+            .map(|(id, s)| HlsSessionInfo {
+                session_id: id.clone(),
+                media_id: s.media_id,
+                variants: s.variants.iter().map(|v| v.name.clone()).collect(),
+                active_transcodes: s.jobs.len(),
+                idle_seconds: s.last_access.elapsed().as_secs(),
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, HlsSession>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Looks up a session and records the access
+    fn session_mut<'a>(
+        sessions: &'a mut HashMap<String, HlsSession>,
+        session_id: &str,
+    ) -> Result<&'a mut HlsSession, TranscodeError> {
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| TranscodeError::SessionNotFound(session_id.to_string()))?;
+        session.last_access = Instant::now();
+        Ok(session)
+    }
+
+    fn variant<'a>(session: &'a HlsSession, name: &str) -> Result<&'a HlsVariant, TranscodeError> {
+        session
+            .variants
+            .iter()
+            .find(|v| v.name == name)
+            .ok_or_else(|| TranscodeError::InvalidRequest(format!("Unknown variant: {}", name)))
+    }
+
+    fn subtitle(session: &HlsSession, index: usize) -> Result<&HlsSubtitle, TranscodeError> {
+        session
+            .subtitles
+            .iter()
+            .find(|s| s.index == index)
+            .ok_or_else(|| TranscodeError::InvalidRequest(format!("Unknown subtitle: {}", index)))
+    }
+
+    fn segment_count(&self, duration_seconds: f64) -> u32 {
+        (duration_seconds / self.segment_seconds as f64).ceil().max(1.0) as u32
+    }
+
+    /// Cache key of a variant's segments within a media item
+    ///
+    /// Everything that changes the segment bytes is part of the key.
+    fn cache_profile(&self, session: &HlsSession, variant: &HlsVariant) -> String {
+        format!(
+            "{}-v{}k-a{}k-t{}-s{}",
+            variant.name, variant.video_bitrate_kbps, variant.audio_bitrate_kbps, session.audio_track, self.segment_seconds
+        )
+    }
+
+    fn cache_segment(&self, media_id: i64, profile: &str, segment_file: &str, path: &Path) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.store(media_id, profile, segment_file, path) {
+                warn!("Failed to cache HLS segment {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Whether the variant's transcoder cannot deliver `index` soon
+    fn needs_restart(&self, session: &mut HlsSession, variant: &str, dir: &Path, index: u32) -> bool {
+        let Some(job) = session.jobs.get_mut(variant) else {
+            return true;
+        };
+        if index < job.start_segment || !matches!(job.process.try_wait(), Ok(None)) {
+            return true;
+        }
+
+        let mut next = job.produced.map_or(job.start_segment, |p| p + 1);
+        while dir.join(HlsTranscodeRequest::segment_file_name(next)).exists() {
+            job.produced = Some(next);
+            next += 1;
+        }
+        index > next + MAX_SEGMENT_LOOKAHEAD
+    }
+
+    /// Whether the variant's transcoder is gone or has exited
+    fn transcoder_exited(&self, session_id: &str, variant: &str) -> bool {
+        let mut sessions = self.lock();
+        match sessions.get_mut(session_id).and_then(|s| s.jobs.get_mut(variant)) {
+            Some(job) => !matches!(job.process.try_wait(), Ok(None)),
+            None => true,
+        }
+    }
+
+    /// Kills a session's transcoders and deletes its directory
+    fn dispose(&self, session_id: &str, mut session: HlsSession) {
+        for job in session.jobs.values_mut() {
+            let _ = job.process.start_kill();
+        }
+        if let Err(e) = std::fs::remove_dir_all(self.root_dir.join(session_id)) {
+            warn!("Failed to remove HLS session directory {}: {}", session_id, e);
+        }
+        info!("HLS session {} stopped (media {})", session_id, session.media_id);
+    }
+}
+
+/// Makes a value safe inside a quoted playlist attribute
+fn quoted(value: &str) -> String {
+    value.chars().filter(|c| *c != '"' && !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Writes three segments up front and idles like a running FFmpeg
+    struct FakeTranscoder;
+
+    impl HlsTranscoder for FakeTranscoder {
+        fn start_hls(&self, request: &HlsTranscodeRequest) -> Result<Child, TranscodeError> {
+            std::fs::create_dir_all(&request.output_dir)?;
+            for index in request.start_segment..request.start_segment + 3 {
+                std::fs::write(request.output_dir.join(HlsTranscodeRequest::segment_file_name(index)), b"ts")?;
+            }
+            Ok(tokio::process::Command::new("sleep").arg("30").kill_on_drop(true).spawn()?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = HlsSessionManager::new(Arc::new(FakeTranscoder), temp_dir.path())
+            .with_segment_wait(Duration::from_millis(300));
+
+        let session_id = manager
+            .create_session(7, "/media/movie.mkv".to_string(), 125.0, (1920, 1080), 0)
+            .unwrap();
+
+        let master = manager.master_playlist(&session_id).unwrap();
+        assert!(master.contains(&format!("{}/720p/index.m3u8", session_id)));
+        assert!(master.contains("RESOLUTION=1280x720"));
+
+        // 125s in 6s segments: 20 full ones and a 5s tail
+        let playlist = manager.media_playlist(&session_id, "720p").unwrap();
+        assert_eq!(playlist.matches("#EXTINF").count(), 21);
+        assert!(playlist.contains("#EXTINF:5.000,\nseg_00020.ts"));
+
+        // Seeking far ahead restarts the transcoder at the requested segment
+        let (media_id, path) = manager.segment(&session_id, "720p", 15).await.unwrap();
+        assert_eq!(media_id, 7);
+        assert!(path.ends_with("720p/seg_00015.ts"));
+        assert!(manager.segment(&session_id, "720p", 21).await.is_err());
+        assert!(manager.segment(&session_id, "4k", 0).await.is_err());
+
+        assert_eq!(manager.sessions()[0].active_transcodes, 1);
+
+        // Subtitles are a rendition group of every variant
+        manager.set_subtitles(&session_id, vec![HlsSubtitle {
+            index: 2,
+            name: "Magyar".to_string(),
+            language: Some("hu".to_string()),
+            is_default: false,
+            forced: true,
+            hearing_impaired: false,
+        }]).unwrap();
+        let master = manager.master_playlist(&session_id).unwrap();
+        assert!(master.contains(&format!(
+            "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"Magyar\",LANGUAGE=\"hu\",DEFAULT=NO,AUTOSELECT=YES,FORCED=YES,URI=\"{}/subs/2/index.m3u8\"",
+            session_id
+        )));
+        assert!(master.contains("SUBTITLES=\"subs\"\n"));
+        assert_eq!(manager.subtitle_playlist(&session_id, 2).unwrap().matches("#EXTINF").count(), 21);
+        assert!(manager.subtitle_cues(&session_id, 0).is_err());
+        assert!(manager.subtitle_cues(&session_id, 2).unwrap().is_none());
+        let cues = manager.store_subtitle_cues(&session_id, 2, vec![Cue { start: 7.0, end: 8.0, text: "Szia".to_string() }]).unwrap();
+        assert!(manager.subtitle_segment(&session_id, &cues, 1).unwrap().contains("Szia"));
+        assert!(manager.subtitle_segment(&session_id, &cues, 21).is_err());
+
+        assert!(manager.stop_session(&session_id));
+        assert!(!temp_dir.path().join(&session_id).exists());
+        assert!(manager.master_playlist(&session_id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_segments_outlive_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("movie.mkv");
+        std::fs::write(&source, b"mkv").unwrap();
+        let cache = Arc::new(TranscodeCache::new(temp_dir.path().join("cache"), 1024).unwrap());
+        let manager = HlsSessionManager::new(Arc::new(FakeTranscoder), temp_dir.path().join("hls"))
+            .with_segment_wait(Duration::from_millis(300))
+            .with_cache(cache.clone());
+
+        let first = manager
+            .create_session(7, source.display().to_string(), 60.0, (1920, 1080), 0)
+            .unwrap();
+        manager.segment(&first, "720p", 0).await.unwrap();
+        assert!(manager.stop_session(&first));
+
+        // A new session is served from the cache without a transcoder
+        let second = manager
+            .create_session(7, source.display().to_string(), 60.0, (1920, 1080), 0)
+            .unwrap();
+        let (_, path) = manager.segment(&second, "720p", 0).await.unwrap();
+        assert!(path.starts_with(temp_dir.path().join("cache")));
+        assert_eq!(manager.sessions()[0].active_transcodes, 0);
+    }
+}
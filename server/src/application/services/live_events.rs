@@ -0,0 +1,190 @@
+//! Live Events
+//!
+//! Fan-out channel for pushing server events (scan progress, newly identified
+//! media) to connected clients.
+//!
+//! Notification-worthy events can be recorded in the event store, which
+//! gives them an ID; clients that reconnect with the last ID they saw get
+//! the recorded events they missed replayed. Transient events (scan
+//! progress) are only sent live.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::infrastructure::event_sourcing::event_store::EventStore;
+use crate::shared::error::EventSourcingError;
+
+/// Aggregate type of live events in the event store
+const STORED_EVENT_KIND: &str = "live_event";
+/// Events read from the event store per batch while replaying
+const REPLAY_BATCH: usize = 500;
+/// Days recorded events are kept for replay
+const REPLAY_RETENTION_DAYS: i64 = 7;
+
+/// Event delivered to live clients
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveEvent {
+    /// Event store ID of recorded events, used as the SSE event ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    /// Event name (e.g. "scan_progress", "media_identified")
+    pub event_type: String,
+    /// Event payload
+    pub data: serde_json::Value,
+}
+
+impl LiveEvent {
+    /// Creates a live event from any serializable payload
+    pub fn new(event_type: impl Into<String>, data: impl Serialize) -> Self {
+        Self {
+            id: None,
+            event_type: event_type.into(),
+            data: serde_json::to_value(data).unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+/// Broadcasts live events to all subscribers
+///
+/// Slow subscribers that fall more than the channel capacity behind miss
+/// events rather than blocking publishers.
+pub struct LiveEventBroadcaster {
+    sender: broadcast::Sender<LiveEvent>,
+    event_store: Option<Arc<EventStore>>,
+}
+
+impl LiveEventBroadcaster {
+    /// Creates a broadcaster buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender, event_store: None }
+    }
+
+    /// Records events published with [`Self::publish_recorded`] in the
+    /// event store for replay
+    pub fn with_event_store(mut self, event_store: Arc<EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Publishes an event; a no-op when nobody is listening
+    pub fn publish(&self, event: LiveEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    /// Records an event for replay, then publishes it
+    ///
+    /// A failed write is logged and the event is still sent live.
+    pub async fn publish_recorded(&self, mut event: LiveEvent) {
+        if let Some(event_store) = &self.event_store {
+            match event_store
+                .append_payload(&event.event_type, STORED_EVENT_KIND, event.data.to_string())
+                .await
+            {
+                Ok(id) => event.id = Some(id),
+                Err(e) => warn!("Failed to record live event {}: {}", event.event_type, e),
+            }
+        }
+        self.publish(event);
+    }
+
+    /// Recorded events after the given ID, oldest first, up to `limit`
+    pub async fn replay(&self, after: u64, limit: usize) -> Result<Vec<LiveEvent>, EventSourcingError> {
+        let Some(event_store) = &self.event_store else {
+            return Ok(Vec::new());
+        };
+
+        let mut events = Vec::new();
+        let mut from = after;
+        while events.len() < limit {
+            let batch = event_store.read(from, REPLAY_BATCH).await?;
+            let Some(last) = batch.last() else { break };
+            from = last.version;
+            let done = batch.len() < REPLAY_BATCH;
+            events.extend(
+                batch
+                    .into_iter()
+                    .filter(|stored| stored.aggregate_type.as_deref() == Some(STORED_EVENT_KIND))
+                    .map(|stored| LiveEvent {
+                        id: Some(stored.version),
+                        data: serde_json::from_str(&stored.payload).unwrap_or(serde_json::Value::Null),
+                        event_type: stored.event_type,
+                    }),
+            );
+            if done {
+                break;
+            }
+        }
+        events.truncate(limit);
+        Ok(events)
+    }
+
+    /// Removes events older than the replay retention from the event store,
+    /// returning how many
+    pub async fn prune(&self) -> Result<u64, EventSourcingError> {
+        match &self.event_store {
+            Some(event_store) => {
+                event_store.prune(chrono::Utc::now() - chrono::Duration::days(REPLAY_RETENTION_DAYS)).await
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Subscribes to future events
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Number of connected subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for LiveEventBroadcaster {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::event_sourcing::sqlite_event_persistence::SqliteEventPersistence;
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_recorded_events_replay() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let event_store = Arc::new(EventStore::new(Arc::new(SqliteEventPersistence::new(Arc::new(pool)))));
+        let broadcaster = LiveEventBroadcaster::default().with_event_store(event_store.clone());
+        let mut receiver = broadcaster.subscribe();
+
+        broadcaster.publish_recorded(LiveEvent::new("media_identified", json!({ "media_id": 1 }))).await;
+        broadcaster.publish(LiveEvent::new("scan_progress", json!({ "processed": 3 })));
+        // Events other parts of the server store are not replayed
+        event_store.append_payload("audit", "other", "{}".to_string()).await.unwrap();
+        broadcaster.publish_recorded(LiveEvent::new("scan_completed", json!({ "total": 2 }))).await;
+
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.event_type, "media_identified");
+        let first_id = first.id.expect("recorded events have an ID");
+        assert_eq!(receiver.recv().await.unwrap().id, None);
+
+        let replayed = broadcaster.replay(first_id, 10).await.unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].event_type, "scan_completed");
+        assert_eq!(replayed[0].data, json!({ "total": 2 }));
+        assert_eq!(broadcaster.replay(0, 10).await.unwrap().len(), 2);
+        assert_eq!(broadcaster.replay(0, 1).await.unwrap().len(), 1);
+    }
+}
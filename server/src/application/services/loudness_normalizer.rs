@@ -0,0 +1,80 @@
+//! Loudness Normalizer
+//!
+//! Picks the loudnorm filter for streams that ask for normalized audio.
+//! Tracks with a stored measurement get the accurate second pass; the first
+//! time a track is played the filter normalizes dynamically while the track
+//! is measured in the background for the next playback.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::domain::repositories::LoudnessRepository;
+use crate::interfaces::external_services::{LoudnessAnalyzer, LoudnessTarget};
+
+/// Loudness filter selection and background measurement
+pub struct LoudnessNormalizer {
+    analyzer: Arc<dyn LoudnessAnalyzer>,
+    repository: Arc<dyn LoudnessRepository>,
+    target: LoudnessTarget,
+    /// Tracks being measured (media ID, audio track)
+    measuring: Arc<Mutex<HashSet<(i64, usize)>>>,
+    /// Measurements read whole tracks, so they run one at a time
+    permits: Arc<Semaphore>,
+}
+
+impl LoudnessNormalizer {
+    pub fn new(analyzer: Arc<dyn LoudnessAnalyzer>, repository: Arc<dyn LoudnessRepository>) -> Self {
+        Self {
+            analyzer,
+            repository,
+            target: LoudnessTarget::default(),
+            measuring: Arc::new(Mutex::new(HashSet::new())),
+            permits: Arc::new(Semaphore::new(1)),
+        }
+    }
+
+    /// Returns the loudnorm filter for an audio track
+    ///
+    /// Starts measuring the track if it has no stored measurement yet.
+    pub async fn filter(&self, media_id: i64, file_path: &str, audio_track: usize) -> String {
+        match self.repository.find(media_id, audio_track).await {
+            Ok(Some(measured)) => return self.target.filter(Some(&measured)),
+            Ok(None) => self.measure_in_background(media_id, file_path, audio_track),
+            Err(e) => warn!("Failed to load loudness of media {}: {}", media_id, e),
+        }
+        self.target.filter(None)
+    }
+
+    fn measure_in_background(&self, media_id: i64, file_path: &str, audio_track: usize) {
+        let key = (media_id, audio_track);
+        if !self.measuring.lock().unwrap_or_else(|e| e.into_inner()).insert(key) {
+            return;
+        }
+
+        let analyzer = self.analyzer.clone();
+        let repository = self.repository.clone();
+        let measuring = self.measuring.clone();
+        let permits = self.permits.clone();
+        let target = self.target;
+        let file_path = file_path.to_string();
+        tokio::spawn(async move {
+            if let Ok(_permit) = permits.acquire().await {
+                match analyzer.measure(&file_path, audio_track, &target).await {
+                    Ok(measured) => {
+                        info!(
+                            "Measured loudness of media {} track {}: {:.1} LUFS, {:.1} LU range",
+                            media_id, audio_track, measured.input_i, measured.input_lra
+                        );
+                        if let Err(e) = repository.save(media_id, audio_track, &measured).await {
+                            warn!("Failed to save loudness of media {}: {}", media_id, e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to measure loudness of media {} track {}: {}", media_id, audio_track, e),
+                }
+            }
+            measuring.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+        });
+    }
+}
@@ -0,0 +1,287 @@
+//! Media Filters
+//!
+//! Narrows and orders media listings and search results by genre, release
+//! year, resolution, watched state, rating and library (the top-level
+//! folder under the media directory), and counts the values of each of
+//! these for filter chips.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::MediaOrder;
+
+/// Filters and sort order of a media listing
+///
+/// Lists (genres, resolutions) are comma-separated and match any value.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MediaFilter {
+    pub genre: Option<String>,
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    pub resolution: Option<String>,
+    pub watched: Option<bool>,
+    pub min_rating: Option<f32>,
+    pub library: Option<String>,
+    pub sort: Option<MediaSort>,
+    pub order: Option<SortOrder>,
+}
+
+impl MediaFilter {
+    /// Whether the filter narrows the listing, rather than only ordering it
+    pub fn narrows(&self) -> bool {
+        self.genre.is_some()
+            || self.year_from.is_some()
+            || self.year_to.is_some()
+            || self.resolution.is_some()
+            || self.watched.is_some()
+            || self.min_rating.is_some()
+            || self.library.is_some()
+    }
+
+    /// Repository order of the whole library in the requested sort, and
+    /// whether it is descending
+    ///
+    /// The library's own order is newest first.
+    pub fn library_order(&self) -> (MediaOrder, bool) {
+        let descending = self.descending();
+        match self.sort.unwrap_or_default() {
+            MediaSort::Relevance => (MediaOrder::Added, !descending),
+            MediaSort::Title => (MediaOrder::Title, descending),
+            MediaSort::Year => (MediaOrder::Year, descending),
+            MediaSort::Rating => (MediaOrder::Rating, descending),
+            MediaSort::Added => (MediaOrder::Added, descending),
+        }
+    }
+
+    fn descending(&self) -> bool {
+        match (self.order, self.sort.unwrap_or_default()) {
+            (Some(order), _) => order == SortOrder::Desc,
+            (None, MediaSort::Year | MediaSort::Rating | MediaSort::Added) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Sort keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaSort {
+    /// Order of the source (search rank for searches)
+    #[default]
+    Relevance,
+    Title,
+    Year,
+    Rating,
+    Added,
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Filterable dimensions, each with its facet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Genre,
+    Year,
+    Resolution,
+    Watched,
+    Rating,
+    Library,
+}
+
+/// A facet value and the number of items having it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Value counts of the filterable dimensions
+///
+/// Each facet counts the items matching every other filter, so picking a
+/// genre still shows how many items the other genres have.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MediaFacets {
+    pub genres: Vec<FacetCount>,
+    /// Release decades ("1990s")
+    pub decades: Vec<FacetCount>,
+    pub resolutions: Vec<FacetCount>,
+    /// "watched" and "unwatched"
+    pub watched: Vec<FacetCount>,
+    /// Items rated at least 9, 8, 7 and 6 ("9+", ...)
+    pub ratings: Vec<FacetCount>,
+    pub libraries: Vec<FacetCount>,
+}
+
+/// Rating thresholds of the rating facet
+const RATING_BUCKETS: [u8; 4] = [9, 8, 7, 6];
+
+/// Applies media filters
+pub struct MediaFilterService {
+    media_dir: PathBuf,
+}
+
+impl MediaFilterService {
+    /// Creates a filter service for the libraries under `media_dir`
+    pub fn new(media_dir: impl Into<PathBuf>) -> Self {
+        Self { media_dir: media_dir.into() }
+    }
+
+    /// Library (top-level folder under the media directory) of a media file
+    pub fn library_of(&self, media: &Media) -> Option<String> {
+        let relative = std::path::Path::new(&media.file_path).strip_prefix(&self.media_dir).ok()?;
+        let mut components = relative.components();
+        let first = components.next()?;
+        // Files directly in the media directory belong to no library
+        components.next()?;
+        Some(first.as_os_str().to_string_lossy().into_owned())
+    }
+
+    /// Keeps the media matching the filter, in the requested order
+    pub fn apply(&self, media: Vec<Media>, filter: &MediaFilter) -> Vec<Media> {
+        let mut media: Vec<Media> = media.into_iter().filter(|m| self.matches(m, filter, None)).collect();
+        let sort = filter.sort.unwrap_or_default();
+        let descending = filter.descending();
+
+        let compare: fn(&Media, &Media) -> Ordering = match sort {
+            MediaSort::Relevance => return if descending { media.into_iter().rev().collect() } else { media },
+            MediaSort::Title => |a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            MediaSort::Year => |a, b| year(a).cmp(&year(b)),
+            MediaSort::Rating => |a, b| a.rating.partial_cmp(&b.rating).unwrap_or(Ordering::Equal),
+            MediaSort::Added => |a, b| a.created_at.cmp(&b.created_at),
+        };
+        media.sort_by(|a, b| if descending { compare(b, a) } else { compare(a, b) });
+        media
+    }
+
+    /// Facet counts of the media for the filter
+    pub fn facets(&self, media: &[Media], filter: &MediaFilter) -> MediaFacets {
+        let count = |dimension: Dimension, values: &dyn Fn(&Media) -> Vec<String>| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for item in media.iter().filter(|m| self.matches(m, filter, Some(dimension))) {
+                for value in values(item) {
+                    *counts.entry(value).or_default() += 1;
+                }
+            }
+            let mut counts: Vec<FacetCount> = counts.into_iter().map(|(value, count)| FacetCount { value, count }).collect();
+            counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+            counts
+        };
+
+        let mut ratings = count(Dimension::Rating, &|m| {
+            RATING_BUCKETS.iter()
+                .filter(|threshold| m.rating.is_some_and(|r| r >= f32::from(**threshold)))
+                .map(|threshold| format!("{}+", threshold))
+                .collect()
+        });
+        ratings.sort_by(|a, b| b.value.cmp(&a.value));
+
+        MediaFacets {
+            genres: count(Dimension::Genre, &|m| genres(m).map(str::to_string).collect()),
+            decades: count(Dimension::Year, &|m| year(m).map(|y| format!("{}s", y / 10 * 10)).into_iter().collect()),
+            resolutions: count(Dimension::Resolution, &|m| m.resolution.clone().into_iter().collect()),
+            watched: count(Dimension::Watched, &|m| vec![if m.is_watched { "watched" } else { "unwatched" }.to_string()]),
+            ratings,
+            libraries: count(Dimension::Library, &|m| self.library_of(m).into_iter().collect()),
+        }
+    }
+
+    /// Whether media passes the filter, ignoring one dimension
+    fn matches(&self, media: &Media, filter: &MediaFilter, except: Option<Dimension>) -> bool {
+        let check = |dimension: Dimension| except != Some(dimension);
+
+        if let Some(wanted) = filter.genre.as_deref().filter(|_| check(Dimension::Genre)) {
+            if !any_of(wanted).any(|w| genres(media).any(|g| g.eq_ignore_ascii_case(w))) {
+                return false;
+            }
+        }
+        if check(Dimension::Year) && (filter.year_from.is_some() || filter.year_to.is_some()) {
+            let Some(year) = year(media) else { return false };
+            if filter.year_from.is_some_and(|from| year < from) || filter.year_to.is_some_and(|to| year > to) {
+                return false;
+            }
+        }
+        if let Some(wanted) = filter.resolution.as_deref().filter(|_| check(Dimension::Resolution)) {
+            let resolution = media.resolution.as_deref().unwrap_or("");
+            if !any_of(wanted).any(|w| w.eq_ignore_ascii_case(resolution)) {
+                return false;
+            }
+        }
+        if filter.watched.is_some_and(|watched| check(Dimension::Watched) && media.is_watched != watched) {
+            return false;
+        }
+        if filter.min_rating.is_some_and(|min| check(Dimension::Rating) && media.rating.is_none_or(|r| r < min)) {
+            return false;
+        }
+        if let Some(wanted) = filter.library.as_deref().filter(|_| check(Dimension::Library)) {
+            if !self.library_of(media).is_some_and(|library| library.eq_ignore_ascii_case(wanted)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Values of a comma-separated list
+fn any_of(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn genres(media: &Media) -> impl Iterator<Item = &str> {
+    any_of(media.genres.as_deref().unwrap_or(""))
+}
+
+fn year(media: &Media) -> Option<i32> {
+    media.release_date.as_deref().and_then(|d| d.get(..4)).and_then(|y| y.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MediaType;
+
+    fn movie(path: &str, genres: &str, date: &str, rating: f32) -> Media {
+        let mut media = Media::new(path.to_string(), MediaType::Movie, path.to_string()).unwrap();
+        media.genres = Some(genres.to_string());
+        media.release_date = Some(date.to_string());
+        media.rating = Some(rating);
+        media
+    }
+
+    #[test]
+    fn test_filter_sort_and_facets() {
+        let service = MediaFilterService::new("/media");
+        let media = vec![
+            movie("/media/Movies/a.mkv", "Comedy, Drama", "1994-05-01", 8.1),
+            movie("/media/Movies/b.mkv", "Comedy", "2004-01-01", 6.5),
+            movie("/media/Kids/c.mkv", "Animation", "1995-11-22", 8.3),
+        ];
+
+        let filter = MediaFilter {
+            genre: Some("comedy".to_string()),
+            sort: Some(MediaSort::Rating),
+            ..Default::default()
+        };
+        let paths: Vec<_> = service.apply(media.clone(), &filter).into_iter().map(|m| m.file_path).collect();
+        assert_eq!(paths, vec!["/media/Movies/a.mkv", "/media/Movies/b.mkv"]);
+
+        let filter = MediaFilter { year_from: Some(1990), year_to: Some(1999), ..filter };
+        assert_eq!(service.apply(media.clone(), &filter).len(), 1);
+
+        // The genre facet ignores the genre filter but not the year range
+        let facets = service.facets(&media, &filter);
+        assert_eq!(facets.genres[0], FacetCount { value: "Animation".to_string(), count: 1 });
+        assert_eq!(facets.genres.len(), 3);
+        assert_eq!(facets.decades.len(), 2);
+        assert_eq!(facets.libraries, vec![FacetCount { value: "Movies".to_string(), count: 1 }]);
+        assert_eq!(facets.ratings[0].value, "8+");
+    }
+}
@@ -6,11 +6,12 @@
 use std::sync::Arc;
 use tracing::{info, debug, warn};
 
-use crate::domain::entities::Media;
+use crate::domain::entities::{Media, Series};
 use crate::domain::repositories::MediaRepository;
 use crate::domain::repositories::SeriesRepository;
 use crate::domain::repositories::CollectionRepository;
-use crate::interfaces::external_services::TmdbService;
+use crate::domain::repositories::{LocalizationRepository, LocalizedMetadata};
+use crate::interfaces::external_services::{TmdbService, TmdbLocalizedFetcher, ArtworkMirror, ArtworkKind};
 use crate::shared::error::ApplicationError;
 
 /// Metadata Enricher
@@ -34,6 +35,14 @@ pub struct MetadataEnricher {
     collection_repository: Arc<dyn CollectionRepository>,
     /// TMDB service for metadata lookup
     tmdb_service: Arc<dyn TmdbService>,
+    /// Localized metadata fetcher (optional)
+    localized_fetcher: Option<Arc<dyn TmdbLocalizedFetcher>>,
+    /// Repository for localized title/overview variants (optional)
+    localization_repository: Option<Arc<dyn LocalizationRepository>>,
+    /// Configured metadata language; refreshing in this language updates the main record
+    default_language: Option<String>,
+    /// Artwork mirror for local image storage (optional)
+    artwork_mirror: Option<Arc<dyn ArtworkMirror>>,
 }
 
 impl MetadataEnricher {
@@ -55,9 +64,141 @@ impl MetadataEnricher {
             series_repository,
             collection_repository,
             tmdb_service,
+            localized_fetcher: None,
+            localization_repository: None,
+            default_language: None,
+            artwork_mirror: None,
         }
     }
 
+    /// Enables localized metadata refresh
+    ///
+    /// # Arguments
+    /// * `fetcher` - TMDB fetcher for language-specific titles and overviews
+    /// * `repository` - Repository for storing localized variants
+    /// * `default_language` - Configured metadata language (e.g. "hu-HU")
+    pub fn with_localization(
+        mut self,
+        fetcher: Arc<dyn TmdbLocalizedFetcher>,
+        repository: Arc<dyn LocalizationRepository>,
+        default_language: Option<String>,
+    ) -> Self {
+        self.localized_fetcher = Some(fetcher);
+        self.localization_repository = Some(repository);
+        self.default_language = default_language;
+        self
+    }
+
+    /// Enables local artwork mirroring
+    pub fn with_artwork_mirror(mut self, mirror: Arc<dyn ArtworkMirror>) -> Self {
+        self.artwork_mirror = Some(mirror);
+        self
+    }
+
+    /// Gets the configured metadata language
+    pub fn default_language(&self) -> Option<&str> {
+        self.default_language.as_deref()
+    }
+
+    /// Re-fetches title and overview for a media item in the given language
+    ///
+    /// The localized variant is always stored. When `language` is the configured
+    /// default language, the main media record is updated as well. The original
+    /// title is TMDB's original title (original name for shows); episodes keep
+    /// theirs. Episodes without season and episode numbers are skipped.
+    ///
+    /// # Arguments
+    /// * `media_id` - ID of media to refresh
+    /// * `language` - Language tag (e.g. "hu-HU")
+    ///
+    /// # Returns
+    /// * `Result<LocalizedMetadata, ApplicationError>` - The stored localized variant
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - Localization is not configured
+    /// - Media not found or not identified
+    /// - The media is an episode without season and episode numbers
+    /// - TMDB has no entry for the media
+    pub async fn refresh_localization(
+        &self,
+        media_id: i64,
+        language: &str,
+    ) -> Result<LocalizedMetadata, ApplicationError> {
+        let (fetcher, repository) = match (&self.localized_fetcher, &self.localization_repository) {
+            (Some(f), Some(r)) => (f, r),
+            _ => return Err(ApplicationError::ServiceUnavailable(
+                "Localized metadata is not configured".to_string(),
+            )),
+        };
+
+        let mut media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                crate::shared::error::DomainError::NotFound(format!("Media with ID {} not found", media_id))
+            ))?;
+
+        // Episodes are localized by episode; everything else by its own TMDB ID
+        let (tmdb_id, media_type) = if media.is_episode() {
+            if media.season.is_none() || media.episode.is_none() {
+                return Err(ApplicationError::Domain(
+                    crate::shared::error::DomainError::InvalidInput(format!(
+                        "Episode {} has no season and episode number", media_id
+                    ))
+                ));
+            }
+            let series_tmdb_id = match media.series_id {
+                Some(series_id) => self.series_repository
+                    .find_by_id(series_id)
+                    .await?
+                    .and_then(|s| s.tmdb_id),
+                None => None,
+            };
+            (series_tmdb_id.or(media.tmdb_id), "tv")
+        } else {
+            (media.tmdb_id, "movie")
+        };
+        let tmdb_id = tmdb_id.ok_or_else(|| ApplicationError::Domain(
+            crate::shared::error::DomainError::InvalidInput(format!("Media {} has no TMDB ID", media_id))
+        ))?;
+
+        let text = fetcher
+            .fetch_localized(tmdb_id, media_type, media.season, media.episode, language)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                crate::shared::error::DomainError::NotFound(format!("No TMDB entry for media {} in {}", media_id, language))
+            ))?;
+
+        let localized = LocalizedMetadata {
+            media_id,
+            language: text.language,
+            title: text.title,
+            overview: text.overview,
+        };
+        repository.save(&localized).await?;
+
+        let mut changed = false;
+        if text.original_title.is_some() && media.original_title != text.original_title {
+            media.original_title = text.original_title;
+            changed = true;
+        }
+        if self.default_language.as_deref() == Some(language) {
+            media.title = localized.title.clone();
+            if localized.overview.is_some() {
+                media.overview = localized.overview.clone();
+            }
+            changed = true;
+        }
+        if changed {
+            media.updated_at = chrono::Utc::now();
+            self.media_repository.update(&media).await?;
+        }
+
+        info!("Refreshed '{}' metadata for media {}", language, media_id);
+        Ok(localized)
+    }
+
     /// Enriches a single media item with TMDB metadata
     ///
     /// # Arguments
@@ -92,6 +233,7 @@ impl MetadataEnricher {
         if let Some(tmdb_id) = media.tmdb_id {
             if media.is_movie() {
                 if let Some(details) = self.tmdb_service.fetch_movie_details(tmdb_id).await? {
+                    media.original_title = details.original_title.filter(|t| *t != details.title);
                     media = media
                         .with_overview(Some(details.overview))
                         .with_poster_url(details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)))
@@ -125,6 +267,7 @@ impl MetadataEnricher {
                                     .with_total_episodes(Some(details.number_of_episodes));
 
                                 self.series_repository.update(&updated_series).await?;
+                                self.mirror_series_artwork(&updated_series).await;
                             }
                         }
                     }
@@ -134,6 +277,7 @@ impl MetadataEnricher {
 
         // Save updated media
         self.media_repository.update(&media).await?;
+        self.mirror_media_artwork(&media).await;
 
         debug!("Media enriched: {} (ID: {})", media.title, media_id);
         Ok(())
@@ -269,12 +413,214 @@ impl MetadataEnricher {
                     .with_total_episodes(Some(details.number_of_episodes));
 
                 self.series_repository.update(&updated_series).await?;
+                self.mirror_series_artwork(&updated_series).await;
             }
         }
 
         info!("Series metadata refreshed: {}", series.title);
         Ok(())
     }
+
+    /// Re-fetches a movie's metadata from TMDB, overwriting stored fields
+    ///
+    /// Unlike [`enrich_media`](Self::enrich_media) this does not skip items
+    /// that are already enriched; it is used when TMDB reports upstream changes.
+    ///
+    /// # Arguments
+    /// * `media_id` - ID of the movie to refresh
+    ///
+    /// # Returns
+    /// * `Result<bool, ApplicationError>` - Whether TMDB returned metadata
+    pub async fn refresh_movie_from_tmdb(&self, media_id: i64) -> Result<bool, ApplicationError> {
+        let mut media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                crate::shared::error::DomainError::NotFound(format!("Media with ID {} not found", media_id))
+            ))?;
+
+        let Some(tmdb_id) = media.tmdb_id.filter(|_| media.is_movie()) else {
+            return Ok(false);
+        };
+        let Some(details) = self.tmdb_service.fetch_movie_details(tmdb_id).await? else {
+            return Ok(false);
+        };
+
+        media.title = details.title.clone();
+        media.original_title = details.original_title.filter(|t| *t != details.title);
+        media = media
+            .with_overview(Some(details.overview))
+            .with_poster_url(details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)))
+            .with_backdrop_url(details.backdrop_path.map(|b| format!("https://image.tmdb.org/t/p/w1280{}", b)))
+            .with_genres(Some(details.genres.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(", ")))
+            .with_rating(Some(details.vote_average))
+            .with_release_date(Some(details.release_date));
+
+        if let Some(collection_info) = details.belongs_to_collection {
+            self.create_or_link_collection(&collection_info).await?;
+        }
+
+        self.media_repository.update(&media).await?;
+        self.mirror_media_artwork(&media).await;
+        debug!("Refreshed movie metadata: {} (ID: {})", media.title, media_id);
+        Ok(true)
+    }
+
+    /// Re-fetches a series and all of its episodes from TMDB
+    ///
+    /// Picks up renamed episodes, new overviews and stills without a rescan.
+    ///
+    /// # Arguments
+    /// * `series_id` - ID of the series to refresh
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of episodes updated
+    pub async fn refresh_series_from_tmdb(&self, series_id: i64) -> Result<usize, ApplicationError> {
+        let series = self.series_repository
+            .find_by_id(series_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                crate::shared::error::DomainError::NotFound(format!("Series with ID {} not found", series_id))
+            ))?;
+        let Some(tmdb_id) = series.tmdb_id else {
+            return Ok(0);
+        };
+
+        if let Some(details) = self.tmdb_service.fetch_tv_details(tmdb_id).await? {
+            let updated_series = series.clone()
+                .with_overview(Some(details.overview))
+                .with_poster_url(details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)))
+                .with_backdrop_url(details.backdrop_path.map(|b| format!("https://image.tmdb.org/t/p/w1280{}", b)))
+                .with_genres(Some(details.genres.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(", ")))
+                .with_rating(Some(details.vote_average))
+                .with_first_air_date(Some(details.first_air_date))
+                .with_last_air_date(details.last_air_date.clone())
+                .with_status(Some(details.status))
+                .with_total_seasons(Some(details.number_of_seasons))
+                .with_total_episodes(Some(details.number_of_episodes));
+            self.series_repository.update(&updated_series).await?;
+            self.mirror_series_artwork(&updated_series).await;
+        }
+
+        // One season request covers all of its episodes
+        let mut seasons: std::collections::HashMap<i32, Option<crate::interfaces::external_services::SeasonDetail>> =
+            std::collections::HashMap::new();
+        let mut updated = 0;
+        for mut episode in self.media_repository.find_by_series(series_id).await? {
+            let (Some(season), Some(episode_number)) = (episode.season, episode.episode) else {
+                continue;
+            };
+            if let std::collections::hash_map::Entry::Vacant(entry) = seasons.entry(season) {
+                let season_detail = match self.tmdb_service.fetch_season(tmdb_id, season).await {
+                    Ok(detail) => detail,
+                    Err(e) => {
+                        warn!("Failed to refresh season {} of '{}': {}", season, series.title, e);
+                        None
+                    }
+                };
+                entry.insert(season_detail);
+            }
+            let Some(details) = seasons.get(&season)
+                .and_then(|s| s.as_ref())
+                .and_then(|s| s.episodes.iter().find(|e| e.episode_number == episode_number))
+            else {
+                continue;
+            };
+
+            let title = Some(details.name.clone()).filter(|n| !n.is_empty());
+            let overview = Some(details.overview.clone()).filter(|o| !o.is_empty());
+            let still_url = details.still_path.as_ref().map(|p| format!("https://image.tmdb.org/t/p/w500{}", p));
+            let air_date = details.air_date.clone();
+            let changed = title.as_ref().is_some_and(|t| *t != episode.title)
+                || (overview.is_some() && episode.overview != overview)
+                || (still_url.is_some() && episode.poster_url != still_url)
+                || (air_date.is_some() && episode.release_date != air_date);
+            if !changed {
+                continue;
+            }
+
+            if let Some(title) = title {
+                episode.title = title;
+            }
+            if overview.is_some() {
+                episode.overview = overview;
+            }
+            if still_url.is_some() {
+                episode.poster_url = still_url;
+            }
+            if air_date.is_some() {
+                episode.release_date = air_date;
+            }
+            self.media_repository.update(&episode).await?;
+            self.mirror_media_artwork(&episode).await;
+            updated += 1;
+        }
+
+        debug!("Refreshed series '{}' from TMDB: {} episodes updated", series.title, updated);
+        Ok(updated)
+    }
+
+    /// Mirrors the poster (or episode still) and backdrop of a media item
+    async fn mirror_media_artwork(&self, media: &Media) {
+        let Some(ref mirror) = self.artwork_mirror else {
+            return;
+        };
+        let poster_kind = if media.is_episode() { ArtworkKind::Still } else { ArtworkKind::Poster };
+        if let Some(ref url) = media.poster_url {
+            mirror.mirror(url, poster_kind).await;
+        }
+        if let Some(ref url) = media.backdrop_url {
+            mirror.mirror(url, ArtworkKind::Backdrop).await;
+        }
+    }
+
+    /// Mirrors the poster and backdrop of a series
+    async fn mirror_series_artwork(&self, series: &Series) {
+        let Some(ref mirror) = self.artwork_mirror else {
+            return;
+        };
+        if let Some(ref url) = series.poster_url {
+            mirror.mirror(url, ArtworkKind::Poster).await;
+        }
+        if let Some(ref url) = series.backdrop_url {
+            mirror.mirror(url, ArtworkKind::Backdrop).await;
+        }
+    }
+
+    /// Mirrors artwork for the whole library
+    ///
+    /// Runs once at startup to cover items scanned before mirroring was
+    /// enabled; images that are already stored locally are not downloaded
+    /// again.
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of image variants available locally
+    pub async fn mirror_library_artwork(&self) -> Result<usize, ApplicationError> {
+        let Some(ref mirror) = self.artwork_mirror else {
+            return Ok(0);
+        };
+
+        let mut artwork: Vec<(String, ArtworkKind)> = Vec::new();
+        for media in self.media_repository.find_all().await? {
+            let poster_kind = if media.is_episode() { ArtworkKind::Still } else { ArtworkKind::Poster };
+            artwork.extend(media.poster_url.map(|url| (url, poster_kind)));
+            artwork.extend(media.backdrop_url.map(|url| (url, ArtworkKind::Backdrop)));
+        }
+        for series in self.series_repository.find_all().await? {
+            artwork.extend(series.poster_url.map(|url| (url, ArtworkKind::Poster)));
+            artwork.extend(series.backdrop_url.map(|url| (url, ArtworkKind::Backdrop)));
+        }
+        artwork.sort_by(|a, b| a.0.cmp(&b.0));
+        artwork.dedup_by(|a, b| a.0 == b.0);
+
+        let mut mirrored = 0;
+        for (url, kind) in &artwork {
+            mirrored += mirror.mirror(url, *kind).await;
+        }
+
+        info!("Artwork mirror: {} images ({} variants) available locally", artwork.len(), mirrored);
+        Ok(mirrored)
+    }
 }
 
 /// Statistics from batch enrichment operation
@@ -6,7 +6,61 @@
 pub mod scanner_orchestrator;
 pub mod metadata_enricher;
 pub mod collection_manager;
+pub mod playback_qos;
+pub mod bootstrap_status;
+pub mod live_events;
+pub mod tmdb_change_monitor;
+pub mod fanart_enricher;
+pub mod blurhash_backfill;
+pub mod content_rating_backfill;
+pub mod hls_sessions;
+pub mod playback_decision;
+pub mod stream_sessions;
+pub mod loudness_normalizer;
+pub mod crop_detection;
+pub mod preview_clips;
+pub mod stream_signing;
+pub mod api_keys;
+pub mod subtitle_selection;
+pub mod parental_controls;
+pub mod recommendations;
+pub mod presets;
+pub mod media_filters;
+pub mod search_suggestions;
+pub mod semantic_search;
+pub mod dialogue_search;
+pub mod webhooks;
+pub mod notifications;
+pub mod runtime_settings;
+pub mod task_scheduler;
 
 pub use scanner_orchestrator::ScannerOrchestrator;
 pub use metadata_enricher::MetadataEnricher;
 pub use collection_manager::CollectionManager;
+pub use playback_qos::{PlaybackQos, PlaybackGuard};
+pub use bootstrap_status::BootstrapTracker;
+pub use live_events::{LiveEvent, LiveEventBroadcaster};
+pub use tmdb_change_monitor::TmdbChangeMonitor;
+pub use fanart_enricher::FanartEnricher;
+pub use blurhash_backfill::BlurhashBackfill;
+pub use content_rating_backfill::ContentRatingBackfill;
+pub use hls_sessions::{HlsSessionManager, HlsSessionInfo, HlsSubtitle};
+pub use playback_decision::{PlaybackDecisionService, PlaybackDecision, PlaybackMethod, ClientCapabilities};
+pub use stream_sessions::{StreamSessionRegistry, StreamSessionInfo, StreamRequest, StreamMode, StreamGuard};
+pub use loudness_normalizer::LoudnessNormalizer;
+pub use crop_detection::CropDetectionService;
+pub use preview_clips::PreviewClipService;
+pub use stream_signing::{StreamClaims, StreamUrlSigner, TokenError};
+pub use api_keys::{ApiKeyService, Caller};
+pub use subtitle_selection::{default_subtitle, SubtitleChoice};
+pub use parental_controls::{ParentalControlService, ContentPolicy};
+pub use recommendations::RecommendationService;
+pub use presets::{PresetService, ImportConflict};
+pub use media_filters::{MediaFilter, MediaFilterService, MediaFacets};
+pub use search_suggestions::SearchSuggestions;
+pub use semantic_search::SemanticSearch;
+pub use dialogue_search::DialogueSearch;
+pub use webhooks::WebhookService;
+pub use notifications::NotificationService;
+pub use runtime_settings::{RuntimeSettings, SettingsService};
+pub use task_scheduler::{TaskDefinition, TaskInfo, TaskScheduler, TaskUpdate};
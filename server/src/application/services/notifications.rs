@@ -0,0 +1,315 @@
+//! Notifications
+//!
+//! Tells people about library events through admin-configured channels:
+//! Discord webhooks, Telegram bots, Gotify and email. Each channel is
+//! routed the event types it should hear about. Unlike webhook deliveries,
+//! sends are not retried or logged; a failing channel shows up in the
+//! server log and in the result of its test endpoint.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::domain::repositories::{NotificationChannel, NotificationChannelRepository, NotificationChannelSettings};
+use crate::infrastructure::external::notifications::{build_notifier, Notification, Notifier, NotifierConfig};
+use crate::shared::error::{ApplicationError, DomainError, NotificationError};
+
+/// Event types channels can be routed
+pub const NOTIFICATION_EVENT_TYPES: &[&str] = &[
+    "media_identified",
+    "scan_completed",
+    "scan_failed",
+    "subtitle_generation_completed",
+    "subtitle_generation_failed",
+    "stream_started",
+];
+
+/// Shown in place of credentials; sent back unchanged, it keeps them
+pub const REDACTED_SECRET: &str = "********";
+
+/// A channel with its sender, ready to notify
+struct ActiveChannel {
+    channel: NotificationChannel,
+    notifier: Arc<dyn Notifier>,
+}
+
+/// Manages notification channels and routes notifications to them
+pub struct NotificationService {
+    repository: Arc<dyn NotificationChannelRepository>,
+    /// Enabled channels loaded for notifying, dropped when they change
+    channels: RwLock<Option<Arc<Vec<ActiveChannel>>>>,
+    /// Off mutes every channel; tests are still sent
+    enabled: AtomicBool,
+}
+
+impl NotificationService {
+    pub fn new(repository: Arc<dyn NotificationChannelRepository>) -> Self {
+        Self {
+            repository,
+            channels: RwLock::new(None),
+            enabled: AtomicBool::new(true),
+        }
+    }
+
+    /// Turns all notifications on or off while running
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Lists the channels
+    pub async fn list(&self) -> Result<Vec<NotificationChannel>, ApplicationError> {
+        Ok(self.repository.find_all().await?)
+    }
+
+    /// Gets a channel
+    pub async fn get(&self, id: i64) -> Result<NotificationChannel, ApplicationError> {
+        self.repository
+            .find(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Notification channel {} not found", id)).into())
+    }
+
+    /// Adds a channel
+    pub async fn create(&self, settings: NotificationChannelSettings) -> Result<NotificationChannel, ApplicationError> {
+        let settings = validate(settings)?;
+        let channel = self.repository.create(&settings).await?;
+        self.invalidate().await;
+        Ok(channel)
+    }
+
+    /// Changes a channel; credentials given as [`REDACTED_SECRET`] keep
+    /// their current value
+    pub async fn update(
+        &self,
+        id: i64,
+        mut settings: NotificationChannelSettings,
+    ) -> Result<NotificationChannel, ApplicationError> {
+        let current = self.get(id).await?;
+        if settings.kind == current.kind {
+            for field in NotifierConfig::secret_fields(&settings.kind) {
+                if settings.config.get(field).and_then(|v| v.as_str()) == Some(REDACTED_SECRET) {
+                    settings.config[*field] = current.config.get(field).cloned().unwrap_or_default();
+                }
+            }
+        }
+
+        let settings = validate(settings)?;
+        let channel = self
+            .repository
+            .update(id, &settings)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Notification channel {} not found", id)))?;
+        self.invalidate().await;
+        Ok(channel)
+    }
+
+    /// Removes a channel
+    pub async fn delete(&self, id: i64) -> Result<(), ApplicationError> {
+        if !self.repository.delete(id).await? {
+            return Err(DomainError::NotFound(format!("Notification channel {} not found", id)).into());
+        }
+        self.invalidate().await;
+        Ok(())
+    }
+
+    /// Sends a test notification to a channel, enabled or not, and waits
+    /// for the outcome
+    pub async fn test(&self, id: i64) -> Result<(), ApplicationError> {
+        let channel = self.get(id).await?;
+        let notifier = build_notifier(NotifierConfig::parse(&channel.kind, &channel.config)?)?;
+        let notification = Notification::new(
+            "Homeflix test notification",
+            format!("Notifications of the '{}' channel arrive here.", channel.name),
+        );
+        Ok(notifier.send(&notification).await?)
+    }
+
+    /// Sends a notification to the enabled channels routed its event type,
+    /// in the background
+    pub async fn notify(&self, event_type: &str, notification: Notification) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let channels = match self.channels().await {
+            Ok(channels) => channels,
+            Err(e) => {
+                warn!("Failed to load notification channels for {}: {}", event_type, e);
+                return;
+            }
+        };
+
+        let notification = Arc::new(notification);
+        for active in channels.iter().filter(|c| c.channel.accepts(event_type)) {
+            let notifier = active.notifier.clone();
+            let (id, name) = (active.channel.id, active.channel.name.clone());
+            let event_type = event_type.to_string();
+            let notification = notification.clone();
+            tokio::spawn(async move {
+                match notifier.send(&notification).await {
+                    Ok(()) => debug!("Notified {} channel {} of {}", notifier.name(), id, event_type),
+                    Err(e) => warn!(
+                        "Failed to notify {} channel {} ('{}') of {}: {}",
+                        notifier.name(),
+                        id,
+                        name,
+                        event_type,
+                        e
+                    ),
+                }
+            });
+        }
+    }
+
+    /// Whether any enabled channel is routed an event type, so callers can
+    /// skip preparing notifications nobody gets
+    pub async fn is_routed(&self, event_type: &str) -> bool {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return false;
+        }
+        match self.channels().await {
+            Ok(channels) => channels.iter().any(|c| c.channel.accepts(event_type)),
+            Err(_) => false,
+        }
+    }
+
+    /// Enabled channels with their senders, loaded on first use
+    async fn channels(&self) -> Result<Arc<Vec<ActiveChannel>>, ApplicationError> {
+        if let Some(channels) = self.channels.read().await.clone() {
+            return Ok(channels);
+        }
+
+        let mut channels = Vec::new();
+        for channel in self.repository.find_all().await?.into_iter().filter(|c| c.enabled) {
+            match NotifierConfig::parse(&channel.kind, &channel.config).and_then(build_notifier) {
+                Ok(notifier) => channels.push(ActiveChannel { channel, notifier }),
+                Err(e) => warn!("Skipping notification channel {} ('{}'): {}", channel.id, channel.name, e),
+            }
+        }
+        let channels = Arc::new(channels);
+        *self.channels.write().await = Some(channels.clone());
+        Ok(channels)
+    }
+
+    async fn invalidate(&self) {
+        *self.channels.write().await = None;
+    }
+}
+
+/// Replaces the credentials of a channel with [`REDACTED_SECRET`], for
+/// showing it
+pub fn redact_secrets(mut channel: NotificationChannel) -> NotificationChannel {
+    for field in NotifierConfig::secret_fields(&channel.kind) {
+        if let Some(value) = channel.config.get_mut(field).filter(|v| !v.is_null()) {
+            *value = serde_json::Value::from(REDACTED_SECRET);
+        }
+    }
+    channel
+}
+
+/// Checks and tidies channel settings
+fn validate(mut settings: NotificationChannelSettings) -> Result<NotificationChannelSettings, ApplicationError> {
+    settings.name = settings.name.trim().to_string();
+    if settings.name.is_empty() {
+        return Err(DomainError::InvalidInput("Notification channel name is empty".to_string()).into());
+    }
+    settings.kind = settings.kind.trim().to_lowercase();
+    NotifierConfig::parse(&settings.kind, &settings.config)
+        .and_then(build_notifier)
+        .map_err(|e| match e {
+            NotificationError::InvalidConfig(msg) => DomainError::InvalidInput(msg),
+            e => DomainError::InvalidInput(e.to_string()),
+        })?;
+
+    if let Some(unknown) = settings.event_types.iter().find(|t| !NOTIFICATION_EVENT_TYPES.contains(&t.as_str())) {
+        return Err(DomainError::InvalidInput(format!(
+            "Unknown event type '{}', expected some of: {}",
+            unknown,
+            NOTIFICATION_EVENT_TYPES.join(", ")
+        ))
+        .into());
+    }
+    settings.event_types.sort();
+    settings.event_types.dedup();
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteNotificationChannelRepository;
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_routing_and_secrets() {
+        // A Discord webhook that passes on the embeds it receives
+        let (tx, mut rx) = mpsc::unbounded_channel::<serde_json::Value>();
+        let app = Router::new().route(
+            "/api/webhooks/1/token",
+            post(move |Json(body): Json<serde_json::Value>| async move {
+                tx.send(body).unwrap();
+                StatusCode::NO_CONTENT
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let service = NotificationService::new(Arc::new(SqliteNotificationChannelRepository::new(pool)));
+
+        let webhook_url = format!("http://{}/api/webhooks/1/token", addr);
+        let settings = NotificationChannelSettings {
+            name: "Discord".to_string(),
+            kind: "discord".to_string(),
+            config: json!({ "webhook_url": webhook_url }),
+            event_types: vec!["scan_failed".to_string()],
+            enabled: true,
+        };
+        assert!(service.create(NotificationChannelSettings { kind: "pager".to_string(), ..settings.clone() }).await.is_err());
+        assert!(service
+            .create(NotificationChannelSettings { event_types: vec!["nope".to_string()], ..settings.clone() })
+            .await
+            .is_err());
+        let channel = service.create(settings.clone()).await.unwrap();
+
+        // Only routed event types are sent
+        assert!(!service.is_routed("scan_completed").await);
+        service.notify("scan_completed", Notification::new("Scan finished", "")).await;
+        service.notify("scan_failed", Notification::new("Scan failed", "Disk gone")).await;
+        let body = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(body["embeds"][0]["title"], "Scan failed");
+        assert_eq!(body["embeds"][0]["description"], "Disk gone");
+        assert!(rx.try_recv().is_err());
+
+        // Credentials are hidden, and sending them back keeps them
+        let shown = redact_secrets(channel.clone());
+        assert_eq!(shown.config["webhook_url"], REDACTED_SECRET);
+        let updated = service
+            .update(channel.id, NotificationChannelSettings { config: shown.config, ..settings })
+            .await
+            .unwrap();
+        assert_eq!(updated.config["webhook_url"], webhook_url.as_str());
+
+        service.test(channel.id).await.unwrap();
+        let body = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(body["embeds"][0]["title"], "Homeflix test notification");
+
+        // Muted notifications are not sent
+        service.set_enabled(false);
+        assert!(!service.is_routed("scan_failed").await);
+        service.notify("scan_failed", Notification::new("Scan failed", "")).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(rx.try_recv().is_err());
+    }
+}
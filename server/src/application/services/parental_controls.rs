@@ -0,0 +1,552 @@
+//! Parental Controls
+//!
+//! Hides titles above a user's maximum age rating or with a blocked genre or
+//! content warning. Certifications come from TMDB (see
+//! [`ContentRatingBackfill`](super::ContentRatingBackfill)) and are mapped to
+//! the minimum age they stand for, so "PG-13", "TV-14" and "FSK 12" compare
+//! on one scale.
+//!
+//! A PIN protects the controls: once set, it is needed to change or remove
+//! them, and lifts them for a while when entered on the override endpoint.
+//! PINs are stored as salted Argon2 hashes, and wrong entries are counted in
+//! the database so a lockout outlasts a restart.
+//!
+//! A user whose active profile is in kid mode only sees children's titles,
+//! on top of the user's own controls.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::{Media, Series};
+use crate::domain::repositories::{
+    ParentalControlRepository, ParentalControls, PinFailures, ProfileRepository, SeriesRepository, StoredParentalControls,
+};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Longest override
+const MAX_OVERRIDE_MINUTES: u32 = 24 * 60;
+/// Wrong PINs accepted per user before entries are refused
+const MAX_PIN_ATTEMPTS: u32 = 5;
+/// How long entries are refused after too many wrong PINs
+const PIN_LOCKOUT_MINUTES: i64 = 5;
+/// Most blocked tags per user
+const MAX_BLOCKED_TAGS: usize = 50;
+/// Highest certification age of kid mode ("PG", "TV-Y7")
+const KID_MODE_MAX_AGE: u8 = 8;
+
+/// Parental controls of a user as shown to clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentalControlStatus {
+    pub controls: ParentalControls,
+    pub pin_set: bool,
+    /// End of an active PIN override
+    pub override_until: Option<DateTime<Utc>>,
+}
+
+/// What a user may see right now
+#[derive(Debug, Clone, Default)]
+pub struct ContentPolicy {
+    /// None = unrestricted
+    controls: Option<ParentalControls>,
+}
+
+impl ContentPolicy {
+    /// A policy that allows everything
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// Whether any title may be hidden
+    pub fn is_restricted(&self) -> bool {
+        self.controls.is_some()
+    }
+
+    /// Whether a movie or episode may be shown
+    pub fn allows_media(&self, media: &Media) -> bool {
+        self.allows(
+            media.content_rating.as_deref(),
+            [media.genres.as_deref(), media.content_warnings.as_deref()],
+        )
+    }
+
+    /// Whether a series may be shown
+    pub fn allows_series(&self, series: &Series) -> bool {
+        self.allows(series.content_rating.as_deref(), [series.genres.as_deref(), None])
+    }
+
+    /// Whether a title with this rating, genres and warnings may be shown
+    pub fn allows_rated(&self, rating: Option<&str>, genres: Option<&str>, warnings: Option<&str>) -> bool {
+        self.allows(rating, [genres, warnings])
+    }
+
+    fn allows(&self, rating: Option<&str>, tags: [Option<&str>; 2]) -> bool {
+        let Some(controls) = &self.controls else { return true };
+
+        if let Some(max_age) = controls.max_age {
+            match rating.and_then(minimum_age) {
+                Some(age) if age > max_age => return false,
+                None if controls.block_unrated => return false,
+                _ => {}
+            }
+        }
+
+        !tags
+            .into_iter()
+            .flatten()
+            .flat_map(|tags| tags.split(','))
+            .map(str::trim)
+            .any(|tag| controls.blocked_tags.iter().any(|blocked| blocked.eq_ignore_ascii_case(tag)))
+    }
+}
+
+/// Per-user parental controls with PIN overrides
+pub struct ParentalControlService {
+    repository: Arc<dyn ParentalControlRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    /// Profiles whose kid mode restricts their user
+    profiles: Option<Arc<dyn ProfileRepository>>,
+    /// Users with lifted controls and when the override ends
+    overrides: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Per-user locks held while a PIN is checked, so parallel guesses are
+    /// all counted
+    pin_checks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl ParentalControlService {
+    pub fn new(repository: Arc<dyn ParentalControlRepository>, series_repository: Arc<dyn SeriesRepository>) -> Self {
+        Self {
+            repository,
+            series_repository,
+            profiles: None,
+            overrides: Mutex::new(HashMap::new()),
+            pin_checks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Restricts users whose active profile is in kid mode
+    pub fn with_profiles(mut self, profiles: Arc<dyn ProfileRepository>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    /// Gets the controls of a user
+    pub async fn get(&self, user: &str) -> Result<Option<ParentalControlStatus>, ApplicationError> {
+        let Some(stored) = self.repository.find(user).await? else { return Ok(None) };
+        Ok(Some(ParentalControlStatus {
+            controls: stored.controls,
+            pin_set: stored.pin_hash.is_some(),
+            override_until: self.active_override(user).await,
+        }))
+    }
+
+    /// Gets what a user may see, taking overrides into account
+    pub async fn policy(&self, user: &str) -> Result<ContentPolicy, ApplicationError> {
+        if self.active_override(user).await.is_some() {
+            return Ok(ContentPolicy::unrestricted());
+        }
+        let mut controls = self.repository.find(user).await?.map(|stored| stored.controls);
+        if self.in_kid_mode(user).await? {
+            let mut kid = controls.unwrap_or_default();
+            kid.max_age = Some(kid.max_age.map_or(KID_MODE_MAX_AGE, |age| age.min(KID_MODE_MAX_AGE)));
+            kid.block_unrated = true;
+            controls = Some(kid);
+        }
+        Ok(ContentPolicy { controls })
+    }
+
+    /// Whether the active profile of a user is in kid mode
+    pub async fn in_kid_mode(&self, user: &str) -> Result<bool, ApplicationError> {
+        Ok(match &self.profiles {
+            Some(profiles) => profiles.find_active(user).await?.is_some_and(|p| p.settings.kid_mode),
+            None => false,
+        })
+    }
+
+    /// Whether a user may watch a movie or episode
+    ///
+    /// Episodes are also checked against their series, whose genres they do
+    /// not carry.
+    pub async fn allows_media(&self, user: &str, media: &Media) -> Result<bool, ApplicationError> {
+        let policy = self.policy(user).await?;
+        Ok(!self.retain_allowed(&policy, vec![media.clone()]).await?.is_empty())
+    }
+
+    /// Keeps the movies and episodes a policy allows, checking episodes
+    /// against their series as well
+    pub async fn retain_allowed(&self, policy: &ContentPolicy, media: Vec<Media>) -> Result<Vec<Media>, ApplicationError> {
+        if !policy.is_restricted() {
+            return Ok(media);
+        }
+        let mut series_allowed: HashMap<i64, bool> = HashMap::new();
+        let mut allowed = Vec::with_capacity(media.len());
+        for item in media {
+            if !policy.allows_media(&item) {
+                continue;
+            }
+            if let Some(series_id) = item.series_id {
+                let series_ok = match series_allowed.get(&series_id) {
+                    Some(ok) => *ok,
+                    None => {
+                        let ok = self
+                            .series_repository
+                            .find_by_id(series_id)
+                            .await?
+                            .is_none_or(|series| policy.allows_series(&series));
+                        series_allowed.insert(series_id, ok);
+                        ok
+                    }
+                };
+                if !series_ok {
+                    continue;
+                }
+            }
+            allowed.push(item);
+        }
+        Ok(allowed)
+    }
+
+    /// Sets the controls of a user
+    ///
+    /// Once a PIN is set, `pin` must match it. `new_pin` replaces the PIN;
+    /// None keeps the current one.
+    pub async fn set(
+        &self,
+        user: &str,
+        mut controls: ParentalControls,
+        pin: Option<&str>,
+        new_pin: Option<&str>,
+    ) -> Result<(), ApplicationError> {
+        let current = self.repository.find(user).await?;
+        let current_hash = current.and_then(|stored| stored.pin_hash);
+        if current_hash.is_some() {
+            self.verify_pin(user, current_hash.as_deref(), pin).await?;
+        }
+
+        let mut blocked_tags: Vec<String> = Vec::new();
+        for tag in controls.blocked_tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+            if !blocked_tags.iter().any(|blocked| blocked.eq_ignore_ascii_case(tag)) {
+                blocked_tags.push(tag.to_string());
+            }
+        }
+        controls.blocked_tags = blocked_tags;
+        if controls.blocked_tags.len() > MAX_BLOCKED_TAGS {
+            return Err(invalid(format!("At most {} blocked tags are allowed", MAX_BLOCKED_TAGS)));
+        }
+
+        let pin_hash = match new_pin {
+            Some(new_pin) => {
+                validate_pin(new_pin)?;
+                Some(hash_pin(new_pin.to_string()).await?)
+            }
+            None => current_hash,
+        };
+        self.repository.save(user, &StoredParentalControls { controls, pin_hash }).await?;
+        info!("Parental controls updated for user '{}'", user);
+        Ok(())
+    }
+
+    /// Removes the controls of a user; needs the PIN once one is set
+    pub async fn remove(&self, user: &str, pin: Option<&str>) -> Result<(), ApplicationError> {
+        let Some(current) = self.repository.find(user).await? else {
+            return Err(ApplicationError::Domain(DomainError::NotFound(format!(
+                "No parental controls for user '{}'", user
+            ))));
+        };
+        if current.pin_hash.is_some() {
+            self.verify_pin(user, current.pin_hash.as_deref(), pin).await?;
+        }
+        self.repository.delete(user).await?;
+        self.overrides.lock().await.remove(user);
+        info!("Parental controls removed for user '{}'", user);
+        Ok(())
+    }
+
+    /// Lifts the controls of a user for a number of minutes
+    ///
+    /// # Returns
+    /// * When the override ends
+    pub async fn unlock(&self, user: &str, pin: &str, minutes: u32) -> Result<DateTime<Utc>, ApplicationError> {
+        if !(1..=MAX_OVERRIDE_MINUTES).contains(&minutes) {
+            return Err(invalid(format!("Override must last 1 to {} minutes", MAX_OVERRIDE_MINUTES)));
+        }
+        let Some(current) = self.repository.find(user).await? else {
+            return Err(ApplicationError::Domain(DomainError::NotFound(format!(
+                "No parental controls for user '{}'", user
+            ))));
+        };
+        if current.pin_hash.is_none() {
+            return Err(invalid("No PIN is set for this user".to_string()));
+        }
+        self.verify_pin(user, current.pin_hash.as_deref(), Some(pin)).await?;
+
+        let until = Utc::now() + chrono::Duration::minutes(i64::from(minutes));
+        self.overrides.lock().await.insert(user.to_string(), until);
+        info!("Parental controls lifted for user '{}' until {}", user, until);
+        Ok(until)
+    }
+
+    /// Ends the override of a user, returning whether one was active
+    pub async fn lock(&self, user: &str) -> bool {
+        let active = self.active_override(user).await.is_some();
+        self.overrides.lock().await.remove(user);
+        active
+    }
+
+    /// Checks the PIN of a user, e.g. before a profile leaves kid mode
+    pub async fn check_pin(&self, user: &str, pin: &str) -> Result<(), ApplicationError> {
+        let pin_hash = self.repository.find(user).await?.and_then(|stored| stored.pin_hash);
+        if pin_hash.is_none() {
+            return Err(invalid("No PIN is set for this user".to_string()));
+        }
+        self.verify_pin(user, pin_hash.as_deref(), Some(pin)).await
+    }
+
+    async fn active_override(&self, user: &str) -> Option<DateTime<Utc>> {
+        let mut overrides = self.overrides.lock().await;
+        let now = Utc::now();
+        overrides.retain(|_, until| *until > now);
+        overrides.get(user).copied()
+    }
+
+    /// Checks a PIN entry, refusing entries for a while after repeated
+    /// wrong PINs
+    async fn verify_pin(&self, user: &str, pin_hash: Option<&str>, pin: Option<&str>) -> Result<(), ApplicationError> {
+        let check = self.pin_check(user).await;
+        let _check = check.lock().await;
+        let now = Utc::now();
+        let mut failures = self.repository.pin_failures(user).await?;
+        if failures.count >= MAX_PIN_ATTEMPTS {
+            let locked_until = failures.last_at.map(|at| at + chrono::Duration::minutes(PIN_LOCKOUT_MINUTES));
+            if locked_until.is_some_and(|until| until > now) {
+                return Err(ApplicationError::Domain(DomainError::InvalidState(
+                    "Too many wrong PINs, try again later".to_string(),
+                )));
+            }
+            failures = PinFailures::default();
+        }
+
+        let matches = match (pin_hash, pin) {
+            (Some(pin_hash), Some(pin)) => pin_matches(pin_hash.to_string(), pin.to_string()).await?,
+            _ => false,
+        };
+        if matches {
+            if failures.count > 0 {
+                self.repository.save_pin_failures(user, &PinFailures::default()).await?;
+            }
+            return Ok(());
+        }
+        failures.count += 1;
+        failures.last_at = Some(now);
+        self.repository.save_pin_failures(user, &failures).await?;
+        Err(ApplicationError::Domain(DomainError::BusinessRuleViolation(
+            if pin.is_some() { "Wrong PIN" } else { "PIN required" }.to_string(),
+        )))
+    }
+
+    /// Lock serializing the PIN checks of a user
+    async fn pin_check(&self, user: &str) -> Arc<Mutex<()>> {
+        let mut checks = self.pin_checks.lock().await;
+        // Drop the locks no check holds
+        checks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        checks.entry(user.to_string()).or_default().clone()
+    }
+}
+
+/// Minimum age a certification stands for, or None if it is unknown
+///
+/// Covers the US movie and TV ratings and the UK and Australian classes;
+/// other systems are read by their age number ("FSK 16", "12A", "MA15+").
+pub fn minimum_age(rating: &str) -> Option<u8> {
+    let rating = rating.trim().to_ascii_uppercase();
+    let age = match rating.as_str() {
+        "G" | "U" | "UC" | "TV-Y" | "TV-G" | "E" | "AL" | "ALL" => 0,
+        "TV-Y7" | "TV-Y7-FV" => 7,
+        "PG" | "TV-PG" => 8,
+        "PG-13" => 13,
+        "TV-14" => 14,
+        "M" => 15,
+        "R" | "TV-MA" => 17,
+        "NC-17" | "X" => 18,
+        _ => {
+            let digits: String = rating
+                .chars()
+                .skip_while(|c| !c.is_ascii_digit())
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse().ok().filter(|age| *age <= 21)?
+        }
+    };
+    Some(age)
+}
+
+fn validate_pin(pin: &str) -> Result<(), ApplicationError> {
+    if (4..=8).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(invalid("PIN must be 4 to 8 digits".to_string()))
+    }
+}
+
+/// Hashes a PIN with Argon2 and a random salt, off the async runtime
+async fn hash_pin(pin: String) -> Result<String, ApplicationError> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+            .map_err(|e| ApplicationError::Internal(format!("PIN salt: {}", e)))?;
+        Argon2::default()
+            .hash_password(pin.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| ApplicationError::Internal(format!("PIN hash: {}", e)))
+    })
+    .await
+    .map_err(|e| ApplicationError::Internal(format!("PIN hash: {}", e)))?
+}
+
+/// Whether a PIN matches its stored Argon2 hash, checked off the async
+/// runtime
+async fn pin_matches(pin_hash: String, pin: String) -> Result<bool, ApplicationError> {
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&pin_hash)
+            .is_ok_and(|hash| Argon2::default().verify_password(pin.as_bytes(), &hash).is_ok())
+    })
+    .await
+    .map_err(|e| ApplicationError::Internal(format!("PIN check: {}", e)))
+}
+
+fn invalid(message: String) -> ApplicationError {
+    ApplicationError::Domain(DomainError::InvalidInput(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::domain::repositories::ProfileSettings;
+    use crate::infrastructure::persistence::sqlite::{SqliteParentalControlRepository, SqliteProfileRepository, SqliteSeriesRepository};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[test]
+    fn test_minimum_age() {
+        assert_eq!(minimum_age("G"), Some(0));
+        assert_eq!(minimum_age("PG-13"), Some(13));
+        assert_eq!(minimum_age("tv-ma"), Some(17));
+        assert_eq!(minimum_age("12A"), Some(12));
+        assert_eq!(minimum_age("FSK 16"), Some(16));
+        assert_eq!(minimum_age("MA15+"), Some(15));
+        assert_eq!(minimum_age("NR"), None);
+        assert_eq!(minimum_age(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_parental_controls() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repository = Arc::new(SqliteParentalControlRepository::new(pool.clone()));
+        let series = Arc::new(SqliteSeriesRepository::new(pool));
+        let service = ParentalControlService::new(repository.clone(), series.clone());
+
+        let movie = |rating: &str, genres: &str| {
+            Media::new("/m.mkv".into(), crate::domain::value_objects::MediaType::Movie, "Movie".into())
+                .unwrap()
+                .with_content_rating(Some(rating.to_string()))
+                .with_genres(Some(genres.to_string()))
+        };
+        assert!(service.policy("kid").await.unwrap().allows_media(&movie("R", "Horror")));
+
+        let controls = ParentalControls {
+            max_age: Some(12),
+            block_unrated: false,
+            blocked_tags: vec![" horror ".to_string()],
+        };
+        service.set("kid", controls.clone(), None, Some("1234")).await.unwrap();
+        let policy = service.policy("kid").await.unwrap();
+        assert!(policy.allows_media(&movie("PG", "Comedy")));
+        assert!(policy.allows_media(&movie("", "Comedy")));
+        assert!(!policy.allows_media(&movie("PG-13", "Comedy")));
+        assert!(!policy.allows_media(&movie("PG", "Comedy, Horror")));
+
+        // The PIN guards changes and lifts the controls for a while
+        assert!(service.set("kid", ParentalControls::default(), None, None).await.is_err());
+        assert!(service.remove("kid", Some("0000")).await.is_err());
+        service.unlock("kid", "1234", 30).await.unwrap();
+        assert!(service.policy("kid").await.unwrap().allows_media(&movie("R", "Horror")));
+        assert!(service.lock("kid").await);
+        assert!(!service.policy("kid").await.unwrap().allows_media(&movie("R", "Horror")));
+
+        // Wrong PINs lock entry, also after a restart
+        for _ in 0..MAX_PIN_ATTEMPTS {
+            assert!(service.unlock("kid", "0000", 30).await.is_err());
+        }
+        let restarted = ParentalControlService::new(repository.clone(), series);
+        assert!(matches!(
+            restarted.unlock("kid", "1234", 30).await,
+            Err(ApplicationError::Domain(DomainError::InvalidState(_)))
+        ));
+        repository.save_pin_failures("kid", &PinFailures::default()).await.unwrap();
+
+        let stored = repository.find("kid").await.unwrap().unwrap();
+        assert!(stored.pin_hash.as_deref().unwrap().starts_with("$argon2"));
+        restarted.unlock("kid", "1234", 1).await.unwrap();
+
+        // A check in progress for one user does not hold up another's
+        service.set("teen", ParentalControls::default(), None, Some("5678")).await.unwrap();
+        {
+            let kid_check = service.pin_check("kid").await;
+            let _kid_check = kid_check.lock().await;
+            let teen = tokio::time::timeout(std::time::Duration::from_secs(5), service.unlock("teen", "5678", 1));
+            assert!(teen.await.expect("PIN checks of other users are independent").is_ok());
+        }
+
+        service.remove("kid", Some("1234")).await.unwrap();
+        assert_eq!(service.get("kid").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_kid_mode_profile() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let profiles = Arc::new(SqliteProfileRepository::new(pool.clone()));
+        let service = ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            Arc::new(SqliteSeriesRepository::new(pool)),
+        )
+        .with_profiles(profiles.clone());
+
+        let movie = |rating: &str| {
+            Media::new("/m.mkv".into(), crate::domain::value_objects::MediaType::Movie, "Movie".into())
+                .unwrap()
+                .with_content_rating(Some(rating.to_string()))
+        };
+        let parent = profiles
+            .create("home", &ProfileSettings { name: "Parent".into(), ..Default::default() })
+            .await
+            .unwrap();
+        let kid = profiles
+            .create("home", &ProfileSettings { name: "Kid".into(), kid_mode: true, ..Default::default() })
+            .await
+            .unwrap();
+        assert!(service.policy("home").await.unwrap().allows_media(&movie("R")));
+
+        profiles.activate(kid.id).await.unwrap();
+        let policy = service.policy("home").await.unwrap();
+        assert!(policy.allows_media(&movie("PG")));
+        assert!(!policy.allows_media(&movie("PG-13")));
+        assert!(!policy.allows_media(&movie("")));
+
+        profiles.activate(parent.id).await.unwrap();
+        assert!(service.policy("home").await.unwrap().allows_media(&movie("R")));
+    }
+}
@@ -0,0 +1,360 @@
+//! Playback Decision Service
+//!
+//! Decides how a client should play a file: direct play of the original,
+//! remux into fragmented MP4 (video copied), or a full transcode. The
+//! decision is based on the capabilities the client reports and the FFprobe
+//! analysis of the file.
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::{QualityConstraint, QualityPreset};
+use crate::interfaces::external_services::{VideoAnalysis, VideoAnalyzer};
+use crate::shared::error::ApplicationError;
+
+/// Codecs the web stream always produces
+const WEB_STREAM_AUDIO_CODEC: &str = "aac";
+
+/// What a client can play, as reported by the client
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientCapabilities {
+    /// Video codecs ("h264", "hevc", "vp9", "av1", ...)
+    #[serde(default = "default_video_codecs")]
+    pub video_codecs: Vec<String>,
+    /// Audio codecs ("aac", "ac3", "eac3", "opus", ...)
+    #[serde(default = "default_audio_codecs")]
+    pub audio_codecs: Vec<String>,
+    /// Containers ("mp4", "mkv", "webm", ...)
+    #[serde(default = "default_containers")]
+    pub containers: Vec<String>,
+    /// Largest frame width the client can display
+    pub max_width: Option<u32>,
+    /// Largest frame height the client can display
+    pub max_height: Option<u32>,
+    /// Highest bitrate the client can receive, in kbps
+    pub max_bitrate_kbps: Option<u32>,
+    /// Whether the client plays HLS (preferred for transcodes)
+    #[serde(default)]
+    pub hls: bool,
+}
+
+fn default_video_codecs() -> Vec<String> {
+    vec!["h264".to_string()]
+}
+
+fn default_audio_codecs() -> Vec<String> {
+    vec!["aac".to_string(), "mp3".to_string()]
+}
+
+fn default_containers() -> Vec<String> {
+    vec!["mp4".to_string()]
+}
+
+impl Default for ClientCapabilities {
+    /// Baseline every browser supports (H.264/AAC in MP4)
+    fn default() -> Self {
+        Self {
+            video_codecs: default_video_codecs(),
+            audio_codecs: default_audio_codecs(),
+            containers: default_containers(),
+            max_width: None,
+            max_height: None,
+            max_bitrate_kbps: None,
+            hls: false,
+        }
+    }
+}
+
+/// How the file will be delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackMethod {
+    /// Original file served as-is
+    DirectPlay,
+    /// Video copied into fragmented MP4, audio converted if needed
+    Remux,
+    /// Video re-encoded to H.264
+    Transcode,
+}
+
+/// Outcome of the playback decision
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackDecision {
+    pub method: PlaybackMethod,
+    /// Why the file cannot be played directly (empty for direct play)
+    pub reasons: Vec<String>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub container: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub transcode_video: bool,
+    pub transcode_audio: bool,
+    /// Limits the transcode must respect (client limits and overrides)
+    pub quality_constraint: Option<QualityConstraint>,
+}
+
+/// Playback decision service
+pub struct PlaybackDecisionService {
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+}
+
+impl PlaybackDecisionService {
+    /// Creates a new playback decision service
+    pub fn new(video_analyzer: Arc<dyn VideoAnalyzer>) -> Self {
+        Self { video_analyzer }
+    }
+
+    /// Analyzes a file and decides how the client should play it
+    ///
+    /// `constraint` is a quality override chosen by the user; it forces a
+    /// transcode when the source exceeds it.
+    pub async fn decide(
+        &self,
+        file_path: &str,
+        capabilities: &ClientCapabilities,
+        audio_track: usize,
+        constraint: Option<QualityConstraint>,
+    ) -> Result<PlaybackDecision, ApplicationError> {
+        let analysis = self.video_analyzer.analyze(file_path).await?;
+        Ok(Self::evaluate(&analysis, capabilities, audio_track, constraint))
+    }
+
+    /// Decides how a client should play an analyzed file
+    pub fn evaluate(
+        analysis: &VideoAnalysis,
+        capabilities: &ClientCapabilities,
+        audio_track: usize,
+        constraint: Option<QualityConstraint>,
+    ) -> PlaybackDecision {
+        let mut reasons = Vec::new();
+
+        let video_codec = analysis.video_codec.as_deref().map(normalize_codec);
+        let audio_codec = analysis
+            .audio_tracks
+            .get(audio_track)
+            .and_then(|t| t.codec.as_deref())
+            .or(analysis.audio_codec.as_deref())
+            .map(normalize_codec);
+
+        // Video: codec, resolution and bitrate must all fit
+        let mut video_ok = match &video_codec {
+            Some(codec) if supports(&capabilities.video_codecs, codec, normalize_codec) => true,
+            Some(codec) => {
+                reasons.push(format!("Video codec {} not supported", codec));
+                false
+            }
+            None => {
+                reasons.push("Video codec unknown".to_string());
+                false
+            }
+        };
+        let exceeds_width = capabilities.max_width.is_some_and(|w| analysis.width > w);
+        let exceeds_height = capabilities.max_height.is_some_and(|h| analysis.height > h);
+        if exceeds_width || exceeds_height {
+            reasons.push(format!("Resolution {}x{} exceeds client limit", analysis.width, analysis.height));
+            video_ok = false;
+        }
+        let source_kbps = analysis.video_bitrate.map(|b| b / 1000);
+        if let (Some(max), Some(kbps)) = (capabilities.max_bitrate_kbps, source_kbps) {
+            if kbps > max as u64 {
+                reasons.push(format!("Bitrate {} kbps exceeds client limit of {} kbps", kbps, max));
+                video_ok = false;
+            }
+        }
+        if constraint.is_some_and(|c| c.requires_transcode(analysis.height)) {
+            reasons.push("Quality override requires a transcode".to_string());
+            video_ok = false;
+        }
+
+        let audio_ok = match &audio_codec {
+            Some(codec) if supports(&capabilities.audio_codecs, codec, normalize_codec) => true,
+            Some(codec) => {
+                reasons.push(format!("Audio codec {} not supported", codec));
+                false
+            }
+            None => true,
+        };
+
+        let container_ok = match &analysis.container {
+            Some(format) => {
+                let ok = format
+                    .split(',')
+                    .any(|name| supports(&capabilities.containers, name, normalize_container));
+                if !ok {
+                    reasons.push(format!("Container {} not supported", format));
+                }
+                ok
+            }
+            None => {
+                reasons.push("Container unknown".to_string());
+                false
+            }
+        };
+
+        let method = match (video_ok, audio_ok && container_ok) {
+            (true, true) => PlaybackMethod::DirectPlay,
+            (true, false) => PlaybackMethod::Remux,
+            (false, _) => PlaybackMethod::Transcode,
+        };
+
+        let quality_constraint = match method {
+            PlaybackMethod::Transcode => transcode_constraint(capabilities, constraint),
+            _ => None,
+        };
+
+        PlaybackDecision {
+            method,
+            reasons,
+            video_codec,
+            audio_codec: audio_codec.clone(),
+            container: analysis.container.clone(),
+            width: analysis.width,
+            height: analysis.height,
+            transcode_video: method == PlaybackMethod::Transcode,
+            transcode_audio: method != PlaybackMethod::DirectPlay
+                && audio_codec.as_deref() != Some(WEB_STREAM_AUDIO_CODEC),
+            quality_constraint,
+        }
+    }
+}
+
+/// Combines client limits and the user's override into one constraint
+fn transcode_constraint(
+    capabilities: &ClientCapabilities,
+    constraint: Option<QualityConstraint>,
+) -> Option<QualityConstraint> {
+    let client_preset = capabilities.max_height.map(preset_for_height);
+    let quality = match (constraint.and_then(|c| c.quality), client_preset) {
+        (Some(a), Some(b)) => Some(if a.max_height() <= b.max_height() { a } else { b }),
+        (a, b) => a.or(b),
+    };
+    let bitrate = match (constraint.and_then(|c| c.max_bitrate_kbps), capabilities.max_bitrate_kbps) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+
+    Some(QualityConstraint::new(quality, bitrate)).filter(|c| !c.is_empty())
+}
+
+/// Largest preset that fits into `height` (360p at minimum)
+fn preset_for_height(height: u32) -> QualityPreset {
+    [QualityPreset::P2160, QualityPreset::P1080, QualityPreset::P720, QualityPreset::P480]
+        .into_iter()
+        .find(|p| p.max_height() <= height)
+        .unwrap_or(QualityPreset::P360)
+}
+
+fn supports(list: &[String], name: &str, normalize: fn(&str) -> String) -> bool {
+    let name = normalize(name);
+    list.iter().any(|entry| normalize(entry) == name)
+}
+
+/// Maps codec aliases to FFprobe codec names
+fn normalize_codec(codec: &str) -> String {
+    let codec = codec.trim().to_lowercase();
+    match codec.as_str() {
+        "avc" | "avc1" | "x264" => "h264".to_string(),
+        "h265" | "hvc1" | "hev1" | "x265" => "hevc".to_string(),
+        "av01" => "av1".to_string(),
+        "vp09" => "vp9".to_string(),
+        "mp4a" => "aac".to_string(),
+        "ac-3" => "ac3".to_string(),
+        "e-ac-3" | "ec-3" => "eac3".to_string(),
+        _ => codec,
+    }
+}
+
+/// Maps container names to FFprobe format names
+fn normalize_container(container: &str) -> String {
+    let container = container.trim().to_lowercase();
+    match container.as_str() {
+        "mkv" => "matroska".to_string(),
+        "ts" | "m2ts" => "mpegts".to_string(),
+        "m4v" | "mov" => "mp4".to_string(),
+        _ => container,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::external_services::AudioTrack;
+
+    fn analysis(video: &str, audio: &str, container: &str, height: u32) -> VideoAnalysis {
+        VideoAnalysis {
+            duration_seconds: 3600.0,
+            width: height * 16 / 9,
+            height,
+            video_codec: Some(video.to_string()),
+            audio_codec: Some(audio.to_string()),
+            video_bitrate: Some(8_000_000),
+            audio_bitrate: None,
+            frame_rate: Some(23.976),
+            pixel_format: None,
+            rotation: None,
+            container: Some(container.to_string()),
+            audio_tracks: vec![AudioTrack {
+                index: 0,
+                language: None,
+                codec: Some(audio.to_string()),
+                sample_rate: None,
+                channels: Some(6),
+                bitrate: None,
+                title: None,
+                is_default: true,
+                is_audio_description: false,
+            }],
+            subtitle_tracks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_direct_play_remux_transcode() {
+        let browser = ClientCapabilities::default();
+
+        let mp4 = analysis("h264", "aac", "mov,mp4,m4a,3gp,3g2,mj2", 1080);
+        let decision = PlaybackDecisionService::evaluate(&mp4, &browser, 0, None);
+        assert_eq!(decision.method, PlaybackMethod::DirectPlay);
+        assert!(decision.reasons.is_empty());
+
+        // Supported video in an unsupported container is remuxed
+        let mkv = analysis("h264", "ac3", "matroska,webm", 1080);
+        let decision = PlaybackDecisionService::evaluate(&mkv, &browser, 0, None);
+        assert_eq!(decision.method, PlaybackMethod::Remux);
+        assert!(decision.transcode_audio);
+
+        // A TV that declares HEVC/AC3/MKV plays the same file directly
+        let tv = ClientCapabilities {
+            video_codecs: vec!["h264".to_string(), "h265".to_string()],
+            audio_codecs: vec!["aac".to_string(), "ac-3".to_string()],
+            containers: vec!["mkv".to_string(), "mp4".to_string()],
+            ..ClientCapabilities::default()
+        };
+        let hevc = analysis("hevc", "ac3", "matroska,webm", 2160);
+        assert_eq!(PlaybackDecisionService::evaluate(&hevc, &tv, 0, None).method, PlaybackMethod::DirectPlay);
+        assert_eq!(PlaybackDecisionService::evaluate(&hevc, &browser, 0, None).method, PlaybackMethod::Transcode);
+    }
+
+    #[test]
+    fn test_client_limits_force_transcode() {
+        let phone = ClientCapabilities {
+            max_height: Some(800),
+            max_bitrate_kbps: Some(4000),
+            ..ClientCapabilities::default()
+        };
+        let mp4 = analysis("h264", "aac", "mov,mp4,m4a,3gp,3g2,mj2", 1080);
+        let decision = PlaybackDecisionService::evaluate(&mp4, &phone, 0, None);
+        assert_eq!(decision.method, PlaybackMethod::Transcode);
+        assert_eq!(decision.reasons.len(), 2);
+        assert_eq!(
+            decision.quality_constraint,
+            Some(QualityConstraint::new(Some(QualityPreset::P720), Some(4000)))
+        );
+
+        // A stricter user override wins over the client limit
+        let override_480 = QualityConstraint::new(Some(QualityPreset::P480), None);
+        let decision = PlaybackDecisionService::evaluate(&mp4, &phone, 0, Some(override_480));
+        assert_eq!(decision.quality_constraint.and_then(|c| c.quality), Some(QualityPreset::P480));
+    }
+}
@@ -0,0 +1,236 @@
+//! Playback QoS
+//!
+//! Quality-of-service policy that keeps background work (library scans,
+//! preview clip generation) from competing with active playback for disk and
+//! CPU. Requests made by users, such as thumbnails, are never held back.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// How background work reacts to active playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosMode {
+    /// Background work waits until playback stops
+    Pause,
+    /// Background work is slowed down by a fixed delay per item
+    Throttle,
+    /// Background work is never held back
+    Off,
+}
+
+impl QosMode {
+    /// Parses a mode name ("pause", "throttle", "off"); unknown values yield None
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pause" => Some(QosMode::Pause),
+            "throttle" => Some(QosMode::Throttle),
+            "off" | "none" | "disabled" => Some(QosMode::Off),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QosMode::Pause => "pause",
+            QosMode::Throttle => "throttle",
+            QosMode::Off => "off",
+        }
+    }
+}
+
+/// Playback activity tracker and background-work gate
+///
+/// A stream counts as active while its response body is being sent (see
+/// [`PlaybackQos::track`]) and for a short grace period afterwards, so that
+/// players issuing back-to-back range requests are treated as one session.
+pub struct PlaybackQos {
+    mode: QosMode,
+    /// Delay applied per item in throttle mode
+    throttle_delay: Duration,
+    /// How long a stream stays active after its last request finished
+    idle_grace: Duration,
+    /// How often paused work re-checks for idleness
+    poll_interval: Duration,
+    /// Number of response bodies currently streaming
+    open_streams: AtomicUsize,
+    /// Last activity per media ID
+    last_activity: Mutex<HashMap<i64, Instant>>,
+}
+
+impl PlaybackQos {
+    /// Creates a new QoS policy
+    ///
+    /// # Defaults
+    /// - Throttle delay: 500ms
+    /// - Idle grace: 30s
+    /// - Poll interval: 2s
+    pub fn new(mode: QosMode) -> Self {
+        Self {
+            mode,
+            throttle_delay: Duration::from_millis(500),
+            idle_grace: Duration::from_secs(30),
+            poll_interval: Duration::from_secs(2),
+            open_streams: AtomicUsize::new(0),
+            last_activity: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the per-item delay used in throttle mode
+    pub fn with_throttle_delay(mut self, delay: Duration) -> Self {
+        self.throttle_delay = delay;
+        self
+    }
+
+    /// Sets how long a stream counts as active after its last request
+    pub fn with_idle_grace(mut self, grace: Duration) -> Self {
+        self.idle_grace = grace;
+        self
+    }
+
+    /// Sets how often paused work re-checks playback state
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval.max(Duration::from_millis(10));
+        self
+    }
+
+    /// Gets the configured mode
+    pub fn mode(&self) -> QosMode {
+        self.mode
+    }
+
+    /// Records playback activity for a media item and returns a guard that
+    /// keeps the stream active until dropped
+    ///
+    /// Move the guard into the response body stream so it lives as long as
+    /// data is being sent.
+    pub fn track(self: &Arc<Self>, media_id: i64) -> PlaybackGuard {
+        self.touch(media_id);
+        self.open_streams.fetch_add(1, Ordering::SeqCst);
+        PlaybackGuard {
+            qos: Arc::clone(self),
+            media_id,
+        }
+    }
+
+    /// Records playback activity without holding the stream open
+    pub fn touch(&self, media_id: i64) {
+        self.last_activity
+            .lock()
+            .unwrap()
+            .insert(media_id, Instant::now());
+    }
+
+    /// Number of media items currently being played
+    pub fn active_streams(&self) -> usize {
+        let mut activity = self.last_activity.lock().unwrap();
+        let grace = self.idle_grace;
+        activity.retain(|_, last| last.elapsed() < grace);
+        activity.len().max(self.open_streams.load(Ordering::SeqCst).min(1))
+    }
+
+    /// Whether any playback is currently active
+    pub fn is_playback_active(&self) -> bool {
+        self.active_streams() > 0
+    }
+
+    /// Yields to active playback before a unit of background work
+    ///
+    /// In pause mode this waits until playback stops; in throttle mode it
+    /// sleeps for the throttle delay. Returns immediately when idle.
+    pub async fn yield_to_playback(&self) {
+        match self.mode {
+            QosMode::Off => {}
+            QosMode::Throttle => self.throttle().await,
+            QosMode::Pause => {
+                if !self.is_playback_active() {
+                    return;
+                }
+                info!("Playback active, pausing background work");
+                let paused_at = Instant::now();
+                while self.is_playback_active() {
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+                info!(
+                    "Playback stopped, resuming background work after {}s",
+                    paused_at.elapsed().as_secs()
+                );
+            }
+        }
+    }
+
+    /// Slows down a unit of background work while playback is active
+    async fn throttle(&self) {
+        if self.mode != QosMode::Off && self.is_playback_active() {
+            debug!("Playback active, throttling background work by {:?}", self.throttle_delay);
+            tokio::time::sleep(self.throttle_delay).await;
+        }
+    }
+}
+
+/// Keeps a stream counted as open while alive
+pub struct PlaybackGuard {
+    qos: Arc<PlaybackQos>,
+    media_id: i64,
+}
+
+impl Drop for PlaybackGuard {
+    fn drop(&mut self) {
+        // Start the idle grace period from the end of the response
+        self.qos.touch(self.media_id);
+        self.qos.open_streams.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_keeps_stream_active_until_grace_expires() {
+        let qos = Arc::new(PlaybackQos::new(QosMode::Pause).with_idle_grace(Duration::from_millis(20)));
+        assert!(!qos.is_playback_active());
+
+        let guard = qos.track(1);
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(qos.is_playback_active());
+
+        drop(guard);
+        assert!(qos.is_playback_active());
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!qos.is_playback_active());
+    }
+
+    #[tokio::test]
+    async fn test_pause_resumes_when_playback_stops() {
+        let qos = Arc::new(
+            PlaybackQos::new(QosMode::Pause)
+                .with_idle_grace(Duration::from_millis(10))
+                .with_poll_interval(Duration::from_millis(10)),
+        );
+        let guard = qos.track(7);
+
+        let waiter = {
+            let qos = Arc::clone(&qos);
+            tokio::spawn(async move { qos.yield_to_playback().await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("background work did not resume")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(QosMode::parse("Pause"), Some(QosMode::Pause));
+        assert_eq!(QosMode::parse("throttle"), Some(QosMode::Throttle));
+        assert_eq!(QosMode::parse("off"), Some(QosMode::Off));
+        assert_eq!(QosMode::parse("bogus"), None);
+    }
+}
@@ -0,0 +1,437 @@
+//! Preset Service
+//!
+//! Manages the preset definitions in the presets directory and applies them
+//! to the preset collections without a restart: right after a change through
+//! the API, and when the YAML files are edited by hand.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+use crate::application::services::collection_manager::PresetStats;
+use crate::application::services::CollectionManager;
+use crate::domain::presets::PresetCollection;
+use crate::infrastructure::presets::PresetLoader;
+use crate::interfaces::external_services::TmdbService;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// A preset with the ID it is stored under
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetEntry {
+    pub id: String,
+    #[serde(flatten)]
+    pub preset: PresetCollection,
+}
+
+/// What happens to an imported preset whose name is taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflict {
+    /// Keep the existing preset
+    #[default]
+    Skip,
+    /// Overwrite the existing preset
+    Replace,
+    /// Import under a free name ("Alien Timeline (2)")
+    Rename,
+}
+
+/// Outcome of one imported preset
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    Created,
+    Replaced,
+    Renamed,
+    Skipped,
+    Invalid,
+}
+
+/// An item of an imported preset and whether it is in the library
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedItem {
+    pub tmdb_id: i64,
+    pub media_type: String,
+    pub title: String,
+    pub timeline_order: i32,
+    pub available: bool,
+}
+
+/// One preset of an import
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedPreset {
+    /// Name it is (or would be) stored under
+    pub name: String,
+    /// ID it is (or would be) stored under; the existing preset's when skipped
+    pub id: Option<String>,
+    pub outcome: ImportOutcome,
+    /// Why an invalid preset was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub total_items: usize,
+    pub available_items: usize,
+    pub items: Vec<ImportedItem>,
+}
+
+/// Result of importing a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetImportReport {
+    /// Whether nothing was stored
+    pub dry_run: bool,
+    pub created: usize,
+    pub replaced: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub presets: Vec<ImportedPreset>,
+}
+
+/// Preset Service
+pub struct PresetService {
+    presets_dir: PathBuf,
+    collection_manager: Arc<CollectionManager>,
+    tmdb_service: Arc<dyn TmdbService>,
+    /// Held while presets are written or applied; the file names and
+    /// modification times applied last
+    applied: Mutex<Option<Vec<(String, SystemTime)>>>,
+}
+
+impl PresetService {
+    /// Creates a preset service
+    ///
+    /// # Arguments
+    /// * `presets_dir` - Directory of the preset YAML files
+    /// * `collection_manager` - Applies presets to collections
+    /// * `tmdb_service` - Checks the TMDB IDs of new items
+    pub fn new(presets_dir: PathBuf, collection_manager: Arc<CollectionManager>, tmdb_service: Arc<dyn TmdbService>) -> Self {
+        Self {
+            presets_dir,
+            collection_manager,
+            tmdb_service,
+            applied: Mutex::new(None),
+        }
+    }
+
+    /// Lists the presets, ordered by ID
+    pub fn list(&self) -> Result<Vec<PresetEntry>, ApplicationError> {
+        Ok(PresetLoader::load_entries(&self.presets_dir)?
+            .into_iter()
+            .map(|(id, preset)| PresetEntry { id, preset })
+            .collect())
+    }
+
+    /// Gets one preset
+    pub fn get(&self, id: &str) -> Result<PresetEntry, ApplicationError> {
+        self.list()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| DomainError::NotFound(format!("Preset '{}' not found", id)).into())
+    }
+
+    /// Adds a preset, stored under an ID made from its name
+    ///
+    /// # Errors
+    /// `InvalidInput` when the preset is invalid or has unknown TMDB IDs,
+    /// `Duplicate` when a preset of the same name exists
+    pub async fn create(self: &Arc<Self>, preset: PresetCollection) -> Result<PresetEntry, ApplicationError> {
+        let preset = self.validate(preset).await?;
+        let guard = self.applied.lock().await;
+
+        let existing = self.list()?;
+        ensure_name_free(&existing, &preset.name, None)?;
+        let id = free_id(&existing, &preset.name);
+        PresetLoader::save_preset(&self.presets_dir, &id, &preset)?;
+        info!("Added preset '{}' ({})", preset.name, id);
+        drop(guard);
+
+        self.spawn_apply();
+        Ok(PresetEntry { id, preset })
+    }
+
+    /// Replaces a preset
+    ///
+    /// A renamed preset (or one with another TMDB collection) gets a new
+    /// collection; the old one is removed.
+    pub async fn update(self: &Arc<Self>, id: &str, preset: PresetCollection) -> Result<PresetEntry, ApplicationError> {
+        let previous = self.get(id)?.preset;
+        let preset = self.validate(preset).await?;
+        let guard = self.applied.lock().await;
+
+        ensure_name_free(&self.list()?, &preset.name, Some(id))?;
+        PresetLoader::save_preset(&self.presets_dir, id, &preset)?;
+        if previous.name != preset.name || previous.tmdb_collection_id != preset.tmdb_collection_id {
+            self.collection_manager.remove_preset_collection(&previous).await?;
+        }
+        info!("Updated preset '{}' ({})", preset.name, id);
+        drop(guard);
+
+        self.spawn_apply();
+        Ok(PresetEntry { id: id.to_string(), preset })
+    }
+
+    /// Removes a preset and its collection
+    pub async fn delete(&self, id: &str) -> Result<(), ApplicationError> {
+        let preset = self.get(id)?.preset;
+        let mut applied = self.applied.lock().await;
+
+        PresetLoader::delete_preset(&self.presets_dir, id)?;
+        self.collection_manager.remove_preset_collection(&preset).await?;
+        // The remaining presets need no new pass
+        *applied = self.fingerprint();
+        info!("Removed preset '{}' ({})", preset.name, id);
+        Ok(())
+    }
+
+    /// Imports a bundle of presets
+    ///
+    /// Invalid presets are reported and left out. Presets whose name is
+    /// taken (by a stored preset or an earlier one of the bundle) are handled
+    /// by `conflict`. With `dry_run` nothing is stored; the report shows
+    /// which items of each preset are in the library either way.
+    pub async fn import(
+        self: &Arc<Self>,
+        presets: Vec<PresetCollection>,
+        conflict: ImportConflict,
+        dry_run: bool,
+    ) -> Result<PresetImportReport, ApplicationError> {
+        let mut report = PresetImportReport {
+            dry_run,
+            created: 0,
+            replaced: 0,
+            skipped: 0,
+            invalid: 0,
+            presets: Vec::new(),
+        };
+        let mut valid = Vec::new();
+        for preset in presets {
+            let name = preset.name.clone();
+            match self.validate(preset).await {
+                Ok(preset) => valid.push(preset),
+                Err(ApplicationError::Domain(DomainError::InvalidInput(msg))) => {
+                    report.invalid += 1;
+                    report.presets.push(ImportedPreset {
+                        name,
+                        id: None,
+                        outcome: ImportOutcome::Invalid,
+                        error: Some(msg),
+                        total_items: 0,
+                        available_items: 0,
+                        items: Vec::new(),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let availability = self.collection_manager.preset_availability(&valid).await?;
+
+        let guard = self.applied.lock().await;
+        let mut existing = self.list()?;
+        let mut written = false;
+        for (mut preset, available) in valid.into_iter().zip(availability) {
+            let taken = existing.iter().position(|entry| entry.preset.name.eq_ignore_ascii_case(&preset.name));
+            let (outcome, id) = match (taken, conflict) {
+                (None, _) => (ImportOutcome::Created, free_id(&existing, &preset.name)),
+                (Some(index), ImportConflict::Skip) => (ImportOutcome::Skipped, existing[index].id.clone()),
+                (Some(index), ImportConflict::Replace) => (ImportOutcome::Replaced, existing[index].id.clone()),
+                (Some(_), ImportConflict::Rename) => {
+                    preset.name = free_name(&existing, &preset.name);
+                    (ImportOutcome::Renamed, free_id(&existing, &preset.name))
+                }
+            };
+
+            if outcome != ImportOutcome::Skipped {
+                if !dry_run {
+                    PresetLoader::save_preset(&self.presets_dir, &id, &preset)?;
+                    if let Some(previous) = taken.map(|index| &existing[index].preset) {
+                        if outcome == ImportOutcome::Replaced
+                            && (previous.name != preset.name || previous.tmdb_collection_id != preset.tmdb_collection_id)
+                        {
+                            self.collection_manager.remove_preset_collection(previous).await?;
+                        }
+                    }
+                    written = true;
+                }
+                let entry = PresetEntry { id: id.clone(), preset: preset.clone() };
+                match taken.filter(|_| outcome == ImportOutcome::Replaced) {
+                    Some(index) => existing[index] = entry,
+                    None => existing.push(entry),
+                }
+            }
+
+            match outcome {
+                ImportOutcome::Replaced => report.replaced += 1,
+                ImportOutcome::Skipped => report.skipped += 1,
+                _ => report.created += 1,
+            }
+            let items: Vec<ImportedItem> = preset.items.iter().zip(available)
+                .map(|(item, available)| ImportedItem {
+                    tmdb_id: item.tmdb_id,
+                    media_type: item.media_type.clone(),
+                    title: item.title.clone(),
+                    timeline_order: item.timeline_order,
+                    available,
+                })
+                .collect();
+            report.presets.push(ImportedPreset {
+                name: preset.name,
+                id: Some(id),
+                outcome,
+                error: None,
+                total_items: items.len(),
+                available_items: items.iter().filter(|item| item.available).count(),
+                items,
+            });
+        }
+        drop(guard);
+
+        if written {
+            info!("Imported presets: {} created, {} replaced, {} skipped", report.created, report.replaced, report.skipped);
+            self.spawn_apply();
+        }
+        Ok(report)
+    }
+
+    /// Applies every preset to the preset collections
+    pub async fn apply(&self) -> Result<PresetStats, ApplicationError> {
+        let mut applied = self.applied.lock().await;
+        let fingerprint = self.fingerprint();
+        let presets = PresetLoader::load_from_directory(&self.presets_dir)?;
+        let stats = self.collection_manager.create_preset_collections(presets).await?;
+        *applied = fingerprint;
+        Ok(stats)
+    }
+
+    /// Applies the presets if their files changed since they were last applied
+    ///
+    /// # Returns
+    /// * `Result<Option<PresetStats>, ApplicationError>` - None when nothing changed
+    pub async fn reload_if_changed(&self) -> Result<Option<PresetStats>, ApplicationError> {
+        if *self.applied.lock().await == self.fingerprint() {
+            return Ok(None);
+        }
+        info!("Preset files changed, reloading presets");
+        self.apply().await.map(Some)
+    }
+
+    /// Applies the presets in the background
+    fn spawn_apply(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            match service.apply().await {
+                Ok(stats) => info!(
+                    "Presets applied: {} collections created, {}/{} items available",
+                    stats.collections_created, stats.available_items, stats.total_items
+                ),
+                Err(e) => error!("Applying presets failed: {}", e),
+            }
+        });
+    }
+
+    /// Names and modification times of the preset files
+    fn fingerprint(&self) -> Option<Vec<(String, SystemTime)>> {
+        let mut files: Vec<_> = std::fs::read_dir(&self.presets_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                (name.ends_with(".yaml") || name.ends_with(".yml")).then_some((name, modified))
+            })
+            .collect();
+        files.sort();
+        Some(files)
+    }
+
+    /// Validates a preset for storing
+    ///
+    /// Items are put in timeline order and their TMDB IDs are looked up; when
+    /// TMDB cannot be reached the IDs are stored unchecked.
+    async fn validate(&self, mut preset: PresetCollection) -> Result<PresetCollection, ApplicationError> {
+        preset.name = preset.name.trim().to_string();
+        preset.items.sort_by_key(|item| item.timeline_order);
+        preset.validate().map_err(|e| match e {
+            DomainError::ValidationError(msg) => DomainError::InvalidInput(msg),
+            e => e,
+        })?;
+
+        let mut unknown = Vec::new();
+        for item in &preset.items {
+            let found = if item.media_type == "movie" {
+                self.tmdb_service.fetch_movie_details(item.tmdb_id).await.map(|d| d.is_some())
+            } else {
+                self.tmdb_service.fetch_tv_details(item.tmdb_id).await.map(|d| d.is_some())
+            };
+            match found {
+                Ok(true) => {}
+                Ok(false) => unknown.push(format!("{} {} ({})", item.media_type, item.tmdb_id, item.title)),
+                Err(e) => {
+                    warn!("Cannot check TMDB IDs of preset '{}': {}", preset.name, e);
+                    break;
+                }
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(DomainError::InvalidInput(format!("Unknown TMDB IDs: {}", unknown.join(", "))).into());
+        }
+
+        Ok(preset)
+    }
+}
+
+/// Fails with `Duplicate` when another preset than `id` has the name
+fn ensure_name_free(existing: &[PresetEntry], name: &str, id: Option<&str>) -> Result<(), ApplicationError> {
+    let taken = existing.iter().any(|entry| {
+        Some(entry.id.as_str()) != id && entry.preset.name.eq_ignore_ascii_case(name)
+    });
+    if taken {
+        return Err(DomainError::Duplicate(format!("A preset named '{}' exists", name)).into());
+    }
+    Ok(())
+}
+
+/// ID for a new preset, numbered when the name's ID is taken
+fn free_id(existing: &[PresetEntry], name: &str) -> String {
+    let base = PresetLoader::preset_id(name);
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while existing.iter().any(|entry| entry.id == id) {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+/// First of "Name (2)", "Name (3)", ... no preset has
+fn free_name(existing: &[PresetEntry], name: &str) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !existing.iter().any(|entry| entry.preset.name.eq_ignore_ascii_case(candidate)))
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_names_and_ids() {
+        let entry = |id: &str, name: &str| PresetEntry {
+            id: id.to_string(),
+            preset: PresetCollection {
+                name: name.to_string(),
+                description: String::new(),
+                tmdb_collection_id: None,
+                items: Vec::new(),
+            },
+        };
+        let existing = vec![entry("alien-timeline", "Alien Timeline"), entry("alien-timeline-2", "Alien Timeline (2)")];
+
+        assert_eq!(free_name(&existing, "alien timeline"), "alien timeline (3)");
+        assert_eq!(free_id(&existing, "Alien Timeline"), "alien-timeline-3");
+        assert_eq!(free_id(&existing, "Predator"), "predator");
+        assert!(ensure_name_free(&existing, "ALIEN TIMELINE", None).is_err());
+        assert!(ensure_name_free(&existing, "Alien Timeline", Some("alien-timeline")).is_ok());
+    }
+}
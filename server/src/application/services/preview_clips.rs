@@ -0,0 +1,236 @@
+//! Preview Clips
+//!
+//! Short, muted clips of each title for hover previews. Clips are cut from
+//! about a third into the video, past intros and recaps, and cached under
+//! the data directory as `{media_id}.mp4`. They are made after library
+//! scans when enabled, and otherwise in the background the first time a
+//! clip is asked for. A clip older than its source file is made again.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
+
+use crate::application::services::PlaybackQos;
+use crate::domain::entities::Media;
+use crate::domain::repositories::MediaRepository;
+use crate::interfaces::external_services::{PreviewClipGenerator, VideoAnalyzer};
+use crate::shared::error::{ApplicationError, DomainError, FilesystemError};
+
+/// Length of a clip, in seconds
+const CLIP_SECONDS: f64 = 8.0;
+
+/// Where clips start, as share of the duration
+const CLIP_START_SHARE: f64 = 0.3;
+
+/// Preview clip cache and generation
+pub struct PreviewClipService {
+    generator: Arc<dyn PreviewClipGenerator>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    media_repository: Arc<dyn MediaRepository>,
+    /// Directory holding the clips
+    dir: PathBuf,
+    /// Backfills yield to active playback
+    playback_qos: Option<Arc<PlaybackQos>>,
+    /// Media items being encoded
+    generating: Arc<Mutex<HashSet<i64>>>,
+    /// Media items whose clip could not be made; not retried until restart
+    failed: Arc<Mutex<HashSet<i64>>>,
+    /// Clips are encoded one at a time
+    permits: Arc<Semaphore>,
+}
+
+impl PreviewClipService {
+    pub fn new(
+        generator: Arc<dyn PreviewClipGenerator>,
+        video_analyzer: Arc<dyn VideoAnalyzer>,
+        media_repository: Arc<dyn MediaRepository>,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            generator,
+            video_analyzer,
+            media_repository,
+            dir: dir.into(),
+            playback_qos: None,
+            generating: Arc::new(Mutex::new(HashSet::new())),
+            failed: Arc::new(Mutex::new(HashSet::new())),
+            permits: Arc::new(Semaphore::new(1)),
+        }
+    }
+
+    /// Pauses or throttles library backfills while streams are playing
+    pub fn with_playback_qos(mut self, playback_qos: Arc<PlaybackQos>) -> Self {
+        self.playback_qos = Some(playback_qos);
+        self
+    }
+
+    /// Path of the cached clip of a media item, if it is up to date
+    pub fn clip(&self, media: &Media) -> Option<PathBuf> {
+        let path = clip_path(&self.dir, media.id?);
+        is_fresh(&path, &media.file_path).then_some(path)
+    }
+
+    /// Starts making the clip of a media item in the background
+    ///
+    /// # Returns
+    /// * `false` if no clip can be made for the item
+    pub fn request(&self, media: &Media) -> bool {
+        let Some(media_id) = media.id else { return false };
+        if self.failed.lock().unwrap_or_else(|e| e.into_inner()).contains(&media_id) {
+            return false;
+        }
+        if !self.generating.lock().unwrap_or_else(|e| e.into_inner()).insert(media_id) {
+            return true;
+        }
+
+        let worker = self.worker();
+        let media = media.clone();
+        tokio::spawn(async move {
+            if let Ok(_permit) = worker.permits.clone().acquire_owned().await {
+                worker.make(media_id, &media).await;
+            }
+            worker.generating.lock().unwrap_or_else(|e| e.into_inner()).remove(&media_id);
+        });
+        true
+    }
+
+    /// Makes the missing and outdated clips of the whole library
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of clips made
+    ///
+    /// # Errors
+    /// Returns error if the media repository fails. Failures for individual
+    /// items are logged and skipped.
+    pub async fn backfill_library(&self) -> Result<usize, ApplicationError> {
+        let worker = self.worker();
+        let mut made = 0;
+        for media in self.media_repository.find_all().await? {
+            let Some(media_id) = media.id else { continue };
+            if self.clip(&media).is_some()
+                || self.failed.lock().unwrap_or_else(|e| e.into_inner()).contains(&media_id)
+                || !self.generating.lock().unwrap_or_else(|e| e.into_inner()).insert(media_id)
+            {
+                continue;
+            }
+
+            if let Some(qos) = &self.playback_qos {
+                qos.yield_to_playback().await;
+            }
+            if let Ok(_permit) = self.permits.acquire().await {
+                if worker.make(media_id, &media).await {
+                    made += 1;
+                }
+            }
+            self.generating.lock().unwrap_or_else(|e| e.into_inner()).remove(&media_id);
+        }
+
+        if made > 0 {
+            info!("Preview clip backfill complete: {} clips made", made);
+        }
+        Ok(made)
+    }
+
+    fn worker(&self) -> ClipWorker {
+        ClipWorker {
+            generator: self.generator.clone(),
+            video_analyzer: self.video_analyzer.clone(),
+            dir: self.dir.clone(),
+            failed: self.failed.clone(),
+            generating: self.generating.clone(),
+            permits: self.permits.clone(),
+        }
+    }
+}
+
+/// The parts of the service a background encode needs
+struct ClipWorker {
+    generator: Arc<dyn PreviewClipGenerator>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    dir: PathBuf,
+    failed: Arc<Mutex<HashSet<i64>>>,
+    generating: Arc<Mutex<HashSet<i64>>>,
+    permits: Arc<Semaphore>,
+}
+
+impl ClipWorker {
+    /// Makes a clip, remembering items that fail; returns whether it succeeded
+    async fn make(&self, media_id: i64, media: &Media) -> bool {
+        match self.encode(media_id, media).await {
+            Ok(()) => {
+                debug!("Made preview clip of media {}", media_id);
+                true
+            }
+            Err(e) => {
+                warn!("Failed to make preview clip of media {}: {}", media_id, e);
+                self.failed.lock().unwrap_or_else(|e| e.into_inner()).insert(media_id);
+                false
+            }
+        }
+    }
+
+    async fn encode(&self, media_id: i64, media: &Media) -> Result<(), ApplicationError> {
+        let duration = match media.duration_seconds {
+            Some(seconds) if seconds > 0 => seconds as f64,
+            _ => self.video_analyzer.analyze(&media.file_path).await?.duration_seconds,
+        };
+        let (start, length) = clip_window(duration).ok_or_else(|| {
+            ApplicationError::Domain(DomainError::InvalidInput(format!("{} has no duration", media.file_path)))
+        })?;
+
+        std::fs::create_dir_all(&self.dir).map_err(FilesystemError::from)?;
+        // Encode next to the clip and move it in place, so a half-written
+        // clip is never served
+        let partial = self.dir.join(format!("{}.part", media_id));
+        let result = self.generator.generate_clip(&media.file_path, start, length, &partial).await;
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e.into());
+        }
+        std::fs::rename(&partial, clip_path(&self.dir, media_id)).map_err(FilesystemError::from)?;
+        Ok(())
+    }
+}
+
+fn clip_path(dir: &Path, media_id: i64) -> PathBuf {
+    dir.join(format!("{}.mp4", media_id))
+}
+
+/// Whether a clip exists and is not older than its source file
+fn is_fresh(clip: &Path, source: &str) -> bool {
+    let Ok(clip_modified) = std::fs::metadata(clip).and_then(|m| m.modified()) else {
+        return false;
+    };
+    match std::fs::metadata(source).and_then(|m| m.modified()) {
+        Ok(source_modified) => clip_modified >= source_modified,
+        // Keep serving the clip while the source is unreachable (unmounted share)
+        Err(_) => true,
+    }
+}
+
+/// Start and length of the clip of a video of `duration` seconds
+fn clip_window(duration: f64) -> Option<(f64, f64)> {
+    if !duration.is_finite() || duration <= 0.0 {
+        return None;
+    }
+    let length = CLIP_SECONDS.min(duration);
+    let start = (duration * CLIP_START_SHARE).min(duration - length);
+    Some((start, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_window() {
+        assert_eq!(clip_window(6000.0), Some((1800.0, 8.0)));
+        // Short videos are covered from the start
+        assert_eq!(clip_window(10.0), Some((2.0, 8.0)));
+        assert_eq!(clip_window(5.0), Some((0.0, 5.0)));
+        assert_eq!(clip_window(0.0), None);
+        assert_eq!(clip_window(f64::NAN), None);
+    }
+}
@@ -0,0 +1,407 @@
+//! Recommendations
+//!
+//! Builds per-user rows of library titles to watch next: "Because you
+//! watched X" from TMDB similar titles, and top picks by genre affinity from
+//! the playback history. Rows are cached and refreshed nightly.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::application::services::ContentPolicy;
+use crate::domain::entities::{Media, Series};
+use crate::domain::repositories::{
+    CacheRepository, MediaRepository, PlaybackEntry, SeriesRepository, WatchHistoryRepository,
+};
+use crate::domain::value_objects::MediaType;
+use crate::interfaces::external_services::TmdbSimilarFetcher;
+use crate::shared::error::ApplicationError;
+
+/// Recently watched titles used for "Because you watched" rows
+const MAX_SEEDS: usize = 3;
+/// Playbacks the recommendations are based on
+const HISTORY_DEPTH: u32 = 200;
+/// Titles per row
+const ROW_SIZE: usize = 20;
+/// Cached rows outlive one nightly refresh, in case a refresh fails
+const CACHE_TTL_SECONDS: u64 = 36 * 3600;
+
+/// Kind of a recommended title
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendedKind {
+    Movie,
+    Series,
+}
+
+impl RecommendedKind {
+    /// TMDB media type
+    fn tmdb_type(&self) -> &'static str {
+        match self {
+            RecommendedKind::Movie => "movie",
+            RecommendedKind::Series => "tv",
+        }
+    }
+}
+
+/// A library title to watch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendedItem {
+    pub kind: RecommendedKind,
+    /// Media ID of a movie, series ID of a series
+    pub id: i64,
+    pub title: String,
+    pub poster_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    pub release_date: Option<String>,
+    pub genres: Option<String>,
+    pub rating: Option<f32>,
+    pub content_rating: Option<String>,
+    pub content_warnings: Option<String>,
+    pub tmdb_id: Option<i64>,
+}
+
+impl RecommendedItem {
+    fn from_movie(media: &Media) -> Self {
+        Self {
+            kind: RecommendedKind::Movie,
+            id: media.id.unwrap_or(0),
+            title: media.title.clone(),
+            poster_url: media.poster_url.clone(),
+            backdrop_url: media.backdrop_url.clone(),
+            release_date: media.release_date.clone(),
+            genres: media.genres.clone(),
+            rating: media.rating,
+            content_rating: media.content_rating.clone(),
+            content_warnings: media.content_warnings.clone(),
+            tmdb_id: media.tmdb_id,
+        }
+    }
+
+    fn from_series(series: &Series) -> Self {
+        Self {
+            kind: RecommendedKind::Series,
+            id: series.id.unwrap_or(0),
+            title: series.title.clone(),
+            poster_url: series.poster_url.clone(),
+            backdrop_url: series.backdrop_url.clone(),
+            release_date: series.first_air_date.clone(),
+            genres: series.genres.clone(),
+            rating: series.rating,
+            content_rating: series.content_rating.clone(),
+            content_warnings: None,
+            tmdb_id: series.tmdb_id,
+        }
+    }
+
+    fn key(&self) -> (RecommendedKind, i64) {
+        (self.kind, self.id)
+    }
+}
+
+/// A row of recommendations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendationRow {
+    /// Stable row ID ("because:movie:12", "top_picks", "highly_rated")
+    pub id: String,
+    pub title: String,
+    pub items: Vec<RecommendedItem>,
+}
+
+/// Recommendation rows of a user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendations {
+    pub user: String,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<RecommendationRow>,
+}
+
+impl Recommendations {
+    /// Drops titles the policy hides, and rows left empty
+    pub fn filtered(mut self, policy: &ContentPolicy) -> Self {
+        if policy.is_restricted() {
+            for row in &mut self.rows {
+                row.items.retain(|item| {
+                    policy.allows_rated(item.content_rating.as_deref(), item.genres.as_deref(), item.content_warnings.as_deref())
+                });
+            }
+            self.rows.retain(|row| !row.items.is_empty());
+        }
+        self
+    }
+}
+
+/// Recommendation service
+///
+/// # Architecture Notes
+/// - Only titles in the library are recommended, and never ones the user
+///   played or that are marked watched
+/// - Rows are computed on first request and by [`RecommendationService::refresh_all`];
+///   parental controls are applied when they are served, not cached
+/// - TMDB failures drop the affected "Because you watched" row
+pub struct RecommendationService {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    history: Arc<dyn WatchHistoryRepository>,
+    similar: Arc<dyn TmdbSimilarFetcher>,
+    cache: Arc<dyn CacheRepository>,
+}
+
+impl RecommendationService {
+    /// Creates a new recommendation service
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        history: Arc<dyn WatchHistoryRepository>,
+        similar: Arc<dyn TmdbSimilarFetcher>,
+        cache: Arc<dyn CacheRepository>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            history,
+            similar,
+            cache,
+        }
+    }
+
+    /// Gets the cached rows of a user, computing them when missing
+    pub async fn get(&self, user: &str) -> Result<Recommendations, ApplicationError> {
+        if let Some(cached) = self.cache.get(&cache_key(user)).await? {
+            match serde_json::from_str(&cached) {
+                Ok(recommendations) => return Ok(recommendations),
+                Err(e) => debug!("Discarding cached recommendations of {}: {}", user, e),
+            }
+        }
+        self.refresh(user).await
+    }
+
+    /// Computes and caches the rows of a user
+    pub async fn refresh(&self, user: &str) -> Result<Recommendations, ApplicationError> {
+        let recommendations = self.compute(user).await?;
+        match serde_json::to_string(&recommendations) {
+            Ok(json) => self.cache.set(&cache_key(user), &json, CACHE_TTL_SECONDS).await?,
+            Err(e) => warn!("Failed to cache recommendations of {}: {}", user, e),
+        }
+        Ok(recommendations)
+    }
+
+    /// Refreshes the rows of every user with playback history
+    ///
+    /// # Returns
+    /// * `Result<usize, ApplicationError>` - Number of users refreshed
+    pub async fn refresh_all(&self) -> Result<usize, ApplicationError> {
+        let users = self.history.by_user().await?;
+        let mut refreshed = 0;
+        for user in &users {
+            match self.refresh(&user.key).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => warn!("Failed to refresh recommendations of {}: {}", user.key, e),
+            }
+        }
+        info!("Refreshed recommendations of {} users", refreshed);
+        Ok(refreshed)
+    }
+
+    async fn compute(&self, user: &str) -> Result<Recommendations, ApplicationError> {
+        let movies: Vec<Media> = self
+            .media_repository
+            .find_by_type(MediaType::Movie)
+            .await?
+            .into_iter()
+            .filter(|m| m.id.is_some())
+            .collect();
+        let series: Vec<Series> = self
+            .series_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|s| s.id.is_some())
+            .collect();
+        let history = self.history.recent(Some(user), HISTORY_DEPTH).await?;
+
+        // Titles of the history, most recent first, with their watch time
+        let movie_by_id: HashMap<i64, &Media> = movies.iter().map(|m| (m.id.unwrap_or(0), m)).collect();
+        let series_by_id: HashMap<i64, &Series> = series.iter().map(|s| (s.id.unwrap_or(0), s)).collect();
+        let mut watched: Vec<(RecommendedItem, i64)> = Vec::new();
+        for entry in &history {
+            let Some(item) = self.history_title(entry, &movie_by_id, &series_by_id).await? else { continue };
+            match watched.iter_mut().find(|(seen, _)| seen.key() == item.key()) {
+                Some((_, seconds)) => *seconds += entry.duration_seconds,
+                None => watched.push((item, entry.duration_seconds)),
+            }
+        }
+        let seen: HashSet<(RecommendedKind, i64)> = watched.iter().map(|(item, _)| item.key()).collect();
+
+        // Candidates: unplayed library titles
+        let candidates: Vec<RecommendedItem> = movies
+            .iter()
+            .filter(|m| !m.is_watched)
+            .map(RecommendedItem::from_movie)
+            .chain(series.iter().map(RecommendedItem::from_series))
+            .filter(|item| !seen.contains(&item.key()))
+            .collect();
+        let by_tmdb: HashMap<(RecommendedKind, i64), &RecommendedItem> = candidates
+            .iter()
+            .filter_map(|item| Some(((item.kind, item.tmdb_id?), item)))
+            .collect();
+
+        let mut rows = Vec::new();
+        for (seed, _) in watched.iter().filter(|(item, _)| item.tmdb_id.is_some()).take(MAX_SEEDS) {
+            let tmdb_id = seed.tmdb_id.unwrap_or(0);
+            let similar = match self.similar.fetch_similar(tmdb_id, seed.kind.tmdb_type()).await {
+                Ok(similar) => similar,
+                Err(e) => {
+                    warn!("Failed to fetch titles similar to {}: {}", seed.title, e);
+                    continue;
+                }
+            };
+            let items: Vec<RecommendedItem> = similar
+                .iter()
+                .filter_map(|s| by_tmdb.get(&(seed.kind, s.id)).map(|item| (*item).clone()))
+                .take(ROW_SIZE)
+                .collect();
+            if !items.is_empty() {
+                rows.push(RecommendationRow {
+                    id: format!("because:{}:{}", kind_name(seed.kind), seed.id),
+                    title: format!("Because you watched {}", seed.title),
+                    items,
+                });
+            }
+        }
+
+        let affinity = genre_affinity(watched.iter().map(|(item, seconds)| (item.genres.as_deref(), *seconds)));
+        let top_picks = rank_by_affinity(&affinity, candidates.clone());
+        if !top_picks.is_empty() {
+            rows.push(RecommendationRow {
+                id: "top_picks".to_string(),
+                title: "Top picks for you".to_string(),
+                items: top_picks.into_iter().take(ROW_SIZE).collect(),
+            });
+        }
+
+        // New users get the best of the library
+        if watched.is_empty() {
+            let mut rated: Vec<RecommendedItem> = candidates.into_iter().filter(|c| c.rating.is_some()).collect();
+            rated.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap_or(std::cmp::Ordering::Equal));
+            rated.truncate(ROW_SIZE);
+            if !rated.is_empty() {
+                rows.push(RecommendationRow {
+                    id: "highly_rated".to_string(),
+                    title: "Highly rated in your library".to_string(),
+                    items: rated,
+                });
+            }
+        }
+
+        Ok(Recommendations {
+            user: user.to_string(),
+            generated_at: Utc::now(),
+            rows,
+        })
+    }
+
+    /// The movie or series a playback belongs to
+    async fn history_title(
+        &self,
+        entry: &PlaybackEntry,
+        movies: &HashMap<i64, &Media>,
+        series: &HashMap<i64, &Series>,
+    ) -> Result<Option<RecommendedItem>, ApplicationError> {
+        if let Some(movie) = movies.get(&entry.media_id) {
+            return Ok(Some(RecommendedItem::from_movie(movie)));
+        }
+        let series_id = self.media_repository.find_by_id(entry.media_id).await?.and_then(|m| m.series_id);
+        Ok(series_id.and_then(|id| series.get(&id)).map(|s| RecommendedItem::from_series(s)))
+    }
+}
+
+fn cache_key(user: &str) -> String {
+    format!("recommendations:{}", user)
+}
+
+fn kind_name(kind: RecommendedKind) -> &'static str {
+    match kind {
+        RecommendedKind::Movie => "movie",
+        RecommendedKind::Series => "series",
+    }
+}
+
+/// Share of watch time per genre (lowercase), summing to 1
+fn genre_affinity<'a>(watched: impl Iterator<Item = (Option<&'a str>, i64)>) -> HashMap<String, f64> {
+    let mut affinity: HashMap<String, f64> = HashMap::new();
+    for (genres, seconds) in watched {
+        for genre in genres.into_iter().flat_map(|g| g.split(',')).map(str::trim).filter(|g| !g.is_empty()) {
+            *affinity.entry(genre.to_lowercase()).or_default() += seconds.max(1) as f64;
+        }
+    }
+    let total: f64 = affinity.values().sum();
+    if total > 0.0 {
+        affinity.values_mut().for_each(|share| *share /= total);
+    }
+    affinity
+}
+
+/// Candidates sharing a genre with the history, best match first (rating
+/// breaks ties)
+fn rank_by_affinity(affinity: &HashMap<String, f64>, candidates: Vec<RecommendedItem>) -> Vec<RecommendedItem> {
+    let mut scored: Vec<(f64, RecommendedItem)> = candidates
+        .into_iter()
+        .filter_map(|item| {
+            let score: f64 = item
+                .genres
+                .as_deref()
+                .into_iter()
+                .flat_map(|g| g.split(','))
+                .filter_map(|g| affinity.get(&g.trim().to_lowercase()))
+                .sum();
+            (score > 0.0).then(|| (score + f64::from(item.rating.unwrap_or(0.0)) / 1000.0, item))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: i64, genres: &str, rating: f32) -> RecommendedItem {
+        RecommendedItem {
+            kind: RecommendedKind::Movie,
+            id,
+            title: format!("Movie {}", id),
+            poster_url: None,
+            backdrop_url: None,
+            release_date: None,
+            genres: Some(genres.to_string()),
+            rating: Some(rating),
+            content_rating: None,
+            content_warnings: None,
+            tmdb_id: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_by_genre_affinity() {
+        // Three hours of horror, one of comedy
+        let affinity = genre_affinity(
+            [(Some("Horror, Thriller"), 3 * 3600), (Some("Comedy"), 3600)].into_iter(),
+        );
+        assert!(affinity["horror"] > affinity["comedy"]);
+
+        let ranked = rank_by_affinity(
+            &affinity,
+            vec![
+                item(1, "Comedy", 9.0),
+                item(2, "Horror", 6.0),
+                item(3, "Documentary", 8.0),
+                item(4, "Horror", 7.5),
+                item(5, "Horror, Thriller", 5.0),
+            ],
+        );
+        assert_eq!(ranked.iter().map(|i| i.id).collect::<Vec<_>>(), vec![5, 4, 2, 1]);
+    }
+}
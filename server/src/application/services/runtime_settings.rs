@@ -0,0 +1,239 @@
+//! Runtime Settings
+//!
+//! Settings admins can change while the server runs: the TMDB API key,
+//! stream limits and whether notifications are sent. (Background task
+//! schedules are changed through the task scheduler.) Changes
+//! are stored, so they outlive restarts and take precedence over the
+//! configuration file and environment. Running services follow them through
+//! [`SettingsService::subscribe`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{watch, Mutex};
+use tracing::warn;
+
+use crate::application::services::notifications::REDACTED_SECRET;
+use crate::domain::repositories::SettingsRepository;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Settings that can be changed while running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSettings {
+    pub tmdb_api_key: String,
+    /// Concurrent stream sessions allowed (0 = unlimited)
+    pub max_streams: usize,
+    /// Concurrent transcoding sessions allowed (0 = unlimited)
+    pub max_transcodes: usize,
+    /// Off mutes every notification channel
+    pub notifications_enabled: bool,
+}
+
+/// Settings shown as [`REDACTED_SECRET`]
+pub const SECRET_SETTINGS: &[&str] = &["tmdb_api_key"];
+
+impl RuntimeSettings {
+    fn validate(&self) -> Result<(), String> {
+        if self.tmdb_api_key.trim().is_empty() {
+            return Err("tmdb_api_key: must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the runtime settings and tells running services about changes
+pub struct SettingsService {
+    repository: Arc<dyn SettingsRepository>,
+    /// Configured values, for settings without a stored one
+    defaults: RuntimeSettings,
+    /// Stored values by key; held while changing them so changes apply in order
+    stored: Mutex<BTreeMap<String, Value>>,
+    current: watch::Sender<RuntimeSettings>,
+}
+
+impl SettingsService {
+    /// Loads the stored settings over the configured ones; stored values
+    /// that are no longer valid are skipped
+    pub async fn load(
+        repository: Arc<dyn SettingsRepository>,
+        defaults: RuntimeSettings,
+    ) -> Result<Self, ApplicationError> {
+        let mut stored = BTreeMap::new();
+        for (key, value) in repository.find_all().await? {
+            match check(&defaults, &key, Some(&value)) {
+                Ok(()) => {
+                    stored.insert(key, value);
+                }
+                Err(e) => warn!("Ignoring stored setting: {}", e),
+            }
+        }
+
+        let current = merge(&defaults, &stored)?;
+        Ok(Self {
+            repository,
+            defaults,
+            stored: Mutex::new(stored),
+            current: watch::channel(current).0,
+        })
+    }
+
+    /// The settings in effect
+    pub fn current(&self) -> RuntimeSettings {
+        self.current.borrow().clone()
+    }
+
+    /// Follows the settings in effect
+    pub fn subscribe(&self) -> watch::Receiver<RuntimeSettings> {
+        self.current.subscribe()
+    }
+
+    /// Keys of the settings changed from their configured value
+    pub async fn overridden(&self) -> Vec<String> {
+        self.stored.lock().await.keys().cloned().collect()
+    }
+
+    /// Changes settings and applies them to running services
+    ///
+    /// Keys given `null` go back to their configured value; credentials
+    /// given as [`REDACTED_SECRET`] keep theirs. Nothing changes when any
+    /// key is unknown or any value invalid.
+    pub async fn update(&self, changes: Map<String, Value>) -> Result<RuntimeSettings, ApplicationError> {
+        let mut stored = self.stored.lock().await;
+        let mut changes: BTreeMap<String, Option<Value>> = changes
+            .into_iter()
+            .filter(|(key, value)| !(SECRET_SETTINGS.contains(&key.as_str()) && value.as_str() == Some(REDACTED_SECRET)))
+            .map(|(key, value)| (key, Some(value).filter(|v| !v.is_null())))
+            .collect();
+        for (key, value) in &changes {
+            check(&self.defaults, key, value.as_ref()).map_err(DomainError::InvalidInput)?;
+        }
+
+        let mut updated = stored.clone();
+        for (key, value) in &changes {
+            match value {
+                Some(value) => updated.insert(key.clone(), value.clone()),
+                None => updated.remove(key),
+            };
+        }
+        let settings = merge(&self.defaults, &updated)?;
+        settings.validate().map_err(DomainError::InvalidInput)?;
+
+        changes.retain(|key, value| stored.get(key) != value.as_ref());
+        if !changes.is_empty() {
+            self.repository.save(&changes).await?;
+        }
+        *stored = updated;
+        self.current.send_replace(settings.clone());
+        Ok(settings)
+    }
+}
+
+/// Replaces the credentials of settings with [`REDACTED_SECRET`], for
+/// showing them
+pub fn redact_secrets(settings: &RuntimeSettings) -> Value {
+    let mut value = serde_json::to_value(settings).unwrap_or_default();
+    for key in SECRET_SETTINGS {
+        if let Some(secret) = value.get_mut(*key) {
+            *secret = Value::from(REDACTED_SECRET);
+        }
+    }
+    value
+}
+
+/// Checks that a key is a setting and, unless `None`, that a value suits it
+fn check(defaults: &RuntimeSettings, key: &str, value: Option<&Value>) -> Result<(), String> {
+    let Ok(Value::Object(mut object)) = serde_json::to_value(defaults) else {
+        return Err("settings are not an object".to_string());
+    };
+    if key == "scan_interval_secs" {
+        return Err("scan_interval_secs: the library scan schedule is changed through PUT /v2/admin/tasks/library_scan".to_string());
+    }
+    if !object.contains_key(key) {
+        return Err(format!(
+            "Unknown setting '{}', expected one of: {}",
+            key,
+            object.keys().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let Some(value) = value else {
+        return Ok(());
+    };
+
+    object.insert(key.to_string(), value.clone());
+    serde_json::from_value::<RuntimeSettings>(Value::Object(object))
+        .map_err(|e| format!("{}: {}", key, e))?
+        .validate()
+}
+
+/// The configured settings with the stored ones in their place
+fn merge(defaults: &RuntimeSettings, stored: &BTreeMap<String, Value>) -> Result<RuntimeSettings, ApplicationError> {
+    let mut value = serde_json::to_value(defaults).map_err(|e| ApplicationError::Internal(e.to_string()))?;
+    if let Value::Object(object) = &mut value {
+        object.extend(stored.iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+    serde_json::from_value(value).map_err(|e| ApplicationError::Internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteSettingsRepository;
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn changes(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_and_reload() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repository = Arc::new(SqliteSettingsRepository::new(pool));
+        let defaults = RuntimeSettings {
+            tmdb_api_key: "configured".to_string(),
+            max_streams: 0,
+            max_transcodes: 0,
+            notifications_enabled: true,
+        };
+        let service = SettingsService::load(repository.clone(), defaults.clone()).await.unwrap();
+        let mut updates = service.subscribe();
+
+        // Invalid changes change nothing
+        for invalid in [
+            json!({ "max_streams": 2, "max_stream": 2 }),
+            json!({ "max_streams": -1 }),
+            json!({ "tmdb_api_key": " " }),
+            // Moved to the task scheduler
+            json!({ "scan_interval_secs": 600 }),
+        ] {
+            let result = service.update(changes(invalid)).await;
+            assert!(matches!(result, Err(ApplicationError::Domain(DomainError::InvalidInput(_)))));
+        }
+        assert_eq!(service.current(), defaults);
+
+        let settings = service
+            .update(changes(json!({ "max_streams": 2, "tmdb_api_key": REDACTED_SECRET, "notifications_enabled": false })))
+            .await
+            .unwrap();
+        assert_eq!(settings.max_streams, 2);
+        assert_eq!(settings.tmdb_api_key, "configured");
+        assert!(updates.has_changed().unwrap());
+        assert_eq!(updates.borrow_and_update().max_streams, 2);
+        assert_eq!(service.overridden().await, vec!["max_streams", "notifications_enabled"]);
+        assert_eq!(redact_secrets(&settings)["tmdb_api_key"], REDACTED_SECRET);
+
+        // Stored changes outlive restarts; null goes back to the configured value
+        let service = SettingsService::load(repository.clone(), defaults.clone()).await.unwrap();
+        assert_eq!(service.current(), settings);
+        let settings = service.update(changes(json!({ "max_streams": null }))).await.unwrap();
+        assert_eq!(settings.max_streams, 0);
+        assert_eq!(service.overridden().await, vec!["notifications_enabled"]);
+    }
+}
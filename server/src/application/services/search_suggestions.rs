@@ -0,0 +1,347 @@
+//! Search Suggestions
+//!
+//! Title suggestions while typing: movies, series, collections and cast
+//! members whose words start with the typed words, ranked by popularity
+//! (plays, library credits) and recency. Lookups use an in-memory prefix
+//! index that is rebuilt in the background after library changes.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, warn};
+
+use crate::application::services::ContentPolicy;
+use crate::domain::repositories::{
+    CollectionRepository, CreditsRepository, MediaRepository, SeriesRepository, WatchHistoryRepository,
+};
+use crate::domain::value_objects::MediaType;
+use crate::shared::error::ApplicationError;
+
+/// Cast members indexed, most credited first
+const MAX_PEOPLE: usize = 5000;
+/// Days over which the recency boost of new titles fades
+const RECENCY_DAYS: f64 = 30.0;
+
+/// Kind of a suggested title
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    Movie,
+    Series,
+    Collection,
+    Person,
+}
+
+/// A suggested title
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub kind: SuggestionKind,
+    /// Media, series, collection or TMDB person ID
+    pub id: i64,
+    pub title: String,
+    pub year: Option<String>,
+    pub image_url: Option<String>,
+}
+
+/// An indexed title
+struct Entry {
+    suggestion: Suggestion,
+    /// Lowercase words of the title
+    words: Vec<String>,
+    /// Rating, genres and warnings for parental controls
+    rating: Option<String>,
+    genres: Option<String>,
+    warnings: Option<String>,
+    score: f64,
+}
+
+/// Prefix index over the words of all titles
+#[derive(Default)]
+struct Index {
+    entries: Vec<Entry>,
+    /// (word, entry) sorted by word
+    words: Vec<(String, usize)>,
+}
+
+impl Index {
+    fn new(entries: Vec<Entry>) -> Self {
+        let mut words: Vec<(String, usize)> = entries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, entry)| entry.words.iter().map(move |word| (word.clone(), i)))
+            .collect();
+        words.sort();
+        words.dedup();
+        Self { entries, words }
+    }
+
+    /// Entries having a word for every query word (the last may be partly
+    /// typed), best first
+    fn lookup(&self, query: &[String], policy: &ContentPolicy, limit: usize) -> Vec<Suggestion> {
+        let Some(first) = query.first() else { return Vec::new() };
+        let start = self.words.partition_point(|(word, _)| word.as_str() < first.as_str());
+        let mut candidates: Vec<usize> = self.words[start..]
+            .iter()
+            .take_while(|(word, _)| word.starts_with(first.as_str()))
+            .map(|(_, i)| *i)
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let phrase = query.join(" ");
+        let mut matches: Vec<(f64, &Entry)> = candidates
+            .into_iter()
+            .map(|i| &self.entries[i])
+            .filter(|entry| query.iter().all(|q| entry.words.iter().any(|w| w.starts_with(q.as_str()))))
+            .filter(|entry| policy.allows_rated(entry.rating.as_deref(), entry.genres.as_deref(), entry.warnings.as_deref()))
+            .map(|entry| {
+                // Titles starting with what was typed come first
+                let boost = if entry.words.join(" ").starts_with(&phrase) { 10.0 } else { 0.0 };
+                (entry.score + boost, entry)
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.suggestion.title.cmp(&b.1.suggestion.title)));
+        matches.into_iter().take(limit).map(|(_, entry)| entry.suggestion.clone()).collect()
+    }
+}
+
+/// Lowercase words of a title or query
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Ranking score from plays (or credits), rating and age in days
+fn score(popularity: i64, rating: Option<f32>, age_days: Option<i64>) -> f64 {
+    let recency = age_days.map_or(0.0, |days| 2.0 / (1.0 + days.max(0) as f64 / RECENCY_DAYS));
+    (1.0 + popularity as f64).ln() * 2.0 + f64::from(rating.unwrap_or(0.0)) / 5.0 + recency
+}
+
+/// Title suggestions service
+pub struct SearchSuggestions {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    collection_repository: Arc<dyn CollectionRepository>,
+    credits_repository: Arc<dyn CreditsRepository>,
+    watch_history: Arc<dyn WatchHistoryRepository>,
+    index: RwLock<Option<Arc<Index>>>,
+    stale: AtomicBool,
+    rebuild: Mutex<()>,
+}
+
+impl SearchSuggestions {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        collection_repository: Arc<dyn CollectionRepository>,
+        credits_repository: Arc<dyn CreditsRepository>,
+        watch_history: Arc<dyn WatchHistoryRepository>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            collection_repository,
+            credits_repository,
+            watch_history,
+            index: RwLock::new(None),
+            stale: AtomicBool::new(true),
+            rebuild: Mutex::new(()),
+        }
+    }
+
+    /// Suggestions for a partly typed query
+    ///
+    /// The first call builds the index. After a library change the current
+    /// index keeps answering while a new one is built in the background.
+    pub async fn suggest(
+        self: &Arc<Self>,
+        query: &str,
+        policy: &ContentPolicy,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>, ApplicationError> {
+        let query = words(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let current = self.index.read().await.clone();
+        let index = match current {
+            Some(index) => {
+                if self.stale.load(Ordering::Relaxed) {
+                    let service = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = service.refresh().await {
+                            warn!("Failed to rebuild the suggestion index: {}", e);
+                        }
+                    });
+                }
+                index
+            }
+            None => {
+                self.refresh().await?;
+                self.index.read().await.clone().unwrap_or_default()
+            }
+        };
+        Ok(index.lookup(&query, policy, limit))
+    }
+
+    /// Marks the index out of date
+    pub fn invalidate(&self) {
+        self.stale.store(true, Ordering::Relaxed);
+    }
+
+    /// Rebuilds the index if it is out of date
+    pub async fn refresh(&self) -> Result<(), ApplicationError> {
+        // One rebuild at a time; later callers find the index fresh
+        let _guard = self.rebuild.lock().await;
+        if !self.stale.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        match self.build().await {
+            Ok(index) => {
+                debug!("Suggestion index rebuilt: {} titles", index.entries.len());
+                *self.index.write().await = Some(Arc::new(index));
+                Ok(())
+            }
+            Err(e) => {
+                self.stale.store(true, Ordering::Relaxed);
+                Err(e)
+            }
+        }
+    }
+
+    async fn build(&self) -> Result<Index, ApplicationError> {
+        let now = Utc::now();
+        let plays: HashMap<i64, i64> = self.watch_history.plays_per_media().await?.into_iter().collect();
+        let mut entries = Vec::new();
+
+        let mut series_plays: HashMap<i64, i64> = HashMap::new();
+        for episode in self.media_repository.find_by_type(MediaType::Episode).await? {
+            if let (Some(id), Some(series_id)) = (episode.id, episode.series_id) {
+                *series_plays.entry(series_id).or_default() += plays.get(&id).copied().unwrap_or(0);
+            }
+        }
+
+        for movie in self.media_repository.find_by_type(MediaType::Movie).await? {
+            let Some(id) = movie.id else { continue };
+            entries.push(Entry {
+                words: words(&movie.title),
+                score: score(plays.get(&id).copied().unwrap_or(0), movie.rating, Some((now - movie.created_at).num_days())),
+                rating: movie.content_rating,
+                genres: movie.genres,
+                warnings: movie.content_warnings,
+                suggestion: Suggestion {
+                    kind: SuggestionKind::Movie,
+                    id,
+                    title: movie.title,
+                    year: movie.release_date.as_deref().and_then(|d| d.get(..4)).map(String::from),
+                    image_url: movie.poster_url,
+                },
+            });
+        }
+
+        for series in self.series_repository.find_all().await? {
+            let Some(id) = series.id else { continue };
+            entries.push(Entry {
+                words: words(&series.title),
+                score: score(
+                    series_plays.get(&id).copied().unwrap_or(0),
+                    series.rating,
+                    Some((now - series.created_at).num_days()),
+                ),
+                rating: series.content_rating,
+                genres: series.genres,
+                warnings: None,
+                suggestion: Suggestion {
+                    kind: SuggestionKind::Series,
+                    id,
+                    title: series.title,
+                    year: series.first_air_date.as_deref().and_then(|d| d.get(..4)).map(String::from),
+                    image_url: series.poster_url,
+                },
+            });
+        }
+
+        for collection in self.collection_repository.find_all().await? {
+            let Some(id) = collection.id else { continue };
+            entries.push(Entry {
+                words: words(&collection.name),
+                score: score(i64::from(collection.available_items), None, None),
+                rating: None,
+                genres: None,
+                warnings: None,
+                suggestion: Suggestion {
+                    kind: SuggestionKind::Collection,
+                    id,
+                    title: collection.name,
+                    year: None,
+                    image_url: collection.poster_url,
+                },
+            });
+        }
+
+        for person in self.credits_repository.credited_people(MAX_PEOPLE).await? {
+            entries.push(Entry {
+                words: words(&person.name),
+                score: score(person.credits, None, None),
+                rating: None,
+                genres: None,
+                warnings: None,
+                suggestion: Suggestion {
+                    kind: SuggestionKind::Person,
+                    id: person.person_id,
+                    title: person.name,
+                    year: None,
+                    image_url: person.profile_url,
+                },
+            });
+        }
+
+        Ok(Index::new(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: SuggestionKind, id: i64, title: &str, score: f64) -> Entry {
+        Entry {
+            suggestion: Suggestion { kind, id, title: title.to_string(), year: None, image_url: None },
+            words: words(title),
+            rating: None,
+            genres: None,
+            warnings: None,
+            score,
+        }
+    }
+
+    #[test]
+    fn test_prefix_lookup_ranking() {
+        let index = Index::new(vec![
+            entry(SuggestionKind::Movie, 1, "The Matrix", 1.0),
+            entry(SuggestionKind::Movie, 2, "The Matrix Reloaded", 3.0),
+            entry(SuggestionKind::Series, 3, "Mad Men", 5.0),
+            entry(SuggestionKind::Person, 4, "Matt Damon", 2.0),
+        ]);
+        let policy = ContentPolicy::unrestricted();
+
+        let titles = |q: &str| index.lookup(&words(q), &policy, 10).into_iter().map(|s| s.title).collect::<Vec<_>>();
+        assert_eq!(titles("ma"), vec!["Mad Men", "Matt Damon", "The Matrix Reloaded", "The Matrix"]);
+        // Titles starting with the query outrank more popular ones
+        assert_eq!(titles("the mat"), vec!["The Matrix Reloaded", "The Matrix"]);
+        assert_eq!(titles("matrix rel"), vec!["The Matrix Reloaded"]);
+        assert!(titles("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_score_prefers_recent_and_played() {
+        assert!(score(10, None, None) > score(0, None, None));
+        assert!(score(0, None, Some(1)) > score(0, None, Some(365)));
+    }
+}
@@ -0,0 +1,242 @@
+//! Semantic Search
+//!
+//! Finds movies and episodes by what happens in them rather than by their
+//! words: overviews and episode synopses are embedded with an embedding
+//! model, and a query matches the overviews whose vectors are closest to
+//! its own ("a guy relives the same day" finds Groundhog Day).
+//!
+//! Vectors are stored per media item with a hash of the embedded text, so
+//! re-indexing after a scan only embeds new and changed overviews.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tracing::info;
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::{EmbeddingRepository, MediaEmbedding, MediaRepository};
+use crate::domain::value_objects::MediaType;
+use crate::infrastructure::external::TextEmbedder;
+use crate::infrastructure::gpu::GpuCoordinator;
+use crate::shared::error::ApplicationError;
+
+/// Overviews embedded per request
+const BATCH_SIZE: usize = 32;
+
+/// Media IDs and unit-length vectors of the indexed overviews
+type Vectors = Vec<(i64, Vec<f32>)>;
+
+/// Semantic search over overviews
+pub struct SemanticSearch {
+    media_repository: Arc<dyn MediaRepository>,
+    embedding_repository: Arc<dyn EmbeddingRepository>,
+    embedder: Arc<dyn TextEmbedder>,
+    gpu_coordinator: Option<Arc<GpuCoordinator>>,
+    /// Vectors loaded for searching, dropped when the index changes
+    vectors: RwLock<Option<Arc<Vectors>>>,
+    indexing: Mutex<()>,
+}
+
+impl SemanticSearch {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        embedding_repository: Arc<dyn EmbeddingRepository>,
+        embedder: Arc<dyn TextEmbedder>,
+    ) -> Self {
+        Self {
+            media_repository,
+            embedding_repository,
+            embedder,
+            gpu_coordinator: None,
+            vectors: RwLock::new(None),
+            indexing: Mutex::new(()),
+        }
+    }
+
+    /// Waits for the GPU while indexing, so embedding doesn't compete with
+    /// Whisper and translation
+    pub fn with_gpu_coordinator(mut self, gpu_coordinator: Arc<GpuCoordinator>) -> Self {
+        self.gpu_coordinator = Some(gpu_coordinator);
+        self
+    }
+
+    /// Embeds the overviews of movies and episodes that are new or changed
+    /// since they were last embedded
+    ///
+    /// Returns the number of overviews embedded.
+    pub async fn index_library(&self) -> Result<usize, ApplicationError> {
+        let _guard = self.indexing.lock().await;
+        let model = self.embedder.model().to_string();
+        let hashes: HashMap<i64, String> = self.embedding_repository.find_hashes(&model).await?.into_iter().collect();
+
+        let mut pending = Vec::new();
+        for media_type in [MediaType::Movie, MediaType::Episode] {
+            for media in self.media_repository.find_by_type(media_type).await? {
+                let (Some(id), Some(text)) = (media.id, document(&media)) else { continue };
+                let hash = hex::encode(Sha256::digest(text.as_bytes()));
+                if hashes.get(&id) != Some(&hash) {
+                    pending.push((id, text, hash));
+                }
+            }
+        }
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        info!("Embedding {} overviews with {}", pending.len(), model);
+        for batch in pending.chunks(BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|(_, text, _)| text.clone()).collect();
+            let vectors = {
+                let _permit = match &self.gpu_coordinator {
+                    Some(gpu) => Some(gpu.acquire().await),
+                    None => None,
+                };
+                self.embedder.embed(&texts).await?
+            };
+            for ((media_id, _, hash), vector) in batch.iter().zip(vectors) {
+                self.embedding_repository
+                    .save(&MediaEmbedding {
+                        media_id: *media_id,
+                        model: model.clone(),
+                        content_hash: hash.clone(),
+                        vector: normalize(vector),
+                    })
+                    .await?;
+            }
+        }
+
+        *self.vectors.write().await = None;
+        Ok(pending.len())
+    }
+
+    /// IDs of the media whose overviews are closest in meaning to the
+    /// query, with their cosine similarity, best first
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<(i64, f32)>, ApplicationError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let embedded = self.embedder.embed(&[query.to_string()]).await?;
+        let query = normalize(embedded.into_iter().next().unwrap_or_default());
+        let vectors = self.vectors().await?;
+
+        let mut scored: Vec<(i64, f32)> = vectors
+            .iter()
+            .filter(|(_, vector)| vector.len() == query.len())
+            .map(|(media_id, vector)| (*media_id, dot(vector, &query)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    /// Vectors of the current model, loaded on first use
+    async fn vectors(&self) -> Result<Arc<Vectors>, ApplicationError> {
+        if let Some(vectors) = self.vectors.read().await.clone() {
+            return Ok(vectors);
+        }
+        let vectors: Arc<Vectors> = Arc::new(
+            self.embedding_repository
+                .find_by_model(self.embedder.model())
+                .await?
+                .into_iter()
+                .map(|embedding| (embedding.media_id, embedding.vector))
+                .collect(),
+        );
+        *self.vectors.write().await = Some(vectors.clone());
+        Ok(vectors)
+    }
+}
+
+/// Text embedded for a media item: its title and overview, if it has one
+fn document(media: &Media) -> Option<String> {
+    let overview = media.overview.as_deref().map(str::trim).filter(|o| !o.is_empty())?;
+    Some(format!("{}\n{}", media.title, overview))
+}
+
+/// Scales a vector to unit length, so dot products are cosine similarities
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let length = dot(&vector, &vector).sqrt();
+    if length > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= length);
+    }
+    vector
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{SqliteEmbeddingRepository, SqliteMediaRepository};
+    use crate::shared::error::EmbeddingError;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Bag of words over a fixed vocabulary
+    struct WordEmbedder {
+        embedded: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl TextEmbedder for WordEmbedder {
+        fn model(&self) -> &str {
+            "words"
+        }
+
+        async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+            const VOCABULARY: [&str; 6] = ["relives", "same", "day", "shark", "beach", "town"];
+            self.embedded.fetch_add(inputs.len(), Ordering::SeqCst);
+            Ok(inputs
+                .iter()
+                .map(|input| {
+                    let input = input.to_lowercase();
+                    VOCABULARY.iter().map(|word| input.matches(word).count() as f32).collect()
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_index_and_search_overviews() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repository = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        for (title, overview) in [
+            ("Groundhog Day", Some("A weatherman relives the same day again and again.")),
+            ("Jaws", Some("A shark terrorizes a beach town.")),
+            ("Untitled", None),
+        ] {
+            let mut media = Media::new(format!("/movies/{}.mkv", title), MediaType::Movie, title.to_string()).unwrap();
+            media.overview = overview.map(String::from);
+            media_repository.save(&media).await.unwrap();
+        }
+
+        let embedder = Arc::new(WordEmbedder { embedded: AtomicUsize::new(0) });
+        let search = SemanticSearch::new(
+            media_repository.clone(),
+            Arc::new(SqliteEmbeddingRepository::new(pool)),
+            embedder.clone(),
+        );
+
+        assert_eq!(search.index_library().await.unwrap(), 2);
+        // Unchanged overviews are not embedded again
+        assert_eq!(search.index_library().await.unwrap(), 0);
+        assert_eq!(embedder.embedded.load(Ordering::SeqCst), 2);
+
+        let found = search.search("movie where a guy relives the same day", 10).await.unwrap();
+        let best = media_repository.find_by_id(found[0].0).await.unwrap().unwrap();
+        assert_eq!(best.title, "Groundhog Day");
+        assert!(found[0].1 > found[1].1);
+        assert!(search.search("  ", 10).await.unwrap().is_empty());
+    }
+}
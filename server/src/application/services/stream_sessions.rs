@@ -0,0 +1,437 @@
+//! Stream Sessions
+//!
+//! Tracks who is watching what: one session per media item, user, client
+//! and delivery mode, however many HTTP requests (range requests, seeks, HLS
+//! segments) the player makes. Enforces the concurrent stream and transcode
+//! limits and publishes `StreamStarted`/`StreamEnded` once per session.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::domain::events::{StreamEndedEvent, StreamStartedEvent};
+use crate::interfaces::messaging::EventBus;
+use crate::shared::error::TranscodeError;
+
+/// How a stream is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamMode {
+    /// Original bytes (direct play, remux or audio copy)
+    Direct,
+    /// Re-encoded by FFmpeg
+    Transcode,
+}
+
+/// A stream a client is about to start
+#[derive(Debug, Clone)]
+pub struct StreamRequest {
+    pub media_id: i64,
+    pub user: String,
+    /// Device ID or user agent
+    pub client: Option<String>,
+    pub mode: StreamMode,
+    /// Delivered bitrate in kbps, if known
+    pub bitrate_kbps: Option<u32>,
+    /// HLS session serving the stream
+    pub hls_session: Option<String>,
+}
+
+impl StreamRequest {
+    /// Requests of one playback share this key
+    fn key(&self) -> String {
+        match &self.hls_session {
+            Some(hls) => format!("hls:{}", hls),
+            None => format!(
+                "{}:{}:{}:{:?}",
+                self.media_id,
+                self.user,
+                self.client.as_deref().unwrap_or(""),
+                self.mode
+            ),
+        }
+    }
+}
+
+/// State shared between a session and its open responses
+struct SessionHandle {
+    open_responses: AtomicUsize,
+    bytes_sent: AtomicU64,
+    terminated: AtomicBool,
+    last_activity: Mutex<Instant>,
+}
+
+impl SessionHandle {
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_activity.lock().unwrap_or_else(|e| e.into_inner()).elapsed()
+    }
+}
+
+struct StreamSession {
+    id: String,
+    request: StreamRequest,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    handle: Arc<SessionHandle>,
+}
+
+/// Summary of an active stream session
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamSessionInfo {
+    pub session_id: String,
+    pub media_id: i64,
+    pub user: String,
+    pub client: Option<String>,
+    pub mode: StreamMode,
+    pub bitrate_kbps: Option<u32>,
+    pub hls_session: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub idle_seconds: u64,
+    pub open_responses: usize,
+    pub bytes_sent: u64,
+}
+
+/// Keeps a response counted against its session while alive
+///
+/// Move it into the response body; [`StreamGuard::record`] counts the bytes
+/// sent and reports whether the session was terminated.
+pub struct StreamGuard {
+    session_id: String,
+    handle: Arc<SessionHandle>,
+}
+
+impl StreamGuard {
+    /// Session the response belongs to
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Counts bytes sent; returns false once the session was terminated
+    pub fn record(&self, bytes: usize) -> bool {
+        self.handle.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        !self.handle.terminated.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        // The idle timeout starts when the last response finishes
+        self.handle.touch();
+        self.handle.open_responses.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Registry {
+    sessions: HashMap<String, StreamSession>,
+    /// Keys of terminated sessions, refused until the idle timeout passes
+    terminated: HashMap<String, Instant>,
+}
+
+/// Stream session registry
+///
+/// # Architecture Notes
+/// - Sessions end when no response is open and no request arrived within
+///   the idle timeout ([`StreamSessionRegistry::cleanup_idle`])
+/// - Limits apply to new sessions only; requests of a running session are
+///   never refused
+/// - A limit of 0 means unlimited
+pub struct StreamSessionRegistry<E: EventBus + ?Sized = crate::infrastructure::messaging::InMemoryEventBus> {
+    event_bus: Arc<E>,
+    max_streams: AtomicUsize,
+    max_transcodes: AtomicUsize,
+    idle_timeout: Duration,
+    registry: Mutex<Registry>,
+}
+
+impl<E: EventBus + ?Sized> StreamSessionRegistry<E> {
+    /// Creates a new registry
+    ///
+    /// # Defaults
+    /// - No stream or transcode limit
+    /// - Idle timeout: 60s
+    pub fn new(event_bus: Arc<E>) -> Self {
+        Self {
+            event_bus,
+            max_streams: AtomicUsize::new(0),
+            max_transcodes: AtomicUsize::new(0),
+            idle_timeout: Duration::from_secs(60),
+            registry: Mutex::new(Registry {
+                sessions: HashMap::new(),
+                terminated: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Sets the concurrent stream and transcode limits (0 = unlimited)
+    pub fn with_limits(self, max_streams: usize, max_transcodes: usize) -> Self {
+        self.set_limits(max_streams, max_transcodes);
+        self
+    }
+
+    /// Changes the limits while running; sessions over a lowered limit
+    /// keep playing, only new ones are refused
+    pub fn set_limits(&self, max_streams: usize, max_transcodes: usize) {
+        self.max_streams.store(max_streams, Ordering::Relaxed);
+        self.max_transcodes.store(max_transcodes, Ordering::Relaxed);
+    }
+
+    /// Sets how long a session may go without requests before it ends
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Joins the running session of a request or starts a new one
+    ///
+    /// # Errors
+    /// * `TranscodeError::LimitReached` - a new session would exceed a limit
+    /// * `TranscodeError::Terminated` - the session was terminated recently
+    pub async fn open(&self, request: StreamRequest) -> Result<StreamGuard, TranscodeError> {
+        let key = request.key();
+        let (guard, started) = {
+            let mut registry = self.lock();
+            let idle_timeout = self.idle_timeout;
+            registry.terminated.retain(|_, at| at.elapsed() < idle_timeout);
+            if registry.terminated.contains_key(&key) {
+                return Err(TranscodeError::Terminated(format!("Stream of media {} was stopped", request.media_id)));
+            }
+
+            match registry.sessions.get(&key) {
+                Some(session) => (Self::guard(session), None),
+                None => {
+                    self.check_limits(&registry, request.mode)?;
+                    let session = StreamSession {
+                        id: uuid::Uuid::new_v4().simple().to_string(),
+                        request: request.clone(),
+                        started_at: Utc::now(),
+                        started: Instant::now(),
+                        handle: Arc::new(SessionHandle {
+                            open_responses: AtomicUsize::new(0),
+                            bytes_sent: AtomicU64::new(0),
+                            terminated: AtomicBool::new(false),
+                            last_activity: Mutex::new(Instant::now()),
+                        }),
+                    };
+                    let guard = Self::guard(&session);
+                    registry.sessions.insert(key, session);
+                    (guard, Some(request))
+                }
+            }
+        };
+
+        if let Some(request) = started {
+            info!(
+                "Stream session {} started: media {} for {} ({:?})",
+                guard.session_id, request.media_id, request.user, request.mode
+            );
+            let event = StreamStartedEvent::new(
+                request.media_id,
+                None,
+                request.client,
+                request.mode == StreamMode::Transcode,
+            );
+            if let Err(e) = self.event_bus.publish(event).await {
+                warn!("Failed to publish stream started event: {}", e);
+            }
+        }
+        Ok(guard)
+    }
+
+    /// Records a segment of an HLS session (segments are fetched one by one,
+    /// so no response stays open between them)
+    pub fn record_hls(&self, hls_session: &str, bytes: usize) {
+        if let Some(session) = self.lock().sessions.get(&format!("hls:{}", hls_session)) {
+            session.handle.touch();
+            session.handle.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Stops a session: open responses end and new requests of the same
+    /// playback are refused until the idle timeout passes
+    ///
+    /// # Returns
+    /// * The terminated session, or None if it does not exist
+    pub async fn terminate(&self, session_id: &str) -> Option<StreamSessionInfo> {
+        let session = {
+            let mut registry = self.lock();
+            let key = registry
+                .sessions
+                .iter()
+                .find(|(_, s)| s.id == session_id)
+                .map(|(key, _)| key.clone())?;
+            let session = registry.sessions.remove(&key)?;
+            registry.terminated.insert(key, Instant::now());
+            session
+        };
+
+        session.handle.terminated.store(true, Ordering::Relaxed);
+        info!("Stream session {} terminated (media {})", session.id, session.request.media_id);
+        let info = Self::info(&session);
+        self.publish_ended(&session).await;
+        Some(info)
+    }
+
+    /// Ends sessions without open responses or requests within the idle timeout
+    ///
+    /// # Returns
+    /// * Number of sessions ended
+    pub async fn cleanup_idle(&self) -> usize {
+        let idle: Vec<StreamSession> = {
+            let mut registry = self.lock();
+            let keys: Vec<String> = registry
+                .sessions
+                .iter()
+                .filter(|(_, s)| {
+                    s.handle.open_responses.load(Ordering::SeqCst) == 0 && s.handle.idle_for() >= self.idle_timeout
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            keys.iter().filter_map(|key| registry.sessions.remove(key)).collect()
+        };
+
+        for session in &idle {
+            info!("Stream session {} ended (media {})", session.id, session.request.media_id);
+            self.publish_ended(session).await;
+        }
+        idle.len()
+    }
+
+    /// Lists active sessions
+    pub fn sessions(&self) -> Vec<StreamSessionInfo> {
+        let mut sessions: Vec<StreamSessionInfo> = self.lock().sessions.values().map(Self::info).collect();
+        sessions.sort_by_key(|s| s.started_at);
+        sessions
+    }
+
+    fn check_limits(&self, registry: &Registry, mode: StreamMode) -> Result<(), TranscodeError> {
+        let max_streams = self.max_streams.load(Ordering::Relaxed);
+        let max_transcodes = self.max_transcodes.load(Ordering::Relaxed);
+        let streams = registry.sessions.len();
+        if max_streams > 0 && streams >= max_streams {
+            return Err(TranscodeError::LimitReached(format!("{} of {} streams active", streams, max_streams)));
+        }
+
+        let transcodes = registry
+            .sessions
+            .values()
+            .filter(|s| s.request.mode == StreamMode::Transcode)
+            .count();
+        if mode == StreamMode::Transcode && max_transcodes > 0 && transcodes >= max_transcodes {
+            return Err(TranscodeError::LimitReached(format!(
+                "{} of {} transcodes active",
+                transcodes, max_transcodes
+            )));
+        }
+        Ok(())
+    }
+
+    async fn publish_ended(&self, session: &StreamSession) {
+        // Until the last request, so the idle timeout does not count as watched
+        let last_activity = *session.handle.last_activity.lock().unwrap_or_else(|e| e.into_inner());
+        let event = StreamEndedEvent::new(
+            session.request.media_id,
+            Some(last_activity.saturating_duration_since(session.started).as_secs_f64()),
+            Some(session.handle.bytes_sent.load(Ordering::Relaxed)),
+        )
+        .with_session(session.request.user.clone(), session.request.client.clone(), session.started_at);
+        if let Err(e) = self.event_bus.publish(event).await {
+            warn!("Failed to publish stream ended event: {}", e);
+        }
+    }
+
+    fn guard(session: &StreamSession) -> StreamGuard {
+        session.handle.touch();
+        session.handle.open_responses.fetch_add(1, Ordering::SeqCst);
+        StreamGuard {
+            session_id: session.id.clone(),
+            handle: Arc::clone(&session.handle),
+        }
+    }
+
+    fn info(session: &StreamSession) -> StreamSessionInfo {
+        StreamSessionInfo {
+            session_id: session.id.clone(),
+            media_id: session.request.media_id,
+            user: session.request.user.clone(),
+            client: session.request.client.clone(),
+            mode: session.request.mode,
+            bitrate_kbps: session.request.bitrate_kbps,
+            hls_session: session.request.hls_session.clone(),
+            started_at: session.started_at,
+            idle_seconds: session.handle.idle_for().as_secs(),
+            open_responses: session.handle.open_responses.load(Ordering::SeqCst),
+            bytes_sent: session.handle.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Registry> {
+        self.registry.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::messaging::InMemoryEventBus;
+
+    fn request(media_id: i64, client: &str, mode: StreamMode) -> StreamRequest {
+        StreamRequest {
+            media_id,
+            user: "default".to_string(),
+            client: Some(client.to_string()),
+            mode,
+            bitrate_kbps: None,
+            hls_session: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limits_and_termination() {
+        let registry = StreamSessionRegistry::new(Arc::new(InMemoryEventBus::new()))
+            .with_limits(2, 1)
+            .with_idle_timeout(Duration::from_millis(20));
+
+        // Range requests of one playback share a session
+        let tv = registry.open(request(1, "tv", StreamMode::Direct)).await.unwrap();
+        let tv_again = registry.open(request(1, "tv", StreamMode::Direct)).await.unwrap();
+        assert_eq!(tv.session_id(), tv_again.session_id());
+        assert_eq!(registry.sessions().len(), 1);
+
+        let phone = registry.open(request(2, "phone", StreamMode::Transcode)).await.unwrap();
+        assert!(matches!(
+            registry.open(request(3, "laptop", StreamMode::Transcode)).await,
+            Err(TranscodeError::LimitReached(_))
+        ));
+
+        // Changed limits apply to the next playback
+        registry.set_limits(0, 0);
+        let laptop = registry.open(request(3, "laptop", StreamMode::Transcode)).await.unwrap();
+        assert!(registry.terminate(laptop.session_id()).await.is_some());
+        registry.set_limits(2, 1);
+
+        // Terminating ends the open responses and refuses the same playback
+        let phone_session = phone.session_id().to_string();
+        assert!(registry.terminate(&phone_session).await.is_some());
+        assert!(!phone.record(1024));
+        assert!(matches!(
+            registry.open(request(2, "phone", StreamMode::Transcode)).await,
+            Err(TranscodeError::Terminated(_))
+        ));
+
+        // Sessions with open responses never idle out
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(registry.cleanup_idle().await, 0);
+        drop((tv, tv_again));
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(registry.cleanup_idle().await, 1);
+        assert!(registry.sessions().is_empty());
+    }
+}
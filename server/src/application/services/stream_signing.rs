@@ -0,0 +1,380 @@
+//! Stream URL Signing
+//!
+//! Issues time-limited tokens that authorize streaming one media item
+//! without an Authorization header, for players that cannot send one (the
+//! Chromecast default receiver fetches URLs on its own). A token is
+//! `<media id>-<expiry unix time>-<HMAC-SHA256 prefix>`.
+//!
+//! Session tokens additionally bind a user and a playback session, for
+//! direct URLs handed to `<video>` elements:
+//! `<media id>-<expiry>-<session>-<hex user>-<HMAC-SHA256 prefix>`. They can
+//! be revoked before they expire, one at a time or all tokens issued to a
+//! device; revocations are stored so they survive restarts.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::domain::repositories::StreamTokenRepository;
+use crate::shared::error::ApplicationError;
+
+/// Hex characters of the MAC kept in the token (128 bits)
+const SIGNATURE_LEN: usize = 32;
+
+/// A signed stream token
+#[derive(Debug, Clone)]
+pub struct SignedStream {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What a session token authorizes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamClaims {
+    pub media_id: i64,
+    /// User the stream is counted for
+    pub user: String,
+    /// Playback session the token was issued for
+    pub session: String,
+}
+
+/// Why a token was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    Malformed,
+    InvalidSignature,
+    Expired,
+    /// No token, but tokens are required
+    Missing,
+    /// The token is for another media item
+    WrongMedia,
+    Revoked,
+}
+
+/// Signs and verifies stream tokens
+pub struct StreamUrlSigner {
+    key: Vec<u8>,
+    ttl: Duration,
+    /// Lifetime of session tokens
+    session_ttl: Duration,
+    /// Whether direct stream URLs need a session token
+    require_session_tokens: bool,
+    /// Revoked sessions, until their tokens expire
+    revoked: Mutex<HashMap<String, i64>>,
+    /// Where issued and revoked sessions are kept (in memory only without one)
+    repository: Option<Arc<dyn StreamTokenRepository>>,
+}
+
+impl StreamUrlSigner {
+    /// Creates a signer
+    ///
+    /// Without a configured secret a random one is generated, so tokens stop
+    /// working when the server restarts.
+    pub fn new(secret: Option<&str>, ttl: Duration) -> Self {
+        let key = match secret {
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => format!("{}{}", uuid::Uuid::new_v4(), uuid::Uuid::new_v4()).into_bytes(),
+        };
+        Self {
+            key,
+            ttl,
+            session_ttl: ttl,
+            require_session_tokens: false,
+            revoked: Mutex::new(HashMap::new()),
+            repository: None,
+        }
+    }
+
+    /// Reads the secret stored at `path`, creating a random one on first
+    /// use, so tokens keep working across restarts
+    pub fn load_or_create_secret(path: &Path) -> std::io::Result<String> {
+        match std::fs::read_to_string(path) {
+            Ok(secret) if !secret.trim().is_empty() => return Ok(secret.trim().to_string()),
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let secret = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, &secret)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(secret)
+    }
+
+    /// Sets the lifetime of session tokens (default: that of cast tokens)
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    /// Rejects direct stream requests without a session token
+    pub fn with_required_session_tokens(mut self, required: bool) -> Self {
+        self.require_session_tokens = required;
+        self
+    }
+
+    /// Stores issued and revoked sessions in a repository
+    pub fn with_repository(mut self, repository: Arc<dyn StreamTokenRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Loads the stored revocations, returning how many are still in force
+    pub async fn load_revoked(&self) -> Result<usize, ApplicationError> {
+        let Some(repository) = &self.repository else {
+            return Ok(0);
+        };
+        let revoked = repository.find_revoked(Utc::now().timestamp()).await?;
+        let count = revoked.len();
+        self.lock_revoked().extend(revoked);
+        Ok(count)
+    }
+
+    /// Issues a token for a media item, valid for the configured lifetime
+    pub fn sign(&self, media_id: i64) -> SignedStream {
+        self.sign_until(media_id, Utc::now() + self.ttl)
+    }
+
+    fn sign_until(&self, media_id: i64, expires_at: DateTime<Utc>) -> SignedStream {
+        let expires = expires_at.timestamp();
+        let payload = format!("{}-{}", media_id, expires);
+        let signature = self.signature(&payload);
+        SignedStream {
+            token: format!("{}-{}", payload, signature),
+            expires_at: DateTime::from_timestamp(expires, 0).unwrap_or(expires_at),
+        }
+    }
+
+    /// Returns the media ID a token authorizes
+    pub fn verify(&self, token: &str) -> Result<i64, TokenError> {
+        let (payload, signature) = token.rsplit_once('-').ok_or(TokenError::Malformed)?;
+        let (media_id, expires) = payload.split_once('-').ok_or(TokenError::Malformed)?;
+        let media_id: i64 = media_id.parse().map_err(|_| TokenError::Malformed)?;
+        let expires: i64 = expires.parse().map_err(|_| TokenError::Malformed)?;
+
+        if !self.verify_signature(payload, signature) {
+            return Err(TokenError::InvalidSignature);
+        }
+        if Utc::now().timestamp() > expires {
+            return Err(TokenError::Expired);
+        }
+        Ok(media_id)
+    }
+
+    /// Issues a session token for a user and a new playback session
+    pub fn sign_session(&self, media_id: i64, user: &str) -> (StreamClaims, SignedStream) {
+        let claims = StreamClaims {
+            media_id,
+            user: user.to_string(),
+            session: uuid::Uuid::new_v4().simple().to_string(),
+        };
+        let signed = self.sign_session_until(&claims, Utc::now() + self.session_ttl);
+        (claims, signed)
+    }
+
+    /// Issues a session token for a device's request, recording it so that
+    /// revoking the device revokes the token
+    pub async fn issue_session(&self, media_id: i64, user: &str, device_id: Option<i64>) -> Result<(StreamClaims, SignedStream), ApplicationError> {
+        let (claims, signed) = self.sign_session(media_id, user);
+        if let (Some(repository), Some(device_id)) = (&self.repository, device_id) {
+            repository.record(&claims.session, device_id, signed.expires_at.timestamp()).await?;
+        }
+        Ok((claims, signed))
+    }
+
+    fn sign_session_until(&self, claims: &StreamClaims, expires_at: DateTime<Utc>) -> SignedStream {
+        let expires = expires_at.timestamp();
+        let payload = format!("{}-{}-{}-{}", claims.media_id, expires, claims.session, hex::encode(&claims.user));
+        let signature = self.signature(&payload);
+        SignedStream {
+            token: format!("{}-{}", payload, signature),
+            expires_at: DateTime::from_timestamp(expires, 0).unwrap_or(expires_at),
+        }
+    }
+
+    /// Returns what a session token authorizes, with its expiry
+    pub fn verify_session(&self, token: &str) -> Result<(StreamClaims, i64), TokenError> {
+        let (payload, signature) = token.rsplit_once('-').ok_or(TokenError::Malformed)?;
+        let [media_id, expires, session, user] = payload.split('-').collect::<Vec<_>>()[..] else {
+            return Err(TokenError::Malformed);
+        };
+        let media_id: i64 = media_id.parse().map_err(|_| TokenError::Malformed)?;
+        let expires: i64 = expires.parse().map_err(|_| TokenError::Malformed)?;
+
+        if !self.verify_signature(payload, signature) {
+            return Err(TokenError::InvalidSignature);
+        }
+        if Utc::now().timestamp() > expires {
+            return Err(TokenError::Expired);
+        }
+        if self.lock_revoked().contains_key(session) {
+            return Err(TokenError::Revoked);
+        }
+        let user = hex::decode(user).ok().and_then(|u| String::from_utf8(u).ok()).ok_or(TokenError::Malformed)?;
+        Ok((StreamClaims { media_id, user, session: session.to_string() }, expires))
+    }
+
+    /// Checks the session token of a direct stream request
+    ///
+    /// Returns None for requests without a token when tokens are optional.
+    pub fn authorize(&self, media_id: i64, token: Option<&str>) -> Result<Option<StreamClaims>, TokenError> {
+        let Some(token) = token else {
+            return if self.require_session_tokens { Err(TokenError::Missing) } else { Ok(None) };
+        };
+        let (claims, _) = self.verify_session(token)?;
+        if claims.media_id != media_id {
+            return Err(TokenError::WrongMedia);
+        }
+        Ok(Some(claims))
+    }
+
+    /// Revokes a session whose tokens expire at `expires`
+    pub async fn revoke(&self, session: &str, expires: i64) -> Result<(), ApplicationError> {
+        if let Some(repository) = &self.repository {
+            repository.revoke(session, expires).await?;
+        }
+        self.remember_revoked([(session.to_string(), expires)]);
+        Ok(())
+    }
+
+    /// Revokes the sessions issued to a device, returning how many were
+    /// still valid
+    pub async fn revoke_device(&self, device_id: i64) -> Result<usize, ApplicationError> {
+        let Some(repository) = &self.repository else {
+            return Ok(0);
+        };
+        let revoked = repository.revoke_device(device_id, Utc::now().timestamp()).await?;
+        let count = revoked.len();
+        self.remember_revoked(revoked);
+        Ok(count)
+    }
+
+    fn remember_revoked(&self, sessions: impl IntoIterator<Item = (String, i64)>) {
+        let now = Utc::now().timestamp();
+        let mut revoked = self.lock_revoked();
+        revoked.retain(|_, until| *until >= now);
+        revoked.extend(sessions);
+    }
+
+    fn lock_revoked(&self) -> std::sync::MutexGuard<'_, HashMap<String, i64>> {
+        self.revoked.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    fn signature(&self, payload: &str) -> String {
+        hex::encode(&self.mac(payload).finalize().into_bytes()[..SIGNATURE_LEN / 2])
+    }
+
+    /// Checks a signature in constant time
+    fn verify_signature(&self, payload: &str, signature: &str) -> bool {
+        if signature.len() != SIGNATURE_LEN {
+            return false;
+        }
+        match hex::decode(signature) {
+            Ok(bytes) => self.mac(payload).verify_truncated_left(&bytes).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        // RFC 4231 test case 2, truncated
+        let signer = StreamUrlSigner::new(Some("Jefe"), Duration::from_secs(3600));
+        assert_eq!(signer.signature("what do ya want for nothing?"), "5bdcc146bf60754e6a042426089575c7");
+
+        let signer = StreamUrlSigner::new(Some("secret"), Duration::from_secs(3600));
+        let signed = signer.sign(42);
+        assert_eq!(signer.verify(&signed.token), Ok(42));
+
+        let forged = signed.token.replacen("42-", "43-", 1);
+        assert_eq!(signer.verify(&forged), Err(TokenError::InvalidSignature));
+        assert_eq!(StreamUrlSigner::new(Some("other"), Duration::from_secs(3600)).verify(&signed.token), Err(TokenError::InvalidSignature));
+        assert_eq!(signer.verify("42"), Err(TokenError::Malformed));
+
+        let expired = signer.sign_until(42, Utc::now() - chrono::Duration::seconds(1));
+        assert_eq!(signer.verify(&expired.token), Err(TokenError::Expired));
+    }
+
+    #[tokio::test]
+    async fn test_session_tokens() {
+        let signer = StreamUrlSigner::new(Some("secret"), Duration::from_secs(3600)).with_required_session_tokens(true);
+        let (claims, signed) = signer.sign_session(42, "anna-b");
+        assert_eq!(signer.authorize(42, Some(&signed.token)), Ok(Some(claims.clone())));
+        assert_eq!(signer.authorize(43, Some(&signed.token)), Err(TokenError::WrongMedia));
+        assert_eq!(signer.authorize(42, None), Err(TokenError::Missing));
+
+        // Cast and session tokens are not interchangeable
+        assert_eq!(signer.verify(&signed.token), Err(TokenError::Malformed));
+        assert_eq!(signer.verify_session(&signer.sign(42).token), Err(TokenError::Malformed));
+
+        let other_user = StreamClaims { user: "bob".to_string(), ..claims.clone() };
+        let forged = signed.token.replacen(&hex::encode("anna-b"), &hex::encode("bob"), 1);
+        assert_eq!(signer.verify_session(&forged), Err(TokenError::InvalidSignature));
+        let expired = signer.sign_session_until(&other_user, Utc::now() - chrono::Duration::seconds(1));
+        assert_eq!(signer.verify_session(&expired.token), Err(TokenError::Expired));
+
+        let (_, expires) = signer.verify_session(&signed.token).unwrap();
+        signer.revoke(&claims.session, expires).await.unwrap();
+        assert_eq!(signer.authorize(42, Some(&signed.token)), Err(TokenError::Revoked));
+        assert_eq!(StreamUrlSigner::new(Some("secret"), Duration::from_secs(60)).authorize(42, None), Ok(None));
+    }
+
+    #[tokio::test]
+    async fn test_revocations_survive_restarts() {
+        use crate::infrastructure::database::schema::initialize_schema;
+        use crate::infrastructure::persistence::sqlite::SqliteStreamTokenRepository;
+
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repository: Arc<dyn StreamTokenRepository> = Arc::new(SqliteStreamTokenRepository::new(pool));
+        let signer = || StreamUrlSigner::new(Some("secret"), Duration::from_secs(3600)).with_repository(repository.clone());
+
+        let before = signer();
+        let (_, tablet) = before.issue_session(42, "default", Some(7)).await.unwrap();
+        let (_, phone) = before.issue_session(42, "default", Some(8)).await.unwrap();
+        let (revoked, single) = before.issue_session(43, "default", None).await.unwrap();
+        assert_eq!(before.revoke_device(7).await.unwrap(), 1);
+        before.revoke(&revoked.session, single.expires_at.timestamp()).await.unwrap();
+        assert_eq!(before.verify_session(&tablet.token).map(|_| ()), Err(TokenError::Revoked));
+
+        // A restarted server still rejects them
+        let after = signer();
+        assert_eq!(after.load_revoked().await.unwrap(), 2);
+        assert_eq!(after.verify_session(&tablet.token).map(|_| ()), Err(TokenError::Revoked));
+        assert_eq!(after.verify_session(&single.token).map(|_| ()), Err(TokenError::Revoked));
+        assert!(after.verify_session(&phone.token).is_ok());
+    }
+
+    #[test]
+    fn test_load_or_create_secret() {
+        let path = std::env::temp_dir().join(format!("homeflix-key-{}", uuid::Uuid::new_v4())).join("stream.key");
+        let secret = StreamUrlSigner::load_or_create_secret(&path).unwrap();
+        assert_eq!(secret.len(), 64);
+        assert_eq!(StreamUrlSigner::load_or_create_secret(&path).unwrap(), secret);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
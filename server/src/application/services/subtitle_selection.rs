@@ -0,0 +1,90 @@
+//! Default Subtitle Selection
+//!
+//! Picks the subtitle a player turns on by default, from the viewer's
+//! language and the language of the audio being played. Forced subtitles
+//! only translate the lines that are not in the audio's main language
+//! (foreign dialogue, signs), so:
+//!
+//! - Audio in another language than the viewer's: a full subtitle in the
+//!   viewer's language, an SDH one if that is all there is
+//! - Audio in the viewer's language: the forced subtitle in that language,
+//!   so foreign-language scenes are still understood
+//!
+//! Viewers who asked for SDH get it whenever there is one in their language,
+//! whatever the audio is in.
+
+use crate::infrastructure::subtitle::normalize_language_code;
+
+/// A subtitle the default is picked from
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubtitleChoice<'a> {
+    /// Language code (ISO 639-1 or 639-2)
+    pub language: Option<&'a str>,
+    pub forced: bool,
+    pub hearing_impaired: bool,
+}
+
+/// Position of the subtitle to turn on by default
+///
+/// `prefer_hearing_impaired` puts SDH subtitles first. Returns None when no
+/// subtitle fits the rules, leaving the choice to the caller.
+pub fn default_subtitle(
+    choices: &[SubtitleChoice],
+    audio_language: Option<&str>,
+    user_language: &str,
+    prefer_hearing_impaired: bool,
+) -> Option<usize> {
+    let user = normalize_language_code(user_language)?;
+    let in_user_language = |choice: &SubtitleChoice| {
+        choice.language.and_then(normalize_language_code).as_deref() == Some(user.as_str())
+    };
+
+    if prefer_hearing_impaired {
+        let sdh = choices.iter().position(|c| !c.forced && c.hearing_impaired && in_user_language(c));
+        if sdh.is_some() {
+            return sdh;
+        }
+    }
+
+    if audio_language.and_then(normalize_language_code).as_deref() == Some(user.as_str()) {
+        return choices.iter().position(|c| c.forced && in_user_language(c));
+    }
+
+    choices.iter().position(|c| !c.forced && !c.hearing_impaired && in_user_language(c))
+        .or_else(|| choices.iter().position(|c| !c.forced && in_user_language(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_subtitle() {
+        let choice = |language: &'static str, forced: bool, hearing_impaired: bool| SubtitleChoice {
+            language: Some(language),
+            forced,
+            hearing_impaired,
+        };
+        let choices = [
+            choice("en", false, false),
+            choice("hun", true, false),
+            choice("hu", false, true),
+            choice("hu", false, false),
+        ];
+
+        // English audio for a Hungarian viewer: full Hungarian subtitle
+        assert_eq!(default_subtitle(&choices, Some("eng"), "hu", false), Some(3));
+        assert_eq!(default_subtitle(&choices[..3], Some("eng"), "hu", false), Some(2));
+        // Hungarian dub: only the forced lines
+        assert_eq!(default_subtitle(&choices, Some("hun"), "hu", false), Some(1));
+        assert_eq!(default_subtitle(&choices, Some("en"), "en", false), None);
+        // Untagged audio is treated as foreign
+        assert_eq!(default_subtitle(&choices, None, "en", false), Some(0));
+        assert_eq!(default_subtitle(&choices, Some("ger"), "fr", false), None);
+
+        // SDH viewers get SDH, even with audio in their language
+        assert_eq!(default_subtitle(&choices, Some("eng"), "hu", true), Some(2));
+        assert_eq!(default_subtitle(&choices, Some("hun"), "hu", true), Some(2));
+        assert_eq!(default_subtitle(&choices, Some("ger"), "en", true), Some(0));
+    }
+}
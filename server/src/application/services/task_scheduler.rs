@@ -0,0 +1,438 @@
+//! Task Scheduler
+//!
+//! Runs named background tasks (library scan, metadata refresh, ...) on
+//! cron schedules in local time. Schedules and whether tasks run come from
+//! the configuration; changes made through the API are stored and take
+//! precedence, and so is the last run of each task, so it is reported
+//! across restarts. A task never runs twice at once: a run that comes due
+//! while the previous one is still going is skipped.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+use crate::domain::repositories::{ScheduledTaskRepository, StoredTask, TaskRun};
+use crate::domain::value_objects::CronSchedule;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Wait before startup runs, to let the server start
+const STARTUP_DELAY: Duration = Duration::from_secs(5);
+
+/// Work a task does, returning a summary of it
+pub type TaskAction = Arc<dyn Fn() -> BoxFuture<'static, Result<String, ApplicationError>> + Send + Sync>;
+
+/// A task as configured
+pub struct TaskDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schedule: CronSchedule,
+    pub enabled: bool,
+    /// Also run shortly after startup when enabled
+    pub run_at_startup: bool,
+    pub action: TaskAction,
+}
+
+impl TaskDefinition {
+    /// An enabled task running `action` on `schedule`
+    pub fn new<F, Fut>(name: &'static str, description: &'static str, schedule: CronSchedule, action: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, ApplicationError>> + Send + 'static,
+    {
+        Self {
+            name,
+            description,
+            schedule,
+            enabled: true,
+            run_at_startup: false,
+            action: Arc::new(move || Box::pin(action())),
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Runs the task once after startup too
+    pub fn with_startup_run(mut self) -> Self {
+        self.run_at_startup = true;
+        self
+    }
+}
+
+/// A task and when it runs
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schedule: CronSchedule,
+    pub enabled: bool,
+    /// Schedule or enabled flag changed through the API
+    pub overridden: bool,
+    pub running: bool,
+    /// None while disabled
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_run: Option<TaskRun>,
+}
+
+/// Changes to a task; fields left out stay as they are
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskUpdate {
+    /// Cron expression
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+struct Task {
+    definition: TaskDefinition,
+    state: Mutex<TaskState>,
+    running: AtomicBool,
+    /// Wakes the task's timer after its schedule changed
+    changed: Notify,
+}
+
+struct TaskState {
+    schedule: CronSchedule,
+    enabled: bool,
+    stored: StoredTask,
+}
+
+impl Task {
+    fn lock(&self) -> std::sync::MutexGuard<'_, TaskState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Marks the task running, false if it already is
+    fn begin(&self) -> bool {
+        !self.running.swap(true, Ordering::AcqRel)
+    }
+
+    fn info(&self) -> TaskInfo {
+        let state = self.lock();
+        TaskInfo {
+            name: self.definition.name,
+            description: self.definition.description,
+            schedule: state.schedule.clone(),
+            enabled: state.enabled,
+            overridden: state.stored.schedule.is_some() || state.stored.enabled.is_some(),
+            running: self.running.load(Ordering::Acquire),
+            next_run: state
+                .enabled
+                .then(|| state.schedule.next_after(&Local::now()))
+                .flatten()
+                .map(|next| next.with_timezone(&Utc)),
+            last_run: state.stored.last_run.clone(),
+        }
+    }
+}
+
+/// Runs registered tasks on their schedules
+pub struct TaskScheduler {
+    repository: Arc<dyn ScheduledTaskRepository>,
+    tasks: RwLock<Vec<Arc<Task>>>,
+    /// Held while storing a task, so the latest state is stored last
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl TaskScheduler {
+    pub fn new(repository: Arc<dyn ScheduledTaskRepository>) -> Self {
+        Self {
+            repository,
+            tasks: RwLock::new(Vec::new()),
+            saving: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Adds a task; tasks added after [`TaskScheduler::start`] never run
+    pub fn register(&self, definition: TaskDefinition) {
+        let state = TaskState {
+            schedule: definition.schedule.clone(),
+            enabled: definition.enabled,
+            stored: StoredTask { name: definition.name.to_string(), ..Default::default() },
+        };
+        let task = Arc::new(Task {
+            definition,
+            state: Mutex::new(state),
+            running: AtomicBool::new(false),
+            changed: Notify::new(),
+        });
+        self.tasks.write().unwrap_or_else(|e| e.into_inner()).push(task);
+    }
+
+    /// Applies the stored changes and starts the tasks' timers
+    ///
+    /// Stored schedules that no longer parse are ignored.
+    pub async fn start(self: &Arc<Self>) -> Result<(), ApplicationError> {
+        let stored = self.repository.find_all().await?;
+        for task in self.tasks() {
+            if let Some(stored) = stored.iter().find(|s| s.name == task.definition.name) {
+                let mut state = task.lock();
+                state.stored = stored.clone();
+                if let Some(schedule) = &stored.schedule {
+                    match schedule.parse() {
+                        Ok(schedule) => state.schedule = schedule,
+                        Err(e) => {
+                            warn!("Ignoring stored schedule of task {}: {}", task.definition.name, e);
+                            state.stored.schedule = None;
+                        }
+                    }
+                }
+                state.enabled = stored.enabled.unwrap_or(task.definition.enabled);
+            }
+
+            let info = task.info();
+            match info.next_run {
+                Some(next) => info!("Task {} scheduled ({}), next run at {}", info.name, info.schedule, next.with_timezone(&Local)),
+                None => info!("Task {} disabled", info.name),
+            }
+            let scheduler = Arc::clone(self);
+            tokio::spawn(async move { scheduler.run_timer(task).await });
+        }
+        Ok(())
+    }
+
+    /// Every task, in the order registered
+    pub fn list(&self) -> Vec<TaskInfo> {
+        self.tasks().iter().map(|task| task.info()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Result<TaskInfo, ApplicationError> {
+        Ok(self.find(name)?.info())
+    }
+
+    /// Changes a task's schedule or whether it runs, stored so it outlives
+    /// restarts
+    pub async fn update(&self, name: &str, update: TaskUpdate) -> Result<TaskInfo, ApplicationError> {
+        let task = self.find(name)?;
+        let schedule = update
+            .schedule
+            .as_deref()
+            .map(str::parse::<CronSchedule>)
+            .transpose()
+            .map_err(|e| DomainError::InvalidInput(format!("schedule: {}", e)))?;
+
+        {
+            let mut state = task.lock();
+            if let Some(schedule) = schedule {
+                state.stored.schedule = Some(schedule.to_string());
+                state.schedule = schedule;
+            }
+            if let Some(enabled) = update.enabled {
+                state.stored.enabled = Some(enabled);
+                state.enabled = enabled;
+            }
+        }
+        self.persist(&task).await?;
+        task.changed.notify_one();
+        Ok(task.info())
+    }
+
+    /// Runs a task now, in the background
+    ///
+    /// Disabled tasks run too. Fails if the task is already running.
+    pub fn run_now(self: &Arc<Self>, name: &str) -> Result<TaskInfo, ApplicationError> {
+        let task = self.find(name)?;
+        if !task.begin() {
+            return Err(DomainError::InvalidState(format!("Task {} is already running", name)).into());
+        }
+        let info = task.info();
+        let scheduler = Arc::clone(self);
+        tokio::spawn(async move { scheduler.execute(&task).await });
+        Ok(info)
+    }
+
+    fn tasks(&self) -> Vec<Arc<Task>> {
+        self.tasks.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn find(&self, name: &str) -> Result<Arc<Task>, ApplicationError> {
+        self.tasks()
+            .into_iter()
+            .find(|task| task.definition.name == name)
+            .ok_or_else(|| DomainError::NotFound(format!("Task {} not found", name)).into())
+    }
+
+    /// Runs a task whenever it comes due, until the process ends
+    async fn run_timer(&self, task: Arc<Task>) {
+        if task.definition.run_at_startup && task.lock().enabled {
+            tokio::time::sleep(STARTUP_DELAY).await;
+            self.run_scheduled(&task).await;
+        }
+
+        // Timers may fire a little early; a run is never due twice
+        let mut last_due: Option<DateTime<Local>> = None;
+        loop {
+            let now = Local::now();
+            let after = last_due.filter(|due| *due > now).unwrap_or(now);
+            let next = {
+                let state = task.lock();
+                state.enabled.then(|| state.schedule.next_after(&after)).flatten()
+            };
+            let Some(next) = next else {
+                task.changed.notified().await;
+                continue;
+            };
+
+            let wait = (next - now).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {
+                    last_due = Some(next);
+                    self.run_scheduled(&task).await;
+                }
+                _ = task.changed.notified() => {}
+            }
+        }
+    }
+
+    async fn run_scheduled(&self, task: &Task) {
+        if task.begin() {
+            self.execute(task).await;
+        } else {
+            warn!("Skipping scheduled run of task {}: still running", task.definition.name);
+        }
+    }
+
+    /// Runs a task marked running and records the run
+    async fn execute(&self, task: &Task) {
+        let name = task.definition.name;
+        info!("Running task {}", name);
+        let started_at = Utc::now();
+        let clock = Instant::now();
+
+        // Run apart, so a panicking task is recorded as failed
+        let result = match tokio::spawn((task.definition.action)()).await {
+            Ok(result) => result,
+            Err(e) => Err(ApplicationError::Internal(format!("Task panicked: {}", e))),
+        };
+        let message = match &result {
+            Ok(summary) => {
+                info!("Task {} finished: {}", name, summary);
+                Some(summary.clone()).filter(|s| !s.is_empty())
+            }
+            Err(e) => {
+                error!("Task {} failed: {}", name, e);
+                Some(e.to_string())
+            }
+        };
+
+        task.lock().stored.last_run = Some(TaskRun {
+            started_at,
+            duration_ms: clock.elapsed().as_millis() as u64,
+            success: result.is_ok(),
+            message,
+        });
+        task.running.store(false, Ordering::Release);
+        if let Err(e) = self.persist(task).await {
+            warn!("Failed to store the run of task {}: {}", name, e);
+        }
+    }
+
+    async fn persist(&self, task: &Task) -> Result<(), ApplicationError> {
+        let _saving = self.saving.lock().await;
+        let stored = task.lock().stored.clone();
+        self.repository.save(&stored).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteScheduledTaskRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::atomic::AtomicUsize;
+
+    fn scheduler(repository: Arc<dyn ScheduledTaskRepository>, runs: Arc<AtomicUsize>) -> Arc<TaskScheduler> {
+        let scheduler = Arc::new(TaskScheduler::new(repository));
+        scheduler.register(TaskDefinition::new(
+            "db_maintenance",
+            "Compact the database",
+            "0 4 * * *".parse().unwrap(),
+            move || {
+                let runs = runs.clone();
+                async move {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+                    if run == 2 {
+                        return Err(ApplicationError::Internal("database is locked".to_string()));
+                    }
+                    Ok(format!("run {}", run))
+                }
+            },
+        ));
+        scheduler.register(
+            TaskDefinition::new("metadata_refresh", "Refresh metadata", "@daily".parse().unwrap(), || async { Ok(String::new()) })
+                .with_enabled(false),
+        );
+        scheduler
+    }
+
+    async fn wait_idle(scheduler: &TaskScheduler, name: &str) -> TaskInfo {
+        loop {
+            let info = scheduler.get(name).unwrap();
+            if !info.running {
+                return info;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tasks() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repository: Arc<dyn ScheduledTaskRepository> = Arc::new(SqliteScheduledTaskRepository::new(pool));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let tasks = scheduler(repository.clone(), runs.clone());
+        tasks.start().await.unwrap();
+        let listed = tasks.list();
+        assert_eq!(listed.iter().map(|t| t.name).collect::<Vec<_>>(), vec!["db_maintenance", "metadata_refresh"]);
+        assert!(listed[0].next_run.is_some() && listed[1].next_run.is_none());
+        assert!(matches!(tasks.run_now("missing"), Err(ApplicationError::Domain(DomainError::NotFound(_)))));
+
+        // One run at a time
+        assert!(tasks.run_now("db_maintenance").unwrap().running);
+        assert!(matches!(tasks.run_now("db_maintenance"), Err(ApplicationError::Domain(DomainError::InvalidState(_)))));
+        let info = wait_idle(&tasks, "db_maintenance").await;
+        let last_run = info.last_run.unwrap();
+        assert!(last_run.success);
+        assert_eq!(last_run.message.as_deref(), Some("run 1"));
+
+        tasks.run_now("db_maintenance").unwrap();
+        let last_run = wait_idle(&tasks, "db_maintenance").await.last_run.unwrap();
+        assert!(!last_run.success);
+        assert_eq!(last_run.message.as_deref(), Some("Internal error: database is locked"));
+
+        let invalid = tasks.update("db_maintenance", TaskUpdate { schedule: Some("0 4 * *".to_string()), enabled: None }).await;
+        assert!(matches!(invalid, Err(ApplicationError::Domain(DomainError::InvalidInput(_)))));
+        let info = tasks
+            .update("metadata_refresh", TaskUpdate { schedule: Some("30 2 * * 1".to_string()), enabled: Some(true) })
+            .await
+            .unwrap();
+        assert!(info.enabled && info.overridden && info.next_run.is_some());
+
+        // Changes and last runs outlive restarts
+        let restarted = scheduler(repository, runs);
+        restarted.start().await.unwrap();
+        let listed = restarted.list();
+        assert!(!listed[0].overridden);
+        assert_eq!(listed[0].last_run.as_ref().map(|r| r.success), Some(false));
+        assert_eq!(listed[1].schedule.to_string(), "30 2 * * 1");
+        assert!(listed[1].enabled);
+    }
+}
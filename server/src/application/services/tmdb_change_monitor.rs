@@ -0,0 +1,168 @@
+//! TMDB Change Monitor
+//!
+//! Polls TMDB's movie and TV change lists and re-enriches only the library
+//! items whose metadata changed upstream (new episode titles, updated
+//! artwork, corrected overviews), instead of requiring a full rescan.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::application::services::MetadataEnricher;
+use crate::domain::repositories::{CacheRepository, MediaRepository, SeriesRepository};
+use crate::domain::value_objects::MediaType;
+use crate::interfaces::external_services::TmdbChangesFetcher;
+use crate::shared::error::ApplicationError;
+
+/// Cache key holding the end of the last processed change window
+const LAST_CHECKED_KEY: &str = "tmdb_changes:last_checked";
+
+/// TMDB keeps change lists for 14 days
+const MAX_LOOKBACK_DAYS: i64 = 14;
+
+/// TMDB Change Monitor
+///
+/// # Architecture Notes
+/// - The last checked timestamp is stored in the cache table, so restarts
+///   resume where the previous run stopped
+/// - The first run (or a cleared cache) looks back one day
+pub struct TmdbChangeMonitor {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    cache_repository: Arc<dyn CacheRepository>,
+    changes_fetcher: Arc<dyn TmdbChangesFetcher>,
+    enricher: Arc<MetadataEnricher>,
+}
+
+impl TmdbChangeMonitor {
+    /// Creates a new change monitor
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        cache_repository: Arc<dyn CacheRepository>,
+        changes_fetcher: Arc<dyn TmdbChangesFetcher>,
+        enricher: Arc<MetadataEnricher>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            cache_repository,
+            changes_fetcher,
+            enricher,
+        }
+    }
+
+    /// Checks TMDB for changes since the last run and refreshes affected items
+    ///
+    /// # Returns
+    /// * `Result<ChangeRefreshStats, ApplicationError>` - What was refreshed
+    ///
+    /// # Errors
+    /// Returns error if the change lists cannot be fetched. Failures while
+    /// refreshing individual items are logged and counted instead.
+    pub async fn check_for_changes(&self) -> Result<ChangeRefreshStats, ApplicationError> {
+        let now = Utc::now();
+        let since = self.last_checked().await
+            .unwrap_or_else(|| now - chrono::Duration::days(1))
+            .max(now - chrono::Duration::days(MAX_LOOKBACK_DAYS));
+        info!("Checking TMDB for metadata changes since {}", since.format("%Y-%m-%d %H:%M"));
+
+        let mut stats = ChangeRefreshStats::default();
+
+        // Movies: map TMDB IDs to local movie records
+        let mut movies: HashMap<i64, Vec<i64>> = HashMap::new();
+        for media in self.media_repository.find_by_type(MediaType::Movie).await? {
+            if let (Some(id), Some(tmdb_id)) = (media.id, media.tmdb_id) {
+                movies.entry(tmdb_id).or_default().push(id);
+            }
+        }
+        if !movies.is_empty() {
+            let changed = self.changes_fetcher
+                .fetch_changed_ids("movie", since.date_naive(), now.date_naive())
+                .await?;
+            for tmdb_id in changed {
+                let Some(media_ids) = movies.get(&tmdb_id) else {
+                    continue;
+                };
+                stats.movies_changed += 1;
+                if let Err(e) = self.changes_fetcher.invalidate_cached(tmdb_id, "movie").await {
+                    warn!("Failed to invalidate TMDB cache for movie {}: {}", tmdb_id, e);
+                }
+                for &media_id in media_ids {
+                    match self.enricher.refresh_movie_from_tmdb(media_id).await {
+                        Ok(_) => stats.items_refreshed += 1,
+                        Err(e) => {
+                            stats.failed += 1;
+                            warn!("Failed to refresh movie {} (TMDB {}): {}", media_id, tmdb_id, e);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Series: refresh the series record and its episodes
+        let series: HashMap<i64, i64> = self.series_repository.find_all().await?
+            .into_iter()
+            .filter_map(|s| Some((s.tmdb_id?, s.id?)))
+            .collect();
+        if !series.is_empty() {
+            let changed = self.changes_fetcher
+                .fetch_changed_ids("tv", since.date_naive(), now.date_naive())
+                .await?;
+            for tmdb_id in changed {
+                let Some(&series_id) = series.get(&tmdb_id) else {
+                    continue;
+                };
+                stats.series_changed += 1;
+                if let Err(e) = self.changes_fetcher.invalidate_cached(tmdb_id, "tv").await {
+                    warn!("Failed to invalidate TMDB cache for series {}: {}", tmdb_id, e);
+                }
+                match self.enricher.refresh_series_from_tmdb(series_id).await {
+                    Ok(episodes) => {
+                        stats.items_refreshed += 1;
+                        stats.episodes_updated += episodes;
+                    }
+                    Err(e) => {
+                        stats.failed += 1;
+                        warn!("Failed to refresh series {} (TMDB {}): {}", series_id, tmdb_id, e);
+                    }
+                }
+            }
+        }
+
+        self.set_last_checked(now).await;
+        info!(
+            "TMDB change check complete: {} movies and {} series changed, {} episodes updated, {} failed",
+            stats.movies_changed, stats.series_changed, stats.episodes_updated, stats.failed
+        );
+        Ok(stats)
+    }
+
+    async fn last_checked(&self) -> Option<DateTime<Utc>> {
+        let value = self.cache_repository.get(LAST_CHECKED_KEY).await.ok()??;
+        DateTime::parse_from_rfc3339(&value).ok().map(|t| t.with_timezone(&Utc))
+    }
+
+    async fn set_last_checked(&self, at: DateTime<Utc>) {
+        let ttl = (MAX_LOOKBACK_DAYS * 86400) as u64;
+        if let Err(e) = self.cache_repository.set(LAST_CHECKED_KEY, &at.to_rfc3339(), ttl).await {
+            debug!("Failed to store TMDB change check time: {}", e);
+        }
+    }
+}
+
+/// Statistics from a change check
+#[derive(Debug, Clone, Default)]
+pub struct ChangeRefreshStats {
+    /// Library movies that changed upstream
+    pub movies_changed: usize,
+    /// Library series that changed upstream
+    pub series_changed: usize,
+    /// Movies and series successfully refreshed
+    pub items_refreshed: usize,
+    /// Episodes whose metadata was updated
+    pub episodes_updated: usize,
+    /// Items that failed to refresh
+    pub failed: usize,
+}
@@ -0,0 +1,365 @@
+//! Webhooks
+//!
+//! POSTs domain events to admin-configured endpoints (Home Assistant
+//! automations, chat bots). Each request carries the event type in
+//! `X-Homeflix-Event` and, when the webhook has a secret, an HMAC-SHA256 of
+//! the body in `X-Homeflix-Signature: sha256=<hex>`. Failed deliveries are
+//! retried with growing delays, and every delivery is logged.
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::domain::repositories::{Webhook, WebhookDelivery, WebhookRepository, WebhookSettings};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Event types webhooks can subscribe to
+pub const WEBHOOK_EVENT_TYPES: &[&str] = &[
+    "media_identified",
+    "scan_completed",
+    "scan_failed",
+    "collection_detected",
+    "subtitle_generation_completed",
+    "subtitle_generation_failed",
+    "stream_started",
+    "stream_ended",
+    "media_watched",
+];
+/// Event type of test deliveries
+pub const PING_EVENT: &str = "ping";
+
+/// Delays before each retry of a failed delivery
+const RETRY_DELAYS: [Duration; 3] = [Duration::from_secs(5), Duration::from_secs(30), Duration::from_secs(120)];
+/// Deliveries logged per webhook
+const DELIVERIES_KEPT: u32 = 100;
+/// Time allowed for one request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Manages webhooks and delivers events to them
+pub struct WebhookService {
+    repository: Arc<dyn WebhookRepository>,
+    client: reqwest::Client,
+    retry_delays: Vec<Duration>,
+    /// Webhooks loaded for dispatching, dropped when they change
+    webhooks: RwLock<Option<Arc<Vec<Webhook>>>>,
+}
+
+impl WebhookService {
+    pub fn new(repository: Arc<dyn WebhookRepository>) -> Self {
+        Self {
+            repository,
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            retry_delays: RETRY_DELAYS.to_vec(),
+            webhooks: RwLock::new(None),
+        }
+    }
+
+    /// Waits these delays before retrying a failed delivery
+    pub fn with_retry_delays(mut self, retry_delays: Vec<Duration>) -> Self {
+        self.retry_delays = retry_delays;
+        self
+    }
+
+    /// Lists the webhooks
+    pub async fn list(&self) -> Result<Vec<Webhook>, ApplicationError> {
+        Ok(self.repository.find_all().await?)
+    }
+
+    /// Gets a webhook
+    pub async fn get(&self, id: i64) -> Result<Webhook, ApplicationError> {
+        self.repository
+            .find(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Webhook {} not found", id)).into())
+    }
+
+    /// Adds a webhook
+    pub async fn create(&self, settings: WebhookSettings) -> Result<Webhook, ApplicationError> {
+        let settings = validate(settings)?;
+        let webhook = self.repository.create(&settings).await?;
+        self.invalidate().await;
+        Ok(webhook)
+    }
+
+    /// Changes a webhook
+    pub async fn update(&self, id: i64, settings: WebhookSettings) -> Result<Webhook, ApplicationError> {
+        let settings = validate(settings)?;
+        let webhook = self
+            .repository
+            .update(id, &settings)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Webhook {} not found", id)))?;
+        self.invalidate().await;
+        Ok(webhook)
+    }
+
+    /// Removes a webhook and its delivery log
+    pub async fn delete(&self, id: i64) -> Result<(), ApplicationError> {
+        if !self.repository.delete(id).await? {
+            return Err(DomainError::NotFound(format!("Webhook {} not found", id)).into());
+        }
+        self.invalidate().await;
+        Ok(())
+    }
+
+    /// Latest deliveries of a webhook, newest first
+    pub async fn deliveries(&self, id: i64, limit: u32) -> Result<Vec<WebhookDelivery>, ApplicationError> {
+        self.get(id).await?;
+        Ok(self.repository.find_deliveries(id, limit).await?)
+    }
+
+    /// Sends a `ping` event to a webhook once, without retries
+    pub async fn ping(&self, id: i64) -> Result<WebhookDelivery, ApplicationError> {
+        let webhook = self.get(id).await?;
+        let data = json!({ "webhook_id": webhook.id, "name": webhook.name });
+        Ok(self.deliver(&webhook, PING_EVENT, &data, &[]).await)
+    }
+
+    /// Delivers an event to the enabled webhooks subscribed to its type,
+    /// in the background
+    pub async fn dispatch(self: &Arc<Self>, event_type: &str, data: serde_json::Value) {
+        let webhooks = match self.webhooks().await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                warn!("Failed to load webhooks for {}: {}", event_type, e);
+                return;
+            }
+        };
+
+        let data = Arc::new(data);
+        for webhook in webhooks.iter().filter(|w| w.accepts(event_type)) {
+            let webhook = webhook.clone();
+            let service = self.clone();
+            let event_type = event_type.to_string();
+            let data = data.clone();
+            tokio::spawn(async move {
+                let delays = service.retry_delays.clone();
+                service.deliver(&webhook, &event_type, &data, &delays).await;
+            });
+        }
+    }
+
+    /// Delivers an event to a webhook, retrying after each of `retry_delays`
+    /// while it fails, and logs the outcome
+    async fn deliver(
+        &self,
+        webhook: &Webhook,
+        event_type: &str,
+        data: &serde_json::Value,
+        retry_delays: &[Duration],
+    ) -> WebhookDelivery {
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        let body = json!({
+            "event": event_type,
+            "delivery": delivery_id,
+            "timestamp": Utc::now().to_rfc3339(),
+            "data": data,
+        })
+        .to_string();
+
+        let mut attempts = 0;
+        let (status_code, error) = loop {
+            attempts += 1;
+            let (status_code, error) = self.post(webhook, event_type, &delivery_id, &body).await;
+            let retryable = match status_code {
+                Some(code) => code >= 500 || code == 408 || code == 429,
+                None => error.is_some(),
+            };
+            match retry_delays.get(attempts as usize - 1) {
+                Some(delay) if retryable => {
+                    debug!("Webhook {} delivery of {} failed, retrying in {:?}", webhook.id, event_type, delay);
+                    tokio::time::sleep(*delay).await;
+                }
+                _ => break (status_code, error),
+            }
+        };
+
+        let delivery = WebhookDelivery {
+            id: 0,
+            webhook_id: webhook.id,
+            event_type: event_type.to_string(),
+            attempts,
+            status_code,
+            success: error.is_none(),
+            error,
+            delivered_at: Utc::now().to_rfc3339(),
+        };
+        if !delivery.success {
+            warn!(
+                "Webhook {} ({}) failed to receive {} after {} attempts: {}",
+                webhook.id,
+                webhook.url,
+                event_type,
+                attempts,
+                delivery.error.as_deref().unwrap_or_default()
+            );
+        }
+        if let Err(e) = self.repository.record_delivery(&delivery, DELIVERIES_KEPT).await {
+            warn!("Failed to log webhook delivery: {}", e);
+        }
+        delivery
+    }
+
+    /// Makes one request, returning the response status and why it failed
+    async fn post(&self, webhook: &Webhook, event_type: &str, delivery_id: &str, body: &str) -> (Option<u16>, Option<String>) {
+        let mut request = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Homeflix-Event", event_type)
+            .header("X-Homeflix-Delivery", delivery_id)
+            .body(body.to_string());
+        if let Some(secret) = &webhook.secret {
+            request = request.header("X-Homeflix-Signature", signature(secret, body));
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+            Ok(response) => (Some(response.status().as_u16()), Some(format!("HTTP {}", response.status()))),
+            Err(e) => (None, Some(e.to_string())),
+        }
+    }
+
+    /// Webhooks, loaded on first use
+    async fn webhooks(&self) -> Result<Arc<Vec<Webhook>>, ApplicationError> {
+        if let Some(webhooks) = self.webhooks.read().await.clone() {
+            return Ok(webhooks);
+        }
+        let webhooks = Arc::new(self.repository.find_all().await?);
+        *self.webhooks.write().await = Some(webhooks.clone());
+        Ok(webhooks)
+    }
+
+    async fn invalidate(&self) {
+        *self.webhooks.write().await = None;
+    }
+}
+
+/// Checks and tidies webhook settings
+fn validate(mut settings: WebhookSettings) -> Result<WebhookSettings, ApplicationError> {
+    settings.name = settings.name.trim().to_string();
+    if settings.name.is_empty() {
+        return Err(DomainError::InvalidInput("Webhook name is empty".to_string()).into());
+    }
+    let url = reqwest::Url::parse(settings.url.trim())
+        .map_err(|e| DomainError::InvalidInput(format!("Invalid webhook URL: {}", e)))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(DomainError::InvalidInput("Webhook URL must be http or https".to_string()).into());
+    }
+    settings.url = url.to_string();
+    settings.secret = settings.secret.filter(|s| !s.is_empty());
+
+    if let Some(unknown) = settings.event_types.iter().find(|t| !WEBHOOK_EVENT_TYPES.contains(&t.as_str())) {
+        return Err(DomainError::InvalidInput(format!(
+            "Unknown event type '{}', expected some of: {}",
+            unknown,
+            WEBHOOK_EVENT_TYPES.join(", ")
+        ))
+        .into());
+    }
+    settings.event_types.sort();
+    settings.event_types.dedup();
+    Ok(settings)
+}
+
+/// `X-Homeflix-Signature` value: HMAC-SHA256 of the body, as `sha256=<hex>`
+fn signature(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteWebhookRepository;
+    use axum::{http::HeaderMap, http::StatusCode, routing::post, Router};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_signature() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            signature("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signed_delivery_with_retry() {
+        // Fails the first request, then records what it receives
+        let received: Arc<Mutex<Vec<(HeaderMap, String)>>> = Arc::default();
+        let app = Router::new().route(
+            "/hook",
+            post({
+                let received = received.clone();
+                move |headers: HeaderMap, body: String| async move {
+                    let mut received = received.lock().unwrap();
+                    received.push((headers, body));
+                    if received.len() == 1 { StatusCode::SERVICE_UNAVAILABLE } else { StatusCode::NO_CONTENT }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let service = WebhookService::new(Arc::new(SqliteWebhookRepository::new(pool)))
+            .with_retry_delays(vec![Duration::ZERO; 2]);
+
+        let invalid = WebhookSettings {
+            name: "Bad".to_string(),
+            url: "ftp://example.com".to_string(),
+            secret: None,
+            event_types: vec![],
+            enabled: true,
+        };
+        assert!(service.create(invalid.clone()).await.is_err());
+        assert!(service
+            .create(WebhookSettings { url: "http://example.com".to_string(), event_types: vec!["nope".to_string()], ..invalid })
+            .await
+            .is_err());
+
+        let webhook = service
+            .create(WebhookSettings {
+                name: "Home Assistant".to_string(),
+                url: format!("http://{}/hook", addr),
+                secret: Some("s3cret".to_string()),
+                event_types: vec!["scan_completed".to_string()],
+                enabled: true,
+            })
+            .await
+            .unwrap();
+        let webhook = service.get(webhook.id).await.unwrap();
+
+        let delivery = service.deliver(&webhook, "scan_completed", &json!({ "total": 3 }), &service.retry_delays).await;
+        assert!(delivery.success);
+        assert_eq!((delivery.attempts, delivery.status_code), (2, Some(204)));
+
+        let received = received.lock().unwrap().clone();
+        let (headers, body) = &received[1];
+        assert_eq!(headers["x-homeflix-event"], "scan_completed");
+        assert_eq!(headers["x-homeflix-signature"], signature("s3cret", body).as_str());
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["data"], json!({ "total": 3 }));
+
+        let deliveries = service.deliveries(webhook.id, 10).await.unwrap();
+        assert_eq!(deliveries, vec![WebhookDelivery { id: deliveries[0].id, ..delivery }]);
+    }
+}
@@ -0,0 +1,395 @@
+//! Admin Dashboard Use Case
+//!
+//! Collects what the admin page shows in one report: library counts by
+//! type, resolution and codec, storage used per library, the distribution
+//! of identification confidence, cache hit rates, TMDB request counters,
+//! the latest scans and the jobs in progress.
+//!
+//! Codecs come from file names (media records do not store them), so files
+//! named without one count as "unknown". Storage is measured on disk.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::{CacheRepository, MediaRepository};
+use crate::domain::value_objects::VerificationStatus;
+use crate::infrastructure::cache::{HitStats, ImageCache, TranscodeCache};
+use crate::infrastructure::event_sourcing::event_store::EventStore;
+use crate::infrastructure::external::tmdb::{TmdbClient, TmdbRequestStats};
+use crate::infrastructure::jobs::{BatchJobStatus, JobStatus, JobStore};
+use crate::shared::error::ApplicationError;
+
+/// Event types of finished scans
+const SCAN_EVENT_TYPES: [&str; 2] = ["scan_completed", "scan_failed"];
+/// Scans listed in the report
+const RECENT_SCANS: usize = 10;
+
+/// Number of media items per type, resolution and codec
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LibraryCounts {
+    pub total: usize,
+    pub series: usize,
+    pub by_type: BTreeMap<&'static str, usize>,
+    /// "2160p", "1080p", ... or "unknown"
+    pub by_resolution: BTreeMap<String, usize>,
+    /// "hevc", "h264", "av1", "vp9", "mpeg4" or "unknown"
+    pub by_codec: BTreeMap<&'static str, usize>,
+}
+
+/// Disk space used by a library
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryStorage {
+    /// "movies" or "series"
+    pub library: &'static str,
+    pub files: usize,
+    pub bytes: u64,
+    /// Files that are listed but missing on disk (not counted in `bytes`)
+    pub missing_files: usize,
+}
+
+/// Identification confidence of the library
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfidenceDistribution {
+    /// Media without a TMDB match
+    pub unidentified: usize,
+    /// Identified media per confidence band (high >= 0.85, medium >= 0.70,
+    /// low >= 0.60, very_low below)
+    pub bands: BTreeMap<&'static str, usize>,
+    /// Identified media per tenth of confidence, from 0.0-0.1 up
+    pub histogram: [usize; 10],
+    pub by_status: BTreeMap<&'static str, usize>,
+}
+
+/// Size and lookups of a cache
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheUsage {
+    /// "metadata", "images" or "transcode"
+    pub cache: &'static str,
+    /// Entries stored, when known cheaply
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entries: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Lookups since the server started
+    #[serde(flatten)]
+    pub lookups: HitStats,
+}
+
+/// Outcome of a finished scan
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanSummary {
+    /// "scan_completed" or "scan_failed"
+    pub event: String,
+    pub scan_path: Option<String>,
+    pub processed: Option<u64>,
+    pub identified: Option<u64>,
+    pub failed: Option<u64>,
+    pub duration_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Jobs in progress
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ActiveJobs {
+    pub jobs: Vec<JobStatus>,
+    pub batches: Vec<BatchJobStatus>,
+}
+
+/// Admin dashboard report
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminDashboard {
+    pub library: LibraryCounts,
+    pub storage: Vec<LibraryStorage>,
+    pub confidence: ConfidenceDistribution,
+    pub caches: Vec<CacheUsage>,
+    /// TMDB requests, coalescing and rate limiting since the server started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmdb: Option<TmdbRequestStats>,
+    /// Latest finished scans, newest first
+    pub recent_scans: Vec<ScanSummary>,
+    pub active_jobs: ActiveJobs,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Admin Dashboard Use Case
+pub struct AdminDashboardUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    job_store: Arc<JobStore>,
+    metadata_cache: Option<Arc<dyn CacheRepository>>,
+    image_cache: Option<Arc<ImageCache>>,
+    transcode_cache: Option<Arc<TranscodeCache>>,
+    event_store: Option<Arc<EventStore>>,
+    tmdb_client: Option<Arc<TmdbClient>>,
+}
+
+impl AdminDashboardUseCase {
+    pub fn new(media_repository: Arc<dyn MediaRepository>, job_store: Arc<JobStore>) -> Self {
+        Self {
+            media_repository,
+            job_store,
+            metadata_cache: None,
+            image_cache: None,
+            transcode_cache: None,
+            event_store: None,
+            tmdb_client: None,
+        }
+    }
+
+    /// Reports the TMDB/provider response cache
+    pub fn with_metadata_cache(mut self, cache: Arc<dyn CacheRepository>) -> Self {
+        self.metadata_cache = Some(cache);
+        self
+    }
+
+    /// Reports the artwork cache
+    pub fn with_image_cache(mut self, cache: Arc<ImageCache>) -> Self {
+        self.image_cache = Some(cache);
+        self
+    }
+
+    /// Reports the transcoded segment cache
+    pub fn with_transcode_cache(mut self, cache: Arc<TranscodeCache>) -> Self {
+        self.transcode_cache = Some(cache);
+        self
+    }
+
+    /// Lists recent scans from the recorded events
+    pub fn with_event_store(mut self, event_store: Arc<EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Reports the TMDB client's request counters
+    pub fn with_tmdb_client(mut self, client: Arc<TmdbClient>) -> Self {
+        self.tmdb_client = Some(client);
+        self
+    }
+
+    /// Builds the report
+    pub async fn execute(&self) -> Result<AdminDashboard, ApplicationError> {
+        let media = self.media_repository.find_all().await?;
+
+        // Stats every file, keep it off the async workers
+        let storage = {
+            let media = media.clone();
+            tokio::task::spawn_blocking(move || library_storage(&media))
+                .await
+                .map_err(|e| ApplicationError::Internal(e.to_string()))?
+        };
+
+        Ok(AdminDashboard {
+            library: library_counts(&media),
+            storage,
+            confidence: confidence_distribution(&media),
+            caches: self.caches().await,
+            tmdb: self.tmdb_client.as_ref().map(|client| client.request_stats()),
+            recent_scans: self.recent_scans().await,
+            active_jobs: ActiveJobs {
+                jobs: self.job_store.active_jobs().await,
+                batches: self.job_store.active_batch_jobs().await,
+            },
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// Cache usage; a cache whose statistics fail is left out
+    async fn caches(&self) -> Vec<CacheUsage> {
+        let mut caches = Vec::new();
+        if let Some(cache) = &self.metadata_cache {
+            match cache.get_stats().await {
+                Ok(stats) => {
+                    let lookups = stats.hits + stats.misses;
+                    caches.push(CacheUsage {
+                        cache: "metadata",
+                        entries: Some(stats.total_entries.max(0) as u64),
+                        size_bytes: Some(stats.total_size_bytes.max(0) as u64),
+                        lookups: HitStats {
+                            hits: stats.hits,
+                            misses: stats.misses,
+                            hit_rate: (lookups > 0).then_some(stats.hit_rate as f64),
+                        },
+                    });
+                }
+                Err(e) => warn!("Failed to read metadata cache statistics: {}", e),
+            }
+        }
+        if let Some(cache) = &self.image_cache {
+            caches.push(CacheUsage { cache: "images", entries: None, size_bytes: None, lookups: cache.hit_stats() });
+        }
+        if let Some(cache) = &self.transcode_cache {
+            caches.push(CacheUsage {
+                cache: "transcode",
+                entries: Some(cache.len() as u64),
+                size_bytes: Some(cache.size()),
+                lookups: cache.hit_stats(),
+            });
+        }
+        caches
+    }
+
+    /// Latest finished scans; empty when they cannot be read
+    async fn recent_scans(&self) -> Vec<ScanSummary> {
+        let Some(event_store) = &self.event_store else {
+            return Vec::new();
+        };
+        match event_store.latest(&SCAN_EVENT_TYPES, RECENT_SCANS).await {
+            Ok(events) => events
+                .into_iter()
+                .map(|event| {
+                    let payload: serde_json::Value = serde_json::from_str(&event.payload).unwrap_or_default();
+                    scan_summary(event.event_type, &payload, event.created_at)
+                })
+                .collect(),
+            Err(e) => {
+                warn!("Failed to load recent scans: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn library_counts(media: &[Media]) -> LibraryCounts {
+    let mut counts = LibraryCounts { total: media.len(), ..Default::default() };
+    let mut series = std::collections::HashSet::new();
+    for m in media {
+        *counts.by_type.entry(m.media_type.as_str()).or_default() += 1;
+        let resolution = m.resolution.as_deref().filter(|r| !r.is_empty()).unwrap_or("unknown");
+        *counts.by_resolution.entry(resolution.to_string()).or_default() += 1;
+        *counts.by_codec.entry(codec(&m.file_path)).or_default() += 1;
+        series.extend(m.series_id);
+    }
+    counts.series = series.len();
+    counts
+}
+
+/// Video codec named in a file name
+fn codec(file_path: &str) -> &'static str {
+    let parsed = media_identifier::parse(file_path);
+    match parsed.quality.codec.as_deref().map(str::to_ascii_lowercase).as_deref() {
+        Some("h.265" | "hevc") => "hevc",
+        Some("h.264") => "h264",
+        Some("av1") => "av1",
+        Some("vp9") => "vp9",
+        Some("xvid" | "divx") => "mpeg4",
+        _ => "unknown",
+    }
+}
+
+/// Disk usage of movies and of series
+fn library_storage(media: &[Media]) -> Vec<LibraryStorage> {
+    let mut movies = LibraryStorage { library: "movies", files: 0, bytes: 0, missing_files: 0 };
+    let mut series = LibraryStorage { library: "series", files: 0, bytes: 0, missing_files: 0 };
+    for m in media {
+        let library = if m.is_episode() { &mut series } else { &mut movies };
+        library.files += 1;
+        match std::fs::metadata(Path::new(&m.file_path)) {
+            Ok(metadata) => library.bytes += metadata.len(),
+            Err(_) => library.missing_files += 1,
+        }
+    }
+    vec![movies, series]
+}
+
+fn confidence_distribution(media: &[Media]) -> ConfidenceDistribution {
+    let mut distribution = ConfidenceDistribution::default();
+    for band in ["high", "medium", "low", "very_low"] {
+        distribution.bands.insert(band, 0);
+    }
+    for m in media {
+        *distribution.by_status.entry(m.verification_status.as_str()).or_default() += 1;
+        if m.tmdb_id.is_none() || m.verification_status == VerificationStatus::Failed {
+            distribution.unidentified += 1;
+            continue;
+        }
+        let score = m.confidence_score;
+        let band = if score.is_high() {
+            "high"
+        } else if score.is_medium() {
+            "medium"
+        } else if score.is_low() {
+            "low"
+        } else {
+            "very_low"
+        };
+        *distribution.bands.entry(band).or_default() += 1;
+        distribution.histogram[((score.value() * 10.0) as usize).min(9)] += 1;
+    }
+    distribution
+}
+
+fn scan_summary(event: String, payload: &serde_json::Value, recorded_at: DateTime<Utc>) -> ScanSummary {
+    let count = |field: &str| payload.get(field).and_then(|v| v.as_u64());
+    ScanSummary {
+        event,
+        scan_path: payload.get("scan_path").and_then(|v| v.as_str()).map(str::to_string),
+        processed: count("processed_count"),
+        identified: count("identified_count"),
+        failed: count("failed_count"),
+        duration_secs: count("duration_secs"),
+        error: payload.get("error_message").and_then(|v| v.as_str()).map(str::to_string),
+        finished_at: payload
+            .get("timestamp")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(recorded_at),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ConfidenceScore, MediaType};
+
+    fn media(path: &str, media_type: MediaType, tmdb_id: Option<i64>, confidence: f32) -> Media {
+        let mut media = Media::new(path.to_string(), media_type, "Title".to_string()).unwrap();
+        media.tmdb_id = tmdb_id;
+        media.confidence_score = ConfidenceScore::new(confidence).unwrap();
+        media
+    }
+
+    #[test]
+    fn test_counts_and_confidence() {
+        let mut episode = media("/tv/Show.S01E01.1080p.WEB.x265.mkv", MediaType::Episode, Some(2), 0.72);
+        episode.series_id = Some(7);
+        episode.resolution = Some("1080p".to_string());
+        let library = vec![
+            media("/movies/Movie.2020.2160p.HEVC.mkv", MediaType::Movie, Some(1), 0.95),
+            episode,
+            media("/movies/Unknown.avi", MediaType::Unknown, None, 0.0),
+        ];
+
+        let counts = library_counts(&library);
+        assert_eq!((counts.total, counts.series), (3, 1));
+        assert_eq!(counts.by_type["movie"], 1);
+        assert_eq!(counts.by_resolution["1080p"], 1);
+        assert_eq!(counts.by_resolution["unknown"], 2);
+        assert_eq!(counts.by_codec["hevc"], 2);
+        assert_eq!(counts.by_codec["unknown"], 1);
+
+        let confidence = confidence_distribution(&library);
+        assert_eq!(confidence.unidentified, 1);
+        assert_eq!((confidence.bands["high"], confidence.bands["medium"], confidence.bands["low"]), (1, 1, 0));
+        assert_eq!((confidence.histogram[9], confidence.histogram[7]), (1, 1));
+
+        // Files that are not on disk are counted as missing
+        let storage = library_storage(&library);
+        assert_eq!((storage[0].library, storage[0].files, storage[0].missing_files), ("movies", 2, 2));
+        assert_eq!((storage[1].files, storage[1].bytes), (1, 0));
+    }
+
+    #[test]
+    fn test_scan_summary() {
+        let event = crate::domain::events::ScanCompletedEvent::new(120, 110, 10, 95, "/media".to_string());
+        let payload = serde_json::to_value(&event).unwrap();
+        let summary = scan_summary("scan_completed".to_string(), &payload, Utc::now());
+        assert_eq!(summary.scan_path.as_deref(), Some("/media"));
+        assert_eq!((summary.processed, summary.identified, summary.failed), (Some(120), Some(110), Some(10)));
+        assert_eq!(summary.finished_at, event.timestamp);
+    }
+}
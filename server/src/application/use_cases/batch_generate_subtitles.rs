@@ -4,13 +4,14 @@
 //! - Entire series (all seasons, all episodes)
 //! - Single season (all episodes)
 //!
-//! Processes sequentially to avoid GPU conflicts.
+//! Processes sequentially to avoid GPU conflicts. Batches interrupted by a
+//! restart continue with the first episode not yet processed.
 
 use std::sync::Arc;
 use tracing::{info, debug, error, warn};
 
 use crate::domain::repositories::MediaRepository;
-use crate::infrastructure::jobs::JobStore;
+use crate::infrastructure::jobs::{JobStore, JobType};
 use crate::interfaces::external_services::VideoAnalyzer;
 use crate::shared::error::ApplicationError;
 
@@ -27,7 +28,7 @@ pub enum BatchTargetType {
 }
 
 /// Request for batch subtitle generation
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BatchGenerateRequest {
     /// Target type (series or season)
     pub target_type: BatchTargetType,
@@ -49,6 +50,14 @@ pub struct BatchGenerateRequest {
     pub target_language: Option<String>,
 }
 
+/// What a batch job is resumed from
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BatchPayload {
+    request: BatchGenerateRequest,
+    /// Episodes in processing order, as found when the batch started
+    episodes: Vec<i64>,
+}
+
 /// Individual episode result in batch
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct BatchItemResult {
@@ -154,14 +163,40 @@ impl BatchGenerateSubtitlesUseCase {
         );
 
         // Create batch job
-        let batch_job_id = self.job_store.create_batch_job(episodes.len()).await;
+        let payload = BatchPayload { request, episodes };
+        let batch_job_id = self.job_store
+            .create_resumable_batch_job(JobType::SubtitleBatch, payload.episodes.len(), &payload)
+            .await;
+        self.spawn_batch(batch_job_id.clone(), payload, 0, 0);
 
-        // Spawn background processing
+        Ok(batch_job_id)
+    }
+
+    /// Continues a batch a restart interrupted, from the payload it was
+    /// created with
+    pub async fn resume(&self, batch_job_id: &str, payload: serde_json::Value) -> Result<(), ApplicationError> {
+        let payload: BatchPayload = serde_json::from_value(payload)
+            .map_err(|e| ApplicationError::Internal(format!("Invalid batch payload: {}", e)))?;
+        let batch = self.job_store.get_batch_job(batch_job_id).await.ok_or_else(|| {
+            ApplicationError::Internal(format!("Batch job {} not found", batch_job_id))
+        })?;
+
+        info!(
+            "Resuming batch subtitle generation {} at episode {}/{}",
+            batch_job_id,
+            batch.processed() + 1,
+            payload.episodes.len()
+        );
+        self.spawn_batch(batch_job_id.to_string(), payload, batch.processed(), batch.completed);
+        Ok(())
+    }
+
+    /// Spawns background processing, skipping the `processed` episodes
+    fn spawn_batch(&self, job_id: String, payload: BatchPayload, processed: usize, completed: usize) {
         let use_case = self.generate_subtitle_use_case.clone();
         let job_store = self.job_store.clone();
         let media_repository = self.media_repository.clone();
         let video_analyzer = self.video_analyzer.clone();
-        let job_id = batch_job_id.clone();
 
         tokio::spawn(async move {
             Self::process_batch(
@@ -170,12 +205,11 @@ impl BatchGenerateSubtitlesUseCase {
                 media_repository,
                 video_analyzer,
                 &job_id,
-                episodes,
-                request,
+                payload,
+                processed,
+                completed,
             ).await;
         });
-
-        Ok(batch_job_id)
     }
 
     /// Finds the best matching audio track index for the preferred language
@@ -248,19 +282,21 @@ impl BatchGenerateSubtitlesUseCase {
     }
 
     /// Processes batch sequentially (runs in background)
+    #[allow(clippy::too_many_arguments)]
     async fn process_batch(
         use_case: Arc<GenerateSubtitleUseCase>,
         job_store: Arc<JobStore>,
         media_repository: Arc<dyn MediaRepository>,
         video_analyzer: Arc<dyn VideoAnalyzer>,
         batch_job_id: &str,
-        episodes: Vec<i64>,
-        request: BatchGenerateRequest,
+        payload: BatchPayload,
+        processed: usize,
+        mut completed: usize,
     ) {
+        let BatchPayload { request, episodes } = payload;
         let total = episodes.len();
-        let mut completed = 0;
 
-        for (index, media_id) in episodes.iter().enumerate() {
+        for (index, media_id) in episodes.iter().enumerate().skip(processed) {
             // Check if batch was cancelled before processing next episode
             if job_store.is_batch_cancelled(batch_job_id).await {
                 info!(
@@ -309,13 +345,14 @@ impl BatchGenerateSubtitlesUseCase {
             );
 
             // Create individual job for this episode
-            let item_job_id = job_store.create_job().await;
+            let item_job_id = job_store.create_job(JobType::Subtitle).await;
 
             let req = GenerateSubtitleRequest {
                 media_id: *media_id,
                 audio_track_index,
                 source_language: request.source_language.clone(),
                 target_language: request.target_language.clone(),
+                skip_silence: None,
             };
 
             match use_case.execute(req, &item_job_id).await {
@@ -471,13 +508,14 @@ impl BatchGenerateSubtitlesUseCase {
             ).await;
 
             // Create job for tracking
-            let job_id = self.job_store.create_job().await;
+            let job_id = self.job_store.create_job(JobType::Subtitle).await;
 
             let req = GenerateSubtitleRequest {
                 media_id,
                 audio_track_index,
                 source_language: request.source_language.clone(),
                 target_language: request.target_language.clone(),
+                skip_silence: None,
             };
 
             match self.generate_subtitle_use_case.execute(req, &job_id).await {
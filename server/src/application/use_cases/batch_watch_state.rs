@@ -0,0 +1,131 @@
+//! Batch Watch State Use Case
+//!
+//! Marks a whole season, series or collection watched/unwatched in a single
+//! transaction.
+
+use std::sync::Arc;
+use serde::Serialize;
+use crate::domain::repositories::{CollectionRepository, MediaRepository};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Group of media whose watch state is changed together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchStateScope {
+    /// All episodes of one season
+    Season { series_id: i64, season: i32 },
+    /// All episodes of a series
+    Series { series_id: i64 },
+    /// All library items of a collection (episodes of TV entries included)
+    Collection { collection_id: i64 },
+}
+
+impl WatchStateScope {
+    /// Scope name used in events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WatchStateScope::Season { .. } => "season",
+            WatchStateScope::Series { .. } => "series",
+            WatchStateScope::Collection { .. } => "collection",
+        }
+    }
+
+    /// Series or collection ID
+    pub fn id(&self) -> i64 {
+        match self {
+            WatchStateScope::Season { series_id, .. } | WatchStateScope::Series { series_id } => *series_id,
+            WatchStateScope::Collection { collection_id } => *collection_id,
+        }
+    }
+
+    /// Season number (season scope only)
+    pub fn season(&self) -> Option<i32> {
+        match self {
+            WatchStateScope::Season { season, .. } => Some(*season),
+            _ => None,
+        }
+    }
+}
+
+/// Result of a batch watch state change
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchStateBatchResult {
+    /// All media in the scope
+    pub media_ids: Vec<i64>,
+    /// Number of items whose state actually changed
+    pub changed: u64,
+    /// New watch state
+    pub is_watched: bool,
+}
+
+pub struct BatchWatchStateUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    collection_repository: Arc<dyn CollectionRepository>,
+}
+
+impl BatchWatchStateUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        collection_repository: Arc<dyn CollectionRepository>,
+    ) -> Self {
+        Self {
+            media_repository,
+            collection_repository,
+        }
+    }
+
+    /// Sets the watch state of every media item in `scope`
+    ///
+    /// Returns `DomainError::NotFound` when the scope contains no media.
+    pub async fn execute(&self, scope: WatchStateScope, watched: bool) -> Result<WatchStateBatchResult, ApplicationError> {
+        let media_ids = self.resolve(scope).await?;
+        if media_ids.is_empty() {
+            return Err(DomainError::NotFound(format!(
+                "No media found for {} {}",
+                scope.as_str(),
+                scope.id()
+            ))
+            .into());
+        }
+
+        let changed = self.media_repository.set_watched_many(&media_ids, watched).await?;
+
+        Ok(WatchStateBatchResult {
+            media_ids,
+            changed,
+            is_watched: watched,
+        })
+    }
+
+    async fn resolve(&self, scope: WatchStateScope) -> Result<Vec<i64>, ApplicationError> {
+        let media = match scope {
+            WatchStateScope::Season { series_id, season } => {
+                self.media_repository.find_by_season(series_id, season).await?
+            }
+            WatchStateScope::Series { series_id } => self.media_repository.find_by_series(series_id).await?,
+            WatchStateScope::Collection { collection_id } => {
+                if self.collection_repository.find_by_id(collection_id).await?.is_none() {
+                    return Err(DomainError::NotFound(format!("Collection {} not found", collection_id)).into());
+                }
+
+                // Movie items point at media, TV items at their series
+                let mut media = Vec::new();
+                for item in self.collection_repository.find_items(collection_id).await? {
+                    let Some(id) = item.media_id.filter(|_| item.is_available) else {
+                        continue;
+                    };
+                    if item.media_type == "movie" {
+                        media.extend(self.media_repository.find_by_id(id).await?);
+                    } else {
+                        media.extend(self.media_repository.find_by_series(id).await?);
+                    }
+                }
+                media
+            }
+        };
+
+        let mut ids: Vec<i64> = media.into_iter().filter_map(|m| m.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        Ok(ids)
+    }
+}
@@ -0,0 +1,309 @@
+//! Download Subtitle Use Case
+//!
+//! Fetches a subtitle for a media item from the subtitle provider
+//! (OpenSubtitles). Subtitles made for the exact file are searched first by
+//! its hash, then subtitles for the identified title. Languages come from
+//! the request, the user's active profile and preference or the configured
+//! defaults, in that order. When nothing is found, Whisper generation is the
+//! fallback.
+
+use std::path::Path;
+use std::sync::Arc;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::{MediaRepository, ProfileRepository, SeriesRepository, SubtitlePreferenceRepository};
+use crate::infrastructure::external::movie_hash;
+use crate::infrastructure::subtitle::{normalize_language_code, SubtitleStore};
+use crate::interfaces::external_services::{SubtitleCandidate, SubtitleProvider, SubtitleSearch};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Request for a subtitle download
+#[derive(Debug, Clone, Default)]
+pub struct DownloadSubtitleRequest {
+    pub media_id: i64,
+    /// Languages in order of preference (empty = the user's or the defaults)
+    pub languages: Vec<String>,
+    /// User whose language preference applies
+    pub user_id: Option<String>,
+}
+
+/// A downloaded subtitle
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadSubtitleResult {
+    /// Path to the stored SRT file
+    pub subtitle_path: String,
+    /// Language code of the subtitle
+    pub language: String,
+    /// Release name the subtitle was made for
+    pub release: Option<String>,
+    /// "hash" (made for this file) or "metadata" (made for the title)
+    pub matched_by: &'static str,
+    pub hearing_impaired: bool,
+    /// Whether the media directory was read-only and the subtitle went to
+    /// the data directory
+    pub in_data_dir: bool,
+}
+
+/// Outcome of a subtitle download
+#[derive(Debug, Clone)]
+pub enum DownloadSubtitleOutcome {
+    Downloaded(DownloadSubtitleResult),
+    /// Nothing could be downloaded; `language` is the most preferred one,
+    /// for generating a subtitle instead
+    Unavailable { language: String, reason: String },
+}
+
+/// Download Subtitle Use Case
+pub struct DownloadSubtitleUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    store: Arc<SubtitleStore>,
+    /// Subtitle provider (None = not configured, downloads are unavailable)
+    provider: Option<Arc<dyn SubtitleProvider>>,
+    /// Per-user languages (None = defaults only)
+    preferences: Option<Arc<dyn SubtitlePreferenceRepository>>,
+    /// Profiles whose subtitle language comes first (None = preferences only)
+    profiles: Option<Arc<dyn ProfileRepository>>,
+    /// Languages used when neither the request nor the user name any
+    default_languages: Vec<String>,
+}
+
+impl DownloadSubtitleUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        store: Arc<SubtitleStore>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            store,
+            provider: None,
+            preferences: None,
+            profiles: None,
+            default_languages: Vec::new(),
+        }
+    }
+
+    /// Sets the provider subtitles are downloaded from
+    pub fn with_provider(mut self, provider: Arc<dyn SubtitleProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Uses the subtitle languages of the requesting user
+    pub fn with_preferences(mut self, preferences: Arc<dyn SubtitlePreferenceRepository>) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    /// Puts the subtitle language of the requesting user's active profile
+    /// first
+    pub fn with_profiles(mut self, profiles: Arc<dyn ProfileRepository>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
+    /// Sets the languages downloaded by default
+    pub fn with_default_languages(mut self, languages: Vec<String>) -> Self {
+        self.default_languages = languages;
+        self
+    }
+
+    /// Languages to search for a request, in order of preference
+    pub async fn languages(&self, request: &DownloadSubtitleRequest) -> Result<Vec<String>, ApplicationError> {
+        let mut languages = request.languages.clone();
+        if languages.is_empty() {
+            if let (Some(preferences), Some(user_id)) = (&self.preferences, &request.user_id) {
+                languages = preferences.find(user_id).await?;
+            }
+            if let (Some(profiles), Some(user_id)) = (&self.profiles, &request.user_id) {
+                if let Some(language) = profiles.find_active(user_id).await?.and_then(|p| p.settings.subtitle_language) {
+                    languages.insert(0, language);
+                }
+            }
+        }
+        if languages.is_empty() {
+            languages = self.default_languages.clone();
+        }
+        if languages.is_empty() {
+            languages.push("en".to_string());
+        }
+
+        let mut normalized: Vec<String> = Vec::new();
+        for language in languages {
+            let language = normalize_language_code(&language).unwrap_or_else(|| language.to_lowercase());
+            if !normalized.contains(&language) {
+                normalized.push(language);
+            }
+        }
+        Ok(normalized)
+    }
+
+    /// Downloads the best subtitle in the most preferred language available
+    pub async fn execute(&self, request: DownloadSubtitleRequest) -> Result<DownloadSubtitleOutcome, ApplicationError> {
+        let media = self.media_repository
+            .find_by_id(request.media_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                DomainError::NotFound(format!("Media with ID {} not found", request.media_id))
+            ))?;
+        let languages = self.languages(&request).await?;
+        let unavailable = |reason: String| DownloadSubtitleOutcome::Unavailable {
+            language: languages[0].clone(),
+            reason,
+        };
+
+        let Some(provider) = &self.provider else {
+            return Ok(unavailable("No subtitle provider configured".to_string()));
+        };
+
+        let (candidate, matched_by) = match self.find_candidate(provider.as_ref(), &media, &languages).await {
+            Ok(Some(found)) => found,
+            Ok(None) => return Ok(unavailable(format!("No subtitles found in {}", languages.join(", ")))),
+            Err(e) => {
+                warn!("Subtitle search for media {} failed: {}", request.media_id, e);
+                return Ok(unavailable(e.to_string()));
+            }
+        };
+
+        debug!(
+            "Downloading subtitle {} ({}, {}) for media {}",
+            candidate.file_id, candidate.language, matched_by, request.media_id
+        );
+        let content = match provider.download(candidate.file_id).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Subtitle download for media {} failed: {}", request.media_id, e);
+                return Ok(unavailable(e.to_string()));
+            }
+        };
+
+        let language = normalize_language_code(&candidate.language)
+            .unwrap_or_else(|| candidate.language.to_lowercase());
+        let stored = self.store.write(request.media_id, Path::new(&media.file_path), &language, &content)?;
+        info!("Subtitle downloaded to: {}", stored.path.display());
+
+        Ok(DownloadSubtitleOutcome::Downloaded(DownloadSubtitleResult {
+            subtitle_path: stored.path.to_string_lossy().to_string(),
+            language,
+            release: candidate.release,
+            matched_by,
+            hearing_impaired: candidate.hearing_impaired,
+            in_data_dir: stored.in_data_dir,
+        }))
+    }
+
+    /// Searches by file hash, then by metadata
+    async fn find_candidate(
+        &self,
+        provider: &dyn SubtitleProvider,
+        media: &Media,
+        languages: &[String],
+    ) -> Result<Option<(SubtitleCandidate, &'static str)>, ApplicationError> {
+        let path = media.file_path.clone();
+        let hash = tokio::task::spawn_blocking(move || movie_hash(Path::new(&path)))
+            .await
+            .map_err(|e| ApplicationError::Internal(e.to_string()))?;
+        match hash {
+            Ok(hash) => {
+                let search = SubtitleSearch {
+                    languages: languages.to_vec(),
+                    moviehash: Some(hash),
+                    ..Default::default()
+                };
+                let candidates: Vec<SubtitleCandidate> = provider.search(&search).await?
+                    .into_iter()
+                    .filter(|c| c.hash_match)
+                    .collect();
+                if let Some(candidate) = best_candidate(candidates, languages) {
+                    return Ok(Some((candidate, "hash")));
+                }
+            }
+            Err(e) => debug!("Cannot hash {}: {}", media.file_path, e),
+        }
+
+        let search = self.metadata_search(media, languages).await?;
+        Ok(best_candidate(provider.search(&search).await?, languages).map(|c| (c, "metadata")))
+    }
+
+    /// Search by TMDB ID when identified, by title otherwise
+    async fn metadata_search(&self, media: &Media, languages: &[String]) -> Result<SubtitleSearch, ApplicationError> {
+        let mut search = SubtitleSearch {
+            languages: languages.to_vec(),
+            ..Default::default()
+        };
+
+        if media.is_episode() {
+            let series = match media.series_id {
+                Some(id) => self.series_repository.find_by_id(id).await?,
+                None => None,
+            };
+            search.parent_tmdb_id = series.as_ref().and_then(|s| s.tmdb_id);
+            if search.parent_tmdb_id.is_none() {
+                search.query = Some(series.map_or_else(|| media.title.clone(), |s| s.title));
+            }
+            search.season = media.season;
+            search.episode = media.episode;
+        } else if media.tmdb_id.is_some() {
+            search.tmdb_id = media.tmdb_id;
+        } else {
+            search.query = Some(media.title.clone());
+            search.year = media.release_date.as_deref()
+                .and_then(|d| d.get(..4))
+                .and_then(|y| y.parse().ok());
+        }
+        Ok(search)
+    }
+}
+
+/// Picks the subtitle to download
+///
+/// The first language with any subtitle wins; within it, subtitles made for
+/// the file beat the rest, regular ones beat hearing impaired ones, and
+/// more downloads beat fewer.
+fn best_candidate(candidates: Vec<SubtitleCandidate>, languages: &[String]) -> Option<SubtitleCandidate> {
+    languages.iter().find_map(|language| {
+        candidates.iter()
+            .filter(|c| normalize_language_code(&c.language).as_deref() == Some(language.as_str()))
+            .max_by_key(|c| (c.hash_match, !c.hearing_impaired, c.download_count))
+            .cloned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(file_id: i64, language: &str, downloads: i64, hash_match: bool, hearing_impaired: bool) -> SubtitleCandidate {
+        SubtitleCandidate {
+            file_id,
+            language: language.to_string(),
+            release: None,
+            download_count: downloads,
+            hash_match,
+            hearing_impaired,
+        }
+    }
+
+    #[test]
+    fn test_best_candidate() {
+        let candidates = vec![
+            candidate(1, "en", 9000, true, false),
+            candidate(2, "hu", 10, false, false),
+            candidate(3, "hu", 500, false, true),
+            candidate(4, "hu", 20, true, false),
+        ];
+        let languages = vec!["hu".to_string(), "en".to_string()];
+
+        // A Hungarian one made for the file wins despite fewer downloads
+        assert_eq!(best_candidate(candidates.clone(), &languages).unwrap().file_id, 4);
+        // Regular subtitles beat hearing impaired ones
+        let no_hash: Vec<SubtitleCandidate> = candidates.iter().filter(|c| c.file_id != 4).cloned().collect();
+        assert_eq!(best_candidate(no_hash, &languages).unwrap().file_id, 2);
+        assert_eq!(best_candidate(candidates.clone(), &["de".to_string(), "en".to_string()]).unwrap().file_id, 1);
+        assert!(best_candidate(candidates, &["de".to_string()]).is_none());
+    }
+}
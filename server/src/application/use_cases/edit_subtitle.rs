@@ -0,0 +1,257 @@
+//! Edit Subtitle Use Case
+//!
+//! Lets generated subtitles be reviewed and corrected: the cues of a
+//! Whisper/Ollama subtitle are served as JSON, single cues can be retimed
+//! or rewritten, and the result is saved back to the SRT file and can be
+//! exported as SRT or WebVTT.
+//!
+//! Cue indices are stable across edits: a cue cannot be moved past its
+//! neighbours, so an editor can keep addressing cues by position.
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::domain::repositories::{GeneratedSubtitle, GeneratedSubtitleRepository};
+use crate::infrastructure::subtitle::{read_subtitle_file, Cue, SanitizedSubtitle, SubtitleOptions, TagPolicy};
+use crate::interfaces::external_services::SubtitleFormat;
+use crate::shared::error::{ApplicationError, DomainError, FilesystemError};
+
+/// A cue of a subtitle being edited
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EditableCue {
+    /// Position of the cue (0-based)
+    pub index: usize,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Text lines separated by '\n'
+    pub text: String,
+}
+
+/// A generated subtitle with its cues
+#[derive(Debug, Clone, Serialize)]
+pub struct SubtitleCues {
+    #[serde(flatten)]
+    pub subtitle: GeneratedSubtitle,
+    pub cues: Vec<EditableCue>,
+}
+
+/// Changes to a cue (omitted fields are kept)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CueUpdate {
+    #[serde(default)]
+    pub start: Option<f64>,
+    #[serde(default)]
+    pub end: Option<f64>,
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+/// Edit Subtitle Use Case
+pub struct EditSubtitleUseCase {
+    generated_subtitles: Arc<dyn GeneratedSubtitleRepository>,
+    /// Serializes edits, so concurrent updates do not overwrite each other
+    write_lock: Mutex<()>,
+}
+
+impl EditSubtitleUseCase {
+    pub fn new(generated_subtitles: Arc<dyn GeneratedSubtitleRepository>) -> Self {
+        Self {
+            generated_subtitles,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Lists the generated subtitles of a media item
+    pub async fn list(&self, media_id: i64) -> Result<Vec<GeneratedSubtitle>, ApplicationError> {
+        Ok(self.generated_subtitles.find_by_media(media_id).await?)
+    }
+
+    /// Gets the cues of the subtitle generated in `language`
+    pub async fn cues(&self, media_id: i64, language: &str) -> Result<SubtitleCues, ApplicationError> {
+        let subtitle = self.find(media_id, language).await?;
+        let cues = read_cues(&subtitle)?;
+        Ok(SubtitleCues {
+            subtitle,
+            cues: cues.into_iter().enumerate().map(|(index, cue)| editable(index, cue)).collect(),
+        })
+    }
+
+    /// Changes the timing or text of cue `index` and saves the subtitle
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` for empty text, an end not after the start,
+    /// or a start that would move the cue past a neighbour
+    pub async fn update_cue(
+        &self,
+        media_id: i64,
+        language: &str,
+        index: usize,
+        update: CueUpdate,
+    ) -> Result<EditableCue, ApplicationError> {
+        let _guard = self.write_lock.lock().await;
+        let subtitle = self.find(media_id, language).await?;
+        let mut cues = read_cues(&subtitle)?;
+
+        let count = cues.len();
+        let cue = cues.get_mut(index).ok_or_else(|| {
+            ApplicationError::Domain(DomainError::NotFound(format!("Cue {} not found ({} cues)", index, count)))
+        })?;
+        if let Some(start) = update.start {
+            cue.start = start;
+        }
+        if let Some(end) = update.end {
+            cue.end = end;
+        }
+        if let Some(text) = update.text {
+            cue.text = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join("\n");
+        }
+        check_cue(&cues, index).map_err(|msg| ApplicationError::Domain(DomainError::InvalidInput(msg)))?;
+
+        let content = SanitizedSubtitle { cues, encoding: "UTF-8", issues: Vec::new() };
+        std::fs::write(&subtitle.subtitle_path, content.to_srt())
+            .map_err(|e| ApplicationError::Filesystem(FilesystemError::Io(e)))?;
+        info!("Edited cue {} of {}", index, subtitle.subtitle_path);
+
+        Ok(editable(index, content.cues[index].clone()))
+    }
+
+    /// Renders the subtitle generated in `language` as SRT or WebVTT
+    pub async fn export(&self, media_id: i64, language: &str, format: SubtitleFormat) -> Result<String, ApplicationError> {
+        let subtitle = self.find(media_id, language).await?;
+        let content = SanitizedSubtitle { cues: read_cues(&subtitle)?, encoding: "UTF-8", issues: Vec::new() };
+        Ok(match format {
+            SubtitleFormat::Srt => content.to_srt(),
+            SubtitleFormat::Vtt => content.to_vtt(0.0),
+        })
+    }
+
+    /// Latest subtitle generated for a media item in `language`
+    async fn find(&self, media_id: i64, language: &str) -> Result<GeneratedSubtitle, ApplicationError> {
+        self.generated_subtitles
+            .find_by_media(media_id)
+            .await?
+            .into_iter()
+            .find(|s| s.language.eq_ignore_ascii_case(language))
+            .ok_or_else(|| ApplicationError::Domain(DomainError::NotFound(format!(
+                "No {} subtitle was generated for media {}", language, media_id
+            ))))
+    }
+}
+
+/// Reads the cues of a generated subtitle, keeping formatting tags
+fn read_cues(subtitle: &GeneratedSubtitle) -> Result<Vec<Cue>, ApplicationError> {
+    let options = SubtitleOptions::new(TagPolicy::Keep).with_language(Some(subtitle.language.clone()));
+    Ok(read_subtitle_file(&subtitle.subtitle_path, &options)?.cues)
+}
+
+fn editable(index: usize, cue: Cue) -> EditableCue {
+    EditableCue { index, start: cue.start, end: cue.end, text: cue.text }
+}
+
+/// Checks cue `index` after an edit
+///
+/// Cues starting together are allowed; a cue ending after the next one
+/// starts is not, as it would be trimmed the next time the file is read.
+fn check_cue(cues: &[Cue], index: usize) -> Result<(), String> {
+    let cue = &cues[index];
+    if cue.text.is_empty() {
+        return Err("Cue text is empty".to_string());
+    }
+    if !cue.start.is_finite() || !cue.end.is_finite() || cue.start < 0.0 {
+        return Err("Cue times must be non-negative seconds".to_string());
+    }
+    if cue.end <= cue.start {
+        return Err(format!("Cue ends at {:.3}s, before its start at {:.3}s", cue.end, cue.start));
+    }
+    if let Some(previous) = index.checked_sub(1).map(|i| &cues[i]) {
+        if cue.start < previous.start {
+            return Err(format!("Cue would start before the previous cue ({:.3}s)", previous.start));
+        }
+    }
+    if let Some(next) = cues.get(index + 1) {
+        if cue.start > next.start {
+            return Err(format!("Cue would start after the next cue ({:.3}s)", next.start));
+        }
+        if cue.end > next.start && next.start > cue.start {
+            return Err(format!("Cue would overlap the next cue, which starts at {:.3}s", next.start));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::shared::error::RepositoryError;
+
+    struct Repository(Vec<GeneratedSubtitle>);
+
+    #[async_trait]
+    impl GeneratedSubtitleRepository for Repository {
+        async fn save(&self, _subtitle: &GeneratedSubtitle) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+
+        async fn find_all(&self) -> Result<Vec<GeneratedSubtitle>, RepositoryError> {
+            Ok(self.0.clone())
+        }
+
+        async fn find_by_media(&self, media_id: i64) -> Result<Vec<GeneratedSubtitle>, RepositoryError> {
+            Ok(self.0.iter().filter(|s| s.media_id == media_id).cloned().collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_cue() {
+        let path = std::env::temp_dir().join(format!("homeflix-edit-{}.hu.srt", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "1\n00:00:01,000 --> 00:00:02,000\nSzia\n\n2\n00:00:03,000 --> 00:00:04,000\n<i>Hogy vagy?</i>\n\n3\n00:00:06,000 --> 00:00:07,000\nJól\n",
+        )
+        .unwrap();
+        let use_case = EditSubtitleUseCase::new(Arc::new(Repository(vec![GeneratedSubtitle {
+            media_id: 3,
+            audio_track_index: Some(0),
+            audio_fingerprint: String::new(),
+            source_language: Some("en".to_string()),
+            language: "hu".to_string(),
+            subtitle_path: path.to_string_lossy().into_owned(),
+            duration_seconds: 10.0,
+            was_translated: true,
+        }])));
+
+        let subtitle = use_case.cues(3, "hu").await.unwrap();
+        assert_eq!(subtitle.cues.len(), 3);
+        assert_eq!(subtitle.cues[1].text, "<i>Hogy vagy?</i>");
+
+        let update = CueUpdate { start: Some(2.5), text: Some("Hogy vagy? \n".to_string()), ..CueUpdate::default() };
+        let cue = use_case.update_cue(3, "hu", 1, update).await.unwrap();
+        assert_eq!(cue, EditableCue { index: 1, start: 2.5, end: 4.0, text: "Hogy vagy?".to_string() });
+        let srt = use_case.export(3, "hu", SubtitleFormat::Srt).await.unwrap();
+        assert!(srt.contains("2\n00:00:02,500 --> 00:00:04,000\nHogy vagy?\n"));
+        assert!(use_case.export(3, "hu", SubtitleFormat::Vtt).await.unwrap().starts_with("WEBVTT"));
+
+        // Invalid edits leave the file alone
+        for update in [
+            CueUpdate { end: Some(2.0), ..CueUpdate::default() },
+            CueUpdate { start: Some(0.5), ..CueUpdate::default() },
+            CueUpdate { end: Some(6.5), ..CueUpdate::default() },
+            CueUpdate { text: Some(" ".to_string()), ..CueUpdate::default() },
+        ] {
+            let result = use_case.update_cue(3, "hu", 1, update).await;
+            assert!(matches!(result, Err(ApplicationError::Domain(DomainError::InvalidInput(_)))));
+        }
+        assert_eq!(use_case.export(3, "hu", SubtitleFormat::Srt).await.unwrap(), srt);
+
+        let missing = use_case.update_cue(3, "hu", 7, CueUpdate::default()).await;
+        assert!(matches!(missing, Err(ApplicationError::Domain(DomainError::NotFound(_)))));
+        assert!(use_case.cues(3, "de").await.is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
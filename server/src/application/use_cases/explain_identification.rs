@@ -0,0 +1,104 @@
+//! Explain Identification Use Case
+//!
+//! Re-parses a media item's file and reports how confident the parser was
+//! of each field, so a questionable match can be traced back to the part of
+//! the filename that caused it.
+
+use std::sync::Arc;
+use serde::Serialize;
+
+use crate::domain::repositories::MediaRepository;
+use crate::domain::services::{ConfidenceLevel, ConfidenceService, IdentificationService};
+use crate::domain::value_objects::IdentificationResult;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Parse breakdown of a media item
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentificationExplanation {
+    pub media_id: i64,
+    pub file_path: String,
+    /// What the parser extracted from the path
+    pub parsed: IdentificationResult,
+    /// Confidence of the parse alone (before TMDB matching)
+    pub confidence: f32,
+    pub level: ConfidenceLevel,
+    /// Stored confidence of the current match
+    pub stored_confidence: f32,
+    pub stored_strategy: Option<String>,
+    /// Parsed fields the parser was unsure about
+    pub uncertain_fields: Vec<&'static str>,
+}
+
+/// Explain Identification Use Case
+pub struct ExplainIdentificationUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    identification_service: Arc<dyn IdentificationService>,
+    confidence_service: Arc<dyn ConfidenceService>,
+}
+
+impl ExplainIdentificationUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        identification_service: Arc<dyn IdentificationService>,
+        confidence_service: Arc<dyn ConfidenceService>,
+    ) -> Self {
+        Self {
+            media_repository,
+            identification_service,
+            confidence_service,
+        }
+    }
+
+    /// Explains how the file of a media item parses
+    pub async fn execute(&self, media_id: i64) -> Result<IdentificationExplanation, ApplicationError> {
+        let media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Media {} not found", media_id)))?;
+
+        let parsed = self.identification_service
+            .identify_content(&media.file_path, media.duration_seconds.map(|d| d.max(0) as u64))
+            .await?;
+        let confidence = self.confidence_service.calculate_confidence(&parsed).await.value();
+        let uncertain_fields = parsed.field_confidence
+            .map(|f| f.uncertain_fields(parsed.media_type.is_episode()))
+            .unwrap_or_default();
+
+        Ok(IdentificationExplanation {
+            media_id,
+            file_path: media.file_path,
+            parsed,
+            confidence,
+            level: ConfidenceLevel::from_score(confidence),
+            stored_confidence: media.confidence_score.value(),
+            stored_strategy: media.identification_strategy,
+            uncertain_fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::services::{DefaultIdentificationService, IdentificationService};
+
+    #[tokio::test]
+    async fn test_uncertain_fields() {
+        let service = DefaultIdentificationService::new();
+
+        let clear = service
+            .identify_content("/media/Movies/The.Matrix.1999.1080p.BluRay.x264.mkv", None)
+            .await
+            .unwrap();
+        let fields = clear.field_confidence.unwrap();
+        assert!(fields.title >= 0.75 && fields.year >= 0.75);
+        assert!(fields.uncertain_fields(false).is_empty());
+
+        // "1917" is both the title and a year
+        let ambiguous = service
+            .identify_content("/media/Movies/1917.2019.1080p.mkv", None)
+            .await
+            .unwrap();
+        let uncertain = ambiguous.field_confidence.unwrap().uncertain_fields(false);
+        assert!(uncertain.contains(&"year") || uncertain.contains(&"title"), "{:?}", ambiguous.field_confidence);
+    }
+}
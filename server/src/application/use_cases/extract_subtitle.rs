@@ -0,0 +1,441 @@
+//! Extract Subtitle Use Case
+//!
+//! Copies embedded text subtitle tracks (SubRip, ASS, mov_text, WebVTT) out
+//! of the container into standalone SRT or VTT files, for clients that only
+//! load external subtitles. Extraction runs on demand for a media item or in
+//! the background for a whole series or season (continued after a restart).
+//! Blu-ray PGS tracks are read
+//! with OCR when a text recognizer is configured; other bitmap tracks
+//! (VobSub, DVB) are skipped.
+//!
+//! Single tracks can also be converted for the subtitle endpoint, which
+//! serves embedded tracks from a cache outside the media directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info};
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::MediaRepository;
+use crate::infrastructure::jobs::{JobStore, JobType};
+use crate::infrastructure::subtitle::{decode_sup, normalize_language_code, Cue, SanitizedSubtitle, StoredSubtitle, SubtitleStore};
+use crate::interfaces::external_services::{SubtitleExtractor, SubtitleFormat, SubtitleTrack, TextRecognizer, VideoAnalyzer};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Subtitle images recognized at the same time
+const OCR_CONCURRENCY: usize = 4;
+
+/// Request for extracting the subtitles of a media item
+#[derive(Debug, Clone, Default)]
+pub struct ExtractSubtitleRequest {
+    pub media_id: i64,
+    /// Subtitle track to extract (None = every text track)
+    pub track_index: Option<usize>,
+    pub format: SubtitleFormat,
+    /// Extract again even if the file exists from an earlier extraction
+    pub overwrite: bool,
+}
+
+/// What an extraction batch is resumed from
+#[derive(Serialize, Deserialize)]
+struct ExtractionBatchPayload {
+    /// Episodes in processing order, as found when the batch started
+    episodes: Vec<i64>,
+    format: SubtitleFormat,
+}
+
+/// An extracted subtitle track
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractedSubtitle {
+    /// Subtitle track index (as listed for the media)
+    pub track_index: usize,
+    /// Language code of the track
+    pub language: Option<String>,
+    /// Codec of the embedded track
+    pub codec: Option<String>,
+    /// Path to the standalone file
+    pub subtitle_path: String,
+    /// Whether the media directory was read-only and the file went to the
+    /// data directory
+    pub in_data_dir: bool,
+    /// Whether the file was already there from an earlier extraction
+    pub existing: bool,
+    /// Whether the text was read from bitmaps with OCR
+    pub ocr: bool,
+}
+
+/// Result of extracting the subtitles of a media item
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractSubtitleResult {
+    pub media_id: i64,
+    pub subtitles: Vec<ExtractedSubtitle>,
+    /// Bitmap tracks that cannot be read as text
+    pub skipped_tracks: Vec<usize>,
+}
+
+/// Extract Subtitle Use Case
+pub struct ExtractSubtitleUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    extractor: Arc<dyn SubtitleExtractor>,
+    store: Arc<SubtitleStore>,
+    /// Job store for batch progress
+    job_store: Arc<JobStore>,
+    /// OCR for PGS tracks (None = bitmap tracks are skipped)
+    text_recognizer: Option<Arc<dyn TextRecognizer>>,
+}
+
+impl ExtractSubtitleUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        video_analyzer: Arc<dyn VideoAnalyzer>,
+        extractor: Arc<dyn SubtitleExtractor>,
+        store: Arc<SubtitleStore>,
+        job_store: Arc<JobStore>,
+    ) -> Self {
+        Self {
+            media_repository,
+            video_analyzer,
+            extractor,
+            store,
+            job_store,
+            text_recognizer: None,
+        }
+    }
+
+    /// Reads PGS bitmap tracks with OCR
+    pub fn with_text_recognizer(mut self, recognizer: Arc<dyn TextRecognizer>) -> Self {
+        self.text_recognizer = Some(recognizer);
+        self
+    }
+
+    /// Extracts one or every text subtitle track of a media item
+    pub async fn execute(&self, request: ExtractSubtitleRequest) -> Result<ExtractSubtitleResult, ApplicationError> {
+        let media = self.find_media(request.media_id).await?;
+        let tracks = self.video_analyzer.get_subtitle_tracks(&media.file_path).await?;
+
+        let selected: Vec<&SubtitleTrack> = match request.track_index {
+            Some(index) => {
+                let track = tracks.iter().find(|t| t.index == index).ok_or_else(|| {
+                    ApplicationError::Domain(DomainError::NotFound(format!(
+                        "Subtitle track {} not found ({} embedded tracks)", index, tracks.len()
+                    )))
+                })?;
+                self.check_readable(track)?;
+                vec![track]
+            }
+            None => tracks.iter().collect(),
+        };
+
+        let labels = track_labels(&tracks, self.text_recognizer.is_some());
+        let mut result = ExtractSubtitleResult {
+            media_id: request.media_id,
+            subtitles: Vec::new(),
+            skipped_tracks: Vec::new(),
+        };
+        for track in selected {
+            let Some(label) = labels.iter().find(|(index, _)| *index == track.index).map(|(_, l)| l) else {
+                result.skipped_tracks.push(track.index);
+                continue;
+            };
+            let subtitle = self.extract_track(&media, track, label, request.format, request.overwrite).await?;
+            result.subtitles.push(subtitle);
+        }
+
+        info!(
+            "Extracted {} subtitle tracks of media {} ({} bitmap tracks skipped)",
+            result.subtitles.len(), request.media_id, result.skipped_tracks.len()
+        );
+        Ok(result)
+    }
+
+    /// Extracts the first embedded readable track in a language as SRT
+    ///
+    /// Returns None when the media has no such track, so the caller can
+    /// generate a subtitle instead.
+    pub async fn extract_language(&self, media_id: i64, language: &str) -> Result<Option<ExtractedSubtitle>, ApplicationError> {
+        let media = self.find_media(media_id).await?;
+        let tracks = self.video_analyzer.get_subtitle_tracks(&media.file_path).await?;
+        let wanted = normalize_language_code(language);
+
+        let labels = track_labels(&tracks, self.text_recognizer.is_some());
+        for (index, label) in &labels {
+            let Some(track) = tracks.iter().find(|t| t.index == *index) else {
+                continue;
+            };
+            if track.language.as_deref().and_then(normalize_language_code) == wanted {
+                return Ok(Some(self.extract_track(&media, track, label, SubtitleFormat::Srt, false).await?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// SRT file of an embedded track, converted on first use
+    ///
+    /// The conversion is cached in the data directory, outside the places
+    /// external subtitles are searched, so the track list stays the same.
+    pub async fn embedded_subtitle(&self, media_id: i64, track_index: usize) -> Result<PathBuf, ApplicationError> {
+        let path = self.store.embedded_cache(media_id, track_index);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let media = self.find_media(media_id).await?;
+        let tracks = self.video_analyzer.get_subtitle_tracks(&media.file_path).await?;
+        let track = tracks.iter().find(|t| t.index == track_index).ok_or_else(|| {
+            ApplicationError::Domain(DomainError::NotFound(format!(
+                "Subtitle track {} not found ({} embedded tracks)", track_index, tracks.len()
+            )))
+        })?;
+        self.check_readable(track)?;
+
+        let content = self.convert_track(&media, track, SubtitleFormat::Srt).await?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| ApplicationError::Internal(e.to_string()))?;
+        }
+        std::fs::write(&path, content).map_err(|e| ApplicationError::Internal(e.to_string()))?;
+        info!("Converted embedded subtitle track {} of media {}", track_index, media_id);
+        Ok(path)
+    }
+
+    /// Starts extraction for the episodes of a series or season (returns the
+    /// batch job ID)
+    pub async fn start_batch(
+        self: &Arc<Self>,
+        series_id: i64,
+        season: Option<i32>,
+        format: SubtitleFormat,
+    ) -> Result<String, ApplicationError> {
+        let mut episodes = match season {
+            Some(season) => self.media_repository.find_by_season(series_id, season).await?,
+            None => self.media_repository.find_by_series(series_id).await?,
+        };
+        if episodes.is_empty() {
+            return Err(ApplicationError::Domain(DomainError::NotFound(
+                "No episodes found for the specified target".to_string()
+            )));
+        }
+        episodes.sort_by_key(|m| (m.season.unwrap_or(0), m.episode.unwrap_or(0)));
+        let episodes: Vec<i64> = episodes.iter().filter_map(|m| m.id).collect();
+
+        info!("Starting subtitle extraction for {} episodes of series {}", episodes.len(), series_id);
+        let payload = ExtractionBatchPayload { episodes, format };
+        let batch_job_id = self.job_store
+            .create_resumable_batch_job(JobType::ExtractionBatch, payload.episodes.len(), &payload)
+            .await;
+
+        let use_case = Arc::clone(self);
+        let job_id = batch_job_id.clone();
+        tokio::spawn(async move {
+            use_case.process_batch(&job_id, payload, 0, 0).await;
+        });
+
+        Ok(batch_job_id)
+    }
+
+    /// Continues an extraction batch a restart interrupted, from the payload
+    /// it was created with
+    pub async fn resume_batch(self: &Arc<Self>, batch_job_id: &str, payload: serde_json::Value) -> Result<(), ApplicationError> {
+        let payload: ExtractionBatchPayload = serde_json::from_value(payload)
+            .map_err(|e| ApplicationError::Internal(format!("Invalid extraction batch payload: {}", e)))?;
+        let batch = self.job_store.get_batch_job(batch_job_id).await.ok_or_else(|| {
+            ApplicationError::Internal(format!("Batch job {} not found", batch_job_id))
+        })?;
+
+        info!("Resuming subtitle extraction {} at episode {}/{}", batch_job_id, batch.processed() + 1, payload.episodes.len());
+        let use_case = Arc::clone(self);
+        let job_id = batch_job_id.to_string();
+        tokio::spawn(async move {
+            use_case.process_batch(&job_id, payload, batch.processed(), batch.completed).await;
+        });
+        Ok(())
+    }
+
+    /// Extracts episode by episode, skipping the `processed` ones (runs in
+    /// background)
+    async fn process_batch(&self, batch_job_id: &str, payload: ExtractionBatchPayload, processed: usize, mut completed: usize) {
+        let ExtractionBatchPayload { episodes, format } = payload;
+        let total = episodes.len();
+
+        for media_id in episodes.into_iter().skip(processed) {
+            if self.job_store.is_batch_cancelled(batch_job_id).await {
+                info!("Extraction batch {} cancelled after {}/{} episodes", batch_job_id, completed, total);
+                return;
+            }
+
+            let request = ExtractSubtitleRequest {
+                media_id,
+                format,
+                ..Default::default()
+            };
+            match self.execute(request).await {
+                Ok(_) => {
+                    completed += 1;
+                    self.job_store.update_batch_progress(batch_job_id, completed).await;
+                }
+                Err(e) => {
+                    error!("Subtitle extraction for media {} failed: {}", media_id, e);
+                    self.job_store.add_batch_error(batch_job_id, media_id, e.to_string()).await;
+                }
+            }
+        }
+
+        if !self.job_store.is_batch_cancelled(batch_job_id).await {
+            self.job_store.complete_batch_job(batch_job_id).await;
+            info!("Subtitle extraction complete: {}/{} episodes", completed, total);
+        }
+    }
+
+    async fn extract_track(
+        &self,
+        media: &Media,
+        track: &SubtitleTrack,
+        label: &str,
+        format: SubtitleFormat,
+        overwrite: bool,
+    ) -> Result<ExtractedSubtitle, ApplicationError> {
+        let media_id = media.id.unwrap_or_default();
+        let video_path = Path::new(&media.file_path);
+        let extracted = |stored: StoredSubtitle, existing| ExtractedSubtitle {
+            track_index: track.index,
+            language: track.language.clone(),
+            codec: track.codec.clone(),
+            subtitle_path: stored.path.to_string_lossy().to_string(),
+            in_data_dir: stored.in_data_dir,
+            existing,
+            ocr: !track.is_text(),
+        };
+
+        if !overwrite {
+            if let Some(stored) = self.store.existing(media_id, video_path, label, format.extension()) {
+                debug!("Subtitle track {} of media {} already extracted", track.index, media_id);
+                return Ok(extracted(stored, true));
+            }
+        }
+
+        let content = self.convert_track(media, track, format).await?;
+        let stored = self.store.write_as(media_id, video_path, label, format.extension(), &content)?;
+        debug!("Extracted subtitle track {} of media {} to {}", track.index, media_id, stored.path.display());
+        Ok(extracted(stored, false))
+    }
+
+    /// Fails for tracks that cannot be turned into text
+    fn check_readable(&self, track: &SubtitleTrack) -> Result<(), ApplicationError> {
+        if track.is_text() || (track.is_pgs() && self.text_recognizer.is_some()) {
+            return Ok(());
+        }
+        Err(ApplicationError::Domain(DomainError::InvalidInput(format!(
+            "Subtitle track {} is a bitmap track ({}) and cannot be read as text",
+            track.index, track.codec.as_deref().unwrap_or("unknown codec")
+        ))))
+    }
+
+    /// Converts a track to `format`, with OCR for PGS tracks
+    async fn convert_track(&self, media: &Media, track: &SubtitleTrack, format: SubtitleFormat) -> Result<Vec<u8>, ApplicationError> {
+        let recognizer = match &self.text_recognizer {
+            Some(recognizer) if track.is_pgs() => recognizer,
+            _ => return Ok(self.extractor.extract(&media.file_path, track.index, format).await?),
+        };
+
+        let sup = self.extractor.extract_bitmap(&media.file_path, track.index).await?;
+        let captions = decode_sup(&sup)?;
+        let language = track.language.as_deref().and_then(normalize_language_code);
+        debug!("Reading {} PGS captions of media {} track {}", captions.len(), media.file_path, track.index);
+
+        let cues: Vec<Cue> = stream::iter(captions)
+            .map(|caption| {
+                let language = language.clone();
+                async move {
+                    let text = recognizer.recognize(&caption.image, language.as_deref()).await?;
+                    Ok::<_, ApplicationError>(Cue { start: caption.start, end: caption.end, text })
+                }
+            })
+            .buffered(OCR_CONCURRENCY)
+            .try_filter(|cue| futures::future::ready(!cue.text.is_empty()))
+            .try_collect()
+            .await?;
+
+        let subtitle = SanitizedSubtitle { cues, encoding: "UTF-8", issues: Vec::new() };
+        Ok(match format {
+            SubtitleFormat::Srt => subtitle.to_srt(),
+            SubtitleFormat::Vtt => subtitle.to_vtt(0.0),
+        }
+        .into_bytes())
+    }
+
+    async fn find_media(&self, media_id: i64) -> Result<Media, ApplicationError> {
+        self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                DomainError::NotFound(format!("Media with ID {} not found", media_id))
+            ))
+    }
+}
+
+/// File labels of the readable tracks (text, and PGS with `ocr`), by track
+/// index
+///
+/// The label is the language (`und` when untagged); a track sharing its
+/// language with another one gets its index appended (`en.2`), so every
+/// track has its own file and the language is still recognized. Forced
+/// and SDH tracks are marked like external ones (`en.2.forced`, `en.sdh`).
+fn track_labels(tracks: &[SubtitleTrack], ocr: bool) -> Vec<(usize, String)> {
+    let language = |track: &SubtitleTrack| {
+        track.language.as_deref()
+            .and_then(normalize_language_code)
+            .unwrap_or_else(|| "und".to_string())
+    };
+    let text: Vec<&SubtitleTrack> = tracks.iter().filter(|t| t.is_text() || (ocr && t.is_pgs())).collect();
+
+    text.iter()
+        .map(|track| {
+            let lang = language(track);
+            let shared = text.iter().filter(|t| language(t) == lang).count() > 1;
+            let mut label = if shared { format!("{}.{}", lang, track.index) } else { lang };
+            if track.is_forced {
+                label.push_str(".forced");
+            } else if track.is_hearing_impaired {
+                label.push_str(".sdh");
+            }
+            (track.index, label)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(index: usize, language: Option<&str>, codec: &str) -> SubtitleTrack {
+        SubtitleTrack {
+            index,
+            language: language.map(str::to_string),
+            codec: Some(codec.to_string()),
+            title: None,
+            is_default: false,
+            is_forced: false,
+            is_hearing_impaired: false,
+        }
+    }
+
+    #[test]
+    fn test_track_labels() {
+        let tracks = vec![
+            track(0, Some("eng"), "subrip"),
+            track(1, Some("hun"), "ass"),
+            SubtitleTrack { is_forced: true, ..track(2, Some("eng"), "subrip") },
+            track(3, Some("ger"), "hdmv_pgs_subtitle"),
+            track(4, None, "mov_text"),
+        ];
+        assert_eq!(track_labels(&tracks, false), vec![
+            (0, "en.0".to_string()),
+            (1, "hu".to_string()),
+            (2, "en.2.forced".to_string()),
+            (4, "und".to_string()),
+        ]);
+        assert_eq!(track_labels(&tracks, true)[3], (3, "de".to_string()));
+    }
+}
@@ -2,32 +2,39 @@
 //!
 //! Orchestrates automatic subtitle generation using:
 //! - Whisper.cpp for speech-to-text transcription
-//! - Ollama for LLM-based translation
+//! - A translation provider (Ollama, DeepL or LibreTranslate)
 //! - Audio fingerprinting for tracking and deduplication
+//! - A transcription cache, so further languages skip Whisper
+//!
+//! Existing external subtitles can also be translated on their own, which
+//! runs only the translation stage.
 
 use std::path::Path;
 use std::sync::Arc;
 use tracing::{info, debug};
+use encoding_rs::Encoding;
 
-use crate::domain::repositories::MediaRepository;
+use crate::domain::repositories::{GeneratedSubtitle, GeneratedSubtitleRepository, MediaRepository};
 use crate::domain::events::{
     SubtitleGenerationStartedEvent,
     SubtitleGenerationCompletedEvent,
     SubtitleGenerationFailedEvent,
 };
 use crate::infrastructure::external::{
-    WhisperAdapter, TranscriptionSegment, segments_to_srt,
-    OllamaClient, language_code_to_name,
+    WhisperAdapter, TranscriptionSegment, TranscriptionProgress, VadConfig, segments_to_srt,
+    SubtitleTranslator,
     FpcalcAdapter, AudioFingerprint,
 };
+use crate::infrastructure::cache::TranscriptionCache;
 use crate::infrastructure::gpu::GpuCoordinator;
-use crate::infrastructure::jobs::JobStore;
+use crate::infrastructure::jobs::{JobKind, JobStore};
+use crate::infrastructure::subtitle::{normalize_language_code, read_subtitle_file, SubtitleDetector, SubtitleOptions, SubtitleStore, TagPolicy};
 use crate::interfaces::messaging::EventBus;
 use crate::infrastructure::messaging::InMemoryEventBus;
-use crate::shared::error::ApplicationError;
+use crate::shared::error::{ApplicationError, DomainError};
 
 /// Request for subtitle generation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GenerateSubtitleRequest {
     /// Media ID to generate subtitles for
     pub media_id: i64,
@@ -37,6 +44,9 @@ pub struct GenerateSubtitleRequest {
     pub source_language: Option<String>,
     /// Target language code for translation (None = no translation)
     pub target_language: Option<String>,
+    /// Whether silence is cut out before transcribing (None = the use
+    /// case's setting)
+    pub skip_silence: Option<bool>,
 }
 
 /// Result of subtitle generation
@@ -54,14 +64,45 @@ pub struct GenerateSubtitleResult {
     pub duration_seconds: f64,
 }
 
+/// Request for translating an existing subtitle
+#[derive(Debug, Clone)]
+pub struct TranslateSubtitleRequest {
+    /// Media ID the subtitle belongs to
+    pub media_id: i64,
+    /// Index of the external subtitle (as listed for the media)
+    pub subtitle_index: usize,
+    /// Language of the subtitle (None = taken from its filename)
+    pub source_language: Option<String>,
+    /// Target language code
+    pub target_language: String,
+    /// Encoding of the subtitle file (None = detected)
+    pub encoding: Option<&'static Encoding>,
+}
+
+/// Result of translating an existing subtitle
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TranslateSubtitleResult {
+    /// Path to the translated SRT file
+    pub subtitle_path: String,
+    /// Path to the subtitle that was translated
+    pub source_path: String,
+    /// Language code of the source subtitle
+    pub source_language: String,
+    /// Language code of the translated subtitle
+    pub language: String,
+    /// Number of translated cues
+    pub segments: usize,
+}
+
 /// Generate Subtitle Use Case
 ///
 /// Orchestrates the complete subtitle generation workflow:
 /// 1. Validates media exists and file is accessible
 /// 2. Acquires GPU lock (prevents Whisper/Ollama conflict)
 /// 3. Optionally generates audio fingerprint for tracking
-/// 4. Extracts audio and runs Whisper transcription
-/// 5. Optionally translates with Ollama
+/// 4. Extracts audio and runs Whisper transcription (or reuses the cached
+///    transcription of the track)
+/// 5. Optionally translates the segments
 /// 6. Writes SRT file next to video
 ///
 /// # GPU Coordination
@@ -73,8 +114,8 @@ pub struct GenerateSubtitleUseCase<E: EventBus + ?Sized = InMemoryEventBus> {
     media_repository: Arc<dyn MediaRepository>,
     /// Whisper adapter for transcription
     whisper_adapter: Arc<WhisperAdapter>,
-    /// Ollama client for translation (optional)
-    ollama_client: Option<Arc<OllamaClient>>,
+    /// Translation provider (None if translation disabled)
+    translator: Option<Arc<dyn SubtitleTranslator>>,
     /// Fpcalc adapter for audio fingerprinting
     fpcalc_adapter: Arc<FpcalcAdapter>,
     /// GPU coordinator for exclusive access
@@ -83,6 +124,16 @@ pub struct GenerateSubtitleUseCase<E: EventBus + ?Sized = InMemoryEventBus> {
     job_store: Arc<JobStore>,
     /// Event bus for publishing events
     event_bus: Arc<E>,
+    /// Earlier transcriptions (None = always transcribe)
+    transcription_cache: Option<Arc<TranscriptionCache>>,
+    /// Record of written subtitle files (None = not recorded)
+    generated_subtitles: Option<Arc<dyn GeneratedSubtitleRepository>>,
+    /// Where downloaded subtitles of read-only media are (None = next to
+    /// the video only)
+    subtitle_store: Option<Arc<SubtitleStore>>,
+    /// Voice activity detection before transcribing (None = transcribe
+    /// everything)
+    vad: Option<VadConfig>,
 }
 
 // Type alias for backward compatibility
@@ -94,7 +145,7 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
     /// # Arguments
     /// * `media_repository` - Repository for media lookup
     /// * `whisper_adapter` - Whisper CLI adapter
-    /// * `ollama_client` - Ollama HTTP client (None if translation disabled)
+    /// * `translator` - Translation provider (None if translation disabled)
     /// * `fpcalc_adapter` - Chromaprint fpcalc adapter
     /// * `gpu_coordinator` - GPU semaphore for exclusive access
     /// * `job_store` - Job status store
@@ -102,7 +153,7 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
     pub fn new(
         media_repository: Arc<dyn MediaRepository>,
         whisper_adapter: Arc<WhisperAdapter>,
-        ollama_client: Option<Arc<OllamaClient>>,
+        translator: Option<Arc<dyn SubtitleTranslator>>,
         fpcalc_adapter: Arc<FpcalcAdapter>,
         gpu_coordinator: Arc<GpuCoordinator>,
         job_store: Arc<JobStore>,
@@ -111,14 +162,46 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
         Self {
             media_repository,
             whisper_adapter,
-            ollama_client,
+            translator,
             fpcalc_adapter,
             gpu_coordinator,
             job_store,
             event_bus,
+            transcription_cache: None,
+            generated_subtitles: None,
+            subtitle_store: None,
+            vad: Some(VadConfig::default()),
         }
     }
 
+    /// Sets the voice activity detection pre-pass (enabled by default)
+    ///
+    /// Skipping silence makes transcription of films with sparse dialogue
+    /// several times faster; requests can still turn it off.
+    pub fn with_vad(mut self, vad: Option<VadConfig>) -> Self {
+        self.vad = vad;
+        self
+    }
+
+    /// Reuses transcriptions across subtitle languages
+    pub fn with_transcription_cache(mut self, cache: Arc<TranscriptionCache>) -> Self {
+        self.transcription_cache = Some(cache);
+        self
+    }
+
+    /// Records written subtitle files, so they can be told apart from
+    /// subtitles that came with the media
+    pub fn with_generated_subtitles(mut self, repository: Arc<dyn GeneratedSubtitleRepository>) -> Self {
+        self.generated_subtitles = Some(repository);
+        self
+    }
+
+    /// Also translates subtitles kept in the data directory
+    pub fn with_subtitle_store(mut self, store: Arc<SubtitleStore>) -> Self {
+        self.subtitle_store = Some(store);
+        self
+    }
+
     /// Executes subtitle generation
     ///
     /// This is a long-running operation. Progress is tracked via the job store.
@@ -184,6 +267,12 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
         let _gpu_permit = self.gpu_coordinator.acquire().await;
         debug!("GPU lock acquired for subtitle generation");
 
+        // Processing starts now; the media length drives the completion estimate
+        let media_seconds = media.duration_seconds.filter(|d| *d > 0).map(f64::from);
+        if let Some(seconds) = media_seconds {
+            self.job_store.set_workload(job_id, JobKind::Subtitle, seconds).await;
+        }
+
         self.job_store.update_progress(job_id, 15.0, Some("Generating audio fingerprint...")).await;
 
         // 3. Generate audio fingerprint (for tracking)
@@ -202,54 +291,133 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
             fingerprint.duration
         );
 
-        // 4. Unload Ollama model before Whisper to free VRAM (important for 8GB systems)
-        if let Some(ollama) = &self.ollama_client {
-            self.job_store.update_progress(job_id, 20.0, Some("Unloading Ollama model from VRAM...")).await;
-            if let Err(e) = ollama.unload_model().await {
-                debug!("Failed to unload Ollama model (may not have been loaded): {}", e);
-            }
-        }
-
-        self.job_store.update_progress(job_id, 25.0, Some("Transcribing audio with Whisper...")).await;
-
-        // 5. Transcribe audio with Whisper
-        let transcription = match self.whisper_adapter
-            .transcribe(
-                video_path,
+        // 4. Reuse an earlier transcription of the track (model and file unchanged)
+        let model = self.whisper_adapter.model_info(request.source_language.as_deref());
+        let cached = self.transcription_cache.as_ref().and_then(|cache| {
+            cache.get(
+                request.media_id,
                 request.audio_track_index,
+                video_path,
+                &model,
                 request.source_language.as_deref(),
             )
-            .await
-        {
-            Ok(t) => t,
-            Err(e) => {
-                let error = ApplicationError::SpeechToText(e);
-                self.publish_failed_event(request.media_id, job_id, &error.to_string()).await;
-                return Err(error);
+        });
+
+        let (transcription, detected_language) = match cached {
+            Some(cached) => {
+                info!(
+                    "Reusing transcription of media {} track {} from {} ({} segments, {})",
+                    request.media_id,
+                    request.audio_track_index,
+                    cached.created_at,
+                    cached.transcription.segments.len(),
+                    cached.language
+                );
+                self.job_store.update_progress(job_id, 60.0, Some("Reused cached transcription")).await;
+                // Only a translation is left, if anything
+                let translating = request.target_language.as_ref().is_some_and(|t| t != &cached.language);
+                match media_seconds {
+                    Some(seconds) if translating => {
+                        self.job_store.set_workload(job_id, JobKind::Translation, seconds).await;
+                    }
+                    _ => self.job_store.clear_workload(job_id).await,
+                }
+                (cached.transcription, cached.language)
             }
-        };
+            None => {
+                // Unload a local translation model before Whisper to free VRAM (important for 8GB systems)
+                if let Some(translator) = &self.translator {
+                    self.job_store.update_progress(job_id, 20.0, Some("Freeing VRAM for Whisper...")).await;
+                    if let Err(e) = translator.release().await {
+                        debug!("Failed to unload translation model (may not have been loaded): {}", e);
+                    }
+                }
+
+                self.job_store.update_progress(job_id, 25.0, Some("Transcribing audio with Whisper...")).await;
+
+                // 5. Transcribe audio with Whisper. Backends that report
+                // progress move the job from 25% to 60%
+                let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<f32>();
+                let last_percent = std::sync::atomic::AtomicU32::new(0);
+                let progress: TranscriptionProgress = Arc::new(move |percent: f32| {
+                    let percent = percent.clamp(0.0, 100.0) as u32;
+                    if percent > last_percent.swap(percent, std::sync::atomic::Ordering::Relaxed) {
+                        let _ = tx.send(25.0 + percent as f32 * 0.35);
+                    }
+                });
+                let progress_job_store = self.job_store.clone();
+                let progress_job_id = job_id.to_string();
+                let forwarder = tokio::spawn(async move {
+                    while let Some(progress) = rx.recv().await {
+                        progress_job_store
+                            .update_progress(&progress_job_id, progress, Some("Transcribing audio with Whisper..."))
+                            .await;
+                    }
+                });
+
+                let vad = match request.skip_silence {
+                    Some(false) => None,
+                    Some(true) => Some(self.vad.clone().unwrap_or_default()),
+                    None => self.vad.clone(),
+                };
+                let transcription = self.whisper_adapter
+                    .transcribe_with(
+                        video_path,
+                        request.audio_track_index,
+                        request.source_language.as_deref(),
+                        vad.as_ref(),
+                        Some(progress),
+                    )
+                    .await;
+                forwarder.abort();
+                let transcription = match transcription {
+                    Ok(t) => t,
+                    Err(e) => {
+                        let error = ApplicationError::SpeechToText(e);
+                        self.publish_failed_event(request.media_id, job_id, &error.to_string()).await;
+                        return Err(error);
+                    }
+                };
 
-        let detected_language = transcription.detected_language.clone()
-            .unwrap_or_else(|| request.source_language.clone().unwrap_or("en".to_string()));
+                let detected_language = transcription.detected_language.clone()
+                    .unwrap_or_else(|| request.source_language.clone().unwrap_or("en".to_string()));
 
-        info!(
-            "Transcription complete: {} segments, detected language: {}",
-            transcription.segments.len(),
-            detected_language
-        );
+                info!(
+                    "Transcription complete: {} segments, detected language: {}",
+                    transcription.segments.len(),
+                    detected_language
+                );
+
+                if let Some(cache) = &self.transcription_cache {
+                    if let Err(e) = cache.store(
+                        request.media_id,
+                        request.audio_track_index,
+                        video_path,
+                        model,
+                        &detected_language,
+                        &transcription,
+                    ) {
+                        tracing::warn!("Failed to cache transcription of media {}: {}", request.media_id, e);
+                    }
+                }
 
-        self.job_store.update_progress(job_id, 60.0, Some("Transcription complete")).await;
+                self.job_store.update_progress(job_id, 60.0, Some("Transcription complete")).await;
 
-        // DEBUG: Save raw transcription for comparison (before translation)
-        // This helps diagnose whether issues come from Whisper or Ollama
-        if let Err(e) = self.write_debug_transcription(video_path, &detected_language, &transcription.segments) {
-            debug!("Failed to write debug transcription: {}", e);
-        }
+                // DEBUG: Save raw transcription for comparison (before translation)
+                // This helps diagnose whether issues come from Whisper or the translation
+                if let Err(e) = self.write_debug_transcription(video_path, &detected_language, &transcription.segments) {
+                    debug!("Failed to write debug transcription: {}", e);
+                }
+
+                (transcription, detected_language)
+            }
+        };
 
         // 6. Optionally translate
+        let source_language = detected_language.clone();
         let (final_segments, output_language, was_translated) = if let Some(target_lang) = &request.target_language {
             if target_lang != &detected_language {
-                self.job_store.update_progress(job_id, 65.0, Some("Translating with Ollama...")).await;
+                self.job_store.update_progress(job_id, 65.0, Some("Translating...")).await;
 
                 let translated = match self.translate_segments(
                     transcription.segments,
@@ -287,6 +455,17 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
 
         info!("Subtitle written to: {}", srt_path);
 
+        self.record_generated(GeneratedSubtitle {
+            media_id: request.media_id,
+            audio_track_index: Some(request.audio_track_index),
+            audio_fingerprint: fingerprint_hex.clone(),
+            source_language: Some(source_language),
+            language: output_language.clone(),
+            subtitle_path: srt_path.clone(),
+            duration_seconds: fingerprint.duration,
+            was_translated,
+        }).await;
+
         self.job_store.update_progress(job_id, 100.0, Some("Complete")).await;
 
         let result = GenerateSubtitleResult {
@@ -314,6 +493,157 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
         Ok(result)
     }
 
+    /// Translates an existing external subtitle without transcription
+    ///
+    /// Runs only the translation stage on the cues of the subtitle file and
+    /// writes the result next to the video like generated subtitles. Holds
+    /// the GPU lock while translating.
+    pub async fn translate_existing(
+        &self,
+        request: TranslateSubtitleRequest,
+        job_id: &str,
+    ) -> Result<TranslateSubtitleResult, ApplicationError> {
+        info!(
+            "Starting subtitle translation for media {} (subtitle {}, target: {})",
+            request.media_id,
+            request.subtitle_index,
+            request.target_language
+        );
+
+        self.job_store.start_job(job_id).await;
+        self.job_store.update_progress(job_id, 5.0, Some("Reading subtitle...")).await;
+
+        let result = self.translate_subtitle_file(&request, job_id).await;
+        match &result {
+            Ok(result) => {
+                // No audio is fingerprinted when only translating
+                let event = SubtitleGenerationCompletedEvent::new(
+                    request.media_id,
+                    job_id.to_string(),
+                    result.subtitle_path.clone(),
+                    result.language.clone(),
+                    true,
+                    String::new(),
+                    0.0,
+                );
+                if let Err(e) = self.event_bus.publish(event).await {
+                    tracing::warn!("Failed to publish subtitle generation completed event: {}", e);
+                }
+            }
+            Err(e) => self.publish_failed_event(request.media_id, job_id, &e.to_string()).await,
+        }
+        result
+    }
+
+    async fn translate_subtitle_file(
+        &self,
+        request: &TranslateSubtitleRequest,
+        job_id: &str,
+    ) -> Result<TranslateSubtitleResult, ApplicationError> {
+        let media = self.media_repository
+            .find_by_id(request.media_id)
+            .await?
+            .ok_or_else(|| ApplicationError::Domain(
+                DomainError::NotFound(format!("Media with ID {} not found", request.media_id))
+            ))?;
+
+        let detector = match &self.subtitle_store {
+            Some(store) => store.detector(request.media_id),
+            None => SubtitleDetector::new(),
+        };
+        let subtitle = detector
+            .discover(Path::new(&media.file_path))
+            .into_iter()
+            .nth(request.subtitle_index)
+            .ok_or_else(|| ApplicationError::Domain(
+                DomainError::NotFound(format!("Subtitle track {} not found", request.subtitle_index))
+            ))?;
+
+        let source_language = request.source_language.as_deref()
+            .and_then(normalize_language_code)
+            .or(subtitle.language.clone())
+            .ok_or_else(|| ApplicationError::Domain(DomainError::InvalidInput(
+                "Subtitle language unknown, set source_language".to_string()
+            )))?;
+        let target_language = normalize_language_code(&request.target_language)
+            .unwrap_or_else(|| request.target_language.clone());
+        if source_language == target_language {
+            return Err(ApplicationError::Domain(DomainError::InvalidInput(
+                format!("Subtitle is already in {}", target_language)
+            )));
+        }
+
+        // Tags would confuse the model and end up in the translation
+        let options = SubtitleOptions::new(TagPolicy::Strip)
+            .with_encoding(request.encoding)
+            .with_language(Some(source_language.clone()));
+        let segments: Vec<TranscriptionSegment> = read_subtitle_file(&subtitle.file_path, &options)?
+            .cues
+            .into_iter()
+            .map(|cue| TranscriptionSegment {
+                start_time: cue.start,
+                end_time: cue.end,
+                text: cue.text.replace('\n', " "),
+            })
+            .collect();
+
+        self.job_store.update_progress(job_id, 10.0, Some("Acquiring GPU lock...")).await;
+        let _gpu_permit = self.gpu_coordinator.acquire().await;
+
+        // Subtitles cover about the whole media, so its end stands in for the length
+        let media_seconds = media.duration_seconds
+            .filter(|d| *d > 0)
+            .map(f64::from)
+            .or_else(|| segments.last().map(|s| s.end_time));
+        if let Some(seconds) = media_seconds {
+            self.job_store.set_workload(job_id, JobKind::Translation, seconds).await;
+        }
+
+        self.job_store.update_progress(job_id, 20.0, Some("Translating...")).await;
+        let translated = self.translate_segments(segments, &source_language, &target_language).await?;
+
+        info!(
+            "Translation complete: {} -> {}, {} segments",
+            source_language,
+            target_language,
+            translated.len()
+        );
+
+        self.job_store.update_progress(job_id, 90.0, Some("Writing SRT file...")).await;
+        let srt_path = self.write_srt_file(&media.file_path, &target_language, &translated)?;
+        info!("Subtitle written to: {}", srt_path);
+
+        self.record_generated(GeneratedSubtitle {
+            media_id: request.media_id,
+            audio_track_index: None,
+            audio_fingerprint: String::new(),
+            source_language: Some(source_language.clone()),
+            language: target_language.clone(),
+            subtitle_path: srt_path.clone(),
+            duration_seconds: translated.last().map_or(0.0, |s| s.end_time),
+            was_translated: true,
+        }).await;
+
+        self.job_store.update_progress(job_id, 100.0, Some("Complete")).await;
+
+        Ok(TranslateSubtitleResult {
+            subtitle_path: srt_path,
+            source_path: subtitle.file_path,
+            source_language,
+            language: target_language,
+            segments: translated.len(),
+        })
+    }
+
+    /// Records a written subtitle file (best effort)
+    async fn record_generated(&self, subtitle: GeneratedSubtitle) {
+        if let Some(repository) = &self.generated_subtitles {
+            if let Err(e) = repository.save(&subtitle).await {
+                tracing::warn!("Failed to record generated subtitle {}: {}", subtitle.subtitle_path, e);
+            }
+        }
+    }
+
     /// Publishes subtitle generation failed event (helper method)
     async fn publish_failed_event(&self, media_id: i64, job_id: &str, error: &str) {
         let event = SubtitleGenerationFailedEvent::new(
@@ -338,26 +668,22 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
             .map_err(|e| ApplicationError::Fingerprint(e))
     }
 
-    /// Translates transcription segments using Ollama
+    /// Translates transcription segments with the configured provider
     async fn translate_segments(
         &self,
         segments: Vec<TranscriptionSegment>,
         source_lang: &str,
         target_lang: &str,
     ) -> Result<Vec<TranscriptionSegment>, ApplicationError> {
-        let ollama = self.ollama_client.as_ref()
+        let translator = self.translator.as_ref()
             .ok_or_else(|| ApplicationError::Translation(
                 crate::shared::error::TranslationError::ServiceUnavailable(
-                    "Ollama client not configured".to_string()
+                    "No translation provider configured".to_string()
                 )
             ))?;
 
-        // Convert language codes to names for better LLM results
-        let source_name = language_code_to_name(source_lang);
-        let target_name = language_code_to_name(target_lang);
-
-        ollama
-            .translate_segments(segments, source_name, target_name)
+        translator
+            .translate_segments(segments, source_lang, target_lang)
             .await
             .map_err(|e| ApplicationError::Translation(e))
     }
@@ -434,11 +760,13 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
     pub async fn check_capabilities(&self) -> ServiceCapabilities {
         ServiceCapabilities {
             whisper_available: self.whisper_adapter.is_available().await,
+            whisper_backend: self.whisper_adapter.backend_name(),
             whisper_model_exists: self.whisper_adapter.model_exists(),
-            ollama_available: match &self.ollama_client {
-                Some(client) => client.is_available().await,
+            translation_available: match &self.translator {
+                Some(translator) => translator.is_available().await,
                 None => false,
             },
+            translation_providers: self.translator.as_ref().map(|t| t.providers()).unwrap_or_default(),
             fpcalc_available: self.fpcalc_adapter.is_available().await,
         }
     }
@@ -447,12 +775,16 @@ impl<E: EventBus + ?Sized> GenerateSubtitleUseCase<E> {
 /// Service availability status
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ServiceCapabilities {
-    /// Whether whisper-cli is available
+    /// Whether the Whisper backend is available
     pub whisper_available: bool,
+    /// Whisper backend in use ("cli" or "native")
+    pub whisper_backend: &'static str,
     /// Whether the Whisper model file exists
     pub whisper_model_exists: bool,
-    /// Whether Ollama API is available
-    pub ollama_available: bool,
+    /// Whether a translation provider is available
+    pub translation_available: bool,
+    /// Translation providers, in the order they are tried
+    pub translation_providers: Vec<&'static str>,
     /// Whether fpcalc is available
     pub fpcalc_available: bool,
 }
@@ -465,6 +797,6 @@ impl ServiceCapabilities {
 
     /// Returns true if translation is possible
     pub fn can_translate(&self) -> bool {
-        self.ollama_available
+        self.translation_available
     }
 }
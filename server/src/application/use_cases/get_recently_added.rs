@@ -1,14 +1,24 @@
 //! Get Recently Added Use Case
 //!
-//! Retrieves recently added content combining movies and series
-//! (ranked by most recent episode date).
+//! Retrieves recently added content combining movies and series episodes.
+//! Episodes of the same series and season that arrive close together are
+//! grouped into one entry per batch (a whole season drop is one row).
 
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use crate::domain::entities::{Media, Series};
 use crate::domain::repositories::{MediaRepository, SeriesRepository};
 use crate::shared::error::ApplicationError;
 
+/// Default gap between additions that still belong to the same batch
+const DEFAULT_BATCH_WINDOW_HOURS: i64 = 12;
+
+/// Episodes fetched per requested entry (batches can be large)
+const EPISODES_PER_ENTRY: usize = 25;
+
 /// Represents a recently added item (either movie or series)
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type")]
@@ -21,7 +31,18 @@ pub enum RecentlyAddedItem {
     #[serde(rename = "series")]
     Series {
         series: Series,
+        /// When the newest episode of the batch was added
         added_at: String,
+        /// Season of the batch
+        season: Option<i32>,
+        /// Lowest and highest episode number in the batch
+        first_episode: Option<i32>,
+        last_episode: Option<i32>,
+        /// Number of episodes added in the batch
+        episode_count: usize,
+        /// Representative still of the batch (first episode, falling back
+        /// to the series backdrop)
+        backdrop_url: Option<String>,
     },
 }
 
@@ -34,9 +55,19 @@ impl RecentlyAddedItem {
     }
 }
 
+/// Episodes of one series/season added within the batch window
+struct EpisodeBatch {
+    series_id: i64,
+    season: Option<i32>,
+    newest: DateTime<Utc>,
+    oldest: DateTime<Utc>,
+    episodes: Vec<Media>,
+}
+
 pub struct GetRecentlyAddedUseCase {
     media_repository: Arc<dyn MediaRepository>,
     series_repository: Arc<dyn SeriesRepository>,
+    batch_window: Duration,
 }
 
 impl GetRecentlyAddedUseCase {
@@ -47,31 +78,38 @@ impl GetRecentlyAddedUseCase {
         Self {
             media_repository,
             series_repository,
+            batch_window: Duration::hours(DEFAULT_BATCH_WINDOW_HOURS),
         }
     }
 
+    /// Sets the maximum gap between two additions of the same batch
+    pub fn with_batch_window(mut self, batch_window: Duration) -> Self {
+        self.batch_window = batch_window;
+        self
+    }
+
     /// Gets the most recently added content
     ///
     /// Combines:
     /// - Recently added movies (by created_at)
-    /// - Series with recently added episodes (by episode's created_at)
+    /// - Batches of recently added episodes, one per series/season drop
     ///
     /// Returns combined list sorted by date, limited to specified count
     pub async fn execute(&self, limit: usize) -> Result<Vec<RecentlyAddedItem>, ApplicationError> {
         // Fetch more than needed to ensure we have enough after combining
         let fetch_limit = limit * 2;
 
-        // Get recent movies and series with recent episodes concurrently
-        let (movies_result, series_result) = tokio::join!(
+        // Get recent movies and episodes concurrently
+        let (movies_result, episodes_result) = tokio::join!(
             self.media_repository.find_recent_movies(fetch_limit),
-            self.series_repository.find_recent_by_episode(fetch_limit)
+            self.media_repository.find_recent_episodes(limit * EPISODES_PER_ENTRY)
         );
 
         let movies = movies_result?;
-        let series_with_dates = series_result?;
+        let batches = self.group_batches(episodes_result?).await?;
 
         // Convert to unified items
-        let mut items: Vec<RecentlyAddedItem> = Vec::with_capacity(movies.len() + series_with_dates.len());
+        let mut items: Vec<RecentlyAddedItem> = Vec::with_capacity(movies.len() + batches.len());
 
         for movie in movies {
             let added_at = movie.created_at.to_rfc3339();
@@ -81,12 +119,7 @@ impl GetRecentlyAddedUseCase {
             });
         }
 
-        for (series, latest_episode_date) in series_with_dates {
-            items.push(RecentlyAddedItem::Series {
-                series,
-                added_at: latest_episode_date,
-            });
-        }
+        items.extend(batches);
 
         // Sort by added_at descending (most recent first)
         items.sort_by(|a, b| b.added_at().cmp(a.added_at()));
@@ -96,4 +129,117 @@ impl GetRecentlyAddedUseCase {
 
         Ok(items)
     }
+
+    /// Groups episodes (newest first) into per-series/season batches
+    async fn group_batches(&self, episodes: Vec<Media>) -> Result<Vec<RecentlyAddedItem>, ApplicationError> {
+        let mut series_cache: HashMap<i64, Option<Series>> = HashMap::new();
+        let mut batches: Vec<EpisodeBatch> = Vec::new();
+        // Open batch per (series key, season); duplicate series entries with
+        // the same TMDB ID share a key
+        let mut open: HashMap<(i64, Option<i32>), usize> = HashMap::new();
+
+        for episode in episodes {
+            let Some(series_id) = episode.series_id else {
+                continue;
+            };
+            if let Entry::Vacant(entry) = series_cache.entry(series_id) {
+                entry.insert(self.series_repository.find_by_id(series_id).await?);
+            }
+            let Some(series) = series_cache.get(&series_id).and_then(|s| s.as_ref()) else {
+                continue;
+            };
+
+            let key = (series.tmdb_id.map_or(series_id, |tmdb_id| -tmdb_id), episode.season);
+            let created_at = episode.created_at;
+            match open.get(&key).map(|&i| &mut batches[i]) {
+                Some(batch) if batch.oldest - created_at <= self.batch_window => {
+                    batch.oldest = created_at;
+                    batch.episodes.push(episode);
+                }
+                _ => {
+                    open.insert(key, batches.len());
+                    batches.push(EpisodeBatch {
+                        series_id,
+                        season: episode.season,
+                        newest: created_at,
+                        oldest: created_at,
+                        episodes: vec![episode],
+                    });
+                }
+            }
+        }
+
+        Ok(batches
+            .into_iter()
+            .filter_map(|batch| {
+                let series = series_cache.get(&batch.series_id)?.clone()?;
+                let first = batch.episodes.iter().min_by_key(|e| e.episode.unwrap_or(i32::MAX));
+                let backdrop_url = first
+                    .and_then(|e| e.backdrop_url.clone())
+                    .or_else(|| series.backdrop_url.clone());
+                Some(RecentlyAddedItem::Series {
+                    added_at: batch.newest.to_rfc3339(),
+                    season: batch.season,
+                    first_episode: batch.episodes.iter().filter_map(|e| e.episode).min(),
+                    last_episode: batch.episodes.iter().filter_map(|e| e.episode_end.or(e.episode)).max(),
+                    episode_count: batch.episodes.len(),
+                    backdrop_url,
+                    series,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MediaType;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{SqliteMediaRepository, SqliteSeriesRepository};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_episodes_grouped_per_season_drop() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let media_repo = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let series_repo = Arc::new(SqliteSeriesRepository::new(pool));
+        let series_id = series_repo.save(&Series::new("Severance".to_string()).unwrap()).await.unwrap();
+
+        // Season 1 arrived at once a week ago, season 2 episode by episode today
+        let now = Utc::now();
+        let drops = (1..=9).map(|e| (1, e, now - Duration::days(7) + Duration::minutes(e as i64)))
+            .chain((1..=2).map(|e| (2, e, now - Duration::hours(3 - e as i64))));
+        for (season, episode, created_at) in drops {
+            let mut media = Media::new(
+                format!("/tv/Severance/S{:02}E{:02}.mkv", season, episode),
+                MediaType::Episode,
+                "Severance".to_string(),
+            ).unwrap();
+            media.series_id = Some(series_id);
+            media.season = Some(season);
+            media.episode = Some(episode);
+            media.created_at = created_at;
+            media_repo.save(&media).await.unwrap();
+        }
+
+        let use_case = GetRecentlyAddedUseCase::new(media_repo, series_repo);
+        let items = use_case.execute(10).await.unwrap();
+        let batches: Vec<(Option<i32>, usize, Option<i32>, Option<i32>)> = items
+            .iter()
+            .map(|item| match item {
+                RecentlyAddedItem::Series { season, episode_count, first_episode, last_episode, .. } => {
+                    (*season, *episode_count, *first_episode, *last_episode)
+                }
+                RecentlyAddedItem::Movie { .. } => panic!("unexpected movie"),
+            })
+            .collect();
+        assert_eq!(batches, vec![(Some(2), 2, Some(1), Some(2)), (Some(1), 9, Some(1), Some(9))]);
+    }
 }
@@ -11,13 +11,19 @@ use tracing::{info, debug, warn};
 
 use crate::domain::entities::Media;
 use crate::domain::events::{MediaIdentifiedEvent, MediaVerifiedEvent};
-use crate::domain::repositories::MediaRepository;
+use crate::domain::repositories::{MediaOrder, MediaRepository};
 use crate::domain::value_objects::{MediaType, MatchStrategy, ConfidenceScore};
-use crate::interfaces::external_services::{TmdbService, TmdbSearcher, TmdbFetcher, TmdbResolver};
+use crate::interfaces::external_services::{
+    TmdbService, TmdbSearcher, TmdbFetcher, TmdbResolver, TmdbMatch,
+};
 use crate::interfaces::messaging::EventBus;
 use crate::shared::error::ApplicationError;
 use crate::shared::text::{TitleNormalizer, FuzzyMatcher};
 
+/// Candidates whose alternative titles are compared when no search result
+/// matches the title
+const ALTERNATIVE_TITLE_CANDIDATES: usize = 5;
+
 /// Result of media identification
 #[derive(Debug, Clone)]
 pub struct IdentificationResult {
@@ -228,6 +234,17 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
         Ok(self.media_repository.find_all().await?)
     }
 
+    /// Lists a page of media, with the number of media on all pages
+    pub async fn list_page(
+        &self,
+        order: MediaOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Media>, i64), ApplicationError> {
+        Ok(self.media_repository.find_page(order, descending, offset, limit).await?)
+    }
+
     /// Searches TMDB using all 6 strategies from roadmap
     ///
     /// # Strategies
@@ -235,7 +252,8 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
     /// 2. Filename + Year
     /// 3. Folder + Year (same as 2 for TMDB)
     /// 4. Filename only (year-agnostic)
-    /// 5. Alternative titles (remove articles)
+    /// 5. Alternative titles (remove articles, then TMDB's AKAs of the
+    ///    results that did not match)
     /// 6. Fuzzy search (as fallback)
     async fn search_with_all_strategies(
         &self,
@@ -244,6 +262,8 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
         media_type: &MediaType,
     ) -> Result<Vec<crate::interfaces::external_services::TmdbMatch>, ApplicationError> {
         let mut all_matches = Vec::new();
+        // Search results whose title did not match, for Strategy 5b
+        let mut unverified: Vec<TmdbMatch> = Vec::new();
 
         // Strategy 1: IMDB ID lookup (if title contains IMDB ID)
         if let Some(imdb_id) = self.extract_imdb_id(title) {
@@ -273,6 +293,8 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
                     all_matches.push(matched);
                     debug!("Strategy 2 (Filename + Year): Verified match '{}' with fuzzy score {:.2}",
                         all_matches.last().unwrap().title, fuzzy_result.score);
+                } else {
+                    unverified.push(m);
                 }
             }
         }
@@ -292,6 +314,8 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
                         all_matches.push(matched);
                         debug!("Strategy 3 (Folder + Year): Verified match '{}' with fuzzy score {:.2}",
                             all_matches.last().unwrap().title, fuzzy_result.score);
+                    } else {
+                        unverified.push(m);
                     }
                 }
             }
@@ -318,6 +342,8 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
                     all_matches.push(matched);
                     debug!("Strategy 4 (Filename only): Verified match '{}' with fuzzy score {:.2}",
                         all_matches.last().unwrap().title, fuzzy_result.score);
+                } else {
+                    unverified.push(m);
                 }
             }
         }
@@ -349,6 +375,37 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
             }
         }
 
+        // Strategy 5b: TMDB alternative titles (files named after a localized
+        // title or AKA, which TMDB finds but lists under another title)
+        if all_matches.is_empty() && !unverified.is_empty() {
+            let mut seen = std::collections::HashSet::new();
+            unverified.retain(|m| seen.insert(m.tmdb_id));
+
+            for m in unverified.into_iter().take(ALTERNATIVE_TITLE_CANDIDATES) {
+                let alternatives = match self.tmdb_service.fetch_alternative_titles(m.tmdb_id, &m.media_type).await {
+                    Ok(alternatives) => alternatives,
+                    Err(e) => {
+                        debug!("Strategy 5b: Failed to fetch alternative titles of TMDB {}: {}", m.tmdb_id, e);
+                        continue;
+                    }
+                };
+                let fuzzy_result = FuzzyMatcher::compare_titles_with_alternatives(
+                    title,
+                    &m.title,
+                    alternatives.iter().map(|a| a.title.as_str()),
+                );
+                if fuzzy_result.score > 0.90 {
+                    let confidence = (0.65 * fuzzy_result.score as f32).max(0.55);
+                    let mut matched = m;
+                    matched.confidence = ConfidenceScore::new(confidence)?;
+                    matched.strategy = MatchStrategy::AlternativeTitle;
+                    debug!("Strategy 5b (TMDB alternative titles): Verified match '{}' as '{}' with fuzzy score {:.2}",
+                        matched.title, fuzzy_result.text, fuzzy_result.score);
+                    all_matches.push(matched);
+                }
+            }
+        }
+
         // Strategy 6: Fuzzy search with title variants (as fallback)
         // Generates variants like "Part Three" -> "Part III", "Part 3"
         if all_matches.is_empty() {
@@ -428,6 +485,7 @@ impl<E: EventBus + ?Sized> IdentifyMediaUseCase<E> {
                 media_type: "unknown".to_string(),
                 confidence: ConfidenceScore::default(),
                 strategy: MatchStrategy::FilenameOnly,
+                origin_country: Vec::new(),
             });
 
         best
@@ -0,0 +1,472 @@
+//! Library Health Use Case
+//!
+//! Aggregates actionable library problems ("library doctor"): unidentified
+//! files, low-confidence matches, missing artwork, missing subtitles in the
+//! preferred language, unreadable files and duplicates. Each issue carries a
+//! suggested API action for resolving it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use media_identifier::ParsedMedia;
+use serde::Serialize;
+
+use crate::domain::entities::{Media, Series};
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::domain::services::IdentificationService;
+use crate::domain::value_objects::VerificationStatus;
+use crate::infrastructure::subtitle::{SubtitleDetector, SubtitleStore};
+use crate::shared::error::ApplicationError;
+
+/// Category of a library issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Unidentified,
+    LowConfidence,
+    MissingArtwork,
+    MissingSubtitles,
+    CorruptFile,
+    Duplicate,
+}
+
+impl IssueKind {
+    /// Weight of one issue in the health score (1.0 = item is unusable)
+    fn weight(&self) -> f64 {
+        match self {
+            IssueKind::Unidentified | IssueKind::CorruptFile => 1.0,
+            IssueKind::LowConfidence => 0.5,
+            IssueKind::MissingArtwork | IssueKind::Duplicate => 0.3,
+            IssueKind::MissingSubtitles => 0.2,
+        }
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        match self {
+            IssueKind::Unidentified | IssueKind::CorruptFile => IssueSeverity::High,
+            IssueKind::LowConfidence | IssueKind::Duplicate => IssueSeverity::Medium,
+            IssueKind::MissingArtwork | IssueKind::MissingSubtitles => IssueSeverity::Low,
+        }
+    }
+}
+
+/// How urgently an issue should be fixed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    High,
+    Medium,
+    Low,
+}
+
+/// API call that resolves (or helps resolve) an issue
+#[derive(Debug, Clone, Serialize)]
+pub struct SuggestedAction {
+    pub method: &'static str,
+    pub endpoint: String,
+    pub description: String,
+}
+
+/// A single actionable problem
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryIssue {
+    pub kind: IssueKind,
+    pub severity: IssueSeverity,
+    pub media_id: Option<i64>,
+    pub series_id: Option<i64>,
+    pub title: String,
+    pub file_path: Option<String>,
+    pub detail: String,
+    pub action: SuggestedAction,
+}
+
+/// Options for a health check
+#[derive(Debug, Clone)]
+pub struct LibraryHealthOptions {
+    /// Subtitle language to require (ISO 639-1, e.g. "hu"); None skips the check
+    pub subtitle_language: Option<String>,
+    /// Identified items below this confidence are reported
+    pub low_confidence_threshold: f32,
+    /// Maximum issues listed per kind (counts are always complete)
+    pub limit_per_kind: usize,
+    /// Whether to check files on disk (existence and size)
+    pub check_files: bool,
+}
+
+impl Default for LibraryHealthOptions {
+    fn default() -> Self {
+        Self {
+            subtitle_language: None,
+            low_confidence_threshold: 0.7,
+            limit_per_kind: 100,
+            check_files: true,
+        }
+    }
+}
+
+/// Result of a health check
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryHealthReport {
+    /// Health score from 0 (broken) to 100 (no issues)
+    pub score: u8,
+    pub total_media: usize,
+    pub total_series: usize,
+    /// Number of issues per kind
+    pub counts: HashMap<IssueKind, usize>,
+    /// Subtitle language that was checked, if any
+    pub subtitle_language: Option<String>,
+    pub issues: Vec<LibraryIssue>,
+}
+
+/// Library Health Use Case
+pub struct LibraryHealthUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    /// Parses release names with the parser profiles the scanner uses
+    identification_service: Arc<dyn IdentificationService>,
+    /// Where downloaded subtitles of read-only media are (None = next to
+    /// the video only)
+    subtitle_store: Option<Arc<SubtitleStore>>,
+}
+
+impl LibraryHealthUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        identification_service: Arc<dyn IdentificationService>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            identification_service,
+            subtitle_store: None,
+        }
+    }
+
+    /// Also finds subtitles kept in the data directory
+    pub fn with_subtitle_store(mut self, store: Arc<SubtitleStore>) -> Self {
+        self.subtitle_store = Some(store);
+        self
+    }
+
+    /// Runs all checks over the library
+    pub async fn execute(&self, options: LibraryHealthOptions) -> Result<LibraryHealthReport, ApplicationError> {
+        let media = self.media_repository.find_all().await?;
+        let series = self.series_repository.find_all().await?;
+
+        let subtitle_language = options.subtitle_language
+            .as_deref()
+            .and_then(|l| l.split(['-', '_']).next())
+            .map(|l| l.to_lowercase())
+            .filter(|l| !l.is_empty());
+
+        let check_files = options.check_files;
+        let subtitle_check = subtitle_language.clone();
+        // Filesystem checks touch every file, keep them off the async workers
+        let file_issues = {
+            let media = media.clone();
+            let store = self.subtitle_store.clone();
+            tokio::task::spawn_blocking(move || file_issues(&media, check_files, subtitle_check.as_deref(), store.as_deref()))
+                .await
+                .map_err(|e| ApplicationError::Internal(e.to_string()))?
+        };
+
+        let mut issues = media_issues(&media, options.low_confidence_threshold);
+        issues.extend(series_issues(&series));
+        issues.extend(duplicate_issues(&media, self.identification_service.as_ref()));
+        issues.extend(file_issues);
+
+        let mut counts: HashMap<IssueKind, usize> = HashMap::new();
+        for issue in &issues {
+            *counts.entry(issue.kind).or_default() += 1;
+        }
+        let score = health_score(&issues, media.len() + series.len());
+
+        // Most severe first, then cap each kind
+        issues.sort_by_key(|i| (i.severity as u8, i.kind as u8));
+        let mut listed: HashMap<IssueKind, usize> = HashMap::new();
+        issues.retain(|i| {
+            let n = listed.entry(i.kind).or_default();
+            *n += 1;
+            *n <= options.limit_per_kind
+        });
+
+        Ok(LibraryHealthReport {
+            score,
+            total_media: media.len(),
+            total_series: series.len(),
+            counts,
+            subtitle_language,
+            issues,
+        })
+    }
+}
+
+fn issue(kind: IssueKind, media: &Media, detail: String, action: SuggestedAction) -> LibraryIssue {
+    LibraryIssue {
+        kind,
+        severity: kind.severity(),
+        media_id: media.id,
+        series_id: media.series_id,
+        title: media.title.clone(),
+        file_path: Some(media.file_path.clone()),
+        detail,
+        action,
+    }
+}
+
+fn identify_action(media_id: Option<i64>, description: &str) -> SuggestedAction {
+    SuggestedAction {
+        method: "POST",
+        endpoint: format!("/v2/media/{}/identify", media_id.unwrap_or_default()),
+        description: description.to_string(),
+    }
+}
+
+/// Identification and artwork problems of individual files
+fn media_issues(media: &[Media], low_confidence_threshold: f32) -> Vec<LibraryIssue> {
+    let mut issues = Vec::new();
+    for m in media {
+        let confidence = m.confidence_score.value();
+        if m.tmdb_id.is_none() || m.verification_status == VerificationStatus::Failed {
+            issues.push(issue(
+                IssueKind::Unidentified,
+                m,
+                m.error_notes.clone().unwrap_or_else(|| "No TMDB match".to_string()),
+                identify_action(m.id, "Assign the correct TMDB ID"),
+            ));
+            continue;
+        }
+        if confidence < low_confidence_threshold || m.verification_status == VerificationStatus::ManualReview {
+            issues.push(issue(
+                IssueKind::LowConfidence,
+                m,
+                format!("Matched with {:.0}% confidence", confidence * 100.0),
+                identify_action(m.id, "Confirm or correct the TMDB match"),
+            ));
+        }
+        if m.poster_url.is_none() && !m.is_episode() {
+            issues.push(issue(
+                IssueKind::MissingArtwork,
+                m,
+                "No poster".to_string(),
+                identify_action(m.id, "Re-identify to fetch artwork from TMDB"),
+            ));
+        }
+    }
+    issues
+}
+
+/// Artwork problems of series
+fn series_issues(series: &[Series]) -> Vec<LibraryIssue> {
+    series.iter()
+        .filter(|s| s.poster_url.is_none())
+        .map(|s| LibraryIssue {
+            kind: IssueKind::MissingArtwork,
+            severity: IssueKind::MissingArtwork.severity(),
+            media_id: None,
+            series_id: s.id,
+            title: s.title.clone(),
+            file_path: None,
+            detail: "Series has no poster".to_string(),
+            action: SuggestedAction {
+                method: "POST",
+                endpoint: format!("/v2/series/{}/refresh", s.id.unwrap_or_default()),
+                description: "Refresh the series from TMDB to fetch artwork".to_string(),
+            },
+        })
+        .collect()
+}
+
+/// Multiple files for the same movie or episode
+fn duplicate_issues(media: &[Media], identification: &dyn IdentificationService) -> Vec<LibraryIssue> {
+    let mut groups: HashMap<(bool, i64, i32, i32), Vec<&Media>> = HashMap::new();
+    for m in media {
+        let key = if m.is_episode() {
+            match (m.series_id, m.season, m.episode) {
+                (Some(series_id), Some(season), Some(episode)) => (true, series_id, season, episode),
+                _ => continue,
+            }
+        } else {
+            match m.tmdb_id {
+                Some(tmdb_id) => (false, tmdb_id, 0, 0),
+                None => continue,
+            }
+        };
+        groups.entry(key).or_default().push(m);
+    }
+
+    let mut issues = Vec::new();
+    for group in groups.values().filter(|g| g.len() > 1) {
+        // A PROPER/REPACK replaces the earlier release, so only the older copies are issues
+        let parsed: Vec<ParsedMedia> = group.iter().map(|m| identification.parse(&m.file_path)).collect();
+        let newer: Vec<Option<&Media>> = parsed.iter()
+            .map(|p| group.iter().zip(&parsed).find(|(_, other)| other.supersedes(p)).map(|(m, _)| *m))
+            .collect();
+        if newer.iter().any(Option::is_some) {
+            for (m, newer) in group.iter().zip(newer) {
+                let Some(newer) = newer else {
+                    continue;
+                };
+                issues.push(issue(
+                    IssueKind::Duplicate,
+                    m,
+                    format!("Superseded by a newer release: {}", newer.file_path),
+                    SuggestedAction {
+                        method: "DELETE",
+                        endpoint: format!("/v2/admin/media/{}", m.id.unwrap_or_default()),
+                        description: "Delete this file from disk, then remove it from the library; a PROPER/REPACK \
+                            release replaces it"
+                            .to_string(),
+                    },
+                ));
+            }
+            continue;
+        }
+
+        let others: Vec<&str> = group.iter().map(|m| m.file_path.as_str()).collect();
+        for m in group {
+            issues.push(issue(
+                IssueKind::Duplicate,
+                m,
+                format!("{} files for the same item: {}", group.len(), others.join(", ")),
+                SuggestedAction {
+                    method: "GET",
+                    endpoint: format!("/v2/media/{}/tracks", m.id.unwrap_or_default()),
+                    description: "Compare versions and remove the worse copy".to_string(),
+                },
+            ));
+        }
+    }
+    issues
+}
+
+/// Problems that need the filesystem: unreadable files and missing subtitles
+fn file_issues(
+    media: &[Media],
+    check_files: bool,
+    subtitle_language: Option<&str>,
+    store: Option<&SubtitleStore>,
+) -> Vec<LibraryIssue> {
+    let mut issues = Vec::new();
+
+    for m in media {
+        let path = Path::new(&m.file_path);
+        if check_files {
+            let problem = match std::fs::metadata(path) {
+                Err(_) => Some("File is missing or unreadable".to_string()),
+                Ok(meta) if meta.len() == 0 => Some("File is empty".to_string()),
+                Ok(_) if m.duration_seconds.is_none_or(|d| d <= 0) => {
+                    Some("Duration could not be read; file may be corrupt".to_string())
+                }
+                Ok(_) => None,
+            };
+            if let Some(detail) = problem {
+                issues.push(issue(
+                    IssueKind::CorruptFile,
+                    m,
+                    detail,
+                    SuggestedAction {
+                        method: "GET",
+                        endpoint: format!("/v2/media/{}/tracks", m.id.unwrap_or_default()),
+                        description: "Inspect the file and replace it".to_string(),
+                    },
+                ));
+                continue;
+            }
+        }
+
+        if let Some(language) = subtitle_language {
+            let detector = match (store, m.id) {
+                (Some(store), Some(id)) => store.detector(id),
+                _ => SubtitleDetector::new(),
+            };
+            let has_subtitle = detector.discover(path)
+                .iter()
+                .any(|s| s.language.as_deref() == Some(language));
+            if !has_subtitle {
+                issues.push(issue(
+                    IssueKind::MissingSubtitles,
+                    m,
+                    format!("No external '{}' subtitle", language),
+                    SuggestedAction {
+                        method: "POST",
+                        endpoint: format!("/v2/subtitles/{}/download", m.id.unwrap_or_default()),
+                        description: "Download subtitles (generated when none are found)".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// Weighted share of healthy items, 0-100
+fn health_score(issues: &[LibraryIssue], total_items: usize) -> u8 {
+    if total_items == 0 {
+        return 100;
+    }
+    let penalty: f64 = issues.iter().map(|i| i.kind.weight()).sum();
+    let score = 100.0 * (1.0 - penalty / total_items as f64);
+    score.clamp(0.0, 100.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::DefaultIdentificationService;
+    use crate::domain::value_objects::{ConfidenceScore, MediaType};
+
+    fn movie(id: i64, tmdb_id: Option<i64>, confidence: f32) -> Media {
+        let mut media = Media::new(format!("/movies/{}.mkv", id), MediaType::Movie, format!("Movie {}", id)).unwrap();
+        media.id = Some(id);
+        media.tmdb_id = tmdb_id;
+        media.poster_url = Some("https://image.tmdb.org/t/p/w500/p.jpg".to_string());
+        media.confidence_score = ConfidenceScore::new(confidence).unwrap();
+        media
+    }
+
+    #[test]
+    fn test_media_and_duplicate_issues() {
+        let media = vec![
+            movie(1, None, 0.0),
+            movie(2, Some(10), 0.5),
+            movie(3, Some(20), 0.95),
+            movie(4, Some(20), 0.95),
+        ];
+
+        let issues = media_issues(&media, 0.7);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].kind, IssueKind::Unidentified);
+        assert_eq!(issues[1].kind, IssueKind::LowConfidence);
+        assert_eq!(issues[1].action.endpoint, "/v2/media/2/identify");
+
+        let identification = DefaultIdentificationService::new();
+        let duplicates = duplicate_issues(&media, &identification);
+        assert_eq!(duplicates.len(), 2);
+        assert!(duplicates.iter().all(|i| i.kind == IssueKind::Duplicate));
+
+        // Only the copy replaced by a REPACK is reported
+        let mut repack = movie(5, Some(20), 0.95);
+        repack.file_path = "/movies/Movie.2020.REPACK.1080p.mkv".to_string();
+        let mut original = movie(6, Some(20), 0.95);
+        original.file_path = "/movies/Movie.2020.1080p.mkv".to_string();
+        let duplicates = duplicate_issues(&[repack, original], &identification);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].action.method, "DELETE");
+        assert_eq!(duplicates[0].action.endpoint, "/v2/admin/media/6");
+
+        let mut series = Series::new("Show".to_string()).unwrap();
+        series.id = Some(7);
+        let issues = series_issues(&[series]);
+        assert_eq!((issues[0].action.method, issues[0].action.endpoint.as_str()), ("POST", "/v2/series/7/refresh"));
+    }
+
+    #[test]
+    fn test_health_score() {
+        assert_eq!(health_score(&[], 0), 100);
+        let media = vec![movie(1, None, 0.0)];
+        let issues = media_issues(&media, 0.7);
+        assert_eq!(health_score(&issues, 4), 75);
+    }
+}
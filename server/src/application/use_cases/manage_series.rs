@@ -1,19 +1,39 @@
 //! Manage Series Use Case
 //!
-//! Handles series-related operations.
+//! Handles series-related operations, including the episode that follows
+//! another in aired order (for auto-play).
 
 use std::sync::Arc;
-use crate::domain::entities::Series;
-use crate::domain::repositories::SeriesRepository;
-use crate::shared::error::ApplicationError;
+use serde::Serialize;
+use crate::domain::entities::{Media, Series};
+use crate::domain::repositories::{MediaRepository, SeriesOrder, SeriesRepository};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Season and episode number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct EpisodeNumber {
+    pub season: i32,
+    pub episode: i32,
+}
+
+/// The episode after another in aired order
+#[derive(Debug, Clone)]
+pub struct NextEpisode {
+    pub media: Media,
+    /// Episodes between the two that are not in the library
+    pub missing_episodes: Vec<EpisodeNumber>,
+    /// Whole seasons between the two that are not in the library
+    pub missing_seasons: Vec<i32>,
+}
 
 pub struct ManageSeriesUseCase {
     series_repository: Arc<dyn SeriesRepository>,
+    media_repository: Arc<dyn MediaRepository>,
 }
 
 impl ManageSeriesUseCase {
-    pub fn new(series_repository: Arc<dyn SeriesRepository>) -> Self {
-        Self { series_repository }
+    pub fn new(series_repository: Arc<dyn SeriesRepository>, media_repository: Arc<dyn MediaRepository>) -> Self {
+        Self { series_repository, media_repository }
     }
 
     pub async fn get_series(&self, id: i64) -> Result<Series, ApplicationError> {
@@ -30,4 +50,118 @@ impl ManageSeriesUseCase {
         // I need to check if find_all is in SeriesRepository trait
         Ok(self.series_repository.find_all().await?)
     }
+
+    /// Lists a page of series, with the number of series on all pages
+    pub async fn list_page(
+        &self,
+        order: SeriesOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Series>, i64), ApplicationError> {
+        Ok(self.series_repository.find_page(order, descending, offset, limit).await?)
+    }
+
+    /// Finds the episode to play after an episode
+    ///
+    /// Follows season and episode numbers across season boundaries and
+    /// skips specials (season 0), which have no place in aired order. Gaps
+    /// are skipped over and reported. Returns None after the last episode
+    /// in the library and for specials.
+    ///
+    /// # Errors
+    /// `NotFound` for an unknown media ID, `InvalidInput` for a movie
+    pub async fn next_episode(&self, media_id: i64) -> Result<Option<NextEpisode>, ApplicationError> {
+        let media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Media with ID {} not found", media_id)))?;
+        let Some(series_id) = media.series_id.filter(|_| media.season.is_some() && media.episode.is_some()) else {
+            return Err(DomainError::InvalidInput(format!("Media {} is not an episode", media_id)).into());
+        };
+
+        let episodes = self.media_repository.find_by_series(series_id).await?;
+        Ok(next_in_aired_order(&media, episodes))
+    }
+}
+
+/// The episode following `current` among the episodes of its series
+fn next_in_aired_order(current: &Media, episodes: Vec<Media>) -> Option<NextEpisode> {
+    let season = current.season.filter(|s| *s > 0)?;
+    let episode = current.episode?;
+    // A multi-episode file ends at its last episode
+    let last = current.episode_end.map_or(episode, |end| end.max(episode));
+
+    let media = episodes
+        .into_iter()
+        .filter(|e| e.season.is_some_and(|s| s > 0))
+        .filter(|e| e.season.zip(e.episode).is_some_and(|number| number > (season, last)))
+        .min_by_key(|e| (e.season, e.episode, e.id))?;
+    let (next_season, next_episode) = media.season.zip(media.episode)?;
+
+    // The length of an earlier season is unknown, so across seasons only
+    // the start of the next one is checked
+    let first_missing = if next_season == season { last + 1 } else { 1 };
+    let missing_episodes = (first_missing..next_episode)
+        .map(|episode| EpisodeNumber { season: next_season, episode })
+        .collect();
+    let missing_seasons = (season + 1..next_season).collect();
+
+    Some(NextEpisode { media, missing_episodes, missing_seasons })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MediaType;
+
+    fn episode(id: i64, season: i32, episode: i32) -> Media {
+        let mut media = Media::new(format!("/tv/show/S{:02}E{:02}.mkv", season, episode), MediaType::Episode, "Show".to_string())
+            .unwrap()
+            .with_series_id(Some(1))
+            .with_season(Some(season))
+            .with_episode(Some(episode));
+        media.id = Some(id);
+        media
+    }
+
+    fn next(current: &Media, episodes: &[Media]) -> Option<(i64, Vec<EpisodeNumber>, Vec<i32>)> {
+        next_in_aired_order(current, episodes.to_vec())
+            .map(|n| (n.media.id.unwrap(), n.missing_episodes, n.missing_seasons))
+    }
+
+    #[test]
+    fn test_next_in_aired_order() {
+        let library = vec![
+            episode(5, 2, 1),
+            episode(1, 1, 1),
+            episode(2, 1, 2),
+            episode(9, 0, 1),
+            episode(3, 1, 4),
+            episode(7, 4, 3),
+        ];
+
+        assert_eq!(next(&library[1], &library), Some((2, vec![], vec![])));
+        // Gap inside a season
+        assert_eq!(next(&library[2], &library), Some((3, vec![EpisodeNumber { season: 1, episode: 3 }], vec![])));
+        // Across the season boundary, skipping the special
+        assert_eq!(next(&library[4], &library), Some((5, vec![], vec![])));
+        // Missing season and the start of the next one
+        assert_eq!(
+            next(&library[0], &library),
+            Some((7, vec![EpisodeNumber { season: 4, episode: 1 }, EpisodeNumber { season: 4, episode: 2 }], vec![3]))
+        );
+        // Last episode and specials have no next
+        assert_eq!(next(&library[5], &library), None);
+        assert_eq!(next(&library[3], &library), None);
+    }
+
+    #[test]
+    fn test_next_after_multi_episode_file() {
+        let double = episode(1, 1, 1).with_episode_end(Some(2));
+        let library = vec![double.clone(), episode(2, 1, 2), episode(3, 1, 3)];
+
+        // A duplicate of an episode the file covers is not next
+        assert_eq!(next(&double, &library), Some((3, vec![], vec![])));
+    }
 }
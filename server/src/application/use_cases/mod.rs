@@ -4,4 +4,14 @@ pub mod stream_media;
 pub mod manage_series;
 pub mod get_recently_added;
 pub mod generate_subtitle;
-pub mod batch_generate_subtitles;
\ No newline at end of file
+pub mod batch_generate_subtitles;
+pub mod library_health;
+pub mod subtitle_coverage;
+pub mod batch_watch_state;
+pub mod remap_media_paths;
+pub mod download_subtitle;
+pub mod extract_subtitle;
+pub mod edit_subtitle;
+pub mod explain_identification;
+pub mod organize_media;
+pub mod admin_dashboard;
@@ -0,0 +1,147 @@
+//! Organize Media Use Case
+//!
+//! Proposes canonical filenames for identified media, rendered from the
+//! identified title, year and numbering with the parser's filename
+//! formatter. Quality, release group and container come from the current
+//! filename, and each proposal is parsed back to check it still identifies
+//! the same content.
+
+use std::path::Path;
+use std::sync::Arc;
+use media_identifier::{FilenameFormatter, MediaParser, MediaType as ParsedType, ParsedMedia};
+use serde::Serialize;
+
+use crate::domain::entities::Media;
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Proposed new name of a media file
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameProposal {
+    pub media_id: i64,
+    pub current_path: String,
+    pub proposed_path: String,
+    /// Whether the file already has the proposed name
+    pub unchanged: bool,
+    /// Whether the proposed name parses back to the same title and numbering
+    pub round_trip: bool,
+}
+
+/// Organize Media Use Case
+pub struct OrganizeMediaUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    parser: MediaParser,
+}
+
+impl OrganizeMediaUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            parser: MediaParser::new(),
+        }
+    }
+
+    /// Proposes a filename for a media item
+    ///
+    /// `template` uses the formatter's syntax (e.g.
+    /// `{title} - {SxxEyy}.{ext}`); None picks the movie or episode default.
+    pub async fn preview(&self, media_id: i64, template: Option<&str>) -> Result<RenameProposal, ApplicationError> {
+        let formatter = template
+            .map(FilenameFormatter::new)
+            .transpose()
+            .map_err(|e| DomainError::InvalidInput(format!("Invalid template: {}", e)))?;
+
+        let media = self.media_repository
+            .find_by_id(media_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Media {} not found", media_id)))?;
+        let series_title = match media.series_id {
+            Some(series_id) => self.series_repository.find_by_id(series_id).await?.map(|s| s.title),
+            None => None,
+        };
+
+        let fields = canonical_fields(&self.parser, &media, series_title);
+        let formatter = formatter.unwrap_or_else(|| FilenameFormatter::for_media_type(fields.media_type));
+        let file_name = formatter.format(&fields);
+
+        let reparsed = self.parser.parse(&file_name);
+        let round_trip = reparsed.title.as_deref().map(str::to_lowercase) == fields.title.as_deref().map(str::to_lowercase)
+            && reparsed.episode_info.season == fields.episode_info.season
+            && reparsed.episode_info.episode == fields.episode_info.episode;
+
+        let current = Path::new(&media.file_path);
+        let proposed_path = current.with_file_name(&file_name).to_string_lossy().to_string();
+        Ok(RenameProposal {
+            media_id,
+            unchanged: proposed_path == media.file_path,
+            current_path: media.file_path,
+            proposed_path,
+            round_trip,
+        })
+    }
+}
+
+/// Parsed fields of the current filename, with the identified metadata in
+/// place of the guessed title, year and numbering
+fn canonical_fields(parser: &MediaParser, media: &Media, series_title: Option<String>) -> ParsedMedia {
+    let file_name = Path::new(&media.file_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut fields = parser.parse(&file_name);
+    let year = media.release_date
+        .as_deref()
+        .and_then(|d| d.get(..4))
+        .and_then(|y| y.parse::<u16>().ok());
+
+    if media.media_type.is_episode() {
+        fields.media_type = ParsedType::Episode;
+        if let Some(series_title) = series_title {
+            // Episodes carry their own name once enriched
+            if !media.title.eq_ignore_ascii_case(&series_title) {
+                fields.episode_info.episode_title = Some(media.title.clone());
+            }
+            fields.title = Some(series_title);
+        } else {
+            fields.title = Some(media.title.clone());
+        }
+        fields.episode_info.season = media.season.and_then(|s| u16::try_from(s).ok());
+        fields.episode_info.episode = media.episode.and_then(|e| u16::try_from(e).ok());
+        fields.episode_info.episode_end = media.episode_end.and_then(|e| u16::try_from(e).ok());
+        fields.year = None;
+    } else {
+        fields.media_type = ParsedType::Movie;
+        fields.title = Some(media.title.clone());
+        fields.year = year.or(fields.year);
+        fields.episode_info = Default::default();
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MediaType;
+
+    #[test]
+    fn test_canonical_fields() {
+        let mut media = Media::new(
+            "/media/Shows/dark.matter.105.720p.hdtv-KILLERS.mkv".to_string(),
+            MediaType::Episode,
+            "Episode Five".to_string(),
+        ).unwrap();
+        media.season = Some(1);
+        media.episode = Some(5);
+
+        let fields = canonical_fields(&MediaParser::new(), &media, Some("Dark Matter".to_string()));
+        assert_eq!(
+            FilenameFormatter::for_media_type(fields.media_type).format(&fields),
+            "Dark Matter - S01E05 - Episode Five [720p].mkv"
+        );
+    }
+}
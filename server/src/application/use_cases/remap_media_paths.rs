@@ -0,0 +1,180 @@
+//! Remap Media Paths Use Case
+//!
+//! Rewrites the path prefix of media items after a library was moved (e.g.
+//! `/mnt/old` → `/mnt/new`). Items keep their IDs, so watch state, progress,
+//! collections and caches keyed by media ID stay attached; generated
+//! subtitles live next to the media and move with it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use serde::Serialize;
+
+use crate::domain::repositories::MediaRepository;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Items listed in a report; the counts cover all of them
+const MAX_LISTED: usize = 500;
+
+/// A path remap request
+#[derive(Debug, Clone)]
+pub struct RemapRequest {
+    /// Old path prefix
+    pub from: String,
+    /// New path prefix
+    pub to: String,
+    /// Only report what would change
+    pub dry_run: bool,
+}
+
+/// Rewritten path of one item
+#[derive(Debug, Clone, Serialize)]
+pub struct PathChange {
+    pub media_id: i64,
+    pub old_path: String,
+    pub new_path: String,
+    /// Whether a file exists at the new path
+    pub exists: bool,
+}
+
+/// Item that cannot move because its new path is taken
+///
+/// Usually a scan already picked up the moved file as a new item; deleting
+/// that duplicate and remapping again keeps the original's history.
+#[derive(Debug, Clone, Serialize)]
+pub struct PathConflict {
+    pub media_id: i64,
+    pub new_path: String,
+    pub existing_media_id: i64,
+}
+
+/// Result of a path remap
+#[derive(Debug, Clone, Serialize)]
+pub struct RemapReport {
+    pub dry_run: bool,
+    /// Items under the old prefix
+    pub matched: usize,
+    /// Items whose path was (or would be) rewritten
+    pub remapped: usize,
+    /// Rewritten items without a file at the new path
+    pub missing: usize,
+    pub changes: Vec<PathChange>,
+    pub conflicts: Vec<PathConflict>,
+}
+
+/// Remap Media Paths Use Case
+pub struct RemapMediaPathsUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+}
+
+impl RemapMediaPathsUseCase {
+    pub fn new(media_repository: Arc<dyn MediaRepository>) -> Self {
+        Self { media_repository }
+    }
+
+    /// Previews or applies a prefix rewrite
+    pub async fn execute(&self, request: RemapRequest) -> Result<RemapReport, ApplicationError> {
+        let from = normalize_prefix(&request.from);
+        let to = normalize_prefix(&request.to);
+        if from.is_empty() || to.is_empty() {
+            return Err(DomainError::InvalidInput("Both path prefixes are required".to_string()).into());
+        }
+        if from == to {
+            return Err(DomainError::InvalidInput("Path prefixes are identical".to_string()).into());
+        }
+
+        let media = self.media_repository.find_all().await?;
+        let ids_by_path: HashMap<&str, i64> = media.iter()
+            .filter_map(|m| m.id.map(|id| (m.file_path.as_str(), id)))
+            .collect();
+
+        let mut matched = 0;
+        let mut updates = Vec::new();
+        let mut conflicts = Vec::new();
+        for item in &media {
+            let (Some(media_id), Some(new_path)) = (item.id, remap_path(&item.file_path, from, to)) else {
+                continue;
+            };
+            matched += 1;
+            match ids_by_path.get(new_path.as_str()) {
+                Some(&existing_media_id) => conflicts.push(PathConflict { media_id, new_path, existing_media_id }),
+                None => updates.push((media_id, item.file_path.clone(), new_path)),
+            }
+        }
+
+        let changes: Vec<PathChange> = updates.iter()
+            .map(|(media_id, old_path, new_path)| PathChange {
+                media_id: *media_id,
+                old_path: old_path.clone(),
+                new_path: new_path.clone(),
+                exists: Path::new(new_path).exists(),
+            })
+            .collect();
+        let missing = changes.iter().filter(|c| !c.exists).count();
+
+        let remapped = if request.dry_run {
+            updates.len()
+        } else {
+            let paths: Vec<(i64, String)> = updates.into_iter().map(|(id, _, new_path)| (id, new_path)).collect();
+            let changed = self.media_repository.update_paths(&paths).await? as usize;
+            tracing::info!(
+                "Remapped {} media paths from {} to {} ({} conflicts, {} without a file)",
+                changed, from, to, conflicts.len(), missing
+            );
+            changed
+        };
+
+        Ok(RemapReport {
+            dry_run: request.dry_run,
+            matched,
+            remapped,
+            missing,
+            changes: changes.into_iter().take(MAX_LISTED).collect(),
+            conflicts,
+        })
+    }
+}
+
+/// Strips trailing slashes (a bare "/" stays)
+fn normalize_prefix(prefix: &str) -> &str {
+    let trimmed = prefix.trim();
+    match trimmed.trim_end_matches('/') {
+        "" if !trimmed.is_empty() => "/",
+        stripped => stripped,
+    }
+}
+
+/// Path with `from` replaced by `to`, if it lies under `from`
+///
+/// Prefixes match whole path components: `/mnt/old` does not match
+/// `/mnt/older/film.mkv`.
+fn remap_path(path: &str, from: &str, to: &str) -> Option<String> {
+    let rest = path.strip_prefix(from)?;
+    if rest.is_empty() {
+        return Some(to.to_string());
+    }
+    if !(from.ends_with('/') || rest.starts_with('/')) {
+        return None;
+    }
+    let rest = rest.trim_start_matches('/');
+    let separator = if to.ends_with('/') { "" } else { "/" };
+    Some(format!("{}{}{}", to, separator, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remap_path() {
+        let from = normalize_prefix("/mnt/old/");
+        let to = normalize_prefix("/mnt/new");
+        assert_eq!(remap_path("/mnt/old/Movies/Film.mkv", from, to).as_deref(), Some("/mnt/new/Movies/Film.mkv"));
+        assert_eq!(remap_path("/mnt/older/Film.mkv", from, to), None);
+        assert_eq!(remap_path("/srv/Film.mkv", from, to), None);
+
+        // Moving to and from the root
+        assert_eq!(remap_path("/media/Film.mkv", "/media", normalize_prefix("/")).as_deref(), Some("/Film.mkv"));
+        assert_eq!(remap_path("/Film.mkv", normalize_prefix("/"), "/media").as_deref(), Some("/media/Film.mkv"));
+    }
+}
@@ -1,8 +1,10 @@
 //! Scan Library Use Case
 //!
 //! Orchestrates library scanning process including:
-//! - Directory traversal
+//! - Directory traversal, streamed so files are processed while the walk
+//!   of a large library is still running
 //! - Media identification
+//! - TMDB lookup, once per title rather than once per file
 //! - Confidence scoring
 //! - Database persistence
 //! - Event publishing
@@ -13,22 +15,28 @@
 //! - Batch database operations
 //! - Adaptive concurrency based on system resources
 
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use futures::stream::{self, StreamExt};
-use tokio::sync::Semaphore;
+use tokio::sync::{OnceCell, Semaphore};
 use tracing::{info, warn, error, debug, instrument};
 
 use crate::domain::entities::{Media, Series, Collection};
 use crate::domain::events::{MediaIdentifiedEvent, ScanCompletedEvent};
-use crate::domain::value_objects::ConfidenceScore;
+use crate::domain::value_objects::{ConfidenceScore, IdentificationResult};
 use crate::domain::repositories::{MediaRepository, SeriesRepository, CollectionRepository};
 use crate::domain::services::{IdentificationService, ConfidenceService, TmdbCrossValidator};
 use crate::interfaces::filesystem::DirectoryWalker;
 use crate::interfaces::messaging::EventBus;
-use crate::interfaces::external_services::{TmdbService, VideoAnalyzer};
-use crate::shared::error::ApplicationError;
+use crate::interfaces::external_services::{
+    TmdbService, TmdbMatch, TvDetail, VideoAnalyzer, VideoInfo, ArtworkMirror, ArtworkKind,
+};
+use crate::infrastructure::external::{BatchTmdbClient, BatchSearchRequest, BatchSearchResult};
+use crate::application::services::PlaybackQos;
+use crate::shared::error::{ApplicationError, FilesystemError, TmdbError};
 use crate::shared::text::{TitleNormalizer, FuzzyMatcher};
 
 /// Result of a library scan operation
@@ -54,6 +62,8 @@ pub struct ScanResult {
 #[derive(Debug, Clone)]
 pub struct TmdbEnrichment {
     pub title: String,
+    /// Title in the original production language (None if same as `title`)
+    pub original_title: Option<String>,
     pub overview: Option<String>,
     pub poster_url: Option<String>,
     pub backdrop_url: Option<String>,
@@ -61,6 +71,8 @@ pub struct TmdbEnrichment {
     pub genres: Option<Vec<String>>,
     pub release_date: Option<String>,
     pub duration_seconds: Option<i32>,
+    /// Best trailer watch URL (movies only)
+    pub trailer_url: Option<String>,
     /// For TV shows, include series-specific data
     pub is_tv_show: bool,
     pub status: Option<String>,
@@ -84,12 +96,15 @@ pub struct TmdbEnrichment {
 pub type ProgressCallback = Arc<dyn Fn(ScanProgress) + Send + Sync>;
 
 /// Scan progress information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ScanProgress {
     /// Number of files processed so far
     pub processed: usize,
-    /// Total number of files to process
+    /// Total number of files to process (files found so far while
+    /// `discovering`)
     pub total: usize,
+    /// Whether the directory walk is still finding files
+    pub discovering: bool,
     /// Percentage complete (0.0 to 100.0)
     pub percentage: f64,
     /// Number of files identified
@@ -108,6 +123,7 @@ impl ScanProgress {
         Self {
             processed: 0,
             total,
+            discovering: false,
             percentage: 0.0,
             identified: 0,
             failed: 0,
@@ -137,10 +153,121 @@ impl ScanProgress {
     }
 }
 
+/// Files found by the directory walk of a running scan
+#[derive(Default)]
+struct Discovery {
+    found: AtomicUsize,
+    done: AtomicBool,
+    /// First walk error, reported when nothing could be scanned
+    first_error: std::sync::Mutex<Option<FilesystemError>>,
+}
+
+impl Discovery {
+    /// Progress with the files found so far as the total
+    fn progress(&self) -> ScanProgress {
+        let mut progress = ScanProgress::new(self.found.load(Ordering::SeqCst));
+        progress.discovering = !self.done.load(Ordering::SeqCst);
+        progress
+    }
+}
+
+/// Files identified together before their titles are looked up on TMDB
+const TITLE_BATCH_SIZE: usize = 256;
+
+/// Candidates whose alternative titles are compared when title scores are
+/// ambiguous
+const ALTERNATIVE_TITLE_CANDIDATES: usize = 5;
+
+/// A walked file, identified but not yet looked up on TMDB
+struct PendingFile {
+    path: String,
+    /// None when the file is verified already and skipped
+    identification: Result<Option<IdentificationResult>, ApplicationError>,
+}
+
+/// What files with the same probable title have in common
+///
+/// Episodes of a series share a key whatever their season and episode;
+/// movies also need the same year, as remakes share titles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TitleKey {
+    is_movie: bool,
+    title: String,
+    year: Option<i32>,
+    country: Option<String>,
+}
+
+impl TitleKey {
+    fn of(result: &IdentificationResult) -> Self {
+        let is_movie = result.media_type.is_movie();
+        Self {
+            is_movie,
+            title: TitleNormalizer::normalize_for_comparison(&result.title),
+            year: if is_movie { result.year } else { None },
+            country: result.country.clone(),
+        }
+    }
+}
+
+/// TMDB lookups shared by the files of a running scan
+#[derive(Default)]
+struct ScanTitles {
+    /// Best match of each title looked up (None when TMDB had none)
+    matches: std::sync::Mutex<HashMap<TitleKey, Option<TmdbMatch>>>,
+    /// Details of the shows matched, fetched once per show
+    shows: std::sync::Mutex<HashMap<i64, Arc<OnceCell<Option<TvDetail>>>>>,
+}
+
+impl ScanTitles {
+    /// Title keys of `results` not looked up yet, each with the file that
+    /// stands for all files with that title
+    fn unresolved<'a>(&self, results: impl Iterator<Item = &'a IdentificationResult>) -> HashMap<TitleKey, IdentificationResult> {
+        let matches = self.matches.lock().unwrap();
+        let mut pending: HashMap<TitleKey, &IdentificationResult> = HashMap::new();
+        for result in results {
+            let key = TitleKey::of(result);
+            if matches.contains_key(&key) {
+                continue;
+            }
+            // An episode with its numbers lets candidates be told apart
+            pending
+                .entry(key)
+                .and_modify(|current| {
+                    if current.episode.is_none() && result.episode.is_some() {
+                        *current = result;
+                    }
+                })
+                .or_insert(result);
+        }
+        pending.into_iter().map(|(key, result)| (key, result.clone())).collect()
+    }
+
+    fn best_match(&self, result: &IdentificationResult) -> Option<TmdbMatch> {
+        self.matches.lock().unwrap().get(&TitleKey::of(result)).cloned().flatten()
+    }
+
+    /// Details of a show, fetched by the first episode that needs them
+    ///
+    /// A failed fetch is not kept, so the next episode tries again.
+    async fn show<F, Fut>(&self, tmdb_id: i64, fetch: F) -> Result<Option<TvDetail>, TmdbError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<TvDetail>, TmdbError>>,
+    {
+        let cell = self.shows.lock().unwrap().entry(tmdb_id).or_default().clone();
+        cell.get_or_try_init(fetch).await.cloned()
+    }
+}
+
+/// Whether two title searches ask TMDB the same thing
+fn same_search(a: &BatchSearchRequest, b: &BatchSearchRequest) -> bool {
+    a.is_movie == b.is_movie && a.year == b.year && a.query == b.query
+}
+
 /// Scan Library Use Case
 ///
 /// Orchestrates complete library scanning workflow:
-/// 1. Walks directory tree
+/// 1. Walks directory tree, streaming entries as they are found
 /// 2. Processes files in parallel
 /// 3. Identifies media content
 /// 4. Calculates confidence scores
@@ -176,6 +303,8 @@ pub struct ScanLibraryUseCase<E: EventBus + ?Sized> {
     confidence_service: Arc<dyn ConfidenceService>,
     /// TMDB service for metadata lookup (optional for offline mode)
     tmdb_service: Option<Arc<dyn TmdbService>>,
+    /// Batch TMDB client for the title searches of a scan (optional)
+    batch_tmdb_client: Option<Arc<BatchTmdbClient>>,
     /// TMDB cross-validator for verifying episodes exist (optional)
     tmdb_cross_validator: Option<Arc<dyn TmdbCrossValidator>>,
     /// Video analyzer for extracting duration from video files (optional)
@@ -190,6 +319,10 @@ pub struct ScanLibraryUseCase<E: EventBus + ?Sized> {
     progress_callback: Option<ProgressCallback>,
     /// Progress update interval in milliseconds
     progress_interval_ms: u64,
+    /// Playback QoS policy (optional); holds back file processing during playback
+    playback_qos: Option<Arc<PlaybackQos>>,
+    /// Artwork mirror (optional); stores posters/backdrops locally
+    artwork_mirror: Option<Arc<dyn ArtworkMirror>>,
 }
 
 impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
@@ -229,6 +362,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             identification_service,
             confidence_service,
             tmdb_service: None,
+            batch_tmdb_client: None,
             tmdb_cross_validator: None,
             video_analyzer: None,
             concurrency_limiter: Arc::new(Semaphore::new(max_concurrent)),
@@ -236,6 +370,8 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             force_rescan: false,
             progress_callback: None,
             progress_interval_ms: 1000,
+            playback_qos: None,
+            artwork_mirror: None,
         }
     }
 
@@ -252,6 +388,16 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         self
     }
 
+    /// Sets the batch TMDB client for title searches
+    ///
+    /// The titles of each batch of files are then searched concurrently.
+    /// Without it they are searched one after another through the TMDB
+    /// service; either way each title is searched once per scan.
+    pub fn with_batch_tmdb_client(mut self, client: Arc<BatchTmdbClient>) -> Self {
+        self.batch_tmdb_client = Some(client);
+        self
+    }
+
     /// Sets the TMDB cross-validator for episode validation
     ///
     /// When cross-validator is provided, the scanner will:
@@ -277,6 +423,25 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         self
     }
 
+    /// Sets the playback QoS policy
+    ///
+    /// When provided, each file waits on the policy before processing so that
+    /// scans pause or slow down while media is being streamed.
+    pub fn with_playback_qos(mut self, qos: Arc<PlaybackQos>) -> Self {
+        self.playback_qos = Some(qos);
+        self
+    }
+
+    /// Sets the artwork mirror
+    ///
+    /// When provided, posters, backdrops and episode stills of identified
+    /// files are downloaded into local storage so the library keeps its
+    /// artwork without internet access.
+    pub fn with_artwork_mirror(mut self, mirror: Arc<dyn ArtworkMirror>) -> Self {
+        self.artwork_mirror = Some(mirror);
+        self
+    }
+
     /// Sets maximum concurrent file processing
     ///
     /// # Arguments
@@ -344,6 +509,8 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
     /// # Performance Characteristics
     /// - Processes files in parallel with bounded concurrency
     /// - Uses semaphore to limit concurrent operations
+    /// - Identifies files in batches and looks each title up on TMDB once,
+    ///   so the episodes of a series share one search
     /// - Provides progress updates at configured intervals
     /// - Calculates throughput metrics
     #[instrument(skip(self, root_path))]
@@ -355,24 +522,31 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         info!("Starting library scan at: {}", root_path);
         debug!("Using {} concurrent workers", self.concurrency_limiter.available_permits());
 
-        // Walk directory to find video files
-        let entries = self.directory_walker.walk_videos(path).await?;
-
-        let total_files = entries.len();
-        if total_files == 0 {
-            info!("No video files found in {}", root_path);
-            return Ok(ScanResult {
-                processed_count: 0,
-                identified_count: 0,
-                failed_count: 0,
-                skipped_count: 0,
-                duration_secs: 0,
-                scan_path: root_path.to_string(),
-                files_per_second: 0.0,
-            });
-        }
-
-        info!("Found {} video files to process", total_files);
+        // Walk the directory while processing, so the first files are
+        // identified before the walk of a large library is done
+        let discovery = Arc::new(Discovery::default());
+        let entries = stream::unfold(
+            (self.directory_walker.walk_videos(path), Arc::clone(&discovery)),
+            |(mut walk, discovery)| async move {
+                loop {
+                    match walk.next().await {
+                        Some(Ok(entry)) => {
+                            discovery.found.fetch_add(1, Ordering::SeqCst);
+                            return Some((entry, (walk, discovery)));
+                        }
+                        Some(Err(e)) => {
+                            warn!("Skipping unreadable path during scan: {}", e);
+                            discovery.first_error.lock().unwrap().get_or_insert(e);
+                        }
+                        None => {
+                            discovery.done.store(true, Ordering::SeqCst);
+                            info!("Found {} video files", discovery.found.load(Ordering::SeqCst));
+                            return None;
+                        }
+                    }
+                }
+            },
+        );
 
         // Atomic counters for thread-safe progress tracking
         let processed_count = Arc::new(AtomicUsize::new(0));
@@ -389,34 +563,41 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         let progress_callback = self.progress_callback.clone();
         let progress_interval = Duration::from_millis(self.progress_interval_ms);
 
-        // Process files in parallel with bounded concurrency
-        let results = stream::iter(entries)
-            .map(move |entry| {
+        // Report the start before the first file is found
+        if let Some(ref callback) = progress_callback {
+            callback(discovery.progress());
+        }
+
+        // Identify files in batches so each title is looked up once, then
+        // process them in parallel with bounded concurrency
+        let workers = self.concurrency_limiter.available_permits();
+        let titles = ScanTitles::default();
+        let titles = &titles;
+        let mut results = entries
+            .chunks(TITLE_BATCH_SIZE)
+            .then(move |batch| self.prepare_batch(batch, titles, workers))
+            .flat_map(stream::iter)
+            .map(move |file| {
                 let limiter = Arc::clone(&self.concurrency_limiter);
                 let repo = Arc::clone(&self.media_repository);
                 let event_bus = Arc::clone(&self.event_bus);
-                let force_rescan = self.force_rescan;
-                let rescan_threshold = self.rescan_threshold;
                 
                 async move {
                     // Acquire permit for bounded parallelism
                     let _permit = limiter.acquire().await;
+
+                    if let Some(ref qos) = self.playback_qos {
+                        qos.yield_to_playback().await;
+                    }
                     
-                    self.process_entry_internal(
-                        entry,
-                        repo,
-                        event_bus,
-                        force_rescan,
-                        rescan_threshold,
-                    ).await
+                    self.process_entry_internal(file, repo, event_bus, titles).await
                 }
             })
-            .buffer_unordered(self.concurrency_limiter.available_permits())
-            .collect::<Vec<_>>()
-            .await;
+            .buffer_unordered(workers)
+            .boxed();
 
-        // Aggregate results
-        for result in &results {
+        // Aggregate results as they complete so progress updates are live
+        while let Some(result) = results.next().await {
             match result {
                 Ok(ProcessResult::Identified(_)) => {
                     identified_count_clone.fetch_add(1, Ordering::SeqCst);
@@ -441,12 +622,15 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                 
                 if now.duration_since(*last_update) >= progress_interval {
                     let elapsed = start_time.elapsed();
-                    let mut progress = ScanProgress::new(total_files);
+                    let mut progress = discovery.progress();
                     progress.processed = processed;
                     progress.identified = identified_count_clone.load(Ordering::SeqCst);
                     progress.failed = failed_count_clone.load(Ordering::SeqCst);
                     progress.skipped = skipped_count_clone.load(Ordering::SeqCst);
-                    progress.update_time_remaining(elapsed.as_secs());
+                    // The total is not known while files are still being found
+                    if !progress.discovering {
+                        progress.update_time_remaining(elapsed.as_secs());
+                    }
                     
                     callback(progress);
                     *last_update = now;
@@ -454,6 +638,24 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             }
         }
 
+        let total_files = discovery.found.load(Ordering::SeqCst);
+        if total_files == 0 {
+            // Nothing found because the root itself could not be walked
+            if let Some(e) = discovery.first_error.lock().unwrap().take() {
+                return Err(ApplicationError::Filesystem(e));
+            }
+            info!("No video files found in {}", root_path);
+            return Ok(ScanResult {
+                processed_count: 0,
+                identified_count: 0,
+                failed_count: 0,
+                skipped_count: 0,
+                duration_secs: 0,
+                scan_path: root_path.to_string(),
+                files_per_second: 0.0,
+            });
+        }
+
         // Final progress update
         if let Some(ref callback) = progress_callback {
             let elapsed = start_time.elapsed();
@@ -511,25 +713,54 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         })
     }
 
-    /// Internal method to process a single directory entry
+    /// Identifies a batch of walked files and looks their titles up on TMDB
     ///
-    /// Separated to allow use in async closure
-    async fn process_entry_internal(
+    /// Titles already looked up earlier in the scan are not looked up again.
+    async fn prepare_batch(
         &self,
-        entry: crate::interfaces::filesystem::WalkEntry,
-        media_repository: Arc<dyn MediaRepository>,
-        event_bus: Arc<E>,
-        force_rescan: bool,
-        rescan_threshold: f32,
-    ) -> Result<ProcessResult, ApplicationError> {
-        let file_path = entry.path.to_string_lossy().to_string();
+        entries: Vec<crate::interfaces::filesystem::WalkEntry>,
+        titles: &ScanTitles,
+        workers: usize,
+    ) -> Vec<PendingFile> {
+        if let Some(ref qos) = self.playback_qos {
+            qos.yield_to_playback().await;
+        }
+
+        let files: Vec<PendingFile> = stream::iter(entries)
+            .map(move |entry| async move {
+                let path = entry.path.to_string_lossy().to_string();
+                let identification = self.identify_unverified(&path, &entry).await;
+                PendingFile { path, identification }
+            })
+            .buffer_unordered(workers.max(1))
+            .collect()
+            .await;
+
+        let identified = files.iter().filter_map(|file| file.identification.as_ref().ok().and_then(Option::as_ref));
+        let pending = titles.unresolved(identified);
+        if !pending.is_empty() && self.tmdb_service.is_some() {
+            let count = pending.len();
+            let resolved = self.resolve_titles(pending, workers).await;
+            let matched = resolved.values().filter(|m| m.is_some()).count();
+            info!("Looked up {} titles on TMDB for {} files: {} matched", count, files.len(), matched);
+            titles.matches.lock().unwrap().extend(resolved);
+        }
 
+        files
+    }
+
+    /// Identifies a file unless it is verified already (returns None then)
+    async fn identify_unverified(
+        &self,
+        file_path: &str,
+        entry: &crate::interfaces::filesystem::WalkEntry,
+    ) -> Result<Option<IdentificationResult>, ApplicationError> {
         // Check if media already exists in database
-        if let Some(existing) = media_repository.find_by_path(&file_path).await? {
+        if let Some(existing) = self.media_repository.find_by_path(file_path).await? {
             // Skip if already verified and not forcing rescan
-            if !force_rescan && existing.confidence_score.value() >= rescan_threshold {
+            if !self.force_rescan && existing.confidence_score.value() >= self.rescan_threshold {
                 debug!("Skipping verified media: {}", file_path);
-                return Ok(ProcessResult::Skipped);
+                return Ok(None);
             }
 
             // Re-identify if confidence is low or forcing rescan
@@ -537,11 +768,28 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         }
 
         // Perform identification using the domain IdentificationService
-        let mut identification_result = self.identify_media(&file_path, &entry).await?;
+        self.identify_media(file_path, entry).await.map(Some)
+    }
+
+    /// Internal method to process a single identified file
+    ///
+    /// Separated to allow use in async closure
+    async fn process_entry_internal(
+        &self,
+        file: PendingFile,
+        media_repository: Arc<dyn MediaRepository>,
+        event_bus: Arc<E>,
+        titles: &ScanTitles,
+    ) -> Result<ProcessResult, ApplicationError> {
+        let file_path = file.path;
+        let mut identification_result = match file.identification? {
+            Some(result) => result,
+            None => return Ok(ProcessResult::Skipped),
+        };
 
         // Enrich with TMDB metadata if service is available
         // TMDB failures are non-fatal - we continue without enrichment
-        let tmdb_enrichment = match self.enrich_with_tmdb(&mut identification_result, &file_path).await {
+        let tmdb_enrichment = match self.enrich_with_tmdb(&mut identification_result, titles).await {
             Ok(enrichment) => enrichment,
             Err(e) => {
                 debug!("TMDB enrichment failed for {}: {}", file_path, e);
@@ -600,6 +848,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             if enrichment.is_tv_show {
                 Some((
                     enrichment.title.clone(),
+                    enrichment.original_title.clone(),
                     enrichment.overview.clone(),
                     enrichment.poster_url.clone(),
                     enrichment.backdrop_url.clone(),
@@ -639,6 +888,10 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
 
         if let Some(enrichment) = tmdb_enrichment {
             media.title = enrichment.title;
+            // Episode titles replace the series name below, so only movies keep it
+            if !enrichment.is_tv_show {
+                media.original_title = enrichment.original_title;
+            }
             media.overview = enrichment.overview;
             media.poster_url = enrichment.poster_url;
             media.backdrop_url = enrichment.backdrop_url;
@@ -652,6 +905,9 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             if let Some(duration) = enrichment.duration_seconds {
                 media.duration_seconds = Some(duration);
             }
+            if let Some(trailer_url) = enrichment.trailer_url {
+                media.trailer_url = Some(trailer_url);
+            }
 
             // Apply episode-specific metadata for episodes
             if identification_result.media_type.is_episode() {
@@ -702,9 +958,10 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                     }
                     Ok(None) => {
                         // Create new series from TV show data
-                        if let Some((title, overview, poster_url, backdrop_url, rating, genres, first_air_date, status, total_seasons, total_episodes)) = tv_show_data {
+                        if let Some((title, original_title, overview, poster_url, backdrop_url, rating, genres, first_air_date, status, total_seasons, total_episodes)) = tv_show_data {
                             let mut series = Series::new(title.clone())
                                 .map_err(|e| ApplicationError::Domain(e))?;
+                            series.original_title = original_title;
                             series = series
                                 .with_tmdb_id(Some(tmdb_id))
                                 .with_overview(overview)
@@ -723,6 +980,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                             match self.series_repository.save(&series).await {
                                 Ok(id) => {
                                     info!("Created new series '{}' with ID {}", title, id);
+                                    self.mirror_series_artwork(&series).await;
                                     Some(id)
                                 }
                                 Err(e) => {
@@ -746,6 +1004,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                             match self.series_repository.save(&series).await {
                                 Ok(id) => {
                                     info!("Created new series '{}' with ID {} (from media data)", media.title, id);
+                                    self.mirror_series_artwork(&series).await;
                                     Some(id)
                                 }
                                 Err(e) => {
@@ -782,6 +1041,17 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             media_repository.save(&media).await?
         };
 
+        // Mirror artwork locally (the backdrop of an episode is its series backdrop)
+        if let Some(ref mirror) = self.artwork_mirror {
+            let poster_kind = if media.is_episode() { ArtworkKind::Still } else { ArtworkKind::Poster };
+            if let Some(ref url) = media.poster_url {
+                mirror.mirror(url, poster_kind).await;
+            }
+            if let Some(ref url) = media.backdrop_url {
+                mirror.mirror(url, ArtworkKind::Backdrop).await;
+            }
+        }
+
         // Publish media identified event
         let event = MediaIdentifiedEvent::new(
             media_id,
@@ -839,6 +1109,19 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         Ok(ProcessResult::Identified(media_id))
     }
 
+    /// Mirrors the poster and backdrop of a newly created series
+    async fn mirror_series_artwork(&self, series: &Series) {
+        let Some(ref mirror) = self.artwork_mirror else {
+            return;
+        };
+        if let Some(ref url) = series.poster_url {
+            mirror.mirror(url, ArtworkKind::Poster).await;
+        }
+        if let Some(ref url) = series.backdrop_url {
+            mirror.mirror(url, ArtworkKind::Backdrop).await;
+        }
+    }
+
     /// Identifies media from file path using the domain IdentificationService
     ///
     /// Uses the proper IdentificationService with:
@@ -860,87 +1143,125 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
         Ok(result)
     }
 
-    /// Enriches identification result with TMDB metadata
-    ///
-    /// If TMDB service is available, searches for matching content
-    /// and enriches the result with TMDB ID, poster, rating, etc.
+    /// Looks titles up on TMDB, returning the best match of each
     ///
-    /// For TV shows with multiple candidates, uses structure-based
-    /// disambiguation to find the best match based on local episode structure.
-    async fn enrich_with_tmdb(
+    /// Each title is searched as found in the file names first; titles TMDB
+    /// has nothing for are searched again by their variants (e.g. "Part Two"
+    /// -> "Part II"), a round of searches per variant. The matches are then
+    /// narrowed to the best one using the file standing for the title.
+    async fn resolve_titles(
         &self,
-        result: &mut crate::domain::value_objects::IdentificationResult,
-        file_path: &str,
-    ) -> Result<Option<TmdbEnrichment>, ApplicationError> {
-        let tmdb_service = match &self.tmdb_service {
-            Some(s) => s,
-            None => return Ok(None),
-        };
-
-        // Search TMDB based on media type
-        // First try the original title, then try variants if no results found
-        info!("TMDB lookup for '{}' (year: {:?}), media_type: {:?}",
-            result.title, result.year, result.media_type);
-
-        let mut matches = if result.media_type.is_movie() {
-            let mut results = tmdb_service.search_movie(&result.title, result.year).await?;
-            info!("Initial TMDB search for '{}' returned {} results", result.title, results.len());
-            if !results.is_empty() {
-                for (i, r) in results.iter().enumerate().take(3) {
-                    info!("  Result {}: '{}' (TMDB ID: {})", i, r.title, r.tmdb_id);
+        pending: HashMap<TitleKey, IdentificationResult>,
+        workers: usize,
+    ) -> HashMap<TitleKey, Option<TmdbMatch>> {
+        let mut searches: Vec<_> = pending
+            .into_iter()
+            .map(|(key, result)| {
+                let variants: Vec<String> = TitleNormalizer::get_search_variants(&result.title)
+                    .into_iter()
+                    .filter(|variant| variant.to_lowercase() != result.title.to_lowercase())
+                    .collect();
+                let queries = std::iter::once(result.title.clone()).chain(variants).collect::<Vec<_>>().into_iter();
+                (key, result, queries)
+            })
+            .collect();
+        let mut found = Vec::new();
+
+        while !searches.is_empty() {
+            let mut round = Vec::new();
+            let mut requests = Vec::new();
+            for (key, result, mut queries) in searches {
+                let Some(query) = queries.next() else {
+                    debug!("No TMDB results for '{}' or its variants", result.title);
+                    found.push((key, result, Vec::new()));
+                    continue;
+                };
+                let request = BatchSearchRequest {
+                    query,
+                    // For TV shows, always search WITHOUT year to get all possible candidates
+                    // Structure matching will pick the right one based on season/episode counts
+                    year: if key.is_movie { result.year } else { None },
+                    is_movie: key.is_movie,
+                };
+                if !requests.iter().any(|r: &BatchSearchRequest| same_search(r, &request)) {
+                    requests.push(request.clone());
                 }
+                round.push((key, result, queries, request));
             }
 
-            // If no results found, try title variants (e.g., "Part Two" -> "Part II")
-            if results.is_empty() {
-                let variants = TitleNormalizer::get_search_variants(&result.title);
-                debug!("No TMDB results for '{}', trying {} variants: {:?}",
-                    result.title, variants.len(), variants);
-
-                for variant in &variants {
-                    // Skip if same as original
-                    if variant.to_lowercase() == result.title.to_lowercase() {
-                        continue;
+            let responses = self.search_titles(requests).await;
+            searches = Vec::new();
+            for (key, result, queries, request) in round {
+                let response = responses.iter().find(|r| same_search(&r.request, &request));
+                match response.map(|r| &r.results) {
+                    Some(Ok(matches)) if matches.is_empty() => searches.push((key, result, queries)),
+                    Some(Ok(matches)) => {
+                        if request.query != result.title {
+                            info!("Found TMDB match using variant '{}' for original title '{}'",
+                                request.query, result.title);
+                        }
+                        found.push((key, result, matches.clone()));
                     }
-
-                    debug!("Trying variant: '{}'", variant);
-                    results = tmdb_service.search_movie(variant, result.year).await?;
-
-                    if !results.is_empty() {
-                        info!("Found TMDB match using variant '{}' for original title '{}'",
-                            variant, result.title);
-                        break;
+                    Some(Err(e)) => {
+                        debug!("TMDB search for '{}' failed: {}", request.query, e);
+                        found.push((key, result, Vec::new()));
                     }
+                    None => found.push((key, result, Vec::new())),
                 }
             }
-            results
-        } else {
-            // For TV shows, always search WITHOUT year to get all possible candidates
-            // Structure matching will pick the right one based on season/episode counts
-            let mut results = tmdb_service.search_tv(&result.title, None).await?;
-
-            // If no results, try variants
-            if results.is_empty() {
-                let variants = TitleNormalizer::get_search_variants(&result.title);
-                debug!("No TMDB results for TV '{}', trying {} variants",
-                    result.title, variants.len());
-
-                for variant in &variants {
-                    if variant.to_lowercase() == result.title.to_lowercase() {
-                        continue;
-                    }
+        }
+
+        stream::iter(found)
+            .map(move |(key, result, matches)| async move {
+                let best = if matches.is_empty() { None } else { self.select_match(&result, matches).await };
+                (key, best)
+            })
+            .buffer_unordered(workers.max(1))
+            .collect()
+            .await
+    }
 
-                    results = tmdb_service.search_tv(variant, None).await?;
+    /// Runs title searches, concurrently when the batch client is set
+    async fn search_titles(&self, requests: Vec<BatchSearchRequest>) -> Vec<BatchSearchResult> {
+        if let Some(ref batch_client) = self.batch_tmdb_client {
+            return batch_client.batch_search(requests).await;
+        }
+        let Some(ref tmdb_service) = self.tmdb_service else {
+            return Vec::new();
+        };
 
-                    if !results.is_empty() {
-                        info!("Found TV match using variant '{}' for original title '{}'",
-                            variant, result.title);
-                        break;
-                    }
-                }
+        stream::iter(requests)
+            .then(move |request| async move {
+                let results = if request.is_movie {
+                    tmdb_service.search_movie(&request.query, request.year).await
+                } else {
+                    tmdb_service.search_tv(&request.query, request.year).await
+                };
+                BatchSearchResult { request, results }
+            })
+            .collect()
+            .await
+    }
+
+    /// Picks the best of the TMDB search results for a title
+    ///
+    /// For TV shows with multiple candidates, the episode of `result` rules
+    /// out the shows that do not have it.
+    async fn select_match(&self, result: &IdentificationResult, mut matches: Vec<TmdbMatch>) -> Option<TmdbMatch> {
+        info!("TMDB search for '{}' (year: {:?}), media_type: {:?} returned {} results",
+            result.title, result.year, result.media_type, matches.len());
+        for (i, r) in matches.iter().enumerate().take(3) {
+            info!("  Result {}: '{}' (TMDB ID: {})", i, r.title, r.tmdb_id);
+        }
+
+        // "The Office US" / "The Office UK": keep the shows from that country
+        if let Some(country) = &result.country {
+            if matches.iter().any(|m| m.origin_country.contains(country)) {
+                let before = matches.len();
+                matches.retain(|m| m.origin_country.contains(country));
+                debug!("Kept {} of {} candidates from {} for '{}'", matches.len(), before, country, result.title);
             }
-            results
-        };
+        }
 
         // For TV shows with multiple candidates, filter by episode existence
         // Check if the season/episode from filename exists in each TMDB candidate
@@ -1007,6 +1328,13 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             // Sort by fuzzy score descending
             scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
+            // Files named after a localized title score low against every
+            // candidate, or alike against several: compare their AKAs too
+            let scores: Vec<f64> = scored.iter().map(|(_, score)| *score).collect();
+            if FuzzyMatcher::is_ambiguous(&scores) {
+                self.rescore_with_alternative_titles(&result.title, &mut scored).await;
+            }
+
             if let Some((best, score)) = scored.first() {
                 info!("Selected best match '{}' (TMDB {}) with fuzzy score {:.2} from {} candidates",
                     best.title, best.tmdb_id, score, matches.len());
@@ -1019,6 +1347,53 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
             matches.first().cloned()
         };
 
+        best_match
+    }
+
+    /// Scores the leading candidates by their alternative titles as well,
+    /// then sorts them again
+    async fn rescore_with_alternative_titles(&self, title: &str, scored: &mut [(&TmdbMatch, f64)]) {
+        let Some(ref tmdb_service) = self.tmdb_service else {
+            return;
+        };
+
+        for (candidate, score) in scored.iter_mut().take(ALTERNATIVE_TITLE_CANDIDATES) {
+            match tmdb_service.fetch_alternative_titles(candidate.tmdb_id, &candidate.media_type).await {
+                Ok(alternatives) => {
+                    let fuzzy = FuzzyMatcher::compare_titles_with_alternatives(
+                        title,
+                        &candidate.title,
+                        alternatives.iter().map(|a| a.title.as_str()),
+                    );
+                    if fuzzy.score > *score {
+                        debug!("'{}' matches '{}' (TMDB {}) as '{}' with fuzzy score {:.2}",
+                            title, candidate.title, candidate.tmdb_id, fuzzy.text, fuzzy.score);
+                        *score = fuzzy.score;
+                    }
+                }
+                Err(e) => debug!("Failed to fetch alternative titles of TMDB {}: {}", candidate.tmdb_id, e),
+            }
+        }
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    /// Enriches identification result with TMDB metadata
+    ///
+    /// Uses the match looked up for the title of the file, and enriches
+    /// the result with TMDB ID, poster, rating, etc. Show details are
+    /// fetched once per show; episode details for each episode.
+    async fn enrich_with_tmdb(
+        &self,
+        result: &mut IdentificationResult,
+        titles: &ScanTitles,
+    ) -> Result<Option<TmdbEnrichment>, ApplicationError> {
+        let tmdb_service = match &self.tmdb_service {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+
+        let best_match = titles.best_match(result);
         if let Some(ref best_match) = best_match {
             result.tmdb_id = Some(best_match.tmdb_id);
             result.strategy = best_match.strategy.clone();
@@ -1041,7 +1416,16 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                             (None, None, None, None)
                         };
 
+                    let trailer_url = match tmdb_service.fetch_videos(best_match.tmdb_id, "movie").await {
+                        Ok(videos) => VideoInfo::best_trailer(&videos).and_then(|v| v.watch_url()),
+                        Err(e) => {
+                            debug!("Failed to fetch videos for movie {}: {}", best_match.tmdb_id, e);
+                            None
+                        }
+                    };
+
                     Some(TmdbEnrichment {
+                        original_title: details.original_title.filter(|t| *t != details.title),
                         title: details.title,
                         overview: Some(details.overview),
                         poster_url: details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)),
@@ -1050,6 +1434,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                         genres: Some(details.genres.iter().map(|g| g.name.clone()).collect()),
                         release_date: Some(details.release_date),
                         duration_seconds: details.runtime.map(|r| r * 60),
+                        trailer_url,
                         // Not a TV show
                         is_tv_show: false,
                         status: None,
@@ -1070,7 +1455,10 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                     None
                 }
             } else {
-                if let Some(details) = tmdb_service.fetch_tv_details(best_match.tmdb_id).await? {
+                let details = titles
+                    .show(best_match.tmdb_id, || tmdb_service.fetch_tv_details(best_match.tmdb_id))
+                    .await?;
+                if let Some(details) = details {
                     // Fetch episode-specific metadata if we have season/episode numbers
                     let (episode_title, episode_overview, episode_still_url, episode_air_date) =
                         if let (Some(season), Some(episode)) = (result.season, result.episode) {
@@ -1101,6 +1489,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                         };
 
                     Some(TmdbEnrichment {
+                        original_title: details.original_name.filter(|t| *t != details.name),
                         title: details.name,
                         overview: Some(details.overview),
                         poster_url: details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)),
@@ -1109,6 +1498,7 @@ impl<E: EventBus + ?Sized> ScanLibraryUseCase<E> {
                         genres: Some(details.genres.iter().map(|g| g.name.clone()).collect()),
                         release_date: Some(details.first_air_date),
                         duration_seconds: None,
+                        trailer_url: None,
                         // TV show specific fields
                         is_tv_show: true,
                         status: Some(details.status),
@@ -1253,4 +1643,50 @@ mod tests {
         // Should be approximately 60 seconds for remaining 50 files
         assert!((remaining - 60.0).abs() < 1.0);
     }
+
+    #[tokio::test]
+    async fn test_titles_looked_up_once() {
+        use crate::domain::value_objects::{MatchStrategy, MediaType};
+
+        let episode = |title: &str, season, number| {
+            IdentificationResult::new(MediaType::Episode, title.to_string(), MatchStrategy::FilenameWithYear)
+                .with_season(Some(season))
+                .with_episode(Some(number))
+        };
+        let movie = |title: &str, year| {
+            IdentificationResult::new(MediaType::Movie, title.to_string(), MatchStrategy::FilenameWithYear)
+                .with_year(Some(year))
+        };
+        let files = vec![
+            IdentificationResult::new(MediaType::Episode, "The Wire".to_string(), MatchStrategy::FilenameWithYear),
+            episode("The Wire", 1, 1),
+            episode("the wire", 3, 7),
+            episode("The Office", 1, 1).with_country(Some("US".to_string())),
+            episode("The Office", 1, 1).with_country(Some("GB".to_string())),
+            movie("Dune", 1984),
+            movie("Dune", 2021),
+            movie("Dune", 2021),
+        ];
+
+        // Episodes of a series share a title, standing for it with their episode
+        let titles = ScanTitles::default();
+        let pending = titles.unresolved(files.iter());
+        assert_eq!(pending.len(), 5);
+        assert_eq!(pending[&TitleKey::of(&files[0])].episode, Some(1));
+
+        titles.matches.lock().unwrap().extend(pending.into_keys().map(|key| (key, None)));
+        assert!(titles.unresolved(files.iter()).is_empty());
+        assert_eq!(titles.unresolved(std::iter::once(&movie("Dune", 2000))).len(), 1);
+
+        // Show details are fetched once; failed fetches are retried
+        let fetches = AtomicUsize::new(0);
+        let fetch = |fail: bool| {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            async move { if fail { Err(TmdbError::RateLimitExceeded) } else { Ok(None) } }
+        };
+        assert!(titles.show(1438, || fetch(true)).await.is_err());
+        assert!(titles.show(1438, || fetch(false)).await.unwrap().is_none());
+        assert!(titles.show(1438, || fetch(false)).await.unwrap().is_none());
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
 }
@@ -11,9 +11,10 @@ use std::path::Path;
 use tracing::{info, debug, warn, error};
 
 use crate::domain::entities::Media;
-use crate::domain::repositories::MediaRepository;
-use crate::interfaces::external_services::VideoAnalyzer;
-use crate::shared::error::ApplicationError;
+use crate::domain::repositories::{AccessibilityPreferenceRepository, AudioPreferenceRepository, MediaRepository, ProfileRepository};
+use crate::infrastructure::subtitle::normalize_language_code;
+use crate::interfaces::external_services::{AudioTrack, VideoAnalyzer};
+use crate::shared::error::{ApplicationError, DomainError};
 
 /// Streaming configuration
 #[derive(Debug, Clone)]
@@ -79,6 +80,12 @@ pub struct StreamMediaUseCase {
     video_analyzer: Arc<dyn VideoAnalyzer>,
     /// Default streaming configuration
     default_config: StreamConfig,
+    /// Remembered audio languages (None = always the default track)
+    audio_preferences: Option<Arc<dyn AudioPreferenceRepository>>,
+    /// Whether users want audio description tracks
+    accessibility_preferences: Option<Arc<dyn AccessibilityPreferenceRepository>>,
+    /// Profiles whose audio language is the default of their user
+    profiles: Option<Arc<dyn ProfileRepository>>,
 }
 
 impl StreamMediaUseCase {
@@ -95,9 +102,33 @@ impl StreamMediaUseCase {
             media_repository,
             video_analyzer,
             default_config: StreamConfig::default(),
+            audio_preferences: None,
+            accessibility_preferences: None,
+            profiles: None,
         }
     }
 
+    /// Remembers the audio language each user picks
+    pub fn with_audio_preferences(mut self, audio_preferences: Arc<dyn AudioPreferenceRepository>) -> Self {
+        self.audio_preferences = Some(audio_preferences);
+        self
+    }
+
+    /// Picks audio description tracks for users who asked for them
+    pub fn with_accessibility_preferences(
+        mut self,
+        accessibility_preferences: Arc<dyn AccessibilityPreferenceRepository>,
+    ) -> Self {
+        self.accessibility_preferences = Some(accessibility_preferences);
+        self
+    }
+
+    /// Picks the audio language of each user's active profile by default
+    pub fn with_profiles(mut self, profiles: Arc<dyn ProfileRepository>) -> Self {
+        self.profiles = Some(profiles);
+        self
+    }
+
     /// Sets the default streaming configuration
     pub fn with_config(mut self, config: StreamConfig) -> Self {
         self.default_config = config;
@@ -445,6 +476,74 @@ impl StreamMediaUseCase {
         })
     }
 
+    /// Selects the audio track to stream
+    ///
+    /// An explicitly requested track is used as-is and its language is
+    /// remembered for the user. Otherwise the track in the audio language of
+    /// the user's active profile, or else in the user's remembered language,
+    /// is picked, falling back to the default track; users who want audio
+    /// description get its described version when there is one.
+    ///
+    /// # Arguments
+    /// * `tracks` - Audio tracks of the file in stream order (`0:a:N`)
+    /// * `requested` - Track index chosen by the client
+    ///
+    /// # Errors
+    /// Returns `DomainError::InvalidInput` if the requested track does not exist
+    pub async fn select_audio_track(
+        &self,
+        user_id: &str,
+        tracks: &[AudioTrack],
+        requested: Option<usize>,
+    ) -> Result<usize, ApplicationError> {
+        if let Some(index) = requested {
+            // Files without probed tracks keep the client's choice
+            if tracks.is_empty() {
+                return Ok(index);
+            }
+            let track = tracks.get(index).ok_or_else(|| {
+                DomainError::InvalidInput(format!("Audio track {} does not exist ({} tracks)", index, tracks.len()))
+            })?;
+
+            let language = track.language.as_deref()
+                .filter(|l| !l.eq_ignore_ascii_case("und"))
+                .and_then(normalize_language_code);
+            if let (Some(preferences), Some(language)) = (&self.audio_preferences, language) {
+                if let Err(e) = preferences.save(user_id, &language).await {
+                    warn!("Failed to save audio preference for {}: {}", user_id, e);
+                }
+            }
+            return Ok(index);
+        }
+
+        let profile_language = match &self.profiles {
+            Some(profiles) => profiles.find_active(user_id).await.unwrap_or_else(|e| {
+                warn!("Failed to load active profile of {}: {}", user_id, e);
+                None
+            }),
+            None => None,
+        }
+        .and_then(|profile| profile.settings.audio_language)
+        .and_then(|language| normalize_language_code(&language));
+        let preferred = match (profile_language, &self.audio_preferences) {
+            (Some(language), _) => Some(language),
+            (None, Some(preferences)) => preferences.find(user_id).await.unwrap_or_else(|e| {
+                warn!("Failed to load audio preference for {}: {}", user_id, e);
+                None
+            }),
+            (None, None) => None,
+        };
+        let audio_description = match &self.accessibility_preferences {
+            Some(preferences) => preferences.find(user_id).await.unwrap_or_else(|e| {
+                warn!("Failed to load accessibility preferences for {}: {}", user_id, e);
+                None
+            }),
+            None => None,
+        }
+        .is_some_and(|p| p.audio_description);
+        Ok(pick_audio_track(tracks, preferred.as_deref(), audio_description))
+    }
+
     /// Validates if a file is streamable
     ///
     /// # Arguments
@@ -465,3 +564,78 @@ impl StreamMediaUseCase {
         Ok(streamable_extensions.contains(&extension.to_lowercase().as_str()))
     }
 }
+
+/// Index of the track in `language`, else of the default track, else 0
+///
+/// Among tracks in the same language, the audio description one is picked
+/// when `audio_description` is set and avoided otherwise.
+fn pick_audio_track(tracks: &[AudioTrack], language: Option<&str>, audio_description: bool) -> usize {
+    let language_of = |t: &AudioTrack| t.language.as_deref().and_then(normalize_language_code);
+
+    if let Some(language) = language {
+        let in_language = |t: &AudioTrack| language_of(t).as_deref() == Some(language);
+        let preferred = tracks.iter().position(|t| in_language(t) && t.is_audio_description == audio_description)
+            .or_else(|| tracks.iter().position(in_language));
+        if let Some(index) = preferred {
+            return index;
+        }
+    }
+
+    let default = tracks.iter().position(|t| t.is_default).unwrap_or(0);
+    if audio_description {
+        if let Some(track) = tracks.get(default) {
+            let language = language_of(track);
+            if let Some(index) = tracks.iter().position(|t| t.is_audio_description && language_of(t) == language) {
+                return index;
+            }
+        }
+    }
+    default
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(index: usize, language: &str, is_default: bool) -> AudioTrack {
+        AudioTrack {
+            index,
+            language: Some(language.to_string()),
+            codec: Some("ac3".to_string()),
+            sample_rate: None,
+            channels: Some(6),
+            bitrate: None,
+            title: None,
+            is_default,
+            is_audio_description: false,
+        }
+    }
+
+    #[test]
+    fn test_pick_audio_track() {
+        let tracks = vec![track(0, "eng", false), track(1, "hun", true), track(2, "ger", false)];
+        assert_eq!(pick_audio_track(&tracks, Some("de"), false), 2);
+        assert_eq!(pick_audio_track(&tracks, Some("en"), false), 0);
+        // Unknown preference and no preference fall back to the default track
+        assert_eq!(pick_audio_track(&tracks, Some("ja"), false), 1);
+        assert_eq!(pick_audio_track(&tracks, None, false), 1);
+        assert_eq!(pick_audio_track(&[], Some("en"), false), 0);
+    }
+
+    #[test]
+    fn test_pick_audio_description_track() {
+        let described = |index: usize, language: &str| AudioTrack { is_audio_description: true, ..track(index, language, false) };
+        let tracks = vec![described(0, "eng"), track(1, "eng", true), track(2, "hun", false), described(3, "hun")];
+
+        // The described version of the language, and only when wanted
+        assert_eq!(pick_audio_track(&tracks, Some("en"), true), 0);
+        assert_eq!(pick_audio_track(&tracks, Some("en"), false), 1);
+        assert_eq!(pick_audio_track(&tracks, Some("hu"), true), 3);
+        assert_eq!(pick_audio_track(&tracks, Some("hu"), false), 2);
+        // Without a language, the described version of the default track
+        assert_eq!(pick_audio_track(&tracks, None, true), 0);
+        assert_eq!(pick_audio_track(&tracks, None, false), 1);
+        // No described track: the regular one
+        assert_eq!(pick_audio_track(&tracks[1..3], Some("hu"), true), 1);
+    }
+}
@@ -0,0 +1,350 @@
+//! Subtitle Coverage Use Case
+//!
+//! Reports which items have subtitles in each language, per library
+//! (movies, series) and per series, counting external, embedded and
+//! generated subtitles separately. Gaps are listed as movie IDs and series
+//! seasons, the targets of subtitle generation.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+
+use crate::domain::entities::{Media, Series};
+use crate::domain::repositories::{GeneratedSubtitleRepository, MediaRepository, SeriesRepository};
+use crate::infrastructure::subtitle::{normalize_language_code, SubtitleDetector, SubtitleStore};
+use crate::interfaces::external_services::VideoAnalyzer;
+use crate::shared::error::ApplicationError;
+
+/// Concurrent FFprobe runs when checking embedded subtitles
+const PROBE_CONCURRENCY: usize = 4;
+
+/// Options for a coverage report
+#[derive(Debug, Clone, Default)]
+pub struct SubtitleCoverageOptions {
+    /// Languages to report (empty = the configured languages)
+    pub languages: Vec<String>,
+    /// Whether to probe files for embedded subtitle tracks
+    pub probe_embedded: bool,
+}
+
+/// Coverage of one language
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LanguageCoverage {
+    /// Items with a subtitle in the language from any source
+    pub covered: usize,
+    /// Items without one
+    pub missing: usize,
+    pub external: usize,
+    pub embedded: usize,
+    pub generated: usize,
+}
+
+/// Coverage of a library (all movies or all episodes)
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryCoverage {
+    /// "movies" or "series"
+    pub library: &'static str,
+    pub total: usize,
+    pub languages: BTreeMap<String, LanguageCoverage>,
+}
+
+/// Coverage of the episodes of a series
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesCoverage {
+    pub series_id: i64,
+    pub title: String,
+    pub total: usize,
+    pub languages: BTreeMap<String, LanguageCoverage>,
+    /// Seasons with at least one episode missing the language
+    pub missing_seasons: BTreeMap<String, Vec<i32>>,
+}
+
+/// Subtitle coverage report
+#[derive(Debug, Clone, Serialize)]
+pub struct SubtitleCoverageReport {
+    pub languages: Vec<String>,
+    /// Whether embedded tracks were counted
+    pub embedded_checked: bool,
+    pub libraries: Vec<LibraryCoverage>,
+    pub series: Vec<SeriesCoverage>,
+    /// Movies missing each language
+    pub missing_movies: BTreeMap<String, Vec<i64>>,
+}
+
+/// Subtitle languages of one media item, by source
+#[derive(Debug, Clone, Default)]
+struct ItemSubtitles {
+    external: HashSet<String>,
+    embedded: HashSet<String>,
+    generated: HashSet<String>,
+}
+
+impl ItemSubtitles {
+    fn languages(&self) -> impl Iterator<Item = &String> {
+        self.external.iter().chain(&self.embedded).chain(&self.generated)
+    }
+}
+
+impl LanguageCoverage {
+    /// Counts an item, returning whether it has the language
+    fn add(&mut self, item: &ItemSubtitles, language: &str) -> bool {
+        let external = item.external.contains(language);
+        let embedded = item.embedded.contains(language);
+        let generated = item.generated.contains(language);
+        self.external += external as usize;
+        self.embedded += embedded as usize;
+        self.generated += generated as usize;
+
+        let covered = external || embedded || generated;
+        if covered {
+            self.covered += 1;
+        } else {
+            self.missing += 1;
+        }
+        covered
+    }
+}
+
+/// Subtitle Coverage Use Case
+pub struct SubtitleCoverageUseCase {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    generated_subtitles: Arc<dyn GeneratedSubtitleRepository>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    /// Languages reported when a request names none (empty = all found)
+    default_languages: Vec<String>,
+    /// Where downloaded subtitles of read-only media are (None = next to
+    /// the video only)
+    subtitle_store: Option<Arc<SubtitleStore>>,
+}
+
+impl SubtitleCoverageUseCase {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        generated_subtitles: Arc<dyn GeneratedSubtitleRepository>,
+        video_analyzer: Arc<dyn VideoAnalyzer>,
+    ) -> Self {
+        Self {
+            media_repository,
+            series_repository,
+            generated_subtitles,
+            video_analyzer,
+            default_languages: Vec::new(),
+            subtitle_store: None,
+        }
+    }
+
+    /// Sets the languages reported by default
+    pub fn with_default_languages(mut self, languages: Vec<String>) -> Self {
+        self.default_languages = languages;
+        self
+    }
+
+    /// Also counts subtitles kept in the data directory
+    pub fn with_subtitle_store(mut self, store: Arc<SubtitleStore>) -> Self {
+        self.subtitle_store = Some(store);
+        self
+    }
+
+    /// Builds the coverage report
+    pub async fn execute(&self, options: SubtitleCoverageOptions) -> Result<SubtitleCoverageReport, ApplicationError> {
+        let media = self.media_repository.find_all().await?;
+        let series = self.series_repository.find_all().await?;
+        let generated_paths: HashSet<String> = self.generated_subtitles.find_all().await?
+            .into_iter()
+            .map(|s| s.subtitle_path)
+            .collect();
+
+        // Discovering external subtitles lists every media directory
+        let mut items = {
+            let media = media.clone();
+            let store = self.subtitle_store.clone();
+            tokio::task::spawn_blocking(move || external_subtitles(&media, &generated_paths, store.as_deref()))
+                .await
+                .map_err(|e| ApplicationError::Internal(e.to_string()))?
+        };
+
+        if options.probe_embedded {
+            let paths: Vec<String> = media.iter().map(|m| m.file_path.clone()).collect();
+            let embedded: Vec<HashSet<String>> = stream::iter(paths)
+                .map(|path| {
+                    let analyzer = self.video_analyzer.clone();
+                    async move { (analyzer.analyze(&path).await, path) }
+                })
+                .buffered(PROBE_CONCURRENCY)
+                .map(|(analysis, path)| match analysis {
+                    Ok(analysis) => analysis.subtitle_tracks
+                        .iter()
+                        .filter_map(|t| t.language.as_deref().and_then(normalize_language_code))
+                        .collect(),
+                    Err(e) => {
+                        tracing::debug!("Skipping embedded subtitles of {}: {}", path, e);
+                        HashSet::new()
+                    }
+                })
+                .collect()
+                .await;
+            for (item, languages) in items.iter_mut().zip(embedded) {
+                item.embedded = languages;
+            }
+        }
+
+        let requested = if options.languages.is_empty() { &self.default_languages } else { &options.languages };
+        let languages: Vec<String> = if requested.is_empty() {
+            // Everything that occurs in the library
+            items.iter().flat_map(|i| i.languages().cloned()).collect::<BTreeSet<_>>().into_iter().collect()
+        } else {
+            requested.iter()
+                .map(|l| normalize_language_code(l).unwrap_or_else(|| l.to_lowercase()))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        };
+
+        Ok(build_report(&media, &series, &items, languages, options.probe_embedded))
+    }
+}
+
+/// External subtitle languages of each item, split into generated and not
+fn external_subtitles(
+    media: &[Media],
+    generated_paths: &HashSet<String>,
+    store: Option<&SubtitleStore>,
+) -> Vec<ItemSubtitles> {
+    media.iter()
+        .map(|m| {
+            let detector = match (store, m.id) {
+                (Some(store), Some(id)) => store.detector(id),
+                _ => SubtitleDetector::new(),
+            };
+            let mut item = ItemSubtitles::default();
+            for subtitle in detector.discover(Path::new(&m.file_path)) {
+                let Some(language) = subtitle.language else {
+                    continue;
+                };
+                if generated_paths.contains(&subtitle.file_path) {
+                    item.generated.insert(language);
+                } else {
+                    item.external.insert(language);
+                }
+            }
+            item
+        })
+        .collect()
+}
+
+fn build_report(
+    media: &[Media],
+    series: &[Series],
+    items: &[ItemSubtitles],
+    languages: Vec<String>,
+    embedded_checked: bool,
+) -> SubtitleCoverageReport {
+    let mut movies = LibraryCoverage { library: "movies", total: 0, languages: BTreeMap::new() };
+    let mut episodes = LibraryCoverage { library: "series", total: 0, languages: BTreeMap::new() };
+    let mut per_series: HashMap<i64, SeriesCoverage> = HashMap::new();
+    let mut missing_movies: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+
+    for (m, item) in media.iter().zip(items) {
+        if !m.is_episode() {
+            movies.total += 1;
+            for language in &languages {
+                if !movies.languages.entry(language.clone()).or_default().add(item, language) {
+                    missing_movies.entry(language.clone()).or_default().extend(m.id);
+                }
+            }
+            continue;
+        }
+
+        episodes.total += 1;
+        for language in &languages {
+            episodes.languages.entry(language.clone()).or_default().add(item, language);
+        }
+
+        let Some(series_id) = m.series_id else {
+            continue;
+        };
+        let coverage = per_series.entry(series_id).or_insert_with(|| SeriesCoverage {
+            series_id,
+            title: series.iter()
+                .find(|s| s.id == Some(series_id))
+                .map(|s| s.title.clone())
+                .unwrap_or_default(),
+            total: 0,
+            languages: BTreeMap::new(),
+            missing_seasons: BTreeMap::new(),
+        });
+        coverage.total += 1;
+        for language in &languages {
+            if !coverage.languages.entry(language.clone()).or_default().add(item, language) {
+                let seasons = coverage.missing_seasons.entry(language.clone()).or_default();
+                let season = m.season.unwrap_or(0);
+                if !seasons.contains(&season) {
+                    seasons.push(season);
+                }
+            }
+        }
+    }
+
+    let mut series: Vec<SeriesCoverage> = per_series.into_values().collect();
+    for s in &mut series {
+        s.missing_seasons.values_mut().for_each(|seasons| seasons.sort_unstable());
+    }
+    series.sort_by(|a, b| a.title.cmp(&b.title));
+
+    SubtitleCoverageReport {
+        languages,
+        embedded_checked,
+        libraries: vec![movies, episodes],
+        series,
+        missing_movies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::MediaType;
+
+    fn item(external: &[&str], embedded: &[&str], generated: &[&str]) -> ItemSubtitles {
+        let set = |l: &[&str]| l.iter().map(|s| s.to_string()).collect();
+        ItemSubtitles { external: set(external), embedded: set(embedded), generated: set(generated) }
+    }
+
+    #[test]
+    fn test_coverage_by_library_and_series() {
+        let mut film = Media::new("/movies/film.mkv".to_string(), MediaType::Movie, "Film".to_string()).unwrap();
+        film.id = Some(1);
+        let episode = |id: i64, season: i32| {
+            let mut m = Media::new(format!("/tv/show/{}.mkv", id), MediaType::Episode, "Show".to_string()).unwrap();
+            m.id = Some(id);
+            m.series_id = Some(7);
+            m.season = Some(season);
+            m
+        };
+        let media = vec![film, episode(2, 1), episode(3, 1), episode(4, 2)];
+        let items = vec![
+            item(&["en"], &[], &[]),
+            item(&[], &["hu"], &[]),
+            item(&[], &[], &["hu"]),
+            item(&["en"], &[], &[]),
+        ];
+
+        let report = build_report(&media, &[], &items, vec!["en".to_string(), "hu".to_string()], true);
+
+        assert_eq!(report.libraries[0].languages["en"].covered, 1);
+        assert_eq!(report.missing_movies["hu"], vec![1]);
+
+        let episodes = &report.libraries[1].languages["hu"];
+        assert_eq!((episodes.covered, episodes.missing), (2, 1));
+        assert_eq!((episodes.embedded, episodes.generated, episodes.external), (1, 1, 0));
+
+        let show = &report.series[0];
+        assert_eq!(show.total, 3);
+        assert_eq!(show.missing_seasons["hu"], vec![2]);
+        assert_eq!(show.missing_seasons["en"], vec![1]);
+    }
+}
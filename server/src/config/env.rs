@@ -0,0 +1,272 @@
+//! Environment variable overrides
+//!
+//! Every setting keeps the variable it was configured with before config
+//! files existed. Set variables override the file; empty ones are ignored.
+
+use std::net::Ipv4Addr;
+
+use media_identifier::ParserProfile;
+
+use super::{Config, WhisperBackend};
+use crate::domain::value_objects::CronSchedule;
+use crate::application::services::playback_qos::QosMode;
+use crate::infrastructure::external::whisper::VadDetector;
+use crate::infrastructure::logging::LogFormat;
+
+/// A setting that can be parsed from its environment variable
+pub trait ConfigValue: Sized {
+    /// Parses a value, explaining what was expected when it is invalid
+    fn parse_value(value: &str) -> Result<Self, String>;
+}
+
+impl ConfigValue for String {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+impl ConfigValue for Option<String> {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        Ok(Some(value.trim().to_string()))
+    }
+}
+
+/// Comma-separated
+impl ConfigValue for Vec<String> {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        Ok(value.split(',').map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect())
+    }
+}
+
+impl ConfigValue for bool {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "1" | "true" | "on" | "yes" => Ok(true),
+            "0" | "false" | "off" | "no" => Ok(false),
+            _ => Err(format!("invalid value '{}', expected true or false", value)),
+        }
+    }
+}
+
+macro_rules! numeric_values {
+    ($($number:ty),*) => {
+        $(
+            impl ConfigValue for $number {
+                fn parse_value(value: &str) -> Result<Self, String> {
+                    value.trim().parse().map_err(|_| format!("invalid value '{}', expected a whole number", value))
+                }
+            }
+
+            impl ConfigValue for Option<$number> {
+                fn parse_value(value: &str) -> Result<Self, String> {
+                    <$number>::parse_value(value).map(Some)
+                }
+            }
+        )*
+    };
+}
+
+numeric_values!(u16, u32, u64, usize);
+
+impl ConfigValue for Option<Ipv4Addr> {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid value '{}', expected an IPv4 address", value))
+    }
+}
+
+impl ConfigValue for LogFormat {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("unknown log format '{}', expected text or json", value)),
+        }
+    }
+}
+
+impl ConfigValue for QosMode {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        QosMode::parse(value).ok_or_else(|| format!("unknown mode '{}', expected pause, throttle or off", value))
+    }
+}
+
+impl ConfigValue for ParserProfile {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        ParserProfile::from_name(value).ok_or_else(|| {
+            let names: Vec<&str> = ParserProfile::ALL.iter().map(|p| p.name()).collect();
+            format!("unknown parser profile '{}', expected one of: {}", value.trim(), names.join(", "))
+        })
+    }
+}
+
+/// "Folder=profile" pairs, comma-separated
+impl ConfigValue for Vec<(String, ParserProfile)> {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| match entry.split_once('=') {
+                Some((folder, profile)) => Ok((folder.trim().to_string(), ParserProfile::parse_value(profile)?)),
+                None => Err(format!("invalid entry '{}', expected folder=profile", entry.trim())),
+            })
+            .collect()
+    }
+}
+
+impl ConfigValue for WhisperBackend {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "cli" => Ok(WhisperBackend::Cli),
+            "native" => Ok(WhisperBackend::Native),
+            _ => Err(format!("unknown backend '{}', expected cli or native", value)),
+        }
+    }
+}
+
+impl ConfigValue for CronSchedule {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// "false" turns detection off, "true" uses the default detector
+impl ConfigValue for Option<VadDetector> {
+    fn parse_value(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "false" | "off" => Ok(None),
+            "true" | "on" => Ok(Some(VadDetector::default())),
+            name => VadDetector::from_name(name)
+                .map(Some)
+                .ok_or_else(|| format!("unknown detector '{}', expected true, false, energy or silencedetect", value)),
+        }
+    }
+}
+
+/// Applies set variables to settings, collecting the invalid ones
+struct Overrides<F> {
+    lookup: F,
+    problems: Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> Overrides<F> {
+    fn set<T: ConfigValue>(&mut self, name: &str, target: &mut T) {
+        let Some(value) = (self.lookup)(name).filter(|v| !v.trim().is_empty()) else {
+            return;
+        };
+        match T::parse_value(&value) {
+            Ok(parsed) => *target = parsed,
+            Err(e) => self.problems.push(format!("{}: {}", name, e)),
+        }
+    }
+}
+
+/// Overrides settings with the variables `lookup` finds, returning the
+/// problems with their values
+pub(super) fn apply(config: &mut Config, lookup: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let mut env = Overrides { lookup, problems: Vec::new() };
+
+    let server = &mut config.server;
+    env.set("PORT", &mut server.port);
+    env.set("DATABASE_URL", &mut server.database_url);
+    env.set("LOG_FORMAT", &mut server.log_format);
+    env.set("READINESS_OPTIONAL", &mut server.readiness_optional);
+    env.set("SLOW_REQUEST_MS", &mut server.slow_request_ms);
+    env.set("SLOW_QUERY_MS", &mut server.slow_query_ms);
+    env.set("COMPRESSION", &mut server.compression);
+    env.set("COMPRESSION_MIN_BYTES", &mut server.compression_min_bytes);
+    env.set("AUDIT_RETENTION_DAYS", &mut server.audit_retention_days);
+    env.set("MIGRATE_DRY_RUN", &mut server.migrate_dry_run);
+
+    let library = &mut config.library;
+    env.set("MEDIA_DIR", &mut library.media_dir);
+    env.set("SCAN_INTERVAL_SECS", &mut library.scan_interval_secs);
+    env.set("PARSER_PROFILE", &mut library.parser_profile);
+    env.set("PARSER_PROFILES", &mut library.parser_profiles);
+    env.set("GENRE_COLLECTIONS", &mut library.genre_collections);
+    env.set("DECADE_COLLECTIONS", &mut library.decade_collections);
+    env.set("ARTWORK_MIRROR", &mut library.artwork_mirror);
+    env.set("PREVIEW_CLIPS", &mut library.preview_clips);
+
+    let metadata = &mut config.metadata;
+    env.set("TMDB_API_KEY", &mut metadata.tmdb_api_key);
+    env.set("TMDB_LANGUAGE", &mut metadata.tmdb_language);
+    env.set("TMDB_CHANGES_INTERVAL_SECS", &mut metadata.tmdb_changes_interval_secs);
+    env.set("TMDB_REQUESTS_PER_SECOND", &mut metadata.tmdb_requests_per_second);
+    env.set("TMDB_OFFLINE", &mut metadata.tmdb_offline);
+    env.set("FANART_API_KEY", &mut metadata.fanart_api_key);
+
+    let transcoding = &mut config.transcoding;
+    env.set("MAX_STREAMS", &mut transcoding.max_streams);
+    env.set("MAX_TRANSCODES", &mut transcoding.max_transcodes);
+    env.set("HLS_IDLE_TIMEOUT_SECS", &mut transcoding.hls_idle_timeout_secs);
+    env.set("PLAYBACK_QOS", &mut transcoding.playback_qos);
+    env.set("PLAYBACK_QOS_THROTTLE_MS", &mut transcoding.playback_qos_throttle_ms);
+    env.set("CROP_DETECTION", &mut transcoding.crop_detection);
+
+    env.set("TRANSCODE_CACHE_MAX_MB", &mut config.cache.transcode_cache_max_mb);
+    env.set("DB_PAGE_CACHE_MB", &mut config.cache.db_page_cache_mb);
+    env.set("MEMORY_CACHE_MB", &mut config.cache.memory_cache_mb);
+
+    let subtitles = &mut config.subtitles;
+    env.set("SUBTITLE_LANGUAGES", &mut subtitles.languages);
+    env.set("OPENSUBTITLES_API_KEY", &mut subtitles.opensubtitles_api_key);
+    env.set("OPENSUBTITLES_USERNAME", &mut subtitles.opensubtitles_username);
+    env.set("OPENSUBTITLES_PASSWORD", &mut subtitles.opensubtitles_password);
+    env.set("TESSERACT_PATH", &mut subtitles.tesseract_path);
+
+    let whisper = &mut config.whisper;
+    env.set("WHISPER_BACKEND", &mut whisper.backend);
+    env.set("WHISPER_MODEL_PATH", &mut whisper.model_path);
+    env.set("WHISPER_CLI_PATH", &mut whisper.cli_path);
+    env.set("WHISPER_VAD", &mut whisper.vad);
+    env.set("WHISPER_MODELS_DIR", &mut whisper.models_dir);
+    env.set("WHISPER_MODELS_URL", &mut whisper.models_url);
+
+    let ollama = &mut config.ollama;
+    env.set("OLLAMA_URL", &mut ollama.url);
+    env.set("OLLAMA_MODEL", &mut ollama.model);
+    env.set("OLLAMA_CONTEXT_LINES", &mut ollama.context_lines);
+    env.set("OLLAMA_REQUESTS_PER_MINUTE", &mut ollama.requests_per_minute);
+    env.set("EMBEDDING_MODEL", &mut ollama.embedding_model);
+
+    let translation = &mut config.translation;
+    env.set("TRANSLATION_PROVIDERS", &mut translation.providers);
+    env.set("DEEPL_API_KEY", &mut translation.deepl.api_key);
+    env.set("DEEPL_API_URL", &mut translation.deepl.api_url);
+    env.set("DEEPL_FORMALITY", &mut translation.deepl.formality);
+    env.set("DEEPL_REQUESTS_PER_MINUTE", &mut translation.deepl.requests_per_minute);
+    env.set("LIBRETRANSLATE_URL", &mut translation.libretranslate.url);
+    env.set("LIBRETRANSLATE_API_KEY", &mut translation.libretranslate.api_key);
+    env.set("LIBRETRANSLATE_REQUESTS_PER_MINUTE", &mut translation.libretranslate.requests_per_minute);
+
+    let auth = &mut config.auth;
+    env.set("API_SECRET", &mut auth.api_secret);
+    env.set("CAST_SECRET", &mut auth.cast_secret);
+    env.set("CAST_URL_TTL_SECS", &mut auth.cast_url_ttl_secs);
+    env.set("STREAM_TOKEN_TTL_SECS", &mut auth.stream_token_ttl_secs);
+    env.set("REQUIRE_STREAM_TOKENS", &mut auth.require_stream_tokens);
+
+    let tls = &mut config.tls;
+    env.set("TLS_CERT_PATH", &mut tls.cert_path);
+    env.set("TLS_KEY_PATH", &mut tls.key_path);
+    env.set("TLS_HTTP_PORT", &mut tls.http_port);
+    env.set("TLS_ACME_WEBROOT", &mut tls.acme_webroot);
+
+    let dlna = &mut config.dlna;
+    env.set("DLNA_ENABLED", &mut dlna.enabled);
+    env.set("DLNA_NAME", &mut dlna.name);
+    env.set("DLNA_ADVERTISE_IP", &mut dlna.advertise_ip);
+
+    let scheduler = &mut config.scheduler;
+    env.set("LIBRARY_SCAN_SCHEDULE", &mut scheduler.library_scan);
+    env.set("METADATA_REFRESH_SCHEDULE", &mut scheduler.metadata_refresh);
+    env.set("PREVIEW_CLIPS_SCHEDULE", &mut scheduler.preview_clips);
+    env.set("RECOMMENDATIONS_REFRESH_SCHEDULE", &mut scheduler.recommendations_refresh);
+    env.set("DB_MAINTENANCE_SCHEDULE", &mut scheduler.db_maintenance);
+
+    env.problems
+}
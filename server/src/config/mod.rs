@@ -0,0 +1,909 @@
+//! Server configuration
+//!
+//! Settings come from a TOML or YAML file, then environment variables,
+//! which override the file, then defaults. The file is named by
+//! `CONFIG_FILE`; without it `homeflix.toml`, `homeflix.yaml` or
+//! `homeflix.yml` in the working directory is used when present, so
+//! deployments configured only through the environment keep working.
+//! Everything is validated at startup, and every problem is reported at
+//! once.
+
+mod env;
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+
+use media_identifier::ParserProfile;
+use serde::{de::Error as _, Deserialize, Deserializer};
+
+use crate::application::services::playback_qos::QosMode;
+use crate::application::services::RuntimeSettings;
+use crate::domain::value_objects::CronSchedule;
+use crate::infrastructure::external::whisper::VadDetector;
+use crate::infrastructure::logging::LogFormat;
+use crate::shared::error::ConfigError;
+
+pub use env::ConfigValue;
+
+/// Config files looked for in the working directory without `CONFIG_FILE`
+const DEFAULT_FILES: &[&str] = &["homeflix.toml", "homeflix.yaml", "homeflix.yml"];
+
+/// Translation providers `translation.providers` can list
+const TRANSLATION_PROVIDERS: &[&str] = &["ollama", "deepl", "libretranslate"];
+
+/// DeepL formality settings
+const DEEPL_FORMALITIES: &[&str] = &["default", "more", "less", "prefer_more", "prefer_less"];
+
+/// All server settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub library: LibraryConfig,
+    pub metadata: MetadataConfig,
+    pub transcoding: TranscodingConfig,
+    pub cache: CacheConfig,
+    pub subtitles: SubtitleConfig,
+    pub whisper: WhisperConfig,
+    pub ollama: OllamaConfig,
+    pub translation: TranslationConfig,
+    pub auth: AuthConfig,
+    pub tls: TlsConfig,
+    pub dlna: DlnaConfig,
+    pub scheduler: SchedulerConfig,
+    /// File the settings were read from
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// HTTP server, database and operations
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// `PORT`
+    pub port: u16,
+    /// `DATABASE_URL`; the data directory is the database's directory
+    pub database_url: String,
+    /// `LOG_FORMAT`: "text" or "json" (levels stay in `RUST_LOG`)
+    #[serde(deserialize_with = "parsed")]
+    pub log_format: LogFormat,
+    /// `READINESS_OPTIONAL`: readiness checks reported without failing
+    /// readiness
+    pub readiness_optional: Vec<String>,
+    /// `SLOW_REQUEST_MS`: requests taking longer are logged and counted
+    pub slow_request_ms: u64,
+    /// `SLOW_QUERY_MS`: queries taking longer are logged and counted
+    pub slow_query_ms: u64,
+    /// `COMPRESSION`: gzip or deflate JSON and text responses
+    pub compression: bool,
+    /// `COMPRESSION_MIN_BYTES`: smaller responses are sent uncompressed
+    pub compression_min_bytes: usize,
+    /// `AUDIT_RETENTION_DAYS`: days audit log entries are kept (0 = forever)
+    pub audit_retention_days: u32,
+    /// `MIGRATE_DRY_RUN`: only list the pending schema migrations and exit
+    pub migrate_dry_run: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 3000,
+            database_url: "sqlite:data.db?mode=rwc".to_string(),
+            log_format: LogFormat::Text,
+            readiness_optional: Vec::new(),
+            slow_request_ms: 1000,
+            slow_query_ms: 250,
+            compression: true,
+            compression_min_bytes: 1024,
+            audit_retention_days: 90,
+            migrate_dry_run: false,
+        }
+    }
+}
+
+/// The media library and scanning
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LibraryConfig {
+    /// `MEDIA_DIR` (required)
+    pub media_dir: String,
+    /// `SCAN_INTERVAL_SECS` (deprecated): 0 turns background scans off;
+    /// other values stand in for an unset `scheduler.library_scan` (see
+    /// [`Config::apply_legacy_intervals`])
+    pub scan_interval_secs: u64,
+    /// `PARSER_PROFILE`: filename parser profile for files outside
+    /// `parser_profiles`
+    #[serde(deserialize_with = "parsed")]
+    pub parser_profile: ParserProfile,
+    /// `PARSER_PROFILES` ("Anime=anime,Sports=sports"): parser profiles of
+    /// library folders, relative to the media directory
+    #[serde(deserialize_with = "parser_profile_map")]
+    pub parser_profiles: Vec<(String, ParserProfile)>,
+    /// `GENRE_COLLECTIONS`: maintain a collection per movie genre
+    pub genre_collections: bool,
+    /// `DECADE_COLLECTIONS`: maintain a collection per release decade
+    pub decade_collections: bool,
+    /// `ARTWORK_MIRROR`: download artwork into local storage during scans
+    pub artwork_mirror: bool,
+    /// `PREVIEW_CLIPS`: make hover preview clips after scans
+    pub preview_clips: bool,
+}
+
+impl Default for LibraryConfig {
+    fn default() -> Self {
+        Self {
+            media_dir: String::new(),
+            scan_interval_secs: 3600,
+            parser_profile: ParserProfile::default(),
+            parser_profiles: Vec::new(),
+            genre_collections: false,
+            decade_collections: false,
+            artwork_mirror: true,
+            preview_clips: false,
+        }
+    }
+}
+
+/// Metadata providers
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataConfig {
+    /// `TMDB_API_KEY`
+    pub tmdb_api_key: String,
+    /// `TMDB_LANGUAGE`, e.g. "hu-HU" (None for TMDB default)
+    pub tmdb_language: Option<String>,
+    /// `TMDB_CHANGES_INTERVAL_SECS` (deprecated): 0 turns TMDB change
+    /// checks off; other values stand in for an unset
+    /// `scheduler.metadata_refresh`
+    pub tmdb_changes_interval_secs: u64,
+    /// `TMDB_REQUESTS_PER_SECOND`: average TMDB request rate (bursts of one
+    /// second's worth are allowed)
+    pub tmdb_requests_per_second: u32,
+    /// `TMDB_OFFLINE`: answer TMDB requests only from responses imported
+    /// with `homeflixd import-metadata`, never contacting TMDB
+    pub tmdb_offline: bool,
+    /// `FANART_API_KEY` (None disables logos, clearart and disc art)
+    pub fanart_api_key: Option<String>,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self {
+            tmdb_api_key: String::new(),
+            tmdb_language: None,
+            tmdb_changes_interval_secs: 86400,
+            tmdb_requests_per_second: crate::infrastructure::external::tmdb::client::DEFAULT_REQUESTS_PER_SECOND,
+            tmdb_offline: false,
+            fanart_api_key: None,
+        }
+    }
+}
+
+/// Streaming and transcoding
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranscodingConfig {
+    /// `MAX_STREAMS`: concurrent stream sessions allowed (0 = unlimited)
+    pub max_streams: usize,
+    /// `MAX_TRANSCODES`: concurrent transcoding sessions allowed (0 = unlimited)
+    pub max_transcodes: usize,
+    /// `HLS_IDLE_TIMEOUT_SECS`: seconds without requests before an HLS
+    /// session is removed
+    pub hls_idle_timeout_secs: u64,
+    /// `PLAYBACK_QOS`: how background work reacts to active playback
+    /// ("pause", "throttle" or "off")
+    #[serde(deserialize_with = "parsed")]
+    pub playback_qos: QosMode,
+    /// `PLAYBACK_QOS_THROTTLE_MS`: per-item delay in throttle mode
+    pub playback_qos_throttle_ms: u64,
+    /// `CROP_DETECTION`: detect black bars when playback info is requested
+    pub crop_detection: bool,
+}
+
+impl Default for TranscodingConfig {
+    fn default() -> Self {
+        Self {
+            max_streams: 0,
+            max_transcodes: 0,
+            hls_idle_timeout_secs: 300,
+            playback_qos: QosMode::Pause,
+            playback_qos_throttle_ms: 500,
+            crop_detection: false,
+        }
+    }
+}
+
+/// Cache sizes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// `TRANSCODE_CACHE_MAX_MB`: size limit of the transcode cache (0 disables it)
+    pub transcode_cache_max_mb: u64,
+    /// `DB_PAGE_CACHE_MB`: SQLite page cache for the whole connection pool
+    pub db_page_cache_mb: u64,
+    /// `MEMORY_CACHE_MB`: in-memory tier of the metadata cache (0 disables it)
+    pub memory_cache_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            transcode_cache_max_mb: 10240,
+            db_page_cache_mb: 64,
+            memory_cache_mb: 32,
+        }
+    }
+}
+
+/// Subtitle downloads and OCR
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubtitleConfig {
+    /// `SUBTITLE_LANGUAGES`: languages reported by coverage stats (empty =
+    /// all found) and downloaded when the user has none set
+    pub languages: Vec<String>,
+    /// `OPENSUBTITLES_API_KEY` (None disables subtitle downloads)
+    pub opensubtitles_api_key: Option<String>,
+    /// `OPENSUBTITLES_USERNAME`: account downloads are counted on
+    pub opensubtitles_username: Option<String>,
+    /// `OPENSUBTITLES_PASSWORD`
+    pub opensubtitles_password: Option<String>,
+    /// `TESSERACT_PATH`: OCR of PGS subtitles, when installed
+    pub tesseract_path: String,
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            languages: Vec::new(),
+            opensubtitles_api_key: None,
+            opensubtitles_username: None,
+            opensubtitles_password: None,
+            tesseract_path: "tesseract".to_string(),
+        }
+    }
+}
+
+/// How whisper.cpp is run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WhisperBackend {
+    /// The whisper-cli executable
+    #[default]
+    Cli,
+    /// In-process (needs a build with the whisper-rs feature)
+    Native,
+}
+
+/// Speech-to-text for subtitle generation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WhisperConfig {
+    /// `WHISPER_BACKEND`: "cli" or "native"
+    #[serde(deserialize_with = "parsed")]
+    pub backend: WhisperBackend,
+    /// `WHISPER_MODEL_PATH`: model used when none is selected per language
+    pub model_path: String,
+    /// `WHISPER_CLI_PATH`
+    pub cli_path: String,
+    /// `WHISPER_VAD`: voice activity detection skipping silence before
+    /// transcribing ("true"/"energy", "silencedetect" or "false")
+    #[serde(deserialize_with = "parsed")]
+    pub vad: Option<VadDetector>,
+    /// `WHISPER_MODELS_DIR`: downloaded models (default: the model's directory)
+    pub models_dir: Option<String>,
+    /// `WHISPER_MODELS_URL`: where models are downloaded from
+    pub models_url: Option<String>,
+}
+
+impl Default for WhisperConfig {
+    fn default() -> Self {
+        Self {
+            backend: WhisperBackend::Cli,
+            model_path: "/app/models/ggml-small.bin".to_string(),
+            cli_path: "whisper-cli".to_string(),
+            vad: Some(VadDetector::default()),
+            models_dir: None,
+            models_url: None,
+        }
+    }
+}
+
+/// The Ollama server, for translation and semantic search
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OllamaConfig {
+    /// `OLLAMA_URL`
+    pub url: String,
+    /// `OLLAMA_MODEL`: translation model
+    pub model: String,
+    /// `OLLAMA_CONTEXT_LINES`: surrounding lines sent with each batch
+    pub context_lines: Option<usize>,
+    /// `OLLAMA_REQUESTS_PER_MINUTE`
+    pub requests_per_minute: Option<u32>,
+    /// `EMBEDDING_MODEL`: enables semantic search
+    pub embedding_model: Option<String>,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:11434".to_string(),
+            model: "gemma3:4b".to_string(),
+            context_lines: None,
+            requests_per_minute: None,
+            embedding_model: None,
+        }
+    }
+}
+
+/// Subtitle translation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranslationConfig {
+    /// `TRANSLATION_PROVIDERS`: tried in the order listed
+    pub providers: Vec<String>,
+    pub deepl: DeepLConfig,
+    pub libretranslate: LibreTranslateConfig,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            providers: vec!["ollama".to_string()],
+            deepl: DeepLConfig::default(),
+            libretranslate: LibreTranslateConfig::default(),
+        }
+    }
+}
+
+/// DeepL translation
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeepLConfig {
+    /// `DEEPL_API_KEY`
+    pub api_key: Option<String>,
+    /// `DEEPL_API_URL` (default: by the key's plan)
+    pub api_url: Option<String>,
+    /// `DEEPL_FORMALITY`
+    pub formality: Option<String>,
+    /// `DEEPL_REQUESTS_PER_MINUTE`
+    pub requests_per_minute: Option<u32>,
+}
+
+/// LibreTranslate translation
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LibreTranslateConfig {
+    /// `LIBRETRANSLATE_URL`
+    pub url: Option<String>,
+    /// `LIBRETRANSLATE_API_KEY`
+    pub api_key: Option<String>,
+    /// `LIBRETRANSLATE_REQUESTS_PER_MINUTE`
+    pub requests_per_minute: Option<u32>,
+}
+
+/// Authentication and stream signing
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// `API_SECRET`: shared secret API requests must carry (None = no
+    /// authentication)
+    pub api_secret: Option<String>,
+    /// `CAST_SECRET`: key signing cast URLs and stream tokens (None =
+    /// generated once and kept in the data dir)
+    pub cast_secret: Option<String>,
+    /// `CAST_URL_TTL_SECS`: lifetime of signed cast stream URLs
+    pub cast_url_ttl_secs: u64,
+    /// `STREAM_TOKEN_TTL_SECS`: lifetime of stream session tokens
+    pub stream_token_ttl_secs: u64,
+    /// `REQUIRE_STREAM_TOKENS`: reject direct stream requests without a
+    /// session token
+    pub require_stream_tokens: bool,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            api_secret: None,
+            cast_secret: None,
+            cast_url_ttl_secs: 21600,
+            stream_token_ttl_secs: 21600,
+            require_stream_tokens: false,
+        }
+    }
+}
+
+/// HTTPS, served with HTTP/2 when clients support it
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+    /// `TLS_CERT_PATH`: PEM certificate chain; with `key_path` the server
+    /// speaks HTTPS only. Renewed files are picked up while running
+    pub cert_path: Option<String>,
+    /// `TLS_KEY_PATH`: PEM private key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: Option<String>,
+    /// `TLS_HTTP_PORT`: plain HTTP port redirecting to HTTPS (e.g. 80)
+    pub http_port: Option<u16>,
+    /// `TLS_ACME_WEBROOT`: webroot of an ACME client such as certbot,
+    /// whose HTTP-01 challenges are answered on `http_port`
+    pub acme_webroot: Option<String>,
+}
+
+impl TlsConfig {
+    /// Whether HTTPS is served
+    pub fn enabled(&self) -> bool {
+        self.cert_path.is_some() && self.key_path.is_some()
+    }
+}
+
+/// DLNA media server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DlnaConfig {
+    /// `DLNA_ENABLED`: announce the server via SSDP
+    pub enabled: bool,
+    /// `DLNA_NAME`: name renderers list the server under
+    pub name: String,
+    /// `DLNA_ADVERTISE_IP`: address advertised to renderers (None = the
+    /// default route's interface)
+    pub advertise_ip: Option<Ipv4Addr>,
+}
+
+impl Default for DlnaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            name: "Homeflix".to_string(),
+            advertise_ip: None,
+        }
+    }
+}
+
+/// When background tasks run, as cron expressions in local time; tasks
+/// can also be changed and run through `/v2/admin/tasks`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulerConfig {
+    /// `LIBRARY_SCAN_SCHEDULE`: library scan, also run at startup
+    #[serde(deserialize_with = "parsed")]
+    pub library_scan: CronSchedule,
+    /// `METADATA_REFRESH_SCHEDULE`: refresh of titles changed on TMDB
+    #[serde(deserialize_with = "parsed")]
+    pub metadata_refresh: CronSchedule,
+    /// `PREVIEW_CLIPS_SCHEDULE`: hover preview clips of new titles (with
+    /// `PREVIEW_CLIPS`)
+    #[serde(deserialize_with = "parsed")]
+    pub preview_clips: CronSchedule,
+    /// `RECOMMENDATIONS_REFRESH_SCHEDULE`: recomputed recommendation rows
+    #[serde(deserialize_with = "parsed")]
+    pub recommendations_refresh: CronSchedule,
+    /// `DB_MAINTENANCE_SCHEDULE`: audit log and event pruning, VACUUM and
+    /// ANALYZE
+    #[serde(deserialize_with = "parsed")]
+    pub db_maintenance: CronSchedule,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        let schedule = |expression: &str| expression.parse().expect("default schedules are valid");
+        Self {
+            library_scan: schedule("0 * * * *"),
+            metadata_refresh: schedule("0 2 * * *"),
+            preview_clips: schedule("30 * * * *"),
+            recommendations_refresh: schedule("0 3 * * *"),
+            db_maintenance: schedule("0 4 * * *"),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file (if any), applies the environment and
+    /// validates the result
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = std::env::var("CONFIG_FILE")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+            .or_else(|| DEFAULT_FILES.iter().map(PathBuf::from).find(|p| p.is_file()));
+
+        let mut config = match &path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        let mut problems = env::apply(&mut config, |name| std::env::var(name).ok());
+        problems.extend(config.problems());
+        if !problems.is_empty() {
+            return Err(ConfigError::Invalid(problems));
+        }
+        Ok(config)
+    }
+
+    /// Reads a TOML or YAML (by extension) config file
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|e| ConfigError::Read {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        let is_yaml = matches!(path.extension().and_then(|e| e.to_str()), Some("yaml" | "yml"));
+        let parsed = if is_yaml {
+            serde_yaml::from_str::<Self>(&text).map_err(|e| e.to_string())
+        } else {
+            toml::from_str::<Self>(&text).map_err(|e| e.to_string())
+        };
+
+        let mut config = parsed.map_err(|message| ConfigError::Parse {
+            path: path.display().to_string(),
+            message: message.trim_end().to_string(),
+        })?;
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Values of the settings that can be changed while running, before
+    /// stored changes are applied
+    pub fn runtime_settings(&self) -> RuntimeSettings {
+        RuntimeSettings {
+            tmdb_api_key: self.metadata.tmdb_api_key.clone(),
+            max_streams: self.transcoding.max_streams,
+            max_transcodes: self.transcoding.max_transcodes,
+            notifications_enabled: true,
+        }
+    }
+
+    /// Directory of the database, where everything else the server keeps
+    /// is stored too
+    ///
+    /// Examples:
+    /// - `sqlite:data.db?mode=rwc` -> `./data` (or current dir)
+    /// - `sqlite:/data/data.db?mode=rwc` -> `/data`
+    /// - `sqlite:./data/data.db?mode=rwc` -> `./data`
+    pub fn data_dir(&self) -> String {
+        // Remove sqlite: prefix
+        let path_part = self
+            .server
+            .database_url
+            .strip_prefix("sqlite:")
+            .unwrap_or(&self.server.database_url)
+            .split('?')
+            .next()
+            .unwrap_or("");
+
+        if path_part.is_empty() {
+            return "./data".to_string();
+        }
+
+        let db_path = Path::new(path_part);
+
+        // If absolute path (starts with /), use parent directory
+        if db_path.is_absolute() {
+            if let Some(parent) = db_path.parent() {
+                return parent.to_string_lossy().to_string();
+            }
+            return "/data".to_string();
+        }
+
+        // For relative paths, use parent directory or default to ./data
+        if let Some(parent) = db_path.parent() {
+            let parent_str = parent.to_string_lossy().to_string();
+            if parent_str.is_empty() || parent_str == "." {
+                return "./data".to_string();
+            }
+            return parent_str;
+        }
+
+        "./data".to_string()
+    }
+
+    /// Turns the deprecated `SCAN_INTERVAL_SECS` and
+    /// `TMDB_CHANGES_INTERVAL_SECS` into the closest task schedules,
+    /// returning the deprecation warnings to log
+    ///
+    /// Only intervals other than 0 (off) and the default count, and only for
+    /// tasks whose schedule is left at its default.
+    pub fn apply_legacy_intervals(&mut self) -> Vec<String> {
+        let schedules = SchedulerConfig::default();
+        [
+            legacy_interval(
+                ("SCAN_INTERVAL_SECS", self.library.scan_interval_secs, LibraryConfig::default().scan_interval_secs),
+                ("LIBRARY_SCAN_SCHEDULE", &mut self.scheduler.library_scan, &schedules.library_scan),
+            ),
+            legacy_interval(
+                ("TMDB_CHANGES_INTERVAL_SECS", self.metadata.tmdb_changes_interval_secs, MetadataConfig::default().tmdb_changes_interval_secs),
+                ("METADATA_REFRESH_SCHEDULE", &mut self.scheduler.metadata_refresh, &schedules.metadata_refresh),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Everything wrong with the settings, naming the key and its variable
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.library.media_dir.trim().is_empty() {
+            problems.push("library.media_dir (MEDIA_DIR) is required".to_string());
+        } else if !Path::new(&self.library.media_dir).is_dir() {
+            problems.push(format!("library.media_dir (MEDIA_DIR): '{}' is not a directory", self.library.media_dir));
+        }
+        for (folder, _) in &self.library.parser_profiles {
+            if Path::new(folder).is_absolute() {
+                problems.push(format!(
+                    "library.parser_profiles (PARSER_PROFILES): '{}' must be relative to the media directory",
+                    folder
+                ));
+            }
+        }
+
+        if self.server.port == 0 {
+            problems.push("server.port (PORT) must not be 0".to_string());
+        }
+        if !self.server.database_url.starts_with("sqlite:") {
+            problems.push(format!(
+                "server.database_url (DATABASE_URL): '{}' is not a sqlite: URL",
+                self.server.database_url
+            ));
+        }
+
+        let mut check_url = |key: &str, var: &str, url: Option<&str>| {
+            let Some(url) = url else { return };
+            match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                Ok(_) => problems.push(format!("{} ({}): '{}' is not an http(s) URL", key, var, url)),
+                Err(e) => problems.push(format!("{} ({}): '{}' is not a URL: {}", key, var, url, e)),
+            }
+        };
+        check_url("ollama.url", "OLLAMA_URL", Some(&self.ollama.url));
+        check_url("whisper.models_url", "WHISPER_MODELS_URL", self.whisper.models_url.as_deref());
+        check_url("translation.deepl.api_url", "DEEPL_API_URL", self.translation.deepl.api_url.as_deref());
+        check_url("translation.libretranslate.url", "LIBRETRANSLATE_URL", self.translation.libretranslate.url.as_deref());
+
+        for provider in &self.translation.providers {
+            match provider.to_lowercase().as_str() {
+                "deepl" if self.translation.deepl.api_key.is_none() => problems.push(
+                    "translation.providers (TRANSLATION_PROVIDERS) lists deepl, but translation.deepl.api_key \
+                     (DEEPL_API_KEY) is not set"
+                        .to_string(),
+                ),
+                "libretranslate" if self.translation.libretranslate.url.is_none() => problems.push(
+                    "translation.providers (TRANSLATION_PROVIDERS) lists libretranslate, but \
+                     translation.libretranslate.url (LIBRETRANSLATE_URL) is not set"
+                        .to_string(),
+                ),
+                p if !TRANSLATION_PROVIDERS.contains(&p) => problems.push(format!(
+                    "translation.providers (TRANSLATION_PROVIDERS): unknown provider '{}', expected some of: {}",
+                    p,
+                    TRANSLATION_PROVIDERS.join(", ")
+                )),
+                _ => {}
+            }
+        }
+        if let Some(formality) = &self.translation.deepl.formality {
+            if !DEEPL_FORMALITIES.contains(&formality.as_str()) {
+                problems.push(format!(
+                    "translation.deepl.formality (DEEPL_FORMALITY): unknown formality '{}', expected one of: {}",
+                    formality,
+                    DEEPL_FORMALITIES.join(", ")
+                ));
+            }
+        }
+
+        if cfg!(not(feature = "whisper-rs")) && self.whisper.backend == WhisperBackend::Native {
+            problems.push(
+                "whisper.backend (WHISPER_BACKEND): native needs a build with --features whisper-rs".to_string(),
+            );
+        }
+
+        if self.subtitles.opensubtitles_username.is_some() != self.subtitles.opensubtitles_password.is_some() {
+            problems.push(
+                "subtitles.opensubtitles_username (OPENSUBTITLES_USERNAME) and subtitles.opensubtitles_password \
+                 (OPENSUBTITLES_PASSWORD) must be set together"
+                    .to_string(),
+            );
+        }
+
+        let tls = &self.tls;
+        if tls.cert_path.is_some() != tls.key_path.is_some() {
+            problems.push("tls.cert_path (TLS_CERT_PATH) and tls.key_path (TLS_KEY_PATH) must be set together".to_string());
+        }
+        for (key, var, path) in [
+            ("tls.cert_path", "TLS_CERT_PATH", &tls.cert_path),
+            ("tls.key_path", "TLS_KEY_PATH", &tls.key_path),
+        ] {
+            if let Some(path) = path.as_deref().filter(|p| !Path::new(p).is_file()) {
+                problems.push(format!("{} ({}): '{}' is not a file", key, var, path));
+            }
+        }
+        if let Some(http_port) = tls.http_port {
+            if !tls.enabled() {
+                problems.push("tls.http_port (TLS_HTTP_PORT) needs tls.cert_path and tls.key_path".to_string());
+            } else if http_port == self.server.port || http_port == 0 {
+                problems.push(format!("tls.http_port (TLS_HTTP_PORT): {} is not a free port", http_port));
+            }
+        }
+        if tls.acme_webroot.is_some() && tls.http_port.is_none() {
+            problems.push(
+                "tls.acme_webroot (TLS_ACME_WEBROOT) needs tls.http_port (TLS_HTTP_PORT) to answer challenges on"
+                    .to_string(),
+            );
+        }
+
+        if self.dlna.name.trim().is_empty() {
+            problems.push("dlna.name (DLNA_NAME) must not be empty".to_string());
+        }
+
+        problems
+    }
+}
+
+/// Deserializes a setting given as a string, number or boolean with its
+/// [`ConfigValue`] parser, so files accept what the environment does
+fn parsed<'de, D: Deserializer<'de>, T: ConfigValue>(deserializer: D) -> Result<T, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scalar {
+        Bool(bool),
+        Int(i64),
+        String(String),
+    }
+    let text = match Scalar::deserialize(deserializer)? {
+        Scalar::Bool(b) => b.to_string(),
+        Scalar::Int(n) => n.to_string(),
+        Scalar::String(s) => s,
+    };
+    T::parse_value(&text).map_err(D::Error::custom)
+}
+
+/// Deserializes folder = profile pairs
+fn parser_profile_map<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, ParserProfile)>, D::Error> {
+    BTreeMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(folder, name)| Ok((folder, ParserProfile::parse_value(&name).map_err(D::Error::custom)?)))
+        .collect()
+}
+
+/// Replaces a default schedule by the one closest to a deprecated
+/// interval, returning the warning to log
+fn legacy_interval(
+    (variable, interval, default_interval): (&str, u64, u64),
+    (schedule_variable, schedule, default_schedule): (&str, &mut CronSchedule, &CronSchedule),
+) -> Option<String> {
+    if interval == 0 || interval == default_interval {
+        return None;
+    }
+    if schedule != default_schedule {
+        return Some(format!("{} is deprecated and ignored because {} is set", variable, schedule_variable));
+    }
+    let (every, exact) = CronSchedule::every(interval);
+    let warning = format!(
+        "{} is deprecated, running on {}=\"{}\"{} instead",
+        variable,
+        schedule_variable,
+        every,
+        if exact { "" } else { ", the closest schedule" }
+    );
+    *schedule = every;
+    Some(warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn with_media_dir(mut config: Config) -> Config {
+        config.library.media_dir = std::env::temp_dir().to_string_lossy().to_string();
+        config
+    }
+
+    #[test]
+    fn test_toml_and_yaml_files() {
+        let toml_config: Config = toml::from_str(
+            r#"
+            [server]
+            port = 8096
+            log_format = "json"
+
+            [library]
+            media_dir = "/srv/media"
+            parser_profiles = { Anime = "anime" }
+
+            [transcoding]
+            playback_qos = "throttle"
+
+            [whisper]
+            vad = false
+
+            [translation]
+            providers = ["deepl", "ollama"]
+            deepl = { api_key = "key:fx", formality = "prefer_less" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(toml_config.server.port, 8096);
+        assert_eq!(toml_config.server.log_format, LogFormat::Json);
+        assert_eq!(toml_config.library.parser_profiles, vec![("Anime".to_string(), ParserProfile::Anime)]);
+        assert_eq!(toml_config.transcoding.playback_qos, QosMode::Throttle);
+        assert_eq!(toml_config.whisper.vad, None);
+        // Untouched sections keep their defaults
+        assert_eq!(toml_config.cache.transcode_cache_max_mb, 10240);
+        assert!(with_media_dir(toml_config).problems().is_empty());
+
+        let yaml_config: Config = serde_yaml::from_str("library:\n  media_dir: /srv/media\n  scan_interval_secs: 0\n").unwrap();
+        assert_eq!(yaml_config.library.scan_interval_secs, 0);
+
+        // The example file lists the defaults
+        let example: Config = toml::from_str(include_str!("../../homeflix.example.toml")).unwrap();
+        assert_eq!(example.ollama.model, Config::default().ollama.model);
+        assert!(with_media_dir(example).problems().is_empty());
+
+        // Typos and bad values name the key
+        let error = toml::from_str::<Config>("[server]\nprot = 1\n").unwrap_err().to_string();
+        assert!(error.contains("prot"), "{}", error);
+        let error = toml::from_str::<Config>("[transcoding]\nplayback_qos = \"fast\"\n").unwrap_err().to_string();
+        assert!(error.contains("fast"), "{}", error);
+    }
+
+    #[test]
+    fn test_environment_overrides_and_validation() {
+        let mut config: Config = toml::from_str("[server]\nport = 8096\n[library]\nscan_interval_secs = 60\n").unwrap();
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("PORT", "9000"),
+            ("SCAN_INTERVAL_SECS", ""),
+            ("PARSER_PROFILES", "Anime=anime, Sports=sports"),
+            ("SUBTITLE_LANGUAGES", "en, hu"),
+            ("MAX_STREAMS", "many"),
+            ("CROP_DETECTION", "yes"),
+            ("TRANSLATION_PROVIDERS", "ollama,libretranslate"),
+            ("LIBRARY_SCAN_SCHEDULE", "*/30 * * * *"),
+            ("DB_MAINTENANCE_SCHEDULE", "0 4 * *"),
+        ]);
+        let problems = env::apply(&mut config, |name| env.get(name).map(|v| v.to_string()));
+
+        assert_eq!(config.server.port, 9000);
+        // Empty variables are ignored
+        assert_eq!(config.library.scan_interval_secs, 60);
+        assert_eq!(config.library.parser_profiles.len(), 2);
+        assert_eq!(config.subtitles.languages, vec!["en", "hu"]);
+        assert!(config.transcoding.crop_detection);
+        assert_eq!(config.scheduler.library_scan.to_string(), "*/30 * * * *");
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0], "MAX_STREAMS: invalid value 'many', expected a whole number");
+        assert!(problems[1].starts_with("DB_MAINTENANCE_SCHEDULE: invalid schedule '0 4 * *'"), "{}", problems[1]);
+
+        let problems = config.problems();
+        assert!(problems.iter().any(|p| p.starts_with("library.media_dir (MEDIA_DIR) is required")));
+        assert!(problems.iter().any(|p| p.contains("LIBRETRANSLATE_URL")));
+
+        assert!(with_media_dir(Config::default()).problems().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_intervals() {
+        let mut config = Config::default();
+        assert!(config.apply_legacy_intervals().is_empty());
+
+        config.library.scan_interval_secs = 1800;
+        config.metadata.tmdb_changes_interval_secs = 7200;
+        config.scheduler.metadata_refresh = "0 3 * * *".parse().unwrap();
+        let warnings = config.apply_legacy_intervals();
+        assert_eq!(config.scheduler.library_scan.to_string(), "*/30 * * * *");
+        assert_eq!(warnings[0], "SCAN_INTERVAL_SECS is deprecated, running on LIBRARY_SCAN_SCHEDULE=\"*/30 * * * *\" instead");
+        // An explicit schedule wins
+        assert_eq!(config.scheduler.metadata_refresh.to_string(), "0 3 * * *");
+        assert_eq!(warnings[1], "TMDB_CHANGES_INTERVAL_SECS is deprecated and ignored because METADATA_REFRESH_SCHEDULE is set");
+    }
+
+    #[test]
+    fn test_data_dir() {
+        let data_dir = |url: &str| {
+            let mut config = Config::default();
+            config.server.database_url = url.to_string();
+            config.data_dir()
+        };
+        assert_eq!(data_dir("sqlite:data.db?mode=rwc"), "./data");
+        assert_eq!(data_dir("sqlite:/data/data.db?mode=rwc"), "/data");
+        assert_eq!(data_dir("sqlite:./data/data.db?mode=rwc"), "./data");
+    }
+}
@@ -54,7 +54,7 @@ pub struct Collection {
     pub tmdb_collection_id: Option<i64>,
     /// Sort mode ("timeline" or "release")
     pub sort_mode: String,
-    /// Collection type ("auto", "preset", "custom")
+    /// Collection type ("auto", "preset", "custom", "genre", "decade")
     pub collection_type: String,
     /// Total number of items in the collection
     pub total_items: i32,
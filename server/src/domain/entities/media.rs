@@ -23,6 +23,10 @@ pub struct Media {
     pub poster_url: Option<String>,
     /// Backdrop image URL
     pub backdrop_url: Option<String>,
+    /// Blurhash placeholder of the poster; empty if the poster cannot be hashed
+    pub poster_blurhash: Option<String>,
+    /// Blurhash placeholder of the backdrop; empty if the backdrop cannot be hashed
+    pub backdrop_blurhash: Option<String>,
     /// Trailer URL
     pub trailer_url: Option<String>,
     /// Duration in seconds
@@ -100,6 +104,8 @@ impl Media {
             overview: None,
             poster_url: None,
             backdrop_url: None,
+            poster_blurhash: None,
+            backdrop_blurhash: None,
             trailer_url: None,
             duration_seconds: None,
             release_date: None,
@@ -21,6 +21,10 @@ pub struct Series {
     pub poster_url: Option<String>,
     /// Backdrop image URL
     pub backdrop_url: Option<String>,
+    /// Blurhash placeholder of the poster; empty if the poster cannot be hashed
+    pub poster_blurhash: Option<String>,
+    /// Blurhash placeholder of the backdrop; empty if the backdrop cannot be hashed
+    pub backdrop_blurhash: Option<String>,
     /// Confidence score (0.0 to 1.0)
     pub confidence_score: ConfidenceScore,
     /// Verification status
@@ -41,6 +45,8 @@ pub struct Series {
     pub genres: Option<String>,
     /// Rating (0.0 to 10.0)
     pub rating: Option<f32>,
+    /// Content rating (e.g., "TV-14"); empty if TMDB has none
+    pub content_rating: Option<String>,
     /// Alternative matches (JSON string)
     pub alternative_matches: Option<String>,
     /// Notes about any errors
@@ -72,6 +78,8 @@ impl Series {
             overview: None,
             poster_url: None,
             backdrop_url: None,
+            poster_blurhash: None,
+            backdrop_blurhash: None,
             confidence_score: ConfidenceScore::default(),
             verification_status: VerificationStatus::Unverified,
             first_air_date: None,
@@ -82,6 +90,7 @@ impl Series {
             original_title: None,
             genres: None,
             rating: None,
+            content_rating: None,
             alternative_matches: None,
             error_notes: None,
             last_verified: None,
@@ -0,0 +1,91 @@
+//! Audit event
+//!
+//! Emitted for security-relevant actions (authentication, identification
+//! overrides, deletions, settings changes) so they end up in the audit log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of audited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Credentials granted (device key issued, parental PIN accepted)
+    Login,
+    /// Missing or invalid API key, stream token or PIN
+    AuthFailed,
+    /// Manual identification override
+    Identification,
+    /// Something removed or revoked
+    Deletion,
+    /// Settings changed
+    SettingsChange,
+}
+
+impl AuditAction {
+    /// Name stored in the audit log
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Login => "login",
+            AuditAction::AuthFailed => "auth_failed",
+            AuditAction::Identification => "identification",
+            AuditAction::Deletion => "deletion",
+            AuditAction::SettingsChange => "settings_change",
+        }
+    }
+
+    /// Parses a stored name
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "login" => Some(AuditAction::Login),
+            "auth_failed" => Some(AuditAction::AuthFailed),
+            "identification" => Some(AuditAction::Identification),
+            "deletion" => Some(AuditAction::Deletion),
+            "settings_change" => Some(AuditAction::SettingsChange),
+            _ => None,
+        }
+    }
+}
+
+/// Event emitted for an audited action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEvent {
+    /// What happened
+    pub action: AuditAction,
+    /// Who did it ("admin", "device:3"; None without authentication)
+    pub actor: Option<String>,
+    /// What it was done to ("media:12", "user:home", a request path)
+    pub target: Option<String>,
+    /// Human-readable details
+    pub details: Option<String>,
+    /// Client IP address
+    pub ip: Option<String>,
+    /// Timestamp of event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    /// Creates a new audit event
+    pub fn new(action: AuditAction, target: impl Into<String>) -> Self {
+        Self {
+            action,
+            actor: None,
+            target: Some(target.into()),
+            details: None,
+            ip: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Adds human-readable details
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+impl crate::interfaces::messaging::DomainEvent for AuditEvent {
+    fn event_type(&self) -> &'static str {
+        "audit"
+    }
+}
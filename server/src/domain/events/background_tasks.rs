@@ -12,19 +12,19 @@ pub struct BackgroundScanScheduledEvent {
     pub scan_path: String,
     /// Scheduled time
     pub scheduled_at: DateTime<Utc>,
-    /// Scan interval in seconds
-    pub scan_interval_secs: u64,
+    /// Cron expression of the scan task
+    pub schedule: String,
     /// Timestamp of event
     pub timestamp: DateTime<Utc>,
 }
 
 impl BackgroundScanScheduledEvent {
     /// Creates a new background scan scheduled event
-    pub fn new(scan_path: String, scheduled_at: DateTime<Utc>, scan_interval_secs: u64) -> Self {
+    pub fn new(scan_path: String, scheduled_at: DateTime<Utc>, schedule: String) -> Self {
         Self {
             scan_path,
             scheduled_at,
-            scan_interval_secs,
+            schedule,
             timestamp: Utc::now(),
         }
     }
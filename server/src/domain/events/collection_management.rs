@@ -125,3 +125,62 @@ impl crate::interfaces::messaging::DomainEvent for CollectionItemAddedEvent {
         "collection_item_added"
     }
 }
+
+/// Event emitted when an item is removed from a collection
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CollectionItemRemovedEvent {
+    /// Collection ID
+    pub collection_id: i64,
+    /// Collection item ID
+    pub item_id: i64,
+    /// Media ID (if applicable)
+    pub media_id: Option<i64>,
+    /// Timestamp of event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CollectionItemRemovedEvent {
+    /// Creates a new collection item removed event
+    pub fn new(collection_id: i64, item_id: i64, media_id: Option<i64>) -> Self {
+        Self {
+            collection_id,
+            item_id,
+            media_id,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl crate::interfaces::messaging::DomainEvent for CollectionItemRemovedEvent {
+    fn event_type(&self) -> &'static str {
+        "collection_item_removed"
+    }
+}
+
+/// Event emitted when a collection is deleted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CollectionDeletedEvent {
+    /// Collection ID
+    pub collection_id: i64,
+    /// Collection name
+    pub name: String,
+    /// Timestamp of event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CollectionDeletedEvent {
+    /// Creates a new collection deleted event
+    pub fn new(collection_id: i64, name: String) -> Self {
+        Self {
+            collection_id,
+            name,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl crate::interfaces::messaging::DomainEvent for CollectionDeletedEvent {
+    fn event_type(&self) -> &'static str {
+        "collection_deleted"
+    }
+}
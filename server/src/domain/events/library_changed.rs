@@ -0,0 +1,65 @@
+//! LibraryChanged event
+//!
+//! Emitted whenever library data is written, so derived read models (search
+//! indexes, response caches, live clients) can subscribe in one place instead
+//! of each feature invalidating them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of library record that changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryEntity {
+    Media,
+    Series,
+    Collection,
+}
+
+/// What happened to the records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryChangeKind {
+    Added,
+    Updated,
+    Removed,
+    /// Watch progress or watched flag only
+    WatchState,
+}
+
+/// Event emitted when library records are added, updated or removed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LibraryChangedEvent {
+    /// Kind of record
+    pub entity: LibraryEntity,
+    /// What happened
+    pub change: LibraryChangeKind,
+    /// IDs of the affected records
+    pub ids: Vec<i64>,
+    /// Timestamp of event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl LibraryChangedEvent {
+    /// Creates a new library changed event
+    pub fn new(entity: LibraryEntity, change: LibraryChangeKind, ids: Vec<i64>) -> Self {
+        Self {
+            entity,
+            change,
+            ids,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Whether the change affects what a record looks like (as opposed to
+    /// watch state only)
+    pub fn affects_metadata(&self) -> bool {
+        self.change != LibraryChangeKind::WatchState
+    }
+}
+
+impl crate::interfaces::messaging::DomainEvent for LibraryChangedEvent {
+    fn event_type(&self) -> &'static str {
+        "library_changed"
+    }
+}
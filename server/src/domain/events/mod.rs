@@ -14,12 +14,16 @@ pub mod streaming;
 pub mod collection_management;
 pub mod thumbnail_generation;
 pub mod background_tasks;
+pub mod library_changed;
+pub mod audit;
 
 pub use collection_detected::CollectionDetectedEvent;
 pub use media_identified::MediaIdentifiedEvent;
 pub use media_verified::MediaVerifiedEvent;
 pub use scan_completed::ScanCompletedEvent;
 pub use scan_failed::ScanFailedEvent;
+pub use library_changed::{LibraryChangedEvent, LibraryChangeKind, LibraryEntity};
+pub use audit::{AuditAction, AuditEvent};
 
 // Subtitle Generation Events
 pub use subtitle_generation::{
@@ -33,6 +37,7 @@ pub use progress_tracking::{
     ProgressUpdatedEvent,
     MediaWatchedEvent,
     MediaUnwatchedEvent,
+    WatchStateBatchUpdatedEvent,
 };
 
 // Streaming Events
@@ -47,6 +52,8 @@ pub use collection_management::{
     CollectionCreatedEvent,
     CollectionUpdatedEvent,
     CollectionItemAddedEvent,
+    CollectionItemRemovedEvent,
+    CollectionDeletedEvent,
 };
 
 // Thumbnail Generation Events
@@ -85,3 +85,41 @@ impl crate::interfaces::messaging::DomainEvent for MediaUnwatchedEvent {
         "media_unwatched"
     }
 }
+
+/// Event emitted once when a whole season, series or collection changes
+/// watch state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchStateBatchUpdatedEvent {
+    /// Scope of the change ("season", "series" or "collection")
+    pub scope: String,
+    /// Series or collection ID
+    pub scope_id: i64,
+    /// Season number (season scope only)
+    pub season: Option<i32>,
+    /// Media IDs that changed
+    pub media_ids: Vec<i64>,
+    /// Whether the media is now watched
+    pub is_watched: bool,
+    /// Timestamp of event
+    pub timestamp: DateTime<Utc>,
+}
+
+impl WatchStateBatchUpdatedEvent {
+    /// Creates a new batch watch state event
+    pub fn new(scope: String, scope_id: i64, season: Option<i32>, media_ids: Vec<i64>, is_watched: bool) -> Self {
+        Self {
+            scope,
+            scope_id,
+            season,
+            media_ids,
+            is_watched,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl crate::interfaces::messaging::DomainEvent for WatchStateBatchUpdatedEvent {
+    fn event_type(&self) -> &'static str {
+        "watch_state_batch_updated"
+    }
+}
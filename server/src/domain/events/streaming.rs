@@ -53,6 +53,15 @@ pub struct StreamEndedEvent {
     pub duration_seconds: Option<f64>,
     /// Bytes streamed
     pub bytes_streamed: Option<u64>,
+    /// User who watched (if known)
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Device ID or user agent (if known)
+    #[serde(default)]
+    pub client: Option<String>,
+    /// When the stream started (if known)
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
     /// Timestamp of event
     pub timestamp: DateTime<Utc>,
 }
@@ -68,9 +77,20 @@ impl StreamEndedEvent {
             media_id,
             duration_seconds,
             bytes_streamed,
+            user: None,
+            client: None,
+            started_at: None,
             timestamp: Utc::now(),
         }
     }
+
+    /// Adds who watched on which device, and since when
+    pub fn with_session(mut self, user: String, client: Option<String>, started_at: DateTime<Utc>) -> Self {
+        self.user = Some(user);
+        self.client = client;
+        self.started_at = Some(started_at);
+        self
+    }
 }
 
 impl crate::interfaces::messaging::DomainEvent for StreamEndedEvent {
@@ -91,7 +91,18 @@ impl PresetCollection {
             ));
         }
 
+        let mut timeline_orders = std::collections::HashSet::new();
         for (idx, item) in self.items.iter().enumerate() {
+            // Items are told apart by their place in the timeline
+            if !timeline_orders.insert(item.timeline_order) {
+                return Err(DomainError::ValidationError(
+                    format!(
+                        "Preset collection '{}', item {}: timeline_order {} is used more than once",
+                        self.name, idx, item.timeline_order
+                    ),
+                ));
+            }
+
             // Validate media type
             if item.media_type != "movie" && item.media_type != "tv" {
                 return Err(DomainError::ValidationError(
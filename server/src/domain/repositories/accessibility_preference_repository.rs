@@ -0,0 +1,33 @@
+//! AccessibilityPreferenceRepository trait
+//!
+//! Repository interface for per-user accessibility preferences
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// Accessibility tracks a user wants picked by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessibilityPreferences {
+    /// Prefer audio description tracks (narration of the picture)
+    #[serde(default)]
+    pub audio_description: bool,
+    /// Prefer SDH/CC subtitles, and turn them on even when the audio is in
+    /// the user's language
+    #[serde(default)]
+    pub hearing_impaired_subtitles: bool,
+}
+
+/// Repository for the accessibility preferences of each user, keyed by a
+/// client-chosen user ID
+#[async_trait]
+pub trait AccessibilityPreferenceRepository: Send + Sync {
+    /// Gets the preferences of a user
+    async fn find(&self, user_id: &str) -> Result<Option<AccessibilityPreferences>, RepositoryError>;
+
+    /// Saves the preferences of a user (replaces the existing ones)
+    async fn save(&self, user_id: &str, preferences: &AccessibilityPreferences) -> Result<(), RepositoryError>;
+
+    /// Removes the preferences of a user, returning whether any were set
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError>;
+}
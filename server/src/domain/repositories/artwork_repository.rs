@@ -0,0 +1,47 @@
+//! ArtworkRepository trait
+//!
+//! Repository interface for additional artwork (logos, clearart, disc art)
+//! attached to media items and series
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Owner of an artwork record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkOwner {
+    Media,
+    Series,
+}
+
+impl ArtworkOwner {
+    /// Value stored in the owner_type column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtworkOwner::Media => "media",
+            ArtworkOwner::Series => "series",
+        }
+    }
+}
+
+/// Additional artwork URLs for a media item or series
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtraArtwork {
+    pub logo_url: Option<String>,
+    pub clearart_url: Option<String>,
+    pub disc_url: Option<String>,
+}
+
+/// Repository for additional artwork
+#[async_trait]
+pub trait ArtworkRepository: Send + Sync {
+    /// Gets the artwork stored for an owner
+    async fn find(&self, owner: ArtworkOwner, owner_id: i64) -> Result<Option<ExtraArtwork>, RepositoryError>;
+
+    /// Saves artwork for an owner (replaces the existing record)
+    ///
+    /// Empty records are stored too, marking the owner as checked.
+    async fn save(&self, owner: ArtworkOwner, owner_id: i64, artwork: &ExtraArtwork) -> Result<(), RepositoryError>;
+
+    /// Gets IDs of owners checked within the last `max_age_days` days
+    async fn find_fresh_ids(&self, owner: ArtworkOwner, max_age_days: i64) -> Result<Vec<i64>, RepositoryError>;
+}
@@ -0,0 +1,17 @@
+//! AudioPreferenceRepository trait
+//!
+//! Repository interface for per-user preferred audio languages
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Repository for the audio language each user last picked, keyed by a
+/// client-chosen user ID
+#[async_trait]
+pub trait AudioPreferenceRepository: Send + Sync {
+    /// Gets the preferred language (ISO 639-1 code) of a user
+    async fn find(&self, user_id: &str) -> Result<Option<String>, RepositoryError>;
+
+    /// Saves the preferred language of a user (replaces the existing one)
+    async fn save(&self, user_id: &str, language: &str) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,40 @@
+//! AuditLogRepository trait
+//!
+//! Repository interface for the audit log of security-relevant actions.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::shared::error::RepositoryError;
+
+/// A recorded action
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub action: AuditAction,
+    pub actor: Option<String>,
+    pub target: Option<String>,
+    pub details: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Repository for the audit log
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Records an action
+    async fn record(&self, event: &AuditEvent) -> Result<(), RepositoryError>;
+
+    /// Gets entries newest first, optionally of one action, with the total
+    /// number of matching entries
+    async fn find_page(
+        &self,
+        action: Option<AuditAction>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<(Vec<AuditEntry>, u64), RepositoryError>;
+
+    /// Removes entries recorded before `cutoff`, returning how many
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError>;
+}
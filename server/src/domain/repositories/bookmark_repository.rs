@@ -0,0 +1,58 @@
+//! BookmarkRepository trait
+//!
+//! Repository interface for scene bookmarks: timestamps with an optional
+//! note that a user marked in a media item
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// A moment of a media item a user bookmarked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: i64,
+    pub media_id: i64,
+    /// Position in seconds
+    pub position_seconds: f64,
+    /// Note such as "great scene"
+    pub note: Option<String>,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Updated at timestamp (ISO 8601)
+    pub updated_at: String,
+}
+
+/// Repository for bookmarks
+#[async_trait]
+pub trait BookmarkRepository: Send + Sync {
+    /// Gets the bookmarks of a user in a media item, by position
+    async fn find_by_media(&self, user_id: &str, media_id: i64) -> Result<Vec<Bookmark>, RepositoryError>;
+
+    /// Gets all bookmarks of a user, newest first
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Bookmark>, RepositoryError>;
+
+    /// Adds a bookmark, returning it with its ID
+    async fn create(
+        &self,
+        user_id: &str,
+        media_id: i64,
+        position_seconds: f64,
+        note: Option<&str>,
+    ) -> Result<Bookmark, RepositoryError>;
+
+    /// Changes the position and note of a bookmark of the user, returning
+    /// None when it does not exist
+    async fn update(
+        &self,
+        user_id: &str,
+        id: i64,
+        position_seconds: f64,
+        note: Option<&str>,
+    ) -> Result<Option<Bookmark>, RepositoryError>;
+
+    /// Gets a bookmark of the user
+    async fn find(&self, user_id: &str, id: i64) -> Result<Option<Bookmark>, RepositoryError>;
+
+    /// Removes a bookmark of the user, returning whether one existed
+    async fn delete(&self, user_id: &str, id: i64) -> Result<bool, RepositoryError>;
+}
@@ -53,6 +53,11 @@ pub struct CacheStats {
     pub expired_entries: i64,
     /// Total cache size in bytes
     pub total_size_bytes: i64,
+    /// Lookups that found a live entry since the server started (0 when
+    /// not tracked)
+    pub hits: u64,
+    /// Lookups that found nothing or an expired entry
+    pub misses: u64,
     /// Hit rate (0.0 to 1.0)
     pub hit_rate: f32,
 }
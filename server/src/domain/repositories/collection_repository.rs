@@ -5,6 +5,16 @@
 use async_trait::async_trait;
 use crate::domain::entities::{Collection, CollectionItem};
 
+/// Sort keys of a page of collections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionOrder {
+    Name,
+    /// Number of items
+    Size,
+    /// Share of the items available in the library
+    Completion,
+}
+
 /// Repository for collection data access
 #[async_trait]
 pub trait CollectionRepository: Send + Sync {
@@ -20,6 +30,15 @@ pub trait CollectionRepository: Send + Sync {
     /// Finds all collections
     async fn find_all(&self) -> Result<Vec<Collection>, crate::shared::error::RepositoryError>;
 
+    /// Finds a page of collections, with the number of collections on all pages
+    async fn find_page(
+        &self,
+        order: CollectionOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Collection>, i64), crate::shared::error::RepositoryError>;
+
     /// Finds collections by type
     async fn find_by_type(&self, collection_type: &str) -> Result<Vec<Collection>, crate::shared::error::RepositoryError>;
 
@@ -65,6 +84,9 @@ pub trait CollectionRepository: Send + Sync {
     /// Updates a collection item (e.g., to link media_id)
     async fn update_item(&self, item: &CollectionItem) -> Result<(), crate::shared::error::RepositoryError>;
 
+    /// Deletes one item of a collection, returning whether it existed
+    async fn delete_item(&self, collection_id: i64, item_id: i64) -> Result<bool, crate::shared::error::RepositoryError>;
+
     /// Deletes all items in a collection
     async fn delete_items(&self, collection_id: i64) -> Result<(), crate::shared::error::RepositoryError>;
 
@@ -18,6 +18,15 @@ pub struct CreditEntry {
     pub credit_type: CreditType,
 }
 
+/// A cast member with the number of library titles they appear in
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditedPerson {
+    pub person_id: i64,
+    pub name: String,
+    pub profile_url: Option<String>,
+    pub credits: i64,
+}
+
 /// Type of credit
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CreditType {
@@ -59,6 +68,12 @@ pub trait CreditsRepository: Send + Sync {
     /// Checks if credits exist for a media item
     async fn has_credits(&self, media_id: i64) -> Result<bool, RepositoryError>;
 
+    /// Gets the IDs of all media items a person is credited on
+    async fn get_media_ids_by_person(&self, person_id: i64) -> Result<Vec<i64>, RepositoryError>;
+
+    /// Gets the cast members of the library, most credited first
+    async fn credited_people(&self, limit: usize) -> Result<Vec<CreditedPerson>, RepositoryError>;
+
     /// Deletes all credits for a media item
     async fn delete_credits(&self, media_id: i64) -> Result<(), RepositoryError>;
 }
@@ -0,0 +1,79 @@
+//! CropRepository trait
+//!
+//! Repository interface for detected black bars, used to crop letterboxed
+//! video on request
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// Bars narrower than this share of the frame are left alone (encoder
+/// padding, slightly off-size masters)
+const MIN_BAR_RATIO: f64 = 0.02;
+
+/// Picture area of a video without its black bars, as found by cropdetect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CropDetection {
+    /// Width of the picture area
+    pub width: u32,
+    /// Height of the picture area
+    pub height: u32,
+    /// Left edge of the picture area
+    pub x: u32,
+    /// Top edge of the picture area
+    pub y: u32,
+    /// Width of the full frame
+    pub source_width: u32,
+    /// Height of the full frame
+    pub source_height: u32,
+}
+
+impl CropDetection {
+    /// Whether the bars are wide enough to be worth cropping
+    pub fn has_bars(&self) -> bool {
+        let removed = |kept: u32, full: u32| full > 0 && (full.saturating_sub(kept)) as f64 / full as f64 >= MIN_BAR_RATIO;
+        removed(self.width, self.source_width) || removed(self.height, self.source_height)
+    }
+
+    /// Aspect ratio of the picture area (2.40 for a scope film)
+    pub fn aspect_ratio(&self) -> f64 {
+        if self.height == 0 {
+            return 0.0;
+        }
+        self.width as f64 / self.height as f64
+    }
+
+    /// FFmpeg filter removing the bars
+    pub fn filter(&self) -> String {
+        format!("crop={}:{}:{}:{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// Repository for the detected crop of media items
+#[async_trait]
+pub trait CropRepository: Send + Sync {
+    /// Gets the detected crop of a media item
+    async fn find(&self, media_id: i64) -> Result<Option<CropDetection>, RepositoryError>;
+
+    /// Saves the detected crop of a media item (replaces the existing one)
+    async fn save(&self, media_id: i64, crop: &CropDetection) -> Result<(), RepositoryError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crop_detection() {
+        let scope = CropDetection { width: 1920, height: 800, x: 0, y: 140, source_width: 1920, source_height: 1080 };
+        assert!(scope.has_bars());
+        assert_eq!(format!("{:.2}", scope.aspect_ratio()), "2.40");
+        assert_eq!(scope.filter(), "crop=1920:800:0:140");
+
+        // A few lines of padding are not letterboxing
+        let padded = CropDetection { height: 1072, y: 4, ..scope };
+        assert!(!padded.has_bars());
+        let pillarboxed = CropDetection { width: 1440, height: 1080, x: 240, y: 0, ..scope };
+        assert!(pillarboxed.has_bars());
+    }
+}
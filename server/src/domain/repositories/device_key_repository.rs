@@ -0,0 +1,43 @@
+//! DeviceKeyRepository trait
+//!
+//! Repository interface for the API keys issued to devices. Only a hash of
+//! each key is stored; the key itself is shown once when it is created.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// An API key issued to a device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceKey {
+    pub id: i64,
+    /// Device name ("Living room tablet")
+    pub name: String,
+    /// User the device streams as
+    pub user_id: String,
+    /// First characters of the key, to tell keys apart
+    pub prefix: String,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Last authenticated request (ISO 8601), updated at most once a minute
+    pub last_used_at: Option<String>,
+}
+
+/// Repository for device API keys
+#[async_trait]
+pub trait DeviceKeyRepository: Send + Sync {
+    /// Stores a new key by its hash, returning the device
+    async fn create(&self, name: &str, user_id: &str, prefix: &str, key_hash: &str) -> Result<DeviceKey, RepositoryError>;
+
+    /// Gets the device a key hash belongs to
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<DeviceKey>, RepositoryError>;
+
+    /// Gets all devices, oldest first
+    async fn find_all(&self) -> Result<Vec<DeviceKey>, RepositoryError>;
+
+    /// Records that a device used its key
+    async fn touch(&self, id: i64) -> Result<(), RepositoryError>;
+
+    /// Removes a device and its key, returning whether it existed
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError>;
+}
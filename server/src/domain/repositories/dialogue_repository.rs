@@ -0,0 +1,59 @@
+//! DialogueRepository trait
+//!
+//! Repository interface for the indexed subtitle cues of media, used to
+//! find titles by what is said in them
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// An indexed subtitle file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleSource {
+    pub media_id: i64,
+    pub path: String,
+    pub language: Option<String>,
+    /// Size and modification time of the file when it was indexed
+    pub signature: String,
+}
+
+/// A cue to index
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueCue {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    pub text: String,
+}
+
+/// A cue matching a dialogue search
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueMatch {
+    pub media_id: i64,
+    pub language: Option<String>,
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    pub text: String,
+}
+
+/// Repository for indexed subtitle dialogue
+#[async_trait]
+pub trait DialogueRepository: Send + Sync {
+    /// Gets all indexed subtitle files
+    async fn find_sources(&self) -> Result<Vec<SubtitleSource>, RepositoryError>;
+
+    /// Indexes the cues of a subtitle file, replacing those indexed from it
+    /// before
+    async fn replace_source(&self, source: &SubtitleSource, cues: &[DialogueCue]) -> Result<(), RepositoryError>;
+
+    /// Removes a subtitle file and its cues from the index
+    async fn delete_source(&self, path: &str) -> Result<(), RepositoryError>;
+
+    /// Finds the cues containing the words of a query, best first
+    ///
+    /// Cues having the words as a phrase are searched first, then cues
+    /// having all of them; the last word matches as a prefix.
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<DialogueMatch>, RepositoryError>;
+}
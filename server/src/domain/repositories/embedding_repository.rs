@@ -0,0 +1,31 @@
+//! EmbeddingRepository trait
+//!
+//! Repository interface for embedding vectors of media overviews, used by
+//! semantic search
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Embedding vector of a media item's overview
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaEmbedding {
+    pub media_id: i64,
+    /// Embedding model that made the vector
+    pub model: String,
+    /// Hash of the embedded text
+    pub content_hash: String,
+    pub vector: Vec<f32>,
+}
+
+/// Repository for media embedding vectors
+#[async_trait]
+pub trait EmbeddingRepository: Send + Sync {
+    /// Content hashes of the media embedded with a model, by media ID
+    async fn find_hashes(&self, model: &str) -> Result<Vec<(i64, String)>, RepositoryError>;
+
+    /// All vectors made with a model
+    async fn find_by_model(&self, model: &str) -> Result<Vec<MediaEmbedding>, RepositoryError>;
+
+    /// Saves an embedding (replaces the media item's existing one)
+    async fn save(&self, embedding: &MediaEmbedding) -> Result<(), RepositoryError>;
+}
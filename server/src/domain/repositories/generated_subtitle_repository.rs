@@ -0,0 +1,41 @@
+//! GeneratedSubtitleRepository trait
+//!
+//! Repository interface for the subtitle files written by Whisper/Ollama
+//! generation, so they can be told apart from subtitles that came with the
+//! media.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// A subtitle file written by subtitle generation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedSubtitle {
+    pub media_id: i64,
+    /// Transcribed audio track (None for translated existing subtitles)
+    pub audio_track_index: Option<usize>,
+    /// Audio fingerprint (hex string, empty without transcription)
+    pub audio_fingerprint: String,
+    /// Language of the transcription or of the translated subtitle
+    pub source_language: Option<String>,
+    /// Language code of the written subtitle
+    pub language: String,
+    /// Path of the SRT file
+    pub subtitle_path: String,
+    pub duration_seconds: f64,
+    pub was_translated: bool,
+}
+
+/// Repository for generated subtitles
+#[async_trait]
+pub trait GeneratedSubtitleRepository: Send + Sync {
+    /// Records a generated subtitle, replacing an earlier one generated from
+    /// the same source into the same language
+    async fn save(&self, subtitle: &GeneratedSubtitle) -> Result<(), RepositoryError>;
+
+    /// Gets all recorded subtitles
+    async fn find_all(&self) -> Result<Vec<GeneratedSubtitle>, RepositoryError>;
+
+    /// Gets the subtitles generated for a media item
+    async fn find_by_media(&self, media_id: i64) -> Result<Vec<GeneratedSubtitle>, RepositoryError>;
+}
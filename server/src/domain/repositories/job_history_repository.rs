@@ -0,0 +1,29 @@
+//! JobHistoryRepository trait
+//!
+//! Repository interface for finished job runs, the basis of job completion
+//! estimates
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// A finished job run
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobRun {
+    /// Kind of job (e.g. "subtitle", "translation")
+    pub kind: String,
+    /// Seconds of media the job processed
+    pub media_seconds: f64,
+    /// Wall-clock seconds the job took
+    pub elapsed_seconds: f64,
+}
+
+/// Repository for the throughput history of jobs on this machine
+#[async_trait]
+pub trait JobHistoryRepository: Send + Sync {
+    /// Records a finished run
+    async fn record(&self, run: &JobRun) -> Result<(), RepositoryError>;
+
+    /// Seconds of media processed per second over the last `samples` runs
+    /// of a kind (None without history)
+    async fn throughput(&self, kind: &str, samples: u32) -> Result<Option<f64>, RepositoryError>;
+}
@@ -0,0 +1,55 @@
+//! JobQueueRepository trait
+//!
+//! Repository interface for background jobs, kept so they survive a
+//! restart and are listed across job types
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::shared::error::RepositoryError;
+
+/// A stored background job
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredJob {
+    pub id: String,
+    /// Kind of job (e.g. "subtitle", "subtitle_batch")
+    pub job_type: String,
+    /// "pending", "processing", "completed", "failed" or "cancelled"
+    pub state: String,
+    /// Unfinished jobs with a higher priority are resumed first
+    pub priority: i32,
+    /// Times the job was started (resumptions included)
+    pub attempts: u32,
+    /// Request the job is resumed from (None = not resumable)
+    pub payload: Option<serde_json::Value>,
+    /// Last known status of the job
+    pub status: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Stored jobs to list
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    pub state: Option<String>,
+    pub job_type: Option<String>,
+    /// Most jobs returned (0 = no limit)
+    pub limit: u32,
+}
+
+/// Repository for background jobs
+#[async_trait]
+pub trait JobQueueRepository: Send + Sync {
+    /// Stores a job, replacing its earlier state
+    async fn save(&self, job: &StoredJob) -> Result<(), RepositoryError>;
+
+    /// Gets a job by ID
+    async fn find(&self, id: &str) -> Result<Option<StoredJob>, RepositoryError>;
+
+    /// Pending and processing jobs, highest priority then oldest first
+    async fn find_unfinished(&self) -> Result<Vec<StoredJob>, RepositoryError>;
+
+    /// Jobs matching a filter, newest first
+    async fn list(&self, filter: &JobFilter) -> Result<Vec<StoredJob>, RepositoryError>;
+
+    /// Removes finished jobs last updated before `cutoff`, returning how many
+    async fn delete_finished_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError>;
+}
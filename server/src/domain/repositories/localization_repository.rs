@@ -0,0 +1,31 @@
+//! LocalizationRepository trait
+//!
+//! Repository interface for per-language title/overview variants of media
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Localized title and overview for a media item
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedMetadata {
+    pub media_id: i64,
+    pub language: String,
+    pub title: String,
+    pub overview: Option<String>,
+}
+
+/// Repository for localized media metadata
+#[async_trait]
+pub trait LocalizationRepository: Send + Sync {
+    /// Gets the localized variant of a media item for a language
+    async fn find(&self, media_id: i64, language: &str) -> Result<Option<LocalizedMetadata>, RepositoryError>;
+
+    /// Gets all stored localized variants of a media item
+    async fn find_by_media(&self, media_id: i64) -> Result<Vec<LocalizedMetadata>, RepositoryError>;
+
+    /// Saves a localized variant (replaces an existing one for the same language)
+    async fn save(&self, localized: &LocalizedMetadata) -> Result<(), RepositoryError>;
+
+    /// Deletes all localized variants of a media item
+    async fn delete_by_media(&self, media_id: i64) -> Result<(), RepositoryError>;
+}
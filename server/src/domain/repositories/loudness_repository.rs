@@ -0,0 +1,33 @@
+//! LoudnessRepository trait
+//!
+//! Repository interface for measured audio loudness, used by two-pass
+//! loudness normalization of streams
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Loudness of an audio track as measured by the first loudnorm pass
+/// (EBU R128)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoudnessMeasurement {
+    /// Integrated loudness (LUFS)
+    pub input_i: f64,
+    /// True peak (dBTP)
+    pub input_tp: f64,
+    /// Loudness range (LU)
+    pub input_lra: f64,
+    /// Gating threshold (LUFS)
+    pub input_thresh: f64,
+    /// Gain offset for the second pass (LU)
+    pub target_offset: f64,
+}
+
+/// Repository for the measured loudness of media audio tracks
+#[async_trait]
+pub trait LoudnessRepository: Send + Sync {
+    /// Gets the measurement of an audio track
+    async fn find(&self, media_id: i64, audio_track: usize) -> Result<Option<LoudnessMeasurement>, RepositoryError>;
+
+    /// Saves the measurement of an audio track (replaces the existing one)
+    async fn save(&self, media_id: i64, audio_track: usize, measurement: &LoudnessMeasurement) -> Result<(), RepositoryError>;
+}
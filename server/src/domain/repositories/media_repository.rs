@@ -6,6 +6,17 @@ use async_trait::async_trait;
 use crate::domain::entities::Media;
 use crate::domain::value_objects::{MediaType, ConfidenceScore, VerificationStatus};
 
+/// Sort keys of a page of media
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaOrder {
+    Title,
+    /// Release year
+    Year,
+    Rating,
+    /// Date added to the library
+    Added,
+}
+
 /// Repository for media data access
 #[async_trait]
 pub trait MediaRepository: Send + Sync {
@@ -18,12 +29,24 @@ pub trait MediaRepository: Send + Sync {
     /// Finds all media
     async fn find_all(&self) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
 
+    /// Finds a page of media, with the number of media on all pages
+    async fn find_page(
+        &self,
+        order: MediaOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Media>, i64), crate::shared::error::RepositoryError>;
+
     /// Finds media by type
     async fn find_by_type(&self, media_type: MediaType) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
 
     /// Finds media by series ID
     async fn find_by_series(&self, series_id: i64) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
 
+    /// Finds media by TMDB ID (movies, or all episodes of a TV show)
+    async fn find_by_tmdb_id(&self, tmdb_id: i64) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
+
     /// Finds media by series and season
     async fn find_by_season(&self, series_id: i64, season: i32) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
 
@@ -63,10 +86,12 @@ pub trait MediaRepository: Send + Sync {
     /// Finds unwatched media
     async fn find_unwatched(&self) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
 
-    /// Search media by title
+    /// Full-text search over titles, original titles, overviews and cast
+    ///
+    /// Words match as prefixes and tolerate typos; best matches come first.
     ///
     /// # Arguments
-    /// * `query` - Search query (partial match)
+    /// * `query` - Search query
     /// * `media_type` - Optional filter by media type ("movie" or "tv")
     /// * `limit` - Maximum results to return
     async fn search(
@@ -91,6 +116,12 @@ pub trait MediaRepository: Send + Sync {
     /// Marks media as unwatched
     async fn mark_unwatched(&self, media_id: i64) -> Result<(), crate::shared::error::RepositoryError>;
 
+    /// Marks several media items watched or unwatched in one transaction
+    ///
+    /// # Returns
+    /// * Number of items whose watch state changed
+    async fn set_watched_many(&self, media_ids: &[i64], watched: bool) -> Result<u64, crate::shared::error::RepositoryError>;
+
     /// Finds in-progress media (started but not finished)
     ///
     /// Returns media where current_position > 0 and is_watched = false,
@@ -101,4 +132,48 @@ pub trait MediaRepository: Send + Sync {
     ///
     /// Returns movies ordered by created_at descending
     async fn find_recent_movies(&self, limit: usize) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
+
+    /// Finds recently added episodes linked to a series
+    ///
+    /// Returns episodes ordered by created_at descending
+    async fn find_recent_episodes(&self, limit: usize) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
+
+    /// Finds media with artwork whose blurhash has not been computed yet
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum results to return
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
+
+    /// Stores blurhash placeholders for the current poster and backdrop
+    ///
+    /// `None` leaves the stored hash unchanged.
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), crate::shared::error::RepositoryError>;
+
+    /// Finds media whose content rating has not been fetched yet
+    ///
+    /// Movies need a TMDB ID; episodes are only returned once their series
+    /// has a content rating, which they inherit.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum results to return
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Media>, crate::shared::error::RepositoryError>;
+
+    /// Stores the content rating (empty if TMDB has none) and warnings
+    async fn update_content_rating(
+        &self,
+        id: i64,
+        content_rating: &str,
+        content_warnings: Option<&str>,
+    ) -> Result<(), crate::shared::error::RepositoryError>;
+
+    /// Rewrites file paths of media items in one transaction
+    ///
+    /// IDs are kept, so watch state and everything keyed by media ID stays
+    /// attached. Returns the number of items changed.
+    async fn update_paths(&self, paths: &[(i64, String)]) -> Result<u64, crate::shared::error::RepositoryError>;
 }
@@ -3,14 +3,72 @@
 //! Repository interfaces define the contract for data access implementations.
 //! They use domain entities and return domain errors.
 
+pub mod accessibility_preference_repository;
+pub mod artwork_repository;
+pub mod audit_log_repository;
+pub mod audio_preference_repository;
+pub mod bookmark_repository;
 pub mod cache_repository;
 pub mod collection_repository;
 pub mod credits_repository;
+pub mod crop_repository;
+pub mod device_key_repository;
+pub mod dialogue_repository;
+pub mod embedding_repository;
+pub mod generated_subtitle_repository;
+pub mod job_history_repository;
+pub mod job_queue_repository;
+pub mod localization_repository;
+pub mod loudness_repository;
 pub mod media_repository;
+pub mod parental_control_repository;
+pub mod person_repository;
+pub mod playlist_repository;
+pub mod watch_history_repository;
+pub mod webhook_repository;
+pub mod notification_channel_repository;
+pub mod scheduled_task_repository;
+pub mod settings_repository;
+pub mod profile_repository;
+pub mod quality_preference_repository;
 pub mod series_repository;
+pub mod stream_token_repository;
+pub mod subtitle_offset_repository;
+pub mod subtitle_preference_repository;
+pub mod tmdb_response_repository;
 
+pub use accessibility_preference_repository::{AccessibilityPreferenceRepository, AccessibilityPreferences};
+pub use artwork_repository::{ArtworkRepository, ArtworkOwner, ExtraArtwork};
+pub use audit_log_repository::{AuditLogRepository, AuditEntry};
+pub use audio_preference_repository::AudioPreferenceRepository;
+pub use bookmark_repository::{BookmarkRepository, Bookmark};
 pub use cache_repository::{CacheRepository, CacheStats};
-pub use collection_repository::CollectionRepository;
-pub use credits_repository::{CreditsRepository, CreditEntry, CreditType};
-pub use media_repository::MediaRepository;
-pub use series_repository::SeriesRepository;
+pub use collection_repository::{CollectionOrder, CollectionRepository};
+pub use credits_repository::{CreditsRepository, CreditEntry, CreditType, CreditedPerson};
+pub use crop_repository::{CropRepository, CropDetection};
+pub use device_key_repository::{DeviceKeyRepository, DeviceKey};
+pub use dialogue_repository::{DialogueCue, DialogueMatch, DialogueRepository, SubtitleSource};
+pub use embedding_repository::{EmbeddingRepository, MediaEmbedding};
+pub use generated_subtitle_repository::{GeneratedSubtitleRepository, GeneratedSubtitle};
+pub use job_history_repository::{JobHistoryRepository, JobRun};
+pub use job_queue_repository::{JobFilter, JobQueueRepository, StoredJob};
+pub use localization_repository::{LocalizationRepository, LocalizedMetadata};
+pub use loudness_repository::{LoudnessRepository, LoudnessMeasurement};
+pub use media_repository::{MediaOrder, MediaRepository};
+pub use parental_control_repository::{ParentalControlRepository, ParentalControls, PinFailures, StoredParentalControls};
+pub use person_repository::{PersonRepository, Person};
+pub use playlist_repository::{Playlist, PlaylistItem, PlaylistRepository};
+pub use stream_token_repository::StreamTokenRepository;
+pub use webhook_repository::{Webhook, WebhookDelivery, WebhookRepository, WebhookSettings};
+pub use notification_channel_repository::{NotificationChannel, NotificationChannelRepository, NotificationChannelSettings};
+pub use scheduled_task_repository::{ScheduledTaskRepository, StoredTask, TaskRun};
+pub use settings_repository::SettingsRepository;
+pub use watch_history_repository::{
+    MonthlyWatchTime, NewPlayback, PlaybackEntry, WatchHistoryRepository, WatchTime, WatchTotals,
+};
+pub use profile_repository::{Profile, ProfileRepository, ProfileSettings};
+pub use quality_preference_repository::QualityPreferenceRepository;
+pub use series_repository::{SeriesOrder, SeriesRepository};
+pub use subtitle_offset_repository::{SubtitleOffsetRepository, SubtitleOffset};
+pub use subtitle_preference_repository::SubtitlePreferenceRepository;
+pub use tmdb_response_repository::{RecordedResponse, TmdbResponseRepository};
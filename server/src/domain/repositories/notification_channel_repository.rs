@@ -0,0 +1,66 @@
+//! NotificationChannelRepository trait
+//!
+//! Repository interface for the channels (Discord, Telegram, Gotify,
+//! email) notifications are sent to.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::shared::error::RepositoryError;
+
+/// A destination of notifications
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NotificationChannel {
+    pub id: i64,
+    pub name: String,
+    /// "discord", "telegram", "gotify" or "email"
+    pub kind: String,
+    /// Settings of the kind, credentials included
+    pub config: serde_json::Value,
+    /// Event types notified; empty = all
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Updated at timestamp (ISO 8601)
+    pub updated_at: String,
+}
+
+impl NotificationChannel {
+    /// Whether events of a type are notified on this channel
+    pub fn accepts(&self, event_type: &str) -> bool {
+        self.enabled && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type))
+    }
+}
+
+/// Settings of a notification channel to add or change
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationChannelSettings {
+    pub name: String,
+    pub kind: String,
+    pub config: serde_json::Value,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+}
+
+/// Repository for notification channels
+#[async_trait]
+pub trait NotificationChannelRepository: Send + Sync {
+    /// Gets all channels, by ID
+    async fn find_all(&self) -> Result<Vec<NotificationChannel>, RepositoryError>;
+
+    /// Gets a channel
+    async fn find(&self, id: i64) -> Result<Option<NotificationChannel>, RepositoryError>;
+
+    /// Adds a channel, returning it with its ID
+    async fn create(&self, settings: &NotificationChannelSettings) -> Result<NotificationChannel, RepositoryError>;
+
+    /// Changes a channel, returning None when it does not exist
+    async fn update(
+        &self,
+        id: i64,
+        settings: &NotificationChannelSettings,
+    ) -> Result<Option<NotificationChannel>, RepositoryError>;
+
+    /// Removes a channel, returning whether it existed
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError>;
+}
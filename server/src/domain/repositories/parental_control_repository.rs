@@ -0,0 +1,60 @@
+//! ParentalControlRepository trait
+//!
+//! Repository interface for per-user parental controls. The override PIN is
+//! stored as a hash.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// Content a user may see
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParentalControls {
+    /// Highest allowed minimum age of a certification (13 allows "PG-13"
+    /// and "TV-14" is blocked); None = no rating limit
+    #[serde(default)]
+    pub max_age: Option<u8>,
+    /// Also hide titles without a known certification
+    #[serde(default)]
+    pub block_unrated: bool,
+    /// Genres or content warnings to hide ("Horror"), case-insensitive
+    #[serde(default)]
+    pub blocked_tags: Vec<String>,
+}
+
+/// Parental controls of a user with the hash of the override PIN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredParentalControls {
+    pub controls: ParentalControls,
+    /// Argon2 hash of the PIN (PHC string); None = no PIN set
+    pub pin_hash: Option<String>,
+}
+
+/// Wrong PIN entries of a user since the last right one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PinFailures {
+    pub count: u32,
+    pub last_at: Option<DateTime<Utc>>,
+}
+
+/// Repository for the parental controls of each user, keyed by a
+/// client-chosen user ID
+#[async_trait]
+pub trait ParentalControlRepository: Send + Sync {
+    /// Gets the controls of a user
+    async fn find(&self, user_id: &str) -> Result<Option<StoredParentalControls>, RepositoryError>;
+
+    /// Saves the controls of a user (replaces the existing ones, keeping
+    /// the PIN failures)
+    async fn save(&self, user_id: &str, controls: &StoredParentalControls) -> Result<(), RepositoryError>;
+
+    /// Removes the controls of a user, returning whether any were set
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError>;
+
+    /// Gets the wrong PIN entries of a user
+    async fn pin_failures(&self, user_id: &str) -> Result<PinFailures, RepositoryError>;
+
+    /// Records the wrong PIN entries of a user; a no-op without controls
+    async fn save_pin_failures(&self, user_id: &str, failures: &PinFailures) -> Result<(), RepositoryError>;
+}
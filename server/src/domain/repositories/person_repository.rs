@@ -0,0 +1,33 @@
+//! PersonRepository trait
+//!
+//! Repository interface for cached person (cast/crew) details
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::shared::error::RepositoryError;
+
+/// Cached person details
+#[derive(Debug, Clone, PartialEq)]
+pub struct Person {
+    /// TMDB person ID
+    pub id: i64,
+    pub name: String,
+    pub biography: Option<String>,
+    pub birthday: Option<String>,
+    pub deathday: Option<String>,
+    pub place_of_birth: Option<String>,
+    pub profile_url: Option<String>,
+    pub known_for_department: Option<String>,
+    pub imdb_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository for person data access
+#[async_trait]
+pub trait PersonRepository: Send + Sync {
+    /// Gets a cached person by TMDB person ID
+    async fn find_by_id(&self, id: i64) -> Result<Option<Person>, RepositoryError>;
+
+    /// Saves a person (replaces existing)
+    async fn save(&self, person: &Person) -> Result<(), RepositoryError>;
+}
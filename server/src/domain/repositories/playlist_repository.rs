@@ -0,0 +1,67 @@
+//! PlaylistRepository trait
+//!
+//! Repository interface for user playlists: named, ordered queues of movies
+//! and episodes, separate from collections.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::shared::error::RepositoryError;
+
+/// A playlist of a user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Playlist {
+    pub id: i64,
+    pub user_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Number of items
+    pub item_count: i64,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Updated at timestamp (ISO 8601)
+    pub updated_at: String,
+}
+
+/// An entry of a playlist; the same media may appear more than once
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlaylistItem {
+    pub id: i64,
+    pub media_id: i64,
+    /// Place in the playlist, from 0
+    pub position: i64,
+    /// Added at timestamp (ISO 8601)
+    pub added_at: String,
+}
+
+/// Repository for playlists
+#[async_trait]
+pub trait PlaylistRepository: Send + Sync {
+    /// Gets the playlists of a user, by name
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Playlist>, RepositoryError>;
+
+    /// Gets a playlist
+    async fn find(&self, id: i64) -> Result<Option<Playlist>, RepositoryError>;
+
+    /// Adds an empty playlist, returning it with its ID
+    async fn create(&self, user_id: &str, name: &str, description: Option<&str>) -> Result<Playlist, RepositoryError>;
+
+    /// Renames a playlist, returning None when it does not exist
+    async fn update(&self, id: i64, name: &str, description: Option<&str>) -> Result<Option<Playlist>, RepositoryError>;
+
+    /// Removes a playlist with its items, returning whether it existed
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError>;
+
+    /// Gets the items of a playlist, in order
+    async fn items(&self, playlist_id: i64) -> Result<Vec<PlaylistItem>, RepositoryError>;
+
+    /// Inserts an item at `position` (None or past the end = appended),
+    /// moving later items down
+    async fn add_item(&self, playlist_id: i64, media_id: i64, position: Option<i64>) -> Result<PlaylistItem, RepositoryError>;
+
+    /// Removes an item, moving later items up; returns whether it existed
+    async fn remove_item(&self, playlist_id: i64, item_id: i64) -> Result<bool, RepositoryError>;
+
+    /// Puts the items in the order of `item_ids`, which must list every item
+    /// of the playlist once
+    async fn reorder(&self, playlist_id: i64, item_ids: &[i64]) -> Result<(), RepositoryError>;
+}
@@ -0,0 +1,75 @@
+//! ProfileRepository trait
+//!
+//! Repository interface for user profiles: named viewers sharing a user,
+//! each with their own languages, avatar and kid mode. One profile of a
+//! user is active at a time.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// A profile of a user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: i64,
+    pub user_id: String,
+    #[serde(flatten)]
+    pub settings: ProfileSettings,
+    /// Whether streams of the user follow this profile
+    pub is_active: bool,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Updated at timestamp (ISO 8601)
+    pub updated_at: String,
+}
+
+/// What a profile stores
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileSettings {
+    /// Display name ("Anna")
+    pub name: String,
+    /// Avatar image URL or client-side avatar name
+    #[serde(default)]
+    pub avatar: Option<String>,
+    /// Preferred audio language (ISO 639-1, e.g. "hu")
+    #[serde(default)]
+    pub audio_language: Option<String>,
+    /// Preferred subtitle language (ISO 639-1)
+    #[serde(default)]
+    pub subtitle_language: Option<String>,
+    /// Language of the client UI and of localized metadata
+    #[serde(default)]
+    pub ui_language: Option<String>,
+    /// Only children's titles are shown (see parental controls)
+    #[serde(default)]
+    pub kid_mode: bool,
+}
+
+/// Repository for profiles
+#[async_trait]
+pub trait ProfileRepository: Send + Sync {
+    /// Gets the profiles of a user, oldest first
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Profile>, RepositoryError>;
+
+    /// Gets a profile
+    async fn find(&self, id: i64) -> Result<Option<Profile>, RepositoryError>;
+
+    /// Gets the active profile of a user
+    async fn find_active(&self, user_id: &str) -> Result<Option<Profile>, RepositoryError>;
+
+    /// Adds a profile, returning it with its ID; the first profile of a user
+    /// becomes active
+    async fn create(&self, user_id: &str, settings: &ProfileSettings) -> Result<Profile, RepositoryError>;
+
+    /// Replaces the settings of a profile, returning None when it does not
+    /// exist
+    async fn update(&self, id: i64, settings: &ProfileSettings) -> Result<Option<Profile>, RepositoryError>;
+
+    /// Makes a profile the active one of its user, returning whether it
+    /// exists
+    async fn activate(&self, id: i64) -> Result<bool, RepositoryError>;
+
+    /// Removes a profile, returning whether it existed; when it was active,
+    /// the user's oldest remaining profile becomes active
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError>;
+}
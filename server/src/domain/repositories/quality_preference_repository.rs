@@ -0,0 +1,21 @@
+//! QualityPreferenceRepository trait
+//!
+//! Repository interface for per-device stream quality preferences
+
+use async_trait::async_trait;
+use crate::domain::value_objects::QualityConstraint;
+use crate::shared::error::RepositoryError;
+
+/// Repository for remembered quality overrides, keyed by a client-chosen
+/// device ID
+#[async_trait]
+pub trait QualityPreferenceRepository: Send + Sync {
+    /// Gets the preference stored for a device
+    async fn find(&self, device_id: &str) -> Result<Option<QualityConstraint>, RepositoryError>;
+
+    /// Saves the preference for a device (replaces the existing one)
+    async fn save(&self, device_id: &str, constraint: &QualityConstraint) -> Result<(), RepositoryError>;
+
+    /// Removes the preference for a device, returning whether one existed
+    async fn delete(&self, device_id: &str) -> Result<bool, RepositoryError>;
+}
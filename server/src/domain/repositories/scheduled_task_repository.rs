@@ -0,0 +1,41 @@
+//! ScheduledTaskRepository trait
+//!
+//! Repository interface for what is kept of scheduled tasks across
+//! restarts: changes to their schedule and their last run
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::shared::error::RepositoryError;
+
+/// A finished run of a scheduled task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskRun {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub success: bool,
+    /// Summary of what was done, or the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Stored state of a scheduled task
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StoredTask {
+    pub name: String,
+    /// Cron expression replacing the configured one
+    pub schedule: Option<String>,
+    /// Replaces whether the task is configured to run
+    pub enabled: Option<bool>,
+    pub last_run: Option<TaskRun>,
+}
+
+/// Repository for scheduled task state
+#[async_trait]
+pub trait ScheduledTaskRepository: Send + Sync {
+    /// Every stored task
+    async fn find_all(&self) -> Result<Vec<StoredTask>, RepositoryError>;
+
+    /// Stores a task, replacing its earlier state
+    async fn save(&self, task: &StoredTask) -> Result<(), RepositoryError>;
+}
@@ -6,6 +6,17 @@ use async_trait::async_trait;
 use crate::domain::entities::Series;
 use crate::domain::value_objects::{ConfidenceScore, VerificationStatus};
 
+/// Sort keys of a page of series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesOrder {
+    Title,
+    /// First air date
+    Year,
+    Rating,
+    /// Date added to the library
+    Added,
+}
+
 /// Repository for series data access
 #[async_trait]
 pub trait SeriesRepository: Send + Sync {
@@ -21,6 +32,16 @@ pub trait SeriesRepository: Send + Sync {
     /// Finds all series
     async fn find_all(&self) -> Result<Vec<Series>, crate::shared::error::RepositoryError>;
 
+    /// Finds a page of series, deduplicated like `find_all`, with the number
+    /// of series on all pages
+    async fn find_page(
+        &self,
+        order: SeriesOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Series>, i64), crate::shared::error::RepositoryError>;
+
     /// Finds series by verification status
     async fn find_by_verification_status(
         &self,
@@ -86,4 +107,29 @@ pub trait SeriesRepository: Send + Sync {
         &self,
         limit: usize,
     ) -> Result<Vec<(Series, String)>, crate::shared::error::RepositoryError>;
+
+    /// Finds series with artwork whose blurhash has not been computed yet
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum results to return
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Series>, crate::shared::error::RepositoryError>;
+
+    /// Stores blurhash placeholders for the current poster and backdrop
+    ///
+    /// `None` leaves the stored hash unchanged.
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), crate::shared::error::RepositoryError>;
+
+    /// Finds series with a TMDB ID whose content rating has not been fetched yet
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum results to return
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Series>, crate::shared::error::RepositoryError>;
+
+    /// Stores the content rating (empty if TMDB has none)
+    async fn update_content_rating(&self, id: i64, content_rating: &str) -> Result<(), crate::shared::error::RepositoryError>;
 }
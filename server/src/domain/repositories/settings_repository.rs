@@ -0,0 +1,19 @@
+//! SettingsRepository trait
+//!
+//! Repository interface for runtime settings changed through the admin API,
+//! stored by key as JSON values.
+
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use crate::shared::error::RepositoryError;
+
+/// Repository trait for stored runtime settings
+#[async_trait]
+pub trait SettingsRepository: Send + Sync {
+    /// Gets the stored settings by key
+    async fn find_all(&self) -> Result<BTreeMap<String, serde_json::Value>, RepositoryError>;
+
+    /// Stores the settings given a value and removes those given `None`,
+    /// all or nothing
+    async fn save(&self, changes: &BTreeMap<String, Option<serde_json::Value>>) -> Result<(), RepositoryError>;
+}
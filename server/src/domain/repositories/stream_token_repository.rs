@@ -0,0 +1,24 @@
+//! StreamTokenRepository trait
+//!
+//! Repository interface for stream token sessions: those issued to device
+//! keys and those revoked before their tokens expire. Expiry times are unix
+//! timestamps, as in the tokens themselves.
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Repository for stream token sessions
+#[async_trait]
+pub trait StreamTokenRepository: Send + Sync {
+    /// Records a session issued to a device
+    async fn record(&self, session: &str, device_id: i64, expires_at: i64) -> Result<(), RepositoryError>;
+
+    /// Marks a session as revoked
+    async fn revoke(&self, session: &str, expires_at: i64) -> Result<(), RepositoryError>;
+
+    /// Revokes the unexpired sessions of a device, returning them with their expiry
+    async fn revoke_device(&self, device_id: i64, now: i64) -> Result<Vec<(String, i64)>, RepositoryError>;
+
+    /// Gets the revoked sessions that have not expired, dropping expired ones
+    async fn find_revoked(&self, now: i64) -> Result<Vec<(String, i64)>, RepositoryError>;
+}
@@ -0,0 +1,30 @@
+//! SubtitleOffsetRepository trait
+//!
+//! Repository interface for subtitle delay corrections made in the player,
+//! remembered per user, media item and subtitle track
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::RepositoryError;
+
+/// Stored subtitle delay for one track
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtitleOffset {
+    /// Subtitle track index (as in /v2/media/:id/tracks)
+    pub track_index: i32,
+    /// Delay in milliseconds; positive values show subtitles later
+    pub offset_ms: i64,
+}
+
+/// Repository for subtitle offsets
+#[async_trait]
+pub trait SubtitleOffsetRepository: Send + Sync {
+    /// Gets all offsets a user stored for a media item
+    async fn find(&self, user_id: &str, media_id: i64) -> Result<Vec<SubtitleOffset>, RepositoryError>;
+
+    /// Saves the offset of a track (replaces the existing one)
+    async fn save(&self, user_id: &str, media_id: i64, offset: &SubtitleOffset) -> Result<(), RepositoryError>;
+
+    /// Removes the offset of a track, returning whether one existed
+    async fn delete(&self, user_id: &str, media_id: i64, track_index: i32) -> Result<bool, RepositoryError>;
+}
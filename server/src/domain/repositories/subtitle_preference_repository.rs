@@ -0,0 +1,20 @@
+//! SubtitlePreferenceRepository trait
+//!
+//! Repository interface for per-user subtitle languages
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// Repository for the subtitle languages of each user (ISO 639-1 codes in
+/// order of preference), keyed by a client-chosen user ID
+#[async_trait]
+pub trait SubtitlePreferenceRepository: Send + Sync {
+    /// Gets the languages of a user (empty when none are set)
+    async fn find(&self, user_id: &str) -> Result<Vec<String>, RepositoryError>;
+
+    /// Saves the languages of a user (replaces the existing ones)
+    async fn save(&self, user_id: &str, languages: &[String]) -> Result<(), RepositoryError>;
+
+    /// Removes the languages of a user, returning whether any were set
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError>;
+}
@@ -0,0 +1,28 @@
+//! TmdbResponseRepository trait
+//!
+//! Repository interface for recorded TMDB responses, which an offline
+//! server answers TMDB requests from.
+
+use async_trait::async_trait;
+use crate::shared::error::RepositoryError;
+
+/// A TMDB response as received
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedResponse {
+    /// HTTP status (200, or 404 for lookups TMDB had nothing for)
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// Repository trait for recorded TMDB responses
+#[async_trait]
+pub trait TmdbResponseRepository: Send + Sync {
+    /// Gets the response recorded for a request (endpoint and language)
+    async fn find(&self, request: &str) -> Result<Option<RecordedResponse>, RepositoryError>;
+
+    /// Records the response to a request, replacing an earlier one
+    async fn save(&self, request: &str, response: &RecordedResponse) -> Result<(), RepositoryError>;
+
+    /// Number of recorded responses
+    async fn count(&self) -> Result<u64, RepositoryError>;
+}
@@ -0,0 +1,104 @@
+//! WatchHistoryRepository trait
+//!
+//! Repository interface for the playback history: one entry per stream
+//! session (what, when, how long, which device), aggregated into watch
+//! statistics.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::shared::error::RepositoryError;
+
+/// A finished playback
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlaybackEntry {
+    pub id: i64,
+    pub user_id: String,
+    pub media_id: i64,
+    /// Title of the media (None once it was removed)
+    pub title: Option<String>,
+    /// Device ID or user agent
+    pub client: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Seconds streamed
+    pub duration_seconds: i64,
+    pub bytes_sent: i64,
+    /// Whether the media was marked watched after this playback
+    pub completed: bool,
+}
+
+/// A playback to record
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPlayback {
+    pub user_id: String,
+    pub media_id: i64,
+    pub client: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+    pub bytes_sent: i64,
+}
+
+/// Totals of a user or the server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WatchTotals {
+    pub plays: i64,
+    pub seconds: i64,
+    /// Distinct media played
+    pub media_count: i64,
+    /// Plays that ended with the media watched
+    pub completed: i64,
+}
+
+/// Watch time of a series, a user or a device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchTime {
+    /// Series ID, user ID or device
+    pub key: String,
+    /// Series title (series only)
+    pub title: Option<String>,
+    pub plays: i64,
+    pub seconds: i64,
+}
+
+/// Watch time of a month
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MonthlyWatchTime {
+    /// "YYYY-MM"
+    pub month: String,
+    pub seconds: i64,
+}
+
+/// Repository for the playback history
+///
+/// Queries take `user = None` for the whole server.
+#[async_trait]
+pub trait WatchHistoryRepository: Send + Sync {
+    /// Records a playback
+    async fn record(&self, playback: &NewPlayback) -> Result<i64, RepositoryError>;
+
+    /// Marks the latest playback of a media item since `since` as
+    /// completed, returning whether there was one
+    async fn mark_completed(&self, media_id: i64, since: DateTime<Utc>) -> Result<bool, RepositoryError>;
+
+    /// Gets the latest playbacks, newest first
+    async fn recent(&self, user: Option<&str>, limit: u32) -> Result<Vec<PlaybackEntry>, RepositoryError>;
+
+    /// Gets the totals
+    async fn totals(&self, user: Option<&str>) -> Result<WatchTotals, RepositoryError>;
+
+    /// Gets the most watched series, by watch time
+    async fn top_series(&self, user: Option<&str>, limit: u32) -> Result<Vec<WatchTime>, RepositoryError>;
+
+    /// Gets the watch time per user, most first
+    async fn by_user(&self) -> Result<Vec<WatchTime>, RepositoryError>;
+
+    /// Gets the watch time per device, most first
+    async fn by_client(&self, user: Option<&str>, limit: u32) -> Result<Vec<WatchTime>, RepositoryError>;
+
+    /// Gets the watch time per month since `since` (months without
+    /// playback are left out), oldest first
+    async fn per_month(&self, user: Option<&str>, since: DateTime<Utc>) -> Result<Vec<MonthlyWatchTime>, RepositoryError>;
+
+    /// Gets the number of plays of every played media, by media ID
+    async fn plays_per_media(&self) -> Result<Vec<(i64, i64)>, RepositoryError>;
+}
@@ -0,0 +1,86 @@
+//! WebhookRepository trait
+//!
+//! Repository interface for outgoing webhooks and the log of their
+//! deliveries.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::shared::error::RepositoryError;
+
+/// An endpoint domain events are POSTed to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Webhook {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    /// Key of the HMAC-SHA256 signature of each body, never returned
+    #[serde(skip_serializing)]
+    pub secret: Option<String>,
+    /// Event types delivered; empty = all
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    /// Created at timestamp (ISO 8601)
+    pub created_at: String,
+    /// Updated at timestamp (ISO 8601)
+    pub updated_at: String,
+}
+
+impl Webhook {
+    /// Whether events of a type are delivered to this webhook
+    pub fn accepts(&self, event_type: &str) -> bool {
+        self.enabled && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type))
+    }
+}
+
+/// Settings of a webhook to add or change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSettings {
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+}
+
+/// Outcome of delivering an event to a webhook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_type: String,
+    /// Requests made, retries included
+    pub attempts: u32,
+    /// HTTP status of the last response
+    pub status_code: Option<u16>,
+    /// Why the last attempt failed
+    pub error: Option<String>,
+    pub success: bool,
+    /// Finished at timestamp (ISO 8601)
+    pub delivered_at: String,
+}
+
+/// Repository for webhooks
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    /// Gets all webhooks, by ID
+    async fn find_all(&self) -> Result<Vec<Webhook>, RepositoryError>;
+
+    /// Gets a webhook
+    async fn find(&self, id: i64) -> Result<Option<Webhook>, RepositoryError>;
+
+    /// Adds a webhook, returning it with its ID
+    async fn create(&self, settings: &WebhookSettings) -> Result<Webhook, RepositoryError>;
+
+    /// Changes a webhook, returning None when it does not exist
+    async fn update(&self, id: i64, settings: &WebhookSettings) -> Result<Option<Webhook>, RepositoryError>;
+
+    /// Removes a webhook with its deliveries, returning whether it existed
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError>;
+
+    /// Logs a delivery (its `id` is ignored), keeping the latest `keep` of
+    /// the webhook
+    async fn record_delivery(&self, delivery: &WebhookDelivery, keep: u32) -> Result<(), RepositoryError>;
+
+    /// Gets the latest deliveries of a webhook, newest first
+    async fn find_deliveries(&self, webhook_id: i64, limit: u32) -> Result<Vec<WebhookDelivery>, RepositoryError>;
+}
@@ -9,7 +9,8 @@ use async_trait::async_trait;
 use crate::domain::value_objects::{ConfidenceScore, IdentificationResult, MatchStrategy};
 
 /// Confidence level classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConfidenceLevel {
     /// High confidence (90-100%)
     High,
@@ -124,15 +125,19 @@ impl ConfidenceService for DefaultConfidenceService {
     async fn calculate_confidence(&self, result: &IdentificationResult) -> ConfidenceScore {
         let mut confidence = result.strategy.confidence_weight();
 
+        // Parsed fields count by how certain the parser was of them
+        // (fully without a breakdown)
+        let fields = result.field_confidence;
+
         // Adjust based on year match
         if result.year.is_some() {
-            confidence += 0.10;
+            confidence += 0.10 * fields.map_or(1.0, |f| f.year);
         }
 
         // Adjust based on season/episode presence for TV
         if result.media_type.is_episode() {
             if result.season.is_some() && result.episode.is_some() {
-                confidence += 0.15;
+                confidence += 0.15 * fields.map_or(1.0, |f| f.season_episode);
             }
         }
 
@@ -141,6 +146,9 @@ impl ConfidenceService for DefaultConfidenceService {
         if title_words.len() >= 2 {
             confidence += 0.05;
         }
+        if let Some(fields) = fields {
+            confidence -= 0.10 * (1.0 - fields.title);
+        }
 
         // Clamp to valid range
         ConfidenceScore::new(confidence.clamp(0.0, 1.0)).unwrap_or_default()
@@ -397,6 +405,23 @@ mod tests {
         assert_approx_eq(score, 0.85);
     }
 
+    #[tokio::test]
+    async fn test_calculate_confidence_weights_parsed_fields() {
+        use crate::domain::value_objects::{FieldConfidence, MediaType};
+
+        let service = DefaultConfidenceService::new();
+        let result = IdentificationResult::new(MediaType::Movie, "Heat".to_string(), MatchStrategy::FilenameOnly)
+            .with_year(Some(1995));
+        let unweighted = service.calculate_confidence(&result).await.value();
+
+        let certain = result.clone().with_field_confidence(FieldConfidence { title: 1.0, season_episode: 0.0, year: 1.0 });
+        assert_approx_eq(service.calculate_confidence(&certain).await.value(), unweighted);
+
+        let doubtful = result.with_field_confidence(FieldConfidence { title: 0.6, season_episode: 0.0, year: 0.5 });
+        // 0.05 less for the year, 0.04 less for the title
+        assert_approx_eq(service.calculate_confidence(&doubtful).await.value(), unweighted - 0.09);
+    }
+
     #[tokio::test]
     async fn test_combine_scores() {
         let service = DefaultConfidenceService::new();
@@ -5,14 +5,16 @@
 //! - Multi-episode detection (S01E01E02, S01E01-E02)
 //! - Quality/source/codec extraction
 //! - Release group detection
-//! - Folder structure analysis for series name extraction
+//! - Folder context for titles missing from the filename
+//! - Parser profiles per library folder (anime, sports, strict, ...)
 
 use async_trait::async_trait;
+use media_identifier::{MediaParser, ParserProfile};
 use regex::Regex;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use once_cell::sync::Lazy;
 
-use crate::domain::value_objects::{MediaType, IdentificationResult, MatchStrategy};
+use crate::domain::value_objects::{FieldConfidence, MediaType, IdentificationResult, MatchStrategy};
 use crate::shared::error::DomainError;
 
 // Regex patterns for folder structure analysis
@@ -20,10 +22,6 @@ static RE_SEASON_FOLDER: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)^(season\s*(\d+)|s(\d+))$").unwrap()
 });
 
-static RE_STRUCTURE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)^(season|s\d+|disc|disk|cd|dvd|part|pt|vol|volume)\b").unwrap()
-});
-
 static RE_SEASON_CHECK: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)s\d+").unwrap()
 });
@@ -32,11 +30,6 @@ static RE_SXXEXX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)S\d+E\d+").unwrap()
 });
 
-/// Patterns that indicate a media root directory (should not be used for title extraction)
-static RE_MEDIA_ROOT: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)^(movies?|films?|tv\s*(shows?|series)?|series|anime|media|videos?|downloads?|library|content|home\s*videos?)$").unwrap()
-});
-
 /// Folder structure pattern detection result
 #[derive(Debug, Clone, PartialEq)]
 pub enum FolderPattern {
@@ -57,265 +50,53 @@ pub trait IdentificationService: Send + Sync {
     async fn analyze_folder(&self, file_path: &str) -> Result<(FolderPattern, Option<String>), DomainError>;
     async fn extract_year(&self, text: &str) -> Result<Option<i32>, DomainError>;
     async fn is_anime(&self, file_path: &str, series_name: Option<&str>) -> Result<bool, DomainError>;
+    /// Parses a file path with the parser profile of its library
+    fn parse(&self, file_path: &str) -> media_identifier::ParsedMedia;
 }
 
 /// Default implementation of identification service using media-identifier crate
-pub struct DefaultIdentificationService;
+pub struct DefaultIdentificationService {
+    /// Parser for files outside the configured libraries
+    parser: MediaParser,
+    /// Parsers of library folders with their own profile
+    libraries: Vec<(PathBuf, MediaParser)>,
+}
 
 impl DefaultIdentificationService {
     pub fn new() -> Self {
-        Self
-    }
-
-    /// Parse filename using media-identifier and extract series name from folder if needed
-    ///
-    /// Strategy: FOLDER-FIRST for movies (scene releases have abbreviated filenames)
-    /// 1. For movies: Try folder name first, use filename only for fallback
-    /// 2. For episodes: Use filename for S01E01 detection, folder for series name
-    ///
-    /// Falls back to folder name parsing when:
-    /// - Filename gives low confidence (<60)
-    /// - Title is missing or too short (<3 chars)
-    /// - Title looks like an abbreviation (all caps, <=5 chars)
-    fn parse_with_folder_context(file_path: &str) -> media_identifier::ParsedMedia {
-        let path = Path::new(file_path);
-
-        // First, parse the filename to detect episode patterns and get baseline
-        let mut parsed = media_identifier::parse(file_path);
-        let filename_is_poor = Self::is_poor_parse_result(&parsed);
-
-        // For non-episodes (movies), try FOLDER FIRST - scene releases have better folder names
-        // Examples: "Back.to.the.Future.Part.Two.1989..." folder vs "walle-bttf.ii.720.mkv" file
-        if parsed.media_type != media_identifier::MediaType::Episode {
-            if let Some(folder_parsed) = Self::try_parse_parent_folder(path) {
-                let folder_is_poor = Self::is_poor_parse_result(&folder_parsed);
-
-                // Use folder if: folder is good, OR filename is poor and folder is at least as good
-                if !folder_is_poor || (filename_is_poor && Self::is_better_result(&folder_parsed, &parsed)) {
-                    // Merge: use title/year from folder, keep any useful info from filename
-                    if folder_parsed.title.is_some() {
-                        parsed.title = folder_parsed.title;
-                    }
-                    if folder_parsed.year.is_some() {
-                        parsed.year = folder_parsed.year;
-                    }
-                    // Update media type from folder if filename didn't detect it
-                    if parsed.media_type == media_identifier::MediaType::Unknown {
-                        parsed.media_type = folder_parsed.media_type;
-                    }
-                    // Use higher confidence
-                    parsed.confidence = parsed.confidence.max(folder_parsed.confidence);
-                }
-            }
-        } else {
-            // For episodes: filename has S01E01 patterns, but get series name from folder
-            // Only use folder fallback if filename gave poor title
-            if filename_is_poor {
-                if let Some(folder_parsed) = Self::try_parse_parent_folder(path) {
-                    if Self::is_better_result(&folder_parsed, &parsed) {
-                        // Keep episode info from filename, use title/year from folder
-                        if folder_parsed.title.is_some() {
-                            parsed.title = folder_parsed.title;
-                        }
-                        if folder_parsed.year.is_some() && parsed.year.is_none() {
-                            parsed.year = folder_parsed.year;
-                        }
-                        parsed.confidence = parsed.confidence.max(folder_parsed.confidence);
-                    }
-                }
-            }
-
-            // For episodes, also try to extract series name from folder structure
-            if let Some(series_name) = Self::extract_series_from_folder(path) {
-                // Episode filenames often contain only the episode title ("1x03 Foo"),
-                // while the parent folder contains the actual series name.
-                if let Some(ref title) = parsed.title {
-                    if !Self::titles_overlap(&series_name, title) {
-                        parsed.title = Some(series_name);
-                    }
-                } else {
-                    parsed.title = Some(series_name);
-                }
-            }
+        Self {
+            parser: MediaParser::new(),
+            libraries: Vec::new(),
         }
-
-        parsed
     }
 
-    /// Check if a parse result is poor (low confidence, missing/bad title)
-    fn is_poor_parse_result(parsed: &media_identifier::ParsedMedia) -> bool {
-        // Low confidence threshold
-        if parsed.confidence < 60 {
-            return true;
-        }
-
-        // Missing title
-        let title = match &parsed.title {
-            Some(t) if !t.is_empty() => t,
-            _ => return true,
-        };
-
-        // Title too short (likely abbreviation like "bttf")
-        if title.len() < 3 {
-            return true;
-        }
-
-        // Unknown media type with no year - likely poorly parsed
-        if parsed.media_type == media_identifier::MediaType::Unknown && parsed.year.is_none() {
-            return true;
-        }
-
-        // Title looks like it contains noise (resolution numbers like "720", "1080")
-        // This suggests the parser couldn't properly identify where the title ends
-        let words: Vec<&str> = title.split_whitespace().collect();
-        for word in &words {
-            if let Ok(num) = word.parse::<u16>() {
-                // Common resolution values that shouldn't be in titles
-                if matches!(num, 480 | 576 | 720 | 1080 | 2160) {
-                    return true;
-                }
-            }
-        }
-
-        // First word of title looks like abbreviation (short, all same case)
-        if let Some(first_word) = words.first() {
-            // Expanded to <=5 chars to catch scene group names like "walle"
-            if first_word.len() <= 5
-                && first_word.chars().all(|c| c.is_ascii_alphabetic())
-                && (first_word.chars().all(|c| c.is_ascii_uppercase())
-                    || first_word.chars().all(|c| c.is_ascii_lowercase()))
-            {
-                // Short abbreviation-like first word
-                return true;
-            }
-        }
-
-        // Check for common scene release patterns that indicate abbreviated filename
-        // e.g., "walle-bttf", "yify-movie", etc.
-        let title_lower = title.to_lowercase();
-        let scene_patterns = ["bttf", "yify", "sparks", "rarbg", "ettv"];
-        for pattern in scene_patterns {
-            if title_lower.contains(pattern) {
-                return true;
-            }
-        }
-
-        false
+    /// Parses files outside the configured libraries with `profile`
+    pub fn with_default_profile(mut self, profile: ParserProfile) -> Self {
+        self.parser = MediaParser::with_profile(profile);
+        self
     }
 
-    fn normalize_title_for_compare(title: &str) -> String {
-        title
-            .chars()
-            .filter(|c| c.is_alphanumeric())
-            .flat_map(|c| c.to_lowercase())
-            .collect()
-    }
-
-    fn titles_overlap(a: &str, b: &str) -> bool {
-        let a = Self::normalize_title_for_compare(a);
-        let b = Self::normalize_title_for_compare(b);
-
-        !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+    /// Parses the files under `folder` with `profile`
+    pub fn with_library_profile(mut self, folder: impl Into<PathBuf>, profile: ParserProfile) -> Self {
+        self.libraries.push((folder.into(), MediaParser::with_profile(profile)));
+        self
     }
 
-    /// Check if folder result is better than filename result
-    fn is_better_result(folder: &media_identifier::ParsedMedia, filename: &media_identifier::ParsedMedia) -> bool {
-        let folder_title = folder.title.as_ref().map(|t| t.as_str()).unwrap_or("");
-        let filename_title = filename.title.as_ref().map(|t| t.as_str()).unwrap_or("");
-
-        // Folder title is significantly longer (likely more complete)
-        if folder_title.len() > filename_title.len() + 3 {
-            return true;
-        }
-
-        // Folder has year but filename doesn't
-        if folder.year.is_some() && filename.year.is_none() {
-            return true;
-        }
-
-        // Folder has higher confidence
-        if folder.confidence > filename.confidence + 10 {
-            return true;
-        }
-
-        false
-    }
-
-    /// Try to parse the parent folder name (skipping media root directories)
-    fn try_parse_parent_folder(path: &Path) -> Option<media_identifier::ParsedMedia> {
-        let mut current = path;
-
-        // Try up to 3 levels of parent folders
-        for _ in 0..3 {
-            if let Some(parent) = current.parent() {
-                if let Some(folder_name) = parent.file_name() {
-                    let folder_str = folder_name.to_string_lossy();
-
-                    // Skip if this is a structural folder (Season X, S01, etc.)
-                    if RE_STRUCTURE.is_match(&folder_str) {
-                        current = parent;
-                        continue;
-                    }
-
-                    // Skip if this looks like a media root directory
-                    if Self::is_media_root_folder(&folder_str) {
-                        return None;
-                    }
-
-                    // Try to parse this folder name
-                    let folder_parsed = media_identifier::parse(&folder_str);
-
-                    // Only return if we got meaningful results
-                    if folder_parsed.title.is_some() && folder_parsed.confidence >= 50 {
-                        return Some(folder_parsed);
-                    }
-                }
-                current = parent;
-            } else {
-                break;
-            }
-        }
-
-        None
-    }
-
-    /// Check if a folder name looks like a media root directory
-    fn is_media_root_folder(folder_name: &str) -> bool {
-        // Match common media root patterns
-        RE_MEDIA_ROOT.is_match(folder_name)
+    /// Parser for a file: the profile of the innermost library containing it
+    fn parser_for(&self, file_path: &str) -> &MediaParser {
+        let path = Path::new(file_path);
+        self.libraries
+            .iter()
+            .filter(|(folder, _)| path.starts_with(folder))
+            .max_by_key(|(folder, _)| folder.components().count())
+            .map(|(_, parser)| parser)
+            .unwrap_or(&self.parser)
     }
 
-    /// Extract series name from folder structure
-    fn extract_series_from_folder(path: &Path) -> Option<String> {
-        let mut components = Vec::new();
-        let mut current = path;
-
-        for _ in 0..3 {
-            if let Some(parent) = current.parent() {
-                if let Some(name) = parent.file_name() {
-                    components.push(name.to_string_lossy().to_string());
-                    current = parent;
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
-
-        // Filter out structural folders (Season X, S01, etc.)
-        let filtered: Vec<String> = components
-            .into_iter()
-            .filter(|c| !RE_STRUCTURE.is_match(c) && !Self::is_media_root_folder(c))
-            .collect();
-
-        // Parse the first non-structural folder name
-        if let Some(folder_name) = filtered.first() {
-            let folder_parsed = media_identifier::parse(folder_name);
-            return folder_parsed.title;
-        }
-
-        None
+    /// Parse a file path, falling back to the parent folders where the
+    /// filename is not descriptive (see `MediaParser::parse_with_context`)
+    fn parse_with_folder_context(&self, file_path: &str) -> media_identifier::ParsedMedia {
+        self.parser_for(file_path).parse_with_context(file_path)
     }
 
     fn is_anime_sync(path: &Path, series_name: Option<&str>) -> bool {
@@ -349,8 +130,12 @@ impl Default for DefaultIdentificationService {
 
 #[async_trait]
 impl IdentificationService for DefaultIdentificationService {
+    fn parse(&self, file_path: &str) -> media_identifier::ParsedMedia {
+        self.parser_for(file_path).parse(file_path)
+    }
+
     async fn identify_media_type(&self, file_path: &str) -> Result<MediaType, DomainError> {
-        let parsed = media_identifier::parse(file_path);
+        let parsed = self.parse(file_path);
 
         Ok(match parsed.media_type {
             media_identifier::MediaType::Episode => MediaType::Episode,
@@ -369,7 +154,7 @@ impl IdentificationService for DefaultIdentificationService {
     }
 
     async fn extract_season_episode(&self, filename: &str) -> Result<Option<(i32, Vec<i32>)>, DomainError> {
-        let parsed = media_identifier::parse(filename);
+        let parsed = self.parser_for(filename).parse(filename);
 
         if let Some(season) = parsed.episode_info.season {
             if let Some(episode) = parsed.episode_info.episode {
@@ -386,7 +171,7 @@ impl IdentificationService for DefaultIdentificationService {
     }
 
     async fn clean_title(&self, title: &str) -> Result<String, DomainError> {
-        let parsed = media_identifier::parse(title);
+        let parsed = self.parser.parse(title);
         Ok(parsed.title.unwrap_or_else(|| title.to_string()))
     }
 
@@ -394,7 +179,7 @@ impl IdentificationService for DefaultIdentificationService {
         let path = Path::new(file_path);
 
         // Use media-identifier with folder context
-        let parsed = Self::parse_with_folder_context(file_path);
+        let parsed = self.parse_with_folder_context(file_path);
 
         // Convert media type
         let media_type = match parsed.media_type {
@@ -450,9 +235,16 @@ impl IdentificationService for DefaultIdentificationService {
         let is_anime = Self::is_anime_sync(path, parsed.title.as_deref());
 
         // Build result
+        let fields = parsed.field_confidence;
         let mut result = IdentificationResult::new(media_type, title.clone(), strategy)
             .with_year(parsed.year.map(|y| y as i32))
-            .with_series_name(parsed.title.clone());
+            .with_series_name(parsed.title.clone())
+            .with_country(parsed.country.clone())
+            .with_field_confidence(FieldConfidence {
+                title: fields.title as f32 / 100.0,
+                season_episode: fields.season_episode as f32 / 100.0,
+                year: fields.year as f32 / 100.0,
+            });
 
         if let Some(s) = season {
             result = result.with_season(Some(s));
@@ -471,6 +263,7 @@ impl IdentificationService for DefaultIdentificationService {
 
     async fn analyze_folder(&self, file_path: &str) -> Result<(FolderPattern, Option<String>), DomainError> {
         let path = Path::new(file_path);
+        let parser = self.parser_for(file_path);
         let mut components = Vec::new();
         let mut current = path;
 
@@ -490,7 +283,7 @@ impl IdentificationService for DefaultIdentificationService {
         if components.len() >= 2 {
             let p1 = components[0].to_lowercase();
             if p1.contains("season") || RE_SEASON_CHECK.is_match(&p1) {
-                let series_parsed = media_identifier::parse(&components[1]);
+                let series_parsed = parser.parse(&components[1]);
                 return Ok((FolderPattern::SeriesSeason, series_parsed.title));
             }
         }
@@ -500,13 +293,13 @@ impl IdentificationService for DefaultIdentificationService {
         }
 
         let name = path.file_name().unwrap_or_default().to_string_lossy();
-        let parsed = media_identifier::parse(&name);
+        let parsed = parser.parse(&name);
         if parsed.year.is_some() && parsed.media_type == media_identifier::MediaType::Movie {
             return Ok((FolderPattern::MovieYear, None));
         }
 
         if !components.is_empty() && RE_SXXEXX.is_match(&name) {
-            let series_parsed = media_identifier::parse(&components[0]);
+            let series_parsed = parser.parse(&components[0]);
             return Ok((FolderPattern::FlatSeries, series_parsed.title));
         }
 
@@ -514,12 +307,12 @@ impl IdentificationService for DefaultIdentificationService {
     }
 
     async fn extract_year(&self, text: &str) -> Result<Option<i32>, DomainError> {
-        let parsed = media_identifier::parse(text);
+        let parsed = self.parser.parse(text);
         Ok(parsed.year.map(|y| y as i32))
     }
 
     async fn is_anime(&self, file_path: &str, series_name: Option<&str>) -> Result<bool, DomainError> {
-        Ok(Self::is_anime_sync(Path::new(file_path), series_name))
+        Ok(self.parser_for(file_path).config().anime || Self::is_anime_sync(Path::new(file_path), series_name))
     }
 }
 
@@ -551,11 +344,10 @@ mod tests {
         let parsed = media_identifier::parse("Wonka.2023.mkv");
         assert_eq!(parsed.year, Some(2023));
 
-        // Note: Titles with years in them (like "2001: A Space Odyssey") may extract
-        // the title year rather than the release year. This is a known parser limitation.
+        // Of several year-like tokens the last is the release year
         let parsed = media_identifier::parse("2001.A.Space.Odyssey.1968.mkv");
-        // Parser sees 2001 first as a valid year (it's in range 1900-2030)
-        assert_eq!(parsed.year, Some(2001));
+        assert_eq!(parsed.title, Some("2001 A Space Odyssey".to_string()));
+        assert_eq!(parsed.year, Some(1968));
     }
 
     #[tokio::test]
@@ -613,61 +405,12 @@ mod tests {
         assert_eq!(parsed.episode_info.episode_end, Some(2));
     }
 
-    #[test]
-    fn test_is_poor_parse_result() {
-        // Good result - should NOT be poor (Episode type, has season/episode)
-        let good = media_identifier::parse("Breaking.Bad.S01E01.720p.mkv");
-        assert!(!DefaultIdentificationService::is_poor_parse_result(&good),
-            "Breaking Bad episode should not be flagged as poor");
-
-        // Unknown type with no year - should be poor
-        let unknown = media_identifier::parse("unknown.file.mkv");
-        assert!(DefaultIdentificationService::is_poor_parse_result(&unknown),
-            "Unknown media type with no year should be poor");
-
-        // Title with resolution number - should be poor
-        let with_res = media_identifier::parse("bttf.720.mkv");
-        assert!(DefaultIdentificationService::is_poor_parse_result(&with_res),
-            "Title containing '720' resolution should be poor");
-
-        // Abbreviation-like first word - should be poor
-        let abbrev = media_identifier::parse("walle-bttf.iii.mkv");
-        assert!(DefaultIdentificationService::is_poor_parse_result(&abbrev),
-            "Title starting with short abbreviation 'bttf' should be poor");
-
-        // Good movie with year - should NOT be poor
-        let movie = media_identifier::parse("Wonka.2023.720p.BluRay.mkv");
-        assert!(!DefaultIdentificationService::is_poor_parse_result(&movie),
-            "Movie with clear title and year should not be poor");
-    }
-
-    #[test]
-    fn test_is_media_root_folder() {
-        // Should match media root patterns
-        assert!(DefaultIdentificationService::is_media_root_folder("Movies"));
-        assert!(DefaultIdentificationService::is_media_root_folder("movies"));
-        assert!(DefaultIdentificationService::is_media_root_folder("TV Shows"));
-        assert!(DefaultIdentificationService::is_media_root_folder("TV Series"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Series"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Anime"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Media"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Videos"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Downloads"));
-        assert!(DefaultIdentificationService::is_media_root_folder("Library"));
-
-        // Should NOT match actual movie/show folders
-        assert!(!DefaultIdentificationService::is_media_root_folder("Back to the Future (1985)"));
-        assert!(!DefaultIdentificationService::is_media_root_folder("Breaking Bad"));
-        assert!(!DefaultIdentificationService::is_media_root_folder("Stargate SG-1"));
-        assert!(!DefaultIdentificationService::is_media_root_folder("2001 A Space Odyssey"));
-    }
-
     #[test]
     fn test_folder_fallback_back_to_the_future() {
         // Simulate the BTTF case: filename is abbreviated, folder has full title
         // Path: /media/Movies/Back to the Future III (1990)/walle-bttf.iii.720.mkv
         let path = "/media/Movies/Back to the Future III (1990)/walle-bttf.iii.720.mkv";
-        let parsed = DefaultIdentificationService::parse_with_folder_context(path);
+        let parsed = DefaultIdentificationService::new().parse_with_folder_context(path);
 
         // Should extract title from folder, not filename
         assert!(parsed.title.is_some(), "Title should be extracted");
@@ -684,7 +427,7 @@ mod tests {
         // When file is directly in media root, should not use root folder name
         // Path: /media/Movies/some-file.720.mkv
         let path = "/media/Movies/some-file.720.mkv";
-        let parsed = DefaultIdentificationService::parse_with_folder_context(path);
+        let parsed = DefaultIdentificationService::new().parse_with_folder_context(path);
 
         // Should NOT extract "Movies" as the title
         if let Some(ref title) = parsed.title {
@@ -701,7 +444,7 @@ mod tests {
         // When filename parsing is good, should not override with folder
         // Path: /media/Movies/Some Collection/Wonka.2023.720p.BluRay.mkv
         let path = "/media/Movies/Some Collection/Wonka.2023.720p.BluRay.mkv";
-        let parsed = DefaultIdentificationService::parse_with_folder_context(path);
+        let parsed = DefaultIdentificationService::new().parse_with_folder_context(path);
 
         // Should keep "Wonka" from filename, not "Some Collection" from folder
         assert!(parsed.title.is_some());
@@ -760,4 +503,20 @@ mod tests {
             result.title
         );
     }
+
+    #[tokio::test]
+    async fn test_library_profiles() {
+        let service = DefaultIdentificationService::new()
+            .with_library_profile("/media/Anime", ParserProfile::Anime)
+            .with_library_profile("/media/TV", ParserProfile::Strict);
+
+        let anime = "/media/Anime/Frieren/[SubsPlease] Sousou no Frieren - 12 (1080p).mkv";
+        assert_eq!(service.identify_media_type(anime).await.unwrap(), MediaType::Episode);
+        assert!(service.is_anime(anime, None).await.unwrap());
+
+        // Bare numbers are only guessed outside the strict library
+        assert!(service.extract_season_episode("/media/TV/Show/Show.117.HDTV.mkv").await.unwrap().is_none());
+        assert_eq!(service.extract_season_episode("/media/Other/Show.117.HDTV.mkv").await.unwrap(), Some((1, vec![17])));
+        assert_eq!(service.parse("/media/TV/Show/Show.117.HDTV.mkv").episode_info.episode, None);
+    }
 }
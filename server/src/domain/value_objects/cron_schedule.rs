@@ -0,0 +1,264 @@
+//! Cron Schedule Value Object
+//!
+//! Five-field cron expressions (minute, hour, day of month, month, day of
+//! week) for scheduled tasks. Fields take `*`, numbers, ranges (`1-5`),
+//! lists (`1,15`) and steps (`*/15`, `0-30/10`); `@hourly`, `@daily`,
+//! `@weekly` and `@monthly` are shorthands. Days of the week run from 0
+//! (Sunday) to 6, with 7 also Sunday. As in cron, when both the day of the
+//! month and the day of the week are restricted, either one matching is
+//! enough.
+
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, TimeZone, Timelike};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// How far ahead the next run is looked for (leap days come around every
+/// four years)
+const SEARCH_DAYS: i64 = 4 * 366;
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    /// Matching values as bit sets
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day fields are anything but `*`
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Schedule closest to running every `seconds`, and whether it matches
+    /// the interval exactly
+    ///
+    /// Intervals are rounded to minutes, hours or days that divide the next
+    /// larger unit evenly, since cron steps restart each hour or day.
+    pub fn every(seconds: u64) -> (Self, bool) {
+        // Largest divisor of `unit` not above `value`
+        let step = |value: u64, unit: u64| (1..=value.clamp(1, unit)).rev().find(|d| unit.is_multiple_of(*d)).unwrap_or(1);
+        let minutes = ((seconds + 30) / 60).max(1);
+        let (expression, period) = if minutes < 60 {
+            let step = step(minutes, 60);
+            (if step == 1 { "* * * * *".to_string() } else { format!("*/{} * * * *", step) }, step * 60)
+        } else if minutes < 24 * 60 {
+            let step = step((minutes + 30) / 60, 24);
+            (if step == 1 { "0 * * * *".to_string() } else { format!("0 */{} * * *", step) }, step * 3600)
+        } else if (minutes + 12 * 60) / (24 * 60) < 7 {
+            ("0 0 * * *".to_string(), 86400)
+        } else {
+            ("0 0 * * 0".to_string(), 7 * 86400)
+        };
+        let schedule = expression.parse().expect("interval schedules are valid");
+        (schedule, period == seconds)
+    }
+
+    /// First time after `after` the schedule matches, to the minute
+    ///
+    /// Times skipped by a daylight saving change never match; times
+    /// repeated by one match the first time.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(SEARCH_DAYS);
+
+        let mut time = start;
+        while time < limit {
+            if !matches(self.months, time.month()) {
+                let (year, month) = if time.month() == 12 { (time.year() + 1, 1) } else { (time.year(), time.month() + 1) };
+                time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(time.date()) {
+                time = (time.date() + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !matches(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !matches(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+
+            match timezone.from_local_datetime(&time) {
+                LocalResult::Single(found) | LocalResult::Ambiguous(found, _) if found > *after => return Some(found),
+                _ => time += Duration::minutes(1),
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = matches(self.days, date.day());
+        let weekday = matches(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+}
+
+fn matches(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into the set of values it matches
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<u64, String> {
+    let invalid = || format!("invalid {} '{}'", name, field);
+    let number = |value: &str| -> Result<u32, String> {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|v| (min..=max).contains(v))
+            .ok_or_else(|| format!("{} '{}' is not between {} and {}", name, value, min, max))
+    };
+
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(invalid)?),
+            None => (part, 1),
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (number(first)?, number(last)?),
+                // "5/10" counts from 5 to the end
+                None if part.contains('/') => (number(range)?, max),
+                None => {
+                    let value = number(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if first > last {
+            return Err(invalid());
+        }
+        for value in (first..=last).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = expression.trim();
+        let fields = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = fields.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "invalid schedule '{}', expected five fields (minute hour day-of-month month day-of-week)",
+                expression
+            ));
+        };
+
+        let mut weekdays = parse_field(weekday, "day of week", 0, 7)?;
+        // 7 is Sunday too
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days: parse_field(day, "day of month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl Serialize for CronSchedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    #[test]
+    fn test_every() {
+        let every = |seconds| {
+            let (schedule, exact) = CronSchedule::every(seconds);
+            (schedule.to_string(), exact)
+        };
+        assert_eq!(every(10), ("* * * * *".to_string(), false));
+        assert_eq!(every(900), ("*/15 * * * *".to_string(), true));
+        assert_eq!(every(420), ("*/6 * * * *".to_string(), false));
+        assert_eq!(every(3600), ("0 * * * *".to_string(), true));
+        assert_eq!(every(21600), ("0 */6 * * *".to_string(), true));
+        assert_eq!(every(18000), ("0 */4 * * *".to_string(), false));
+        assert_eq!(every(86400), ("0 0 * * *".to_string(), true));
+        assert_eq!(every(3 * 86400), ("0 0 * * *".to_string(), false));
+        assert_eq!(every(7 * 86400), ("0 0 * * 0".to_string(), true));
+    }
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        let schedule: CronSchedule = expression.parse().unwrap();
+        schedule.next_after(&at(after)).unwrap().to_rfc3339()
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 2026-03-14 is a Saturday
+        assert_eq!(next("0 * * * *", "2026-03-14T10:00:00Z"), "2026-03-14T11:00:00+00:00");
+        assert_eq!(next("*/15 * * * *", "2026-03-14T10:07:30Z"), "2026-03-14T10:15:00+00:00");
+        assert_eq!(next("30 3 * * *", "2026-03-14T04:00:00Z"), "2026-03-15T03:30:00+00:00");
+        assert_eq!(next("0 9 * * 1-5", "2026-03-14T10:00:00Z"), "2026-03-16T09:00:00+00:00");
+        assert_eq!(next("0 4 * * 7", "2026-03-14T10:00:00Z"), "2026-03-15T04:00:00+00:00");
+        assert_eq!(next("@monthly", "2026-12-15T00:00:00Z"), "2027-01-01T00:00:00+00:00");
+        assert_eq!(next("0 0 29 2 *", "2026-03-01T00:00:00Z"), "2028-02-29T00:00:00+00:00");
+        // Either day field matches when both are restricted
+        assert_eq!(next("0 0 1 * 1", "2026-03-14T10:00:00Z"), "2026-03-16T00:00:00+00:00");
+        assert_eq!(next("0 0 13 * 5", "2026-03-14T10:00:00Z"), "2026-03-20T00:00:00+00:00");
+
+        // Local time of the timezone given
+        let budapest = FixedOffset::east_opt(3600).unwrap();
+        let schedule: CronSchedule = "0 3 * * *".parse().unwrap();
+        let found = schedule.next_after(&at("2026-03-14T10:00:00Z").with_timezone(&budapest)).unwrap();
+        assert_eq!(found.to_rfc3339(), "2026-03-15T03:00:00+01:00");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for invalid in ["", "* * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "*/0 * * * *", "5-1 * * * *", "a * * * *", "0 0 * * 8"] {
+            assert!(invalid.parse::<CronSchedule>().is_err(), "{} should not parse", invalid);
+        }
+        assert!("0 30 * * *".parse::<CronSchedule>().unwrap_err().contains("hour"));
+        assert_eq!("  @daily ".parse::<CronSchedule>().unwrap().to_string(), "@daily");
+        // Impossible dates parse but never match
+        assert_eq!("0 0 31 2 *".parse::<CronSchedule>().unwrap().next_after(&Utc::now()), None);
+    }
+}
@@ -30,8 +30,44 @@ pub struct IdentificationResult {
     pub imdb_id: Option<String>,
     /// Series name (extracted from folder/filename)
     pub series_name: Option<String>,
+    /// Country of a remake or local version (ISO 3166 code, e.g. "US" for
+    /// "The Office US")
+    #[serde(default)]
+    pub country: Option<String>,
     /// Alternative matches (lower confidence)
     pub alternative_matches: Vec<AlternativeMatch>,
+    /// Confidence of the parsed fields (None when not parsed from a filename)
+    #[serde(default)]
+    pub field_confidence: Option<FieldConfidence>,
+}
+
+/// Confidence of the individual fields parsed from a filename (0.0 to 1.0,
+/// 0.0 when the field was not found)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FieldConfidence {
+    pub title: f32,
+    pub season_episode: f32,
+    pub year: f32,
+}
+
+impl FieldConfidence {
+    /// Fields below this confidence are reported as uncertain
+    pub const UNCERTAIN: f32 = 0.75;
+
+    /// Names of the found fields below [`Self::UNCERTAIN`]
+    ///
+    /// Season/episode is only considered for episodes.
+    pub fn uncertain_fields(&self, is_episode: bool) -> Vec<&'static str> {
+        [
+            ("title", self.title, true),
+            ("season_episode", self.season_episode, is_episode),
+            ("year", self.year, true),
+        ]
+        .into_iter()
+        .filter(|(_, confidence, applies)| *applies && *confidence > 0.0 && *confidence < Self::UNCERTAIN)
+        .map(|(name, _, _)| name)
+        .collect()
+    }
 }
 
 /// Alternative match with lower confidence
@@ -66,7 +102,9 @@ impl IdentificationResult {
             tmdb_id: None,
             imdb_id: None,
             series_name: None,
+            country: None,
             alternative_matches: Vec::new(),
+            field_confidence: None,
         }
     }
 
@@ -100,6 +138,12 @@ impl IdentificationResult {
         self
     }
 
+    /// Sets the country of the version
+    pub fn with_country(mut self, country: Option<String>) -> Self {
+        self.country = country;
+        self
+    }
+
     /// Sets the TMDB ID
     pub fn with_tmdb_id(mut self, tmdb_id: Option<i64>) -> Self {
         self.tmdb_id = tmdb_id;
@@ -112,6 +156,12 @@ impl IdentificationResult {
         self
     }
 
+    /// Sets the confidence of the parsed fields
+    pub fn with_field_confidence(mut self, field_confidence: FieldConfidence) -> Self {
+        self.field_confidence = Some(field_confidence);
+        self
+    }
+
     /// Sets the confidence score
     pub fn with_confidence(mut self, confidence: f32) -> Self {
         self.confidence = confidence.clamp(0.0, 1.0);
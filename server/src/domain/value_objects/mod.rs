@@ -5,16 +5,20 @@
 
 pub mod audio_track;
 pub mod confidence_score;
+pub mod cron_schedule;
 pub mod identification_result;
 pub mod match_strategy;
 pub mod media_type;
+pub mod quality_constraint;
 pub mod verification_status;
 pub mod video_details;
 
 pub use audio_track::AudioTrack;
 pub use confidence_score::ConfidenceScore;
-pub use identification_result::IdentificationResult;
+pub use cron_schedule::CronSchedule;
+pub use identification_result::{FieldConfidence, IdentificationResult};
 pub use match_strategy::MatchStrategy;
 pub use media_type::MediaType;
+pub use quality_constraint::{QualityConstraint, QualityPreset};
 pub use verification_status::VerificationStatus;
 pub use video_details::VideoDetails;
@@ -0,0 +1,130 @@
+//! Quality Constraint Value Object
+//!
+//! Client-chosen limits on stream quality that override the automatic
+//! copy/transcode decision.
+
+use serde::{Deserialize, Serialize};
+
+/// Resolution preset a client can force
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QualityPreset {
+    #[serde(rename = "2160p")]
+    P2160,
+    #[serde(rename = "1080p")]
+    P1080,
+    #[serde(rename = "720p")]
+    P720,
+    #[serde(rename = "480p")]
+    P480,
+    #[serde(rename = "360p")]
+    P360,
+}
+
+impl QualityPreset {
+    /// Parses a preset name ("2160p"/"4k", "1080p", "720p", "480p", "360p")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "2160p" | "2160" | "4k" | "uhd" => Some(QualityPreset::P2160),
+            "1080p" | "1080" | "fhd" => Some(QualityPreset::P1080),
+            "720p" | "720" | "hd" => Some(QualityPreset::P720),
+            "480p" | "480" | "sd" => Some(QualityPreset::P480),
+            "360p" | "360" => Some(QualityPreset::P360),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QualityPreset::P2160 => "2160p",
+            QualityPreset::P1080 => "1080p",
+            QualityPreset::P720 => "720p",
+            QualityPreset::P480 => "480p",
+            QualityPreset::P360 => "360p",
+        }
+    }
+
+    /// Maximum frame height of the preset in pixels
+    pub fn max_height(&self) -> u32 {
+        match self {
+            QualityPreset::P2160 => 2160,
+            QualityPreset::P1080 => 1080,
+            QualityPreset::P720 => 720,
+            QualityPreset::P480 => 480,
+            QualityPreset::P360 => 360,
+        }
+    }
+}
+
+/// Quality limits for a stream
+///
+/// An empty constraint (no preset, no bitrate) leaves the decision to the
+/// server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QualityConstraint {
+    /// Maximum resolution
+    pub quality: Option<QualityPreset>,
+    /// Maximum video bitrate in kbps
+    pub max_bitrate_kbps: Option<u32>,
+}
+
+impl QualityConstraint {
+    /// Lowest accepted video bitrate; anything below is unwatchable
+    pub const MIN_BITRATE_KBPS: u32 = 200;
+    /// Highest accepted video bitrate
+    pub const MAX_BITRATE_KBPS: u32 = 100_000;
+
+    /// Creates a constraint, clamping the bitrate to the supported range
+    pub fn new(quality: Option<QualityPreset>, max_bitrate_kbps: Option<u32>) -> Self {
+        Self {
+            quality,
+            max_bitrate_kbps: max_bitrate_kbps
+                .map(|b| b.clamp(Self::MIN_BITRATE_KBPS, Self::MAX_BITRATE_KBPS)),
+        }
+    }
+
+    /// Whether the constraint sets no limits
+    pub fn is_empty(&self) -> bool {
+        self.quality.is_none() && self.max_bitrate_kbps.is_none()
+    }
+
+    /// Height to scale a source of `source_height` pixels down to, if it
+    /// exceeds the preset
+    pub fn target_height(&self, source_height: u32) -> Option<u32> {
+        self.quality
+            .map(|q| q.max_height())
+            .filter(|max| source_height > *max)
+    }
+
+    /// Whether the video must be re-encoded to honor the constraint
+    ///
+    /// A bitrate cap always needs an encoder; a resolution cap only when the
+    /// source is larger.
+    pub fn requires_transcode(&self, source_height: u32) -> bool {
+        self.max_bitrate_kbps.is_some() || self.target_height(source_height).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_presets() {
+        assert_eq!(QualityPreset::parse("720p"), Some(QualityPreset::P720));
+        assert_eq!(QualityPreset::parse("4K"), Some(QualityPreset::P2160));
+        assert_eq!(QualityPreset::parse("auto"), None);
+    }
+
+    #[test]
+    fn test_requires_transcode() {
+        let constraint = QualityConstraint::new(Some(QualityPreset::P1080), None);
+        assert!(!constraint.requires_transcode(1080));
+        assert!(constraint.requires_transcode(2160));
+        assert_eq!(constraint.target_height(2160), Some(1080));
+
+        let constraint = QualityConstraint::new(None, Some(50));
+        assert_eq!(constraint.max_bitrate_kbps, Some(QualityConstraint::MIN_BITRATE_KBPS));
+        assert!(constraint.requires_transcode(480));
+        assert!(QualityConstraint::default().is_empty());
+    }
+}
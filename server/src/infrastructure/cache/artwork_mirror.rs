@@ -0,0 +1,87 @@
+//! Artwork Mirror
+//!
+//! Downloads remote artwork into the filesystem image cache. Images are keyed
+//! by their source URL, so the image proxy serves mirrored files as well.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::infrastructure::cache::ImageCache;
+use crate::interfaces::external_services::{tmdb_variant_url, ArtworkKind, ArtworkMirror};
+use crate::shared::error::TmdbError;
+
+/// Artwork mirror backed by [`ImageCache`]
+pub struct LocalArtworkMirror {
+    image_cache: Arc<ImageCache>,
+    http_client: Client,
+}
+
+impl LocalArtworkMirror {
+    /// Creates a new artwork mirror
+    ///
+    /// # Arguments
+    /// * `image_cache` - Filesystem cache to store images in
+    pub fn new(image_cache: Arc<ImageCache>) -> Self {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            image_cache,
+            http_client,
+        }
+    }
+}
+
+#[async_trait]
+impl ArtworkMirror for LocalArtworkMirror {
+    async fn fetch(&self, url: &str) -> Result<Option<Vec<u8>>, TmdbError> {
+        match self.image_cache.get_cached_image(url) {
+            Ok(Some(bytes)) => return Ok(Some(bytes)),
+            Ok(None) => {}
+            Err(e) => warn!("Cache read error for {}: {}, downloading again", url, e),
+        }
+
+        let response = self.http_client.get(url).send().await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(TmdbError::ApiError(status.as_u16()));
+        }
+
+        let bytes = response.bytes().await?.to_vec();
+        if let Err(e) = self.image_cache.save_cached_image(url, &bytes) {
+            warn!("Failed to save image to cache {}: {}", url, e);
+        }
+        Ok(Some(bytes))
+    }
+
+    async fn mirror(&self, url: &str, kind: ArtworkKind) -> usize {
+        let mut mirrored = 0;
+
+        // The stored URL itself is what the image proxy is asked for
+        let mut urls = vec![url.to_string()];
+        for size in kind.mirrored_sizes() {
+            let variant = tmdb_variant_url(url, kind.tmdb_size(*size));
+            if !urls.contains(&variant) {
+                urls.push(variant);
+            }
+        }
+
+        for variant in urls {
+            match self.fetch(&variant).await {
+                Ok(Some(_)) => mirrored += 1,
+                Ok(None) => debug!("Artwork not found upstream: {}", variant),
+                Err(e) => warn!("Failed to mirror artwork {}: {}", variant, e),
+            }
+        }
+
+        mirrored
+    }
+}
@@ -184,6 +184,8 @@ impl CacheRepository for DatabaseCache {
             total_entries,
             expired_entries,
             total_size_bytes,
+            hits: 0,
+            misses: 0,
             hit_rate: 0.0, // Would need tracking for real implementation
         })
     }
@@ -0,0 +1,47 @@
+//! Cache Hit Counter
+//!
+//! Counts the lookups of a cache that found an entry and those that did
+//! not, since the server started.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Lookup counter of a cache
+#[derive(Debug, Default)]
+pub struct HitCounter {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Lookups of a cache so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct HitStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Share of lookups that were hits (None before the first lookup)
+    pub hit_rate: Option<f64>,
+}
+
+impl HitCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a lookup
+    pub fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Lookups so far
+    pub fn stats(&self) -> HitStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+        HitStats {
+            hits,
+            misses,
+            hit_rate: (lookups > 0).then(|| hits as f64 / lookups as f64),
+        }
+    }
+}
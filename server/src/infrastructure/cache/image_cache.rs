@@ -2,18 +2,111 @@
 //!
 //! Provides filesystem-based caching for TMDB images.
 //! Images are cached in data/.cache/tmdb-images/ directory with SHA256-hashed filenames.
+//! Resized and re-encoded variants (see [`ImageTransform`]) are cached alongside
+//! the originals.
 
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
 use std::path::{Path, PathBuf};
 use sha2::{Sha256, Digest};
 use std::fs;
 use tracing::{debug, warn, error};
 use hex;
+use crate::infrastructure::cache::hit_counter::{HitCounter, HitStats};
 use crate::shared::error::FilesystemError;
 
+/// Largest width a variant can be resized to
+const MAX_VARIANT_WIDTH: u32 = 3840;
+
+/// Images are shrunk to this width before hashing; blurhash only keeps a few
+/// low-frequency components, so more pixels just cost time
+const BLURHASH_SAMPLE_WIDTH: u32 = 32;
+
+/// Output format of an image variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Jpeg,
+    Png,
+    WebP,
+    Avif,
+}
+
+impl OutputFormat {
+    /// Parses a format name ("jpeg", "jpg", "png", "webp", "avif")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "jpeg" | "jpg" => Some(OutputFormat::Jpeg),
+            "png" => Some(OutputFormat::Png),
+            "webp" => Some(OutputFormat::WebP),
+            "avif" => Some(OutputFormat::Avif),
+            _ => None,
+        }
+    }
+
+    /// MIME type of the format
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "image/jpeg",
+            OutputFormat::Png => "image/png",
+            OutputFormat::WebP => "image/webp",
+            OutputFormat::Avif => "image/avif",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "jpg",
+            OutputFormat::Png => "png",
+            OutputFormat::WebP => "webp",
+            OutputFormat::Avif => "avif",
+        }
+    }
+}
+
+/// Resize/re-encode options for an image variant
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImageTransform {
+    /// Target width in pixels (aspect ratio is kept; images are never upscaled)
+    pub width: Option<u32>,
+    /// Encoder quality 1-100 for JPEG and AVIF (default 80; WebP is lossless)
+    pub quality: Option<u8>,
+    /// Output format (default: same as the source, JPEG for unknown sources)
+    pub format: Option<OutputFormat>,
+}
+
+impl ImageTransform {
+    /// Whether the original image is served unchanged
+    pub fn is_identity(&self) -> bool {
+        self.width.is_none() && self.quality.is_none() && self.format.is_none()
+    }
+
+    /// Cache key of the variant (the source URL plus normalized options)
+    ///
+    /// Ends with the output extension, which the cache file name is built from.
+    fn cache_key(&self, url: &str, format: OutputFormat) -> String {
+        format!(
+            "{}#w={}&q={}.{}",
+            url,
+            self.width.map(|w| w.to_string()).unwrap_or_default(),
+            self.quality(),
+            format.extension()
+        )
+    }
+
+    fn quality(&self) -> u8 {
+        self.quality.unwrap_or(80).clamp(1, 100)
+    }
+}
+
 /// Image cache for TMDB images
 pub struct ImageCache {
     /// Base directory for cache (e.g., /data/.cache/tmdb-images/)
     cache_dir: PathBuf,
+    /// Lookups of originals and variants
+    lookups: HitCounter,
 }
 
 impl ImageCache {
@@ -37,7 +130,7 @@ impl ImageCache {
 
         debug!("Image cache initialized at: {:?}", cache_dir);
 
-        Ok(Self { cache_dir })
+        Ok(Self { cache_dir, lookups: HitCounter::new() })
     }
 
     /// Gets the cache file path for a given TMDB image URL
@@ -79,11 +172,13 @@ impl ImageCache {
 
         if !cache_path.exists() {
             debug!("Image not in cache: {}", url);
+            self.lookups.record(false);
             return Ok(None);
         }
 
         match fs::read(&cache_path) {
             Ok(bytes) => {
+                self.lookups.record(true);
                 debug!("Image retrieved from cache: {} ({} bytes)", url, bytes.len());
                 Ok(Some(bytes))
             }
@@ -126,10 +221,104 @@ impl ImageCache {
         }
     }
 
+    /// Gets a resized/re-encoded variant of an image, creating it on first access
+    ///
+    /// CPU-bound: call from a blocking task.
+    ///
+    /// # Arguments
+    /// * `url` - Source image URL (the variant is cached under a key derived from it)
+    /// * `original` - Source image bytes
+    /// * `transform` - Resize/re-encode options
+    ///
+    /// # Returns
+    /// * `Ok((bytes, content_type))` - Variant bytes and their MIME type
+    ///
+    /// # Errors
+    /// Returns error if the source cannot be decoded or the variant cannot be encoded
+    pub fn get_or_create_variant(
+        &self,
+        url: &str,
+        original: &[u8],
+        transform: &ImageTransform,
+    ) -> Result<(Vec<u8>, &'static str), FilesystemError> {
+        let source_format = image::guess_format(original).ok();
+        let format = transform.format.unwrap_or(match source_format {
+            Some(ImageFormat::Png) => OutputFormat::Png,
+            Some(ImageFormat::WebP) => OutputFormat::WebP,
+            Some(ImageFormat::Avif) => OutputFormat::Avif,
+            _ => OutputFormat::Jpeg,
+        });
+
+        let key = transform.cache_key(url, format);
+        if let Some(bytes) = self.get_cached_image(&key)? {
+            return Ok((bytes, format.content_type()));
+        }
+
+        let mut img = image::load_from_memory(original)
+            .map_err(|e| FilesystemError::ImageProcessing(e.to_string()))?;
+        if let Some(width) = transform.width {
+            let width = width.clamp(1, MAX_VARIANT_WIDTH);
+            if width < img.width() {
+                let height = ((img.height() as u64 * width as u64) / img.width() as u64).max(1) as u32;
+                img = img.resize_exact(width, height, FilterType::Lanczos3);
+            }
+        }
+
+        let bytes = Self::encode(&img, format, transform.quality())?;
+        if let Err(e) = self.save_cached_image(&key, &bytes) {
+            warn!("Failed to cache image variant {}: {}", key, e);
+        }
+        debug!("Image variant created: {} ({} bytes)", key, bytes.len());
+
+        Ok((bytes, format.content_type()))
+    }
+
+    /// Computes a blurhash placeholder for an image
+    ///
+    /// Uses 4 components along the long side and 3 along the short one, which
+    /// gives a ~28 character hash for posters and backdrops alike.
+    pub fn blurhash(original: &[u8]) -> Result<String, FilesystemError> {
+        let img = image::load_from_memory(original)
+            .map_err(|e| FilesystemError::ImageProcessing(e.to_string()))?;
+        if img.width() == 0 || img.height() == 0 {
+            return Err(FilesystemError::ImageProcessing("Empty image".to_string()));
+        }
+
+        let width = BLURHASH_SAMPLE_WIDTH.min(img.width());
+        let height = ((img.height() as u64 * width as u64) / img.width() as u64).max(1) as u32;
+        let sample = img.resize_exact(width, height, FilterType::Triangle).to_rgba8();
+        let (components_x, components_y) = if width >= height { (4, 3) } else { (3, 4) };
+
+        blurhash::encode(components_x, components_y, width, height, sample.as_raw())
+            .map_err(|e| FilesystemError::ImageProcessing(e.to_string()))
+    }
+
+    /// Encodes an image in the given format
+    fn encode(img: &DynamicImage, format: OutputFormat, quality: u8) -> Result<Vec<u8>, FilesystemError> {
+        let mut bytes = Vec::new();
+        let result = match format {
+            // JPEG has no alpha channel
+            OutputFormat::Jpeg => JpegEncoder::new_with_quality(&mut bytes, quality)
+                .encode_image(&DynamicImage::ImageRgb8(img.to_rgb8())),
+            OutputFormat::Png => img.write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png),
+            OutputFormat::WebP => img.to_rgba8().write_with_encoder(WebPEncoder::new_lossless(&mut bytes)),
+            OutputFormat::Avif => img.to_rgba8().write_with_encoder(
+                AvifEncoder::new_with_speed_quality(&mut bytes, 8, quality),
+            ),
+        };
+        result.map_err(|e| FilesystemError::ImageProcessing(e.to_string()))?;
+        Ok(bytes)
+    }
+
     /// Gets the cache directory path
     pub fn cache_dir(&self) -> &Path {
         &self.cache_dir
     }
+
+    /// Cache lookups since the server started
+    pub fn hit_stats(&self) -> HitStats {
+        self.lookups.stats()
+    }
 }
 
 #[cfg(test)]
@@ -177,4 +366,46 @@ mod tests {
         assert!(cached.is_some());
         assert_eq!(cached.unwrap(), test_data);
     }
+
+    #[test]
+    fn test_variant_resizes_and_converts() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ImageCache::new(temp_dir.path().to_str().unwrap()).unwrap();
+
+        let mut original = Vec::new();
+        DynamicImage::new_rgb8(400, 200)
+            .write_to(&mut std::io::Cursor::new(&mut original), ImageFormat::Png)
+            .unwrap();
+
+        let url = "https://image.tmdb.org/t/p/w780/backdrop.png";
+        let transform = ImageTransform {
+            width: Some(100),
+            quality: None,
+            format: Some(OutputFormat::WebP),
+        };
+        let (bytes, content_type) = cache.get_or_create_variant(url, &original, &transform).unwrap();
+        assert_eq!(content_type, "image/webp");
+
+        let variant = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((variant.width(), variant.height()), (100, 50));
+
+        // Cached under its own key, the original is untouched
+        assert!(cache.get_cached_image(&transform.cache_key(url, OutputFormat::WebP)).unwrap().is_some());
+        assert!(cache.get_cached_image(url).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_blurhash() {
+        let mut poster = Vec::new();
+        DynamicImage::new_rgb8(200, 300)
+            .write_to(&mut std::io::Cursor::new(&mut poster), ImageFormat::Png)
+            .unwrap();
+
+        // 3x4 components: 1 size + 1 max AC + 4 DC + 2 * 11 AC characters
+        let hash = ImageCache::blurhash(&poster).unwrap();
+        assert_eq!(hash.len(), 28);
+        assert!(blurhash::decode(&hash, 3, 4, 1.0).is_ok());
+
+        assert!(ImageCache::blurhash(b"not an image").is_err());
+    }
 }
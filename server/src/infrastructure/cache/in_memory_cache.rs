@@ -1,9 +1,13 @@
 //! In-Memory Cache Implementation
 //!
 //! Provides an in-memory implementation of CacheRepository interface
+//!
+//! Bounded by entry count and optionally by bytes (keys plus values), so a
+//! few large values cannot grow the process on memory-constrained hosts.
 
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -22,6 +26,10 @@ struct CacheEntry {
 pub struct InMemoryCache {
     entries: Arc<RwLock<HashMap<String, CacheEntry>>>,
     max_size: usize,
+    /// Byte budget for keys and values (None = unbounded)
+    max_bytes: Option<usize>,
+    /// Bytes held by keys and values; only changed under the write lock
+    used_bytes: AtomicUsize,
     ttl: Duration,
 }
 
@@ -35,10 +43,25 @@ impl InMemoryCache {
         Self {
             entries: Arc::new(RwLock::new(HashMap::new())),
             max_size,
+            max_bytes: None,
+            used_bytes: AtomicUsize::new(0),
             ttl,
         }
     }
 
+    /// Caps the memory held by keys and values
+    ///
+    /// Values larger than the whole budget are not cached.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Default time to live, used for values copied in from a slower cache
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
     /// Checks if an entry is expired
     fn is_expired(&self, entry: &CacheEntry) -> bool {
         if let Some(expires_at) = entry.expires_at {
@@ -49,37 +72,67 @@ impl InMemoryCache {
         }
     }
 
-    /// Cleans up expired entries
-    async fn cleanup_expired(&self) {
-        let mut entries = self.entries.write().await;
-        let now = chrono::Utc::now().timestamp();
-
-        entries.retain(|_, entry| !self.is_expired(entry));
+    /// Drops expired entries, returning how many were dropped
+    fn retain_unexpired(&self, entries: &mut HashMap<String, CacheEntry>) -> usize {
+        let initial_count = entries.len();
+        let mut freed = 0;
+        entries.retain(|key, entry| {
+            let keep = !self.is_expired(entry);
+            if !keep {
+                freed += entry_size(key, &entry.value);
+            }
+            keep
+        });
+        self.used_bytes.fetch_sub(freed, Ordering::Relaxed);
+        initial_count - entries.len()
+    }
 
-        if entries.len() < self.entries.read().await.len() {
-            // Some entries were removed
-            drop(entries);
+    /// Removes an entry, keeping the byte count
+    fn remove(&self, entries: &mut HashMap<String, CacheEntry>, key: &str) {
+        if let Some((key, entry)) = entries.remove_entry(key) {
+            self.used_bytes.fetch_sub(entry_size(&key, &entry.value), Ordering::Relaxed);
         }
     }
 
-    /// Evicts entries if cache is full (LRU policy)
-    async fn evict_if_needed(&self) {
-        let mut entries = self.entries.write().await;
+    /// Inserts an entry, evicting until it fits both budgets
+    ///
+    /// Evicts the least accessed entries first, oldest among equals. Takes
+    /// the already held write guard's map.
+    fn insert_bounded(&self, entries: &mut HashMap<String, CacheEntry>, key: &str, value: &str, expires_at: Option<i64>) {
+        self.remove(entries, key);
+        let size = entry_size(key, value);
+        if self.max_bytes.is_some_and(|max| size > max) {
+            return;
+        }
 
-        if entries.len() >= self.max_size {
-            // Find oldest entry (least recently accessed)
-            let oldest_key = entries
+        let mut used = self.used_bytes.load(Ordering::Relaxed);
+        while !entries.is_empty()
+            && (entries.len() >= self.max_size || self.max_bytes.is_some_and(|max| used + size > max))
+        {
+            let evicted = entries
                 .iter()
-                .min_by_key(|(_, v)| v.access_count)
+                .min_by_key(|(_, e)| (e.access_count, e.created_at))
                 .map(|(k, _)| k.clone());
-
-            if let Some(key) = oldest_key {
-                entries.remove(&key);
+            match evicted.and_then(|k| entries.remove_entry(&k)) {
+                Some((k, e)) => used -= entry_size(&k, &e.value),
+                None => break,
             }
         }
+
+        entries.insert(key.to_string(), CacheEntry {
+            value: value.to_string(),
+            expires_at,
+            created_at: Instant::now(),
+            access_count: 0,
+        });
+        self.used_bytes.store(used + size, Ordering::Relaxed);
     }
 }
 
+fn entry_size(key: &str, value: &str) -> usize {
+    key.len() + value.len()
+}
+
 #[async_trait]
 impl CacheRepository for InMemoryCache {
     async fn get(&self, key: &str) -> Result<Option<String>, RepositoryError> {
@@ -114,23 +167,14 @@ impl CacheRepository for InMemoryCache {
         let expires_at = Some(now + ttl as i64);
 
         let mut entries = self.entries.write().await;
-
-        // Evict if needed
-        self.evict_if_needed().await;
-
-        entries.insert(key.to_string(), CacheEntry {
-            value: value.to_string(),
-            expires_at,
-            created_at: Instant::now(),
-            access_count: 0,
-        });
+        self.insert_bounded(&mut entries, key, value, expires_at);
 
         Ok(())
     }
 
     async fn delete(&self, key: &str) -> Result<(), RepositoryError> {
         let mut entries = self.entries.write().await;
-        entries.remove(key);
+        self.remove(&mut entries, key);
         Ok(())
     }
 
@@ -142,6 +186,7 @@ impl CacheRepository for InMemoryCache {
     async fn clear(&self) -> Result<(), RepositoryError> {
         let mut entries = self.entries.write().await;
         entries.clear();
+        self.used_bytes.store(0, Ordering::Relaxed);
         Ok(())
     }
 
@@ -172,17 +217,8 @@ impl CacheRepository for InMemoryCache {
         let expires_at = Some(now + ttl as i64);
 
         let mut cache_entries = self.entries.write().await;
-
-        // Evict if needed
-        self.evict_if_needed().await;
-
         for (key, value) in entries {
-            cache_entries.insert(key.to_string(), CacheEntry {
-                value: value.to_string(),
-                expires_at,
-                created_at: Instant::now(),
-                access_count: 0,
-            });
+            self.insert_bounded(&mut cache_entries, key, value, expires_at);
         }
 
         Ok(())
@@ -192,7 +228,7 @@ impl CacheRepository for InMemoryCache {
         let mut entries = self.entries.write().await;
 
         for key in keys {
-            entries.remove(*key);
+            self.remove(&mut entries, key);
         }
 
         Ok(())
@@ -237,25 +273,44 @@ impl CacheRepository for InMemoryCache {
             total_entries: entries.len() as i64,
             expired_entries,
             total_size_bytes,
+            hits: 0,
+            misses: 0,
             hit_rate: 0.0, // Would need tracking for real implementation
         })
     }
 
     async fn clear_expired(&self) -> Result<usize, RepositoryError> {
         let mut entries = self.entries.write().await;
-        let now = chrono::Utc::now().timestamp();
-
-        let initial_count = entries.len();
-
-        entries.retain(|_, entry| !self.is_expired(entry));
-
-        let removed_count = initial_count - entries.len();
-
-        if removed_count > 0 {
-            // Some entries were removed
-            drop(entries);
-        }
+        Ok(self.retain_unexpired(&mut entries))
+    }
+}
 
-        Ok(removed_count)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_byte_budget() {
+        let cache = InMemoryCache::new(100, Duration::from_secs(60)).with_max_bytes(30);
+        cache.set("a", "0123456789", 60).await.unwrap();
+        cache.set("b", "0123456789", 60).await.unwrap();
+        cache.get("a").await.unwrap();
+
+        // Evicts the less used entry to stay within 30 bytes
+        cache.set("c", "0123456789", 60).await.unwrap();
+        assert!(cache.exists("a").await.unwrap());
+        assert!(!cache.exists("b").await.unwrap());
+        assert!(cache.exists("c").await.unwrap());
+
+        // Larger than the whole budget: not cached
+        cache.set("d", &"x".repeat(64), 60).await.unwrap();
+        assert!(!cache.exists("d").await.unwrap());
+        assert_eq!(cache.count().await.unwrap(), 2);
+
+        // Deleted entries free their bytes
+        cache.delete("a").await.unwrap();
+        cache.set("e", &"x".repeat(18), 60).await.unwrap();
+        assert!(cache.exists("c").await.unwrap());
+        assert!(cache.exists("e").await.unwrap());
     }
 }
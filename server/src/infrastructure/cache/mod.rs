@@ -5,15 +5,26 @@
 // - Database cache (L2)
 // - Multi-level cache with eviction policies
 // - TMDB-specific cache for external ID lookups
+// - Size-bounded transcode segment cache
+// - Whisper transcription cache
+// - Hit/miss counters for cache statistics
 
 pub mod in_memory_cache;
 pub mod database_cache;
 pub mod multi_level_cache;
 pub mod tmdb_cache;
 pub mod image_cache;
+pub mod artwork_mirror;
+pub mod transcode_cache;
+pub mod transcription_cache;
+pub mod hit_counter;
 
 pub use in_memory_cache::InMemoryCache;
 pub use database_cache::DatabaseCache;
 pub use multi_level_cache::MultiLevelCache;
 pub use tmdb_cache::{TmdbCache, TmdbCacheEntry};
-pub use image_cache::ImageCache;
+pub use image_cache::{ImageCache, ImageTransform, OutputFormat};
+pub use artwork_mirror::LocalArtworkMirror;
+pub use transcode_cache::TranscodeCache;
+pub use transcription_cache::TranscriptionCache;
+pub use hit_counter::{HitCounter, HitStats};
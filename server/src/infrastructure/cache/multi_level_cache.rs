@@ -1,17 +1,21 @@
 //! Multi-Level Cache Implementation
 //!
 //! Provides a multi-level cache combining in-memory (L1) and database (L2) caches
+//!
+//! Writes go to both levels and L2 hits are copied into L1, so L1 holds the
+//! hot entries within its byte budget. L2 is authoritative for counts and
+//! statistics.
 
 use async_trait::async_trait;
 use std::sync::Arc;
 use crate::domain::repositories::{CacheRepository, CacheStats};
 use crate::shared::error::RepositoryError;
-use crate::infrastructure::cache::{InMemoryCache, DatabaseCache};
+use crate::infrastructure::cache::InMemoryCache;
 
 /// Multi-level cache with L1 (in-memory) and L2 (database)
 pub struct MultiLevelCache {
     l1: Arc<InMemoryCache>,
-    l2: Arc<DatabaseCache>,
+    l2: Arc<dyn CacheRepository>,
 }
 
 impl MultiLevelCache {
@@ -20,7 +24,7 @@ impl MultiLevelCache {
     /// # Arguments
     /// * `l1` - L1 in-memory cache
     /// * `l2` - L2 database cache
-    pub fn new(l1: Arc<InMemoryCache>, l2: Arc<DatabaseCache>) -> Self {
+    pub fn new(l1: Arc<InMemoryCache>, l2: Arc<dyn CacheRepository>) -> Self {
         Self { l1, l2 }
     }
 }
@@ -34,8 +38,12 @@ impl CacheRepository for MultiLevelCache {
             return Ok(Some(value));
         }
 
-        // Fall back to L2
-        self.l2.get(key).await
+        // Fall back to L2, keeping the value in L1 for a while
+        let value = self.l2.get(key).await?;
+        if let Some(value) = &value {
+            self.l1.set(key, value, self.l1.ttl().as_secs()).await?;
+        }
+        Ok(value)
     }
 
     /// Set in both L1 and L2 caches
@@ -118,40 +126,19 @@ impl CacheRepository for MultiLevelCache {
         Ok(())
     }
 
-    /// Find keys matching pattern in both caches
+    /// Find keys matching pattern (L1 only holds copies of L2 entries)
     async fn find_keys(&self, pattern: &str) -> Result<Vec<String>, RepositoryError> {
-        let mut keys = Vec::new();
-
-        // Get from L1
-        let l1_keys = self.l1.find_keys(pattern).await?;
-        keys.extend(l1_keys);
-
-        // Get from L2
-        let l2_keys = self.l2.find_keys(pattern).await?;
-        keys.extend(l2_keys);
-
-        Ok(keys)
+        self.l2.find_keys(pattern).await
     }
 
-    /// Count total entries across both caches
+    /// Count entries (L1 only holds copies of L2 entries)
     async fn count(&self) -> Result<i64, RepositoryError> {
-        let l1_count = self.l1.count().await?;
-        let l2_count = self.l2.count().await?;
-
-        Ok(l1_count + l2_count)
+        self.l2.count().await
     }
 
-    /// Get combined statistics from both caches
+    /// Get statistics of L2, which holds every entry
     async fn get_stats(&self) -> Result<CacheStats, RepositoryError> {
-        let l1_stats = self.l1.get_stats().await?;
-        let l2_stats = self.l2.get_stats().await?;
-
-        Ok(CacheStats {
-            total_entries: l1_stats.total_entries + l2_stats.total_entries,
-            expired_entries: l1_stats.expired_entries + l2_stats.expired_entries,
-            total_size_bytes: l1_stats.total_size_bytes + l2_stats.total_size_bytes,
-            hit_rate: (l1_stats.hit_rate + l2_stats.hit_rate) / 2.0,
-        })
+        self.l2.get_stats().await
     }
 
     /// Clear expired entries from both caches
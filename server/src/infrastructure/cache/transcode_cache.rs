@@ -0,0 +1,313 @@
+//! Transcode Cache
+//!
+//! Keeps finished HLS segments on disk after their session ends, so playing
+//! the same file again with the same profile reuses the earlier transcode.
+//!
+//! Layout: `{root}/{media_id}/{profile}/seg_00000.ts`, plus a `source` file
+//! per media holding the fingerprint (size and modification time) of the
+//! file the segments were made from. The total size is bounded; the least
+//! recently used segments are evicted first.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{debug, info, warn};
+
+use crate::infrastructure::cache::hit_counter::{HitCounter, HitStats};
+use crate::shared::error::FilesystemError;
+
+/// Name of the per-media fingerprint file
+const SOURCE_FILE: &str = "source";
+
+/// A cached segment file
+struct CacheEntry {
+    size: u64,
+    last_used: SystemTime,
+}
+
+#[derive(Default)]
+struct CacheIndex {
+    entries: HashMap<PathBuf, CacheEntry>,
+    total_bytes: u64,
+}
+
+/// Size-bounded LRU cache of transcoded segments
+pub struct TranscodeCache {
+    root: PathBuf,
+    max_bytes: u64,
+    index: Mutex<CacheIndex>,
+    lookups: HitCounter,
+}
+
+impl TranscodeCache {
+    /// Opens the cache, indexing segments left by previous runs
+    ///
+    /// File modification times stand in for the last use of existing
+    /// segments. Anything over `max_bytes` is evicted right away.
+    ///
+    /// # Errors
+    /// Returns error if the cache directory cannot be created or read
+    pub fn new(root: impl Into<PathBuf>, max_bytes: u64) -> Result<Self, FilesystemError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+
+        let mut index = CacheIndex::default();
+        for media_dir in read_dirs(&root)? {
+            for profile_dir in read_dirs(&media_dir)? {
+                for entry in fs::read_dir(&profile_dir)? {
+                    let entry = entry?;
+                    let metadata = entry.metadata()?;
+                    if !metadata.is_file() {
+                        continue;
+                    }
+                    index.total_bytes += metadata.len();
+                    index.entries.insert(
+                        entry.path(),
+                        CacheEntry {
+                            size: metadata.len(),
+                            last_used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                        },
+                    );
+                }
+            }
+        }
+
+        info!(
+            "Transcode cache: {} segments, {} MB of {} MB",
+            index.entries.len(),
+            index.total_bytes / (1024 * 1024),
+            max_bytes / (1024 * 1024)
+        );
+        let cache = Self {
+            root,
+            max_bytes,
+            index: Mutex::new(index),
+            lookups: HitCounter::new(),
+        };
+        cache.evict();
+        Ok(cache)
+    }
+
+    /// Total size of the cached segments in bytes
+    pub fn size(&self) -> u64 {
+        self.lock().total_bytes
+    }
+
+    /// Number of cached segments
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Segment lookups since the server started
+    pub fn hit_stats(&self) -> HitStats {
+        self.lookups.stats()
+    }
+
+    /// Drops the cached segments of a media item if its file changed
+    ///
+    /// Call before using the cache for a media item; segments made from a
+    /// replaced file would otherwise be served for the new one.
+    pub fn validate_source(&self, media_id: i64, file_path: &str) -> Result<(), FilesystemError> {
+        let fingerprint = source_fingerprint(Path::new(file_path))?;
+        let source_file = self.media_dir(media_id).join(SOURCE_FILE);
+
+        match fs::read_to_string(&source_file) {
+            Ok(stored) if stored == fingerprint => return Ok(()),
+            Ok(_) => {
+                debug!("Source of media {} changed, dropping cached segments", media_id);
+                self.remove_media(media_id)?;
+            }
+            Err(_) => {}
+        }
+
+        fs::create_dir_all(self.media_dir(media_id))?;
+        fs::write(source_file, fingerprint)?;
+        Ok(())
+    }
+
+    /// Gets a cached segment, marking it as recently used
+    pub fn get(&self, media_id: i64, profile: &str, segment_file: &str) -> Option<PathBuf> {
+        let path = self.segment_path(media_id, profile, segment_file);
+        let now = SystemTime::now();
+        {
+            let mut index = self.lock();
+            let entry = index.entries.get_mut(&path);
+            self.lookups.record(entry.is_some());
+            entry?.last_used = now;
+        }
+
+        // Keep the recency across restarts (best effort)
+        if let Ok(file) = fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(now);
+        }
+        Some(path)
+    }
+
+    /// Adds a finished segment to the cache
+    ///
+    /// The segment is hard-linked when possible (copied otherwise), so the
+    /// session directory can be removed independently. Evicts the least
+    /// recently used segments when the cache grows over its limit.
+    pub fn store(&self, media_id: i64, profile: &str, segment_file: &str, source: &Path) -> Result<(), FilesystemError> {
+        let path = self.segment_path(media_id, profile, segment_file);
+        if self.lock().entries.contains_key(&path) {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::hard_link(source, &path).is_err() {
+            fs::copy(source, &path)?;
+        }
+
+        let size = fs::metadata(&path)?.len();
+        {
+            let mut index = self.lock();
+            index.total_bytes += size;
+            index.entries.insert(path, CacheEntry { size, last_used: SystemTime::now() });
+        }
+        self.evict();
+        Ok(())
+    }
+
+    /// Deletes all cached segments of a media item
+    pub fn remove_media(&self, media_id: i64) -> Result<(), FilesystemError> {
+        let dir = self.media_dir(media_id);
+        {
+            let mut index = self.lock();
+            let paths: Vec<PathBuf> = index.entries.keys().filter(|p| p.starts_with(&dir)).cloned().collect();
+            for path in paths {
+                if let Some(entry) = index.entries.remove(&path) {
+                    index.total_bytes -= entry.size;
+                }
+            }
+        }
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+
+    /// Removes least recently used segments until the cache fits its limit
+    fn evict(&self) {
+        let victims: Vec<PathBuf> = {
+            let mut index = self.lock();
+            if index.total_bytes <= self.max_bytes {
+                return;
+            }
+
+            let mut by_age: Vec<(SystemTime, PathBuf)> = index
+                .entries
+                .iter()
+                .map(|(path, entry)| (entry.last_used, path.clone()))
+                .collect();
+            by_age.sort();
+
+            let mut victims = Vec::new();
+            for (_, path) in by_age {
+                if index.total_bytes <= self.max_bytes {
+                    break;
+                }
+                if let Some(entry) = index.entries.remove(&path) {
+                    index.total_bytes -= entry.size;
+                    victims.push(path);
+                }
+            }
+            victims
+        };
+
+        debug!("Transcode cache over limit, evicting {} segments", victims.len());
+        for path in victims {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to evict cached segment {}: {}", path.display(), e);
+            }
+            // Drop the profile directory once it is empty
+            if let Some(parent) = path.parent() {
+                let _ = fs::remove_dir(parent);
+            }
+        }
+    }
+
+    fn media_dir(&self, media_id: i64) -> PathBuf {
+        self.root.join(media_id.to_string())
+    }
+
+    fn segment_path(&self, media_id: i64, profile: &str, segment_file: &str) -> PathBuf {
+        self.media_dir(media_id).join(profile).join(segment_file)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheIndex> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Subdirectories of `dir`
+fn read_dirs(dir: &Path) -> Result<Vec<PathBuf>, FilesystemError> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+/// Size and modification time of a source file
+///
+/// Changes when the file is replaced, so artifacts derived from it can be
+/// invalidated without hashing the contents.
+pub(crate) fn source_fingerprint(path: &Path) -> Result<String, FilesystemError> {
+    let metadata = fs::metadata(path).map_err(|_| FilesystemError::PathNotFound(path.display().to_string()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    Ok(format!("{}:{}", metadata.len(), modified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lru_eviction_and_source_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("movie.mkv");
+        fs::write(&source, b"original").unwrap();
+        let segment = temp_dir.path().join("seg.ts");
+        fs::write(&segment, [0u8; 100]).unwrap();
+
+        let cache = TranscodeCache::new(temp_dir.path().join("cache"), 250).unwrap();
+        cache.validate_source(1, source.to_str().unwrap()).unwrap();
+        cache.store(1, "720p", "seg_00000.ts", &segment).unwrap();
+        cache.store(1, "720p", "seg_00001.ts", &segment).unwrap();
+
+        // Using segment 0 makes segment 1 the eviction candidate
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(cache.get(1, "720p", "seg_00000.ts").is_some());
+        cache.store(1, "720p", "seg_00002.ts", &segment).unwrap();
+        assert_eq!(cache.size(), 200);
+        assert!(cache.get(1, "720p", "seg_00001.ts").is_none());
+        assert!(cache.get(1, "720p", "seg_00000.ts").is_some());
+
+        // Reopening picks up the segments from disk
+        let cache = TranscodeCache::new(temp_dir.path().join("cache"), 250).unwrap();
+        assert_eq!(cache.size(), 200);
+
+        // A replaced source file invalidates its segments
+        fs::write(&source, b"replacement file").unwrap();
+        cache.validate_source(1, source.to_str().unwrap()).unwrap();
+        assert_eq!(cache.size(), 0);
+        assert!(cache.get(1, "720p", "seg_00002.ts").is_none());
+    }
+}
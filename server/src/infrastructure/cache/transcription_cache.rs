@@ -0,0 +1,164 @@
+//! Transcription Cache
+//!
+//! Keeps the Whisper transcription of each media audio track, so
+//! subtitles in further languages are translated from it instead of
+//! transcribing the audio again.
+//!
+//! Transcriptions are stored as JSON in `{data_dir}/transcriptions/` and are
+//! only reused while both the media file and the Whisper model are unchanged.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use super::transcode_cache::source_fingerprint;
+use crate::infrastructure::external::{TranscriptionResult, WhisperModelInfo};
+use crate::shared::error::FilesystemError;
+
+/// A stored transcription with the metadata it is validated against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedTranscription {
+    pub media_id: i64,
+    pub audio_track: usize,
+    /// Spoken language (detected or requested)
+    pub language: String,
+    pub model: WhisperModelInfo,
+    /// Size and modification time of the media file
+    pub source_fingerprint: String,
+    pub transcription: TranscriptionResult,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filesystem cache of Whisper transcriptions
+pub struct TranscriptionCache {
+    cache_dir: PathBuf,
+}
+
+impl TranscriptionCache {
+    /// Creates a new transcription cache
+    ///
+    /// # Arguments
+    /// * `data_dir` - Base data directory (e.g., /data or ./data)
+    ///
+    /// # Errors
+    /// Returns error if cache directory cannot be created
+    pub fn new(data_dir: &str) -> Result<Self, FilesystemError> {
+        let cache_dir = Path::new(data_dir).join("transcriptions");
+        fs::create_dir_all(&cache_dir)?;
+        Ok(Self { cache_dir })
+    }
+
+    /// Gets the transcription of an audio track if it is still valid
+    ///
+    /// Transcriptions of a replaced media file or made with another model
+    /// are deleted. With `language` set, only a transcription in that
+    /// language is returned.
+    pub fn get(
+        &self,
+        media_id: i64,
+        audio_track: usize,
+        file_path: &str,
+        model: &WhisperModelInfo,
+        language: Option<&str>,
+    ) -> Option<CachedTranscription> {
+        let path = self.entry_path(media_id, audio_track);
+        let cached: CachedTranscription = match fs::read(&path).map(|bytes| serde_json::from_slice(&bytes)) {
+            Ok(Ok(cached)) => cached,
+            Ok(Err(e)) => {
+                warn!("Discarding unreadable transcription {}: {}", path.display(), e);
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+            Err(_) => return None,
+        };
+
+        let fingerprint = source_fingerprint(Path::new(file_path)).ok()?;
+        if cached.source_fingerprint != fingerprint || &cached.model != model {
+            debug!("Transcription of media {} track {} is stale, removing", media_id, audio_track);
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        if language.is_some_and(|l| l != cached.language) {
+            return None;
+        }
+        Some(cached)
+    }
+
+    /// Stores a transcription made from the current media file
+    pub fn store(
+        &self,
+        media_id: i64,
+        audio_track: usize,
+        file_path: &str,
+        model: WhisperModelInfo,
+        language: &str,
+        transcription: &TranscriptionResult,
+    ) -> Result<(), FilesystemError> {
+        let cached = CachedTranscription {
+            media_id,
+            audio_track,
+            language: language.to_string(),
+            model,
+            source_fingerprint: source_fingerprint(Path::new(file_path))?,
+            transcription: transcription.clone(),
+            created_at: Utc::now(),
+        };
+        let json = serde_json::to_vec(&cached).map_err(std::io::Error::from)?;
+        fs::write(self.entry_path(media_id, audio_track), json)?;
+        Ok(())
+    }
+
+    /// Deletes all transcriptions of a media item
+    pub fn remove_media(&self, media_id: i64) -> Result<(), FilesystemError> {
+        let prefix = format!("{}-", media_id);
+        for entry in fs::read_dir(&self.cache_dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, media_id: i64, audio_track: usize) -> PathBuf {
+        self.cache_dir.join(format!("{}-{}.json", media_id, audio_track))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::external::TranscriptionSegment;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reuse_and_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let video = temp_dir.path().join("movie.mkv");
+        fs::write(&video, b"video").unwrap();
+        let video = video.to_str().unwrap();
+
+        let cache = TranscriptionCache::new(temp_dir.path().to_str().unwrap()).unwrap();
+        let model = WhisperModelInfo { name: "ggml-small.bin".to_string(), version: "1:1".to_string() };
+        let transcription = TranscriptionResult {
+            segments: vec![TranscriptionSegment { start_time: 1.0, end_time: 2.5, text: "Hello".to_string() }],
+            detected_language: Some("en".to_string()),
+            duration_seconds: 60.0,
+            srt_content: String::new(),
+        };
+        cache.store(3, 0, video, model.clone(), "en", &transcription).unwrap();
+
+        let cached = cache.get(3, 0, video, &model, None).unwrap();
+        assert_eq!(cached.transcription.segments[0].text, "Hello");
+        assert!(cache.get(3, 0, video, &model, Some("en")).is_some());
+        assert!(cache.get(3, 0, video, &model, Some("de")).is_none());
+        assert!(cache.get(3, 1, video, &model, None).is_none());
+
+        // Another model invalidates the transcription
+        let medium = WhisperModelInfo { name: "ggml-medium.bin".to_string(), ..model.clone() };
+        assert!(cache.get(3, 0, video, &medium, None).is_none());
+        assert!(cache.get(3, 0, video, &model, None).is_none());
+    }
+}
@@ -12,7 +12,7 @@
 //! - Enables connection reuse
 //! - Provides monitoring capabilities
 
-use sqlx::{Pool, Sqlite, sqlite::SqliteConnectOptions, pool::PoolOptions};
+use sqlx::{ConnectOptions, Pool, Sqlite, sqlite::SqliteConnectOptions, pool::PoolOptions};
 use std::time::Duration;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -38,6 +38,10 @@ pub struct ConnectionPoolConfig {
     pub test_on_checkout: bool,
     /// Enable connection statistics (default: true)
     pub enable_metrics: bool,
+    /// Queries taking longer are logged as slow, in milliseconds (default: 1000)
+    pub slow_query_threshold_ms: u64,
+    /// SQLite page cache shared out across all connections, in MB (default: 64)
+    pub page_cache_mb: u64,
 }
 
 impl Default for ConnectionPoolConfig {
@@ -51,6 +55,8 @@ impl Default for ConnectionPoolConfig {
             max_lifetime_secs: 3600,
             test_on_checkout: true,
             enable_metrics: true,
+            slow_query_threshold_ms: 1000,
+            page_cache_mb: 64,
         }
     }
 }
@@ -133,6 +139,29 @@ impl ConnectionPoolConfig {
         self
     }
 
+    /// Sets the slow query threshold
+    ///
+    /// # Arguments
+    /// * `threshold_ms` - Duration in milliseconds above which queries are logged as slow
+    pub fn with_slow_query_threshold(mut self, threshold_ms: u64) -> Self {
+        self.slow_query_threshold_ms = threshold_ms;
+        self
+    }
+
+    /// Sets the SQLite page cache budget for the whole pool
+    ///
+    /// # Arguments
+    /// * `megabytes` - Cache size split evenly across the maximum connections
+    pub fn with_page_cache(mut self, megabytes: u64) -> Self {
+        self.page_cache_mb = megabytes;
+        self
+    }
+
+    /// Page cache per connection in KiB
+    fn page_cache_per_connection_kib(&self) -> u64 {
+        (self.page_cache_mb * 1024 / u64::from(self.max_connections.max(1))).max(1024)
+    }
+
     /// Validates configuration
     ///
     /// # Returns
@@ -237,22 +266,24 @@ impl ConnectionPool {
         config.validate()?;
 
         info!(
-            "Creating connection pool: max={}, min={}, timeout={}s",
+            "Creating connection pool: max={}, min={}, timeout={}s, page cache={}KiB per connection",
             config.max_connections,
             config.min_connections,
-            config.connection_timeout_secs
+            config.connection_timeout_secs,
+            config.page_cache_per_connection_kib()
         );
 
         // Build connection options
         let options = SqliteConnectOptions::from_str(&config.database_url)
             .map_err(|e| format!("Invalid database URL: {}", e))?
-            .create_if_missing(true);
+            .create_if_missing(true)
+            .log_slow_statements(log::LevelFilter::Warn, Duration::from_millis(config.slow_query_threshold_ms));
 
         // Configure SQLite pragmas for performance
         let options = options
             .pragma("journal_mode", "WAL") // Write-Ahead Logging for better concurrency
             .pragma("synchronous", "NORMAL") // Balance between safety and performance
-            .pragma("cache_size", "-64000") // 64MB cache
+            .pragma("cache_size", format!("-{}", config.page_cache_per_connection_kib())) // Negative = KiB
             .pragma("temp_store", "MEMORY") // Store temporary tables in memory
             .pragma("mmap_size", "268435456") // 256MB memory-mapped I/O
             .pragma("page_size", "4096"); // 4KB page size (matches filesystem)
@@ -389,30 +420,6 @@ impl ConnectionPool {
         info!("Database maintenance completed");
         Ok(())
     }
-
-    /// Applies database migrations
-    ///
-    /// # Arguments
-    /// * `migration_sql` - SQL migration to execute
-    ///
-    /// # Returns
-    /// * `Result<(), sqlx::Error>` - Success or error
-    pub async fn apply_migration(&self, migration_sql: &str) -> Result<(), sqlx::Error> {
-        info!("Applying database migration");
-
-        // Split migration into individual statements
-        for statement in migration_sql.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() {
-                sqlx::query(statement)
-                    .execute(&self.pool)
-                    .await?;
-            }
-        }
-
-        info!("Migration applied successfully");
-        Ok(())
-    }
 }
 
 impl Drop for ConnectionPool {
@@ -443,7 +450,8 @@ mod tests {
             .with_idle_timeout(300)
             .with_max_lifetime(1800)
             .with_test_on_checkout(false)
-            .with_metrics(false);
+            .with_metrics(false)
+            .with_page_cache(40);
 
         assert_eq!(config.max_connections, 20);
         assert_eq!(config.min_connections, 5);
@@ -452,6 +460,7 @@ mod tests {
         assert_eq!(config.max_lifetime_secs, 1800);
         assert!(!config.test_on_checkout);
         assert!(!config.enable_metrics);
+        assert_eq!(config.page_cache_per_connection_kib(), 2048);
     }
 
     #[test]
@@ -0,0 +1,289 @@
+//! Versioned Migrations
+//!
+//! The schema is built by embedded migrations applied in version order: SQL
+//! files in `server/migrations/` named `NNNN_name.sql`, and data migrations
+//! written in Rust where SQL is not enough. Every applied migration is
+//! recorded in `schema_migrations` with a checksum of its content.
+//!
+//! Each migration runs in one transaction together with its bookkeeping, so
+//! it is either fully applied or not at all. A migration whose row was
+//! started but never finished was interrupted (e.g. the server was killed)
+//! and is rolled back and retried. A migration that was changed after it was
+//! applied, or a database from a newer release, stops startup.
+//!
+//! Released migrations are never edited: schema changes go into a new file
+//! with the next version.
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, Pool, Sqlite, SqliteConnection};
+use tracing::{info, warn};
+
+use super::schema;
+use crate::shared::error::MigrationError;
+
+/// Data migration written in Rust
+type RustStep = for<'c> fn(&'c mut SqliteConnection) -> BoxFuture<'c, Result<(), sqlx::Error>>;
+
+/// What a migration runs
+enum Step {
+    Sql(&'static str),
+    Rust(RustStep),
+}
+
+/// One schema migration
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    step: Step,
+}
+
+impl Migration {
+    const fn sql(version: i64, name: &'static str, sql: &'static str) -> Self {
+        Self { version, name, step: Step::Sql(sql) }
+    }
+
+    const fn rust(version: i64, name: &'static str, run: RustStep) -> Self {
+        Self { version, name, step: Step::Rust(run) }
+    }
+
+    /// SHA-256 of the SQL, or of the name for Rust migrations
+    pub fn checksum(&self) -> String {
+        let content = match &self.step {
+            Step::Sql(sql) => sql.to_string(),
+            Step::Rust(_) => format!("rust:{}", self.name),
+        };
+        hex::encode(Sha256::digest(content.as_bytes()))
+    }
+}
+
+/// All migrations, in version order
+static MIGRATIONS: &[Migration] = &[
+    Migration::sql(1, "initial_schema", include_str!("../../../migrations/0001_initial_schema.sql")),
+    Migration::rust(2, "legacy_columns", |conn| Box::pin(schema::add_legacy_columns(conn))),
+    Migration::rust(3, "backfill_episode_end", |conn| Box::pin(schema::backfill_episode_end(conn))),
+    Migration::sql(4, "media_search", include_str!("../../../migrations/0004_media_search.sql")),
+    Migration::sql(5, "media_embeddings", include_str!("../../../migrations/0005_media_embeddings.sql")),
+    Migration::sql(6, "subtitle_dialogue", include_str!("../../../migrations/0006_subtitle_dialogue.sql")),
+    Migration::sql(7, "webhooks", include_str!("../../../migrations/0007_webhooks.sql")),
+    Migration::sql(8, "notification_channels", include_str!("../../../migrations/0008_notification_channels.sql")),
+    Migration::sql(9, "settings", include_str!("../../../migrations/0009_settings.sql")),
+    Migration::sql(10, "tmdb_responses", include_str!("../../../migrations/0010_tmdb_responses.sql")),
+    Migration::sql(11, "jobs", include_str!("../../../migrations/0011_jobs.sql")),
+    Migration::sql(12, "scheduled_tasks", include_str!("../../../migrations/0012_scheduled_tasks.sql")),
+    Migration::sql(13, "device_users", include_str!("../../../migrations/0013_device_users.sql")),
+    Migration::sql(14, "parental_pin_failures", include_str!("../../../migrations/0014_parental_pin_failures.sql")),
+    Migration::sql(15, "phase8_indexes", include_str!("../../../migrations/0015_phase8_indexes.sql")),
+    Migration::sql(16, "stream_tokens", include_str!("../../../migrations/0016_stream_tokens.sql")),
+];
+
+/// State of a migration in a database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Started but never finished; retried on the next run
+    Interrupted,
+    /// Applied with a different checksum than this release has
+    Modified,
+}
+
+/// A migration and its state
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: &'static str,
+    pub state: MigrationState,
+}
+
+/// A row of `schema_migrations`
+struct AppliedMigration {
+    version: i64,
+    checksum: String,
+    finished: bool,
+}
+
+/// State of every migration, without changing the database
+///
+/// This is the dry run: everything not `Applied` is what [`migrate`] would
+/// do next.
+pub async fn status(pool: &Pool<Sqlite>) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let applied = applied(pool).await?;
+    if let Some(unknown) = applied.iter().find(|row| !MIGRATIONS.iter().any(|m| m.version == row.version)) {
+        return Err(MigrationError::UnknownVersion(unknown.version));
+    }
+
+    Ok(MIGRATIONS.iter()
+        .map(|migration| {
+            let state = match applied.iter().find(|row| row.version == migration.version) {
+                None => MigrationState::Pending,
+                Some(row) if !row.finished => MigrationState::Interrupted,
+                Some(row) if row.checksum != migration.checksum() => MigrationState::Modified,
+                Some(_) => MigrationState::Applied,
+            };
+            MigrationStatus { version: migration.version, name: migration.name, state }
+        })
+        .collect())
+}
+
+/// Applies the pending migrations in version order
+///
+/// Returns the number of migrations applied.
+pub async fn migrate(pool: &Pool<Sqlite>) -> Result<usize, MigrationError> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let statuses = status(pool).await?;
+    if let Some(modified) = statuses.iter().find(|s| s.state == MigrationState::Modified) {
+        return Err(MigrationError::ChecksumMismatch {
+            version: modified.version,
+            name: modified.name.to_string(),
+        });
+    }
+
+    let mut conn = pool.acquire().await?;
+    let mut count = 0;
+    for (migration, status) in MIGRATIONS.iter().zip(&statuses) {
+        match status.state {
+            MigrationState::Applied => continue,
+            MigrationState::Interrupted => warn!(
+                "Migration {} ({}) was interrupted before it finished, retrying",
+                migration.version, migration.name
+            ),
+            _ => {}
+        }
+
+        info!("Applying migration {} ({})", migration.version, migration.name);
+        apply(&mut conn, migration).await.map_err(|source| MigrationError::Failed {
+            version: migration.version,
+            name: migration.name.to_string(),
+            source,
+        })?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Marks a migration as started, then runs it and marks it finished in one
+/// transaction
+async fn apply(conn: &mut SqliteConnection, migration: &Migration) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO schema_migrations (version, name, checksum, started_at, finished_at)
+        VALUES (?, ?, ?, ?, NULL)
+        ON CONFLICT(version) DO UPDATE SET
+            name = excluded.name,
+            checksum = excluded.checksum,
+            started_at = excluded.started_at,
+            finished_at = NULL
+        "#,
+    )
+    .bind(migration.version)
+    .bind(migration.name)
+    .bind(migration.checksum())
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *conn)
+    .await?;
+
+    let mut tx = conn.begin().await?;
+    match migration.step {
+        Step::Sql(sql) => {
+            sqlx::raw_sql(sql).execute(&mut *tx).await?;
+        }
+        Step::Rust(run) => run(&mut tx).await?,
+    }
+    sqlx::query("UPDATE schema_migrations SET finished_at = ? WHERE version = ?")
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(migration.version)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await
+}
+
+/// Rows of `schema_migrations`, empty before the first run
+async fn applied(pool: &Pool<Sqlite>) -> Result<Vec<AppliedMigration>, sqlx::Error> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<(i64, String, Option<String>)> =
+        sqlx::query_as("SELECT version, checksum, finished_at FROM schema_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter()
+        .map(|(version, checksum, finished_at)| AppliedMigration {
+            version,
+            checksum,
+            finished: finished_at.is_some(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_migrate_records_and_detects_changes() {
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].version < w[1].version));
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+
+        let planned = status(&pool).await.unwrap();
+        assert!(planned.iter().all(|s| s.state == MigrationState::Pending));
+        assert_eq!(migrate(&pool).await.unwrap(), MIGRATIONS.len());
+        assert_eq!(migrate(&pool).await.unwrap(), 0);
+        let index: Option<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'index' AND name = 'idx_media_series_season_episode'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+        assert!(index.is_some());
+
+        // Interrupted migrations are retried
+        sqlx::query("UPDATE schema_migrations SET finished_at = NULL WHERE version = 3")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status(&pool).await.unwrap()[2].state, MigrationState::Interrupted);
+        assert_eq!(migrate(&pool).await.unwrap(), 1);
+
+        sqlx::query("UPDATE schema_migrations SET checksum = 'edited' WHERE version = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            migrate(&pool).await,
+            Err(MigrationError::ChecksumMismatch { version: 1, .. })
+        ));
+
+        sqlx::query("INSERT INTO schema_migrations (version, name, checksum, started_at) VALUES (999, 'future', '', '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(status(&pool).await, Err(MigrationError::UnknownVersion(999))));
+    }
+}
@@ -4,7 +4,8 @@
 //!
 //! # Modules
 //! - `connection_pool`: Optimized connection pool with metrics
-//! - `schema`: Database schema initialization and migrations
+//! - `migrations`: Embedded, versioned schema migrations
+//! - `schema`: Database schema initialization
 //!
 //! # Features
 //! - Configurable pool sizing
@@ -12,15 +13,16 @@
 //! - Connection validation
 //! - Pool metrics tracking
 //! - Database maintenance operations
-//! - Schema initialization
+//! - Schema initialization through versioned migrations
 
 pub mod connection_pool;
+pub mod migrations;
 pub mod schema;
 
 pub use connection_pool::{
     ConnectionPool, ConnectionPoolConfig, PoolMetrics,
 };
-pub use schema::initialize_schema;
+pub use schema::{initialize_schema, missing_tables};
 
 /// Database maintenance operations
 pub async fn run_maintenance(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx::Error> {
@@ -37,31 +39,6 @@ pub async fn run_maintenance(pool: &sqlx::Pool<sqlx::Sqlite>) -> Result<(), sqlx
     Ok(())
 }
 
-/// Applies database migration from SQL file
-///
-/// # Arguments
-/// * `pool` - Database connection pool
-/// * `migration_sql` - SQL migration script
-///
-/// # Returns
-/// * `Result<(), sqlx::Error>` - Success or error
-pub async fn apply_migration(
-    pool: &sqlx::Pool<sqlx::Sqlite>,
-    migration_sql: &str,
-) -> Result<(), sqlx::Error> {
-    // Split migration into individual statements
-    for statement in migration_sql.split(';') {
-        let statement = statement.trim();
-        if !statement.is_empty() {
-            sqlx::query(statement)
-                .execute(pool)
-                .await?;
-        }
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,10 +48,4 @@ mod tests {
         // This would require a real database connection
         // In a real scenario, use testcontainers or sqlite in-memory
     }
-
-    #[tokio::test]
-    async fn test_apply_migration() {
-        // This would require a real database connection
-        // In a real scenario, use testcontainers or sqlite in-memory
-    }
 }
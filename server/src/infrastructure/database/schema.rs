@@ -1,315 +1,43 @@
 //! Database Schema Management
 //!
-//! Provides schema initialization and migrations for HomeFlixD.
+//! Provides schema initialization and the Rust data migrations for HomeFlixD.
 //! Migrated from legacy db.rs to align with Clean Architecture.
 
-use sqlx::{Pool, Row, Sqlite};
+use sqlx::{Pool, Row, Sqlite, SqliteConnection};
 use tracing::info;
 
-/// Initialize all database tables
+use super::migrations;
+use crate::shared::error::MigrationError;
+
+/// Tables created by [`initialize_schema`]
+const SCHEMA_TABLES: &[&str] = &[
+    "media", "watch_progress", "series", "collections", "collection_items",
+    "verification_history", "tmdb_cache", "cache", "events", "media_credits",
+    "generated_subtitles", "seasons", "media_localizations", "people", "extra_artwork",
+    "device_quality_preferences", "subtitle_offsets", "user_audio_preferences", "job_runs",
+    "media_loudness", "user_subtitle_preferences", "media_bookmarks", "media_crop", "device_keys",
+    "user_accessibility_preferences", "user_parental_controls", "user_profiles",
+    "audit_log", "playlists", "playlist_items",
+    "watch_history", "schema_migrations", "media_search", "media_embeddings",
+    "subtitle_sources", "subtitle_cues", "subtitle_dialogue", "webhooks", "webhook_deliveries",
+    "notification_channels", "settings", "tmdb_responses", "jobs", "scheduled_tasks",
+    "stream_tokens",
+];
+
+/// Brings the database schema up to date
 ///
-/// Creates tables if they don't exist and applies column migrations.
+/// Applies the pending versioned migrations (see [`super::migrations`]).
 /// This is idempotent - safe to call multiple times.
-pub async fn initialize_schema(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+pub async fn initialize_schema(pool: &Pool<Sqlite>) -> Result<(), MigrationError> {
     info!("Initializing database schema");
-
-    // 1. Create Media Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS media (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            file_path TEXT NOT NULL UNIQUE,
-            media_type TEXT DEFAULT 'movie',
-            title TEXT NOT NULL,
-            overview TEXT,
-            poster_url TEXT,
-            backdrop_url TEXT,
-            trailer_url TEXT,
-            duration_seconds INTEGER,
-            release_date TEXT,
-            resolution TEXT,
-            genres TEXT,
-            series_id INTEGER REFERENCES series(id),
-            season INTEGER,
-            episode INTEGER,
-            episode_end INTEGER,
-            tmdb_id INTEGER,
-            original_title TEXT,
-            rating REAL,
-            confidence_score REAL DEFAULT 0.0,
-            verification_status TEXT DEFAULT 'unverified',
-            identification_strategy TEXT,
-            error_notes TEXT,
-            alternative_matches TEXT,
-            content_rating TEXT,
-            content_warnings TEXT,
-            current_position INTEGER DEFAULT 0,
-            is_watched INTEGER DEFAULT 0,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 2. Create Watch Progress Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS watch_progress (
-            media_id INTEGER PRIMARY KEY,
-            current_position_seconds INTEGER NOT NULL DEFAULT 0,
-            is_watched BOOLEAN NOT NULL DEFAULT 0,
-            last_updated DATETIME DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 3. Create Series Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS series (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            tmdb_id INTEGER,
-            title TEXT NOT NULL,
-            overview TEXT,
-            poster_url TEXT
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 4. Create Collections Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS collections (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL,
-            description TEXT,
-            poster_url TEXT,
-            backdrop_url TEXT,
-            tmdb_collection_id INTEGER,
-            sort_mode TEXT DEFAULT 'timeline',
-            collection_type TEXT DEFAULT 'auto',
-            total_items INTEGER DEFAULT 0,
-            available_items INTEGER DEFAULT 0
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 5. Create Collection Items Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS collection_items (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            collection_id INTEGER NOT NULL,
-            media_id INTEGER,
-            tmdb_id INTEGER NOT NULL,
-            media_type TEXT DEFAULT 'movie',
-            title TEXT NOT NULL,
-            overview TEXT,
-            poster_url TEXT,
-            release_date TEXT,
-            timeline_order INTEGER NOT NULL,
-            release_order INTEGER NOT NULL,
-            timeline_year INTEGER,
-            timeline_notes TEXT,
-            season_number INTEGER,
-            episode_number INTEGER,
-            is_available INTEGER DEFAULT 0,
-            FOREIGN KEY(collection_id) REFERENCES collections(id) ON DELETE CASCADE,
-            FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE SET NULL
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 6. Create Verification History Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS verification_history (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            content_id TEXT NOT NULL,
-            content_type TEXT NOT NULL,
-            original_match_id INTEGER NOT NULL,
-            corrected_match_id INTEGER,
-            confidence_before REAL NOT NULL,
-            confidence_after REAL NOT NULL,
-            verified_by TEXT NOT NULL,
-            verification_date DATETIME DEFAULT CURRENT_TIMESTAMP,
-            notes TEXT
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 7. Create TMDB Cache Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS tmdb_cache (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            external_id TEXT NOT NULL,
-            external_type TEXT NOT NULL,
-            resolved_tmdb_id INTEGER NOT NULL,
-            resolved_type TEXT NOT NULL,
-            cached_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            ttl DATETIME NOT NULL,
-            UNIQUE(external_id, external_type)
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // 8. Create General Cache Table (for CacheRepository)
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS cache (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL,
-            expires_at INTEGER NOT NULL
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Create index for cache expiration cleanup
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_cache_expires ON cache(expires_at)")
-        .execute(pool)
-        .await?;
-
-    // 8.5. Create Events Table (for event sourcing)
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS events (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            event_type TEXT NOT NULL,
-            aggregate_id TEXT,
-            aggregate_type TEXT,
-            payload TEXT NOT NULL,
-            version INTEGER NOT NULL DEFAULT 1,
-            correlation_id TEXT,
-            causation_id TEXT,
-            created_at TEXT NOT NULL DEFAULT (datetime('now'))
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Create indexes for event queries
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type)")
-        .execute(pool)
-        .await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_aggregate ON events(aggregate_type, aggregate_id)")
-        .execute(pool)
-        .await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_created ON events(created_at)")
-        .execute(pool)
-        .await?;
-
-    // 9. Create Media Credits Table (cast/crew cache from TMDB)
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS media_credits (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            media_id INTEGER NOT NULL,
-            person_id INTEGER NOT NULL,
-            person_name TEXT NOT NULL,
-            role TEXT NOT NULL,
-            character_name TEXT,
-            department TEXT,
-            profile_url TEXT,
-            credit_order INTEGER DEFAULT 0,
-            credit_type TEXT NOT NULL,
-            FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Create index for credits lookup by media_id
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_media_credits_media_id ON media_credits(media_id)")
-        .execute(pool)
-        .await?;
-
-    // 10. Create Generated Subtitles Table (for tracking auto-generated subtitles)
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS generated_subtitles (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            media_id INTEGER NOT NULL,
-            audio_track_index INTEGER NOT NULL,
-            audio_fingerprint TEXT NOT NULL,
-            source_language TEXT,
-            target_language TEXT,
-            srt_filename TEXT NOT NULL,
-            duration_seconds REAL,
-            was_translated INTEGER DEFAULT 0,
-            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY(media_id) REFERENCES media(id) ON DELETE CASCADE,
-            UNIQUE(media_id, audio_track_index, target_language)
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Create index for generated subtitles lookup
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_generated_subtitles_media_id ON generated_subtitles(media_id)")
-        .execute(pool)
-        .await?;
-
-    // Create index for fingerprint lookup (for finding existing subtitles by audio track)
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_generated_subtitles_fingerprint ON generated_subtitles(audio_fingerprint)")
-        .execute(pool)
-        .await?;
-
-    // 11. Create Seasons Table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS seasons (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            series_id INTEGER NOT NULL,
-            season_number INTEGER NOT NULL,
-            tmdb_id INTEGER,
-            name TEXT,
-            overview TEXT,
-            poster_url TEXT,
-            air_date TEXT,
-            episode_count INTEGER,
-            rating REAL,
-            FOREIGN KEY(series_id) REFERENCES series(id) ON DELETE CASCADE,
-            UNIQUE(series_id, season_number)
-        );
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    // Apply column migrations
-    apply_column_migrations(pool).await?;
-    backfill_episode_end(pool).await?;
-
-    info!("Database schema initialized successfully");
+    let applied = migrations::migrate(pool).await?;
+    info!("Database schema initialized successfully ({} migrations applied)", applied);
     Ok(())
 }
 
-/// Apply column migrations for existing tables
-///
-/// Uses ALTER TABLE to add columns that may not exist in older schemas.
-/// All operations are idempotent (silently ignore if column exists).
-async fn apply_column_migrations(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+/// Adds columns missing from tables created by releases before the current
+/// column set (migration 2)
+pub(super) async fn add_legacy_columns(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
     // Media table migrations
     let media_columns = [
         "ALTER TABLE media ADD COLUMN resolution TEXT",
@@ -334,11 +62,11 @@ async fn apply_column_migrations(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error>
         "ALTER TABLE media ADD COLUMN is_watched INTEGER DEFAULT 0",
         "ALTER TABLE media ADD COLUMN updated_at DATETIME DEFAULT CURRENT_TIMESTAMP",
         "ALTER TABLE media ADD COLUMN duration_seconds INTEGER",
+        "ALTER TABLE media ADD COLUMN poster_blurhash TEXT",
+        "ALTER TABLE media ADD COLUMN backdrop_blurhash TEXT",
     ];
 
-    for sql in &media_columns {
-        let _ = sqlx::query(sql).execute(pool).await;
-    }
+    add_missing_columns(conn, &media_columns).await?;
 
     // Series table migrations
     let series_columns = [
@@ -358,11 +86,12 @@ async fn apply_column_migrations(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error>
         "ALTER TABLE series ADD COLUMN last_verified DATETIME",
         "ALTER TABLE series ADD COLUMN created_at DATETIME DEFAULT CURRENT_TIMESTAMP",
         "ALTER TABLE series ADD COLUMN updated_at DATETIME DEFAULT CURRENT_TIMESTAMP",
+        "ALTER TABLE series ADD COLUMN poster_blurhash TEXT",
+        "ALTER TABLE series ADD COLUMN backdrop_blurhash TEXT",
+        "ALTER TABLE series ADD COLUMN content_rating TEXT",
     ];
 
-    for sql in &series_columns {
-        let _ = sqlx::query(sql).execute(pool).await;
-    }
+    add_missing_columns(conn, &series_columns).await?;
 
     // Collections table migrations
     let collection_columns = [
@@ -377,9 +106,7 @@ async fn apply_column_migrations(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error>
         "ALTER TABLE collections ADD COLUMN updated_at DATETIME DEFAULT CURRENT_TIMESTAMP",
     ];
 
-    for sql in &collection_columns {
-        let _ = sqlx::query(sql).execute(pool).await;
-    }
+    add_missing_columns(conn, &collection_columns).await?;
 
     // Collection items table migrations
     let collection_item_columns = [
@@ -401,17 +128,47 @@ async fn apply_column_migrations(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error>
         "ALTER TABLE collection_items ADD COLUMN rating REAL",
     ];
 
-    for sql in &collection_item_columns {
-        let _ = sqlx::query(sql).execute(pool).await;
-    }
+    add_missing_columns(conn, &collection_item_columns).await?;
+
+    Ok(())
+}
 
+/// Runs the `ALTER TABLE <table> ADD COLUMN <column> ...` statements whose
+/// column does not exist yet
+async fn add_missing_columns(conn: &mut SqliteConnection, statements: &[&str]) -> Result<(), sqlx::Error> {
+    for sql in statements {
+        let words: Vec<&str> = sql.split_whitespace().collect();
+        let (table, column) = (words[2], words[5]);
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pragma_table_info(?) WHERE name = ?)")
+            .bind(table)
+            .bind(column)
+            .fetch_one(&mut *conn)
+            .await?;
+        if !exists {
+            sqlx::query(sql).execute(&mut *conn).await?;
+        }
+    }
     Ok(())
 }
 
+/// Schema tables missing from the database
+///
+/// Empty once [`initialize_schema`] has run against this database.
+pub async fn missing_tables(pool: &Pool<Sqlite>) -> Result<Vec<String>, sqlx::Error> {
+    let existing: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .fetch_all(pool)
+        .await?;
+    Ok(SCHEMA_TABLES.iter()
+        .filter(|table| !existing.iter().any(|name| name == *table))
+        .map(|table| table.to_string())
+        .collect())
+}
+
 /// Backfill episode ranges for media scanned before the episode_end column was
-/// populated. This only updates rows where the filename parser agrees with the
-/// already stored starting episode, so unrelated metadata is left untouched.
-async fn backfill_episode_end(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+/// populated (migration 3). This only updates rows where the filename parser
+/// agrees with the already stored starting episode, so unrelated metadata is
+/// left untouched.
+pub(super) async fn backfill_episode_end(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
     let rows = sqlx::query(
         r#"
         SELECT id, file_path, episode
@@ -421,7 +178,7 @@ async fn backfill_episode_end(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
           AND episode_end IS NULL
         "#,
     )
-    .fetch_all(pool)
+    .fetch_all(&mut *conn)
     .await?;
 
     let mut updated = 0;
@@ -449,7 +206,7 @@ async fn backfill_episode_end(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
             )
             .bind(parsed_episode_end)
             .bind(id)
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
             updated += 1;
         }
@@ -484,6 +241,8 @@ mod tests {
             .await
             .expect("Failed to initialize schema");
 
+        assert!(missing_tables(&pool).await.unwrap().is_empty());
+
         // Verify tables exist
         let result: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='media'")
             .fetch_one(&pool)
@@ -544,9 +303,11 @@ mod tests {
         .await
         .expect("Failed to insert stale media row");
 
-        backfill_episode_end(&pool)
+        let mut conn = pool.acquire().await.expect("Failed to acquire connection");
+        backfill_episode_end(&mut conn)
             .await
             .expect("Failed to backfill episode_end");
+        drop(conn);
 
         let episode_end: Option<i32> =
             sqlx::query_scalar("SELECT episode_end FROM media WHERE title = 'Broken Bow'")
@@ -28,4 +28,20 @@ pub trait EventPersistence: Send + Sync {
     /// # Returns
     /// * `Result<Vec<StoredEvent>, EventSourcingError>` - List of stored events
     async fn load(&self, from_version: u64, limit: usize) -> Result<Vec<StoredEvent>, EventSourcingError>;
+
+    /// Load the newest events of the given types
+    ///
+    /// # Arguments
+    /// * `event_types` - Event type identifiers to include
+    /// * `limit` - Maximum number of events to load
+    ///
+    /// # Returns
+    /// * `Result<Vec<StoredEvent>, EventSourcingError>` - Stored events, newest first
+    async fn load_latest(&self, event_types: &[&str], limit: usize) -> Result<Vec<StoredEvent>, EventSourcingError>;
+
+    /// Delete events created before a point in time
+    ///
+    /// # Returns
+    /// * `Result<u64, EventSourcingError>` - Number of events deleted
+    async fn delete_before(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<u64, EventSourcingError>;
 }
@@ -41,10 +41,42 @@ impl EventStore {
         self.persistence.save(&stored_event).await
     }
 
+    /// Appends an already serialized event under an aggregate type, so
+    /// readers can tell it apart from domain events
+    pub async fn append_payload(
+        &self,
+        event_type: &str,
+        aggregate_type: &str,
+        payload: String,
+    ) -> Result<u64, EventSourcingError> {
+        let stored_event = StoredEvent {
+            version: 0,
+            event_type: event_type.to_string(),
+            aggregate_id: None,
+            aggregate_type: Some(aggregate_type.to_string()),
+            payload,
+            correlation_id: None,
+            causation_id: None,
+            created_at: chrono::Utc::now(),
+        };
+
+        self.persistence.save(&stored_event).await
+    }
+
     /// Reads events from the store
     pub async fn read(&self, from_version: u64, limit: usize) -> Result<Vec<StoredEvent>, EventSourcingError> {
         self.persistence.load(from_version, limit).await
     }
+
+    /// Reads the newest events of the given types, newest first
+    pub async fn latest(&self, event_types: &[&str], limit: usize) -> Result<Vec<StoredEvent>, EventSourcingError> {
+        self.persistence.load_latest(event_types, limit).await
+    }
+
+    /// Removes events created before the cutoff, returning how many
+    pub async fn prune(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<u64, EventSourcingError> {
+        self.persistence.delete_before(cutoff).await
+    }
 }
 
 /// Represents a stored event
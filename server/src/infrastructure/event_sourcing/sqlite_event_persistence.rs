@@ -3,7 +3,7 @@
 //! Provides SQLite-based persistence for domain events.
 
 use async_trait::async_trait;
-use sqlx::{Pool, Sqlite, Row};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
 use std::sync::Arc;
 use tracing::debug;
 
@@ -86,38 +86,86 @@ impl EventPersistence for SqliteEventPersistence {
         .await
         .map_err(|e| EventSourcingError::Persistence(format!("Failed to load events: {}", e)))?;
 
-        let stored_events: Vec<StoredEvent> = rows
-            .into_iter()
-            .map(|row| -> Result<StoredEvent, EventSourcingError> {
-                let version: i64 = row.try_get("id")?;
-                let event_type: String = row.try_get("event_type")?;
-                let aggregate_id: Option<String> = row.try_get("aggregate_id").ok();
-                let aggregate_type: Option<String> = row.try_get("aggregate_type").ok();
-                let payload: String = row.try_get("payload")?;
-                let correlation_id: Option<String> = row.try_get("correlation_id").ok();
-                let causation_id: Option<String> = row.try_get("causation_id").ok();
-                let created_at_str: String = row.try_get("created_at")?;
-                
-                // Parse created_at from string (RFC3339 format)
-                let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
-                    .map(|dt| dt.with_timezone(&chrono::Utc))
-                    .map_err(|e| EventSourcingError::Deserialization(format!("Failed to parse created_at: {}", e)))?;
-                
-                Ok(StoredEvent {
-                    version: version as u64,
-                    event_type,
-                    aggregate_id,
-                    aggregate_type,
-                    payload,
-                    correlation_id,
-                    causation_id,
-                    created_at,
-                })
-            })
+        let stored_events = rows
+            .iter()
+            .map(stored_event)
             .collect::<Result<Vec<_>, _>>()?;
 
         debug!("Loaded {} events from version {}", stored_events.len(), from_version);
         Ok(stored_events)
     }
+
+    async fn load_latest(&self, event_types: &[&str], limit: usize) -> Result<Vec<StoredEvent>, EventSourcingError> {
+        if event_types.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; event_types.len()].join(", ");
+        let sql = format!(
+            r#"
+            SELECT
+                id,
+                event_type,
+                aggregate_id,
+                aggregate_type,
+                payload,
+                correlation_id,
+                causation_id,
+                created_at
+            FROM events
+            WHERE event_type IN ({})
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+            placeholders
+        );
+        let mut query = sqlx::query(&sql);
+        for event_type in event_types {
+            query = query.bind(*event_type);
+        }
+        let rows = query
+            .bind(limit as i64)
+            .fetch_all(&*self.pool)
+            .await
+            .map_err(|e| EventSourcingError::Persistence(format!("Failed to load events: {}", e)))?;
+
+        rows.iter().map(stored_event).collect()
+    }
+
+    async fn delete_before(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<u64, EventSourcingError> {
+        // created_at is stored as RFC 3339 in UTC, so it compares as text
+        let result = sqlx::query("DELETE FROM events WHERE created_at < ?")
+            .bind(cutoff.to_rfc3339())
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| EventSourcingError::Persistence(format!("Failed to delete events: {}", e)))?;
+        Ok(result.rows_affected())
+    }
 }
 
+/// Maps an `events` row to a stored event
+fn stored_event(row: &SqliteRow) -> Result<StoredEvent, EventSourcingError> {
+    let version: i64 = row.try_get("id")?;
+    let event_type: String = row.try_get("event_type")?;
+    let aggregate_id: Option<String> = row.try_get("aggregate_id").ok();
+    let aggregate_type: Option<String> = row.try_get("aggregate_type").ok();
+    let payload: String = row.try_get("payload")?;
+    let correlation_id: Option<String> = row.try_get("correlation_id").ok();
+    let causation_id: Option<String> = row.try_get("causation_id").ok();
+    let created_at_str: String = row.try_get("created_at")?;
+
+    // Parse created_at from string (RFC3339 format)
+    let created_at = chrono::DateTime::parse_from_rfc3339(&created_at_str)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|e| EventSourcingError::Deserialization(format!("Failed to parse created_at: {}", e)))?;
+
+    Ok(StoredEvent {
+        version: version as u64,
+        event_type,
+        aggregate_id,
+        aggregate_type,
+        payload,
+        correlation_id,
+        causation_id,
+        created_at,
+    })
+}
@@ -0,0 +1,182 @@
+//! fanart.tv Client
+//!
+//! Fetches community artwork from the fanart.tv v3 API with response caching.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::domain::repositories::CacheRepository;
+use crate::interfaces::external_services::{FanartArtwork, FanartService};
+use crate::shared::error::FanartError;
+
+/// Cache TTL for artwork picks (7 days)
+const CACHE_TTL_SECS: u64 = 86400 * 7;
+
+/// fanart.tv API client
+pub struct FanartClient {
+    api_key: String,
+    http_client: Client,
+    cache: Arc<dyn CacheRepository>,
+    base_url: String,
+}
+
+impl FanartClient {
+    /// Creates a new fanart.tv client
+    ///
+    /// # Arguments
+    /// * `api_key` - fanart.tv project API key
+    /// * `cache` - Cache repository for caching responses
+    pub fn new(api_key: &str, cache: Arc<dyn CacheRepository>) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            cache,
+            base_url: "https://webservice.fanart.tv/v3".to_string(),
+        }
+    }
+
+    /// Fetches and caches the artwork picks for one item
+    async fn fetch_artwork(
+        &self,
+        endpoint: &str,
+        cache_key: String,
+        language: Option<&str>,
+        pick: fn(FanartResponse, Option<&str>) -> FanartArtwork,
+    ) -> Result<FanartArtwork, FanartError> {
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let url = format!("{}{}?api_key={}", self.base_url, endpoint, self.api_key);
+        let response = self.http_client.get(&url).send().await?;
+        let status = response.status();
+
+        let artwork = if status == reqwest::StatusCode::NOT_FOUND {
+            debug!("No fanart.tv entry for {}", endpoint);
+            FanartArtwork::default()
+        } else if !status.is_success() {
+            return Err(FanartError::ApiError(status.as_u16()));
+        } else {
+            pick(response.json().await?, language)
+        };
+
+        // Empty results are cached too so unknown items aren't re-requested every scan
+        let cached_value = serde_json::to_string(&artwork)?;
+        self.cache.set(&cache_key, &cached_value, CACHE_TTL_SECS).await?;
+
+        Ok(artwork)
+    }
+}
+
+#[async_trait]
+impl FanartService for FanartClient {
+    async fn fetch_movie_artwork(&self, tmdb_id: i64, language: Option<&str>) -> Result<FanartArtwork, FanartError> {
+        let cache_key = format!("fanart:movie:{}:{}", tmdb_id, language.unwrap_or("en"));
+        self.fetch_artwork(&format!("/movies/{}", tmdb_id), cache_key, language, |r, lang| FanartArtwork {
+            clearlogo: best_image(&[&r.hdmovielogo, &r.movielogo], lang),
+            clearart: best_image(&[&r.hdmovieclearart, &r.movieart], lang),
+            disc: best_image(&[&r.moviedisc], lang),
+        })
+        .await
+    }
+
+    async fn fetch_tv_artwork(&self, tvdb_id: i64, language: Option<&str>) -> Result<FanartArtwork, FanartError> {
+        let cache_key = format!("fanart:tv:{}:{}", tvdb_id, language.unwrap_or("en"));
+        self.fetch_artwork(&format!("/tv/{}", tvdb_id), cache_key, language, |r, lang| FanartArtwork {
+            clearlogo: best_image(&[&r.hdtvlogo, &r.clearlogo], lang),
+            clearart: best_image(&[&r.hdclearart, &r.clearart], lang),
+            disc: None,
+        })
+        .await
+    }
+}
+
+/// Picks the best image from lists ordered by preference (HD before SD)
+///
+/// Within a list, images in the preferred language win, then English, then
+/// textless ones; ties are broken by community likes.
+fn best_image(lists: &[&Vec<FanartImage>], language: Option<&str>) -> Option<String> {
+    let rank = |image: &FanartImage| -> u8 {
+        let lang = image.lang.as_deref().unwrap_or("");
+        if language.is_some_and(|l| l.eq_ignore_ascii_case(lang)) {
+            0
+        } else if lang == "en" {
+            1
+        } else if lang.is_empty() || lang == "00" {
+            2
+        } else {
+            3
+        }
+    };
+
+    lists.iter()
+        .filter_map(|list| {
+            list.iter().min_by_key(|image| {
+                let likes: i64 = image.likes.as_deref().and_then(|l| l.parse().ok()).unwrap_or(0);
+                (rank(image), -likes)
+            })
+        })
+        .min_by_key(|image| rank(image))
+        .map(|image| image.url.clone())
+}
+
+/// fanart.tv response (movies and TV share one struct; unused lists stay empty)
+#[derive(Debug, Default, Deserialize)]
+struct FanartResponse {
+    #[serde(default)]
+    hdmovielogo: Vec<FanartImage>,
+    #[serde(default)]
+    movielogo: Vec<FanartImage>,
+    #[serde(default)]
+    hdmovieclearart: Vec<FanartImage>,
+    #[serde(default)]
+    movieart: Vec<FanartImage>,
+    #[serde(default)]
+    moviedisc: Vec<FanartImage>,
+    #[serde(default)]
+    hdtvlogo: Vec<FanartImage>,
+    #[serde(default)]
+    clearlogo: Vec<FanartImage>,
+    #[serde(default)]
+    hdclearart: Vec<FanartImage>,
+    #[serde(default)]
+    clearart: Vec<FanartImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FanartImage {
+    url: String,
+    lang: Option<String>,
+    likes: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(url: &str, lang: &str, likes: &str) -> FanartImage {
+        FanartImage {
+            url: url.to_string(),
+            lang: Some(lang.to_string()),
+            likes: Some(likes.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_best_image_prefers_language_then_likes() {
+        let hd = vec![image("hd-de", "de", "9"), image("hd-en", "en", "1"), image("hd-en-top", "en", "5")];
+        let sd = vec![image("sd-hu", "hu", "0")];
+
+        assert_eq!(best_image(&[&hd, &sd], None).as_deref(), Some("hd-en-top"));
+        // An SD logo in the preferred language beats HD logos in other languages
+        assert_eq!(best_image(&[&hd, &sd], Some("hu")).as_deref(), Some("sd-hu"));
+        assert_eq!(best_image(&[&Vec::new()], None), None);
+    }
+}
@@ -0,0 +1,7 @@
+//! fanart.tv Module
+//!
+//! Provides logos, clearart and disc art from fanart.tv.
+
+mod client;
+
+pub use client::*;
@@ -1,17 +1,48 @@
 //! FFmpeg Adapter Implementation
 //!
-//! Provides FFmpeg-based implementation of ThumbnailGenerator interface
+//! Provides FFmpeg-based implementations of the ThumbnailGenerator,
+//! HlsTranscoder, LoudnessAnalyzer, CropDetector, PreviewClipGenerator and
+//! SubtitleExtractor interfaces
 
 use async_trait::async_trait;
-use tokio::process::Command;
+use tokio::process::{Child, Command};
+use std::path::Path;
+use std::process::Stdio;
 use std::time::Duration;
 use tokio::time::timeout;
+use crate::domain::repositories::{CropDetection, LoudnessMeasurement};
 use crate::interfaces::external_services::{
-    ThumbnailGenerator, ThumbnailOptions, ThumbnailResult,
+    CropDetector, HlsTranscodeRequest, HlsTranscoder, LoudnessAnalyzer, LoudnessTarget, PreviewClipGenerator,
+    SubtitleExtractor, SubtitleFormat, ThumbnailGenerator, ThumbnailOptions, ThumbnailResult,
 };
-use crate::shared::error::ThumbnailError;
+use crate::shared::error::{ThumbnailError, TranscodeError};
 
-/// FFmpeg adapter for thumbnail generation
+/// Time allowed for measuring the loudness of a whole track
+const LOUDNESS_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Time allowed for one crop detection sample
+const CROP_SAMPLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Positions of the crop detection samples, as shares of the duration
+const CROP_SAMPLES: [f64; 5] = [0.1, 0.3, 0.5, 0.7, 0.9];
+
+/// Frames analyzed per crop detection sample
+const CROP_SAMPLE_FRAMES: u32 = 48;
+
+/// Time allowed for encoding a preview clip
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Height of preview clips
+const PREVIEW_HEIGHT: u32 = 360;
+
+/// Video bitrate of preview clips, in kbit/s
+const PREVIEW_BITRATE_KBPS: u32 = 400;
+
+/// Time allowed for extracting a subtitle track, which reads the whole file
+const EXTRACT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// FFmpeg adapter for thumbnail generation, HLS transcoding, loudness
+/// measurement and subtitle extraction
 pub struct FFmpegAdapter {
     timeout: Duration,
 }
@@ -104,6 +135,119 @@ impl FFmpegAdapter {
         args
     }
 
+    /// Builds FFmpeg arguments for HLS transcoding
+    ///
+    /// Video is always encoded to 8-bit H.264 with keyframes forced on
+    /// segment boundaries, so segments line up with the server-generated
+    /// playlist no matter where transcoding (re)starts.
+    fn build_hls_args(request: &HlsTranscodeRequest) -> Vec<String> {
+        let segment_seconds = request.segment_seconds.max(1);
+        let start_seconds = request.start_segment as u64 * segment_seconds as u64;
+        let video_kbps = request.variant.video_bitrate_kbps;
+
+        let mut args: Vec<String> = vec![
+            "-hide_banner".into(), "-loglevel".into(), "error".into(),
+            "-ss".into(), start_seconds.to_string(),
+            "-i".into(), request.input_path.clone(),
+            "-map".into(), "0:v:0".into(),
+            "-map".into(), format!("0:a:{}?", request.audio_track),
+            "-c:v".into(), "libx264".into(),
+            "-preset".into(), "veryfast".into(),
+            "-pix_fmt".into(), "yuv420p".into(),
+            "-b:v".into(), format!("{}k", video_kbps),
+            "-maxrate".into(), format!("{}k", video_kbps),
+            "-bufsize".into(), format!("{}k", video_kbps * 2),
+            "-force_key_frames".into(), format!("expr:gte(t,n_forced*{})", segment_seconds),
+            "-sc_threshold".into(), "0".into(),
+        ];
+
+        if let Some(height) = request.variant.height {
+            // -2 keeps the aspect ratio with an even width
+            args.push("-vf".into());
+            args.push(format!("scale=-2:{}", height));
+        }
+
+        args.extend([
+            "-c:a".into(), "aac".into(),
+            "-ac".into(), "2".into(),
+            "-b:a".into(), format!("{}k", request.variant.audio_bitrate_kbps),
+            // Keep timestamps continuous with earlier segments after a restart
+            "-output_ts_offset".into(), start_seconds.to_string(),
+            "-f".into(), "hls".into(),
+            "-hls_time".into(), segment_seconds.to_string(),
+            "-hls_list_size".into(), "0".into(),
+            "-hls_flags".into(), "temp_file+independent_segments".into(),
+            "-start_number".into(), request.start_segment.to_string(),
+            "-hls_segment_filename".into(),
+            request.output_dir.join("seg_%05d.ts").to_string_lossy().to_string(),
+            // FFmpeg's own playlist is unused; the server serves a full VOD playlist
+            request.output_dir.join("ffmpeg.m3u8").to_string_lossy().to_string(),
+        ]);
+
+        args
+    }
+
+    /// Builds FFmpeg arguments for a preview clip
+    ///
+    /// Clips are muted, low-bitrate H.264 with the index up front, so they
+    /// start playing while still downloading.
+    fn build_preview_args(file_path: &str, start_seconds: f64, duration_seconds: f64, output_path: &Path) -> Vec<String> {
+        vec![
+            "-hide_banner".into(), "-loglevel".into(), "error".into(), "-nostdin".into(), "-y".into(),
+            "-ss".into(), format!("{:.3}", start_seconds.max(0.0)),
+            "-i".into(), file_path.to_string(),
+            "-t".into(), format!("{:.3}", duration_seconds),
+            "-map".into(), "0:v:0".into(),
+            "-an".into(), "-sn".into(), "-dn".into(),
+            // -2 keeps the aspect ratio with an even width; never upscale
+            "-vf".into(), format!("scale=-2:'min({},ih)'", PREVIEW_HEIGHT),
+            "-c:v".into(), "libx264".into(),
+            "-preset".into(), "veryfast".into(),
+            "-profile:v".into(), "main".into(),
+            "-pix_fmt".into(), "yuv420p".into(),
+            "-b:v".into(), format!("{}k", PREVIEW_BITRATE_KBPS),
+            "-maxrate".into(), format!("{}k", PREVIEW_BITRATE_KBPS * 3 / 2),
+            "-bufsize".into(), format!("{}k", PREVIEW_BITRATE_KBPS * 2),
+            "-movflags".into(), "+faststart".into(),
+            "-f".into(), "mp4".into(),
+            output_path.to_string_lossy().to_string(),
+        ]
+    }
+
+    /// Parses the JSON summary loudnorm prints at the end of a first pass
+    fn parse_loudnorm_output(stderr: &str) -> Option<LoudnessMeasurement> {
+        let start = stderr.rfind('{')?;
+        let end = start + stderr[start..].find('}')?;
+        let json: serde_json::Value = serde_json::from_str(&stderr[start..=end]).ok()?;
+        // Values are printed as strings ("-27.61", or "-inf" for silence)
+        let value = |key: &str| -> Option<f64> {
+            json.get(key)?.as_str()?.parse::<f64>().ok().filter(|v| v.is_finite())
+        };
+        Some(LoudnessMeasurement {
+            input_i: value("input_i")?,
+            input_tp: value("input_tp")?,
+            input_lra: value("input_lra")?,
+            input_thresh: value("input_thresh")?,
+            target_offset: value("target_offset")?,
+        })
+    }
+
+    /// Parses the picture area cropdetect reports last (`crop=W:H:X:Y`)
+    ///
+    /// With `reset=0` the last line covers all analyzed frames. Fully black
+    /// samples report no positive area and are skipped.
+    fn parse_cropdetect_output(stderr: &str) -> Option<(u32, u32, u32, u32)> {
+        let line = stderr.lines().rev().find(|l| l.contains("Parsed_cropdetect") && l.contains("crop="))?;
+        let values: Vec<i64> = line[line.rfind("crop=")? + 5..]
+            .split(':')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<Vec<_>>>()?;
+        match values[..] {
+            [w, h, x, y] if w > 0 && h > 0 && x >= 0 && y >= 0 => Some((w as u32, h as u32, x as u32, y as u32)),
+            _ => None,
+        }
+    }
+
     /// Determines output format from format option
     fn get_output_format(format: &str) -> &'static str {
         match format.to_lowercase().as_str() {
@@ -134,6 +278,173 @@ impl Default for FFmpegAdapter {
     }
 }
 
+impl HlsTranscoder for FFmpegAdapter {
+    fn start_hls(&self, request: &HlsTranscodeRequest) -> Result<Child, TranscodeError> {
+        std::fs::create_dir_all(&request.output_dir)?;
+
+        Command::new("ffmpeg")
+            .args(Self::build_hls_args(request))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| TranscodeError::ExecutionFailed(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl LoudnessAnalyzer for FFmpegAdapter {
+    async fn measure(
+        &self,
+        file_path: &str,
+        audio_track: usize,
+        target: &LoudnessTarget,
+    ) -> Result<LoudnessMeasurement, TranscodeError> {
+        let filter = format!("{}:print_format=json", target.filter(None));
+        let output = timeout(LOUDNESS_TIMEOUT, async {
+            Command::new("ffmpeg")
+                .args(["-hide_banner", "-nostats", "-i", file_path])
+                .args(["-map", &format!("0:a:{}", audio_track)])
+                .args(["-af", &filter, "-f", "null", "-"])
+                .kill_on_drop(true)
+                .output()
+                .await
+        })
+        .await
+        .map_err(|_| TranscodeError::Timeout("Loudness measurement timed out".into()))??;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(TranscodeError::ExecutionFailed(stderr.to_string()));
+        }
+        Self::parse_loudnorm_output(&stderr)
+            .ok_or_else(|| TranscodeError::ExecutionFailed("No loudness measurement in FFmpeg output".into()))
+    }
+}
+
+#[async_trait]
+impl CropDetector for FFmpegAdapter {
+    async fn detect(
+        &self,
+        file_path: &str,
+        duration_seconds: f64,
+        width: u32,
+        height: u32,
+    ) -> Result<Option<CropDetection>, TranscodeError> {
+        // Union of the samples' picture areas, as (left, top, right, bottom)
+        let mut area: Option<(u32, u32, u32, u32)> = None;
+        for share in CROP_SAMPLES {
+            let position = format!("{:.3}", duration_seconds.max(0.0) * share);
+            let output = timeout(CROP_SAMPLE_TIMEOUT, async {
+                Command::new("ffmpeg")
+                    .args(["-hide_banner", "-nostats", "-nostdin", "-ss", &position, "-i", file_path])
+                    .args(["-map", "0:v:0", "-frames:v", &CROP_SAMPLE_FRAMES.to_string()])
+                    .args(["-vf", "cropdetect=limit=24:round=2:reset=0", "-f", "null", "-"])
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+            })
+            .await
+            .map_err(|_| TranscodeError::Timeout("Crop detection timed out".into()))??;
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !output.status.success() {
+                return Err(TranscodeError::ExecutionFailed(stderr.to_string()));
+            }
+            if let Some((w, h, x, y)) = Self::parse_cropdetect_output(&stderr) {
+                let (left, top, right, bottom) = area.unwrap_or((x, y, x + w, y + h));
+                area = Some((left.min(x), top.min(y), right.max(x + w), bottom.max(y + h)));
+            }
+        }
+
+        Ok(area.map(|(left, top, right, bottom)| {
+            let (right, bottom) = (right.min(width), bottom.min(height));
+            CropDetection {
+                width: right.saturating_sub(left),
+                height: bottom.saturating_sub(top),
+                x: left,
+                y: top,
+                source_width: width,
+                source_height: height,
+            }
+        }))
+    }
+}
+
+#[async_trait]
+impl PreviewClipGenerator for FFmpegAdapter {
+    async fn generate_clip(
+        &self,
+        file_path: &str,
+        start_seconds: f64,
+        duration_seconds: f64,
+        output_path: &Path,
+    ) -> Result<(), TranscodeError> {
+        let args = Self::build_preview_args(file_path, start_seconds, duration_seconds, output_path);
+        let output = timeout(PREVIEW_TIMEOUT, async {
+            Command::new("ffmpeg")
+                .args(&args)
+                .kill_on_drop(true)
+                .output()
+                .await
+        })
+        .await
+        .map_err(|_| TranscodeError::Timeout("Preview clip encoding timed out".into()))??;
+
+        if !output.status.success() {
+            return Err(TranscodeError::ExecutionFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubtitleExtractor for FFmpegAdapter {
+    async fn extract(
+        &self,
+        file_path: &str,
+        track: usize,
+        format: SubtitleFormat,
+    ) -> Result<Vec<u8>, TranscodeError> {
+        let output = timeout(EXTRACT_TIMEOUT, async {
+            Command::new("ffmpeg")
+                .args(["-hide_banner", "-nostdin", "-i", file_path])
+                .args(["-map", &format!("0:s:{}", track)])
+                .args(["-f", format.ffmpeg_format(), "-"])
+                .kill_on_drop(true)
+                .output()
+                .await
+        })
+        .await
+        .map_err(|_| TranscodeError::Timeout("Subtitle extraction timed out".into()))??;
+
+        if !output.status.success() {
+            return Err(TranscodeError::ExecutionFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+        Ok(output.stdout)
+    }
+
+    async fn extract_bitmap(&self, file_path: &str, track: usize) -> Result<Vec<u8>, TranscodeError> {
+        let output = timeout(EXTRACT_TIMEOUT, async {
+            Command::new("ffmpeg")
+                .args(["-hide_banner", "-nostdin", "-i", file_path])
+                .args(["-map", &format!("0:s:{}", track)])
+                .args(["-c:s", "copy", "-f", "sup", "-"])
+                .kill_on_drop(true)
+                .output()
+                .await
+        })
+        .await
+        .map_err(|_| TranscodeError::Timeout("Subtitle extraction timed out".into()))??;
+
+        if !output.status.success() {
+            return Err(TranscodeError::ExecutionFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+        Ok(output.stdout)
+    }
+}
+
 #[async_trait]
 impl ThumbnailGenerator for FFmpegAdapter {
     async fn generate(
@@ -223,3 +534,86 @@ impl ThumbnailGenerator for FFmpegAdapter {
         self.generate(file_path, options).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::external_services::HlsVariant;
+
+    #[test]
+    fn test_build_hls_args_restart() {
+        let request = HlsTranscodeRequest {
+            input_path: "/media/movie.mkv".to_string(),
+            output_dir: "/data/hls/abc/720p".into(),
+            start_segment: 10,
+            segment_seconds: 6,
+            audio_track: 1,
+            variant: HlsVariant {
+                name: "720p".to_string(),
+                height: Some(720),
+                video_bitrate_kbps: 3000,
+                audio_bitrate_kbps: 160,
+            },
+        };
+        let args = FFmpegAdapter::build_hls_args(&request).join(" ");
+
+        assert!(args.contains("-ss 60 -i /media/movie.mkv"));
+        assert!(args.contains("-map 0:a:1?"));
+        assert!(args.contains("-vf scale=-2:720"));
+        assert!(args.contains("-output_ts_offset 60"));
+        assert!(args.contains("-start_number 10"));
+        assert!(args.contains("-force_key_frames expr:gte(t,n_forced*6)"));
+    }
+
+    #[test]
+    fn test_build_preview_args() {
+        let args = FFmpegAdapter::build_preview_args("/media/movie.mkv", 1234.5, 8.0, Path::new("/data/previews/7.mp4"))
+            .join(" ");
+
+        assert!(args.contains("-ss 1234.500 -i /media/movie.mkv -t 8.000"));
+        assert!(args.contains("-an"));
+        assert!(args.contains("-b:v 400k"));
+        assert!(args.contains("-movflags +faststart"));
+        assert!(args.ends_with("/data/previews/7.mp4"));
+    }
+
+    #[test]
+    fn test_parse_loudnorm_output() {
+        let stderr = r#"Output #0, null, to 'pipe:':
+[Parsed_loudnorm_0 @ 0x55d0c0a3b2c0]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-16.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "6.80",
+	"output_thresh" : "-27.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+        let measured = FFmpegAdapter::parse_loudnorm_output(stderr).unwrap();
+        assert_eq!(measured.input_i, -27.61);
+        assert_eq!(measured.input_thresh, -39.2);
+        assert_eq!(measured.target_offset, 0.58);
+
+        // Silent tracks cannot be normalized
+        assert!(FFmpegAdapter::parse_loudnorm_output(&stderr.replace("-27.61", "-inf")).is_none());
+    }
+
+    #[test]
+    fn test_parse_cropdetect_output() {
+        let stderr = "\
+[Parsed_cropdetect_0 @ 0x5581] x1:0 x2:1919 y1:132 y2:947 w:1920 h:800 x:0 y:140 pts:1001 t:0.041708 crop=1920:800:0:140
+[Parsed_cropdetect_0 @ 0x5581] x1:0 x2:1919 y1:130 y2:949 w:1920 h:816 x:0 y:132 pts:2002 t:0.083417 crop=1920:816:0:132
+[out#0/null @ 0x5590] video:22kB audio:0kB";
+        assert_eq!(FFmpegAdapter::parse_cropdetect_output(stderr), Some((1920, 816, 0, 132)));
+
+        // Black frames
+        let black = "[Parsed_cropdetect_0 @ 0x5581] x1:1919 x2:0 y1:1079 y2:0 w:-1904 h:-1072 x:1912 y:1076 crop=-1904:-1072:1912:1076";
+        assert_eq!(FFmpegAdapter::parse_cropdetect_output(black), None);
+        assert_eq!(FFmpegAdapter::parse_cropdetect_output("No video"), None);
+    }
+}
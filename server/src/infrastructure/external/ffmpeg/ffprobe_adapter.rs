@@ -113,6 +113,10 @@ impl FFprobeAdapter {
         for stream in streams.iter() {
             if let Some(codec_type) = stream.get("codec_type").and_then(|ct| ct.as_str()) {
                 if codec_type == "audio" {
+                    let title = stream.get("tags")
+                        .and_then(|t| t.get("title"))
+                        .and_then(|title| title.as_str())
+                        .map(|s| s.to_string());
                     let track = AudioTrack {
                         index: audio_index,
                         language: stream.get("tags")
@@ -130,15 +134,15 @@ impl FFprobeAdapter {
                             .map(|ch| ch as u32),
                         bitrate: stream.get("bit_rate")
                             .and_then(|br| br.as_u64()),
-                        title: stream.get("tags")
-                            .and_then(|t| t.get("title"))
-                            .and_then(|title| title.as_str())
-                            .map(|s| s.to_string()),
                         is_default: stream.get("disposition")
                             .and_then(|d| d.get("default"))
                             .and_then(|df| df.as_i64())
                             .map(|df| df != 0)
                             .unwrap_or(false),
+                        // ffprobe calls audio description "visual_impaired"
+                        is_audio_description: Self::disposition(stream, "visual_impaired")
+                            || Self::title_has_word(&title, &["ad", "description", "descriptive", "described", "dvs"]),
+                        title,
                     };
                     audio_tracks.push(track);
                     audio_index += 1;
@@ -149,6 +153,24 @@ impl FFprobeAdapter {
         Ok(audio_tracks)
     }
 
+    /// Whether a disposition flag ("default", "forced", ...) is set on a stream
+    fn disposition(stream: &serde_json::Value, flag: &str) -> bool {
+        stream.get("disposition")
+            .and_then(|d| d.get(flag))
+            .and_then(|f| f.as_i64())
+            .is_some_and(|f| f != 0)
+    }
+
+    /// Whether a track title contains one of `words` (lowercase)
+    ///
+    /// Muxers do not always set the dispositions, but the title often says.
+    fn title_has_word(title: &Option<String>, words: &[&str]) -> bool {
+        title.as_deref()
+            .unwrap_or("")
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| words.contains(&word.to_lowercase().as_str()))
+    }
+
     /// Extracts subtitle tracks from FFprobe output
     ///
     /// Note: The `index` field uses subtitle-relative indexing (0, 1, 2...)
@@ -163,6 +185,10 @@ impl FFprobeAdapter {
         for stream in streams.iter() {
             if let Some(codec_type) = stream.get("codec_type").and_then(|ct| ct.as_str()) {
                 if codec_type == "subtitle" {
+                    let title = stream.get("tags")
+                        .and_then(|t| t.get("title"))
+                        .and_then(|title| title.as_str())
+                        .map(|s| s.to_string());
                     let track = SubtitleTrack {
                         index: subtitle_index,
                         language: stream.get("tags")
@@ -172,15 +198,12 @@ impl FFprobeAdapter {
                         codec: stream.get("codec_name")
                             .and_then(|c| c.as_str())
                             .map(|s| s.to_string()),
-                        title: stream.get("tags")
-                            .and_then(|t| t.get("title"))
-                            .and_then(|title| title.as_str())
-                            .map(|s| s.to_string()),
-                        is_default: stream.get("disposition")
-                            .and_then(|d| d.get("default"))
-                            .and_then(|df| df.as_i64())
-                            .map(|df| df != 0)
-                            .unwrap_or(false),
+                        is_default: Self::disposition(stream, "default"),
+                        is_forced: Self::disposition(stream, "forced") || Self::title_has_word(&title, &["forced"]),
+                        is_hearing_impaired: Self::disposition(stream, "hearing_impaired")
+                            || Self::disposition(stream, "captions")
+                            || Self::title_has_word(&title, &["sdh", "cc"]),
+                        title,
                     };
                     subtitle_tracks.push(track);
                     subtitle_index += 1;
@@ -342,3 +365,55 @@ impl VideoAnalyzer for FFprobeAdapter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subtitle_track_flags() {
+        let json = serde_json::json!({
+            "streams": [
+                { "codec_type": "video", "codec_name": "h264" },
+                { "codec_type": "subtitle", "codec_name": "subrip", "tags": { "language": "eng" },
+                  "disposition": { "default": 1, "forced": 0, "hearing_impaired": 0 } },
+                { "codec_type": "subtitle", "codec_name": "subrip", "tags": { "language": "eng" },
+                  "disposition": { "default": 0, "forced": 1, "hearing_impaired": 0 } },
+                { "codec_type": "subtitle", "codec_name": "hdmv_pgs_subtitle",
+                  "tags": { "language": "eng", "title": "English (SDH)" } },
+                { "codec_type": "subtitle", "codec_name": "ass", "tags": { "title": "Signs & Songs [Forced]" } }
+            ]
+        });
+
+        let tracks = FFprobeAdapter::extract_subtitle_tracks(&json).unwrap();
+        let flags: Vec<(usize, bool, bool, bool)> = tracks
+            .iter()
+            .map(|t| (t.index, t.is_default, t.is_forced, t.is_hearing_impaired))
+            .collect();
+        assert_eq!(flags, vec![
+            (0, true, false, false),
+            (1, false, true, false),
+            (2, false, false, true),
+            (3, false, true, false),
+        ]);
+    }
+
+    #[test]
+    fn test_audio_description_flag() {
+        let json = serde_json::json!({
+            "streams": [
+                { "codec_type": "audio", "codec_name": "ac3", "tags": { "language": "eng" },
+                  "disposition": { "default": 1, "visual_impaired": 0 } },
+                { "codec_type": "audio", "codec_name": "aac", "tags": { "language": "eng" },
+                  "disposition": { "default": 0, "visual_impaired": 1 } },
+                { "codec_type": "audio", "codec_name": "aac",
+                  "tags": { "language": "eng", "title": "English (Audio Description)" } },
+                { "codec_type": "audio", "codec_name": "aac", "tags": { "title": "Director's Commentary" } }
+            ]
+        });
+
+        let tracks = FFprobeAdapter::extract_audio_tracks(&json).unwrap();
+        let flags: Vec<bool> = tracks.iter().map(|t| t.is_audio_description).collect();
+        assert_eq!(flags, vec![false, true, true, false]);
+    }
+}
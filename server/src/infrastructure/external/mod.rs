@@ -6,7 +6,12 @@
 // - NFO file parser
 // - Chromaprint (fpcalc) audio fingerprinting
 // - Whisper.cpp speech-to-text
-// - Ollama LLM translation
+// - Ollama LLM translation and text embeddings
+// - DeepL and LibreTranslate translation
+// - fanart.tv artwork
+// - OpenSubtitles subtitle downloads
+// - Tesseract OCR of bitmap subtitles
+// - Notification channels (Discord, Telegram, Gotify, email)
 
 pub mod tmdb;
 pub mod ffmpeg;
@@ -14,6 +19,11 @@ pub mod nfo;
 pub mod chromaprint;
 pub mod whisper;
 pub mod ollama;
+pub mod translation;
+pub mod fanart;
+pub mod opensubtitles;
+pub mod tesseract;
+pub mod notifications;
 
 pub use tmdb::*;
 pub use ffmpeg::*;
@@ -21,3 +31,7 @@ pub use nfo::{NfoParser, NfoMetadata};
 pub use chromaprint::*;
 pub use whisper::*;
 pub use ollama::*;
+pub use translation::*;
+pub use fanart::*;
+pub use opensubtitles::*;
+pub use tesseract::*;
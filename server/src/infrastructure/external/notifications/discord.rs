@@ -0,0 +1,59 @@
+//! Discord webhook notifier
+
+use async_trait::async_trait;
+use serde_json::json;
+use super::notifier::{check_status, http_client, truncate, Notification, Notifier};
+use crate::shared::error::NotificationError;
+
+/// Longest embed title Discord accepts
+const MAX_TITLE: usize = 256;
+/// Longest embed description Discord accepts
+const MAX_DESCRIPTION: usize = 4096;
+/// Embed accent color (#E50914)
+const EMBED_COLOR: u32 = 0xE50914;
+
+/// Posts notifications as embeds to a Discord channel webhook
+pub struct DiscordNotifier {
+    webhook_url: String,
+    username: Option<String>,
+    http_client: reqwest::Client,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: &str) -> Self {
+        Self {
+            webhook_url: webhook_url.to_string(),
+            username: None,
+            http_client: http_client(),
+        }
+    }
+
+    /// Posts under this name instead of the webhook's
+    pub fn with_username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let mut body = json!({
+            "embeds": [{
+                "title": truncate(&notification.title, MAX_TITLE),
+                "description": truncate(&notification.body, MAX_DESCRIPTION),
+                "color": EMBED_COLOR,
+            }],
+        });
+        if let Some(username) = &self.username {
+            body["username"] = json!(username);
+        }
+
+        let response = self.http_client.post(&self.webhook_url).json(&body).send().await?;
+        check_status(response)
+    }
+}
@@ -0,0 +1,107 @@
+//! SMTP email notifier
+
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Deserialize;
+use super::notifier::{Notification, Notifier, REQUEST_TIMEOUT};
+use crate::shared::error::NotificationError;
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Upgrades a plain connection, port 587 by default
+    #[default]
+    Starttls,
+    /// Implicit TLS, port 465 by default
+    Tls,
+    /// Unencrypted, port 25 by default; for relays on the local network only
+    None,
+}
+
+/// Settings of an email channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    /// SMTP server host name
+    pub host: String,
+    /// Default: by `security`
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender, e.g. "Homeflix <homeflix@example.com>"
+    pub from: String,
+    /// Recipients
+    pub to: Vec<String>,
+}
+
+/// Sends notifications as plain-text email over SMTP
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailNotifier {
+    /// Checks the addresses and sets up the transport; nothing is
+    /// connected until the first message
+    pub fn new(config: EmailConfig) -> Result<Self, NotificationError> {
+        let parse = |address: &str| {
+            address
+                .trim()
+                .parse::<Mailbox>()
+                .map_err(|e| NotificationError::InvalidConfig(format!("Invalid email address '{}': {}", address, e)))
+        };
+        let from = parse(&config.from)?;
+        let to = config.to.iter().map(|address| parse(address)).collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err(NotificationError::InvalidConfig("Email needs at least one recipient".to_string()));
+        }
+
+        let host = config.host.trim();
+        let builder = match config.security {
+            SmtpSecurity::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)),
+        }
+        .map_err(|e| NotificationError::InvalidConfig(format!("Invalid SMTP server '{}': {}", host, e)))?;
+
+        let mut builder = builder.timeout(Some(REQUEST_TIMEOUT));
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let Some(username) = config.username.filter(|u| !u.is_empty()) {
+            builder = builder.credentials(Credentials::new(username, config.password.unwrap_or_default()));
+        }
+
+        Ok(Self { transport: builder.build(), from, to })
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let mut message = Message::builder().from(self.from.clone());
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = message
+            .subject(&notification.title)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())
+            .map_err(|e| NotificationError::Email(e.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| NotificationError::Email(e.to_string()))?;
+        Ok(())
+    }
+}
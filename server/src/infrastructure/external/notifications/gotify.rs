@@ -0,0 +1,59 @@
+//! Gotify notifier
+
+use async_trait::async_trait;
+use serde_json::json;
+use super::notifier::{check_status, http_client, Notification, Notifier};
+use crate::shared::error::NotificationError;
+
+/// Priority of messages unless configured (Gotify clients show 4-7 as normal)
+const DEFAULT_PRIORITY: u8 = 5;
+
+/// Pushes notifications to a Gotify server as an application
+pub struct GotifyNotifier {
+    url: String,
+    token: String,
+    priority: u8,
+    http_client: reqwest::Client,
+}
+
+impl GotifyNotifier {
+    /// Creates a notifier for the server at `url` with an application token
+    pub fn new(url: &str, token: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            priority: DEFAULT_PRIORITY,
+            http_client: http_client(),
+        }
+    }
+
+    /// Sends messages with this priority
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+#[async_trait]
+impl Notifier for GotifyNotifier {
+    fn name(&self) -> &'static str {
+        "gotify"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let body = json!({
+            "title": notification.title,
+            "message": notification.body,
+            "priority": self.priority,
+        });
+
+        let response = self
+            .http_client
+            .post(format!("{}/message", self.url))
+            .header("X-Gotify-Key", &self.token)
+            .json(&body)
+            .send()
+            .await?;
+        check_status(response)
+    }
+}
@@ -0,0 +1,17 @@
+//! Notification channels
+//!
+//! [`Notifier`] is implemented for Discord webhooks, Telegram bots, Gotify
+//! and SMTP email. Channels are configured through the API, and
+//! [`build_notifier`] turns a stored configuration into its sender.
+
+mod notifier;
+mod discord;
+mod telegram;
+mod gotify;
+mod email;
+
+pub use notifier::*;
+pub use discord::DiscordNotifier;
+pub use telegram::TelegramNotifier;
+pub use gotify::GotifyNotifier;
+pub use email::{EmailConfig, EmailNotifier};
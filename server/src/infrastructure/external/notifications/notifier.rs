@@ -0,0 +1,205 @@
+//! Notifier trait and channel configuration
+
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{Deserialize, Deserializer};
+use super::{DiscordNotifier, EmailConfig, EmailNotifier, GotifyNotifier, TelegramNotifier};
+use crate::shared::error::NotificationError;
+
+/// Time allowed for one request to a notification service
+pub(super) const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Channel kinds [`NotifierConfig`] accepts
+pub const NOTIFIER_KINDS: &[&str] = &["discord", "telegram", "gotify", "email"];
+
+/// A message for people, rendered by each channel its own way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// One line, e.g. "New movie: Dune (2021)"
+    pub title: String,
+    /// Details, plain text
+    pub body: String,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self { title: title.into(), body: body.into() }
+    }
+}
+
+/// Sends notifications to one channel
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Short name for logs ("discord", "telegram", ...)
+    fn name(&self) -> &'static str;
+
+    /// Sends a notification
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError>;
+}
+
+/// Settings of a channel, tagged by its kind
+///
+/// Stored as `kind` plus a `config` object, e.g.
+/// `{"kind": "telegram", "config": {"bot_token": "...", "chat_id": "-100123"}}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", content = "config", rename_all = "snake_case")]
+pub enum NotifierConfig {
+    Discord {
+        /// Webhook URL from the channel's integration settings
+        webhook_url: String,
+        /// Overrides the webhook's name
+        username: Option<String>,
+    },
+    Telegram {
+        bot_token: String,
+        /// User, group or channel; numeric IDs may be given as numbers
+        #[serde(deserialize_with = "string_or_number")]
+        chat_id: String,
+    },
+    Gotify {
+        /// Server URL, e.g. "https://gotify.example.com"
+        url: String,
+        /// Application token
+        token: String,
+        /// Message priority (default: 5)
+        priority: Option<u8>,
+    },
+    Email(EmailConfig),
+}
+
+impl NotifierConfig {
+    /// Parses the configuration of a channel
+    pub fn parse(kind: &str, config: &serde_json::Value) -> Result<Self, NotificationError> {
+        if !NOTIFIER_KINDS.contains(&kind) {
+            return Err(NotificationError::InvalidConfig(format!(
+                "Unknown channel kind '{}', expected one of: {}",
+                kind,
+                NOTIFIER_KINDS.join(", ")
+            )));
+        }
+        serde_json::from_value(serde_json::json!({ "kind": kind, "config": config }))
+            .map_err(|e| NotificationError::InvalidConfig(format!("Invalid {} configuration: {}", kind, e)))
+    }
+
+    /// Config fields holding credentials, never returned by the API
+    pub fn secret_fields(kind: &str) -> &'static [&'static str] {
+        match kind {
+            "discord" => &["webhook_url"],
+            "telegram" => &["bot_token"],
+            "gotify" => &["token"],
+            "email" => &["password"],
+            _ => &[],
+        }
+    }
+}
+
+/// Builds the sender of a channel
+pub fn build_notifier(config: NotifierConfig) -> Result<Arc<dyn Notifier>, NotificationError> {
+    Ok(match config {
+        NotifierConfig::Discord { webhook_url, username } => {
+            let url = parse_http_url(&webhook_url)?;
+            let notifier = DiscordNotifier::new(url.as_str());
+            Arc::new(match username {
+                Some(username) => notifier.with_username(&username),
+                None => notifier,
+            })
+        }
+        NotifierConfig::Telegram { bot_token, chat_id } => {
+            if bot_token.is_empty() || chat_id.is_empty() {
+                return Err(NotificationError::InvalidConfig("Telegram needs a bot token and a chat ID".to_string()));
+            }
+            Arc::new(TelegramNotifier::new(&bot_token, &chat_id))
+        }
+        NotifierConfig::Gotify { url, token, priority } => {
+            let url = parse_http_url(&url)?;
+            let notifier = GotifyNotifier::new(url.as_str(), &token);
+            Arc::new(match priority {
+                Some(priority) => notifier.with_priority(priority),
+                None => notifier,
+            })
+        }
+        NotifierConfig::Email(config) => Arc::new(EmailNotifier::new(config)?),
+    })
+}
+
+/// Shared HTTP client of the web-based senders
+pub(super) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Turns a non-2xx response into an error
+pub(super) fn check_status(response: reqwest::Response) -> Result<(), NotificationError> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(NotificationError::ApiError(response.status().as_u16()))
+    }
+}
+
+/// Shortens text to at most `max` characters, ending it with "…" when cut
+pub(super) fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+fn parse_http_url(url: &str) -> Result<reqwest::Url, NotificationError> {
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|e| NotificationError::InvalidConfig(format!("Invalid URL: {}", e)))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(NotificationError::InvalidConfig("URL must be http or https".to_string()));
+    }
+    Ok(parsed)
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(i64),
+    }
+    Ok(match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(s) => s,
+        StringOrNumber::Number(n) => n.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_config() {
+        let config = NotifierConfig::parse("telegram", &json!({ "bot_token": "123:abc", "chat_id": -100123 })).unwrap();
+        assert!(matches!(config, NotifierConfig::Telegram { ref chat_id, .. } if chat_id == "-100123"));
+
+        assert!(NotifierConfig::parse("pager", &json!({})).is_err());
+        assert!(NotifierConfig::parse("gotify", &json!({ "url": "https://gotify.local" })).is_err());
+        assert!(build_notifier(NotifierConfig::parse("discord", &json!({ "webhook_url": "ftp://x" })).unwrap()).is_err());
+        assert!(build_notifier(
+            NotifierConfig::parse("email", &json!({ "host": "smtp.local", "from": "not an address", "to": ["a@b.c"] }))
+                .unwrap()
+        )
+        .is_err());
+
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("abc", 4), "abc");
+    }
+
+    #[tokio::test]
+    async fn test_errors_hide_url() {
+        let err = http_client().post("http://127.0.0.1:9/bot123:secret/sendMessage").send().await.unwrap_err();
+        let err = NotificationError::from(err);
+        assert!(matches!(err, NotificationError::Network(_)));
+        assert!(!err.to_string().contains("secret"));
+    }
+}
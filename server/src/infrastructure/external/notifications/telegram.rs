@@ -0,0 +1,51 @@
+//! Telegram bot notifier
+
+use async_trait::async_trait;
+use serde_json::json;
+use super::notifier::{check_status, http_client, truncate, Notification, Notifier};
+use crate::shared::error::NotificationError;
+
+const API_URL: &str = "https://api.telegram.org";
+
+/// Longest message Telegram accepts
+const MAX_MESSAGE: usize = 4096;
+
+/// Sends notifications as bot messages to a Telegram chat
+///
+/// The bot must have been started by the user, or added to the group or
+/// channel, before it can write there.
+pub struct TelegramNotifier {
+    bot_token: String,
+    chat_id: String,
+    http_client: reqwest::Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: &str, chat_id: &str) -> Self {
+        Self {
+            bot_token: bot_token.to_string(),
+            chat_id: chat_id.to_string(),
+            http_client: http_client(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let text = format!("{}\n\n{}", notification.title, notification.body);
+        let body = json!({
+            "chat_id": self.chat_id,
+            "text": truncate(text.trim_end(), MAX_MESSAGE),
+            "disable_web_page_preview": true,
+        });
+
+        let url = format!("{}/bot{}/sendMessage", API_URL, self.bot_token);
+        let response = self.http_client.post(&url).json(&body).send().await?;
+        check_status(response)
+    }
+}
@@ -1,12 +1,17 @@
 //! OllamaClient - LLM-based subtitle translation
 //!
 //! Uses Ollama's HTTP API to translate subtitles between languages.
-//! Processes segments in batches to maintain context while avoiding token limits.
+//! Processes segments in overlapping windows to maintain context while
+//! avoiding token limits.
 
+use std::collections::VecDeque;
+use std::ops::Range;
 use std::time::Duration;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::shared::error::TranslationError;
 use crate::infrastructure::external::whisper::TranscriptionSegment;
+use crate::infrastructure::external::translation::{RequestThrottle, SubtitleTranslator};
 
 /// Ollama API request body
 #[derive(Debug, Serialize)]
@@ -51,6 +56,12 @@ pub struct OllamaClient {
     timeout: Duration,
     /// Batch size for segment translation (maintains context)
     batch_size: usize,
+    /// Translated cues before a batch included in its prompt
+    context_lines: usize,
+    /// Retries of a batch whose output does not match its cues
+    max_retries: usize,
+    /// Spacing of requests (None = unthrottled)
+    throttle: Option<RequestThrottle>,
 }
 
 impl OllamaClient {
@@ -69,6 +80,9 @@ impl OllamaClient {
                 .expect("Failed to create HTTP client"),
             timeout: Duration::from_secs(300),
             batch_size: 10, // Translate 10 segments at a time for better context
+            context_lines: 3,
+            max_retries: 2,
+            throttle: None,
         }
     }
 
@@ -88,9 +102,25 @@ impl OllamaClient {
                 .expect("Failed to create HTTP client"),
             timeout,
             batch_size,
+            context_lines: 3,
+            max_retries: 2,
+            throttle: None,
         }
     }
 
+    /// Sets how many translated cues before each batch are shown as context
+    /// (0 = none)
+    pub fn with_context_lines(mut self, lines: usize) -> Self {
+        self.context_lines = lines;
+        self
+    }
+
+    /// Sends at most `requests` requests per minute
+    pub fn with_rate_limit(mut self, requests: u32) -> Self {
+        self.throttle = Some(RequestThrottle::per_minute(requests));
+        self
+    }
+
     /// Checks if Ollama is available and responding
     pub async fn is_available(&self) -> bool {
         let url = format!("{}/api/tags", self.base_url);
@@ -186,6 +216,9 @@ impl OllamaClient {
             keep_alive: Some(0), // Unload immediately after to free VRAM
         };
 
+        if let Some(throttle) = &self.throttle {
+            throttle.wait().await;
+        }
         let url = format!("{}/api/generate", self.base_url);
 
         let response = self.http_client
@@ -214,7 +247,11 @@ impl OllamaClient {
 
     /// Translates transcription segments while preserving timestamps
     ///
-    /// Processes segments in batches to maintain context for better translations.
+    /// Segments are translated in windows of `batch_size` cues. Each window
+    /// is prompted with the last `context_lines` cues before it and their
+    /// translations, so the dialogue stays coherent across windows and
+    /// speakers keep their voice and form of address. A window whose output
+    /// does not have one line per cue is retried, then split in half.
     ///
     /// # Arguments
     /// * `segments` - Transcription segments to translate
@@ -226,67 +263,82 @@ impl OllamaClient {
         source_lang: &str,
         target_lang: &str,
     ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
-        let mut translated_segments = Vec::with_capacity(segments.len());
-
-        // Process in batches for context preservation
-        for chunk in segments.chunks(self.batch_size) {
-            let batch_text = chunk
-                .iter()
-                .enumerate()
-                .map(|(i, s)| format!("[{}] {}", i + 1, s.text))
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            let translated_batch = self.translate_batch(&batch_text, source_lang, target_lang).await?;
-
-            // Parse translated batch back to segments
-            let translated_texts = parse_batch_response(&translated_batch, chunk.len());
-
-            for (i, segment) in chunk.iter().enumerate() {
-                translated_segments.push(TranscriptionSegment {
-                    start_time: segment.start_time,
-                    end_time: segment.end_time,
-                    text: translated_texts.get(i)
-                        .cloned()
-                        .unwrap_or_else(|| segment.text.clone()),
-                });
+        let mut translated: Vec<String> = Vec::with_capacity(segments.len());
+        let mut pending: VecDeque<Range<usize>> = (0..segments.len())
+            .step_by(self.batch_size.max(1))
+            .map(|start| start..(start + self.batch_size.max(1)).min(segments.len()))
+            .collect();
+
+        // Windows are translated in order, so the context is always the
+        // translations so far
+        while let Some(window) = pending.pop_front() {
+            let context_start = window.start.saturating_sub(self.context_lines);
+            let context: Vec<(&str, &str)> = (context_start..window.start)
+                .map(|i| (segments[i].text.as_str(), translated[i].as_str()))
+                .collect();
+            let lines: Vec<&str> = segments[window.clone()].iter().map(|s| s.text.as_str()).collect();
+
+            match self.translate_window(&lines, &context, source_lang, target_lang).await? {
+                Ok(texts) => translated.extend(texts),
+                Err(_) if window.len() > 1 => {
+                    let middle = window.start + window.len() / 2;
+                    tracing::debug!("Splitting subtitle window {:?} at {}", window, middle);
+                    pending.push_front(middle..window.end);
+                    pending.push_front(window.start..middle);
+                }
+                // A single cue is whatever came back
+                Err(response) => {
+                    tracing::warn!("Unnumbered translation of cue {}: {}", window.start + 1, response.trim());
+                    let text = parse_batch_response(&response, 1).remove(0);
+                    translated.push(if text.is_empty() { lines[0].to_string() } else { text });
+                }
             }
         }
 
-        Ok(translated_segments)
+        Ok(segments
+            .into_iter()
+            .zip(translated)
+            .map(|(segment, text)| TranscriptionSegment { text, ..segment })
+            .collect())
     }
 
-    /// Translates a batch of numbered lines
-    async fn translate_batch(
+    /// Translates one window of cues, retrying malformed output
+    ///
+    /// The inner error is the last response if no attempt had exactly one
+    /// translation per cue.
+    async fn translate_window(
         &self,
-        batch_text: &str,
+        lines: &[&str],
+        context: &[(&str, &str)],
         source_lang: &str,
         target_lang: &str,
-    ) -> Result<String, TranslationError> {
-        // Build language-specific instructions for more natural output
-        let style_instructions = build_style_instructions(target_lang);
-
-        let prompt = format!(
-            "You are translating movie/TV dialogue subtitles from {} to {}.\n\n\
-             CRITICAL RULES:\n\
-             1. Keep the [1], [2], [3] numbering exactly as is\n\
-             2. Output ONLY the translations, nothing else\n\
-             3. These are SPOKEN dialogues - use natural, everyday speech\n\
-             4. Match the tone: casual speech stays casual, formal stays formal\n\
-             5. Use contractions and colloquialisms appropriate for dialogue\n\
-             6. If a segment appears NONSENSICAL or INCOMPLETE:\n\
-                - Use surrounding context (previous/next segments) to understand the meaning\n\
-                - Correct obvious transcription errors (misheard words that sound similar)\n\
-                - Make the subtitle readable and sensible\n\
-                - If truly unrecoverable, translate literally but keep it grammatical\n\n\
-             {}\n\n\
-             Subtitles to translate:\n{}",
-            source_lang, target_lang, style_instructions, batch_text
-        );
+    ) -> Result<Result<Vec<String>, String>, TranslationError> {
+        let prompt = build_window_prompt(lines, context, source_lang, target_lang);
+        let mut response = String::new();
+        for attempt in 0..=self.max_retries {
+            response = self.translate_batch(&prompt).await?;
+            match parse_numbered_lines(&response, lines.len()) {
+                Some(texts) => {
+                    return Ok(Ok(
+                        lines.iter().zip(texts).map(|(line, text)| keep_dialogue_dashes(line, &text)).collect(),
+                    ));
+                }
+                None => tracing::debug!(
+                    "Malformed translation of {} cues (attempt {}/{})",
+                    lines.len(),
+                    attempt + 1,
+                    self.max_retries + 1
+                ),
+            }
+        }
+        Ok(Err(response))
+    }
 
+    /// Sends a window prompt to the model
+    async fn translate_batch(&self, prompt: &str) -> Result<String, TranslationError> {
         let request = OllamaRequest {
             model: self.model.clone(),
-            prompt,
+            prompt: prompt.to_string(),
             stream: false,
             options: Some(OllamaOptions {
                 temperature: 0.3,
@@ -295,6 +347,9 @@ impl OllamaClient {
             keep_alive: None, // Keep model loaded between batches for efficiency
         };
 
+        if let Some(throttle) = &self.throttle {
+            throttle.wait().await;
+        }
         let url = format!("{}/api/generate", self.base_url);
 
         let response = self.http_client
@@ -334,6 +389,135 @@ impl OllamaClient {
     }
 }
 
+#[async_trait]
+impl SubtitleTranslator for OllamaClient {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    async fn is_available(&self) -> bool {
+        OllamaClient::is_available(self).await
+    }
+
+    /// Prompts with language names, which LLMs follow better than codes
+    async fn translate_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
+        OllamaClient::translate_segments(
+            self,
+            segments,
+            language_code_to_name(source_language),
+            language_code_to_name(target_language),
+        )
+        .await
+    }
+
+    async fn release(&self) -> Result<(), TranslationError> {
+        self.unload_model().await
+    }
+}
+
+/// Builds the prompt of a window of cues
+///
+/// Cues are numbered `[1]`..`[n]`; the context cues before the window are
+/// listed with their translations and are not to be translated again.
+fn build_window_prompt(lines: &[&str], context: &[(&str, &str)], source_lang: &str, target_lang: &str) -> String {
+    // Build language-specific instructions for more natural output
+    let style_instructions = build_style_instructions(target_lang);
+
+    let batch_text = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("[{}] {}", i + 1, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let context_text = if context.is_empty() {
+        String::new()
+    } else {
+        let previous = context
+            .iter()
+            .map(|(source, translation)| format!("{} => {}", source.replace('\n', " "), translation.replace('\n', " ")))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Previous lines and their translations (context only, do NOT output them):\n{}\n\n",
+            previous
+        )
+    };
+
+    format!(
+        "You are translating movie/TV dialogue subtitles from {} to {}.\n\n\
+         CRITICAL RULES:\n\
+         1. Keep the [1], [2], [3] numbering exactly as is, one numbered line per input line\n\
+         2. Output ONLY the translations, nothing else\n\
+         3. These are SPOKEN dialogues - use natural, everyday speech\n\
+         4. Match the tone: casual speech stays casual, formal stays formal\n\
+         5. Use contractions and colloquialisms appropriate for dialogue\n\
+         6. Continue the conversation of the previous lines: keep each speaker's voice, \
+            gender and form of address consistent with them\n\
+         7. Lines starting with '-' contain several speakers; keep the dashes\n\
+         8. If a segment appears NONSENSICAL or INCOMPLETE:\n\
+            - Use surrounding context (previous/next segments) to understand the meaning\n\
+            - Correct obvious transcription errors (misheard words that sound similar)\n\
+            - Make the subtitle readable and sensible\n\
+            - If truly unrecoverable, translate literally but keep it grammatical\n\n\
+         {}\n\n\
+         {}Subtitles to translate:\n{}",
+        source_lang, target_lang, style_instructions, context_text, batch_text
+    )
+}
+
+/// Parses a numbered response strictly: exactly one non-empty translation
+/// for each of `[1]`..`[expected_count]`, in order
+///
+/// Unnumbered lines continue the cue before them (multi-line cues).
+fn parse_numbered_lines(response: &str, expected_count: usize) -> Option<Vec<String>> {
+    let mut results: Vec<String> = Vec::with_capacity(expected_count);
+    for line in response.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let numbered = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .and_then(|(number, text)| number.trim().parse::<usize>().ok().map(|n| (n, text.trim())));
+        match numbered {
+            Some((number, text)) if number == results.len() + 1 => results.push(text.to_string()),
+            Some(_) => return None,
+            // Text before the first cue is commentary
+            None => {
+                if let Some(last) = results.last_mut() {
+                    last.push('\n');
+                    last.push_str(line);
+                }
+            }
+        }
+    }
+    let complete = results.len() == expected_count && results.iter().all(|r| !r.is_empty());
+    complete.then_some(results)
+}
+
+/// Puts back the dialogue dashes of a multi-speaker cue the model dropped
+fn keep_dialogue_dashes(source: &str, translation: &str) -> String {
+    let source_lines: Vec<&str> = source.lines().collect();
+    let translated_lines: Vec<&str> = translation.lines().collect();
+    if source_lines.len() != translated_lines.len() {
+        return translation.to_string();
+    }
+    source_lines
+        .iter()
+        .zip(translated_lines)
+        .map(|(source, translated)| {
+            if source.trim_start().starts_with('-') && !translated.trim_start().starts_with('-') {
+                format!("- {}", translated.trim_start())
+            } else {
+                translated.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Parses numbered batch response back into individual texts
 fn parse_batch_response(response: &str, expected_count: usize) -> Vec<String> {
     let mut results = Vec::with_capacity(expected_count);
@@ -508,6 +692,26 @@ mod tests {
         assert_eq!(results[2], "Harmadik sor");
     }
 
+    #[test]
+    fn test_parse_numbered_lines() {
+        let response = "Here you go:\n[1] Szia!\n[2] - Hová mész?\n- Haza.\n[3] Jó.";
+        assert_eq!(
+            parse_numbered_lines(response, 3),
+            Some(vec!["Szia!".to_string(), "- Hová mész?\n- Haza.".to_string(), "Jó.".to_string()])
+        );
+        // Missing, merged or renumbered cues are rejected
+        assert_eq!(parse_numbered_lines("[1] Szia!\n[3] Jó.", 3), None);
+        assert_eq!(parse_numbered_lines("[1] Szia!\n[2] Jó.", 3), None);
+        assert_eq!(parse_numbered_lines("[1] Szia!\n[2]\n[3] Jó.", 3), None);
+
+        assert_eq!(keep_dialogue_dashes("- Where to?\n- Home.", "Hová?\n- Haza."), "- Hová?\n- Haza.");
+        assert_eq!(keep_dialogue_dashes("Where to?", "Hová?"), "Hová?");
+
+        let prompt = build_window_prompt(&["Go home."], &[("Where to?", "Hová?")], "English", "Hungarian");
+        assert!(prompt.contains("Where to? => Hová?"));
+        assert!(prompt.ends_with("[1] Go home."));
+    }
+
     #[test]
     fn test_language_code_to_name() {
         assert_eq!(language_code_to_name("en"), "English");
@@ -0,0 +1,102 @@
+//! OllamaEmbedder - text embeddings for semantic search
+//!
+//! Uses Ollama's `/api/embed` endpoint with an embedding model (e.g.
+//! "nomic-embed-text") to turn texts into vectors whose cosine similarity
+//! reflects how close their meanings are.
+
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::EmbeddingError;
+
+/// Turns texts into embedding vectors
+#[async_trait]
+pub trait TextEmbedder: Send + Sync {
+    /// Name of the embedding model; vectors of different models don't mix
+    fn model(&self) -> &str;
+
+    /// One vector per input, in input order
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError>;
+}
+
+/// Ollama embed request body
+#[derive(Debug, Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+/// Ollama embed response
+#[derive(Debug, Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Ollama client for text embeddings
+pub struct OllamaEmbedder {
+    /// Ollama API base URL
+    base_url: String,
+    /// Embedding model
+    model: String,
+    http_client: reqwest::Client,
+}
+
+impl OllamaEmbedder {
+    /// Creates a new OllamaEmbedder
+    ///
+    /// # Arguments
+    /// * `base_url` - Ollama API URL (e.g., "http://localhost:11434")
+    /// * `model` - Embedding model name (e.g., "nomic-embed-text")
+    pub fn new(base_url: &str, model: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(120))
+                .build()
+                .expect("Failed to create HTTP client"),
+        }
+    }
+}
+
+#[async_trait]
+impl TextEmbedder for OllamaEmbedder {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/api/embed", self.base_url);
+        let response = self.http_client
+            .post(&url)
+            .json(&EmbedRequest { model: &self.model, input: inputs })
+            .send()
+            .await
+            .map_err(|e| EmbeddingError::HttpError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(EmbeddingError::ServiceUnavailable(
+                format!("Ollama returned {}: {}", status, error_text)
+            ));
+        }
+
+        let body: EmbedResponse = response
+            .json()
+            .await
+            .map_err(|e| EmbeddingError::ParseError(e.to_string()))?;
+        if body.embeddings.len() != inputs.len() {
+            return Err(EmbeddingError::ParseError(format!(
+                "Expected {} embeddings, got {}",
+                inputs.len(),
+                body.embeddings.len()
+            )));
+        }
+        Ok(body.embeddings)
+    }
+}
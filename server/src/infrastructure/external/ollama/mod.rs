@@ -1,8 +1,10 @@
-//! Ollama LLM Translation Module
+//! Ollama LLM Module
 //!
-//! Provides subtitle translation using Ollama's local LLM API.
-//! Translates transcribed subtitles from one language to another.
+//! Provides subtitle translation using Ollama's local LLM API, and text
+//! embeddings for semantic search.
 
 mod client;
+mod embeddings;
 
 pub use client::*;
+pub use embeddings::*;
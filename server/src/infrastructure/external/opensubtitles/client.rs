@@ -0,0 +1,302 @@
+//! OpenSubtitles Client
+//!
+//! Searches and downloads subtitles through the OpenSubtitles REST API
+//! (api.opensubtitles.com). Search results are cached; downloads count
+//! against the daily quota of the API key, or of the account when
+//! credentials are configured.
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::domain::repositories::CacheRepository;
+use crate::interfaces::external_services::{SubtitleCandidate, SubtitleProvider, SubtitleSearch};
+use crate::shared::error::SubtitleProviderError;
+
+/// Cache TTL for search results (1 day)
+const CACHE_TTL_SECS: u64 = 86400;
+
+/// OpenSubtitles API client
+pub struct OpenSubtitlesClient {
+    api_key: String,
+    /// Username and password of the account downloads are counted on
+    credentials: Option<(String, String)>,
+    /// Session token from the last login
+    token: Mutex<Option<String>>,
+    http_client: Client,
+    cache: Arc<dyn CacheRepository>,
+    base_url: String,
+}
+
+impl OpenSubtitlesClient {
+    /// Creates a new OpenSubtitles client
+    ///
+    /// # Arguments
+    /// * `api_key` - OpenSubtitles consumer API key
+    /// * `cache` - Cache repository for caching search results
+    pub fn new(api_key: &str, cache: Arc<dyn CacheRepository>) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            credentials: None,
+            token: Mutex::new(None),
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("homeflixd v", env!("CARGO_PKG_VERSION")))
+                .build()
+                .unwrap_or_default(),
+            cache,
+            base_url: "https://api.opensubtitles.com/api/v1".to_string(),
+        }
+    }
+
+    /// Logs in with an account, which raises the daily download quota
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+            .header("Api-Key", &self.api_key)
+            .header(reqwest::header::ACCEPT, "application/json")
+    }
+
+    /// Session token, logging in first if needed (None without credentials)
+    async fn token(&self) -> Result<Option<String>, SubtitleProviderError> {
+        let Some((username, password)) = &self.credentials else {
+            return Ok(None);
+        };
+        let mut token = self.token.lock().await;
+        if token.is_none() {
+            let response = self.request(self.http_client.post(format!("{}/login", self.base_url)))
+                .json(&serde_json::json!({ "username": username, "password": password }))
+                .send()
+                .await?;
+            let status = response.status();
+            if status == StatusCode::UNAUTHORIZED {
+                return Err(SubtitleProviderError::Authentication(format!("Login of {} rejected", username)));
+            }
+            if !status.is_success() {
+                return Err(SubtitleProviderError::ApiError(status.as_u16()));
+            }
+            let login: LoginResponse = response.json().await?;
+            debug!("Logged in to OpenSubtitles as {}", username);
+            *token = Some(login.token);
+        }
+        Ok(token.clone())
+    }
+}
+
+#[async_trait]
+impl SubtitleProvider for OpenSubtitlesClient {
+    async fn search(&self, search: &SubtitleSearch) -> Result<Vec<SubtitleCandidate>, SubtitleProviderError> {
+        let params = search_params(search);
+        let query = params.iter()
+            .map(|(name, value)| format!("{}={}", name, urlencoding::encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let cache_key = format!("opensubtitles:search:{}", query);
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let url = format!("{}/subtitles?{}", self.base_url, query);
+        let response = self.request(self.http_client.get(&url)).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SubtitleProviderError::ApiError(status.as_u16()));
+        }
+        let candidates = candidates(response.json().await?);
+        debug!("OpenSubtitles search {} found {} subtitles", query, candidates.len());
+
+        let cached_value = serde_json::to_string(&candidates)?;
+        self.cache.set(&cache_key, &cached_value, CACHE_TTL_SECS).await?;
+
+        Ok(candidates)
+    }
+
+    async fn download(&self, file_id: i64) -> Result<Vec<u8>, SubtitleProviderError> {
+        let token = self.token().await?;
+        let mut request = self.request(self.http_client.post(format!("{}/download", self.base_url)))
+            .json(&serde_json::json!({ "file_id": file_id }));
+        if let Some(token) = &token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        match status {
+            // Quota used up; the message says when it resets
+            StatusCode::NOT_ACCEPTABLE | StatusCode::TOO_MANY_REQUESTS => {
+                let message = response.json::<ErrorResponse>().await
+                    .map(|e| e.message)
+                    .unwrap_or_else(|_| status.to_string());
+                return Err(SubtitleProviderError::QuotaExceeded(message));
+            }
+            StatusCode::UNAUTHORIZED => {
+                // Expired session; the next download logs in again
+                *self.token.lock().await = None;
+                return Err(SubtitleProviderError::Authentication("Session expired".to_string()));
+            }
+            _ if !status.is_success() => return Err(SubtitleProviderError::ApiError(status.as_u16())),
+            _ => {}
+        }
+
+        let download: DownloadResponse = response.json().await?;
+        if let Some(remaining) = download.remaining {
+            debug!("OpenSubtitles downloads remaining today: {}", remaining);
+        }
+
+        let response = self.http_client.get(&download.link).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SubtitleProviderError::ApiError(status.as_u16()));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// Query parameters of a search
+///
+/// OpenSubtitles redirects requests whose parameters are not sorted or not
+/// lowercase, so they are built in alphabetical order.
+fn search_params(search: &SubtitleSearch) -> Vec<(&'static str, String)> {
+    let mut languages: Vec<String> = search.languages.iter().map(|l| l.to_lowercase()).collect();
+    languages.sort();
+    languages.dedup();
+
+    let mut params = Vec::new();
+    if let Some(episode) = search.episode {
+        params.push(("episode_number", episode.to_string()));
+    }
+    if !languages.is_empty() {
+        params.push(("languages", languages.join(",")));
+    }
+    if let Some(hash) = &search.moviehash {
+        params.push(("moviehash", hash.to_lowercase()));
+    }
+    if let Some(id) = search.parent_tmdb_id {
+        params.push(("parent_tmdb_id", id.to_string()));
+    }
+    if let Some(query) = &search.query {
+        params.push(("query", query.to_lowercase()));
+    }
+    if let Some(season) = search.season {
+        params.push(("season_number", season.to_string()));
+    }
+    if let Some(id) = search.tmdb_id {
+        params.push(("tmdb_id", id.to_string()));
+    }
+    if let Some(year) = search.year {
+        params.push(("year", year.to_string()));
+    }
+    params
+}
+
+/// Flattens search results into one candidate per subtitle file
+fn candidates(response: SearchResponse) -> Vec<SubtitleCandidate> {
+    response.data
+        .into_iter()
+        .flat_map(|subtitle| {
+            let attributes = subtitle.attributes;
+            attributes.files.into_iter().map(move |file| SubtitleCandidate {
+                file_id: file.file_id,
+                language: attributes.language.clone().unwrap_or_default(),
+                release: attributes.release.clone(),
+                download_count: attributes.download_count,
+                hash_match: attributes.moviehash_match,
+                hearing_impaired: attributes.hearing_impaired,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    data: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    attributes: SubtitleAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubtitleAttributes {
+    language: Option<String>,
+    release: Option<String>,
+    #[serde(default)]
+    download_count: i64,
+    #[serde(default)]
+    moviehash_match: bool,
+    #[serde(default)]
+    hearing_impaired: bool,
+    #[serde(default)]
+    files: Vec<SubtitleFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubtitleFile {
+    file_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct LoginResponse {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadResponse {
+    link: String,
+    remaining: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_params_and_candidates() {
+        let search = SubtitleSearch {
+            languages: vec!["hu".to_string(), "EN".to_string()],
+            parent_tmdb_id: Some(1399),
+            season: Some(1),
+            episode: Some(2),
+            ..Default::default()
+        };
+        let names: Vec<&str> = search_params(&search).iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["episode_number", "languages", "parent_tmdb_id", "season_number"]);
+        assert_eq!(search_params(&search)[1].1, "en,hu");
+
+        let response: SearchResponse = serde_json::from_str(r#"{
+            "total_count": 1,
+            "data": [{
+                "id": "123",
+                "type": "subtitle",
+                "attributes": {
+                    "language": "hu",
+                    "download_count": 4200,
+                    "hearing_impaired": false,
+                    "moviehash_match": true,
+                    "release": "Show.S01E02.1080p.WEB",
+                    "files": [{"file_id": 987, "file_name": "Show.S01E02.srt"}]
+                }
+            }]
+        }"#).unwrap();
+        let candidates = candidates(response);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].file_id, 987);
+        assert_eq!(candidates[0].language, "hu");
+        assert!(candidates[0].hash_match);
+    }
+}
@@ -0,0 +1,10 @@
+//! OpenSubtitles Module
+//!
+//! Searches and downloads subtitles from OpenSubtitles, by file hash or by
+//! the identified title.
+
+mod client;
+mod hash;
+
+pub use client::*;
+pub use hash::*;
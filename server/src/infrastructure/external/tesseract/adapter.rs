@@ -0,0 +1,125 @@
+//! TesseractAdapter - Subtitle OCR using the tesseract CLI
+//!
+//! Reads the text of PGS subtitle images. Images are piped to tesseract as
+//! PGM, so no temporary files are needed. Recognition needs the traineddata
+//! of the subtitle language (e.g. `tesseract-ocr-hun`); English is used for
+//! languages without a known model name.
+
+use std::process::Stdio;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::interfaces::external_services::{SubtitleImage, TextRecognizer};
+use crate::shared::error::SubtitleError;
+
+/// Time allowed for recognizing one subtitle image
+const RECOGNIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// tesseract adapter for subtitle OCR
+pub struct TesseractAdapter {
+    /// Path to tesseract binary
+    cli_path: String,
+}
+
+impl TesseractAdapter {
+    /// Creates a new TesseractAdapter
+    ///
+    /// # Arguments
+    /// * `cli_path` - Path to the tesseract binary
+    pub fn new(cli_path: &str) -> Self {
+        Self { cli_path: cli_path.to_string() }
+    }
+
+    /// Checks if tesseract is available
+    pub async fn is_available(&self) -> bool {
+        Command::new(&self.cli_path)
+            .arg("--version")
+            .output()
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+}
+
+#[async_trait]
+impl TextRecognizer for TesseractAdapter {
+    async fn recognize(&self, image: &SubtitleImage, language: Option<&str>) -> Result<String, SubtitleError> {
+        let mut child = Command::new(&self.cli_path)
+            // A subtitle is a single block of text
+            .args(["stdin", "stdout", "-l", tesseract_language(language), "--psm", "6"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let pgm = image.to_pgm();
+        let output = timeout(RECOGNIZE_TIMEOUT, async {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(&pgm).await?;
+            }
+            child.wait_with_output().await
+        })
+        .await
+        .map_err(|_| SubtitleError::ParseError("tesseract timed out".to_string()))??;
+
+        if !output.status.success() {
+            return Err(SubtitleError::ParseError(format!(
+                "tesseract failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        Ok(text.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// Tesseract model name for an ISO 639-1 language code
+pub fn tesseract_language(language: Option<&str>) -> &'static str {
+    match language.unwrap_or("en") {
+        "hu" => "hun",
+        "de" => "deu",
+        "es" => "spa",
+        "fr" => "fra",
+        "it" => "ita",
+        "pt" => "por",
+        "ru" => "rus",
+        "pl" => "pol",
+        "nl" => "nld",
+        "ja" => "jpn",
+        "ko" => "kor",
+        "zh" => "chi_sim",
+        "ar" => "ara",
+        "cs" => "ces",
+        "sv" => "swe",
+        "da" => "dan",
+        "fi" => "fin",
+        "no" => "nor",
+        "el" => "ell",
+        "he" => "heb",
+        "tr" => "tur",
+        "ro" => "ron",
+        "uk" => "ukr",
+        "bg" => "bul",
+        "hr" => "hrv",
+        "sk" => "slk",
+        "sl" => "slv",
+        _ => "eng",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tesseract_language() {
+        assert_eq!(tesseract_language(Some("hu")), "hun");
+        assert_eq!(tesseract_language(Some("zh")), "chi_sim");
+        assert_eq!(tesseract_language(Some("xx")), "eng");
+        assert_eq!(tesseract_language(None), "eng");
+    }
+}
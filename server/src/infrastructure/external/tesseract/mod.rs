@@ -0,0 +1,7 @@
+//! Tesseract OCR Module
+//!
+//! Provides text recognition of bitmap subtitles using the tesseract CLI.
+
+mod adapter;
+
+pub use adapter::*;
@@ -3,29 +3,66 @@
 //! Provides TMDB API client with caching, rate limiting, and retry logic
 
 use async_trait::async_trait;
-use reqwest::Client;
+use bytes::Bytes;
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Semaphore;
-use tracing::debug;
+use tracing::{debug, warn};
 use crate::interfaces::external_services::{
     TmdbService, TmdbSearcher, TmdbFetcher, TmdbResolver, TmdbCreditsFetcher,
+    TmdbLocalizedFetcher, LocalizedText, TmdbVideoFetcher, VideoInfo,
+    TmdbAlternativeTitlesFetcher, AlternativeTitle,
+    TmdbPersonFetcher, PersonDetail, PersonCreditInfo, TmdbChangesFetcher, TmdbExternalIdsFetcher,
     TmdbMatch, MovieDetail, TvDetail, SeasonDetail, EpisodeDetail, Genre, CollectionInfo,
     CollectionDetail, CollectionPartInfo, Credits, CastMember, CrewMember,
 };
 use crate::domain::value_objects::{MatchStrategy, ConfidenceScore};
-use crate::domain::repositories::CacheRepository;
+use crate::domain::repositories::{CacheRepository, RecordedResponse, TmdbResponseRepository};
 use crate::shared::error::TmdbError;
 use crate::shared::text::{TitleNormalizer, FuzzyMatcher, FuzzyMatchConfig};
+use super::limiter::{LimiterStats, RequestCoalescer, TokenBucket};
+
+/// Default requests per second sent to TMDB
+pub const DEFAULT_REQUESTS_PER_SECOND: u32 = 20;
+/// Times a request answered with 429 is retried
+const RATE_LIMIT_RETRIES: u32 = 2;
+/// Longest pause honored from a 429's `Retry-After`
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// TMDB request activity since the server started
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TmdbRequestStats {
+    /// Requests sent to TMDB
+    pub requests: u64,
+    /// Requests answered by an identical one already in flight
+    pub coalesced: u64,
+    /// 429 responses from TMDB
+    pub rate_limited: u64,
+    #[serde(flatten)]
+    pub limiter: LimiterStats,
+}
 
 /// TMDB API client with caching and rate limiting
 pub struct TmdbClient {
-    api_key: String,
+    /// Replaceable while running, see [`TmdbClient::set_api_key`]
+    api_key: std::sync::RwLock<String>,
     http_client: Client,
     cache: Arc<dyn CacheRepository>,
     base_url: String,
     image_base_url: String,
-    rate_limiter: Arc<RateLimiter>,
+    rate_limiter: TokenBucket,
+    /// Identical requests in flight share one response body
+    in_flight: RequestCoalescer<Result<Bytes, TmdbError>>,
+    requests: AtomicU64,
+    rate_limited: AtomicU64,
+    /// Default metadata language (e.g. "hu-HU"); None uses TMDB's default (en-US)
+    language: Option<String>,
+    /// Where answered requests are recorded, for offline servers
+    responses: Option<Arc<dyn TmdbResponseRepository>>,
+    /// Requests are answered from `responses` only
+    offline: bool,
 }
 
 impl TmdbClient {
@@ -43,7 +80,7 @@ impl TmdbClient {
         }
 
         Ok(Self {
-            api_key: api_key.to_string(),
+            api_key: std::sync::RwLock::new(api_key.to_string()),
             http_client: Client::builder()
                 .timeout(Duration::from_secs(30))
                 .build()
@@ -51,34 +88,191 @@ impl TmdbClient {
             cache,
             base_url: "https://api.themoviedb.org/3".to_string(),
             image_base_url: "https://image.tmdb.org/t/p/w500".to_string(),
-            rate_limiter: Arc::new(RateLimiter::new(4)), // 4 requests per second
+            rate_limiter: TokenBucket::new(DEFAULT_REQUESTS_PER_SECOND),
+            in_flight: RequestCoalescer::new(),
+            requests: AtomicU64::new(0),
+            rate_limited: AtomicU64::new(0),
+            language: None,
+            responses: None,
+            offline: false,
         })
     }
 
+    /// Creates a client that never contacts TMDB
+    ///
+    /// Requests are answered with the responses recorded by an online
+    /// server (see [`TmdbClient::with_response_store`]) and imported; others
+    /// fail with [`TmdbError::Offline`].
+    pub fn offline(
+        cache: Arc<dyn CacheRepository>,
+        responses: Arc<dyn TmdbResponseRepository>,
+    ) -> Result<Self, TmdbError> {
+        let mut client = Self::new("offline", cache)?;
+        client.responses = Some(responses);
+        client.offline = true;
+        Ok(client)
+    }
+
+    /// Records the responses of TMDB requests for offline servers
+    pub fn with_response_store(mut self, responses: Arc<dyn TmdbResponseRepository>) -> Self {
+        self.responses = Some(responses);
+        self
+    }
+
+    /// Whether requests are answered from recorded responses only
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Sets the default language for detail requests
+    ///
+    /// Search requests are intentionally left unlocalized so that matching
+    /// against (usually English or original) filenames is not affected.
+    ///
+    /// # Arguments
+    /// * `language` - ISO 639-1 language tag, optionally with region (e.g. "hu-HU")
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language.filter(|l| !l.trim().is_empty());
+        self
+    }
+
+    /// Sets how many requests per second are sent to TMDB on average
+    pub fn with_rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limiter = TokenBucket::new(requests_per_second);
+        self
+    }
+
+    /// Request, coalescing and rate limiter counters
+    pub fn request_stats(&self) -> TmdbRequestStats {
+        TmdbRequestStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            coalesced: self.in_flight.coalesced(),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            limiter: self.rate_limiter.stats(),
+        }
+    }
+
+    /// Replaces the API key for the requests that follow
+    ///
+    /// Cached responses stay valid; an empty key is ignored.
+    pub fn set_api_key(&self, api_key: &str) {
+        if !api_key.is_empty() {
+            *self.api_key.write().unwrap_or_else(|e| e.into_inner()) = api_key.to_string();
+        }
+    }
+
+    /// Gets the configured default language
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Builds a cache key that is unique per language
+    fn localized_cache_key(base: String, language: Option<&str>) -> String {
+        match language {
+            Some(lang) => format!("{}:{}", base, lang),
+            None => base,
+        }
+    }
+
     /// Makes a GET request to TMDB API
     async fn make_request<T: serde::de::DeserializeOwned>(
         &self,
         endpoint: &str,
     ) -> Result<T, TmdbError> {
-        self.rate_limiter.acquire().await;
+        self.make_request_in(endpoint, None).await
+    }
+
+    /// Makes a GET request to TMDB API in the given language
+    ///
+    /// Concurrent identical requests share one response.
+    async fn make_request_in<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        language: Option<&str>,
+    ) -> Result<T, TmdbError> {
+        let key = format!("{}#{}", endpoint, language.unwrap_or_default());
+        let body = self.in_flight.run(&key, || self.respond(&key, endpoint, language)).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Answers a request from the recorded responses when offline, or from
+    /// TMDB, recording what it says
+    ///
+    /// Not-found answers are recorded too, so an offline lookup fails the
+    /// way the online one did.
+    async fn respond(&self, request: &str, endpoint: &str, language: Option<&str>) -> Result<Bytes, TmdbError> {
+        if self.offline {
+            let recorded = match &self.responses {
+                Some(responses) => responses.find(request).await?,
+                None => None,
+            };
+            return match recorded {
+                Some(response) if response.status == StatusCode::OK.as_u16() => Ok(Bytes::from(response.body)),
+                Some(response) => Err(TmdbError::ApiError(response.status)),
+                None => Err(TmdbError::Offline(request.to_string())),
+            };
+        }
+
+        let result = self.fetch(endpoint, language).await;
+        let Some(responses) = &self.responses else { return result };
+        let recorded = match &result {
+            Ok(body) => RecordedResponse { status: StatusCode::OK.as_u16(), body: body.to_vec() },
+            Err(TmdbError::ApiError(404)) => RecordedResponse { status: 404, body: Vec::new() },
+            Err(_) => return result,
+        };
+        if let Err(e) = responses.save(request, &recorded).await {
+            warn!("Failed to record TMDB response to {}: {}", endpoint, e);
+        }
+        result
+    }
 
+    /// Sends a GET request within the rate limit
+    ///
+    /// A 429 pauses all requests for the `Retry-After` TMDB sends (1 second
+    /// without one) and is retried.
+    async fn fetch(&self, endpoint: &str, language: Option<&str>) -> Result<Bytes, TmdbError> {
         // Determine separator: use & if endpoint already has query params, else ?
         let separator = if endpoint.contains('?') { '&' } else { '?' };
-        let url = format!("{}{}{}api_key={}", self.base_url, endpoint, separator, self.api_key);
-
-        let response = self.http_client
-            .get(&url)
-            .send()
-            .await?;
+        let mut attempt = 0;
+        loop {
+            self.rate_limiter.acquire().await;
+            self.requests.fetch_add(1, Ordering::Relaxed);
+
+            let api_key = self.api_key.read().unwrap_or_else(|e| e.into_inner()).clone();
+            let mut url = format!("{}{}{}api_key={}", self.base_url, endpoint, separator, api_key);
+            if let Some(lang) = language {
+                url.push_str(&format!("&language={}", urlencoding::encode(lang)));
+            }
 
-        let status = response.status();
+            let response = self.http_client
+                .get(&url)
+                .send()
+                .await?;
+
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                self.rate_limited.fetch_add(1, Ordering::Relaxed);
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse().ok())
+                    .map_or(Duration::from_secs(1), Duration::from_secs)
+                    .min(MAX_RETRY_AFTER);
+                warn!("TMDB rate limit hit, pausing requests for {:?}", retry_after);
+                self.rate_limiter.pause(retry_after);
+                if attempt == RATE_LIMIT_RETRIES {
+                    return Err(TmdbError::RateLimitExceeded);
+                }
+                attempt += 1;
+                continue;
+            }
+            if !status.is_success() {
+                return Err(TmdbError::ApiError(status.as_u16()));
+            }
 
-        if !status.is_success() {
-            return Err(TmdbError::ApiError(status.as_u16()));
+            return Ok(response.bytes().await?);
         }
-
-        let data = response.json().await?;
-        Ok(data)
     }
 
     /// Searches with multiple strategies
@@ -227,6 +421,7 @@ impl TmdbSearcher for TmdbClient {
                     media_type: "movie".to_string(),
                     confidence: ConfidenceScore::default(),
                     strategy: MatchStrategy::FilenameOnly,
+                    origin_country: m.origin_country,
                 })
             })
             .collect();
@@ -253,6 +448,7 @@ impl TmdbSearcher for TmdbClient {
                     media_type: "tv".to_string(),
                     confidence: ConfidenceScore::default(),
                     strategy: MatchStrategy::FilenameOnly,
+                    origin_country: m.origin_country,
                 })
             })
             .collect();
@@ -265,13 +461,13 @@ impl TmdbSearcher for TmdbClient {
 impl TmdbFetcher for TmdbClient {
     async fn fetch_movie_details(&self, id: i64) -> Result<Option<MovieDetail>, TmdbError> {
         // Check cache first
-        let cache_key = format!("movie:{}", id);
+        let cache_key = Self::localized_cache_key(format!("movie:{}", id), self.language());
         if let Some(cached) = self.cache.get(&cache_key).await? {
             return Ok(serde_json::from_str(&cached)?);
         }
 
         let endpoint = format!("/movie/{}", id);
-        let response: Option<MovieDetail> = self.make_request(&endpoint).await?;
+        let response: Option<MovieDetail> = self.make_request_in(&endpoint, self.language()).await?;
 
         // Cache result
         if let Some(ref detail) = response {
@@ -284,13 +480,13 @@ impl TmdbFetcher for TmdbClient {
 
     async fn fetch_tv_details(&self, id: i64) -> Result<Option<TvDetail>, TmdbError> {
         // Check cache first
-        let cache_key = format!("tv:{}", id);
+        let cache_key = Self::localized_cache_key(format!("tv:{}", id), self.language());
         if let Some(cached) = self.cache.get(&cache_key).await? {
             return Ok(serde_json::from_str(&cached)?);
         }
 
         let endpoint = format!("/tv/{}", id);
-        let response: Option<TvDetail> = self.make_request(&endpoint).await?;
+        let response: Option<TvDetail> = self.make_request_in(&endpoint, self.language()).await?;
 
         // Cache result
         if let Some(ref detail) = response {
@@ -303,13 +499,13 @@ impl TmdbFetcher for TmdbClient {
 
     async fn fetch_season(&self, tv_id: i64, season_number: i32) -> Result<Option<SeasonDetail>, TmdbError> {
         // Check cache first
-        let cache_key = format!("season:{}:{}", tv_id, season_number);
+        let cache_key = Self::localized_cache_key(format!("season:{}:{}", tv_id, season_number), self.language());
         if let Some(cached) = self.cache.get(&cache_key).await? {
             return Ok(serde_json::from_str(&cached)?);
         }
 
         let endpoint = format!("/tv/{}/season/{}", tv_id, season_number);
-        let response: Option<SeasonDetail> = self.make_request(&endpoint).await?;
+        let response: Option<SeasonDetail> = self.make_request_in(&endpoint, self.language()).await?;
 
         // Cache result
         if let Some(ref detail) = response {
@@ -327,13 +523,13 @@ impl TmdbFetcher for TmdbClient {
         episode: i32,
     ) -> Result<Option<EpisodeDetail>, TmdbError> {
         // Check cache first
-        let cache_key = format!("episode:{}:{}:{}", tv_id, season, episode);
+        let cache_key = Self::localized_cache_key(format!("episode:{}:{}:{}", tv_id, season, episode), self.language());
         if let Some(cached) = self.cache.get(&cache_key).await? {
             return Ok(serde_json::from_str(&cached)?);
         }
 
         let endpoint = format!("/tv/{}/season/{}/episode/{}", tv_id, season, episode);
-        let response: Option<EpisodeDetail> = self.make_request(&endpoint).await?;
+        let response: Option<EpisodeDetail> = self.make_request_in(&endpoint, self.language()).await?;
 
         // Cache result
         if let Some(ref detail) = response {
@@ -382,6 +578,43 @@ impl TmdbFetcher for TmdbClient {
     }
 }
 
+#[async_trait]
+impl TmdbLocalizedFetcher for TmdbClient {
+    async fn fetch_localized(
+        &self,
+        tmdb_id: i64,
+        media_type: &str,
+        season: Option<i32>,
+        episode: Option<i32>,
+        language: &str,
+    ) -> Result<Option<LocalizedText>, TmdbError> {
+        let text = match (media_type, season, episode) {
+            ("tv", Some(season), Some(episode)) => {
+                let endpoint = format!("/tv/{}/season/{}/episode/{}", tmdb_id, season, episode);
+                let detail: Option<EpisodeDetail> = self.make_request_in(&endpoint, Some(language)).await?;
+                detail.map(|d| (d.name, d.overview, None))
+            }
+            ("tv", _, _) => {
+                let endpoint = format!("/tv/{}", tmdb_id);
+                let detail: Option<TvDetail> = self.make_request_in(&endpoint, Some(language)).await?;
+                detail.map(|d| (d.name, d.overview, d.original_name))
+            }
+            _ => {
+                let endpoint = format!("/movie/{}", tmdb_id);
+                let detail: Option<MovieDetail> = self.make_request_in(&endpoint, Some(language)).await?;
+                detail.map(|d| (d.title, d.overview, d.original_title))
+            }
+        };
+
+        Ok(text.map(|(title, overview, original_title)| LocalizedText {
+            language: language.to_string(),
+            title,
+            overview: if overview.is_empty() { None } else { Some(overview) },
+            original_title: original_title.filter(|t| !t.is_empty()),
+        }))
+    }
+}
+
 #[async_trait]
 impl TmdbResolver for TmdbClient {
     async fn find_by_external_id(&self, id: &str, source: &str) -> Result<Option<TmdbMatch>, TmdbError> {
@@ -403,6 +636,7 @@ impl TmdbResolver for TmdbClient {
                 media_type: "movie".to_string(),
                 confidence: ConfidenceScore::new(0.95).unwrap(), // High confidence for IMDB ID
                 strategy: MatchStrategy::ImdbId,
+                origin_country: Vec::new(),
             })
             .next();
 
@@ -464,7 +698,8 @@ impl TmdbContentRatingFetcher for TmdbClient {
 
                 Ok(ContentRatingInfo::default())
             }
-            Err(_) => Ok(ContentRatingInfo::default()),
+            Err(TmdbError::ApiError(404)) => Ok(ContentRatingInfo::default()),
+            Err(e) => Err(e),
         }
     }
 
@@ -500,7 +735,8 @@ impl TmdbContentRatingFetcher for TmdbClient {
 
                 Ok(ContentRatingInfo::default())
             }
-            Err(_) => Ok(ContentRatingInfo::default()),
+            Err(TmdbError::ApiError(404)) => Ok(ContentRatingInfo::default()),
+            Err(e) => Err(e),
         }
     }
 }
@@ -533,6 +769,216 @@ impl TmdbSimilarFetcher for TmdbClient {
     }
 }
 
+#[async_trait]
+impl TmdbVideoFetcher for TmdbClient {
+    async fn fetch_videos(&self, tmdb_id: i64, media_type: &str) -> Result<Vec<VideoInfo>, TmdbError> {
+        let endpoint_type = if media_type == "tv" { "tv" } else { "movie" };
+
+        // Check cache first
+        let cache_key = Self::localized_cache_key(
+            format!("videos:{}:{}", endpoint_type, tmdb_id),
+            self.language(),
+        );
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        // With a configured language, TMDB only returns videos in that language
+        // unless fallbacks are requested explicitly
+        let endpoint = match self.language() {
+            Some(lang) => {
+                let primary = lang.split('-').next().unwrap_or(lang);
+                format!("/{}/{}/videos?include_video_language={},en,null", endpoint_type, tmdb_id, primary)
+            }
+            None => format!("/{}/{}/videos", endpoint_type, tmdb_id),
+        };
+        let response: TmdbVideosResponse = self.make_request_in(&endpoint, self.language()).await?;
+
+        let videos: Vec<VideoInfo> = response.results.into_iter().map(|v| VideoInfo {
+            key: v.key,
+            name: v.name,
+            site: v.site,
+            video_type: v.video_type,
+            official: v.official.unwrap_or(false),
+            language: v.iso_639_1,
+            published_at: v.published_at,
+        }).collect();
+
+        // Cache result
+        let cached_value = serde_json::to_string(&videos)?;
+        self.cache.set(&cache_key, &cached_value, 86400 * 7).await?; // 7 days TTL
+
+        Ok(videos)
+    }
+}
+
+#[async_trait]
+impl TmdbAlternativeTitlesFetcher for TmdbClient {
+    async fn fetch_alternative_titles(&self, tmdb_id: i64, media_type: &str) -> Result<Vec<AlternativeTitle>, TmdbError> {
+        let endpoint_type = if media_type == "tv" { "tv" } else { "movie" };
+
+        // Check cache first (alternative titles do not depend on the language)
+        let cache_key = format!("alternative_titles:{}:{}", endpoint_type, tmdb_id);
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let endpoint = format!("/{}/{}/alternative_titles", endpoint_type, tmdb_id);
+        let response: TmdbAlternativeTitlesResponse = match self.make_request(&endpoint).await {
+            Ok(body) => body,
+            Err(TmdbError::ApiError(404)) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let titles: Vec<AlternativeTitle> = response.into_titles();
+
+        // Cache result
+        let cached_value = serde_json::to_string(&titles)?;
+        self.cache.set(&cache_key, &cached_value, 86400 * 7).await?; // 7 days TTL
+
+        Ok(titles)
+    }
+}
+
+#[async_trait]
+impl TmdbChangesFetcher for TmdbClient {
+    async fn fetch_changed_ids(
+        &self,
+        media_type: &str,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Result<Vec<i64>, TmdbError> {
+        // TMDB rejects ranges longer than 14 days
+        let start_date = start_date.max(end_date - chrono::Duration::days(14));
+
+        let mut ids = Vec::new();
+        let mut page = 1;
+        loop {
+            let endpoint = format!(
+                "/{}/changes?start_date={}&end_date={}&page={}",
+                media_type,
+                start_date.format("%Y-%m-%d"),
+                end_date.format("%Y-%m-%d"),
+                page
+            );
+            let response: TmdbChangesResponse = self.make_request(&endpoint).await?;
+            ids.extend(
+                response.results.into_iter()
+                    .filter(|r| !r.adult.unwrap_or(false))
+                    .map(|r| r.id),
+            );
+
+            if page >= response.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+        debug!("TMDB reports {} changed {} items since {}", ids.len(), media_type, start_date);
+        Ok(ids)
+    }
+
+    async fn invalidate_cached(&self, tmdb_id: i64, media_type: &str) -> Result<(), TmdbError> {
+        let mut prefixes = vec![
+            format!("{}:{}", media_type, tmdb_id),
+            format!("videos:{}:{}", media_type, tmdb_id),
+        ];
+        if media_type == "tv" {
+            prefixes.push(format!("season:{}", tmdb_id));
+            prefixes.push(format!("episode:{}", tmdb_id));
+        }
+
+        for prefix in prefixes {
+            // find_keys matches substrings, so keep only this item's keys
+            // (e.g. "movie:12" must not drop "movie:123")
+            let keys: Vec<String> = self.cache.find_keys(&prefix).await?
+                .into_iter()
+                .filter(|k| *k == prefix || k.starts_with(&format!("{}:", prefix)))
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+            let key_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+            self.cache.delete_many(&key_refs).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TmdbExternalIdsFetcher for TmdbClient {
+    async fn fetch_tvdb_id(&self, tmdb_id: i64) -> Result<Option<i64>, TmdbError> {
+        // Check cache first
+        let cache_key = format!("tv_external_ids:{}", tmdb_id);
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let endpoint = format!("/tv/{}/external_ids", tmdb_id);
+        let tvdb_id = match self.make_request::<TmdbExternalIdsResponse>(&endpoint).await {
+            Ok(ids) => ids.tvdb_id,
+            Err(TmdbError::ApiError(404)) => None,
+            Err(e) => return Err(e),
+        };
+
+        // Cache result (IDs practically never change)
+        let cached_value = serde_json::to_string(&tvdb_id)?;
+        self.cache.set(&cache_key, &cached_value, 86400 * 30).await?; // 30 days TTL
+
+        Ok(tvdb_id)
+    }
+}
+
+#[async_trait]
+impl TmdbPersonFetcher for TmdbClient {
+    async fn fetch_person(&self, person_id: i64) -> Result<Option<PersonDetail>, TmdbError> {
+        let endpoint = format!("/person/{}", person_id);
+        match self.make_request_in(&endpoint, self.language()).await {
+            Ok(detail) => Ok(Some(detail)),
+            Err(TmdbError::ApiError(404)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn fetch_person_credits(&self, person_id: i64) -> Result<Vec<PersonCreditInfo>, TmdbError> {
+        // Check cache first
+        let cache_key = format!("person_credits:{}", person_id);
+        if let Some(cached) = self.cache.get(&cache_key).await? {
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let endpoint = format!("/person/{}/combined_credits", person_id);
+        let response: TmdbCombinedCreditsResponse = self.make_request(&endpoint).await?;
+
+        let to_info = |c: TmdbCombinedCredit, character: Option<String>, job: Option<String>| PersonCreditInfo {
+            tmdb_id: c.id,
+            media_type: c.media_type,
+            title: c.title.or(c.name).unwrap_or_default(),
+            character,
+            job,
+        };
+        let mut credits: Vec<PersonCreditInfo> = response.cast.into_iter()
+            .map(|c| {
+                let character = c.character.clone();
+                to_info(c, character, None)
+            })
+            .collect();
+        credits.extend(response.crew.into_iter().map(|c| {
+            let job = c.job.clone();
+            to_info(c, None, job)
+        }));
+
+        // Cache result
+        let cached_value = serde_json::to_string(&credits)?;
+        self.cache.set(&cache_key, &cached_value, 86400).await?; // 24 hours TTL
+
+        Ok(credits)
+    }
+}
+
 #[async_trait]
 impl TmdbCreditsFetcher for TmdbClient {
     async fn fetch_movie_credits(&self, tmdb_id: i64) -> Result<Credits, TmdbError> {
@@ -765,31 +1211,6 @@ impl TmdbReconciler for TmdbClient {
     }
 }
 
-/// Rate limiter for TMDB API
-pub struct RateLimiter {
-    semaphore: Arc<Semaphore>,
-    permits_per_second: usize,
-}
-
-impl RateLimiter {
-    /// Creates a new rate limiter
-    ///
-    /// # Arguments
-    /// * `permits_per_second` - Number of requests allowed per second
-    pub fn new(permits_per_second: usize) -> Self {
-        Self {
-            semaphore: Arc::new(Semaphore::new(permits_per_second)),
-            permits_per_second,
-        }
-    }
-
-    /// Acquires a permit from the rate limiter
-    pub async fn acquire(&self) {
-        let _permit = self.semaphore.acquire().await;
-        tokio::time::sleep(Duration::from_millis(1000) / self.permits_per_second as u32).await;
-    }
-}
-
 // ============================================================================
 // TMDB API Response DTOs
 // ============================================================================
@@ -806,6 +1227,8 @@ struct TmdbSearchResult {
     name: Option<String>,
     release_date: Option<String>,
     first_air_date: Option<String>,
+    #[serde(default)]
+    origin_country: Vec<String>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -868,6 +1291,96 @@ struct TmdbSimilarResult {
     vote_average: Option<f32>,
 }
 
+// Videos response
+#[derive(Debug, serde::Deserialize)]
+struct TmdbVideosResponse {
+    results: Vec<TmdbVideoResult>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TmdbVideoResult {
+    key: String,
+    name: String,
+    site: String,
+    #[serde(rename = "type")]
+    video_type: String,
+    official: Option<bool>,
+    iso_639_1: Option<String>,
+    published_at: Option<String>,
+}
+
+// Alternative titles response ("titles" for movies, "results" for TV shows)
+#[derive(Debug, serde::Deserialize)]
+struct TmdbAlternativeTitlesResponse {
+    #[serde(default)]
+    titles: Vec<TmdbAlternativeTitle>,
+    #[serde(default)]
+    results: Vec<TmdbAlternativeTitle>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TmdbAlternativeTitle {
+    title: String,
+    iso_3166_1: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+impl TmdbAlternativeTitlesResponse {
+    fn into_titles(self) -> Vec<AlternativeTitle> {
+        self.titles
+            .into_iter()
+            .chain(self.results)
+            .filter(|t| !t.title.trim().is_empty())
+            .map(|t| AlternativeTitle {
+                title: t.title,
+                country: t.iso_3166_1.filter(|c| !c.is_empty()),
+                kind: t.kind.filter(|k| !k.is_empty()),
+            })
+            .collect()
+    }
+}
+
+// Change list response
+#[derive(Debug, serde::Deserialize)]
+struct TmdbChangesResponse {
+    #[serde(default)]
+    results: Vec<TmdbChangeResult>,
+    #[serde(default)]
+    total_pages: u32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TmdbChangeResult {
+    id: i64,
+    adult: Option<bool>,
+}
+
+// External IDs response
+#[derive(Debug, serde::Deserialize)]
+struct TmdbExternalIdsResponse {
+    tvdb_id: Option<i64>,
+}
+
+// Person combined credits response
+#[derive(Debug, serde::Deserialize)]
+struct TmdbCombinedCreditsResponse {
+    #[serde(default)]
+    cast: Vec<TmdbCombinedCredit>,
+    #[serde(default)]
+    crew: Vec<TmdbCombinedCredit>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TmdbCombinedCredit {
+    id: i64,
+    media_type: String,
+    title: Option<String>,
+    name: Option<String>,
+    character: Option<String>,
+    job: Option<String>,
+}
+
 // Credits response (for movies)
 #[derive(Debug, serde::Deserialize)]
 struct TmdbCreditsResponse {
@@ -927,3 +1440,31 @@ struct TmdbTvAggregateCrewMember {
 struct TmdbTvJob {
     job: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{SqliteCacheRepository, SqliteTmdbResponseRepository};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_offline_answers_from_recorded_responses() {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        initialize_schema(&pool).await.unwrap();
+        let responses = Arc::new(SqliteTmdbResponseRepository::new(pool.clone()));
+        let body = br#"{"results": [{"id": 603, "title": "The Matrix", "release_date": "1999-03-30"}]}"#;
+        responses
+            .save("/search/movie?query=the%20matrix&year=1999#", &RecordedResponse { status: 200, body: body.to_vec() })
+            .await
+            .unwrap();
+        responses.save("/movie/1#", &RecordedResponse { status: 404, body: Vec::new() }).await.unwrap();
+
+        let client = TmdbClient::offline(Arc::new(SqliteCacheRepository::new(pool)), responses).unwrap();
+        let matches = client.search_movie("the matrix", Some(1999)).await.unwrap();
+        assert_eq!((matches[0].tmdb_id, matches[0].year), (603, Some(1999)));
+        assert!(matches!(client.fetch_movie_details(1).await, Err(TmdbError::ApiError(404))));
+        assert!(matches!(client.search_movie("the matrix", None).await, Err(TmdbError::Offline(_))));
+        assert_eq!(client.request_stats().requests, 0);
+    }
+}
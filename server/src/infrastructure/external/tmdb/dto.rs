@@ -68,6 +68,8 @@ pub struct TmdbTvResult {
 pub struct TmdbMovieDetailResponse {
     pub id: i64,
     pub title: String,
+    #[serde(default)]
+    pub original_title: Option<String>,
     pub overview: String,
     pub release_date: String,
     pub poster_path: Option<String>,
@@ -85,6 +87,8 @@ pub struct TmdbMovieDetailResponse {
 pub struct TmdbTvDetailResponse {
     pub id: i64,
     pub name: String,
+    #[serde(default)]
+    pub original_name: Option<String>,
     pub overview: String,
     pub first_air_date: String,
     pub last_air_date: Option<String>,
@@ -0,0 +1,224 @@
+//! TMDB Request Limiting
+//!
+//! [`TokenBucket`] spaces requests out to stay under TMDB's rate limit while
+//! allowing bursts of up to one second's worth; when TMDB answers 429 anyway
+//! the bucket is paused for as long as TMDB asks. [`RequestCoalescer`]
+//! lets concurrent identical requests (parallel scan workers looking up the
+//! same title) share a single one.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tokio::sync::OnceCell;
+
+/// Token bucket rate limiter
+pub struct TokenBucket {
+    /// Tokens added per second
+    rate: f64,
+    /// Most tokens held (the burst size)
+    capacity: f64,
+    state: Mutex<BucketState>,
+    throttled: AtomicU64,
+    throttled_wait_ms: AtomicU64,
+}
+
+struct BucketState {
+    /// Negative when requests are queued for tokens not yet added
+    tokens: f64,
+    /// When `tokens` was last refilled; in the future while paused
+    updated: Instant,
+}
+
+/// Rate limiter activity
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LimiterStats {
+    pub requests_per_second: f64,
+    /// Tokens available for immediate requests
+    pub available_tokens: f64,
+    /// Requests that had to wait for a token
+    pub throttled: u64,
+    /// Total time requests waited for tokens
+    pub throttled_wait_ms: u64,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket allowing `requests_per_second` on average
+    pub fn new(requests_per_second: u32) -> Self {
+        let rate = requests_per_second.max(1) as f64;
+        Self {
+            rate,
+            capacity: rate,
+            state: Mutex::new(BucketState { tokens: rate, updated: Instant::now() }),
+            throttled: AtomicU64::new(0),
+            throttled_wait_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Waits for a token
+    pub async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            self.throttled.fetch_add(1, Ordering::Relaxed);
+            self.throttled_wait_ms.fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes a token, returning how long to wait before using it
+    fn reserve(&self) -> Duration {
+        let mut state = self.lock();
+        let now = Instant::now();
+        state.refill(now, self.rate, self.capacity);
+        state.tokens -= 1.0;
+
+        let paused = state.updated.saturating_duration_since(now);
+        let queued = if state.tokens < 0.0 { Duration::from_secs_f64(-state.tokens / self.rate) } else { Duration::ZERO };
+        paused + queued
+    }
+
+    /// Stops handing out tokens for a while (TMDB said to slow down)
+    pub fn pause(&self, duration: Duration) {
+        let mut state = self.lock();
+        let now = Instant::now();
+        state.refill(now, self.rate, self.capacity);
+        state.tokens = state.tokens.min(0.0);
+        state.updated = state.updated.max(now + duration);
+    }
+
+    pub fn stats(&self) -> LimiterStats {
+        let available_tokens = {
+            let mut state = self.lock();
+            state.refill(Instant::now(), self.rate, self.capacity);
+            state.tokens.max(0.0)
+        };
+        LimiterStats {
+            requests_per_second: self.rate,
+            available_tokens,
+            throttled: self.throttled.load(Ordering::Relaxed),
+            throttled_wait_ms: self.throttled_wait_ms.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BucketState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl BucketState {
+    fn refill(&mut self, now: Instant, rate: f64, capacity: f64) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(capacity);
+        self.updated = self.updated.max(now);
+    }
+}
+
+/// Shares the result of a request with identical requests made while it
+/// is in flight
+pub struct RequestCoalescer<V> {
+    in_flight: Mutex<HashMap<String, Arc<OnceCell<V>>>>,
+    coalesced: AtomicU64,
+}
+
+impl<V: Clone> RequestCoalescer<V> {
+    pub fn new() -> Self {
+        Self { in_flight: Mutex::new(HashMap::new()), coalesced: AtomicU64::new(0) }
+    }
+
+    /// Runs `request`, or waits for the identical one in flight
+    ///
+    /// If the caller running the request goes away, one of the waiting
+    /// callers runs its own.
+    pub async fn run<F, Fut>(&self, key: &str, request: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let cell = {
+            let mut in_flight = self.lock();
+            match in_flight.get(key) {
+                Some(cell) => {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    cell.clone()
+                }
+                None => {
+                    let cell = Arc::new(OnceCell::new());
+                    in_flight.insert(key.to_string(), cell.clone());
+                    cell
+                }
+            }
+        };
+
+        let value = cell.get_or_init(request).await.clone();
+
+        let mut in_flight = self.lock();
+        if in_flight.get(key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            in_flight.remove(key);
+        }
+        value
+    }
+
+    /// Requests answered by one already in flight
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceCell<V>>>> {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<V: Clone> Default for RequestCoalescer<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_token_bucket() {
+        let bucket = TokenBucket::new(10);
+        // A second's worth goes out at once, then requests are spaced
+        for _ in 0..10 {
+            assert!(bucket.reserve() < Duration::from_millis(5));
+        }
+        let wait = bucket.reserve();
+        assert!(wait > Duration::from_millis(80) && wait <= Duration::from_millis(100));
+        let wait = bucket.reserve();
+        assert!(wait > Duration::from_millis(180) && wait <= Duration::from_millis(200));
+
+        bucket.pause(Duration::from_secs(2));
+        assert!(bucket.reserve() > Duration::from_secs(2));
+        assert_eq!(bucket.stats().available_tokens, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_coalescing() {
+        let coalescer: RequestCoalescer<Result<u32, String>> = RequestCoalescer::new();
+        let requests = AtomicUsize::new(0);
+        let request = || async {
+            requests.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(7)
+        };
+
+        let (a, b, c) = tokio::join!(
+            coalescer.run("/search/movie?query=alien", request),
+            coalescer.run("/search/movie?query=alien", request),
+            coalescer.run("/search/movie?query=aliens", request),
+        );
+        assert_eq!((a, b, c), (Ok(7), Ok(7), Ok(7)));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert_eq!(coalescer.coalesced(), 1);
+
+        // Finished requests are not reused
+        coalescer.run("/search/movie?query=alien", request).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
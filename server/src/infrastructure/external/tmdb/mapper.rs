@@ -23,6 +23,7 @@ pub fn map_movie_detail(dto: TmdbMovieDetailResponse) -> MovieDetail {
     MovieDetail {
         id: dto.id,
         title: dto.title,
+        original_title: dto.original_title,
         overview: dto.overview,
         release_date: dto.release_date,
         poster_path: dto.poster_path,
@@ -46,6 +47,7 @@ pub fn map_tv_detail(dto: TmdbTvDetailResponse) -> TvDetail {
     TvDetail {
         id: dto.id,
         name: dto.name,
+        original_name: dto.original_name,
         overview: dto.overview,
                     first_air_date: dto.first_air_date,
                     last_air_date: dto.last_air_date,
@@ -6,8 +6,9 @@ pub mod client;
 pub mod batch_client;
 pub mod dto;
 pub mod mapper;
+pub mod limiter;
 
-pub use client::TmdbClient;
+pub use client::{TmdbClient, TmdbRequestStats};
 pub use batch_client::{
     BatchTmdbClient, BatchSearchRequest, BatchSearchResult,
     BatchFetchRequest, BatchFetchResult, TmdbDetail,
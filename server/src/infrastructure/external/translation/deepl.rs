@@ -0,0 +1,186 @@
+//! DeepL API client
+//!
+//! Keys of the free plan end in `:fx` and use the api-free host.
+
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use super::translator::{translate_in_batches, SubtitleTranslator};
+use super::throttle::RequestThrottle;
+use crate::infrastructure::external::whisper::TranscriptionSegment;
+use crate::shared::error::TranslationError;
+
+const FREE_API_URL: &str = "https://api-free.deepl.com";
+const PRO_API_URL: &str = "https://api.deepl.com";
+
+/// DeepL accepts up to 50 texts per request
+const BATCH_SIZE: usize = 50;
+
+#[derive(Debug, Serialize)]
+struct DeepLRequest<'a> {
+    text: Vec<String>,
+    source_lang: String,
+    target_lang: String,
+    preserve_formatting: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    formality: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLTranslation {
+    text: String,
+}
+
+/// DeepL translation client
+pub struct DeepLClient {
+    api_key: String,
+    base_url: String,
+    http_client: reqwest::Client,
+    throttle: Option<RequestThrottle>,
+    /// "more", "less", "prefer_more" or "prefer_less"
+    formality: Option<String>,
+}
+
+impl DeepLClient {
+    /// Creates a client for the plan of `api_key`
+    pub fn new(api_key: &str) -> Self {
+        let base_url = if api_key.ends_with(":fx") { FREE_API_URL } else { PRO_API_URL };
+        Self {
+            api_key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .expect("Failed to create HTTP client"),
+            throttle: None,
+            formality: None,
+        }
+    }
+
+    /// Uses another API host (e.g. a proxy)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sends at most `requests` requests per minute
+    pub fn with_rate_limit(mut self, requests: u32) -> Self {
+        self.throttle = Some(RequestThrottle::per_minute(requests));
+        self
+    }
+
+    /// Sets the formality of translations into languages that have one
+    /// (e.g. "prefer_less" for casual dialogue)
+    pub fn with_formality(mut self, formality: &str) -> Self {
+        self.formality = Some(formality.to_string());
+        self
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, TranslationError> {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait().await;
+        }
+
+        let request = DeepLRequest {
+            text: texts,
+            source_lang: source_code(source_language),
+            target_lang: target_code(target_language),
+            preserve_formatting: true,
+            formality: self.formality.as_deref(),
+        };
+        let response = self.http_client
+            .post(format!("{}/v2/translate", self.base_url))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| TranslationError::HttpError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            // 429: too many requests, 456: character quota used up
+            return Err(TranslationError::ServiceUnavailable(
+                format!("DeepL returned {}: {}", status, error_text)
+            ));
+        }
+
+        let response: DeepLResponse = response
+            .json()
+            .await
+            .map_err(|e| TranslationError::ParseError(e.to_string()))?;
+        Ok(response.translations.into_iter().map(|t| t.text).collect())
+    }
+}
+
+#[async_trait]
+impl SubtitleTranslator for DeepLClient {
+    fn name(&self) -> &'static str {
+        "deepl"
+    }
+
+    async fn is_available(&self) -> bool {
+        self.http_client
+            .get(format!("{}/v2/usage", self.base_url))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn translate_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
+        translate_in_batches(segments, BATCH_SIZE, |texts| {
+            self.translate_batch(texts, source_language, target_language)
+        })
+        .await
+    }
+}
+
+/// DeepL source language ("EN")
+fn source_code(language: &str) -> String {
+    language.split(['-', '_']).next().unwrap_or(language).to_uppercase()
+}
+
+/// DeepL target language; English and Portuguese need a variant
+fn target_code(language: &str) -> String {
+    match source_code(language).as_str() {
+        "EN" if !language.contains(['-', '_']) => "EN-US".to_string(),
+        "PT" if !language.contains(['-', '_']) => "PT-PT".to_string(),
+        _ => language.replace('_', "-").to_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_codes() {
+        assert_eq!(source_code("en"), "EN");
+        assert_eq!(source_code("pt-BR"), "PT");
+        assert_eq!(target_code("hu"), "HU");
+        assert_eq!(target_code("en"), "EN-US");
+        assert_eq!(target_code("en_gb"), "EN-GB");
+        assert_eq!(target_code("pt"), "PT-PT");
+
+        assert_eq!(DeepLClient::new("key:fx").base_url, FREE_API_URL);
+        assert_eq!(DeepLClient::new("key").base_url, PRO_API_URL);
+    }
+}
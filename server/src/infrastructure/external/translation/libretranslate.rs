@@ -0,0 +1,138 @@
+//! LibreTranslate API client
+//!
+//! Works with self-hosted instances (no key) and with hosted ones that
+//! require an API key.
+
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use super::translator::{translate_in_batches, SubtitleTranslator};
+use super::throttle::RequestThrottle;
+use crate::infrastructure::external::whisper::TranscriptionSegment;
+use crate::shared::error::TranslationError;
+
+const BATCH_SIZE: usize = 25;
+
+#[derive(Debug, Serialize)]
+struct LibreTranslateRequest<'a> {
+    q: Vec<String>,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: Vec<String>,
+}
+
+/// LibreTranslate translation client
+pub struct LibreTranslateClient {
+    base_url: String,
+    api_key: Option<String>,
+    http_client: reqwest::Client,
+    throttle: Option<RequestThrottle>,
+}
+
+impl LibreTranslateClient {
+    /// Creates a client for the instance at `base_url`
+    /// (e.g. "http://localhost:5000")
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(120))
+                .build()
+                .expect("Failed to create HTTP client"),
+            throttle: None,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Sends at most `requests` requests per minute
+    pub fn with_rate_limit(mut self, requests: u32) -> Self {
+        self.throttle = Some(RequestThrottle::per_minute(requests));
+        self
+    }
+
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<String>, TranslationError> {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait().await;
+        }
+
+        let request = LibreTranslateRequest {
+            q: texts,
+            source: base_language(source_language),
+            target: base_language(target_language),
+            format: "text",
+            api_key: self.api_key.as_deref(),
+        };
+        let response = self.http_client
+            .post(format!("{}/translate", self.base_url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| TranslationError::HttpError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(TranslationError::ServiceUnavailable(
+                format!("LibreTranslate returned {}: {}", status, error_text)
+            ));
+        }
+
+        let response: LibreTranslateResponse = response
+            .json()
+            .await
+            .map_err(|e| TranslationError::ParseError(e.to_string()))?;
+        Ok(response.translated_text)
+    }
+}
+
+#[async_trait]
+impl SubtitleTranslator for LibreTranslateClient {
+    fn name(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    async fn is_available(&self) -> bool {
+        self.http_client
+            .get(format!("{}/languages", self.base_url))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn translate_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
+        translate_in_batches(segments, BATCH_SIZE, |texts| {
+            self.translate_batch(texts, source_language, target_language)
+        })
+        .await
+    }
+}
+
+/// LibreTranslate takes plain language codes ("en", not "en-US")
+fn base_language(language: &str) -> &str {
+    language.split(['-', '_']).next().unwrap_or(language)
+}
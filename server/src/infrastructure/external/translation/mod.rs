@@ -0,0 +1,16 @@
+//! Subtitle translation providers
+//!
+//! [`SubtitleTranslator`] is implemented by the Ollama client (local LLM),
+//! DeepL and LibreTranslate. [`FallbackTranslator`] chains providers, so a
+//! slow or exhausted provider hands over to the next one, and each HTTP
+//! provider can be throttled with a [`RequestThrottle`].
+
+mod translator;
+mod throttle;
+mod deepl;
+mod libretranslate;
+
+pub use translator::*;
+pub use throttle::RequestThrottle;
+pub use deepl::DeepLClient;
+pub use libretranslate::LibreTranslateClient;
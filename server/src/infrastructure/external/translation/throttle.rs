@@ -0,0 +1,33 @@
+//! Request throttling for translation APIs
+
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Spaces out requests to a provider, e.g. to stay within the limits of a
+/// free API plan
+pub struct RequestThrottle {
+    interval: Duration,
+    /// When the next request may be sent
+    next: Mutex<Instant>,
+}
+
+impl RequestThrottle {
+    /// Allows `requests` requests per minute (at least one)
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / requests.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next request may be sent
+    pub async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            tokio::time::sleep_until(*next).await;
+        }
+        *next = (*next).max(now) + self.interval;
+    }
+}
@@ -0,0 +1,182 @@
+//! SubtitleTranslator trait and provider fallback
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::warn;
+use crate::infrastructure::external::whisper::TranscriptionSegment;
+use crate::shared::error::TranslationError;
+
+/// Translates subtitle segments between languages
+#[async_trait]
+pub trait SubtitleTranslator: Send + Sync {
+    /// Short name for logs and capabilities ("ollama", "deepl", ...)
+    fn name(&self) -> &'static str;
+
+    /// Names of the providers behind this translator, in the order they
+    /// are tried
+    fn providers(&self) -> Vec<&'static str> {
+        vec![self.name()]
+    }
+
+    /// Whether the provider is reachable
+    async fn is_available(&self) -> bool;
+
+    /// Translates segments, keeping their timing
+    ///
+    /// Languages are ISO 639-1 codes ("en", "hu").
+    async fn translate_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<TranscriptionSegment>, TranslationError>;
+
+    /// Frees GPU memory before Whisper runs (local models only)
+    async fn release(&self) -> Result<(), TranslationError> {
+        Ok(())
+    }
+}
+
+/// Tries providers in order until one translates the subtitle
+pub struct FallbackTranslator {
+    providers: Vec<Arc<dyn SubtitleTranslator>>,
+}
+
+impl FallbackTranslator {
+    pub fn new(providers: Vec<Arc<dyn SubtitleTranslator>>) -> Self {
+        Self { providers }
+    }
+}
+
+#[async_trait]
+impl SubtitleTranslator for FallbackTranslator {
+    fn name(&self) -> &'static str {
+        "fallback"
+    }
+
+    fn providers(&self) -> Vec<&'static str> {
+        self.providers.iter().flat_map(|p| p.providers()).collect()
+    }
+
+    async fn is_available(&self) -> bool {
+        for provider in &self.providers {
+            if provider.is_available().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn translate_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
+        let mut last_error = TranslationError::ServiceUnavailable("No translation provider configured".to_string());
+        for provider in &self.providers {
+            match provider.translate_segments(segments.clone(), source_language, target_language).await {
+                Ok(translated) => return Ok(translated),
+                Err(e) => {
+                    warn!("Translation with {} failed, trying the next provider: {}", provider.name(), e);
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn release(&self) -> Result<(), TranslationError> {
+        for provider in &self.providers {
+            provider.release().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Translates the texts of `segments` in batches with `translate`, which
+/// returns one translation per text
+///
+/// Empty texts are not sent.
+pub(super) async fn translate_in_batches<F, Fut>(
+    segments: Vec<TranscriptionSegment>,
+    batch_size: usize,
+    mut translate: F,
+) -> Result<Vec<TranscriptionSegment>, TranslationError>
+where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<String>, TranslationError>>,
+{
+    let pending: Vec<usize> = (0..segments.len()).filter(|&i| !segments[i].text.trim().is_empty()).collect();
+    let mut segments = segments;
+    for batch in pending.chunks(batch_size.max(1)) {
+        let texts: Vec<String> = batch.iter().map(|&i| segments[i].text.clone()).collect();
+        let translated = translate(texts).await?;
+        if translated.len() != batch.len() {
+            return Err(TranslationError::ParseError(format!(
+                "expected {} translations, got {}",
+                batch.len(),
+                translated.len()
+            )));
+        }
+        for (&i, text) in batch.iter().zip(translated) {
+            segments[i].text = text;
+        }
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Provider(&'static str, bool);
+
+    #[async_trait]
+    impl SubtitleTranslator for Provider {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        async fn is_available(&self) -> bool {
+            self.1
+        }
+
+        async fn translate_segments(
+            &self,
+            segments: Vec<TranscriptionSegment>,
+            _source_language: &str,
+            target_language: &str,
+        ) -> Result<Vec<TranscriptionSegment>, TranslationError> {
+            if !self.1 {
+                return Err(TranslationError::ServiceUnavailable(self.0.to_string()));
+            }
+            translate_in_batches(segments, 1, |texts| async move {
+                Ok(texts.iter().map(|t| format!("{}:{}", target_language, t)).collect())
+            })
+            .await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_translator() {
+        let segment = |text: &str| TranscriptionSegment { start_time: 1.0, end_time: 2.0, text: text.to_string() };
+        let translator = FallbackTranslator::new(vec![
+            Arc::new(Provider("ollama", false)),
+            Arc::new(Provider("deepl", true)),
+        ]);
+        assert_eq!(translator.providers(), vec!["ollama", "deepl"]);
+        assert!(translator.is_available().await);
+
+        let translated = translator
+            .translate_segments(vec![segment("Hello"), segment(" "), segment("Bye")], "en", "hu")
+            .await
+            .unwrap();
+        let texts: Vec<&str> = translated.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["hu:Hello", " ", "hu:Bye"]);
+        assert_eq!(translated[2].start_time, 1.0);
+
+        let unavailable = FallbackTranslator::new(vec![Arc::new(Provider("ollama", false))]);
+        assert!(unavailable.translate_segments(vec![segment("Hello")], "en", "hu").await.is_err());
+    }
+}
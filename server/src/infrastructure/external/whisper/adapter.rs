@@ -1,13 +1,24 @@
-//! WhisperAdapter - Speech-to-text using whisper.cpp CLI
+//! WhisperAdapter - Speech-to-text using whisper.cpp
 //!
-//! Uses whisper.cpp's whisper-cli tool to transcribe audio from video files.
-//! Outputs SRT format subtitles with accurate timestamps.
-
-use std::path::PathBuf;
+//! Transcribes audio from video files with a [`WhisperBackend`]: the
+//! whisper-cli tool by default, or whisper.cpp in-process (see
+//! [`super::native`]). Outputs SRT format subtitles with accurate
+//! timestamps. Silent parts of the audio are cut out before transcription
+//! (see [`super::vad`]).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::time::timeout;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use super::backend::{RawTranscription, TranscriptionProgress, WhisperBackend};
+use super::models::WhisperModelManager;
+use super::vad::{
+    condense_regions, condense_speech, silencedetect_filter, silencedetect_regions, wav_duration,
+    SpeechMap, SpeechRegion, VadConfig, VadDetector,
+};
 use crate::shared::error::SpeechToTextError;
 
 /// Transcription segment with timestamps
@@ -34,17 +45,29 @@ pub struct TranscriptionResult {
     pub srt_content: String,
 }
 
+/// Whisper model a transcription was made with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhisperModelInfo {
+    /// Model file name (e.g., ggml-small.bin)
+    pub name: String,
+    /// Size and modification time of the model file; changes when the
+    /// model is replaced
+    pub version: String,
+}
+
 /// Whisper.cpp adapter for speech-to-text
 ///
-/// Uses the whisper-cli binary to transcribe audio from video files.
-/// Follows the same CLI wrapper pattern as FFprobeAdapter and FpcalcAdapter.
+/// Prepares the audio of video files for the backend and cleans up the
+/// segments it returns.
 pub struct WhisperAdapter {
     /// Path to whisper model file (.bin)
     model_path: PathBuf,
-    /// Path to whisper-cli binary
-    cli_path: String,
-    /// Timeout for transcription (can be long for full movies)
-    timeout: Duration,
+    /// Runs the model (whisper-cli unless replaced)
+    backend: Arc<dyn WhisperBackend>,
+    /// Voice activity detection pre-pass (None = transcribe everything)
+    vad: Option<VadConfig>,
+    /// Per-language model selection (None = always `model_path`)
+    models: Option<Arc<WhisperModelManager>>,
 }
 
 impl WhisperAdapter {
@@ -54,35 +77,79 @@ impl WhisperAdapter {
     /// * `model_path` - Path to the whisper model file (e.g., ggml-small.bin)
     /// * `timeout` - Timeout for transcription operations
     pub fn new(model_path: PathBuf, timeout: Duration) -> Self {
-        Self {
-            model_path,
-            cli_path: "whisper-cli".to_string(),
-            timeout,
-        }
+        Self::with_cli_path(model_path, "whisper-cli".to_string(), timeout)
     }
 
     /// Creates a WhisperAdapter with custom CLI path
     pub fn with_cli_path(model_path: PathBuf, cli_path: String, timeout: Duration) -> Self {
         Self {
             model_path,
-            cli_path,
-            timeout,
+            backend: Arc::new(CliBackend::new(cli_path, timeout)),
+            vad: Some(VadConfig::default()),
+            models: None,
+        }
+    }
+
+    /// Runs the model with another backend (e.g. in-process whisper.cpp)
+    pub fn with_backend(mut self, backend: Arc<dyn WhisperBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Name of the backend running the model
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Picks the model per language from the managed models directory,
+    /// falling back to `model_path`
+    pub fn with_models(mut self, models: Arc<WhisperModelManager>) -> Self {
+        self.models = Some(models);
+        self
+    }
+
+    /// Model file used for `language` (None = auto-detect)
+    pub fn model_path_for(&self, language: Option<&str>) -> PathBuf {
+        match &self.models {
+            Some(models) => models.model_path(language),
+            None => self.model_path.clone(),
         }
     }
 
-    /// Checks if whisper-cli is available
+    /// Sets the voice activity detection pre-pass of [`Self::transcribe`]
+    /// (enabled by default)
+    pub fn with_vad(mut self, vad: Option<VadConfig>) -> Self {
+        self.vad = vad;
+        self
+    }
+
+    /// Checks if the backend can run
     pub async fn is_available(&self) -> bool {
-        Command::new(&self.cli_path)
-            .arg("--version")
-            .output()
-            .await
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        self.backend.is_available().await
     }
 
-    /// Checks if the model file exists
+    /// Checks if the model file for auto-detected languages exists
     pub fn model_exists(&self) -> bool {
-        self.model_path.exists()
+        self.model_path_for(None).exists()
+    }
+
+    /// Identifies the model used for `language`
+    pub fn model_info(&self, language: Option<&str>) -> WhisperModelInfo {
+        let model_path = self.model_path_for(language);
+        let name = model_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let version = std::fs::metadata(&model_path)
+            .map(|m| {
+                let modified = m.modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs());
+                format!("{}:{}", m.len(), modified)
+            })
+            .unwrap_or_default();
+        WhisperModelInfo { name, version }
     }
 
     /// Transcribes audio from a video file
@@ -99,19 +166,102 @@ impl WhisperAdapter {
         video_path: &str,
         audio_track_index: usize,
         language: Option<&str>,
+    ) -> Result<TranscriptionResult, SpeechToTextError> {
+        self.transcribe_with(video_path, audio_track_index, language, self.vad.as_ref(), None).await
+    }
+
+    /// Transcribes audio from a video file with the given VAD pre-pass
+    /// (None = transcribe everything), reporting the progress of the model
+    /// run (backends that cannot report it never call `progress`)
+    pub async fn transcribe_with(
+        &self,
+        video_path: &str,
+        audio_track_index: usize,
+        language: Option<&str>,
+        vad: Option<&VadConfig>,
+        progress: Option<TranscriptionProgress>,
     ) -> Result<TranscriptionResult, SpeechToTextError> {
         // Extract audio track to temporary WAV file (16kHz mono for Whisper)
         let temp_audio = self.extract_audio(video_path, audio_track_index).await?;
 
-        // Run whisper-cli
-        let result = self.run_whisper(&temp_audio, language).await;
+        // Cut out silence so Whisper only processes speech
+        let speech_audio = format!("{}.speech.wav", temp_audio);
+        let speech_map = match vad {
+            Some(config) => self.detect_speech(&temp_audio, &speech_audio, config).await,
+            None => None,
+        };
+
+        // Run the model
+        let model_path = self.model_path_for(language);
+        let audio = if speech_map.is_some() { &speech_audio } else { &temp_audio };
+        let result = self.backend
+            .transcribe(&model_path, audio, language, progress)
+            .await
+            .map(|raw| self.clean_up(raw, language, speech_map.as_ref()));
 
-        // Clean up temp file
+        // Clean up temp files
         let _ = tokio::fs::remove_file(&temp_audio).await;
+        let _ = tokio::fs::remove_file(&speech_audio).await;
 
         result
     }
 
+    /// Writes the speech regions of the audio to `speech_path`
+    ///
+    /// Returns `None` if the whole audio should be transcribed instead;
+    /// VAD failures are logged and never fail the transcription.
+    async fn detect_speech(&self, audio_path: &str, speech_path: &str, config: &VadConfig) -> Option<SpeechMap> {
+        let input = PathBuf::from(audio_path);
+        let output = PathBuf::from(speech_path);
+        let result = match config.detector {
+            VadDetector::Energy => {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || condense_speech(&input, &output, &config))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|r| r.map_err(|e| e.to_string()))
+            }
+            VadDetector::Silencedetect => match self.silencedetect(&input, config).await {
+                Ok(regions) => {
+                    let config = config.clone();
+                    tokio::task::spawn_blocking(move || condense_regions(&input, &output, &regions, &config))
+                        .await
+                        .map_err(|e| e.to_string())
+                        .and_then(|r| r.map_err(|e| e.to_string()))
+                }
+                Err(e) => Err(e),
+            },
+        };
+        result.unwrap_or_else(|e| {
+            tracing::warn!("VAD pre-pass failed, transcribing all audio: {}", e);
+            None
+        })
+    }
+
+    /// Finds the speech regions of a WAV file with ffmpeg's silencedetect
+    async fn silencedetect(&self, audio_path: &Path, config: &VadConfig) -> Result<Vec<SpeechRegion>, String> {
+        let total = wav_duration(audio_path).map_err(|e| e.to_string())?;
+        let output = timeout(Duration::from_secs(300), async {
+            Command::new("ffmpeg")
+                .arg("-hide_banner")
+                .arg("-nostats")
+                .arg("-i")
+                .arg(audio_path)
+                .args(["-af", &silencedetect_filter(config), "-f", "null", "-"])
+                .output()
+                .await
+        })
+        .await
+        .map_err(|_| "silencedetect timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(stderr.lines().last().unwrap_or("ffmpeg failed").to_string());
+        }
+        Ok(silencedetect_regions(&stderr, total, config))
+    }
+
     /// Extracts audio from video to a temporary WAV file
     ///
     /// Whisper requires 16kHz mono audio for best results.
@@ -152,15 +302,98 @@ impl WhisperAdapter {
         Ok(temp_path)
     }
 
-    /// Runs whisper-cli on an audio file
-    async fn run_whisper(
+    /// Cleans up the segments of the model
+    ///
+    /// With a `speech_map` the audio was the condensed speech, and segment
+    /// times are moved back to the original timeline.
+    fn clean_up(
+        &self,
+        raw: RawTranscription,
+        language: Option<&str>,
+        speech_map: Option<&SpeechMap>,
+    ) -> TranscriptionResult {
+        let mut segments = raw.segments;
+        if let Some(map) = speech_map {
+            segments = map.restore_segments(segments);
+        }
+        let srt_content = segments_to_srt(&segments);
+
+        // Filter out non-speech annotations like (dramatic music), [MUSIC], etc.
+        let segments = filter_non_speech_annotations(segments);
+
+        // Detect and filter hallucinated segments (repeated phrases)
+        let segments = filter_hallucinations(segments);
+
+        // Split long segments for better readability (max 4s or 70 chars)
+        let segments = split_long_segments(segments);
+
+        // Normalize capitalization (capitalize after sentence endings, not after ...)
+        let segments = normalize_capitalization(segments);
+
+        // Format text into 2 lines for better on-screen display (~38 chars/line)
+        let segments = format_subtitle_lines(segments);
+
+        // Calculate total duration from last segment
+        let duration_seconds = segments
+            .last()
+            .map(|s| s.end_time)
+            .unwrap_or(0.0);
+
+        // Log warnings about large gaps in transcription (informational only)
+        detect_gaps(&segments, duration_seconds);
+
+        let detected_language = language.map(String::from).or(raw.detected_language);
+
+        TranscriptionResult {
+            segments,
+            detected_language,
+            duration_seconds,
+            srt_content,
+        }
+    }
+}
+
+/// Runs the whisper-cli binary
+///
+/// Follows the same CLI wrapper pattern as FFprobeAdapter and FpcalcAdapter.
+pub struct CliBackend {
+    /// Path to whisper-cli binary
+    cli_path: String,
+    /// Timeout for transcription (can be long for full movies)
+    timeout: Duration,
+}
+
+impl CliBackend {
+    pub fn new(cli_path: String, timeout: Duration) -> Self {
+        Self { cli_path, timeout }
+    }
+}
+
+#[async_trait]
+impl WhisperBackend for CliBackend {
+    fn name(&self) -> &'static str {
+        "cli"
+    }
+
+    async fn is_available(&self) -> bool {
+        Command::new(&self.cli_path)
+            .arg("--version")
+            .output()
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    async fn transcribe(
         &self,
+        model_path: &Path,
         audio_path: &str,
         language: Option<&str>,
-    ) -> Result<TranscriptionResult, SpeechToTextError> {
+        _progress: Option<TranscriptionProgress>,
+    ) -> Result<RawTranscription, SpeechToTextError> {
         // Build command arguments
         let mut args = vec![
-            "-m".to_string(), self.model_path.to_string_lossy().to_string(),
+            "-m".to_string(), model_path.to_string_lossy().to_string(),
             "-f".to_string(), audio_path.to_string(),
             "-osrt".to_string(),  // Output SRT format
             "-of".to_string(), audio_path.to_string(),  // Output file base name
@@ -211,45 +444,13 @@ impl WhisperAdapter {
         // Clean up the SRT file
         let _ = tokio::fs::remove_file(&srt_path).await;
 
-        // Parse SRT content to segments
         let segments = parse_srt(&srt_content)?;
 
-        // Filter out non-speech annotations like (dramatic music), [MUSIC], etc.
-        let segments = filter_non_speech_annotations(segments);
-
-        // Detect and filter hallucinated segments (repeated phrases)
-        let segments = filter_hallucinations(segments);
-
-        // Split long segments for better readability (max 4s or 70 chars)
-        let segments = split_long_segments(segments);
-
-        // Normalize capitalization (capitalize after sentence endings, not after ...)
-        let segments = normalize_capitalization(segments);
-
-        // Format text into 2 lines for better on-screen display (~38 chars/line)
-        let segments = format_subtitle_lines(segments);
-
-        // Calculate total duration from last segment
-        let duration_seconds = segments
-            .last()
-            .map(|s| s.end_time)
-            .unwrap_or(0.0);
-
-        // Log warnings about large gaps in transcription (informational only)
-        detect_gaps(&segments, duration_seconds);
-
-        // Try to detect language from whisper output
-        let detected_language = language.map(String::from).or_else(|| {
-            // Whisper outputs detected language in stderr
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            extract_detected_language(&stderr)
-        });
-
-        Ok(TranscriptionResult {
+        // Whisper outputs the detected language in stderr
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Ok(RawTranscription {
             segments,
-            detected_language,
-            duration_seconds,
-            srt_content,
+            detected_language: extract_detected_language(&stderr),
         })
     }
 }
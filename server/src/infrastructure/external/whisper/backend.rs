@@ -0,0 +1,41 @@
+//! Whisper backends
+//!
+//! A backend runs the model over prepared audio (16 kHz mono WAV); audio
+//! extraction, the VAD pre-pass and the clean-up of the segments stay in
+//! [`super::WhisperAdapter`], so all backends produce the same subtitles.
+
+use std::path::Path;
+use std::sync::Arc;
+use async_trait::async_trait;
+use super::adapter::TranscriptionSegment;
+use crate::shared::error::SpeechToTextError;
+
+/// Receives the transcription progress in percent (0-100)
+pub type TranscriptionProgress = Arc<dyn Fn(f32) + Send + Sync>;
+
+/// Segments as produced by the model, before clean-up
+#[derive(Debug, Clone, Default)]
+pub struct RawTranscription {
+    pub segments: Vec<TranscriptionSegment>,
+    /// Language the model detected (or was given)
+    pub detected_language: Option<String>,
+}
+
+/// Runs Whisper over an audio file
+#[async_trait]
+pub trait WhisperBackend: Send + Sync {
+    /// Short name for logs and capabilities ("cli", "native")
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend can run on this machine
+    async fn is_available(&self) -> bool;
+
+    /// Transcribes a 16 kHz mono WAV file with the given model
+    async fn transcribe(
+        &self,
+        model_path: &Path,
+        audio_path: &str,
+        language: Option<&str>,
+        progress: Option<TranscriptionProgress>,
+    ) -> Result<RawTranscription, SpeechToTextError>;
+}
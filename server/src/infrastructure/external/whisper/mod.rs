@@ -1,8 +1,20 @@
 //! Whisper.cpp Speech-to-Text Module
 //!
 //! Provides audio transcription using the whisper.cpp CLI tool.
-//! Generates SRT subtitles with timestamps from video audio tracks.
+//! Generates SRT subtitles with timestamps from video audio tracks;
+//! silence is skipped by a voice activity detection pre-pass. Models can
+//! be downloaded and selected per language at runtime.
 
 mod adapter;
+mod backend;
+mod models;
+#[cfg(feature = "whisper-rs")]
+mod native;
+mod vad;
 
 pub use adapter::*;
+pub use backend::TranscriptionProgress;
+pub use models::{WhisperModelManager, ANY_LANGUAGE};
+#[cfg(feature = "whisper-rs")]
+pub use native::NativeBackend;
+pub use vad::{VadConfig, VadDetector};
@@ -0,0 +1,315 @@
+//! Whisper model management
+//!
+//! Lists the ggml models in the models directory, downloads further models
+//! from the whisper.cpp model repository and remembers which model to use
+//! per transcription language. The selection is kept in
+//! `{data_dir}/whisper_models.json`, so the models directory itself may be
+//! a read-only mount as long as nothing is downloaded into it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use crate::shared::error::SpeechToTextError;
+
+/// Where `ggml-{name}.bin` files are downloaded from
+pub const DEFAULT_MODELS_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Language key of the model used when no language-specific one is selected
+pub const ANY_LANGUAGE: &str = "*";
+
+/// Models published by whisper.cpp, with their approximate download size
+const CATALOG: &[(&str, u32)] = &[
+    ("tiny", 75),
+    ("tiny.en", 75),
+    ("base", 142),
+    ("base.en", 142),
+    ("small", 466),
+    ("small.en", 466),
+    ("medium", 1500),
+    ("medium.en", 1500),
+    ("large-v2", 2900),
+    ("large-v3", 2900),
+    ("large-v3-turbo", 1500),
+];
+
+/// A Whisper model, installed or available for download
+#[derive(Debug, Clone, Serialize)]
+pub struct WhisperModel {
+    /// Model name (e.g. "small", "large-v3-turbo")
+    pub name: String,
+    pub file_name: String,
+    pub installed: bool,
+    /// Size of the installed file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Approximate download size of a catalog model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_size_mb: Option<u32>,
+    /// Whether the model can be downloaded
+    pub downloadable: bool,
+    /// Whether a download is in progress
+    pub downloading: bool,
+    /// Languages this model is the default for ("*" = all others)
+    pub default_for: Vec<String>,
+}
+
+/// Whisper model directory and per-language model selection
+pub struct WhisperModelManager {
+    models_dir: PathBuf,
+    /// Model used when nothing is selected (WHISPER_MODEL_PATH)
+    fallback: PathBuf,
+    defaults_path: PathBuf,
+    /// Language (or "*") -> model name
+    defaults: RwLock<BTreeMap<String, String>>,
+    downloading: Mutex<HashSet<String>>,
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl WhisperModelManager {
+    /// Creates a manager for `models_dir`, loading the saved selection
+    pub fn new(models_dir: PathBuf, fallback: PathBuf, data_dir: &str) -> Self {
+        let defaults_path = Path::new(data_dir).join("whisper_models.json");
+        let defaults = match std::fs::read_to_string(&defaults_path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid {}: {}", defaults_path.display(), e);
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        Self {
+            models_dir,
+            fallback,
+            defaults_path,
+            defaults: RwLock::new(defaults),
+            downloading: Mutex::new(HashSet::new()),
+            base_url: DEFAULT_MODELS_URL.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Downloads models from another mirror of the whisper.cpp models
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn models_dir(&self) -> &Path {
+        &self.models_dir
+    }
+
+    /// Path of the model to transcribe `language` with
+    ///
+    /// The model selected for the language, else the one selected for all
+    /// languages, else the configured model. Selected models that were
+    /// deleted since are skipped.
+    pub fn model_path(&self, language: Option<&str>) -> PathBuf {
+        let defaults = self.defaults.read().unwrap_or_else(|e| e.into_inner());
+        language
+            .map(normalize_language)
+            .and_then(|l| defaults.get(&l))
+            .into_iter()
+            .chain(defaults.get(ANY_LANGUAGE))
+            .map(|name| self.path_of(name))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| self.fallback.clone())
+    }
+
+    /// Installed and downloadable models
+    pub fn list(&self) -> Vec<WhisperModel> {
+        let mut names: Vec<String> = CATALOG.iter().map(|(name, _)| name.to_string()).collect();
+        if let Ok(entries) = std::fs::read_dir(&self.models_dir) {
+            for entry in entries.flatten() {
+                if let Some(name) = model_name(&entry.file_name().to_string_lossy()) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+
+        let defaults = self.defaults.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let downloading = self.downloading.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        names
+            .into_iter()
+            .map(|name| {
+                let size_bytes = std::fs::metadata(self.path_of(&name)).ok().map(|m| m.len());
+                let catalog_size = CATALOG.iter().find(|(n, _)| *n == name).map(|(_, size)| *size);
+                WhisperModel {
+                    file_name: file_name(&name),
+                    installed: size_bytes.is_some(),
+                    size_bytes,
+                    download_size_mb: catalog_size,
+                    downloadable: catalog_size.is_some(),
+                    downloading: downloading.contains(&name),
+                    default_for: defaults.iter()
+                        .filter(|(_, model)| **model == name)
+                        .map(|(language, _)| language.clone())
+                        .collect(),
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether `name` is in the models directory
+    pub fn is_installed(&self, name: &str) -> bool {
+        is_valid_name(name) && self.path_of(name).exists()
+    }
+
+    /// Whether `name` can be downloaded
+    pub fn is_downloadable(&self, name: &str) -> bool {
+        CATALOG.iter().any(|(n, _)| *n == name)
+    }
+
+    /// Selects an installed model for `language` ("*" = all languages)
+    pub fn set_default(&self, language: &str, name: &str) -> Result<(), SpeechToTextError> {
+        if !self.is_installed(name) {
+            return Err(SpeechToTextError::ModelNotFound(name.to_string()));
+        }
+        let json = {
+            let mut defaults = self.defaults.write().unwrap_or_else(|e| e.into_inner());
+            defaults.insert(normalize_language(language), name.to_string());
+            serde_json::to_string_pretty(&*defaults)
+                .map_err(|e| SpeechToTextError::ParseError(e.to_string()))?
+        };
+        std::fs::write(&self.defaults_path, json)?;
+        Ok(())
+    }
+
+    /// Downloads a catalog model into the models directory
+    ///
+    /// `progress` is called with the bytes received and the total size (if
+    /// known). The file is written under a temporary name and only moved in
+    /// place once complete.
+    pub async fn download(
+        &self,
+        name: &str,
+        progress: impl Fn(u64, Option<u64>),
+    ) -> Result<PathBuf, SpeechToTextError> {
+        if !self.is_downloadable(name) {
+            return Err(SpeechToTextError::ModelNotFound(name.to_string()));
+        }
+        if !self.downloading.lock().unwrap_or_else(|e| e.into_inner()).insert(name.to_string()) {
+            return Err(SpeechToTextError::ModelDownloadFailed(format!("{} is already being downloaded", name)));
+        }
+        let result = self.fetch(name, progress).await;
+        self.downloading.lock().unwrap_or_else(|e| e.into_inner()).remove(name);
+        result
+    }
+
+    async fn fetch(&self, name: &str, progress: impl Fn(u64, Option<u64>)) -> Result<PathBuf, SpeechToTextError> {
+        let url = format!("{}/{}", self.base_url, file_name(name));
+        let mut response = self.client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| SpeechToTextError::ModelDownloadFailed(e.to_string()))?;
+        let total = response.content_length();
+
+        tokio::fs::create_dir_all(&self.models_dir).await?;
+        let path = self.path_of(name);
+        let partial = path.with_extension("bin.part");
+        let mut file = tokio::fs::File::create(&partial).await?;
+        let mut received = 0u64;
+        let written: Result<(), SpeechToTextError> = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| SpeechToTextError::ModelDownloadFailed(e.to_string()))?
+            {
+                file.write_all(&chunk).await?;
+                received += chunk.len() as u64;
+                progress(received, total);
+            }
+            file.flush().await?;
+            Ok(())
+        }
+        .await;
+        drop(file);
+
+        let complete = written.and_then(|_| match total {
+            Some(total) if total != received => Err(SpeechToTextError::ModelDownloadFailed(
+                format!("received {} of {} bytes", received, total),
+            )),
+            _ => Ok(()),
+        });
+        if let Err(e) = complete {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(path)
+    }
+
+    fn path_of(&self, name: &str) -> PathBuf {
+        self.models_dir.join(file_name(name))
+    }
+}
+
+fn file_name(name: &str) -> String {
+    format!("ggml-{}.bin", name)
+}
+
+/// Model name of a `ggml-{name}.bin` file
+fn model_name(file_name: &str) -> Option<String> {
+    file_name
+        .strip_prefix("ggml-")?
+        .strip_suffix(".bin")
+        .filter(|name| is_valid_name(name))
+        .map(String::from)
+}
+
+/// Model names become file names, so only allow what whisper.cpp uses
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_')
+}
+
+fn normalize_language(language: &str) -> String {
+    language.split(['-', '_']).next().unwrap_or(language).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_selection() {
+        let dir = std::env::temp_dir().join(format!("homeflix-whisper-models-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ggml-small.bin"), b"small").unwrap();
+        std::fs::write(dir.join("ggml-medium.bin"), b"medium").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"").unwrap();
+        let fallback = dir.join("ggml-small.bin");
+
+        let manager = WhisperModelManager::new(dir.clone(), fallback.clone(), dir.to_str().unwrap());
+        assert_eq!(manager.model_path(Some("hu")), fallback);
+
+        manager.set_default("hu-HU", "medium").unwrap();
+        assert!(manager.set_default("*", "large-v3").is_err());
+        assert!(manager.set_default("*", "../ggml-small").is_err());
+        assert_eq!(manager.model_path(Some("hu")), dir.join("ggml-medium.bin"));
+        assert_eq!(manager.model_path(Some("en")), fallback);
+
+        let models = manager.list();
+        let medium = models.iter().find(|m| m.name == "medium").unwrap();
+        assert!(medium.installed && medium.downloadable);
+        assert_eq!(medium.default_for, vec!["hu".to_string()]);
+        assert!(!models.iter().any(|m| m.name == "notes"));
+
+        // The selection survives a restart
+        let reloaded = WhisperModelManager::new(dir.clone(), fallback.clone(), dir.to_str().unwrap());
+        assert_eq!(reloaded.model_path(Some("hu")), dir.join("ggml-medium.bin"));
+
+        // A deleted model falls back
+        std::fs::remove_file(dir.join("ggml-medium.bin")).unwrap();
+        assert_eq!(reloaded.model_path(Some("hu")), fallback);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+//! In-process Whisper backend
+//!
+//! Runs whisper.cpp through the whisper-rs bindings instead of spawning
+//! whisper-cli, so the model stays loaded between jobs and progress comes
+//! from whisper.cpp's callback. Built with the `whisper-rs` feature and
+//! selected with `WHISPER_BACKEND=native`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use super::adapter::TranscriptionSegment;
+use super::backend::{RawTranscription, TranscriptionProgress, WhisperBackend};
+use super::vad::read_samples;
+use crate::shared::error::SpeechToTextError;
+
+/// Last loaded model, reused while the same file is requested
+type ModelCache = Arc<Mutex<Option<(PathBuf, Arc<WhisperContext>)>>>;
+
+/// whisper.cpp linked into the server
+pub struct NativeBackend {
+    context: ModelCache,
+    threads: i32,
+}
+
+impl NativeBackend {
+    pub fn new() -> Self {
+        Self {
+            context: Arc::new(Mutex::new(None)),
+            threads: num_cpus::get().clamp(1, 8) as i32,
+        }
+    }
+}
+
+impl Default for NativeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WhisperBackend for NativeBackend {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    async fn transcribe(
+        &self,
+        model_path: &Path,
+        audio_path: &str,
+        language: Option<&str>,
+        progress: Option<TranscriptionProgress>,
+    ) -> Result<RawTranscription, SpeechToTextError> {
+        let audio_path = PathBuf::from(audio_path);
+        let language = language.map(String::from);
+        let threads = self.threads;
+        let model_path = model_path.to_path_buf();
+        let cache = self.context.clone();
+
+        // Model loading and inference block for minutes
+        tokio::task::spawn_blocking(move || {
+            let context = load_model(&cache, &model_path)?;
+            let samples = read_samples(&audio_path)?;
+            if samples.is_empty() {
+                return Ok(RawTranscription::default());
+            }
+            let failed = |e: whisper_rs::WhisperError| SpeechToTextError::TranscriptionFailed(e.to_string());
+            let mut state = context.create_state().map_err(failed)?;
+
+            // Same settings as the whisper-cli arguments
+            let mut params = FullParams::new(SamplingStrategy::BeamSearch { beam_size: 5, patience: -1.0 });
+            params.set_n_threads(threads);
+            params.set_language(Some(language.as_deref().unwrap_or("auto")));
+            params.set_entropy_thold(2.4);
+            params.set_logprob_thold(-0.5);
+            params.set_n_max_text_ctx(224);
+            params.set_print_special(false);
+            params.set_print_progress(false);
+            params.set_print_realtime(false);
+            params.set_print_timestamps(false);
+            if let Some(progress) = progress {
+                params.set_progress_callback_safe(move |percent: i32| progress(percent as f32));
+            }
+
+            state.full(params, &samples).map_err(failed)?;
+
+            let count = state.full_n_segments().map_err(failed)?;
+            let mut segments = Vec::with_capacity(count.max(0) as usize);
+            for i in 0..count {
+                let text = state.full_get_segment_text_lossy(i).map_err(failed)?;
+                let text = text.trim();
+                if text.is_empty() {
+                    continue;
+                }
+                // Timestamps are in centiseconds
+                segments.push(TranscriptionSegment {
+                    start_time: state.full_get_segment_t0(i).map_err(failed)? as f64 / 100.0,
+                    end_time: state.full_get_segment_t1(i).map_err(failed)? as f64 / 100.0,
+                    text: text.to_string(),
+                });
+            }
+            let detected_language = state.full_lang_id_from_state()
+                .ok()
+                .and_then(whisper_rs::get_lang_str)
+                .map(String::from);
+
+            Ok(RawTranscription { segments, detected_language })
+        })
+        .await
+        .map_err(|e| SpeechToTextError::TranscriptionFailed(e.to_string()))?
+    }
+}
+
+/// Loads `model_path`, or returns it if already loaded
+fn load_model(cache: &ModelCache, model_path: &Path) -> Result<Arc<WhisperContext>, SpeechToTextError> {
+    let mut cached = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((path, context)) = cached.as_ref() {
+        if path == model_path {
+            return Ok(context.clone());
+        }
+    }
+    // Free the previous model before loading the next one (VRAM)
+    *cached = None;
+
+    let path = model_path.to_str()
+        .ok_or_else(|| SpeechToTextError::ModelNotFound(model_path.display().to_string()))?;
+    if !model_path.exists() {
+        return Err(SpeechToTextError::ModelNotFound(path.to_string()));
+    }
+    let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+        .map_err(|e| SpeechToTextError::TranscriptionFailed(format!("Failed to load {}: {}", path, e)))?;
+    let context = Arc::new(context);
+    *cached = Some((model_path.to_path_buf(), context.clone()));
+    Ok(context)
+}
@@ -0,0 +1,547 @@
+//! Voice Activity Detection
+//!
+//! Pre-pass over the extracted 16kHz WAV: finds the regions that contain
+//! sound, writes them back to back into a shorter WAV for whisper.cpp, and
+//! maps the resulting timestamps back to the original timeline. Long silent
+//! stretches (sparse dialogue, credits) are then never transcribed.
+//!
+//! Regions are found from the frame energy above the noise floor, or with
+//! ffmpeg's `silencedetect` filter (see [`VadDetector`]).
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::adapter::TranscriptionSegment;
+
+/// Silence inserted between speech regions in the condensed audio
+/// (seconds), so Whisper does not run sentences across a cut
+const REGION_SEPARATOR: f64 = 0.5;
+
+/// How speech regions are found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VadDetector {
+    /// Frame energy relative to the noise floor (in-process)
+    #[default]
+    Energy,
+    /// ffmpeg's `silencedetect` filter with a fixed noise level
+    Silencedetect,
+}
+
+impl VadDetector {
+    /// Parses "energy" or "silencedetect"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "energy" => Some(Self::Energy),
+            "silencedetect" => Some(Self::Silencedetect),
+            _ => None,
+        }
+    }
+}
+
+/// Voice activity detection settings
+#[derive(Debug, Clone)]
+pub struct VadConfig {
+    /// How speech regions are found
+    pub detector: VadDetector,
+    /// Analysis frame length in milliseconds
+    pub frame_ms: u32,
+    /// Level above the noise floor that counts as speech (dB)
+    pub threshold_db: f32,
+    /// Frames quieter than this are never speech (dBFS)
+    pub min_level_db: f32,
+    /// Noise level below which `silencedetect` hears silence (dBFS)
+    pub silence_db: f32,
+    /// Audio kept before and after each region (seconds)
+    pub padding: f64,
+    /// Silences shorter than this are kept inside a region (seconds)
+    pub min_silence: f64,
+    /// Regions shorter than this are dropped as noise (seconds)
+    pub min_speech: f64,
+    /// Speech share above which condensing is not worth it
+    pub max_speech_ratio: f64,
+}
+
+impl VadConfig {
+    /// Settings with another detector
+    pub fn with_detector(mut self, detector: VadDetector) -> Self {
+        self.detector = detector;
+        self
+    }
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            detector: VadDetector::Energy,
+            frame_ms: 30,
+            threshold_db: 12.0,
+            min_level_db: -55.0,
+            silence_db: -35.0,
+            padding: 0.3,
+            min_silence: 2.0,
+            min_speech: 0.25,
+            max_speech_ratio: 0.9,
+        }
+    }
+}
+
+/// A speech region in the original audio (seconds)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechRegion {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Position of a speech region in the condensed audio
+#[derive(Debug, Clone, Copy)]
+struct MappedRegion {
+    condensed_start: f64,
+    original_start: f64,
+    duration: f64,
+}
+
+/// Maps timestamps of the condensed audio back to the original
+#[derive(Debug, Clone)]
+pub struct SpeechMap {
+    regions: Vec<MappedRegion>,
+}
+
+impl SpeechMap {
+    fn new(regions: &[SpeechRegion]) -> Self {
+        let mut condensed_start = 0.0;
+        let regions = regions
+            .iter()
+            .map(|r| {
+                let mapped = MappedRegion {
+                    condensed_start,
+                    original_start: r.start,
+                    duration: r.end - r.start,
+                };
+                condensed_start += mapped.duration + REGION_SEPARATOR;
+                mapped
+            })
+            .collect();
+        Self { regions }
+    }
+
+    /// Converts a condensed timestamp to the original timeline
+    ///
+    /// Times inside a separator snap to the end of the previous region, or
+    /// with `snap_forward` to the start of the next one.
+    fn to_original(&self, time: f64, snap_forward: bool) -> f64 {
+        let index = self.regions
+            .partition_point(|r| r.condensed_start <= time)
+            .saturating_sub(1);
+        let Some(region) = self.regions.get(index) else {
+            return time;
+        };
+        let offset = time - region.condensed_start;
+        if offset <= region.duration {
+            return region.original_start + offset.max(0.0);
+        }
+        match self.regions.get(index + 1) {
+            Some(next) if snap_forward => next.original_start,
+            _ => region.original_start + region.duration,
+        }
+    }
+
+    /// Moves transcription segments back to the original timeline
+    pub fn restore_segments(&self, segments: Vec<TranscriptionSegment>) -> Vec<TranscriptionSegment> {
+        segments
+            .into_iter()
+            .map(|mut segment| {
+                segment.start_time = self.to_original(segment.start_time, true);
+                segment.end_time = self.to_original(segment.end_time, false).max(segment.start_time);
+                segment
+            })
+            .collect()
+    }
+}
+
+/// PCM layout of a 16-bit WAV file
+struct WavInfo {
+    sample_rate: u32,
+    channels: u16,
+    data_offset: u64,
+    data_len: u64,
+}
+
+impl WavInfo {
+    fn duration(&self) -> f64 {
+        self.data_len as f64 / (self.sample_rate.max(1) as f64 * self.channels as f64 * 2.0)
+    }
+}
+
+/// Finds speech in a 16-bit PCM WAV file and writes it to `output`
+///
+/// Returns `None` when condensing would not pay off (little silence, or no
+/// speech found at all, which rather means the threshold failed); the
+/// original file should be transcribed then.
+///
+/// # Errors
+/// Returns error if the files cannot be read or written
+pub fn condense_speech(input: &Path, output: &Path, config: &VadConfig) -> io::Result<Option<SpeechMap>> {
+    let mut reader = BufReader::new(File::open(input)?);
+    let wav = read_wav_info(&mut reader)?;
+    let frame_samples = (wav.sample_rate as u64 * config.frame_ms as u64 / 1000).max(1) * wav.channels as u64;
+    let frame_secs = config.frame_ms as f64 / 1000.0;
+
+    let levels = frame_levels(&mut reader, &wav, frame_samples)?;
+    let regions = speech_regions(&levels, frame_secs, config);
+    condense(&mut reader, &wav, &regions, output, config)
+}
+
+/// Writes the given speech regions of a 16-bit PCM WAV file to `output`
+///
+/// Like [`condense_speech`], for regions found by another detector.
+///
+/// # Errors
+/// Returns error if the files cannot be read or written
+pub fn condense_regions(
+    input: &Path,
+    output: &Path,
+    regions: &[SpeechRegion],
+    config: &VadConfig,
+) -> io::Result<Option<SpeechMap>> {
+    let mut reader = BufReader::new(File::open(input)?);
+    let wav = read_wav_info(&mut reader)?;
+    condense(&mut reader, &wav, regions, output, config)
+}
+
+fn condense<R: Read + Seek>(
+    reader: &mut R,
+    wav: &WavInfo,
+    regions: &[SpeechRegion],
+    output: &Path,
+    config: &VadConfig,
+) -> io::Result<Option<SpeechMap>> {
+    let total = wav.duration();
+    let speech: f64 = regions.iter().map(|r| r.end - r.start).sum();
+
+    tracing::info!(
+        "VAD: {:.0}s of speech in {:.0}s of audio ({} regions)",
+        speech, total, regions.len()
+    );
+    if regions.is_empty() || speech > total * config.max_speech_ratio {
+        return Ok(None);
+    }
+
+    write_regions(reader, wav, regions, output)?;
+    Ok(Some(SpeechMap::new(regions)))
+}
+
+/// Duration of a WAV file in seconds, from its header
+pub fn wav_duration(path: &Path) -> io::Result<f64> {
+    read_wav_info(&mut BufReader::new(File::open(path)?)).map(|wav| wav.duration())
+}
+
+/// ffmpeg filter that prints the silences `config` looks for
+pub fn silencedetect_filter(config: &VadConfig) -> String {
+    format!("silencedetect=noise={}dB:d={}", config.silence_db, config.min_silence)
+}
+
+/// Speech regions between the silences reported by `silencedetect`
+///
+/// `log` is ffmpeg's stderr with `silence_start: 12.3` and
+/// `silence_end: 45.6 | ...` lines; a silence still open at the end runs
+/// to `total`.
+pub fn silencedetect_regions(log: &str, total: f64, config: &VadConfig) -> Vec<SpeechRegion> {
+    let value = |line: &str, key: &str| -> Option<f64> {
+        let rest = &line[line.find(key)? + key.len()..];
+        rest.split_whitespace().next()?.parse().ok()
+    };
+
+    let mut silences: Vec<(f64, f64)> = Vec::new();
+    let mut open = None;
+    for line in log.lines() {
+        if let Some(start) = value(line, "silence_start:") {
+            open = Some(start.max(0.0));
+        } else if let Some(end) = value(line, "silence_end:") {
+            silences.push((open.take().unwrap_or(0.0), end.min(total)));
+        }
+    }
+    if let Some(start) = open {
+        silences.push((start, total));
+    }
+
+    let mut regions: Vec<SpeechRegion> = Vec::new();
+    let mut speech_start = 0.0;
+    for (start, end) in silences.into_iter().chain(std::iter::once((total, total))) {
+        if start > speech_start {
+            let region = SpeechRegion {
+                start: (speech_start - config.padding).max(0.0),
+                end: (start + config.padding).min(total),
+            };
+            match regions.last_mut() {
+                Some(last) if region.start <= last.end => last.end = region.end,
+                _ => regions.push(region),
+            }
+        }
+        speech_start = speech_start.max(end);
+    }
+
+    regions.retain(|r| r.end - r.start >= config.min_speech + 2.0 * config.padding);
+    regions
+}
+
+/// Reads a 16-bit PCM WAV file as mono samples in -1.0..1.0
+///
+/// Channels are averaged; the sample rate is left as is (the extracted
+/// audio is already 16 kHz).
+#[cfg_attr(not(feature = "whisper-rs"), allow(dead_code))]
+pub(super) fn read_samples(path: &Path) -> io::Result<Vec<f32>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let wav = read_wav_info(&mut reader)?;
+    reader.seek(SeekFrom::Start(wav.data_offset))?;
+    let mut data = Vec::with_capacity(wav.data_len as usize);
+    reader.take(wav.data_len).read_to_end(&mut data)?;
+
+    let channels = wav.channels as usize;
+    Ok(data
+        .chunks_exact(2 * channels)
+        .map(|frame| {
+            let sum: f32 = frame
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                .sum();
+            sum / channels as f32
+        })
+        .collect())
+}
+
+/// Reads the format and locates the sample data of a WAV file
+fn read_wav_info<R: Read + Seek>(reader: &mut R) -> io::Result<WavInfo> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Err(invalid("not a WAV file"));
+    }
+
+    let mut format = None;
+    loop {
+        let mut chunk = [0u8; 8];
+        reader.read_exact(&mut chunk)?;
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as u64;
+        match &chunk[0..4] {
+            b"fmt " => {
+                let mut fmt = vec![0u8; size as usize];
+                reader.read_exact(&mut fmt)?;
+                if fmt.len() < 16 || u16::from_le_bytes([fmt[14], fmt[15]]) != 16 {
+                    return Err(invalid("only 16-bit PCM is supported"));
+                }
+                let channels = u16::from_le_bytes([fmt[2], fmt[3]]).max(1);
+                let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                format = Some((sample_rate, channels));
+                // Chunks are word aligned
+                reader.seek(SeekFrom::Current((size % 2) as i64))?;
+            }
+            b"data" => {
+                let (sample_rate, channels) = format.ok_or_else(|| invalid("data chunk before fmt chunk"))?;
+                let data_offset = reader.stream_position()?;
+                let file_len = reader.seek(SeekFrom::End(0))?;
+                // Streamed writers leave the size unset
+                let data_len = if size == 0 || size == u32::MAX as u64 {
+                    file_len - data_offset
+                } else {
+                    size.min(file_len - data_offset)
+                };
+                return Ok(WavInfo { sample_rate, channels, data_offset, data_len });
+            }
+            _ => {
+                reader.seek(SeekFrom::Current((size + size % 2) as i64))?;
+            }
+        }
+    }
+}
+
+/// RMS level of each frame in dBFS
+fn frame_levels<R: Read + Seek>(reader: &mut R, wav: &WavInfo, frame_samples: u64) -> io::Result<Vec<f32>> {
+    reader.seek(SeekFrom::Start(wav.data_offset))?;
+    let mut data = reader.take(wav.data_len);
+    let mut buffer = vec![0u8; frame_samples as usize * 2];
+    let mut levels = Vec::new();
+
+    loop {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            let n = data.read(&mut buffer[filled..])?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        if filled < 2 {
+            break;
+        }
+
+        let samples = filled / 2;
+        let sum: f64 = buffer[..samples * 2]
+            .chunks_exact(2)
+            .map(|b| {
+                let s = i16::from_le_bytes([b[0], b[1]]) as f64 / i16::MAX as f64;
+                s * s
+            })
+            .sum();
+        let rms = (sum / samples as f64).sqrt();
+        levels.push((20.0 * rms.max(1e-9).log10()) as f32);
+
+        if filled < buffer.len() {
+            break;
+        }
+    }
+    Ok(levels)
+}
+
+/// Groups frames above the speech threshold into padded regions
+///
+/// The threshold follows the noise floor (the 10th percentile level), so
+/// quiet and loud mixes are handled alike.
+fn speech_regions(levels: &[f32], frame_secs: f64, config: &VadConfig) -> Vec<SpeechRegion> {
+    if levels.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted = levels.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let noise_floor = sorted[sorted.len() / 10];
+    let threshold = (noise_floor + config.threshold_db).max(config.min_level_db);
+
+    let total = levels.len() as f64 * frame_secs;
+    let mut regions: Vec<SpeechRegion> = Vec::new();
+    let mut start = None;
+    for (i, &level) in levels.iter().enumerate().chain(std::iter::once((levels.len(), &f32::MIN))) {
+        match (level >= threshold, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                start = None;
+                let region = SpeechRegion {
+                    start: (s as f64 * frame_secs - config.padding).max(0.0),
+                    end: (i as f64 * frame_secs + config.padding).min(total),
+                };
+                match regions.last_mut() {
+                    Some(last) if region.start - last.end < config.min_silence => last.end = region.end,
+                    _ => regions.push(region),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    regions.retain(|r| r.end - r.start >= config.min_speech + 2.0 * config.padding);
+    regions
+}
+
+/// Writes the regions back to back, separated by short silences, as WAV
+fn write_regions<R: Read + Seek>(reader: &mut R, wav: &WavInfo, regions: &[SpeechRegion], output: &Path) -> io::Result<()> {
+    let block = wav.channels as u64 * 2;
+    let bytes_per_sec = wav.sample_rate as u64 * block;
+    let to_offset = |secs: f64| ((secs * bytes_per_sec as f64) as u64 / block * block).min(wav.data_len);
+    let separator = vec![0u8; to_offset(REGION_SEPARATOR) as usize];
+
+    let data_len: u64 = regions.iter().map(|r| to_offset(r.end) - to_offset(r.start)).sum::<u64>()
+        + separator.len() as u64 * regions.len().saturating_sub(1) as u64;
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_len as u32).to_le_bytes())?;
+    writer.write_all(b"WAVEfmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&1u16.to_le_bytes())?; // PCM
+    writer.write_all(&wav.channels.to_le_bytes())?;
+    writer.write_all(&wav.sample_rate.to_le_bytes())?;
+    writer.write_all(&(bytes_per_sec as u32).to_le_bytes())?;
+    writer.write_all(&(block as u16).to_le_bytes())?;
+    writer.write_all(&16u16.to_le_bytes())?;
+    writer.write_all(b"data")?;
+    writer.write_all(&(data_len as u32).to_le_bytes())?;
+
+    for (i, region) in regions.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(&separator)?;
+        }
+        let start = to_offset(region.start);
+        reader.seek(SeekFrom::Start(wav.data_offset + start))?;
+        io::copy(&mut reader.take(to_offset(region.end) - start), &mut writer)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_condense_and_restore_timestamps() {
+        // 1s tone at 2s and 20s in 30s of near silence, 1kHz mono
+        let rate = 1000u32;
+        let samples: Vec<i16> = (0..30 * rate)
+            .map(|i| {
+                let t = i as f64 / rate as f64;
+                let speaking = (2.0..3.0).contains(&t) || (20.0..21.0).contains(&t);
+                if speaking { if i % 2 == 0 { 8000 } else { -8000 } } else { (i % 3) as i16 }
+            })
+            .collect();
+
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("audio.wav");
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + samples.len() as u32 * 2).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&[16, 0, 0, 0, 1, 0, 1, 0]);
+        wav.extend_from_slice(&rate.to_le_bytes());
+        wav.extend_from_slice(&(rate * 2).to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(samples.len() as u32 * 2).to_le_bytes());
+        samples.iter().for_each(|s| wav.extend_from_slice(&s.to_le_bytes()));
+        std::fs::write(&input, wav).unwrap();
+
+        let read = read_samples(&input).unwrap();
+        assert_eq!(read.len(), samples.len());
+        assert!((read[2000] - 8000.0 / 32768.0).abs() < 1e-6);
+
+        let output = temp_dir.path().join("speech.wav");
+        let map = condense_speech(&input, &output, &VadConfig::default()).unwrap().unwrap();
+
+        // Two padded 1.62s regions and a separator instead of 30s
+        assert_eq!(map.regions.len(), 2);
+        assert_eq!(std::fs::metadata(&output).unwrap().len(), 44 + 3740 * 2);
+
+        let segment = |start: f64, end: f64| TranscriptionSegment { start_time: start, end_time: end, text: String::new() };
+        let restored = map.restore_segments(vec![segment(0.4, 1.2), segment(1.8, 2.5)]);
+        assert!((restored[0].start_time - 2.08).abs() < 0.001);
+        assert!((restored[0].end_time - 2.88).abs() < 0.001);
+        // Starts inside the separator snap to the next region
+        assert!((restored[1].start_time - 19.68).abs() < 0.001);
+        assert!((restored[1].end_time - 20.06).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_silencedetect_regions() {
+        let log = "\
+[silencedetect @ 0x5581] silence_start: 0
+[silencedetect @ 0x5581] silence_end: 12.5 | silence_duration: 12.5
+size=N/A time=00:00:30.00 bitrate=N/A
+[silencedetect @ 0x5581] silence_start: 14.2
+[silencedetect @ 0x5581] silence_end: 14.6 | silence_duration: 0.4
+[silencedetect @ 0x5581] silence_start: 20
+[silencedetect @ 0x5581] silence_end: 25.5 | silence_duration: 5.5
+[silencedetect @ 0x5581] silence_start: 25.6";
+        let config = VadConfig::default().with_detector(VadDetector::Silencedetect);
+        assert_eq!(silencedetect_filter(&config), "silencedetect=noise=-35dB:d=2");
+
+        let regions = silencedetect_regions(log, 30.0, &config);
+        // Padded regions that touch merge; the 0.1s blip at 25.5s is dropped
+        assert_eq!(regions.len(), 1);
+        assert!((regions[0].start - 12.2).abs() < 1e-9);
+        assert!((regions[0].end - 20.3).abs() < 1e-9);
+
+        // No silence at all is one region
+        assert_eq!(silencedetect_regions("", 30.0, &config), vec![SpeechRegion { start: 0.0, end: 30.0 }]);
+        assert_eq!(VadDetector::from_name("SilenceDetect"), Some(VadDetector::Silencedetect));
+    }
+}
@@ -0,0 +1,65 @@
+//! Collection Poster Store
+//!
+//! Keeps uploaded posters of custom collections in the data directory, one
+//! file per collection (`collection_posters/<id>.<ext>`).
+
+use std::path::{Path, PathBuf};
+
+/// Accepted image formats: content type and file extension
+const FORMATS: [(&str, &str); 3] = [("image/jpeg", "jpg"), ("image/png", "png"), ("image/webp", "webp")];
+
+/// Uploaded collection posters
+pub struct CollectionPosterStore {
+    dir: PathBuf,
+}
+
+impl CollectionPosterStore {
+    /// Creates a store
+    ///
+    /// # Arguments
+    /// * `data_dir` - Base data directory (e.g., /data or ./data)
+    pub fn new(data_dir: &str) -> Self {
+        Self {
+            dir: Path::new(data_dir).join("collection_posters"),
+        }
+    }
+
+    /// Whether a content type can be stored
+    pub fn accepts(content_type: &str) -> bool {
+        FORMATS.iter().any(|(accepted, _)| *accepted == content_type)
+    }
+
+    /// Stores the poster of a collection, replacing any previous one
+    pub async fn save(&self, collection_id: i64, content_type: &str, bytes: &[u8]) -> std::io::Result<()> {
+        let Some((_, extension)) = FORMATS.iter().find(|(accepted, _)| *accepted == content_type) else {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unsupported image format"));
+        };
+        tokio::fs::create_dir_all(&self.dir).await?;
+        self.delete(collection_id).await?;
+        tokio::fs::write(self.dir.join(format!("{}.{}", collection_id, extension)), bytes).await
+    }
+
+    /// Reads the poster of a collection with its content type
+    pub async fn load(&self, collection_id: i64) -> std::io::Result<Option<(Vec<u8>, &'static str)>> {
+        for (content_type, extension) in FORMATS {
+            match tokio::fs::read(self.dir.join(format!("{}.{}", collection_id, extension))).await {
+                Ok(bytes) => return Ok(Some((bytes, content_type))),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Removes the poster of a collection, if any
+    pub async fn delete(&self, collection_id: i64) -> std::io::Result<()> {
+        for (_, extension) in FORMATS {
+            match tokio::fs::remove_file(self.dir.join(format!("{}.{}", collection_id, extension))).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
@@ -5,6 +5,8 @@
 
 pub mod walkdir_adapter;
 pub mod file_operations_adapter;
+pub mod collection_posters;
 
 pub use walkdir_adapter::WalkDirAdapter;
 pub use file_operations_adapter::FileOperationsAdapter;
+pub use collection_posters::CollectionPosterStore;
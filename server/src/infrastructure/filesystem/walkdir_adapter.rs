@@ -3,10 +3,17 @@
 //! Provides WalkDir-based implementation of DirectoryWalker interface
 
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
 use std::path::Path;
-use crate::interfaces::filesystem::{DirectoryWalker, WalkEntry};
+use crate::interfaces::filesystem::{DirectoryWalker, WalkEntry, WalkFilter, WalkStream};
 use crate::shared::error::FilesystemError;
 
+/// Entries buffered ahead of the consumer
+///
+/// The walk pauses when the consumer falls behind, so memory stays bounded
+/// however large the library is.
+const WALK_BUFFER: usize = 256;
+
 /// WalkDir adapter for directory traversal
 pub struct WalkDirAdapter;
 
@@ -16,22 +23,8 @@ impl WalkDirAdapter {
         Self
     }
 
-    /// Helper function to filter entries - uses explicit loop to avoid closure lifetime issues
-    fn apply_filter_loop(
-        entries: Vec<WalkEntry>,
-        filter: Box<dyn for<'a> Fn(&'a WalkEntry) -> bool + Send + Sync>,
-    ) -> Vec<WalkEntry> {
-        let mut result = Vec::new();
-        for entry in entries {
-            if filter(&entry) {
-                result.push(entry);
-            }
-        }
-        result
-    }
-
     /// Creates a WalkEntry from a directory entry
-    fn create_walk_entry(&self, entry: &walkdir::DirEntry) -> Result<WalkEntry, FilesystemError> {
+    fn create_walk_entry(entry: &walkdir::DirEntry) -> Result<WalkEntry, FilesystemError> {
         let path = entry.path();
         let metadata = entry.metadata().map_err(|e| FilesystemError::WalkError(e.to_string()))?;
 
@@ -69,64 +62,35 @@ impl WalkDirAdapter {
 
 #[async_trait]
 impl DirectoryWalker for WalkDirAdapter {
-    async fn walk(&self, root: &Path) -> Result<Vec<WalkEntry>, FilesystemError> {
-        let mut entries = Vec::new();
-
-        let walker = walkdir::WalkDir::new(root);
-
-        for entry in walker.into_iter() {
-            let entry = entry.map_err(|e| FilesystemError::WalkError(e.to_string()))?;
-
-            let path = entry.path();
-
-            let metadata = entry.metadata().map_err(|e| FilesystemError::WalkError(e.to_string()))?;
-
-            let is_file = metadata.is_file();
-            let is_dir = metadata.is_dir();
-            let depth = path.ancestors().count();
-
-            let file_size = if is_file {
-                Some(metadata.len())
-            } else {
-                None
-            };
-
-            let extension = if is_file {
-                path.extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|s| s.to_string())
-            } else {
-                None
-            };
-
-            let is_symlink = metadata.file_type().is_symlink();
-
-            entries.push(WalkEntry {
-                path: path.to_path_buf(),
-                is_file,
-                is_dir,
-                depth,
-                file_size,
-                extension,
-                is_symlink,
-            });
-        }
-
-        Ok(entries)
-    }
-
-    async fn walk_with_filter(
-        &self,
-        root: &Path,
-        filter: Box<dyn for<'a> Fn(&'a WalkEntry) -> bool + Send + Sync>,
-    ) -> Result<Vec<WalkEntry>, FilesystemError> {
-        // First collect all entries
-        let all_entries = self.walk(root).await?;
-
-        // Use helper to filter - taking ownership avoids lifetime issues
-        let result = Self::apply_filter_loop(all_entries, filter);
+    /// Walks on a blocking thread, handing entries over through a bounded
+    /// channel
+    ///
+    /// Filtering as entries are visited keeps the channel to the matches
+    /// (subtitles, artwork, NFOs and directories outnumber videos in most
+    /// libraries). Must be called within a Tokio runtime.
+    fn walk_stream(&self, root: &Path, filter: WalkFilter) -> WalkStream {
+        let (tx, rx) = tokio::sync::mpsc::channel(WALK_BUFFER);
+        let root = root.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            for entry in walkdir::WalkDir::new(&root) {
+                let item = entry
+                    .map_err(|e| FilesystemError::WalkError(e.to_string()))
+                    .and_then(|entry| Self::create_walk_entry(&entry));
+                if matches!(&item, Ok(walk_entry) if !filter(walk_entry)) {
+                    continue;
+                }
+                // The consumer dropped the stream
+                if tx.blocking_send(item).is_err() {
+                    break;
+                }
+            }
+        });
 
-        Ok(result)
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        })
+        .boxed()
     }
 
     async fn walk_parallel(
@@ -142,7 +106,7 @@ impl DirectoryWalker for WalkDirAdapter {
         for entry in walker.into_iter() {
             let entry = entry.map_err(|e| FilesystemError::WalkError(e.to_string()))?;
 
-            let walk_entry = self.create_walk_entry(&entry)?;
+            let walk_entry = Self::create_walk_entry(&entry)?;
 
             entries.push(walk_entry);
         }
@@ -150,3 +114,44 @@ impl DirectoryWalker for WalkDirAdapter {
         Ok(entries)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_walk_videos_streams_entries() {
+        let root = std::env::temp_dir().join(format!("homeflix-walk-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("Film (2020)/Sample")).unwrap();
+        std::fs::create_dir_all(root.join("Show/Season 1")).unwrap();
+        for file in [
+            "Film (2020)/Film.mkv",
+            "Film (2020)/Film.en.srt",
+            "Film (2020)/Sample/film-sample.mkv",
+            "Show/Season 1/Show.S01E01.mp4",
+            "Show/Season 1/folder.jpg",
+        ] {
+            std::fs::write(root.join(file), b"").unwrap();
+        }
+
+        let walker = WalkDirAdapter::new();
+        let mut videos: Vec<String> = walker.walk_videos(&root)
+            .map(|entry| entry.unwrap().path.strip_prefix(&root).unwrap().to_string_lossy().to_string())
+            .collect()
+            .await;
+        videos.sort();
+        assert_eq!(videos, vec!["Film (2020)/Film.mkv", "Show/Season 1/Show.S01E01.mp4"]);
+
+        // Dropping the stream early stops the walk
+        let first = walker.walk_stream(&root, Box::new(|_| true)).next().await;
+        assert!(first.unwrap().is_ok());
+
+        // A missing root is an error item, and an error for collecting walks
+        let missing = root.join("missing");
+        let items: Vec<_> = walker.walk_videos(&missing).collect().await;
+        assert!(matches!(items.as_slice(), [Err(_)]));
+        assert!(walker.walk(&missing).await.is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
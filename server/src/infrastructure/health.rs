@@ -0,0 +1,181 @@
+//! Readiness Probe
+//!
+//! Checks the dependencies the server needs to serve the library: the
+//! database, its schema and the media directory. Checks listed as optional
+//! are reported but do not make the server unready, e.g. a media share that
+//! is allowed to be briefly unmounted.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::database::migrations::{self, MigrationState};
+use crate::infrastructure::database::missing_tables;
+
+/// Dependency check names
+pub const CHECK_DATABASE: &str = "database";
+pub const CHECK_MIGRATIONS: &str = "migrations";
+pub const CHECK_MEDIA_DIR: &str = "media_dir";
+
+/// Longest a single check may take before it counts as failed
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Outcome of one dependency check
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    pub name: &'static str,
+    pub healthy: bool,
+    /// Whether a failure makes the server unready
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Outcome of all checks
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// "ready", "degraded" (an optional check failed) or "not_ready"
+    pub status: &'static str,
+    pub ready: bool,
+    pub checks: Vec<DependencyStatus>,
+}
+
+/// Readiness probe
+pub struct ReadinessProbe {
+    pool: Pool<Sqlite>,
+    media_dir: PathBuf,
+    optional: HashSet<String>,
+}
+
+impl ReadinessProbe {
+    pub fn new(pool: Pool<Sqlite>, media_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            pool,
+            media_dir: media_dir.into(),
+            optional: HashSet::new(),
+        }
+    }
+
+    /// Checks whose failure is reported without failing readiness
+    pub fn with_optional_checks(mut self, checks: impl IntoIterator<Item = String>) -> Self {
+        self.optional = checks.into_iter().collect();
+        self
+    }
+
+    /// Runs all checks
+    pub async fn check(&self) -> ReadinessReport {
+        let (database, migrations, media_dir) = tokio::join!(
+            self.run(CHECK_DATABASE, self.check_database()),
+            self.run(CHECK_MIGRATIONS, self.check_migrations()),
+            self.run(CHECK_MEDIA_DIR, self.check_media_dir()),
+        );
+        report(vec![database, migrations, media_dir])
+    }
+
+    async fn run(
+        &self,
+        name: &'static str,
+        check: impl std::future::Future<Output = Result<(), String>>,
+    ) -> DependencyStatus {
+        let started = Instant::now();
+        let result = tokio::time::timeout(CHECK_TIMEOUT, check).await
+            .unwrap_or_else(|_| Err(format!("Timed out after {}s", CHECK_TIMEOUT.as_secs())));
+        if let Err(e) = &result {
+            tracing::warn!("Readiness check {} failed: {}", name, e);
+        }
+        DependencyStatus {
+            name,
+            healthy: result.is_ok(),
+            required: !self.optional.contains(name),
+            error: result.err(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    async fn check_database(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn check_migrations(&self) -> Result<(), String> {
+        let missing = missing_tables(&self.pool).await.map_err(|e| e.to_string())?;
+        if !missing.is_empty() {
+            return Err(format!("Missing tables: {}", missing.join(", ")));
+        }
+        let unapplied: Vec<String> = migrations::status(&self.pool).await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|m| m.state != MigrationState::Applied)
+            .map(|m| format!("{} ({:?})", m.version, m.state))
+            .collect();
+        if unapplied.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Migrations not applied: {}", unapplied.join(", ")))
+        }
+    }
+
+    /// An empty directory usually means the volume is not mounted
+    async fn check_media_dir(&self) -> Result<(), String> {
+        let mut entries = tokio::fs::read_dir(&self.media_dir).await
+            .map_err(|e| format!("{}: {}", self.media_dir.display(), e))?;
+        match entries.next_entry().await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(format!("{} is empty (not mounted?)", self.media_dir.display())),
+            Err(e) => Err(format!("{}: {}", self.media_dir.display(), e)),
+        }
+    }
+}
+
+fn report(checks: Vec<DependencyStatus>) -> ReadinessReport {
+    let ready = checks.iter().all(|c| c.healthy || !c.required);
+    let status = if !ready {
+        "not_ready"
+    } else if checks.iter().all(|c| c.healthy) {
+        "ready"
+    } else {
+        "degraded"
+    };
+    ReadinessReport { status, ready, checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_readiness() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let media_dir = std::env::temp_dir().join(format!("homeflix-ready-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&media_dir).unwrap();
+
+        // Fresh database and empty media directory
+        let probe = ReadinessProbe::new(pool.clone(), &media_dir);
+        let report = probe.check().await;
+        assert_eq!(report.status, "not_ready");
+        let failed: Vec<&str> = report.checks.iter().filter(|c| !c.healthy).map(|c| c.name).collect();
+        assert_eq!(failed, vec![CHECK_MIGRATIONS, CHECK_MEDIA_DIR]);
+
+        crate::infrastructure::database::initialize_schema(&pool).await.unwrap();
+        let probe = probe.with_optional_checks([CHECK_MEDIA_DIR.to_string()]);
+        assert_eq!(probe.check().await.status, "degraded");
+
+        std::fs::write(media_dir.join("Film.mkv"), b"").unwrap();
+        let report = probe.check().await;
+        assert_eq!(report.status, "ready");
+        assert!(report.ready);
+
+        std::fs::remove_dir_all(&media_dir).unwrap();
+    }
+}
@@ -1,14 +1,34 @@
-//! Job Store - In-memory job status tracking
+//! Job Store - Job status tracking
 //!
 //! Provides a thread-safe store for tracking long-running async jobs
 //! like subtitle generation. Supports progress updates, completion,
 //! and failure states.
+//!
+//! Jobs that report their workload (seconds of media) get a completion
+//! estimate from the throughput of earlier jobs of the same kind, which are
+//! recorded in the job history when configured.
+//!
+//! With a queue repository, jobs are also stored as they change state
+//! (progress is kept in memory only). Jobs created with a payload are
+//! resumed after a restart; see [`JobStore::recover_interrupted`].
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::domain::repositories::{JobFilter, JobHistoryRepository, JobQueueRepository, JobRun, StoredJob};
+use crate::shared::error::RepositoryError;
+
+/// Recent runs the throughput of a job kind is averaged over
+const THROUGHPUT_SAMPLES: u32 = 20;
+/// Times a job is started before a restart no longer resumes it (a job
+/// crashing the server would otherwise do so forever)
+const MAX_ATTEMPTS: u32 = 3;
+/// Days finished jobs are kept in the queue
+const FINISHED_JOB_RETENTION_DAYS: i64 = 30;
+/// Error of jobs a restart interrupted that are not resumed
+const INTERRUPTED: &str = "Interrupted by a server restart";
 
 /// Job state enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,13 +46,118 @@ pub enum JobState {
     Cancelled,
 }
 
+impl JobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Pending => "pending",
+            JobState::Processing => "processing",
+            JobState::Completed => "completed",
+            JobState::Failed => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self, JobState::Pending | JobState::Processing)
+    }
+}
+
+/// What a job does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobType {
+    /// Subtitle generation for one media item
+    Subtitle,
+    /// Translation of an existing subtitle
+    Translation,
+    /// Whisper model download
+    ModelDownload,
+    /// Subtitle generation for the episodes of a series or season
+    SubtitleBatch,
+    /// Subtitle extraction for the episodes of a series or season
+    ExtractionBatch,
+}
+
+impl JobType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobType::Subtitle => "subtitle",
+            JobType::Translation => "translation",
+            JobType::ModelDownload => "model_download",
+            JobType::SubtitleBatch => "subtitle_batch",
+            JobType::ExtractionBatch => "extraction_batch",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            JobType::Subtitle,
+            JobType::Translation,
+            JobType::ModelDownload,
+            JobType::SubtitleBatch,
+            JobType::ExtractionBatch,
+        ]
+        .into_iter()
+        .find(|t| t.as_str() == value)
+    }
+
+    /// Whether jobs of this type are tracked as [`BatchJobStatus`]
+    pub fn is_batch(&self) -> bool {
+        matches!(self, JobType::SubtitleBatch | JobType::ExtractionBatch)
+    }
+
+    /// Resumption order after a restart: jobs someone is waiting on first
+    fn priority(&self) -> i32 {
+        match self {
+            JobType::ModelDownload => 2,
+            JobType::Subtitle | JobType::Translation => 1,
+            JobType::SubtitleBatch | JobType::ExtractionBatch => 0,
+        }
+    }
+}
+
+/// Kind of work a job does, for throughput history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Whisper transcription (optionally translated)
+    Subtitle,
+    /// Translation of an existing subtitle
+    Translation,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::Subtitle => "subtitle",
+            JobKind::Translation => "translation",
+        }
+    }
+}
+
+/// Work a job is processing, measured in media time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobWorkload {
+    pub kind: JobKind,
+    /// Seconds of media being processed
+    pub media_seconds: f64,
+    /// When the work started (after waiting for resources)
+    pub started_at: DateTime<Utc>,
+    /// Historical seconds of media processed per second (None = no history)
+    pub throughput: Option<f64>,
+}
+
 /// Single job status with progress and result tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobStatus {
     /// Unique job identifier
     pub id: String,
+    /// What the job does
+    pub job_type: JobType,
     /// Current job state
     pub state: JobState,
+    /// Times the job was started, resumptions after a restart included
+    pub attempts: u32,
     /// Progress percentage (0.0 - 100.0)
     pub progress: f32,
     /// Human-readable status message
@@ -50,6 +175,85 @@ pub struct JobStatus {
     /// When the job completed (if finished)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    /// Work being processed (when reported by the job)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workload: Option<JobWorkload>,
+    /// Estimated completion time of an active job
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_completion: Option<DateTime<Utc>>,
+    /// Estimated seconds until an active job completes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_seconds_remaining: Option<f64>,
+    /// Request the job is resumed from after a restart
+    #[serde(skip)]
+    payload: Option<serde_json::Value>,
+}
+
+impl JobStatus {
+    fn new(id: String, job_type: JobType) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            job_type,
+            state: JobState::Pending,
+            attempts: 1,
+            progress: 0.0,
+            message: None,
+            result: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            workload: None,
+            estimated_completion: None,
+            estimated_seconds_remaining: None,
+            payload: None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.state.is_active()
+    }
+
+    fn to_stored(&self) -> StoredJob {
+        StoredJob {
+            id: self.id.clone(),
+            job_type: self.job_type.as_str().to_string(),
+            state: self.state.as_str().to_string(),
+            priority: self.job_type.priority(),
+            attempts: self.attempts,
+            payload: self.payload.clone(),
+            status: serde_json::to_value(self).unwrap_or_default(),
+            created_at: self.created_at,
+        }
+    }
+
+    /// Fills in the completion estimate of an active job
+    ///
+    /// The expected duration is the workload divided by the historical
+    /// throughput. A job running over it is extrapolated from its progress.
+    fn with_estimate(mut self, now: DateTime<Utc>) -> Self {
+        let Some(workload) = self.workload.as_ref().filter(|_| self.is_active()) else {
+            return self;
+        };
+        let Some(throughput) = workload.throughput.filter(|t| *t > 0.0) else {
+            return self;
+        };
+
+        let elapsed = (now - workload.started_at).num_milliseconds() as f64 / 1000.0;
+        let mut remaining = workload.media_seconds / throughput - elapsed;
+        if remaining <= 0.0 {
+            let progress = self.progress as f64;
+            if progress <= 0.0 || progress >= 100.0 {
+                return self;
+            }
+            remaining = elapsed * (100.0 - progress) / progress;
+        }
+
+        self.estimated_seconds_remaining = Some(remaining);
+        self.estimated_completion = Some(now + chrono::Duration::milliseconds((remaining * 1000.0) as i64));
+        self
+    }
 }
 
 /// Batch job status for multi-item operations
@@ -57,8 +261,12 @@ pub struct JobStatus {
 pub struct BatchJobStatus {
     /// Unique batch job identifier
     pub id: String,
+    /// What the batch does
+    pub job_type: JobType,
     /// Current batch state
     pub state: JobState,
+    /// Times the batch was started, resumptions after a restart included
+    pub attempts: u32,
     /// Total number of items to process
     pub total: usize,
     /// Number of items completed successfully
@@ -75,18 +283,79 @@ pub struct BatchJobStatus {
     /// When the batch job completed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    /// Why the batch stopped before processing every item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Request the batch is resumed from after a restart
+    #[serde(skip)]
+    payload: Option<serde_json::Value>,
+}
+
+impl BatchJobStatus {
+    /// Items processed so far, successfully or not
+    pub fn processed(&self) -> usize {
+        self.completed + self.failed
+    }
+
+    fn to_stored(&self) -> StoredJob {
+        StoredJob {
+            id: self.id.clone(),
+            job_type: self.job_type.as_str().to_string(),
+            state: self.state.as_str().to_string(),
+            priority: self.job_type.priority(),
+            attempts: self.attempts,
+            payload: self.payload.clone(),
+            status: serde_json::to_value(self).unwrap_or_default(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// A single or batch job
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum JobEntry {
+    Job(JobStatus),
+    Batch(BatchJobStatus),
+}
+
+impl JobEntry {
+    fn from_stored(stored: StoredJob) -> Result<Self, RepositoryError> {
+        let batch = JobType::parse(&stored.job_type).is_some_and(|t| t.is_batch());
+        Ok(if batch {
+            let mut status: BatchJobStatus = serde_json::from_value(stored.status)?;
+            status.payload = stored.payload;
+            JobEntry::Batch(status)
+        } else {
+            let mut status: JobStatus = serde_json::from_value(stored.status)?;
+            status.payload = stored.payload;
+            JobEntry::Job(status)
+        })
+    }
+}
+
+/// A job a restart interrupted, to be run again
+#[derive(Debug, Clone)]
+pub struct InterruptedJob {
+    pub id: String,
+    pub job_type: JobType,
+    /// Request the job was created with
+    pub payload: serde_json::Value,
 }
 
-/// In-memory job store
+/// Job store
 ///
-/// Thread-safe storage for job status tracking.
-/// Jobs are stored in memory and cleared after a configurable retention period.
-#[derive(Debug)]
+/// Thread-safe storage for job status tracking. Jobs are kept in memory,
+/// and in the queue repository when configured.
 pub struct JobStore {
     /// Single jobs (subtitle generation for one media)
     jobs: Arc<RwLock<HashMap<String, JobStatus>>>,
     /// Batch jobs (series/season generation)
     batch_jobs: Arc<RwLock<HashMap<String, BatchJobStatus>>>,
+    /// Throughput of finished jobs (None = no completion estimates)
+    history: Option<Arc<dyn JobHistoryRepository>>,
+    /// Stored jobs (None = jobs are lost on restart)
+    queue: Option<Arc<dyn JobQueueRepository>>,
 }
 
 impl JobStore {
@@ -95,55 +364,142 @@ impl JobStore {
         Self {
             jobs: Arc::new(RwLock::new(HashMap::new())),
             batch_jobs: Arc::new(RwLock::new(HashMap::new())),
+            history: None,
+            queue: None,
+        }
+    }
+
+    /// Records finished jobs and estimates completion times from them
+    pub fn with_history(mut self, history: Arc<dyn JobHistoryRepository>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Stores jobs, so they outlive the process and are resumed after a
+    /// restart
+    pub fn with_queue(mut self, queue: Arc<dyn JobQueueRepository>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Stores a job's current state in the queue
+    async fn persist(&self, job: StoredJob) {
+        if let Some(queue) = &self.queue {
+            if let Err(e) = queue.save(&job).await {
+                tracing::warn!("Failed to store {} job {}: {}", job.job_type, job.id, e);
+            }
+        }
+    }
+
+    /// Stores a single job after a change, if it exists
+    async fn persist_job(&self, job_id: &str) {
+        let stored = self.jobs.read().await.get(job_id).map(JobStatus::to_stored);
+        if let Some(stored) = stored {
+            self.persist(stored).await;
+        }
+    }
+
+    /// Stores a batch job after a change, if it exists
+    async fn persist_batch(&self, job_id: &str) {
+        let stored = self.batch_jobs.read().await.get(job_id).map(BatchJobStatus::to_stored);
+        if let Some(stored) = stored {
+            self.persist(stored).await;
         }
     }
 
     // ========== Single Job Operations ==========
 
     /// Creates a new job and returns its ID
-    pub async fn create_job(&self) -> String {
-        let id = uuid::Uuid::new_v4().to_string();
-        let now = Utc::now();
-
-        let job = JobStatus {
-            id: id.clone(),
-            state: JobState::Pending,
-            progress: 0.0,
-            message: None,
-            result: None,
-            error: None,
-            created_at: now,
-            updated_at: now,
-            completed_at: None,
-        };
+    ///
+    /// The job is not resumed after a restart; it fails instead.
+    pub async fn create_job(&self, job_type: JobType) -> String {
+        self.insert_job(JobStatus::new(uuid::Uuid::new_v4().to_string(), job_type)).await
+    }
 
-        self.jobs.write().await.insert(id.clone(), job);
-        id
+    /// Creates a job that is run again from `payload` if a restart
+    /// interrupts it, and returns its ID
+    pub async fn create_resumable_job<T: Serialize>(&self, job_type: JobType, payload: &T) -> String {
+        let mut job = JobStatus::new(uuid::Uuid::new_v4().to_string(), job_type);
+        job.payload = serde_json::to_value(payload).ok();
+        self.insert_job(job).await
     }
 
     /// Creates a job with a specific ID (for predictable testing)
     pub async fn create_job_with_id(&self, id: String) -> String {
-        let now = Utc::now();
-
-        let job = JobStatus {
-            id: id.clone(),
-            state: JobState::Pending,
-            progress: 0.0,
-            message: None,
-            result: None,
-            error: None,
-            created_at: now,
-            updated_at: now,
-            completed_at: None,
-        };
+        self.insert_job(JobStatus::new(id, JobType::Subtitle)).await
+    }
 
+    async fn insert_job(&self, job: JobStatus) -> String {
+        let id = job.id.clone();
+        let stored = job.to_stored();
         self.jobs.write().await.insert(id.clone(), job);
+        self.persist(stored).await;
         id
     }
 
-    /// Gets the status of a job
+    /// Gets the status of a job, with its completion estimate
+    ///
+    /// Jobs from before a restart come from the queue.
     pub async fn get_job(&self, job_id: &str) -> Option<JobStatus> {
-        self.jobs.read().await.get(job_id).cloned()
+        let job = self.jobs.read().await.get(job_id).cloned();
+        if let Some(job) = job {
+            return Some(job.with_estimate(Utc::now()));
+        }
+        match self.find_stored(job_id).await? {
+            JobEntry::Job(job) => Some(job),
+            JobEntry::Batch(_) => None,
+        }
+    }
+
+    /// Gets a job from the queue
+    async fn find_stored(&self, job_id: &str) -> Option<JobEntry> {
+        let stored = match self.queue.as_ref()?.find(job_id).await {
+            Ok(stored) => stored?,
+            Err(e) => {
+                tracing::warn!("Failed to load job {}: {}", job_id, e);
+                return None;
+            }
+        };
+        JobEntry::from_stored(stored)
+            .map_err(|e| tracing::warn!("Failed to read job {}: {}", job_id, e))
+            .ok()
+    }
+
+    /// Lists pending and processing jobs with their completion estimates
+    pub async fn active_jobs(&self) -> Vec<JobStatus> {
+        let now = Utc::now();
+        let mut jobs: Vec<JobStatus> = self.jobs.read().await.values()
+            .filter(|j| j.is_active())
+            .map(|j| j.clone().with_estimate(now))
+            .collect();
+        jobs.sort_by_key(|j| j.created_at);
+        jobs
+    }
+
+    /// Sets the work a job is processing, starting its clock
+    ///
+    /// Call once the job stops waiting for resources (e.g. the GPU), so
+    /// queueing time does not count as processing.
+    pub async fn set_workload(&self, job_id: &str, kind: JobKind, media_seconds: f64) {
+        let throughput = match &self.history {
+            Some(history) => history.throughput(kind.as_str(), THROUGHPUT_SAMPLES).await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to load {} job throughput: {}", kind.as_str(), e);
+                None
+            }),
+            None => None,
+        };
+        if let Some(job) = self.jobs.write().await.get_mut(job_id) {
+            job.workload = Some(JobWorkload { kind, media_seconds, started_at: Utc::now(), throughput });
+            job.updated_at = Utc::now();
+        }
+    }
+
+    /// Clears the workload of a job whose remaining work is not typical of
+    /// its kind, so it is neither estimated nor recorded
+    pub async fn clear_workload(&self, job_id: &str) {
+        if let Some(job) = self.jobs.write().await.get_mut(job_id) {
+            job.workload = None;
+        }
     }
 
     /// Marks a job as processing
@@ -152,9 +508,12 @@ impl JobStore {
             job.state = JobState::Processing;
             job.updated_at = Utc::now();
         }
+        self.persist_job(job_id).await;
     }
 
     /// Updates job progress (0.0 - 100.0)
+    ///
+    /// Progress is not stored; a resumed job starts over.
     pub async fn update_progress(&self, job_id: &str, progress: f32, message: Option<&str>) {
         if let Some(job) = self.jobs.write().await.get_mut(job_id) {
             job.progress = progress.clamp(0.0, 100.0);
@@ -164,13 +523,31 @@ impl JobStore {
     }
 
     /// Marks a job as completed with a result
+    ///
+    /// The run is added to the job history if the job had a workload.
     pub async fn complete_job<T: Serialize>(&self, job_id: &str, result: &T) {
+        let mut run = None;
         if let Some(job) = self.jobs.write().await.get_mut(job_id) {
+            let now = Utc::now();
             job.state = JobState::Completed;
             job.progress = 100.0;
             job.result = Some(serde_json::to_value(result).unwrap_or(serde_json::Value::Null));
-            job.completed_at = Some(Utc::now());
-            job.updated_at = Utc::now();
+            job.completed_at = Some(now);
+            job.updated_at = now;
+            run = job.workload.as_ref().map(|w| JobRun {
+                kind: w.kind.as_str().to_string(),
+                media_seconds: w.media_seconds,
+                elapsed_seconds: (now - w.started_at).num_milliseconds() as f64 / 1000.0,
+            });
+        }
+        self.persist_job(job_id).await;
+
+        if let (Some(history), Some(run)) = (&self.history, run) {
+            if run.media_seconds > 0.0 && run.elapsed_seconds > 0.0 {
+                if let Err(e) = history.record(&run).await {
+                    tracing::warn!("Failed to record {} job run: {}", run.kind, e);
+                }
+            }
         }
     }
 
@@ -182,31 +559,55 @@ impl JobStore {
             job.completed_at = Some(Utc::now());
             job.updated_at = Utc::now();
         }
+        self.persist_job(job_id).await;
     }
 
     /// Cancels a job
     pub async fn cancel_job(&self, job_id: &str) -> bool {
+        let mut cancelled = false;
         if let Some(job) = self.jobs.write().await.get_mut(job_id) {
-            if job.state == JobState::Pending || job.state == JobState::Processing {
+            if job.is_active() {
                 job.state = JobState::Cancelled;
                 job.completed_at = Some(Utc::now());
                 job.updated_at = Utc::now();
-                return true;
+                cancelled = true;
             }
         }
-        false
+        if cancelled {
+            self.persist_job(job_id).await;
+        }
+        cancelled
     }
 
     // ========== Batch Job Operations ==========
 
     /// Creates a new batch job and returns its ID
-    pub async fn create_batch_job(&self, total_items: usize) -> String {
+    ///
+    /// The batch is not resumed after a restart; it fails instead.
+    pub async fn create_batch_job(&self, job_type: JobType, total_items: usize) -> String {
+        self.insert_batch_job(job_type, total_items, None).await
+    }
+
+    /// Creates a batch job that is continued from `payload` if a restart
+    /// interrupts it, and returns its ID
+    pub async fn create_resumable_batch_job<T: Serialize>(
+        &self,
+        job_type: JobType,
+        total_items: usize,
+        payload: &T,
+    ) -> String {
+        self.insert_batch_job(job_type, total_items, serde_json::to_value(payload).ok()).await
+    }
+
+    async fn insert_batch_job(&self, job_type: JobType, total_items: usize, payload: Option<serde_json::Value>) -> String {
         let id = uuid::Uuid::new_v4().to_string();
         let now = Utc::now();
 
         let batch = BatchJobStatus {
             id: id.clone(),
+            job_type,
             state: JobState::Processing,
+            attempts: 1,
             total: total_items,
             completed: 0,
             failed: 0,
@@ -214,15 +615,28 @@ impl JobStore {
             created_at: now,
             updated_at: now,
             completed_at: None,
+            error: None,
+            payload,
         };
 
+        let stored = batch.to_stored();
         self.batch_jobs.write().await.insert(id.clone(), batch);
+        self.persist(stored).await;
         id
     }
 
     /// Gets the status of a batch job
+    ///
+    /// Batches from before a restart come from the queue.
     pub async fn get_batch_job(&self, job_id: &str) -> Option<BatchJobStatus> {
-        self.batch_jobs.read().await.get(job_id).cloned()
+        let batch = self.batch_jobs.read().await.get(job_id).cloned();
+        if batch.is_some() {
+            return batch;
+        }
+        match self.find_stored(job_id).await? {
+            JobEntry::Batch(batch) => Some(batch),
+            JobEntry::Job(_) => None,
+        }
     }
 
     /// Updates batch job progress (increments completed count)
@@ -231,6 +645,7 @@ impl JobStore {
             batch.completed = completed;
             batch.updated_at = Utc::now();
         }
+        self.persist_batch(job_id).await;
     }
 
     /// Records an error for a specific item in a batch
@@ -240,6 +655,7 @@ impl JobStore {
             batch.errors.insert(media_id, error);
             batch.updated_at = Utc::now();
         }
+        self.persist_batch(job_id).await;
     }
 
     /// Marks a batch job as completed
@@ -255,19 +671,160 @@ impl JobStore {
             batch.completed_at = Some(Utc::now());
             batch.updated_at = Utc::now();
         }
+        self.persist_batch(job_id).await;
     }
 
     /// Cancels a batch job
     pub async fn cancel_batch_job(&self, job_id: &str) -> bool {
+        let mut cancelled = false;
         if let Some(batch) = self.batch_jobs.write().await.get_mut(job_id) {
             if batch.state == JobState::Processing {
                 batch.state = JobState::Cancelled;
                 batch.completed_at = Some(Utc::now());
                 batch.updated_at = Utc::now();
-                return true;
+                cancelled = true;
             }
         }
-        false
+        if cancelled {
+            self.persist_batch(job_id).await;
+        }
+        cancelled
+    }
+
+    /// Fails a single or batch job that cannot be run (e.g. its payload no
+    /// longer parses)
+    pub async fn abandon_job(&self, job_id: &str, error: &str) {
+        let is_batch = self.batch_jobs.read().await.contains_key(job_id);
+        if !is_batch {
+            return self.fail_job(job_id, error).await;
+        }
+        if let Some(batch) = self.batch_jobs.write().await.get_mut(job_id) {
+            batch.state = JobState::Failed;
+            batch.error = Some(error.to_string());
+            batch.completed_at = Some(Utc::now());
+            batch.updated_at = Utc::now();
+        }
+        self.persist_batch(job_id).await;
+    }
+
+    // ========== Queue Operations ==========
+
+    /// Loads the jobs a restart interrupted and prunes old finished ones
+    ///
+    /// Resumable jobs come back pending (batches processing, with their
+    /// progress) with the attempt counted, and are returned to be run
+    /// again, highest priority first. Jobs created without a payload, and
+    /// those already started [`MAX_ATTEMPTS`] times, fail instead.
+    pub async fn recover_interrupted(&self) -> Result<Vec<InterruptedJob>, RepositoryError> {
+        let Some(queue) = &self.queue else { return Ok(Vec::new()) };
+        let cutoff = Utc::now() - chrono::Duration::days(FINISHED_JOB_RETENTION_DAYS);
+        let pruned = queue.delete_finished_before(cutoff).await?;
+        if pruned > 0 {
+            tracing::debug!("Pruned {} finished jobs", pruned);
+        }
+
+        let mut resumed = Vec::new();
+        for stored in queue.find_unfinished().await? {
+            let id = stored.id.clone();
+            let entry = match JobEntry::from_stored(stored) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    tracing::warn!("Dropping unreadable job {}: {}", id, e);
+                    continue;
+                }
+            };
+            let now = Utc::now();
+            match entry {
+                JobEntry::Job(mut job) => {
+                    let payload = job.payload.clone().filter(|_| job.attempts < MAX_ATTEMPTS);
+                    job.updated_at = now;
+                    match payload {
+                        Some(payload) => {
+                            job.attempts += 1;
+                            job.state = JobState::Pending;
+                            job.progress = 0.0;
+                            job.message = None;
+                            job.workload = None;
+                            resumed.push(InterruptedJob { id: id.clone(), job_type: job.job_type, payload });
+                        }
+                        None => {
+                            job.state = JobState::Failed;
+                            job.error = Some(INTERRUPTED.to_string());
+                            job.completed_at = Some(now);
+                        }
+                    }
+                    let stored = job.to_stored();
+                    self.jobs.write().await.insert(id, job);
+                    self.persist(stored).await;
+                }
+                JobEntry::Batch(mut batch) => {
+                    let payload = batch.payload.clone().filter(|_| batch.attempts < MAX_ATTEMPTS);
+                    batch.updated_at = now;
+                    match payload {
+                        Some(payload) => {
+                            batch.attempts += 1;
+                            batch.state = JobState::Processing;
+                            resumed.push(InterruptedJob { id: id.clone(), job_type: batch.job_type, payload });
+                        }
+                        None => {
+                            batch.state = JobState::Failed;
+                            batch.error = Some(INTERRUPTED.to_string());
+                            batch.completed_at = Some(now);
+                        }
+                    }
+                    let stored = batch.to_stored();
+                    self.batch_jobs.write().await.insert(id, batch);
+                    self.persist(stored).await;
+                }
+            }
+        }
+        Ok(resumed)
+    }
+
+    /// Lists jobs of every type, newest first
+    ///
+    /// Active jobs come with their current progress and completion
+    /// estimate. Without a queue only jobs since the start are listed.
+    pub async fn list_jobs(&self, filter: &JobFilter) -> Result<Vec<JobEntry>, RepositoryError> {
+        let now = Utc::now();
+        let live = |entry: JobEntry, jobs: &HashMap<String, JobStatus>, batches: &HashMap<String, BatchJobStatus>| match entry {
+            JobEntry::Job(job) => JobEntry::Job(jobs.get(&job.id).cloned().unwrap_or(job).with_estimate(now)),
+            JobEntry::Batch(batch) => JobEntry::Batch(batches.get(&batch.id).cloned().unwrap_or(batch)),
+        };
+
+        let entries: Vec<JobEntry> = match &self.queue {
+            Some(queue) => queue
+                .list(filter)
+                .await?
+                .into_iter()
+                .map(JobEntry::from_stored)
+                .collect::<Result<_, _>>()?,
+            None => {
+                let mut entries: Vec<JobEntry> = self.jobs.read().await.values().cloned().map(JobEntry::Job)
+                    .chain(self.batch_jobs.read().await.values().cloned().map(JobEntry::Batch))
+                    .filter(|entry| {
+                        let (job_type, state) = match entry {
+                            JobEntry::Job(job) => (job.job_type, job.state),
+                            JobEntry::Batch(batch) => (batch.job_type, batch.state),
+                        };
+                        filter.job_type.as_deref().is_none_or(|t| t == job_type.as_str())
+                            && filter.state.as_deref().is_none_or(|s| s == state.as_str())
+                    })
+                    .collect();
+                entries.sort_by_key(|entry| std::cmp::Reverse(match entry {
+                    JobEntry::Job(job) => job.created_at,
+                    JobEntry::Batch(batch) => batch.created_at,
+                }));
+                if filter.limit > 0 {
+                    entries.truncate(filter.limit as usize);
+                }
+                entries
+            }
+        };
+
+        let jobs = self.jobs.read().await;
+        let batches = self.batch_jobs.read().await;
+        Ok(entries.into_iter().map(|entry| live(entry, &jobs, &batches)).collect())
     }
 
     /// Checks if a batch job has been cancelled
@@ -310,7 +867,7 @@ impl JobStore {
     /// Returns count of active jobs (pending + processing)
     pub async fn active_job_count(&self) -> usize {
         self.jobs.read().await.values()
-            .filter(|j| j.state == JobState::Pending || j.state == JobState::Processing)
+            .filter(|j| j.is_active())
             .count()
     }
 
@@ -320,6 +877,16 @@ impl JobStore {
             .filter(|b| b.state == JobState::Processing)
             .count()
     }
+
+    /// Lists processing batch jobs, oldest first
+    pub async fn active_batch_jobs(&self) -> Vec<BatchJobStatus> {
+        let mut batches: Vec<BatchJobStatus> = self.batch_jobs.read().await.values()
+            .filter(|b| b.state == JobState::Processing)
+            .cloned()
+            .collect();
+        batches.sort_by_key(|b| b.created_at);
+        batches
+    }
 }
 
 impl Default for JobStore {
@@ -333,6 +900,8 @@ impl Clone for JobStore {
         Self {
             jobs: self.jobs.clone(),
             batch_jobs: self.batch_jobs.clone(),
+            history: self.history.clone(),
+            queue: self.queue.clone(),
         }
     }
 }
@@ -346,7 +915,7 @@ mod tests {
         let store = JobStore::new();
 
         // Create job
-        let job_id = store.create_job().await;
+        let job_id = store.create_job(JobType::Subtitle).await;
         let job = store.get_job(&job_id).await.unwrap();
         assert_eq!(job.state, JobState::Pending);
         assert_eq!(job.progress, 0.0);
@@ -378,7 +947,7 @@ mod tests {
     async fn test_job_failure() {
         let store = JobStore::new();
 
-        let job_id = store.create_job().await;
+        let job_id = store.create_job(JobType::Subtitle).await;
         store.start_job(&job_id).await;
         store.fail_job(&job_id, "Something went wrong").await;
 
@@ -392,7 +961,7 @@ mod tests {
         let store = JobStore::new();
 
         // Create batch job for 10 items
-        let batch_id = store.create_batch_job(10).await;
+        let batch_id = store.create_batch_job(JobType::SubtitleBatch, 10).await;
         let batch = store.get_batch_job(&batch_id).await.unwrap();
         assert_eq!(batch.state, JobState::Processing);
         assert_eq!(batch.total, 10);
@@ -416,11 +985,43 @@ mod tests {
         assert_eq!(batch.state, JobState::Completed);
     }
 
+    #[test]
+    fn test_completion_estimate() {
+        let job = |progress: f32, started_secs_ago: i64| {
+            let now = Utc::now();
+            let mut job = JobStatus::new("job".to_string(), JobType::Subtitle);
+            job.state = JobState::Processing;
+            job.progress = progress;
+            job.workload = Some(JobWorkload {
+                kind: JobKind::Subtitle,
+                media_seconds: 6000.0,
+                started_at: now - chrono::Duration::seconds(started_secs_ago),
+                throughput: Some(10.0),
+            });
+            (job, now)
+        };
+
+        // 600s expected, 100s in
+        let (status, now) = job(25.0, 100);
+        let status = status.with_estimate(now);
+        assert!((status.estimated_seconds_remaining.unwrap() - 500.0).abs() < 0.01);
+        assert_eq!(status.estimated_completion, Some(now + chrono::Duration::seconds(500)));
+
+        // Over the estimate, the progress takes over
+        let (status, now) = job(80.0, 800);
+        assert!((status.with_estimate(now).estimated_seconds_remaining.unwrap() - 200.0).abs() < 0.01);
+
+        // No history, no estimate
+        let (mut status, now) = job(25.0, 100);
+        status.workload.as_mut().unwrap().throughput = None;
+        assert!(status.with_estimate(now).estimated_completion.is_none());
+    }
+
     #[tokio::test]
     async fn test_job_cancellation() {
         let store = JobStore::new();
 
-        let job_id = store.create_job().await;
+        let job_id = store.create_job(JobType::Subtitle).await;
         store.start_job(&job_id).await;
 
         assert!(store.cancel_job(&job_id).await);
@@ -430,4 +1031,54 @@ mod tests {
         // Cannot cancel again
         assert!(!store.cancel_job(&job_id).await);
     }
+
+    #[tokio::test]
+    async fn test_recover_interrupted() {
+        use crate::infrastructure::database::schema::initialize_schema;
+        use crate::infrastructure::persistence::sqlite::SqliteJobQueueRepository;
+        use sqlx::sqlite::SqlitePoolOptions;
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let queue: Arc<dyn JobQueueRepository> = Arc::new(SqliteJobQueueRepository::new(pool));
+
+        let before = JobStore::new().with_queue(queue.clone());
+        let generation = before.create_resumable_job(JobType::Subtitle, &serde_json::json!({ "media_id": 7 })).await;
+        before.start_job(&generation).await;
+        before.update_progress(&generation, 40.0, Some("Transcribing...")).await;
+        let download = before.create_job(JobType::ModelDownload).await;
+        let batch = before.create_resumable_batch_job(JobType::SubtitleBatch, 3, &serde_json::json!({ "episodes": [1, 2, 3] })).await;
+        before.update_batch_progress(&batch, 1).await;
+        before.add_batch_error(&batch, 2, "No audio".to_string()).await;
+        let done = before.create_resumable_job(JobType::Translation, &serde_json::json!({})).await;
+        before.complete_job(&done, &"ok").await;
+
+        // A restart: only the queue is left
+        let after = JobStore::new().with_queue(queue.clone());
+        let resumed = after.recover_interrupted().await.unwrap();
+        assert_eq!(resumed.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(), vec![generation.as_str(), batch.as_str()]);
+        assert_eq!(resumed[0].payload, serde_json::json!({ "media_id": 7 }));
+
+        let job = after.get_job(&generation).await.unwrap();
+        assert_eq!((job.state, job.attempts, job.progress), (JobState::Pending, 2, 0.0));
+        let job = after.get_job(&download).await.unwrap();
+        assert_eq!((job.state, job.error.as_deref()), (JobState::Failed, Some(INTERRUPTED)));
+        let batch_status = after.get_batch_job(&batch).await.unwrap();
+        assert_eq!((batch_status.state, batch_status.processed(), batch_status.attempts), (JobState::Processing, 2, 2));
+        assert_eq!(after.get_job(&done).await.unwrap().state, JobState::Completed);
+
+        let failed = after.list_jobs(&JobFilter { state: Some("failed".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(after.list_jobs(&JobFilter::default()).await.unwrap().len(), 4);
+
+        // Jobs that keep getting interrupted are given up on
+        after.recover_interrupted().await.unwrap();
+        let last = JobStore::new().with_queue(queue);
+        assert!(last.recover_interrupted().await.unwrap().is_empty());
+        assert_eq!(last.get_job(&generation).await.unwrap().state, JobState::Failed);
+    }
 }
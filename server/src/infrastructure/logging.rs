@@ -0,0 +1,206 @@
+//! Logging Setup
+//!
+//! Installs the global tracing subscriber. Output is human-readable text or
+//! one JSON object per line (`LOG_FORMAT=json`) for log collectors. Levels
+//! use the `RUST_LOG` filter syntax (`info,homeflixd::infrastructure::external::tmdb=debug`)
+//! and can be changed at runtime through [`LogLevelHandle`]. The filter only
+//! applies to the output; slow query tracking sees sqlx warnings regardless.
+
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{DefaultFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+use crate::infrastructure::slow_operations::{SlowOperationTracker, SlowQueryLayer};
+
+/// Filter used when `RUST_LOG` is not set
+pub const DEFAULT_FILTER: &str = "info";
+
+/// Log output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Changes the active log filter at runtime
+pub struct LogLevelHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogLevelHandle {
+    /// Active filter directives
+    pub fn current(&self) -> String {
+        self.handle.with_current(|filter| filter.to_string()).unwrap_or_default()
+    }
+
+    /// Replaces the filter; invalid directives leave the current one active
+    pub fn set(&self, directives: &str) -> Result<String, String> {
+        let filter = EnvFilter::try_new(directives.trim()).map_err(|e| e.to_string())?;
+        self.handle.reload(filter).map_err(|e| e.to_string())?;
+        let current = self.current();
+        tracing::info!("Log filter changed to {}", current);
+        Ok(current)
+    }
+}
+
+/// Installs the global subscriber
+///
+/// Falls back to [`DEFAULT_FILTER`] when `directives` cannot be parsed.
+pub fn init(
+    format: LogFormat,
+    directives: Option<&str>,
+    slow_operations: Arc<SlowOperationTracker>,
+) -> anyhow::Result<LogLevelHandle> {
+    let (filter, invalid) = match directives.map(EnvFilter::try_new) {
+        Some(Ok(filter)) => (filter, None),
+        Some(Err(e)) => (EnvFilter::new(DEFAULT_FILTER), Some(e)),
+        None => (EnvFilter::new(DEFAULT_FILTER), None),
+    };
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let output: Box<dyn Layer<Registry> + Send + Sync> = match format {
+        LogFormat::Json => Box::new(json_layer()),
+        LogFormat::Text => Box::new(tracing_subscriber::fmt::layer()),
+    };
+    tracing_subscriber::registry()
+        .with(output.with_filter(filter))
+        .with(SlowQueryLayer::new(slow_operations).with_filter(filter_fn(SlowQueryLayer::interested)))
+        .try_init()?;
+
+    if let Some(e) = invalid {
+        tracing::warn!("Invalid RUST_LOG filter, using {}: {}", DEFAULT_FILTER, e);
+    }
+    Ok(LogLevelHandle { handle })
+}
+
+/// Formatting layer writing [`JsonFormat`] lines to stdout
+///
+/// Span fields are formatted before the event is, so colors must be off.
+fn json_layer<S>() -> tracing_subscriber::fmt::Layer<S, DefaultFields, JsonFormat>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer().with_ansi(false).event_format(JsonFormat)
+}
+
+/// Formats events as single-line JSON objects
+///
+/// `{"timestamp", "level", "target", "message", <event fields>, "spans"}`;
+/// spans carry their name and formatted fields.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut object = Map::new();
+        object.insert("timestamp".to_string(), chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true).into());
+        object.insert("level".to_string(), metadata.level().as_str().into());
+        object.insert("target".to_string(), metadata.target().into());
+        event.record(&mut JsonVisitor(&mut object));
+
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope.from_root()
+                .map(|span| {
+                    let mut entry = Map::new();
+                    entry.insert("name".to_string(), span.name().into());
+                    if let Some(fields) = span.extensions().get::<FormattedFields<N>>() {
+                        if !fields.fields.is_empty() {
+                            entry.insert("fields".to_string(), fields.fields.as_str().into());
+                        }
+                    }
+                    Value::Object(entry)
+                })
+                .collect();
+            if !spans.is_empty() {
+                object.insert("spans".to_string(), spans.into());
+            }
+        }
+
+        let line = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Collects event fields into a JSON object
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_format() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(json_layer().with_writer(move || writer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", uri = "/v2/media");
+            let _entered = span.enter();
+            tracing::info!(media_id = 42, "Scanned \"{}\"", "Film");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "Scanned \"Film\"");
+        assert_eq!(line["media_id"], 42);
+        assert_eq!(line["spans"][0]["name"], "request");
+        assert_eq!(line["spans"][0]["fields"], "uri=\"/v2/media\"");
+    }
+}
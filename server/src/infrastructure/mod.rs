@@ -19,6 +19,10 @@ pub mod subtitle;
 pub mod gpu;
 pub mod jobs;
 pub mod presets;
+pub mod health;
+pub mod logging;
+pub mod slow_operations;
+pub mod process_memory;
 
 pub use persistence::sqlite::*;
 pub use external::tmdb::*;
@@ -3,5 +3,6 @@
 // This module contains all repository implementations for data persistence.
 
 pub mod sqlite;
+pub mod notifying;
 
 pub use sqlite::*;
@@ -0,0 +1,568 @@
+//! Notifying repository decorators
+//!
+//! Wrap the media, series and collection repositories and publish a
+//! `LibraryChangedEvent` after every successful write. Reads are forwarded
+//! unchanged.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::domain::entities::{Collection, CollectionItem, Media, Series};
+use crate::domain::events::{LibraryChangeKind, LibraryChangedEvent, LibraryEntity};
+use crate::domain::repositories::{
+    CollectionOrder, CollectionRepository, MediaOrder, MediaRepository, SeriesOrder, SeriesRepository,
+};
+use crate::domain::value_objects::{ConfidenceScore, MediaType, VerificationStatus};
+use crate::interfaces::messaging::EventBus;
+use crate::shared::error::RepositoryError;
+
+/// Publishes a library change, logging (not failing) on bus errors
+async fn notify<E: EventBus>(bus: &E, entity: LibraryEntity, change: LibraryChangeKind, ids: Vec<i64>) {
+    if ids.is_empty() {
+        return;
+    }
+    if let Err(e) = bus.publish(LibraryChangedEvent::new(entity, change, ids)).await {
+        warn!("Failed to publish library changed event: {}", e);
+    }
+}
+
+/// Media repository that publishes library changes
+pub struct NotifyingMediaRepository<E: EventBus> {
+    inner: Arc<dyn MediaRepository>,
+    event_bus: Arc<E>,
+}
+
+impl<E: EventBus> NotifyingMediaRepository<E> {
+    /// Wraps `inner`, publishing changes on `event_bus`
+    pub fn new(inner: Arc<dyn MediaRepository>, event_bus: Arc<E>) -> Self {
+        Self { inner, event_bus }
+    }
+
+    async fn notify(&self, change: LibraryChangeKind, ids: Vec<i64>) {
+        notify(self.event_bus.as_ref(), LibraryEntity::Media, change, ids).await;
+    }
+}
+
+#[async_trait]
+impl<E: EventBus + 'static> MediaRepository for NotifyingMediaRepository<E> {
+    async fn find_by_id(&self, id: i64) -> Result<Option<Media>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_path(&self, path: &str) -> Result<Option<Media>, RepositoryError> {
+        self.inner.find_by_path(path).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_all().await
+    }
+
+    async fn find_page(
+        &self,
+        order: MediaOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Media>, i64), RepositoryError> {
+        self.inner.find_page(order, descending, offset, limit).await
+    }
+
+    async fn find_by_type(&self, media_type: MediaType) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_by_type(media_type).await
+    }
+
+    async fn find_by_series(&self, series_id: i64) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_by_series(series_id).await
+    }
+
+    async fn find_by_tmdb_id(&self, tmdb_id: i64) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_by_tmdb_id(tmdb_id).await
+    }
+
+    async fn find_by_season(&self, series_id: i64, season: i32) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_by_season(series_id, season).await
+    }
+
+    async fn find_unverified(&self) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_unverified().await
+    }
+
+    async fn find_by_confidence(&self, min_score: ConfidenceScore) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_by_confidence(min_score).await
+    }
+
+    async fn save(&self, media: &Media) -> Result<i64, RepositoryError> {
+        let id = self.inner.save(media).await?;
+        // Saving an entity that already has this ID is an update
+        let change = if media.id == Some(id) { LibraryChangeKind::Updated } else { LibraryChangeKind::Added };
+        self.notify(change, vec![id]).await;
+        Ok(id)
+    }
+
+    async fn update(&self, media: &Media) -> Result<(), RepositoryError> {
+        self.inner.update(media).await?;
+        self.notify(LibraryChangeKind::Updated, media.id.into_iter().collect()).await;
+        Ok(())
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), RepositoryError> {
+        self.inner.delete(id).await?;
+        self.notify(LibraryChangeKind::Removed, vec![id]).await;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, RepositoryError> {
+        self.inner.count().await
+    }
+
+    async fn count_by_type(&self, media_type: MediaType) -> Result<i64, RepositoryError> {
+        self.inner.count_by_type(media_type).await
+    }
+
+    async fn exists_by_path(&self, path: &str) -> Result<bool, RepositoryError> {
+        self.inner.exists_by_path(path).await
+    }
+
+    async fn update_progress(&self, media_id: i64, position: i64, watched: bool) -> Result<(), RepositoryError> {
+        self.inner.update_progress(media_id, position, watched).await?;
+        self.notify(LibraryChangeKind::WatchState, vec![media_id]).await;
+        Ok(())
+    }
+
+    async fn find_recent(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_recent(limit).await
+    }
+
+    async fn find_watched(&self) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_watched().await
+    }
+
+    async fn find_unwatched(&self) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_unwatched().await
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        media_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.search(query, media_type, limit).await
+    }
+
+    async fn get_progress(&self, media_id: i64) -> Result<Option<(i64, bool, String)>, RepositoryError> {
+        self.inner.get_progress(media_id).await
+    }
+
+    async fn mark_watched(&self, media_id: i64) -> Result<(), RepositoryError> {
+        self.inner.mark_watched(media_id).await?;
+        self.notify(LibraryChangeKind::WatchState, vec![media_id]).await;
+        Ok(())
+    }
+
+    async fn mark_unwatched(&self, media_id: i64) -> Result<(), RepositoryError> {
+        self.inner.mark_unwatched(media_id).await?;
+        self.notify(LibraryChangeKind::WatchState, vec![media_id]).await;
+        Ok(())
+    }
+
+    async fn set_watched_many(&self, media_ids: &[i64], watched: bool) -> Result<u64, RepositoryError> {
+        let changed = self.inner.set_watched_many(media_ids, watched).await?;
+        if changed > 0 {
+            self.notify(LibraryChangeKind::WatchState, media_ids.to_vec()).await;
+        }
+        Ok(changed)
+    }
+
+    async fn find_in_progress(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_in_progress(limit).await
+    }
+
+    async fn find_recent_movies(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_recent_movies(limit).await
+    }
+
+    async fn find_recent_episodes(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_recent_episodes(limit).await
+    }
+
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_missing_blurhashes(limit).await
+    }
+
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        self.inner.update_blurhashes(id, poster_blurhash, backdrop_blurhash).await?;
+        self.notify(LibraryChangeKind::Updated, vec![id]).await;
+        Ok(())
+    }
+
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        self.inner.find_missing_content_ratings(limit).await
+    }
+
+    async fn update_content_rating(
+        &self,
+        id: i64,
+        content_rating: &str,
+        content_warnings: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        self.inner.update_content_rating(id, content_rating, content_warnings).await?;
+        self.notify(LibraryChangeKind::Updated, vec![id]).await;
+        Ok(())
+    }
+
+    async fn update_paths(&self, paths: &[(i64, String)]) -> Result<u64, RepositoryError> {
+        let changed = self.inner.update_paths(paths).await?;
+        if changed > 0 {
+            self.notify(LibraryChangeKind::Updated, paths.iter().map(|(id, _)| *id).collect()).await;
+        }
+        Ok(changed)
+    }
+}
+
+/// Series repository that publishes library changes
+pub struct NotifyingSeriesRepository<E: EventBus> {
+    inner: Arc<dyn SeriesRepository>,
+    event_bus: Arc<E>,
+}
+
+impl<E: EventBus> NotifyingSeriesRepository<E> {
+    /// Wraps `inner`, publishing changes on `event_bus`
+    pub fn new(inner: Arc<dyn SeriesRepository>, event_bus: Arc<E>) -> Self {
+        Self { inner, event_bus }
+    }
+
+    async fn notify(&self, change: LibraryChangeKind, ids: Vec<i64>) {
+        notify(self.event_bus.as_ref(), LibraryEntity::Series, change, ids).await;
+    }
+}
+
+#[async_trait]
+impl<E: EventBus + 'static> SeriesRepository for NotifyingSeriesRepository<E> {
+    async fn find_by_id(&self, id: i64) -> Result<Option<Series>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_tmdb_id(&self, tmdb_id: i64) -> Result<Option<Series>, RepositoryError> {
+        self.inner.find_by_tmdb_id(tmdb_id).await
+    }
+
+    async fn find_by_title(&self, title: &str) -> Result<Option<Series>, RepositoryError> {
+        self.inner.find_by_title(title).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_all().await
+    }
+
+    async fn find_page(
+        &self,
+        order: SeriesOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Series>, i64), RepositoryError> {
+        self.inner.find_page(order, descending, offset, limit).await
+    }
+
+    async fn find_by_verification_status(
+        &self,
+        status: VerificationStatus,
+    ) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_by_verification_status(status).await
+    }
+
+    async fn find_by_confidence(
+        &self,
+        min_score: ConfidenceScore,
+    ) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_by_confidence(min_score).await
+    }
+
+    async fn save(&self, series: &Series) -> Result<i64, RepositoryError> {
+        let id = self.inner.save(series).await?;
+        let change = if series.id == Some(id) { LibraryChangeKind::Updated } else { LibraryChangeKind::Added };
+        self.notify(change, vec![id]).await;
+        Ok(id)
+    }
+
+    async fn update(&self, series: &Series) -> Result<(), RepositoryError> {
+        self.inner.update(series).await?;
+        self.notify(LibraryChangeKind::Updated, series.id.into_iter().collect()).await;
+        Ok(())
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), RepositoryError> {
+        self.inner.delete(id).await?;
+        self.notify(LibraryChangeKind::Removed, vec![id]).await;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, RepositoryError> {
+        self.inner.count().await
+    }
+
+    async fn exists_by_title(&self, title: &str) -> Result<bool, RepositoryError> {
+        self.inner.exists_by_title(title).await
+    }
+
+    async fn exists_by_tmdb_id(&self, tmdb_id: i64) -> Result<bool, RepositoryError> {
+        self.inner.exists_by_tmdb_id(tmdb_id).await
+    }
+
+    async fn find_recent(&self, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_recent(limit).await
+    }
+
+    async fn find_low_confidence(&self) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_low_confidence().await
+    }
+
+    async fn find_requires_review(&self) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_requires_review().await
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.search(query, limit).await
+    }
+
+    async fn find_recent_by_episode(&self, limit: usize) -> Result<Vec<(Series, String)>, RepositoryError> {
+        self.inner.find_recent_by_episode(limit).await
+    }
+
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_missing_blurhashes(limit).await
+    }
+
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        self.inner.update_blurhashes(id, poster_blurhash, backdrop_blurhash).await?;
+        self.notify(LibraryChangeKind::Updated, vec![id]).await;
+        Ok(())
+    }
+
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        self.inner.find_missing_content_ratings(limit).await
+    }
+
+    async fn update_content_rating(&self, id: i64, content_rating: &str) -> Result<(), RepositoryError> {
+        self.inner.update_content_rating(id, content_rating).await?;
+        self.notify(LibraryChangeKind::Updated, vec![id]).await;
+        Ok(())
+    }
+}
+
+/// Collection repository that publishes library changes
+///
+/// Item writes are reported as an update of their collection.
+pub struct NotifyingCollectionRepository<E: EventBus> {
+    inner: Arc<dyn CollectionRepository>,
+    event_bus: Arc<E>,
+}
+
+impl<E: EventBus> NotifyingCollectionRepository<E> {
+    /// Wraps `inner`, publishing changes on `event_bus`
+    pub fn new(inner: Arc<dyn CollectionRepository>, event_bus: Arc<E>) -> Self {
+        Self { inner, event_bus }
+    }
+
+    async fn notify(&self, change: LibraryChangeKind, ids: Vec<i64>) {
+        notify(self.event_bus.as_ref(), LibraryEntity::Collection, change, ids).await;
+    }
+}
+
+#[async_trait]
+impl<E: EventBus + 'static> CollectionRepository for NotifyingCollectionRepository<E> {
+    async fn find_by_id(&self, id: i64) -> Result<Option<Collection>, RepositoryError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Collection>, RepositoryError> {
+        self.inner.find_by_name(name).await
+    }
+
+    async fn find_by_tmdb_id(&self, tmdb_id: i64) -> Result<Option<Collection>, RepositoryError> {
+        self.inner.find_by_tmdb_id(tmdb_id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_all().await
+    }
+
+    async fn find_page(
+        &self,
+        order: CollectionOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Collection>, i64), RepositoryError> {
+        self.inner.find_page(order, descending, offset, limit).await
+    }
+
+    async fn find_by_type(&self, collection_type: &str) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_by_type(collection_type).await
+    }
+
+    async fn save(&self, collection: &Collection) -> Result<i64, RepositoryError> {
+        let id = self.inner.save(collection).await?;
+        let change = if collection.id == Some(id) { LibraryChangeKind::Updated } else { LibraryChangeKind::Added };
+        self.notify(change, vec![id]).await;
+        Ok(id)
+    }
+
+    async fn update(&self, collection: &Collection) -> Result<(), RepositoryError> {
+        self.inner.update(collection).await?;
+        self.notify(LibraryChangeKind::Updated, collection.id.into_iter().collect()).await;
+        Ok(())
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), RepositoryError> {
+        self.inner.delete(id).await?;
+        self.notify(LibraryChangeKind::Removed, vec![id]).await;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, RepositoryError> {
+        self.inner.count().await
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, RepositoryError> {
+        self.inner.exists_by_name(name).await
+    }
+
+    async fn exists_by_tmdb_id(&self, tmdb_id: i64) -> Result<bool, RepositoryError> {
+        self.inner.exists_by_tmdb_id(tmdb_id).await
+    }
+
+    async fn find_auto(&self) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_auto().await
+    }
+
+    async fn find_preset(&self) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_preset().await
+    }
+
+    async fn find_custom(&self) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_custom().await
+    }
+
+    async fn update_counts(&self, id: i64, total: i32, available: i32) -> Result<(), RepositoryError> {
+        self.inner.update_counts(id, total, available).await?;
+        self.notify(LibraryChangeKind::Updated, vec![id]).await;
+        Ok(())
+    }
+
+    async fn find_items(&self, collection_id: i64) -> Result<Vec<CollectionItem>, RepositoryError> {
+        self.inner.find_items(collection_id).await
+    }
+
+    async fn save_item(&self, item: &CollectionItem) -> Result<i64, RepositoryError> {
+        let id = self.inner.save_item(item).await?;
+        self.notify(LibraryChangeKind::Updated, vec![item.collection_id]).await;
+        Ok(id)
+    }
+
+    async fn save_items(&self, items: &[CollectionItem]) -> Result<usize, RepositoryError> {
+        let saved = self.inner.save_items(items).await?;
+        let mut ids: Vec<i64> = items.iter().map(|item| item.collection_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.notify(LibraryChangeKind::Updated, ids).await;
+        Ok(saved)
+    }
+
+    async fn update_item(&self, item: &CollectionItem) -> Result<(), RepositoryError> {
+        self.inner.update_item(item).await?;
+        self.notify(LibraryChangeKind::Updated, vec![item.collection_id]).await;
+        Ok(())
+    }
+
+    async fn delete_item(&self, collection_id: i64, item_id: i64) -> Result<bool, RepositoryError> {
+        let deleted = self.inner.delete_item(collection_id, item_id).await?;
+        if deleted {
+            self.notify(LibraryChangeKind::Updated, vec![collection_id]).await;
+        }
+        Ok(deleted)
+    }
+
+    async fn delete_items(&self, collection_id: i64) -> Result<(), RepositoryError> {
+        self.inner.delete_items(collection_id).await?;
+        self.notify(LibraryChangeKind::Updated, vec![collection_id]).await;
+        Ok(())
+    }
+
+    async fn find_item_by_tmdb(&self, collection_id: i64, tmdb_id: i64, media_type: &str) -> Result<Option<CollectionItem>, RepositoryError> {
+        self.inner.find_item_by_tmdb(collection_id, tmdb_id, media_type).await
+    }
+
+    async fn find_collections_by_item_tmdb_id(&self, tmdb_id: i64) -> Result<Vec<Collection>, RepositoryError> {
+        self.inner.find_collections_by_item_tmdb_id(tmdb_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::sqlite::SqliteMediaRepository;
+    use crate::interfaces::messaging::EventHandler;
+    use crate::shared::error::MessagingError;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<LibraryChangedEvent>>);
+
+    #[async_trait]
+    impl EventHandler<LibraryChangedEvent> for Recorder {
+        async fn handle(&self, event: LibraryChangedEvent) -> Result<(), MessagingError> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writes_publish_library_changes() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let bus = Arc::new(InMemoryEventBus::new());
+        let recorder = Arc::new(Recorder::default());
+        bus.subscribe::<LibraryChangedEvent>(recorder.clone()).await.unwrap();
+
+        let repo = NotifyingMediaRepository::new(Arc::new(SqliteMediaRepository::new(pool)), bus);
+        let mut media = Media::new("/movies/Heat (1995).mkv".to_string(), MediaType::Movie, "Heat".to_string()).unwrap();
+        let id = repo.save(&media).await.unwrap();
+        media.id = Some(id);
+        repo.find_by_id(id).await.unwrap();
+        repo.update(&media).await.unwrap();
+        repo.mark_watched(id).await.unwrap();
+        repo.delete(id).await.unwrap();
+
+        let changes: Vec<(LibraryChangeKind, Vec<i64>)> = recorder.0.lock().unwrap()
+            .iter()
+            .map(|e| (e.change, e.ids.clone()))
+            .collect();
+        assert_eq!(changes, vec![
+            (LibraryChangeKind::Added, vec![id]),
+            (LibraryChangeKind::Updated, vec![id]),
+            (LibraryChangeKind::WatchState, vec![id]),
+            (LibraryChangeKind::Removed, vec![id]),
+        ]);
+    }
+}
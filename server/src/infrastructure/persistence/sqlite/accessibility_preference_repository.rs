@@ -0,0 +1,66 @@
+//! SQLite implementation of AccessibilityPreferenceRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Row, Sqlite};
+use crate::domain::repositories::{AccessibilityPreferenceRepository, AccessibilityPreferences};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based accessibility preference repository implementation
+pub struct SqliteAccessibilityPreferenceRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAccessibilityPreferenceRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AccessibilityPreferenceRepository for SqliteAccessibilityPreferenceRepository {
+    async fn find(&self, user_id: &str) -> Result<Option<AccessibilityPreferences>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT audio_description, hearing_impaired_subtitles FROM user_accessibility_preferences WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| AccessibilityPreferences {
+            audio_description: row.get("audio_description"),
+            hearing_impaired_subtitles: row.get("hearing_impaired_subtitles"),
+        }))
+    }
+
+    async fn save(&self, user_id: &str, preferences: &AccessibilityPreferences) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_accessibility_preferences (user_id, audio_description, hearing_impaired_subtitles, updated_at)
+            VALUES (?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(user_id) DO UPDATE SET
+                audio_description = excluded.audio_description,
+                hearing_impaired_subtitles = excluded.hearing_impaired_subtitles,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(preferences.audio_description)
+        .bind(preferences.hearing_impaired_subtitles)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM user_accessibility_preferences WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,105 @@
+//! SQLite implementation of ArtworkRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{ArtworkOwner, ArtworkRepository, ExtraArtwork};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based artwork repository implementation
+pub struct SqliteArtworkRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteArtworkRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ArtworkRepository for SqliteArtworkRepository {
+    async fn find(&self, owner: ArtworkOwner, owner_id: i64) -> Result<Option<ExtraArtwork>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT logo_url, clearart_url, disc_url FROM extra_artwork WHERE owner_type = ? AND owner_id = ?",
+        )
+        .bind(owner.as_str())
+        .bind(owner_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| ExtraArtwork {
+            logo_url: row.get("logo_url"),
+            clearart_url: row.get("clearart_url"),
+            disc_url: row.get("disc_url"),
+        }))
+    }
+
+    async fn save(&self, owner: ArtworkOwner, owner_id: i64, artwork: &ExtraArtwork) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO extra_artwork (owner_type, owner_id, logo_url, clearart_url, disc_url, updated_at)
+            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(owner_type, owner_id) DO UPDATE SET
+                logo_url = excluded.logo_url,
+                clearart_url = excluded.clearart_url,
+                disc_url = excluded.disc_url,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(owner.as_str())
+        .bind(owner_id)
+        .bind(&artwork.logo_url)
+        .bind(&artwork.clearart_url)
+        .bind(&artwork.disc_url)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_fresh_ids(&self, owner: ArtworkOwner, max_age_days: i64) -> Result<Vec<i64>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT owner_id FROM extra_artwork WHERE owner_type = ? AND updated_at >= datetime('now', ?)",
+        )
+        .bind(owner.as_str())
+        .bind(format!("-{} days", max_age_days))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|row| row.get("owner_id")).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_replaces_existing_artwork() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteArtworkRepository::new(pool);
+        repo.save(ArtworkOwner::Media, 1, &ExtraArtwork::default()).await.unwrap();
+
+        let artwork = ExtraArtwork {
+            logo_url: Some("https://assets.fanart.tv/logo.png".to_string()),
+            clearart_url: None,
+            disc_url: Some("https://assets.fanart.tv/disc.png".to_string()),
+        };
+        repo.save(ArtworkOwner::Media, 1, &artwork).await.unwrap();
+
+        assert_eq!(repo.find(ArtworkOwner::Media, 1).await.unwrap(), Some(artwork));
+        assert_eq!(repo.find(ArtworkOwner::Series, 1).await.unwrap(), None);
+        assert_eq!(repo.find_fresh_ids(ArtworkOwner::Media, 30).await.unwrap(), vec![1]);
+    }
+}
@@ -0,0 +1,47 @@
+//! SQLite implementation of AudioPreferenceRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+use crate::domain::repositories::AudioPreferenceRepository;
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based audio preference repository implementation
+pub struct SqliteAudioPreferenceRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAudioPreferenceRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AudioPreferenceRepository for SqliteAudioPreferenceRepository {
+    async fn find(&self, user_id: &str) -> Result<Option<String>, RepositoryError> {
+        sqlx::query_scalar("SELECT language FROM user_audio_preferences WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))
+    }
+
+    async fn save(&self, user_id: &str, language: &str) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_audio_preferences (user_id, language, updated_at)
+            VALUES (?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(user_id) DO UPDATE SET
+                language = excluded.language,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(language)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
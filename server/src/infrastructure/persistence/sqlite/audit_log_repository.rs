@@ -0,0 +1,131 @@
+//! SQLite implementation of AuditLogRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{AuditEntry, AuditLogRepository};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based audit log repository implementation
+pub struct SqliteAuditLogRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAuditLogRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_entry(row: &SqliteRow) -> Result<AuditEntry, RepositoryError> {
+    let action: String = row.get("action");
+    Ok(AuditEntry {
+        id: row.get("id"),
+        action: AuditAction::parse(&action)
+            .ok_or_else(|| RepositoryError::Database(format!("Unknown audit action '{}'", action)))?,
+        actor: row.get("actor"),
+        target: row.get("target"),
+        details: row.get("details"),
+        ip: row.get("ip"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[async_trait]
+impl AuditLogRepository for SqliteAuditLogRepository {
+    async fn record(&self, event: &AuditEvent) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO audit_log (action, actor, target, details, ip, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(event.action.as_str())
+        .bind(&event.actor)
+        .bind(&event.target)
+        .bind(&event.details)
+        .bind(&event.ip)
+        .bind(event.timestamp.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_page(
+        &self,
+        action: Option<AuditAction>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<(Vec<AuditEntry>, u64), RepositoryError> {
+        let action = action.map(|a| a.as_str());
+        let rows = sqlx::query(
+            "SELECT id, action, actor, target, details, ip, created_at FROM audit_log
+             WHERE ? IS NULL OR action = ?
+             ORDER BY id DESC LIMIT ? OFFSET ?",
+        )
+        .bind(action)
+        .bind(action)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_log WHERE ? IS NULL OR action = ?")
+            .bind(action)
+            .bind(action)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        let entries = rows.iter().map(row_to_entry).collect::<Result<Vec<_>, _>>()?;
+        Ok((entries, total as u64))
+    }
+
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM audit_log WHERE created_at < ?")
+            .bind(cutoff.to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use chrono::Duration;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_audit_log_paging_and_retention() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteAuditLogRepository::new(pool);
+        let mut old = AuditEvent::new(AuditAction::AuthFailed, "/v2/media");
+        old.timestamp = Utc::now() - Duration::days(100);
+        repo.record(&old).await.unwrap();
+        for id in 1..=3 {
+            repo.record(&AuditEvent::new(AuditAction::Deletion, format!("device:{}", id)).with_details("Device key revoked"))
+                .await
+                .unwrap();
+        }
+
+        let (page, total) = repo.find_page(None, 0, 2).await.unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(page.iter().map(|e| e.target.as_deref()).collect::<Vec<_>>(), vec![Some("device:3"), Some("device:2")]);
+        let (failed, total) = repo.find_page(Some(AuditAction::AuthFailed), 0, 10).await.unwrap();
+        assert_eq!((failed.len(), total), (1, 1));
+        assert_eq!(failed[0].target.as_deref(), Some("/v2/media"));
+
+        assert_eq!(repo.delete_before(Utc::now() - Duration::days(90)).await.unwrap(), 1);
+        assert_eq!(repo.find_page(None, 0, 10).await.unwrap().1, 3);
+    }
+}
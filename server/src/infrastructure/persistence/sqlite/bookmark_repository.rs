@@ -0,0 +1,181 @@
+//! SQLite implementation of BookmarkRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{Bookmark, BookmarkRepository};
+use crate::shared::error::RepositoryError;
+
+const COLUMNS: &str = "id, media_id, position_seconds, note, created_at, updated_at";
+
+/// SQLite-based bookmark repository implementation
+pub struct SqliteBookmarkRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteBookmarkRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_bookmark(row: &SqliteRow) -> Bookmark {
+    Bookmark {
+        id: row.get("id"),
+        media_id: row.get("media_id"),
+        position_seconds: row.get("position_seconds"),
+        note: row.get("note"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[async_trait]
+impl BookmarkRepository for SqliteBookmarkRepository {
+    async fn find_by_media(&self, user_id: &str, media_id: i64) -> Result<Vec<Bookmark>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM media_bookmarks WHERE user_id = ? AND media_id = ? ORDER BY position_seconds, id",
+            COLUMNS
+        ))
+        .bind(user_id)
+        .bind(media_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_bookmark).collect())
+    }
+
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Bookmark>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM media_bookmarks WHERE user_id = ? ORDER BY created_at DESC, id DESC",
+            COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_bookmark).collect())
+    }
+
+    async fn create(
+        &self,
+        user_id: &str,
+        media_id: i64,
+        position_seconds: f64,
+        note: Option<&str>,
+    ) -> Result<Bookmark, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO media_bookmarks (user_id, media_id, position_seconds, note, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id)
+        .bind(media_id)
+        .bind(position_seconds)
+        .bind(note)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(Bookmark {
+            id: result.last_insert_rowid(),
+            media_id,
+            position_seconds,
+            note: note.map(str::to_string),
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    async fn update(
+        &self,
+        user_id: &str,
+        id: i64,
+        position_seconds: f64,
+        note: Option<&str>,
+    ) -> Result<Option<Bookmark>, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE media_bookmarks SET position_seconds = ?, note = ?, updated_at = ? WHERE user_id = ? AND id = ?",
+        )
+        .bind(position_seconds)
+        .bind(note)
+        .bind(Utc::now().to_rfc3339())
+        .bind(user_id)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find(user_id, id).await
+    }
+
+    async fn find(&self, user_id: &str, id: i64) -> Result<Option<Bookmark>, RepositoryError> {
+        let row = sqlx::query(&format!("SELECT {} FROM media_bookmarks WHERE user_id = ? AND id = ?", COLUMNS))
+            .bind(user_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_bookmark))
+    }
+
+    async fn delete(&self, user_id: &str, id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM media_bookmarks WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_bookmarks_are_per_user() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteBookmarkRepository::new(pool);
+        let late = repo.create("anna", 1, 2530.0, Some("Great scene")).await.unwrap();
+        let early = repo.create("anna", 1, 95.5, None).await.unwrap();
+        repo.create("anna", 2, 10.0, None).await.unwrap();
+        let other = repo.create("ben", 1, 30.0, None).await.unwrap();
+
+        let bookmarks = repo.find_by_media("anna", 1).await.unwrap();
+        assert_eq!(bookmarks, vec![early.clone(), late.clone()]);
+        assert_eq!(repo.find_by_user("anna").await.unwrap().len(), 3);
+
+        let moved = repo.update("anna", late.id, 2525.0, Some("Even better")).await.unwrap().unwrap();
+        assert_eq!(moved.position_seconds, 2525.0);
+        assert_eq!(moved.note.as_deref(), Some("Even better"));
+        assert_eq!(moved.created_at, late.created_at);
+
+        // Other users' bookmarks cannot be touched
+        assert!(repo.update("anna", other.id, 1.0, None).await.unwrap().is_none());
+        assert!(!repo.delete("anna", other.id).await.unwrap());
+        assert!(repo.delete("anna", early.id).await.unwrap());
+        assert!(repo.find("anna", early.id).await.unwrap().is_none());
+        assert_eq!(repo.find_by_media("ben", 1).await.unwrap(), vec![other]);
+    }
+}
@@ -5,11 +5,13 @@
 use async_trait::async_trait;
 use sqlx::{Pool, Sqlite, Row};
 use crate::domain::repositories::{CacheRepository, CacheStats};
+use crate::infrastructure::cache::HitCounter;
 use crate::shared::error::RepositoryError;
 
 /// SQLite implementation of CacheRepository
 pub struct SqliteCacheRepository {
     pool: Pool<Sqlite>,
+    lookups: HitCounter,
 }
 
 impl SqliteCacheRepository {
@@ -18,7 +20,7 @@ impl SqliteCacheRepository {
     /// # Arguments
     /// * `pool` - SQLite connection pool
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self { pool, lookups: HitCounter::new() }
     }
 
     /// Checks if a cache entry is expired
@@ -52,13 +54,18 @@ impl CacheRepository for SqliteCacheRepository {
                         .bind(key)
                         .execute(&self.pool)
                         .await?;
+                    self.lookups.record(false);
                     Ok(None)
                 } else {
                     let value: String = row.try_get("value")?;
+                    self.lookups.record(true);
                     Ok(Some(value))
                 }
             }
-            None => Ok(None),
+            None => {
+                self.lookups.record(false);
+                Ok(None)
+            }
         }
     }
 
@@ -191,14 +198,15 @@ impl CacheRepository for SqliteCacheRepository {
         let expired_entries: i64 = expired_result.try_get("count")?;
         let total_size_bytes: i64 = size_result.try_get::<Option<i64>, _>("total_size")?.unwrap_or(0);
 
-        // Calculate hit rate (placeholder - in real implementation, track hits/misses)
-        let hit_rate = 0.0;
+        let lookups = self.lookups.stats();
 
         Ok(CacheStats {
             total_entries,
             expired_entries,
             total_size_bytes,
-            hit_rate,
+            hits: lookups.hits,
+            misses: lookups.misses,
+            hit_rate: lookups.hit_rate.unwrap_or(0.0) as f32,
         })
     }
 
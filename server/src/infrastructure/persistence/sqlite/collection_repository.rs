@@ -5,7 +5,7 @@
 use async_trait::async_trait;
 use sqlx::{Pool, Sqlite, Row};
 use crate::domain::entities::{Collection, CollectionItem};
-use crate::domain::repositories::CollectionRepository;
+use crate::domain::repositories::{CollectionOrder, CollectionRepository};
 use crate::shared::error::RepositoryError;
 
 /// SQLite implementation of CollectionRepository
@@ -96,6 +96,37 @@ impl CollectionRepository for SqliteCollectionRepository {
         Ok(collection_list)
     }
 
+    async fn find_page(
+        &self,
+        order: CollectionOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Collection>, i64), RepositoryError> {
+        let column = match order {
+            CollectionOrder::Name => "name COLLATE NOCASE",
+            CollectionOrder::Size => "total_items",
+            CollectionOrder::Completion => {
+                "CASE WHEN total_items > 0 THEN CAST(available_items AS REAL) / total_items ELSE 0 END"
+            }
+        };
+        let direction = if descending { "DESC" } else { "ASC" };
+        let rows = sqlx::query(&format!(
+            "SELECT * FROM collections ORDER BY {column} {direction}, id {direction} LIMIT ? OFFSET ?"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut collections = Vec::with_capacity(rows.len());
+        for row in rows {
+            collections.push(Self::map_row_to_collection(row)?);
+        }
+
+        Ok((collections, self.count().await?))
+    }
+
     async fn find_by_type(&self, collection_type: &str) -> Result<Vec<Collection>, RepositoryError> {
         let rows = sqlx::query(
             "SELECT * FROM collections WHERE collection_type = ? ORDER BY name ASC"
@@ -361,6 +392,16 @@ impl CollectionRepository for SqliteCollectionRepository {
         Ok(())
     }
 
+    async fn delete_item(&self, collection_id: i64, item_id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM collection_items WHERE collection_id = ? AND id = ?")
+            .bind(collection_id)
+            .bind(item_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn delete_items(&self, collection_id: i64) -> Result<(), RepositoryError> {
         sqlx::query("DELETE FROM collection_items WHERE collection_id = ?")
             .bind(collection_id)
@@ -2,7 +2,7 @@
 
 use async_trait::async_trait;
 use sqlx::{Pool, Sqlite, Row};
-use crate::domain::repositories::{CreditsRepository, CreditEntry, CreditType};
+use crate::domain::repositories::{CreditsRepository, CreditEntry, CreditType, CreditedPerson};
 use crate::shared::error::RepositoryError;
 
 /// SQLite-based credits repository implementation
@@ -154,6 +154,46 @@ impl CreditsRepository for SqliteCreditsRepository {
         Ok(result.0 > 0)
     }
 
+    async fn get_media_ids_by_person(&self, person_id: i64) -> Result<Vec<i64>, RepositoryError> {
+        let rows: Vec<(i64,)> = sqlx::query_as(
+            "SELECT DISTINCT media_id FROM media_credits WHERE person_id = ? ORDER BY media_id",
+        )
+        .bind(person_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    async fn credited_people(&self, limit: usize) -> Result<Vec<CreditedPerson>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT person_id, MAX(person_name) AS name, MAX(profile_url) AS profile_url,
+                   COUNT(DISTINCT media_id) AS credits
+            FROM media_credits
+            WHERE credit_type = 'cast'
+            GROUP BY person_id
+            ORDER BY credits DESC, MIN(credit_order)
+            LIMIT ?
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CreditedPerson {
+                person_id: row.get("person_id"),
+                name: row.get("name"),
+                profile_url: row.get("profile_url"),
+                credits: row.get("credits"),
+            })
+            .collect())
+    }
+
     async fn delete_credits(&self, media_id: i64) -> Result<(), RepositoryError> {
         sqlx::query("DELETE FROM media_credits WHERE media_id = ?")
             .bind(media_id)
@@ -0,0 +1,68 @@
+//! SQLite implementation of CropRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{CropDetection, CropRepository};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based crop repository implementation
+pub struct SqliteCropRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteCropRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CropRepository for SqliteCropRepository {
+    async fn find(&self, media_id: i64) -> Result<Option<CropDetection>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT width, height, x, y, source_width, source_height FROM media_crop WHERE media_id = ?",
+        )
+        .bind(media_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| CropDetection {
+            width: row.get::<i64, _>("width") as u32,
+            height: row.get::<i64, _>("height") as u32,
+            x: row.get::<i64, _>("x") as u32,
+            y: row.get::<i64, _>("y") as u32,
+            source_width: row.get::<i64, _>("source_width") as u32,
+            source_height: row.get::<i64, _>("source_height") as u32,
+        }))
+    }
+
+    async fn save(&self, media_id: i64, crop: &CropDetection) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_crop (media_id, width, height, x, y, source_width, source_height, detected_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(media_id) DO UPDATE SET
+                width = excluded.width,
+                height = excluded.height,
+                x = excluded.x,
+                y = excluded.y,
+                source_width = excluded.source_width,
+                source_height = excluded.source_height,
+                detected_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(media_id)
+        .bind(crop.width as i64)
+        .bind(crop.height as i64)
+        .bind(crop.x as i64)
+        .bind(crop.y as i64)
+        .bind(crop.source_width as i64)
+        .bind(crop.source_height as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
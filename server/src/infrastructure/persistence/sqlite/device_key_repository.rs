@@ -0,0 +1,106 @@
+//! SQLite implementation of DeviceKeyRepository
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{DeviceKey, DeviceKeyRepository};
+use crate::shared::error::RepositoryError;
+
+/// Minimum time between two last-used updates of a device, in seconds
+const TOUCH_INTERVAL_SECS: i64 = 60;
+
+/// SQLite-based device key repository implementation
+pub struct SqliteDeviceKeyRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteDeviceKeyRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_device(row: &SqliteRow) -> DeviceKey {
+    DeviceKey {
+        id: row.get("id"),
+        name: row.get("name"),
+        user_id: row.get("user_id"),
+        prefix: row.get("prefix"),
+        created_at: row.get("created_at"),
+        last_used_at: row.get("last_used_at"),
+    }
+}
+
+#[async_trait]
+impl DeviceKeyRepository for SqliteDeviceKeyRepository {
+    async fn create(&self, name: &str, user_id: &str, prefix: &str, key_hash: &str) -> Result<DeviceKey, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "INSERT INTO device_keys (name, user_id, prefix, key_hash, created_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(name)
+        .bind(user_id)
+        .bind(prefix)
+        .bind(key_hash)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(DeviceKey {
+            id: result.last_insert_rowid(),
+            name: name.to_string(),
+            user_id: user_id.to_string(),
+            prefix: prefix.to_string(),
+            created_at: now,
+            last_used_at: None,
+        })
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<DeviceKey>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT id, name, user_id, prefix, created_at, last_used_at FROM device_keys WHERE key_hash = ?",
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_device))
+    }
+
+    async fn find_all(&self) -> Result<Vec<DeviceKey>, RepositoryError> {
+        let rows = sqlx::query("SELECT id, name, user_id, prefix, created_at, last_used_at FROM device_keys ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_device).collect())
+    }
+
+    async fn touch(&self, id: i64) -> Result<(), RepositoryError> {
+        let now = Utc::now();
+        let stale = (now - Duration::seconds(TOUCH_INTERVAL_SECS)).to_rfc3339();
+        sqlx::query(
+            "UPDATE device_keys SET last_used_at = ? WHERE id = ? AND (last_used_at IS NULL OR last_used_at < ?)",
+        )
+        .bind(now.to_rfc3339())
+        .bind(id)
+        .bind(stale)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM device_keys WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,197 @@
+//! SQLite implementation of DialogueRepository
+//!
+//! Cues live in `subtitle_cues`; `subtitle_dialogue` is an FTS5 index over
+//! their text kept in sync by triggers.
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{DialogueCue, DialogueMatch, DialogueRepository, SubtitleSource};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based dialogue repository implementation
+pub struct SqliteDialogueRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteDialogueRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    async fn search_index(&self, expression: &str, limit: usize) -> Result<Vec<DialogueMatch>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.media_id, s.language, c.start_seconds, c.end_seconds, c.text
+            FROM subtitle_dialogue
+            JOIN subtitle_cues c ON c.id = subtitle_dialogue.rowid
+            JOIN subtitle_sources s ON s.id = c.source_id
+            WHERE subtitle_dialogue MATCH ?
+            ORDER BY bm25(subtitle_dialogue), c.start_seconds
+            LIMIT ?
+            "#,
+        )
+        .bind(expression)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DialogueMatch {
+                media_id: row.get("media_id"),
+                language: row.get("language"),
+                start: row.get("start_seconds"),
+                end: row.get("end_seconds"),
+                text: row.get("text"),
+            })
+            .collect())
+    }
+}
+
+/// Lowercase words of a query
+fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+#[async_trait]
+impl DialogueRepository for SqliteDialogueRepository {
+    async fn find_sources(&self) -> Result<Vec<SubtitleSource>, RepositoryError> {
+        let rows = sqlx::query("SELECT media_id, path, language, signature FROM subtitle_sources")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SubtitleSource {
+                media_id: row.get("media_id"),
+                path: row.get("path"),
+                language: row.get("language"),
+                signature: row.get("signature"),
+            })
+            .collect())
+    }
+
+    async fn replace_source(&self, source: &SubtitleSource, cues: &[DialogueCue]) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::Database(e.to_string());
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        let source_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO subtitle_sources (media_id, path, language, signature, indexed_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(path) DO UPDATE SET
+                media_id = excluded.media_id,
+                language = excluded.language,
+                signature = excluded.signature,
+                indexed_at = CURRENT_TIMESTAMP
+            RETURNING id
+            "#,
+        )
+        .bind(source.media_id)
+        .bind(&source.path)
+        .bind(&source.language)
+        .bind(&source.signature)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        sqlx::query("DELETE FROM subtitle_cues WHERE source_id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        for cue in cues {
+            sqlx::query("INSERT INTO subtitle_cues (source_id, start_seconds, end_seconds, text) VALUES (?, ?, ?, ?)")
+                .bind(source_id)
+                .bind(cue.start)
+                .bind(cue.end)
+                .bind(&cue.text)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)
+    }
+
+    async fn delete_source(&self, path: &str) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM subtitle_sources WHERE path = ?")
+            .bind(path)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<DialogueMatch>, RepositoryError> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let phrase = format!("\"{}\"*", terms.join(" "));
+        let matches = self.search_index(&phrase, limit).await?;
+        if !matches.is_empty() || terms.len() == 1 {
+            return Ok(matches);
+        }
+        let all_words = terms.iter().map(|t| format!("\"{}\"", t)).collect::<Vec<_>>().join(" ") + "*";
+        self.search_index(&all_words, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn cue(start: f64, text: &str) -> DialogueCue {
+        DialogueCue { start, end: start + 2.0, text: text.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_index_and_search_dialogue() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        sqlx::query("INSERT INTO media (id, file_path, media_type, title) VALUES (4, '/tv/s01e01.mkv', 'episode', 'Pilot')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteDialogueRepository::new(pool.clone());
+
+        let mut source = SubtitleSource {
+            media_id: 4,
+            path: "/tv/s01e01.en.srt".to_string(),
+            language: Some("en".to_string()),
+            signature: "1".to_string(),
+        };
+        repo.replace_source(&source, &[cue(1.0, "I am the one who knocks"), cue(5.0, "Say my name")]).await.unwrap();
+
+        let found = repo.search("the one who kno", 10).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].media_id, found[0].start), (4, 1.0));
+        // Words not next to each other still match
+        assert_eq!(repo.search("knocks one", 10).await.unwrap().len(), 1);
+
+        // Re-indexing replaces the old cues
+        source.signature = "2".to_string();
+        repo.replace_source(&source, &[cue(7.0, "Say my name")]).await.unwrap();
+        assert!(repo.search("knocks", 10).await.unwrap().is_empty());
+        assert_eq!(repo.find_sources().await.unwrap(), vec![source]);
+
+        // Deleting the media removes its sources and cues
+        sqlx::query("DELETE FROM media WHERE id = 4").execute(&pool).await.unwrap();
+        assert!(repo.search("name", 10).await.unwrap().is_empty());
+        assert!(repo.find_sources().await.unwrap().is_empty());
+    }
+}
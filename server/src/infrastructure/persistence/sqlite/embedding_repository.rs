@@ -0,0 +1,119 @@
+//! SQLite implementation of EmbeddingRepository
+//!
+//! Vectors are stored as little-endian f32 blobs.
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{EmbeddingRepository, MediaEmbedding};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based embedding repository implementation
+pub struct SqliteEmbeddingRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteEmbeddingRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn encode(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[async_trait]
+impl EmbeddingRepository for SqliteEmbeddingRepository {
+    async fn find_hashes(&self, model: &str) -> Result<Vec<(i64, String)>, RepositoryError> {
+        sqlx::query_as("SELECT media_id, content_hash FROM media_embeddings WHERE model = ?")
+            .bind(model)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))
+    }
+
+    async fn find_by_model(&self, model: &str) -> Result<Vec<MediaEmbedding>, RepositoryError> {
+        let rows = sqlx::query("SELECT media_id, model, content_hash, vector FROM media_embeddings WHERE model = ?")
+            .bind(model)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MediaEmbedding {
+                media_id: row.get("media_id"),
+                model: row.get("model"),
+                content_hash: row.get("content_hash"),
+                vector: decode(row.get::<&[u8], _>("vector")),
+            })
+            .collect())
+    }
+
+    async fn save(&self, embedding: &MediaEmbedding) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_embeddings (media_id, model, content_hash, vector, embedded_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(media_id) DO UPDATE SET
+                model = excluded.model,
+                content_hash = excluded.content_hash,
+                vector = excluded.vector,
+                embedded_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(embedding.media_id)
+        .bind(&embedding.model)
+        .bind(&embedding.content_hash)
+        .bind(encode(&embedding.vector))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_and_find_embeddings() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        // The table references media(id)
+        sqlx::query("INSERT INTO media (id, file_path, media_type, title) VALUES (4, '/movies/film.mkv', 'movie', 'Film')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteEmbeddingRepository::new(pool);
+
+        let mut embedding = MediaEmbedding {
+            media_id: 4,
+            model: "old-model".to_string(),
+            content_hash: "a".to_string(),
+            vector: vec![0.5, -1.25],
+        };
+        repo.save(&embedding).await.unwrap();
+        embedding.model = "nomic-embed-text".to_string();
+        embedding.vector = vec![0.25, 2.0, -3.5];
+        repo.save(&embedding).await.unwrap();
+
+        assert_eq!(repo.find_by_model("nomic-embed-text").await.unwrap(), vec![embedding]);
+        assert!(repo.find_hashes("old-model").await.unwrap().is_empty());
+    }
+}
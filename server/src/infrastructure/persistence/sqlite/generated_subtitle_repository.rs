@@ -0,0 +1,147 @@
+//! SQLite implementation of GeneratedSubtitleRepository
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{GeneratedSubtitle, GeneratedSubtitleRepository};
+use crate::shared::error::RepositoryError;
+
+/// Stored audio track index of translated existing subtitles (the column is NOT NULL)
+const NO_AUDIO_TRACK: i64 = -1;
+
+/// SQLite-based generated subtitle repository implementation
+pub struct SqliteGeneratedSubtitleRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteGeneratedSubtitleRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GeneratedSubtitleRepository for SqliteGeneratedSubtitleRepository {
+    async fn save(&self, subtitle: &GeneratedSubtitle) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO generated_subtitles (
+                media_id, audio_track_index, audio_fingerprint, source_language,
+                target_language, srt_filename, duration_seconds, was_translated, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(media_id, audio_track_index, target_language) DO UPDATE SET
+                audio_fingerprint = excluded.audio_fingerprint,
+                source_language = excluded.source_language,
+                srt_filename = excluded.srt_filename,
+                duration_seconds = excluded.duration_seconds,
+                was_translated = excluded.was_translated,
+                created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(subtitle.media_id)
+        .bind(subtitle.audio_track_index.map_or(NO_AUDIO_TRACK, |i| i as i64))
+        .bind(&subtitle.audio_fingerprint)
+        .bind(&subtitle.source_language)
+        .bind(&subtitle.language)
+        .bind(&subtitle.subtitle_path)
+        .bind(subtitle.duration_seconds)
+        .bind(subtitle.was_translated)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<GeneratedSubtitle>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT media_id, audio_track_index, audio_fingerprint, source_language,
+                   target_language, srt_filename, duration_seconds, was_translated
+            FROM generated_subtitles
+            ORDER BY media_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_subtitle).collect())
+    }
+
+    async fn find_by_media(&self, media_id: i64) -> Result<Vec<GeneratedSubtitle>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT media_id, audio_track_index, audio_fingerprint, source_language,
+                   target_language, srt_filename, duration_seconds, was_translated
+            FROM generated_subtitles
+            WHERE media_id = ?
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(media_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_subtitle).collect())
+    }
+}
+
+fn row_to_subtitle(row: &SqliteRow) -> GeneratedSubtitle {
+    let audio_track_index: i64 = row.get("audio_track_index");
+    GeneratedSubtitle {
+        media_id: row.get("media_id"),
+        audio_track_index: usize::try_from(audio_track_index).ok(),
+        audio_fingerprint: row.get("audio_fingerprint"),
+        source_language: row.get("source_language"),
+        language: row.get::<Option<String>, _>("target_language").unwrap_or_default(),
+        subtitle_path: row.get("srt_filename"),
+        duration_seconds: row.get::<Option<f64>, _>("duration_seconds").unwrap_or_default(),
+        was_translated: row.get::<i64, _>("was_translated") != 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_replaces_same_source_and_language() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        // The table references media(id)
+        sqlx::query("INSERT INTO media (id, file_path, media_type, title) VALUES (4, '/movies/film.mkv', 'movie', 'Film')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let repo = SqliteGeneratedSubtitleRepository::new(pool);
+        let subtitle = GeneratedSubtitle {
+            media_id: 4,
+            audio_track_index: Some(0),
+            audio_fingerprint: "abcd".to_string(),
+            source_language: Some("en".to_string()),
+            language: "hu".to_string(),
+            subtitle_path: "/movies/film.hu.srt".to_string(),
+            duration_seconds: 5400.0,
+            was_translated: true,
+        };
+        repo.save(&subtitle).await.unwrap();
+        repo.save(&GeneratedSubtitle { audio_fingerprint: "ef01".to_string(), ..subtitle.clone() }).await.unwrap();
+        repo.save(&GeneratedSubtitle { audio_track_index: None, ..subtitle.clone() }).await.unwrap();
+
+        let all = repo.find_all().await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|s| s.audio_track_index == Some(0) && s.audio_fingerprint == "ef01"));
+        assert!(all.iter().any(|s| s.audio_track_index.is_none()));
+        assert_eq!(repo.find_by_media(4).await.unwrap().len(), 2);
+        assert!(repo.find_by_media(5).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+//! SQLite implementation of JobHistoryRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+use crate::domain::repositories::{JobHistoryRepository, JobRun};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based job history repository implementation
+pub struct SqliteJobHistoryRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteJobHistoryRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl JobHistoryRepository for SqliteJobHistoryRepository {
+    async fn record(&self, run: &JobRun) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT INTO job_runs (kind, media_seconds, elapsed_seconds) VALUES (?, ?, ?)")
+            .bind(&run.kind)
+            .bind(run.media_seconds)
+            .bind(run.elapsed_seconds)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn throughput(&self, kind: &str, samples: u32) -> Result<Option<f64>, RepositoryError> {
+        // Totals rather than the mean of ratios, so short jobs do not dominate
+        sqlx::query_scalar(
+            r#"
+            SELECT SUM(media_seconds) / SUM(elapsed_seconds) FROM (
+                SELECT media_seconds, elapsed_seconds FROM job_runs
+                WHERE kind = ? AND elapsed_seconds > 0
+                ORDER BY id DESC LIMIT ?
+            )
+            "#,
+        )
+        .bind(kind)
+        .bind(samples)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_throughput_of_recent_runs() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteJobHistoryRepository::new(pool);
+
+        assert_eq!(repo.throughput("subtitle", 20).await.unwrap(), None);
+
+        let run = |media_seconds: f64, elapsed_seconds: f64| JobRun {
+            kind: "subtitle".to_string(),
+            media_seconds,
+            elapsed_seconds,
+        };
+        repo.record(&run(100.0, 100.0)).await.unwrap();
+        repo.record(&run(3000.0, 500.0)).await.unwrap();
+        repo.record(&run(600.0, 100.0)).await.unwrap();
+
+        assert_eq!(repo.throughput("subtitle", 2).await.unwrap(), Some(6.0));
+        assert_eq!(repo.throughput("translation", 20).await.unwrap(), None);
+    }
+}
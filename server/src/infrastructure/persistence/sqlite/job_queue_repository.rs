@@ -0,0 +1,179 @@
+//! SQLite implementation of JobQueueRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{JobFilter, JobQueueRepository, StoredJob};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based job queue repository implementation
+pub struct SqliteJobQueueRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteJobQueueRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+const COLUMNS: &str = "id, job_type, state, priority, attempts, payload, status, created_at";
+
+fn row_to_job(row: &SqliteRow) -> Result<StoredJob, RepositoryError> {
+    let payload: Option<String> = row.get("payload");
+    let status: String = row.get("status");
+    let created_at: String = row.get("created_at");
+    Ok(StoredJob {
+        id: row.get("id"),
+        job_type: row.get("job_type"),
+        state: row.get("state"),
+        priority: row.get("priority"),
+        attempts: row.get::<i64, _>("attempts") as u32,
+        payload: payload.map(|p| serde_json::from_str(&p)).transpose()?,
+        status: serde_json::from_str(&status)?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| RepositoryError::Database(format!("Invalid job created_at '{}': {}", created_at, e)))?
+            .with_timezone(&Utc),
+    })
+}
+
+#[async_trait]
+impl JobQueueRepository for SqliteJobQueueRepository {
+    async fn save(&self, job: &StoredJob) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO jobs (id, job_type, state, priority, attempts, payload, status, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                state = excluded.state, priority = excluded.priority, attempts = excluded.attempts,
+                payload = excluded.payload, status = excluded.status, updated_at = excluded.updated_at",
+        )
+        .bind(&job.id)
+        .bind(&job.job_type)
+        .bind(&job.state)
+        .bind(job.priority)
+        .bind(i64::from(job.attempts))
+        .bind(job.payload.as_ref().map(|p| p.to_string()))
+        .bind(job.status.to_string())
+        .bind(job.created_at.to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<StoredJob>, RepositoryError> {
+        let row = sqlx::query(&format!("SELECT {} FROM jobs WHERE id = ?", COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        row.as_ref().map(row_to_job).transpose()
+    }
+
+    async fn find_unfinished(&self) -> Result<Vec<StoredJob>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM jobs WHERE state IN ('pending', 'processing')
+             ORDER BY priority DESC, created_at",
+            COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter().map(row_to_job).collect()
+    }
+
+    async fn list(&self, filter: &JobFilter) -> Result<Vec<StoredJob>, RepositoryError> {
+        let limit = if filter.limit == 0 { -1 } else { i64::from(filter.limit) };
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM jobs
+             WHERE (? IS NULL OR state = ?) AND (? IS NULL OR job_type = ?)
+             ORDER BY created_at DESC LIMIT ?",
+            COLUMNS
+        ))
+        .bind(&filter.state)
+        .bind(&filter.state)
+        .bind(&filter.job_type)
+        .bind(&filter.job_type)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter().map(row_to_job).collect()
+    }
+
+    async fn delete_finished_before(&self, cutoff: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "DELETE FROM jobs WHERE state NOT IN ('pending', 'processing') AND updated_at < ?",
+        )
+        .bind(cutoff.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use chrono::Duration;
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn job(id: &str, job_type: &str, state: &str, priority: i32, age_minutes: i64) -> StoredJob {
+        StoredJob {
+            id: id.to_string(),
+            job_type: job_type.to_string(),
+            state: state.to_string(),
+            priority,
+            attempts: 1,
+            payload: Some(json!({ "media_id": 7 })),
+            status: json!({ "id": id, "state": state }),
+            created_at: Utc::now() - Duration::minutes(age_minutes),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jobs() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteJobQueueRepository::new(pool);
+
+        repo.save(&job("batch", "subtitle_batch", "processing", 0, 30)).await.unwrap();
+        let old = job("old", "subtitle", "pending", 1, 20);
+        repo.save(&old).await.unwrap();
+        repo.save(&job("new", "subtitle", "pending", 1, 10)).await.unwrap();
+        repo.save(&job("done", "translation", "completed", 1, 5)).await.unwrap();
+        let mut resumed = StoredJob { state: "processing".to_string(), attempts: 2, ..old };
+        resumed.status = json!({ "id": "old", "state": "processing" });
+        repo.save(&resumed).await.unwrap();
+
+        let unfinished = repo.find_unfinished().await.unwrap();
+        assert_eq!(unfinished.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(), vec!["old", "new", "batch"]);
+        assert_eq!(unfinished[0], resumed);
+        assert_eq!(repo.find("old").await.unwrap(), Some(resumed));
+        assert_eq!(repo.find("missing").await.unwrap(), None);
+
+        let subtitles = repo.list(&JobFilter { job_type: Some("subtitle".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(subtitles.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(), vec!["new", "old"]);
+        let latest = repo.list(&JobFilter { limit: 1, ..Default::default() }).await.unwrap();
+        assert_eq!(latest[0].id, "done");
+        let completed = repo.list(&JobFilter { state: Some("completed".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(completed.len(), 1);
+
+        // Only finished jobs are removed
+        assert_eq!(repo.delete_finished_before(Utc::now() + Duration::minutes(1)).await.unwrap(), 1);
+        assert_eq!(repo.list(&JobFilter::default()).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,128 @@
+//! SQLite implementation of LocalizationRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{LocalizationRepository, LocalizedMetadata};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based localization repository implementation
+pub struct SqliteLocalizationRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteLocalizationRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LocalizationRepository for SqliteLocalizationRepository {
+    async fn find(&self, media_id: i64, language: &str) -> Result<Option<LocalizedMetadata>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT media_id, language, title, overview FROM media_localizations WHERE media_id = ? AND language = ?",
+        )
+        .bind(media_id)
+        .bind(language)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| LocalizedMetadata {
+            media_id: row.get("media_id"),
+            language: row.get("language"),
+            title: row.get("title"),
+            overview: row.get("overview"),
+        }))
+    }
+
+    async fn find_by_media(&self, media_id: i64) -> Result<Vec<LocalizedMetadata>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT media_id, language, title, overview FROM media_localizations WHERE media_id = ? ORDER BY language",
+        )
+        .bind(media_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| LocalizedMetadata {
+                media_id: row.get("media_id"),
+                language: row.get("language"),
+                title: row.get("title"),
+                overview: row.get("overview"),
+            })
+            .collect())
+    }
+
+    async fn save(&self, localized: &LocalizedMetadata) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_localizations (media_id, language, title, overview, updated_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(media_id, language) DO UPDATE SET
+                title = excluded.title,
+                overview = excluded.overview,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(localized.media_id)
+        .bind(&localized.language)
+        .bind(&localized.title)
+        .bind(&localized.overview)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_by_media(&self, media_id: i64) -> Result<(), RepositoryError> {
+        sqlx::query("DELETE FROM media_localizations WHERE media_id = ?")
+            .bind(media_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_replaces_existing_language() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        sqlx::query("INSERT INTO media (id, file_path, title) VALUES (1, '/m/a.mkv', 'A')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let repo = SqliteLocalizationRepository::new(pool);
+        let mut localized = LocalizedMetadata {
+            media_id: 1,
+            language: "hu-HU".to_string(),
+            title: "Régi cím".to_string(),
+            overview: None,
+        };
+        repo.save(&localized).await.unwrap();
+
+        localized.title = "Új cím".to_string();
+        localized.overview = Some("Leírás".to_string());
+        repo.save(&localized).await.unwrap();
+
+        assert_eq!(repo.find(1, "hu-HU").await.unwrap(), Some(localized));
+        assert_eq!(repo.find(1, "de-DE").await.unwrap(), None);
+        assert_eq!(repo.find_by_media(1).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+//! SQLite implementation of LoudnessRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{LoudnessMeasurement, LoudnessRepository};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based loudness repository implementation
+pub struct SqliteLoudnessRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteLoudnessRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoudnessRepository for SqliteLoudnessRepository {
+    async fn find(&self, media_id: i64, audio_track: usize) -> Result<Option<LoudnessMeasurement>, RepositoryError> {
+        let row = sqlx::query(
+            r#"
+            SELECT input_i, input_tp, input_lra, input_thresh, target_offset
+            FROM media_loudness WHERE media_id = ? AND audio_track = ?
+            "#,
+        )
+        .bind(media_id)
+        .bind(audio_track as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| LoudnessMeasurement {
+            input_i: row.get("input_i"),
+            input_tp: row.get("input_tp"),
+            input_lra: row.get("input_lra"),
+            input_thresh: row.get("input_thresh"),
+            target_offset: row.get("target_offset"),
+        }))
+    }
+
+    async fn save(&self, media_id: i64, audio_track: usize, measurement: &LoudnessMeasurement) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO media_loudness
+                (media_id, audio_track, input_i, input_tp, input_lra, input_thresh, target_offset, measured_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(media_id, audio_track) DO UPDATE SET
+                input_i = excluded.input_i,
+                input_tp = excluded.input_tp,
+                input_lra = excluded.input_lra,
+                input_thresh = excluded.input_thresh,
+                target_offset = excluded.target_offset,
+                measured_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(media_id)
+        .bind(audio_track as i64)
+        .bind(measurement.input_i)
+        .bind(measurement.input_tp)
+        .bind(measurement.input_lra)
+        .bind(measurement.input_thresh)
+        .bind(measurement.target_offset)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_and_replace_measurement() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        // The table references media(id)
+        sqlx::query("INSERT INTO media (id, file_path, media_type, title) VALUES (4, '/movies/film.mkv', 'movie', 'Film')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteLoudnessRepository::new(pool);
+
+        let mut measurement = LoudnessMeasurement {
+            input_i: -27.6,
+            input_tp: -4.5,
+            input_lra: 18.1,
+            input_thresh: -38.2,
+            target_offset: 0.4,
+        };
+        repo.save(4, 1, &measurement).await.unwrap();
+        measurement.input_i = -25.0;
+        repo.save(4, 1, &measurement).await.unwrap();
+
+        assert_eq!(repo.find(4, 1).await.unwrap(), Some(measurement));
+        assert_eq!(repo.find(4, 0).await.unwrap(), None);
+    }
+}
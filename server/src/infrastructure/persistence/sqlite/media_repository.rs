@@ -6,10 +6,35 @@ use async_trait::async_trait;
 use sqlx::{Pool, Sqlite, Row};
 use std::str::FromStr;
 use crate::domain::entities::Media;
-use crate::domain::repositories::MediaRepository;
+use crate::domain::repositories::{MediaOrder, MediaRepository};
 use crate::domain::value_objects::{MediaType, ConfidenceScore, VerificationStatus};
 use crate::shared::error::RepositoryError;
 
+/// bm25 weights of the search columns: title, original title, overview, cast
+const SEARCH_WEIGHTS: &str = "10.0, 8.0, 1.0, 3.0";
+
+/// Lowercase words of a search query
+///
+/// Only letters and digits are kept, so the words can be quoted into an FTS5
+/// expression as they are.
+fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Typos tolerated in a search term: none for short words, one from four
+/// letters and two from eight
+fn allowed_typos(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
 /// SQLite implementation of MediaRepository
 pub struct SqliteMediaRepository {
     pool: Pool<Sqlite>,
@@ -34,6 +59,8 @@ impl SqliteMediaRepository {
             overview: row.try_get("overview")?,
             poster_url: row.try_get("poster_url")?,
             backdrop_url: row.try_get("backdrop_url")?,
+            poster_blurhash: row.try_get("poster_blurhash")?,
+            backdrop_blurhash: row.try_get("backdrop_blurhash")?,
             trailer_url: row.try_get("trailer_url")?,
             duration_seconds: row.try_get("duration_seconds")?,
             release_date: row.try_get("release_date")?,
@@ -61,6 +88,69 @@ impl SqliteMediaRepository {
     }
 }
 
+impl SqliteMediaRepository {
+    /// Media matching all terms as prefixes, best matches first
+    async fn search_index(
+        &self,
+        terms: &[String],
+        media_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Media>, RepositoryError> {
+        let expression = terms.iter().map(|t| format!("\"{}\"*", t)).collect::<Vec<_>>().join(" ");
+        let rows = sqlx::query(&format!(
+            "SELECT media.* FROM media_search JOIN media ON media.id = media_search.rowid
+             WHERE media_search MATCH ? AND (? IS NULL OR media.media_type = ?)
+             ORDER BY bm25(media_search, {}) LIMIT ?",
+            SEARCH_WEIGHTS
+        ))
+        .bind(&expression)
+        .bind(media_type)
+        .bind(media_type)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::map_row_to_media).collect()
+    }
+
+    /// Replaces terms that no indexed word starts with by the closest
+    /// indexed word within the allowed number of typos
+    async fn correct_terms(&self, terms: &[String]) -> Result<Vec<String>, RepositoryError> {
+        let mut corrected = Vec::with_capacity(terms.len());
+        for term in terms {
+            let typos = allowed_typos(term);
+            let len = term.chars().count() as i64;
+            let known: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM media_search_vocab WHERE term >= ? AND substr(term, 1, length(?)) = ?)",
+            )
+            .bind(term)
+            .bind(term)
+            .bind(term)
+            .fetch_one(&self.pool)
+            .await?;
+            if known || typos == 0 {
+                corrected.push(term.clone());
+                continue;
+            }
+
+            let candidates: Vec<(String, i64)> = sqlx::query_as(
+                "SELECT term, doc FROM media_search_vocab WHERE length(term) BETWEEN ? AND ?",
+            )
+            .bind(len - typos as i64)
+            .bind(len + typos as i64)
+            .fetch_all(&self.pool)
+            .await?;
+            let best = candidates
+                .into_iter()
+                .map(|(candidate, docs)| (strsim::levenshtein(term, &candidate), -docs, candidate))
+                .filter(|(distance, _, _)| *distance <= typos)
+                .min();
+            corrected.push(best.map(|(_, _, candidate)| candidate).unwrap_or_else(|| term.clone()));
+        }
+        Ok(corrected)
+    }
+}
+
 #[async_trait]
 impl MediaRepository for SqliteMediaRepository {
     async fn find_by_id(&self, id: i64) -> Result<Option<Media>, RepositoryError> {
@@ -104,6 +194,36 @@ impl MediaRepository for SqliteMediaRepository {
         Ok(media_list)
     }
 
+    async fn find_page(
+        &self,
+        order: MediaOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Media>, i64), RepositoryError> {
+        let column = match order {
+            MediaOrder::Title => "title COLLATE NOCASE",
+            MediaOrder::Year => "CAST(NULLIF(substr(release_date, 1, 4), '') AS INTEGER)",
+            MediaOrder::Rating => "rating",
+            MediaOrder::Added => "created_at",
+        };
+        let direction = if descending { "DESC" } else { "ASC" };
+        let rows = sqlx::query(&format!(
+            "SELECT * FROM media ORDER BY {column} {direction}, id {direction} LIMIT ? OFFSET ?"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut media_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            media_list.push(Self::map_row_to_media(row)?);
+        }
+
+        Ok((media_list, self.count().await?))
+    }
+
     async fn find_by_type(&self, media_type: MediaType) -> Result<Vec<Media>, RepositoryError> {
         let type_str = media_type.as_str();
         let rows = sqlx::query(
@@ -121,6 +241,22 @@ impl MediaRepository for SqliteMediaRepository {
         Ok(media_list)
     }
 
+    async fn find_by_tmdb_id(&self, tmdb_id: i64) -> Result<Vec<Media>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM media WHERE tmdb_id = ? ORDER BY season, episode"
+        )
+        .bind(tmdb_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut media_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            media_list.push(Self::map_row_to_media(row)?);
+        }
+
+        Ok(media_list)
+    }
+
     async fn find_by_series(&self, series_id: i64) -> Result<Vec<Media>, RepositoryError> {
         let rows = sqlx::query(
             "SELECT * FROM media WHERE series_id = ? ORDER BY season, episode"
@@ -194,8 +330,9 @@ impl MediaRepository for SqliteMediaRepository {
                 duration_seconds, release_date, resolution, genres, series_id, season, episode,
                 episode_end, tmdb_id, original_title, rating, confidence_score, verification_status,
                 identification_strategy, error_notes, alternative_matches, content_rating,
-                content_warnings, current_position, is_watched, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                content_warnings, current_position, is_watched, created_at, updated_at,
+                poster_blurhash, backdrop_blurhash
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&media.file_path)
         .bind(media.media_type.as_str())
@@ -226,6 +363,8 @@ impl MediaRepository for SqliteMediaRepository {
         .bind(media.is_watched)
         .bind(media.created_at)
         .bind(media.updated_at)
+        .bind(&media.poster_blurhash)
+        .bind(&media.backdrop_blurhash)
         .execute(&self.pool)
         .await?;
 
@@ -233,17 +372,26 @@ impl MediaRepository for SqliteMediaRepository {
     }
 
     async fn update(&self, media: &Media) -> Result<(), RepositoryError> {
+        // Blurhashes are only written by update_blurhashes; here they are
+        // dropped when the artwork they were computed from changes. The
+        // content rating is dropped with a new match, to be fetched again.
         sqlx::query(
             "UPDATE media SET
+                poster_blurhash = CASE WHEN poster_url IS ? THEN poster_blurhash END,
+                backdrop_blurhash = CASE WHEN backdrop_url IS ? THEN backdrop_blurhash END,
                 file_path = ?, media_type = ?, title = ?, overview = ?, poster_url = ?,
                 backdrop_url = ?, trailer_url = ?, duration_seconds = ?, release_date = ?,
                 resolution = ?, genres = ?, series_id = ?, season = ?, episode = ?,
                 episode_end = ?, tmdb_id = ?, original_title = ?, rating = ?, confidence_score = ?,
                 verification_status = ?, identification_strategy = ?, error_notes = ?,
-                alternative_matches = ?, content_rating = ?, content_warnings = ?,
+                alternative_matches = ?,
+                content_rating = CASE WHEN tmdb_id IS ? THEN ? END,
+                content_warnings = CASE WHEN tmdb_id IS ? THEN ? END,
                 current_position = ?, is_watched = ?, updated_at = ?
             WHERE id = ?"
         )
+        .bind(&media.poster_url)
+        .bind(&media.backdrop_url)
         .bind(&media.file_path)
         .bind(media.media_type.as_str())
         .bind(&media.title)
@@ -267,7 +415,9 @@ impl MediaRepository for SqliteMediaRepository {
         .bind(&media.identification_strategy)
         .bind(&media.error_notes)
         .bind(&media.alternative_matches)
+        .bind(media.tmdb_id)
         .bind(&media.content_rating)
+        .bind(media.tmdb_id)
         .bind(&media.content_warnings)
         .bind(media.current_position)
         .bind(media.is_watched)
@@ -382,36 +532,23 @@ impl MediaRepository for SqliteMediaRepository {
         media_type: Option<&str>,
         limit: usize,
     ) -> Result<Vec<Media>, RepositoryError> {
-        let search_pattern = format!("%{}%", query);
-
-        let rows = match media_type {
-            Some(mt) => {
-                sqlx::query(
-                    "SELECT * FROM media WHERE title LIKE ? AND media_type = ? ORDER BY title LIMIT ?"
-                )
-                .bind(&search_pattern)
-                .bind(mt)
-                .bind(limit as i64)
-                .fetch_all(&self.pool)
-                .await?
-            }
-            None => {
-                sqlx::query(
-                    "SELECT * FROM media WHERE title LIKE ? ORDER BY title LIMIT ?"
-                )
-                .bind(&search_pattern)
-                .bind(limit as i64)
-                .fetch_all(&self.pool)
-                .await?
-            }
-        };
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let mut media_list = Vec::with_capacity(rows.len());
-        for row in rows {
-            media_list.push(Self::map_row_to_media(row)?);
+        let media_list = self.search_index(&terms, media_type, limit).await?;
+        if !media_list.is_empty() {
+            return Ok(media_list);
         }
 
-        Ok(media_list)
+        // Nothing found: retry with misspelled terms replaced by the closest
+        // indexed ones
+        let corrected = self.correct_terms(&terms).await?;
+        if corrected == terms {
+            return Ok(media_list);
+        }
+        self.search_index(&corrected, media_type, limit).await
     }
 
     async fn get_progress(&self, media_id: i64) -> Result<Option<(i64, bool, String)>, RepositoryError> {
@@ -451,6 +588,48 @@ impl MediaRepository for SqliteMediaRepository {
         Ok(())
     }
 
+    async fn set_watched_many(&self, media_ids: &[i64], watched: bool) -> Result<u64, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        let mut changed = 0;
+
+        for media_id in media_ids {
+            // Library flag (shown in listings) and watch progress are kept in step
+            let result = sqlx::query(
+                "UPDATE media SET is_watched = ?, updated_at = ? WHERE id = ? AND is_watched IS NOT ?"
+            )
+            .bind(watched)
+            .bind(chrono::Utc::now())
+            .bind(media_id)
+            .bind(watched)
+            .execute(&mut *tx)
+            .await?;
+            changed += result.rows_affected();
+
+            if watched {
+                sqlx::query(
+                    r#"INSERT INTO watch_progress (media_id, current_position_seconds, is_watched, last_updated)
+                       VALUES (?, 0, 1, CURRENT_TIMESTAMP)
+                       ON CONFLICT(media_id) DO UPDATE SET
+                       is_watched = 1,
+                       last_updated = CURRENT_TIMESTAMP"#
+                )
+                .bind(media_id)
+                .execute(&mut *tx)
+                .await?;
+            } else {
+                sqlx::query(
+                    "UPDATE watch_progress SET is_watched = 0, last_updated = CURRENT_TIMESTAMP WHERE media_id = ?"
+                )
+                .bind(media_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(changed)
+    }
+
     async fn find_in_progress(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
         let rows = sqlx::query(
             "SELECT * FROM media WHERE current_position > 0 AND is_watched = 0 ORDER BY updated_at DESC LIMIT ?"
@@ -482,15 +661,215 @@ impl MediaRepository for SqliteMediaRepository {
 
         Ok(media_list)
     }
+
+    async fn find_recent_episodes(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM media WHERE media_type = 'episode' AND series_id IS NOT NULL ORDER BY created_at DESC LIMIT ?"
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut media_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            media_list.push(Self::map_row_to_media(row)?);
+        }
+
+        Ok(media_list)
+    }
+
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM media
+             WHERE (poster_url IS NOT NULL AND poster_blurhash IS NULL)
+                OR (backdrop_url IS NOT NULL AND backdrop_blurhash IS NULL)
+             ORDER BY created_at DESC LIMIT ?"
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut media_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            media_list.push(Self::map_row_to_media(row)?);
+        }
+
+        Ok(media_list)
+    }
+
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE media SET
+                poster_blurhash = COALESCE(?, poster_blurhash),
+                backdrop_blurhash = COALESCE(?, backdrop_blurhash)
+            WHERE id = ?"
+        )
+        .bind(poster_blurhash)
+        .bind(backdrop_blurhash)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Media>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT m.* FROM media m
+             LEFT JOIN series s ON s.id = m.series_id
+             WHERE m.content_rating IS NULL
+               AND ((m.media_type = 'movie' AND m.tmdb_id IS NOT NULL) OR s.content_rating IS NOT NULL)
+             ORDER BY m.created_at DESC LIMIT ?"
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut media_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            media_list.push(Self::map_row_to_media(row)?);
+        }
+
+        Ok(media_list)
+    }
+
+    async fn update_content_rating(
+        &self,
+        id: i64,
+        content_rating: &str,
+        content_warnings: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE media SET content_rating = ?, content_warnings = ? WHERE id = ?")
+            .bind(content_rating)
+            .bind(content_warnings)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_paths(&self, paths: &[(i64, String)]) -> Result<u64, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
+        let mut changed = 0;
+
+        for (id, file_path) in paths {
+            let result = sqlx::query("UPDATE media SET file_path = ?, updated_at = ? WHERE id = ?")
+                .bind(file_path)
+                .bind(chrono::Utc::now())
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            changed += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(changed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
 
     #[tokio::test]
     async fn test_map_row_to_media() {
         // This would require a real database connection
         // In a real scenario, use testcontainers or sqlite in-memory
     }
+
+    #[tokio::test]
+    async fn test_set_watched_many() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteMediaRepository::new(pool);
+        let mut ids = Vec::new();
+        for episode in 1..=3 {
+            let media = Media::new(format!("/tv/Show/S01E0{}.mkv", episode), MediaType::Episode, "Show".to_string()).unwrap();
+            ids.push(repo.save(&media).await.unwrap());
+        }
+        repo.mark_watched(ids[0]).await.unwrap();
+        repo.update_progress(ids[0], 0, true).await.unwrap();
+
+        // Only items whose state changes are counted
+        assert_eq!(repo.set_watched_many(&ids, true).await.unwrap(), 2);
+        assert_eq!(repo.find_watched().await.unwrap().len(), 3);
+        assert!(repo.get_progress(ids[2]).await.unwrap().unwrap().1);
+
+        assert_eq!(repo.set_watched_many(&ids[..2], false).await.unwrap(), 2);
+        assert_eq!(repo.find_watched().await.unwrap().len(), 1);
+        assert!(!repo.get_progress(ids[0]).await.unwrap().unwrap().1);
+    }
+
+    #[tokio::test]
+    async fn test_full_text_search() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteMediaRepository::new(pool.clone());
+        let mut media = Media::new("/movies/shawshank.mkv".to_string(), MediaType::Movie, "The Shawshank Redemption".to_string()).unwrap();
+        media.overview = Some("Two imprisoned men bond over a number of years.".to_string());
+        let id = repo.save(&media).await.unwrap();
+        let other = Media::new("/movies/redemption.mkv".to_string(), MediaType::Movie, "Redemption Road".to_string()).unwrap();
+        repo.save(&other).await.unwrap();
+        sqlx::query("INSERT INTO media_credits (media_id, person_id, person_name, role, credit_type) VALUES (?, 192, 'Morgan Freeman', 'Actor', 'cast')")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let titles = |list: Vec<Media>| list.into_iter().map(|m| m.title).collect::<Vec<_>>();
+        // Prefixes, cast names and overviews match; title matches rank first
+        assert_eq!(titles(repo.search("shaw", None, 10).await.unwrap()), vec!["The Shawshank Redemption"]);
+        assert_eq!(titles(repo.search("freeman", None, 10).await.unwrap()), vec!["The Shawshank Redemption"]);
+        assert_eq!(titles(repo.search("imprisoned", Some("movie"), 10).await.unwrap()), vec!["The Shawshank Redemption"]);
+        assert_eq!(repo.search("redemption", None, 10).await.unwrap().len(), 2);
+        // Typos
+        assert_eq!(titles(repo.search("shawshenk", None, 10).await.unwrap()), vec!["The Shawshank Redemption"]);
+        assert!(repo.search("(\"", None, 10).await.unwrap().is_empty());
+
+        repo.delete(id).await.unwrap();
+        assert!(repo.search("freeman", None, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_page() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteMediaRepository::new(pool);
+        for (title, release_date, rating) in [("b", "1994-05-01", 8.1), ("C", "2004-01-01", 6.5), ("a", "", 7.0)] {
+            let mut media = Media::new(format!("/movies/{}.mkv", title), MediaType::Movie, title.to_string()).unwrap();
+            media.release_date = Some(release_date.to_string());
+            media.rating = Some(rating);
+            repo.save(&media).await.unwrap();
+        }
+
+        let titles = |(list, total): (Vec<Media>, i64)| (list.into_iter().map(|m| m.title).collect::<Vec<_>>(), total);
+        assert_eq!(titles(repo.find_page(MediaOrder::Title, false, 0, 2).await.unwrap()), (vec!["a".into(), "b".into()], 3));
+        assert_eq!(titles(repo.find_page(MediaOrder::Title, false, 2, 2).await.unwrap()), (vec!["C".into()], 3));
+        // Media without a release date come last when newest first
+        assert_eq!(titles(repo.find_page(MediaOrder::Year, true, 0, 3).await.unwrap()).0, vec!["C", "b", "a"]);
+        assert_eq!(titles(repo.find_page(MediaOrder::Rating, true, 0, 1).await.unwrap()).0, vec!["b"]);
+    }
 }
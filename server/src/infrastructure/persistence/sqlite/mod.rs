@@ -4,12 +4,68 @@
 
 pub mod media_repository;
 pub mod series_repository;
+pub mod stream_token_repository;
 pub mod collection_repository;
 pub mod cache_repository;
 pub mod credits_repository;
+pub mod localization_repository;
+pub mod person_repository;
+pub mod artwork_repository;
+pub mod quality_preference_repository;
+pub mod subtitle_offset_repository;
+pub mod audio_preference_repository;
+pub mod generated_subtitle_repository;
+pub mod job_history_repository;
+pub mod job_queue_repository;
+pub mod loudness_repository;
+pub mod subtitle_preference_repository;
+pub mod bookmark_repository;
+pub mod crop_repository;
+pub mod device_key_repository;
+pub mod accessibility_preference_repository;
+pub mod parental_control_repository;
+pub mod profile_repository;
+pub mod audit_log_repository;
+pub mod playlist_repository;
+pub mod watch_history_repository;
+pub mod webhook_repository;
+pub mod notification_channel_repository;
+pub mod scheduled_task_repository;
+pub mod settings_repository;
+pub mod embedding_repository;
+pub mod dialogue_repository;
+pub mod tmdb_response_repository;
 
 pub use media_repository::SqliteMediaRepository;
 pub use series_repository::SqliteSeriesRepository;
+pub use stream_token_repository::SqliteStreamTokenRepository;
 pub use collection_repository::SqliteCollectionRepository;
 pub use cache_repository::SqliteCacheRepository;
 pub use credits_repository::SqliteCreditsRepository;
+pub use localization_repository::SqliteLocalizationRepository;
+pub use person_repository::SqlitePersonRepository;
+pub use artwork_repository::SqliteArtworkRepository;
+pub use quality_preference_repository::SqliteQualityPreferenceRepository;
+pub use subtitle_offset_repository::SqliteSubtitleOffsetRepository;
+pub use audio_preference_repository::SqliteAudioPreferenceRepository;
+pub use generated_subtitle_repository::SqliteGeneratedSubtitleRepository;
+pub use job_history_repository::SqliteJobHistoryRepository;
+pub use job_queue_repository::SqliteJobQueueRepository;
+pub use loudness_repository::SqliteLoudnessRepository;
+pub use subtitle_preference_repository::SqliteSubtitlePreferenceRepository;
+pub use bookmark_repository::SqliteBookmarkRepository;
+pub use crop_repository::SqliteCropRepository;
+pub use device_key_repository::SqliteDeviceKeyRepository;
+pub use accessibility_preference_repository::SqliteAccessibilityPreferenceRepository;
+pub use parental_control_repository::SqliteParentalControlRepository;
+pub use profile_repository::SqliteProfileRepository;
+pub use audit_log_repository::SqliteAuditLogRepository;
+pub use playlist_repository::SqlitePlaylistRepository;
+pub use watch_history_repository::SqliteWatchHistoryRepository;
+pub use webhook_repository::SqliteWebhookRepository;
+pub use notification_channel_repository::SqliteNotificationChannelRepository;
+pub use scheduled_task_repository::SqliteScheduledTaskRepository;
+pub use settings_repository::SqliteSettingsRepository;
+pub use embedding_repository::SqliteEmbeddingRepository;
+pub use dialogue_repository::SqliteDialogueRepository;
+pub use tmdb_response_repository::SqliteTmdbResponseRepository;
@@ -0,0 +1,158 @@
+//! SQLite implementation of NotificationChannelRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{NotificationChannel, NotificationChannelRepository, NotificationChannelSettings};
+use crate::shared::error::RepositoryError;
+
+const CHANNEL_SELECT: &str =
+    "SELECT id, name, kind, config, event_types, enabled, created_at, updated_at FROM notification_channels";
+
+/// SQLite-based notification channel repository implementation
+pub struct SqliteNotificationChannelRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteNotificationChannelRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_channel(row: &SqliteRow) -> Result<NotificationChannel, RepositoryError> {
+    let config: String = row.get("config");
+    let event_types: String = row.get("event_types");
+    Ok(NotificationChannel {
+        id: row.get("id"),
+        name: row.get("name"),
+        kind: row.get("kind"),
+        config: serde_json::from_str(&config)?,
+        event_types: event_types.split(',').filter(|t| !t.is_empty()).map(String::from).collect(),
+        enabled: row.get("enabled"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+#[async_trait]
+impl NotificationChannelRepository for SqliteNotificationChannelRepository {
+    async fn find_all(&self) -> Result<Vec<NotificationChannel>, RepositoryError> {
+        let rows = sqlx::query(&format!("{} ORDER BY id", CHANNEL_SELECT))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter().map(row_to_channel).collect()
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<NotificationChannel>, RepositoryError> {
+        let row = sqlx::query(&format!("{} WHERE id = ?", CHANNEL_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        row.as_ref().map(row_to_channel).transpose()
+    }
+
+    async fn create(&self, settings: &NotificationChannelSettings) -> Result<NotificationChannel, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "INSERT INTO notification_channels (name, kind, config, event_types, enabled, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&settings.name)
+        .bind(&settings.kind)
+        .bind(settings.config.to_string())
+        .bind(settings.event_types.join(","))
+        .bind(settings.enabled)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        self.find(result.last_insert_rowid())
+            .await?
+            .ok_or_else(|| RepositoryError::Database("Created notification channel not found".to_string()))
+    }
+
+    async fn update(
+        &self,
+        id: i64,
+        settings: &NotificationChannelSettings,
+    ) -> Result<Option<NotificationChannel>, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE notification_channels
+             SET name = ?, kind = ?, config = ?, event_types = ?, enabled = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&settings.name)
+        .bind(&settings.kind)
+        .bind(settings.config.to_string())
+        .bind(settings.event_types.join(","))
+        .bind(settings.enabled)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find(id).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM notification_channels WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_notification_channels() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteNotificationChannelRepository::new(pool);
+
+        let mut settings = NotificationChannelSettings {
+            name: "Family chat".to_string(),
+            kind: "telegram".to_string(),
+            config: json!({ "bot_token": "123:abc", "chat_id": "-100123" }),
+            event_types: vec!["media_identified".to_string()],
+            enabled: true,
+        };
+        let channel = repo.create(&settings).await.unwrap();
+        assert_eq!(channel.config, settings.config);
+        assert!(channel.accepts("media_identified") && !channel.accepts("scan_failed"));
+
+        settings.event_types.clear();
+        settings.config["chat_id"] = json!("42");
+        let updated = repo.update(channel.id, &settings).await.unwrap().unwrap();
+        assert!(updated.accepts("scan_failed"));
+        assert_eq!(updated.config["chat_id"], "42");
+        assert!(repo.update(99, &settings).await.unwrap().is_none());
+
+        assert!(repo.delete(channel.id).await.unwrap());
+        assert!(!repo.delete(channel.id).await.unwrap());
+        assert!(repo.find_all().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! SQLite implementation of ParentalControlRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Row, Sqlite};
+use crate::domain::repositories::{ParentalControlRepository, ParentalControls, PinFailures, StoredParentalControls};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based parental control repository implementation
+pub struct SqliteParentalControlRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteParentalControlRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ParentalControlRepository for SqliteParentalControlRepository {
+    async fn find(&self, user_id: &str) -> Result<Option<StoredParentalControls>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT max_age, block_unrated, blocked_tags, pin_hash FROM user_parental_controls WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        let Some(row) = row else { return Ok(None) };
+        let max_age: Option<i64> = row.get("max_age");
+        let blocked_tags: String = row.get("blocked_tags");
+        Ok(Some(StoredParentalControls {
+            controls: ParentalControls {
+                max_age: max_age.map(|age| age.clamp(0, u8::MAX as i64) as u8),
+                block_unrated: row.get("block_unrated"),
+                blocked_tags: serde_json::from_str(&blocked_tags)
+                    .map_err(|e| RepositoryError::Database(format!("Invalid blocked tags: {}", e)))?,
+            },
+            pin_hash: row.get("pin_hash"),
+        }))
+    }
+
+    async fn save(&self, user_id: &str, controls: &StoredParentalControls) -> Result<(), RepositoryError> {
+        let blocked_tags = serde_json::to_string(&controls.controls.blocked_tags)
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        sqlx::query(
+            r#"
+            INSERT INTO user_parental_controls (user_id, max_age, block_unrated, blocked_tags, pin_hash, updated_at)
+            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(user_id) DO UPDATE SET
+                max_age = excluded.max_age,
+                block_unrated = excluded.block_unrated,
+                blocked_tags = excluded.blocked_tags,
+                pin_hash = excluded.pin_hash,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(controls.controls.max_age.map(i64::from))
+        .bind(controls.controls.block_unrated)
+        .bind(blocked_tags)
+        .bind(&controls.pin_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM user_parental_controls WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn pin_failures(&self, user_id: &str) -> Result<PinFailures, RepositoryError> {
+        let row = sqlx::query("SELECT pin_failures, pin_failed_at FROM user_parental_controls WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        let Some(row) = row else { return Ok(PinFailures::default()) };
+        let last_at: Option<String> = row.get("pin_failed_at");
+        Ok(PinFailures {
+            count: row.get::<i64, _>("pin_failures").clamp(0, u32::MAX as i64) as u32,
+            last_at: last_at
+                .map(|at| {
+                    DateTime::parse_from_rfc3339(&at)
+                        .map(|at| at.with_timezone(&Utc))
+                        .map_err(|e| RepositoryError::Database(format!("Invalid pin_failed_at '{}': {}", at, e)))
+                })
+                .transpose()?,
+        })
+    }
+
+    async fn save_pin_failures(&self, user_id: &str, failures: &PinFailures) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE user_parental_controls SET pin_failures = ?, pin_failed_at = ? WHERE user_id = ?")
+            .bind(i64::from(failures.count))
+            .bind(failures.last_at.map(|at| at.to_rfc3339()))
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! SQLite implementation of PersonRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{PersonRepository, Person};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based person repository implementation
+pub struct SqlitePersonRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePersonRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PersonRepository for SqlitePersonRepository {
+    async fn find_by_id(&self, id: i64) -> Result<Option<Person>, RepositoryError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, biography, birthday, deathday, place_of_birth,
+                   profile_url, known_for_department, imdb_id, updated_at
+            FROM people
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| Person {
+            id: row.get("id"),
+            name: row.get("name"),
+            biography: row.get("biography"),
+            birthday: row.get("birthday"),
+            deathday: row.get("deathday"),
+            place_of_birth: row.get("place_of_birth"),
+            profile_url: row.get("profile_url"),
+            known_for_department: row.get("known_for_department"),
+            imdb_id: row.get("imdb_id"),
+            updated_at: row.get("updated_at"),
+        }))
+    }
+
+    async fn save(&self, person: &Person) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO people
+            (id, name, biography, birthday, deathday, place_of_birth, profile_url, known_for_department, imdb_id, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(person.id)
+        .bind(&person.name)
+        .bind(&person.biography)
+        .bind(&person.birthday)
+        .bind(&person.deathday)
+        .bind(&person.place_of_birth)
+        .bind(&person.profile_url)
+        .bind(&person.known_for_department)
+        .bind(&person.imdb_id)
+        .bind(person.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+//! SQLite implementation of PlaylistRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{Playlist, PlaylistItem, PlaylistRepository};
+use crate::shared::error::RepositoryError;
+
+const PLAYLIST_SELECT: &str = "SELECT p.id, p.user_id, p.name, p.description, p.created_at, p.updated_at,
+    (SELECT COUNT(*) FROM playlist_items i WHERE i.playlist_id = p.id) AS item_count
+    FROM playlists p";
+
+/// SQLite-based playlist repository implementation
+pub struct SqlitePlaylistRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePlaylistRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Marks a playlist as changed
+    async fn touch(&self, tx: &mut sqlx::Transaction<'_, Sqlite>, playlist_id: i64) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE playlists SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(playlist_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn row_to_playlist(row: &SqliteRow) -> Playlist {
+    Playlist {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        item_count: row.get("item_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn row_to_item(row: &SqliteRow) -> PlaylistItem {
+    PlaylistItem {
+        id: row.get("id"),
+        media_id: row.get("media_id"),
+        position: row.get("position"),
+        added_at: row.get("added_at"),
+    }
+}
+
+#[async_trait]
+impl PlaylistRepository for SqlitePlaylistRepository {
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Playlist>, RepositoryError> {
+        let rows = sqlx::query(&format!("{} WHERE p.user_id = ? ORDER BY p.name COLLATE NOCASE, p.id", PLAYLIST_SELECT))
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_playlist).collect())
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Playlist>, RepositoryError> {
+        let row = sqlx::query(&format!("{} WHERE p.id = ?", PLAYLIST_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_playlist))
+    }
+
+    async fn create(&self, user_id: &str, name: &str, description: Option<&str>) -> Result<Playlist, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "INSERT INTO playlists (user_id, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(description)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        self.find(result.last_insert_rowid())
+            .await?
+            .ok_or_else(|| RepositoryError::Database("Created playlist not found".to_string()))
+    }
+
+    async fn update(&self, id: i64, name: &str, description: Option<&str>) -> Result<Option<Playlist>, RepositoryError> {
+        let result = sqlx::query("UPDATE playlists SET name = ?, description = ?, updated_at = ? WHERE id = ?")
+            .bind(name)
+            .bind(description)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find(id).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        sqlx::query("DELETE FROM playlist_items WHERE playlist_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let result = sqlx::query("DELETE FROM playlists WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn items(&self, playlist_id: i64) -> Result<Vec<PlaylistItem>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, media_id, position, added_at FROM playlist_items WHERE playlist_id = ? ORDER BY position",
+        )
+        .bind(playlist_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_item).collect())
+    }
+
+    async fn add_item(&self, playlist_id: i64, media_id: i64, position: Option<i64>) -> Result<PlaylistItem, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM playlist_items WHERE playlist_id = ?")
+            .bind(playlist_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let position = position.map_or(count, |p| p.clamp(0, count));
+
+        sqlx::query("UPDATE playlist_items SET position = position + 1 WHERE playlist_id = ? AND position >= ?")
+            .bind(playlist_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let added_at = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "INSERT INTO playlist_items (playlist_id, media_id, position, added_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(playlist_id)
+        .bind(media_id)
+        .bind(position)
+        .bind(&added_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        self.touch(&mut tx, playlist_id).await?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(PlaylistItem {
+            id: result.last_insert_rowid(),
+            media_id,
+            position,
+            added_at,
+        })
+    }
+
+    async fn remove_item(&self, playlist_id: i64, item_id: i64) -> Result<bool, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let Some(position) = sqlx::query_scalar::<_, i64>(
+            "SELECT position FROM playlist_items WHERE playlist_id = ? AND id = ?",
+        )
+        .bind(playlist_id)
+        .bind(item_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?
+        else {
+            return Ok(false);
+        };
+
+        sqlx::query("DELETE FROM playlist_items WHERE id = ?")
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        sqlx::query("UPDATE playlist_items SET position = position - 1 WHERE playlist_id = ? AND position > ?")
+            .bind(playlist_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        self.touch(&mut tx, playlist_id).await?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(true)
+    }
+
+    async fn reorder(&self, playlist_id: i64, item_ids: &[i64]) -> Result<(), RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        for (position, item_id) in item_ids.iter().enumerate() {
+            sqlx::query("UPDATE playlist_items SET position = ? WHERE playlist_id = ? AND id = ?")
+                .bind(position as i64)
+                .bind(playlist_id)
+                .bind(item_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        }
+        self.touch(&mut tx, playlist_id).await?;
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_playlist_item_order() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqlitePlaylistRepository::new(pool);
+        let playlist = repo.create("home", "Halloween night", None).await.unwrap();
+        let media_order = |items: Vec<PlaylistItem>| items.into_iter().map(|i| i.media_id).collect::<Vec<_>>();
+
+        let first = repo.add_item(playlist.id, 10, None).await.unwrap();
+        repo.add_item(playlist.id, 20, None).await.unwrap();
+        repo.add_item(playlist.id, 30, Some(0)).await.unwrap();
+        repo.add_item(playlist.id, 10, Some(99)).await.unwrap();
+        assert_eq!(media_order(repo.items(playlist.id).await.unwrap()), vec![30, 10, 20, 10]);
+        assert_eq!(repo.find(playlist.id).await.unwrap().unwrap().item_count, 4);
+
+        assert!(repo.remove_item(playlist.id, first.id).await.unwrap());
+        assert!(!repo.remove_item(playlist.id, first.id).await.unwrap());
+        let items = repo.items(playlist.id).await.unwrap();
+        assert_eq!(items.iter().map(|i| i.position).collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let reversed: Vec<i64> = items.iter().rev().map(|i| i.id).collect();
+        repo.reorder(playlist.id, &reversed).await.unwrap();
+        assert_eq!(media_order(repo.items(playlist.id).await.unwrap()), vec![10, 20, 30]);
+
+        assert!(repo.delete(playlist.id).await.unwrap());
+        assert!(repo.items(playlist.id).await.unwrap().is_empty());
+        assert!(repo.find_by_user("home").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! SQLite implementation of ProfileRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{Profile, ProfileRepository, ProfileSettings};
+use crate::shared::error::RepositoryError;
+
+const COLUMNS: &str = "id, user_id, name, avatar, audio_language, subtitle_language, ui_language, kid_mode, is_active, created_at, updated_at";
+
+/// SQLite-based profile repository implementation
+pub struct SqliteProfileRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteProfileRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_profile(row: &SqliteRow) -> Profile {
+    Profile {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        settings: ProfileSettings {
+            name: row.get("name"),
+            avatar: row.get("avatar"),
+            audio_language: row.get("audio_language"),
+            subtitle_language: row.get("subtitle_language"),
+            ui_language: row.get("ui_language"),
+            kid_mode: row.get("kid_mode"),
+        },
+        is_active: row.get("is_active"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[async_trait]
+impl ProfileRepository for SqliteProfileRepository {
+    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Profile>, RepositoryError> {
+        let rows = sqlx::query(&format!("SELECT {} FROM user_profiles WHERE user_id = ? ORDER BY id", COLUMNS))
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_profile).collect())
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Profile>, RepositoryError> {
+        let row = sqlx::query(&format!("SELECT {} FROM user_profiles WHERE id = ?", COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_profile))
+    }
+
+    async fn find_active(&self, user_id: &str) -> Result<Option<Profile>, RepositoryError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM user_profiles WHERE user_id = ? AND is_active = 1 LIMIT 1",
+            COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_profile))
+    }
+
+    async fn create(&self, user_id: &str, settings: &ProfileSettings) -> Result<Profile, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_profiles (user_id, name, avatar, audio_language, subtitle_language, ui_language,
+                kid_mode, is_active, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?,
+                NOT EXISTS (SELECT 1 FROM user_profiles WHERE user_id = ?), ?, ?)
+            "#,
+        )
+        .bind(user_id)
+        .bind(&settings.name)
+        .bind(&settings.avatar)
+        .bind(&settings.audio_language)
+        .bind(&settings.subtitle_language)
+        .bind(&settings.ui_language)
+        .bind(settings.kid_mode)
+        .bind(user_id)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        self.find(result.last_insert_rowid())
+            .await?
+            .ok_or_else(|| RepositoryError::Database("Created profile not found".to_string()))
+    }
+
+    async fn update(&self, id: i64, settings: &ProfileSettings) -> Result<Option<Profile>, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE user_profiles SET name = ?, avatar = ?, audio_language = ?, subtitle_language = ?,
+                ui_language = ?, kid_mode = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&settings.name)
+        .bind(&settings.avatar)
+        .bind(&settings.audio_language)
+        .bind(&settings.subtitle_language)
+        .bind(&settings.ui_language)
+        .bind(settings.kid_mode)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find(id).await
+    }
+
+    async fn activate(&self, id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE user_profiles SET is_active = (id = ?)
+             WHERE user_id = (SELECT user_id FROM user_profiles WHERE id = ?)",
+        )
+        .bind(id)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError> {
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        let Some(row) = sqlx::query("SELECT user_id, is_active FROM user_profiles WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?
+        else {
+            return Ok(false);
+        };
+        let user_id: String = row.get("user_id");
+        let was_active: bool = row.get("is_active");
+
+        sqlx::query("DELETE FROM user_profiles WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        if was_active {
+            sqlx::query(
+                "UPDATE user_profiles SET is_active = 1
+                 WHERE id = (SELECT MIN(id) FROM user_profiles WHERE user_id = ?)",
+            )
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        }
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_one_active_profile_per_user() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteProfileRepository::new(pool);
+        let settings = |name: &str| ProfileSettings { name: name.to_string(), ..Default::default() };
+        let anna = repo.create("home", &settings("Anna")).await.unwrap();
+        let kid = repo
+            .create("home", &ProfileSettings { kid_mode: true, ..settings("Kid") })
+            .await
+            .unwrap();
+        let other = repo.create("guest", &settings("Guest")).await.unwrap();
+        assert!(anna.is_active && !kid.is_active && other.is_active);
+
+        assert!(repo.activate(kid.id).await.unwrap());
+        assert_eq!(repo.find_active("home").await.unwrap().map(|p| p.id), Some(kid.id));
+        assert!(repo.find_active("guest").await.unwrap().is_some_and(|p| p.id == other.id));
+
+        let renamed = repo.update(kid.id, &ProfileSettings { kid_mode: true, ..settings("Lili") }).await.unwrap().unwrap();
+        assert_eq!(renamed.settings.name, "Lili");
+        assert!(renamed.is_active);
+
+        // Deleting the active profile activates the oldest remaining one
+        assert!(repo.delete(kid.id).await.unwrap());
+        assert_eq!(repo.find_active("home").await.unwrap().map(|p| p.id), Some(anna.id));
+        assert!(!repo.delete(kid.id).await.unwrap());
+        assert!(!repo.activate(kid.id).await.unwrap());
+        assert_eq!(repo.find_by_user("home").await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+//! SQLite implementation of QualityPreferenceRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::QualityPreferenceRepository;
+use crate::domain::value_objects::{QualityConstraint, QualityPreset};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based quality preference repository implementation
+pub struct SqliteQualityPreferenceRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteQualityPreferenceRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QualityPreferenceRepository for SqliteQualityPreferenceRepository {
+    async fn find(&self, device_id: &str) -> Result<Option<QualityConstraint>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT quality, max_bitrate_kbps FROM device_quality_preferences WHERE device_id = ?",
+        )
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| {
+            let quality: Option<String> = row.get("quality");
+            let max_bitrate_kbps: Option<i64> = row.get("max_bitrate_kbps");
+            QualityConstraint::new(
+                quality.as_deref().and_then(QualityPreset::parse),
+                max_bitrate_kbps.map(|b| b as u32),
+            )
+        }))
+    }
+
+    async fn save(&self, device_id: &str, constraint: &QualityConstraint) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO device_quality_preferences (device_id, quality, max_bitrate_kbps, updated_at)
+            VALUES (?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(device_id) DO UPDATE SET
+                quality = excluded.quality,
+                max_bitrate_kbps = excluded.max_bitrate_kbps,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(device_id)
+        .bind(constraint.quality.map(|q| q.as_str()))
+        .bind(constraint.max_bitrate_kbps.map(|b| b as i64))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, device_id: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM device_quality_preferences WHERE device_id = ?")
+            .bind(device_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_find_delete() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteQualityPreferenceRepository::new(pool);
+        assert_eq!(repo.find("living-room-tv").await.unwrap(), None);
+
+        repo.save("living-room-tv", &QualityConstraint::new(Some(QualityPreset::P1080), None)).await.unwrap();
+        let constraint = QualityConstraint::new(Some(QualityPreset::P720), Some(3000));
+        repo.save("living-room-tv", &constraint).await.unwrap();
+        assert_eq!(repo.find("living-room-tv").await.unwrap(), Some(constraint));
+
+        assert!(repo.delete("living-room-tv").await.unwrap());
+        assert!(!repo.delete("living-room-tv").await.unwrap());
+    }
+}
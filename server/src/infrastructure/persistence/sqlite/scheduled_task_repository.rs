@@ -0,0 +1,125 @@
+//! SQLite implementation of ScheduledTaskRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{ScheduledTaskRepository, StoredTask, TaskRun};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based scheduled task repository implementation
+pub struct SqliteScheduledTaskRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteScheduledTaskRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ScheduledTaskRepository for SqliteScheduledTaskRepository {
+    async fn find_all(&self) -> Result<Vec<StoredTask>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT name, schedule, enabled, last_started_at, last_duration_ms, last_success, last_message
+             FROM scheduled_tasks ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let started_at: Option<String> = row.get("last_started_at");
+                let last_run = started_at
+                    .map(|started_at| -> Result<TaskRun, RepositoryError> {
+                        Ok(TaskRun {
+                            started_at: DateTime::parse_from_rfc3339(&started_at)
+                                .map_err(|e| RepositoryError::Database(format!("Invalid task run time '{}': {}", started_at, e)))?
+                                .with_timezone(&Utc),
+                            duration_ms: row.get::<Option<i64>, _>("last_duration_ms").unwrap_or(0) as u64,
+                            success: row.get::<Option<bool>, _>("last_success").unwrap_or(false),
+                            message: row.get("last_message"),
+                        })
+                    })
+                    .transpose()?;
+                Ok(StoredTask {
+                    name: row.get("name"),
+                    schedule: row.get("schedule"),
+                    enabled: row.get("enabled"),
+                    last_run,
+                })
+            })
+            .collect()
+    }
+
+    async fn save(&self, task: &StoredTask) -> Result<(), RepositoryError> {
+        let run = task.last_run.as_ref();
+        sqlx::query(
+            "INSERT INTO scheduled_tasks
+                (name, schedule, enabled, last_started_at, last_duration_ms, last_success, last_message, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(name) DO UPDATE SET
+                schedule = excluded.schedule, enabled = excluded.enabled,
+                last_started_at = excluded.last_started_at, last_duration_ms = excluded.last_duration_ms,
+                last_success = excluded.last_success, last_message = excluded.last_message,
+                updated_at = excluded.updated_at",
+        )
+        .bind(&task.name)
+        .bind(&task.schedule)
+        .bind(task.enabled)
+        .bind(run.map(|r| r.started_at.to_rfc3339()))
+        .bind(run.map(|r| r.duration_ms as i64))
+        .bind(run.map(|r| r.success))
+        .bind(run.and_then(|r| r.message.clone()))
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_save_and_find() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteScheduledTaskRepository::new(pool);
+        assert!(repo.find_all().await.unwrap().is_empty());
+
+        let scan = StoredTask {
+            name: "library_scan".to_string(),
+            schedule: Some("*/30 * * * *".to_string()),
+            ..Default::default()
+        };
+        repo.save(&scan).await.unwrap();
+        let maintenance = StoredTask {
+            name: "db_maintenance".to_string(),
+            enabled: Some(false),
+            last_run: Some(TaskRun {
+                started_at: DateTime::parse_from_rfc3339("2026-10-17T04:00:00Z").unwrap().with_timezone(&Utc),
+                duration_ms: 1250,
+                success: false,
+                message: Some("database is locked".to_string()),
+            }),
+            ..Default::default()
+        };
+        repo.save(&maintenance).await.unwrap();
+        assert_eq!(repo.find_all().await.unwrap(), vec![maintenance.clone(), scan.clone()]);
+
+        // Saving replaces the earlier state
+        let scan = StoredTask { schedule: None, enabled: Some(true), ..scan };
+        repo.save(&scan).await.unwrap();
+        assert_eq!(repo.find_all().await.unwrap(), vec![maintenance, scan]);
+    }
+}
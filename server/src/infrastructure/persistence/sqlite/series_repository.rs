@@ -6,10 +6,26 @@ use async_trait::async_trait;
 use sqlx::{Pool, Sqlite, Row};
 use std::str::FromStr;
 use crate::domain::entities::Series;
-use crate::domain::repositories::SeriesRepository;
+use crate::domain::repositories::{SeriesOrder, SeriesRepository};
 use crate::domain::value_objects::{ConfidenceScore, VerificationStatus};
 use crate::shared::error::RepositoryError;
 
+/// Series as listed: deduplicated by tmdb_id to avoid showing the same series
+/// twice if there are duplicate entries. For each tmdb_id, the series with
+/// the most episodes is picked.
+const LISTED_SERIES: &str = r#"
+    SELECT * FROM (
+        SELECT s.*, ROW_NUMBER() OVER (
+            PARTITION BY COALESCE(s.tmdb_id, s.id)
+            ORDER BY (
+                SELECT COUNT(*) FROM media m WHERE m.series_id = s.id AND m.media_type = 'episode'
+            ) DESC, s.created_at DESC, s.id DESC
+        ) AS pick
+        FROM series s
+    )
+    WHERE pick = 1
+"#;
+
 /// SQLite implementation of SeriesRepository
 pub struct SqliteSeriesRepository {
     pool: Pool<Sqlite>,
@@ -33,6 +49,8 @@ impl SqliteSeriesRepository {
             overview: row.try_get("overview")?,
             poster_url: row.try_get("poster_url")?,
             backdrop_url: row.try_get("backdrop_url")?,
+            poster_blurhash: row.try_get("poster_blurhash")?,
+            backdrop_blurhash: row.try_get("backdrop_blurhash")?,
             confidence_score: ConfidenceScore::new(row.try_get("confidence_score")?)?,
             verification_status: VerificationStatus::from_str(row.try_get("verification_status")?)?,
             first_air_date: row.try_get("first_air_date")?,
@@ -43,6 +61,7 @@ impl SqliteSeriesRepository {
             original_title: row.try_get("original_title")?,
             genres: row.try_get("genres")?,
             rating: row.try_get("rating")?,
+            content_rating: row.try_get("content_rating")?,
             alternative_matches: row.try_get("alternative_matches")?,
             error_notes: row.try_get("error_notes")?,
             last_verified: row.try_get("last_verified")?,
@@ -97,25 +116,37 @@ impl SeriesRepository for SqliteSeriesRepository {
     }
 
     async fn find_all(&self) -> Result<Vec<Series>, RepositoryError> {
-        // Deduplicate by tmdb_id to avoid showing same series twice if there are
-        // duplicate entries. For each tmdb_id, pick the series with most episodes.
-        let rows = sqlx::query(
-            r#"
-            SELECT s.*
-            FROM series s
-            LEFT JOIN media m ON m.series_id = s.id AND m.media_type = 'episode'
-            GROUP BY COALESCE(s.tmdb_id, s.id)
-            HAVING s.id = (
-                SELECT s2.id FROM series s2
-                LEFT JOIN media m2 ON m2.series_id = s2.id AND m2.media_type = 'episode'
-                WHERE COALESCE(s2.tmdb_id, s2.id) = COALESCE(s.tmdb_id, s.id)
-                GROUP BY s2.id
-                ORDER BY COUNT(m2.id) DESC, s2.created_at DESC
-                LIMIT 1
-            )
-            ORDER BY s.created_at DESC
-            "#
-        )
+        let rows = sqlx::query(&format!("{LISTED_SERIES} ORDER BY created_at DESC"))
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut series_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            series_list.push(Self::map_row_to_series(row)?);
+        }
+
+        Ok(series_list)
+    }
+
+    async fn find_page(
+        &self,
+        order: SeriesOrder,
+        descending: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<Series>, i64), RepositoryError> {
+        let column = match order {
+            SeriesOrder::Title => "title COLLATE NOCASE",
+            SeriesOrder::Year => "first_air_date",
+            SeriesOrder::Rating => "rating",
+            SeriesOrder::Added => "created_at",
+        };
+        let direction = if descending { "DESC" } else { "ASC" };
+        let rows = sqlx::query(&format!(
+            "{LISTED_SERIES} ORDER BY {column} {direction}, id {direction} LIMIT ? OFFSET ?"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await?;
 
@@ -124,7 +155,11 @@ impl SeriesRepository for SqliteSeriesRepository {
             series_list.push(Self::map_row_to_series(row)?);
         }
 
-        Ok(series_list)
+        let total = sqlx::query("SELECT COUNT(DISTINCT COALESCE(tmdb_id, id)) as count FROM series")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok((series_list, total.try_get("count")?))
     }
 
     async fn find_by_verification_status(
@@ -172,8 +207,9 @@ impl SeriesRepository for SqliteSeriesRepository {
                 tmdb_id, title, overview, poster_url, backdrop_url, confidence_score,
                 verification_status, first_air_date, last_air_date, status, total_seasons,
                 total_episodes, original_title, genres, rating, alternative_matches,
-                error_notes, last_verified, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                error_notes, last_verified, created_at, updated_at, poster_blurhash,
+                backdrop_blurhash
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(series.tmdb_id)
         .bind(&series.title)
@@ -195,6 +231,8 @@ impl SeriesRepository for SqliteSeriesRepository {
         .bind(series.last_verified)
         .bind(series.created_at)
         .bind(series.updated_at)
+        .bind(&series.poster_blurhash)
+        .bind(&series.backdrop_blurhash)
         .execute(&self.pool)
         .await?;
 
@@ -202,8 +240,14 @@ impl SeriesRepository for SqliteSeriesRepository {
     }
 
     async fn update(&self, series: &Series) -> Result<(), RepositoryError> {
+        // Blurhashes and the content rating are only written by their own
+        // updates; here they are dropped when the artwork or match they were
+        // computed from changes
         sqlx::query(
             "UPDATE series SET
+                poster_blurhash = CASE WHEN poster_url IS ? THEN poster_blurhash END,
+                backdrop_blurhash = CASE WHEN backdrop_url IS ? THEN backdrop_blurhash END,
+                content_rating = CASE WHEN tmdb_id IS ? THEN content_rating END,
                 tmdb_id = ?, title = ?, overview = ?, poster_url = ?, backdrop_url = ?,
                 confidence_score = ?, verification_status = ?, first_air_date = ?,
                 last_air_date = ?, status = ?, total_seasons = ?, total_episodes = ?,
@@ -211,6 +255,9 @@ impl SeriesRepository for SqliteSeriesRepository {
                 error_notes = ?, last_verified = ?, updated_at = ?
             WHERE id = ?"
         )
+        .bind(&series.poster_url)
+        .bind(&series.backdrop_url)
+        .bind(series.tmdb_id)
         .bind(series.tmdb_id)
         .bind(&series.title)
         .bind(&series.overview)
@@ -390,4 +437,135 @@ impl SeriesRepository for SqliteSeriesRepository {
 
         Ok(result)
     }
+
+    async fn find_missing_blurhashes(&self, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM series
+             WHERE (poster_url IS NOT NULL AND poster_blurhash IS NULL)
+                OR (backdrop_url IS NOT NULL AND backdrop_blurhash IS NULL)
+             ORDER BY created_at DESC LIMIT ?"
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut series_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            series_list.push(Self::map_row_to_series(row)?);
+        }
+
+        Ok(series_list)
+    }
+
+    async fn update_blurhashes(
+        &self,
+        id: i64,
+        poster_blurhash: Option<&str>,
+        backdrop_blurhash: Option<&str>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE series SET
+                poster_blurhash = COALESCE(?, poster_blurhash),
+                backdrop_blurhash = COALESCE(?, backdrop_blurhash)
+            WHERE id = ?"
+        )
+        .bind(poster_blurhash)
+        .bind(backdrop_blurhash)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_missing_content_ratings(&self, limit: usize) -> Result<Vec<Series>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM series
+             WHERE content_rating IS NULL AND tmdb_id IS NOT NULL
+             ORDER BY created_at DESC LIMIT ?"
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut series_list = Vec::with_capacity(rows.len());
+        for row in rows {
+            series_list.push(Self::map_row_to_series(row)?);
+        }
+
+        Ok(series_list)
+    }
+
+    async fn update_content_rating(&self, id: i64, content_rating: &str) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE series SET content_rating = ? WHERE id = ?")
+            .bind(content_rating)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_blurhash_cleared_when_artwork_changes() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteSeriesRepository::new(pool);
+        let series = Series::new("Severance".to_string())
+            .unwrap()
+            .with_poster_url(Some("https://image.tmdb.org/t/p/w500/a.jpg".to_string()))
+            .with_backdrop_url(Some("https://image.tmdb.org/t/p/w1280/b.jpg".to_string()));
+        let id = repo.save(&series).await.unwrap();
+        assert_eq!(repo.find_missing_blurhashes(10).await.unwrap().len(), 1);
+
+        repo.update_blurhashes(id, Some("LEHV6nWB2yk8"), Some("")).await.unwrap();
+        assert!(repo.find_missing_blurhashes(10).await.unwrap().is_empty());
+
+        // Unrelated updates keep the hashes, a new poster drops its hash only
+        let mut series = repo.find_by_id(id).await.unwrap().unwrap();
+        series.rating = Some(8.7);
+        repo.update(&series).await.unwrap();
+        assert_eq!(repo.find_by_id(id).await.unwrap().unwrap().poster_blurhash.as_deref(), Some("LEHV6nWB2yk8"));
+
+        series.poster_url = Some("https://image.tmdb.org/t/p/w500/c.jpg".to_string());
+        repo.update(&series).await.unwrap();
+        let series = repo.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(series.poster_blurhash, None);
+        assert_eq!(series.backdrop_blurhash.as_deref(), Some(""));
+        assert_eq!(repo.find_missing_blurhashes(10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_page_counts_duplicates_once() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteSeriesRepository::new(pool);
+        for (title, tmdb_id) in [("Severance", Some(95396)), ("Severance (2022)", Some(95396)), ("Andor", None), ("Dark", None)] {
+            repo.save(&Series::new(title.to_string()).unwrap().with_tmdb_id(tmdb_id)).await.unwrap();
+        }
+
+        let (page, total) = repo.find_page(SeriesOrder::Title, false, 0, 2).await.unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.iter().map(|s| s.title.as_str()).collect::<Vec<_>>(), vec!["Andor", "Dark"]);
+        let (page, _) = repo.find_page(SeriesOrder::Title, false, 2, 2).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(repo.find_all().await.unwrap().len(), 3);
+    }
 }
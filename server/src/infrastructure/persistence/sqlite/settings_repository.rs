@@ -0,0 +1,87 @@
+//! SQLite implementation of SettingsRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, Row};
+use std::collections::BTreeMap;
+use crate::domain::repositories::SettingsRepository;
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based settings repository implementation
+pub struct SqliteSettingsRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteSettingsRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SettingsRepository for SqliteSettingsRepository {
+    async fn find_all(&self) -> Result<BTreeMap<String, serde_json::Value>, RepositoryError> {
+        let rows = sqlx::query("SELECT key, value FROM settings")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let value: String = row.get("value");
+                Ok((row.get("key"), serde_json::from_str(&value)?))
+            })
+            .collect()
+    }
+
+    async fn save(&self, changes: &BTreeMap<String, Option<serde_json::Value>>) -> Result<(), RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        for (key, value) in changes {
+            let query = match value {
+                Some(value) => sqlx::query(
+                    "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)
+                     ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                )
+                .bind(key)
+                .bind(value.to_string())
+                .bind(&now),
+                None => sqlx::query("DELETE FROM settings WHERE key = ?").bind(key),
+            };
+            query.execute(&mut *tx).await.map_err(|e| RepositoryError::Database(e.to_string()))?;
+        }
+        tx.commit().await.map_err(|e| RepositoryError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_settings() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteSettingsRepository::new(pool);
+
+        let changes = BTreeMap::from([
+            ("max_streams".to_string(), Some(json!(4))),
+            ("tmdb_api_key".to_string(), Some(json!("key"))),
+        ]);
+        repo.save(&changes).await.unwrap();
+        let changes = BTreeMap::from([
+            ("max_streams".to_string(), Some(json!(2))),
+            ("tmdb_api_key".to_string(), None),
+        ]);
+        repo.save(&changes).await.unwrap();
+
+        assert_eq!(repo.find_all().await.unwrap(), BTreeMap::from([("max_streams".to_string(), json!(2))]));
+    }
+}
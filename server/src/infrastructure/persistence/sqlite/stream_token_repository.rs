@@ -0,0 +1,74 @@
+//! SQLite implementation of StreamTokenRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::StreamTokenRepository;
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based stream token repository implementation
+pub struct SqliteStreamTokenRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteStreamTokenRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StreamTokenRepository for SqliteStreamTokenRepository {
+    async fn record(&self, session: &str, device_id: i64, expires_at: i64) -> Result<(), RepositoryError> {
+        sqlx::query("INSERT OR IGNORE INTO stream_tokens (session, device_id, expires_at) VALUES (?, ?, ?)")
+            .bind(session)
+            .bind(device_id)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, session: &str, expires_at: i64) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO stream_tokens (session, expires_at, revoked) VALUES (?, ?, 1)
+             ON CONFLICT(session) DO UPDATE SET revoked = 1",
+        )
+        .bind(session)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn revoke_device(&self, device_id: i64, now: i64) -> Result<Vec<(String, i64)>, RepositoryError> {
+        let rows = sqlx::query(
+            "UPDATE stream_tokens SET revoked = 1 WHERE device_id = ? AND expires_at >= ? RETURNING session, expires_at",
+        )
+        .bind(device_id)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|row| (row.get("session"), row.get("expires_at"))).collect())
+    }
+
+    async fn find_revoked(&self, now: i64) -> Result<Vec<(String, i64)>, RepositoryError> {
+        sqlx::query("DELETE FROM stream_tokens WHERE expires_at < ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        let rows = sqlx::query("SELECT session, expires_at FROM stream_tokens WHERE revoked = 1")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|row| (row.get("session"), row.get("expires_at"))).collect())
+    }
+}
@@ -0,0 +1,110 @@
+//! SQLite implementation of SubtitleOffsetRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite, Row};
+use crate::domain::repositories::{SubtitleOffset, SubtitleOffsetRepository};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based subtitle offset repository implementation
+pub struct SqliteSubtitleOffsetRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteSubtitleOffsetRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SubtitleOffsetRepository for SqliteSubtitleOffsetRepository {
+    async fn find(&self, user_id: &str, media_id: i64) -> Result<Vec<SubtitleOffset>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT track_index, offset_ms FROM subtitle_offsets WHERE user_id = ? AND media_id = ? ORDER BY track_index",
+        )
+        .bind(user_id)
+        .bind(media_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SubtitleOffset {
+                track_index: row.get("track_index"),
+                offset_ms: row.get("offset_ms"),
+            })
+            .collect())
+    }
+
+    async fn save(&self, user_id: &str, media_id: i64, offset: &SubtitleOffset) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO subtitle_offsets (user_id, media_id, track_index, offset_ms, updated_at)
+            VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(user_id, media_id, track_index) DO UPDATE SET
+                offset_ms = excluded.offset_ms,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(media_id)
+        .bind(offset.track_index)
+        .bind(offset.offset_ms)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str, media_id: i64, track_index: i32) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            "DELETE FROM subtitle_offsets WHERE user_id = ? AND media_id = ? AND track_index = ?",
+        )
+        .bind(user_id)
+        .bind(media_id)
+        .bind(track_index)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_offsets_are_per_user_and_track() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+
+        let repo = SqliteSubtitleOffsetRepository::new(pool);
+        repo.save("anna", 1, &SubtitleOffset { track_index: 0, offset_ms: 500 }).await.unwrap();
+        repo.save("anna", 1, &SubtitleOffset { track_index: 0, offset_ms: -1200 }).await.unwrap();
+        repo.save("anna", 1, &SubtitleOffset { track_index: 2, offset_ms: 300 }).await.unwrap();
+        repo.save("ben", 1, &SubtitleOffset { track_index: 0, offset_ms: 800 }).await.unwrap();
+
+        assert_eq!(
+            repo.find("anna", 1).await.unwrap(),
+            vec![
+                SubtitleOffset { track_index: 0, offset_ms: -1200 },
+                SubtitleOffset { track_index: 2, offset_ms: 300 },
+            ]
+        );
+        assert!(repo.find("anna", 2).await.unwrap().is_empty());
+
+        assert!(repo.delete("anna", 1, 2).await.unwrap());
+        assert!(!repo.delete("anna", 1, 2).await.unwrap());
+        assert_eq!(repo.find("ben", 1).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+//! SQLite implementation of SubtitlePreferenceRepository
+
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+use crate::domain::repositories::SubtitlePreferenceRepository;
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based subtitle preference repository implementation
+///
+/// Languages are stored comma-separated.
+pub struct SqliteSubtitlePreferenceRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteSubtitlePreferenceRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SubtitlePreferenceRepository for SqliteSubtitlePreferenceRepository {
+    async fn find(&self, user_id: &str) -> Result<Vec<String>, RepositoryError> {
+        let languages: Option<String> = sqlx::query_scalar("SELECT languages FROM user_subtitle_preferences WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(languages
+            .map(|l| l.split(',').filter(|l| !l.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default())
+    }
+
+    async fn save(&self, user_id: &str, languages: &[String]) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_subtitle_preferences (user_id, languages, updated_at)
+            VALUES (?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT(user_id) DO UPDATE SET
+                languages = excluded.languages,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(languages.join(","))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &str) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM user_subtitle_preferences WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,172 @@
+//! SQLite implementation of TmdbResponseRepository
+//!
+//! Besides the repository, exports the recorded responses to a standalone
+//! SQLite file and imports them from one, for moving them to an air-gapped
+//! server.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, Row};
+use std::path::Path;
+use crate::domain::repositories::{RecordedResponse, TmdbResponseRepository};
+use crate::shared::error::RepositoryError;
+
+/// SQLite-based TMDB response repository implementation
+pub struct SqliteTmdbResponseRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteTmdbResponseRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Copies the recorded responses into the SQLite file at `path`
+    /// (created if missing), returning how many were copied
+    pub async fn export_to(&self, path: &Path) -> Result<u64, RepositoryError> {
+        self.run_attached(path, &[
+            "CREATE TABLE IF NOT EXISTS export.tmdb_responses (
+                request TEXT PRIMARY KEY,
+                status INTEGER NOT NULL,
+                body BLOB NOT NULL,
+                fetched_at TEXT NOT NULL
+            )",
+            "INSERT OR REPLACE INTO export.tmdb_responses (request, status, body, fetched_at)
+             SELECT request, status, body, fetched_at FROM main.tmdb_responses",
+        ])
+        .await
+    }
+
+    /// Copies the responses of an exported file at `path`, keeping recorded
+    /// ones that are newer, and returns how many were copied
+    pub async fn import_from(&self, path: &Path) -> Result<u64, RepositoryError> {
+        if !path.is_file() {
+            return Err(RepositoryError::NotFound(format!("{} does not exist", path.display())));
+        }
+        self.run_attached(path, &[
+            "INSERT INTO main.tmdb_responses (request, status, body, fetched_at)
+             SELECT request, status, body, fetched_at FROM export.tmdb_responses WHERE true
+             ON CONFLICT(request) DO UPDATE SET
+                status = excluded.status, body = excluded.body, fetched_at = excluded.fetched_at
+             WHERE excluded.fetched_at > tmdb_responses.fetched_at",
+        ])
+        .await
+    }
+
+    /// Runs `statements` with the file at `path` attached as `export`,
+    /// returning the rows changed by the last one
+    async fn run_attached(&self, path: &Path, statements: &[&str]) -> Result<u64, RepositoryError> {
+        let database = |e: sqlx::Error| RepositoryError::Database(e.to_string());
+        let mut conn = self.pool.acquire().await.map_err(database)?;
+        sqlx::query("ATTACH DATABASE ? AS export")
+            .bind(path.to_string_lossy().as_ref())
+            .execute(&mut *conn)
+            .await
+            .map_err(database)?;
+
+        let mut changed = Ok(0);
+        for statement in statements {
+            changed = sqlx::query(statement).execute(&mut *conn).await.map(|done| done.rows_affected());
+            if changed.is_err() {
+                break;
+            }
+        }
+
+        // Detached even after a failure, the connection goes back to the pool
+        sqlx::query("DETACH DATABASE export").execute(&mut *conn).await.map_err(database)?;
+        changed.map_err(database)
+    }
+}
+
+#[async_trait]
+impl TmdbResponseRepository for SqliteTmdbResponseRepository {
+    async fn find(&self, request: &str) -> Result<Option<RecordedResponse>, RepositoryError> {
+        let row = sqlx::query("SELECT status, body FROM tmdb_responses WHERE request = ?")
+            .bind(request)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.map(|row| RecordedResponse {
+            status: row.get::<i64, _>("status") as u16,
+            body: row.get("body"),
+        }))
+    }
+
+    async fn save(&self, request: &str, response: &RecordedResponse) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO tmdb_responses (request, status, body, fetched_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(request) DO UPDATE SET
+                status = excluded.status, body = excluded.body, fetched_at = excluded.fetched_at",
+        )
+        .bind(request)
+        .bind(i64::from(response.status))
+        .bind(&response.body)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<u64, RepositoryError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tmdb_responses")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        Ok(count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    /// File-backed, files attached to in-memory databases stay in memory
+    async fn repository(path: &Path) -> SqliteTmdbResponseRepository {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(SqliteConnectOptions::new().filename(path).create_if_missing(true))
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        SqliteTmdbResponseRepository::new(pool)
+    }
+
+    fn response(status: u16, body: &str) -> RecordedResponse {
+        RecordedResponse { status, body: body.as_bytes().to_vec() }
+    }
+
+    #[tokio::test]
+    async fn test_export_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let online = repository(&dir.path().join("online.db")).await;
+        online.save("/movie/603#", &response(200, "{\"id\":603}")).await.unwrap();
+        online.save("/movie/1#", &response(404, "")).await.unwrap();
+        online.save("/movie/603#", &response(200, "{\"id\":603,\"title\":\"The Matrix\"}")).await.unwrap();
+        assert_eq!(online.count().await.unwrap(), 2);
+
+        let file = dir.path().join("metadata.db");
+        assert_eq!(online.export_to(&file).await.unwrap(), 2);
+
+        let offline = repository(&dir.path().join("offline.db")).await;
+        offline.save("/tv/1399#", &response(200, "{\"id\":1399}")).await.unwrap();
+        assert_eq!(offline.import_from(&file).await.unwrap(), 2);
+        assert_eq!(offline.count().await.unwrap(), 3);
+        assert_eq!(
+            offline.find("/movie/603#").await.unwrap(),
+            Some(response(200, "{\"id\":603,\"title\":\"The Matrix\"}"))
+        );
+        assert_eq!(offline.find("/movie/1#").await.unwrap().map(|r| r.status), Some(404));
+        assert_eq!(offline.find("/movie/2#").await.unwrap(), None);
+
+        // Responses recorded since the export are kept
+        offline.save("/movie/603#", &response(200, "{\"id\":603,\"title\":\"Mátrix\"}")).await.unwrap();
+        assert_eq!(offline.import_from(&file).await.unwrap(), 0);
+        assert_eq!(offline.find("/movie/603#").await.unwrap().unwrap().body, b"{\"id\":603,\"title\":\"M\xc3\xa1trix\"}");
+
+        assert!(offline.import_from(&dir.path().join("missing.db")).await.is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! SQLite implementation of WatchHistoryRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{
+    MonthlyWatchTime, NewPlayback, PlaybackEntry, WatchHistoryRepository, WatchTime, WatchTotals,
+};
+use crate::shared::error::RepositoryError;
+
+/// Matches every user when the bound user is NULL (bind it twice)
+const USER_FILTER: &str = "(? IS NULL OR h.user_id = ?)";
+
+/// SQLite-based watch history repository implementation
+pub struct SqliteWatchHistoryRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteWatchHistoryRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_entry(row: &SqliteRow) -> Result<PlaybackEntry, RepositoryError> {
+    let started_at: String = row.get("started_at");
+    Ok(PlaybackEntry {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        media_id: row.get("media_id"),
+        title: row.get("title"),
+        client: row.get("client"),
+        started_at: DateTime::parse_from_rfc3339(&started_at)
+            .map_err(|e| RepositoryError::Database(e.to_string()))?
+            .with_timezone(&Utc),
+        duration_seconds: row.get("duration_seconds"),
+        bytes_sent: row.get("bytes_sent"),
+        completed: row.get("completed"),
+    })
+}
+
+fn row_to_watch_time(row: &SqliteRow) -> WatchTime {
+    WatchTime {
+        key: row.get("key"),
+        title: row.get("title"),
+        plays: row.get("plays"),
+        seconds: row.get("seconds"),
+    }
+}
+
+#[async_trait]
+impl WatchHistoryRepository for SqliteWatchHistoryRepository {
+    async fn record(&self, playback: &NewPlayback) -> Result<i64, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO watch_history (user_id, media_id, client, started_at, duration_seconds, bytes_sent, completed)
+            VALUES (?, ?, ?, ?, ?, ?, 0)
+            "#,
+        )
+        .bind(&playback.user_id)
+        .bind(playback.media_id)
+        .bind(&playback.client)
+        .bind(playback.started_at.to_rfc3339())
+        .bind(playback.duration_seconds)
+        .bind(playback.bytes_sent)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn mark_completed(&self, media_id: i64, since: DateTime<Utc>) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE watch_history SET completed = 1
+            WHERE id = (
+                SELECT id FROM watch_history
+                WHERE media_id = ? AND started_at >= ?
+                ORDER BY started_at DESC, id DESC LIMIT 1
+            )
+            "#,
+        )
+        .bind(media_id)
+        .bind(since.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn recent(&self, user: Option<&str>, limit: u32) -> Result<Vec<PlaybackEntry>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT h.id, h.user_id, h.media_id, m.title, h.client, h.started_at,
+                   h.duration_seconds, h.bytes_sent, h.completed
+            FROM watch_history h LEFT JOIN media m ON m.id = h.media_id
+            WHERE {}
+            ORDER BY h.started_at DESC, h.id DESC LIMIT ?
+            "#,
+            USER_FILTER
+        ))
+        .bind(user)
+        .bind(user)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        rows.iter().map(row_to_entry).collect()
+    }
+
+    async fn totals(&self, user: Option<&str>) -> Result<WatchTotals, RepositoryError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT COUNT(*) AS plays, COALESCE(SUM(h.duration_seconds), 0) AS seconds,
+                   COUNT(DISTINCT h.media_id) AS media_count, COALESCE(SUM(h.completed), 0) AS completed
+            FROM watch_history h WHERE {}
+            "#,
+            USER_FILTER
+        ))
+        .bind(user)
+        .bind(user)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(WatchTotals {
+            plays: row.get("plays"),
+            seconds: row.get("seconds"),
+            media_count: row.get("media_count"),
+            completed: row.get("completed"),
+        })
+    }
+
+    async fn top_series(&self, user: Option<&str>, limit: u32) -> Result<Vec<WatchTime>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT CAST(m.series_id AS TEXT) AS key, s.title AS title,
+                   COUNT(*) AS plays, SUM(h.duration_seconds) AS seconds
+            FROM watch_history h
+            JOIN media m ON m.id = h.media_id
+            LEFT JOIN series s ON s.id = m.series_id
+            WHERE m.series_id IS NOT NULL AND {}
+            GROUP BY m.series_id
+            ORDER BY seconds DESC, plays DESC LIMIT ?
+            "#,
+            USER_FILTER
+        ))
+        .bind(user)
+        .bind(user)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_watch_time).collect())
+    }
+
+    async fn by_user(&self) -> Result<Vec<WatchTime>, RepositoryError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT user_id AS key, NULL AS title, COUNT(*) AS plays, SUM(duration_seconds) AS seconds
+            FROM watch_history
+            GROUP BY user_id
+            ORDER BY seconds DESC, plays DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_watch_time).collect())
+    }
+
+    async fn by_client(&self, user: Option<&str>, limit: u32) -> Result<Vec<WatchTime>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT COALESCE(h.client, 'unknown') AS key, NULL AS title,
+                   COUNT(*) AS plays, SUM(h.duration_seconds) AS seconds
+            FROM watch_history h WHERE {}
+            GROUP BY key
+            ORDER BY seconds DESC, plays DESC LIMIT ?
+            "#,
+            USER_FILTER
+        ))
+        .bind(user)
+        .bind(user)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_watch_time).collect())
+    }
+
+    async fn per_month(&self, user: Option<&str>, since: DateTime<Utc>) -> Result<Vec<MonthlyWatchTime>, RepositoryError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT substr(h.started_at, 1, 7) AS month, SUM(h.duration_seconds) AS seconds
+            FROM watch_history h
+            WHERE h.started_at >= ? AND {}
+            GROUP BY month
+            ORDER BY month
+            "#,
+            USER_FILTER
+        ))
+        .bind(since.to_rfc3339())
+        .bind(user)
+        .bind(user)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| MonthlyWatchTime {
+                month: row.get("month"),
+                seconds: row.get("seconds"),
+            })
+            .collect())
+    }
+
+    async fn plays_per_media(&self) -> Result<Vec<(i64, i64)>, RepositoryError> {
+        sqlx::query_as("SELECT media_id, COUNT(*) FROM watch_history GROUP BY media_id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use chrono::TimeZone;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn playback(user: &str, media_id: i64, started_at: DateTime<Utc>, seconds: i64) -> NewPlayback {
+        NewPlayback {
+            user_id: user.to_string(),
+            media_id,
+            client: Some("tv".to_string()),
+            started_at,
+            duration_seconds: seconds,
+            bytes_sent: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_history_aggregates() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        sqlx::query("INSERT INTO series (id, title) VALUES (1, 'Dark')").execute(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO media (id, file_path, media_type, title, series_id, season, episode) VALUES
+             (10, '/tv/dark/s01e01.mkv', 'episode', 'Secrets', 1, 1, 1),
+             (20, '/movies/alien.mkv', 'movie', 'Alien', NULL, NULL, NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteWatchHistoryRepository::new(pool);
+        let september = Utc.with_ymd_and_hms(2026, 9, 20, 20, 0, 0).unwrap();
+        let october = Utc.with_ymd_and_hms(2026, 10, 2, 21, 0, 0).unwrap();
+        repo.record(&playback("anna", 10, september, 3000)).await.unwrap();
+        repo.record(&playback("anna", 20, october, 6000)).await.unwrap();
+        repo.record(&playback("ben", 10, october, 600)).await.unwrap();
+
+        assert!(repo.mark_completed(10, september).await.unwrap());
+        let recent = repo.recent(Some("anna"), 10).await.unwrap();
+        assert_eq!(recent.iter().map(|e| e.media_id).collect::<Vec<_>>(), vec![20, 10]);
+        assert_eq!(recent[0].title.as_deref(), Some("Alien"));
+        // The latest playback of media 10 is Ben's
+        assert!(!recent[1].completed);
+
+        let totals = repo.totals(None).await.unwrap();
+        assert_eq!((totals.plays, totals.seconds, totals.media_count, totals.completed), (3, 9600, 2, 1));
+        assert_eq!(repo.totals(Some("anna")).await.unwrap().seconds, 9000);
+
+        let series = repo.top_series(None, 5).await.unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!((series[0].key.as_str(), series[0].title.as_deref(), series[0].seconds), ("1", Some("Dark"), 3600));
+
+        let users = repo.by_user().await.unwrap();
+        assert_eq!(users.iter().map(|u| u.key.as_str()).collect::<Vec<_>>(), vec!["anna", "ben"]);
+
+        let months = repo.per_month(Some("anna"), september).await.unwrap();
+        assert_eq!(
+            months,
+            vec![
+                MonthlyWatchTime { month: "2026-09".to_string(), seconds: 3000 },
+                MonthlyWatchTime { month: "2026-10".to_string(), seconds: 6000 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! SQLite implementation of WebhookRepository
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use crate::domain::repositories::{Webhook, WebhookDelivery, WebhookRepository, WebhookSettings};
+use crate::shared::error::RepositoryError;
+
+const WEBHOOK_SELECT: &str =
+    "SELECT id, name, url, secret, event_types, enabled, created_at, updated_at FROM webhooks";
+
+/// SQLite-based webhook repository implementation
+pub struct SqliteWebhookRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteWebhookRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_webhook(row: &SqliteRow) -> Webhook {
+    let event_types: String = row.get("event_types");
+    Webhook {
+        id: row.get("id"),
+        name: row.get("name"),
+        url: row.get("url"),
+        secret: row.get("secret"),
+        event_types: event_types.split(',').filter(|t| !t.is_empty()).map(String::from).collect(),
+        enabled: row.get("enabled"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn row_to_delivery(row: &SqliteRow) -> WebhookDelivery {
+    WebhookDelivery {
+        id: row.get("id"),
+        webhook_id: row.get("webhook_id"),
+        event_type: row.get("event_type"),
+        attempts: row.get::<i64, _>("attempts") as u32,
+        status_code: row.get::<Option<i64>, _>("status_code").map(|code| code as u16),
+        error: row.get("error"),
+        success: row.get("success"),
+        delivered_at: row.get("delivered_at"),
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for SqliteWebhookRepository {
+    async fn find_all(&self) -> Result<Vec<Webhook>, RepositoryError> {
+        let rows = sqlx::query(&format!("{} ORDER BY id", WEBHOOK_SELECT))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_webhook).collect())
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Webhook>, RepositoryError> {
+        let row = sqlx::query(&format!("{} WHERE id = ?", WEBHOOK_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(row_to_webhook))
+    }
+
+    async fn create(&self, settings: &WebhookSettings) -> Result<Webhook, RepositoryError> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "INSERT INTO webhooks (name, url, secret, event_types, enabled, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&settings.name)
+        .bind(&settings.url)
+        .bind(&settings.secret)
+        .bind(settings.event_types.join(","))
+        .bind(settings.enabled)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        self.find(result.last_insert_rowid())
+            .await?
+            .ok_or_else(|| RepositoryError::Database("Created webhook not found".to_string()))
+    }
+
+    async fn update(&self, id: i64, settings: &WebhookSettings) -> Result<Option<Webhook>, RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE webhooks SET name = ?, url = ?, secret = ?, event_types = ?, enabled = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(&settings.name)
+        .bind(&settings.url)
+        .bind(&settings.secret)
+        .bind(settings.event_types.join(","))
+        .bind(settings.enabled)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.find(id).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_delivery(&self, delivery: &WebhookDelivery, keep: u32) -> Result<(), RepositoryError> {
+        let db_error = |e: sqlx::Error| RepositoryError::Database(e.to_string());
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            "INSERT INTO webhook_deliveries
+                (webhook_id, event_type, attempts, status_code, error, success, delivered_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(delivery.webhook_id)
+        .bind(&delivery.event_type)
+        .bind(delivery.attempts as i64)
+        .bind(delivery.status_code.map(i64::from))
+        .bind(&delivery.error)
+        .bind(delivery.success)
+        .bind(&delivery.delivered_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        sqlx::query(
+            "DELETE FROM webhook_deliveries WHERE webhook_id = ? AND id NOT IN
+                (SELECT id FROM webhook_deliveries WHERE webhook_id = ? ORDER BY id DESC LIMIT ?)",
+        )
+        .bind(delivery.webhook_id)
+        .bind(delivery.webhook_id)
+        .bind(keep as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)
+    }
+
+    async fn find_deliveries(&self, webhook_id: i64, limit: u32) -> Result<Vec<WebhookDelivery>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, webhook_id, event_type, attempts, status_code, error, success, delivered_at
+             FROM webhook_deliveries WHERE webhook_id = ? ORDER BY id DESC LIMIT ?",
+        )
+        .bind(webhook_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(row_to_delivery).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn delivery(webhook_id: i64, event_type: &str) -> WebhookDelivery {
+        WebhookDelivery {
+            id: 0,
+            webhook_id,
+            event_type: event_type.to_string(),
+            attempts: 2,
+            status_code: Some(204),
+            error: None,
+            success: true,
+            delivered_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_and_deliveries() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let repo = SqliteWebhookRepository::new(pool);
+
+        let mut settings = WebhookSettings {
+            name: "Home Assistant".to_string(),
+            url: "http://ha.local:8123/api/webhook/homeflix".to_string(),
+            secret: Some("s3cret".to_string()),
+            event_types: vec!["scan_completed".to_string(), "media_identified".to_string()],
+            enabled: true,
+        };
+        let webhook = repo.create(&settings).await.unwrap();
+        assert_eq!(webhook.event_types, settings.event_types);
+        assert!(webhook.accepts("scan_completed") && !webhook.accepts("stream_started"));
+
+        settings.event_types.clear();
+        settings.enabled = false;
+        let updated = repo.update(webhook.id, &settings).await.unwrap().unwrap();
+        assert!(updated.event_types.is_empty() && !updated.accepts("scan_completed"));
+        assert!(repo.update(99, &settings).await.unwrap().is_none());
+
+        // Only the latest deliveries are kept
+        for event_type in ["a", "b", "c"] {
+            repo.record_delivery(&delivery(webhook.id, event_type), 2).await.unwrap();
+        }
+        let deliveries = repo.find_deliveries(webhook.id, 10).await.unwrap();
+        let types: Vec<&str> = deliveries.iter().map(|d| d.event_type.as_str()).collect();
+        assert_eq!(types, vec!["c", "b"]);
+        assert_eq!((deliveries[0].attempts, deliveries[0].status_code), (2, Some(204)));
+
+        assert!(repo.delete(webhook.id).await.unwrap());
+        assert!(repo.find_all().await.unwrap().is_empty());
+        assert!(repo.find_deliveries(webhook.id, 10).await.unwrap().is_empty());
+    }
+}
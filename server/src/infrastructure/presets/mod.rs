@@ -1,7 +1,10 @@
 //! Preset loading infrastructure
 //!
-//! Handles loading collection presets from YAML files in the presets directory.
+//! Handles loading collection presets from YAML files in the presets directory
+//! and reading shared preset bundles.
 
 mod preset_loader;
+mod preset_bundle;
 
 pub use preset_loader::PresetLoader;
+pub use preset_bundle::{PresetBundle, MAX_BUNDLE_BYTES};
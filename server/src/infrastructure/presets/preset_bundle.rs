@@ -0,0 +1,95 @@
+//! Preset Bundles
+//!
+//! Reads preset definitions shared outside the presets directory: a single
+//! preset, a list of presets or `presets:` with a list, in YAML or JSON,
+//! optionally as several YAML documents.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::domain::presets::PresetCollection;
+use crate::shared::error::PresetLoadError;
+
+/// Largest bundle accepted, in bytes
+pub const MAX_BUNDLE_BYTES: usize = 2 * 1024 * 1024;
+
+/// One document of a bundle
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BundleDocument {
+    Wrapped { presets: Vec<PresetCollection> },
+    Many(Vec<PresetCollection>),
+    One(Box<PresetCollection>),
+}
+
+/// Parses and downloads preset bundles
+pub struct PresetBundle;
+
+impl PresetBundle {
+    /// Parses the presets of a bundle
+    ///
+    /// JSON is read as YAML, which it is a subset of. Presets are not
+    /// validated here.
+    pub fn parse(content: &str) -> Result<Vec<PresetCollection>, PresetLoadError> {
+        let mut presets = Vec::new();
+        for (index, document) in serde_yaml::Deserializer::from_str(content).enumerate() {
+            let document = BundleDocument::deserialize(document).map_err(|e| {
+                PresetLoadError::YamlParse(format!("bundle document {}", index + 1), e.to_string())
+            })?;
+            match document {
+                BundleDocument::Wrapped { presets: list } | BundleDocument::Many(list) => presets.extend(list),
+                BundleDocument::One(preset) => presets.push(*preset),
+            }
+        }
+        if presets.is_empty() {
+            return Err(PresetLoadError::Validation("bundle".to_string(), "No presets found".to_string()));
+        }
+        Ok(presets)
+    }
+
+    /// Downloads a bundle over HTTP(S)
+    pub async fn fetch(url: &str) -> Result<String, PresetLoadError> {
+        let parsed = reqwest::Url::parse(url).map_err(|e| PresetLoadError::Fetch(format!("Invalid URL: {}", e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(PresetLoadError::Fetch("Only http and https URLs can be imported".to_string()));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| PresetLoadError::Fetch(e.to_string()))?;
+        let mut response = client
+            .get(parsed)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| PresetLoadError::Fetch(e.to_string()))?;
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| PresetLoadError::Fetch(e.to_string()))? {
+            if body.len() + chunk.len() > MAX_BUNDLE_BYTES {
+                return Err(PresetLoadError::Fetch("Bundle is larger than 2 MB".to_string()));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        String::from_utf8(body).map_err(|_| PresetLoadError::Fetch("Bundle is not UTF-8 text".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bundle_formats() {
+        let item = r#"{"tmdb_id": 348, "media_type": "movie", "title": "Alien", "timeline_order": 1}"#;
+        let json = format!(r#"{{"presets": [{{"name": "Alien", "description": "", "items": [{}]}}]}}"#, item);
+        assert_eq!(PresetBundle::parse(&json).unwrap()[0].name, "Alien");
+
+        let yaml = "name: A\ndescription: x\nitems: []\n---\n- name: B\n  description: y\n  items: []\n- name: C\n  description: z\n  items: []\n";
+        let names: Vec<_> = PresetBundle::parse(yaml).unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+
+        assert!(PresetBundle::parse("title: not a preset").is_err());
+    }
+}
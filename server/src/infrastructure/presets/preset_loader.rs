@@ -25,6 +25,14 @@ impl PresetLoader {
     /// # Returns
     /// Vector of loaded presets. Empty vector if directory doesn't exist or contains no valid presets.
     pub fn load_from_directory(presets_dir: &Path) -> Result<Vec<PresetCollection>, PresetLoadError> {
+        Ok(Self::load_entries(presets_dir)?.into_iter().map(|(_, preset)| preset).collect())
+    }
+
+    /// Load all presets from a directory with their IDs
+    ///
+    /// The ID of a preset is its file name without the extension
+    /// (`star-trek-timeline` for `star-trek-timeline.yaml`).
+    pub fn load_entries(presets_dir: &Path) -> Result<Vec<(String, PresetCollection)>, PresetLoadError> {
         if !presets_dir.exists() {
             warn!("Presets directory does not exist: {:?}", presets_dir);
             return Ok(Vec::new());
@@ -71,13 +79,17 @@ impl PresetLoader {
                 continue;
             }
 
+            let Some(id) = path.file_stem().and_then(|stem| stem.to_str()).map(String::from) else {
+                continue;
+            };
+
             match Self::load_preset_file(&path) {
-                Ok(mut preset) => {
+                Ok(preset) => {
                     // Validate the preset
                     match preset.validate() {
                         Ok(()) => {
                             info!("Loaded preset: {}", preset.name);
-                            presets.push(preset);
+                            presets.push((id, preset));
                         }
                         Err(e) => {
                             let error_msg = format!(
@@ -110,9 +122,58 @@ impl PresetLoader {
             info!("Successfully loaded {} presets from {:?}", presets.len(), presets_dir);
         }
 
+        presets.sort_by(|a, b| a.0.cmp(&b.0));
         Ok(presets)
     }
 
+    /// Write a preset to `<id>.yaml`, replacing the file of the same ID
+    ///
+    /// The file is written next to its target and renamed over it, so a
+    /// scan reloading presets meanwhile never reads half of it.
+    pub fn save_preset(presets_dir: &Path, id: &str, preset: &PresetCollection) -> Result<PathBuf, PresetLoadError> {
+        let yaml = serde_yaml::to_string(preset)
+            .map_err(|e| PresetLoadError::Validation(id.to_string(), e.to_string()))?;
+        fs::create_dir_all(presets_dir)?;
+        Self::delete_preset(presets_dir, id)?;
+
+        let path = presets_dir.join(format!("{}.yaml", id));
+        let partial = presets_dir.join(format!(".{}.yaml.partial", id));
+        fs::write(&partial, yaml)?;
+        fs::rename(&partial, &path)?;
+        debug!("Saved preset '{}' to {:?}", preset.name, path);
+        Ok(path)
+    }
+
+    /// Remove the file of a preset
+    ///
+    /// # Returns
+    /// Whether a file was removed
+    pub fn delete_preset(presets_dir: &Path, id: &str) -> Result<bool, PresetLoadError> {
+        let mut removed = false;
+        for extension in ["yaml", "yml"] {
+            match fs::remove_file(presets_dir.join(format!("{}.{}", id, extension))) {
+                Ok(()) => removed = true,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(PresetLoadError::Io(e)),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// File name safe ID for a preset name ("Star Trek Timeline" -> "star-trek-timeline")
+    pub fn preset_id(name: &str) -> String {
+        let mut id = String::new();
+        for c in name.chars() {
+            if c.is_ascii_alphanumeric() {
+                id.push(c.to_ascii_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+        let id = id.trim_end_matches('-');
+        if id.is_empty() { "preset".to_string() } else { id.to_string() }
+    }
+
     /// Load a single preset from a YAML file
     fn load_preset_file(path: &Path) -> Result<PresetCollection, PresetLoadError> {
         let content = fs::read_to_string(path)
@@ -226,6 +287,25 @@ mod tests {
         assert_eq!(result.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_save_and_delete_preset() {
+        let temp_dir = TempDir::new().unwrap();
+        let preset: PresetCollection = serde_yaml::from_str(
+            "name: Alien Timeline\ndescription: Alien films\ntmdb_collection_id: null\nitems:\n  - tmdb_id: 348\n    media_type: movie\n    title: Alien\n    timeline_order: 1\n    timeline_year: 2122\n    timeline_notes: null\n    season_range: null\n",
+        ).unwrap();
+        let id = PresetLoader::preset_id(&preset.name);
+        assert_eq!(id, "alien-timeline");
+
+        PresetLoader::save_preset(temp_dir.path(), &id, &preset).unwrap();
+        let entries = PresetLoader::load_entries(temp_dir.path()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, "alien-timeline");
+        assert_eq!(entries[0].1.items[0].tmdb_id, 348);
+
+        assert!(PresetLoader::delete_preset(temp_dir.path(), &id).unwrap());
+        assert!(PresetLoader::load_entries(temp_dir.path()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_load_from_empty_directory() {
         let temp_dir = TempDir::new().unwrap();
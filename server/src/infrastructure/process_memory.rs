@@ -0,0 +1,82 @@
+//! Process Memory
+//!
+//! Reads the server's memory footprint from procfs and the container's
+//! memory limit from cgroups (v2, then v1). Unavailable values are None,
+//! e.g. on non-Linux hosts.
+
+use serde::Serialize;
+
+/// cgroup v1 reports "no limit" as a huge page-aligned number
+const UNLIMITED_THRESHOLD: u64 = 1 << 60;
+
+/// Current memory usage of the process
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessMemory {
+    /// Resident set size
+    pub rss_bytes: Option<u64>,
+    /// Highest resident set size since start
+    pub peak_rss_bytes: Option<u64>,
+    /// Virtual memory size
+    pub virtual_bytes: Option<u64>,
+    /// Memory limit of the container (None = unlimited or unknown)
+    pub limit_bytes: Option<u64>,
+    /// RSS as a percentage of the limit
+    pub limit_used_percent: Option<f64>,
+}
+
+impl ProcessMemory {
+    /// Reads the current usage
+    pub fn current() -> Self {
+        let mut memory = std::fs::read_to_string("/proc/self/status")
+            .map(|status| parse_status(&status))
+            .unwrap_or_default();
+        memory.limit_bytes = cgroup_limit();
+        memory.limit_used_percent = memory.rss_bytes
+            .zip(memory.limit_bytes)
+            .map(|(rss, limit)| (rss as f64 / limit as f64 * 1000.0).round() / 10.0);
+        memory
+    }
+}
+
+/// Parses `VmRSS`, `VmHWM` and `VmSize` (reported in kB) from /proc/self/status
+fn parse_status(status: &str) -> ProcessMemory {
+    let mut memory = ProcessMemory::default();
+    for line in status.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let bytes = value.trim()
+            .strip_suffix("kB")
+            .and_then(|kb| kb.trim().parse::<u64>().ok())
+            .map(|kb| kb * 1024);
+        match name {
+            "VmRSS" => memory.rss_bytes = bytes,
+            "VmHWM" => memory.peak_rss_bytes = bytes,
+            "VmSize" => memory.virtual_bytes = bytes,
+            _ => {}
+        }
+    }
+    memory
+}
+
+fn cgroup_limit() -> Option<u64> {
+    ["/sys/fs/cgroup/memory.max", "/sys/fs/cgroup/memory/memory.limit_in_bytes"]
+        .iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())
+        .and_then(|limit| limit.trim().parse::<u64>().ok())
+        .filter(|limit| *limit < UNLIMITED_THRESHOLD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let status = "Name:\thomeflixd\nVmPeak:\t  912340 kB\nVmSize:\t  900000 kB\nVmHWM:\t  204800 kB\nVmRSS:\t  102400 kB\nThreads:\t12\n";
+        let memory = parse_status(status);
+        assert_eq!(memory.rss_bytes, Some(100 * 1024 * 1024));
+        assert_eq!(memory.peak_rss_bytes, Some(200 * 1024 * 1024));
+        assert_eq!(memory.virtual_bytes, Some(900000 * 1024));
+    }
+}
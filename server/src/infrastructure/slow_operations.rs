@@ -0,0 +1,247 @@
+//! Slow Operation Tracking
+//!
+//! Records HTTP requests and database queries that exceed their thresholds
+//! and summarizes the most recent ones per endpoint or statement, to show
+//! where optimization pays off. Slow queries are picked up from the warnings
+//! sqlx itself emits (see [`SlowQueryLayer`]); requests are reported by the
+//! HTTP logging middleware.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Target sqlx logs statements under
+pub const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// Slow operations kept per kind for the summary
+const HISTORY_SIZE: usize = 1000;
+/// Statements are shortened to this many characters
+const MAX_STATEMENT_LEN: usize = 300;
+
+/// One slow operation
+#[derive(Debug, Clone)]
+struct SlowEvent {
+    key: String,
+    duration: Duration,
+    at: DateTime<Utc>,
+}
+
+/// Summary of the slow operations of one endpoint or statement
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowEntry {
+    /// "GET /v2/media/:id" or the SQL statement
+    pub key: String,
+    pub count: usize,
+    pub max_ms: u64,
+    pub avg_ms: u64,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Slow operations overview
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowOperationsReport {
+    pub request_threshold_ms: u64,
+    pub query_threshold_ms: u64,
+    /// Slow requests since start
+    pub slow_requests_total: u64,
+    /// Slow queries since start
+    pub slow_queries_total: u64,
+    /// Slowest endpoints among the recent slow requests
+    pub endpoints: Vec<SlowEntry>,
+    /// Slowest statements among the recent slow queries
+    pub queries: Vec<SlowEntry>,
+}
+
+/// Slow request and query tracker
+pub struct SlowOperationTracker {
+    request_threshold: Duration,
+    query_threshold: Duration,
+    slow_requests: AtomicU64,
+    slow_queries: AtomicU64,
+    requests: Mutex<VecDeque<SlowEvent>>,
+    queries: Mutex<VecDeque<SlowEvent>>,
+}
+
+impl SlowOperationTracker {
+    pub fn new(request_threshold: Duration, query_threshold: Duration) -> Self {
+        Self {
+            request_threshold,
+            query_threshold,
+            slow_requests: AtomicU64::new(0),
+            slow_queries: AtomicU64::new(0),
+            requests: Mutex::new(VecDeque::new()),
+            queries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Threshold queries are reported slow at (configured on the pool)
+    pub fn query_threshold(&self) -> Duration {
+        self.query_threshold
+    }
+
+    /// Records a finished request; returns whether it was slow
+    pub fn record_request(&self, endpoint: &str, duration: Duration) -> bool {
+        if duration < self.request_threshold {
+            return false;
+        }
+        self.slow_requests.fetch_add(1, Ordering::Relaxed);
+        push(&self.requests, endpoint.to_string(), duration);
+        true
+    }
+
+    /// Records a query sqlx reported as slow
+    pub fn record_query(&self, statement: &str, duration: Duration) {
+        self.slow_queries.fetch_add(1, Ordering::Relaxed);
+        push(&self.queries, normalize_statement(statement), duration);
+    }
+
+    /// Summarizes recent slow operations, slowest first
+    pub fn report(&self, limit: usize) -> SlowOperationsReport {
+        SlowOperationsReport {
+            request_threshold_ms: self.request_threshold.as_millis() as u64,
+            query_threshold_ms: self.query_threshold.as_millis() as u64,
+            slow_requests_total: self.slow_requests.load(Ordering::Relaxed),
+            slow_queries_total: self.slow_queries.load(Ordering::Relaxed),
+            endpoints: summarize(&self.requests.lock().unwrap(), limit),
+            queries: summarize(&self.queries.lock().unwrap(), limit),
+        }
+    }
+}
+
+fn push(history: &Mutex<VecDeque<SlowEvent>>, key: String, duration: Duration) {
+    let mut history = history.lock().unwrap();
+    if history.len() == HISTORY_SIZE {
+        history.pop_front();
+    }
+    history.push_back(SlowEvent { key, duration, at: Utc::now() });
+}
+
+fn summarize(history: &VecDeque<SlowEvent>, limit: usize) -> Vec<SlowEntry> {
+    let mut by_key: HashMap<&str, Vec<&SlowEvent>> = HashMap::new();
+    for event in history {
+        by_key.entry(event.key.as_str()).or_default().push(event);
+    }
+
+    let mut entries: Vec<SlowEntry> = by_key.into_iter()
+        .map(|(key, events)| {
+            let total: Duration = events.iter().map(|e| e.duration).sum();
+            SlowEntry {
+                key: key.to_string(),
+                count: events.len(),
+                max_ms: events.iter().map(|e| e.duration).max().unwrap_or_default().as_millis() as u64,
+                avg_ms: (total / events.len() as u32).as_millis() as u64,
+                last_seen: events.iter().map(|e| e.at).max().unwrap_or_else(Utc::now),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.max_ms.cmp(&a.max_ms).then(b.count.cmp(&a.count)));
+    entries.truncate(limit);
+    entries
+}
+
+/// Collapses whitespace so one statement always maps to one key
+fn normalize_statement(statement: &str) -> String {
+    let mut normalized = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some((index, _)) = normalized.char_indices().nth(MAX_STATEMENT_LEN) {
+        normalized.truncate(index);
+        normalized.push('…');
+    }
+    normalized
+}
+
+/// Feeds sqlx's slow statement warnings into a [`SlowOperationTracker`]
+pub struct SlowQueryLayer {
+    tracker: Arc<SlowOperationTracker>,
+}
+
+impl SlowQueryLayer {
+    pub fn new(tracker: Arc<SlowOperationTracker>) -> Self {
+        Self { tracker }
+    }
+
+    /// Events the layer needs: sqlx's slow statement warnings, not its
+    /// per-statement debug lines
+    pub fn interested(metadata: &Metadata<'_>) -> bool {
+        metadata.target() == SQLX_QUERY_TARGET && *metadata.level() <= Level::WARN
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SlowQueryLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = QueryFields::default();
+        event.record(&mut fields);
+        let Some(elapsed) = fields.elapsed_secs else {
+            return;
+        };
+        // The full statement is only logged when it is longer than the summary
+        let statement = fields.statement.filter(|s| !s.trim().is_empty()).or(fields.summary).unwrap_or_default();
+        self.tracker.record_query(&statement, Duration::from_secs_f64(elapsed.max(0.0)));
+    }
+}
+
+#[derive(Default)]
+struct QueryFields {
+    summary: Option<String>,
+    statement: Option<String>,
+    elapsed_secs: Option<f64>,
+}
+
+impl Visit for QueryFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = Some(value.to_string()),
+            "db.statement" => self.statement = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_slow_operations() {
+        let tracker = Arc::new(SlowOperationTracker::new(Duration::from_millis(500), Duration::from_millis(100)));
+        assert!(!tracker.record_request("GET /v2/media", Duration::from_millis(20)));
+        assert!(tracker.record_request("GET /v2/media", Duration::from_millis(800)));
+        assert!(tracker.record_request("GET /v2/media", Duration::from_millis(600)));
+        assert!(tracker.record_request("POST /v2/scan", Duration::from_secs(2)));
+
+        // A slow statement warning as sqlx emits it
+        let subscriber = tracing_subscriber::registry().with(SlowQueryLayer::new(tracker.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(
+                target: "sqlx::query",
+                summary = "SELECT * FROM media",
+                db.statement = "\n\nSELECT *\n  FROM media\n WHERE title LIKE ?\n",
+                elapsed_secs = 0.25,
+                "slow statement: execution time exceeded alert threshold"
+            );
+        });
+
+        let report = tracker.report(10);
+        assert_eq!(report.slow_requests_total, 3);
+        assert_eq!(report.endpoints[0].key, "POST /v2/scan");
+        assert_eq!(report.endpoints[1].count, 2);
+        assert_eq!(report.endpoints[1].avg_ms, 700);
+        assert_eq!(report.slow_queries_total, 1);
+        assert_eq!(report.queries[0].key, "SELECT * FROM media WHERE title LIKE ?");
+        assert_eq!(report.queries[0].max_ms, 250);
+    }
+}
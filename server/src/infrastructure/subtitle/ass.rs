@@ -0,0 +1,201 @@
+//! ASS/SSA Subtitle Parser
+//!
+//! Reads the dialogue of Advanced SubStation Alpha (.ass) and SubStation
+//! Alpha (.ssa) files into cues, so they go through the same sanitizing and
+//! WebVTT conversion as SRT files. Bold, italic and underline, set by the
+//! style of a line or by override tags (`{\i1}`), become `<b>`, `<i>` and
+//! `<u>`; positioning, colors, karaoke and drawings are dropped.
+
+use super::sanitizer::{parse_time, Cue};
+
+/// Whether decoded subtitle text is ASS/SSA rather than SRT or WebVTT
+pub fn is_ass(text: &str) -> bool {
+    text.lines()
+        .take_while(|l| !l.contains("-->"))
+        .any(|l| l.trim().eq_ignore_ascii_case("[events]"))
+}
+
+/// Styling of a style definition
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Styling {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+/// Parses the Dialogue lines of the [Events] section into cues
+///
+/// Lines are returned in file order; sorting and overlap handling are left
+/// to the sanitizer.
+pub(super) fn parse_cues(text: &str, issues: &mut Vec<String>) -> Vec<Cue> {
+    let mut section = String::new();
+    let mut style_format: Vec<String> = Vec::new();
+    let mut event_format: Vec<String> = Vec::new();
+    let mut styles: Vec<(String, Styling)> = Vec::new();
+    let mut cues = Vec::new();
+    let mut bad_lines = 0;
+
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line.to_lowercase();
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim_start();
+
+        match (section.as_str(), key) {
+            ("[v4+ styles]" | "[v4 styles]", "Format") => style_format = format_fields(value),
+            ("[v4+ styles]" | "[v4 styles]", "Style") => {
+                let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+                let get = |name: &str| style_format.iter().position(|f| f == name).and_then(|i| fields.get(i));
+                // -1 is true in ASS; some writers use 1
+                let flag = |name: &str| get(name).is_some_and(|v| *v != "0" && !v.is_empty());
+                if let Some(name) = get("name") {
+                    styles.push((name.to_lowercase(), Styling {
+                        bold: flag("bold"),
+                        italic: flag("italic"),
+                        underline: flag("underline"),
+                    }));
+                }
+            }
+            ("[events]", "Format") => event_format = format_fields(value),
+            ("[events]", "Dialogue") => {
+                // The text is the last field and may contain commas
+                let fields: Vec<&str> = value.splitn(event_format.len().max(1), ',').collect();
+                let get = |name: &str| event_format.iter().position(|f| f == name).and_then(|i| fields.get(i));
+                let timing = get("start").zip(get("end"))
+                    .and_then(|(start, end)| Some((parse_time(start.trim())?, parse_time(end.trim())?)));
+                let (Some((start, end)), Some(text)) = (timing, get("text")) else {
+                    bad_lines += 1;
+                    continue;
+                };
+                let style = get("style")
+                    .map(|s| s.trim().trim_start_matches('*').to_lowercase())
+                    .and_then(|s| styles.iter().find(|(name, _)| *name == s))
+                    .map(|(_, styling)| *styling)
+                    .unwrap_or_default();
+                cues.push(Cue { start, end, text: convert_text(text, style) });
+            }
+            _ => {}
+        }
+    }
+
+    if bad_lines > 0 {
+        issues.push(format!("{} dialogue lines with unreadable timing dropped", bad_lines));
+    }
+    cues
+}
+
+fn format_fields(value: &str) -> Vec<String> {
+    value.split(',').map(|f| f.trim().to_lowercase()).collect()
+}
+
+/// Converts the text of a dialogue line to SRT-style markup
+fn convert_text(text: &str, style: Styling) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut state = Styling::default();
+    let mut drawing = false;
+    set_styling(&mut out, &mut state, style);
+
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '{' {
+            if let Some(close) = rest.find('}') {
+                for tag in rest[1..close].split('\\').map(str::trim).filter(|t| !t.is_empty()) {
+                    let mut next = state;
+                    if tag.starts_with('r') && !tag.starts_with("rnd") {
+                        next = style;
+                    } else if let Some(on) = toggle(tag, 'b') {
+                        next.bold = on;
+                    } else if let Some(on) = toggle(tag, 'i') {
+                        next.italic = on;
+                    } else if let Some(on) = toggle(tag, 'u') {
+                        next.underline = on;
+                    } else if let Some(level) = tag.strip_prefix('p').and_then(|l| l.parse::<u32>().ok()) {
+                        drawing = level > 0;
+                    }
+                    set_styling(&mut out, &mut state, next);
+                }
+                rest = &rest[close + 1..];
+                continue;
+            }
+        }
+        if c == '\\' {
+            let escape = match rest[1..].chars().next() {
+                Some('N') | Some('n') => Some("\n"),
+                Some('h') => Some(" "),
+                _ => None,
+            };
+            if let Some(replacement) = escape {
+                if !drawing {
+                    out.push_str(replacement);
+                }
+                rest = &rest[2..];
+                continue;
+            }
+        }
+        if !drawing {
+            out.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    set_styling(&mut out, &mut state, Styling::default());
+    out
+}
+
+/// Reads `b1`, `b0`, `b700` (font weight) and the like for one tag letter
+fn toggle(tag: &str, letter: char) -> Option<bool> {
+    let value = tag.strip_prefix(letter)?;
+    value.parse::<u32>().ok().map(|v| v != 0)
+}
+
+/// Emits the tags turning `state` into `next`
+fn set_styling(out: &mut String, state: &mut Styling, next: Styling) {
+    for (on, was, tag) in [
+        (next.bold, state.bold, "b"),
+        (next.italic, state.italic, "i"),
+        (next.underline, state.underline, "u"),
+    ] {
+        match (was, on) {
+            (false, true) => out.push_str(&format!("<{}>", tag)),
+            (true, false) => out.push_str(&format!("</{}>", tag)),
+            _ => {}
+        }
+    }
+    *state = next;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ass() {
+        let ass = "[Script Info]\nScriptType: v4.00+\n\n\
+                   [V4+ Styles]\n\
+                   Format: Name, Fontname, Fontsize, PrimaryColour, Bold, Italic, Underline\n\
+                   Style: Default,Arial,20,&H00FFFFFF,0,0,0\n\
+                   Style: Thoughts,Arial,20,&H00FFFFFF,0,-1,0\n\n\
+                   [Events]\n\
+                   Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                   Dialogue: 0,0:00:01.00,0:00:04.50,Default,,0,0,0,,{\\an8}Hello, {\\b1}world{\\b0}!\\NSecond line\n\
+                   Comment: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,Not shown\n\
+                   Dialogue: 0,0:00:05.00,0:00:06.00,Thoughts,,0,0,0,,I wonder{\\i0} aloud\n\
+                   Dialogue: 0,0:00:07.00,0:00:08.00,Default,,0,0,0,,{\\p1}m 0 0 l 100 0{\\p0}Sign\n\
+                   Dialogue: 0,broken,0:00:08.00,Default,,0,0,0,,Dropped\n";
+        assert!(is_ass(ass));
+
+        let mut issues = Vec::new();
+        let cues = parse_cues(ass, &mut issues);
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0], Cue { start: 1.0, end: 4.5, text: "Hello, <b>world</b>!\nSecond line".to_string() });
+        assert_eq!(cues[1].text, "<i>I wonder</i> aloud");
+        assert_eq!(cues[2].text, "Sign");
+        assert_eq!(issues.len(), 1);
+
+        assert!(!is_ass("1\n00:00:01,000 --> 00:00:02,000\n[Events]\n"));
+    }
+}
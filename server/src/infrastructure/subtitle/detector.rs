@@ -1,9 +1,10 @@
 //! Subtitle Detector
 //!
-//! Discovers external subtitle files (.srt) located alongside video files.
+//! Discovers external subtitle files (.srt, .ass, .ssa) located alongside
+//! video files.
 //! Supports language detection from filename patterns.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Represents an external subtitle file discovered on the filesystem.
 #[derive(Debug, Clone)]
@@ -14,12 +15,19 @@ pub struct ExternalSubtitle {
     pub language: Option<String>,
     /// Human-readable language name (e.g., "Magyar", "English")
     pub language_name: Option<String>,
+    /// Only covers foreign-language dialogue and signs (`movie.en.forced.srt`)
+    pub forced: bool,
+    /// Describes sounds for the deaf and hard of hearing (`movie.en.sdh.srt`)
+    pub hearing_impaired: bool,
 }
 
 /// Discovers external subtitle files for video files.
 ///
-/// Scans the video file's directory for .srt files that match
-/// the video filename pattern.
+/// Scans the video file's directory (and any extra directories, such as
+/// where downloads for read-only media are kept) for .srt, .ass and .ssa
+/// files that match the video filename pattern.
+///
+/// The patterns below apply to every extension (`movie.hu.ass`).
 ///
 /// # Supported patterns
 /// - `movie.srt` - Default subtitle (no language)
@@ -29,16 +37,29 @@ pub struct ExternalSubtitle {
 /// - `movie.es.srt`, `movie.spa.srt`, `movie.spanish.srt` - Spanish
 /// - `movie.fr.srt`, `movie.fra.srt`, `movie.french.srt` - French
 /// - `movie.it.srt`, `movie.ita.srt`, `movie.italian.srt` - Italian
+///
+/// Flags may follow the language: `forced` marks forced subtitles, `sdh`,
+/// `cc` and `hi` (not as the first part, where it is Hindi) mark subtitles
+/// for the hearing impaired (`movie.en.forced.srt`, `movie.en.sdh.srt`).
 #[derive(Debug, Clone)]
-pub struct SubtitleDetector;
+pub struct SubtitleDetector {
+    /// Directories searched besides the video's own
+    extra_dirs: Vec<PathBuf>,
+}
 
 impl SubtitleDetector {
     /// Creates a new SubtitleDetector instance.
     pub fn new() -> Self {
-        Self
+        Self { extra_dirs: Vec::new() }
     }
 
-    /// Discovers all .srt subtitle files for a given video file.
+    /// Also searches `dir` for subtitles of the video
+    pub fn with_extra_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.extra_dirs.push(dir.into());
+        self
+    }
+
+    /// Discovers all subtitle files for a given video file.
     ///
     /// # Arguments
     /// * `video_path` - Path to the video file
@@ -60,29 +81,35 @@ impl SubtitleDetector {
             None => return subtitles,
         };
 
-        // Read directory entries
-        let entries = match std::fs::read_dir(parent_dir) {
-            Ok(entries) => entries,
+        // Read directory entries (extra directories need not exist yet)
+        let mut entries = Vec::new();
+        match std::fs::read_dir(parent_dir) {
+            Ok(dir) => entries.extend(dir.flatten()),
             Err(e) => {
                 tracing::warn!("Failed to read directory for subtitles: {}", e);
                 return subtitles;
             }
-        };
+        }
+        for dir in &self.extra_dirs {
+            if let Ok(dir) = std::fs::read_dir(dir) {
+                entries.extend(dir.flatten());
+            }
+        }
 
-        // Find matching .srt files
-        for entry in entries.flatten() {
+        // Find matching subtitle files
+        for entry in entries {
             let path = entry.path();
 
-            // Skip if not a file or not .srt
+            // Skip if not a file or not a subtitle
             if !path.is_file() {
                 continue;
             }
-            let extension = path.extension().and_then(|e| e.to_str());
-            if extension != Some("srt") {
+            let extension = path.extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+            if !matches!(extension.as_deref(), Some("srt" | "ass" | "ssa")) {
                 continue;
             }
 
-            // Get filename without .srt extension
+            // Get filename without extension
             let filename = match path.file_stem().and_then(|s| s.to_str()) {
                 Some(name) => name.to_lowercase(),
                 None => continue,
@@ -95,21 +122,34 @@ impl SubtitleDetector {
 
             // Extract language suffix (everything after video stem)
             let suffix = &filename[video_stem.len()..];
-            let (language, language_name) = self.detect_language(suffix);
+            let (forced, hearing_impaired) = detect_flags(suffix);
+            let (language, language_name) = match suffix.trim_start_matches('.').split('.').next() {
+                // `movie.forced.srt` has no language
+                Some(first) if is_flag(first) => (None, None),
+                _ => self.detect_language(suffix),
+            };
 
             subtitles.push(ExternalSubtitle {
                 file_path: path.to_string_lossy().to_string(),
                 language,
                 language_name,
+                forced,
+                hearing_impaired,
             });
         }
 
-        // Sort by language (None first, then alphabetically)
-        subtitles.sort_by(|a, b| match (&a.language, &b.language) {
-            (None, None) => std::cmp::Ordering::Equal,
-            (None, Some(_)) => std::cmp::Ordering::Less,
-            (Some(_), None) => std::cmp::Ordering::Greater,
-            (Some(la), Some(lb)) => la.cmp(lb),
+        // Sort by language (None first, then alphabetically), full subtitles
+        // before forced and SDH ones
+        subtitles.sort_by(|a, b| {
+            let language = match (&a.language, &b.language) {
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (Some(la), Some(lb)) => la.cmp(lb),
+            };
+            language
+                .then(a.forced.cmp(&b.forced))
+                .then(a.hearing_impaired.cmp(&b.hearing_impaired))
         });
 
         subtitles
@@ -179,6 +219,36 @@ impl SubtitleDetector {
     }
 }
 
+/// Whether a filename part is a forced or SDH flag rather than a language
+fn is_flag(part: &str) -> bool {
+    matches!(part, "forced" | "sdh" | "cc")
+}
+
+/// Reads the forced and hearing-impaired flags from a filename suffix
+/// (".en.forced", ".hu.sdh")
+///
+/// The first part is the language, so "hi" only counts after it.
+fn detect_flags(suffix: &str) -> (bool, bool) {
+    let suffix = suffix.trim_start_matches('.').to_lowercase();
+    let mut forced = false;
+    let mut hearing_impaired = false;
+    for (i, part) in suffix.split('.').enumerate() {
+        match part {
+            "forced" => forced = true,
+            "sdh" | "cc" => hearing_impaired = true,
+            "hi" if i > 0 => hearing_impaired = true,
+            _ => {}
+        }
+    }
+    (forced, hearing_impaired)
+}
+
+/// Normalizes a language code or name ("eng", "English", "en") to its
+/// ISO 639-1 code; unknown codes are returned lowercased
+pub fn normalize_language_code(code: &str) -> Option<String> {
+    SubtitleDetector::new().detect_language(code).0
+}
+
 impl Default for SubtitleDetector {
     fn default() -> Self {
         Self::new()
@@ -249,4 +319,38 @@ mod tests {
         assert_eq!(code, Some("hu".to_string()));
         assert_eq!(name, Some("Magyar".to_string()));
     }
+
+    #[test]
+    fn test_detect_flags() {
+        assert_eq!(detect_flags(".en.forced"), (true, false));
+        assert_eq!(detect_flags(".en.sdh"), (false, true));
+        assert_eq!(detect_flags(".EN.HI"), (false, true));
+        assert_eq!(detect_flags(".forced.cc"), (true, true));
+        assert_eq!(detect_flags(".en"), (false, false));
+        // Hindi, not hearing impaired
+        assert_eq!(detect_flags(".hi"), (false, false));
+    }
+
+    #[test]
+    fn test_discover_flags() {
+        let dir = std::env::temp_dir().join(format!("homeflix-detector-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["Film.mkv", "Film.en.forced.srt", "Film.en.srt", "Film.en.sdh.srt", "Film.forced.srt"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let subtitles = SubtitleDetector::new().discover(&dir.join("Film.mkv"));
+        let found: Vec<(Option<&str>, bool, bool)> = subtitles
+            .iter()
+            .map(|s| (s.language.as_deref(), s.forced, s.hearing_impaired))
+            .collect();
+        assert_eq!(found, vec![
+            (None, true, false),
+            (Some("en"), false, false),
+            (Some("en"), false, true),
+            (Some("en"), true, false),
+        ]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,12 +1,25 @@
 //! Subtitle Infrastructure Module
 //!
 //! This module provides subtitle-related functionality including:
-//! - Detection of external subtitle files (.srt)
+//! - Detection of external subtitle files (.srt, .ass, .ssa)
 //! - Language detection from filenames
-//! - SRT to WebVTT conversion for HTML5 compatibility
+//! - SRT and ASS/SSA to WebVTT conversion for HTML5 compatibility
+//! - Decoding of PGS bitmap subtitles for OCR
+//! - Validation and repair of subtitle files (encoding, timings, tags)
+//! - Storage of downloaded subtitles
+//! - WebVTT segments of HLS subtitle renditions
 
+pub mod ass;
 pub mod detector;
 pub mod converter;
+pub mod pgs;
+pub mod sanitizer;
+pub mod segmenter;
+pub mod store;
 
 pub use detector::*;
 pub use converter::*;
+pub use pgs::decode_sup;
+pub use sanitizer::*;
+pub use segmenter::VttSegmenter;
+pub use store::*;
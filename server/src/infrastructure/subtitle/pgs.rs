@@ -0,0 +1,253 @@
+//! PGS Subtitle Decoder
+//!
+//! Decodes Presentation Graphic Stream subtitles (the bitmap subtitles of
+//! Blu-ray discs, stored as .sup) into one grayscale image per caption,
+//! ready for OCR. Each display set is a run of segments: a palette, the
+//! run-length encoded objects and a composition placing them on screen; a
+//! composition without objects clears the screen and ends the caption.
+
+use crate::interfaces::external_services::SubtitleImage;
+use crate::shared::error::SubtitleError;
+
+/// PTS clock rate of PGS timestamps
+const PTS_PER_SECOND: f64 = 90_000.0;
+
+const SEGMENT_PALETTE: u8 = 0x14;
+const SEGMENT_OBJECT: u8 = 0x15;
+const SEGMENT_COMPOSITION: u8 = 0x16;
+
+/// Caption shown when no end is found (seconds)
+const DEFAULT_DURATION: f64 = 4.0;
+
+/// A decoded caption
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgsCaption {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    pub image: SubtitleImage,
+}
+
+/// An object placed on screen by a composition
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    object_id: u16,
+    x: usize,
+    y: usize,
+}
+
+/// A decoded object bitmap (palette indices)
+#[derive(Debug, Clone, Default)]
+struct Object {
+    width: usize,
+    height: usize,
+    /// RLE data, collected across segments
+    data: Vec<u8>,
+}
+
+/// Decodes a .sup stream into captions
+///
+/// # Errors
+/// Returns error if the stream is not PGS
+pub fn decode_sup(bytes: &[u8]) -> Result<Vec<PgsCaption>, SubtitleError> {
+    let mut captions: Vec<PgsCaption> = Vec::new();
+    let mut palette = [(0u8, 0u8); 256];
+    let mut objects: Vec<(u16, Object)> = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
+    let mut shown_since: Option<f64> = None;
+
+    let mut pos = 0;
+    while pos + 13 <= bytes.len() {
+        if &bytes[pos..pos + 2] != b"PG" {
+            return Err(SubtitleError::InvalidFormat(format!("No PGS segment at byte {}", pos)));
+        }
+        let pts = u32::from_be_bytes([bytes[pos + 2], bytes[pos + 3], bytes[pos + 4], bytes[pos + 5]]) as f64 / PTS_PER_SECOND;
+        let kind = bytes[pos + 10];
+        let size = u16::from_be_bytes([bytes[pos + 11], bytes[pos + 12]]) as usize;
+        let data = bytes.get(pos + 13..pos + 13 + size)
+            .ok_or_else(|| SubtitleError::InvalidFormat("Truncated PGS segment".to_string()))?;
+        pos += 13 + size;
+
+        match kind {
+            SEGMENT_PALETTE if data.len() >= 2 => {
+                for entry in data[2..].chunks_exact(5) {
+                    // Only luminance and alpha matter in grayscale
+                    palette[entry[0] as usize] = (entry[1], entry[4]);
+                }
+            }
+            SEGMENT_OBJECT if data.len() >= 4 => {
+                let id = u16::from_be_bytes([data[0], data[1]]);
+                let first = data[3] & 0x80 != 0;
+                if first {
+                    let object = match data.get(4..11) {
+                        Some(header) => Object {
+                            width: u16::from_be_bytes([header[3], header[4]]) as usize,
+                            height: u16::from_be_bytes([header[5], header[6]]) as usize,
+                            data: data[11..].to_vec(),
+                        },
+                        None => continue,
+                    };
+                    objects.retain(|(existing, _)| *existing != id);
+                    objects.push((id, object));
+                } else if let Some((_, object)) = objects.iter_mut().find(|(existing, _)| *existing == id) {
+                    object.data.extend_from_slice(&data[4..]);
+                }
+            }
+            SEGMENT_COMPOSITION if data.len() >= 11 => {
+                // The caption on screen ends when the next composition shows
+                if let Some(start) = shown_since.take() {
+                    if let Some(image) = render(&placements, &objects, &palette) {
+                        captions.push(PgsCaption { start, end: pts, image });
+                    }
+                }
+                placements.clear();
+                let mut entry = 11;
+                for _ in 0..data[10] {
+                    let Some(c) = data.get(entry..entry + 8) else { break };
+                    placements.push(Placement {
+                        object_id: u16::from_be_bytes([c[0], c[1]]),
+                        x: u16::from_be_bytes([c[4], c[5]]) as usize,
+                        y: u16::from_be_bytes([c[6], c[7]]) as usize,
+                    });
+                    // Cropped objects carry the crop rectangle too
+                    entry += if c[3] & 0x40 != 0 { 16 } else { 8 };
+                }
+                if !placements.is_empty() {
+                    shown_since = Some(pts);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(start) = shown_since {
+        if let Some(image) = render(&placements, &objects, &palette) {
+            captions.push(PgsCaption { start, end: start + DEFAULT_DURATION, image });
+        }
+    }
+    Ok(captions)
+}
+
+/// Draws the placed objects onto one image covering all of them
+///
+/// Text is drawn dark on white: the luminance of each pixel, weighted by
+/// its opacity, is inverted, so the usual white text with a black outline
+/// becomes black text.
+fn render(placements: &[Placement], objects: &[(u16, Object)], palette: &[(u8, u8); 256]) -> Option<SubtitleImage> {
+    let placed: Vec<(Placement, &Object, Vec<u8>)> = placements
+        .iter()
+        .filter_map(|p| {
+            let (_, object) = objects.iter().find(|(id, _)| *id == p.object_id).filter(|(_, o)| o.width > 0)?;
+            let indices = decode_rle(&object.data, object.width, object.height);
+            Some((*p, object, indices))
+        })
+        .collect();
+    let left = placed.iter().map(|(p, _, _)| p.x).min()?;
+    let top = placed.iter().map(|(p, _, _)| p.y).min()?;
+    let width = placed.iter().map(|(p, o, _)| p.x + o.width).max()? - left;
+    let height = placed.iter().map(|(p, o, _)| p.y + o.height).max()? - top;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut pixels = vec![255u8; width * height];
+    for (placement, object, indices) in &placed {
+        for (i, index) in indices.iter().enumerate() {
+            let (luma, alpha) = palette[*index as usize];
+            let x = placement.x - left + i % object.width;
+            let y = placement.y - top + i / object.width;
+            pixels[y * width + x] = 255 - (luma as u16 * alpha as u16 / 255) as u8;
+        }
+    }
+    Some(SubtitleImage { width, height, pixels })
+}
+
+/// Expands PGS run-length encoding into `width * height` palette indices
+fn decode_rle(data: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(width * height);
+    let mut line = 0;
+    let mut i = 0;
+    let mut next = || {
+        let byte = data.get(i).copied();
+        i += 1;
+        byte
+    };
+
+    while let Some(byte) = next() {
+        let (count, color) = if byte != 0 {
+            (1, byte)
+        } else {
+            let Some(flags) = next() else { break };
+            let short = (flags & 0x3F) as usize;
+            match flags >> 6 {
+                _ if flags == 0 => {
+                    // End of line: pad short lines to the object width
+                    line += 1;
+                    pixels.resize(line * width, 0);
+                    continue;
+                }
+                0 => (short, 0),
+                1 => ((short << 8) | next().unwrap_or(0) as usize, 0),
+                2 => (short, next().unwrap_or(0)),
+                _ => ((short << 8) | next().unwrap_or(0) as usize, next().unwrap_or(0)),
+            }
+        };
+        pixels.extend(std::iter::repeat_n(color, count));
+    }
+
+    pixels.resize(width * height, 0);
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(pts: u32, kind: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = b"PG".to_vec();
+        segment.extend_from_slice(&pts.to_be_bytes());
+        segment.extend_from_slice(&0u32.to_be_bytes());
+        segment.push(kind);
+        segment.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        segment.extend_from_slice(data);
+        segment
+    }
+
+    fn composition(objects: &[(u16, u16, u16)]) -> Vec<u8> {
+        let mut data = vec![0x07, 0x80, 0x04, 0x38, 0x10, 0, 0, 0x80, 0, 0, objects.len() as u8];
+        for (id, x, y) in objects {
+            data.extend_from_slice(&id.to_be_bytes());
+            data.extend_from_slice(&[0, 0]);
+            data.extend_from_slice(&x.to_be_bytes());
+            data.extend_from_slice(&y.to_be_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_sup() {
+        // Palette: 1 = opaque white text, 2 = opaque black outline
+        let palette = [0, 0, 1, 235, 128, 128, 255, 2, 16, 128, 128, 255];
+        // 4x2 object: "1 1 2 <1 transparent>", then a row of 4 transparent
+        let rle = [1, 1, 2, 0, 0x01, 0, 0, 0, 0x04, 0, 0];
+        let mut object = vec![0, 7, 0, 0xC0, 0, 0, 0, 0, 4, 0, 2];
+        object.extend_from_slice(&rle);
+
+        let mut sup = segment(90_000, SEGMENT_COMPOSITION, &composition(&[(7, 100, 900)]));
+        sup.extend(segment(90_000, SEGMENT_PALETTE, &palette));
+        sup.extend(segment(90_000, SEGMENT_OBJECT, &object));
+        sup.extend(segment(90_000, 0x80, &[]));
+        sup.extend(segment(270_000, SEGMENT_COMPOSITION, &composition(&[])));
+
+        let captions = decode_sup(&sup).unwrap();
+        assert_eq!(captions.len(), 1);
+        assert_eq!((captions[0].start, captions[0].end), (1.0, 3.0));
+        let image = &captions[0].image;
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.pixels, vec![20, 20, 239, 255, 255, 255, 255, 255]);
+        assert!(image.to_pgm().starts_with(b"P5\n4 2\n255\n"));
+
+        assert!(decode_sup(b"not a sup file").is_err());
+    }
+}
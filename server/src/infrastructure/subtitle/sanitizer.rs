@@ -0,0 +1,455 @@
+//! Subtitle Sanitizer
+//!
+//! Validates and repairs subtitle files (.srt, .vtt, and .ass/.ssa through
+//! [`super::ass`]) before they are served or fed into the translation
+//! pipeline:
+//!
+//! - Decodes the file to UTF-8 (BOMs, UTF-16, and legacy encodings such
+//!   as Windows-1250, detected with chardetng unless given explicitly)
+//! - Drops cues with unreadable timings and fixes zero-length cues
+//! - Sorts cues and trims overlapping ones
+//! - Strips formatting tags according to a [`TagPolicy`]
+//!
+//! Every repair is recorded as an issue, so broken files can be found in
+//! the logs.
+
+use std::path::Path;
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::shared::error::SubtitleError;
+
+/// Duration given to cues whose end is not after their start (seconds)
+const DEFAULT_CUE_DURATION: f64 = 2.0;
+
+/// Which formatting tags are kept in cue text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagPolicy {
+    /// Keep all tags as they are
+    Keep,
+    /// Keep `<b>`, `<i>` and `<u>`, strip the rest (e.g. `<font>`)
+    #[default]
+    Basic,
+    /// Strip all tags
+    Strip,
+}
+
+impl TagPolicy {
+    fn keeps(&self, tag: &str) -> bool {
+        match self {
+            TagPolicy::Keep => true,
+            TagPolicy::Basic => matches!(tag, "b" | "i" | "u"),
+            TagPolicy::Strip => false,
+        }
+    }
+}
+
+/// How a subtitle file is read
+#[derive(Debug, Clone, Default)]
+pub struct SubtitleOptions {
+    pub tags: TagPolicy,
+    /// Encoding of the file, overriding detection
+    pub encoding: Option<&'static Encoding>,
+    /// Language of the subtitle (ISO 639-1), used as a detection hint
+    pub language: Option<String>,
+}
+
+impl SubtitleOptions {
+    pub fn new(tags: TagPolicy) -> Self {
+        Self { tags, ..Self::default() }
+    }
+
+    /// Sets the encoding override
+    pub fn with_encoding(mut self, encoding: Option<&'static Encoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Sets the language hint
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+}
+
+/// Looks up an encoding by its WHATWG label (e.g. "windows-1250", "latin2")
+///
+/// # Errors
+/// Returns error if the label is unknown
+pub fn encoding_for_label(label: &str) -> Result<&'static Encoding, SubtitleError> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| SubtitleError::EncodingError(format!("Unknown encoding: {}", label)))
+}
+
+/// A subtitle cue
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Text lines separated by '\n'
+    pub text: String,
+}
+
+/// A validated subtitle with the repairs that were made
+#[derive(Debug, Clone)]
+pub struct SanitizedSubtitle {
+    pub cues: Vec<Cue>,
+    /// Encoding the file was decoded from
+    pub encoding: &'static str,
+    /// Problems found and fixed
+    pub issues: Vec<String>,
+}
+
+impl SanitizedSubtitle {
+    /// Renders the cues as WebVTT, shifted back by `offset_seconds`
+    ///
+    /// Cues that end before the offset are left out.
+    pub fn to_vtt(&self, offset_seconds: f64) -> String {
+        let mut vtt = String::from("WEBVTT\n\n");
+        let cues = self.cues.iter().filter(|c| c.end - offset_seconds > 0.0);
+        for (i, cue) in cues.enumerate() {
+            vtt.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_time((cue.start - offset_seconds).max(0.0)),
+                format_time(cue.end - offset_seconds),
+                cue.text
+            ));
+        }
+        vtt
+    }
+
+    /// Renders the cues as SubRip
+    pub fn to_srt(&self) -> String {
+        let mut srt = String::new();
+        for (i, cue) in self.cues.iter().enumerate() {
+            srt.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_time(cue.start).replace('.', ","),
+                format_time(cue.end).replace('.', ","),
+                cue.text
+            ));
+        }
+        srt
+    }
+}
+
+/// Reads and sanitizes a subtitle file, logging the problems found
+///
+/// # Errors
+/// Returns error if the file cannot be read or has no usable cues
+pub fn read_subtitle_file(file_path: &str, options: &SubtitleOptions) -> Result<SanitizedSubtitle, SubtitleError> {
+    if !Path::new(file_path).exists() {
+        return Err(SubtitleError::FileNotFound(file_path.to_string()));
+    }
+    let bytes = std::fs::read(file_path)?;
+    let subtitle = sanitize_subtitle(&bytes, options);
+
+    if !subtitle.issues.is_empty() {
+        warn!("Subtitle {} ({}): {}", file_path, subtitle.encoding, subtitle.issues.join("; "));
+    }
+    if subtitle.cues.is_empty() {
+        return Err(SubtitleError::InvalidFormat(format!("No usable cues in {}", file_path)));
+    }
+    Ok(subtitle)
+}
+
+/// Decodes, validates and repairs subtitle content (SRT, WebVTT or ASS/SSA)
+pub fn sanitize_subtitle(bytes: &[u8], options: &SubtitleOptions) -> SanitizedSubtitle {
+    let mut issues = Vec::new();
+    let (text, encoding) = decode(bytes, options);
+    if encoding != "UTF-8" {
+        issues.push(format!("decoded from {}", encoding));
+    }
+
+    let mut cues = if super::ass::is_ass(&text) {
+        super::ass::parse_cues(&text, &mut issues)
+    } else {
+        parse_cues(&text, &mut issues)
+    };
+
+    let mut zero_length = 0;
+    for cue in &mut cues {
+        if cue.end <= cue.start {
+            cue.end = cue.start + DEFAULT_CUE_DURATION;
+            zero_length += 1;
+        }
+    }
+    if zero_length > 0 {
+        issues.push(format!("{} cues ended before they started", zero_length));
+    }
+
+    if cues.windows(2).any(|w| w[1].start < w[0].start) {
+        cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+        issues.push("cues were out of order".to_string());
+    }
+
+    // Cues starting together are shown together; only staggered overlaps are trimmed
+    let mut overlapping = 0;
+    for i in 1..cues.len() {
+        let next_start = cues[i].start;
+        let cue = &mut cues[i - 1];
+        if cue.end > next_start && next_start > cue.start {
+            cue.end = next_start;
+            overlapping += 1;
+        }
+    }
+    if overlapping > 0 {
+        issues.push(format!("{} overlapping cues trimmed", overlapping));
+    }
+
+    let mut empty = 0;
+    cues.retain_mut(|cue| {
+        // Tags alone (e.g. "<i></i>") leave nothing to show
+        let visible = !clean_text(&cue.text, TagPolicy::Strip).is_empty();
+        cue.text = clean_text(&cue.text, options.tags);
+        if !visible {
+            empty += 1;
+        }
+        visible
+    });
+    if empty > 0 {
+        issues.push(format!("{} empty cues removed", empty));
+    }
+
+    SanitizedSubtitle { cues, encoding, issues }
+}
+
+/// Decodes subtitle bytes to text
+///
+/// An explicit encoding wins (a BOM still takes precedence). Otherwise
+/// UTF-8 is used as is, files with a UTF-16 BOM are decoded accordingly,
+/// and anything else goes through charset detection, hinted by the
+/// subtitle language.
+fn decode(bytes: &[u8], options: &SubtitleOptions) -> (String, &'static str) {
+    let encoding = match options.encoding {
+        Some(encoding) => encoding,
+        None => {
+            if let Ok(text) = std::str::from_utf8(bytes) {
+                return (text.trim_start_matches('\u{feff}').to_string(), "UTF-8");
+            }
+            let mut detector = EncodingDetector::new();
+            detector.feed(bytes, true);
+            let tld = options.language.as_deref().and_then(language_tld);
+            detector.guess(tld.map(str::as_bytes), false)
+        }
+    };
+    // Sniffs a BOM first, so UTF-16 files end up here too
+    let (text, encoding, _) = encoding.decode(bytes);
+    (text.trim_start_matches('\u{feff}').to_string(), encoding.name())
+}
+
+/// Country code top-level domain whose legacy encodings fit a language
+///
+/// chardetng weighs its guess by the TLD a page came from; for subtitles
+/// the language is the closest equivalent.
+fn language_tld(language: &str) -> Option<&str> {
+    let tld = match language {
+        "cs" => "cz",
+        "sl" => "si",
+        "sr" => "rs",
+        "uk" => "ua",
+        "el" => "gr",
+        "da" => "dk",
+        "et" => "ee",
+        "ja" => "jp",
+        "ko" => "kr",
+        "zh" => "cn",
+        "he" => "il",
+        "ar" => "sa",
+        "fa" => "ir",
+        "vi" => "vn",
+        // Generic, no regional hint
+        "en" => return None,
+        other if other.len() == 2 => other,
+        _ => return None,
+    };
+    Some(tld)
+}
+
+/// Splits the content into cues, skipping WebVTT header and metadata blocks
+fn parse_cues(text: &str, issues: &mut Vec<String>) -> Vec<Cue> {
+    // Whitespace-only lines also separate cues
+    let text = text
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut bad_timings = 0;
+    let mut stray_blocks = 0;
+
+    for block in text.split("\n\n").map(str::trim).filter(|b| !b.is_empty()) {
+        let lines: Vec<&str> = block.lines().map(str::trim).collect();
+        let Some(timing) = lines.iter().position(|l| l.contains("-->")) else {
+            let first = lines[0];
+            let is_metadata = ["WEBVTT", "NOTE", "STYLE", "REGION"].iter().any(|k| first.starts_with(k));
+            let is_cue_number = lines.len() == 1 && first.parse::<u32>().is_ok();
+            if is_metadata || is_cue_number {
+                continue;
+            }
+            // Text after a blank line inside a cue
+            if let Some(previous) = cues.last_mut() {
+                previous.text.push('\n');
+                previous.text.push_str(&lines.join("\n"));
+            }
+            stray_blocks += 1;
+            continue;
+        };
+
+        let Some((start, end)) = parse_timing(lines[timing]) else {
+            bad_timings += 1;
+            continue;
+        };
+        cues.push(Cue {
+            start,
+            end,
+            text: lines[timing + 1..].join("\n"),
+        });
+    }
+
+    if bad_timings > 0 {
+        issues.push(format!("{} cues with unreadable timing dropped", bad_timings));
+    }
+    if stray_blocks > 0 {
+        issues.push(format!("{} text blocks without timing joined to the previous cue", stray_blocks));
+    }
+    cues
+}
+
+/// Parses "00:00:01,000 --> 00:00:04,000" (cue settings after the end are ignored)
+fn parse_timing(line: &str) -> Option<(f64, f64)> {
+    let (start, end) = line.split_once("-->")?;
+    let end = end.split_whitespace().next()?;
+    Some((parse_time(start.trim())?, parse_time(end)?))
+}
+
+/// Parses "HH:MM:SS,mmm", "HH:MM:SS.mmm" or "MM:SS.mmm" to seconds
+pub(super) fn parse_time(ts: &str) -> Option<f64> {
+    let parts: Vec<&str> = ts.split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>().ok()?, m.parse::<f64>().ok()?, *s),
+        [m, s] => (0.0, m.parse::<f64>().ok()?, *s),
+        _ => return None,
+    };
+    let seconds: f64 = seconds.replace(',', ".").parse().ok()?;
+    let total = hours * 3600.0 + minutes * 60.0 + seconds;
+    (total.is_finite() && total >= 0.0 && minutes < 60.0 && seconds < 60.0).then_some(total)
+}
+
+/// Formats seconds as a WebVTT timestamp (HH:MM:SS.mmm)
+pub(crate) fn format_time(total_seconds: f64) -> String {
+    let total_millis = (total_seconds * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        total_millis / 3_600_000,
+        (total_millis / 60_000) % 60,
+        (total_millis / 1000) % 60,
+        total_millis % 1000
+    )
+}
+
+/// Applies the tag policy and removes ASS override blocks like `{\an8}`
+fn clean_text(text: &str, policy: TagPolicy) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '{' && rest[1..].starts_with('\\') {
+            if let Some(close) = rest.find('}') {
+                rest = &rest[close + 1..];
+                continue;
+            }
+        }
+        if c == '<' {
+            match rest.find('>') {
+                Some(close) => {
+                    let tag = &rest[..=close];
+                    let name = tag[1..tag.len() - 1]
+                        .trim_start_matches('/')
+                        .split(|c: char| c.is_whitespace() || c == '.')
+                        .next()
+                        .unwrap_or("")
+                        .to_lowercase();
+                    if policy.keeps(&name) {
+                        cleaned.push_str(tag);
+                    }
+                    rest = &rest[close + 1..];
+                }
+                None => {
+                    // A lone '<' would start a tag in WebVTT
+                    cleaned.push_str("&lt;");
+                    rest = &rest[1..];
+                }
+            }
+            continue;
+        }
+        cleaned.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    cleaned
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repairs_malformed_srt() {
+        let srt = "1\r\n00:00:05,000 --> 00:00:07,000\r\n<font color=\"red\">{\\an8}<i>Later</i></font>\r\n\r\n\
+                   2\r\n00:00:01,000 --> 00:00:06,000\r\nFirst\r\n\r\ncontinued\r\n\r\n\
+                   3\r\nbroken --> timing\r\nDropped\r\n\r\n\
+                   4\r\n00:00:09,000 --> 00:00:08,000\r\n<b></b>\r\n";
+        let subtitle = sanitize_subtitle(srt.as_bytes(), &SubtitleOptions::default());
+
+        assert_eq!(subtitle.encoding, "UTF-8");
+        assert_eq!(subtitle.cues.len(), 2);
+        assert_eq!(subtitle.cues[0], Cue { start: 1.0, end: 5.0, text: "First\ncontinued".to_string() });
+        assert_eq!(subtitle.cues[1].text, "<i>Later</i>");
+        assert_eq!(subtitle.issues.len(), 6);
+
+        let vtt = subtitle.to_vtt(2.0);
+        assert!(vtt.starts_with("WEBVTT\n\n1\n00:00:00.000 --> 00:00:03.000\nFirst\ncontinued\n"));
+        assert_eq!(clean_text("<b>a</b> < b", TagPolicy::Strip), "a &lt; b");
+    }
+
+    #[test]
+    fn test_decodes_legacy_encodings() {
+        // The quotes only exist in Windows-1250, not in ISO-8859-2
+        let text = "1\n00:00:01,000 --> 00:00:02,000\nÁrvíztűrő tükörfúrógép, ő és ű betűk.\n\n\
+                    2\n00:00:03,000 --> 00:00:04,000\n„Hűtőszekrény, kőműves, gyűrű.”\n";
+        let (bytes, _, _) = encoding_rs::WINDOWS_1250.encode(text);
+        let hungarian = SubtitleOptions::default().with_language(Some("hu".to_string()));
+        let subtitle = sanitize_subtitle(&bytes, &hungarian);
+        assert_eq!(subtitle.encoding, "windows-1250");
+        assert_eq!(subtitle.cues[1].text, "„Hűtőszekrény, kőműves, gyűrű.”");
+
+        // The override wins over detection
+        let latin2 = SubtitleOptions::default().with_encoding(Some(encoding_for_label("latin2").unwrap()));
+        let subtitle = sanitize_subtitle(&bytes, &latin2);
+        assert_eq!(subtitle.encoding, "ISO-8859-2");
+        assert!(encoding_for_label("klingon").is_err());
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "WEBVTT\n\n00:01.000 --> 00:02.500\nHi".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        let subtitle = sanitize_subtitle(&utf16, &SubtitleOptions::default());
+        assert_eq!(subtitle.encoding, "UTF-16LE");
+        assert_eq!(subtitle.cues[0], Cue { start: 1.0, end: 2.5, text: "Hi".to_string() });
+    }
+}
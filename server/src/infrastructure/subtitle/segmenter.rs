@@ -0,0 +1,95 @@
+//! WebVTT segmenting for HLS
+//!
+//! HLS subtitle renditions are WebVTT files cut into segments of the same
+//! length as the media segments. Each segment holds the cues that overlap
+//! its time range, with their absolute media times; a cue spanning two
+//! segments is repeated in both and players show it once.
+//!
+//! The `X-TIMESTAMP-MAP` header ties the cue times to the MPEG-TS clock of
+//! the video segments, which FFmpeg's muxer starts 1.4s in.
+
+use std::fmt::Write as _;
+use super::sanitizer::{format_time, Cue};
+
+/// MPEG-TS timestamp (90 kHz) of media time 0 in FFmpeg's HLS output
+const MPEGTS_START: u64 = 126_000;
+
+/// Cuts subtitle cues into WebVTT segments
+#[derive(Debug, Clone, Copy)]
+pub struct VttSegmenter {
+    segment_seconds: u32,
+}
+
+impl VttSegmenter {
+    /// Creates a segmenter for media segments of `segment_seconds`
+    pub fn new(segment_seconds: u32) -> Self {
+        Self { segment_seconds: segment_seconds.max(1) }
+    }
+
+    /// File name of a segment (`sub_00042.vtt`)
+    pub fn segment_file_name(index: u32) -> String {
+        format!("sub_{:05}.vtt", index)
+    }
+
+    /// Number of segments of a media item
+    pub fn segment_count(&self, duration_seconds: f64) -> u32 {
+        (duration_seconds / self.segment_seconds as f64).ceil().max(1.0) as u32
+    }
+
+    /// Builds the media playlist of a subtitle rendition
+    ///
+    /// Segment URIs are relative to the playlist.
+    pub fn playlist(&self, duration_seconds: f64) -> String {
+        let mut playlist = format!(
+            "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{}\n#EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n",
+            self.segment_seconds
+        );
+        for index in 0..self.segment_count(duration_seconds) {
+            let start = index as f64 * self.segment_seconds as f64;
+            let length = (duration_seconds - start).min(self.segment_seconds as f64);
+            let _ = writeln!(playlist, "#EXTINF:{:.3},\n{}", length, Self::segment_file_name(index));
+        }
+        playlist.push_str("#EXT-X-ENDLIST\n");
+        playlist
+    }
+
+    /// Renders segment `index` from the cues of the whole subtitle
+    pub fn segment(&self, cues: &[Cue], index: u32) -> String {
+        let start = index as f64 * self.segment_seconds as f64;
+        let end = start + self.segment_seconds as f64;
+
+        let mut vtt = format!("WEBVTT\nX-TIMESTAMP-MAP=MPEGTS:{},LOCAL:00:00:00.000\n\n", MPEGTS_START);
+        for cue in cues.iter().filter(|c| c.start < end && c.end > start) {
+            let _ = write!(vtt, "{} --> {}\n{}\n\n", format_time(cue.start), format_time(cue.end), cue.text);
+        }
+        vtt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        let cue = |start: f64, end: f64, text: &str| Cue { start, end, text: text.to_string() };
+        let cues = vec![cue(1.0, 3.0, "First"), cue(5.0, 7.5, "Across"), cue(13.0, 14.0, "Third")];
+        let segmenter = VttSegmenter::new(6);
+
+        let playlist = segmenter.playlist(14.0);
+        assert_eq!(playlist.matches("#EXTINF").count(), 3);
+        assert!(playlist.contains("#EXTINF:2.000,\nsub_00002.vtt"));
+
+        let first = segmenter.segment(&cues, 0);
+        assert!(first.starts_with("WEBVTT\nX-TIMESTAMP-MAP=MPEGTS:126000,LOCAL:00:00:00.000\n"));
+        assert!(first.contains("00:00:01.000 --> 00:00:03.000\nFirst"));
+        assert!(first.contains("Across"));
+
+        // The cue across the boundary is repeated, with absolute times
+        let second = segmenter.segment(&cues, 1);
+        assert!(second.contains("00:00:05.000 --> 00:00:07.500\nAcross"));
+        assert!(!second.contains("Third"));
+
+        assert!(!segmenter.segment(&cues, 5).contains("-->"));
+    }
+}
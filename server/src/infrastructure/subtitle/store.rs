@@ -0,0 +1,145 @@
+//! Subtitle Store
+//!
+//! Writes downloaded subtitles next to the video as `video.LANG.srt`, where
+//! other players find them too. Media on read-only shares gets its
+//! subtitles in `{data_dir}/subtitles/{media_id}/` instead, which
+//! [`SubtitleStore::detector`] searches as well.
+
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::subtitle::SubtitleDetector;
+use crate::shared::error::FilesystemError;
+
+/// Where a subtitle was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredSubtitle {
+    pub path: PathBuf,
+    /// Whether the media directory was not writable and the data directory
+    /// was used
+    pub in_data_dir: bool,
+}
+
+/// Subtitle file store
+#[derive(Debug, Clone)]
+pub struct SubtitleStore {
+    fallback_dir: PathBuf,
+}
+
+impl SubtitleStore {
+    /// Creates a store
+    ///
+    /// # Arguments
+    /// * `data_dir` - Base data directory (e.g., /data or ./data)
+    pub fn new(data_dir: &str) -> Self {
+        Self {
+            fallback_dir: Path::new(data_dir).join("subtitles"),
+        }
+    }
+
+    /// Data directory subtitles of a media item go to when its own
+    /// directory is read-only
+    pub fn fallback_dir(&self, media_id: i64) -> PathBuf {
+        self.fallback_dir.join(media_id.to_string())
+    }
+
+    /// Cached conversion of an embedded subtitle track
+    ///
+    /// Kept apart from the directories the detector searches, so converting
+    /// a track does not add an external subtitle and shift track indices.
+    pub fn embedded_cache(&self, media_id: i64, track: usize) -> PathBuf {
+        self.fallback_dir.join("embedded").join(format!("{}.{}.srt", media_id, track))
+    }
+
+    /// Detector finding the subtitles of a media item in both places
+    pub fn detector(&self, media_id: i64) -> SubtitleDetector {
+        SubtitleDetector::new().with_extra_dir(self.fallback_dir(media_id))
+    }
+
+    /// Writes a subtitle for a video, replacing one in the same language
+    pub fn write(
+        &self,
+        media_id: i64,
+        video_path: &Path,
+        language: &str,
+        content: &[u8],
+    ) -> Result<StoredSubtitle, FilesystemError> {
+        self.write_as(media_id, video_path, language, "srt", content)
+    }
+
+    /// Writes a subtitle as `video.LABEL.EXTENSION`
+    ///
+    /// The label starts with the language, so `hu.2` is still found as
+    /// Hungarian.
+    pub fn write_as(
+        &self,
+        media_id: i64,
+        video_path: &Path,
+        label: &str,
+        extension: &str,
+        content: &[u8],
+    ) -> Result<StoredSubtitle, FilesystemError> {
+        let filename = Self::filename(video_path, label, extension)?;
+
+        if let Some(parent) = video_path.parent() {
+            let path = parent.join(&filename);
+            match std::fs::write(&path, content) {
+                Ok(()) => return Ok(StoredSubtitle { path, in_data_dir: false }),
+                Err(e) => tracing::debug!("Cannot write {} ({}), using the data directory", path.display(), e),
+            }
+        }
+
+        let dir = self.fallback_dir(media_id);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(&filename);
+        std::fs::write(&path, content)?;
+        Ok(StoredSubtitle { path, in_data_dir: true })
+    }
+
+    /// A subtitle written by [`SubtitleStore::write_as`] earlier, if any
+    pub fn existing(&self, media_id: i64, video_path: &Path, label: &str, extension: &str) -> Option<StoredSubtitle> {
+        let filename = Self::filename(video_path, label, extension).ok()?;
+        if let Some(path) = video_path.parent().map(|p| p.join(&filename)).filter(|p| p.is_file()) {
+            return Some(StoredSubtitle { path, in_data_dir: false });
+        }
+        let path = self.fallback_dir(media_id).join(&filename);
+        path.is_file().then_some(StoredSubtitle { path, in_data_dir: true })
+    }
+
+    fn filename(video_path: &Path, label: &str, extension: &str) -> Result<String, FilesystemError> {
+        let stem = video_path.file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| FilesystemError::InvalidPath(video_path.display().to_string()))?;
+        Ok(format!("{}.{}.{}", stem, label, extension))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_falls_back_to_data_dir() {
+        let root = std::env::temp_dir().join(format!("homeflix-substore-{}", uuid::Uuid::new_v4()));
+        let media_dir = root.join("media");
+        std::fs::create_dir_all(&media_dir).unwrap();
+        let store = SubtitleStore::new(root.join("data").to_str().unwrap());
+
+        let video = media_dir.join("Film.mkv");
+        let stored = store.write(7, &video, "hu", b"1\n00:00:01,000 --> 00:00:02,000\nSzia\n").unwrap();
+        assert_eq!(stored.path, media_dir.join("Film.hu.srt"));
+        assert!(!stored.in_data_dir);
+
+        // A video whose directory does not exist stands in for a read-only share
+        let unwritable = root.join("missing").join("Film.mkv");
+        let stored = store.write(7, &unwritable, "en", b"1\n00:00:01,000 --> 00:00:02,000\nHi\n").unwrap();
+        assert!(stored.in_data_dir);
+        assert_eq!(stored.path, store.fallback_dir(7).join("Film.en.srt"));
+        assert_eq!(store.existing(7, &unwritable, "en", "srt"), Some(stored));
+        assert!(store.existing(7, &unwritable, "en", "vtt").is_none());
+
+        let languages: Vec<Option<String>> = store.detector(7).discover(&video).into_iter().map(|s| s.language).collect();
+        assert_eq!(languages, vec![Some("en".to_string()), Some("hu".to_string())]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+// Artwork Mirror Interface
+//
+// This module defines interface for mirroring remote artwork (TMDB posters,
+// backdrops, episode stills) into local storage, so the library keeps its
+// artwork without internet access after the initial scan.
+
+use async_trait::async_trait;
+use crate::shared::error::TmdbError;
+
+/// Kind of artwork attached to a media item or series
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkKind {
+    Poster,
+    Backdrop,
+    /// Episode still (stored as the episode's poster)
+    Still,
+    /// Transparent title logo (fanart.tv)
+    Logo,
+    /// Transparent character art (fanart.tv)
+    ClearArt,
+    /// Disc art (fanart.tv)
+    Disc,
+}
+
+impl ArtworkKind {
+    /// Parses a kind name ("poster", "backdrop", "still", "logo", "clearart", "disc")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "poster" => Some(ArtworkKind::Poster),
+            "backdrop" => Some(ArtworkKind::Backdrop),
+            "still" => Some(ArtworkKind::Still),
+            "logo" | "clearlogo" => Some(ArtworkKind::Logo),
+            "clearart" => Some(ArtworkKind::ClearArt),
+            "disc" => Some(ArtworkKind::Disc),
+            _ => None,
+        }
+    }
+
+    /// TMDB size segment used for each size variant
+    pub fn tmdb_size(&self, size: ArtworkSize) -> &'static str {
+        match (self, size) {
+            (_, ArtworkSize::Original) => "original",
+            (ArtworkKind::Poster, ArtworkSize::Small) => "w185",
+            (ArtworkKind::Poster, ArtworkSize::Medium) => "w500",
+            (ArtworkKind::Poster, ArtworkSize::Large) => "w780",
+            (ArtworkKind::Backdrop, ArtworkSize::Small) => "w300",
+            (ArtworkKind::Backdrop, ArtworkSize::Medium) => "w780",
+            (ArtworkKind::Backdrop, ArtworkSize::Large) => "w1280",
+            (ArtworkKind::Still, ArtworkSize::Small) => "w185",
+            (ArtworkKind::Still, ArtworkSize::Medium) => "w300",
+            (ArtworkKind::Still, ArtworkSize::Large) => "w500",
+            // fanart.tv images come in a single size
+            (ArtworkKind::Logo | ArtworkKind::ClearArt | ArtworkKind::Disc, _) => "original",
+        }
+    }
+
+    /// Size variants mirrored during enrichment
+    ///
+    /// Originals are only fetched on demand since they can be several MB.
+    pub fn mirrored_sizes(&self) -> &'static [ArtworkSize] {
+        match self {
+            ArtworkKind::Poster | ArtworkKind::Backdrop | ArtworkKind::Still => {
+                &[ArtworkSize::Small, ArtworkSize::Medium, ArtworkSize::Large]
+            }
+            ArtworkKind::Logo | ArtworkKind::ClearArt | ArtworkKind::Disc => &[],
+        }
+    }
+}
+
+/// Artwork size variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkSize {
+    Small,
+    Medium,
+    Large,
+    Original,
+}
+
+impl ArtworkSize {
+    /// Parses a size name ("small", "medium", "large", "original")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "small" | "sm" => Some(ArtworkSize::Small),
+            "medium" | "md" => Some(ArtworkSize::Medium),
+            "large" | "lg" => Some(ArtworkSize::Large),
+            "original" => Some(ArtworkSize::Original),
+            _ => None,
+        }
+    }
+}
+
+/// Rewrites a TMDB image URL to another size variant
+///
+/// `https://image.tmdb.org/t/p/w500/abc.jpg` with `w185` becomes
+/// `https://image.tmdb.org/t/p/w185/abc.jpg`. Non-TMDB URLs are returned as is.
+pub fn tmdb_variant_url(url: &str, size: &str) -> String {
+    const PREFIX: &str = "https://image.tmdb.org/t/p/";
+    match url.strip_prefix(PREFIX).and_then(|rest| rest.split_once('/')) {
+        Some((_, path)) => format!("{}{}/{}", PREFIX, size, path),
+        None => url.to_string(),
+    }
+}
+
+/// Artwork mirror interface
+#[async_trait]
+pub trait ArtworkMirror: Send + Sync {
+    /// Gets image bytes from local storage, downloading them on first access
+    ///
+    /// # Arguments
+    /// * `url` - Remote image URL
+    ///
+    /// # Returns
+    /// * `Result<Option<Vec<u8>>, TmdbError>` - Image bytes, or None if the
+    ///   image does not exist upstream
+    async fn fetch(&self, url: &str) -> Result<Option<Vec<u8>>, TmdbError>;
+
+    /// Downloads all mirrored size variants of an artwork URL
+    ///
+    /// Best-effort: failures are logged, not returned.
+    ///
+    /// # Returns
+    /// * Number of variants now available locally
+    async fn mirror(&self, url: &str, kind: ArtworkKind) -> usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tmdb_variant_url() {
+        assert_eq!(
+            tmdb_variant_url("https://image.tmdb.org/t/p/w500/abc.jpg", "w185"),
+            "https://image.tmdb.org/t/p/w185/abc.jpg"
+        );
+        assert_eq!(
+            tmdb_variant_url("https://example.com/abc.jpg", "w185"),
+            "https://example.com/abc.jpg"
+        );
+    }
+}
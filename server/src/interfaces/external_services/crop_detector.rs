@@ -0,0 +1,26 @@
+// Crop Detector Interface
+//
+// This module defines interface for finding the black bars of letterboxed
+// or pillarboxed video. Typically implemented using FFmpeg's cropdetect
+// filter on a few samples of the video.
+
+use async_trait::async_trait;
+use crate::domain::repositories::CropDetection;
+use crate::shared::error::TranscodeError;
+
+/// Interface for black bar detection
+#[async_trait]
+pub trait CropDetector: Send + Sync {
+    /// Finds the picture area of a video of `duration_seconds` with frames
+    /// of `width`x`height`
+    ///
+    /// Samples are spread over the video, so dark scenes do not shrink the
+    /// result. Returns None when no sample could be analyzed.
+    async fn detect(
+        &self,
+        file_path: &str,
+        duration_seconds: f64,
+        width: u32,
+        height: u32,
+    ) -> Result<Option<CropDetection>, TranscodeError>;
+}
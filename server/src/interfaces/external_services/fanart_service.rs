@@ -0,0 +1,48 @@
+// fanart.tv Service Interface
+//
+// This module defines interface for fetching community artwork (logos,
+// clearart, disc art) from fanart.tv. Movies are keyed by TMDB ID, TV shows
+// by TVDB ID.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::FanartError;
+
+/// Best artwork picks for a movie or TV show
+///
+/// Each field holds the full image URL, or None when fanart.tv has nothing
+/// of that kind.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FanartArtwork {
+    /// Transparent title logo
+    pub clearlogo: Option<String>,
+    /// Transparent character/scene art
+    pub clearart: Option<String>,
+    /// Disc art (movies only)
+    pub disc: Option<String>,
+}
+
+impl FanartArtwork {
+    /// Whether no artwork was found
+    pub fn is_empty(&self) -> bool {
+        self.clearlogo.is_none() && self.clearart.is_none() && self.disc.is_none()
+    }
+}
+
+/// fanart.tv service interface
+#[async_trait]
+pub trait FanartService: Send + Sync {
+    /// Fetch artwork for a movie
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB movie ID
+    /// * `language` - Preferred language (ISO 639-1); falls back to English and textless art
+    async fn fetch_movie_artwork(&self, tmdb_id: i64, language: Option<&str>) -> Result<FanartArtwork, FanartError>;
+
+    /// Fetch artwork for a TV show
+    ///
+    /// # Arguments
+    /// * `tvdb_id` - TVDB series ID
+    /// * `language` - Preferred language (ISO 639-1); falls back to English and textless art
+    async fn fetch_tv_artwork(&self, tvdb_id: i64, language: Option<&str>) -> Result<FanartArtwork, FanartError>;
+}
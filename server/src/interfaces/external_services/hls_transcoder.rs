@@ -0,0 +1,111 @@
+// HLS Transcoder Interface
+//
+// This module defines interface for transcoding a media file into HLS
+// segments. Typically implemented using FFmpeg; the server builds the
+// playlists itself so players can seek anywhere before segments exist.
+
+use std::path::PathBuf;
+use tokio::process::Child;
+use crate::shared::error::TranscodeError;
+
+/// One rendition of an HLS stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HlsVariant {
+    /// Variant name used in URLs ("1080p", "720p", ...)
+    pub name: String,
+    /// Output frame height (None keeps the source height)
+    pub height: Option<u32>,
+    /// Target video bitrate in kbps
+    pub video_bitrate_kbps: u32,
+    /// Target audio bitrate in kbps
+    pub audio_bitrate_kbps: u32,
+}
+
+impl HlsVariant {
+    /// Builds the variant ladder for a source of `source_height` pixels
+    ///
+    /// Contains every standard rung at or below the source, so a 1080p file
+    /// yields 1080p, 720p and 480p. Sources below 480p get a single variant
+    /// at their own height.
+    pub fn ladder(source_height: u32) -> Vec<HlsVariant> {
+        const RUNGS: [(u32, u32); 4] = [(2160, 16000), (1080, 6000), (720, 3000), (480, 1200)];
+
+        let mut variants: Vec<HlsVariant> = RUNGS
+            .iter()
+            .filter(|(height, _)| *height <= source_height)
+            .map(|(height, bitrate)| HlsVariant {
+                name: format!("{}p", height),
+                height: Some(*height).filter(|h| *h < source_height),
+                video_bitrate_kbps: *bitrate,
+                audio_bitrate_kbps: 160,
+            })
+            .collect();
+
+        if variants.is_empty() {
+            variants.push(HlsVariant {
+                name: "source".to_string(),
+                height: None,
+                video_bitrate_kbps: 800,
+                audio_bitrate_kbps: 128,
+            });
+        }
+        variants
+    }
+
+    /// Peak bandwidth advertised in the master playlist (bits per second)
+    pub fn bandwidth(&self) -> u64 {
+        (self.video_bitrate_kbps as u64 + self.audio_bitrate_kbps as u64) * 1000
+    }
+}
+
+/// Request to transcode a file into HLS segments
+#[derive(Debug, Clone)]
+pub struct HlsTranscodeRequest {
+    /// Source media file
+    pub input_path: String,
+    /// Directory the segments are written to
+    pub output_dir: PathBuf,
+    /// Index of the first segment to produce (transcoding starts at
+    /// `start_segment * segment_seconds`)
+    pub start_segment: u32,
+    /// Segment length in seconds
+    pub segment_seconds: u32,
+    /// Audio track index
+    pub audio_track: u32,
+    /// Rendition to produce
+    pub variant: HlsVariant,
+}
+
+impl HlsTranscodeRequest {
+    /// File name of a segment (`seg_00042.ts`)
+    pub fn segment_file_name(index: u32) -> String {
+        format!("seg_{:05}.ts", index)
+    }
+}
+
+/// HLS transcoder interface
+pub trait HlsTranscoder: Send + Sync {
+    /// Starts transcoding in the background
+    ///
+    /// Segments appear in the output directory as they complete; the
+    /// returned process must be killed to stop early.
+    fn start_hls(&self, request: &HlsTranscodeRequest) -> Result<Child, TranscodeError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder() {
+        let names: Vec<String> = HlsVariant::ladder(1080).into_iter().map(|v| v.name).collect();
+        assert_eq!(names, vec!["1080p", "720p", "480p"]);
+
+        // The top rung keeps the source size, lower rungs are scaled
+        let ladder = HlsVariant::ladder(1080);
+        assert_eq!(ladder[0].height, None);
+        assert_eq!(ladder[1].height, Some(720));
+
+        assert_eq!(HlsVariant::ladder(360)[0].name, "source");
+    }
+}
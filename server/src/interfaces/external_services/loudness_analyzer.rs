@@ -0,0 +1,88 @@
+// Loudness Analyzer Interface
+//
+// This module defines interface for measuring the loudness of audio tracks,
+// the first pass of EBU R128 loudness normalization. Typically implemented
+// using FFmpeg's loudnorm filter.
+
+use async_trait::async_trait;
+use crate::domain::repositories::LoudnessMeasurement;
+use crate::shared::error::TranscodeError;
+
+/// Loudness streams are normalized to
+///
+/// The narrow loudness range keeps dialogue and explosions closer together,
+/// which is the point of normalizing for late-night viewing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessTarget {
+    /// Integrated loudness (LUFS)
+    pub integrated: f64,
+    /// Maximum true peak (dBTP)
+    pub true_peak: f64,
+    /// Loudness range (LU)
+    pub range: f64,
+}
+
+impl Default for LoudnessTarget {
+    fn default() -> Self {
+        Self {
+            integrated: -16.0,
+            true_peak: -1.5,
+            range: 7.0,
+        }
+    }
+}
+
+impl LoudnessTarget {
+    /// Builds the loudnorm filter for this target
+    ///
+    /// With a measurement the filter runs as the second pass (linear gain
+    /// where possible); without one it normalizes dynamically in a single
+    /// pass.
+    pub fn filter(&self, measured: Option<&LoudnessMeasurement>) -> String {
+        let mut filter = format!("loudnorm=I={}:TP={}:LRA={}", self.integrated, self.true_peak, self.range);
+        if let Some(m) = measured {
+            filter.push_str(&format!(
+                ":measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+                m.input_i, m.input_tp, m.input_lra, m.input_thresh, m.target_offset
+            ));
+        }
+        filter
+    }
+}
+
+/// Interface for loudness measurement
+#[async_trait]
+pub trait LoudnessAnalyzer: Send + Sync {
+    /// Measures an audio track of a media file against `target`
+    ///
+    /// Reads the whole track, so this takes a while for full movies.
+    async fn measure(
+        &self,
+        file_path: &str,
+        audio_track: usize,
+        target: &LoudnessTarget,
+    ) -> Result<LoudnessMeasurement, TranscodeError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_passes() {
+        let target = LoudnessTarget::default();
+        assert_eq!(target.filter(None), "loudnorm=I=-16:TP=-1.5:LRA=7");
+
+        let measured = LoudnessMeasurement {
+            input_i: -27.61,
+            input_tp: -4.47,
+            input_lra: 18.06,
+            input_thresh: -39.2,
+            target_offset: 0.58,
+        };
+        assert_eq!(
+            target.filter(Some(&measured)),
+            "loudnorm=I=-16:TP=-1.5:LRA=7:measured_I=-27.61:measured_TP=-4.47:measured_LRA=18.06:measured_thresh=-39.2:offset=0.58:linear=true"
+        );
+    }
+}
@@ -7,18 +7,48 @@
 // - tmdb_service: TMDB API interfaces (TmdbSearcher, TmdbFetcher, TmdbResolver)
 // - video_analyzer: FFprobe/FFmpeg video analysis interface
 // - thumbnail_generator: Thumbnail generation interface
+// - artwork_mirror: Local artwork mirroring interface
+// - fanart_service: fanart.tv artwork interface
+// - hls_transcoder: HLS segment transcoding interface
+// - loudness_analyzer: Audio loudness measurement interface
+// - crop_detector: Black bar detection interface
+// - preview_clip_generator: Hover preview clip interface
+// - subtitle_provider: Online subtitle search and download interface
+// - subtitle_extractor: Embedded subtitle extraction interface
+// - text_recognizer: OCR interface for bitmap subtitles
 
 pub mod tmdb_service;
 pub mod video_analyzer;
 pub mod thumbnail_generator;
+pub mod artwork_mirror;
+pub mod fanart_service;
+pub mod hls_transcoder;
+pub mod loudness_analyzer;
+pub mod crop_detector;
+pub mod preview_clip_generator;
+pub mod subtitle_provider;
+pub mod subtitle_extractor;
+pub mod text_recognizer;
 
 // Re-export all external service traits and types
 pub use tmdb_service::{
     TmdbSearcher, TmdbFetcher, TmdbResolver, TmdbService,
     TmdbContentRatingFetcher, TmdbSimilarFetcher, TmdbCreditsFetcher, TmdbReconciler,
+    TmdbLocalizedFetcher, LocalizedText, TmdbVideoFetcher, VideoInfo,
+    TmdbAlternativeTitlesFetcher, AlternativeTitle,
+    TmdbPersonFetcher, PersonDetail, PersonCreditInfo, TmdbChangesFetcher, TmdbExternalIdsFetcher,
     TmdbMatch, MovieDetail, TvDetail, SeasonDetail, EpisodeDetail,
     Genre, CollectionInfo, CollectionDetail, CollectionPartInfo, ContentRatingInfo, SimilarResult,
     Credits, CastMember, CrewMember,
 };
 pub use video_analyzer::{VideoAnalyzer, VideoAnalysis, AudioTrack, SubtitleTrack};
 pub use thumbnail_generator::{ThumbnailGenerator, ThumbnailOptions, ThumbnailResult};
+pub use artwork_mirror::{ArtworkMirror, ArtworkKind, ArtworkSize, tmdb_variant_url};
+pub use fanart_service::{FanartService, FanartArtwork};
+pub use hls_transcoder::{HlsTranscoder, HlsTranscodeRequest, HlsVariant};
+pub use loudness_analyzer::{LoudnessAnalyzer, LoudnessTarget};
+pub use crop_detector::CropDetector;
+pub use preview_clip_generator::PreviewClipGenerator;
+pub use subtitle_provider::{SubtitleProvider, SubtitleSearch, SubtitleCandidate};
+pub use subtitle_extractor::{SubtitleExtractor, SubtitleFormat};
+pub use text_recognizer::{TextRecognizer, SubtitleImage};
@@ -0,0 +1,22 @@
+// Preview Clip Generator Interface
+//
+// This module defines interface for cutting the short, muted clips clients
+// play as hover previews. Typically implemented using FFmpeg.
+
+use async_trait::async_trait;
+use std::path::Path;
+use crate::shared::error::TranscodeError;
+
+/// Interface for preview clip generation
+#[async_trait]
+pub trait PreviewClipGenerator: Send + Sync {
+    /// Encodes `duration_seconds` of video from `start_seconds` into a
+    /// small MP4 without audio at `output_path`
+    async fn generate_clip(
+        &self,
+        file_path: &str,
+        start_seconds: f64,
+        duration_seconds: f64,
+        output_path: &Path,
+    ) -> Result<(), TranscodeError>;
+}
@@ -0,0 +1,53 @@
+// Subtitle Extractor Interface
+//
+// This module defines interface for copying embedded subtitle tracks out of
+// a container into standalone files. Typically implemented using FFmpeg.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::TranscodeError;
+
+/// Format of an extracted subtitle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    #[default]
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    /// File extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+        }
+    }
+
+    /// FFmpeg muxer writing the format
+    pub fn ffmpeg_format(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "webvtt",
+        }
+    }
+}
+
+/// Interface for subtitle extraction
+#[async_trait]
+pub trait SubtitleExtractor: Send + Sync {
+    /// Extracts an embedded subtitle track converted to `format`
+    ///
+    /// `track` counts subtitle tracks only, as listed by the video analyzer.
+    /// Only text tracks can be converted; bitmap tracks (PGS, VobSub) fail.
+    async fn extract(
+        &self,
+        file_path: &str,
+        track: usize,
+        format: SubtitleFormat,
+    ) -> Result<Vec<u8>, TranscodeError>;
+
+    /// Copies a PGS bitmap subtitle track out as a .sup stream, for OCR
+    async fn extract_bitmap(&self, file_path: &str, track: usize) -> Result<Vec<u8>, TranscodeError>;
+}
@@ -0,0 +1,55 @@
+// Subtitle Provider Interface
+//
+// This module defines interface for searching and downloading subtitles from
+// an online subtitle database (OpenSubtitles). Searches match either the
+// exact file (by its OpenSubtitles hash) or the identified title.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::shared::error::SubtitleProviderError;
+
+/// Subtitle search parameters
+///
+/// Every set field narrows the search; a hash search usually sets only
+/// `moviehash` and `languages`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SubtitleSearch {
+    /// Languages to search (ISO 639-1), in order of preference
+    pub languages: Vec<String>,
+    /// OpenSubtitles hash of the video file
+    pub moviehash: Option<String>,
+    /// TMDB ID of the movie or episode
+    pub tmdb_id: Option<i64>,
+    /// TMDB ID of the series (episodes only)
+    pub parent_tmdb_id: Option<i64>,
+    pub season: Option<i32>,
+    pub episode: Option<i32>,
+    /// Title to search when no ID is known
+    pub query: Option<String>,
+    pub year: Option<i32>,
+}
+
+/// One subtitle file offered by the provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtitleCandidate {
+    /// Provider ID used to download the file
+    pub file_id: i64,
+    /// Language (ISO 639-1)
+    pub language: String,
+    /// Release name the subtitle was made for
+    pub release: Option<String>,
+    pub download_count: i64,
+    /// Whether the subtitle was made for this exact file
+    pub hash_match: bool,
+    pub hearing_impaired: bool,
+}
+
+/// Subtitle provider interface
+#[async_trait]
+pub trait SubtitleProvider: Send + Sync {
+    /// Searches subtitles
+    async fn search(&self, search: &SubtitleSearch) -> Result<Vec<SubtitleCandidate>, SubtitleProviderError>;
+
+    /// Downloads the content of a subtitle file
+    async fn download(&self, file_id: i64) -> Result<Vec<u8>, SubtitleProviderError>;
+}
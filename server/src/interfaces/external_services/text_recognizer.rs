@@ -0,0 +1,39 @@
+// Text Recognizer Interface
+//
+// This module defines interface for reading the text of bitmap subtitles
+// (PGS, as on Blu-ray discs). Typically implemented using Tesseract OCR.
+
+use async_trait::async_trait;
+use crate::shared::error::SubtitleError;
+
+/// A subtitle rendered as an 8-bit grayscale image, dark text on white
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtitleImage {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major pixels, `width * height` bytes
+    pub pixels: Vec<u8>,
+}
+
+impl SubtitleImage {
+    /// Encodes the image as binary PGM, which OCR tools read directly
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut pgm = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        pgm.extend_from_slice(&self.pixels);
+        pgm
+    }
+}
+
+/// Interface for optical character recognition of subtitle images
+#[async_trait]
+pub trait TextRecognizer: Send + Sync {
+    /// Reads the text of a subtitle image
+    ///
+    /// # Arguments
+    /// * `image` - The subtitle
+    /// * `language` - Language of the text (ISO 639-1), None for English
+    ///
+    /// # Returns
+    /// The lines of text, empty when nothing was recognized
+    async fn recognize(&self, image: &SubtitleImage, language: Option<&str>) -> Result<String, SubtitleError>;
+}
@@ -120,6 +120,8 @@ pub trait TmdbResolver: Send + Sync {
 /// Content rating fetcher interface
 ///
 /// Provides methods for fetching content ratings (certifications) from TMDB.
+/// Titles without a certification (or unknown to TMDB) yield an empty
+/// [`ContentRatingInfo`]; other failures are errors.
 #[async_trait]
 pub trait TmdbContentRatingFetcher: Send + Sync {
     /// Fetch content rating for a movie
@@ -157,6 +159,39 @@ pub trait TmdbSimilarFetcher: Send + Sync {
     async fn fetch_similar(&self, tmdb_id: i64, media_type: &str) -> Result<Vec<SimilarResult>, TmdbError>;
 }
 
+/// Video fetcher interface
+///
+/// Provides methods for fetching trailers, teasers and other extras.
+#[async_trait]
+pub trait TmdbVideoFetcher: Send + Sync {
+    /// Fetch videos (trailers, teasers, featurettes) for a movie or TV show
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB ID
+    /// * `media_type` - "movie" or "tv"
+    ///
+    /// # Returns
+    /// * `Result<Vec<VideoInfo>, TmdbError>` - List of videos (may be empty)
+    async fn fetch_videos(&self, tmdb_id: i64, media_type: &str) -> Result<Vec<VideoInfo>, TmdbError>;
+}
+
+/// Alternative titles fetcher interface
+///
+/// Provides the other titles a movie or TV show is known by (AKAs, titles of
+/// foreign releases), for matching files named after one of them.
+#[async_trait]
+pub trait TmdbAlternativeTitlesFetcher: Send + Sync {
+    /// Fetch alternative titles of a movie or TV show
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB ID
+    /// * `media_type` - "movie" or "tv"
+    ///
+    /// # Returns
+    /// * `Result<Vec<AlternativeTitle>, TmdbError>` - Alternative titles (may be empty)
+    async fn fetch_alternative_titles(&self, tmdb_id: i64, media_type: &str) -> Result<Vec<AlternativeTitle>, TmdbError>;
+}
+
 /// Credits fetcher interface
 ///
 /// Provides methods for fetching cast and crew information.
@@ -181,6 +216,78 @@ pub trait TmdbCreditsFetcher: Send + Sync {
     async fn fetch_tv_credits(&self, tmdb_id: i64) -> Result<Credits, TmdbError>;
 }
 
+/// Person fetcher interface
+///
+/// Provides methods for fetching people (cast and crew) and their filmography.
+#[async_trait]
+pub trait TmdbPersonFetcher: Send + Sync {
+    /// Fetch person details (biography, photo, dates)
+    ///
+    /// # Arguments
+    /// * `person_id` - TMDB person ID
+    ///
+    /// # Returns
+    /// * `Result<Option<PersonDetail>, TmdbError>` - Person details or None if not found
+    async fn fetch_person(&self, person_id: i64) -> Result<Option<PersonDetail>, TmdbError>;
+
+    /// Fetch combined movie and TV credits for a person
+    ///
+    /// # Arguments
+    /// * `person_id` - TMDB person ID
+    ///
+    /// # Returns
+    /// * `Result<Vec<PersonCreditInfo>, TmdbError>` - Cast and crew credits
+    async fn fetch_person_credits(&self, person_id: i64) -> Result<Vec<PersonCreditInfo>, TmdbError>;
+}
+
+/// Change-list interface for TMDB
+///
+/// Provides access to TMDB's change feeds so that metadata can be refreshed
+/// selectively instead of re-fetching the whole library.
+#[async_trait]
+pub trait TmdbChangesFetcher: Send + Sync {
+    /// Fetch IDs of items whose metadata changed in a date range
+    ///
+    /// TMDB only keeps change lists for the last 14 days; wider ranges are
+    /// clamped to that window.
+    ///
+    /// # Arguments
+    /// * `media_type` - "movie" or "tv"
+    /// * `start_date` - First day of the range (inclusive)
+    /// * `end_date` - Last day of the range (inclusive)
+    ///
+    /// # Returns
+    /// * `Result<Vec<i64>, TmdbError>` - TMDB IDs that changed upstream
+    async fn fetch_changed_ids(
+        &self,
+        media_type: &str,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Result<Vec<i64>, TmdbError>;
+
+    /// Drop cached responses for an item so the next fetch hits TMDB
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB ID
+    /// * `media_type` - "movie" or "tv" (TV also drops seasons and episodes)
+    async fn invalidate_cached(&self, tmdb_id: i64, media_type: &str) -> Result<(), TmdbError>;
+}
+
+/// External ID lookup for TMDB TV shows
+///
+/// Some artwork providers (fanart.tv) key TV shows by TVDB ID.
+#[async_trait]
+pub trait TmdbExternalIdsFetcher: Send + Sync {
+    /// Fetch the TVDB ID of a TV show
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB TV show ID
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>, TmdbError>` - TVDB ID or None if TMDB has none
+    async fn fetch_tvdb_id(&self, tmdb_id: i64) -> Result<Option<i64>, TmdbError>;
+}
+
 /// Reconciler interface for multi-strategy TMDB matching
 ///
 /// Provides advanced reconciliation with fuzzy matching and scoring.
@@ -218,16 +325,49 @@ pub trait TmdbReconciler: Send + Sync {
     ) -> Result<(Option<TmdbMatch>, Vec<TmdbMatch>), TmdbError>;
 }
 
+/// Localized metadata fetcher interface
+///
+/// Provides methods for fetching titles and overviews in a specific language,
+/// independent of the client's configured default language.
+#[async_trait]
+pub trait TmdbLocalizedFetcher: Send + Sync {
+    /// Fetch localized title and overview
+    ///
+    /// # Arguments
+    /// * `tmdb_id` - TMDB movie or TV show ID
+    /// * `media_type` - "movie" or "tv"
+    /// * `season` - Season number (episodes only)
+    /// * `episode` - Episode number (episodes only)
+    /// * `language` - ISO 639-1 language tag, optionally with region (e.g. "hu-HU")
+    ///
+    /// # Returns
+    /// * `Result<Option<LocalizedText>, TmdbError>` - Localized text or None if not found
+    async fn fetch_localized(
+        &self,
+        tmdb_id: i64,
+        media_type: &str,
+        season: Option<i32>,
+        episode: Option<i32>,
+        language: &str,
+    ) -> Result<Option<LocalizedText>, TmdbError>;
+}
+
 /// Combined TMDB service interface
 /// 
 /// Convenience trait that combines all TMDB interfaces for implementations
 /// that provide full TMDB functionality.
 #[async_trait]
-pub trait TmdbService: TmdbSearcher + TmdbFetcher + TmdbResolver + TmdbSimilarFetcher {}
+pub trait TmdbService:
+    TmdbSearcher + TmdbFetcher + TmdbResolver + TmdbSimilarFetcher + TmdbVideoFetcher + TmdbAlternativeTitlesFetcher
+{
+}
 
 // Blanket implementation for any type that implements all traits
 #[async_trait]
-impl<T> TmdbService for T where T: TmdbSearcher + TmdbFetcher + TmdbResolver + TmdbSimilarFetcher {}
+impl<T> TmdbService for T where
+    T: TmdbSearcher + TmdbFetcher + TmdbResolver + TmdbSimilarFetcher + TmdbVideoFetcher + TmdbAlternativeTitlesFetcher
+{
+}
 
 // ============================================================================
 // Types used by TMDB interfaces
@@ -248,6 +388,9 @@ pub struct TmdbMatch {
     pub confidence: ConfidenceScore,
     /// Match strategy used
     pub strategy: MatchStrategy,
+    /// Countries of origin of a show (ISO 3166 codes)
+    #[serde(default)]
+    pub origin_country: Vec<String>,
 }
 
 /// Detailed movie information
@@ -257,6 +400,9 @@ pub struct MovieDetail {
     pub id: i64,
     /// Movie title
     pub title: String,
+    /// Title in the original production language
+    #[serde(default)]
+    pub original_title: Option<String>,
     /// Plot overview
     pub overview: String,
     /// Release date
@@ -286,6 +432,9 @@ pub struct TvDetail {
     pub id: i64,
     /// TV show name
     pub name: String,
+    /// Name in the original production language
+    #[serde(default)]
+    pub original_name: Option<String>,
     /// Plot overview
     pub overview: String,
     /// First air date
@@ -363,6 +512,19 @@ pub struct EpisodeDetail {
     pub runtime: Option<i32>,
 }
 
+/// Localized title and overview for a single language
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LocalizedText {
+    /// Language tag the text was requested in
+    pub language: String,
+    /// Localized title (episode name for episodes)
+    pub title: String,
+    /// Localized overview (None when TMDB has no translation)
+    pub overview: Option<String>,
+    /// Title in the original production language (None for episodes)
+    pub original_title: Option<String>,
+}
+
 /// Genre information
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Genre {
@@ -448,6 +610,110 @@ impl ContentRatingInfo {
     }
 }
 
+/// Video (trailer, teaser, featurette, ...) attached to a movie or TV show
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VideoInfo {
+    /// Site-specific video key (YouTube video ID for YouTube)
+    pub key: String,
+    /// Video title
+    pub name: String,
+    /// Hosting site ("YouTube", "Vimeo")
+    pub site: String,
+    /// Video type ("Trailer", "Teaser", "Featurette", "Clip", ...)
+    pub video_type: String,
+    /// Whether the video is an official release
+    pub official: bool,
+    /// Language of the video (ISO 639-1)
+    pub language: Option<String>,
+    /// Publication timestamp (ISO 8601)
+    pub published_at: Option<String>,
+}
+
+impl VideoInfo {
+    /// Whether the video is hosted on YouTube
+    pub fn is_youtube(&self) -> bool {
+        self.site.eq_ignore_ascii_case("youtube")
+    }
+
+    /// Public watch URL for the video, if the site is supported
+    pub fn watch_url(&self) -> Option<String> {
+        match self.site.to_lowercase().as_str() {
+            "youtube" => Some(format!("https://www.youtube.com/watch?v={}", self.key)),
+            "vimeo" => Some(format!("https://vimeo.com/{}", self.key)),
+            _ => None,
+        }
+    }
+
+    /// Picks the best trailer: YouTube trailers before teasers, official first,
+    /// then the most recently published
+    pub fn best_trailer(videos: &[VideoInfo]) -> Option<&VideoInfo> {
+        videos
+            .iter()
+            .filter(|v| v.is_youtube() && (v.video_type == "Trailer" || v.video_type == "Teaser"))
+            .max_by(|a, b| {
+                (a.video_type == "Trailer", a.official, a.published_at.as_deref())
+                    .cmp(&(b.video_type == "Trailer", b.official, b.published_at.as_deref()))
+            })
+    }
+
+    /// Extracts a YouTube video key from a watch URL
+    pub fn youtube_key_from_url(url: &str) -> Option<&str> {
+        url.strip_prefix("https://www.youtube.com/watch?v=")
+            .or_else(|| url.strip_prefix("https://youtu.be/"))
+            .map(|key| key.split('&').next().unwrap_or(key))
+            .filter(|key| !key.is_empty())
+    }
+}
+
+/// Another title of a movie or TV show
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AlternativeTitle {
+    pub title: String,
+    /// Country the title is used in (ISO 3166-1)
+    pub country: Option<String>,
+    /// Kind of title ("working title", "informal title", ...)
+    pub kind: Option<String>,
+}
+
+/// Person details
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PersonDetail {
+    /// TMDB person ID
+    pub id: i64,
+    /// Name
+    pub name: String,
+    /// Biography
+    #[serde(default)]
+    pub biography: String,
+    /// Birthday (YYYY-MM-DD)
+    pub birthday: Option<String>,
+    /// Day of death (YYYY-MM-DD)
+    pub deathday: Option<String>,
+    /// Place of birth
+    pub place_of_birth: Option<String>,
+    /// Profile photo path (relative to TMDB base URL)
+    pub profile_path: Option<String>,
+    /// Main department ("Acting", "Directing", ...)
+    pub known_for_department: Option<String>,
+    /// IMDB ID
+    pub imdb_id: Option<String>,
+}
+
+/// A single movie or TV credit of a person
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PersonCreditInfo {
+    /// TMDB ID of the movie or TV show
+    pub tmdb_id: i64,
+    /// "movie" or "tv"
+    pub media_type: String,
+    /// Title or show name
+    pub title: String,
+    /// Character name (cast credits)
+    pub character: Option<String>,
+    /// Job (crew credits, e.g. "Director")
+    pub job: Option<String>,
+}
+
 /// Similar content result
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimilarResult {
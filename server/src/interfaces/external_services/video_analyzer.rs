@@ -61,6 +61,10 @@ pub struct AudioTrack {
     pub title: Option<String>,
     /// Whether this is the default audio track
     pub is_default: bool,
+    /// Whether the track narrates the picture for blind and visually
+    /// impaired viewers (audio description)
+    #[serde(default)]
+    pub is_audio_description: bool,
 }
 
 /// Subtitle track information
@@ -76,6 +80,29 @@ pub struct SubtitleTrack {
     pub title: Option<String>,
     /// Whether this is the default subtitle track
     pub is_default: bool,
+    /// Whether the track only covers foreign-language dialogue and signs
+    #[serde(default)]
+    pub is_forced: bool,
+    /// Whether the track is for the deaf and hard of hearing (SDH)
+    #[serde(default)]
+    pub is_hearing_impaired: bool,
+}
+
+impl SubtitleTrack {
+    /// Whether the track holds text that can be converted to SRT/VTT
+    ///
+    /// Bitmap tracks (PGS, VobSub, DVB) would need OCR instead.
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self.codec.as_deref(),
+            Some("subrip" | "srt" | "ass" | "ssa" | "webvtt" | "mov_text" | "text" | "microdvd" | "subviewer")
+        )
+    }
+
+    /// Whether the track is a Blu-ray PGS bitmap track, readable with OCR
+    pub fn is_pgs(&self) -> bool {
+        self.codec.as_deref() == Some("hdmv_pgs_subtitle")
+    }
 }
 
 /// Video analyzer interface
@@ -7,10 +7,21 @@
 // - Testing with in-memory filesystems
 // - Parallel traversal for performance
 // - Filtering based on file extensions, etc.
+// - Streaming, so large libraries are processed while they are walked
 
 use async_trait::async_trait;
+use futures::stream::{BoxStream, TryStreamExt};
 use crate::shared::error::FilesystemError;
 
+/// Entries of a walk as they are found
+///
+/// Errors (e.g. an unreadable directory) are yielded in place and the walk
+/// goes on; dropping the stream stops the walk.
+pub type WalkStream = BoxStream<'static, Result<WalkEntry, FilesystemError>>;
+
+/// Filter deciding which entries a walk yields
+pub type WalkFilter = Box<dyn for<'a> Fn(&'a WalkEntry) -> bool + Send + Sync>;
+
 /// Entry result from directory walk
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WalkEntry {
@@ -33,9 +44,22 @@ pub struct WalkEntry {
 /// Directory walker interface
 /// 
 /// Provides methods for traversing directory trees and finding files.
-/// Supports both sequential and parallel traversal.
+/// Supports both sequential and parallel traversal. Implementations stream
+/// entries; the methods returning a `Vec` collect the stream and fail on the
+/// first error.
 #[async_trait]
 pub trait DirectoryWalker: Send + Sync {
+    /// Walk a directory tree, streaming the entries the filter accepts
+    ///
+    /// # Arguments
+    /// * `root` - Root directory path to walk
+    /// * `filter` - Filter function that returns true for entries to include
+    ///
+    /// # Returns
+    /// * `WalkStream` - Entries in walk order; a missing root is yielded as
+    ///   an error
+    fn walk_stream(&self, root: &std::path::Path, filter: WalkFilter) -> WalkStream;
+
     /// Walk a directory tree and return all entries
     /// 
     /// # Arguments
@@ -49,7 +73,9 @@ pub trait DirectoryWalker: Send + Sync {
     /// - Root directory does not exist
     /// - Permission denied
     /// - IO error occurs
-    async fn walk(&self, root: &std::path::Path) -> Result<Vec<WalkEntry>, FilesystemError>;
+    async fn walk(&self, root: &std::path::Path) -> Result<Vec<WalkEntry>, FilesystemError> {
+        self.walk_stream(root, Box::new(|_| true)).try_collect().await
+    }
     
     /// Walk a directory tree with a filter function
     /// 
@@ -68,8 +94,10 @@ pub trait DirectoryWalker: Send + Sync {
     async fn walk_with_filter(
         &self,
         root: &std::path::Path,
-        filter: Box<dyn for<'a> Fn(&'a WalkEntry) -> bool + Send + Sync>,
-    ) -> Result<Vec<WalkEntry>, FilesystemError>;
+        filter: WalkFilter,
+    ) -> Result<Vec<WalkEntry>, FilesystemError> {
+        self.walk_stream(root, filter).try_collect().await
+    }
     
     /// Walk a directory tree in parallel
     /// 
@@ -91,7 +119,7 @@ pub trait DirectoryWalker: Send + Sync {
         max_depth: Option<usize>,
     ) -> Result<Vec<WalkEntry>, FilesystemError>;
     
-    /// Walk a directory tree and return only video files
+    /// Walk a directory tree, streaming only video files
     ///
     /// Filters out:
     /// - Non-video files
@@ -101,18 +129,11 @@ pub trait DirectoryWalker: Send + Sync {
     /// * `root` - Root directory path to walk
     ///
     /// # Returns
-    /// * `Result<Vec<WalkEntry>, FilesystemError>` - Video file entries
-    ///
-    /// # Errors
-    /// Returns error if:
-    /// - Root directory does not exist
-    /// - Permission denied
-    /// - IO error occurs
-    async fn walk_videos(&self, root: &std::path::Path) -> Result<Vec<WalkEntry>, FilesystemError> {
-        self.walk_with_filter(root, Box::new(|entry| {
+    /// * `WalkStream` - Video file entries as they are found
+    fn walk_videos(&self, root: &std::path::Path) -> WalkStream {
+        self.walk_stream(root, Box::new(|entry| {
             entry.is_file && is_video_file(&entry.path) && !is_sample_file(&entry.path)
         }))
-        .await
     }
     
     /// Walk a directory tree and return files matching extensions
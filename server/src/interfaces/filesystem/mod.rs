@@ -12,5 +12,5 @@ pub mod directory_walker;
 pub mod file_operations;
 
 // Re-export all filesystem traits
-pub use directory_walker::{DirectoryWalker, WalkEntry};
+pub use directory_walker::{DirectoryWalker, WalkEntry, WalkFilter, WalkStream};
 pub use file_operations::{FileOperations, FileMetadata};
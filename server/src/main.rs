@@ -4,21 +4,24 @@ mod interfaces;
 mod presentation;
 mod shared;
 mod infrastructure;
+mod config;
 
 use axum::http::{header, Method};
 use axum::{
-    extract::FromRef,
-    routing::{get, post, delete},
+    extract::{DefaultBodyLimit, FromRef},
+    routing::{any, get, post, put, patch, delete},
     Router,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_http::cors::{AllowOrigin, CorsLayer};
-use tracing::{info, warn, Level};
-use tracing_subscriber::FmtSubscriber;
+use tracing::{info, warn};
 
+use crate::config::Config;
 use crate::infrastructure::database::{ConnectionPool, ConnectionPoolConfig, initialize_schema};
+use crate::infrastructure::database::migrations::{self, MigrationState};
 use crate::shared::di::{ServiceRegistry, ServiceLifetime};
+use crate::shared::error::{ApplicationError, RepositoryError};
 
 // Type alias for backward compatibility during migration
 pub type DbPool = sqlx::Pool<sqlx::Sqlite>;
@@ -26,41 +29,76 @@ pub type DbPool = sqlx::Pool<sqlx::Sqlite>;
 // Imports for DI
 use crate::infrastructure::persistence::sqlite::{
     SqliteMediaRepository, SqliteSeriesRepository, SqliteCollectionRepository, SqliteCacheRepository,
-    SqliteCreditsRepository,
+    SqliteCreditsRepository, SqliteLocalizationRepository, SqlitePersonRepository, SqliteArtworkRepository,
+    SqliteQualityPreferenceRepository, SqliteSubtitleOffsetRepository, SqliteBookmarkRepository, SqliteAudioPreferenceRepository, SqliteJobHistoryRepository, SqliteJobQueueRepository,
+    SqliteLoudnessRepository, SqliteCropRepository, SqliteDeviceKeyRepository, SqliteStreamTokenRepository,
+    SqliteGeneratedSubtitleRepository, SqliteSubtitlePreferenceRepository, SqliteAccessibilityPreferenceRepository,
+    SqliteParentalControlRepository, SqliteProfileRepository, SqliteAuditLogRepository, SqlitePlaylistRepository, SqliteWatchHistoryRepository,
+    SqliteEmbeddingRepository, SqliteDialogueRepository, SqliteWebhookRepository,
+    SqliteNotificationChannelRepository, SqliteSettingsRepository, SqliteTmdbResponseRepository, SqliteScheduledTaskRepository,
 };
-use crate::infrastructure::external::tmdb::TmdbClient;
-use crate::infrastructure::external::ffmpeg::FFprobeAdapter;
-use crate::infrastructure::external::{WhisperAdapter, OllamaClient, FpcalcAdapter};
+use crate::infrastructure::persistence::notifying::{
+    NotifyingMediaRepository, NotifyingSeriesRepository, NotifyingCollectionRepository,
+};
+use crate::infrastructure::external::tmdb::{BatchTmdbClient, TmdbClient};
+use crate::infrastructure::external::{FanartClient, OpenSubtitlesClient, TesseractAdapter};
+use crate::infrastructure::external::ffmpeg::{FFprobeAdapter, FFmpegAdapter};
+use crate::infrastructure::external::{WhisperAdapter, WhisperModelManager, VadConfig, OllamaClient, OllamaEmbedder, DeepLClient, LibreTranslateClient, SubtitleTranslator, FallbackTranslator, FpcalcAdapter};
+#[cfg(feature = "whisper-rs")]
+use crate::infrastructure::external::NativeBackend;
 use crate::infrastructure::gpu::GpuCoordinator;
 use crate::infrastructure::jobs::JobStore;
-use crate::infrastructure::filesystem::WalkDirAdapter;
+use crate::infrastructure::filesystem::{CollectionPosterStore, WalkDirAdapter};
 use crate::infrastructure::messaging::{InMemoryEventBus, PersistentEventBus};
 use crate::infrastructure::event_sourcing::{event_store::EventStore, sqlite_event_persistence::SqliteEventPersistence};
-use crate::infrastructure::cache::ImageCache;
+use crate::infrastructure::cache::{ImageCache, InMemoryCache, LocalArtworkMirror, MultiLevelCache, TranscodeCache, TranscriptionCache};
+use crate::infrastructure::subtitle::SubtitleStore;
 use crate::domain::services::{DefaultIdentificationService, DefaultConfidenceService, TmdbCrossValidatorImpl};
 use crate::application::{
-    ScanLibraryUseCase, IdentifyMediaUseCase, StreamMediaUseCase, ManageSeriesUseCase
+    ScanLibraryUseCase, IdentifyMediaUseCase, StreamMediaUseCase, ManageSeriesUseCase,
+    MetadataEnricher, PlaybackQos, CollectionManager,
 };
 use crate::application::use_cases::get_recently_added::GetRecentlyAddedUseCase;
+use crate::application::use_cases::batch_watch_state::BatchWatchStateUseCase;
 use crate::application::use_cases::generate_subtitle::GenerateSubtitleUseCase;
 use crate::application::use_cases::batch_generate_subtitles::BatchGenerateSubtitlesUseCase;
+use crate::application::use_cases::library_health::LibraryHealthUseCase;
+use crate::application::use_cases::admin_dashboard::AdminDashboardUseCase;
+use crate::application::use_cases::explain_identification::ExplainIdentificationUseCase;
+use crate::application::use_cases::organize_media::OrganizeMediaUseCase;
+use crate::application::use_cases::remap_media_paths::RemapMediaPathsUseCase;
+use crate::application::use_cases::subtitle_coverage::SubtitleCoverageUseCase;
+use crate::application::use_cases::download_subtitle::DownloadSubtitleUseCase;
+use crate::application::use_cases::extract_subtitle::ExtractSubtitleUseCase;
+use crate::application::use_cases::edit_subtitle::EditSubtitleUseCase;
 use crate::application::handlers::{
     MediaIdentifiedHandler, ScanCompletedHandler, CollectionDetectedHandler,
     CacheInvalidationHandler, NotificationHandler, MetricsHandler,
     SubtitleGenerationHandler, ProgressTrackingHandler, StreamingHandler,
     CollectionManagementHandler, ThumbnailGenerationHandler, BackgroundTaskHandler,
+    LiveEventHandler, AuditLogHandler, WatchHistoryHandler, SearchSuggestionsHandler, DialogueIndexHandler, WebhookHandler,
 };
+use crate::application::services::{BootstrapTracker, LiveEvent, LiveEventBroadcaster, TmdbChangeMonitor, FanartEnricher, BlurhashBackfill, HlsSessionManager, PlaybackDecisionService, StreamSessionRegistry, LoudnessNormalizer, CropDetectionService, PreviewClipService, StreamUrlSigner, ApiKeyService, ContentRatingBackfill, ParentalControlService, RecommendationService, PresetService, MediaFilterService, SearchSuggestions, SemanticSearch, DialogueSearch, WebhookService, NotificationService, SettingsService, TaskDefinition, TaskScheduler};
 use crate::interfaces::messaging::EventBus;
 use crate::presentation::http::handlers::{
     media_handlers, series_handlers, streaming_handlers,
     collection_handlers, progress_handlers, search_handlers, proxy_handlers,
-    subtitle_generation_handlers, health_handlers,
+    subtitle_generation_handlers, health_handlers, people_handlers, events_handlers,
+    admin_handlers, hls_handlers, session_handlers, stats_handlers, cast_handlers,
+    subtitle_download_handlers, subtitle_extraction_handlers, subtitle_editing_handlers, bookmark_handlers, preview_handlers,
+    auth_handlers, parental_control_handlers, profile_handlers, audit_handlers, playlist_handlers, recommendation_handlers,
+    preset_handlers, webhook_handlers, notification_handlers, settings_handlers, task_handlers,
 };
-use crate::presentation::http::middleware::{auth, cors, logging};
+use crate::presentation::http::middleware::{auth, compression, conditional, cors, logging, stream_token};
+use crate::presentation::http::{problem, tls};
+use crate::presentation::dlna::{self, DlnaServer};
+use crate::infrastructure::health::ReadinessProbe;
+use crate::infrastructure::logging::LogLevelHandle;
+use crate::infrastructure::slow_operations::SlowOperationTracker;
 
 // Import repository traits for handlers
-use crate::domain::repositories::{MediaRepository, SeriesRepository, CollectionRepository, CreditsRepository};
-use crate::interfaces::external_services::{VideoAnalyzer, TmdbService, TmdbCreditsFetcher};
+use crate::domain::repositories::{MediaRepository, SeriesRepository, CollectionRepository, CreditsRepository, LocalizationRepository, PersonRepository, ArtworkRepository, QualityPreferenceRepository, SubtitleOffsetRepository, SubtitlePreferenceRepository, BookmarkRepository, AccessibilityPreferenceRepository, ProfileRepository, AuditLogRepository, PlaylistRepository, WatchHistoryRepository, TmdbResponseRepository, CacheRepository};
+use crate::interfaces::external_services::{VideoAnalyzer, TmdbService, TmdbCreditsFetcher, TmdbPersonFetcher, TmdbSimilarFetcher, ArtworkMirror};
 
 /// Application state containing DI registry and core services
 #[derive(Clone)]
@@ -72,46 +110,103 @@ struct AppState {
     series_repo: Arc<dyn SeriesRepository>,
     collection_repo: Arc<dyn CollectionRepository>,
     credits_repo: Arc<dyn CreditsRepository>,
+    localization_repo: Arc<dyn LocalizationRepository>,
+    person_repo: Arc<dyn PersonRepository>,
+    artwork_repo: Arc<dyn ArtworkRepository>,
+    quality_preference_repo: Arc<dyn QualityPreferenceRepository>,
+    subtitle_offset_repo: Arc<dyn SubtitleOffsetRepository>,
+    bookmark_repo: Arc<dyn BookmarkRepository>,
+    subtitle_preference_repo: Arc<dyn SubtitlePreferenceRepository>,
+    accessibility_preference_repo: Arc<dyn AccessibilityPreferenceRepository>,
+    profile_repo: Arc<dyn ProfileRepository>,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    playlist_repo: Arc<dyn PlaylistRepository>,
+    watch_history_repo: Arc<dyn WatchHistoryRepository>,
     // External Services
     video_analyzer: Arc<dyn VideoAnalyzer>,
     tmdb_service: Arc<dyn TmdbService>,
     tmdb_credits: Arc<dyn TmdbCreditsFetcher + Send + Sync>,
+    tmdb_people: Arc<dyn TmdbPersonFetcher>,
     // Cache
     image_cache: Arc<ImageCache>,
+    artwork_mirror: Arc<dyn ArtworkMirror>,
+    subtitle_store: Arc<SubtitleStore>,
+    collection_posters: Arc<CollectionPosterStore>,
     // Use Cases
     scan_use_case: Arc<ScanLibraryUseCase<InMemoryEventBus>>,
     identify_use_case: Arc<IdentifyMediaUseCase<InMemoryEventBus>>,
     stream_use_case: Arc<StreamMediaUseCase>,
     manage_series_use_case: Arc<ManageSeriesUseCase>,
     recently_added_use_case: Arc<GetRecentlyAddedUseCase>,
+    batch_watch_state_use_case: Arc<BatchWatchStateUseCase>,
+    library_health_use_case: Arc<LibraryHealthUseCase>,
+    admin_dashboard_use_case: Arc<AdminDashboardUseCase>,
+    explain_identification_use_case: Arc<ExplainIdentificationUseCase>,
+    organize_media_use_case: Arc<OrganizeMediaUseCase>,
+    remap_media_paths_use_case: Arc<RemapMediaPathsUseCase>,
+    subtitle_coverage_use_case: Arc<SubtitleCoverageUseCase>,
     generate_subtitle_use_case: Arc<GenerateSubtitleUseCase<InMemoryEventBus>>,
     batch_generate_subtitles_use_case: Arc<BatchGenerateSubtitlesUseCase>,
+    download_subtitle_use_case: Arc<DownloadSubtitleUseCase>,
+    extract_subtitle_use_case: Arc<ExtractSubtitleUseCase>,
+    edit_subtitle_use_case: Arc<EditSubtitleUseCase>,
+    // Services
+    metadata_enricher: Arc<MetadataEnricher>,
+    collection_manager: Arc<CollectionManager>,
+    playback_qos: Arc<PlaybackQos>,
+    hls_sessions: Arc<HlsSessionManager>,
+    playback_decision: Arc<PlaybackDecisionService>,
+    stream_sessions: Arc<StreamSessionRegistry>,
+    loudness: Arc<LoudnessNormalizer>,
+    crop_detection: Arc<CropDetectionService>,
+    preview_clips: Arc<PreviewClipService>,
+    dlna: Arc<DlnaServer>,
+    stream_signer: Arc<StreamUrlSigner>,
+    api_keys: Arc<ApiKeyService>,
+    parental_controls: Arc<ParentalControlService>,
+    /// Records audit events and prunes the audit log
+    audit_log: Arc<AuditLogHandler>,
+    readiness: Arc<ReadinessProbe>,
+    log_levels: Arc<LogLevelHandle>,
+    slow_operations: Arc<SlowOperationTracker>,
+    bootstrap: Arc<BootstrapTracker>,
+    live_events: Arc<LiveEventBroadcaster>,
+    tmdb_change_monitor: Arc<TmdbChangeMonitor>,
+    /// None when FANART_API_KEY is not set
+    fanart_enricher: Option<Arc<FanartEnricher>>,
+    blurhash_backfill: Arc<BlurhashBackfill>,
+    content_rating_backfill: Arc<ContentRatingBackfill>,
+    recommendations: Arc<RecommendationService>,
+    presets: Arc<PresetService>,
+    media_filters: Arc<MediaFilterService>,
+    search_suggestions: Arc<SearchSuggestions>,
+    /// None when EMBEDDING_MODEL is not set
+    semantic_search: Option<Arc<SemanticSearch>>,
+    dialogue_search: Arc<DialogueSearch>,
+    webhooks: Arc<WebhookService>,
+    notifications: Arc<NotificationService>,
+    settings: Arc<SettingsService>,
+    scheduler: Arc<TaskScheduler>,
     // Job Management
     job_store: Arc<JobStore>,
+    whisper_models: Arc<WhisperModelManager>,
     // Event Bus (for handlers that need it)
     event_bus: Arc<InMemoryEventBus>,
 }
 
 impl AppState {
     /// Create new application state with DI registry
-    async fn new(pool: DbPool, config: &Config) -> anyhow::Result<Self> {
+    async fn new(
+        pool: DbPool,
+        config: &Config,
+        log_levels: Arc<LogLevelHandle>,
+        slow_operations: Arc<SlowOperationTracker>,
+    ) -> anyhow::Result<Self> {
         let mut registry = ServiceRegistry::new();
 
         // Register database pool
         registry.register(pool.clone(), ServiceLifetime::Singleton);
 
-        // Repositories
-        let media_repo = Arc::new(SqliteMediaRepository::new(pool.clone()));
-        let series_repo = Arc::new(SqliteSeriesRepository::new(pool.clone()));
-        let collection_repo = Arc::new(SqliteCollectionRepository::new(pool.clone()));
-        let cache_repo = Arc::new(SqliteCacheRepository::new(pool.clone()));
-        let credits_repo = Arc::new(SqliteCreditsRepository::new(pool.clone()));
-
-        // External Services
-        let tmdb_client = Arc::new(TmdbClient::new(&config.tmdb_api_key, cache_repo.clone())?);
-        let video_analyzer = Arc::new(FFprobeAdapter::new(std::time::Duration::from_secs(10)));
-        let directory_walker = Arc::new(WalkDirAdapter::new());
-        
         // Event Bus Setup (with event sourcing)
         // Initialize event store for persistence
         let event_persistence = Arc::new(SqliteEventPersistence::new(Arc::new(pool.clone())));
@@ -119,7 +214,7 @@ impl AppState {
         
         // Create persistent event bus wrapper
         let inner_event_bus = Arc::new(InMemoryEventBus::new());
-        let persistent_event_bus = Arc::new(PersistentEventBus::new(inner_event_bus.clone(), event_store));
+        let persistent_event_bus = Arc::new(PersistentEventBus::new(inner_event_bus.clone(), event_store.clone()));
         
         // For backward compatibility, use cases still use InMemoryEventBus type
         // But we intercept publishes through a custom wrapper or modify InMemoryEventBus
@@ -129,26 +224,201 @@ impl AppState {
         
         info!("Event bus initialized (event sourcing infrastructure ready)");
 
+        // Repositories
+        // Library writes publish LibraryChangedEvent for derived read models
+        let media_repo = Arc::new(NotifyingMediaRepository::new(
+            Arc::new(SqliteMediaRepository::new(pool.clone())),
+            event_bus.clone(),
+        ));
+        let series_repo = Arc::new(NotifyingSeriesRepository::new(
+            Arc::new(SqliteSeriesRepository::new(pool.clone())),
+            event_bus.clone(),
+        ));
+        let collection_repo = Arc::new(NotifyingCollectionRepository::new(
+            Arc::new(SqliteCollectionRepository::new(pool.clone())),
+            event_bus.clone(),
+        ));
+        // Metadata cache, with recently used entries also kept in memory
+        let cache_repo: Arc<dyn CacheRepository> = {
+            let db_cache = Arc::new(SqliteCacheRepository::new(pool.clone()));
+            if config.cache.memory_cache_mb > 0 {
+                let memory_cache = InMemoryCache::new(100_000, std::time::Duration::from_secs(3600))
+                    .with_max_bytes((config.cache.memory_cache_mb * 1024 * 1024) as usize);
+                Arc::new(MultiLevelCache::new(Arc::new(memory_cache), db_cache))
+            } else {
+                db_cache
+            }
+        };
+        let credits_repo = Arc::new(SqliteCreditsRepository::new(pool.clone()));
+        let localization_repo = Arc::new(SqliteLocalizationRepository::new(pool.clone()));
+        let person_repo = Arc::new(SqlitePersonRepository::new(pool.clone()));
+        let artwork_repo = Arc::new(SqliteArtworkRepository::new(pool.clone()));
+        let quality_preference_repo = Arc::new(SqliteQualityPreferenceRepository::new(pool.clone()));
+        let subtitle_offset_repo = Arc::new(SqliteSubtitleOffsetRepository::new(pool.clone()));
+        let bookmark_repo = Arc::new(SqliteBookmarkRepository::new(pool.clone()));
+        let audio_preference_repo = Arc::new(SqliteAudioPreferenceRepository::new(pool.clone()));
+        let generated_subtitle_repo = Arc::new(SqliteGeneratedSubtitleRepository::new(pool.clone()));
+        let subtitle_preference_repo = Arc::new(SqliteSubtitlePreferenceRepository::new(pool.clone()));
+        let accessibility_preference_repo = Arc::new(SqliteAccessibilityPreferenceRepository::new(pool.clone()));
+        let profile_repo = Arc::new(SqliteProfileRepository::new(pool.clone()));
+        let audit_log_repo = Arc::new(SqliteAuditLogRepository::new(pool.clone()));
+        let playlist_repo = Arc::new(SqlitePlaylistRepository::new(pool.clone()));
+        let watch_history_repo = Arc::new(SqliteWatchHistoryRepository::new(pool.clone()));
+        // Downloaded subtitles of read-only media go to the data directory
+        let subtitle_store = Arc::new(SubtitleStore::new(&config.data_dir()));
+        let collection_posters = Arc::new(CollectionPosterStore::new(&config.data_dir()));
+
+        // Runtime settings (changes stored through the admin API override the configuration)
+        let settings = Arc::new(
+            SettingsService::load(Arc::new(SqliteSettingsRepository::new(pool.clone())), config.runtime_settings()).await?,
+        );
+        let runtime_settings = settings.current();
+
+        // External Services (offline servers answer TMDB requests from imported responses)
+        let tmdb_responses = Arc::new(SqliteTmdbResponseRepository::new(pool.clone()));
+        let tmdb_client = if config.metadata.tmdb_offline {
+            info!("TMDB offline: {} recorded responses", tmdb_responses.count().await?);
+            TmdbClient::offline(cache_repo.clone(), tmdb_responses)?
+        } else {
+            TmdbClient::new(&runtime_settings.tmdb_api_key, cache_repo.clone())?
+                .with_response_store(tmdb_responses)
+        };
+        let tmdb_client = Arc::new(
+            tmdb_client
+                .with_language(config.metadata.tmdb_language.clone())
+                .with_rate_limit(config.metadata.tmdb_requests_per_second)
+        );
+        if let Some(language) = tmdb_client.language() {
+            info!("TMDB metadata language: {}", language);
+        }
+        let video_analyzer = Arc::new(FFprobeAdapter::new(std::time::Duration::from_secs(10)));
+        let directory_walker = Arc::new(WalkDirAdapter::new());
+        
+
         // Domain Services
-        let identification_service = Arc::new(DefaultIdentificationService::new());
+        let mut identification_service = DefaultIdentificationService::new()
+            .with_default_profile(config.library.parser_profile);
+        for (folder, profile) in &config.library.parser_profiles {
+            info!("Parser profile for {}: {}", folder, profile.name());
+            identification_service = identification_service
+                .with_library_profile(std::path::Path::new(&config.library.media_dir).join(folder), *profile);
+        }
+        let identification_service = Arc::new(identification_service);
         let confidence_service = Arc::new(DefaultConfidenceService::new());
         let tmdb_cross_validator = Arc::new(TmdbCrossValidatorImpl::new(tmdb_client.clone()));
 
-        // Use Cases
-        let scan_use_case = Arc::new(
-            ScanLibraryUseCase::new(
+        // Playback QoS (holds back library scans and preview clips while streams are active)
+        let playback_qos = Arc::new(
+            PlaybackQos::new(config.transcoding.playback_qos)
+                .with_throttle_delay(std::time::Duration::from_millis(config.transcoding.playback_qos_throttle_ms))
+        );
+        info!("Playback QoS mode: {}", config.transcoding.playback_qos.as_str());
+
+        // Transcode cache (finished HLS segments kept across sessions)
+        let transcode_cache = if config.cache.transcode_cache_max_mb > 0 {
+            match TranscodeCache::new(
+                std::path::Path::new(&config.data_dir()).join("transcode-cache"),
+                config.cache.transcode_cache_max_mb * 1024 * 1024,
+            ) {
+                Ok(cache) => Some(Arc::new(cache)),
+                Err(e) => {
+                    warn!("Transcode cache disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // HLS sessions (segments live under the data directory until idle)
+        let mut hls_sessions = HlsSessionManager::new(
+            Arc::new(FFmpegAdapter::default()),
+            std::path::Path::new(&config.data_dir()).join("hls"),
+        )
+        .with_idle_timeout(std::time::Duration::from_secs(config.transcoding.hls_idle_timeout_secs));
+        if let Some(cache) = &transcode_cache {
+            hls_sessions = hls_sessions.with_cache(cache.clone());
+        }
+        let hls_sessions = Arc::new(hls_sessions);
+        if let Err(e) = hls_sessions.clear_stale() {
+            warn!("Failed to clear HLS segment directory: {}", e);
+        }
+        let playback_decision = Arc::new(PlaybackDecisionService::new(video_analyzer.clone()));
+        let stream_sessions = Arc::new(
+            StreamSessionRegistry::new(event_bus.clone())
+                .with_limits(runtime_settings.max_streams, runtime_settings.max_transcodes),
+        );
+        let loudness = Arc::new(LoudnessNormalizer::new(
+            Arc::new(FFmpegAdapter::default()),
+            Arc::new(SqliteLoudnessRepository::new(pool.clone())),
+        ));
+        let crop_detection = Arc::new(
+            CropDetectionService::new(
+                Arc::new(FFmpegAdapter::default()),
+                video_analyzer.clone(),
+                Arc::new(SqliteCropRepository::new(pool.clone())),
+            )
+            .with_auto_detect(config.transcoding.crop_detection),
+        );
+        let preview_clips = Arc::new(
+            PreviewClipService::new(
+                Arc::new(FFmpegAdapter::default()),
+                video_analyzer.clone(),
                 media_repo.clone(),
-                series_repo.clone(),
-                collection_repo.clone(),
-                directory_walker.clone(),
-                event_bus.clone(),
-                identification_service.clone(),
-                confidence_service.clone(),
+                std::path::Path::new(&config.data_dir()).join("previews"),
             )
-            .with_tmdb_service(tmdb_client.clone())
-            .with_tmdb_cross_validator(tmdb_cross_validator)
-            .with_video_analyzer(video_analyzer.clone())
+            .with_playback_qos(playback_qos.clone()),
+        );
+
+        // First-run bootstrap tracking and live client events
+        let first_run = media_repo.count().await.unwrap_or(0) == 0;
+        if first_run {
+            info!("Empty library detected, API will be served while the initial scan runs");
+        }
+        let bootstrap = Arc::new(BootstrapTracker::new(first_run));
+        let live_events = Arc::new(LiveEventBroadcaster::default().with_event_store(event_store.clone()));
+        let scan_progress_callback = {
+            let bootstrap = bootstrap.clone();
+            let live_events = live_events.clone();
+            Arc::new(move |progress: crate::application::use_cases::scan_library::ScanProgress| {
+                bootstrap.update_progress(&progress);
+                live_events.publish(LiveEvent::new("scan_progress", &progress));
+            })
+        };
+
+        // Initialize Image Cache
+        let image_cache = Arc::new(
+            ImageCache::new(&config.data_dir())
+                .map_err(|e| anyhow::anyhow!("Failed to initialize image cache: {}", e))?
         );
+        info!("Image cache initialized at: {:?}", image_cache.cache_dir());
+
+        // Artwork mirror (downloads TMDB artwork into the image cache)
+        let artwork_mirror: Arc<dyn ArtworkMirror> = Arc::new(LocalArtworkMirror::new(image_cache.clone()));
+        if config.library.artwork_mirror {
+            info!("Artwork mirroring enabled");
+        }
+
+        // Use Cases
+        let mut scan_use_case = ScanLibraryUseCase::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            collection_repo.clone(),
+            directory_walker.clone(),
+            event_bus.clone(),
+            identification_service.clone(),
+            confidence_service.clone(),
+        )
+        .with_tmdb_service(tmdb_client.clone())
+        .with_batch_tmdb_client(Arc::new(BatchTmdbClient::with_defaults(tmdb_client.clone())))
+        .with_tmdb_cross_validator(tmdb_cross_validator)
+        .with_video_analyzer(video_analyzer.clone())
+        .with_playback_qos(playback_qos.clone())
+        .with_progress_callback(scan_progress_callback);
+        if config.library.artwork_mirror {
+            scan_use_case = scan_use_case.with_artwork_mirror(artwork_mirror.clone());
+        }
+        let scan_use_case = Arc::new(scan_use_case);
 
         let identify_use_case = Arc::new(IdentifyMediaUseCase::new(
             media_repo.clone(),
@@ -156,13 +426,16 @@ impl AppState {
             event_bus.clone(),
         ));
 
-        let stream_use_case = Arc::new(StreamMediaUseCase::new(
-            media_repo.clone(),
-            video_analyzer.clone(),
-        ));
+        let stream_use_case = Arc::new(
+            StreamMediaUseCase::new(media_repo.clone(), video_analyzer.clone())
+                .with_audio_preferences(audio_preference_repo.clone())
+                .with_accessibility_preferences(accessibility_preference_repo.clone())
+                .with_profiles(profile_repo.clone()),
+        );
 
         let manage_series_use_case = Arc::new(ManageSeriesUseCase::new(
             series_repo.clone(),
+            media_repo.clone(),
         ));
 
         let recently_added_use_case = Arc::new(GetRecentlyAddedUseCase::new(
@@ -170,41 +443,300 @@ impl AppState {
             series_repo.clone(),
         ));
 
+        let batch_watch_state_use_case = Arc::new(BatchWatchStateUseCase::new(
+            media_repo.clone(),
+            collection_repo.clone(),
+        ));
+
+        let collection_manager = Arc::new(CollectionManager::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            collection_repo.clone(),
+            tmdb_client.clone(),
+            event_bus.clone(),
+        ));
+
+        let library_health_use_case = Arc::new(
+            LibraryHealthUseCase::new(media_repo.clone(), series_repo.clone(), identification_service.clone())
+                .with_subtitle_store(subtitle_store.clone()),
+        );
+        let remap_media_paths_use_case = Arc::new(RemapMediaPathsUseCase::new(media_repo.clone()));
+        let explain_identification_use_case = Arc::new(ExplainIdentificationUseCase::new(
+            media_repo.clone(),
+            identification_service.clone(),
+            confidence_service.clone(),
+        ));
+        let organize_media_use_case = Arc::new(OrganizeMediaUseCase::new(media_repo.clone(), series_repo.clone()));
+
+        let subtitle_coverage_use_case = Arc::new(
+            SubtitleCoverageUseCase::new(
+                media_repo.clone(),
+                series_repo.clone(),
+                generated_subtitle_repo.clone(),
+                video_analyzer.clone(),
+            )
+            .with_default_languages(config.subtitles.languages.clone())
+            .with_subtitle_store(subtitle_store.clone()),
+        );
+
+        let mut metadata_enricher = MetadataEnricher::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            collection_repo.clone(),
+            tmdb_client.clone(),
+        )
+        .with_localization(
+            tmdb_client.clone(),
+            localization_repo.clone(),
+            config.metadata.tmdb_language.clone(),
+        );
+        if config.library.artwork_mirror {
+            metadata_enricher = metadata_enricher.with_artwork_mirror(artwork_mirror.clone());
+        }
+        let metadata_enricher = Arc::new(metadata_enricher);
+
+        let tmdb_change_monitor = Arc::new(TmdbChangeMonitor::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            cache_repo.clone(),
+            tmdb_client.clone(),
+            metadata_enricher.clone(),
+        ));
+
+        // fanart.tv artwork (logos, clearart, disc art) is optional
+        let fanart_enricher = match config.metadata.fanart_api_key.as_deref() {
+            Some(api_key) => {
+                let fanart_client = Arc::new(FanartClient::new(api_key, cache_repo.clone()));
+                let mut fanart_enricher = FanartEnricher::new(
+                    media_repo.clone(),
+                    series_repo.clone(),
+                    artwork_repo.clone(),
+                    fanart_client,
+                    tmdb_client.clone(),
+                    config.metadata.tmdb_language.clone(),
+                );
+                if config.library.artwork_mirror {
+                    fanart_enricher = fanart_enricher.with_artwork_mirror(artwork_mirror.clone());
+                }
+                info!("fanart.tv artwork enabled");
+                Some(Arc::new(fanart_enricher))
+            }
+            None => None,
+        };
+
+        let blurhash_backfill = Arc::new(BlurhashBackfill::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            artwork_mirror.clone(),
+        ));
+
+        let content_rating_backfill = Arc::new(ContentRatingBackfill::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            tmdb_client.clone(),
+        ));
+
+        let recommendations = Arc::new(RecommendationService::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            watch_history_repo.clone(),
+            tmdb_client.clone(),
+            cache_repo.clone(),
+        ));
+        let presets = Arc::new(PresetService::new(
+            std::path::Path::new(&config.data_dir()).join("presets"),
+            collection_manager.clone(),
+            tmdb_client.clone(),
+        ));
+        let media_filters = Arc::new(MediaFilterService::new(&config.library.media_dir));
+        let search_suggestions = Arc::new(SearchSuggestions::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            collection_repo.clone(),
+            credits_repo.clone(),
+            watch_history_repo.clone(),
+        ));
+        let dialogue_search = Arc::new(DialogueSearch::new(
+            media_repo.clone(),
+            Arc::new(SqliteDialogueRepository::new(pool.clone())),
+            subtitle_store.clone(),
+        ));
+        let webhooks = Arc::new(WebhookService::new(Arc::new(SqliteWebhookRepository::new(pool.clone()))));
+        let notifications = Arc::new(NotificationService::new(Arc::new(SqliteNotificationChannelRepository::new(pool.clone()))));
+        notifications.set_enabled(runtime_settings.notifications_enabled);
+
+        // Apply changed runtime settings to the running services
+        {
+            let mut changes = settings.subscribe();
+            let tmdb_client = tmdb_client.clone();
+            let stream_sessions = stream_sessions.clone();
+            let notifications = notifications.clone();
+            tokio::spawn(async move {
+                while changes.changed().await.is_ok() {
+                    let current = changes.borrow_and_update().clone();
+                    tmdb_client.set_api_key(&current.tmdb_api_key);
+                    stream_sessions.set_limits(current.max_streams, current.max_transcodes);
+                    notifications.set_enabled(current.notifications_enabled);
+                    info!("Runtime settings applied");
+                }
+            });
+        }
+
         // Subtitle Generation Services
         let gpu_coordinator = Arc::new(GpuCoordinator::new());
-        let job_store = Arc::new(JobStore::new());
+        // Finished jobs are recorded to estimate completion times; jobs are
+        // stored to outlive restarts
+        let job_store = Arc::new(
+            JobStore::new()
+                .with_history(Arc::new(SqliteJobHistoryRepository::new(pool.clone())))
+                .with_queue(Arc::new(SqliteJobQueueRepository::new(pool.clone()))),
+        );
+        let mut admin_dashboard = AdminDashboardUseCase::new(media_repo.clone(), job_store.clone())
+            .with_metadata_cache(cache_repo.clone())
+            .with_image_cache(image_cache.clone())
+            .with_event_store(event_store.clone())
+            .with_tmdb_client(tmdb_client.clone());
+        if let Some(cache) = &transcode_cache {
+            admin_dashboard = admin_dashboard.with_transcode_cache(cache.clone());
+        }
+        let admin_dashboard_use_case = Arc::new(admin_dashboard);
         let fpcalc_adapter = Arc::new(FpcalcAdapter::new(
             std::time::Duration::from_secs(120),
         ));
 
-        // Whisper adapter (optional - depends on environment)
-        let whisper_model_path = std::env::var("WHISPER_MODEL_PATH")
-            .unwrap_or_else(|_| "/app/models/ggml-small.bin".to_string());
-        let whisper_cli_path = std::env::var("WHISPER_CLI_PATH")
-            .unwrap_or_else(|_| "whisper-cli".to_string());
-        let whisper_adapter = Arc::new(WhisperAdapter::with_cli_path(
-            std::path::PathBuf::from(&whisper_model_path),
-            whisper_cli_path,
+        // Whisper adapter (optional - depends on the installed models)
+        let whisper = &config.whisper;
+        let whisper_vad = whisper.vad.map(|detector| VadConfig::default().with_detector(detector));
+        // Downloaded models and the per-language selection; the model path
+        // stays the model used when none is selected
+        let whisper_models_dir = whisper
+            .models_dir
+            .as_ref()
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| {
+                std::path::Path::new(&whisper.model_path)
+                    .parent()
+                    .map(|p| p.to_path_buf())
+                    .unwrap_or_else(|| std::path::PathBuf::from("."))
+            });
+        let mut whisper_models = WhisperModelManager::new(
+            whisper_models_dir,
+            std::path::PathBuf::from(&whisper.model_path),
+            &config.data_dir(),
+        );
+        if let Some(url) = &whisper.models_url {
+            whisper_models = whisper_models.with_base_url(url);
+        }
+        let whisper_models = Arc::new(whisper_models);
+        let whisper_adapter = WhisperAdapter::with_cli_path(
+            std::path::PathBuf::from(&whisper.model_path),
+            whisper.cli_path.clone(),
             std::time::Duration::from_secs(3600), // 1 hour timeout for long videos
-        ));
-
-        // Ollama client (optional - for translation)
-        let ollama_url = std::env::var("OLLAMA_URL")
-            .unwrap_or_else(|_| "http://localhost:11434".to_string());
-        let ollama_model = std::env::var("OLLAMA_MODEL")
-            .unwrap_or_else(|_| "gemma3:4b".to_string());
-        let ollama_client = Some(Arc::new(OllamaClient::new(&ollama_url, &ollama_model)));
+        ).with_vad(whisper_vad.clone())
+        .with_models(whisper_models.clone());
+        // Native runs whisper.cpp in-process (validation rejects it in
+        // builds without the whisper-rs feature)
+        #[cfg(feature = "whisper-rs")]
+        let whisper_adapter = match whisper.backend {
+            crate::config::WhisperBackend::Native => whisper_adapter.with_backend(Arc::new(NativeBackend::new())),
+            crate::config::WhisperBackend::Cli => whisper_adapter,
+        };
+        info!("Whisper backend: {}", whisper_adapter.backend_name());
+        let whisper_adapter = Arc::new(whisper_adapter);
+
+        // Translation providers, tried in the order listed
+        let ollama = &config.ollama;
+        let translation = &config.translation;
+        let mut translators: Vec<Arc<dyn SubtitleTranslator>> = Vec::new();
+        for provider in &translation.providers {
+            match provider.to_lowercase().as_str() {
+                "ollama" => {
+                    let mut client = OllamaClient::new(&ollama.url, &ollama.model);
+                    if let Some(lines) = ollama.context_lines {
+                        client = client.with_context_lines(lines);
+                    }
+                    if let Some(limit) = ollama.requests_per_minute {
+                        client = client.with_rate_limit(limit);
+                    }
+                    translators.push(Arc::new(client));
+                }
+                "deepl" => {
+                    let deepl = &translation.deepl;
+                    let mut client = DeepLClient::new(deepl.api_key.as_deref().unwrap_or_default());
+                    if let Some(url) = &deepl.api_url {
+                        client = client.with_base_url(url);
+                    }
+                    if let Some(formality) = &deepl.formality {
+                        client = client.with_formality(formality);
+                    }
+                    if let Some(limit) = deepl.requests_per_minute {
+                        client = client.with_rate_limit(limit);
+                    }
+                    translators.push(Arc::new(client));
+                }
+                "libretranslate" => {
+                    let libretranslate = &translation.libretranslate;
+                    let mut client = LibreTranslateClient::new(libretranslate.url.as_deref().unwrap_or_default());
+                    if let Some(api_key) = &libretranslate.api_key {
+                        client = client.with_api_key(api_key);
+                    }
+                    if let Some(limit) = libretranslate.requests_per_minute {
+                        client = client.with_rate_limit(limit);
+                    }
+                    translators.push(Arc::new(client));
+                }
+                // Unknown providers fail validation
+                _ => {}
+            }
+        }
+        let translator: Option<Arc<dyn SubtitleTranslator>> = match translators.len() {
+            0 => None,
+            1 => translators.pop(),
+            _ => Some(Arc::new(FallbackTranslator::new(translators))),
+        };
+
+        // Semantic search embeds overviews with an Ollama embedding model
+        let semantic_search = match &ollama.embedding_model {
+            Some(model) => {
+                info!("Semantic search enabled (embedding model: {})", model);
+                Some(Arc::new(
+                    SemanticSearch::new(
+                        media_repo.clone(),
+                        Arc::new(SqliteEmbeddingRepository::new(pool.clone())),
+                        Arc::new(OllamaEmbedder::new(&ollama.url, model)),
+                    )
+                    .with_gpu_coordinator(gpu_coordinator.clone()),
+                ))
+            }
+            None => None,
+        };
+
+        // Whisper transcriptions are kept so further languages only translate
+        let transcription_cache = match TranscriptionCache::new(&config.data_dir()) {
+            Ok(cache) => Some(Arc::new(cache)),
+            Err(e) => {
+                warn!("Transcription cache disabled: {}", e);
+                None
+            }
+        };
 
         // Generate Subtitle Use Case
-        let generate_subtitle_use_case = Arc::new(GenerateSubtitleUseCase::new(
+        let mut generate_subtitle_use_case = GenerateSubtitleUseCase::new(
             media_repo.clone(),
             whisper_adapter.clone(),
-            ollama_client.clone(),
+            translator.clone(),
             fpcalc_adapter.clone(),
             gpu_coordinator.clone(),
             job_store.clone(),
             event_bus.clone(),
-        ));
+        )
+        .with_generated_subtitles(generated_subtitle_repo.clone())
+        .with_subtitle_store(subtitle_store.clone())
+        .with_vad(whisper_vad);
+        if let Some(cache) = &transcription_cache {
+            generate_subtitle_use_case = generate_subtitle_use_case.with_transcription_cache(cache.clone());
+        }
+        let generate_subtitle_use_case = Arc::new(generate_subtitle_use_case);
 
         // Batch Generate Subtitles Use Case
         let batch_generate_subtitles_use_case = Arc::new(BatchGenerateSubtitlesUseCase::new(
@@ -215,8 +747,48 @@ impl AppState {
         ));
 
         info!(
-            "Subtitle generation initialized: whisper_model={}, ollama_url={}",
-            whisper_model_path, ollama_url
+            "Subtitle generation initialized: whisper_model={}, translation={:?}",
+            whisper.model_path,
+            translator.as_ref().map(|t| t.providers()).unwrap_or_default()
+        );
+
+        // OpenSubtitles downloads are optional; generation is the fallback
+        let mut download_subtitle_use_case = DownloadSubtitleUseCase::new(
+            media_repo.clone(),
+            series_repo.clone(),
+            subtitle_store.clone(),
+        )
+        .with_preferences(subtitle_preference_repo.clone())
+        .with_profiles(profile_repo.clone())
+        .with_default_languages(config.subtitles.languages.clone());
+        if let Some(api_key) = config.subtitles.opensubtitles_api_key.as_deref() {
+            let mut client = OpenSubtitlesClient::new(api_key, cache_repo.clone());
+            if let (Some(username), Some(password)) = (&config.subtitles.opensubtitles_username, &config.subtitles.opensubtitles_password) {
+                client = client.with_credentials(username, password);
+            }
+            download_subtitle_use_case = download_subtitle_use_case.with_provider(Arc::new(client));
+            info!("OpenSubtitles downloads enabled");
+        }
+        let download_subtitle_use_case = Arc::new(download_subtitle_use_case);
+        let mut extract_subtitle_use_case = ExtractSubtitleUseCase::new(
+            media_repo.clone(),
+            video_analyzer.clone(),
+            Arc::new(FFmpegAdapter::default()),
+            subtitle_store.clone(),
+            job_store.clone(),
+        );
+        // OCR for PGS subtitle tracks (optional - depends on tesseract being installed)
+        let tesseract_path = &config.subtitles.tesseract_path;
+        let tesseract = TesseractAdapter::new(tesseract_path);
+        if tesseract.is_available().await {
+            extract_subtitle_use_case = extract_subtitle_use_case.with_text_recognizer(Arc::new(tesseract));
+            info!("PGS subtitle OCR enabled ({})", tesseract_path);
+        }
+        let extract_subtitle_use_case = Arc::new(extract_subtitle_use_case);
+        let edit_subtitle_use_case = Arc::new(EditSubtitleUseCase::new(generated_subtitle_repo.clone()));
+
+        let audit_log = Arc::new(
+            AuditLogHandler::new(audit_log_repo.clone()).with_retention_days(config.server.audit_retention_days),
         );
 
         // Event Handlers - Create and subscribe to event bus
@@ -237,9 +809,6 @@ impl AppState {
             ));
             event_bus.subscribe(scan_completed_handler).await?;
 
-            let notification_handler = Arc::new(NotificationHandler::new());
-            event_bus.subscribe(notification_handler).await?;
-
             let metrics_handler_scan: Arc<dyn crate::interfaces::messaging::EventHandler<crate::domain::events::ScanCompletedEvent>> = Arc::new(MetricsHandler::new());
             event_bus.subscribe(metrics_handler_scan).await?;
 
@@ -250,11 +819,20 @@ impl AppState {
             ));
             event_bus.subscribe(collection_detected_handler).await?;
 
-            // MediaVerifiedEvent handler
-            let cache_invalidation_handler = Arc::new(CacheInvalidationHandler::new(
-                cache_repo.clone(),
-            ));
+            // LibraryChangedEvent handler (derived caches)
+            let mut cache_invalidation_handler = CacheInvalidationHandler::new(cache_repo.clone());
+            if let Some(cache) = &transcode_cache {
+                cache_invalidation_handler = cache_invalidation_handler.with_transcode_cache(cache.clone());
+            }
+            if let Some(cache) = &transcription_cache {
+                cache_invalidation_handler = cache_invalidation_handler.with_transcription_cache(cache.clone());
+            }
+            let cache_invalidation_handler = Arc::new(cache_invalidation_handler);
             event_bus.subscribe(cache_invalidation_handler).await?;
+            event_bus.subscribe(Arc::new(SearchSuggestionsHandler::new(search_suggestions.clone()))).await?;
+
+            let metrics_handler_verified: Arc<dyn crate::interfaces::messaging::EventHandler<crate::domain::events::MediaVerifiedEvent>> = Arc::new(MetricsHandler::new());
+            event_bus.subscribe(metrics_handler_verified).await?;
 
             // SubtitleGenerationEvent handlers
             let subtitle_generation_handler = Arc::new(SubtitleGenerationHandler::new());
@@ -264,6 +842,32 @@ impl AppState {
             event_bus.subscribe::<crate::domain::events::SubtitleGenerationFailedEvent>(
                 subtitle_generation_handler
             ).await?;
+            event_bus.subscribe(Arc::new(DialogueIndexHandler::new(dialogue_search.clone()))).await?;
+
+            // Webhooks (one handler for every event type they can subscribe to)
+            let webhook_handler = Arc::new(WebhookHandler::new(webhooks.clone()));
+            event_bus.subscribe::<crate::domain::events::MediaIdentifiedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::ScanCompletedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::ScanFailedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::CollectionDetectedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::SubtitleGenerationCompletedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::SubtitleGenerationFailedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::StreamStartedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::StreamEndedEvent>(webhook_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::MediaWatchedEvent>(webhook_handler).await?;
+
+            // Notification channels (one handler for every event type they can be routed)
+            let notification_handler = Arc::new(NotificationHandler::new(
+                notifications.clone(),
+                media_repo.clone(),
+                series_repo.clone(),
+            ));
+            event_bus.subscribe::<crate::domain::events::MediaIdentifiedEvent>(notification_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::ScanCompletedEvent>(notification_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::ScanFailedEvent>(notification_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::SubtitleGenerationCompletedEvent>(notification_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::SubtitleGenerationFailedEvent>(notification_handler.clone()).await?;
+            event_bus.subscribe::<crate::domain::events::StreamStartedEvent>(notification_handler).await?;
 
             // ProgressTrackingEvent handlers
             let progress_tracking_handler = Arc::new(ProgressTrackingHandler::new());
@@ -274,6 +878,9 @@ impl AppState {
                 progress_tracking_handler.clone()
             ).await?;
             event_bus.subscribe::<crate::domain::events::MediaUnwatchedEvent>(
+                progress_tracking_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::WatchStateBatchUpdatedEvent>(
                 progress_tracking_handler
             ).await?;
 
@@ -289,6 +896,18 @@ impl AppState {
                 streaming_handler
             ).await?;
 
+            // Playback history (watch statistics)
+            let watch_history_handler = Arc::new(WatchHistoryHandler::new(watch_history_repo.clone()));
+            event_bus.subscribe::<crate::domain::events::StreamEndedEvent>(
+                watch_history_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::ProgressUpdatedEvent>(
+                watch_history_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::MediaWatchedEvent>(
+                watch_history_handler
+            ).await?;
+
             // CollectionManagementEvent handlers
             let collection_management_handler = Arc::new(CollectionManagementHandler::new());
             event_bus.subscribe::<crate::domain::events::CollectionCreatedEvent>(
@@ -298,6 +917,12 @@ impl AppState {
                 collection_management_handler.clone()
             ).await?;
             event_bus.subscribe::<crate::domain::events::CollectionItemAddedEvent>(
+                collection_management_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::CollectionItemRemovedEvent>(
+                collection_management_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::CollectionDeletedEvent>(
                 collection_management_handler
             ).await?;
 
@@ -319,15 +944,74 @@ impl AppState {
                 background_task_handler
             ).await?;
 
+            // Live client events
+            let live_event_handler = Arc::new(LiveEventHandler::new(
+                media_repo.clone(),
+                live_events.clone(),
+            ));
+            event_bus.subscribe::<crate::domain::events::MediaIdentifiedEvent>(
+                live_event_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::ScanCompletedEvent>(
+                live_event_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::BackgroundScanStartedEvent>(
+                live_event_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::WatchStateBatchUpdatedEvent>(
+                live_event_handler.clone()
+            ).await?;
+            event_bus.subscribe::<crate::domain::events::LibraryChangedEvent>(
+                live_event_handler
+            ).await?;
+
+            // Audit log
+            event_bus.subscribe::<crate::domain::events::AuditEvent>(
+                audit_log.clone()
+            ).await?;
+
             info!("Event handlers registered successfully");
         }
 
-        // Initialize Image Cache
-        let image_cache = Arc::new(
-            ImageCache::new(&config.data_dir)
-                .map_err(|e| anyhow::anyhow!("Failed to initialize image cache: {}", e))?
+        let readiness = Arc::new(
+            ReadinessProbe::new(pool.clone(), config.library.media_dir.clone())
+                .with_optional_checks(config.server.readiness_optional.clone()),
         );
-        info!("Image cache initialized at: {:?}", image_cache.cache_dir());
+
+        // Stream URL signing, with a key kept in the data dir so links survive restarts
+        let cast_secret = config.auth.cast_secret.clone().or_else(|| {
+            let path = std::path::Path::new(&config.data_dir()).join("stream_signing.key");
+            StreamUrlSigner::load_or_create_secret(&path)
+                .map_err(|e| warn!("Could not persist the stream signing key, links break on restart: {}", e))
+                .ok()
+        });
+        let stream_signer = Arc::new(
+            StreamUrlSigner::new(cast_secret.as_deref(), std::time::Duration::from_secs(config.auth.cast_url_ttl_secs))
+                .with_session_ttl(std::time::Duration::from_secs(config.auth.stream_token_ttl_secs))
+                .with_required_session_tokens(config.auth.require_stream_tokens)
+                .with_repository(Arc::new(SqliteStreamTokenRepository::new(pool.clone()))),
+        );
+        // Without them revoked tokens would work again until they expire
+        let revoked = stream_signer.load_revoked().await
+            .map_err(|e| anyhow::anyhow!("Could not load revoked stream tokens: {}", e))?;
+        if revoked > 0 {
+            info!("{} revoked stream token session(s) still in force", revoked);
+        }
+
+        let api_keys = Arc::new(ApiKeyService::new(
+            Arc::new(SqliteDeviceKeyRepository::new(pool.clone())),
+            config.auth.api_secret.as_deref(),
+        ));
+        if !api_keys.is_enabled() {
+            info!("API_SECRET is not set, API requests are not authenticated");
+        }
+
+        let parental_controls = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            series_repo.clone(),
+        ).with_profiles(profile_repo.clone()));
+
+        let scheduler = Arc::new(TaskScheduler::new(Arc::new(SqliteScheduledTaskRepository::new(pool.clone()))));
 
         // Note: In a full DI implementation, we would register these in registry and resolve them.
         // For simplicity and compiler safety here, we construct them manually and store in AppState.
@@ -339,18 +1023,78 @@ impl AppState {
             series_repo,
             collection_repo,
             credits_repo,
+            localization_repo,
+            person_repo,
+            artwork_repo,
+            quality_preference_repo,
+            subtitle_offset_repo,
+            bookmark_repo,
+            subtitle_preference_repo,
+            accessibility_preference_repo,
+            profile_repo,
+            audit_log_repo,
+            playlist_repo,
+            watch_history_repo,
             video_analyzer,
             tmdb_service: tmdb_client.clone(),
-            tmdb_credits: tmdb_client,
+            tmdb_credits: tmdb_client.clone(),
+            tmdb_people: tmdb_client,
             image_cache,
+            artwork_mirror,
+            subtitle_store,
+            collection_posters,
             scan_use_case,
             identify_use_case,
             stream_use_case,
             manage_series_use_case,
             recently_added_use_case,
+            batch_watch_state_use_case,
+            library_health_use_case,
+            admin_dashboard_use_case,
+            explain_identification_use_case,
+            organize_media_use_case,
+            remap_media_paths_use_case,
+            subtitle_coverage_use_case,
             generate_subtitle_use_case,
             batch_generate_subtitles_use_case,
+            download_subtitle_use_case,
+            extract_subtitle_use_case,
+            edit_subtitle_use_case,
+            metadata_enricher,
+            collection_manager,
+            playback_qos,
+            hls_sessions,
+            playback_decision,
+            stream_sessions,
+            loudness,
+            crop_detection,
+            preview_clips,
+            dlna: Arc::new(DlnaServer::new(config.dlna.name.clone(), config.server.port)),
+            stream_signer,
+            api_keys,
+            parental_controls,
+            audit_log,
+            readiness,
+            log_levels,
+            slow_operations,
+            bootstrap,
+            live_events,
+            tmdb_change_monitor,
+            fanart_enricher,
+            blurhash_backfill,
+            content_rating_backfill,
+            recommendations,
+            presets,
+            media_filters,
+            search_suggestions,
+            semantic_search,
+            dialogue_search,
+            webhooks,
+            notifications,
+            settings,
+            scheduler,
             job_store,
+            whisper_models,
             event_bus: event_bus.clone(),
         })
     }
@@ -387,166 +1131,448 @@ impl FromRef<AppState> for Arc<dyn CreditsRepository> {
     }
 }
 
-impl FromRef<AppState> for Arc<ScanLibraryUseCase<InMemoryEventBus>> {
+impl FromRef<AppState> for Arc<dyn LocalizationRepository> {
     fn from_ref(state: &AppState) -> Self {
-        state.scan_use_case.clone()
+        state.localization_repo.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<IdentifyMediaUseCase<InMemoryEventBus>> {
+impl FromRef<AppState> for Arc<dyn PersonRepository> {
     fn from_ref(state: &AppState) -> Self {
-        state.identify_use_case.clone()
+        state.person_repo.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<StreamMediaUseCase> {
+impl FromRef<AppState> for Arc<dyn TmdbSimilarFetcher> {
     fn from_ref(state: &AppState) -> Self {
-        state.stream_use_case.clone()
+        state.tmdb_service.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<ManageSeriesUseCase> {
+impl FromRef<AppState> for Arc<dyn TmdbPersonFetcher> {
     fn from_ref(state: &AppState) -> Self {
-        state.manage_series_use_case.clone()
+        state.tmdb_people.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<dyn VideoAnalyzer> {
+impl FromRef<AppState> for Arc<MetadataEnricher> {
     fn from_ref(state: &AppState) -> Self {
-        state.video_analyzer.clone()
+        state.metadata_enricher.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<dyn TmdbService> {
+impl FromRef<AppState> for Arc<PlaybackQos> {
     fn from_ref(state: &AppState) -> Self {
-        state.tmdb_service.clone()
+        state.playback_qos.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<dyn TmdbCreditsFetcher + Send + Sync> {
+impl FromRef<AppState> for Arc<HlsSessionManager> {
     fn from_ref(state: &AppState) -> Self {
-        state.tmdb_credits.clone()
+        state.hls_sessions.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<GetRecentlyAddedUseCase> {
+impl FromRef<AppState> for Arc<PlaybackDecisionService> {
     fn from_ref(state: &AppState) -> Self {
-        state.recently_added_use_case.clone()
+        state.playback_decision.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<GenerateSubtitleUseCase<InMemoryEventBus>> {
+impl FromRef<AppState> for Arc<StreamSessionRegistry> {
     fn from_ref(state: &AppState) -> Self {
-        state.generate_subtitle_use_case.clone()
+        state.stream_sessions.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<BatchGenerateSubtitlesUseCase> {
+impl FromRef<AppState> for Arc<LoudnessNormalizer> {
     fn from_ref(state: &AppState) -> Self {
-        state.batch_generate_subtitles_use_case.clone()
+        state.loudness.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<JobStore> {
+impl FromRef<AppState> for Arc<CropDetectionService> {
     fn from_ref(state: &AppState) -> Self {
-        state.job_store.clone()
+        state.crop_detection.clone()
     }
 }
 
-impl FromRef<AppState> for Arc<ImageCache> {
+impl FromRef<AppState> for Arc<PreviewClipService> {
     fn from_ref(state: &AppState) -> Self {
-        state.image_cache.clone()
+        state.preview_clips.clone()
     }
 }
 
-impl FromRef<AppState> for Option<Arc<InMemoryEventBus>> {
+impl FromRef<AppState> for Arc<DlnaServer> {
     fn from_ref(state: &AppState) -> Self {
-        Some(state.event_bus.clone())
+        state.dlna.clone()
     }
 }
 
-struct Config {
-    database_url: String,
-    media_dir: String,
-    data_dir: String,
-    port: u16,
-    tmdb_api_key: String,
-    /// Interval between library scans in seconds (0 to disable)
-    scan_interval_secs: u64,
+impl FromRef<AppState> for Arc<RecommendationService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.recommendations.clone()
+    }
 }
 
-impl Config {
-    /// Extracts the data directory from DATABASE_URL
-    ///
-    /// Examples:
-    /// - `sqlite:data.db?mode=rwc` -> `./data` (or current dir)
-    /// - `sqlite:/data/data.db?mode=rwc` -> `/data`
-    /// - `sqlite:./data/data.db?mode=rwc` -> `./data`
-    fn extract_data_dir(database_url: &str) -> String {
-        // Remove sqlite: prefix
-        let path_part = database_url
-            .strip_prefix("sqlite:")
-            .unwrap_or(database_url)
-            .split('?')
-            .next()
-            .unwrap_or("");
+impl FromRef<AppState> for Arc<PresetService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.presets.clone()
+    }
+}
 
-        if path_part.is_empty() {
-            return "./data".to_string();
-        }
+impl FromRef<AppState> for Arc<SearchSuggestions> {
+    fn from_ref(state: &AppState) -> Self {
+        state.search_suggestions.clone()
+    }
+}
 
-        let db_path = std::path::Path::new(path_part);
-        
-        // If absolute path (starts with /), use parent directory
-        if db_path.is_absolute() {
-            if let Some(parent) = db_path.parent() {
-                return parent.to_string_lossy().to_string();
-            }
-            return "/data".to_string();
-        }
+impl FromRef<AppState> for Option<Arc<SemanticSearch>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.semantic_search.clone()
+    }
+}
 
-        // For relative paths, use parent directory or default to ./data
-        if let Some(parent) = db_path.parent() {
-            let parent_str = parent.to_string_lossy().to_string();
-            if parent_str.is_empty() || parent_str == "." {
-                return "./data".to_string();
-            }
-            return parent_str;
-        }
+impl FromRef<AppState> for Arc<DialogueSearch> {
+    fn from_ref(state: &AppState) -> Self {
+        state.dialogue_search.clone()
+    }
+}
 
-        "./data".to_string()
+impl FromRef<AppState> for Arc<WebhookService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.webhooks.clone()
     }
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    // Setup logging
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)?;
-
-    // Config
-    let database_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:data.db?mode=rwc".to_string());
-    let data_dir = Config::extract_data_dir(&database_url);
-    let config = Config {
-        database_url: database_url.clone(),
-        media_dir: std::env::var("MEDIA_DIR").expect("MEDIA_DIR must be set"),
-        data_dir: data_dir.clone(),
-        port: std::env::var("PORT").unwrap_or_else(|_| "3000".to_string()).parse()?,
-        tmdb_api_key: std::env::var("TMDB_API_KEY").unwrap_or_default(), // Should be required in prod
-        scan_interval_secs: std::env::var("SCAN_INTERVAL_SECS")
-            .unwrap_or_else(|_| "3600".to_string()) // Default: 1 hour
-            .parse()
-            .unwrap_or(3600),
-    };
-    
-    info!("Data directory: {}", config.data_dir);
+impl FromRef<AppState> for Arc<NotificationService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.notifications.clone()
+    }
+}
 
-    // Initialize presets directory
-    let presets_dir = std::path::Path::new(&config.data_dir).join("presets");
-    
-    // Try to find builtin presets - check multiple possible locations
+impl FromRef<AppState> for Arc<SettingsService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.settings.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<TaskScheduler> {
+    fn from_ref(state: &AppState) -> Self {
+        state.scheduler.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<MediaFilterService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.media_filters.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ParentalControlService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.parental_controls.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ApiKeyService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.api_keys.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<StreamUrlSigner> {
+    fn from_ref(state: &AppState) -> Self {
+        state.stream_signer.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ReadinessProbe> {
+    fn from_ref(state: &AppState) -> Self {
+        state.readiness.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LogLevelHandle> {
+    fn from_ref(state: &AppState) -> Self {
+        state.log_levels.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SlowOperationTracker> {
+    fn from_ref(state: &AppState) -> Self {
+        state.slow_operations.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn SubtitlePreferenceRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.subtitle_preference_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn AccessibilityPreferenceRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.accessibility_preference_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ProfileRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.profile_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn PlaylistRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.playlist_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn WatchHistoryRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.watch_history_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn AuditLogRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.audit_log_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SubtitleStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.subtitle_store.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<CollectionPosterStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.collection_posters.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<DownloadSubtitleUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.download_subtitle_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ExtractSubtitleUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.extract_subtitle_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EditSubtitleUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.edit_subtitle_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<BootstrapTracker> {
+    fn from_ref(state: &AppState) -> Self {
+        state.bootstrap.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LiveEventBroadcaster> {
+    fn from_ref(state: &AppState) -> Self {
+        state.live_events.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ScanLibraryUseCase<InMemoryEventBus>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.scan_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<IdentifyMediaUseCase<InMemoryEventBus>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.identify_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<StreamMediaUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.stream_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ManageSeriesUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.manage_series_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn VideoAnalyzer> {
+    fn from_ref(state: &AppState) -> Self {
+        state.video_analyzer.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn TmdbService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.tmdb_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn TmdbCreditsFetcher + Send + Sync> {
+    fn from_ref(state: &AppState) -> Self {
+        state.tmdb_credits.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<GetRecentlyAddedUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.recently_added_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<BatchWatchStateUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.batch_watch_state_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<GenerateSubtitleUseCase<InMemoryEventBus>> {
+    fn from_ref(state: &AppState) -> Self {
+        state.generate_subtitle_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<BatchGenerateSubtitlesUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.batch_generate_subtitles_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WhisperModelManager> {
+    fn from_ref(state: &AppState) -> Self {
+        state.whisper_models.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<JobStore> {
+    fn from_ref(state: &AppState) -> Self {
+        state.job_store.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ArtworkMirror> {
+    fn from_ref(state: &AppState) -> Self {
+        state.artwork_mirror.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn ArtworkRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.artwork_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn QualityPreferenceRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.quality_preference_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn SubtitleOffsetRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.subtitle_offset_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn BookmarkRepository> {
+    fn from_ref(state: &AppState) -> Self {
+        state.bookmark_repo.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<CollectionManager> {
+    fn from_ref(state: &AppState) -> Self {
+        state.collection_manager.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ExplainIdentificationUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.explain_identification_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<OrganizeMediaUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.organize_media_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LibraryHealthUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.library_health_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AdminDashboardUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.admin_dashboard_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<RemapMediaPathsUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.remap_media_paths_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SubtitleCoverageUseCase> {
+    fn from_ref(state: &AppState) -> Self {
+        state.subtitle_coverage_use_case.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ImageCache> {
+    fn from_ref(state: &AppState) -> Self {
+        state.image_cache.clone()
+    }
+}
+
+impl FromRef<AppState> for Option<Arc<InMemoryEventBus>> {
+    fn from_ref(state: &AppState) -> Self {
+        Some(state.event_bus.clone())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // Config file, environment overrides and validation
+    let mut config = Config::load()?;
+
+    // Setup logging (LOG_FORMAT=json for collectors, levels from RUST_LOG)
+    let slow_operations = Arc::new(SlowOperationTracker::new(
+        std::time::Duration::from_millis(config.server.slow_request_ms),
+        std::time::Duration::from_millis(config.server.slow_query_ms),
+    ));
+    let log_levels = Arc::new(crate::infrastructure::logging::init(
+        config.server.log_format,
+        std::env::var("RUST_LOG").ok().as_deref(),
+        slow_operations.clone(),
+    )?);
+    
+    if let Some(source) = &config.source {
+        info!("Config file: {}", source.display());
+    }
+    for warning in config.apply_legacy_intervals() {
+        warn!("{}", warning);
+    }
+    info!("Data directory: {}", config.data_dir());
+
+    // Initialize presets directory
+    let presets_dir = std::path::Path::new(&config.data_dir()).join("presets");
+    
+    // Try to find builtin presets - check multiple possible locations
     let builtin_presets_path = if std::path::Path::new("server/presets").exists() {
         std::path::Path::new("server/presets").to_path_buf()
     } else if std::path::Path::new("presets").exists() {
@@ -586,205 +1612,612 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Initialize Database with new infrastructure
-    let pool_config = ConnectionPoolConfig::new(config.database_url.clone());
+    let pool_config = ConnectionPoolConfig::new(config.server.database_url.clone())
+        .with_slow_query_threshold(config.server.slow_query_ms)
+        .with_page_cache(config.cache.db_page_cache_mb);
     let connection_pool = ConnectionPool::create(pool_config).await
         .map_err(|e| anyhow::anyhow!("Failed to create connection pool: {}", e))?;
     let pool = connection_pool.inner().clone();
 
     // Initialize database schema
+    if config.server.migrate_dry_run {
+        for migration in migrations::status(&pool).await? {
+            if migration.state != MigrationState::Applied {
+                info!("Pending migration {} ({}): {:?}", migration.version, migration.name, migration.state);
+            }
+        }
+        info!("MIGRATE_DRY_RUN is set, exiting without applying migrations");
+        return Ok(());
+    }
     initialize_schema(&pool).await?;
     info!("Database initialized with new infrastructure");
 
+    // `homeflixd export-metadata <file>` / `import-metadata <file>` move the
+    // recorded TMDB responses to and from an offline server
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, file] = args.as_slice() {
+        let responses = SqliteTmdbResponseRepository::new(pool.clone());
+        let file = std::path::Path::new(file);
+        match command.as_str() {
+            "export-metadata" => {
+                let exported = responses.export_to(file).await?;
+                info!("Exported {} TMDB responses to {}", exported, file.display());
+                return Ok(());
+            }
+            "import-metadata" => {
+                let imported = responses.import_from(file).await?;
+                info!("Imported {} TMDB responses from {}", imported, file.display());
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+    if let Some(command) = args.first() {
+        anyhow::bail!("Unknown command: {} (expected export-metadata <file> or import-metadata <file>)", command);
+    }
+
     // Initialize Application State
-    let state = AppState::new(pool.clone(), &config).await?;
-
-    // Start background scanner if interval > 0
-    if config.scan_interval_secs > 0 {
-        let scan_use_case = state.scan_use_case.clone();
-        let event_bus_for_collection = state.event_bus.clone();
-        let collection_manager = Arc::new(crate::application::services::CollectionManager::new(
-            state.media_repo.clone(),
-            state.series_repo.clone(),
-            state.collection_repo.clone(),
-            state.tmdb_service.clone(),
-            event_bus_for_collection,
-        ));
-        let media_dir = config.media_dir.clone();
-        let scan_interval = std::time::Duration::from_secs(config.scan_interval_secs);
-        let presets_dir_clone = presets_dir.clone();
+    let state = AppState::new(pool.clone(), &config, log_levels, slow_operations).await?;
+
+    // Pick up the jobs the last shutdown interrupted
+    subtitle_generation_handlers::resume_interrupted_jobs(
+        state.job_store.clone(),
+        state.generate_subtitle_use_case.clone(),
+        state.batch_generate_subtitles_use_case.clone(),
+        state.extract_subtitle_use_case.clone(),
+    ).await;
+
+    // Build the suggestion index before the first keystroke needs it
+    {
+        let suggestions = state.search_suggestions.clone();
+        tokio::spawn(async move {
+            if let Err(e) = suggestions.refresh().await {
+                warn!("Failed to build the suggestion index: {}", e);
+            }
+        });
+    }
 
-        info!(
-            "Background scanner enabled: scanning {} every {} seconds",
-            media_dir, config.scan_interval_secs
+    // Mirror artwork of items scanned before mirroring was enabled; scans
+    // mirror the artwork of the items they add or update themselves
+    {
+        let enricher = state.metadata_enricher.clone();
+        tokio::spawn(async move {
+            if let Err(e) = enricher.mirror_library_artwork().await {
+                warn!("Artwork mirroring failed: {}", e);
+            }
+        });
+    }
+
+    // Background tasks on cron schedules, changed through /v2/admin/tasks
+    {
+        let schedules = &config.scheduler;
+        let scan_state = state.clone();
+        let library = config.library.clone();
+        state.scheduler.register(
+            TaskDefinition::new("library_scan", "Scan the library and update collections, artwork and search indexes", schedules.library_scan.clone(), move || {
+                run_library_scan(scan_state.clone(), library.clone())
+            })
+            .with_enabled(config.library.scan_interval_secs != 0)
+            .with_startup_run(),
         );
 
-        let event_bus_for_background = state.event_bus.clone();
-        tokio::spawn(async move {
-            // Initial scan on startup (with small delay to let server start)
-            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-            info!("Running initial library scan at: {}", media_dir);
+        let offline = config.metadata.tmdb_offline;
+        let change_monitor = state.tmdb_change_monitor.clone();
+        let bootstrap = state.bootstrap.clone();
+        state.scheduler.register(
+            TaskDefinition::new("metadata_refresh", "Refresh titles changed on TMDB", schedules.metadata_refresh.clone(), move || {
+                let change_monitor = change_monitor.clone();
+                let bootstrap = bootstrap.clone();
+                async move {
+                    if offline {
+                        return Ok("Skipped: TMDB is offline".to_string());
+                    }
+                    // Freshly scanned items are already up to date
+                    if !bootstrap.is_complete() {
+                        return Ok("Skipped: the first library scan is not complete".to_string());
+                    }
+                    let stats = change_monitor.check_for_changes().await?;
+                    Ok(format!(
+                        "{} movies and {} series changed, {} refreshed, {} episodes updated",
+                        stats.movies_changed, stats.series_changed, stats.items_refreshed, stats.episodes_updated
+                    ))
+                }
+            })
+            .with_enabled(!offline && config.metadata.tmdb_changes_interval_secs > 0),
+        );
+
+        let preview_clips = state.preview_clips.clone();
+        state.scheduler.register(
+            TaskDefinition::new("preview_clips", "Make hover preview clips for new titles", schedules.preview_clips.clone(), move || {
+                let preview_clips = preview_clips.clone();
+                async move {
+                    let made = preview_clips.backfill_library().await?;
+                    Ok(format!("{} clips made", made))
+                }
+            })
+            .with_enabled(config.library.preview_clips),
+        );
 
-            // Publish background scan scheduled event
+        let recommendations = state.recommendations.clone();
+        state.scheduler.register(
+            TaskDefinition::new("recommendations_refresh", "Recompute the cached recommendation rows of every user", schedules.recommendations_refresh.clone(), move || {
+                let recommendations = recommendations.clone();
+                async move {
+                    let refreshed = recommendations.refresh_all().await?;
+                    Ok(format!("Recommendations of {} users refreshed", refreshed))
+                }
+            }),
+        );
+
+        let pool = state.pool.clone();
+        let audit_log = state.audit_log.clone();
+        let live_events = state.live_events.clone();
+        state.scheduler.register(
+            TaskDefinition::new("db_maintenance", "Prune the audit log and recorded events, then VACUUM and ANALYZE the database", schedules.db_maintenance.clone(), move || {
+                let pool = pool.clone();
+                let audit_log = audit_log.clone();
+                let live_events = live_events.clone();
+                async move {
+                    let audit_entries = audit_log.prune().await?;
+                    let events = live_events.prune().await?;
+                    crate::infrastructure::database::run_maintenance(&pool)
+                        .await
+                        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+                    Ok(format!("{} audit entries and {} events pruned", audit_entries, events))
+                }
+            }),
+        );
+
+        state.scheduler.start().await?;
+
+        // Clients follow first-run progress via /v2/bootstrap and /v2/events
+        let scan = state.scheduler.get("library_scan")?;
+        if scan.enabled {
             let scheduled_event = crate::domain::events::BackgroundScanScheduledEvent::new(
-                media_dir.clone(),
+                config.library.media_dir.clone(),
                 chrono::Utc::now(),
-                config.scan_interval_secs,
+                scan.schedule.to_string(),
             );
-            if let Err(e) = event_bus_for_background.publish(scheduled_event).await {
-                tracing::warn!("Failed to publish background scan scheduled event: {}", e);
+            if let Err(e) = state.event_bus.publish(scheduled_event).await {
+                warn!("Failed to publish background scan scheduled event: {}", e);
             }
+        } else {
+            info!("Background library scans disabled");
+            state.bootstrap.ready();
+        }
+    }
 
+    // Remove idle HLS sessions and their segments
+    {
+        let hls_sessions = state.hls_sessions.clone();
+        tokio::spawn(async move {
             loop {
-                // Publish background scan started event
-                let started_event = crate::domain::events::BackgroundScanStartedEvent::new(
-                    media_dir.clone(),
-                );
-                if let Err(e) = event_bus_for_background.publish(started_event).await {
-                    tracing::warn!("Failed to publish background scan started event: {}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                let removed = hls_sessions.cleanup_idle();
+                if removed > 0 {
+                    info!("Removed {} idle HLS sessions", removed);
                 }
+            }
+        });
+    }
 
-                match scan_use_case.execute(&media_dir).await {
-                    Ok(result) => {
-                        info!(
-                            "Library scan completed: {} files processed, {} identified, {} failed",
-                            result.processed_count, result.identified_count, result.failed_count
-                        );
-
-                        // Publish background task completed event
-                        let completed_event = crate::domain::events::BackgroundTaskCompletedEvent::new(
-                            "library_scan".to_string(),
-                            None,
-                            true,
-                            Some(format!("{} files processed, {} identified", result.processed_count, result.identified_count)),
-                        );
-                        if let Err(e) = event_bus_for_background.publish(completed_event).await {
-                            tracing::warn!("Failed to publish background task completed event: {}", e);
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Library scan failed: {}", e);
-
-                        // Publish background task completed event (failed)
-                        let completed_event = crate::domain::events::BackgroundTaskCompletedEvent::new(
-                            "library_scan".to_string(),
-                            None,
-                            false,
-                            Some(e.to_string()),
-                        );
-                        if let Err(e) = event_bus_for_background.publish(completed_event).await {
-                            tracing::warn!("Failed to publish background task completed event: {}", e);
-                        }
-                    }
+    // Apply preset files edited by hand
+    {
+        let presets = state.presets.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                if let Err(e) = presets.reload_if_changed().await {
+                    tracing::error!("Preset reload failed: {}", e);
                 }
+            }
+        });
+    }
 
-                // Post-scan: create/update preset franchise collections (Star Trek, Stargate, MCU)
-                info!("Creating/updating preset franchise collections...");
-                // Reload presets in case they were updated
-                let presets = match crate::infrastructure::presets::PresetLoader::load_from_directory(&presets_dir_clone) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        warn!("Failed to reload presets: {}", e);
-                        Vec::new()
-                    }
-                };
-                match collection_manager.create_preset_collections(presets).await {
-                    Ok(stats) => {
-                        info!(
-                            "Preset collections complete: {} created, {}/{} items available",
-                            stats.collections_created, stats.available_items, stats.total_items
-                        );
-                    }
-                    Err(e) => {
-                        tracing::error!("Preset collections failed: {}", e);
-                    }
-                }
+    // End stream sessions whose players stopped requesting
+    {
+        let stream_sessions = state.stream_sessions.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                stream_sessions.cleanup_idle().await;
+            }
+        });
+    }
 
-                // Post-scan: detect TMDB-based movie collections
-                info!("Running TMDB collection detection for movies...");
-                match collection_manager.detect_and_create_collections().await {
-                    Ok(stats) => {
-                        if stats.total_collections > 0 || stats.total_media_linked > 0 {
-                            info!(
-                                "TMDB collection detection complete: {} collections, {} media linked",
-                                stats.total_collections, stats.total_media_linked
-                            );
-                        }
+    // Announce the DLNA media server to renderers on the LAN
+    if config.dlna.enabled {
+        match config.dlna.advertise_ip.or_else(dlna::ssdp::local_ipv4) {
+            Some(ip) => {
+                let ssdp = dlna::ssdp::SsdpService::new(state.dlna.clone(), ip);
+                tokio::spawn(async move {
+                    if let Err(e) = ssdp.run().await {
+                        tracing::error!("SSDP discovery stopped: {}", e);
                     }
-                    Err(e) => {
-                        tracing::error!("TMDB collection detection failed: {}", e);
-                    }
-                }
-
-                // Wait for next scan interval
-                tokio::time::sleep(scan_interval).await;
+                });
             }
-        });
-    } else {
-        info!("Background scanner disabled (SCAN_INTERVAL_SECS=0)");
+            None => warn!("DLNA disabled: no LAN address found, set DLNA_ADVERTISE_IP"),
+        }
     }
 
     // Routes
+    let auth_state = auth::AuthState {
+        api_keys: state.api_keys.clone(),
+        stream_signer: state.stream_signer.clone(),
+        event_bus: Some(state.event_bus.clone()),
+    };
+    let stream_signer = state.stream_signer.clone();
+    let stream_token = move || {
+        axum::middleware::from_fn_with_state(stream_signer.clone(), stream_token::stream_token_middleware)
+    };
+
+    // Admin routes need the shared secret; device keys get 403. Admin-only
+    // methods elsewhere take the same layer.
+    let api_keys = state.api_keys.clone();
+    let admin = move || axum::middleware::from_fn_with_state(api_keys.clone(), auth::admin_middleware);
+    let admin_routes = Router::new()
+        .route("/v2/admin/issues", get(admin_handlers::get_library_issues))
+        .route("/v2/admin/collections/reconcile", post(admin_handlers::reconcile_collections))
+        .route("/v2/admin/media/remap-paths", post(admin_handlers::remap_media_paths))
+        .route("/v2/admin/media/:id", delete(admin_handlers::delete_media))
+        .route("/v2/admin/audit", get(audit_handlers::list_audit_log))
+        .route("/v2/admin/webhooks", get(webhook_handlers::list_webhooks).post(webhook_handlers::create_webhook))
+        .route("/v2/admin/webhooks/:id", get(webhook_handlers::get_webhook).put(webhook_handlers::update_webhook).delete(webhook_handlers::delete_webhook))
+        .route("/v2/admin/webhooks/:id/deliveries", get(webhook_handlers::list_deliveries))
+        .route("/v2/admin/webhooks/:id/ping", post(webhook_handlers::ping_webhook))
+        .route("/v2/admin/notifications", get(notification_handlers::list_channels).post(notification_handlers::create_channel))
+        .route("/v2/admin/notifications/:id", get(notification_handlers::get_channel).put(notification_handlers::update_channel).delete(notification_handlers::delete_channel))
+        .route("/v2/admin/notifications/:id/test", post(notification_handlers::test_channel))
+        .route("/v2/admin/settings", get(settings_handlers::get_settings).put(settings_handlers::update_settings))
+        .route("/v2/admin/tasks", get(task_handlers::list_tasks))
+        .route("/v2/admin/tasks/:name", get(task_handlers::get_task).put(task_handlers::update_task))
+        .route("/v2/admin/tasks/:name/run", post(task_handlers::run_task))
+        .route("/v2/admin/log-level", get(admin_handlers::get_log_level).put(admin_handlers::set_log_level))
+        .route("/v2/admin/stats", get(stats_handlers::get_admin_stats))
+        .route("/v2/admin/stats/slow", get(stats_handlers::get_slow_operations))
+        .route("/v2/admin/stats/memory", get(stats_handlers::get_memory_usage))
+        .route("/v2/admin/sessions/:id", delete(session_handlers::terminate_session))
+        .route_layer(admin());
+
     let app = Router::new()
         // Health Check (must be before middleware to avoid auth requirement)
         .route("/health", get(health_handlers::health_check))
+        .route("/health/live", get(health_handlers::health_check))
+        .route("/health/ready", get(health_handlers::readiness_check))
         
         // V2 Routes - Media
         .route("/v2/media", get(media_handlers::list_grouped_library))
         .route("/v2/media/recent", get(media_handlers::list_recently_added))
         .route("/v2/media/all", get(media_handlers::list_media))
+        .route("/v2/media/facets", get(media_handlers::get_media_facets))
         .route("/v2/media/:id", get(media_handlers::get_media))
         .route("/v2/media/:id/tracks", get(media_handlers::get_media_tracks))
+        .route("/v2/media/:id/subtitle-offsets", get(streaming_handlers::get_subtitle_offsets))
+        .route("/v2/media/:id/subtitle-offsets/:track", put(streaming_handlers::set_subtitle_offset).delete(streaming_handlers::delete_subtitle_offset))
+        .route("/v2/media/:id/crop", get(streaming_handlers::get_crop))
+        .route("/v2/media/:id/crop/detect", post(streaming_handlers::detect_crop))
+        .route("/v2/media/:id/preview", get(preview_handlers::get_preview))
+        .route("/v2/media/:id/bookmarks", get(bookmark_handlers::list_bookmarks).post(bookmark_handlers::create_bookmark))
+        .route("/v2/media/:id/bookmarks/:bookmark", patch(bookmark_handlers::update_bookmark).delete(bookmark_handlers::delete_bookmark))
+        .route("/v2/bookmarks", get(bookmark_handlers::list_user_bookmarks))
         .route("/v2/media/:id/credits", get(media_handlers::get_media_credits))
         .route("/v2/media/:id/similar", get(media_handlers::get_media_similar))
-        .route("/v2/media/:id/identify", post(media_handlers::manual_identify))
-        .route("/v2/scan", post(media_handlers::scan_library))
+        .route("/v2/media/:id/trailers", get(media_handlers::get_media_trailers))
+        .route("/v2/media/:id/identify", post(media_handlers::manual_identify).route_layer(admin()))
+        .route("/v2/media/:id/next", get(media_handlers::get_next_episode))
+        .route("/v2/media/:id/explain", get(media_handlers::explain_identification))
+        .route("/v2/media/:id/rename-preview", get(media_handlers::preview_rename))
+        .route("/v2/media/:id/localize", post(media_handlers::refresh_localization))
+        .route("/v2/scan", post(media_handlers::scan_library).route_layer(admin()))
 
         // V2 Routes - Series
         .route("/v2/series", get(series_handlers::list_series))
         .route("/v2/series/:id", get(series_handlers::get_series))
+        .route("/v2/series/:id/refresh", post(series_handlers::refresh_series))
 
         // V2 Routes - Collections
-        .route("/v2/collections", get(collection_handlers::list_collections))
-        .route("/v2/collections/:id", get(collection_handlers::get_collection))
+        .route("/v2/collections", get(collection_handlers::list_collections).merge(post(collection_handlers::create_collection).route_layer(admin())))
+        .route(
+            "/v2/collections/:id",
+            get(collection_handlers::get_collection)
+                .merge(put(collection_handlers::update_collection).delete(collection_handlers::delete_collection).route_layer(admin())),
+        )
+        .route("/v2/collections/:id/items", post(collection_handlers::add_collection_item).put(collection_handlers::reorder_collection_items).route_layer(admin()))
+        .route("/v2/collections/:id/items/:item", delete(collection_handlers::remove_collection_item).route_layer(admin()))
+        .route(
+            "/v2/collections/:id/poster",
+            get(collection_handlers::get_collection_poster)
+                .merge(
+                    put(collection_handlers::upload_collection_poster)
+                        .delete(collection_handlers::delete_collection_poster)
+                        .route_layer(admin()),
+                )
+                // Room for the multipart framing around a 10 MB image
+                .layer(DefaultBodyLimit::max(collection_handlers::MAX_POSTER_BYTES + 64 * 1024)),
+        )
 
         // V2 Routes - Watch Progress
         .route("/v2/progress/:id", get(progress_handlers::get_progress).post(progress_handlers::update_progress))
         .route("/v2/progress/:id/watched", post(progress_handlers::mark_watched).delete(progress_handlers::mark_unwatched))
+        .route("/v2/series/:id/watched", post(progress_handlers::mark_series_watched).delete(progress_handlers::mark_series_unwatched))
+        .route("/v2/series/:id/seasons/:season/watched", post(progress_handlers::mark_season_watched).delete(progress_handlers::mark_season_unwatched))
+        .route("/v2/collections/:id/watched", post(progress_handlers::mark_collection_watched).delete(progress_handlers::mark_collection_unwatched))
+
+        // V2 Routes - People
+        .route("/v2/people/:id", get(people_handlers::get_person))
+        .route("/v2/people/:id/media", get(people_handlers::get_person_media))
+
+        // V2 Routes - Admin
+        .merge(admin_routes)
+        .route("/v2/stats/subtitles", get(stats_handlers::get_subtitle_coverage))
+        .route("/v2/stats/user", get(stats_handlers::get_user_stats))
+        .route("/v2/recommendations", get(recommendation_handlers::get_recommendations))
+        .route("/v2/presets", get(preset_handlers::list_presets).merge(post(preset_handlers::create_preset).route_layer(admin())))
+        .route(
+            "/v2/presets/:id",
+            get(preset_handlers::get_preset)
+                .merge(put(preset_handlers::update_preset).delete(preset_handlers::delete_preset).route_layer(admin())),
+        )
+        .route(
+            "/v2/presets/import",
+            post(preset_handlers::import_presets)
+                .route_layer(admin())
+                .layer(DefaultBodyLimit::max(crate::infrastructure::presets::MAX_BUNDLE_BYTES + 64 * 1024)),
+        )
+        .route("/v2/stats/server", get(stats_handlers::get_server_stats).route_layer(admin()))
+
+        // V2 Routes - Events
+        .route("/v2/bootstrap", get(events_handlers::get_bootstrap_status))
+        .route("/v2/events", get(events_handlers::stream_events))
 
         // V2 Routes - Search
         .route("/v2/search", get(search_handlers::search_media))
         .route("/v2/search/series", get(search_handlers::search_series))
+        .route("/v2/search/suggest", get(search_handlers::suggest))
+        .route("/v2/search/semantic", get(search_handlers::semantic_search))
+        .route("/v2/search/dialogue", get(search_handlers::search_dialogue))
 
         // V2 Routes - Streaming
-        .route("/v2/stream/:id", get(streaming_handlers::stream_media))
-        .route("/v2/stream/web/:id", get(streaming_handlers::stream_web))
+        .route("/v2/stream/:id", get(streaming_handlers::stream_media).route_layer(stream_token()))
+        .route("/v2/stream/:id/playback-info", post(streaming_handlers::playback_info))
+        .route("/v2/stream/:id/token", post(streaming_handlers::issue_stream_token))
+        .route("/v2/stream/tokens/:token", delete(streaming_handlers::revoke_stream_token))
+        .route("/v2/stream/web/:id", get(streaming_handlers::stream_web).route_layer(stream_token()))
         .route("/v2/stream/diagnostic/:id", get(streaming_handlers::stream_diagnostic))
+        .route("/v2/stream/hls/sessions", get(hls_handlers::list_sessions))
+        .route("/v2/sessions", get(session_handlers::list_sessions))
+        .route("/v2/auth/devices", get(auth_handlers::list_devices).merge(post(auth_handlers::create_device).route_layer(admin())))
+        .route("/v2/auth/devices/:id", delete(auth_handlers::revoke_device).route_layer(admin()))
+        .route("/v2/stream/hls/:id/master.m3u8", get(hls_handlers::master_playlist).route_layer(stream_token()))
+        .route("/v2/stream/hls/:id/:session", delete(hls_handlers::stop_session))
+        .route("/v2/stream/hls/:id/:session/:variant/index.m3u8", get(hls_handlers::media_playlist))
+        .route("/v2/stream/hls/:id/:session/:variant/:segment", get(hls_handlers::segment))
+        .route("/v2/stream/hls/:id/:session/subs/:index/index.m3u8", get(hls_handlers::subtitle_playlist))
+        .route("/v2/stream/hls/:id/:session/subs/:index/:segment", get(hls_handlers::subtitle_segment))
+        .route("/v2/devices/:device_id/quality", get(streaming_handlers::get_quality_preference).put(streaming_handlers::set_quality_preference).delete(streaming_handlers::delete_quality_preference))
         .route("/v2/thumbnail/:id", get(streaming_handlers::generate_thumbnail))
         .route("/v2/subtitles/:media_id/:index", get(streaming_handlers::get_subtitle))
 
+        // V2 Routes - Chromecast (signed URLs for the receiver)
+        .route("/v2/cast/:id/load", post(cast_handlers::load))
+        .route("/v2/cast/play/:token/video", get(cast_handlers::play_video))
+        .route("/v2/cast/play/:token/master.m3u8", get(cast_handlers::play_master_playlist))
+        .route("/v2/cast/play/:token/:session/:variant/index.m3u8", get(cast_handlers::play_media_playlist))
+        .route("/v2/cast/play/:token/:session/:variant/:segment", get(cast_handlers::play_segment))
+        .route("/v2/cast/play/:token/:session/subs/:index/index.m3u8", get(cast_handlers::play_subtitle_playlist))
+        .route("/v2/cast/play/:token/:session/subs/:index/:segment", get(cast_handlers::play_subtitle_segment))
+
         // V2 Routes - Subtitle Generation (Whisper + Ollama)
         .route("/v2/subtitles/capabilities", get(subtitle_generation_handlers::get_capabilities))
+        .route("/v2/subtitles/models", get(subtitle_generation_handlers::list_whisper_models).merge(post(subtitle_generation_handlers::install_whisper_model).route_layer(admin())))
         .route("/v2/subtitles/active", get(subtitle_generation_handlers::get_active_jobs))
         .route("/v2/subtitles/:media_id/generate", post(subtitle_generation_handlers::generate_subtitle))
+        .route("/v2/subtitles/:media_id/translate", post(subtitle_generation_handlers::translate_subtitle))
+        .route("/v2/subtitles/:media_id/download", post(subtitle_download_handlers::download_subtitle))
+        .route("/v2/subtitles/:media_id/extract", post(subtitle_extraction_handlers::extract_subtitles))
+        .route("/v2/subtitles/batch/extract", post(subtitle_extraction_handlers::batch_extract_subtitles))
+        .route("/v2/subtitles/:media_id/generated", get(subtitle_editing_handlers::list_generated_subtitles))
+        .route("/v2/subtitles/:media_id/generated/:language", get(subtitle_editing_handlers::get_cues))
+        .route("/v2/subtitles/:media_id/generated/:language/cues/:index", patch(subtitle_editing_handlers::update_cue))
+        .route("/v2/subtitles/:media_id/generated/:language/export", get(subtitle_editing_handlers::export_subtitle))
+        .route("/v2/users/:user_id/parental-controls", get(parental_control_handlers::get_parental_controls).put(parental_control_handlers::set_parental_controls).delete(parental_control_handlers::delete_parental_controls))
+        .route("/v2/users/:user_id/parental-controls/override", post(parental_control_handlers::unlock).delete(parental_control_handlers::lock))
+        .route("/v2/profiles", get(profile_handlers::list_profiles).post(profile_handlers::create_profile))
+        .route("/v2/profiles/:id", get(profile_handlers::get_profile).put(profile_handlers::update_profile).delete(profile_handlers::delete_profile))
+        .route("/v2/profiles/:id/activate", post(profile_handlers::activate_profile))
+        .route("/v2/playlists", get(playlist_handlers::list_playlists).post(playlist_handlers::create_playlist))
+        .route("/v2/playlists/:id", get(playlist_handlers::get_playlist).put(playlist_handlers::update_playlist).delete(playlist_handlers::delete_playlist))
+        .route("/v2/playlists/:id/items", post(playlist_handlers::add_playlist_item).put(playlist_handlers::reorder_playlist_items))
+        .route("/v2/playlists/:id/items/:item", delete(playlist_handlers::remove_playlist_item))
+        .route("/v2/playlists/:id/play", get(playlist_handlers::play_playlist))
+        .route("/v2/users/:user_id/accessibility", get(streaming_handlers::get_accessibility_preferences).put(streaming_handlers::set_accessibility_preferences).delete(streaming_handlers::delete_accessibility_preferences))
+        .route("/v2/users/:user_id/subtitle-languages", get(subtitle_download_handlers::get_subtitle_languages).put(subtitle_download_handlers::set_subtitle_languages).delete(subtitle_download_handlers::delete_subtitle_languages))
+        .route("/v2/jobs", get(subtitle_generation_handlers::list_jobs))
         .route("/v2/subtitles/jobs/:job_id", get(subtitle_generation_handlers::get_job_status).delete(subtitle_generation_handlers::cancel_job))
         .route("/v2/subtitles/batch/generate", post(subtitle_generation_handlers::batch_generate_subtitles))
         .route("/v2/subtitles/batch/jobs/:job_id", get(subtitle_generation_handlers::get_batch_job_status).delete(subtitle_generation_handlers::cancel_batch_job))
 
+        // DLNA (device/service descriptions and SOAP control)
+        .route("/dlna/description.xml", get(dlna::handlers::device_description))
+        .route("/dlna/content_directory.xml", get(dlna::handlers::content_directory_scpd))
+        .route("/dlna/connection_manager.xml", get(dlna::handlers::connection_manager_scpd))
+        .route("/dlna/control/content_directory", post(dlna::handlers::content_directory_control))
+        .route("/dlna/control/connection_manager", post(dlna::handlers::connection_manager_control))
+        .route("/dlna/event/:service", any(dlna::handlers::event_subscription))
+        .route("/dlna/images/:id/:kind", get(proxy_handlers::get_artwork))
+
         // V2 Routes - Proxy (for TMDB images - CORS bypass)
         .route("/v2/images/proxy", get(proxy_handlers::proxy_image))
+        .route("/v2/images/:id/:kind", get(proxy_handlers::get_artwork))
 
         // Apply Middleware
-        .layer(axum::middleware::from_fn(auth::auth_middleware))
-        .layer(axum::middleware::from_fn(logging::logging_middleware))
+        .layer(axum::middleware::from_fn(problem::problem_middleware))
+        .layer(axum::middleware::from_fn(conditional::conditional_middleware))
+        .layer(axum::middleware::from_fn_with_state(
+            compression::CompressionSettings {
+                enabled: config.server.compression,
+                min_bytes: config.server.compression_min_bytes,
+            },
+            compression::compression_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(auth_state, auth::auth_middleware))
+        .layer(axum::middleware::from_fn_with_state(state.slow_operations.clone(), logging::logging_middleware))
         .layer(cors::cors_layer())
 
         .with_state(state);
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
+    let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    info!("Server listening on {}", addr);
 
-    axum::serve(listener, app.into_make_service()).await?;
+    if let (Some(cert_path), Some(key_path)) = (&config.tls.cert_path, &config.tls.key_path) {
+        let certificates = Arc::new(tls::CertificateStore::load(cert_path, key_path)?);
+        certificates.watch_renewals();
+        let tls_config = certificates.server_config()?;
+
+        // Plain HTTP redirects to HTTPS and answers ACME challenges
+        if let Some(http_port) = config.tls.http_port {
+            let http_addr = SocketAddr::from(([0, 0, 0, 0], http_port));
+            let http_listener = tokio::net::TcpListener::bind(&http_addr).await?;
+            let redirect = tls::redirect_app(config.server.port, config.tls.acme_webroot.as_ref().map(std::path::PathBuf::from));
+            info!("Redirecting HTTP on {} to HTTPS", http_addr);
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(http_listener, redirect).await {
+                    tracing::error!("HTTP redirect listener failed: {}", e);
+                }
+            });
+        }
+
+        info!("Server listening on {} (HTTPS, HTTP/2)", addr);
+        tls::serve(listener, tls_config, app).await;
+        return Ok(());
+    }
+
+    info!("Server listening on {}", addr);
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
+
+/// Scans the library, then updates what depends on it: collections,
+/// artwork, parental control ratings and the search indexes
+///
+/// The steps after the scan run even when it fails. The first scan marks
+/// the server bootstrapped.
+async fn run_library_scan(state: AppState, library: crate::config::LibraryConfig) -> Result<String, ApplicationError> {
+    let media_dir = &library.media_dir;
+    info!("Running library scan at: {}", media_dir);
+
+    let started_event = crate::domain::events::BackgroundScanStartedEvent::new(media_dir.clone());
+    if let Err(e) = state.event_bus.publish(started_event).await {
+        warn!("Failed to publish background scan started event: {}", e);
+    }
+
+    state.bootstrap.scan_started();
+    let result = state.scan_use_case.execute(media_dir).await;
+    let completed_event = match &result {
+        Ok(result) => {
+            state.bootstrap.finalizing();
+            info!(
+                "Library scan completed: {} files processed, {} identified, {} failed",
+                result.processed_count, result.identified_count, result.failed_count
+            );
+            crate::domain::events::BackgroundTaskCompletedEvent::new(
+                "library_scan".to_string(),
+                None,
+                true,
+                Some(format!("{} files processed, {} identified", result.processed_count, result.identified_count)),
+            )
+        }
+        Err(e) => {
+            state.bootstrap.failed(e.to_string());
+            crate::domain::events::BackgroundTaskCompletedEvent::new("library_scan".to_string(), None, false, Some(e.to_string()))
+        }
+    };
+    if let Err(e) = state.event_bus.publish(completed_event).await {
+        warn!("Failed to publish background task completed event: {}", e);
+    }
+
+    // Post-scan: create/update preset franchise collections (Star Trek, Stargate, MCU)
+    info!("Creating/updating preset franchise collections...");
+    match state.presets.apply().await {
+        Ok(stats) => {
+            info!(
+                "Preset collections complete: {} created, {}/{} items available",
+                stats.collections_created, stats.available_items, stats.total_items
+            );
+        }
+        Err(e) => {
+            tracing::error!("Preset collections failed: {}", e);
+        }
+    }
+
+    // Post-scan: detect TMDB-based movie collections
+    info!("Running TMDB collection detection for movies...");
+    match state.collection_manager.detect_and_create_collections().await {
+        Ok(stats) => {
+            if stats.total_collections > 0 || stats.total_media_linked > 0 {
+                info!(
+                    "TMDB collection detection complete: {} collections, {} media linked",
+                    stats.total_collections, stats.total_media_linked
+                );
+            }
+        }
+        Err(e) => {
+            tracing::error!("TMDB collection detection failed: {}", e);
+        }
+    }
+
+    // Post-scan: genre and decade collections (also removes them once disabled)
+    if let Err(e) = state
+        .collection_manager
+        .sync_auto_collections(library.genre_collections, library.decade_collections)
+        .await
+    {
+        tracing::error!("Genre and decade collections failed: {}", e);
+    }
+
+    // Post-scan: repair counts of collections whose items were deleted
+    if let Err(e) = state.collection_manager.reconcile_counts(false).await {
+        tracing::error!("Collection reconcile failed: {}", e);
+    }
+
+    // Post-scan: fetch fanart.tv logos, clearart and disc art
+    if let Some(fanart_enricher) = &state.fanart_enricher {
+        if let Err(e) = fanart_enricher.enrich_library().await {
+            tracing::error!("fanart.tv enrichment failed: {}", e);
+        }
+    }
+
+    // Post-scan: compute blurhash placeholders for new artwork
+    if let Err(e) = state.blurhash_backfill.backfill_library().await {
+        tracing::error!("Blurhash backfill failed: {}", e);
+    }
+
+    // Post-scan: fetch certifications of new titles for parental controls
+    if let Err(e) = state.content_rating_backfill.backfill_library().await {
+        tracing::error!("Content rating backfill failed: {}", e);
+    }
+
+    // Post-scan: index new and changed subtitles for dialogue search
+    if let Err(e) = state.dialogue_search.index_library().await {
+        tracing::error!("Dialogue indexing failed: {}", e);
+    }
+
+    // Post-scan: embed new and changed overviews for semantic search
+    if let Some(semantic_search) = &state.semantic_search {
+        if let Err(e) = semantic_search.index_library().await {
+            tracing::error!("Semantic search indexing failed: {}", e);
+        }
+    }
+
+    if !state.bootstrap.is_complete() {
+        state.bootstrap.ready();
+        info!("Initial library scan and collection setup complete");
+    }
+
+    let result = result?;
+    Ok(format!(
+        "{} files processed, {} identified, {} failed",
+        result.processed_count, result.identified_count, result.failed_count
+    ))
+}
@@ -0,0 +1,532 @@
+//! DLNA Content Directory
+//!
+//! Maps the library onto the UPnP object hierarchy:
+//!
+//! ```text
+//! 0
+//! ├── movies                      → media:<id>
+//! ├── series  → series:<id>       → season:<series>:<n> → media:<id>
+//! └── collections → collection:<id> → media:<id> / series:<id>
+//! ```
+//!
+//! and renders browse results as DIDL-Lite. Titles the parental controls
+//! block are left out.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use quick_xml::escape::escape;
+
+use crate::application::services::{ContentPolicy, ParentalControlService};
+use crate::domain::entities::Media;
+use crate::domain::repositories::{CollectionRepository, MediaRepository, SeriesRepository};
+use crate::domain::value_objects::MediaType;
+use crate::shared::error::ApplicationError;
+
+/// Object ID of the content hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectId {
+    Root,
+    Movies,
+    Series,
+    Collections,
+    Show(i64),
+    Season(i64, i32),
+    Collection(i64),
+    Media(i64),
+}
+
+impl ObjectId {
+    pub fn parse(id: &str) -> Option<Self> {
+        let id = match id {
+            "0" => return Some(ObjectId::Root),
+            "movies" => return Some(ObjectId::Movies),
+            "series" => return Some(ObjectId::Series),
+            "collections" => return Some(ObjectId::Collections),
+            id => id,
+        };
+        let (kind, rest) = id.split_once(':')?;
+        match kind {
+            "series" => rest.parse().ok().map(ObjectId::Show),
+            "collection" => rest.parse().ok().map(ObjectId::Collection),
+            "media" => rest.parse().ok().map(ObjectId::Media),
+            "season" => {
+                let (series, season) = rest.split_once(':')?;
+                Some(ObjectId::Season(series.parse().ok()?, season.parse().ok()?))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectId::Root => write!(f, "0"),
+            ObjectId::Movies => write!(f, "movies"),
+            ObjectId::Series => write!(f, "series"),
+            ObjectId::Collections => write!(f, "collections"),
+            ObjectId::Show(id) => write!(f, "series:{}", id),
+            ObjectId::Season(series, season) => write!(f, "season:{}:{}", series, season),
+            ObjectId::Collection(id) => write!(f, "collection:{}", id),
+            ObjectId::Media(id) => write!(f, "media:{}", id),
+        }
+    }
+}
+
+/// Poster of a container or item, served by the artwork endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artwork {
+    Media(i64),
+    Series(i64),
+}
+
+/// Entry of a browse result
+#[derive(Debug, Clone)]
+pub enum DlnaObject {
+    Container {
+        id: ObjectId,
+        parent: ObjectId,
+        title: String,
+        /// UPnP class, e.g. `object.container.storageFolder`
+        class: &'static str,
+        child_count: Option<usize>,
+        artwork: Option<Artwork>,
+    },
+    Item {
+        parent: ObjectId,
+        title: String,
+        media: Box<Media>,
+        /// File size in bytes (needed by renderers to seek)
+        size: Option<u64>,
+        /// Session token of the stream URL (renderers cannot send a key)
+        token: Option<String>,
+    },
+}
+
+impl DlnaObject {
+    fn media_item(parent: ObjectId, media: Media) -> Self {
+        let title = match (media.season, media.episode) {
+            (Some(season), Some(episode)) if media.is_episode() => {
+                format!("S{:02}E{:02} {}", season, episode, media.title)
+            }
+            _ => media.title.clone(),
+        };
+        DlnaObject::Item { parent, title, media: Box::new(media), size: None, token: None }
+    }
+}
+
+/// Browses the library through the repositories
+pub struct ContentDirectory {
+    media_repository: Arc<dyn MediaRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    collection_repository: Arc<dyn CollectionRepository>,
+    parental: Arc<ParentalControlService>,
+    /// What renderers may see
+    policy: ContentPolicy,
+}
+
+impl ContentDirectory {
+    pub fn new(
+        media_repository: Arc<dyn MediaRepository>,
+        series_repository: Arc<dyn SeriesRepository>,
+        collection_repository: Arc<dyn CollectionRepository>,
+        parental: Arc<ParentalControlService>,
+        policy: ContentPolicy,
+    ) -> Self {
+        Self { media_repository, series_repository, collection_repository, parental, policy }
+    }
+
+    /// Children of a container (None if the object does not exist)
+    pub async fn children(&self, id: ObjectId) -> Result<Option<Vec<DlnaObject>>, ApplicationError> {
+        let children = match id {
+            ObjectId::Root => {
+                // Counting blocked titles would give them away
+                let (movies, series, collections) = if self.policy.is_restricted() {
+                    (self.movies().await?.len(), self.shows().await?.len(), self.collections().await?.len())
+                } else {
+                    (
+                        self.media_repository.count_by_type(MediaType::Movie).await? as usize,
+                        self.series_repository.count().await? as usize,
+                        self.collection_repository.count().await? as usize,
+                    )
+                };
+                vec![
+                    self.folder(ObjectId::Movies, "Movies", movies),
+                    self.folder(ObjectId::Series, "Series", series),
+                    self.folder(ObjectId::Collections, "Collections", collections),
+                ]
+            }
+            ObjectId::Movies => self.movies().await?,
+            ObjectId::Series => self.shows().await?,
+            ObjectId::Show(series_id) => {
+                let Some(series) = self.series_repository.find_by_id(series_id).await? else {
+                    return Ok(None);
+                };
+                if !self.policy.allows_series(&series) {
+                    return Ok(None);
+                }
+                let mut seasons: BTreeMap<i32, usize> = BTreeMap::new();
+                for episode in self.allowed(self.media_repository.find_by_series(series_id).await?).await? {
+                    *seasons.entry(episode.season.unwrap_or(0)).or_default() += 1;
+                }
+                seasons.into_iter()
+                    .map(|(season, episodes)| DlnaObject::Container {
+                        id: ObjectId::Season(series_id, season),
+                        parent: ObjectId::Show(series_id),
+                        title: if season == 0 { "Specials".to_string() } else { format!("Season {}", season) },
+                        class: "object.container.album.videoAlbum",
+                        child_count: Some(episodes),
+                        artwork: series.poster_url.is_some().then_some(Artwork::Series(series_id)),
+                    })
+                    .collect()
+            }
+            ObjectId::Season(series_id, season) => {
+                let mut episodes = self.allowed(self.media_repository.find_by_season(series_id, season).await?).await?;
+                episodes.sort_by_key(|e| e.episode.unwrap_or(0));
+                episodes.into_iter()
+                    .map(|e| DlnaObject::media_item(ObjectId::Season(series_id, season), e))
+                    .collect()
+            }
+            ObjectId::Collections => self.collections().await?,
+            ObjectId::Collection(collection_id) => {
+                if self.collection_repository.find_by_id(collection_id).await?.is_none() {
+                    return Ok(None);
+                }
+                let mut items = self.collection_repository.find_items(collection_id).await?;
+                items.sort_by_key(|i| i.timeline_order);
+                let mut children = Vec::new();
+                for item in items.into_iter().filter(|i| i.is_available) {
+                    let Some(library_id) = item.media_id else {
+                        continue;
+                    };
+                    // Series items link the series, movie items the media
+                    if item.media_type == "movie" {
+                        if let Some(media) = self.media_repository.find_by_id(library_id).await? {
+                            for media in self.allowed(vec![media]).await? {
+                                children.push(DlnaObject::media_item(ObjectId::Collection(collection_id), media));
+                            }
+                        }
+                    } else if let Some(series) = self.series_repository.find_by_id(library_id).await? {
+                        if !self.policy.allows_series(&series) {
+                            continue;
+                        }
+                        children.push(DlnaObject::Container {
+                            id: ObjectId::Show(library_id),
+                            parent: ObjectId::Collection(collection_id),
+                            title: series.title,
+                            class: "object.container.album.videoAlbum",
+                            child_count: None,
+                            artwork: series.poster_url.is_some().then_some(Artwork::Series(library_id)),
+                        });
+                    }
+                }
+                children
+            }
+            ObjectId::Media(_) => return Ok(None),
+        };
+        Ok(Some(children))
+    }
+
+    async fn movies(&self) -> Result<Vec<DlnaObject>, ApplicationError> {
+        let mut movies = self.allowed(self.media_repository.find_by_type(MediaType::Movie).await?).await?;
+        movies.sort_by_key(|m| m.title.to_lowercase());
+        Ok(movies.into_iter().map(|m| DlnaObject::media_item(ObjectId::Movies, m)).collect())
+    }
+
+    async fn shows(&self) -> Result<Vec<DlnaObject>, ApplicationError> {
+        let mut seasons: BTreeMap<i64, Vec<i32>> = BTreeMap::new();
+        for episode in self.allowed(self.media_repository.find_by_type(MediaType::Episode).await?).await? {
+            if let Some(series_id) = episode.series_id {
+                let entry = seasons.entry(series_id).or_default();
+                let season = episode.season.unwrap_or(0);
+                if !entry.contains(&season) {
+                    entry.push(season);
+                }
+            }
+        }
+        let mut series = self.series_repository.find_all().await?;
+        series.retain(|s| self.policy.allows_series(s));
+        series.sort_by_key(|s| s.title.to_lowercase());
+        Ok(series.into_iter()
+            .filter_map(|s| {
+                let id = s.id?;
+                Some(DlnaObject::Container {
+                    id: ObjectId::Show(id),
+                    parent: ObjectId::Series,
+                    title: s.title,
+                    class: "object.container.album.videoAlbum",
+                    child_count: Some(seasons.get(&id).map_or(0, |s| s.len())),
+                    artwork: s.poster_url.is_some().then_some(Artwork::Series(id)),
+                })
+            })
+            .collect())
+    }
+
+    async fn collections(&self) -> Result<Vec<DlnaObject>, ApplicationError> {
+        let mut collections = self.collection_repository.find_all().await?;
+        collections.retain(|c| c.available_items > 0);
+        collections.sort_by_key(|c| c.name.to_lowercase());
+        Ok(collections.into_iter()
+            .filter_map(|c| {
+                Some(DlnaObject::Container {
+                    id: ObjectId::Collection(c.id?),
+                    parent: ObjectId::Collections,
+                    title: c.name,
+                    class: "object.container.storageFolder",
+                    // Items are only filtered when browsed
+                    child_count: (!self.policy.is_restricted()).then_some(c.available_items as usize),
+                    artwork: None,
+                })
+            })
+            .collect())
+    }
+
+    /// Keeps the movies and episodes the policy allows
+    async fn allowed(&self, media: Vec<Media>) -> Result<Vec<Media>, ApplicationError> {
+        self.parental.retain_allowed(&self.policy, media).await
+    }
+
+    /// The object itself (BrowseMetadata)
+    pub async fn metadata(&self, id: ObjectId) -> Result<Option<DlnaObject>, ApplicationError> {
+        let object = match id {
+            ObjectId::Root => DlnaObject::Container {
+                id,
+                // Rendered as parentID="-1"
+                parent: ObjectId::Root,
+                title: "Homeflix".to_string(),
+                class: "object.container.storageFolder",
+                child_count: Some(3),
+                artwork: None,
+            },
+            ObjectId::Movies | ObjectId::Series | ObjectId::Collections => {
+                let Some(children) = self.children(ObjectId::Root).await? else {
+                    return Ok(None);
+                };
+                return Ok(children.into_iter().find(|c| matches!(c, DlnaObject::Container { id: child, .. } if *child == id)));
+            }
+            ObjectId::Show(series_id) => {
+                let Some(series) = self.series_repository.find_by_id(series_id).await? else {
+                    return Ok(None);
+                };
+                if !self.policy.allows_series(&series) {
+                    return Ok(None);
+                }
+                DlnaObject::Container {
+                    id,
+                    parent: ObjectId::Series,
+                    title: series.title,
+                    class: "object.container.album.videoAlbum",
+                    child_count: None,
+                    artwork: series.poster_url.is_some().then_some(Artwork::Series(series_id)),
+                }
+            }
+            ObjectId::Season(series_id, _) => {
+                let Some(children) = self.children(ObjectId::Show(series_id)).await? else {
+                    return Ok(None);
+                };
+                return Ok(children.into_iter().find(|c| matches!(c, DlnaObject::Container { id: child, .. } if *child == id)));
+            }
+            ObjectId::Collection(collection_id) => {
+                let Some(collection) = self.collection_repository.find_by_id(collection_id).await? else {
+                    return Ok(None);
+                };
+                DlnaObject::Container {
+                    id,
+                    parent: ObjectId::Collections,
+                    title: collection.name,
+                    class: "object.container.storageFolder",
+                    child_count: (!self.policy.is_restricted()).then_some(collection.available_items.max(0) as usize),
+                    artwork: None,
+                }
+            }
+            ObjectId::Media(media_id) => {
+                let Some(media) = self.media_repository.find_by_id(media_id).await? else {
+                    return Ok(None);
+                };
+                let Some(media) = self.allowed(vec![media]).await?.pop() else {
+                    return Ok(None);
+                };
+                let parent = match (media.series_id, media.season) {
+                    (Some(series_id), season) if media.is_episode() => ObjectId::Season(series_id, season.unwrap_or(0)),
+                    _ => ObjectId::Movies,
+                };
+                DlnaObject::media_item(parent, media)
+            }
+        };
+        Ok(Some(object))
+    }
+
+    fn folder(&self, id: ObjectId, title: &str, child_count: usize) -> DlnaObject {
+        DlnaObject::Container {
+            id,
+            parent: ObjectId::Root,
+            title: title.to_string(),
+            class: "object.container.storageFolder",
+            child_count: Some(child_count),
+            artwork: None,
+        }
+    }
+}
+
+/// Renders objects as a DIDL-Lite document
+///
+/// `base_url` is the server address as seen by the renderer
+/// (e.g. `http://192.168.1.10:3000`).
+pub fn render_didl(objects: &[DlnaObject], base_url: &str) -> String {
+    let mut didl = String::from(
+        r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/" xmlns:dlna="urn:schemas-dlna-org:metadata-1-0/">"#,
+    );
+    for object in objects {
+        match object {
+            DlnaObject::Container { id, parent, title, class, child_count, artwork } => {
+                didl.push_str(&format!(r#"<container id="{}" parentID="{}" restricted="1""#, id, parent_id(*id, *parent)));
+                if let Some(count) = child_count {
+                    didl.push_str(&format!(r#" childCount="{}""#, count));
+                }
+                didl.push_str(&format!("><dc:title>{}</dc:title><upnp:class>{}</upnp:class>", escape(title.as_str()), class));
+                if let Some(artwork) = artwork {
+                    didl.push_str(&album_art(*artwork, base_url));
+                }
+                didl.push_str("</container>");
+            }
+            DlnaObject::Item { parent, title, media, size, token } => {
+                let Some(media_id) = media.id else {
+                    continue;
+                };
+                let class = if media.is_episode() { "object.item.videoItem" } else { "object.item.videoItem.movie" };
+                didl.push_str(&format!(
+                    r#"<item id="{}" parentID="{}" restricted="1"><dc:title>{}</dc:title><upnp:class>{}</upnp:class>"#,
+                    ObjectId::Media(media_id), parent, escape(title.as_str()), class
+                ));
+                if let Some(date) = media.release_date.as_deref().filter(|d| !d.is_empty()) {
+                    didl.push_str(&format!("<dc:date>{}</dc:date>", escape(date)));
+                }
+                if let Some(overview) = media.overview.as_deref().filter(|o| !o.is_empty()) {
+                    didl.push_str(&format!("<dc:description>{}</dc:description>", escape(overview)));
+                }
+                if media.poster_url.is_some() {
+                    didl.push_str(&album_art(Artwork::Media(media_id), base_url));
+                }
+
+                let mime = mime_guess::from_path(&media.file_path).first_or_octet_stream();
+                // OP=01: byte range seeking; FLAGS: streaming transfer mode, DLNA 1.5
+                didl.push_str(&format!(
+                    r#"<res protocolInfo="http-get:*:{}:DLNA.ORG_OP=01;DLNA.ORG_CI=0;DLNA.ORG_FLAGS=01700000000000000000000000000000""#,
+                    mime
+                ));
+                if let Some(size) = size {
+                    didl.push_str(&format!(r#" size="{}""#, size));
+                }
+                if let Some(seconds) = media.duration_seconds.filter(|s| *s > 0) {
+                    didl.push_str(&format!(
+                        r#" duration="{}:{:02}:{:02}.000""#,
+                        seconds / 3600, (seconds % 3600) / 60, seconds % 60
+                    ));
+                }
+                didl.push_str(&format!(">{}/v2/stream/{}", base_url, media_id));
+                if let Some(token) = token {
+                    didl.push_str(&format!("?token={}", escape(token.as_str())));
+                }
+                didl.push_str("</res></item>");
+            }
+        }
+    }
+    didl.push_str("</DIDL-Lite>");
+    didl
+}
+
+fn parent_id(id: ObjectId, parent: ObjectId) -> String {
+    if id == ObjectId::Root { "-1".to_string() } else { parent.to_string() }
+}
+
+fn album_art(artwork: Artwork, base_url: &str) -> String {
+    let url = match artwork {
+        Artwork::Media(id) => format!("{}/dlna/images/{}/poster?size=small&amp;format=jpeg", base_url, id),
+        Artwork::Series(id) => format!("{}/dlna/images/{}/poster?size=small&amp;format=jpeg&amp;type=series", base_url, id),
+    };
+    format!(r#"<upnp:albumArtURI dlna:profileID="JPEG_TN">{}</upnp:albumArtURI>"#, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::ParentalControls;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{
+        SqliteCollectionRepository, SqliteMediaRepository, SqliteParentalControlRepository, SqliteSeriesRepository,
+    };
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[test]
+    fn test_object_ids_and_didl() {
+        for id in [ObjectId::Root, ObjectId::Movies, ObjectId::Show(4), ObjectId::Season(4, 2), ObjectId::Collection(9), ObjectId::Media(12)] {
+            assert_eq!(ObjectId::parse(&id.to_string()), Some(id));
+        }
+        assert_eq!(ObjectId::parse("season:4"), None);
+
+        let mut episode = Media::new("/tv/Show/S02E03.mkv".to_string(), MediaType::Episode, "Show & Tell".to_string()).unwrap();
+        episode.id = Some(12);
+        episode.season = Some(2);
+        episode.episode = Some(3);
+        episode.duration_seconds = Some(3725);
+        let mut item = DlnaObject::media_item(ObjectId::Season(4, 2), episode);
+        if let DlnaObject::Item { size, token, .. } = &mut item {
+            *size = Some(1024);
+            *token = Some("12-1700000000-abc-64656661756c74-0123".to_string());
+        }
+
+        let didl = render_didl(&[item], "http://192.168.1.10:3000");
+        assert!(didl.contains(r#"<item id="media:12" parentID="season:4:2" restricted="1"><dc:title>S02E03 Show &amp; Tell</dc:title>"#));
+        assert!(didl.contains(r#"protocolInfo="http-get:*:video/x-matroska:DLNA.ORG_OP=01;"#));
+        assert!(didl.contains(r#" size="1024" duration="1:02:05.000">http://192.168.1.10:3000/v2/stream/12?token=12-1700000000-abc-64656661756c74-0123</res>"#));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_titles_are_hidden() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repository: Arc<dyn MediaRepository> = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let series_repository = Arc::new(SqliteSeriesRepository::new(pool.clone()));
+        let mut ids = Vec::new();
+        for (title, rating) in [("Allowed", "PG"), ("Blocked", "R")] {
+            let movie = Media::new(format!("/movies/{}.mkv", title), MediaType::Movie, title.to_string())
+                .unwrap()
+                .with_content_rating(Some(rating.to_string()));
+            ids.push(media_repository.save(&movie).await.unwrap());
+        }
+        let parental = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            series_repository.clone(),
+        ));
+        let controls = ParentalControls { max_age: Some(12), ..Default::default() };
+        parental.set("default", controls, None, None).await.unwrap();
+
+        let directory = ContentDirectory::new(
+            media_repository,
+            series_repository,
+            Arc::new(SqliteCollectionRepository::new(pool)),
+            parental.clone(),
+            parental.policy("default").await.unwrap(),
+        );
+        let movies = directory.children(ObjectId::Movies).await.unwrap().unwrap();
+        let titles: Vec<&str> = movies
+            .iter()
+            .filter_map(|m| match m {
+                DlnaObject::Item { title, .. } => Some(title.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(titles, vec!["Allowed"]);
+        assert!(matches!(
+            &directory.children(ObjectId::Root).await.unwrap().unwrap()[0],
+            DlnaObject::Container { child_count: Some(1), .. }
+        ));
+        assert!(directory.metadata(ObjectId::Media(ids[0])).await.unwrap().is_some());
+        assert!(directory.metadata(ObjectId::Media(ids[1])).await.unwrap().is_none());
+    }
+}
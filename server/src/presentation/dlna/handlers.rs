@@ -0,0 +1,230 @@
+//! DLNA Handlers
+//!
+//! - `GET /dlna/description.xml` - Device description
+//! - `GET /dlna/content_directory.xml`, `GET /dlna/connection_manager.xml` - Service descriptions
+//! - `POST /dlna/control/content_directory` - Browse (SOAP)
+//! - `POST /dlna/control/connection_manager` - Protocol info (SOAP)
+//! - `/dlna/event/:service` - Event subscriptions (accepted, never notified)
+//! - `GET /dlna/images/:id/:kind` - Album art (same as `/v2/images/:id/:kind`)
+//!
+//! With `API_SECRET` set these are only served on the local network (see
+//! `auth_middleware`), and item stream URLs carry a session token of the
+//! default user.
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::services::{ParentalControlService, StreamUrlSigner};
+use crate::domain::repositories::{CollectionRepository, MediaRepository, SeriesRepository};
+use crate::presentation::http::handlers::streaming_handlers::DEFAULT_USER;
+use super::content_directory::{render_didl, ContentDirectory, DlnaObject, ObjectId};
+use super::description::{self, CONNECTION_MANAGER_TYPE, CONTENT_DIRECTORY_TYPE};
+use super::DlnaServer;
+
+const XML_CONTENT_TYPE: &str = r#"text/xml; charset="utf-8""#;
+
+/// Device description
+pub async fn device_description(State(server): State<Arc<DlnaServer>>) -> Response {
+    xml_response(StatusCode::OK, description::device_description(&server))
+}
+
+/// ContentDirectory service description
+pub async fn content_directory_scpd() -> Response {
+    xml_response(StatusCode::OK, description::CONTENT_DIRECTORY_SCPD.to_string())
+}
+
+/// ConnectionManager service description
+pub async fn connection_manager_scpd() -> Response {
+    xml_response(StatusCode::OK, description::CONNECTION_MANAGER_SCPD.to_string())
+}
+
+/// ContentDirectory control endpoint
+///
+/// Renderers have no user of their own, so the default user's parental
+/// controls apply.
+pub async fn content_directory_control(
+    State(media_repository): State<Arc<dyn MediaRepository>>,
+    State(series_repository): State<Arc<dyn SeriesRepository>>,
+    State(collection_repository): State<Arc<dyn CollectionRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let arguments = soap_arguments(&body);
+    match soap_action(&headers).as_deref() {
+        Some("Browse") => {}
+        Some("GetSearchCapabilities") => return soap_response(CONTENT_DIRECTORY_TYPE, "GetSearchCapabilities", &[("SearchCaps", "")]),
+        Some("GetSortCapabilities") => return soap_response(CONTENT_DIRECTORY_TYPE, "GetSortCapabilities", &[("SortCaps", "")]),
+        Some("GetSystemUpdateID") => return soap_response(CONTENT_DIRECTORY_TYPE, "GetSystemUpdateID", &[("Id", "0")]),
+        _ => return soap_fault(401, "Invalid Action"),
+    }
+
+    let Some(object_id) = arguments.get("ObjectID").and_then(|id| ObjectId::parse(id)) else {
+        return soap_fault(701, "No such object");
+    };
+    let start: usize = arguments.get("StartingIndex").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let requested: usize = arguments.get("RequestedCount").and_then(|v| v.parse().ok()).unwrap_or(0);
+
+    let policy = match parental.policy(DEFAULT_USER).await {
+        Ok(policy) => policy,
+        Err(e) => {
+            tracing::error!("DLNA parental controls failed: {}", e);
+            return soap_fault(501, "Action Failed");
+        }
+    };
+    let directory = ContentDirectory::new(media_repository, series_repository, collection_repository, parental, policy);
+    let result = match arguments.get("BrowseFlag").map(String::as_str) {
+        Some("BrowseMetadata") => directory.metadata(object_id).await.map(|o| o.map(|o| vec![o])),
+        Some("BrowseDirectChildren") => directory.children(object_id).await,
+        _ => return soap_fault(402, "Invalid Args"),
+    };
+    let objects = match result {
+        Ok(Some(objects)) => objects,
+        Ok(None) => return soap_fault(701, "No such object"),
+        Err(e) => {
+            tracing::error!("DLNA browse of {} failed: {}", object_id, e);
+            return soap_fault(501, "Action Failed");
+        }
+    };
+
+    // RequestedCount 0 means all remaining objects
+    let total = objects.len();
+    let mut page: Vec<DlnaObject> = objects.into_iter()
+        .skip(start)
+        .take(if requested == 0 { usize::MAX } else { requested })
+        .collect();
+    for object in &mut page {
+        if let DlnaObject::Item { media, size, token, .. } = object {
+            *size = tokio::fs::metadata(&media.file_path).await.ok().map(|m| m.len());
+            *token = media.id.map(|id| signer.sign_session(id, DEFAULT_USER).1.token);
+        }
+    }
+
+    let didl = render_didl(&page, &base_url(&headers));
+    let returned = page.len().to_string();
+    let total = total.to_string();
+    soap_response(CONTENT_DIRECTORY_TYPE, "Browse", &[
+        ("Result", &didl),
+        ("NumberReturned", &returned),
+        ("TotalMatches", &total),
+        ("UpdateID", "0"),
+    ])
+}
+
+/// ConnectionManager control endpoint
+pub async fn connection_manager_control(headers: HeaderMap) -> Response {
+    match soap_action(&headers).as_deref() {
+        Some("GetProtocolInfo") => soap_response(CONNECTION_MANAGER_TYPE, "GetProtocolInfo", &[
+            ("Source", "http-get:*:video/mp4:*,http-get:*:video/x-matroska:*,http-get:*:video/x-msvideo:*,http-get:*:video/mp2t:*,http-get:*:video/webm:*"),
+            ("Sink", ""),
+        ]),
+        Some("GetCurrentConnectionIDs") => soap_response(CONNECTION_MANAGER_TYPE, "GetCurrentConnectionIDs", &[("ConnectionIDs", "0")]),
+        _ => soap_fault(401, "Invalid Action"),
+    }
+}
+
+/// Event subscription endpoint
+///
+/// Some renderers refuse servers whose SUBSCRIBE fails. The library is
+/// static from a renderer's point of view, so no events are ever sent.
+pub async fn event_subscription(method: Method) -> Response {
+    match method.as_str() {
+        "SUBSCRIBE" => (
+            StatusCode::OK,
+            [
+                ("SID", format!("uuid:{}", uuid::Uuid::new_v4())),
+                ("TIMEOUT", "Second-1800".to_string()),
+            ],
+        ).into_response(),
+        "UNSUBSCRIBE" => StatusCode::OK.into_response(),
+        _ => StatusCode::METHOD_NOT_ALLOWED.into_response(),
+    }
+}
+
+/// Action name from `SOAPACTION: "urn:...:ContentDirectory:1#Browse"`
+fn soap_action(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get("soapaction")?.to_str().ok()?;
+    Some(value.trim().trim_matches('"').rsplit_once('#')?.1.to_string())
+}
+
+/// Arguments of a SOAP action, by element name
+fn soap_arguments(body: &str) -> HashMap<String, String> {
+    let mut reader = Reader::from_str(body);
+    let mut arguments = HashMap::new();
+    let mut current: Option<String> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                current = Some(String::from_utf8_lossy(e.local_name().as_ref()).to_string());
+            }
+            Ok(Event::Text(text)) => {
+                if let (Some(name), Ok(value)) = (&current, text.unescape()) {
+                    arguments.insert(name.clone(), value.to_string());
+                }
+            }
+            Ok(Event::End(_)) => current = None,
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    arguments
+}
+
+/// Server address as the renderer reached it
+fn base_url(headers: &HeaderMap) -> String {
+    let host = headers.get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("localhost");
+    format!("http://{}", host)
+}
+
+fn soap_response(service: &str, action: &str, arguments: &[(&str, &str)]) -> Response {
+    let body: String = arguments.iter()
+        .map(|(name, value)| format!("<{name}>{}</{name}>", escape(value)))
+        .collect();
+    xml_response(StatusCode::OK, format!(
+        r#"<?xml version="1.0" encoding="utf-8"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"><s:Body><u:{action}Response xmlns:u="{service}">{body}</u:{action}Response></s:Body></s:Envelope>"#,
+    ))
+}
+
+fn soap_fault(code: u16, description: &str) -> Response {
+    xml_response(StatusCode::INTERNAL_SERVER_ERROR, format!(
+        r#"<?xml version="1.0" encoding="utf-8"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"><s:Body><s:Fault><faultcode>s:Client</faultcode><faultstring>UPnPError</faultstring><detail><UPnPError xmlns="urn:schemas-upnp-org:control-1-0"><errorCode>{code}</errorCode><errorDescription>{description}</errorDescription></UPnPError></detail></s:Fault></s:Body></s:Envelope>"#,
+    ))
+}
+
+fn xml_response(status: StatusCode, body: String) -> Response {
+    (status, [(header::CONTENT_TYPE, XML_CONTENT_TYPE)], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soap_request_parsing() {
+        let body = r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body>
+<u:Browse xmlns:u="urn:schemas-upnp-org:service:ContentDirectory:1">
+<ObjectID>season:4:2</ObjectID><BrowseFlag>BrowseDirectChildren</BrowseFlag><Filter>*</Filter>
+<StartingIndex>0</StartingIndex><RequestedCount>50</RequestedCount><SortCriteria></SortCriteria>
+</u:Browse></s:Body></s:Envelope>"#;
+        let arguments = soap_arguments(body);
+        assert_eq!(arguments["ObjectID"], "season:4:2");
+        assert_eq!(arguments["BrowseFlag"], "BrowseDirectChildren");
+        assert_eq!(arguments["RequestedCount"], "50");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("SOAPACTION", r#""urn:schemas-upnp-org:service:ContentDirectory:1#Browse""#.parse().unwrap());
+        assert_eq!(soap_action(&headers).as_deref(), Some("Browse"));
+    }
+}
@@ -0,0 +1,50 @@
+//! DLNA Media Server
+//!
+//! Exposes the library to UPnP AV renderers (smart TVs, consoles) that do
+//! not run a Homeflix client:
+//!
+//! - `ssdp`: multicast discovery (M-SEARCH replies and alive notifications)
+//! - `description`: device and service descriptions
+//! - `content_directory`: the browsable hierarchy (movies, series/seasons/
+//!   episodes, collections) rendered as DIDL-Lite
+//! - `handlers`: HTTP endpoints under `/dlna`
+//!
+//! Items point at the direct stream endpoint (`/v2/stream/:id?token=`), so
+//! DLNA playback is served by `StreamMediaUseCase` with range support like
+//! any other direct play.
+
+pub mod content_directory;
+pub mod description;
+pub mod handlers;
+pub mod ssdp;
+
+use sha2::{Digest, Sha256};
+
+/// Identity of the DLNA media server
+#[derive(Debug, Clone)]
+pub struct DlnaServer {
+    /// Name shown in the renderer's source list
+    pub friendly_name: String,
+    /// Device UUID (without the `uuid:` prefix)
+    pub uuid: String,
+    /// HTTP port the description and streams are served on
+    pub port: u16,
+}
+
+impl DlnaServer {
+    /// Creates the server identity
+    ///
+    /// The UUID is derived from the name so renderers recognise the server
+    /// across restarts.
+    pub fn new(friendly_name: String, port: u16) -> Self {
+        let hash = Sha256::digest(format!("homeflix-dlna:{}", friendly_name).as_bytes());
+        let hex = hex::encode(&hash[..16]);
+        let uuid = format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32]);
+        Self { friendly_name, uuid, port }
+    }
+
+    /// Unique device name as used in descriptions and SSDP
+    pub fn udn(&self) -> String {
+        format!("uuid:{}", self.uuid)
+    }
+}
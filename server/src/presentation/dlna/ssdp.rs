@@ -0,0 +1,181 @@
+//! SSDP Discovery
+//!
+//! Answers M-SEARCH requests on the UPnP multicast group and periodically
+//! announces the server with NOTIFY messages, advertising the root device,
+//! its UUID, the MediaServer device type and both services.
+
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket as StdUdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tracing::{debug, info, warn};
+
+use super::description::{CONNECTION_MANAGER_TYPE, CONTENT_DIRECTORY_TYPE, MEDIA_SERVER_TYPE};
+use super::DlnaServer;
+
+const SSDP_ADDR: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
+const SSDP_PORT: u16 = 1900;
+/// Advertisement lifetime sent in CACHE-CONTROL
+const MAX_AGE_SECS: u64 = 1800;
+/// Announcements are repeated well within the advertised lifetime
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(MAX_AGE_SECS / 2);
+
+/// SSDP responder and announcer
+pub struct SsdpService {
+    server: Arc<DlnaServer>,
+    /// Address renderers fetch the description from
+    advertise_ip: Ipv4Addr,
+}
+
+impl SsdpService {
+    pub fn new(server: Arc<DlnaServer>, advertise_ip: Ipv4Addr) -> Self {
+        Self { server, advertise_ip }
+    }
+
+    /// Runs until the multicast socket fails
+    pub async fn run(self) -> std::io::Result<()> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, SSDP_PORT)).await?;
+        socket.join_multicast_v4(SSDP_ADDR, Ipv4Addr::UNSPECIFIED)?;
+        info!(
+            "DLNA server '{}' announced at {}",
+            self.server.friendly_name, self.location()
+        );
+
+        let multicast = SocketAddr::V4(SocketAddrV4::new(SSDP_ADDR, SSDP_PORT));
+        let mut notify = tokio::time::interval(NOTIFY_INTERVAL);
+        let mut buf = [0u8; 2048];
+        loop {
+            tokio::select! {
+                _ = notify.tick() => {
+                    for target in self.targets() {
+                        if let Err(e) = socket.send_to(self.notify_message(&target).as_bytes(), multicast).await {
+                            warn!("Failed to send SSDP notification: {}", e);
+                        }
+                    }
+                }
+                received = socket.recv_from(&mut buf) => {
+                    let (len, from) = received?;
+                    let Some(search_target) = parse_search(&String::from_utf8_lossy(&buf[..len])) else {
+                        continue;
+                    };
+                    let matching: Vec<String> = self.targets()
+                        .into_iter()
+                        .filter(|t| search_target == "ssdp:all" || *t == search_target)
+                        .collect();
+                    if !matching.is_empty() {
+                        debug!("SSDP search for {} from {}", search_target, from);
+                    }
+                    for target in matching {
+                        if let Err(e) = socket.send_to(self.search_response(&target).as_bytes(), from).await {
+                            debug!("Failed to answer SSDP search from {}: {}", from, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn location(&self) -> String {
+        format!("http://{}:{}/dlna/description.xml", self.advertise_ip, self.server.port)
+    }
+
+    /// Notification types the server advertises
+    fn targets(&self) -> Vec<String> {
+        vec![
+            "upnp:rootdevice".to_string(),
+            self.server.udn(),
+            MEDIA_SERVER_TYPE.to_string(),
+            CONTENT_DIRECTORY_TYPE.to_string(),
+            CONNECTION_MANAGER_TYPE.to_string(),
+        ]
+    }
+
+    fn usn(&self, target: &str) -> String {
+        let udn = self.server.udn();
+        if target == udn {
+            udn
+        } else {
+            format!("{}::{}", udn, target)
+        }
+    }
+
+    fn notify_message(&self, target: &str) -> String {
+        format!(
+            "NOTIFY * HTTP/1.1\r\nHOST: {}:{}\r\nCACHE-CONTROL: max-age={}\r\nLOCATION: {}\r\nNT: {}\r\nNTS: ssdp:alive\r\nSERVER: {}\r\nUSN: {}\r\n\r\n",
+            SSDP_ADDR, SSDP_PORT, MAX_AGE_SECS, self.location(), target, server_header(), self.usn(target)
+        )
+    }
+
+    fn search_response(&self, target: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age={}\r\nDATE: {}\r\nEXT:\r\nLOCATION: {}\r\nSERVER: {}\r\nST: {}\r\nUSN: {}\r\n\r\n",
+            MAX_AGE_SECS,
+            chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT"),
+            self.location(),
+            server_header(),
+            target,
+            self.usn(target)
+        )
+    }
+}
+
+fn server_header() -> String {
+    format!("{}/1.0 UPnP/1.0 DLNADOC/1.50 homeflixd/{}", std::env::consts::OS, env!("CARGO_PKG_VERSION"))
+}
+
+/// Search target of an SSDP discovery request
+///
+/// Returns None for anything but an `ssdp:discover` M-SEARCH.
+fn parse_search(message: &str) -> Option<String> {
+    let mut lines = message.lines();
+    if !lines.next()?.trim().to_ascii_uppercase().starts_with("M-SEARCH") {
+        return None;
+    }
+
+    let mut target = None;
+    let mut discover = false;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_uppercase().as_str() {
+            "ST" => target = Some(value.to_string()),
+            "MAN" => discover = value.trim_matches('"') == "ssdp:discover",
+            _ => {}
+        }
+    }
+    target.filter(|_| discover)
+}
+
+/// Local IPv4 address of the interface multicast traffic leaves on
+///
+/// Connecting a UDP socket sends nothing; it only selects the route.
+pub fn local_ipv4() -> Option<Ipv4Addr> {
+    let socket = StdUdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((SSDP_ADDR, SSDP_PORT)).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        std::net::IpAddr::V4(ip) if !ip.is_unspecified() => Some(ip),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_handling() {
+        let request = "M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\nST: urn:schemas-upnp-org:device:MediaServer:1\r\n\r\n";
+        assert_eq!(parse_search(request).as_deref(), Some(MEDIA_SERVER_TYPE));
+        assert_eq!(parse_search("NOTIFY * HTTP/1.1\r\nNT: upnp:rootdevice\r\n\r\n"), None);
+        assert_eq!(parse_search("M-SEARCH * HTTP/1.1\r\nST: ssdp:all\r\n\r\n"), None);
+
+        let server = Arc::new(DlnaServer::new("Homeflix".to_string(), 3000));
+        let ssdp = SsdpService::new(server.clone(), Ipv4Addr::new(192, 168, 1, 10));
+        let response = ssdp.search_response(MEDIA_SERVER_TYPE);
+        assert!(response.contains("LOCATION: http://192.168.1.10:3000/dlna/description.xml\r\n"));
+        assert!(response.contains(&format!("USN: uuid:{}::{}\r\n", server.uuid, MEDIA_SERVER_TYPE)));
+        assert!(ssdp.search_response(&server.udn()).contains(&format!("USN: uuid:{}\r\n", server.uuid)));
+    }
+}
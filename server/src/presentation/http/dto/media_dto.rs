@@ -4,7 +4,11 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::application::use_cases::manage_series::{EpisodeNumber, NextEpisode};
 use crate::domain::entities::Media;
+use crate::domain::repositories::{Bookmark, ExtraArtwork};
+use crate::interfaces::external_services::VideoInfo;
+use crate::presentation::http::dto::pagination::Page;
 
 /// Media response DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +21,10 @@ pub struct MediaResponse {
     pub media_type: String,
     /// Title
     pub title: String,
+    /// Title in the original production language
+    pub original_title: Option<String>,
+    /// Language of title/overview when a localized variant was applied
+    pub language: Option<String>,
     /// Release year
     pub year: Option<i32>,
     /// TMDB ID
@@ -39,12 +47,29 @@ pub struct MediaResponse {
     pub poster_url: Option<String>,
     /// Backdrop URL
     pub backdrop_url: Option<String>,
+    /// Blurhash placeholder of the poster
+    pub poster_blurhash: Option<String>,
+    /// Blurhash placeholder of the backdrop
+    pub backdrop_blurhash: Option<String>,
+    /// Transparent title logo (local artwork URL)
+    pub logo_url: Option<String>,
+    /// Transparent character art (local artwork URL)
+    pub clearart_url: Option<String>,
+    /// Disc art (local artwork URL)
+    pub disc_url: Option<String>,
+    /// Trailer watch URL
+    pub trailer_url: Option<String>,
+    /// Trailer YouTube video key (for embedding)
+    pub trailer_key: Option<String>,
     /// Rating
     pub rating: Option<f32>,
     /// Is watched
     pub is_watched: bool,
     /// Current position
     pub current_position: i64,
+    /// Scene bookmarks of the requesting user (media detail only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<Bookmark>,
     /// Created at timestamp (ISO 8601)
     pub created_at: String,
     /// Updated at timestamp (ISO 8601)
@@ -58,6 +83,8 @@ impl From<Media> for MediaResponse {
             file_path: media.file_path,
             media_type: media.media_type.as_str().to_string(),
             title: media.title,
+            original_title: media.original_title,
+            language: None,
             year: media.release_date.as_ref().and_then(|d| d.split('-').next()).and_then(|y| y.parse().ok()),
             tmdb_id: media.tmdb_id,
             season: media.season,
@@ -69,15 +96,38 @@ impl From<Media> for MediaResponse {
             overview: media.overview,
             poster_url: media.poster_url,
             backdrop_url: media.backdrop_url,
+            poster_blurhash: blurhash_placeholder(media.poster_blurhash),
+            backdrop_blurhash: blurhash_placeholder(media.backdrop_blurhash),
+            logo_url: None,
+            clearart_url: None,
+            disc_url: None,
+            trailer_key: media.trailer_url.as_deref()
+                .and_then(VideoInfo::youtube_key_from_url)
+                .map(|k| k.to_string()),
+            trailer_url: media.trailer_url,
             rating: media.rating,
             is_watched: media.is_watched,
             current_position: media.current_position,
+            bookmarks: Vec::new(),
             created_at: media.created_at.to_rfc3339(),
             updated_at: media.updated_at.to_rfc3339(),
         }
     }
 }
 
+impl MediaResponse {
+    /// Sets the additional artwork URLs, pointing at the local artwork endpoint
+    pub fn with_extra_artwork(mut self, artwork: &ExtraArtwork) -> Self {
+        let local = |url: &Option<String>, kind: &str| {
+            url.as_ref().map(|_| format!("/v2/images/{}/{}", self.id, kind))
+        };
+        self.logo_url = local(&artwork.logo_url, "logo");
+        self.clearart_url = local(&artwork.clearart_url, "clearart");
+        self.disc_url = local(&artwork.disc_url, "disc");
+        self
+    }
+}
+
 /// Library media response DTO for grouped library views
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryMediaResponse {
@@ -87,6 +137,8 @@ pub struct LibraryMediaResponse {
     pub overview: Option<String>,
     pub poster_url: Option<String>,
     pub backdrop_url: Option<String>,
+    pub poster_blurhash: Option<String>,
+    pub backdrop_blurhash: Option<String>,
     pub trailer_url: Option<String>,
     pub duration: Option<i32>,
     pub release_date: Option<String>,
@@ -105,6 +157,8 @@ pub struct LibraryMediaResponse {
     pub content_warnings: Option<String>,
     pub current_position: i64,
     pub is_watched: bool,
+    /// Episodes added in one batch (recently added series rows only)
+    pub episode_count: Option<usize>,
 }
 
 impl LibraryMediaResponse {
@@ -116,6 +170,8 @@ impl LibraryMediaResponse {
             overview: media.overview,
             poster_url: media.poster_url,
             backdrop_url: media.backdrop_url,
+            poster_blurhash: blurhash_placeholder(media.poster_blurhash),
+            backdrop_blurhash: blurhash_placeholder(media.backdrop_blurhash),
             trailer_url: media.trailer_url,
             duration: media.duration_seconds,
             release_date: media.release_date,
@@ -130,20 +186,32 @@ impl LibraryMediaResponse {
             tmdb_id: media.tmdb_id,
             original_title: media.original_title,
             rating: media.rating,
-            content_rating: media.content_rating,
+            content_rating: content_rating(media.content_rating),
             content_warnings: media.content_warnings,
             current_position: media.current_position,
             is_watched: media.is_watched,
+            episode_count: None,
         }
     }
 }
 
+/// Drops the empty hash stored for artwork that cannot be hashed
+pub(crate) fn blurhash_placeholder(hash: Option<String>) -> Option<String> {
+    hash.filter(|h| !h.is_empty())
+}
+
+/// Drops the empty rating stored for titles TMDB has no certification for
+pub(crate) fn content_rating(rating: Option<String>) -> Option<String> {
+    rating.filter(|r| !r.is_empty())
+}
+
 /// Grouped library response DTO
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GroupedLibraryResponse {
     pub recent: Vec<LibraryMediaResponse>,
     pub continue_watching: Vec<LibraryMediaResponse>,
-    pub categories: HashMap<String, Vec<LibraryMediaResponse>>,
+    /// One page of each genre row
+    pub categories: HashMap<String, Page<LibraryMediaResponse>>,
 }
 
 /// Scan request DTO
@@ -166,6 +234,77 @@ pub struct ScanResponse {
     pub duration_secs: u64,
 }
 
+/// Media detail query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct MediaQuery {
+    /// Preferred metadata language (overrides Accept-Language)
+    pub language: Option<String>,
+    /// User whose bookmarks are included and whose parental controls
+    /// apply (default: "default")
+    pub user: Option<String>,
+}
+
+/// Library listing query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct LibraryQuery {
+    /// User whose parental controls apply (default: "default")
+    pub user: Option<String>,
+}
+
+/// Localized metadata refresh query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct RefreshLocalizationQuery {
+    /// Language to fetch (defaults to the configured TMDB language)
+    pub language: Option<String>,
+}
+
+/// Localized metadata response DTO
+#[derive(Debug, Serialize)]
+pub struct LocalizedMetadataResponse {
+    /// Media ID
+    pub media_id: i64,
+    /// Language tag
+    pub language: String,
+    /// Localized title
+    pub title: String,
+    /// Localized overview
+    pub overview: Option<String>,
+}
+
+/// Trailer / extra video response DTO
+#[derive(Debug, Serialize)]
+pub struct TrailerResponse {
+    /// Site-specific video key (YouTube video ID)
+    pub key: String,
+    /// Video title
+    pub name: String,
+    /// Hosting site
+    pub site: String,
+    /// Video type (Trailer, Teaser, Featurette, ...)
+    #[serde(rename = "type")]
+    pub video_type: String,
+    /// Whether the video is official
+    pub official: bool,
+    /// Video language (ISO 639-1)
+    pub language: Option<String>,
+    /// Public watch URL
+    pub url: Option<String>,
+}
+
+impl From<VideoInfo> for TrailerResponse {
+    fn from(video: VideoInfo) -> Self {
+        Self {
+            url: video.watch_url(),
+            key: video.key,
+            name: video.name,
+            site: video.site,
+            video_type: video.video_type,
+            official: video.official,
+            language: video.language,
+        }
+    }
+}
+
 /// Manual identify request DTO
 #[derive(Debug, Deserialize)]
 pub struct ManualIdentifyRequest {
@@ -173,6 +312,27 @@ pub struct ManualIdentifyRequest {
     pub tmdb_id: i64,
 }
 
+/// Next episode response DTO
+#[derive(Debug, Serialize)]
+pub struct NextEpisodeResponse {
+    /// Episode to play next
+    pub media: MediaResponse,
+    /// Episodes skipped because they are not in the library
+    pub missing_episodes: Vec<EpisodeNumber>,
+    /// Seasons skipped because they are not in the library
+    pub missing_seasons: Vec<i32>,
+}
+
+impl From<NextEpisode> for NextEpisodeResponse {
+    fn from(next: NextEpisode) -> Self {
+        Self {
+            media: MediaResponse::from(next.media),
+            missing_episodes: next.missing_episodes,
+            missing_seasons: next.missing_seasons,
+        }
+    }
+}
+
 /// Manual identify response DTO
 #[derive(Debug, Serialize)]
 pub struct ManualIdentifyResponse {
@@ -1,3 +1,4 @@
 pub mod media_dto;
 pub mod series_dto;
 pub mod collection_dto;
+pub mod pagination;
@@ -0,0 +1,127 @@
+//! Pagination DTOs
+//!
+//! Page and sort parameters shared by list endpoints, and the envelope their
+//! pages are returned in.
+
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::application::services::media_filters::SortOrder;
+
+/// Items per page when `limit` is not given
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page size accepted
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Page and sort query parameters
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PageQuery {
+    /// Page number, starting at 1 (default: 1)
+    pub page: Option<usize>,
+    /// Items per page (default: 50, max: 500)
+    pub limit: Option<usize>,
+    /// Sort key; each endpoint lists the keys it accepts
+    pub sort: Option<String>,
+    /// Sort direction (default depends on the key)
+    pub order: Option<SortOrder>,
+}
+
+/// A page of a list
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Items on all pages
+    pub total: usize,
+    pub page: usize,
+    pub limit: usize,
+}
+
+impl PageQuery {
+    /// Requested page, starting at 1
+    pub fn page(&self) -> usize {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    /// Requested page size, within 1..=MAX_PAGE_SIZE
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+    }
+
+    /// Checks the sort key against the keys an endpoint accepts
+    pub fn sort_key(&self, accepted: &[&str]) -> Result<Option<&str>, (StatusCode, String)> {
+        match self.sort.as_deref() {
+            None => Ok(None),
+            Some(key) if accepted.contains(&key) => Ok(Some(key)),
+            Some(key) => Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown sort '{}', expected one of: {}", key, accepted.join(", ")),
+            )),
+        }
+    }
+
+    /// Sorts items with a comparison for the ascending order, reversed when
+    /// `order=desc` or, without `order`, when `descending` is the default
+    pub fn sort_by<T>(&self, items: &mut [T], descending: bool, compare: impl Fn(&T, &T) -> Ordering) {
+        let descending = self.order.map_or(descending, |order| order == SortOrder::Desc);
+        items.sort_by(|a, b| if descending { compare(b, a) } else { compare(a, b) });
+    }
+
+    /// Items on the pages before the requested one
+    pub fn offset(&self) -> usize {
+        (self.page() - 1).saturating_mul(self.limit())
+    }
+
+    /// The requested page of all items
+    pub fn paginate<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        let items = items.into_iter().skip(self.offset()).take(self.limit()).collect();
+        Page { items, total, page: self.page(), limit: self.limit() }
+    }
+
+    /// The requested page, read on its own, of `total` items
+    pub fn page_of<T>(&self, items: Vec<T>, total: i64) -> Page<T> {
+        Page { items, total: total.max(0) as usize, page: self.page(), limit: self.limit() }
+    }
+}
+
+impl<T> Page<T> {
+    /// Converts the items of the page
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            limit: self.limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_and_sort() {
+        let query = PageQuery { page: Some(2), limit: Some(3), ..Default::default() };
+        let page = query.paginate((1..=8).collect::<Vec<_>>());
+        assert_eq!((page.items, page.total, page.page, page.limit), (vec![4, 5, 6], 8, 2, 3));
+        assert!(query.paginate(vec![1]).items.is_empty());
+
+        let defaults = PageQuery { page: Some(0), limit: Some(100_000), ..Default::default() };
+        assert_eq!((defaults.page(), defaults.limit()), (1, MAX_PAGE_SIZE));
+        assert_eq!(query.offset(), 3);
+        assert_eq!(query.page_of(vec![4, 5], 5).total, 5);
+
+        let last = PageQuery { page: Some(usize::MAX), ..Default::default() };
+        assert_eq!(last.offset(), usize::MAX);
+        assert!(last.paginate(vec![1, 2, 3]).items.is_empty());
+
+        let query = PageQuery { sort: Some("title".to_string()), order: Some(SortOrder::Desc), ..Default::default() };
+        assert_eq!(query.sort_key(&["title", "added"]).unwrap(), Some("title"));
+        assert!(query.sort_key(&["added"]).is_err());
+        let mut items = vec![2, 3, 1];
+        query.sort_by(&mut items, false, |a, b| a.cmp(b));
+        assert_eq!(items, vec![3, 2, 1]);
+    }
+}
@@ -4,7 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 use crate::domain::entities::{Media, Series};
-use crate::presentation::http::dto::media_dto::LibraryMediaResponse;
+use crate::domain::repositories::ExtraArtwork;
+use crate::presentation::http::dto::media_dto::{blurhash_placeholder, content_rating, LibraryMediaResponse};
 
 /// Series response DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +22,10 @@ pub struct SeriesResponse {
     pub poster_url: Option<String>,
     /// Backdrop URL
     pub backdrop_url: Option<String>,
+    /// Blurhash placeholder of the poster
+    pub poster_blurhash: Option<String>,
+    /// Blurhash placeholder of the backdrop
+    pub backdrop_blurhash: Option<String>,
     /// Confidence score
     pub confidence_score: f32,
     /// Verification status
@@ -37,6 +42,8 @@ pub struct SeriesResponse {
     pub total_episodes: Option<i32>,
     /// Rating
     pub rating: Option<f32>,
+    /// Content rating (e.g., "TV-14")
+    pub content_rating: Option<String>,
     /// Created at timestamp (ISO 8601)
     pub created_at: String,
     /// Updated at timestamp (ISO 8601)
@@ -52,6 +59,8 @@ impl From<Series> for SeriesResponse {
             overview: series.overview,
             poster_url: series.poster_url,
             backdrop_url: series.backdrop_url,
+            poster_blurhash: blurhash_placeholder(series.poster_blurhash),
+            backdrop_blurhash: blurhash_placeholder(series.backdrop_blurhash),
             confidence_score: series.confidence_score.value(),
             verification_status: series.verification_status.as_str().to_string(),
             first_air_date: series.first_air_date,
@@ -60,6 +69,7 @@ impl From<Series> for SeriesResponse {
             total_seasons: series.total_seasons,
             total_episodes: series.total_episodes,
             rating: series.rating,
+            content_rating: content_rating(series.content_rating),
             created_at: series.created_at.to_rfc3339(),
             updated_at: series.updated_at.to_rfc3339(),
         }
@@ -80,7 +90,12 @@ pub struct SeriesInfo {
     pub title: String,
     pub overview: Option<String>,
     pub poster_url: Option<String>,
+    pub poster_blurhash: Option<String>,
     pub tmdb_id: Option<i64>,
+    /// Transparent title logo (local artwork URL)
+    pub logo_url: Option<String>,
+    /// Transparent character art (local artwork URL)
+    pub clearart_url: Option<String>,
 }
 
 impl From<&Series> for SeriesInfo {
@@ -90,11 +105,26 @@ impl From<&Series> for SeriesInfo {
             title: series.title.clone(),
             overview: series.overview.clone(),
             poster_url: series.poster_url.clone(),
+            poster_blurhash: blurhash_placeholder(series.poster_blurhash.clone()),
             tmdb_id: series.tmdb_id,
+            logo_url: None,
+            clearart_url: None,
         }
     }
 }
 
+impl SeriesInfo {
+    /// Sets the additional artwork URLs, pointing at the local artwork endpoint
+    pub fn with_extra_artwork(mut self, artwork: &ExtraArtwork) -> Self {
+        let local = |url: &Option<String>, kind: &str| {
+            url.as_ref().map(|_| format!("/v2/images/{}/{}?type=series", self.id, kind))
+        };
+        self.logo_url = local(&artwork.logo_url, "logo");
+        self.clearart_url = local(&artwork.clearart_url, "clearart");
+        self
+    }
+}
+
 /// Series details response with episodes grouped by season
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SeriesDetailsResponse {
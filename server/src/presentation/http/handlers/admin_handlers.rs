@@ -0,0 +1,187 @@
+//! Admin Handlers
+//!
+//! HTTP handlers for library maintenance endpoints.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use crate::application::services::CollectionManager;
+use crate::application::use_cases::library_health::{LibraryHealthOptions, LibraryHealthUseCase};
+use crate::application::use_cases::remap_media_paths::{RemapMediaPathsUseCase, RemapRequest};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::MediaRepository;
+use crate::infrastructure::logging::LogLevelHandle;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Query parameters for the library issues report
+#[derive(Debug, Deserialize)]
+pub struct IssuesQuery {
+    /// Subtitle language to require (e.g. "hu"); omit to skip the subtitle check
+    pub subtitle_language: Option<String>,
+    /// Maximum issues listed per kind (default: 100)
+    pub limit: Option<usize>,
+    /// Identified items below this confidence are reported (default: 0.7)
+    pub min_confidence: Option<f32>,
+    /// Set to false to skip filesystem checks on large libraries
+    pub check_files: Option<bool>,
+}
+
+/// Get library health score and actionable issues
+///
+/// `GET /v2/admin/issues`
+pub async fn get_library_issues(
+    State(use_case): State<Arc<LibraryHealthUseCase>>,
+    Query(query): Query<IssuesQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let defaults = LibraryHealthOptions::default();
+    let options = LibraryHealthOptions {
+        subtitle_language: query.subtitle_language,
+        low_confidence_threshold: query.min_confidence.unwrap_or(defaults.low_confidence_threshold),
+        limit_per_kind: query.limit.unwrap_or(defaults.limit_per_kind),
+        check_files: query.check_files.unwrap_or(defaults.check_files),
+    };
+
+    match use_case.execute(options).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("Error building library health report: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Remove a media item from the library
+///
+/// `DELETE /v2/admin/media/:id`
+///
+/// Only the library entry goes; the file must already be gone from disk,
+/// otherwise the next scan would add it back.
+pub async fn delete_media(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let media = match media_repo.find_by_id(id).await {
+        Ok(Some(media)) => media,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, format!("Media {} not found", id))),
+        Err(e) => {
+            tracing::error!("Error loading media {}: {}", id, e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()));
+        }
+    };
+    if tokio::fs::try_exists(&media.file_path).await.unwrap_or(false) {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("{} still exists; delete the file before removing it from the library", media.file_path),
+        ));
+    }
+
+    if let Err(e) = media_repo.delete(id).await {
+        tracing::error!("Error deleting media {}: {}", id, e);
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()));
+    }
+    auditor.record(
+        AuditEvent::new(AuditAction::Deletion, format!("media:{}", id))
+            .with_details(format!("Removed {} from the library", media.file_path)),
+    ).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Query parameters for the collection reconcile
+#[derive(Debug, Deserialize)]
+pub struct ReconcileQuery {
+    /// Only report collections that are out of sync
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Recompute collection counts and item availability from the library
+///
+/// `POST /v2/admin/collections/reconcile`
+pub async fn reconcile_collections(
+    State(collection_manager): State<Arc<CollectionManager>>,
+    Query(query): Query<ReconcileQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    match collection_manager.reconcile_counts(query.dry_run).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("Error reconciling collections: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Request body for a media path remap
+#[derive(Debug, Deserialize)]
+pub struct RemapPathsRequest {
+    /// Old path prefix (e.g. "/mnt/old")
+    pub from: String,
+    /// New path prefix (e.g. "/mnt/new")
+    pub to: String,
+    /// Only preview the rewritten paths (default: true)
+    pub dry_run: Option<bool>,
+}
+
+/// Rewrite the path prefix of media after the library was moved
+///
+/// `POST /v2/admin/media/remap-paths`
+///
+/// Previews by default; send `"dry_run": false` to apply.
+pub async fn remap_media_paths(
+    State(use_case): State<Arc<RemapMediaPathsUseCase>>,
+    Json(body): Json<RemapPathsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let request = RemapRequest {
+        from: body.from,
+        to: body.to,
+        dry_run: body.dry_run.unwrap_or(true),
+    };
+
+    match use_case.execute(request).await {
+        Ok(report) => Ok(Json(report)),
+        Err(ApplicationError::Domain(DomainError::InvalidInput(msg))) => Err((StatusCode::BAD_REQUEST, msg)),
+        Err(e) => {
+            tracing::error!("Error remapping media paths: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Request body for a log filter change
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    /// Filter directives in `RUST_LOG` syntax
+    /// (e.g. "info,homeflixd::infrastructure::external::tmdb=debug")
+    pub filter: String,
+}
+
+/// Get the active log filter
+///
+/// `GET /v2/admin/log-level`
+pub async fn get_log_level(State(log_levels): State<Arc<LogLevelHandle>>) -> impl IntoResponse {
+    Json(json!({ "filter": log_levels.current() }))
+}
+
+/// Change the log filter until the next restart
+///
+/// `PUT /v2/admin/log-level`
+pub async fn set_log_level(
+    State(log_levels): State<Arc<LogLevelHandle>>,
+    auditor: Auditor,
+    Json(body): Json<LogLevelRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    match log_levels.set(&body.filter) {
+        Ok(filter) => {
+            auditor.record(AuditEvent::new(AuditAction::SettingsChange, "log-level").with_details(format!("Log filter set to '{}'", filter))).await;
+            Ok(Json(json!({ "filter": filter })))
+        }
+        Err(e) => Err((StatusCode::BAD_REQUEST, format!("Invalid log filter: {}", e))),
+    }
+}
@@ -0,0 +1,117 @@
+//! Audit Handlers
+//!
+//! `GET /v2/admin/audit` lists the audit log, and [`Auditor`] lets other
+//! handlers record audited actions with the caller and client IP.
+//!
+//! Entries are written by [`AuditLogHandler`](crate::application::handlers::AuditLogHandler)
+//! and kept for `AUDIT_RETENTION_DAYS`.
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRef, FromRequestParts, Query, State},
+    http::{request::Parts, Extensions, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::application::services::Caller;
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::AuditLogRepository;
+use crate::infrastructure::messaging::InMemoryEventBus;
+use crate::interfaces::messaging::EventBus;
+use crate::presentation::http::dto::pagination::PageQuery;
+
+/// Records audit events of a request
+///
+/// Extracted by handlers of audited actions; fills in the caller and the
+/// client IP of each event.
+pub struct Auditor {
+    bus: Option<Arc<InMemoryEventBus>>,
+    actor: Option<String>,
+    ip: Option<String>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Auditor
+where
+    S: Send + Sync,
+    Option<Arc<InMemoryEventBus>>: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            bus: Option::<Arc<InMemoryEventBus>>::from_ref(state),
+            actor: parts.extensions.get::<Caller>().map(actor),
+            ip: client_ip(&parts.extensions),
+        })
+    }
+}
+
+impl Auditor {
+    /// Publishes an audit event of this request
+    pub async fn record(&self, mut event: AuditEvent) {
+        event.actor = event.actor.or_else(|| self.actor.clone());
+        event.ip = event.ip.or_else(|| self.ip.clone());
+        publish(self.bus.as_deref(), event).await;
+    }
+}
+
+/// Publishes an audit event
+pub(crate) async fn publish(bus: Option<&InMemoryEventBus>, event: AuditEvent) {
+    if let Some(bus) = bus {
+        if let Err(e) = bus.publish(event).await {
+            tracing::warn!("Failed to publish audit event: {}", e);
+        }
+    }
+}
+
+/// Client IP address of a request (needs the connect info of the server)
+pub(crate) fn client_ip(extensions: &Extensions) -> Option<String> {
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+}
+
+/// How a caller appears in the audit and access logs
+pub(crate) fn actor(caller: &Caller) -> String {
+    match caller {
+        Caller::Admin => "admin".to_string(),
+        Caller::Device(device) => format!("device:{}", device.id),
+    }
+}
+
+/// Filter of the audit log
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    /// Only entries of this action (e.g. "auth_failed")
+    pub action: Option<AuditAction>,
+}
+
+/// List the audit log, newest first
+///
+/// `GET /v2/admin/audit`
+///
+/// Takes `page`, `limit` and `action`.
+///
+/// # Responses
+/// - 200: A page of entries
+/// - 401/403: Authentication is enabled and the caller is not the admin
+pub async fn list_audit_log(
+    State(audit_log): State<Arc<dyn AuditLogRepository>>,
+    Query(query): Query<AuditQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let offset = u32::try_from(page.offset()).unwrap_or(u32::MAX);
+
+    let (entries, total) = audit_log
+        .find_page(query.action, offset, page.limit() as u32)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(page.page_of(entries, total as i64)))
+}
@@ -0,0 +1,114 @@
+//! Auth Handlers
+//!
+//! HTTP handlers for device API keys:
+//!
+//! - `GET /v2/auth/devices`
+//! - `POST /v2/auth/devices`
+//! - `DELETE /v2/auth/devices/:id`
+//!
+//! With `API_SECRET` set, issuing and revoking keys needs the shared secret
+//! (the router layers `admin_middleware` on them); devices can only list.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::{ApiKeyService, Caller, StreamUrlSigner};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::DeviceKey;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::streaming_handlers::DEFAULT_USER;
+use crate::presentation::http::problem::ApiError;
+
+/// Request body for issuing a device key
+#[derive(Debug, Deserialize)]
+pub struct CreateDeviceRequest {
+    /// Device name ("Living room tablet")
+    pub name: String,
+    /// User the device streams as (default: "default")
+    pub user: Option<String>,
+}
+
+/// A new device with its key
+#[derive(Debug, Serialize)]
+pub struct CreatedDeviceResponse {
+    #[serde(flatten)]
+    pub device: DeviceKey,
+    /// The API key; it is shown only once
+    pub key: String,
+}
+
+/// List the devices with an API key
+pub async fn list_devices(
+    State(api_keys): State<Arc<ApiKeyService>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let devices = api_keys.list().await?;
+    Ok(Json(devices))
+}
+
+/// Issue an API key for a device
+///
+/// # Responses
+/// - 201: The device and its key (shown only once)
+/// - 400: Empty or too long name
+/// - 403: Called with a device key instead of the shared secret
+pub async fn create_device(
+    State(api_keys): State<Arc<ApiKeyService>>,
+    auditor: Auditor,
+    Json(request): Json<CreateDeviceRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (device, key) = api_keys.create(&request.name, request.user.as_deref().unwrap_or(DEFAULT_USER)).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::Login, format!("device:{}", device.id))
+            .with_details(format!("API key issued for '{}' of user {}", device.name, device.user_id)),
+    ).await;
+    Ok((StatusCode::CREATED, Json(CreatedDeviceResponse { device, key })))
+}
+
+/// Revoke the API key of a device
+///
+/// Requests with the key, and the stream tokens it was issued, are rejected
+/// right away.
+pub async fn revoke_device(
+    State(api_keys): State<Arc<ApiKeyService>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    api_keys.revoke(id).await?;
+    let sessions = signer.revoke_device(id).await?;
+    tracing::info!("Revoked {} stream token session(s) of device {}", sessions, id);
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("device:{}", id)).with_details("API key revoked")).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// User a request acts as: a device's own user, otherwise `requested`
+/// (default: "default")
+///
+/// Devices asking for another user are rejected.
+pub(crate) fn caller_user(caller: Option<&Caller>, requested: Option<&str>) -> Result<String, ApiError> {
+    match caller.and_then(Caller::device_user) {
+        Some(user) if requested.is_some_and(|requested| requested != user) => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "wrong_user",
+            format!("This device acts as user {}", user),
+        )),
+        Some(user) => Ok(user.to_string()),
+        None => Ok(requested.unwrap_or(DEFAULT_USER).to_string()),
+    }
+}
+
+/// Rejects device keys when authentication is enabled
+pub(crate) fn require_admin(api_keys: &ApiKeyService, caller: Option<&Caller>) -> Result<(), ApiError> {
+    match caller {
+        Some(Caller::Device(_)) => Err(ApiError::new(StatusCode::FORBIDDEN, "admin_required", "This needs the shared secret")),
+        None if api_keys.is_enabled() => Err(ApiError::new(StatusCode::UNAUTHORIZED, "unauthorized", "Authentication required")),
+        _ => Ok(()),
+    }
+}
+
@@ -0,0 +1,208 @@
+//! Bookmark Handlers
+//!
+//! HTTP handlers for scene bookmarks: moments of a media item a user marked,
+//! with an optional note, to jump back to later. A device key only sees its
+//! own user's bookmarks.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::services::Caller;
+use crate::domain::entities::Media;
+use crate::domain::repositories::{Bookmark, BookmarkRepository, MediaRepository};
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+
+/// Longest accepted note, in characters
+const MAX_NOTE_CHARS: usize = 500;
+
+/// Query parameters selecting the user of a bookmark
+#[derive(Debug, Deserialize)]
+pub struct BookmarkQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Request body for adding a bookmark
+#[derive(Debug, Deserialize)]
+pub struct CreateBookmarkRequest {
+    /// Position in seconds
+    pub position_seconds: f64,
+    pub note: Option<String>,
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Request body for changing a bookmark (omitted fields are kept, an empty
+/// note removes it)
+#[derive(Debug, Deserialize)]
+pub struct UpdateBookmarkRequest {
+    pub position_seconds: Option<f64>,
+    pub note: Option<String>,
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// List all bookmarks of a user, newest first
+///
+/// GET /v2/bookmarks?user=
+pub async fn list_user_bookmarks(
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<BookmarkQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let bookmarks = bookmark_repo
+        .find_by_user(&user)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(bookmarks))
+}
+
+/// List the bookmarks of a user in a media item, by position
+///
+/// GET /v2/media/:id/bookmarks?user=
+pub async fn list_bookmarks(
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(media_id): Path<i64>,
+    Query(query): Query<BookmarkQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let bookmarks = bookmark_repo
+        .find_by_media(&user, media_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(bookmarks))
+}
+
+/// Bookmark a moment of a media item
+///
+/// POST /v2/media/:id/bookmarks
+///
+/// # Responses
+/// - 201: The new bookmark
+/// - 400: Position outside the media, or a note that is too long
+/// - 404: Media not found
+pub async fn create_bookmark(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(media_id): Path<i64>,
+    Json(request): Json<CreateBookmarkRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    let media = find_media(&media_repo, media_id).await?;
+    check_position(&media, request.position_seconds)?;
+    let note = clean_note(request.note.as_deref())?;
+
+    let bookmark = bookmark_repo
+        .create(&user, media_id, request.position_seconds, note.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(bookmark)))
+}
+
+/// Move a bookmark or change its note
+///
+/// PATCH /v2/media/:id/bookmarks/:bookmark
+pub async fn update_bookmark(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path((media_id, bookmark_id)): Path<(i64, i64)>,
+    Json(request): Json<UpdateBookmarkRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = &caller_user(caller.as_deref(), request.user.as_deref())?;
+    let bookmark = find_bookmark(&bookmark_repo, user, media_id, bookmark_id).await?;
+
+    let position_seconds = request.position_seconds.unwrap_or(bookmark.position_seconds);
+    check_position(&find_media(&media_repo, media_id).await?, position_seconds)?;
+    let note = match request.note.as_deref() {
+        Some(note) => clean_note(Some(note))?,
+        None => bookmark.note,
+    };
+
+    let updated = bookmark_repo
+        .update(user, bookmark_id, position_seconds, note.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Bookmark {} not found", bookmark_id)))?;
+
+    Ok(Json(updated))
+}
+
+/// Remove a bookmark
+///
+/// DELETE /v2/media/:id/bookmarks/:bookmark?user=
+pub async fn delete_bookmark(
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path((media_id, bookmark_id)): Path<(i64, i64)>,
+    Query(query): Query<BookmarkQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = &caller_user(caller.as_deref(), query.user.as_deref())?;
+    find_bookmark(&bookmark_repo, user, media_id, bookmark_id).await?;
+    bookmark_repo
+        .delete(user, bookmark_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_media(media_repo: &Arc<dyn MediaRepository>, media_id: i64) -> Result<Media, (StatusCode, String)> {
+    media_repo
+        .find_by_id(media_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Media {} not found", media_id)))
+}
+
+/// Gets a bookmark of the user, if it belongs to the media item
+async fn find_bookmark(
+    bookmark_repo: &Arc<dyn BookmarkRepository>,
+    user: &str,
+    media_id: i64,
+    bookmark_id: i64,
+) -> Result<Bookmark, (StatusCode, String)> {
+    bookmark_repo
+        .find(user, bookmark_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .filter(|b| b.media_id == media_id)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Bookmark {} not found", bookmark_id)))
+}
+
+/// Rejects positions before the start or past the end of the media
+fn check_position(media: &Media, position_seconds: f64) -> Result<(), (StatusCode, String)> {
+    if !position_seconds.is_finite() || position_seconds < 0.0 {
+        return Err((StatusCode::BAD_REQUEST, "Position must be non-negative seconds".to_string()));
+    }
+    if let Some(duration) = media.duration_seconds.filter(|&d| d > 0) {
+        if position_seconds > duration as f64 {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Position {:.0}s is past the end of the media ({}s)", position_seconds, duration),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Trims a note; blank notes are dropped
+fn clean_note(note: Option<&str>) -> Result<Option<String>, (StatusCode, String)> {
+    let note = note.map(str::trim).filter(|n| !n.is_empty());
+    if note.is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS) {
+        return Err((StatusCode::BAD_REQUEST, format!("Note is longer than {} characters", MAX_NOTE_CHARS)));
+    }
+    Ok(note.map(str::to_string))
+}
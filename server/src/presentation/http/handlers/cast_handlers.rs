@@ -0,0 +1,423 @@
+//! Cast Handlers
+//!
+//! Chromecast support. A sender app asks the server for a load request and
+//! hands it to the default media receiver, which fetches the stream itself
+//! and therefore cannot send an Authorization header; the URLs it gets are
+//! signed and expire instead (see [`StreamUrlSigner`]).
+//!
+//! - `POST /v2/cast/:id/load` - Cast-ready media information with a signed URL
+//! - `GET /v2/cast/play/:token/video` - Original file (direct play)
+//! - `GET /v2/cast/play/:token/master.m3u8` - HLS master playlist
+//! - `GET /v2/cast/play/:token/:session/:variant/index.m3u8`
+//! - `GET /v2/cast/play/:token/:session/:variant/:segment`
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::{
+    Caller, ClientCapabilities, HlsSessionManager, LoudnessNormalizer, PlaybackDecisionService, PlaybackMethod,
+    ParentalControlService, PlaybackQos, StreamSessionRegistry, StreamUrlSigner, TokenError,
+};
+use crate::application::use_cases::extract_subtitle::ExtractSubtitleUseCase;
+use crate::application::use_cases::stream_media::StreamMediaUseCase;
+use crate::domain::entities::Media;
+use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::infrastructure::subtitle::SubtitleStore;
+use crate::interfaces::external_services::VideoAnalyzer;
+use crate::presentation::http::problem::ApiError;
+use super::auth_handlers::caller_user;
+use super::hls_handlers::{self, HlsQuery, PlaylistQuery};
+use super::parental_control_handlers::ensure_allowed;
+use super::streaming_handlers::{self, StreamQuery};
+
+/// Cast metadata types (`chrome.cast.media.MetadataType`)
+const METADATA_MOVIE: u8 = 1;
+const METADATA_TV_SHOW: u8 = 2;
+
+/// Request body for a cast load
+#[derive(Debug, Default, Deserialize)]
+pub struct CastLoadRequest {
+    /// Audio track index (default: 0)
+    pub audio: Option<u32>,
+    /// Start position in seconds
+    #[serde(default)]
+    pub start: f64,
+    /// The receiver decodes HEVC and 4K (Chromecast Ultra, Google TV)
+    #[serde(default)]
+    pub hevc: bool,
+    /// User the stream session is counted for (default: "default")
+    pub user: Option<String>,
+    /// Cast device name, used as the stream session's client
+    pub device: Option<String>,
+}
+
+/// Load request for the sender to pass on to the receiver
+#[derive(Debug, Serialize)]
+pub struct CastLoadResponse {
+    pub media_id: i64,
+    pub method: PlaybackMethod,
+    /// `chrome.cast.media.MediaInfo`
+    pub media: CastMediaInfo,
+    /// Position to start playback at, in seconds
+    pub current_time: f64,
+    /// When the signed URL stops working
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Media information in the Cast SDK's shape
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CastMediaInfo {
+    /// Absolute, signed URL of the stream
+    pub content_id: String,
+    pub content_url: String,
+    pub content_type: String,
+    pub stream_type: &'static str,
+    /// Duration in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i32>,
+    pub metadata: CastMetadata,
+}
+
+/// `chrome.cast.media.MovieMediaMetadata` / `TvShowMediaMetadata`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CastMetadata {
+    pub metadata_type: u8,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub episode: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+    pub images: Vec<CastImage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CastImage {
+    pub url: String,
+}
+
+/// Prepare a media item for casting
+///
+/// Files the default receiver plays as they are (H.264 with AAC/MP3 in MP4)
+/// are served directly; anything else goes through HLS, which is always
+/// encoded to H.264/AAC.
+#[allow(clippy::too_many_arguments)]
+pub async fn load(
+    State(use_case): State<Arc<StreamMediaUseCase>>,
+    State(playback_decision): State<Arc<PlaybackDecisionService>>,
+    State(series_repository): State<Arc<dyn SeriesRepository>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    body: Option<Json<CastLoadRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut request = body.map(|Json(r)| r).unwrap_or_default();
+    let (media, _result) = use_case.prepare_stream(id).await
+        ?;
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    ensure_allowed(&parental, &user, &media).await?;
+    request.user = Some(user);
+
+    let audio = request.audio.unwrap_or(0);
+    let decision = playback_decision
+        .decide(&media.file_path, &cast_capabilities(request.hevc), audio as usize, None)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to decide cast playback for {}: {}", media.file_path, e);
+            ApiError::internal("Failed to analyze video")
+        })?;
+
+    let signed = signer.sign(id);
+    let base = format!("{}/v2/cast/play/{}", external_base_url(&headers), signed.token);
+    // The user's parental controls are checked again when the receiver plays
+    let mut params = String::new();
+    for (name, value) in [("user", &request.user), ("device", &request.device)] {
+        if let Some(value) = value {
+            params.push_str(&format!("&{}={}", name, urlencoding::encode(value)));
+        }
+    }
+    let (content_id, content_type) = match decision.method {
+        PlaybackMethod::DirectPlay => {
+            let url = format!("{}/video", base);
+            let url = match params.strip_prefix('&') {
+                Some(params) => format!("{}?{}", url, params),
+                None => url,
+            };
+            (url, "video/mp4".to_string())
+        }
+        PlaybackMethod::Remux | PlaybackMethod::Transcode => {
+            (format!("{}/master.m3u8?audio={}{}", base, audio, params), "application/x-mpegurl".to_string())
+        }
+    };
+
+    let series_title = match media.series_id {
+        Some(series_id) => series_repository.find_by_id(series_id).await
+            .map_err(ApiError::from)?
+            .map(|s| s.title),
+        None => None,
+    };
+
+    tracing::info!("Cast load for media {}: {:?} ({:?})", id, decision.method, decision.reasons);
+
+    Ok(Json(CastLoadResponse {
+        media_id: id,
+        method: decision.method,
+        media: CastMediaInfo {
+            content_url: content_id.clone(),
+            content_id,
+            content_type,
+            stream_type: "BUFFERED",
+            duration: media.duration_seconds,
+            metadata: cast_metadata(&media, series_title),
+        },
+        current_time: request.start.max(0.0),
+        expires_at: signed.expires_at,
+    }))
+}
+
+/// Original file behind a signed URL
+#[allow(clippy::too_many_arguments)]
+pub async fn play_video(
+    State(use_case): State<Arc<StreamMediaUseCase>>,
+    State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(playback_qos): State<Arc<PlaybackQos>>,
+    State(loudness): State<Arc<LoudnessNormalizer>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(token): Path<String>,
+    query: Query<StreamQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = streaming_handlers::stream_media(
+        State(use_case),
+        State(video_analyzer),
+        State(stream_sessions),
+        State(playback_qos),
+        State(loudness),
+        State(parental),
+        Path(id),
+        query,
+        None,
+        None,
+        headers,
+    ).await?;
+    Ok(with_cors(response))
+}
+
+/// HLS master playlist behind a signed URL
+///
+/// Variant and segment URLs are relative, so they stay under the token.
+#[allow(clippy::too_many_arguments)]
+pub async fn play_master_playlist(
+    State(use_case): State<Arc<StreamMediaUseCase>>,
+    State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(token): Path<String>,
+    query: Query<HlsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = hls_handlers::master_playlist(
+        State(use_case),
+        State(video_analyzer),
+        State(subtitle_store),
+        State(hls_sessions),
+        State(stream_sessions),
+        State(parental),
+        Path(id),
+        query,
+        None,
+        None,
+        headers,
+    ).await?;
+    Ok(with_cors(response.into_response()))
+}
+
+/// HLS media playlist behind a signed URL
+pub async fn play_media_playlist(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    Path((token, session_id, variant)): Path<(String, String, String)>,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = hls_handlers::media_playlist(
+        State(hls_sessions),
+        Path((id, session_id, variant)),
+        Query(PlaylistQuery { token: None }),
+    ).await?;
+    Ok(with_cors(response.into_response()))
+}
+
+/// HLS segment behind a signed URL
+pub async fn play_segment(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(playback_qos): State<Arc<PlaybackQos>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    Path((token, session_id, variant, segment)): Path<(String, String, String, String)>,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = hls_handlers::segment(
+        State(hls_sessions),
+        State(playback_qos),
+        State(stream_sessions),
+        Path((id, session_id, variant, segment)),
+    ).await?;
+    Ok(with_cors(response.into_response()))
+}
+
+/// HLS subtitle playlist behind a signed URL
+pub async fn play_subtitle_playlist(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    Path((token, session_id, index)): Path<(String, String, usize)>,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = hls_handlers::subtitle_playlist(
+        State(hls_sessions),
+        Path((id, session_id, index)),
+        Query(PlaylistQuery { token: None }),
+    ).await?;
+    Ok(with_cors(response.into_response()))
+}
+
+/// HLS subtitle segment behind a signed URL
+pub async fn play_subtitle_segment(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(extract_use_case): State<Arc<ExtractSubtitleUseCase>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    Path((token, session_id, index, segment)): Path<(String, String, usize, String)>,
+) -> Result<Response, ApiError> {
+    let id = verify(&signer, &token)?;
+    let response = hls_handlers::subtitle_segment(
+        State(hls_sessions),
+        State(media_repo),
+        State(subtitle_store),
+        State(extract_use_case),
+        Path((id, session_id, index, segment)),
+    ).await?;
+    Ok(with_cors(response.into_response()))
+}
+
+/// What the default media receiver plays without help
+///
+/// MKV is not supported, and HEVC and 4K only on newer devices.
+fn cast_capabilities(hevc: bool) -> ClientCapabilities {
+    let mut capabilities = ClientCapabilities {
+        max_width: Some(1920),
+        max_height: Some(1080),
+        hls: true,
+        ..ClientCapabilities::default()
+    };
+    if hevc {
+        capabilities.video_codecs.push("hevc".to_string());
+        capabilities.max_width = Some(3840);
+        capabilities.max_height = Some(2160);
+    }
+    capabilities
+}
+
+fn cast_metadata(media: &Media, series_title: Option<String>) -> CastMetadata {
+    let images = media.poster_url.iter()
+        .map(|url| CastImage { url: url.clone() })
+        .collect();
+    if media.is_episode() {
+        CastMetadata {
+            metadata_type: METADATA_TV_SHOW,
+            title: media.title.clone(),
+            series_title,
+            season: media.season,
+            episode: media.episode,
+            release_date: media.release_date.clone(),
+            images,
+        }
+    } else {
+        CastMetadata {
+            metadata_type: METADATA_MOVIE,
+            title: media.title.clone(),
+            series_title: None,
+            season: None,
+            episode: None,
+            release_date: media.release_date.clone(),
+            images,
+        }
+    }
+}
+
+/// Server address as the sender reached it
+///
+/// The receiver fetches URLs on its own, so they must be absolute; behind a
+/// reverse proxy the forwarded scheme and host apply.
+fn external_base_url(headers: &HeaderMap) -> String {
+    let header_value = |name: &str| {
+        headers.get(name)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let scheme = header_value("x-forwarded-proto").unwrap_or_else(|| "http".to_string());
+    let host = header_value("x-forwarded-host")
+        .or_else(|| header_value(header::HOST.as_str()))
+        .unwrap_or_else(|| "localhost".to_string());
+    format!("{}://{}", scheme, host)
+}
+
+fn verify(signer: &StreamUrlSigner, token: &str) -> Result<i64, ApiError> {
+    signer.verify(token).map_err(|e| match e {
+        TokenError::Expired => ApiError::new(StatusCode::FORBIDDEN, "link_expired", "Stream link expired"),
+        _ => ApiError::new(StatusCode::FORBIDDEN, "invalid_link", "Invalid stream link"),
+    })
+}
+
+/// Receivers fetch from their own origin without credentials
+fn with_cors(mut response: Response) -> Response {
+    response.headers_mut().insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+    response
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_base_url() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(external_base_url(&headers), "http://localhost");
+
+        headers.insert(header::HOST, "192.168.1.10:3000".parse().unwrap());
+        assert_eq!(external_base_url(&headers), "http://192.168.1.10:3000");
+
+        headers.insert("x-forwarded-proto", "https, http".parse().unwrap());
+        headers.insert("x-forwarded-host", "homeflix.example".parse().unwrap());
+        assert_eq!(external_base_url(&headers), "https://homeflix.example");
+
+        let capabilities = cast_capabilities(true);
+        assert!(capabilities.hls);
+        assert_eq!(capabilities.video_codecs, vec!["h264", "hevc"]);
+        assert_eq!(capabilities.max_height, Some(2160));
+    }
+}
@@ -1,18 +1,46 @@
 //! Collection Handlers
 //!
 //! HTTP handlers for collection operations using repository pattern.
+//!
+//! Custom collections are managed through [`CollectionManager`], which
+//! publishes the collection management events:
+//!
+//! - `POST /v2/collections`, `PUT|DELETE /v2/collections/:id`
+//! - `POST|PUT /v2/collections/:id/items` (add / reorder)
+//! - `DELETE /v2/collections/:id/items/:item`
+//! - `GET|PUT|DELETE /v2/collections/:id/poster`
+//!
+//! With `API_SECRET` set, all but the `GET`s need the shared secret.
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Multipart, Path, Query, State},
+    http::{header, StatusCode},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::info;
 
-use crate::domain::repositories::CollectionRepository;
+use crate::application::services::media_filters::SortOrder;
+use crate::application::services::{Caller, CollectionManager, ParentalControlService};
+use crate::domain::entities::{Collection, CollectionItem};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{CollectionOrder, CollectionRepository, MediaRepository};
+use crate::infrastructure::filesystem::CollectionPosterStore;
+use crate::presentation::http::dto::media_dto::LibraryQuery;
+use crate::presentation::http::dto::pagination::PageQuery;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
+use crate::presentation::http::problem::ApiError;
+
+/// Longest accepted collection name, in characters
+const MAX_NAME_CHARS: usize = 100;
+/// Longest accepted description, in characters
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+/// Largest accepted poster upload, in bytes
+pub const MAX_POSTER_BYTES: usize = 10 * 1024 * 1024;
 
 /// Collection summary for list view
 #[derive(Debug, Serialize)]
@@ -28,6 +56,22 @@ pub struct CollectionSummary {
     pub sort_mode: String,
 }
 
+impl From<Collection> for CollectionSummary {
+    fn from(c: Collection) -> Self {
+        Self {
+            id: c.id.unwrap_or(0),
+            name: c.name,
+            description: c.description,
+            poster_url: c.poster_url,
+            backdrop_url: c.backdrop_url,
+            total_items: c.total_items,
+            available_items: c.available_items,
+            collection_type: c.collection_type,
+            sort_mode: c.sort_mode,
+        }
+    }
+}
+
 /// Collection detail with items
 #[derive(Debug, Serialize)]
 pub struct CollectionDetail {
@@ -53,48 +97,107 @@ pub struct CollectionItemResponse {
     pub media_id: Option<i64>,
 }
 
-/// List all collections
+impl From<CollectionItem> for CollectionItemResponse {
+    fn from(item: CollectionItem) -> Self {
+        Self {
+            id: item.id,
+            tmdb_id: item.tmdb_id,
+            media_type: item.media_type,
+            title: item.title,
+            overview: item.overview,
+            poster_url: item.poster_url,
+            release_date: item.release_date,
+            timeline_order: item.timeline_order,
+            timeline_year: item.timeline_year,
+            timeline_notes: item.timeline_notes,
+            is_available: item.is_available,
+            media_id: item.media_id,
+        }
+    }
+}
+
+/// Request body for adding a custom collection
+#[derive(Debug, Deserialize)]
+pub struct CreateCollectionRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Movies and episodes to start with, in order
+    #[serde(default)]
+    pub media_ids: Vec<i64>,
+}
+
+/// Request body for changing a custom collection
+#[derive(Debug, Deserialize)]
+pub struct UpdateCollectionRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// "timeline" or "release" (default: unchanged)
+    pub sort_mode: Option<String>,
+}
+
+/// Request body for adding an item
+#[derive(Debug, Deserialize)]
+pub struct AddCollectionItemRequest {
+    /// Movie or episode to add
+    pub media_id: i64,
+    /// Place to insert at, from 0 (default: the end)
+    pub position: Option<usize>,
+}
+
+/// Request body for reordering the items
+#[derive(Debug, Deserialize)]
+pub struct ReorderCollectionItemsRequest {
+    /// Every item ID of the collection, in the new order
+    pub item_ids: Vec<i64>,
+}
+
+/// List collections, a page at a time
+///
+/// Sorted with `sort=name|size|completion` and `order=asc|desc`.
 pub async fn list_collections(
     State(collection_repo): State<Arc<dyn CollectionRepository>>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Query(page): Query<PageQuery>,
+) -> Result<impl IntoResponse, ApiError> {
     info!("Listing all collections");
+    let sort = page.sort_key(&["name", "size", "completion"])?;
 
-    let collections = collection_repo
-        .find_all()
+    let (order, descending) = match sort {
+        Some("size") => (CollectionOrder::Size, true),
+        Some("completion") => (CollectionOrder::Completion, true),
+        _ => (CollectionOrder::Name, false),
+    };
+    // Without `sort`, the listing keeps its own order
+    let descending = match (sort, page.order) {
+        (Some(_), Some(order)) => order == SortOrder::Desc,
+        _ => descending,
+    };
+    let (collections, total) = collection_repo
+        .find_page(order, descending, page.offset(), page.limit())
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::from)?;
 
-    let summaries: Vec<CollectionSummary> = collections
-        .into_iter()
-        .map(|c| CollectionSummary {
-            id: c.id.unwrap_or(0),
-            name: c.name,
-            description: c.description,
-            poster_url: c.poster_url,
-            backdrop_url: c.backdrop_url,
-            total_items: c.total_items,
-            available_items: c.available_items,
-            collection_type: c.collection_type,
-            sort_mode: c.sort_mode,
-        })
-        .collect();
-
-    Ok(Json(summaries))
+    Ok(Json(page.page_of(collections, total).map(CollectionSummary::from)))
 }
 
 /// Get collection by ID with items
+///
+/// Library items blocked by the user's parental controls are left out.
 pub async fn get_collection(
     State(collection_repo): State<Arc<dyn CollectionRepository>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Query(query): Query<LibraryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
     info!("Getting collection {}", id);
 
     // Get collection
     let collection = collection_repo
         .find_by_id(id)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, format!("Collection {} not found", id)))?;
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::not_found(format!("Collection {} not found", id)))?;
 
     let summary = CollectionSummary {
         id: collection.id.unwrap_or(0),
@@ -112,27 +215,252 @@ pub async fn get_collection(
     let collection_items = collection_repo
         .find_items(id)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::from)?;
+
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let mut blocked = HashSet::new();
+    if policy.is_restricted() {
+        for media_id in collection_items.iter().filter_map(|item| item.media_id) {
+            let Some(media) = media_repo
+                .find_by_id(media_id)
+                .await
+                .map_err(ApiError::from)?
+            else {
+                continue;
+            };
+            let allowed = parental
+                .retain_allowed(&policy, vec![media])
+                .await
+                .map_err(ApiError::from)?;
+            if allowed.is_empty() {
+                blocked.insert(media_id);
+            }
+        }
+    }
 
     let items: Vec<CollectionItemResponse> = collection_items
         .into_iter()
-        .map(|item| CollectionItemResponse {
-            id: item.id,
-            tmdb_id: item.tmdb_id,
-            media_type: item.media_type,
-            title: item.title,
-            overview: item.overview,
-            poster_url: item.poster_url,
-            release_date: item.release_date,
-            timeline_order: item.timeline_order,
-            timeline_year: item.timeline_year,
-            timeline_notes: item.timeline_notes,
-            is_available: item.is_available,
-            media_id: item.media_id,
-        })
+        .filter(|item| item.media_id.is_none_or(|media_id| !blocked.contains(&media_id)))
+        .map(CollectionItemResponse::from)
         .collect();
 
     let detail = CollectionDetail { summary, items };
 
     Ok(Json(detail))
 }
+
+/// Add a custom collection
+///
+/// POST /v2/collections
+///
+/// # Responses
+/// - 201: The new collection
+/// - 400: Empty or too long name or description, or media that is not a
+///   movie or episode
+/// - 404: Media not found
+pub async fn create_collection(
+    State(manager): State<Arc<CollectionManager>>,
+    Json(request): Json<CreateCollectionRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (name, description) = clean_text(&request.name, request.description.as_deref())?;
+    let collection = manager
+        .create_custom_collection(name, description, request.media_ids)
+        .await
+        ?;
+
+    Ok((StatusCode::CREATED, Json(CollectionSummary::from(collection))))
+}
+
+/// Rename a custom collection, or change its description or sort mode
+///
+/// PUT /v2/collections/:id
+///
+/// # Responses
+/// - 200: The changed collection
+/// - 400: Invalid name, description or sort mode, or not a custom collection
+/// - 404: Collection not found
+pub async fn update_collection(
+    State(manager): State<Arc<CollectionManager>>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateCollectionRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (name, description) = clean_text(&request.name, request.description.as_deref())?;
+    let collection = manager
+        .update_custom_collection(id, name, description, request.sort_mode)
+        .await
+        ?;
+
+    Ok(Json(CollectionSummary::from(collection)))
+}
+
+/// Remove a custom collection with its items and poster
+///
+/// DELETE /v2/collections/:id
+pub async fn delete_collection(
+    State(manager): State<Arc<CollectionManager>>,
+    State(posters): State<Arc<CollectionPosterStore>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    manager.delete_collection(id).await?;
+    if let Err(e) = posters.delete(id).await {
+        tracing::warn!("Failed to remove poster of collection {}: {}", id, e);
+    }
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("collection:{}", id)).with_details("Custom collection removed")).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add a movie or episode to a custom collection
+///
+/// POST /v2/collections/:id/items
+///
+/// # Responses
+/// - 201: The new item
+/// - 400: Media already in the collection, not a movie or episode, or not a
+///   custom collection
+/// - 404: Collection or media not found
+pub async fn add_collection_item(
+    State(manager): State<Arc<CollectionManager>>,
+    Path(id): Path<i64>,
+    Json(request): Json<AddCollectionItemRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let item = manager
+        .add_custom_item(id, request.media_id, request.position)
+        .await
+        ?;
+
+    Ok((StatusCode::CREATED, Json(CollectionItemResponse::from(item))))
+}
+
+/// Put the items of a custom collection in a new order
+///
+/// PUT /v2/collections/:id/items
+///
+/// # Responses
+/// - 200: The items in their new order
+/// - 400: `item_ids` does not list every item exactly once
+pub async fn reorder_collection_items(
+    State(manager): State<Arc<CollectionManager>>,
+    Path(id): Path<i64>,
+    Json(request): Json<ReorderCollectionItemsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let items = manager
+        .reorder_custom_items(id, &request.item_ids)
+        .await
+        ?;
+
+    Ok(Json(items.into_iter().map(CollectionItemResponse::from).collect::<Vec<_>>()))
+}
+
+/// Remove an item from a custom collection
+///
+/// DELETE /v2/collections/:id/items/:item
+pub async fn remove_collection_item(
+    State(manager): State<Arc<CollectionManager>>,
+    Path((id, item_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, ApiError> {
+    manager.remove_custom_item(id, item_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Upload the poster of a custom collection
+///
+/// PUT /v2/collections/:id/poster (multipart, `poster` field)
+///
+/// # Responses
+/// - 200: The collection, with `poster_url` pointing at the upload
+/// - 400: Missing field, or not a JPEG, PNG or WebP image
+/// - 413: Larger than 10 MB
+pub async fn upload_collection_poster(
+    State(manager): State<Arc<CollectionManager>>,
+    State(posters): State<Arc<CollectionPosterStore>>,
+    Path(id): Path<i64>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::bad_request(e.to_string()))?
+    {
+        if field.name() != Some("poster") {
+            continue;
+        }
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        if !CollectionPosterStore::accepts(&content_type) {
+            return Err(ApiError::bad_request("Poster must be a JPEG, PNG or WebP image"));
+        }
+        let bytes = field.bytes().await.map_err(|e| ApiError::bad_request(e.to_string()))?;
+        if bytes.len() > MAX_POSTER_BYTES {
+            return Err(ApiError::from((StatusCode::PAYLOAD_TOO_LARGE, "Poster must be at most 10 MB".to_string())));
+        }
+        upload = Some((content_type, bytes));
+    }
+    let (content_type, bytes) = upload.ok_or_else(|| ApiError::bad_request("Missing 'poster' field"))?;
+
+    // Only custom collections take a poster; checked before writing the file
+    let collection = manager.set_custom_poster(id, Some(poster_path(id))).await?;
+    posters.save(id, &content_type, &bytes).await.map_err(|e| {
+        tracing::error!("Failed to store poster of collection {}: {}", id, e);
+        ApiError::internal("Internal server error")
+    })?;
+
+    Ok(Json(CollectionSummary::from(collection)))
+}
+
+/// Get the uploaded poster of a collection
+///
+/// GET /v2/collections/:id/poster
+pub async fn get_collection_poster(
+    State(posters): State<Arc<CollectionPosterStore>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (bytes, content_type) = posters
+        .load(id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::not_found(format!("Collection {} has no uploaded poster", id)))?;
+
+    Ok(([(header::CONTENT_TYPE, content_type), (header::CACHE_CONTROL, "no-cache")], bytes))
+}
+
+/// Remove the uploaded poster of a custom collection
+///
+/// DELETE /v2/collections/:id/poster
+pub async fn delete_collection_poster(
+    State(manager): State<Arc<CollectionManager>>,
+    State(posters): State<Arc<CollectionPosterStore>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    manager.set_custom_poster(id, None).await?;
+    posters
+        .delete(id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn poster_path(id: i64) -> String {
+    format!("/v2/collections/{}/poster", id)
+}
+
+/// Trims the name and description
+fn clean_text(name: &str, description: Option<&str>) -> Result<(String, Option<String>), ApiError> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+        return Err(ApiError::bad_request(format!("Name must be 1 to {} characters", MAX_NAME_CHARS)));
+    }
+    let description = description.map(str::trim).filter(|d| !d.is_empty()).map(String::from);
+    if description.as_ref().is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_CHARS) {
+        return Err(ApiError::bad_request(format!(
+            "Description must be at most {} characters",
+            MAX_DESCRIPTION_CHARS
+        )));
+    }
+
+    Ok((name, description))
+}
+
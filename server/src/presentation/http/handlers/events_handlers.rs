@@ -0,0 +1,135 @@
+//! Event Handlers
+//!
+//! HTTP handlers for live server events and first-run bootstrap status.
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    Json,
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::application::services::{BootstrapTracker, LiveEvent, LiveEventBroadcaster};
+
+/// Recorded events replayed on reconnect at most
+const MAX_REPLAYED_EVENTS: usize = 1000;
+
+/// Live event stream parameters
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Comma-separated event types to receive (default: all)
+    pub types: Option<String>,
+    /// Replay recorded events after this ID, for clients that can't send
+    /// the `Last-Event-ID` header
+    pub last_event_id: Option<u64>,
+}
+
+/// Get first-run bootstrap status
+///
+/// Clients poll this on startup to show scan progress instead of an empty
+/// library while the initial scan runs.
+pub async fn get_bootstrap_status(
+    State(tracker): State<Arc<BootstrapTracker>>,
+) -> impl IntoResponse {
+    (StatusCode::OK, Json(tracker.snapshot()))
+}
+
+/// Stream live events (Server-Sent Events)
+///
+/// Sends the current bootstrap status on connect, followed by scan progress
+/// and newly identified media as they happen. Each SSE event is named after
+/// the event type and carries a JSON payload; `types` limits the stream to
+/// some event types.
+///
+/// Recorded events (identified media, scan and watch state changes) carry
+/// an ID. A client reconnecting with `Last-Event-ID` (sent by `EventSource`
+/// automatically) first gets the recorded events it missed.
+pub async fn stream_events(
+    State(broadcaster): State<Arc<LiveEventBroadcaster>>,
+    State(tracker): State<Arc<BootstrapTracker>>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let types: Option<Arc<HashSet<String>>> = query.types.as_deref().map(|types| {
+        Arc::new(types.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+    });
+    let wanted = move |event_type: &str| types.as_ref().is_none_or(|types| types.contains(event_type));
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .or(query.last_event_id);
+
+    // Subscribe before replaying, so nothing falls between the two
+    let receiver = broadcaster.subscribe();
+    let mut initial = Vec::new();
+    if wanted("bootstrap") {
+        initial.push(
+            Event::default()
+                .event("bootstrap")
+                .json_data(tracker.snapshot())
+                .unwrap_or_else(|_| Event::default().event("bootstrap")),
+        );
+    }
+    let mut replayed_up_to = last_event_id.unwrap_or(0);
+    if let Some(after) = last_event_id {
+        match broadcaster.replay(after, MAX_REPLAYED_EVENTS).await {
+            Ok(events) => {
+                for event in events {
+                    replayed_up_to = replayed_up_to.max(event.id.unwrap_or(0));
+                    if wanted(&event.event_type) {
+                        initial.push(sse_event(event));
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Failed to replay live events after {}: {}", after, e),
+        }
+    }
+
+    let live = stream::unfold(receiver, move |mut receiver| {
+        let wanted = wanted.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        let replayed = event.id.is_some_and(|id| id <= replayed_up_to);
+                        if replayed || !wanted(&event.event_type) {
+                            continue;
+                        }
+                        return Some((Ok(sse_event(event)), receiver));
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::debug!("Live event client lagged, skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+
+    let events = stream::iter(initial.into_iter().map(Ok)).chain(live);
+    Sse::new(events).keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+}
+
+/// SSE event named after the event type, with the event store ID if recorded
+fn sse_event(event: LiveEvent) -> Event {
+    let mut sse_event = Event::default()
+        .event(event.event_type)
+        .json_data(event.data)
+        .unwrap_or_else(|_| Event::default().comment("unserializable event"));
+    if let Some(id) = event.id {
+        sse_event = sse_event.id(id.to_string());
+    }
+    sse_event
+}
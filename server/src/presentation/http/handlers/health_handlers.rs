@@ -1,10 +1,17 @@
 //! Health Check Handlers
 //!
 //! HTTP handlers for health check endpoints.
+//!
+//! - `GET /health`, `GET /health/live` - Liveness (the process answers)
+//! - `GET /health/ready` - Readiness (database, schema and media directory)
 
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use serde_json::json;
+use std::sync::Arc;
+
+use crate::infrastructure::health::ReadinessProbe;
 
 /// Health check endpoint
 ///
@@ -16,3 +23,18 @@ pub async fn health_check() -> impl IntoResponse {
         "service": "homeflix-server"
     })))
 }
+
+/// Readiness endpoint
+///
+/// Returns 503 while a required dependency is unavailable, so orchestrators
+/// stop routing traffic here without restarting the process. The body lists
+/// every check either way.
+pub async fn readiness_check(State(probe): State<Arc<ReadinessProbe>>) -> impl IntoResponse {
+    let report = probe.check().await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, axum::Json(report))
+}
@@ -0,0 +1,326 @@
+//! HLS Handlers
+//!
+//! HTTP handlers for adaptive HLS streaming. Clients open a session via the
+//! master playlist and then follow its relative variant and segment URLs:
+//!
+//! - `GET /v2/stream/hls/:id/master.m3u8`
+//! - `GET /v2/stream/hls/:id/:session/:variant/index.m3u8`
+//! - `GET /v2/stream/hls/:id/:session/:variant/:segment`
+//! - `GET /v2/stream/hls/:id/:session/subs/:index/index.m3u8`
+//! - `GET /v2/stream/hls/:id/:session/subs/:index/:segment`
+//! - `DELETE /v2/stream/hls/:id/:session`
+//!
+//! `GET /v2/stream/hls/sessions` lists active sessions. A `?token=` session
+//! token on a playlist request is added to the URIs in the playlist, so
+//! players can follow them without an `Authorization` header.
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension,
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::application::services::{HlsSessionInfo, HlsSessionManager, HlsSubtitle, PlaybackQos, StreamClaims, StreamMode, StreamRequest, StreamSessionRegistry, ParentalControlService, Caller};
+use crate::application::use_cases::extract_subtitle::ExtractSubtitleUseCase;
+use crate::application::use_cases::stream_media::StreamMediaUseCase;
+use crate::domain::repositories::MediaRepository;
+use crate::infrastructure::subtitle::{read_subtitle_file, SubtitleOptions, SubtitleStore};
+use crate::interfaces::external_services::VideoAnalyzer;
+use crate::shared::error::{ApplicationError, TranscodeError};
+use crate::presentation::http::handlers::parental_control_handlers::ensure_allowed;
+use crate::presentation::http::handlers::streaming_handlers::{open_stream_session, stream_user, subtitle_file};
+
+const PLAYLIST_CONTENT_TYPE: &str = "application/vnd.apple.mpegurl";
+
+/// Query parameters for the master playlist
+#[derive(Debug, Deserialize)]
+pub struct HlsQuery {
+    /// Audio track index (default: the user's remembered language, its
+    /// audio description version when the user prefers it)
+    pub audio: Option<u32>,
+    /// User the stream session is counted for (default: "default")
+    pub user: Option<String>,
+    /// Client device ID (default: the user agent)
+    pub device: Option<String>,
+    /// Session token, carried into the playlist's URIs
+    pub token: Option<String>,
+}
+
+/// Query parameters of the variant and subtitle playlists
+#[derive(Debug, Deserialize)]
+pub struct PlaylistQuery {
+    /// Session token, carried into the playlist's URIs
+    pub token: Option<String>,
+}
+
+/// Start an HLS session and return its master playlist
+///
+/// Every variant is encoded to H.264/AAC, so this works for clients that
+/// cannot decode the source codec (e.g. HEVC). External subtitles and
+/// embedded text tracks are offered as WebVTT renditions.
+#[allow(clippy::too_many_arguments)]
+pub async fn master_playlist(
+    State(use_case): State<Arc<StreamMediaUseCase>>,
+    State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(id): Path<i64>,
+    Query(query): Query<HlsQuery>,
+    claims: Option<Extension<StreamClaims>>,
+    caller: Option<Extension<Caller>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (media, _result) = use_case.prepare_stream(id).await
+        .map_err(map_application_error)?;
+    let user = stream_user(claims.as_ref(), caller.as_deref(), query.user.as_deref())?;
+    ensure_allowed(&parental, &user, &media).await?;
+
+    let analysis = video_analyzer.analyze(&media.file_path).await
+        .map_err(|e| {
+            tracing::error!("Failed to analyze video {}: {}", media.file_path, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to analyze video".to_string())
+        })?;
+
+    let audio = use_case
+        .select_audio_track(&user, &analysis.audio_tracks, query.audio.map(|a| a as usize))
+        .await
+        .map_err(map_application_error)?;
+
+    let session_id = hls_sessions
+        .create_session(
+            id,
+            media.file_path.clone(),
+            analysis.duration_seconds,
+            (analysis.width, analysis.height),
+            audio as u32,
+        )
+        .map_err(map_transcode_error)?;
+
+    // Same indices as /v2/subtitles/:media_id/:index
+    let external = subtitle_store.detector(id).discover(std::path::Path::new(&media.file_path));
+    let mut subtitles: Vec<HlsSubtitle> = external
+        .iter()
+        .enumerate()
+        .map(|(index, subtitle)| HlsSubtitle {
+            index,
+            name: subtitle.language_name.clone().unwrap_or_else(|| format!("Subtitle {}", index + 1)),
+            language: subtitle.language.clone(),
+            is_default: false,
+            forced: subtitle.forced,
+            hearing_impaired: subtitle.hearing_impaired,
+        })
+        .collect();
+    subtitles.extend(analysis.subtitle_tracks.iter().filter(|t| t.is_text()).map(|track| HlsSubtitle {
+        index: external.len() + track.index,
+        name: track.title.clone()
+            .or_else(|| track.language.clone())
+            .unwrap_or_else(|| format!("Track {}", track.index + 1)),
+        language: track.language.clone(),
+        is_default: track.is_default,
+        forced: track.is_forced,
+        hearing_impaired: track.is_hearing_impaired,
+    }));
+    hls_sessions.set_subtitles(&session_id, subtitles).map_err(map_transcode_error)?;
+
+    // Counted as one transcode until its segments stop being requested
+    let client = query
+        .device
+        .or_else(|| headers.get(header::USER_AGENT).and_then(|h| h.to_str().ok()).map(|s| s.to_string()));
+    let opened = open_stream_session(&stream_sessions, StreamRequest {
+        media_id: id,
+        user,
+        client,
+        mode: StreamMode::Transcode,
+        bitrate_kbps: None,
+        hls_session: Some(session_id.clone()),
+    }).await;
+    if let Err(e) = opened {
+        hls_sessions.stop_session(&session_id);
+        return Err(e.into());
+    }
+
+    let playlist = hls_sessions.master_playlist(&session_id).map_err(map_transcode_error)?;
+
+    Ok(playlist_response(playlist, query.token.as_deref()))
+}
+
+/// Get the media playlist of a variant
+pub async fn media_playlist(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    Path((_id, session_id, variant)): Path<(i64, String, String)>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let playlist = hls_sessions.media_playlist(&session_id, &variant).map_err(map_transcode_error)?;
+    Ok(playlist_response(playlist, query.token.as_deref()))
+}
+
+/// Get a segment, waiting for the transcoder if needed
+pub async fn segment(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(playback_qos): State<Arc<PlaybackQos>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    Path((_id, session_id, variant, segment)): Path<(i64, String, String, String)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let index = segment
+        .strip_prefix("seg_")
+        .and_then(|s| s.strip_suffix(".ts"))
+        .and_then(|s| s.parse::<u32>().ok())
+        .ok_or((StatusCode::NOT_FOUND, format!("Unknown segment: {}", segment)))?;
+
+    let (media_id, path) = hls_sessions.segment(&session_id, &variant, index).await
+        .map_err(map_transcode_error)?;
+    // Segments arrive as separate requests; keep background work held back
+    playback_qos.touch(media_id);
+
+    let bytes = tokio::fs::read(&path).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    stream_sessions.record_hls(&session_id, bytes.len());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "video/mp2t".parse().unwrap());
+    headers.insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+    Ok((headers, bytes))
+}
+
+/// Get the media playlist of a subtitle rendition
+pub async fn subtitle_playlist(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    Path((_id, session_id, index)): Path<(i64, String, usize)>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let playlist = hls_sessions.subtitle_playlist(&session_id, index).map_err(map_transcode_error)?;
+    Ok(playlist_response(playlist, query.token.as_deref()))
+}
+
+/// Get a WebVTT segment of a subtitle rendition
+///
+/// The subtitle is read (or extracted) on the first request of a session.
+pub async fn subtitle_segment(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(extract_use_case): State<Arc<ExtractSubtitleUseCase>>,
+    Path((id, session_id, index, segment)): Path<(i64, String, usize, String)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let segment_index = segment
+        .strip_prefix("sub_")
+        .and_then(|s| s.strip_suffix(".vtt"))
+        .and_then(|s| s.parse::<u32>().ok())
+        .ok_or((StatusCode::NOT_FOUND, format!("Unknown segment: {}", segment)))?;
+
+    let cues = match hls_sessions.subtitle_cues(&session_id, index).map_err(map_transcode_error)? {
+        Some(cues) => cues,
+        None => {
+            let (file_path, language) = subtitle_file(&media_repo, &subtitle_store, &extract_use_case, id, index).await?;
+            let options = SubtitleOptions::default().with_language(language);
+            let subtitle = read_subtitle_file(&file_path, &options).map_err(|e| {
+                tracing::error!("Failed to read subtitle {}: {}", file_path, e);
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read subtitle: {}", e))
+            })?;
+            hls_sessions.store_subtitle_cues(&session_id, index, subtitle.cues).map_err(map_transcode_error)?
+        }
+    };
+    let vtt = hls_sessions.subtitle_segment(&session_id, &cues, segment_index).map_err(map_transcode_error)?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, "text/vtt; charset=utf-8".parse().unwrap());
+    headers.insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+    Ok((headers, vtt))
+}
+
+/// Stop a session and delete its segments
+pub async fn stop_session(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    Path((_id, session_id)): Path<(i64, String)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    if !hls_sessions.stop_session(&session_id) {
+        return Err((StatusCode::NOT_FOUND, "Session not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List active sessions
+pub async fn list_sessions(
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+) -> Json<Vec<HlsSessionInfo>> {
+    Json(hls_sessions.sessions())
+}
+
+fn playlist_response(playlist: String, token: Option<&str>) -> impl IntoResponse {
+    let playlist = match token {
+        Some(token) => with_token(&playlist, token),
+        None => playlist,
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, PLAYLIST_CONTENT_TYPE.parse().unwrap());
+    headers.insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+    (headers, playlist)
+}
+
+fn map_transcode_error(e: TranscodeError) -> (StatusCode, String) {
+    match e {
+        TranscodeError::SessionNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        TranscodeError::InvalidRequest(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        TranscodeError::Timeout(_) => (StatusCode::SERVICE_UNAVAILABLE, e.to_string()),
+        _ => {
+            tracing::error!("HLS error: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Transcoding failed".to_string())
+        }
+    }
+}
+
+fn map_application_error(e: ApplicationError) -> (StatusCode, String) {
+    match e {
+        ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg)) => (StatusCode::NOT_FOUND, msg),
+        ApplicationError::Filesystem(crate::shared::error::FilesystemError::PathNotFound(msg)) => (StatusCode::NOT_FOUND, format!("File not found: {}", msg)),
+        _ => {
+            tracing::error!("Streaming error: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+        }
+    }
+}
+
+/// Adds `?token=` to the URI lines and `URI` attributes of a playlist
+fn with_token(playlist: &str, token: &str) -> String {
+    let query = format!("?token={}", urlencoding::encode(token));
+    let mut tagged = String::with_capacity(playlist.len());
+    for line in playlist.lines() {
+        if !line.is_empty() && !line.starts_with('#') {
+            tagged.push_str(line);
+            tagged.push_str(&query);
+        } else if let Some((before, uri)) = line.split_once("URI=\"") {
+            let (uri, after) = uri.split_once('"').unwrap_or((uri, ""));
+            tagged.push_str(&format!("{}URI=\"{}{}\"{}", before, uri, query, after));
+        } else {
+            tagged.push_str(line);
+        }
+        tagged.push('\n');
+    }
+    tagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_token() {
+        let master = "#EXTM3U\n\
+            #EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",URI=\"abc/subs/0/index.m3u8\"\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=800000,SUBTITLES=\"subs\"\n\
+            abc/480p/index.m3u8\n";
+        assert_eq!(
+            with_token(master, "v1.a+b"),
+            "#EXTM3U\n\
+            #EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",URI=\"abc/subs/0/index.m3u8?token=v1.a%2Bb\"\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=800000,SUBTITLES=\"subs\"\n\
+            abc/480p/index.m3u8?token=v1.a%2Bb\n"
+        );
+        assert_eq!(with_token("#EXTINF:6.000,\nseg_00000.ts\n#EXT-X-ENDLIST\n", "t"), "#EXTINF:6.000,\nseg_00000.ts?token=t\n#EXT-X-ENDLIST\n");
+    }
+}
@@ -3,29 +3,42 @@
 //! HTTP handlers for media operations.
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use crate::application::{IdentifyMediaUseCase, ScanLibraryUseCase};
+use crate::application::{IdentifyMediaUseCase, MetadataEnricher, ScanLibraryUseCase};
+use crate::application::use_cases::explain_identification::ExplainIdentificationUseCase;
+use crate::application::use_cases::organize_media::OrganizeMediaUseCase;
 use crate::application::use_cases::get_recently_added::{GetRecentlyAddedUseCase, RecentlyAddedItem};
-use crate::domain::entities::Series;
-use crate::domain::repositories::{MediaRepository, SeriesRepository, CollectionRepository, CreditsRepository, CreditEntry, CreditType};
+use crate::application::use_cases::manage_series::ManageSeriesUseCase;
+use crate::domain::entities::{Media, Series};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{MediaRepository, SeriesRepository, CollectionRepository, CreditsRepository, CreditEntry, CreditType, LocalizationRepository, ArtworkRepository, ArtworkOwner, BookmarkRepository, AccessibilityPreferenceRepository, ProfileRepository};
 use crate::domain::value_objects::MediaType;
 use crate::presentation::http::dto::media_dto::{
     GroupedLibraryResponse, LibraryMediaResponse, MediaResponse, ScanRequest, ScanResponse,
-    ManualIdentifyRequest, ManualIdentifyResponse,
+    ManualIdentifyRequest, ManualIdentifyResponse, MediaQuery, RefreshLocalizationQuery,
+    LocalizedMetadataResponse, TrailerResponse, LibraryQuery, NextEpisodeResponse, blurhash_placeholder, content_rating,
 };
-use crate::interfaces::external_services::{TmdbService, TmdbCreditsFetcher, Credits, CastMember, CrewMember, SimilarResult};
-use crate::shared::error::ApplicationError;
+use crate::presentation::http::dto::pagination::PageQuery;
+use crate::presentation::http::problem::ApiError;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::interfaces::external_services::{TmdbService, TmdbCreditsFetcher, TmdbSimilarFetcher, VideoInfo, Credits, CastMember, CrewMember, SimilarResult};
+use crate::shared::error::{ApplicationError, DomainError};
 use crate::infrastructure::messaging::in_memory_event_bus::InMemoryEventBus;
 use crate::interfaces::external_services::VideoAnalyzer;
-use crate::infrastructure::subtitle::SubtitleDetector;
+use crate::infrastructure::subtitle::SubtitleStore;
+use crate::application::services::{default_subtitle, Caller, ContentPolicy, MediaFilter, MediaFilterService, ParentalControlService, SubtitleChoice};
+use crate::presentation::http::handlers::parental_control_handlers::{content_policy, ensure_allowed};
+use crate::application::use_cases::download_subtitle::{DownloadSubtitleRequest, DownloadSubtitleUseCase};
+use crate::application::use_cases::stream_media::StreamMediaUseCase;
 
-fn series_to_library_media(series: &Series, created_at: &chrono::DateTime<chrono::Utc>) -> LibraryMediaResponse {
+pub(crate) fn series_to_library_media(series: &Series, created_at: &chrono::DateTime<chrono::Utc>) -> LibraryMediaResponse {
     let series_id = series.id.unwrap_or(0);
     LibraryMediaResponse {
         id: -series_id,
@@ -34,6 +47,8 @@ fn series_to_library_media(series: &Series, created_at: &chrono::DateTime<chrono
         overview: series.overview.clone(),
         poster_url: series.poster_url.clone(),
         backdrop_url: series.backdrop_url.clone(),
+        poster_blurhash: blurhash_placeholder(series.poster_blurhash.clone()),
+        backdrop_blurhash: blurhash_placeholder(series.backdrop_blurhash.clone()),
         trailer_url: None,
         duration: None,
         release_date: series.first_air_date.clone(),
@@ -48,43 +63,74 @@ fn series_to_library_media(series: &Series, created_at: &chrono::DateTime<chrono
         tmdb_id: series.tmdb_id,
         original_title: series.original_title.clone(),
         rating: series.rating,
-        content_rating: None,
+        content_rating: content_rating(series.content_rating.clone()),
         content_warnings: None,
         current_position: 0,
         is_watched: false,
+        episode_count: None,
     }
 }
 
 /// Grouped library for homeflix-web (movies + series, no episodes)
+///
+/// Each genre row is paged with `page` and `limit`; the rest of a row is
+/// listed by `/v2/media/all?genre=`. Titles blocked by the user's parental
+/// controls are left out.
 pub async fn list_grouped_library(
     State(media_repo): State<Arc<dyn MediaRepository>>,
     State(series_repo): State<Arc<dyn SeriesRepository>>,
     State(recently_added_use_case): State<Arc<GetRecentlyAddedUseCase>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<LibraryQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     const RECENT_LIMIT: usize = 10;
     const CONTINUE_WATCHING_LIMIT: usize = 20;
     // Fetch more items than needed since episodes collapse into series
     const FETCH_LIMIT: usize = 50;
 
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+
     // Use the GetRecentlyAddedUseCase for properly combined and sorted recent items
     let recent_items = recently_added_use_case
-        .execute(RECENT_LIMIT)
+        .execute(if policy.is_restricted() { FETCH_LIMIT } else { RECENT_LIMIT })
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // Convert RecentlyAddedItem to LibraryMediaResponse
     let recent: Vec<LibraryMediaResponse> = recent_items
         .into_iter()
+        .filter(|item| recently_added_allowed(&policy, item))
+        .take(RECENT_LIMIT)
         .map(|item| match item {
             RecentlyAddedItem::Movie { media, added_at: _ } => {
                 LibraryMediaResponse::from_media(media)
             }
-            RecentlyAddedItem::Series { series, added_at } => {
+            RecentlyAddedItem::Series {
+                series,
+                added_at,
+                season,
+                first_episode,
+                last_episode,
+                episode_count,
+                backdrop_url,
+            } => {
                 // Parse the added_at string back to DateTime for the series_to_library_media function
                 let created_at = chrono::DateTime::parse_from_rfc3339(&added_at)
                     .map(|dt| dt.with_timezone(&chrono::Utc))
                     .unwrap_or_else(|_| chrono::Utc::now());
-                series_to_library_media(&series, &created_at)
+                let mut response = series_to_library_media(&series, &created_at);
+                // One row per batch: show which episodes arrived
+                response.season_number = season;
+                response.episode_number = first_episode;
+                response.episode_end = last_episode.filter(|last| Some(*last) != first_episode);
+                response.episode_count = Some(episode_count);
+                if backdrop_url != series.backdrop_url {
+                    response.backdrop_url = backdrop_url;
+                    response.backdrop_blurhash = None;
+                }
+                response
             }
         })
         .collect();
@@ -95,6 +141,10 @@ pub async fn list_grouped_library(
         .find_in_progress(FETCH_LIMIT)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let in_progress_items = parental
+        .retain_allowed(&policy, in_progress_items)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // Collapse episodes into series for continue watching
     let mut continue_watching = Vec::new();
@@ -136,6 +186,10 @@ pub async fn list_grouped_library(
         .find_by_type(MediaType::Movie)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let movies = parental
+        .retain_allowed(&policy, movies)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let mut categories: HashMap<String, Vec<LibraryMediaResponse>> = HashMap::new();
 
@@ -165,17 +219,89 @@ pub async fn list_grouped_library(
         }
     }
 
+    let categories = categories
+        .into_iter()
+        .map(|(genre, items)| (genre, page.paginate(items)))
+        .collect();
+
     Ok(Json(GroupedLibraryResponse { recent, continue_watching, categories }))
 }
 
+/// Whether a recently added movie or series may be shown
+fn recently_added_allowed(policy: &ContentPolicy, item: &RecentlyAddedItem) -> bool {
+    match item {
+        RecentlyAddedItem::Movie { media, .. } => policy.allows_media(media),
+        RecentlyAddedItem::Series { series, .. } => policy.allows_series(series),
+    }
+}
+
 /// Get media by ID
+///
+/// Returns 403 if the user's parental controls block the item. Localized
+/// title and overview follow `?language=`, then the UI language of the
+/// user's active profile, then Accept-Language; `original_title` stays the
+/// one stored from TMDB.
+#[allow(clippy::too_many_arguments)]
 pub async fn get_media(
     State(use_case): State<Arc<IdentifyMediaUseCase<InMemoryEventBus>>>,
+    State(localization_repo): State<Arc<dyn LocalizationRepository>>,
+    State(artwork_repo): State<Arc<dyn ArtworkRepository>>,
+    State(bookmark_repo): State<Arc<dyn BookmarkRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
+    Query(query): Query<MediaQuery>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     match use_case.execute(id).await {
         Ok(result) => {
-            let response = MediaResponse::from(result.media);
+            let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+            ensure_allowed(&parental, &user, &result.media).await?;
+            let mut response = MediaResponse::from(result.media);
+            if let Some(artwork) = artwork_repo
+                .find(ArtworkOwner::Media, id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            {
+                response = response.with_extra_artwork(&artwork);
+            }
+            response.bookmarks = bookmark_repo
+                .find_by_media(&user, id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            // Overlay a stored localized variant for the preferred language, if any
+            let profile_language = match query.language {
+                Some(_) => None,
+                None => profiles
+                    .find_active(&user)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                    .and_then(|p| p.settings.ui_language),
+            };
+            let preferred = query.language.or(profile_language).or_else(|| preferred_language(&headers));
+            if let Some(language) = preferred {
+                let variants = localization_repo
+                    .find_by_media(id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+                let primary = language.split('-').next().unwrap_or(&language).to_lowercase();
+                let variant = variants
+                    .iter()
+                    .find(|v| v.language.eq_ignore_ascii_case(&language))
+                    .or_else(|| variants.iter().find(|v| {
+                        v.language.split('-').next().unwrap_or(&v.language).eq_ignore_ascii_case(&primary)
+                    }));
+                if let Some(variant) = variant {
+                    response.title = variant.title.clone();
+                    if variant.overview.is_some() {
+                        response.overview = variant.overview.clone();
+                    }
+                    response.language = Some(variant.language.clone());
+                }
+            }
+
             Ok(Json(response))
         }
         Err(ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg))) => {
@@ -188,26 +314,158 @@ pub async fn get_media(
     }
 }
 
-/// List all media
-pub async fn list_media(
-    State(use_case): State<Arc<IdentifyMediaUseCase<InMemoryEventBus>>>,
+/// Explain how a media item's filename parses
+///
+/// Returns the parsed fields with their confidence and lists the ones the
+/// parser was unsure about.
+pub async fn explain_identification(
+    State(use_case): State<Arc<ExplainIdentificationUseCase>>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    match use_case.list_all().await {
-        Ok(media_list) => {
-            let response: Vec<MediaResponse> = media_list
-                .into_iter()
-                .map(MediaResponse::from)
-                .collect();
-            Ok(Json(response))
+    match use_case.execute(id).await {
+        Ok(explanation) => Ok(Json(explanation)),
+        Err(ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg))) => {
+            Err((StatusCode::NOT_FOUND, msg))
         }
         Err(e) => {
-            tracing::error!("Error listing media: {}", e);
+            tracing::error!("Error explaining identification: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Query of the rename preview
+#[derive(Debug, serde::Deserialize)]
+pub struct RenamePreviewQuery {
+    /// Filename template (default: per media type)
+    pub template: Option<String>,
+}
+
+/// Preview the canonical filename of a media item
+///
+/// Renders the identified title and numbering with the filename template;
+/// nothing is renamed.
+pub async fn preview_rename(
+    State(use_case): State<Arc<OrganizeMediaUseCase>>,
+    Path(id): Path<i64>,
+    Query(query): Query<RenamePreviewQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    match use_case.preview(id, query.template.as_deref()).await {
+        Ok(proposal) => Ok(Json(proposal)),
+        Err(ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg))) => {
+            Err((StatusCode::NOT_FOUND, msg))
+        }
+        Err(ApplicationError::Domain(crate::shared::error::DomainError::InvalidInput(msg))) => {
+            Err((StatusCode::BAD_REQUEST, msg))
+        }
+        Err(e) => {
+            tracing::error!("Error previewing rename: {}", e);
             Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
         }
     }
 }
 
+/// Extracts the first language tag from an Accept-Language header
+fn preferred_language(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::ACCEPT_LANGUAGE)?.to_str().ok()?;
+    value
+        .split(',')
+        .map(|part| part.split(';').next().unwrap_or("").trim())
+        .find(|tag| !tag.is_empty() && *tag != "*")
+        .map(|tag| tag.to_string())
+}
+
+/// Re-fetch title and overview for a media item in a specific language
+///
+/// Uses `?language=` or falls back to the configured TMDB language.
+pub async fn refresh_localization(
+    State(enricher): State<Arc<MetadataEnricher>>,
+    Path(id): Path<i64>,
+    Query(query): Query<RefreshLocalizationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let language = query
+        .language
+        .or_else(|| enricher.default_language().map(|l| l.to_string()))
+        .ok_or_else(|| ApiError::bad_request("No language given and TMDB_LANGUAGE is not set"))?;
+
+    let localized = enricher.refresh_localization(id, &language).await?;
+    Ok(Json(LocalizedMetadataResponse {
+        media_id: localized.media_id,
+        language: localized.language,
+        title: localized.title,
+        overview: localized.overview,
+    }))
+}
+
+/// List media, a page at a time
+///
+/// `GET /v2/media/all` takes `page` and `limit`, the filters `genre`,
+/// `year_from`, `year_to`, `resolution`, `watched`, `min_rating` and
+/// `library`, and `sort=title|year|rating|added` with `order=asc|desc`.
+pub async fn list_media(
+    State(use_case): State<Arc<IdentifyMediaUseCase<InMemoryEventBus>>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    State(filters): State<Arc<MediaFilterService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<LibraryQuery>,
+    Query(filter): Query<MediaFilter>,
+    Query(page): Query<PageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    if policy.is_restricted() || filter.narrows() {
+        // Parental controls and filters look at every item
+        let media_list = allowed_media(&use_case, &parental, &policy).await?;
+        let media_list = filters.apply(media_list, &filter);
+        return Ok(Json(page.paginate(media_list).map(MediaResponse::from)));
+    }
+
+    let (order, descending) = filter.library_order();
+    let (media_list, total) = use_case
+        .list_page(order, descending, page.offset(), page.limit())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error listing media: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+        })?;
+    Ok(Json(page.page_of(media_list, total).map(MediaResponse::from)))
+}
+
+/// Facet counts of the library for filter chips
+///
+/// `GET /v2/media/facets` takes the same filters as `/v2/media/all`; each
+/// facet counts the items matching the other filters.
+pub async fn get_media_facets(
+    State(use_case): State<Arc<IdentifyMediaUseCase<InMemoryEventBus>>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    State(filters): State<Arc<MediaFilterService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<LibraryQuery>,
+    Query(filter): Query<MediaFilter>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let media_list = allowed_media(&use_case, &parental, &policy).await?;
+    Ok(Json(filters.facets(&media_list, &filter)))
+}
+
+/// All media the user's parental controls allow
+async fn allowed_media(
+    use_case: &IdentifyMediaUseCase<InMemoryEventBus>,
+    parental: &ParentalControlService,
+    policy: &ContentPolicy,
+) -> Result<Vec<Media>, (StatusCode, String)> {
+    let media_list = use_case.list_all().await.map_err(|e| {
+        tracing::error!("Error listing media: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+    })?;
+    parental
+        .retain_allowed(policy, media_list)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
 /// Scan library
+///
+/// With `API_SECRET` set, this needs the shared secret.
 pub async fn scan_library(
     State(use_case): State<Arc<ScanLibraryUseCase<InMemoryEventBus>>>,
     Json(request): Json<ScanRequest>,
@@ -238,6 +496,8 @@ pub struct AudioTrackResponse {
     pub channels: Option<u32>,
     pub title: Option<String>,
     pub is_default: bool,
+    /// Narrates the picture for blind and visually impaired viewers
+    pub audio_description: bool,
 }
 
 /// Subtitle track response DTO
@@ -249,10 +509,25 @@ pub struct SubtitleTrackResponse {
     pub language: Option<String>,
     /// Human-readable language name (e.g., "Magyar", "English")
     pub language_name: Option<String>,
-    /// Source of the subtitle: "external" (.srt file) or "embedded" (in video)
+    /// Source of the subtitle: "external" (.srt/.ass file) or "embedded" (in video)
     pub source: String,
     /// Whether this is the default subtitle track
     pub is_default: bool,
+    /// Only covers foreign-language dialogue and signs
+    pub forced: bool,
+    /// For the deaf and hard of hearing (SDH)
+    pub hearing_impaired: bool,
+}
+
+/// Query parameters for the track list
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct MediaTracksQuery {
+    /// Audio track the default subtitle is chosen for (default: the track
+    /// the stream would pick for the user)
+    pub audio: Option<usize>,
+    /// User whose subtitle languages and accessibility preferences apply
+    /// (default: the server's)
+    pub user: Option<String>,
 }
 
 /// Media tracks response DTO
@@ -266,11 +541,24 @@ pub struct MediaTracksResponse {
 }
 
 /// Get media tracks (audio/subtitle info) by ID
+///
+/// The default subtitle follows the user's first subtitle language (that of
+/// the active profile when set): a full subtitle when the audio is in another
+/// language, the forced one when the audio is in the user's language, an SDH
+/// one when the user prefers SDH.
+#[allow(clippy::too_many_arguments)]
 pub async fn get_media_tracks(
     State(media_repo): State<Arc<dyn MediaRepository>>,
     State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(download_use_case): State<Arc<DownloadSubtitleUseCase>>,
+    State(accessibility): State<Arc<dyn AccessibilityPreferenceRepository>>,
+    State(stream_use_case): State<Arc<StreamMediaUseCase>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
+    Query(query): Query<MediaTracksQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
     // Get media to find file path
     let media = media_repo
         .find_by_id(id)
@@ -287,6 +575,29 @@ pub async fn get_media_tracks(
             (StatusCode::INTERNAL_SERVER_ERROR, "Failed to analyze video".to_string())
         })?;
 
+    // Without a chosen track, the one the stream defaults to (active profile,
+    // remembered language, then the file's default)
+    let audio_index = match query.audio {
+        Some(index) => Some(index),
+        None => stream_use_case.select_audio_track(&user, &analysis.audio_tracks, None).await.ok(),
+    };
+    let audio_language = audio_index
+        .and_then(|index| analysis.audio_tracks.get(index))
+        .or_else(|| analysis.audio_tracks.iter().find(|t| t.is_default))
+        .or_else(|| analysis.audio_tracks.first())
+        .and_then(|t| t.language.clone());
+    let prefer_hearing_impaired = accessibility
+        .find(&user)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .is_some_and(|p| p.hearing_impaired_subtitles);
+    let languages_request = DownloadSubtitleRequest { media_id: id, user_id: Some(user.clone()), ..Default::default() };
+    let user_language = download_use_case.languages(&languages_request).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "en".to_string());
+
     let audio_tracks: Vec<AudioTrackResponse> = analysis.audio_tracks
         .into_iter()
         .enumerate()
@@ -297,11 +608,12 @@ pub async fn get_media_tracks(
             channels: track.channels,
             title: track.title,
             is_default: track.is_default,
+            audio_description: track.is_audio_description,
         })
         .collect();
 
-    // Discover external subtitle files (.srt)
-    let subtitle_detector = SubtitleDetector::new();
+    // Discover external subtitle files (.srt, .ass, .ssa), downloads included
+    let subtitle_detector = subtitle_store.detector(id);
     let video_path = std::path::Path::new(&media.file_path);
     let external_subtitles = subtitle_detector.discover(video_path);
 
@@ -317,6 +629,8 @@ pub async fn get_media_tracks(
             language_name: ext_sub.language_name,
             source: "external".to_string(),
             is_default: index == 0, // First subtitle is default
+            forced: ext_sub.forced,
+            hearing_impaired: ext_sub.hearing_impaired,
         });
         index += 1;
     }
@@ -329,10 +643,27 @@ pub async fn get_media_tracks(
             language_name: None, // Embedded subtitles don't have display names
             source: "embedded".to_string(),
             is_default: subtitle_tracks.is_empty() && embedded.is_default,
+            forced: embedded.is_forced,
+            hearing_impaired: embedded.is_hearing_impaired,
         });
         index += 1;
     }
 
+    // Language rules win over the first-subtitle default
+    let choices: Vec<SubtitleChoice> = subtitle_tracks
+        .iter()
+        .map(|t| SubtitleChoice {
+            language: t.language.as_deref(),
+            forced: t.forced,
+            hearing_impaired: t.hearing_impaired,
+        })
+        .collect();
+    if let Some(preferred) = default_subtitle(&choices, audio_language.as_deref(), &user_language, prefer_hearing_impaired) {
+        for (i, track) in subtitle_tracks.iter_mut().enumerate() {
+            track.is_default = i == preferred;
+        }
+    }
+
     Ok(Json(MediaTracksResponse {
         duration: analysis.duration_seconds,
         current_position: media.current_position,
@@ -480,10 +811,19 @@ pub async fn get_media_credits(
 }
 
 /// Get similar content for a media item
+///
+/// Returns 403 if the user's parental controls block the media item. For a
+/// restricted user only similar titles in the library that the controls
+/// allow are listed, since other titles have no known certification.
+#[allow(clippy::too_many_arguments)]
 pub async fn get_media_similar(
     State(media_repo): State<Arc<dyn MediaRepository>>,
-    State(tmdb_service): State<Arc<dyn TmdbService>>,
+    State(series_repo): State<Arc<dyn SeriesRepository>>,
+    State(tmdb_service): State<Arc<dyn TmdbSimilarFetcher>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     // Get media to find TMDB ID
     let media = media_repo
@@ -491,26 +831,154 @@ pub async fn get_media_similar(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Media {} not found", id)))?;
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    ensure_allowed(&parental, &user, &media).await?;
 
     let tmdb_id = media.tmdb_id
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "Media has no TMDB ID".to_string()))?;
 
     let media_type = if media.media_type.is_movie() { "movie" } else { "tv" };
 
-    match tmdb_service.fetch_similar(tmdb_id, media_type).await {
-        Ok(results) => Ok(Json(results)),
+    let results = match tmdb_service.fetch_similar(tmdb_id, media_type).await {
+        Ok(results) => results,
         Err(e) => {
             tracing::error!("Error getting similar content: {}", e);
-            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to get similar content".to_string()))
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to get similar content".to_string()));
+        }
+    };
+
+    let policy = parental
+        .policy(&user)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !policy.is_restricted() {
+        return Ok(Json(results));
+    }
+    let mut allowed: Vec<SimilarResult> = Vec::with_capacity(results.len());
+    for result in results {
+        let in_library = if result.media_type == "tv" {
+            series_repo
+                .find_by_tmdb_id(result.id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .is_some_and(|series| policy.allows_series(&series))
+        } else {
+            let movies: Vec<Media> = media_repo
+                .find_by_tmdb_id(result.id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .into_iter()
+                .filter(|m| m.media_type.is_movie())
+                .collect();
+            !parental
+                .retain_allowed(&policy, movies)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .is_empty()
+        };
+        if in_library {
+            allowed.push(result);
         }
     }
+    Ok(Json(allowed))
+}
+
+/// Get trailers and extras for a media item (proxied from TMDB)
+///
+/// Episodes get the trailers of their series. Also stores the best trailer
+/// on a movie if it has none yet.
+///
+/// # Responses
+/// - 200: The trailers and extras
+/// - 400: The movie, or the series of the episode, has no TMDB ID
+/// - 403: Blocked by the user's parental controls
+/// - 502: TMDB failed
+pub async fn get_media_trailers(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(series_repo): State<Arc<dyn SeriesRepository>>,
+    State(tmdb_service): State<Arc<dyn TmdbService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut media = media_repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", id)))?;
+    ensure_allowed(&parental, &caller_user(caller.as_deref(), query.user.as_deref())?, &media).await?;
+
+    // TMDB keeps videos per movie and per show, not per episode
+    let (tmdb_id, media_type) = if media.media_type.is_movie() {
+        (media.tmdb_id.ok_or_else(|| ApiError::bad_request("Media has no TMDB ID"))?, "movie")
+    } else {
+        let series_id = media.series_id.ok_or_else(|| ApiError::bad_request("Media is not in a series"))?;
+        let series = series_repo
+            .find_by_id(series_id)
+            .await?
+            .ok_or_else(|| ApiError::not_found(format!("Series {} not found", series_id)))?;
+        (series.tmdb_id.ok_or_else(|| ApiError::bad_request("Series has no TMDB ID"))?, "tv")
+    };
+
+    let videos = tmdb_service.fetch_videos(tmdb_id, media_type).await.map_err(|e| {
+        tracing::error!("Error getting trailers: {}", e);
+        ApiError::new(StatusCode::BAD_GATEWAY, "upstream_error", "Failed to get trailers")
+    })?;
+
+    if media.trailer_url.is_none() && media.media_type.is_movie() {
+        if let Some(url) = VideoInfo::best_trailer(&videos).and_then(|v| v.watch_url()) {
+            media.trailer_url = Some(url);
+            if let Err(e) = media_repo.update(&media).await {
+                tracing::warn!("Failed to store trailer for media {}: {}", id, e);
+            }
+        }
+    }
+
+    let response: Vec<TrailerResponse> = videos.into_iter().map(TrailerResponse::from).collect();
+    Ok(Json(response))
+}
+
+/// Get the episode to play after an episode (auto-play next)
+///
+/// `GET /v2/media/:id/next[?user=]`
+///
+/// # Responses
+/// - 200: The next episode in aired order, with the episodes and seasons
+///   skipped because they are not in the library
+/// - 204: Last episode in the library, or a special
+/// - 400: The media is not an episode
+/// - 403: The next episode is blocked by the user's parental controls
+pub async fn get_next_episode(
+    State(use_case): State<Arc<ManageSeriesUseCase>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
+) -> Result<axum::response::Response, (StatusCode, String)> {
+    let next = use_case.next_episode(id).await.map_err(|e| match e {
+        ApplicationError::Domain(DomainError::NotFound(msg)) => (StatusCode::NOT_FOUND, msg),
+        ApplicationError::Domain(DomainError::InvalidInput(msg)) => (StatusCode::BAD_REQUEST, msg),
+        e => {
+            tracing::error!("Error resolving next episode of {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+        }
+    })?;
+    let Some(next) = next else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    ensure_allowed(&parental, &caller_user(caller.as_deref(), query.user.as_deref())?, &next.media).await?;
+
+    Ok(Json(NextEpisodeResponse::from(next)).into_response())
 }
 
 /// Manually identify a media item with a specific TMDB ID
+///
+/// With `API_SECRET` set, this needs the shared secret.
 pub async fn manual_identify(
     State(media_repo): State<Arc<dyn MediaRepository>>,
     State(collection_repo): State<Arc<dyn CollectionRepository>>,
     State(tmdb_service): State<Arc<dyn TmdbService>>,
+    auditor: Auditor,
     Path(id): Path<i64>,
     Json(request): Json<ManualIdentifyRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
@@ -533,6 +1001,7 @@ pub async fn manual_identify(
         {
             media.tmdb_id = Some(tmdb_id);
             media.title = details.title.clone();
+            media.original_title = details.original_title.clone().filter(|t| *t != details.title);
             media.overview = Some(details.overview);
             media.poster_url = details.poster_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p));
             media.backdrop_url = details.backdrop_path.map(|b| format!("https://image.tmdb.org/t/p/w1280{}", b));
@@ -617,6 +1086,10 @@ pub async fn manual_identify(
         .update(&media)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to save: {}", e)))?;
+    auditor.record(
+        AuditEvent::new(AuditAction::Identification, format!("media:{}", id))
+            .with_details(format!("Identified as TMDB {} ({})", tmdb_id, media.title)),
+    ).await;
 
     Ok(Json(ManualIdentifyResponse {
         message: format!("Successfully identified as TMDB ID {}", tmdb_id),
@@ -630,14 +1103,111 @@ pub async fn manual_identify(
 /// where series are ranked by their most recently added episode.
 pub async fn list_recently_added(
     State(use_case): State<Arc<GetRecentlyAddedUseCase>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<LibraryQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     const LIMIT: usize = 10;
+    // Fetched instead when parental controls may hide some
+    const FETCH_LIMIT: usize = 50;
 
-    match use_case.execute(LIMIT).await {
-        Ok(items) => Ok(Json(items)),
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    match use_case.execute(if policy.is_restricted() { FETCH_LIMIT } else { LIMIT }).await {
+        Ok(items) => Ok(Json(
+            items
+                .into_iter()
+                .filter(|item| recently_added_allowed(&policy, item))
+                .take(LIMIT)
+                .collect::<Vec<_>>(),
+        )),
         Err(e) => {
             tracing::error!("Error getting recently added: {}", e);
             Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to get recently added items".to_string()))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::ParentalControls;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{
+        SqliteMediaRepository, SqliteParentalControlRepository, SqliteSeriesRepository,
+    };
+    use crate::shared::error::TmdbError;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// TMDB finds movies 2, 3 and 4 similar to anything
+    struct Similar;
+
+    #[async_trait::async_trait]
+    impl TmdbSimilarFetcher for Similar {
+        async fn fetch_similar(&self, _tmdb_id: i64, media_type: &str) -> Result<Vec<SimilarResult>, TmdbError> {
+            Ok([2, 3, 4]
+                .into_iter()
+                .map(|id| SimilarResult {
+                    id,
+                    title: format!("Movie {}", id),
+                    poster_path: None,
+                    backdrop_path: None,
+                    release_date: None,
+                    vote_average: 7.0,
+                    media_type: media_type.to_string(),
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_similar_hides_blocked_titles() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repo: Arc<dyn MediaRepository> = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let series_repo = Arc::new(SqliteSeriesRepository::new(pool.clone()));
+        let mut ids = Vec::new();
+        for (tmdb_id, rating) in [(1, "PG"), (2, "PG"), (3, "R")] {
+            let movie = Media::new(format!("/movies/{}.mkv", tmdb_id), MediaType::Movie, format!("Movie {}", tmdb_id))
+                .unwrap()
+                .with_tmdb_id(Some(tmdb_id))
+                .with_content_rating(Some(rating.to_string()));
+            ids.push(media_repo.save(&movie).await.unwrap());
+        }
+        let parental = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool)),
+            series_repo.clone(),
+        ));
+        let controls = ParentalControls { max_age: Some(12), ..Default::default() };
+        parental.set("kid", controls, None, None).await.unwrap();
+
+        let similar = |id: i64, user: &str| {
+            get_media_similar(
+                State(media_repo.clone()),
+                State(series_repo.clone()),
+                State(Arc::new(Similar)),
+                State(parental.clone()),
+                None,
+                Path(id),
+                Query(LibraryQuery { user: Some(user.to_string()) }),
+            )
+        };
+
+        // Only the allowed title in the library is left; 3 is rated R and 4
+        // is unknown
+        let response = similar(ids[0], "kid").await.unwrap().into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: Vec<SimilarResult> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2]);
+
+        let response = similar(ids[0], "default").await.unwrap().into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let results: Vec<SimilarResult> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(similar(ids[2], "kid").await.err().unwrap().0, StatusCode::FORBIDDEN);
+    }
+}
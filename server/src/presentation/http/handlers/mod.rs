@@ -7,3 +7,26 @@ pub mod search_handlers;
 pub mod proxy_handlers;
 pub mod subtitle_generation_handlers;
 pub mod health_handlers;
+pub mod people_handlers;
+pub mod events_handlers;
+pub mod admin_handlers;
+pub mod hls_handlers;
+pub mod session_handlers;
+pub mod stats_handlers;
+pub mod cast_handlers;
+pub mod subtitle_download_handlers;
+pub mod subtitle_extraction_handlers;
+pub mod subtitle_editing_handlers;
+pub mod bookmark_handlers;
+pub mod auth_handlers;
+pub mod preview_handlers;
+pub mod parental_control_handlers;
+pub mod profile_handlers;
+pub mod audit_handlers;
+pub mod playlist_handlers;
+pub mod recommendation_handlers;
+pub mod preset_handlers;
+pub mod webhook_handlers;
+pub mod notification_handlers;
+pub mod settings_handlers;
+pub mod task_handlers;
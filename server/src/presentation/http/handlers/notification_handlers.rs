@@ -0,0 +1,159 @@
+//! Notification Handlers
+//!
+//! HTTP handlers for managing notification channels:
+//!
+//! - `GET|POST /v2/admin/notifications`
+//! - `GET|PUT|DELETE /v2/admin/notifications/:id`
+//! - `POST /v2/admin/notifications/:id/test`
+//!
+//! Credentials in channel configs are shown as `********`; sending that
+//! back when changing a channel keeps them. With `API_SECRET` set, all of
+//! them need the shared secret.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::NotificationService;
+use crate::application::services::notifications::{redact_secrets, NOTIFICATION_EVENT_TYPES};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{NotificationChannel, NotificationChannelSettings};
+use crate::infrastructure::external::notifications::NOTIFIER_KINDS;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::shared::error::ApplicationError;
+use crate::presentation::http::problem::ApiError;
+
+/// Body of a notification channel to add or change
+#[derive(Debug, Deserialize)]
+pub struct ChannelRequest {
+    pub name: String,
+    /// "discord", "telegram", "gotify" or "email"
+    pub kind: String,
+    /// Settings of the kind
+    pub config: serde_json::Value,
+    /// Event types to notify (default: all)
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Default: true
+    pub enabled: Option<bool>,
+}
+
+/// Channels with the kinds and event types they can have
+#[derive(Debug, Serialize)]
+pub struct ChannelListResponse {
+    pub channels: Vec<NotificationChannel>,
+    pub kinds: &'static [&'static str],
+    pub event_types: &'static [&'static str],
+}
+
+/// Outcome of a test notification
+#[derive(Debug, Serialize)]
+pub struct TestResponse {
+    pub success: bool,
+    /// Why the channel did not take it
+    pub error: Option<String>,
+}
+
+/// List the notification channels
+pub async fn list_channels(
+    State(notifications): State<Arc<NotificationService>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let channels = notifications.list().await?;
+    Ok(Json(ChannelListResponse {
+        channels: channels.into_iter().map(redact_secrets).collect(),
+        kinds: NOTIFIER_KINDS,
+        event_types: NOTIFICATION_EVENT_TYPES,
+    }))
+}
+
+/// Get one notification channel
+pub async fn get_channel(
+    State(notifications): State<Arc<NotificationService>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(redact_secrets(notifications.get(id).await?)))
+}
+
+/// Add a notification channel
+///
+/// # Responses
+/// - 201: The channel with its ID
+/// - 400: Empty name, unknown kind or event type, invalid config
+pub async fn create_channel(
+    State(notifications): State<Arc<NotificationService>>,
+    auditor: Auditor,
+    Json(request): Json<ChannelRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let settings = NotificationChannelSettings {
+        name: request.name,
+        kind: request.kind,
+        config: request.config,
+        event_types: request.event_types,
+        enabled: request.enabled.unwrap_or(true),
+    };
+    let channel = notifications.create(settings).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("notification_channel:{}", channel.id))
+            .with_details(format!("Notification channel '{}' ({}) added", channel.name, channel.kind)),
+    ).await;
+    Ok((StatusCode::CREATED, Json(redact_secrets(channel))))
+}
+
+/// Change a notification channel
+pub async fn update_channel(
+    State(notifications): State<Arc<NotificationService>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+    Json(request): Json<ChannelRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let current = notifications.get(id).await?;
+    let settings = NotificationChannelSettings {
+        name: request.name,
+        kind: request.kind,
+        config: request.config,
+        event_types: request.event_types,
+        enabled: request.enabled.unwrap_or(current.enabled),
+    };
+    let channel = notifications.update(id, settings).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("notification_channel:{}", channel.id))
+            .with_details(format!("Notification channel '{}' changed", channel.name)),
+    ).await;
+    Ok(Json(redact_secrets(channel)))
+}
+
+/// Remove a notification channel
+pub async fn delete_channel(
+    State(notifications): State<Arc<NotificationService>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    notifications.delete(id).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::Deletion, format!("notification_channel:{}", id))
+            .with_details("Notification channel removed"),
+    ).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Send a test notification to a channel, to check its settings
+///
+/// # Responses
+/// - 200: Whether the channel took it, and if not why
+pub async fn test_channel(
+    State(notifications): State<Arc<NotificationService>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let response = match notifications.test(id).await {
+        Ok(()) => TestResponse { success: true, error: None },
+        Err(ApplicationError::Notification(e)) => TestResponse { success: false, error: Some(e.to_string()) },
+        Err(e) => return Err(ApiError::from(e)),
+    };
+    Ok(Json(response))
+}
+
@@ -0,0 +1,356 @@
+//! Parental Control Handlers
+//!
+//! HTTP handlers for per-user parental controls:
+//!
+//! - `GET/PUT/DELETE /v2/users/:user_id/parental-controls`
+//! - `POST/DELETE /v2/users/:user_id/parental-controls/override`
+//!
+//! Library, search and streaming endpoints hide or refuse what the calling
+//! user's controls block (see [`ParentalControlService`]). A device key acts
+//! as the user it was issued for; other callers may pass a `user`. Devices
+//! can only see and change their own user's controls.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::{ApiKeyService, Caller, ContentPolicy, ParentalControlService};
+use crate::domain::entities::Media;
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::ParentalControls;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::auth_handlers::{caller_user, require_admin};
+use crate::shared::error::{ApplicationError, DomainError};
+use crate::presentation::http::problem::ApiError;
+
+/// Parental controls of a user
+#[derive(Debug, Serialize)]
+pub struct ParentalControlResponse {
+    #[serde(flatten)]
+    pub controls: ParentalControls,
+    /// Whether changes and overrides need a PIN
+    pub pin_set: bool,
+    /// End of an active PIN override
+    pub override_until: Option<DateTime<Utc>>,
+}
+
+/// Request body for setting parental controls
+#[derive(Debug, Deserialize)]
+pub struct SetParentalControlsRequest {
+    #[serde(flatten)]
+    pub controls: ParentalControls,
+    /// Current PIN; required once a PIN is set
+    pub pin: Option<String>,
+    /// New PIN (4 to 8 digits); omitted keeps the current one
+    pub new_pin: Option<String>,
+}
+
+/// Request body carrying the PIN
+#[derive(Debug, Default, Deserialize)]
+pub struct PinRequest {
+    pub pin: Option<String>,
+}
+
+/// Request body for lifting the controls
+#[derive(Debug, Deserialize)]
+pub struct OverrideRequest {
+    pub pin: String,
+    /// How long the controls are lifted (default: 60 minutes)
+    pub minutes: Option<u32>,
+}
+
+/// End of a PIN override
+#[derive(Debug, Serialize)]
+pub struct OverrideResponse {
+    pub override_until: DateTime<Utc>,
+}
+
+/// Get the parental controls of a user
+pub async fn get_parental_controls(
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let status = parental.get(&user_id).await?
+        .ok_or(ApiError::not_found("No parental controls for this user"))?;
+
+    Ok(Json(ParentalControlResponse {
+        controls: status.controls,
+        pin_set: status.pin_set,
+        override_until: status.override_until,
+    }))
+}
+
+/// Set the parental controls of a user
+///
+/// Setting the first PIN, and any change while the user's active profile is
+/// in kid mode, needs the shared secret; otherwise a device could pick a PIN
+/// and use it to leave kid mode.
+///
+/// # Responses
+/// - 204: Controls stored
+/// - 400: Invalid PIN format or too many blocked tags
+/// - 403: Wrong or missing PIN (`wrong_pin`), another user's controls, or a
+///   device setting the first PIN or changing a kid's controls
+///   (`admin_required`)
+/// - 429: Too many wrong PINs (`pin_locked`)
+pub async fn set_parental_controls(
+    State(parental): State<Arc<ParentalControlService>>,
+    State(api_keys): State<Arc<ApiKeyService>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(user_id): Path<String>,
+    Json(request): Json<SetParentalControlsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let pin_set = parental.get(&user_id).await?.is_some_and(|status| status.pin_set);
+    if (!pin_set && request.new_pin.is_some()) || parental.in_kid_mode(&user_id).await? {
+        require_admin(&api_keys, caller.as_deref())?;
+    }
+    let result = parental
+        .set(&user_id, request.controls, request.pin.as_deref(), request.new_pin.as_deref())
+        .await;
+    audit(&auditor, &user_id, AuditAction::SettingsChange, "Parental controls changed", &result).await;
+    result.map_err(map_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove the parental controls of a user
+///
+/// Takes the PIN in the body once one is set.
+pub async fn delete_parental_controls(
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(user_id): Path<String>,
+    body: Option<Json<PinRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    let result = parental.remove(&user_id, request.pin.as_deref()).await;
+    audit(&auditor, &user_id, AuditAction::Deletion, "Parental controls removed", &result).await;
+    result.map_err(map_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lift the parental controls of a user with the PIN
+///
+/// The override is kept in memory and ends after `minutes`, on
+/// `DELETE .../override` or on restart.
+///
+/// # Responses
+/// - 200: End of the override
+/// - 403: Wrong PIN (`wrong_pin`), or another user's controls
+/// - 429: Too many wrong PINs (`pin_locked`)
+pub async fn unlock(
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(user_id): Path<String>,
+    Json(request): Json<OverrideRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let result = parental
+        .unlock(&user_id, &request.pin, request.minutes.unwrap_or(60))
+        .await;
+    audit(&auditor, &user_id, AuditAction::Login, "Parental controls lifted with the PIN", &result).await;
+    let override_until = result.map_err(map_error)?;
+
+    Ok(Json(OverrideResponse { override_until }))
+}
+
+/// End a PIN override early
+pub async fn lock(
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    if !parental.lock(&user_id).await {
+        return Err(ApiError::not_found("No active override for this user"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Records a PIN-protected change, or the wrong PIN that refused it
+async fn audit<T>(
+    auditor: &Auditor,
+    user_id: &str,
+    action: AuditAction,
+    details: &str,
+    result: &Result<T, ApplicationError>,
+) {
+    let target = format!("user:{}", user_id);
+    let event = match result {
+        Ok(_) => AuditEvent::new(action, target).with_details(details),
+        Err(ApplicationError::Domain(DomainError::BusinessRuleViolation(msg) | DomainError::InvalidState(msg))) => {
+            AuditEvent::new(AuditAction::AuthFailed, target).with_details(msg.clone())
+        }
+        Err(_) => return,
+    };
+    auditor.record(event).await;
+}
+
+/// What the calling user may see: a device key's own user, else the
+/// requested one (default: the server's)
+pub(crate) async fn content_policy(
+    parental: &ParentalControlService,
+    caller: Option<&Caller>,
+    user: Option<&str>,
+) -> Result<ContentPolicy, ApiError> {
+    let user = caller_user(caller, user)?;
+    parental.policy(&user).await.map_err(map_error)
+}
+
+/// Refuses a movie or episode the user's parental controls block
+pub(crate) async fn ensure_allowed(
+    parental: &ParentalControlService,
+    user: &str,
+    media: &Media,
+) -> Result<(), ApiError> {
+    if parental.allows_media(user, media).await? {
+        Ok(())
+    } else {
+        Err(ApiError::new(StatusCode::FORBIDDEN, "parental_block", "Blocked by parental controls"))
+    }
+}
+
+/// Wrong PINs and PIN lockouts have their own codes
+pub(crate) fn map_error(e: ApplicationError) -> ApiError {
+    match e {
+        ApplicationError::Domain(DomainError::BusinessRuleViolation(msg)) => {
+            ApiError::new(StatusCode::FORBIDDEN, "wrong_pin", msg)
+        }
+        ApplicationError::Domain(DomainError::InvalidState(msg)) => {
+            ApiError::new(StatusCode::TOO_MANY_REQUESTS, "pin_locked", msg)
+        }
+        e => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{DeviceKey, ProfileRepository, ProfileSettings};
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::messaging::InMemoryEventBus;
+    use crate::infrastructure::persistence::sqlite::{
+        SqliteDeviceKeyRepository, SqliteParentalControlRepository, SqliteProfileRepository, SqliteSeriesRepository,
+    };
+    use axum::extract::FromRequestParts;
+    use axum::http::Request;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn auditor() -> Auditor {
+        let (mut parts, _) = Request::new(()).into_parts();
+        let bus: Option<Arc<InMemoryEventBus>> = None;
+        Auditor::from_request_parts(&mut parts, &bus).await.unwrap()
+    }
+
+    fn device(user: &str) -> Extension<Caller> {
+        Extension(Caller::Device(DeviceKey {
+            id: 1,
+            name: "Tablet".to_string(),
+            user_id: user.to_string(),
+            prefix: "hf_1234".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            last_used_at: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_unlock_reports_wrong_pins_and_lockout() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let parental = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            Arc::new(SqliteSeriesRepository::new(pool)),
+        ));
+        parental.set("kid", ParentalControls::default(), None, Some("1234")).await.unwrap();
+
+        let unlock_as = |caller: Option<Extension<Caller>>, user: &str, pin: &str| {
+            let parental = parental.clone();
+            let user = user.to_string();
+            let pin = pin.to_string();
+            async move {
+                unlock(State(parental), caller, auditor().await, Path(user), Json(OverrideRequest { pin, minutes: None }))
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        // A device only unlocks its own user
+        let e = unlock_as(Some(device("default")), "kid", "1234").await.unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "wrong_user"));
+
+        // Five wrong PINs lock entry, even with the right one
+        for _ in 0..5 {
+            let e = unlock_as(Some(device("kid")), "kid", "0000").await.unwrap_err();
+            assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "wrong_pin"));
+        }
+        let e = unlock_as(Some(device("kid")), "kid", "1234").await.unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::TOO_MANY_REQUESTS, "pin_locked"));
+        let e = unlock_as(None, "kid", "1234").await.unwrap_err();
+        assert_eq!(e.status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_kid_device_cannot_set_the_first_pin() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let profiles = Arc::new(SqliteProfileRepository::new(pool.clone()));
+        let kid = ProfileSettings { name: "Kid".to_string(), kid_mode: true, ..Default::default() };
+        profiles.create("kid", &kid).await.unwrap();
+        let parental = Arc::new(
+            ParentalControlService::new(
+                Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+                Arc::new(SqliteSeriesRepository::new(pool.clone())),
+            )
+            .with_profiles(profiles),
+        );
+        let api_keys = Arc::new(ApiKeyService::new(Arc::new(SqliteDeviceKeyRepository::new(pool)), Some("secret")));
+
+        let set_as = |caller: Option<Extension<Caller>>, new_pin: Option<&str>| {
+            let parental = parental.clone();
+            let api_keys = api_keys.clone();
+            let request = SetParentalControlsRequest {
+                controls: ParentalControls::default(),
+                pin: None,
+                new_pin: new_pin.map(str::to_string),
+            };
+            async move {
+                set_parental_controls(State(parental), State(api_keys), caller, auditor().await, Path("kid".to_string()), Json(request))
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        // The kid's own device can neither pick a PIN nor change the controls
+        let e = set_as(Some(device("kid")), Some("1234")).await.unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "admin_required"));
+        let e = set_as(Some(device("kid")), None).await.unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "admin_required"));
+        assert_eq!(parental.get("kid").await.unwrap(), None);
+
+        set_as(Some(Extension(Caller::Admin)), Some("1234")).await.unwrap();
+        assert!(parental.get("kid").await.unwrap().unwrap().pin_set);
+    }
+}
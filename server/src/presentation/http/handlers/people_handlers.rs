@@ -0,0 +1,355 @@
+//! People Handlers
+//!
+//! HTTP handlers for browsing cast and crew.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use crate::application::services::{Caller, ParentalControlService};
+use crate::domain::repositories::{CreditsRepository, MediaRepository, Person, PersonRepository, SeriesRepository};
+use crate::interfaces::external_services::{PersonCreditInfo, TmdbPersonFetcher};
+use crate::presentation::http::dto::media_dto::{LibraryMediaResponse, LibraryQuery};
+use crate::presentation::http::handlers::media_handlers::series_to_library_media;
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
+
+/// Cached person details are refreshed from TMDB after this many days
+const PERSON_CACHE_DAYS: i64 = 30;
+
+/// Person response DTO
+#[derive(Debug, serde::Serialize)]
+pub struct PersonResponse {
+    pub id: i64,
+    pub name: String,
+    pub biography: Option<String>,
+    pub birthday: Option<String>,
+    pub deathday: Option<String>,
+    pub place_of_birth: Option<String>,
+    pub profile_url: Option<String>,
+    pub known_for_department: Option<String>,
+    pub imdb_id: Option<String>,
+}
+
+impl From<Person> for PersonResponse {
+    fn from(person: Person) -> Self {
+        Self {
+            id: person.id,
+            name: person.name,
+            biography: person.biography,
+            birthday: person.birthday,
+            deathday: person.deathday,
+            place_of_birth: person.place_of_birth,
+            profile_url: person.profile_url,
+            known_for_department: person.known_for_department,
+            imdb_id: person.imdb_id,
+        }
+    }
+}
+
+/// A library item featuring a person
+#[derive(Debug, serde::Serialize)]
+pub struct PersonMediaItem {
+    #[serde(flatten)]
+    pub media: LibraryMediaResponse,
+    /// Character played (cast credits)
+    pub character: Option<String>,
+    /// Job (crew credits, e.g. "Director")
+    pub job: Option<String>,
+}
+
+/// Person filmography within the library
+#[derive(Debug, serde::Serialize)]
+pub struct PersonMediaResponse {
+    pub person_id: i64,
+    pub movies: Vec<PersonMediaItem>,
+    pub series: Vec<PersonMediaItem>,
+}
+
+/// Get person details (biography, photo)
+/// First checks the people cache, then fetches from TMDB if missing or stale.
+pub async fn get_person(
+    State(person_repo): State<Arc<dyn PersonRepository>>,
+    State(tmdb_service): State<Arc<dyn TmdbPersonFetcher>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let cached = person_repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Some(ref person) = cached {
+        if chrono::Utc::now() - person.updated_at < chrono::Duration::days(PERSON_CACHE_DAYS) {
+            return Ok(Json(PersonResponse::from(person.clone())));
+        }
+    }
+
+    match tmdb_service.fetch_person(id).await {
+        Ok(Some(detail)) => {
+            let person = Person {
+                id: detail.id,
+                name: detail.name,
+                biography: Some(detail.biography).filter(|b| !b.is_empty()),
+                birthday: detail.birthday,
+                deathday: detail.deathday,
+                place_of_birth: detail.place_of_birth,
+                profile_url: detail.profile_path.map(|p| format!("https://image.tmdb.org/t/p/w500{}", p)),
+                known_for_department: detail.known_for_department,
+                imdb_id: detail.imdb_id,
+                updated_at: chrono::Utc::now(),
+            };
+            if let Err(e) = person_repo.save(&person).await {
+                tracing::warn!("Failed to cache person {}: {}", id, e);
+            }
+            Ok(Json(PersonResponse::from(person)))
+        }
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!("Person {} not found", id))),
+        Err(e) => {
+            // Serve stale data rather than failing when TMDB is unavailable
+            if let Some(person) = cached {
+                tracing::warn!("TMDB person fetch failed, serving cached person {}: {}", id, e);
+                return Ok(Json(PersonResponse::from(person)));
+            }
+            tracing::error!("Error getting person {}: {}", id, e);
+            Err((StatusCode::BAD_GATEWAY, "Failed to get person".to_string()))
+        }
+    }
+}
+
+/// Get everything in the library featuring a person
+///
+/// Combines locally cached credits with the person's TMDB filmography, so
+/// items whose credits were never viewed are found as well. Episodes are
+/// collapsed into their series. Titles the user's parental controls block
+/// are left out.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_person_media(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(series_repo): State<Arc<dyn SeriesRepository>>,
+    State(credits_repo): State<Arc<dyn CreditsRepository>>,
+    State(tmdb_service): State<Arc<dyn TmdbPersonFetcher>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let mut movies: Vec<PersonMediaItem> = Vec::new();
+    let mut series: Vec<PersonMediaItem> = Vec::new();
+    let mut seen_movies = HashSet::new();
+    let mut seen_series = HashSet::new();
+
+    // TMDB filmography (best effort)
+    let tmdb_credits = match tmdb_service.fetch_person_credits(id).await {
+        Ok(credits) => credits,
+        Err(e) => {
+            tracing::warn!("Failed to fetch TMDB credits for person {}: {}", id, e);
+            Vec::new()
+        }
+    };
+
+    // Merge multiple credits for the same title (e.g. writer and director)
+    let mut by_title: HashMap<(String, i64), (Option<String>, Option<String>)> = HashMap::new();
+    for PersonCreditInfo { tmdb_id, media_type, character, job, .. } in tmdb_credits {
+        let entry = by_title.entry((media_type, tmdb_id)).or_default();
+        if entry.0.is_none() {
+            entry.0 = character.filter(|c| !c.is_empty());
+        }
+        if let Some(job) = job {
+            entry.1 = Some(match entry.1.take() {
+                Some(existing) if !existing.contains(&job) => format!("{}, {}", existing, job),
+                Some(existing) => existing,
+                None => job,
+            });
+        }
+    }
+
+    for ((media_type, tmdb_id), (character, job)) in by_title {
+        if media_type == "tv" {
+            if let Some(s) = series_repo
+                .find_by_tmdb_id(tmdb_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            {
+                if let Some(series_id) = s.id.filter(|_| policy.allows_series(&s)) {
+                    if seen_series.insert(series_id) {
+                        series.push(PersonMediaItem {
+                            media: series_to_library_media(&s, &s.created_at),
+                            character,
+                            job,
+                        });
+                    }
+                }
+            }
+        } else {
+            let found = media_repo
+                .find_by_tmdb_id(tmdb_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            let found = parental
+                .retain_allowed(&policy, found)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            for media in found.into_iter().filter(|m| m.media_type.is_movie()) {
+                if let Some(media_id) = media.id {
+                    if seen_movies.insert(media_id) {
+                        movies.push(PersonMediaItem {
+                            media: LibraryMediaResponse::from_media(media),
+                            character: character.clone(),
+                            job: job.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // Locally cached credits (covers items TMDB attributes differently)
+    let media_ids = credits_repo
+        .get_media_ids_by_person(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    for media_id in media_ids {
+        let Some(media) = media_repo
+            .find_by_id(media_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        else {
+            continue;
+        };
+        // Checks episodes against their series as well
+        let Some(media) = parental
+            .retain_allowed(&policy, vec![media])
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .pop()
+        else {
+            continue;
+        };
+
+        let credit = credits_repo
+            .get_credits(media_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .into_iter()
+            .find(|c| c.person_id == id);
+        let (character, job) = match credit {
+            Some(c) if c.character_name.is_some() => (c.character_name, None),
+            Some(c) => (None, Some(c.role)),
+            None => (None, None),
+        };
+
+        if media.media_type.is_episode() {
+            let Some(series_id) = media.series_id else { continue };
+            if seen_series.insert(series_id) {
+                if let Some(s) = series_repo
+                    .find_by_id(series_id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                    .filter(|s| policy.allows_series(s))
+                {
+                    series.push(PersonMediaItem {
+                        media: series_to_library_media(&s, &s.created_at),
+                        character,
+                        job,
+                    });
+                }
+            }
+        } else if seen_movies.insert(media_id) {
+            movies.push(PersonMediaItem {
+                media: LibraryMediaResponse::from_media(media),
+                character,
+                job,
+            });
+        }
+    }
+
+    movies.sort_by(|a, b| b.media.release_date.cmp(&a.media.release_date));
+    series.sort_by(|a, b| b.media.release_date.cmp(&a.media.release_date));
+
+    Ok(Json(PersonMediaResponse { person_id: id, movies, series }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Media;
+    use crate::domain::repositories::ParentalControls;
+    use crate::domain::value_objects::MediaType;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::{
+        SqliteCreditsRepository, SqliteMediaRepository, SqliteParentalControlRepository, SqliteSeriesRepository,
+    };
+    use crate::interfaces::external_services::PersonDetail;
+    use crate::shared::error::TmdbError;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// A person who played in TMDB movies 1 and 2
+    struct Filmography;
+
+    #[async_trait::async_trait]
+    impl TmdbPersonFetcher for Filmography {
+        async fn fetch_person(&self, _person_id: i64) -> Result<Option<PersonDetail>, TmdbError> {
+            Ok(None)
+        }
+
+        async fn fetch_person_credits(&self, _person_id: i64) -> Result<Vec<PersonCreditInfo>, TmdbError> {
+            Ok([1, 2]
+                .into_iter()
+                .map(|tmdb_id| PersonCreditInfo {
+                    tmdb_id,
+                    media_type: "movie".to_string(),
+                    title: format!("Movie {}", tmdb_id),
+                    character: Some("Hero".to_string()),
+                    job: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_person_media_hides_blocked_titles() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repo: Arc<dyn MediaRepository> = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let series_repo = Arc::new(SqliteSeriesRepository::new(pool.clone()));
+        for (tmdb_id, rating) in [(1, "PG"), (2, "R")] {
+            let movie = Media::new(format!("/movies/{}.mkv", tmdb_id), MediaType::Movie, format!("Movie {}", tmdb_id))
+                .unwrap()
+                .with_tmdb_id(Some(tmdb_id))
+                .with_content_rating(Some(rating.to_string()));
+            media_repo.save(&movie).await.unwrap();
+        }
+        let parental = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            series_repo.clone(),
+        ));
+        let controls = ParentalControls { max_age: Some(12), ..Default::default() };
+        parental.set("kid", controls, None, None).await.unwrap();
+
+        let response = get_person_media(
+            State(media_repo),
+            State(series_repo),
+            State(Arc::new(SqliteCreditsRepository::new(pool))),
+            State(Arc::new(Filmography)),
+            State(parental),
+            None,
+            Path(7),
+            Query(LibraryQuery { user: Some("kid".to_string()) }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let titles: Vec<&str> = body["movies"].as_array().unwrap().iter().map(|m| m["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["Movie 1"]);
+    }
+}
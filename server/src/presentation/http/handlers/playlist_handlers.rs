@@ -0,0 +1,457 @@
+//! Playlist Handlers
+//!
+//! HTTP handlers for user playlists: ordered queues mixing movies and
+//! episodes (a "Halloween night"), kept apart from collections.
+//!
+//! - `GET /v2/playlists?user=` / `POST /v2/playlists?user=`
+//! - `GET|PUT|DELETE /v2/playlists/:id?user=`
+//! - `POST|PUT /v2/playlists/:id/items?user=` (add / reorder)
+//! - `DELETE /v2/playlists/:id/items/:item?user=`
+//! - `GET /v2/playlists/:id/play?user=&start=`
+//!
+//! Playlists belong to one user; other users get a 404. A device key acts
+//! as its own user.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::application::services::{Caller, ParentalControlService};
+use crate::domain::entities::Media;
+use crate::domain::repositories::{MediaRepository, Playlist, PlaylistItem, PlaylistRepository};
+use crate::domain::value_objects::MediaType;
+use crate::presentation::http::dto::media_dto::LibraryMediaResponse;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
+
+/// Longest accepted playlist name, in characters
+const MAX_NAME_CHARS: usize = 100;
+/// Longest accepted description, in characters
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+/// Most items per playlist
+const MAX_ITEMS: usize = 1000;
+
+/// Query parameters selecting the user of a playlist
+#[derive(Debug, Deserialize)]
+pub struct PlaylistQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Query parameters of the play queue
+#[derive(Debug, Deserialize)]
+pub struct PlayQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+    /// Item to start at (default: the first)
+    pub start: Option<i64>,
+}
+
+/// Request body for adding or renaming a playlist
+#[derive(Debug, Deserialize)]
+pub struct PlaylistRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Request body for adding an item
+#[derive(Debug, Deserialize)]
+pub struct AddItemRequest {
+    /// Movie or episode to add
+    pub media_id: i64,
+    /// Place to insert at, from 0 (default: the end)
+    pub position: Option<i64>,
+}
+
+/// Request body for reordering the items
+#[derive(Debug, Deserialize)]
+pub struct ReorderRequest {
+    /// Every item ID of the playlist, in the new order
+    pub item_ids: Vec<i64>,
+}
+
+/// A playlist with its items
+#[derive(Debug, Serialize)]
+pub struct PlaylistDetail {
+    #[serde(flatten)]
+    pub playlist: Playlist,
+    pub items: Vec<PlaylistItemResponse>,
+}
+
+/// An item of a playlist with its media
+#[derive(Debug, Serialize)]
+pub struct PlaylistItemResponse {
+    pub id: i64,
+    pub position: i64,
+    pub added_at: String,
+    pub media: LibraryMediaResponse,
+}
+
+/// An entry of the play queue
+#[derive(Debug, Serialize)]
+pub struct QueueEntry {
+    pub item_id: i64,
+    pub media_id: i64,
+    pub title: String,
+    pub media_type: String,
+    pub series_id: Option<i64>,
+    pub season_number: Option<i32>,
+    pub episode_number: Option<i32>,
+    pub duration: Option<i32>,
+    /// Where to resume, in seconds (0 for watched or unstarted media)
+    pub resume_position: i64,
+    pub stream_url: String,
+}
+
+/// The queue of a playlist, ready to play
+#[derive(Debug, Serialize)]
+pub struct PlayQueueResponse {
+    pub playlist_id: i64,
+    pub name: String,
+    pub queue: Vec<QueueEntry>,
+}
+
+/// List the playlists of a user, by name
+///
+/// GET /v2/playlists?user=
+pub async fn list_playlists(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let list = playlists
+        .find_by_user(&user)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(list))
+}
+
+/// Add an empty playlist
+///
+/// POST /v2/playlists?user=
+///
+/// # Responses
+/// - 201: The new playlist
+/// - 400: Empty or too long name, or too long description
+pub async fn create_playlist(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<PlaylistQuery>,
+    Json(request): Json<PlaylistRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let (name, description) = clean_request(request)?;
+    let playlist = playlists
+        .create(&user, &name, description.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(playlist)))
+}
+
+/// Get a playlist with its items, in order
+///
+/// GET /v2/playlists/:id?user=
+///
+/// Items whose media is gone or blocked by the user's parental controls
+/// are left out.
+pub async fn get_playlist(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(id): Path<i64>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = &caller_user(caller.as_deref(), query.user.as_deref())?;
+    let playlist = find_playlist(&playlists, user, id).await?;
+    let items = playable_items(&playlists, &media_repo, &parental, user, id).await?;
+
+    Ok(Json(PlaylistDetail {
+        playlist,
+        items: items
+            .into_iter()
+            .map(|(item, media)| PlaylistItemResponse {
+                id: item.id,
+                position: item.position,
+                added_at: item.added_at,
+                media: LibraryMediaResponse::from_media(media),
+            })
+            .collect(),
+    }))
+}
+
+/// Rename a playlist or change its description
+///
+/// PUT /v2/playlists/:id?user=
+pub async fn update_playlist(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<PlaylistQuery>,
+    Json(request): Json<PlaylistRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    find_playlist(&playlists, &user, id).await?;
+    let (name, description) = clean_request(request)?;
+
+    let playlist = playlists
+        .update(id, &name, description.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Playlist {} not found", id)))?;
+
+    Ok(Json(playlist))
+}
+
+/// Remove a playlist
+///
+/// DELETE /v2/playlists/:id?user=
+pub async fn delete_playlist(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    find_playlist(&playlists, &user, id).await?;
+    playlists
+        .delete(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add a movie or episode to a playlist
+///
+/// POST /v2/playlists/:id/items?user=
+///
+/// # Responses
+/// - 201: The new item
+/// - 400: Media that is not a movie or episode, or a full playlist
+/// - 404: Playlist or media not found
+pub async fn add_playlist_item(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<PlaylistQuery>,
+    Json(request): Json<AddItemRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let playlist = find_playlist(&playlists, &user, id).await?;
+    if playlist.item_count as usize >= MAX_ITEMS {
+        return Err((StatusCode::BAD_REQUEST, format!("At most {} items per playlist", MAX_ITEMS)));
+    }
+    let media = media_repo
+        .find_by_id(request.media_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Media {} not found", request.media_id)))?;
+    if media.media_type == MediaType::Unknown {
+        return Err((StatusCode::BAD_REQUEST, "Only movies and episodes can be added".to_string()));
+    }
+
+    let item = playlists
+        .add_item(id, request.media_id, request.position)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+/// Put the items of a playlist in a new order
+///
+/// PUT /v2/playlists/:id/items?user=
+///
+/// # Responses
+/// - 200: The items in their new order
+/// - 400: `item_ids` does not list every item of the playlist exactly once
+pub async fn reorder_playlist_items(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<PlaylistQuery>,
+    Json(request): Json<ReorderRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    find_playlist(&playlists, &user, id).await?;
+    let items = playlists
+        .items(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !is_permutation(&items, &request.item_ids) {
+        return Err((StatusCode::BAD_REQUEST, "item_ids must list every item of the playlist once".to_string()));
+    }
+
+    playlists
+        .reorder(id, &request.item_ids)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let items = playlists
+        .items(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(items))
+}
+
+/// Remove an item from a playlist
+///
+/// DELETE /v2/playlists/:id/items/:item?user=
+pub async fn remove_playlist_item(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path((id, item_id)): Path<(i64, i64)>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    find_playlist(&playlists, &user, id).await?;
+    let removed = playlists
+        .remove_item(id, item_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !removed {
+        return Err((StatusCode::NOT_FOUND, format!("Item {} not found", item_id)));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the play queue of a playlist
+///
+/// GET /v2/playlists/:id/play?user=&start=
+///
+/// The queue holds the playable items in order, from `start` on, each with
+/// its stream URL and resume position.
+///
+/// # Responses
+/// - 200: The queue (empty when nothing is playable)
+/// - 404: Playlist, or the `start` item, not found
+pub async fn play_playlist(
+    State(playlists): State<Arc<dyn PlaylistRepository>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(id): Path<i64>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<PlayQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = &caller_user(caller.as_deref(), query.user.as_deref())?;
+    let playlist = find_playlist(&playlists, user, id).await?;
+    let mut items = playable_items(&playlists, &media_repo, &parental, user, id).await?;
+    if let Some(start) = query.start {
+        let index = items
+            .iter()
+            .position(|(item, _)| item.id == start)
+            .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Item {} not found", start)))?;
+        items.drain(..index);
+    }
+
+    Ok(Json(PlayQueueResponse {
+        playlist_id: playlist.id,
+        name: playlist.name,
+        queue: items.into_iter().map(|(item, media)| queue_entry(item, media)).collect(),
+    }))
+}
+
+async fn find_playlist(
+    playlists: &Arc<dyn PlaylistRepository>,
+    user: &str,
+    id: i64,
+) -> Result<Playlist, (StatusCode, String)> {
+    playlists
+        .find(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .filter(|playlist| playlist.user_id == user)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Playlist {} not found", id)))
+}
+
+/// Items of a playlist with their media, skipping removed and blocked media
+async fn playable_items(
+    playlists: &Arc<dyn PlaylistRepository>,
+    media_repo: &Arc<dyn MediaRepository>,
+    parental: &ParentalControlService,
+    user: &str,
+    id: i64,
+) -> Result<Vec<(PlaylistItem, Media)>, (StatusCode, String)> {
+    let items = playlists
+        .items(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let policy = content_policy(parental, None, Some(user)).await?;
+
+    let mut playable = Vec::with_capacity(items.len());
+    for item in items {
+        let Some(media) = media_repo
+            .find_by_id(item.media_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        else {
+            continue;
+        };
+        let media = if policy.is_restricted() {
+            let allowed = parental
+                .retain_allowed(&policy, vec![media])
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            match allowed.into_iter().next() {
+                Some(media) => media,
+                None => continue,
+            }
+        } else {
+            media
+        };
+        playable.push((item, media));
+    }
+
+    Ok(playable)
+}
+
+fn queue_entry(item: PlaylistItem, media: Media) -> QueueEntry {
+    QueueEntry {
+        item_id: item.id,
+        media_id: item.media_id,
+        stream_url: format!("/v2/stream/{}", item.media_id),
+        title: media.title,
+        media_type: media.media_type.as_str().to_string(),
+        series_id: media.series_id,
+        season_number: media.season,
+        episode_number: media.episode,
+        duration: media.duration_seconds,
+        resume_position: if media.is_watched { 0 } else { media.current_position },
+    }
+}
+
+/// Whether `item_ids` lists every item exactly once
+fn is_permutation(items: &[PlaylistItem], item_ids: &[i64]) -> bool {
+    let wanted: HashSet<i64> = items.iter().map(|item| item.id).collect();
+    let given: HashSet<i64> = item_ids.iter().copied().collect();
+    item_ids.len() == items.len() && given == wanted
+}
+
+/// Trims the name and description
+fn clean_request(request: PlaylistRequest) -> Result<(String, Option<String>), (StatusCode, String)> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+        return Err((StatusCode::BAD_REQUEST, format!("Name must be 1 to {} characters", MAX_NAME_CHARS)));
+    }
+    let description = request.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if description.as_ref().is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_CHARS) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Description must be at most {} characters", MAX_DESCRIPTION_CHARS),
+        ));
+    }
+
+    Ok((name, description))
+}
@@ -0,0 +1,174 @@
+//! Preset Handlers
+//!
+//! HTTP handlers for the preset franchise timelines in the presets directory:
+//!
+//! - `GET /v2/presets`
+//! - `POST /v2/presets`
+//! - `GET|PUT|DELETE /v2/presets/:id`
+//! - `POST /v2/presets/import`
+//!
+//! Changes are applied to the preset collections right away. With
+//! `API_SECRET` set, changing presets needs the shared secret.
+
+use axum::{
+    extract::{FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::services::{ImportConflict, PresetService};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::presets::PresetCollection;
+use crate::infrastructure::presets::{PresetBundle, MAX_BUNDLE_BYTES};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::problem::ApiError;
+
+/// Query parameters of an import
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// What to do with presets whose name is taken (default: skip)
+    pub conflict: Option<ImportConflict>,
+    /// Only report what would be imported
+    pub dry_run: Option<bool>,
+}
+
+/// JSON body of an import from a URL
+#[derive(Debug, Deserialize)]
+pub struct ImportSource {
+    /// http(s) URL of a YAML or JSON bundle
+    pub url: String,
+}
+
+/// List the presets
+pub async fn list_presets(
+    State(presets): State<Arc<PresetService>>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(presets.list()?))
+}
+
+/// Get one preset
+pub async fn get_preset(
+    State(presets): State<Arc<PresetService>>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(presets.get(&id)?))
+}
+
+/// Add a preset
+///
+/// # Responses
+/// - 201: The preset with its ID
+/// - 400: Invalid preset (empty name or items, repeated `timeline_order`,
+///   unknown TMDB IDs)
+/// - 409: A preset of the same name exists
+pub async fn create_preset(
+    State(presets): State<Arc<PresetService>>,
+    auditor: Auditor,
+    Json(preset): Json<PresetCollection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let entry = presets.create(preset).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("preset:{}", entry.id))
+            .with_details(format!("Preset '{}' added", entry.preset.name)),
+    ).await;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Replace a preset
+pub async fn update_preset(
+    State(presets): State<Arc<PresetService>>,
+    auditor: Auditor,
+    Path(id): Path<String>,
+    Json(preset): Json<PresetCollection>,
+) -> Result<impl IntoResponse, ApiError> {
+    let entry = presets.update(&id, preset).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("preset:{}", entry.id))
+            .with_details(format!("Preset '{}' changed", entry.preset.name)),
+    ).await;
+    Ok(Json(entry))
+}
+
+/// Remove a preset and its collection
+pub async fn delete_preset(
+    State(presets): State<Arc<PresetService>>,
+    auditor: Auditor,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    presets.delete(&id).await?;
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("preset:{}", id)).with_details("Preset removed")).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Import a bundle of presets from a URL or an upload
+///
+/// `POST /v2/presets/import?conflict=skip|replace|rename&dry_run=true`
+///
+/// The body is either JSON `{"url": "..."}` or a multipart upload with a
+/// `bundle` file. A bundle holds one preset, a list of them or `presets:`
+/// with a list, in YAML or JSON.
+///
+/// # Responses
+/// - 200: What was (or would be) created, replaced, skipped or rejected,
+///   with the items available in the library
+/// - 400: Missing source, bundle that cannot be downloaded or parsed
+pub async fn import_presets(
+    State(presets): State<Arc<PresetService>>,
+    auditor: Auditor,
+    Query(query): Query<ImportQuery>,
+    request: Request,
+) -> Result<impl IntoResponse, ApiError> {
+    let multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let (source, content) = if multipart {
+        let mut multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        let mut content = None;
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))?
+        {
+            if field.name() == Some("bundle") {
+                let name = field.file_name().unwrap_or("upload").to_string();
+                let text = field.text().await.map_err(|e| ApiError::bad_request(e.to_string()))?;
+                content = Some((name, text));
+            }
+        }
+        content.ok_or_else(|| ApiError::bad_request("Missing 'bundle' field"))?
+    } else {
+        let Json(source) = Json::<ImportSource>::from_request(request, &())
+            .await
+            .map_err(|e| ApiError::bad_request(e.body_text()))?;
+        let content = PresetBundle::fetch(&source.url)
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        (source.url, content)
+    };
+    if content.len() > MAX_BUNDLE_BYTES {
+        return Err(ApiError::from((StatusCode::PAYLOAD_TOO_LARGE, "Bundle is larger than 2 MB".to_string())));
+    }
+
+    let bundle = PresetBundle::parse(&content).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let dry_run = query.dry_run.unwrap_or(false);
+    let report = presets
+        .import(bundle, query.conflict.unwrap_or_default(), dry_run)
+        .await
+        ?;
+    if !dry_run && report.created + report.replaced > 0 {
+        auditor.record(
+            AuditEvent::new(AuditAction::SettingsChange, "presets")
+                .with_details(format!("Imported from {}: {} created, {} replaced", source, report.created, report.replaced)),
+        ).await;
+    }
+    Ok(Json(report))
+}
+
@@ -0,0 +1,129 @@
+//! Preview Handlers
+//!
+//! HTTP handler for the muted hover-preview clip of a media item:
+//!
+//! - `GET /v2/media/:id/preview`
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+
+use crate::application::services::{Caller, ParentalControlService, PreviewClipService};
+use crate::domain::repositories::MediaRepository;
+use crate::presentation::http::dto::media_dto::LibraryQuery;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::presentation::http::handlers::parental_control_handlers::ensure_allowed;
+
+/// Seconds clients should wait before asking again for a clip being made
+const RETRY_AFTER_SECS: &str = "10";
+
+/// Get the preview clip of a media item
+///
+/// Range requests are supported, so the clip can be the source of a
+/// `<video>` element.
+///
+/// # Responses
+/// - 200/206: MP4 clip (H.264, no audio)
+/// - 202: The clip is being made; retry after the `Retry-After` seconds
+/// - 403: Blocked by the user's parental controls
+/// - 404: Media not found, or no clip can be made for it
+pub async fn get_preview(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(previews): State<Arc<PreviewClipService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
+    request: Request<Body>,
+) -> Result<Response, (StatusCode, String)> {
+    let media = media_repo
+        .find_by_id(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Media {} not found", id)))?;
+    ensure_allowed(&parental, &caller_user(caller.as_deref(), query.user.as_deref())?, &media).await?;
+
+    let Some(clip) = previews.clip(&media) else {
+        if !previews.request(&media) {
+            return Err((StatusCode::NOT_FOUND, format!("No preview available for media {}", id)));
+        }
+        return Ok((
+            StatusCode::ACCEPTED,
+            [(header::RETRY_AFTER, RETRY_AFTER_SECS)],
+            "Preview is being generated",
+        )
+            .into_response());
+    };
+
+    let mut response = ServeFile::new(clip)
+        .oneshot(request)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Body::new);
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("max-age=86400"));
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Media;
+    use crate::domain::repositories::ParentalControls;
+    use crate::domain::value_objects::MediaType;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::external::ffmpeg::{FFmpegAdapter, FFprobeAdapter};
+    use crate::infrastructure::persistence::sqlite::{
+        SqliteMediaRepository, SqliteParentalControlRepository, SqliteSeriesRepository,
+    };
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_preview_of_blocked_media_is_refused() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let media_repo: Arc<dyn MediaRepository> = Arc::new(SqliteMediaRepository::new(pool.clone()));
+        let movie = Media::new("/movies/Blocked.mkv".to_string(), MediaType::Movie, "Blocked".to_string())
+            .unwrap()
+            .with_content_rating(Some("R".to_string()));
+        let id = media_repo.save(&movie).await.unwrap();
+        let parental = Arc::new(ParentalControlService::new(
+            Arc::new(SqliteParentalControlRepository::new(pool.clone())),
+            Arc::new(SqliteSeriesRepository::new(pool)),
+        ));
+        let controls = ParentalControls { max_age: Some(12), ..Default::default() };
+        parental.set("kid", controls, None, None).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let previews = Arc::new(PreviewClipService::new(
+            Arc::new(FFmpegAdapter::new(Duration::from_secs(1))),
+            Arc::new(FFprobeAdapter::new(Duration::from_secs(1))),
+            media_repo.clone(),
+            dir.path(),
+        ));
+
+        let result = get_preview(
+            State(media_repo),
+            State(previews),
+            State(parental),
+            None,
+            Path(id),
+            Query(LibraryQuery { user: Some("kid".to_string()) }),
+            Request::new(Body::empty()),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().0, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,266 @@
+//! Profile Handlers
+//!
+//! HTTP handlers for user profiles: viewers sharing a user, each with their
+//! own avatar, languages and kid mode.
+//!
+//! - `GET /v2/profiles?user=` / `POST /v2/profiles`
+//! - `GET|PUT|DELETE /v2/profiles/:id`
+//! - `POST /v2/profiles/:id/activate`
+//!
+//! Streams of a user default to the audio and subtitle languages of the
+//! user's active profile; kid mode hides titles above children's ratings.
+//! A device key only sees its own user's profiles, and needs the parental
+//! PIN to turn kid mode off or to leave or remove a kid profile.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::services::{ApiKeyService, Caller, ParentalControlService};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{Profile, ProfileRepository, ProfileSettings};
+use crate::infrastructure::subtitle::normalize_language_code;
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::auth_handlers::{caller_user, require_admin};
+use crate::presentation::http::handlers::parental_control_handlers::{map_error, PinRequest};
+use crate::presentation::http::problem::ApiError;
+
+/// Longest accepted profile name, in characters
+const MAX_NAME_CHARS: usize = 50;
+/// Longest accepted avatar URL or name, in characters
+const MAX_AVATAR_CHARS: usize = 500;
+/// Most profiles per user
+const MAX_PROFILES_PER_USER: usize = 10;
+
+/// Query parameters selecting the user of the profiles
+#[derive(Debug, Deserialize)]
+pub struct ProfileQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Request body for adding a profile
+#[derive(Debug, Deserialize)]
+pub struct CreateProfileRequest {
+    #[serde(flatten)]
+    pub settings: ProfileSettings,
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Request body for replacing the settings of a profile
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    #[serde(flatten)]
+    pub settings: ProfileSettings,
+    /// Parental PIN; needed by devices to turn kid mode off
+    pub pin: Option<String>,
+}
+
+/// List the profiles of a user, oldest first
+///
+/// GET /v2/profiles?user=
+pub async fn list_profiles(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<ProfileQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let list = profiles.find_by_user(&user).await?;
+
+    Ok(Json(list))
+}
+
+/// Add a profile to a user
+///
+/// POST /v2/profiles
+///
+/// # Responses
+/// - 201: The new profile (active if it is the user's first)
+/// - 400: Empty or too long name or avatar, unknown language code, or too
+///   many profiles
+/// - 403: A device adding a profile to another user
+pub async fn create_profile(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    caller: Option<Extension<Caller>>,
+    Json(request): Json<CreateProfileRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    let settings = clean_settings(request.settings)?;
+    let existing = profiles.find_by_user(&user).await?;
+    if existing.len() >= MAX_PROFILES_PER_USER {
+        return Err(ApiError::bad_request(format!("At most {} profiles per user", MAX_PROFILES_PER_USER)));
+    }
+
+    let profile = profiles.create(&user, &settings).await?;
+
+    Ok((StatusCode::CREATED, Json(profile)))
+}
+
+/// Get a profile
+///
+/// GET /v2/profiles/:id
+pub async fn get_profile(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(find_profile(&profiles, caller.as_deref(), id).await?))
+}
+
+/// Replace the settings of a profile
+///
+/// PUT /v2/profiles/:id
+///
+/// # Responses
+/// - 200: The updated profile
+/// - 403: Another user's profile, or kid mode turned off without the PIN
+///   (`kid_mode_locked`, `wrong_pin`)
+/// - 429: Too many wrong PINs (`pin_locked`)
+pub async fn update_profile(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    State(api_keys): State<Arc<ApiKeyService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateProfileRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let settings = clean_settings(request.settings)?;
+    let current = find_profile(&profiles, caller.as_deref(), id).await?;
+    if current.settings.kid_mode && !settings.kid_mode {
+        leave_kid_mode(&api_keys, &parental, caller.as_deref(), &current.user_id, request.pin.as_deref()).await?;
+    }
+    let profile = profiles
+        .update(id, &settings)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Profile {} not found", id)))?;
+    let kid_mode = if profile.settings.kid_mode { "on" } else { "off" };
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("profile:{}", id))
+            .with_details(format!("Profile '{}' of {} updated, kid mode {}", profile.settings.name, profile.user_id, kid_mode)),
+    ).await;
+
+    Ok(Json(profile))
+}
+
+/// Remove a profile
+///
+/// DELETE /v2/profiles/:id
+///
+/// When the active profile is removed, the user's oldest remaining one
+/// becomes active. Removing a kid profile takes the PIN in the body.
+pub async fn delete_profile(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    State(api_keys): State<Arc<ApiKeyService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+    body: Option<Json<PinRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    let current = find_profile(&profiles, caller.as_deref(), id).await?;
+    if current.settings.kid_mode {
+        leave_kid_mode(&api_keys, &parental, caller.as_deref(), &current.user_id, request.pin.as_deref()).await?;
+    }
+    if !profiles.delete(id).await? {
+        return Err(ApiError::not_found(format!("Profile {} not found", id)));
+    }
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("profile:{}", id)).with_details("Profile removed")).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Make a profile the active one of its user
+///
+/// POST /v2/profiles/:id/activate
+///
+/// Switching from a kid profile to one without kid mode takes the PIN in
+/// the body.
+pub async fn activate_profile(
+    State(profiles): State<Arc<dyn ProfileRepository>>,
+    State(api_keys): State<Arc<ApiKeyService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Path(id): Path<i64>,
+    body: Option<Json<PinRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    let profile = find_profile(&profiles, caller.as_deref(), id).await?;
+    let active = profiles.find_active(&profile.user_id).await?;
+    if active.is_some_and(|active| active.settings.kid_mode) && !profile.settings.kid_mode {
+        leave_kid_mode(&api_keys, &parental, caller.as_deref(), &profile.user_id, request.pin.as_deref()).await?;
+    }
+    if !profiles.activate(id).await? {
+        return Err(ApiError::not_found(format!("Profile {} not found", id)));
+    }
+
+    Ok(Json(find_profile(&profiles, caller.as_deref(), id).await?))
+}
+
+/// Finds a profile of the caller's user
+async fn find_profile(profiles: &Arc<dyn ProfileRepository>, caller: Option<&Caller>, id: i64) -> Result<Profile, ApiError> {
+    let profile = profiles
+        .find(id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Profile {} not found", id)))?;
+    caller_user(caller, Some(&profile.user_id))?;
+    Ok(profile)
+}
+
+/// Lets the shared secret, or the user's parental PIN, lift kid mode
+async fn leave_kid_mode(
+    api_keys: &ApiKeyService,
+    parental: &ParentalControlService,
+    caller: Option<&Caller>,
+    user: &str,
+    pin: Option<&str>,
+) -> Result<(), ApiError> {
+    if require_admin(api_keys, caller).is_ok() {
+        return Ok(());
+    }
+    match pin {
+        Some(pin) => parental.check_pin(user, pin).await.map_err(map_error),
+        None => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "kid_mode_locked",
+            "Leaving kid mode needs the parental PIN",
+        )),
+    }
+}
+
+/// Trims the name and avatar and normalizes the language codes
+fn clean_settings(settings: ProfileSettings) -> Result<ProfileSettings, ApiError> {
+    let name = settings.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_CHARS {
+        return Err(ApiError::bad_request(format!("Name must be 1 to {} characters", MAX_NAME_CHARS)));
+    }
+    let avatar = settings.avatar.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    if avatar.as_ref().is_some_and(|a| a.chars().count() > MAX_AVATAR_CHARS) {
+        return Err(ApiError::bad_request(format!("Avatar must be at most {} characters", MAX_AVATAR_CHARS)));
+    }
+
+    Ok(ProfileSettings {
+        name,
+        avatar,
+        audio_language: clean_language(settings.audio_language)?,
+        subtitle_language: clean_language(settings.subtitle_language)?,
+        ui_language: clean_language(settings.ui_language)?,
+        kid_mode: settings.kid_mode,
+    })
+}
+
+fn clean_language(language: Option<String>) -> Result<Option<String>, ApiError> {
+    match language.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+        Some(code) => normalize_language_code(code)
+            .map(Some)
+            .ok_or_else(|| ApiError::bad_request(format!("Unknown language code '{}'", code))),
+        None => Ok(None),
+    }
+}
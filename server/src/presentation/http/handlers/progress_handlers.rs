@@ -11,12 +11,15 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::info;
 
+use crate::application::use_cases::batch_watch_state::{BatchWatchStateUseCase, WatchStateScope};
 use crate::domain::repositories::MediaRepository;
 use crate::domain::events::{
     ProgressUpdatedEvent,
     MediaWatchedEvent,
     MediaUnwatchedEvent,
+    WatchStateBatchUpdatedEvent,
 };
+use crate::shared::error::{ApplicationError, DomainError};
 use crate::infrastructure::messaging::InMemoryEventBus;
 use crate::interfaces::messaging::EventBus;
 
@@ -176,3 +179,93 @@ pub async fn mark_unwatched(
 
     Ok(StatusCode::OK)
 }
+
+/// Mark every episode of a season as watched
+pub async fn mark_season_watched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path((series_id, season)): Path<(i64, i32)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Season { series_id, season }, true).await
+}
+
+/// Mark every episode of a season as unwatched
+pub async fn mark_season_unwatched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path((series_id, season)): Path<(i64, i32)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Season { series_id, season }, false).await
+}
+
+/// Mark every episode of a series as watched
+pub async fn mark_series_watched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path(series_id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Series { series_id }, true).await
+}
+
+/// Mark every episode of a series as unwatched
+pub async fn mark_series_unwatched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path(series_id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Series { series_id }, false).await
+}
+
+/// Mark every available item of a collection as watched
+pub async fn mark_collection_watched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path(collection_id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Collection { collection_id }, true).await
+}
+
+/// Mark every available item of a collection as unwatched
+pub async fn mark_collection_unwatched(
+    State(use_case): State<Arc<BatchWatchStateUseCase>>,
+    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    Path(collection_id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    set_batch_watch_state(&use_case, &event_bus, WatchStateScope::Collection { collection_id }, false).await
+}
+
+/// Applies a batch change and publishes a single event for it
+async fn set_batch_watch_state(
+    use_case: &Arc<BatchWatchStateUseCase>,
+    event_bus: &Option<Arc<InMemoryEventBus>>,
+    scope: WatchStateScope,
+    watched: bool,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let result = use_case.execute(scope, watched).await.map_err(|e| match e {
+        ApplicationError::Domain(DomainError::NotFound(msg)) => (StatusCode::NOT_FOUND, msg),
+        e => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+    info!(
+        "Marked {} {} {}: {} of {} items changed",
+        scope.as_str(),
+        scope.id(),
+        if watched { "watched" } else { "unwatched" },
+        result.changed,
+        result.media_ids.len()
+    );
+
+    if let (Some(bus), true) = (event_bus, result.changed > 0) {
+        let event = WatchStateBatchUpdatedEvent::new(
+            scope.as_str().to_string(),
+            scope.id(),
+            scope.season(),
+            result.media_ids.clone(),
+            watched,
+        );
+        if let Err(e) = publish_event(bus, event).await {
+            tracing::warn!("Failed to publish batch watch state event: {}", e);
+        }
+    }
+
+    Ok(Json(result))
+}
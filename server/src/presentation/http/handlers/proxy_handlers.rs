@@ -3,13 +3,15 @@
 //! HTTP handlers for proxying external resources (CORS bypass).
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
 };
 use serde::Deserialize;
 use std::sync::Arc;
-use crate::infrastructure::cache::ImageCache;
+use crate::infrastructure::cache::{ImageCache, ImageTransform, OutputFormat};
+use crate::domain::repositories::{ArtworkOwner, ArtworkRepository, ExtraArtwork, MediaRepository, SeriesRepository};
+use crate::interfaces::external_services::{tmdb_variant_url, ArtworkKind, ArtworkMirror, ArtworkSize};
 use tracing::{debug, warn};
 
 /// Query parameters for image proxy
@@ -17,12 +19,20 @@ use tracing::{debug, warn};
 pub struct ImageProxyQuery {
     /// URL to proxy (must be TMDB image URL)
     pub url: String,
+    /// Resize to this width in pixels (keeps aspect ratio, never upscales)
+    pub width: Option<u32>,
+    /// Encoder quality 1-100 (JPEG/AVIF)
+    pub quality: Option<u8>,
+    /// Re-encode as jpeg, png, webp or avif
+    pub format: Option<String>,
 }
 
 /// Proxy TMDB images to bypass CORS restrictions
 ///
 /// Only allows proxying from image.tmdb.org for security.
 /// Images are cached in the filesystem cache for faster subsequent requests.
+/// `?width=`, `?quality=` and `?format=webp|avif` serve a resized/re-encoded
+/// variant, which is cached as well.
 pub async fn proxy_image(
     State(image_cache): State<Arc<ImageCache>>,
     Query(query): Query<ImageProxyQuery>,
@@ -31,6 +41,7 @@ pub async fn proxy_image(
     if !query.url.starts_with("https://image.tmdb.org/") {
         return Err((StatusCode::BAD_REQUEST, "Invalid image URL - only TMDB images allowed".to_string()));
     }
+    let transform = image_transform(query.width, query.quality, query.format.as_deref())?;
 
     // Try to get from cache first
     match image_cache.get_cached_image(&query.url) {
@@ -38,7 +49,9 @@ pub async fn proxy_image(
             debug!("Serving image from cache: {}", query.url);
             
             // Determine content type from URL extension
-            let content_type = get_content_type_from_url(&query.url);
+            let content_type = get_content_type_from_url(&query.url).to_string();
+            let (bytes, content_type) =
+                image_variant(&image_cache, &query.url, cached_bytes, content_type, transform).await?;
             
             let mut headers = HeaderMap::new();
             headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
@@ -47,7 +60,7 @@ pub async fn proxy_image(
                 "public, max-age=31536000".parse().unwrap(), // 1 year cache
             );
 
-            return Ok((headers, cached_bytes));
+            return Ok((headers, bytes));
         }
         Ok(None) => {
             // Not in cache, will download below
@@ -89,6 +102,8 @@ pub async fn proxy_image(
     if let Err(e) = image_cache.save_cached_image(&query.url, &bytes_vec) {
         warn!("Failed to save image to cache {}: {}", query.url, e);
     }
+    let (bytes_vec, content_type) =
+        image_variant(&image_cache, &query.url, bytes_vec, content_type, transform).await?;
 
     let mut headers = HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
@@ -100,6 +115,159 @@ pub async fn proxy_image(
     Ok((headers, bytes_vec))
 }
 
+/// Query parameters for local artwork
+#[derive(Debug, Deserialize)]
+pub struct ArtworkQuery {
+    /// Size variant: small, medium (default), large or original
+    pub size: Option<String>,
+    /// Set to "series" when the ID is a series ID
+    #[serde(rename = "type")]
+    pub owner: Option<String>,
+    /// Resize to this width in pixels (keeps aspect ratio, never upscales)
+    pub width: Option<u32>,
+    /// Encoder quality 1-100 (JPEG/AVIF)
+    pub quality: Option<u8>,
+    /// Re-encode as jpeg, png, webp or avif
+    pub format: Option<String>,
+}
+
+/// Serve locally mirrored artwork
+///
+/// `GET /v2/images/:id/:kind?size=small|medium|large|original[&type=series]`
+///
+/// `kind` is poster, backdrop, still, logo, clearart or disc. Images are
+/// served from local storage and only downloaded when missing. If a size
+/// variant is unavailable (e.g. offline and never mirrored), the stored
+/// default size is served instead. `width`, `quality` and `format` work as
+/// on the image proxy.
+pub async fn get_artwork(
+    State(image_cache): State<Arc<ImageCache>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(series_repo): State<Arc<dyn SeriesRepository>>,
+    State(artwork_mirror): State<Arc<dyn ArtworkMirror>>,
+    State(artwork_repo): State<Arc<dyn ArtworkRepository>>,
+    Path((id, kind)): Path<(i64, String)>,
+    Query(query): Query<ArtworkQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let kind = ArtworkKind::parse(&kind)
+        .ok_or((StatusCode::BAD_REQUEST, format!("Unknown artwork kind: {}", kind)))?;
+    let transform = image_transform(query.width, query.quality, query.format.as_deref())?;
+    let size = match query.size.as_deref() {
+        Some(s) => ArtworkSize::parse(s)
+            .ok_or((StatusCode::BAD_REQUEST, format!("Unknown artwork size: {}", s)))?,
+        None => ArtworkSize::Medium,
+    };
+
+    let is_series = query.owner.as_deref() == Some("series");
+    let url = if matches!(kind, ArtworkKind::Logo | ArtworkKind::ClearArt | ArtworkKind::Disc) {
+        let owner = if is_series { ArtworkOwner::Series } else { ArtworkOwner::Media };
+        let extra = artwork_repo.find(owner, id).await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .unwrap_or_default();
+        extra_artwork_url(extra, kind)
+    } else if is_series {
+        let series = series_repo.find_by_id(id).await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Series not found".to_string()))?;
+        match kind {
+            ArtworkKind::Poster => series.poster_url,
+            ArtworkKind::Backdrop => series.backdrop_url,
+            _ => None,
+        }
+    } else {
+        let media = media_repo.find_by_id(id).await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Media not found".to_string()))?;
+        let is_episode = media.is_episode();
+        match kind {
+            ArtworkKind::Poster => media.poster_url,
+            ArtworkKind::Still => media.poster_url.filter(|_| is_episode),
+            ArtworkKind::Backdrop => media.backdrop_url,
+            _ => None,
+        }
+    }
+    .ok_or((StatusCode::NOT_FOUND, "No artwork of this kind".to_string()))?;
+
+    let variant_url = tmdb_variant_url(&url, kind.tmdb_size(size));
+    let mut image = artwork_mirror.fetch(&variant_url).await;
+    if !matches!(image, Ok(Some(_))) && variant_url != url {
+        debug!("Artwork variant unavailable, falling back to stored size: {}", variant_url);
+        image = artwork_mirror.fetch(&url).await;
+    }
+
+    let (served_url, bytes) = match image {
+        Ok(Some(bytes)) => (variant_url, bytes),
+        Ok(None) => return Err((StatusCode::NOT_FOUND, "Artwork not found".to_string())),
+        Err(e) => {
+            warn!("Failed to load artwork for {} {}: {}", id, url, e);
+            return Err((StatusCode::BAD_GATEWAY, "Artwork unavailable".to_string()));
+        }
+    };
+
+    let content_type = get_content_type_from_url(&served_url).to_string();
+    let (bytes, content_type) = image_variant(&image_cache, &served_url, bytes, content_type, transform).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+    headers.insert(
+        header::CACHE_CONTROL,
+        "public, max-age=86400".parse().unwrap(), // URL is stable but artwork may be refreshed
+    );
+
+    Ok((headers, bytes))
+}
+
+/// Builds an image transform from query parameters
+fn image_transform(
+    width: Option<u32>,
+    quality: Option<u8>,
+    format: Option<&str>,
+) -> Result<ImageTransform, (StatusCode, String)> {
+    let format = match format {
+        Some(f) => Some(
+            OutputFormat::parse(f)
+                .ok_or((StatusCode::BAD_REQUEST, format!("Unknown image format: {}", f)))?,
+        ),
+        None => None,
+    };
+    Ok(ImageTransform { width, quality, format })
+}
+
+/// Applies a transform to image bytes, returning the bytes and content type to serve
+async fn image_variant(
+    image_cache: &Arc<ImageCache>,
+    url: &str,
+    bytes: Vec<u8>,
+    content_type: String,
+    transform: ImageTransform,
+) -> Result<(Vec<u8>, String), (StatusCode, String)> {
+    if transform.is_identity() {
+        return Ok((bytes, content_type));
+    }
+
+    // Decoding and encoding are CPU-bound
+    let image_cache = image_cache.clone();
+    let url = url.to_string();
+    let (bytes, content_type) = tokio::task::spawn_blocking(move || {
+        image_cache.get_or_create_variant(&url, &bytes, &transform)
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+
+    Ok((bytes, content_type.to_string()))
+}
+
+/// Picks the URL of a fanart.tv artwork kind
+fn extra_artwork_url(extra: ExtraArtwork, kind: ArtworkKind) -> Option<String> {
+    match kind {
+        ArtworkKind::Logo => extra.logo_url,
+        ArtworkKind::ClearArt => extra.clearart_url,
+        ArtworkKind::Disc => extra.disc_url,
+        _ => None,
+    }
+}
+
 /// Determines content type from URL extension
 fn get_content_type_from_url(url: &str) -> &str {
     if url.ends_with(".jpg") || url.ends_with(".jpeg") {
@@ -108,6 +276,8 @@ fn get_content_type_from_url(url: &str) -> &str {
         "image/png"
     } else if url.ends_with(".webp") {
         "image/webp"
+    } else if url.ends_with(".avif") {
+        "image/avif"
     } else {
         "image/jpeg" // Default
     }
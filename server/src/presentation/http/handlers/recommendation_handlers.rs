@@ -0,0 +1,54 @@
+//! Recommendation Handlers
+//!
+//! `GET /v2/recommendations` serves a user's recommendation rows: "Because
+//! you watched X" and top picks by genre affinity, refreshed nightly.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::services::{Caller, ParentalControlService, RecommendationService};
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+
+/// Query parameters for recommendations
+#[derive(Debug, Deserialize)]
+pub struct RecommendationQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+    /// Recompute instead of serving the cached rows
+    pub refresh: Option<bool>,
+}
+
+/// Get the recommendation rows of a user
+///
+/// `GET /v2/recommendations?user=&refresh=`
+///
+/// Titles blocked by the user's parental controls are left out.
+pub async fn get_recommendations(
+    State(recommendations): State<Arc<RecommendationService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<RecommendationQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = &caller_user(caller.as_deref(), query.user.as_deref())?;
+    let policy = content_policy(&parental, None, Some(user)).await?;
+
+    let rows = if query.refresh.unwrap_or(false) {
+        recommendations.refresh(user).await
+    } else {
+        recommendations.get(user).await
+    };
+    match rows {
+        Ok(rows) => Ok(Json(rows.filtered(&policy))),
+        Err(e) => {
+            tracing::error!("Error building recommendations for {}: {}", user, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
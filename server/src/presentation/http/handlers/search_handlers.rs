@@ -6,13 +6,23 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::info;
 
+use crate::application::services::dialogue_search::MatchedCue;
+use crate::application::services::search_suggestions::Suggestion;
+use crate::application::services::{
+    Caller, DialogueSearch, MediaFacets, MediaFilter, MediaFilterService, ParentalControlService, SearchSuggestions,
+    SemanticSearch,
+};
+use crate::domain::entities::Media;
 use crate::domain::repositories::{MediaRepository, SeriesRepository};
+use crate::shared::error::ApplicationError;
+use crate::presentation::http::dto::pagination::{Page, PageQuery};
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
 
 /// Search query parameters
 #[derive(Debug, Deserialize)]
@@ -22,8 +32,8 @@ pub struct SearchQuery {
     /// Optional media type filter ("movie" or "tv")
     #[serde(rename = "type")]
     pub media_type: Option<String>,
-    /// Maximum results (default: 20)
-    pub limit: Option<i32>,
+    /// User whose parental controls apply (default: "default")
+    pub user: Option<String>,
 }
 
 /// Search result item
@@ -36,74 +46,144 @@ pub struct SearchResult {
     pub poster_url: Option<String>,
     pub overview: Option<String>,
     pub tmdb_id: Option<i64>,
+    /// Similarity to the query (semantic search only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+impl From<Media> for SearchResult {
+    fn from(m: Media) -> Self {
+        Self {
+            id: m.id.unwrap_or(0),
+            year: m.release_date.as_ref().and_then(|d| d.get(..4).map(String::from)),
+            title: m.title,
+            media_type: m.media_type.as_str().to_string(),
+            poster_url: m.poster_url,
+            overview: m.overview,
+            tmdb_id: m.tmdb_id,
+            score: None,
+        }
+    }
+}
+
+/// Search response: a page of results
+#[derive(Debug, Serialize)]
+pub struct SearchResponse<T = SearchResult> {
+    #[serde(flatten)]
+    pub page: Page<T>,
+    pub query: String,
+    /// Value counts for filter chips (media search only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<MediaFacets>,
 }
 
-/// Search response
+/// Suggestion query parameters
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    /// What was typed so far
+    pub q: String,
+    /// Maximum suggestions (default: 8)
+    pub limit: Option<usize>,
+    /// User whose parental controls apply (default: "default")
+    pub user: Option<String>,
+}
+
+/// Suggestion response
 #[derive(Debug, Serialize)]
-pub struct SearchResponse {
-    pub results: Vec<SearchResult>,
-    pub total: usize,
+pub struct SuggestResponse {
+    /// The query answered, to drop responses to outdated keystrokes
     pub query: String,
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Semantic and dialogue search query parameters
+#[derive(Debug, Deserialize)]
+pub struct TextQuery {
+    /// Description of what happens, or words said, in the title
+    pub q: String,
+    /// User whose parental controls apply (default: "default")
+    pub user: Option<String>,
+}
+
+/// A title with the lines matching a dialogue search
+#[derive(Debug, Serialize)]
+pub struct DialogueResult {
+    #[serde(flatten)]
+    pub media: SearchResult,
+    /// Matching cues in playback order
+    pub cues: Vec<MatchedCue>,
 }
 
-/// Search media by title
+/// Search matches filtered, faceted, sorted and paged in memory
+const SEARCH_CANDIDATES: usize = 500;
+/// Closest titles ranked by semantic search
+const SEMANTIC_CANDIDATES: usize = 100;
+
+/// Search media by title, episode title, overview and cast
+///
+/// Uses the full-text index, so words match as prefixes and small typos are
+/// forgiven. Titles blocked by the user's parental controls are left out.
+///
+/// Results can be narrowed with `genre`, `year_from`, `year_to`,
+/// `resolution`, `watched`, `min_rating` and `library`, ordered with
+/// `sort=relevance|title|year|rating|added` and `order=asc|desc`, and
+/// paged with `page` and `limit`. `total` counts all filtered matches;
+/// `facets` has the value counts per filter.
 pub async fn search_media(
     State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    State(filters): State<Arc<MediaFilterService>>,
+    caller: Option<Extension<Caller>>,
     Query(query): Query<SearchQuery>,
+    Query(filter): Query<MediaFilter>,
+    Query(page): Query<PageQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     info!("Searching for: {}", query.q);
 
-    let limit = query.limit.unwrap_or(20).min(100) as usize;
     let media_type = query.media_type.as_deref();
 
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
     let media_list = media_repo
-        .search(&query.q, media_type, limit)
+        .search(&query.q, media_type, SEARCH_CANDIDATES)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let media_list = parental
+        .retain_allowed(&policy, media_list)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let results: Vec<SearchResult> = media_list
-        .into_iter()
-        .map(|m| {
-            let year = m.release_date.as_ref().and_then(|d| d.get(..4).map(String::from));
-            SearchResult {
-                id: m.id.unwrap_or(0),
-                title: m.title,
-                media_type: m.media_type.as_str().to_string(),
-                year,
-                poster_url: m.poster_url,
-                overview: m.overview,
-                tmdb_id: m.tmdb_id,
-            }
-        })
-        .collect();
+    let facets = filters.facets(&media_list, &filter);
+    let media_list = filters.apply(media_list, &filter);
 
-    let total = results.len();
     let response = SearchResponse {
-        results,
-        total,
+        page: page.paginate(media_list).map(SearchResult::from),
         query: query.q,
+        facets: Some(facets),
     };
 
     Ok(Json(response))
 }
 
-/// Search series by title
+/// Search series by title, a page at a time
+///
+/// Series blocked by the user's parental controls are left out.
 pub async fn search_series(
     State(series_repo): State<Arc<dyn SeriesRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
     Query(query): Query<SearchQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     info!("Searching series for: {}", query.q);
 
-    let limit = query.limit.unwrap_or(20).min(100) as usize;
-
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
     let series_list = series_repo
-        .search(&query.q, limit)
+        .search(&query.q, SEARCH_CANDIDATES)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let results: Vec<SearchResult> = series_list
-        .into_iter()
-        .map(|s| {
+    let series_list: Vec<_> = series_list.into_iter().filter(|s| policy.allows_series(s)).collect();
+    let results = page.paginate(series_list).map(|s| {
             SearchResult {
                 id: s.id.unwrap_or(0),
                 title: s.title,
@@ -112,16 +192,149 @@ pub async fn search_series(
                 poster_url: s.poster_url,
                 overview: s.overview,
                 tmdb_id: s.tmdb_id,
+                score: None,
             }
-        })
-        .collect();
+        });
 
-    let total = results.len();
     let response = SearchResponse {
-        results,
-        total,
+        page: results,
         query: query.q,
+        facets: None,
     };
 
     Ok(Json(response))
 }
+
+/// Suggest titles while typing
+///
+/// `GET /v2/search/suggest?q=` answers from an in-memory prefix index with
+/// movies, series, collections and cast members, ranked by popularity and
+/// recency. Titles blocked by the user's parental controls are left out.
+pub async fn suggest(
+    State(suggestions): State<Arc<SearchSuggestions>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<SuggestQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(8).min(50);
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let found = suggestions
+        .suggest(&query.q, &policy, limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(SuggestResponse {
+        query: query.q,
+        suggestions: found,
+    }))
+}
+
+/// Search movies and episodes by meaning
+///
+/// `GET /v2/search/semantic?q=` ranks titles by how close their overview is
+/// to the query in meaning, so a description of the plot finds the title.
+/// Needs an embedding model (`EMBEDDING_MODEL`); overviews are indexed after
+/// each library scan. The 100 closest titles are paged with `page` and
+/// `limit`. Titles blocked by the user's parental controls are left out.
+pub async fn semantic_search(
+    State(semantic): State<Option<Arc<SemanticSearch>>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<TextQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let Some(semantic) = semantic else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Semantic search is disabled (EMBEDDING_MODEL is not set)".to_string(),
+        ));
+    };
+    info!("Semantic search for: {}", query.q);
+
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let ranked = semantic.search(&query.q, SEMANTIC_CANDIDATES).await.map_err(|e| match e {
+        ApplicationError::Embedding(_) => (StatusCode::SERVICE_UNAVAILABLE, e.to_string()),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+
+    let mut scores = std::collections::HashMap::new();
+    let mut media_list = Vec::with_capacity(ranked.len());
+    for (media_id, score) in ranked {
+        if let Some(media) = media_repo
+            .find_by_id(media_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        {
+            scores.insert(media_id, score);
+            media_list.push(media);
+        }
+    }
+    let media_list = parental
+        .retain_allowed(&policy, media_list)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let results = page.paginate(media_list).map(|m| {
+        let score = m.id.and_then(|id| scores.get(&id).copied());
+        SearchResult { score, ..SearchResult::from(m) }
+    });
+
+    Ok(Json(SearchResponse {
+        page: results,
+        query: query.q,
+        facets: None,
+    }))
+}
+
+/// Search movies and episodes by what is said in them
+///
+/// `GET /v2/search/dialogue?q=` looks through the indexed subtitles
+/// (downloaded, extracted and generated) and returns each title with the
+/// times of its matching cues, so playback can start at the scene. Cues
+/// with the words as a phrase come first; results are paged with `page` and
+/// `limit`. Titles blocked by the user's parental controls are left out.
+pub async fn search_dialogue(
+    State(dialogue): State<Arc<DialogueSearch>>,
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<TextQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    info!("Dialogue search for: {}", query.q);
+
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let hits = dialogue
+        .search(&query.q, SEARCH_CANDIDATES)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut cues = std::collections::HashMap::new();
+    let mut media_list = Vec::with_capacity(hits.len());
+    for hit in hits {
+        if let Some(media) = media_repo
+            .find_by_id(hit.media_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        {
+            cues.insert(hit.media_id, hit.cues);
+            media_list.push(media);
+        }
+    }
+    let media_list = parental
+        .retain_allowed(&policy, media_list)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let results = page.paginate(media_list).map(|m| {
+        let cues = m.id.and_then(|id| cues.remove(&id)).unwrap_or_default();
+        DialogueResult { media: SearchResult::from(m), cues }
+    });
+
+    Ok(Json(SearchResponse {
+        page: results,
+        query: query.q,
+        facets: None,
+    }))
+}
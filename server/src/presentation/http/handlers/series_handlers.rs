@@ -3,23 +3,39 @@
 //! HTTP handlers for series operations.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
+use serde::Serialize;
 use std::sync::Arc;
+use crate::application::MetadataEnricher;
+use crate::application::services::{Caller, ParentalControlService};
+use crate::application::services::media_filters::SortOrder;
 use crate::application::use_cases::manage_series::ManageSeriesUseCase;
-use crate::domain::repositories::MediaRepository;
+use crate::domain::repositories::{ArtworkOwner, ArtworkRepository, MediaRepository, SeriesOrder};
+use crate::presentation::http::dto::media_dto::LibraryQuery;
+use crate::presentation::http::dto::pagination::PageQuery;
 use crate::presentation::http::dto::series_dto::{SeriesResponse, SeriesDetailsResponse};
+use crate::presentation::http::handlers::parental_control_handlers::content_policy;
 use crate::shared::error::ApplicationError;
 
 /// Get series by ID with episodes grouped by season
+///
+/// Returns 403 if the user's parental controls block the series; blocked
+/// episodes are left out.
 pub async fn get_series(
     State(use_case): State<Arc<ManageSeriesUseCase>>,
     State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(artwork_repo): State<Arc<dyn ArtworkRepository>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
+    Query(query): Query<LibraryQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+
     // Fetch series
     let series = match use_case.get_series(id).await {
         Ok(s) => s,
@@ -32,6 +48,10 @@ pub async fn get_series(
         }
     };
 
+    if !policy.allows_series(&series) {
+        return Err((StatusCode::FORBIDDEN, "Blocked by parental controls".to_string()));
+    }
+
     // Fetch episodes for this series
     let episodes = match media_repo.find_by_series(id).await {
         Ok(eps) => eps,
@@ -41,24 +61,98 @@ pub async fn get_series(
         }
     };
 
-    let response = SeriesDetailsResponse::from_series_and_episodes(series, episodes);
+    let episodes = parental
+        .retain_allowed(&policy, episodes)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut response = SeriesDetailsResponse::from_series_and_episodes(series, episodes);
+    match artwork_repo.find(ArtworkOwner::Series, id).await {
+        Ok(Some(artwork)) => response.series = response.series.with_extra_artwork(&artwork),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Error fetching artwork for series {}: {}", id, e),
+    }
     Ok(Json(response))
 }
 
-/// List all series
+/// List the series the user's parental controls allow, a page at a time
+///
+/// Sorted with `sort=title|year|rating|added` and `order=asc|desc`.
 pub async fn list_series(
     State(use_case): State<Arc<ManageSeriesUseCase>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<LibraryQuery>,
+    Query(page): Query<PageQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    match use_case.list_all().await {
-        Ok(series_list) => {
-            let response: Vec<SeriesResponse> = series_list
-                .into_iter()
-                .map(SeriesResponse::from)
-                .collect();
-            Ok(Json(response))
+    let sort = page.sort_key(&["title", "year", "rating", "added"])?;
+    let policy = content_policy(&parental, caller.as_deref(), query.user.as_deref()).await?;
+    let internal_error = |e: ApplicationError| {
+        tracing::error!("Error listing series: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+    };
+
+    if !policy.is_restricted() {
+        let (order, descending) = match sort {
+            Some("year") => (SeriesOrder::Year, true),
+            Some("rating") => (SeriesOrder::Rating, true),
+            Some("title") => (SeriesOrder::Title, false),
+            _ => (SeriesOrder::Added, true),
+        };
+        // Without `sort`, the listing keeps its own order
+        let descending = match (sort, page.order) {
+            (Some(_), Some(order)) => order == SortOrder::Desc,
+            _ => descending,
+        };
+        let (series_list, total) = use_case
+            .list_page(order, descending, page.offset(), page.limit())
+            .await
+            .map_err(internal_error)?;
+        return Ok(Json(page.page_of(series_list, total).map(SeriesResponse::from)));
+    }
+
+    // Parental controls look at every series
+    let mut series_list: Vec<_> = use_case
+        .list_all()
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .filter(|series| policy.allows_series(series))
+        .collect();
+    match sort {
+        Some("year") => page.sort_by(&mut series_list, true, |a, b| a.first_air_date.cmp(&b.first_air_date)),
+        Some("rating") => page.sort_by(&mut series_list, true, |a, b| {
+            a.rating.partial_cmp(&b.rating).unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        Some("added") => page.sort_by(&mut series_list, true, |a, b| a.created_at.cmp(&b.created_at)),
+        Some(_) => page.sort_by(&mut series_list, false, |a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase())),
+        None => {}
+    }
+    Ok(Json(page.paginate(series_list).map(SeriesResponse::from)))
+}
+
+/// Result of a series refresh
+#[derive(Debug, Serialize)]
+pub struct SeriesRefreshResponse {
+    pub series_id: i64,
+    pub episodes_updated: usize,
+}
+
+/// Re-fetch a series and its episodes from TMDB
+///
+/// `POST /v2/series/:id/refresh` updates overview, artwork and episode
+/// titles without a rescan. Series without a TMDB ID are left as they are.
+pub async fn refresh_series(
+    State(enricher): State<Arc<MetadataEnricher>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    match enricher.refresh_series_from_tmdb(id).await {
+        Ok(episodes_updated) => Ok(Json(SeriesRefreshResponse { series_id: id, episodes_updated })),
+        Err(ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg))) => {
+            Err((StatusCode::NOT_FOUND, msg))
         }
         Err(e) => {
-            tracing::error!("Error listing series: {}", e);
+            tracing::error!("Error refreshing series {}: {}", id, e);
             Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
         }
     }
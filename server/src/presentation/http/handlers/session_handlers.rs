@@ -0,0 +1,47 @@
+//! Session Handlers
+//!
+//! HTTP handlers for active stream sessions:
+//!
+//! - `GET /v2/sessions`
+//! - `DELETE /v2/admin/sessions/:id`
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use crate::application::services::{HlsSessionManager, StreamSessionInfo, StreamSessionRegistry};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+
+/// List active stream sessions
+pub async fn list_sessions(
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+) -> Json<Vec<StreamSessionInfo>> {
+    Json(stream_sessions.sessions())
+}
+
+/// Terminate a stream session
+///
+/// Open responses of the session end, its HLS transcoder (if any) is
+/// stopped, and the client cannot resume the same playback until the
+/// session idle timeout passes.
+pub async fn terminate_session(
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(hls_sessions): State<Arc<HlsSessionManager>>,
+    auditor: Auditor,
+    Path(session_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = stream_sessions
+        .terminate(&session_id)
+        .await
+        .ok_or((StatusCode::NOT_FOUND, "Session not found".to_string()))?;
+
+    if let Some(hls_session) = &session.hls_session {
+        hls_sessions.stop_session(hls_session);
+    }
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("session:{}", session_id)).with_details("Stream session terminated")).await;
+    Ok(Json(session))
+}
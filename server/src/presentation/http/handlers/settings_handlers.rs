@@ -0,0 +1,67 @@
+//! Settings Handlers
+//!
+//! HTTP handlers for the settings that can be changed while running:
+//!
+//! - `GET /v2/admin/settings` - Settings in effect
+//! - `PUT /v2/admin/settings` - Change some of them
+//!
+//! The TMDB API key is shown as `********`. With `API_SECRET` set, both
+//! need the shared secret.
+
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::application::services::SettingsService;
+use crate::application::services::runtime_settings::redact_secrets;
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::problem::ApiError;
+
+/// Settings in effect
+#[derive(Debug, Serialize)]
+pub struct SettingsResponse {
+    pub settings: serde_json::Value,
+    /// Keys changed through the API, overriding the configuration
+    pub overridden: Vec<String>,
+}
+
+/// Get the settings in effect
+pub async fn get_settings(
+    State(settings): State<Arc<SettingsService>>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(SettingsResponse {
+        settings: redact_secrets(&settings.current()),
+        overridden: settings.overridden().await,
+    }))
+}
+
+/// Change settings, taking effect without a restart
+///
+/// The body holds the settings to change; `null` goes back to the
+/// configured value.
+///
+/// # Responses
+/// - 200: The settings in effect
+/// - 400: Unknown setting or invalid value; nothing is changed
+pub async fn update_settings(
+    State(settings): State<Arc<SettingsService>>,
+    auditor: Auditor,
+    Json(changes): Json<serde_json::Map<String, serde_json::Value>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let keys: Vec<String> = changes.keys().cloned().collect();
+    let current = settings.update(changes).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, "settings")
+            .with_details(format!("Settings changed: {}", keys.join(", "))),
+    ).await;
+    Ok(Json(SettingsResponse {
+        settings: redact_secrets(&current),
+        overridden: settings.overridden().await,
+    }))
+}
+
@@ -0,0 +1,282 @@
+//! Stats Handlers
+//!
+//! HTTP handlers for library statistics:
+//!
+//! - `GET /v2/stats/subtitles`
+//! - `GET /v2/stats/user`
+//! - `GET /v2/stats/server`
+//! - `GET /v2/admin/stats`
+//! - `GET /v2/admin/stats/slow`
+//! - `GET /v2/admin/stats/memory`
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::application::services::Caller;
+use crate::application::use_cases::admin_dashboard::AdminDashboardUseCase;
+use crate::application::use_cases::subtitle_coverage::{SubtitleCoverageOptions, SubtitleCoverageUseCase};
+use crate::domain::repositories::{PlaybackEntry, WatchHistoryRepository, WatchTime, WatchTotals};
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::presentation::http::problem::ApiError;
+use crate::infrastructure::process_memory::ProcessMemory;
+use crate::infrastructure::slow_operations::SlowOperationTracker;
+
+/// Query parameters for the subtitle coverage report
+#[derive(Debug, Deserialize)]
+pub struct SubtitleCoverageQuery {
+    /// Comma-separated language codes (default: SUBTITLE_LANGUAGES)
+    pub languages: Option<String>,
+    /// Set to false to skip probing files for embedded tracks
+    pub embedded: Option<bool>,
+}
+
+/// Get per-language subtitle coverage per library and per series
+///
+/// `GET /v2/stats/subtitles`
+pub async fn get_subtitle_coverage(
+    State(use_case): State<Arc<SubtitleCoverageUseCase>>,
+    Query(query): Query<SubtitleCoverageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let options = SubtitleCoverageOptions {
+        languages: query.languages
+            .as_deref()
+            .map(|l| l.split(',').map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+            .unwrap_or_default(),
+        probe_embedded: query.embedded.unwrap_or(true),
+    };
+
+    match use_case.execute(options).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("Error building subtitle coverage report: {}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Get everything the admin dashboard shows
+///
+/// `GET /v2/admin/stats`
+///
+/// # Responses
+/// - 200: Library counts by type, resolution and codec, storage per
+///   library, identification confidence, cache hit rates, recent scans and
+///   active jobs
+/// - 401/403: Authentication is enabled and the caller is not the admin
+pub async fn get_admin_stats(
+    State(use_case): State<Arc<AdminDashboardUseCase>>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(use_case.execute().await?))
+}
+
+/// Query parameters for the slow operations report
+#[derive(Debug, Deserialize)]
+pub struct SlowOperationsQuery {
+    /// Maximum endpoints and statements listed (default: 20)
+    pub limit: Option<usize>,
+}
+
+/// Get the slowest recent endpoints and queries
+///
+/// `GET /v2/admin/stats/slow`
+pub async fn get_slow_operations(
+    State(tracker): State<Arc<SlowOperationTracker>>,
+    Query(query): Query<SlowOperationsQuery>,
+) -> impl IntoResponse {
+    Json(tracker.report(query.limit.unwrap_or(20)))
+}
+
+/// Get the server's memory footprint and container limit
+///
+/// `GET /v2/admin/stats/memory`
+pub async fn get_memory_usage() -> impl IntoResponse {
+    Json(ProcessMemory::current())
+}
+
+/// Months covered by the monthly watch time
+const STATS_MONTHS: u32 = 12;
+/// Series and devices listed
+const STATS_TOP: u32 = 10;
+/// Latest playbacks listed in user statistics
+const STATS_RECENT: u32 = 20;
+
+/// Query parameters selecting the user of the statistics
+#[derive(Debug, Deserialize)]
+pub struct UserStatsQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Watch totals
+#[derive(Debug, Serialize)]
+pub struct WatchTotalsResponse {
+    pub plays: i64,
+    pub hours: f64,
+    /// Distinct media played
+    pub media_count: i64,
+    /// Plays that ended with the media watched
+    pub completed: i64,
+}
+
+/// Watch time of a series
+#[derive(Debug, Serialize)]
+pub struct SeriesWatchTimeResponse {
+    pub series_id: i64,
+    pub title: Option<String>,
+    pub plays: i64,
+    pub hours: f64,
+}
+
+/// Watch time of a user or device
+#[derive(Debug, Serialize)]
+pub struct WatchTimeResponse {
+    /// User ID or device
+    pub name: String,
+    pub plays: i64,
+    pub hours: f64,
+}
+
+/// Watch time of a month
+#[derive(Debug, Serialize)]
+pub struct MonthlyHoursResponse {
+    /// "YYYY-MM"
+    pub month: String,
+    pub hours: f64,
+}
+
+/// Watch statistics of a user
+#[derive(Debug, Serialize)]
+pub struct UserStatsResponse {
+    pub user: String,
+    pub totals: WatchTotalsResponse,
+    pub most_watched_series: Vec<SeriesWatchTimeResponse>,
+    /// The last 12 months, oldest first
+    pub hours_per_month: Vec<MonthlyHoursResponse>,
+    pub devices: Vec<WatchTimeResponse>,
+    /// Latest playbacks, newest first
+    pub recent: Vec<PlaybackEntry>,
+}
+
+/// Watch statistics of the server
+#[derive(Debug, Serialize)]
+pub struct ServerStatsResponse {
+    pub totals: WatchTotalsResponse,
+    pub users: Vec<WatchTimeResponse>,
+    pub most_watched_series: Vec<SeriesWatchTimeResponse>,
+    /// The last 12 months, oldest first
+    pub hours_per_month: Vec<MonthlyHoursResponse>,
+    pub devices: Vec<WatchTimeResponse>,
+}
+
+/// Get the watch statistics of a user
+///
+/// `GET /v2/stats/user?user=`
+pub async fn get_user_stats(
+    State(history): State<Arc<dyn WatchHistoryRepository>>,
+    caller: Option<Extension<Caller>>,
+    Query(query): Query<UserStatsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let scope = Some(user.as_str());
+
+    Ok(Json(UserStatsResponse {
+        user: user.clone(),
+        totals: totals_response(history.totals(scope).await.map_err(map_history_error)?),
+        most_watched_series: series_response(history.top_series(scope, STATS_TOP).await.map_err(map_history_error)?),
+        hours_per_month: monthly_hours(&history, scope).await?,
+        devices: watch_time_response(history.by_client(scope, STATS_TOP).await.map_err(map_history_error)?),
+        recent: history.recent(scope, STATS_RECENT).await.map_err(map_history_error)?,
+    }))
+}
+
+/// Get the watch statistics of all users
+///
+/// `GET /v2/stats/server`
+///
+/// # Responses
+/// - 200: Totals, per-user watch time, most watched series, hours per month
+/// - 401/403: Authentication is enabled and the caller is not the admin
+pub async fn get_server_stats(
+    State(history): State<Arc<dyn WatchHistoryRepository>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Ok(Json(ServerStatsResponse {
+        totals: totals_response(history.totals(None).await.map_err(map_history_error)?),
+        users: watch_time_response(history.by_user().await.map_err(map_history_error)?),
+        most_watched_series: series_response(history.top_series(None, STATS_TOP).await.map_err(map_history_error)?),
+        hours_per_month: monthly_hours(&history, None).await?,
+        devices: watch_time_response(history.by_client(None, STATS_TOP).await.map_err(map_history_error)?),
+    }))
+}
+
+/// Hours of each of the last 12 months, including months without playback
+async fn monthly_hours(
+    history: &Arc<dyn WatchHistoryRepository>,
+    user: Option<&str>,
+) -> Result<Vec<MonthlyHoursResponse>, (StatusCode, String)> {
+    let now = Utc::now();
+    let months_back = now.year() * 12 + now.month0() as i32 - (STATS_MONTHS as i32 - 1);
+    let since = Utc
+        .with_ymd_and_hms(months_back.div_euclid(12), months_back.rem_euclid(12) as u32 + 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now);
+    let recorded = history.per_month(user, since).await.map_err(map_history_error)?;
+
+    Ok((0..STATS_MONTHS as i32)
+        .map(|offset| {
+            let month_index = months_back + offset;
+            let month = format!("{:04}-{:02}", month_index.div_euclid(12), month_index.rem_euclid(12) + 1);
+            let seconds = recorded.iter().find(|m| m.month == month).map_or(0, |m| m.seconds);
+            MonthlyHoursResponse { month, hours: hours(seconds) }
+        })
+        .collect())
+}
+
+fn totals_response(totals: WatchTotals) -> WatchTotalsResponse {
+    WatchTotalsResponse {
+        plays: totals.plays,
+        hours: hours(totals.seconds),
+        media_count: totals.media_count,
+        completed: totals.completed,
+    }
+}
+
+fn series_response(series: Vec<WatchTime>) -> Vec<SeriesWatchTimeResponse> {
+    series
+        .into_iter()
+        .filter_map(|s| {
+            Some(SeriesWatchTimeResponse {
+                series_id: s.key.parse().ok()?,
+                title: s.title,
+                plays: s.plays,
+                hours: hours(s.seconds),
+            })
+        })
+        .collect()
+}
+
+fn watch_time_response(times: Vec<WatchTime>) -> Vec<WatchTimeResponse> {
+    times
+        .into_iter()
+        .map(|t| WatchTimeResponse {
+            name: t.key,
+            plays: t.plays,
+            hours: hours(t.seconds),
+        })
+        .collect()
+}
+
+/// Seconds as hours, to two decimals
+fn hours(seconds: i64) -> f64 {
+    (seconds as f64 / 36.0).round() / 100.0
+}
+
+fn map_history_error(e: crate::shared::error::RepositoryError) -> (StatusCode, String) {
+    tracing::error!("Error reading watch history: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+}
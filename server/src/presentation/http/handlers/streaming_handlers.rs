@@ -7,23 +7,31 @@ use axum::{
     extract::{Path, Query, State},
     http::{header, StatusCode, HeaderMap},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
+use crate::application::use_cases::extract_subtitle::ExtractSubtitleUseCase;
 use crate::application::use_cases::stream_media::StreamMediaUseCase;
+use crate::application::services::{PlaybackQos, PlaybackGuard, PlaybackDecisionService, PlaybackDecision, PlaybackMethod, ClientCapabilities, StreamSessionRegistry, StreamRequest, StreamMode, StreamGuard, LoudnessNormalizer, CropDetectionService, StreamClaims, StreamUrlSigner, TokenError, ParentalControlService, Caller};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::presentation::http::handlers::parental_control_handlers::ensure_allowed;
 use crate::interfaces::external_services::VideoAnalyzer;
-use crate::shared::error::ApplicationError;
-use crate::infrastructure::subtitle::{SubtitleDetector, read_and_convert_srt_with_offset};
-use crate::domain::repositories::MediaRepository;
+use crate::shared::error::{ApplicationError, DomainError, TranscodeError};
+use crate::infrastructure::subtitle::{encoding_for_label, SubtitleOptions, SubtitleStore, TagPolicy, read_and_convert_srt_with_offset};
+use crate::domain::repositories::{AccessibilityPreferenceRepository, AccessibilityPreferences, CropDetection, MediaRepository, QualityPreferenceRepository, SubtitleOffset, SubtitleOffsetRepository};
+use crate::domain::value_objects::{QualityConstraint, QualityPreset};
 use crate::domain::events::{
-    StreamStartedEvent,
+    AuditAction,
+    AuditEvent,
     StreamEndedEvent,
     StreamErrorEvent,
     ThumbnailGeneratedEvent,
 };
 use crate::infrastructure::messaging::InMemoryEventBus;
 use crate::interfaces::messaging::EventBus;
+use crate::presentation::http::problem::ApiError;
 use std::ops::Deref;
 use tokio::io::{AsyncSeekExt, AsyncReadExt};
 use tokio::process::Command;
@@ -35,8 +43,108 @@ pub struct WebStreamQuery {
     /// Start position in seconds (accepts float, converted to integer)
     #[serde(default)]
     pub start: f64,
-    /// Audio track index (optional)
+    /// Audio track index; remembered as the user's preferred language.
+    /// Without it the remembered language (or the default track) is used
     pub audio: Option<i32>,
+    /// Force a maximum resolution for this session (2160p, 1080p, 720p,
+    /// 480p, 360p; "auto" ignores the remembered preference)
+    pub quality: Option<String>,
+    /// Force a maximum video bitrate in kbps for this session
+    pub bitrate: Option<u32>,
+    /// Client device ID; its remembered preference applies when the session
+    /// sets no override
+    pub device: Option<String>,
+    /// Remember this session's override as the device's preference
+    #[serde(default)]
+    pub remember: bool,
+    /// Copy the video stream even if browsers cannot decode it (the client
+    /// reported support via playback-info)
+    #[serde(default)]
+    pub copy_video: bool,
+    /// Normalize loudness (EBU R128); forces an audio transcode
+    #[serde(default)]
+    pub loudnorm: bool,
+    /// Remove the detected black bars; forces a video transcode when the
+    /// media has a stored crop with bars
+    #[serde(default)]
+    pub crop: bool,
+    /// User the stream session is counted for (default: "default")
+    pub user: Option<String>,
+}
+
+/// Query parameters for direct streaming
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    /// Stream only the audio track (AAC), for listening over low bandwidth
+    #[serde(default)]
+    pub audio_only: bool,
+    /// Audio track index for audio-only mode (default: preferred language)
+    pub audio: Option<i32>,
+    /// Start position in seconds for audio-only mode
+    #[serde(default)]
+    pub start: f64,
+    /// Audio bitrate in kbps for audio-only mode (default: source AAC is
+    /// copied, other codecs are transcoded at 128k)
+    pub bitrate: Option<u32>,
+    /// Normalize loudness (EBU R128) in audio-only mode; forces a transcode
+    #[serde(default)]
+    pub loudnorm: bool,
+    /// User the stream session is counted for (default: "default")
+    pub user: Option<String>,
+    /// Client device ID (default: the user agent)
+    pub device: Option<String>,
+}
+
+/// Wraps a response body stream so playback counts as active while it is
+/// sent, and ends it when its stream session is terminated
+fn guarded_body<S>(stream: S, guard: PlaybackGuard, session: StreamGuard) -> Body
+where
+    S: futures::Stream<Item = std::io::Result<bytes::Bytes>> + Send + 'static,
+{
+    use futures::StreamExt;
+    Body::from_stream(stream.map(move |chunk| {
+        let _ = &guard;
+        match chunk {
+            Ok(bytes) if !session.record(bytes.len()) => {
+                Err(std::io::Error::other("Stream session terminated"))
+            }
+            chunk => chunk,
+        }
+    }))
+}
+
+/// Client name of a stream session: the device ID, else the user agent
+fn stream_client(device: Option<&str>, headers: &HeaderMap) -> Option<String> {
+    device
+        .map(|d| d.to_string())
+        .or_else(|| headers.get(header::USER_AGENT).and_then(|h| h.to_str().ok()).map(|s| s.to_string()))
+}
+
+/// User a stream is counted for: the one its session token was issued to,
+/// else the device key's user, else the `user` parameter
+pub(crate) fn stream_user(
+    claims: Option<&Extension<StreamClaims>>,
+    caller: Option<&Caller>,
+    user: Option<&str>,
+) -> Result<String, ApiError> {
+    match claims {
+        Some(Extension(claims)) => Ok(claims.user.clone()),
+        None => caller_user(caller, user),
+    }
+}
+
+/// Joins or starts the stream session of a request
+pub(crate) async fn open_stream_session(
+    stream_sessions: &StreamSessionRegistry,
+    request: StreamRequest,
+) -> Result<StreamGuard, ApiError> {
+    stream_sessions.open(request).await.map_err(|e| match e {
+        TranscodeError::LimitReached(msg) => {
+            ApiError::new(StatusCode::TOO_MANY_REQUESTS, "stream_limit_reached", format!("Stream limit reached: {}", msg))
+        }
+        TranscodeError::Terminated(msg) => ApiError::new(StatusCode::GONE, "stream_terminated", msg),
+        e => ApiError::internal(e.to_string()),
+    })
 }
 
 /// Helper function to publish streaming events
@@ -53,12 +161,38 @@ async fn publish_stream_event<T: crate::interfaces::messaging::DomainEvent>(
 }
 
 /// Stream media by ID
+///
+/// With `?audio_only=true` only the audio track is streamed (see [`stream_audio_only`]).
+/// Media blocked by the user's parental controls is refused with 403.
+#[allow(clippy::too_many_arguments)]
 pub async fn stream_media(
     State(use_case): State<Arc<StreamMediaUseCase>>,
-    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(playback_qos): State<Arc<PlaybackQos>>,
+    State(loudness): State<Arc<LoudnessNormalizer>>,
+    State(parental): State<Arc<ParentalControlService>>,
     Path(id): Path<i64>,
+    Query(query): Query<StreamQuery>,
+    claims: Option<Extension<StreamClaims>>,
+    caller: Option<Extension<Caller>>,
     headers: HeaderMap,
-) -> Result<Response, (StatusCode, String)> {
+) -> Result<Response, ApiError> {
+    let session_request = StreamRequest {
+        media_id: id,
+        user: stream_user(claims.as_ref(), caller.as_deref(), query.user.as_deref())?,
+        client: stream_client(query.device.as_deref(), &headers),
+        mode: StreamMode::Direct,
+        bitrate_kbps: None,
+        hls_session: None,
+    };
+    let (media, _result) = use_case.prepare_stream(id).await
+        ?;
+    ensure_allowed(&parental, &session_request.user, &media).await?;
+    if query.audio_only {
+        return stream_audio_only(use_case, video_analyzer, stream_sessions, playback_qos, loudness, id, query, session_request).await;
+    }
+
     // Check for Range header
     let range_header = headers.get(header::RANGE)
         .and_then(|h| h.to_str().ok());
@@ -71,44 +205,31 @@ pub async fn stream_media(
             // Prepare stream and get file handle from use case (delegates file I/O)
             return match use_case.prepare_stream(id).await {
                 Ok((media, result)) => {
-                    // Publish stream started event
-                    let client_ip = headers.get(header::FORWARDED)
-                        .or_else(|| headers.get("x-forwarded-for"))
-                        .and_then(|h| h.to_str().ok())
-                        .map(|s| s.to_string());
-                    let user_agent = headers.get(header::USER_AGENT)
-                        .and_then(|h| h.to_str().ok())
-                        .map(|s| s.to_string());
-                    let event = StreamStartedEvent::new(
-                        id,
-                        client_ip,
-                        user_agent,
-                        result.needs_transcoding,
-                    );
-                    publish_stream_event(&event_bus, event).await;
+                    // Range requests of one playback join the same session
+                    let session = open_stream_session(&stream_sessions, session_request).await?;
 
                     let file_size = result.content_length;
                     let end = end.unwrap_or(file_size - 1);
 
                     if start >= file_size {
-                        return Err((StatusCode::RANGE_NOT_SATISFIABLE, "Range not satisfiable".to_string()));
+                        return Err(ApiError::from((StatusCode::RANGE_NOT_SATISFIABLE, "Range not satisfiable".to_string())));
                     }
 
                     // Get file handle from use case (delegates file I/O)
                     let file = use_case.get_file_handle(id).await
-                        .map_err(|e| map_error(e))?;
+                        .map_err(ApiError::from)?;
 
                     // Seek to start position
                     let length = end - start + 1;
                     let mut file = file;
                     file.seek(std::io::SeekFrom::Start(start)).await
-                        .map_err(|e| map_error(ApplicationError::Filesystem(
+                        .map_err(|e| ApiError::from(ApplicationError::Filesystem(
                             crate::shared::error::FilesystemError::Io(e)
                         )))?;
 
                     // Create stream limited to range length
                     let stream = ReaderStream::new(file.take(length));
-                    let body = Body::from_stream(stream);
+                    let body = guarded_body(stream, playback_qos.track(id), session);
 
                     // Build partial content response
                     let mut response = Response::new(body);
@@ -120,7 +241,7 @@ pub async fn stream_media(
 
                     Ok(response)
                 }
-                Err(e) => Err(map_error(e)),
+                Err(e) => Err(ApiError::from(e)),
             };
         }
     }
@@ -128,29 +249,15 @@ pub async fn stream_media(
     // No range header - stream full file
     match use_case.prepare_stream(id).await {
         Ok((media, result)) => {
-            // Publish stream started event
-            let client_ip = headers.get(header::FORWARDED)
-                .or_else(|| headers.get("x-forwarded-for"))
-                .and_then(|h| h.to_str().ok())
-                .map(|s| s.to_string());
-            let user_agent = headers.get(header::USER_AGENT)
-                .and_then(|h| h.to_str().ok())
-                .map(|s| s.to_string());
-            let event = StreamStartedEvent::new(
-                id,
-                client_ip,
-                user_agent,
-                result.needs_transcoding,
-            );
-            publish_stream_event(&event_bus, event).await;
+            let session = open_stream_session(&stream_sessions, session_request).await?;
 
             // Get file handle from use case (delegates file I/O)
             let file = use_case.get_file_handle(id).await
-                .map_err(|e| map_error(e))?;
+                .map_err(ApiError::from)?;
             
             // Create stream from file
             let stream = ReaderStream::new(file);
-            let body = Body::from_stream(stream);
+            let body = guarded_body(stream, playback_qos.track(id), session);
             
             // Build response
             let mut response = Response::new(body);
@@ -160,10 +267,103 @@ pub async fn stream_media(
             
             Ok(response)
         }
-        Err(e) => Err(map_error(e)),
+        Err(e) => Err(ApiError::from(e)),
     }
 }
 
+/// Audio-only streaming
+///
+/// Extracts the selected audio track as an ADTS AAC stream (concert films,
+/// talks, listening over mobile data). AAC sources are copied unless a
+/// bitrate or loudness normalization is requested; other codecs are
+/// transcoded to stereo AAC.
+#[allow(clippy::too_many_arguments)]
+async fn stream_audio_only(
+    use_case: Arc<StreamMediaUseCase>,
+    video_analyzer: Arc<dyn VideoAnalyzer>,
+    stream_sessions: Arc<StreamSessionRegistry>,
+    playback_qos: Arc<PlaybackQos>,
+    loudness: Arc<LoudnessNormalizer>,
+    id: i64,
+    query: StreamQuery,
+    session_request: StreamRequest,
+) -> Result<Response, ApiError> {
+    let (media, _) = use_case.prepare_stream(id).await
+        .map_err(ApiError::from)?;
+
+    let file_path = &media.file_path;
+    let start_seconds = query.start.max(0.0).floor() as i64;
+
+    let analysis = video_analyzer.analyze(file_path).await
+        .map_err(|e| {
+            tracing::error!("Failed to analyze media {}: {}", file_path, e);
+            ApiError::internal("Failed to analyze media")
+        })?;
+    if analysis.audio_tracks.is_empty() && analysis.audio_codec.is_none() {
+        return Err(ApiError::not_found("Media has no audio track"));
+    }
+    let audio_track = use_case
+        .select_audio_track(&session_request.user, &analysis.audio_tracks, query.audio.map(|a| a.max(0) as usize))
+        .await
+        ?;
+
+    // Tracks are listed in stream order, matching ffmpeg's 0:a:N selector
+    let source_codec = analysis.audio_tracks.get(audio_track)
+        .and_then(|t| t.codec.clone())
+        .or(analysis.audio_codec)
+        .unwrap_or_default();
+    let needs_transcode = query.bitrate.is_some() || query.loudnorm || !source_codec.eq_ignore_ascii_case("aac");
+
+    let session = open_stream_session(&stream_sessions, StreamRequest {
+        mode: if needs_transcode { StreamMode::Transcode } else { StreamMode::Direct },
+        bitrate_kbps: needs_transcode.then(|| query.bitrate.unwrap_or(128).clamp(32, 320)),
+        ..session_request
+    }).await?;
+
+    tracing::info!(
+        "Audio-only stream: id={}, file={}, start={}s, audio_track={}, codec={}, transcode={}",
+        id, file_path, start_seconds, audio_track, source_codec, needs_transcode
+    );
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-ss", &start_seconds.to_string()])
+        .args(["-i", file_path])
+        .args(["-map", &format!("0:a:{}", audio_track)])
+        .args(["-vn", "-sn", "-dn"]);
+    if needs_transcode {
+        let bitrate = query.bitrate.unwrap_or(128).clamp(32, 320);
+        cmd.args(["-c:a", "aac", "-b:a", &format!("{}k", bitrate), "-ac", "2"]);
+        if query.loudnorm {
+            // loudnorm upsamples to 192 kHz internally
+            cmd.args(["-af", &loudness.filter(id, file_path, audio_track).await, "-ar", "48000"]);
+        }
+    } else {
+        cmd.args(["-c:a", "copy"]);
+    }
+    cmd.args(["-f", "adts", "-"]);
+
+    let mut ffmpeg = cmd
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            tracing::error!("Failed to spawn FFmpeg: {}", e);
+            ApiError::internal("Failed to start audio extraction")
+        })?;
+
+    let stdout = ffmpeg.stdout.take()
+        .ok_or_else(|| ApiError::internal("Failed to get FFmpeg stdout"))?;
+
+    let stream = ReaderStream::new(stdout);
+    let body = guarded_body(stream, playback_qos.track(id), session);
+
+    let mut response = Response::new(body);
+    response.headers_mut().insert(header::CONTENT_TYPE, "audio/aac".parse().unwrap());
+    response.headers_mut().insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+
+    Ok(response)
+}
+
 /// Parse HTTP Range header
 /// 
 /// # Arguments
@@ -189,6 +389,81 @@ fn parse_range_header(header: &str) -> Option<(u64, Option<u64>)> {
     Some((start, end))
 }
 
+/// Query parameters for the stream diagnostic
+#[derive(Debug, Deserialize)]
+pub struct DiagnosticQuery {
+    /// Quality override to evaluate (as on the web stream)
+    pub quality: Option<String>,
+    /// Bitrate override to evaluate (as on the web stream)
+    pub bitrate: Option<u32>,
+    /// Device whose remembered preference applies
+    pub device: Option<String>,
+    /// User whose subtitle offsets are returned (default: "default")
+    pub user: Option<String>,
+}
+
+/// User ID used when a client does not send one
+pub(crate) const DEFAULT_USER: &str = "default";
+
+/// Quality constraint in effect for a stream
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveQualityConstraint {
+    /// "session" for a per-request override, "device" for a remembered preference
+    pub source: &'static str,
+    #[serde(flatten)]
+    pub constraint: QualityConstraint,
+}
+
+/// Resolves the quality constraint for a stream
+///
+/// A session override (`quality`/`bitrate`) wins over the device's remembered
+/// preference; with `remember` it replaces that preference. `quality=auto`
+/// without a bitrate clears any constraint.
+async fn resolve_quality_constraint(
+    preferences: &Arc<dyn QualityPreferenceRepository>,
+    quality: Option<&str>,
+    bitrate: Option<u32>,
+    device: Option<&str>,
+    remember: bool,
+) -> Result<Option<ActiveQualityConstraint>, ApiError> {
+    let session = match (quality, bitrate) {
+        (None, None) => None,
+        (quality, bitrate) => {
+            let preset = match quality {
+                None | Some("auto") | Some("original") => None,
+                Some(q) => Some(
+                    QualityPreset::parse(q)
+                        .ok_or(ApiError::bad_request(format!("Unknown quality: {}", q)))?,
+                ),
+            };
+            Some(QualityConstraint::new(preset, bitrate))
+        }
+    };
+
+    match (session, device) {
+        (Some(constraint), device) => {
+            if let (true, Some(device)) = (remember, device) {
+                let stored = if constraint.is_empty() {
+                    preferences.delete(device).await.map(|_| ())
+                } else {
+                    preferences.save(device, &constraint).await
+                };
+                stored.map_err(ApiError::from)?;
+            }
+            Ok(Some(constraint)
+                .filter(|c| !c.is_empty())
+                .map(|constraint| ActiveQualityConstraint { source: "session", constraint }))
+        }
+        (None, Some(device)) => Ok(preferences
+            .find(device)
+            .await
+            .map_err(ApiError::from)?
+            .filter(|c| !c.is_empty())
+            .map(|constraint| ActiveQualityConstraint { source: "device", constraint })),
+        (None, None) => Ok(None),
+    }
+}
+
 /// Stream diagnostic response
 #[derive(Debug, Serialize)]
 pub struct StreamDiagnostic {
@@ -203,6 +478,10 @@ pub struct StreamDiagnostic {
     pub audio_tracks: usize,
     pub needs_video_transcode: bool,
     pub browser_compatible: bool,
+    /// Client quality override applied instead of the automatic decision
+    pub quality_constraint: Option<ActiveQualityConstraint>,
+    /// Subtitle delays the user stored for this media
+    pub subtitle_offsets: Vec<SubtitleOffset>,
 }
 
 /// Check if video codec is browser-compatible
@@ -216,14 +495,23 @@ fn is_browser_compatible_codec(codec: &str) -> bool {
 }
 
 /// Diagnostic endpoint to check stream compatibility
+///
+/// `quality`, `bitrate` and `device` report the constraint the web stream
+/// would apply with the same parameters; `user` selects whose subtitle
+/// offsets are returned.
 pub async fn stream_diagnostic(
     State(use_case): State<Arc<StreamMediaUseCase>>,
     State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    State(subtitle_offsets): State<Arc<dyn SubtitleOffsetRepository>>,
+    caller: Option<Extension<Caller>>,
     Path(id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Query(query): Query<DiagnosticQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
     // Get media info
     let (media, _result) = use_case.prepare_stream(id).await
-        .map_err(|e| map_error(e))?;
+        .map_err(ApiError::from)?;
 
     let file_path = &media.file_path;
 
@@ -231,13 +519,28 @@ pub async fn stream_diagnostic(
     let analysis = video_analyzer.analyze(file_path).await
         .map_err(|e| {
             tracing::error!("Failed to analyze video {}: {}", file_path, e);
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to analyze video: {}", e))
+            ApiError::internal(format!("Failed to analyze video: {}", e))
         })?;
 
+    let quality_constraint = resolve_quality_constraint(
+        &preferences,
+        query.quality.as_deref(),
+        query.bitrate,
+        query.device.as_deref(),
+        false,
+    ).await?;
+
+    let subtitle_offsets = subtitle_offsets
+        .find(&user, id)
+        .await
+        .map_err(ApiError::from)?;
+
     let video_codec = analysis.video_codec.clone();
     let needs_transcode = video_codec.as_ref()
         .map(|c| !is_browser_compatible_codec(c))
-        .unwrap_or(false);
+        .unwrap_or(false)
+        || quality_constraint.as_ref()
+            .is_some_and(|q| q.constraint.requires_transcode(analysis.height));
 
     let browser_compatible = video_codec.as_ref()
         .map(|c| is_browser_compatible_codec(c))
@@ -255,73 +558,385 @@ pub async fn stream_diagnostic(
         audio_tracks: analysis.audio_tracks.len(),
         needs_video_transcode: needs_transcode,
         browser_compatible,
+        quality_constraint,
+        subtitle_offsets,
     }))
 }
 
+/// Request body for the playback decision
+#[derive(Debug, Deserialize)]
+pub struct PlaybackInfoRequest {
+    /// What the client can play (defaults to H.264/AAC in MP4)
+    #[serde(flatten)]
+    pub capabilities: ClientCapabilities,
+    /// Audio track index (default: the user's remembered language, its
+    /// audio description version when the user prefers it)
+    pub audio: Option<u32>,
+    /// Client device ID; its remembered quality preference applies
+    pub device: Option<String>,
+    /// User the stream token is issued to (default: "default"); a device
+    /// key always gets its own user
+    pub user: Option<String>,
+}
+
+/// Playback decision with the URL to play
+#[derive(Debug, Serialize)]
+pub struct PlaybackInfoResponse {
+    pub media_id: i64,
+    /// URL to request, relative to the server, with a session token
+    pub stream_url: String,
+    /// When the token in the URL expires
+    pub token_expires_at: chrono::DateTime<chrono::Utc>,
+    /// Audio track the stream URL plays
+    pub audio: u32,
+    /// Detected black bars; add `crop=true` to a web stream URL to remove them
+    pub crop: Option<CropInfo>,
+    #[serde(flatten)]
+    pub decision: PlaybackDecision,
+}
+
+/// Detected picture area of a media item
+#[derive(Debug, Serialize)]
+pub struct CropInfo {
+    #[serde(flatten)]
+    pub area: CropDetection,
+    /// Aspect ratio of the picture area
+    pub aspect_ratio: f64,
+    /// Whether the bars are wide enough to be worth cropping
+    pub has_bars: bool,
+    /// FFmpeg filter removing the bars
+    pub filter: String,
+}
+
+impl From<CropDetection> for CropInfo {
+    fn from(area: CropDetection) -> Self {
+        Self {
+            aspect_ratio: (area.aspect_ratio() * 100.0).round() / 100.0,
+            has_bars: area.has_bars(),
+            filter: area.filter(),
+            area,
+        }
+    }
+}
+
+/// Decide between direct play, remux and transcode for a client
+///
+/// Returns the stream URL matching the decision: the original file, the web
+/// stream with the video copied, or a transcode (HLS when the client
+/// supports it), and the detected black bars if the media has any. Without
+/// an audio track in the request, the user's track is picked (see
+/// [`StreamMediaUseCase::select_audio_track`]).
+#[allow(clippy::too_many_arguments)]
+pub async fn playback_info(
+    State(use_case): State<Arc<StreamMediaUseCase>>,
+    State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
+    State(playback_decision): State<Arc<PlaybackDecisionService>>,
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    State(crop_detection): State<Arc<CropDetectionService>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(id): Path<i64>,
+    caller: Option<Extension<Caller>>,
+    Json(request): Json<PlaybackInfoRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    let user = user.as_str();
+    let (media, _result) = use_case.prepare_stream(id).await
+        ?;
+    ensure_allowed(&parental, user, &media).await?;
+
+    let constraint = resolve_quality_constraint(
+        &preferences,
+        None,
+        None,
+        request.device.as_deref(),
+        false,
+    ).await?
+    .map(|q| q.constraint);
+
+    let audio = match request.audio {
+        Some(audio) => audio,
+        None => {
+            let analysis = video_analyzer.analyze(&media.file_path).await
+                .map_err(|e| {
+                    tracing::error!("Failed to analyze video {}: {}", media.file_path, e);
+                    ApiError::internal("Failed to analyze video")
+                })?;
+            use_case.select_audio_track(user, &analysis.audio_tracks, None).await
+                ? as u32
+        }
+    };
+    let decision = playback_decision
+        .decide(&media.file_path, &request.capabilities, audio as usize, constraint)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to decide playback for {}: {}", media.file_path, e);
+            ApiError::internal("Failed to analyze video")
+        })?;
+
+    let device_id = caller.as_deref().and_then(Caller::device_id);
+    let (_, signed) = signer.issue_session(id, user, device_id).await?;
+    let mut stream_url = match decision.method {
+        PlaybackMethod::DirectPlay => format!("/v2/stream/{}", id),
+        PlaybackMethod::Remux => format!("/v2/stream/web/{}?audio={}&copy_video=true", id, audio),
+        PlaybackMethod::Transcode if request.capabilities.hls => {
+            format!("/v2/stream/hls/{}/master.m3u8?audio={}", id, audio)
+        }
+        PlaybackMethod::Transcode => {
+            let mut url = format!("/v2/stream/web/{}?audio={}", id, audio);
+            match decision.quality_constraint {
+                Some(constraint) => {
+                    if let Some(quality) = constraint.quality {
+                        url.push_str(&format!("&quality={}", quality.as_str()));
+                    }
+                    if let Some(bitrate) = constraint.max_bitrate_kbps {
+                        url.push_str(&format!("&bitrate={}", bitrate));
+                    }
+                }
+                // Without limits the device preference must not apply again
+                None => url.push_str("&quality=auto"),
+            }
+            url
+        }
+    };
+
+    tracing::info!("Playback decision for media {}: {:?} ({:?})", id, decision.method, decision.reasons);
+    stream_url.push(if stream_url.contains('?') { '&' } else { '?' });
+    stream_url.push_str(&format!("token={}", signed.token));
+
+    let crop = crop_detection.crop(id, &media.file_path).await
+        .filter(CropDetection::has_bars)
+        .map(CropInfo::from);
+
+    Ok(Json(PlaybackInfoResponse {
+        media_id: id,
+        stream_url,
+        token_expires_at: signed.expires_at,
+        audio,
+        crop,
+        decision,
+    }))
+}
+
+/// Request body for a stream token
+#[derive(Debug, Default, Deserialize)]
+pub struct StreamTokenRequest {
+    /// User the token is issued to (default: "default"); a device key
+    /// always gets its own user
+    pub user: Option<String>,
+}
+
+/// A session token with the stream URLs it authorizes
+#[derive(Debug, Serialize)]
+pub struct StreamTokenResponse {
+    pub token: String,
+    /// Playback session the token is bound to
+    pub session: String,
+    pub user: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Original file
+    pub stream_url: String,
+    /// Fragmented MP4 for browsers
+    pub web_url: String,
+    /// Adaptive HLS
+    pub hls_url: String,
+}
+
+/// Issue a session token for the stream URLs of a media item
+///
+/// The token is bound to the user, the item and a new playback session, and
+/// can be put in `<video src>` URLs as `?token=`.
+///
+/// # Responses
+/// - 200: The token and its URLs
+/// - 403: A device key asked for another user, or parental controls block
+///   the item
+/// - 404: No such media
+pub async fn issue_stream_token(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(signer): State<Arc<StreamUrlSigner>>,
+    State(parental): State<Arc<ParentalControlService>>,
+    Path(id): Path<i64>,
+    caller: Option<Extension<Caller>>,
+    body: Option<Json<StreamTokenRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let request = body.map(|Json(body)| body).unwrap_or_default();
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    let user = user.as_str();
+    let media = media_repo
+        .find_by_id(id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", id)))?;
+    ensure_allowed(&parental, user, &media).await?;
+
+    let device_id = caller.as_deref().and_then(Caller::device_id);
+    let (claims, signed) = signer.issue_session(id, user, device_id).await?;
+    Ok(Json(StreamTokenResponse {
+        stream_url: format!("/v2/stream/{}?token={}", id, signed.token),
+        web_url: format!("/v2/stream/web/{}?token={}", id, signed.token),
+        hls_url: format!("/v2/stream/hls/{}/master.m3u8?token={}", id, signed.token),
+        token: signed.token,
+        session: claims.session,
+        user: claims.user,
+        expires_at: signed.expires_at,
+    }))
+}
+
+/// Revoke a stream token before it expires
+///
+/// Devices can only revoke their own user's tokens.
+pub async fn revoke_stream_token(
+    State(signer): State<Arc<StreamUrlSigner>>,
+    caller: Option<Extension<Caller>>,
+    auditor: Auditor,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (claims, expires) = signer.verify_session(&token).map_err(|e| match e {
+        TokenError::Expired | TokenError::Revoked => {
+            ApiError::new(StatusCode::GONE, "token_expired", "Stream token no longer valid")
+        }
+        _ => ApiError::bad_request("Invalid stream token"),
+    })?;
+    caller_user(caller.as_deref(), Some(&claims.user))?;
+    let session = claims.session;
+    signer.revoke(&session, expires).await?;
+    tracing::info!("Stream token of session {} revoked", session);
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("session:{}", session)).with_details("Stream token revoked")).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Web streaming with FFmpeg transcoding
 ///
 /// Transcodes media to fragmented MP4 for web playback, starting from a specified position.
 /// Video is transcoded to H.264 if needed (HEVC etc), audio is transcoded to AAC for compatibility.
+/// A client quality override (or the device's remembered one) forces a
+/// downscaled and/or bitrate-capped H.264 encode. `?loudnorm=true`
+/// normalizes the audio to EBU R128 (see [`LoudnessNormalizer`]), and
+/// `?crop=true` removes the detected black bars (see [`CropDetectionService`]).
+#[allow(clippy::too_many_arguments)]
 pub async fn stream_web(
     State(use_case): State<Arc<StreamMediaUseCase>>,
     State(video_analyzer): State<Arc<dyn VideoAnalyzer>>,
-    State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
+    State(stream_sessions): State<Arc<StreamSessionRegistry>>,
+    State(playback_qos): State<Arc<PlaybackQos>>,
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    State(loudness): State<Arc<LoudnessNormalizer>>,
+    State(crop_detection): State<Arc<CropDetectionService>>,
+    State(parental): State<Arc<ParentalControlService>>,
     Path(id): Path<i64>,
     Query(query): Query<WebStreamQuery>,
-) -> Result<Response, (StatusCode, String)> {
+    claims: Option<Extension<StreamClaims>>,
+    caller: Option<Extension<Caller>>,
+) -> Result<Response, ApiError> {
     // Get media info
-    let (media, result) = use_case.prepare_stream(id).await
-        .map_err(|e| map_error(e))?;
-
-    // Publish stream started event
-    let event = StreamStartedEvent::new(
-        id,
-        None, // Client IP not available in this context
-        None, // User agent not available in this context
-        result.needs_transcoding,
-    );
-    publish_stream_event(&event_bus, event).await;
+    let (media, _result) = use_case.prepare_stream(id).await
+        .map_err(ApiError::from)?;
+    let user = stream_user(claims.as_ref(), caller.as_deref(), query.user.as_deref())?;
+    ensure_allowed(&parental, &user, &media).await?;
+
+    let quality_constraint = resolve_quality_constraint(
+        &preferences,
+        query.quality.as_deref(),
+        query.bitrate,
+        query.device.as_deref(),
+        query.remember,
+    ).await?
+    .map(|q| q.constraint);
 
     let file_path = &media.file_path;
     let start_seconds = query.start.floor() as i64; // Convert float to integer seconds
-    let audio_track = query.audio.unwrap_or(0);
 
     // Analyze video to check codec compatibility
     let analysis = video_analyzer.analyze(file_path).await
         .map_err(|e| {
             tracing::error!("Failed to analyze video {}: {}", file_path, e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to analyze video".to_string())
+            ApiError::internal("Failed to analyze video")
         })?;
 
+    // Explicit ?audio= is remembered per user; otherwise the remembered language wins
+    let audio_track = use_case
+        .select_audio_track(&user, &analysis.audio_tracks, query.audio.map(|a| a.max(0) as usize))
+        .await
+        ?;
+
     let video_codec = analysis.video_codec.as_deref().unwrap_or("unknown");
-    let audio_codec = analysis.audio_codec.as_deref().unwrap_or("unknown");
-    let needs_video_transcode = !is_browser_compatible_codec(video_codec);
+    // Tracks are listed in stream order, matching ffmpeg's 0:a:N selector
+    let audio_codec = analysis.audio_tracks.get(audio_track)
+        .and_then(|t| t.codec.as_deref())
+        .or(analysis.audio_codec.as_deref())
+        .unwrap_or("unknown");
+    let crop = match query.crop {
+        true => crop_detection.crop(id, file_path).await.filter(CropDetection::has_bars),
+        false => None,
+    };
+    let needs_video_transcode = (!is_browser_compatible_codec(video_codec) && !query.copy_video)
+        || quality_constraint.is_some_and(|q| q.requires_transcode(analysis.height))
+        || crop.is_some();
     
     // Check if audio is already AAC (case-insensitive)
     let audio_is_aac = audio_codec.to_lowercase() == "aac";
-    let needs_audio_transcode = !audio_is_aac;
+    let needs_audio_transcode = !audio_is_aac || query.loudnorm;
+
+    // Seeks restart FFmpeg but stay in the same session
+    let session = open_stream_session(&stream_sessions, StreamRequest {
+        media_id: id,
+        user,
+        client: query.device.clone(),
+        mode: if needs_video_transcode { StreamMode::Transcode } else { StreamMode::Direct },
+        bitrate_kbps: quality_constraint
+            .and_then(|q| q.max_bitrate_kbps)
+            .or(analysis.video_bitrate.map(|b| (b / 1000) as u32)),
+        hls_session: None,
+    }).await?;
 
     tracing::info!(
-        "Web stream: id={}, file={}, start={}s, audio_track={}, video_codec={}, audio_codec={}, video_transcode={}, audio_transcode={}",
-        id, file_path, start_seconds, audio_track, video_codec, audio_codec, needs_video_transcode, needs_audio_transcode
+        "Web stream: id={}, file={}, start={}s, audio_track={}, video_codec={}, audio_codec={}, video_transcode={}, audio_transcode={}, quality_constraint={:?}, crop={:?}",
+        id, file_path, start_seconds, audio_track, video_codec, audio_codec, needs_video_transcode, needs_audio_transcode, quality_constraint,
+        crop.map(|c| c.filter())
     );
 
     // Build FFmpeg command - transcode video if needed
-    let video_codec_args: Vec<&str> = if needs_video_transcode {
+    let video_codec_args: Vec<String> = if needs_video_transcode {
         // Transcode to H.264 for browser compatibility
-        vec!["-c:v", "libx264", "-preset", "fast", "-crf", "23"]
+        let mut args: Vec<String> = ["-c:v", "libx264", "-preset", "fast"]
+            .iter().map(|a| a.to_string()).collect();
+        match quality_constraint.and_then(|q| q.max_bitrate_kbps) {
+            Some(kbps) => args.extend([
+                "-b:v".to_string(), format!("{}k", kbps),
+                "-maxrate".to_string(), format!("{}k", kbps),
+                "-bufsize".to_string(), format!("{}k", kbps * 2),
+            ]),
+            None => args.extend(["-crf".to_string(), "23".to_string()]),
+        }
+        let mut filters: Vec<String> = crop.iter().map(CropDetection::filter).collect();
+        if let Some(height) = quality_constraint.and_then(|q| q.target_height(crop.map_or(analysis.height, |c| c.height))) {
+            // -2 keeps the aspect ratio with an even width
+            filters.push(format!("scale=-2:{}", height));
+        }
+        if !filters.is_empty() {
+            args.extend(["-vf".to_string(), filters.join(",")]);
+        }
+        args
     } else {
         // Copy video stream (no re-encoding)
-        vec!["-c:v", "copy"]
+        vec!["-c:v".to_string(), "copy".to_string()]
     };
 
     // Build audio codec args - only transcode if not already AAC
-    let audio_codec_args: Vec<&str> = if needs_audio_transcode {
+    let audio_codec_args: Vec<String> = if needs_audio_transcode {
         // Transcode audio to AAC
-        vec!["-c:a", "aac", "-b:a", "192k", "-ac", "2"]
+        let mut args: Vec<String> = ["-c:a", "aac", "-b:a", "192k", "-ac", "2"]
+            .iter().map(|a| a.to_string()).collect();
+        if query.loudnorm {
+            // loudnorm upsamples to 192 kHz internally
+            let filter = loudness.filter(id, file_path, audio_track).await;
+            args.extend(["-af".to_string(), filter, "-ar".to_string(), "48000".to_string()]);
+        }
+        args
     } else {
         // Copy audio stream (already AAC, no re-encoding)
-        vec!["-c:a", "copy"]
+        vec!["-c:a".to_string(), "copy".to_string()]
     };
 
     let mut cmd = Command::new("ffmpeg");
@@ -376,15 +991,15 @@ pub async fn stream_web(
         .spawn()
         .map_err(|e| {
             tracing::error!("Failed to spawn FFmpeg: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to start transcoding".to_string())
+            ApiError::internal("Failed to start transcoding")
         })?;
 
     let stdout = ffmpeg.stdout.take()
-        .ok_or_else(|| (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get FFmpeg stdout".to_string()))?;
+        .ok_or_else(|| ApiError::internal("Failed to get FFmpeg stdout"))?;
 
     // Stream FFmpeg output directly to client
     let stream = ReaderStream::new(stdout);
-    let body = Body::from_stream(stream);
+    let body = guarded_body(stream, playback_qos.track(id), session);
 
     // Build response
     let mut response = Response::new(body);
@@ -414,10 +1029,10 @@ pub async fn generate_thumbnail(
     State(event_bus): State<Option<Arc<InMemoryEventBus>>>,
     Path(id): Path<i64>,
     Query(query): Query<ThumbnailQuery>,
-) -> Result<Response, (StatusCode, String)> {
+) -> Result<Response, ApiError> {
     // Get media info
     let (media, _result) = use_case.prepare_stream(id).await
-        .map_err(|e| map_error(e))?;
+        .map_err(ApiError::from)?;
 
     let file_path = &media.file_path;
     let width = query.width.unwrap_or(320);
@@ -426,7 +1041,7 @@ pub async fn generate_thumbnail(
     let analysis = video_analyzer.analyze(file_path).await
         .map_err(|e| {
             tracing::error!("Failed to analyze video {}: {}", file_path, e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to analyze video".to_string())
+            ApiError::internal("Failed to analyze video")
         })?;
 
     // Default to 10% into video if no timestamp specified
@@ -447,13 +1062,13 @@ pub async fn generate_thumbnail(
         .await
         .map_err(|e| {
             tracing::error!("Failed to run FFmpeg: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to generate thumbnail".to_string())
+            ApiError::internal("Failed to generate thumbnail")
         })?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         tracing::error!("FFmpeg failed: {}", stderr);
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to generate thumbnail".to_string()));
+        return Err(ApiError::internal("Failed to generate thumbnail"));
     }
 
     let body = Body::from(output.stdout);
@@ -488,57 +1103,56 @@ pub struct SubtitleQuery {
     /// Used when streaming starts from a position other than 0.
     #[serde(default)]
     pub offset: f64,
+    /// Formatting tags to keep: keep, basic (b/i/u, default) or strip
+    #[serde(default)]
+    pub tags: TagPolicy,
+    /// Encoding of the file (e.g. windows-1250), overriding detection
+    pub encoding: Option<String>,
 }
 
 /// Get subtitle by media ID and track index
 ///
 /// Returns subtitle content in WebVTT format for HTML5 video compatibility.
-/// Converts SRT subtitles to WebVTT on-the-fly.
+/// Converts SRT and ASS/SSA subtitles to WebVTT on-the-fly. Embedded tracks
+/// are extracted on first use (PGS tracks with OCR, when available).
 ///
 /// # Path Parameters
 /// - `media_id` - Media item ID
-/// - `index` - Subtitle track index (from /v2/media/{id}/tracks response)
+/// - `index` - Subtitle track index (from /v2/media/{id}/tracks response);
+///   embedded tracks follow the external ones
 ///
 /// # Query Parameters
 /// - `offset` - (optional) Seconds to subtract from timestamps for sync with seeked video
+/// - `tags` - (optional) Formatting tags to keep: keep, basic or strip
+/// - `encoding` - (optional) Encoding of the file when detection guesses wrong
 ///
 /// # Response
 /// - 200: WebVTT subtitle content
+/// - 400: Unknown encoding, or an embedded bitmap track that cannot be read
 /// - 404: Media or subtitle not found
 /// - 500: Internal error
 pub async fn get_subtitle(
     State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(subtitle_store): State<Arc<SubtitleStore>>,
+    State(extract_use_case): State<Arc<ExtractSubtitleUseCase>>,
     Path((media_id, index)): Path<(i64, usize)>,
     Query(query): Query<SubtitleQuery>,
-) -> Result<Response, (StatusCode, String)> {
-    // Get media to find file path
-    let media = media_repo
-        .find_by_id(media_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Media {} not found", media_id)))?;
-
-    // Discover external subtitles
-    let subtitle_detector = SubtitleDetector::new();
-    let video_path = std::path::Path::new(&media.file_path);
-    let external_subtitles = subtitle_detector.discover(video_path);
-
-    // Check if requested index is valid
-    if index >= external_subtitles.len() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            format!("Subtitle track {} not found (only {} external subtitles available)", index, external_subtitles.len())
-        ));
-    }
-
-    // Get the requested subtitle
-    let subtitle = &external_subtitles[index];
-
-    // Read and convert SRT to WebVTT (with optional offset for seek sync)
-    let vtt_content = read_and_convert_srt_with_offset(&subtitle.file_path, query.offset)
+) -> Result<Response, ApiError> {
+    let encoding = query.encoding.as_deref()
+        .map(encoding_for_label)
+        .transpose()
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let (file_path, language) = subtitle_file(&media_repo, &subtitle_store, &extract_use_case, media_id, index).await?;
+
+    // Sanitize and convert to WebVTT (with optional offset for seek sync)
+    let options = SubtitleOptions::new(query.tags)
+        .with_encoding(encoding)
+        .with_language(language);
+    let vtt_content = read_and_convert_srt_with_offset(&file_path, query.offset, &options)
         .map_err(|e| {
-            tracing::error!("Failed to convert subtitle {}: {}", subtitle.file_path, e);
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to convert subtitle: {}", e))
+            tracing::error!("Failed to convert subtitle {}: {}", file_path, e);
+            ApiError::internal(format!("Failed to convert subtitle: {}", e))
         })?;
 
     // Build response with WebVTT content
@@ -565,14 +1179,327 @@ pub async fn get_subtitle(
     Ok(response)
 }
 
-/// Map ApplicationError to HTTP response
-fn map_error(e: ApplicationError) -> (StatusCode, String) {
-    match e {
-        ApplicationError::Domain(crate::shared::error::DomainError::NotFound(msg)) => (StatusCode::NOT_FOUND, msg),
-        ApplicationError::Filesystem(crate::shared::error::FilesystemError::PathNotFound(msg)) => (StatusCode::NOT_FOUND, format!("File not found: {}", msg)),
-        _ => {
-            tracing::error!("Streaming error: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+/// Path and language of subtitle `index` of a media item
+///
+/// Indices past the external subtitles (downloads included) address the
+/// embedded tracks, which are converted on first use.
+pub(crate) async fn subtitle_file(
+    media_repo: &Arc<dyn MediaRepository>,
+    subtitle_store: &SubtitleStore,
+    extract_use_case: &ExtractSubtitleUseCase,
+    media_id: i64,
+    index: usize,
+) -> Result<(String, Option<String>), ApiError> {
+    let media = media_repo
+        .find_by_id(media_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", media_id)))?;
+
+    let video_path = std::path::Path::new(&media.file_path);
+    let external_subtitles = subtitle_store.detector(media_id).discover(video_path);
+
+    match external_subtitles.get(index) {
+        Some(subtitle) => Ok((subtitle.file_path.clone(), subtitle.language.clone())),
+        None => {
+            let track = index - external_subtitles.len();
+            let path = extract_use_case.embedded_subtitle(media_id, track).await.map_err(|e| match e {
+                ApplicationError::Domain(DomainError::NotFound(msg)) => ApiError::not_found(msg),
+                ApplicationError::Domain(DomainError::InvalidInput(msg)) => ApiError::bad_request(msg),
+                e => {
+                    tracing::error!("Failed to extract subtitle track {} of media {}: {}", track, media_id, e);
+                    ApiError::internal(format!("Failed to extract subtitle: {}", e))
+                }
+            })?;
+            Ok((path.to_string_lossy().into_owned(), None))
         }
     }
 }
+
+/// Request body for storing a device's quality preference
+#[derive(Debug, Deserialize)]
+pub struct QualityPreferenceRequest {
+    /// Maximum resolution (2160p, 1080p, 720p, 480p, 360p)
+    pub quality: Option<String>,
+    /// Maximum video bitrate in kbps
+    pub max_bitrate_kbps: Option<u32>,
+}
+
+/// Get the remembered quality preference of a device
+pub async fn get_quality_preference(
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    Path(device_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let constraint = preferences.find(&device_id).await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::not_found("No quality preference for this device"))?;
+
+    Ok(Json(constraint))
+}
+
+/// Store the quality preference of a device
+///
+/// Web streams requested with `?device=` use it unless they pass their own
+/// override.
+pub async fn set_quality_preference(
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    Path(device_id): Path<String>,
+    Json(request): Json<QualityPreferenceRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let quality = match request.quality.as_deref() {
+        Some(q) => Some(
+            QualityPreset::parse(q)
+                .ok_or(ApiError::bad_request(format!("Unknown quality: {}", q)))?,
+        ),
+        None => None,
+    };
+    let constraint = QualityConstraint::new(quality, request.max_bitrate_kbps);
+    if constraint.is_empty() {
+        return Err(ApiError::bad_request("Set quality and/or max_bitrate_kbps"));
+    }
+
+    preferences.save(&device_id, &constraint).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(constraint))
+}
+
+/// Forget the quality preference of a device
+pub async fn delete_quality_preference(
+    State(preferences): State<Arc<dyn QualityPreferenceRepository>>,
+    Path(device_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let deleted = preferences.delete(&device_id).await
+        .map_err(ApiError::from)?;
+    if !deleted {
+        return Err(ApiError::not_found("No quality preference for this device"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the accessibility preferences of a user
+///
+/// Devices can only see their own user's preferences.
+pub async fn get_accessibility_preferences(
+    State(preferences): State<Arc<dyn AccessibilityPreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let stored = preferences.find(&user_id).await
+        .map_err(ApiError::from)?
+        .ok_or(ApiError::not_found("No accessibility preferences for this user"))?;
+
+    Ok(Json(stored))
+}
+
+/// Store the accessibility preferences of a user
+///
+/// Streams and playback decisions without an explicit audio track pick the
+/// audio description version of the user's language, and track listings
+/// turn on SDH subtitles by default.
+pub async fn set_accessibility_preferences(
+    State(preferences): State<Arc<dyn AccessibilityPreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+    Json(request): Json<AccessibilityPreferences>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    preferences.save(&user_id, &request).await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(request))
+}
+
+/// Forget the accessibility preferences of a user
+pub async fn delete_accessibility_preferences(
+    State(preferences): State<Arc<dyn AccessibilityPreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let deleted = preferences.delete(&user_id).await
+        .map_err(ApiError::from)?;
+    if !deleted {
+        return Err(ApiError::not_found("No accessibility preferences for this user"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Query parameters selecting the user of a subtitle offset
+#[derive(Debug, Deserialize)]
+pub struct SubtitleOffsetQuery {
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// Request body for storing a subtitle offset
+#[derive(Debug, Deserialize)]
+pub struct SubtitleOffsetRequest {
+    /// Delay in milliseconds; positive values show subtitles later
+    pub offset_ms: i64,
+    /// User ID (default: "default")
+    pub user: Option<String>,
+}
+
+/// List the subtitle offsets a user stored for a media item
+pub async fn get_subtitle_offsets(
+    State(subtitle_offsets): State<Arc<dyn SubtitleOffsetRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(media_id): Path<i64>,
+    Query(query): Query<SubtitleOffsetQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let offsets = subtitle_offsets
+        .find(&user, media_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(offsets))
+}
+
+/// Store the subtitle offset of a track
+///
+/// The offset is returned by the stream diagnostic so players can reapply the
+/// correction on any device.
+pub async fn set_subtitle_offset(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(subtitle_offsets): State<Arc<dyn SubtitleOffsetRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path((media_id, track_index)): Path<(i64, i32)>,
+    Json(request): Json<SubtitleOffsetRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), request.user.as_deref())?;
+    if track_index < 0 {
+        return Err(ApiError::bad_request("Track index must not be negative"));
+    }
+    media_repo
+        .find_by_id(media_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", media_id)))?;
+
+    let offset = SubtitleOffset { track_index, offset_ms: request.offset_ms };
+    subtitle_offsets
+        .save(&user, media_id, &offset)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(offset))
+}
+
+/// Forget the subtitle offset of a track
+pub async fn delete_subtitle_offset(
+    State(subtitle_offsets): State<Arc<dyn SubtitleOffsetRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path((media_id, track_index)): Path<(i64, i32)>,
+    Query(query): Query<SubtitleOffsetQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = caller_user(caller.as_deref(), query.user.as_deref())?;
+    let deleted = subtitle_offsets
+        .delete(&user, media_id, track_index)
+        .await
+        .map_err(ApiError::from)?;
+    if !deleted {
+        return Err(ApiError::not_found("No subtitle offset for this track"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the detected picture area of a media item
+///
+/// GET /v2/media/:id/crop
+///
+/// # Responses
+/// - 200: The picture area, its aspect ratio and the crop filter
+/// - 404: Media not found, or not detected yet
+pub async fn get_crop(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(crop_detection): State<Arc<CropDetectionService>>,
+    Path(media_id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let media = media_repo
+        .find_by_id(media_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", media_id)))?;
+    let crop = crop_detection
+        .crop(media_id, &media.file_path)
+        .await
+        .ok_or_else(|| ApiError::not_found(format!("No crop detected for media {}", media_id)))?;
+
+    Ok(Json(CropInfo::from(crop)))
+}
+
+/// Detect the black bars of a media item with cropdetect
+///
+/// POST /v2/media/:id/crop/detect
+///
+/// Samples the video at several points and stores the result, replacing an
+/// earlier detection.
+pub async fn detect_crop(
+    State(media_repo): State<Arc<dyn MediaRepository>>,
+    State(crop_detection): State<Arc<CropDetectionService>>,
+    Path(media_id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let media = media_repo
+        .find_by_id(media_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Media {} not found", media_id)))?;
+    let crop = crop_detection.detect(media_id, &media.file_path).await?;
+
+    Ok(Json(CropInfo::from(crop)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::DeviceKey;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteAccessibilityPreferenceRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_devices_only_reach_their_own_accessibility_preferences() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        let preferences: Arc<dyn AccessibilityPreferenceRepository> =
+            Arc::new(SqliteAccessibilityPreferenceRepository::new(pool));
+        let stored = AccessibilityPreferences { audio_description: true, hearing_impaired_subtitles: false };
+        preferences.save("default", &stored).await.unwrap();
+        let kid = || {
+            Some(Extension(Caller::Device(DeviceKey {
+                id: 1,
+                name: "Tablet".to_string(),
+                user_id: "kid".to_string(),
+                prefix: "hf_1234".to_string(),
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+                last_used_at: None,
+            })))
+        };
+        let user = || Path("default".to_string());
+
+        let e = get_accessibility_preferences(State(preferences.clone()), kid(), user()).await.map(|_| ()).unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "wrong_user"));
+        let e = set_accessibility_preferences(State(preferences.clone()), kid(), user(), Json(AccessibilityPreferences::default()))
+            .await
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "wrong_user"));
+        let e = delete_accessibility_preferences(State(preferences.clone()), kid(), user()).await.map(|_| ()).unwrap_err();
+        assert_eq!((e.status, e.code), (StatusCode::FORBIDDEN, "wrong_user"));
+        assert_eq!(preferences.find("default").await.unwrap(), Some(stored));
+
+        // The shared secret may act for any user
+        get_accessibility_preferences(State(preferences.clone()), Some(Extension(Caller::Admin)), user()).await.unwrap();
+    }
+}
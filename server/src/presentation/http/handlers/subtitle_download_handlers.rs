@@ -0,0 +1,232 @@
+//! Subtitle Download Handlers
+//!
+//! HTTP handlers for downloading subtitles from OpenSubtitles, falling back
+//! to an embedded track and then to Whisper generation, and for the
+//! subtitle languages of each user. A device key acts as its own user.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::Caller;
+use crate::application::use_cases::download_subtitle::{
+    DownloadSubtitleOutcome, DownloadSubtitleRequest, DownloadSubtitleResult, DownloadSubtitleUseCase,
+};
+use crate::application::use_cases::extract_subtitle::{ExtractSubtitleUseCase, ExtractedSubtitle};
+use crate::application::use_cases::generate_subtitle::{GenerateSubtitleRequest, GenerateSubtitleUseCase};
+use crate::domain::repositories::SubtitlePreferenceRepository;
+use crate::infrastructure::jobs::JobStore;
+use crate::infrastructure::subtitle::normalize_language_code;
+use crate::presentation::http::handlers::auth_handlers::caller_user;
+use crate::presentation::http::handlers::subtitle_generation_handlers::spawn_generation;
+use crate::shared::error::{ApplicationError, DomainError};
+
+fn default_fallback() -> bool {
+    true
+}
+
+/// Request body for a subtitle download
+#[derive(Debug, Deserialize)]
+pub struct DownloadSubtitleBody {
+    /// Languages in order of preference (empty = the user's or the defaults)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// User whose subtitle languages apply (default: a device key's own
+    /// user)
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Extract an embedded track, or generate with Whisper, when nothing can
+    /// be downloaded (default: true)
+    #[serde(default = "default_fallback")]
+    pub fallback: bool,
+    /// Audio track transcribed by the fallback (0-based)
+    #[serde(default)]
+    pub audio_track_index: usize,
+}
+
+/// Response for a subtitle download
+#[derive(Debug, Serialize)]
+pub struct DownloadSubtitleResponse {
+    /// "downloaded", "extracted" or "generating"
+    pub status: &'static str,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<DownloadSubtitleResult>,
+    /// Embedded track extracted instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extracted: Option<ExtractedSubtitle>,
+    /// Generation job (GET /v2/subtitles/jobs/:job_id)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    /// Why nothing was downloaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Download a subtitle for a media item
+///
+/// POST /v2/subtitles/:media_id/download
+///
+/// Downloads the best subtitle in the most preferred language and stores it
+/// next to the video (or in the data directory for read-only media). When
+/// none is found, an embedded text track in that language is extracted;
+/// failing that, if Whisper is available, generation starts and a job ID is
+/// returned.
+///
+/// # Responses
+/// - 200: Subtitle downloaded or extracted
+/// - 202: Nothing found, generation started
+/// - 404: Media not found, or nothing found and no fallback
+pub async fn download_subtitle(
+    State(download_use_case): State<Arc<DownloadSubtitleUseCase>>,
+    State(extract_use_case): State<Arc<ExtractSubtitleUseCase>>,
+    State(generate_use_case): State<Arc<GenerateSubtitleUseCase>>,
+    State(job_store): State<Arc<JobStore>>,
+    caller: Option<Extension<Caller>>,
+    Path(media_id): Path<i64>,
+    Json(body): Json<DownloadSubtitleBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let device_user = caller.as_deref().and_then(Caller::device_user);
+    let user_id = match (&body.user_id, device_user) {
+        (None, None) => None,
+        (requested, _) => Some(caller_user(caller.as_deref(), requested.as_deref())?),
+    };
+    let request = DownloadSubtitleRequest {
+        media_id,
+        languages: body.languages,
+        user_id,
+    };
+
+    let (language, reason) = match download_use_case.execute(request).await {
+        Ok(DownloadSubtitleOutcome::Downloaded(subtitle)) => {
+            return Ok((StatusCode::OK, Json(DownloadSubtitleResponse {
+                status: "downloaded",
+                subtitle: Some(subtitle),
+                extracted: None,
+                job_id: None,
+                reason: None,
+            })));
+        }
+        Ok(DownloadSubtitleOutcome::Unavailable { language, reason }) => (language, reason),
+        Err(ApplicationError::Domain(DomainError::NotFound(msg))) => return Err((StatusCode::NOT_FOUND, msg)),
+        Err(e) => {
+            tracing::error!("Subtitle download for media {} failed: {}", media_id, e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()));
+        }
+    };
+
+    if !body.fallback {
+        return Err((StatusCode::NOT_FOUND, reason));
+    }
+
+    // An embedded track beats spending Whisper time on the same language
+    match extract_use_case.extract_language(media_id, &language).await {
+        Ok(Some(extracted)) => {
+            return Ok((StatusCode::OK, Json(DownloadSubtitleResponse {
+                status: "extracted",
+                subtitle: None,
+                extracted: Some(extracted),
+                job_id: None,
+                reason: Some(reason),
+            })));
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Embedded subtitle extraction for media {} failed: {}", media_id, e),
+    }
+
+    if !generate_use_case.check_capabilities().await.can_transcribe() {
+        return Err((StatusCode::NOT_FOUND, reason));
+    }
+
+    tracing::info!("No subtitle to download for media {} ({}), generating one", media_id, reason);
+    let request = GenerateSubtitleRequest {
+        media_id,
+        audio_track_index: body.audio_track_index,
+        source_language: None,
+        target_language: Some(language),
+        skip_silence: None,
+    };
+    let job_id = spawn_generation(generate_use_case, job_store, request).await;
+
+    Ok((StatusCode::ACCEPTED, Json(DownloadSubtitleResponse {
+        status: "generating",
+        subtitle: None,
+        extracted: None,
+        job_id: Some(job_id),
+        reason: Some(reason),
+    })))
+}
+
+/// Subtitle languages of a user
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubtitleLanguages {
+    /// Language codes in order of preference
+    pub languages: Vec<String>,
+}
+
+/// Get the subtitle languages of a user
+pub async fn get_subtitle_languages(
+    State(preferences): State<Arc<dyn SubtitlePreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let languages = preferences.find(&user_id).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if languages.is_empty() {
+        return Err((StatusCode::NOT_FOUND, "No subtitle languages for this user".to_string()));
+    }
+
+    Ok(Json(SubtitleLanguages { languages }))
+}
+
+/// Store the subtitle languages of a user
+///
+/// Downloads requested with `user_id` and no languages of their own use
+/// them.
+pub async fn set_subtitle_languages(
+    State(preferences): State<Arc<dyn SubtitlePreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+    Json(request): Json<SubtitleLanguages>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let mut languages: Vec<String> = Vec::new();
+    for language in request.languages.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if language.contains(',') {
+            return Err((StatusCode::BAD_REQUEST, format!("Invalid language: {}", language)));
+        }
+        let language = normalize_language_code(language).unwrap_or_else(|| language.to_lowercase());
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+    if languages.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Set at least one language".to_string()));
+    }
+
+    preferences.save(&user_id, &languages).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(SubtitleLanguages { languages }))
+}
+
+/// Forget the subtitle languages of a user
+pub async fn delete_subtitle_languages(
+    State(preferences): State<Arc<dyn SubtitlePreferenceRepository>>,
+    caller: Option<Extension<Caller>>,
+    Path(user_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let user_id = caller_user(caller.as_deref(), Some(&user_id))?;
+    let deleted = preferences.delete(&user_id).await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !deleted {
+        return Err((StatusCode::NOT_FOUND, "No subtitle languages for this user".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,98 @@
+//! Subtitle Editing Handlers
+//!
+//! HTTP handlers for reviewing and correcting generated subtitles: cues as
+//! JSON, single-cue edits, and SRT/WebVTT export.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::use_cases::edit_subtitle::{CueUpdate, EditSubtitleUseCase};
+use crate::interfaces::external_services::SubtitleFormat;
+use crate::presentation::http::problem::ApiError;
+
+/// List the generated subtitles of a media item
+///
+/// GET /v2/subtitles/:media_id/generated
+pub async fn list_generated_subtitles(
+    State(use_case): State<Arc<EditSubtitleUseCase>>,
+    Path(media_id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let subtitles = use_case.list(media_id).await?;
+    Ok(Json(subtitles))
+}
+
+/// Get the cues of a generated subtitle
+///
+/// GET /v2/subtitles/:media_id/generated/:language
+///
+/// # Responses
+/// - 200: The subtitle with its cues (times in seconds)
+/// - 404: No subtitle was generated in the language
+pub async fn get_cues(
+    State(use_case): State<Arc<EditSubtitleUseCase>>,
+    Path((media_id, language)): Path<(i64, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let subtitle = use_case.cues(media_id, &language).await?;
+    Ok(Json(subtitle))
+}
+
+/// Change the timing or text of a cue
+///
+/// PATCH /v2/subtitles/:media_id/generated/:language/cues/:index
+///
+/// Takes any of `start`, `end` (seconds) and `text`, and saves the subtitle
+/// file right away.
+///
+/// # Responses
+/// - 200: The updated cue
+/// - 400: Empty text, or timing that is inverted or passes a neighbour
+/// - 404: Subtitle or cue not found
+pub async fn update_cue(
+    State(use_case): State<Arc<EditSubtitleUseCase>>,
+    Path((media_id, language, index)): Path<(i64, String, usize)>,
+    Json(update): Json<CueUpdate>,
+) -> Result<impl IntoResponse, ApiError> {
+    let cue = use_case.update_cue(media_id, &language, index, update).await?;
+    Ok(Json(cue))
+}
+
+/// Query parameters for exporting a subtitle
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// srt (default) or vtt
+    #[serde(default)]
+    pub format: SubtitleFormat,
+}
+
+/// Download a generated subtitle as SRT or WebVTT
+///
+/// GET /v2/subtitles/:media_id/generated/:language/export?format=vtt
+pub async fn export_subtitle(
+    State(use_case): State<Arc<EditSubtitleUseCase>>,
+    Path((media_id, language)): Path<(i64, String)>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let content = use_case.export(media_id, &language, query.format).await?;
+
+    let content_type = match query.format {
+        SubtitleFormat::Srt => "application/x-subrip; charset=utf-8",
+        SubtitleFormat::Vtt => "text/vtt; charset=utf-8",
+    };
+    let filename = format!("{}.{}.{}", media_id, language, query.format.extension());
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"{}\"", filename)
+            .parse()
+            .map_err(|_| ApiError::bad_request(format!("Invalid language: {}", language)))?,
+    );
+    Ok((headers, content))
+}
+
@@ -0,0 +1,108 @@
+//! Subtitle Extraction Handlers
+//!
+//! HTTP handlers for copying embedded subtitle tracks into standalone files,
+//! for a media item or for the episodes of a series.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::use_cases::extract_subtitle::{ExtractSubtitleRequest, ExtractSubtitleUseCase};
+use crate::interfaces::external_services::SubtitleFormat;
+use crate::presentation::http::handlers::subtitle_generation_handlers::GenerateResponse;
+use crate::shared::error::{ApplicationError, DomainError};
+
+/// Request body for extracting the subtitles of a media item
+#[derive(Debug, Default, Deserialize)]
+pub struct ExtractSubtitleBody {
+    /// Subtitle track to extract (omit for every text track)
+    #[serde(default)]
+    pub track_index: Option<usize>,
+    /// srt (default) or vtt
+    #[serde(default)]
+    pub format: SubtitleFormat,
+    /// Extract again even if the file exists
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Extract embedded subtitles of a media item
+///
+/// POST /v2/subtitles/:media_id/extract
+///
+/// Writes the embedded text tracks next to the video (or to the data
+/// directory for read-only media), where clients loading only external
+/// subtitles find them. Bitmap tracks are listed as skipped.
+///
+/// # Responses
+/// - 200: Subtitles extracted
+/// - 400: The requested track is a bitmap track
+/// - 404: Media or track not found
+pub async fn extract_subtitles(
+    State(use_case): State<Arc<ExtractSubtitleUseCase>>,
+    Path(media_id): Path<i64>,
+    body: Option<Json<ExtractSubtitleBody>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let body = body.map(|Json(body)| body).unwrap_or_default();
+    let request = ExtractSubtitleRequest {
+        media_id,
+        track_index: body.track_index,
+        format: body.format,
+        overwrite: body.overwrite,
+    };
+
+    match use_case.execute(request).await {
+        Ok(result) => Ok(Json(result)),
+        Err(ApplicationError::Domain(DomainError::NotFound(msg))) => Err((StatusCode::NOT_FOUND, msg)),
+        Err(ApplicationError::Domain(DomainError::InvalidInput(msg))) => Err((StatusCode::BAD_REQUEST, msg)),
+        Err(e) => {
+            tracing::error!("Subtitle extraction for media {} failed: {}", media_id, e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()))
+        }
+    }
+}
+
+/// Request body for extracting the subtitles of a series
+#[derive(Debug, Deserialize)]
+pub struct BatchExtractBody {
+    pub series_id: i64,
+    /// Season to extract (omit for the whole series)
+    #[serde(default)]
+    pub season_number: Option<i32>,
+    /// srt (default) or vtt
+    #[serde(default)]
+    pub format: SubtitleFormat,
+}
+
+/// Extract embedded subtitles of a series
+///
+/// POST /v2/subtitles/batch/extract
+///
+/// Runs in the background; progress is reported by
+/// GET /v2/subtitles/batch/jobs/:job_id.
+pub async fn batch_extract_subtitles(
+    State(use_case): State<Arc<ExtractSubtitleUseCase>>,
+    Json(body): Json<BatchExtractBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let job_id = use_case.start_batch(body.series_id, body.season_number, body.format).await
+        .map_err(|e| match e {
+            ApplicationError::Domain(DomainError::NotFound(msg)) => (StatusCode::NOT_FOUND, msg),
+            e => {
+                tracing::error!("Failed to start subtitle extraction: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+        })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(GenerateResponse {
+            job_id,
+            status: "processing".to_string(),
+        }),
+    ))
+}
@@ -1,9 +1,11 @@
 //! Subtitle Generation Handlers
 //!
 //! HTTP handlers for automatic subtitle generation using Whisper + Ollama.
+//! Generation and translation jobs are resumable: a restart runs them again
+//! from their request, see [`resume_interrupted_jobs`].
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -13,11 +15,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::application::use_cases::generate_subtitle::{
     GenerateSubtitleUseCase, GenerateSubtitleRequest, GenerateSubtitleResult, ServiceCapabilities,
+    TranslateSubtitleRequest,
 };
 use crate::application::use_cases::batch_generate_subtitles::{
     BatchGenerateSubtitlesUseCase, BatchGenerateRequest, BatchTargetType,
 };
-use crate::infrastructure::jobs::{JobStore, JobStatus, BatchJobStatus};
+use crate::infrastructure::external::whisper::{WhisperModelManager, ANY_LANGUAGE};
+use crate::application::use_cases::extract_subtitle::ExtractSubtitleUseCase;
+use crate::domain::repositories::JobFilter;
+use crate::infrastructure::jobs::{JobEntry, JobState, JobStore, JobStatus, JobType};
+use crate::presentation::http::problem::ApiError;
+use crate::infrastructure::subtitle::encoding_for_label;
 
 /// Request body for single subtitle generation
 #[derive(Debug, Deserialize)]
@@ -31,6 +39,9 @@ pub struct GenerateSubtitleBody {
     /// Target language code for translation (null = no translation)
     #[serde(default)]
     pub target_language: Option<String>,
+    /// Cut out silence before transcribing (null = server setting)
+    #[serde(default)]
+    pub skip_silence: Option<bool>,
 }
 
 /// Response for subtitle generation request
@@ -54,35 +65,114 @@ pub async fn generate_subtitle(
     Path(media_id): Path<i64>,
     Json(body): Json<GenerateSubtitleBody>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    // Create job for tracking
-    let job_id = job_store.create_job().await;
-
-    // Build request
     let request = GenerateSubtitleRequest {
         media_id,
         audio_track_index: body.audio_track_index,
         source_language: body.source_language,
         target_language: body.target_language,
+        skip_silence: body.skip_silence,
     };
+    let job_id = spawn_generation(use_case, job_store, request).await;
 
-    // Spawn background task
-    let use_case = use_case.clone();
-    let job_store_clone = job_store.clone();
-    let job_id_clone = job_id.clone();
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(GenerateResponse {
+            job_id,
+            status: "processing".to_string(),
+        }),
+    ))
+}
 
+/// Starts subtitle generation in the background, returning the job ID
+pub(crate) async fn spawn_generation(
+    use_case: Arc<GenerateSubtitleUseCase>,
+    job_store: Arc<JobStore>,
+    request: GenerateSubtitleRequest,
+) -> String {
+    // Create job for tracking
+    let job_id = job_store.create_resumable_job(JobType::Subtitle, &request).await;
+    run_generation(use_case, job_store, job_id.clone(), request);
+    job_id
+}
+
+/// Runs subtitle generation for an existing job in the background
+fn run_generation(
+    use_case: Arc<GenerateSubtitleUseCase>,
+    job_store: Arc<JobStore>,
+    job_id: String,
+    request: GenerateSubtitleRequest,
+) {
     tokio::spawn(async move {
-        match use_case.execute(request, &job_id_clone).await {
+        match use_case.execute(request, &job_id).await {
             Ok(result) => {
-                job_store_clone.complete_job(&job_id_clone, &result).await;
+                job_store.complete_job(&job_id, &result).await;
                 tracing::info!("Subtitle generation completed: {}", result.subtitle_path);
             }
             Err(e) => {
                 let error_msg = e.to_string();
-                job_store_clone.fail_job(&job_id_clone, &error_msg).await;
+                job_store.fail_job(&job_id, &error_msg).await;
                 tracing::error!("Subtitle generation failed: {}", error_msg);
             }
         }
     });
+}
+
+/// Request body for translating an existing subtitle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslateSubtitleBody {
+    /// External subtitle index (as in GET /v2/subtitles/:media_id/:index)
+    pub subtitle_index: usize,
+    /// Source language code (null = from the subtitle filename)
+    #[serde(default)]
+    pub source_language: Option<String>,
+    /// Target language code
+    pub target_language: String,
+    /// Encoding of the subtitle file (null = detected)
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+/// What a translation job is resumed from
+#[derive(Serialize, Deserialize)]
+struct TranslationPayload {
+    media_id: i64,
+    #[serde(flatten)]
+    body: TranslateSubtitleBody,
+}
+
+impl TranslationPayload {
+    fn to_request(&self) -> Result<TranslateSubtitleRequest, String> {
+        let encoding = self.body.encoding.as_deref()
+            .map(encoding_for_label)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        Ok(TranslateSubtitleRequest {
+            media_id: self.media_id,
+            subtitle_index: self.body.subtitle_index,
+            source_language: self.body.source_language.clone(),
+            target_language: self.body.target_language.clone(),
+            encoding,
+        })
+    }
+}
+
+/// Translate an existing subtitle
+///
+/// POST /v2/subtitles/:media_id/translate
+///
+/// Translates an external subtitle with Ollama in the background, without
+/// running Whisper. Progress is tracked like generation jobs via
+/// GET /v2/subtitles/jobs/:job_id.
+pub async fn translate_subtitle(
+    State(use_case): State<Arc<GenerateSubtitleUseCase>>,
+    State(job_store): State<Arc<JobStore>>,
+    Path(media_id): Path<i64>,
+    Json(body): Json<TranslateSubtitleBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let payload = TranslationPayload { media_id, body };
+    let request = payload.to_request().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let job_id = job_store.create_resumable_job(JobType::Translation, &payload).await;
+    run_translation(use_case, job_store, job_id.clone(), request);
 
     Ok((
         StatusCode::ACCEPTED,
@@ -93,6 +183,111 @@ pub async fn generate_subtitle(
     ))
 }
 
+/// Runs a subtitle translation for an existing job in the background
+fn run_translation(
+    use_case: Arc<GenerateSubtitleUseCase>,
+    job_store: Arc<JobStore>,
+    job_id: String,
+    request: TranslateSubtitleRequest,
+) {
+    tokio::spawn(async move {
+        match use_case.translate_existing(request, &job_id).await {
+            Ok(result) => {
+                job_store.complete_job(&job_id, &result).await;
+                tracing::info!("Subtitle translation completed: {}", result.subtitle_path);
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                job_store.fail_job(&job_id, &error_msg).await;
+                tracing::error!("Subtitle translation failed: {}", error_msg);
+            }
+        }
+    });
+}
+
+/// Runs the jobs a restart interrupted again
+///
+/// Called once at startup. Jobs whose request can no longer be read fail.
+pub async fn resume_interrupted_jobs(
+    job_store: Arc<JobStore>,
+    generate: Arc<GenerateSubtitleUseCase>,
+    batch_generate: Arc<BatchGenerateSubtitlesUseCase>,
+    extract: Arc<ExtractSubtitleUseCase>,
+) {
+    let interrupted = match job_store.recover_interrupted().await {
+        Ok(interrupted) => interrupted,
+        Err(e) => {
+            tracing::error!("Failed to load interrupted jobs: {}", e);
+            return;
+        }
+    };
+    if !interrupted.is_empty() {
+        tracing::info!("Resuming {} interrupted jobs", interrupted.len());
+    }
+
+    for job in interrupted {
+        let resumed = match job.job_type {
+            JobType::Subtitle => serde_json::from_value(job.payload)
+                .map(|request| run_generation(generate.clone(), job_store.clone(), job.id.clone(), request))
+                .map_err(|e| e.to_string()),
+            JobType::Translation => serde_json::from_value::<TranslationPayload>(job.payload)
+                .map_err(|e| e.to_string())
+                .and_then(|payload| payload.to_request())
+                .map(|request| run_translation(generate.clone(), job_store.clone(), job.id.clone(), request)),
+            JobType::SubtitleBatch => batch_generate.resume(&job.id, job.payload).await.map_err(|e| e.to_string()),
+            JobType::ExtractionBatch => extract.resume_batch(&job.id, job.payload).await.map_err(|e| e.to_string()),
+            JobType::ModelDownload => Err("Model downloads are not resumed".to_string()),
+        };
+        if let Err(e) = resumed {
+            tracing::warn!("Cannot resume {} job {}: {}", job.job_type.as_str(), job.id, e);
+            job_store.abandon_job(&job.id, &format!("Cannot resume after a restart: {}", e)).await;
+        }
+    }
+}
+
+/// Query parameters for listing jobs
+#[derive(Debug, Deserialize)]
+pub struct ListJobsQuery {
+    /// Only jobs in this state
+    #[serde(default)]
+    pub state: Option<JobState>,
+    /// Only jobs of this type
+    #[serde(default, rename = "type")]
+    pub job_type: Option<JobType>,
+    /// Most jobs returned
+    #[serde(default = "default_jobs_limit")]
+    pub limit: u32,
+}
+
+fn default_jobs_limit() -> u32 {
+    100
+}
+
+/// Response for the job listing
+#[derive(Debug, Serialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<JobEntry>,
+}
+
+/// List jobs
+///
+/// GET /v2/jobs
+///
+/// Returns jobs of every type (single and batch), newest first, including
+/// finished ones and those from before a restart.
+pub async fn list_jobs(
+    State(job_store): State<Arc<JobStore>>,
+    Query(query): Query<ListJobsQuery>,
+) -> Result<Json<JobsResponse>, ApiError> {
+    let filter = JobFilter {
+        state: query.state.map(|s| s.as_str().to_string()),
+        job_type: query.job_type.map(|t| t.as_str().to_string()),
+        limit: query.limit.min(1000),
+    };
+    let jobs = job_store.list_jobs(&filter).await?;
+    Ok(Json(JobsResponse { jobs }))
+}
+
 /// Get job status
 ///
 /// GET /v2/subtitles/jobs/:job_id
@@ -235,28 +430,159 @@ pub async fn get_capabilities(
     Json(capabilities)
 }
 
-/// Response for active jobs count
+/// Response for active jobs
 #[derive(Debug, Serialize)]
 pub struct ActiveJobsResponse {
     /// Number of active single jobs
     pub active_jobs: usize,
     /// Number of active batch jobs
     pub active_batch_jobs: usize,
+    /// Active single jobs with their completion estimates
+    pub jobs: Vec<JobStatus>,
 }
 
-/// Get count of active jobs
+/// Get active jobs
 ///
 /// GET /v2/subtitles/active
 ///
-/// Returns the number of currently running subtitle generation jobs.
+/// Returns the currently running subtitle generation jobs, with estimated
+/// completion times once earlier jobs have measured this machine's speed.
 pub async fn get_active_jobs(
     State(job_store): State<Arc<JobStore>>,
 ) -> impl IntoResponse {
-    let active_jobs = job_store.active_job_count().await;
+    let jobs = job_store.active_jobs().await;
     let active_batch_jobs = job_store.active_batch_job_count().await;
 
     Json(ActiveJobsResponse {
-        active_jobs,
+        active_jobs: jobs.len(),
         active_batch_jobs,
+        jobs,
     })
 }
+
+/// Request body for installing or selecting a Whisper model
+#[derive(Debug, Deserialize)]
+pub struct WhisperModelBody {
+    /// Model name (e.g. "medium", "large-v3-turbo")
+    pub model: String,
+    /// Language to make the model the default for ("*" = all languages;
+    /// null = only download)
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// List Whisper models
+///
+/// GET /v2/subtitles/models
+///
+/// Returns the installed models and the ones that can be downloaded, with
+/// the languages each is the default for.
+pub async fn list_whisper_models(
+    State(models): State<Arc<WhisperModelManager>>,
+) -> impl IntoResponse {
+    let models_dir = models.models_dir().to_string_lossy().to_string();
+    let models = tokio::task::spawn_blocking(move || models.list()).await.unwrap_or_default();
+    Json(serde_json::json!({
+        "models_dir": models_dir,
+        "models": models,
+    }))
+}
+
+/// Install or select a Whisper model
+///
+/// POST /v2/subtitles/models
+///
+/// An installed model is selected for `language` right away. A missing
+/// model is downloaded in the background (tracked via
+/// GET /v2/subtitles/jobs/:job_id) and selected once complete. With
+/// `API_SECRET` set, this needs the shared secret.
+pub async fn install_whisper_model(
+    State(models): State<Arc<WhisperModelManager>>,
+    State(job_store): State<Arc<JobStore>>,
+    Json(body): Json<WhisperModelBody>,
+) -> Result<axum::response::Response, (StatusCode, String)> {
+    let language = body.language
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    let language_ok = language.as_deref()
+        .is_none_or(|l| l == ANY_LANGUAGE || l.chars().all(|c| c.is_ascii_alphabetic() || c == '-' || c == '_'));
+    if !language_ok {
+        return Err((StatusCode::BAD_REQUEST, "Invalid language".to_string()));
+    }
+
+    if models.is_installed(&body.model) {
+        if let Some(language) = &language {
+            models.set_default(language, &body.model)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+        return Ok(Json(serde_json::json!({
+            "model": body.model,
+            "status": "installed",
+            "language": language,
+        })).into_response());
+    }
+    if !models.is_downloadable(&body.model) {
+        return Err((StatusCode::NOT_FOUND, format!("Unknown Whisper model: {}", body.model)));
+    }
+
+    let job_id = job_store.create_job(JobType::ModelDownload).await;
+    job_store.start_job(&job_id).await;
+    let job_id_clone = job_id.clone();
+
+    tokio::spawn(async move {
+        // Progress is reported from the download loop; forward whole percents
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<f32>();
+        let last_percent = std::sync::atomic::AtomicU32::new(0);
+        let progress_job_store = job_store.clone();
+        let progress_job_id = job_id_clone.clone();
+        let forwarder = tokio::spawn(async move {
+            while let Some(percent) = rx.recv().await {
+                progress_job_store
+                    .update_progress(&progress_job_id, percent, Some("Downloading Whisper model..."))
+                    .await;
+            }
+        });
+
+        let result = models
+            .download(&body.model, |received, total| {
+                if let Some(total) = total.filter(|t| *t > 0) {
+                    let percent = (received * 100 / total) as u32;
+                    if percent > last_percent.swap(percent, std::sync::atomic::Ordering::Relaxed) {
+                        let _ = tx.send(percent as f32);
+                    }
+                }
+            })
+            .await;
+        drop(tx);
+        let _ = forwarder.await;
+
+        let result = result.and_then(|path| {
+            if let Some(language) = &language {
+                models.set_default(language, &body.model)?;
+            }
+            Ok(path)
+        });
+        match result {
+            Ok(path) => {
+                tracing::info!("Whisper model {} downloaded to {}", body.model, path.display());
+                job_store.complete_job(&job_id_clone, &serde_json::json!({
+                    "model": body.model,
+                    "path": path,
+                    "language": language,
+                })).await;
+            }
+            Err(e) => {
+                tracing::error!("Whisper model download failed: {}", e);
+                job_store.fail_job(&job_id_clone, &e.to_string()).await;
+            }
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(GenerateResponse {
+            job_id,
+            status: "downloading".to_string(),
+        }),
+    ).into_response())
+}
@@ -0,0 +1,85 @@
+//! Task Handlers
+//!
+//! HTTP handlers for the scheduled background tasks:
+//!
+//! - `GET /v2/admin/tasks` - Tasks with their schedules and last runs
+//! - `GET /v2/admin/tasks/:name` - One task
+//! - `PUT /v2/admin/tasks/:name` - Change its schedule or turn it on or off
+//! - `POST /v2/admin/tasks/:name/run` - Run it now
+//!
+//! With `API_SECRET` set, all of them need the shared secret.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::application::services::{TaskInfo, TaskScheduler, TaskUpdate};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::problem::ApiError;
+
+/// Scheduled tasks
+#[derive(Debug, Serialize)]
+pub struct TaskListResponse {
+    pub tasks: Vec<TaskInfo>,
+}
+
+/// List the scheduled tasks
+pub async fn list_tasks(
+    State(scheduler): State<Arc<TaskScheduler>>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(TaskListResponse { tasks: scheduler.list() }))
+}
+
+/// Get one scheduled task
+///
+/// # Responses
+/// - 200: The task
+/// - 404: No task by that name
+pub async fn get_task(
+    State(scheduler): State<Arc<TaskScheduler>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(scheduler.get(&name)?))
+}
+
+/// Change when a task runs, taking effect without a restart
+///
+/// The body may hold `schedule` (a cron expression in the server's local
+/// time) and `enabled`.
+///
+/// # Responses
+/// - 200: The task
+/// - 400: Invalid schedule; nothing is changed
+/// - 404: No task by that name
+pub async fn update_task(
+    State(scheduler): State<Arc<TaskScheduler>>,
+    auditor: Auditor,
+    Path(name): Path<String>,
+    Json(update): Json<TaskUpdate>,
+) -> Result<impl IntoResponse, ApiError> {
+    let task = scheduler.update(&name, update).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("task:{}", name))
+            .with_details(format!("Schedule {}, {}", task.schedule, if task.enabled { "enabled" } else { "disabled" })),
+    ).await;
+    Ok(Json(task))
+}
+
+/// Run a task now, also when it is disabled
+///
+/// # Responses
+/// - 202: The run started
+/// - 404: No task by that name
+/// - 409: The task is already running
+pub async fn run_task(
+    State(scheduler): State<Arc<TaskScheduler>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok((StatusCode::ACCEPTED, Json(scheduler.run_now(&name)?)))
+}
@@ -0,0 +1,176 @@
+//! Webhook Handlers
+//!
+//! HTTP handlers for managing outgoing webhooks:
+//!
+//! - `GET|POST /v2/admin/webhooks`
+//! - `GET|PUT|DELETE /v2/admin/webhooks/:id`
+//! - `GET /v2/admin/webhooks/:id/deliveries`
+//! - `POST /v2/admin/webhooks/:id/ping`
+//!
+//! With `API_SECRET` set, all of them need the shared secret.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::application::services::WebhookService;
+use crate::application::services::webhooks::WEBHOOK_EVENT_TYPES;
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::domain::repositories::{Webhook, WebhookSettings};
+use crate::presentation::http::handlers::audit_handlers::Auditor;
+use crate::presentation::http::problem::ApiError;
+
+/// Deliveries listed by default
+const DEFAULT_DELIVERIES: u32 = 20;
+/// Most deliveries listed
+const MAX_DELIVERIES: u32 = 100;
+
+/// Body of a webhook to add or change
+#[derive(Debug, Deserialize)]
+pub struct WebhookRequest {
+    pub name: String,
+    /// http(s) URL events are POSTed to
+    pub url: String,
+    /// Signs each body; when changing a webhook, leaving it out keeps the
+    /// current one and `""` removes it
+    pub secret: Option<String>,
+    /// Event types to deliver (default: all)
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Default: true
+    pub enabled: Option<bool>,
+}
+
+/// A webhook, without its secret
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// Whether bodies are signed
+    pub signed: bool,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self { signed: webhook.secret.is_some(), webhook }
+    }
+}
+
+/// Webhooks with the event types they can subscribe to
+#[derive(Debug, Serialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookResponse>,
+    pub event_types: &'static [&'static str],
+}
+
+/// Query parameters of the delivery log
+#[derive(Debug, Deserialize)]
+pub struct DeliveriesQuery {
+    /// Deliveries to list (default: 20, at most 100)
+    pub limit: Option<u32>,
+}
+
+/// List the webhooks
+pub async fn list_webhooks(
+    State(webhooks): State<Arc<WebhookService>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let list = webhooks.list().await?;
+    Ok(Json(WebhookListResponse {
+        webhooks: list.into_iter().map(WebhookResponse::from).collect(),
+        event_types: WEBHOOK_EVENT_TYPES,
+    }))
+}
+
+/// Get one webhook
+pub async fn get_webhook(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(WebhookResponse::from(webhooks.get(id).await?)))
+}
+
+/// Add a webhook
+///
+/// # Responses
+/// - 201: The webhook with its ID
+/// - 400: Empty name, URL that is not http(s), unknown event type
+pub async fn create_webhook(
+    State(webhooks): State<Arc<WebhookService>>,
+    auditor: Auditor,
+    Json(request): Json<WebhookRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let settings = WebhookSettings {
+        name: request.name,
+        url: request.url,
+        secret: request.secret,
+        event_types: request.event_types,
+        enabled: request.enabled.unwrap_or(true),
+    };
+    let webhook = webhooks.create(settings).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("webhook:{}", webhook.id))
+            .with_details(format!("Webhook '{}' added", webhook.name)),
+    ).await;
+    Ok((StatusCode::CREATED, Json(WebhookResponse::from(webhook))))
+}
+
+/// Change a webhook
+pub async fn update_webhook(
+    State(webhooks): State<Arc<WebhookService>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+    Json(request): Json<WebhookRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let current = webhooks.get(id).await?;
+    let settings = WebhookSettings {
+        name: request.name,
+        url: request.url,
+        secret: request.secret.or(current.secret),
+        event_types: request.event_types,
+        enabled: request.enabled.unwrap_or(current.enabled),
+    };
+    let webhook = webhooks.update(id, settings).await?;
+    auditor.record(
+        AuditEvent::new(AuditAction::SettingsChange, format!("webhook:{}", webhook.id))
+            .with_details(format!("Webhook '{}' changed", webhook.name)),
+    ).await;
+    Ok(Json(WebhookResponse::from(webhook)))
+}
+
+/// Remove a webhook and its delivery log
+pub async fn delete_webhook(
+    State(webhooks): State<Arc<WebhookService>>,
+    auditor: Auditor,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    webhooks.delete(id).await?;
+    auditor.record(AuditEvent::new(AuditAction::Deletion, format!("webhook:{}", id)).with_details("Webhook removed")).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the latest deliveries of a webhook, newest first
+pub async fn list_deliveries(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i64>,
+    Query(query): Query<DeliveriesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_DELIVERIES).clamp(1, MAX_DELIVERIES);
+    Ok(Json(webhooks.deliveries(id, limit).await?))
+}
+
+/// Send a `ping` event to a webhook, to check it is reachable
+///
+/// # Responses
+/// - 200: The delivery, successful or not
+pub async fn ping_webhook(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(webhooks.ping(id).await?))
+}
+
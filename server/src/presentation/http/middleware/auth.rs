@@ -1,27 +1,257 @@
 //! Authentication Middleware
 //!
 //! Handles API authentication and authorization.
+//!
+//! Without `API_SECRET` every request is allowed. With it, requests need
+//! `Authorization: Bearer <key>` carrying the shared secret or a device key
+//! (see [`ApiKeyService`]); the caller is handed to handlers as a
+//! [`Caller`] request extension. Rejected requests are recorded in the audit
+//! log. Admin routes additionally need the shared secret (see
+//! [`admin_middleware`]).
+//!
+//! DLNA renderers cannot send a key, so `/dlna/*` is only open to clients on
+//! the local network; the stream URLs it hands out carry session tokens.
 
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, Method, Request, StatusCode},
     middleware::Next,
     response::Response,
 };
 
+use crate::application::services::{ApiKeyService, Caller, StreamUrlSigner};
+use crate::domain::events::{AuditAction, AuditEvent};
+use crate::infrastructure::messaging::InMemoryEventBus;
+use crate::presentation::http::handlers::audit_handlers::{client_ip, publish};
+use crate::presentation::http::handlers::auth_handlers::require_admin;
+use crate::presentation::http::middleware::stream_token::query_token;
+use crate::presentation::http::problem::ApiError;
+
+/// State of the authentication middleware
+#[derive(Clone)]
+pub struct AuthState {
+    pub api_keys: Arc<ApiKeyService>,
+    pub stream_signer: Arc<StreamUrlSigner>,
+    /// Bus for audit events of rejected requests
+    pub event_bus: Option<Arc<InMemoryEventBus>>,
+}
+
 /// Authentication middleware
 pub async fn auth_middleware(
-    req: Request<Body>,
+    State(auth): State<AuthState>,
+    mut req: Request<Body>,
     next: Next,
-) -> Result<Response, StatusCode> {
-    // Skip authentication for health check endpoint
-    if req.uri().path() == "/health" {
+) -> Result<Response, ApiError> {
+    let path = req.uri().path();
+
+    // Skip authentication for health check endpoints
+    if path == "/health" || path.starts_with("/health/") {
+        return Ok(next.run(req).await);
+    }
+
+    // Signed cast URLs carry their own authorization (see cast_handlers)
+    if path.starts_with("/v2/cast/play/") {
+        return Ok(next.run(req).await);
+    }
+
+    if !auth.api_keys.is_enabled() {
         return Ok(next.run(req).await);
     }
-    
-    // Placeholder for authentication logic
-    // In a real implementation, we would check JWT tokens or API keys
-    
-    // For now, we allow all requests
+
+    // DLNA discovery, browsing and artwork for renderers on the LAN
+    if path.starts_with("/dlna/") {
+        let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip());
+        if peer.is_some_and(is_local) && !is_proxied(req.headers()) {
+            return Ok(next.run(req).await);
+        }
+        return Err(reject(&auth, path.to_string(), client_ip(req.extensions()), "DLNA is only served on the local network").await);
+    }
+
+    // Stream URLs for <video> elements carry a session token instead of a header
+    if let Some(media_id) = stream_media_id(req.method(), path) {
+        let token = req.uri().query().and_then(query_token);
+        if let Some(token) = token {
+            return match auth.stream_signer.verify_session(&token) {
+                Ok((claims, _)) if claims.media_id == media_id => Ok(next.run(req).await),
+                _ => Err(reject(&auth, req.uri().path().to_string(), client_ip(req.extensions()), "Invalid stream token").await),
+            };
+        }
+    }
+
+    let key = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    let Some(key) = key else {
+        return Err(reject(&auth, req.uri().path().to_string(), client_ip(req.extensions()), "Missing API key").await);
+    };
+    let Some(caller) = auth.api_keys.authenticate(key).await else {
+        return Err(reject(&auth, req.uri().path().to_string(), client_ip(req.extensions()), "Invalid API key").await);
+    };
+
+    req.extensions_mut().insert::<Caller>(caller.clone());
+    let mut response = next.run(req).await;
+    // For the access log
+    response.extensions_mut().insert(caller);
+    Ok(response)
+}
+
+/// Rejects device keys on the routes it is layered on
+///
+/// Runs after [`auth_middleware`], which identified the caller.
+pub async fn admin_middleware(
+    State(api_keys): State<Arc<ApiKeyService>>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    require_admin(&api_keys, req.extensions().get::<Caller>())?;
     Ok(next.run(req).await)
 }
+
+/// Records a rejected request in the audit log
+async fn reject(auth: &AuthState, path: String, ip: Option<String>, reason: &str) -> ApiError {
+    let mut event = AuditEvent::new(AuditAction::AuthFailed, path).with_details(reason);
+    event.ip = ip;
+    publish(auth.event_bus.as_deref(), event).await;
+    ApiError::new(StatusCode::UNAUTHORIZED, "unauthorized", reason)
+}
+
+/// Media ID of a stream request that may use a session token (`GET` of
+/// `/v2/stream/:id`, `/v2/stream/web/:id` and `/v2/stream/hls/:id/...`)
+///
+/// Issuing tokens and playback info need a key, so a token cannot be used
+/// to mint further tokens.
+fn stream_media_id(method: &Method, path: &str) -> Option<i64> {
+    if method != Method::GET && method != Method::HEAD {
+        return None;
+    }
+    let rest = path.strip_prefix("/v2/stream/")?;
+    match rest.strip_prefix("hls/") {
+        Some(hls) => hls.split('/').next()?.parse().ok(),
+        None => rest.strip_prefix("web/").unwrap_or(rest).parse().ok(),
+    }
+}
+
+/// Whether a peer address is on the local network (loopback, private,
+/// link-local or unique local)
+fn is_local(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_local(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback() || first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// Whether the request came through a reverse proxy, whose own address says
+/// nothing about the client
+fn is_proxied(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::FORWARDED) || headers.contains_key("x-forwarded-for") || headers.contains_key("x-real-ip")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::schema::initialize_schema;
+    use crate::infrastructure::persistence::sqlite::SqliteDeviceKeyRepository;
+    use axum::{routing::get, Router};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tower::ServiceExt;
+
+    async fn api_keys() -> Arc<ApiKeyService> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test pool");
+        initialize_schema(&pool).await.expect("Failed to initialize schema");
+        Arc::new(ApiKeyService::new(Arc::new(SqliteDeviceKeyRepository::new(pool)), Some("shared secret")))
+    }
+
+    fn app(api_keys: Arc<ApiKeyService>) -> Router {
+        let admin = Router::new()
+            .route("/v2/admin/log-level", get(|| async { "admin" }))
+            .route_layer(axum::middleware::from_fn_with_state(api_keys.clone(), admin_middleware));
+        let auth = AuthState {
+            api_keys,
+            stream_signer: Arc::new(StreamUrlSigner::new(Some("signing key"), std::time::Duration::from_secs(3600))),
+            event_bus: None,
+        };
+        Router::new()
+            .route("/v2/media", get(|| async { "media" }))
+            .route("/dlna/description.xml", get(|| async { "description" }))
+            .merge(admin)
+            .layer(axum::middleware::from_fn_with_state(auth, auth_middleware))
+    }
+
+    async fn status(app: &Router, uri: &str, key: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().uri(uri);
+        if let Some(key) = key {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {}", key));
+        }
+        app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes() {
+        let api_keys = api_keys().await;
+        let (_, device_key) = api_keys.create("Tablet", "default").await.unwrap();
+        let app = app(api_keys);
+
+        assert_eq!(status(&app, "/v2/media", Some(&device_key)).await, StatusCode::OK);
+        assert_eq!(status(&app, "/v2/admin/log-level", Some(&device_key)).await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&app, "/v2/admin/log-level", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(&app, "/v2/admin/log-level", Some("shared secret")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_dlna_is_limited_to_the_local_network() {
+        let app = app(api_keys().await);
+        let status = |peer: &str, forwarded: bool| {
+            let app = app.clone();
+            let peer: SocketAddr = peer.parse().unwrap();
+            async move {
+                let mut req = Request::builder().uri("/dlna/description.xml");
+                if forwarded {
+                    req = req.header("x-forwarded-for", "203.0.113.7");
+                }
+                let mut req = req.body(Body::empty()).unwrap();
+                req.extensions_mut().insert(ConnectInfo(peer));
+                app.oneshot(req).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(status("192.168.1.20:50000", false).await, StatusCode::OK);
+        assert_eq!(status("[fd00::20]:50000", false).await, StatusCode::OK);
+        assert_eq!(status("[::ffff:10.0.0.5]:50000", false).await, StatusCode::OK);
+        assert_eq!(status("203.0.113.7:50000", false).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status("[2001:db8::1]:50000", false).await, StatusCode::UNAUTHORIZED);
+        // A local reverse proxy may be relaying a remote client
+        assert_eq!(status("127.0.0.1:50000", true).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_stream_media_id() {
+        let get = |path| stream_media_id(&Method::GET, path);
+        assert_eq!(get("/v2/stream/12"), Some(12));
+        assert_eq!(get("/v2/stream/web/12"), Some(12));
+        assert_eq!(get("/v2/stream/hls/12/master.m3u8"), Some(12));
+        assert_eq!(get("/v2/stream/hls/12/abc/720p/seg_00001.ts"), Some(12));
+        assert_eq!(get("/v2/stream/hls/sessions"), None);
+        assert_eq!(get("/v2/media/12"), None);
+        // Tokens cannot issue tokens or stop sessions
+        assert_eq!(get("/v2/stream/12/playback-info"), None);
+        assert_eq!(stream_media_id(&Method::POST, "/v2/stream/12/token"), None);
+        assert_eq!(stream_media_id(&Method::POST, "/v2/stream/12/playback-info"), None);
+        assert_eq!(stream_media_id(&Method::DELETE, "/v2/stream/hls/12/abc"), None);
+    }
+}
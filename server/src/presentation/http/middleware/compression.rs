@@ -0,0 +1,260 @@
+//! Compression Middleware
+//!
+//! Compresses buffered JSON and text responses with gzip or deflate,
+//! whichever the client's `Accept-Encoding` prefers. Video and audio
+//! streams, range responses, HLS playlists and segments, images and
+//! anything already encoded are passed through untouched, as are bodies
+//! smaller than the configured minimum.
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use flate2::{
+    write::{GzEncoder, ZlibEncoder},
+    Compression,
+};
+use std::io::Write;
+
+/// Largest body buffered for compression
+const MAX_COMPRESSED_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Response compression settings
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionSettings {
+    pub enabled: bool,
+    /// Bodies smaller than this are sent as they are
+    pub min_bytes: usize,
+}
+
+/// Content codings the server can produce, in order of preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Gzip,
+    Deflate,
+}
+
+impl Encoding {
+    const ALL: [Encoding; 2] = [Encoding::Gzip, Encoding::Deflate];
+
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    fn encode(self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            Encoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Compression middleware
+pub async fn compression_middleware(
+    State(settings): State<CompressionSettings>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !settings.enabled || req.headers().contains_key(header::RANGE) {
+        return next.run(req).await;
+    }
+    let encoding = req.headers().get(header::ACCEPT_ENCODING).and_then(negotiate);
+
+    let mut response = next.run(req).await;
+    let size = response.body().size_hint().exact();
+    let eligible = size.is_some_and(|size| size >= settings.min_bytes as u64 && size <= MAX_COMPRESSED_BYTES);
+    if !eligible || !compressible(response.status(), response.headers()) {
+        return response;
+    }
+    response.headers_mut().append(header::VARY, HeaderValue::from_static("accept-encoding"));
+    let Some(encoding) = encoding else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_COMPRESSED_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let source = bytes.clone();
+    let compressed = match tokio::task::spawn_blocking(move || encoding.encode(&source)).await {
+        Ok(Ok(compressed)) if compressed.len() < bytes.len() => compressed,
+        _ => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    parts.headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding.name()));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    // The compressed body is a different representation of the same resource
+    if let Some(etag) = parts.headers.get(header::ETAG).and_then(|etag| etag.to_str().ok()) {
+        if !etag.starts_with("W/") {
+            let weak = format!("W/{}", etag);
+            if let Ok(weak) = HeaderValue::from_str(&weak) {
+                parts.headers.insert(header::ETAG, weak);
+            }
+        }
+    }
+    Response::from_parts(parts, Body::from(Bytes::from(compressed)))
+}
+
+/// The supported coding the client prefers, if it accepts any
+fn negotiate(accept_encoding: &HeaderValue) -> Option<Encoding> {
+    let accept_encoding = accept_encoding.to_str().ok()?;
+    let mut accepted: Vec<(&str, f32)> = Vec::new();
+    for entry in accept_encoding.split(',') {
+        let mut params = entry.split(';');
+        let coding = params.next().unwrap_or_default().trim();
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if !coding.is_empty() {
+            accepted.push((coding, quality));
+        }
+    }
+
+    let quality = |encoding: Encoding| -> f32 {
+        let named = accepted.iter().find(|(coding, _)| coding.eq_ignore_ascii_case(encoding.name()));
+        let any = accepted.iter().find(|(coding, _)| *coding == "*");
+        named.or(any).map_or(0.0, |(_, q)| *q)
+    };
+    // Ties go to the earlier coding in `Encoding::ALL`
+    Encoding::ALL
+        .into_iter()
+        .map(|encoding| (encoding, quality(encoding)))
+        .filter(|(_, q)| *q > 0.0)
+        .fold(None, |best: Option<(Encoding, f32)>, (encoding, q)| match best {
+            Some((_, best_q)) if best_q >= q => best,
+            _ => Some((encoding, q)),
+        })
+        .map(|(encoding, _)| encoding)
+}
+
+/// Whether a response is worth compressing: unencoded, not a range or
+/// empty, and JSON or text
+fn compressible(status: StatusCode, headers: &HeaderMap) -> bool {
+    if status == StatusCode::PARTIAL_CONTENT
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || headers.contains_key(header::CONTENT_ENCODING)
+        || headers.contains_key(header::CONTENT_RANGE)
+    {
+        return false;
+    }
+    let no_transform = headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.to_ascii_lowercase().contains("no-transform"));
+    if no_transform {
+        return false;
+    }
+
+    let Some(content_type) = headers.get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    match mime.split_once('/') {
+        Some(("text", "event-stream")) => false,
+        Some(("text", _)) => true,
+        Some(("application", subtype)) => {
+            matches!(subtype, "json" | "xml" | "javascript")
+                || subtype.ends_with("+json")
+                || subtype.ends_with("+xml")
+        }
+        Some(("image", "svg+xml")) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    fn app(min_bytes: usize) -> Router {
+        let listing = serde_json::json!({ "items": vec!["The Grand Budapest Hotel"; 200] });
+        Router::new()
+            .route("/v2/media", get(move || async move { axum::Json(listing) }))
+            .route("/v2/media/1", get(|| async { axum::Json(serde_json::json!({ "id": 1 })) }))
+            .route(
+                "/v2/images/1/poster",
+                get(|| async { ([(header::CONTENT_TYPE, "image/jpeg")], vec![0u8; 4096]) }),
+            )
+            .route(
+                "/v2/stream/1",
+                get(|| async {
+                    let chunks = (0..4).map(|_| Ok::<_, std::io::Error>("chunk".repeat(1000)));
+                    ([(header::CONTENT_TYPE, "text/plain")], Body::from_stream(futures::stream::iter(chunks)))
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                CompressionSettings { enabled: true, min_bytes },
+                compression_middleware,
+            ))
+    }
+
+    fn get_with(uri: &str, accept_encoding: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(header::ACCEPT_ENCODING, accept_encoding)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let negotiate = |value: &'static str| negotiate(&HeaderValue::from_static(value));
+        assert_eq!(negotiate("gzip, deflate, br"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("deflate;q=1.0, gzip;q=0.5"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("br, *;q=0.1"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("gzip;q=0, deflate;q=0"), None);
+        assert_eq!(negotiate("identity, br, zstd"), None);
+    }
+
+    #[tokio::test]
+    async fn test_compression_and_exemptions() {
+        let response = app(1024).oneshot(get_with("/v2/media", "br, gzip")).await.unwrap();
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[header::VARY], "accept-encoding");
+        let compressed = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut json = String::new();
+        GzDecoder::new(&compressed[..]).read_to_string(&mut json).unwrap();
+        assert!(json.len() > compressed.len());
+        assert!(json.starts_with("{\"items\":[\"The Grand Budapest Hotel\""));
+
+        // Range requests, small bodies, images and streams are left alone
+        let mut request = get_with("/v2/media", "gzip");
+        request.headers_mut().insert(header::RANGE, HeaderValue::from_static("bytes=0-99"));
+        let exempt = [
+            request,
+            get_with("/v2/media/1", "gzip"),
+            get_with("/v2/images/1/poster", "gzip"),
+            get_with("/v2/stream/1", "gzip"),
+        ];
+        for request in exempt {
+            let response = app(1024).oneshot(request).await.unwrap();
+            assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+        }
+
+        let response = app(0).oneshot(get_with("/v2/media", "identity")).await.unwrap();
+        assert!(!response.headers().contains_key(header::CONTENT_ENCODING));
+        assert_eq!(response.headers()[header::VARY], "accept-encoding");
+    }
+}
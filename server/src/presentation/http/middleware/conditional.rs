@@ -0,0 +1,137 @@
+//! Conditional Request Middleware
+//!
+//! Adds an `ETag` to buffered `GET` responses (JSON, images) and answers
+//! `If-None-Match` and `If-Modified-Since` with `304 Not Modified`, so
+//! clients polling the home screen or re-showing posters don't download
+//! what they already have. Streams are passed through untouched.
+
+use axum::{
+    body::{Body, HttpBody},
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, FixedOffset};
+use sha2::{Digest, Sha256};
+
+/// Largest body buffered to compute an ETag
+const MAX_TAGGED_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Headers kept on a `304` response
+const NOT_MODIFIED_HEADERS: [header::HeaderName; 5] = [
+    header::ETAG,
+    header::CACHE_CONTROL,
+    header::LAST_MODIFIED,
+    header::VARY,
+    header::EXPIRES,
+];
+
+/// Conditional request middleware
+pub async fn conditional_middleware(req: Request<Body>, next: Next) -> Response {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return next.run(req).await;
+    }
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+    let if_modified_since = req.headers().get(header::IF_MODIFIED_SINCE).cloned();
+
+    let response = next.run(req).await;
+    let buffered = response.body().size_hint().exact().is_some_and(|size| size <= MAX_TAGGED_BYTES);
+    if response.status() != StatusCode::OK || !buffered {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_TAGGED_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    if !parts.headers.contains_key(header::ETAG) {
+        let digest = Sha256::digest(&bytes);
+        let etag = format!("\"{}\"", hex::encode(&digest[..16]));
+        parts.headers.insert(header::ETAG, HeaderValue::from_str(&etag).expect("hex ETag is a valid header"));
+    }
+
+    // If-None-Match wins over If-Modified-Since (RFC 9110 13.2.2)
+    let not_modified = match (if_none_match, if_modified_since) {
+        (Some(tags), _) => parts.headers.get(header::ETAG).is_some_and(|etag| etag_matches(&tags, etag)),
+        (None, Some(since)) => not_modified_since(&parts.headers, &since),
+        (None, None) => false,
+    };
+    if !not_modified {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::NOT_MODIFIED;
+    for name in NOT_MODIFIED_HEADERS {
+        if let Some(value) = parts.headers.get(&name) {
+            response.headers_mut().insert(name, value.clone());
+        }
+    }
+    response
+}
+
+/// Whether an `If-None-Match` list names the ETag, compared weakly
+fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(tags), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    tags.split(',').any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// Whether the response's `Last-Modified` is no later than `If-Modified-Since`
+fn not_modified_since(headers: &HeaderMap, since: &HeaderValue) -> bool {
+    let parse = |value: &HeaderValue| -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc2822(value.to_str().ok()?).ok()
+    };
+    match (headers.get(header::LAST_MODIFIED).and_then(parse), parse(since)) {
+        (Some(modified), Some(since)) => modified <= since,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/v2/media/1", get(|| async { axum::Json(serde_json::json!({ "id": 1 })) }))
+            .route(
+                "/v2/stream/1",
+                get(|| async { Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>("chunk")])) }),
+            )
+            .layer(axum::middleware::from_fn(conditional_middleware))
+    }
+
+    fn get_with(uri: &str, if_none_match: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(tags) = if_none_match {
+            builder = builder.header(header::IF_NONE_MATCH, tags);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_etag_and_not_modified() {
+        let response = app().oneshot(get_with("/v2/media/1", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let response = app().oneshot(get_with("/v2/media/1", Some(&format!("\"x\", W/{}", etag)))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag.as_str());
+
+        let response = app().oneshot(get_with("/v2/media/1", Some("\"stale\""))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Streams are not buffered
+        let response = app().oneshot(get_with("/v2/stream/1", Some("*"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::ETAG));
+    }
+}
@@ -6,6 +6,8 @@ use tower_http::cors::{AllowOrigin, CorsLayer, Any};
 use axum::http::{header, Method};
 use std::time::Duration;
 
+use crate::presentation::http::middleware::logging::REQUEST_ID_HEADER;
+
 /// Creates a predefined CORS layer
 pub fn cors_layer() -> CorsLayer {
     let allowed_origins = [
@@ -34,6 +36,15 @@ pub fn cors_layer() -> CorsLayer {
             header::ACCEPT,
             header::RANGE,
             "x-test-chromecast".parse().unwrap(),
+            REQUEST_ID_HEADER,
+        ])
+        // Cast receivers issue range requests and read these back; the
+        // request ID is for bug reports
+        .expose_headers([
+            header::CONTENT_LENGTH,
+            header::CONTENT_RANGE,
+            header::ACCEPT_RANGES,
+            REQUEST_ID_HEADER,
         ])
         .allow_credentials(true)
         .max_age(Duration::from_secs(3600))
@@ -1,43 +1,185 @@
 //! Logging Middleware
 //!
-//! Logs HTTP requests and responses.
+//! Gives every request an ID and writes an access log line for it, and
+//! reports slow requests to the [`SlowOperationTracker`].
+//!
+//! The ID comes from the client's `X-Request-Id` header when it sends a
+//! usable one, so a client can follow its requests into the server log, and
+//! is generated otherwise. It is returned in the `X-Request-Id` response
+//! header and in problem details, handed to handlers as a [`RequestId`]
+//! request extension and recorded on the `request` span, so everything
+//! logged while handling the request carries it. Access log lines have the
+//! target `homeflixd::access`; with `LOG_FORMAT=json` their fields are JSON
+//! keys.
 
 use axum::{
-    body::Body,
-    http::Request,
+    body::{Body, HttpBody},
+    extract::{MatchedPath, State},
+    http::{header, HeaderName, HeaderValue, Request},
     middleware::Next,
     response::Response,
 };
-use tracing::{info, info_span, Instrument};
+use tracing::{info, info_span, warn, Instrument};
+use std::sync::Arc;
 use std::time::Instant;
 
+use crate::application::services::Caller;
+use crate::infrastructure::slow_operations::SlowOperationTracker;
+use crate::presentation::http::handlers::audit_handlers::actor;
+
+/// Header carrying the request ID
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client request ID kept
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// ID of the request being handled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+tokio::task_local! {
+    /// ID of the request the task is handling, for error responses
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// ID of the request being handled, outside tasks the handler spawned
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
 /// Logging middleware
+///
+/// Streaming responses count until their headers are sent; their size is
+/// the `Content-Length` they announce, if any.
 pub async fn logging_middleware(
-    req: Request<Body>,
+    State(slow_operations): State<Arc<SlowOperationTracker>>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Response {
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
     let method = req.method().clone();
-    let uri = req.uri().clone();
+    // Without the query, which may hold stream tokens
+    let path = req.uri().path().to_string();
+    // Route template, so /v2/media/1 and /v2/media/2 are one endpoint
+    let endpoint = format!(
+        "{} {}",
+        method,
+        req.extensions().get::<MatchedPath>().map(|p| p.as_str()).unwrap_or(&path)
+    );
     let start = Instant::now();
 
-    let span = info_span!("request", %method, %uri);
+    let span = info_span!("request", request_id = %request_id, %method, %path);
 
+    let scope_id = request_id.clone();
     async move {
-        let response = next.run(req).await;
+        let mut response = CURRENT_REQUEST_ID.scope(scope_id, next.run(req)).await;
 
-        let duration = start.elapsed();
-        let status = response.status();
+        let latency = start.elapsed();
+        let status = response.status().as_u16();
+        let latency_ms = latency.as_millis() as u64;
+        let user = response.extensions().get::<Caller>().map(actor).unwrap_or_else(|| "-".to_string());
+        let bytes = response_bytes(&response);
 
-        info!(
-            method = %method,
-            uri = %uri,
-            status = %status,
-            duration = ?duration,
-            "Request processed"
-        );
+        if slow_operations.record_request(&endpoint, latency) {
+            warn!(
+                target: "homeflixd::access",
+                request_id = %request_id,
+                method = %method,
+                path = %path,
+                status,
+                latency_ms,
+                user = %user,
+                bytes,
+                "Slow request"
+            );
+        } else {
+            info!(
+                target: "homeflixd::access",
+                request_id = %request_id,
+                method = %method,
+                path = %path,
+                status,
+                latency_ms,
+                user = %user,
+                bytes,
+                "Request processed"
+            );
+        }
 
+        if let Ok(value) = HeaderValue::from_str(&request_id) {
+            response.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
         response
     }
     .instrument(span)
     .await
 }
+
+/// Whether a client request ID is short and plain enough to log and echo
+fn valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// Size of the response body, as announced or known (0 when unknown)
+fn response_bytes(response: &Response) -> u64 {
+    response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .or_else(|| response.body().size_hint().exact())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::get, Extension, Router};
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/v2/media/1", get(|Extension(id): Extension<RequestId>| async move { id.0 }))
+            .route("/v2/media/2", get(|| async { (StatusCode::NOT_FOUND, "Media not found") }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(SlowOperationTracker::new(Duration::from_secs(1), Duration::from_secs(1))),
+                logging_middleware,
+            ))
+    }
+
+    fn get_with(uri: &str, request_id: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(id) = request_id {
+            builder = builder.header(&REQUEST_ID_HEADER, id);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        // The client's ID reaches the handler and comes back
+        let response = app().oneshot(get_with("/v2/media/1", Some("tv-1234.5"))).await.unwrap();
+        assert_eq!(response.headers()[&REQUEST_ID_HEADER], "tv-1234.5");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"tv-1234.5");
+
+        // Unusable IDs are replaced, and errors carry theirs
+        for id in [None, Some("two words"), Some(&"x".repeat(65)[..])] {
+            let response = app().oneshot(get_with("/v2/media/2", id)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let generated = response.headers()[&REQUEST_ID_HEADER].to_str().unwrap();
+            assert!(uuid::Uuid::parse_str(generated).is_ok());
+        }
+    }
+}
@@ -1,4 +1,7 @@
 pub mod auth;
+pub mod compression;
+pub mod conditional;
 pub mod cors;
 pub mod logging;
 pub mod rate_limit;
+pub mod stream_token;
@@ -1,3 +1,5 @@
 pub mod handlers;
 pub mod middleware;
 pub mod dto;
+pub mod problem;
+pub mod tls;
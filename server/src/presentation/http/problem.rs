@@ -0,0 +1,290 @@
+//! Problem Details
+//!
+//! Error responses are RFC 7807 `application/problem+json` bodies:
+//!
+//! ```json
+//! {"type": "about:blank", "title": "Not Found", "status": 404,
+//!  "detail": "Media not found: 12", "code": "not_found",
+//!  "request_id": "5f0c..."}
+//! ```
+//!
+//! `code` is stable and meant for programs; `detail` is for people. Handlers
+//! return [`ApiError`], which application, domain and repository errors
+//! convert into. [`problem_middleware`] turns the remaining plain-text and
+//! empty error responses (framework rejections, older handlers) into the
+//! same shape, so clients only ever see one.
+
+use axum::{
+    body::{Body, HttpBody},
+    http::{header, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::presentation::http::middleware::logging::current_request_id;
+use crate::shared::error::{
+    ApplicationError, DomainError, FilesystemError, JobError, RepositoryError, TmdbError,
+};
+
+/// Media type of problem details
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Largest plain-text error body rewritten by [`problem_middleware`]
+const MAX_REWRITTEN_BYTES: u64 = 64 * 1024;
+
+/// An error response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    /// Machine-readable error code ("not_found", "invalid_input", ...)
+    pub code: &'static str,
+    pub detail: String,
+}
+
+/// Problem details body
+#[derive(Debug, Serialize)]
+struct Problem<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: &'a str,
+    status: u16,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    detail: &'a str,
+    code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, detail: impl Into<String>) -> Self {
+        Self { status, code, detail: detail.into() }
+    }
+
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "invalid_input", detail)
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", detail)
+    }
+
+    /// An internal error, with a detail safe to show
+    pub fn internal(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", detail)
+    }
+
+    /// An unexpected error; the cause is logged, not shown
+    fn unexpected(cause: impl std::fmt::Display) -> Self {
+        tracing::error!("Request failed: {}", cause);
+        Self::internal("Internal server error")
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let problem = Problem {
+            kind: "about:blank",
+            title: self.status.canonical_reason().unwrap_or("Error"),
+            status: self.status.as_u16(),
+            detail: &self.detail,
+            code: self.code,
+            request_id: current_request_id(),
+        };
+        let body = serde_json::to_vec(&problem).unwrap_or_default();
+        (self.status, [(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON))], body).into_response()
+    }
+}
+
+/// Errors of handlers not yet returning [`ApiError`]
+impl From<(StatusCode, String)> for ApiError {
+    fn from((status, detail): (StatusCode, String)) -> Self {
+        Self::new(status, status_code(status), detail)
+    }
+}
+
+/// For handlers not yet returning [`ApiError`]; [`problem_middleware`]
+/// restores the problem details, with the status's code
+impl From<ApiError> for (StatusCode, String) {
+    fn from(e: ApiError) -> Self {
+        (e.status, e.detail)
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(e: DomainError) -> Self {
+        match e {
+            DomainError::NotFound(msg) => Self::not_found(msg),
+            DomainError::InvalidInput(msg) | DomainError::ParseError(msg) => Self::bad_request(msg),
+            DomainError::ValidationError(msg) => Self::new(StatusCode::BAD_REQUEST, "validation_failed", msg),
+            DomainError::Duplicate(msg) => Self::new(StatusCode::CONFLICT, "duplicate", msg),
+            DomainError::InvalidState(msg) => Self::new(StatusCode::CONFLICT, "invalid_state", msg),
+            DomainError::BusinessRuleViolation(msg) => Self::new(StatusCode::FORBIDDEN, "rule_violation", msg),
+            e @ DomainError::RegexError(_) => Self::unexpected(e),
+        }
+    }
+}
+
+impl From<RepositoryError> for ApiError {
+    fn from(e: RepositoryError) -> Self {
+        match e {
+            RepositoryError::NotFound(msg) => Self::not_found(msg),
+            RepositoryError::InvalidInput(msg) => Self::bad_request(msg),
+            RepositoryError::Duplicate(msg) => Self::new(StatusCode::CONFLICT, "duplicate", msg),
+            RepositoryError::ConstraintViolation(msg) => Self::new(StatusCode::CONFLICT, "constraint_violation", msg),
+            RepositoryError::Domain(e) => e.into(),
+            e => Self::unexpected(e),
+        }
+    }
+}
+
+impl From<ApplicationError> for ApiError {
+    fn from(e: ApplicationError) -> Self {
+        match e {
+            ApplicationError::Domain(e) => e.into(),
+            ApplicationError::Repository(e) => e.into(),
+            ApplicationError::Tmdb(TmdbError::NotFound(msg)) => Self::not_found(msg),
+            ApplicationError::Tmdb(TmdbError::RateLimitExceeded) => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "upstream_rate_limited", "TMDB rate limit exceeded")
+            }
+            ApplicationError::Tmdb(e @ TmdbError::Offline(_)) => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "tmdb_offline", e.to_string())
+            }
+            ApplicationError::Filesystem(FilesystemError::PathNotFound(msg)) => {
+                Self::new(StatusCode::NOT_FOUND, "file_not_found", format!("File not found: {}", msg))
+            }
+            ApplicationError::Job(JobError::NotFound(msg)) => Self::not_found(msg),
+            ApplicationError::ServiceUnavailable(msg) => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "service_unavailable", msg)
+            }
+            e => Self::unexpected(e),
+        }
+    }
+}
+
+/// Error code of responses that only have a status
+fn status_code(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "invalid_input",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::METHOD_NOT_ALLOWED => "method_not_allowed",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::GONE => "gone",
+        StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+        StatusCode::RANGE_NOT_SATISFIABLE => "range_not_satisfiable",
+        StatusCode::UNPROCESSABLE_ENTITY => "unprocessable_entity",
+        StatusCode::TOO_MANY_REQUESTS => "too_many_requests",
+        StatusCode::NOT_IMPLEMENTED => "not_implemented",
+        StatusCode::BAD_GATEWAY => "upstream_error",
+        StatusCode::SERVICE_UNAVAILABLE => "service_unavailable",
+        StatusCode::GATEWAY_TIMEOUT => "upstream_timeout",
+        status if status.is_client_error() => "client_error",
+        _ => "internal_error",
+    }
+}
+
+/// Rewrites plain-text and empty error responses as problem details
+///
+/// Other error bodies (problem details already, DLNA's SOAP faults, JSON
+/// some clients rely on) pass through, as do their headers.
+pub async fn problem_middleware(req: Request<Body>, next: Next) -> Response {
+    let response = next.run(req).await;
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+    let plain = match response.headers().get(header::CONTENT_TYPE) {
+        None => true,
+        Some(value) => value.to_str().is_ok_and(|value| value.starts_with("text/plain")),
+    };
+    let buffered = response.body().size_hint().exact().is_some_and(|size| size <= MAX_REWRITTEN_BYTES);
+    if !plain || !buffered || response.headers().contains_key(header::CONTENT_ENCODING) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let detail = match axum::body::to_bytes(body, MAX_REWRITTEN_BYTES as usize).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).trim().to_string(),
+        Err(_) => String::new(),
+    };
+    let problem = ApiError::from((status, detail)).into_response();
+    let (problem_parts, body) = problem.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.extend(problem_parts.headers);
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/v2/media/:id",
+                get(|axum::extract::Path(id): axum::extract::Path<i64>| async move {
+                    Err::<(), _>(ApiError::from(ApplicationError::Domain(DomainError::NotFound(format!(
+                        "Media not found: {}",
+                        id
+                    )))))
+                }),
+            )
+            .route("/v2/legacy", get(|| async { (StatusCode::CONFLICT, "Already scanning") }))
+            .route(
+                "/v2/fails",
+                get(|| async { ApiError::from(ApplicationError::Internal("disk on fire".to_string())) }),
+            )
+            .route("/v2/empty", get(|| async { StatusCode::UNAUTHORIZED }))
+            .route("/v2/ok", get(|| async { Json(serde_json::json!({ "ok": true })) }))
+            .layer(axum::middleware::from_fn(problem_middleware))
+    }
+
+    async fn problem(uri: &str) -> (StatusCode, Value) {
+        let response = app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        if status.is_success() {
+            return (status, Value::Null);
+        }
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_problem_responses() {
+        let (status, body) = problem("/v2/media/12").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "not_found");
+        assert_eq!(body["detail"], "Media not found: 12");
+        assert_eq!(body["title"], "Not Found");
+        assert_eq!(body["status"], 404);
+
+        // Internal details are not shown
+        let (status, body) = problem("/v2/fails").await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "internal_error");
+        assert_eq!(body["detail"], "Internal server error");
+
+        // Plain-text, empty and framework errors are rewritten
+        let (_, body) = problem("/v2/legacy").await;
+        assert_eq!((body["code"].as_str(), body["detail"].as_str()), (Some("conflict"), Some("Already scanning")));
+        let (_, body) = problem("/v2/empty").await;
+        assert_eq!(body["code"], "unauthorized");
+        assert!(body.get("detail").is_none());
+        let (status, body) = problem("/v2/media/twelve").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_input");
+        assert_eq!(problem("/v2/missing").await.1["code"], "not_found");
+
+        assert_eq!(problem("/v2/ok").await.0, StatusCode::OK);
+    }
+}
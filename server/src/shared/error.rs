@@ -114,6 +114,10 @@ pub enum TmdbError {
     #[error("Invalid API key")]
     InvalidApiKey,
 
+    /// Offline and no response recorded for the request
+    #[error("No recorded TMDB response for {0} (offline)")]
+    Offline(String),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
@@ -151,6 +155,68 @@ impl From<serde_json::Error> for TmdbError {
     }
 }
 
+/// fanart.tv service errors
+#[derive(Debug, Clone, Error)]
+pub enum FanartError {
+    #[error("API error: {0}")]
+    ApiError(u16),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Deserialization error: {0}")]
+    Deserialization(String),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+impl From<reqwest::Error> for FanartError {
+    fn from(err: reqwest::Error) -> Self {
+        FanartError::Network(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for FanartError {
+    fn from(err: serde_json::Error) -> Self {
+        FanartError::Deserialization(err.to_string())
+    }
+}
+
+/// Subtitle download provider errors
+#[derive(Debug, Error)]
+pub enum SubtitleProviderError {
+    #[error("API error: {0}")]
+    ApiError(u16),
+
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Download quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Deserialization error: {0}")]
+    Deserialization(String),
+
+    #[error("Repository error: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+impl From<reqwest::Error> for SubtitleProviderError {
+    fn from(err: reqwest::Error) -> Self {
+        SubtitleProviderError::Network(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for SubtitleProviderError {
+    fn from(err: serde_json::Error) -> Self {
+        SubtitleProviderError::Deserialization(err.to_string())
+    }
+}
+
 /// Video analyzer errors
 #[derive(Debug, Error)]
 pub enum VideoAnalyzerError {
@@ -207,6 +273,31 @@ pub enum ThumbnailError {
     ImageEncoding(String),
 }
 
+/// Stream transcoding errors
+#[derive(Debug, Error)]
+pub enum TranscodeError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("FFmpeg execution failed: {0}")]
+    ExecutionFailed(String),
+
+    #[error("Session not found: {0}")]
+    SessionNotFound(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    #[error("Stream limit reached: {0}")]
+    LimitReached(String),
+
+    #[error("Stream terminated: {0}")]
+    Terminated(String),
+}
+
 /// Filesystem errors
 #[derive(Debug, Error)]
 pub enum FilesystemError {
@@ -233,6 +324,9 @@ pub enum FilesystemError {
 
     #[error("UTF-8 encoding error: {0}")]
     Utf8Error(String),
+
+    #[error("Image processing error: {0}")]
+    ImageProcessing(String),
 }
 
 /// Messaging/Event bus errors
@@ -333,6 +427,12 @@ pub enum SpeechToTextError {
 
     #[error("Timeout: {0}")]
     Timeout(String),
+
+    #[error("Whisper model not found: {0}")]
+    ModelNotFound(String),
+
+    #[error("Model download failed: {0}")]
+    ModelDownloadFailed(String),
 }
 
 /// Translation (Ollama) errors
@@ -354,6 +454,43 @@ pub enum TranslationError {
     Timeout(String),
 }
 
+/// Text embedding (Ollama) errors
+#[derive(Debug, Error)]
+pub enum EmbeddingError {
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+}
+
+/// Notification channel (Discord, Telegram, Gotify, email) errors
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("API error: {0}")]
+    ApiError(u16),
+
+    #[error("Network error: {0}")]
+    Network(String),
+
+    #[error("Email error: {0}")]
+    Email(String),
+}
+
+/// Drops the request URL, which carries the Telegram bot token or the
+/// Discord webhook secret, so errors can be logged and stored
+impl From<reqwest::Error> for NotificationError {
+    fn from(err: reqwest::Error) -> Self {
+        NotificationError::Network(err.without_url().to_string())
+    }
+}
+
 /// Audio fingerprinting (fpcalc/Chromaprint) errors
 #[derive(Debug, Error)]
 pub enum FingerprintError {
@@ -403,6 +540,58 @@ pub enum PresetLoadError {
 
     #[error("Directory not found: {0}")]
     DirectoryNotFound(String),
+
+    #[error("Download failed: {0}")]
+    Fetch(String),
+}
+
+/// Schema migration errors
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Migration {version} ({name}) failed: {source}")]
+    Failed {
+        version: i64,
+        name: String,
+        source: sqlx::Error,
+    },
+
+    #[error("Migration {version} ({name}) was changed after it was applied")]
+    ChecksumMismatch { version: i64, name: String },
+
+    #[error("Database is at migration {0}, which this release does not know (downgrade?)")]
+    UnknownVersion(i64),
+}
+
+/// Server configuration errors
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Cannot read config file {path}: {message}")]
+    Read { path: String, message: String },
+
+    #[error("Invalid config file {path}: {message}")]
+    Parse { path: String, message: String },
+
+    #[error("Invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+/// TLS certificate errors
+#[derive(Debug, Error)]
+pub enum TlsError {
+    #[error("Cannot read {path}: {message}")]
+    Read { path: String, message: String },
+
+    #[error("No certificates in {0}")]
+    NoCertificates(String),
+
+    #[error("Unsupported private key in {path}: {message}")]
+    InvalidKey { path: String, message: String },
+
+    #[error("TLS configuration error: {0}")]
+    Config(String),
 }
 
 /// Application errors - errors that occur in the application layer
@@ -417,6 +606,9 @@ pub enum ApplicationError {
     #[error("TMDB error: {0}")]
     Tmdb(#[from] TmdbError),
 
+    #[error("fanart.tv error: {0}")]
+    Fanart(#[from] FanartError),
+
     #[error("Filesystem error: {0}")]
     Filesystem(#[from] FilesystemError),
 
@@ -432,18 +624,30 @@ pub enum ApplicationError {
     #[error("Thumbnail generator error: {0}")]
     Thumbnail(#[from] ThumbnailError),
 
+    #[error("Transcode error: {0}")]
+    Transcode(#[from] TranscodeError),
+
     #[error("Subtitle error: {0}")]
     Subtitle(#[from] SubtitleError),
 
+    #[error("Subtitle provider error: {0}")]
+    SubtitleProvider(#[from] SubtitleProviderError),
+
     #[error("Speech-to-text error: {0}")]
     SpeechToText(#[from] SpeechToTextError),
 
     #[error("Translation error: {0}")]
     Translation(#[from] TranslationError),
 
+    #[error("Embedding error: {0}")]
+    Embedding(#[from] EmbeddingError),
+
     #[error("Fingerprint error: {0}")]
     Fingerprint(#[from] FingerprintError),
 
+    #[error("Notification error: {0}")]
+    Notification(#[from] NotificationError),
+
     #[error("Job error: {0}")]
     Job(#[from] JobError),
 
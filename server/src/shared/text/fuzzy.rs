@@ -8,6 +8,11 @@
 use std::collections::HashSet;
 use super::TitleNormalizer;
 
+/// Title scores below this do not settle which candidate is meant
+pub const AMBIGUOUS_BELOW: f64 = 0.90;
+/// Candidates scoring within this of the best are as likely
+pub const AMBIGUOUS_MARGIN: f64 = 0.05;
+
 /// Configuration for fuzzy matching
 #[derive(Debug, Clone)]
 pub struct FuzzyMatchConfig {
@@ -304,6 +309,35 @@ impl FuzzyMatcher {
         }
     }
 
+    /// Compare a title with a candidate known by several titles
+    ///
+    /// Scores `a` against `b` and each of its alternatives (AKAs, titles of
+    /// foreign releases), keeping the best; `text` is the title that matched.
+    pub fn compare_titles_with_alternatives<'a>(
+        a: &str,
+        b: &str,
+        alternatives: impl IntoIterator<Item = &'a str>,
+    ) -> FuzzyMatch {
+        alternatives
+            .into_iter()
+            .map(|alternative| Self::compare_titles(a, alternative))
+            .fold(Self::compare_titles(a, b), |best, other| if other.score > best.score { other } else { best })
+    }
+
+    /// Whether title scores leave the best candidate in doubt
+    ///
+    /// The best score is below [`AMBIGUOUS_BELOW`], or the runner-up is
+    /// within [`AMBIGUOUS_MARGIN`] of it. `scores` need not be sorted.
+    pub fn is_ambiguous(scores: &[f64]) -> bool {
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        match sorted.as_slice() {
+            [] => false,
+            [best] => *best < AMBIGUOUS_BELOW,
+            [best, second, ..] => *best < AMBIGUOUS_BELOW || best - second < AMBIGUOUS_MARGIN,
+        }
+    }
+
     /// Find the best match from a list of candidates
     ///
     /// Returns the best match if it meets the minimum similarity threshold.
@@ -516,6 +550,30 @@ mod tests {
         assert_eq!(ranked[0].text, "Back to the Future Part III");
     }
 
+    #[test]
+    fn test_compare_titles_with_alternatives() {
+        // A file named after the Spanish title of a show
+        let result = FuzzyMatcher::compare_titles_with_alternatives(
+            "La Casa de Papel",
+            "Money Heist",
+            ["La casa de papel", "Haus des Geldes"],
+        );
+        assert!(result.score > 0.99, "Score {} should be > 0.99", result.score);
+        assert_eq!(result.text, "La casa de papel");
+
+        let result = FuzzyMatcher::compare_titles_with_alternatives("Money Heist", "Money Heist", ["Haus des Geldes"]);
+        assert_eq!(result.text, "Money Heist");
+    }
+
+    #[test]
+    fn test_is_ambiguous() {
+        assert!(!FuzzyMatcher::is_ambiguous(&[]));
+        assert!(!FuzzyMatcher::is_ambiguous(&[0.98]));
+        assert!(!FuzzyMatcher::is_ambiguous(&[0.55, 0.98]));
+        assert!(FuzzyMatcher::is_ambiguous(&[0.60]));
+        assert!(FuzzyMatcher::is_ambiguous(&[0.95, 0.93]));
+    }
+
     #[test]
     fn test_spider_man_variants() {
         // Note: After normalization, these become:
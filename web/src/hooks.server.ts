@@ -1,5 +1,52 @@
 import type { Handle } from '@sveltejs/kit';
+import { sequence } from '@sveltejs/kit/hooks';
 import { paraglideMiddleware } from '$lib/paraglide/server';
+import { getBackendUrl, isBackendPath, withApiKey } from '$lib/server/api';
+
+/** Headers that describe a single connection and must not be forwarded */
+const HOP_BY_HOP = ['connection', 'keep-alive', 'transfer-encoding', 'upgrade', 'host'];
+
+/**
+ * Proxies browser requests for the backend (`/v2/...`) and adds the API key,
+ * so streams, images and event sources work with API_SECRET set
+ *
+ * Pages render in the browser (`ssr = false`), so this is the only path the
+ * frontend's requests take to the backend when PUBLIC_API_URL is unset.
+ */
+const handleApiProxy: Handle = async ({ event, resolve }) => {
+	if (!isBackendPath(event.url.pathname)) {
+		return resolve(event);
+	}
+
+	const headers = withApiKey(event.request.headers);
+	for (const name of HOP_BY_HOP) {
+		headers.delete(name);
+	}
+	headers.set('x-forwarded-for', event.getClientAddress());
+
+	const method = event.request.method;
+	const hasBody = method !== 'GET' && method !== 'HEAD';
+	const upstream = await fetch(`${getBackendUrl()}${event.url.pathname}${event.url.search}`, {
+		method,
+		headers,
+		body: hasBody ? event.request.body : undefined,
+		// Required by Node's fetch for streamed request bodies
+		...(hasBody ? { duplex: 'half' } : {}),
+		redirect: 'manual'
+	} as RequestInit);
+
+	const responseHeaders = new Headers(upstream.headers);
+	if (responseHeaders.has('content-encoding')) {
+		// fetch already decoded the body
+		responseHeaders.delete('content-encoding');
+		responseHeaders.delete('content-length');
+	}
+	return new Response(upstream.body, {
+		status: upstream.status,
+		statusText: upstream.statusText,
+		headers: responseHeaders
+	});
+};
 
 const handleParaglide: Handle = ({ event, resolve }) =>
 	paraglideMiddleware(event.request, ({ request, locale }) => {
@@ -10,4 +57,5 @@ const handleParaglide: Handle = ({ event, resolve }) =>
 		});
 	});
 
-export const handle: Handle = handleParaglide;
+export const handle: Handle = sequence(handleApiProxy, handleParaglide);
+
@@ -25,13 +25,48 @@ export function getApiBase(): string {
 		return apiUrl.replace('localhost', '127.0.0.1');
 	}
 
-	// Client-side: use relative URLs (empty string) since frontend and backend are on same domain
-	// This works because the browser will use the current origin
+	// Client-side: use relative URLs (empty string); the frontend server proxies them to
+	// the backend and adds the API key (see hooks.server.ts)
 	return '';
 }
 
 type FetchFn = typeof fetch;
 
+/** Human-readable detail of an error response (problem+json or text) */
+async function errorDetail(res: Response): Promise<string> {
+	const text = await res.text();
+	try {
+		const problem = JSON.parse(text);
+		return problem.detail || problem.title || text;
+	} catch {
+		return text;
+	}
+}
+
+/** A page of a list endpoint */
+interface Page<T> {
+    items: T[];
+    total: number;
+    page: number;
+    limit: number;
+}
+
+/** Fetches every page of a list endpoint */
+async function fetchAllPages<T>(customFetch: FetchFn, path: string, error: string): Promise<T[]> {
+    const items: T[] = [];
+    for (let page = 1; ; page++) {
+        const res = await customFetch(`${getApiBase()}${path}?page=${page}&limit=500`);
+        if (!res.ok) {
+            throw new Error(error);
+        }
+        const body: Page<T> = await res.json();
+        items.push(...body.items);
+        if (body.items.length === 0 || items.length >= body.total) {
+            return items;
+        }
+    }
+}
+
 export async function fetchGroupedLibrary(customFetch: FetchFn = fetch): Promise<GroupedLibrary> {
     const res = await customFetch(`${getApiBase()}/v2/media`);
     if (!res.ok) {
@@ -41,11 +76,7 @@ export async function fetchGroupedLibrary(customFetch: FetchFn = fetch): Promise
 }
 
 export async function fetchAllSeries(customFetch: FetchFn = fetch): Promise<Series[]> {
-    const res = await customFetch(`${getApiBase()}/v2/series`);
-    if (!res.ok) {
-        throw new Error('Failed to fetch series');
-    }
-    return res.json();
+    return fetchAllPages<Series>(customFetch, '/v2/series', 'Failed to fetch series');
 }
 
 export async function fetchSeriesDetails(id: number, customFetch: FetchFn = fetch): Promise<SeriesDetails> {
@@ -70,11 +101,11 @@ interface RawCollectionDetails extends Omit<CollectionDetails, 'items' | 'comple
 }
 
 export async function fetchCollections(customFetch: FetchFn = fetch): Promise<CollectionSummary[]> {
-    const res = await customFetch(`${getApiBase()}/v2/collections`);
-    if (!res.ok) {
-        throw new Error('Failed to fetch collections');
-    }
-    const collections: RawCollectionSummary[] = await res.json();
+    const collections = await fetchAllPages<RawCollectionSummary>(
+        customFetch,
+        '/v2/collections',
+        'Failed to fetch collections'
+    );
     return collections.map((collection) => ({
         ...collection,
         completion_percentage:
@@ -123,6 +154,14 @@ export function getThumbnailUrl(mediaId: number, width: number = 320): string {
 	return `${getApiBase()}/v2/thumbnail/${mediaId}?width=${width}`;
 }
 
+/**
+ * Get the muted hover preview clip URL for a media item.
+ * The server answers 202 while the clip is still being made.
+ */
+export function getPreviewUrl(mediaId: number): string {
+	return `${getApiBase()}/v2/media/${mediaId}/preview`;
+}
+
 // Media details with audio tracks
 export interface MediaDetails {
 	id: number;
@@ -142,6 +181,18 @@ export interface MediaDetails {
 	is_watched: boolean;
 	subtitles: SubtitleTrack[];
 	audio_tracks: AudioTrack[];
+	bookmarks?: Bookmark[];
+}
+
+/// Scene bookmark from backend /v2/media/{id}/bookmarks endpoint
+export interface Bookmark {
+	id: number;
+	media_id: number;
+	/// Position in seconds
+	position_seconds: number;
+	note: string | null;
+	created_at: string;
+	updated_at: string;
 }
 
 /// Subtitle track from backend /v2/media/{id}/tracks endpoint
@@ -156,6 +207,10 @@ export interface SubtitleTrack {
 	source: 'external' | 'embedded';
 	/// Whether this is the default track
 	is_default: boolean;
+	/// Only covers foreign-language dialogue and signs
+	forced: boolean;
+	/// For the deaf and hard of hearing (SDH)
+	hearing_impaired: boolean;
 }
 
 export interface AudioTrack {
@@ -165,6 +220,8 @@ export interface AudioTrack {
 	channels: number | null;
 	title: string | null;
 	is_default: boolean;
+	/// Narrates the picture for blind and visually impaired viewers
+	audio_description: boolean;
 }
 
 /// Response from /v2/media/{id}/tracks endpoint
@@ -260,7 +317,8 @@ export async function fetchMediaCredits(
 export interface ServiceCapabilities {
 	whisper_available: boolean;
 	whisper_model_exists: boolean;
-	ollama_available: boolean;
+	translation_available: boolean;
+	translation_providers: string[];
 	fpcalc_available: boolean;
 }
 
@@ -341,7 +399,7 @@ export async function generateSubtitle(
 		body: JSON.stringify(request)
 	});
 	if (!res.ok) {
-		const error = await res.text();
+		const error = await errorDetail(res);
 		throw new Error(error || 'Failed to start subtitle generation');
 	}
 	return res.json();
@@ -368,7 +426,7 @@ export async function cancelJob(
 		method: 'DELETE'
 	});
 	if (!res.ok) {
-		const error = await res.text();
+		const error = await errorDetail(res);
 		throw new Error(error || 'Failed to cancel job');
 	}
 }
@@ -384,7 +442,7 @@ export async function batchGenerateSubtitles(
 		body: JSON.stringify(request)
 	});
 	if (!res.ok) {
-		const error = await res.text();
+		const error = await errorDetail(res);
 		throw new Error(error || 'Failed to start batch subtitle generation');
 	}
 	return res.json();
@@ -411,7 +469,7 @@ export async function cancelBatchJob(
 		method: 'DELETE'
 	});
 	if (!res.ok) {
-		const error = await res.text();
+		const error = await errorDetail(res);
 		throw new Error(error || 'Failed to cancel batch job');
 	}
 }
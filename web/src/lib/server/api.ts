@@ -0,0 +1,34 @@
+import { env } from '$env/dynamic/private';
+
+/**
+ * Backend the web server talks to
+ *
+ * Browsers use relative `/v2/...` URLs when PUBLIC_API_URL is unset; those
+ * requests are proxied here (see hooks.server.ts), so the API key never
+ * reaches the browser.
+ */
+export function getBackendUrl(): string {
+	const apiUrl = env.API_URL || import.meta.env.VITE_API_URL || 'http://127.0.0.1:3000';
+	// Use 127.0.0.1 to avoid potential localhost resolution issues
+	return apiUrl.replace('localhost', '127.0.0.1').replace(/\/$/, '');
+}
+
+/** Key sent to the backend (its API_SECRET or a device key), if configured */
+export function getApiKey(): string | undefined {
+	return env.HOMEFLIX_API_KEY || undefined;
+}
+
+/** Paths served by the backend rather than by SvelteKit */
+export function isBackendPath(pathname: string): boolean {
+	return pathname.startsWith('/v2/') || pathname === '/search' || pathname === '/health';
+}
+
+/** Copy of `headers` with the API key added */
+export function withApiKey(headers: Headers): Headers {
+	const key = getApiKey();
+	const result = new Headers(headers);
+	if (key) {
+		result.set('authorization', `Bearer ${key}`);
+	}
+	return result;
+}